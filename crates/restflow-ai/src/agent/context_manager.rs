@@ -5,7 +5,17 @@
 //!
 //! **Compact** runs inside the loop when estimated tokens approach the context
 //! window limit, asking the LLM to generate a handoff summary that replaces
-//! old messages.
+//! old messages. Older messages relevant to the current work (by embedding
+//! similarity to the goal and recent turns), pinned messages and results of
+//! pinned tools are kept verbatim instead of being summarized.
+//!
+//! **Token counting** uses the active model's BPE tokenizer (`o200k_base` or
+//! `cl100k_base`), calibrated against reported usage. Near the compaction
+//! threshold the provider's exact count is used when it offers one.
+//!
+//! **Tool schema slimming** keeps large tool registries from dominating the
+//! prompt: unused tools are sent with short descriptions, full schemas are
+//! loaded on first use or via `tool_manual`, and idle tools are dropped.
 //!
 //! Design references:
 //! - OpenCode: two-stage prune+compact, summary-as-boundary, protected tools
@@ -15,12 +25,23 @@ mod compact;
 mod config;
 mod constants;
 mod prune;
+mod relevance;
 mod token;
+mod tokenizer;
+mod tool_schemas;
 
-pub use compact::{CompactStats, compact, compact_was_effective, should_compact};
+pub use compact::{
+    CompactStats, compact, compact_was_effective, compact_with_embedder, near_compact_threshold,
+    should_compact,
+};
 pub use config::ContextManagerConfig;
 pub use prune::{PruneStats, prune};
-pub use token::{TokenEstimator, estimate_tokens, middle_truncate};
+pub use relevance::{LexicalEmbedder, MessageEmbedder};
+pub use token::{TokenEstimator, count_tokens, estimate_tokens, middle_truncate};
+pub use tokenizer::TokenizerKind;
+pub use tool_schemas::{
+    TOOL_MANUAL_TOOL_NAME, ToolSchemaSlimmer, ToolSchemaStats, tool_manual_output,
+};
 
 #[cfg(test)]
 pub(crate) use compact::{find_compact_split, format_conversation_for_summary};
@@ -29,7 +50,11 @@ pub(crate) use constants::ROLE_OVERHEAD_TOKENS;
 #[cfg(test)]
 pub(crate) use prune::find_protection_boundary;
 #[cfg(test)]
+pub(crate) use relevance::{cosine_similarity, message_units};
+#[cfg(test)]
 pub(crate) use token::estimate_message_tokens;
+#[cfg(test)]
+pub(crate) use tool_schemas::{estimate_schema_tokens, short_description};
 
 #[cfg(test)]
 mod tests;
@@ -2,7 +2,13 @@ use crate::error::Result;
 use crate::llm::{CompletionRequest, LlmClient, Message, Role};
 
 use super::config::ContextManagerConfig;
-use super::constants::{COMPACT_MIN_REDUCTION, HANDOFF_PROMPT, SUMMARY_TRUNCATE_CHARS};
+use super::constants::{
+    COMPACT_MIN_REDUCTION, EXACT_COUNT_MARGIN, HANDOFF_PROMPT, RELEVANCE_QUERY_MESSAGES,
+    SUMMARY_TRUNCATE_CHARS,
+};
+use super::relevance::{
+    LexicalEmbedder, MessageEmbedder, cosine_similarity, message_units, relevance_text,
+};
 use super::token::{estimate_message_tokens, estimate_tokens, middle_truncate};
 
 /// Statistics from a compact operation.
@@ -23,6 +29,16 @@ pub fn should_compact(estimated_tokens: usize, config: &ContextManagerConfig) ->
     estimated_tokens > threshold
 }
 
+/// Check whether an estimate is close enough to the compaction threshold
+/// that an exact provider token count is worth requesting.
+pub fn near_compact_threshold(estimated_tokens: usize, config: &ContextManagerConfig) -> bool {
+    if config.context_window == 0 {
+        return false;
+    }
+    let threshold = config.context_window as f64 * config.compact_trigger_ratio;
+    estimated_tokens as f64 > threshold * EXACT_COUNT_MARGIN
+}
+
 /// Format conversation transcript for the summarization LLM call.
 pub(crate) fn format_conversation_for_summary(messages: &[Message]) -> String {
     let mut out = String::new();
@@ -105,32 +121,63 @@ pub(crate) fn find_compact_split(messages: &[Message], preserve_tokens: usize) -
 
 /// Generate a handoff summary and replace old messages.
 ///
+/// Uses [`LexicalEmbedder`] for relevance scoring; see
+/// [`compact_with_embedder`].
+pub async fn compact(
+    messages: &mut Vec<Message>,
+    config: &ContextManagerConfig,
+    llm: &dyn LlmClient,
+) -> Result<CompactStats> {
+    compact_with_embedder(messages, config, llm, &LexicalEmbedder).await
+}
+
+/// Generate a handoff summary for old messages, keeping the ones that still
+/// matter verbatim.
+///
+/// Messages before the preserved tail are grouped into units (an assistant
+/// tool call with its results) and scored by embedding similarity to the
+/// current work: pinned messages and the recent turns. Pinned messages,
+/// results of `compact_pinned_tools`, and the most relevant units within
+/// `compact_relevance_keep_ratio` of the preserve budget are kept verbatim
+/// after the summary; everything else is summarized.
+///
 /// Returns `CompactStats` with `messages_replaced == 0` if there's nothing to
 /// compact, or if the LLM returns an empty summary (safety: don't replace
 /// real history with nothing).
-pub async fn compact(
+pub async fn compact_with_embedder(
     messages: &mut Vec<Message>,
     config: &ContextManagerConfig,
     llm: &dyn LlmClient,
+    embedder: &dyn MessageEmbedder,
 ) -> Result<CompactStats> {
     let tokens_before = estimate_tokens(messages);
+    let noop = CompactStats {
+        messages_replaced: 0,
+        tokens_before,
+        tokens_after: tokens_before,
+        summary_length: 0,
+    };
     let split = find_compact_split(messages, config.compact_preserve_tokens);
 
     // Nothing to compact if split is at 1 (only system prompt) or beyond end.
     if split <= 1 || split >= messages.len() {
-        return Ok(CompactStats {
-            messages_replaced: 0,
-            tokens_before,
-            tokens_after: tokens_before,
-            summary_length: 0,
-        });
+        return Ok(noop);
     }
 
-    // Extract old messages for summarization (skip system prompt at [0]).
-    let old_messages = &messages[1..split];
-    let transcript = format_conversation_for_summary(old_messages);
+    let keep = select_verbatim(messages, split, config, embedder).await;
+    let (kept, summarized): (Vec<_>, Vec<_>) = messages[1..split]
+        .iter()
+        .cloned()
+        .zip(&keep[1..split])
+        .partition(|(_, keep)| **keep);
+    let kept: Vec<Message> = kept.into_iter().map(|(msg, _)| msg).collect();
+    let summarized: Vec<Message> = summarized.into_iter().map(|(msg, _)| msg).collect();
+    if summarized.is_empty() {
+        return Ok(noop);
+    }
 
-    // Ask LLM for handoff summary.
+    // Ask LLM for handoff summary of the messages not kept.
+    let transcript = format_conversation_for_summary(&summarized);
     let summary_request = CompletionRequest::new(vec![
         Message::system(HANDOFF_PROMPT),
         Message::user(transcript),
@@ -142,15 +189,10 @@ pub async fn compact(
     // Safety: don't replace real messages with an empty summary.
     if summary.trim().is_empty() {
         tracing::warn!("LLM returned empty summary, skipping compaction");
-        return Ok(CompactStats {
-            messages_replaced: 0,
-            tokens_before,
-            tokens_after: tokens_before,
-            summary_length: 0,
-        });
+        return Ok(noop);
     }
 
-    // Rebuild messages: system + summary + preserved tail.
+    // Rebuild messages: system + summary + kept messages + preserved tail.
     let system_msg = messages[0].clone();
     let preserved = messages[split..].to_vec();
 
@@ -160,18 +202,114 @@ pub async fn compact(
     messages.clear();
     messages.push(system_msg);
     messages.push(summary_msg);
+    messages.extend(kept);
     messages.extend(preserved);
 
     let tokens_after = estimate_tokens(messages);
 
     Ok(CompactStats {
-        messages_replaced: split - 1, // excluding system prompt
+        messages_replaced: summarized.len(),
         tokens_before,
         tokens_after,
         summary_length,
     })
 }
 
+/// Decide which messages before `split` are kept verbatim.
+///
+/// Returns one flag per message in `messages[..split]`. Scoring failures
+/// fall back to keeping only pinned messages.
+async fn select_verbatim(
+    messages: &[Message],
+    split: usize,
+    config: &ContextManagerConfig,
+    embedder: &dyn MessageEmbedder,
+) -> Vec<bool> {
+    let mut keep = vec![false; split];
+    let mut candidates = Vec::new();
+    for (start, end) in message_units(&messages[1..split]) {
+        let (start, end) = (start + 1, end + 1);
+        if is_pinned(&messages[start..end], config) {
+            keep[start..end].fill(true);
+        } else {
+            candidates.push((start, end));
+        }
+    }
+
+    let budget =
+        (config.compact_preserve_tokens as f64 * config.compact_relevance_keep_ratio) as usize;
+    if budget == 0 || candidates.is_empty() {
+        return keep;
+    }
+
+    // The current work: pinned messages (typically the goal) and recent turns.
+    let tail_start = messages
+        .len()
+        .saturating_sub(RELEVANCE_QUERY_MESSAGES)
+        .max(split);
+    let focus: Vec<Message> = messages[1..split]
+        .iter()
+        .filter(|msg| msg.pinned)
+        .chain(&messages[tail_start..])
+        .cloned()
+        .collect();
+    let mut texts = vec![relevance_text(&focus)];
+    texts.extend(
+        candidates
+            .iter()
+            .map(|&(start, end)| relevance_text(&messages[start..end])),
+    );
+
+    let vectors = match embedder.embed(&texts).await {
+        Ok(vectors) if vectors.len() == texts.len() => vectors,
+        Ok(vectors) => {
+            tracing::warn!(
+                expected = texts.len(),
+                actual = vectors.len(),
+                "Embedder returned wrong number of vectors, compacting by position"
+            );
+            return keep;
+        }
+        Err(error) => {
+            tracing::warn!(error = %error, "Relevance scoring failed, compacting by position");
+            return keep;
+        }
+    };
+
+    let mut scored: Vec<(f32, (usize, usize))> = candidates
+        .into_iter()
+        .zip(&vectors[1..])
+        .map(|(unit, vector)| (cosine_similarity(&vectors[0], vector), unit))
+        .filter(|(score, _)| f64::from(*score) >= config.compact_relevance_min_score)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut used = 0;
+    for (_, (start, end)) in scored {
+        let tokens: usize = messages[start..end]
+            .iter()
+            .map(estimate_message_tokens)
+            .sum();
+        if used + tokens <= budget {
+            used += tokens;
+            keep[start..end].fill(true);
+        }
+    }
+    keep
+}
+
+/// A unit is pinned when any message in it is, or it calls a pinned tool.
+fn is_pinned(unit: &[Message], config: &ContextManagerConfig) -> bool {
+    unit.iter().any(|msg| {
+        msg.pinned
+            || msg.tool_calls.as_ref().is_some_and(|calls| {
+                calls
+                    .iter()
+                    .any(|call| config.compact_pinned_tools.contains(&call.name))
+            })
+    })
+}
+
 /// Check whether compaction was effective. If the reduction ratio is too small,
 /// the caller should activate a cooldown to prevent compaction loops.
 pub fn compact_was_effective(stats: &CompactStats) -> bool {
@@ -3,7 +3,11 @@ use restflow_traits::{
     DEFAULT_AGENT_PRUNE_TOOL_MAX_CHARS,
 };
 
-use super::constants::{COMPACT_TRIGGER_RATIO, MIN_PRUNE_SAVINGS_TOKENS, PRUNE_PROTECTED_TURNS};
+use super::constants::{
+    COMPACT_RELEVANCE_KEEP_RATIO, COMPACT_RELEVANCE_MIN_SCORE, COMPACT_TRIGGER_RATIO,
+    MIN_PRUNE_SAVINGS_TOKENS, PRUNE_PROTECTED_TURNS, TOOL_IDLE_EXCLUSION_ITERATIONS,
+    TOOL_SCHEMA_SLIM_MIN_TOOLS, TOOL_SHORT_DESCRIPTION_CHARS,
+};
 
 /// Configuration for the two-stage context manager.
 #[derive(Debug, Clone)]
@@ -14,6 +18,19 @@ pub struct ContextManagerConfig {
     pub min_prune_savings_tokens: usize,
     pub compact_trigger_ratio: f64,
     pub compact_preserve_tokens: usize,
+    /// Share of `compact_preserve_tokens` spent keeping older messages that
+    /// are relevant to the current work verbatim (0 disables relevance keeping).
+    pub compact_relevance_keep_ratio: f64,
+    /// Minimum similarity for an older message to be kept verbatim.
+    pub compact_relevance_min_score: f64,
+    /// Tools whose results are always kept verbatim by compaction.
+    pub compact_pinned_tools: Vec<String>,
+    /// Registry size at which tool schemas are slimmed (0 disables slimming).
+    pub tool_schema_slim_min_tools: usize,
+    /// Maximum characters kept from a tool description when slimmed.
+    pub tool_short_description_chars: usize,
+    /// Iterations without use before a tool is excluded (0 disables exclusion).
+    pub tool_idle_exclusion_iterations: usize,
 }
 
 impl Default for ContextManagerConfig {
@@ -25,6 +42,12 @@ impl Default for ContextManagerConfig {
             min_prune_savings_tokens: MIN_PRUNE_SAVINGS_TOKENS,
             compact_trigger_ratio: COMPACT_TRIGGER_RATIO,
             compact_preserve_tokens: DEFAULT_AGENT_COMPACT_PRESERVE_TOKENS,
+            compact_relevance_keep_ratio: COMPACT_RELEVANCE_KEEP_RATIO,
+            compact_relevance_min_score: COMPACT_RELEVANCE_MIN_SCORE,
+            compact_pinned_tools: Vec::new(),
+            tool_schema_slim_min_tools: TOOL_SCHEMA_SLIM_MIN_TOOLS,
+            tool_short_description_chars: TOOL_SHORT_DESCRIPTION_CHARS,
+            tool_idle_exclusion_iterations: TOOL_IDLE_EXCLUSION_ITERATIONS,
         }
     }
 }
@@ -47,4 +70,22 @@ impl ContextManagerConfig {
         self.compact_preserve_tokens = tokens;
         self
     }
+
+    /// Override the tools whose results compaction keeps verbatim.
+    pub fn with_compact_pinned_tools(mut self, tools: Vec<String>) -> Self {
+        self.compact_pinned_tools = tools;
+        self
+    }
+
+    /// Override the registry size at which tool schemas are slimmed.
+    pub fn with_tool_schema_slim_min_tools(mut self, min_tools: usize) -> Self {
+        self.tool_schema_slim_min_tools = min_tools;
+        self
+    }
+
+    /// Override the idle iteration count before a tool is excluded.
+    pub fn with_tool_idle_exclusion_iterations(mut self, iterations: usize) -> Self {
+        self.tool_idle_exclusion_iterations = iterations;
+        self
+    }
 }
@@ -3,7 +3,22 @@ pub(crate) const ROLE_OVERHEAD_TOKENS: usize = 4;
 pub(super) const MIN_PRUNE_SAVINGS_TOKENS: usize = 5_000;
 pub(super) const PRUNE_PROTECTED_TURNS: usize = 3;
 pub(super) const COMPACT_TRIGGER_RATIO: f64 = 0.90;
+/// Fraction of the compaction threshold above which an exact provider
+/// token count is requested before deciding.
+pub(super) const EXACT_COUNT_MARGIN: f64 = 0.80;
 pub(super) const SUMMARY_TRUNCATE_CHARS: usize = 4_000;
 pub(super) const COMPACT_MIN_REDUCTION: f64 = 0.70;
+/// Share of the preserved-token budget spent keeping relevant older
+/// messages verbatim during compaction.
+pub(super) const COMPACT_RELEVANCE_KEEP_RATIO: f64 = 0.25;
+/// Minimum similarity to the current work for an older message to be kept.
+pub(super) const COMPACT_RELEVANCE_MIN_SCORE: f64 = 0.30;
+/// Recent messages that describe the current work when scoring relevance.
+pub(super) const RELEVANCE_QUERY_MESSAGES: usize = 6;
+pub(super) const RELEVANCE_TEXT_CHARS: usize = 2_000;
+pub(super) const LEXICAL_EMBEDDING_DIMENSION: usize = 512;
+pub(super) const TOOL_SCHEMA_SLIM_MIN_TOOLS: usize = 40;
+pub(super) const TOOL_SHORT_DESCRIPTION_CHARS: usize = 160;
+pub(super) const TOOL_IDLE_EXCLUSION_ITERATIONS: usize = 10;
 
 pub(super) const HANDOFF_PROMPT: &str = include_str!("../../../assets/agents/handoff_prompt.md");
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::llm::{Message, Role};
+
+use super::constants::{LEXICAL_EMBEDDING_DIMENSION, RELEVANCE_TEXT_CHARS};
+use super::token::middle_truncate;
+
+/// Turns text into vectors for relevance scoring during compaction.
+#[async_trait]
+pub trait MessageEmbedder: Send + Sync {
+    /// Embed `texts`, returning one vector per input in the same order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Offline embedder using hashed bag-of-words vectors.
+///
+/// Used when no embedding model is configured. It captures shared vocabulary
+/// (file names, identifiers, error text), which is most of what ties an old
+/// tool result to the current work.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexicalEmbedder;
+
+#[async_trait]
+impl MessageEmbedder for LexicalEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| lexical_vector(text)).collect())
+    }
+}
+
+fn lexical_vector(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0; LEXICAL_EMBEDDING_DIMENSION];
+    for term in text
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|term| term.len() > 2)
+    {
+        let term = term.to_lowercase();
+        // FNV-1a keeps the buckets stable across runs and platforms.
+        let hash = term.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        vector[(hash % LEXICAL_EMBEDDING_DIMENSION as u64) as usize] += 1.0;
+    }
+    vector
+}
+
+/// Cosine similarity of two vectors; 0.0 when either is empty or zero.
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0_f32, 0.0_f32, 0.0_f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Text used to embed a group of messages.
+pub(crate) fn relevance_text(messages: &[Message]) -> String {
+    let mut out = String::new();
+    for msg in messages {
+        if let Some(calls) = &msg.tool_calls {
+            for call in calls {
+                out.push_str(&format!("{} {}\n", call.name, call.arguments));
+            }
+        }
+        out.push_str(&msg.content);
+        out.push('\n');
+    }
+    if out.len() > RELEVANCE_TEXT_CHARS {
+        middle_truncate(&out, RELEVANCE_TEXT_CHARS)
+    } else {
+        out
+    }
+}
+
+/// Split messages into units that must be kept or dropped together: an
+/// assistant message with tool calls plus its tool results, or a single
+/// message. Returns `(start, end)` ranges into `messages`.
+pub(crate) fn message_units(messages: &[Message]) -> Vec<(usize, usize)> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    while start < messages.len() {
+        let mut end = start + 1;
+        let has_calls = messages[start]
+            .tool_calls
+            .as_ref()
+            .is_some_and(|calls| !calls.is_empty());
+        if has_calls || messages[start].role == Role::Tool {
+            while end < messages.len() && messages[end].role == Role::Tool {
+                end += 1;
+            }
+        }
+        units.push((start, end));
+        start = end;
+    }
+    units
+}
@@ -251,6 +251,59 @@ fn token_estimator_cooldown() {
     assert!(est.compact_allowed());
 }
 
+// ======================================================================
+// TokenizerKind
+// ======================================================================
+
+#[test]
+fn tokenizer_for_model_picks_encoding() {
+    assert_eq!(TokenizerKind::for_model("gpt-5"), TokenizerKind::O200k);
+    assert_eq!(
+        TokenizerKind::for_model("openai/gpt-4o-mini"),
+        TokenizerKind::O200k
+    );
+    assert_eq!(TokenizerKind::for_model("o3-mini"), TokenizerKind::O200k);
+    assert_eq!(
+        TokenizerKind::for_model("gpt-4-turbo"),
+        TokenizerKind::Cl100k
+    );
+    assert_eq!(
+        TokenizerKind::for_model("claude-sonnet-4-5"),
+        TokenizerKind::Cl100k
+    );
+    assert_eq!(TokenizerKind::default(), TokenizerKind::Heuristic);
+}
+
+#[test]
+fn tokenizer_counts_bpe_tokens() {
+    assert_eq!(TokenizerKind::O200k.count("hello world"), 2);
+    assert_eq!(TokenizerKind::Cl100k.count("hello world"), 2);
+    assert_eq!(TokenizerKind::Heuristic.count("hello world"), 2);
+    assert_eq!(TokenizerKind::O200k.count(""), 0);
+}
+
+#[test]
+fn token_estimator_for_model_uses_tokenizer() {
+    let est = TokenEstimator::for_model("gpt-5");
+    let msgs = vec![Message::user("hello world")];
+    assert_eq!(est.count(&msgs), 2 + ROLE_OVERHEAD_TOKENS);
+    assert_eq!(est.count(&msgs), count_tokens(&msgs, TokenizerKind::O200k));
+}
+
+#[test]
+fn token_estimator_set_model_resets_calibration_on_change() {
+    let mut est = TokenEstimator::for_model("gpt-5");
+    est.calibrate(100, 200);
+
+    est.set_model("gpt-5-mini");
+    assert_eq!(est.samples, 1);
+
+    est.set_model("claude-sonnet-4-5");
+    assert_eq!(est.tokenizer, TokenizerKind::Cl100k);
+    assert_eq!(est.samples, 0);
+    assert!((est.calibration_factor - 1.0).abs() < f64::EPSILON);
+}
+
 // ======================================================================
 // find_protection_boundary
 // ======================================================================
@@ -596,6 +649,17 @@ fn should_compact_zero_tokens() {
     assert!(!should_compact(0, &config));
 }
 
+#[test]
+fn near_compact_threshold_starts_below_trigger() {
+    let config = ContextManagerConfig {
+        context_window: 100_000,
+        compact_trigger_ratio: 0.90,
+        ..Default::default()
+    };
+    assert!(!near_compact_threshold(72_000, &config));
+    assert!(near_compact_threshold(72_001, &config));
+}
+
 // ======================================================================
 // find_compact_split
 // ======================================================================
@@ -937,6 +1001,145 @@ async fn compact_preserves_recent_messages() {
     assert_eq!(last.content, "recent answer");
 }
 
+// ======================================================================
+// Relevance-aware compaction
+// ======================================================================
+
+fn call(id: &str, name: &str, arguments: serde_json::Value) -> Message {
+    Message::assistant_with_tool_calls(
+        None,
+        vec![ToolCall {
+            id: id.to_string(),
+            name: name.to_string(),
+            arguments,
+        }],
+    )
+}
+
+/// Goal (pinned), a relevant file read, an unrelated listing, then the tail.
+fn parser_session() -> Vec<Message> {
+    vec![
+        Message::system("sys"),
+        Message::user("Fix the parser bug in src/parser.rs").pinned(),
+        call("c1", "read_file", json!({"path": "src/parser.rs"})),
+        Message::tool_result("c1", "fn parse_expression(tokens) { parser tokens nested }"),
+        call("c2", "bash", json!({"command": "ls docs"})),
+        Message::tool_result("c2", "README.md CHANGELOG.md LICENSE"),
+        Message::user(
+            "The parse_expression function in src/parser.rs still fails on nested tokens, \
+             please fix the parser",
+        ),
+        Message::assistant("Looking at parse_expression in parser.rs"),
+    ]
+}
+
+struct FailingEmbedder;
+
+#[async_trait::async_trait]
+impl MessageEmbedder for FailingEmbedder {
+    async fn embed(&self, _texts: &[String]) -> crate::error::Result<Vec<Vec<f32>>> {
+        Err(crate::error::AiError::Llm(
+            "embeddings unavailable".to_string(),
+        ))
+    }
+}
+
+#[test]
+fn message_units_group_tool_calls_with_results() {
+    let msgs = parser_session();
+    assert_eq!(
+        message_units(&msgs[1..]),
+        vec![(0, 1), (1, 3), (3, 5), (5, 6), (6, 7)]
+    );
+}
+
+#[test]
+fn cosine_similarity_handles_zero_vectors() {
+    assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+    assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+    assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+}
+
+#[tokio::test]
+async fn compact_keeps_pinned_and_relevant_messages_verbatim() {
+    let mock = MockLlmClient::from_steps("mock", vec![MockStep::text("Listed docs.")]);
+    let mut msgs = parser_session();
+    let config = ContextManagerConfig {
+        compact_preserve_tokens: 20,
+        compact_relevance_keep_ratio: 10.0,
+        ..Default::default()
+    };
+
+    let stats = compact(&mut msgs, &config, &mock).await.unwrap();
+
+    assert_eq!(stats.messages_replaced, 2);
+    assert!(msgs[1].content.starts_with("[Session Summary]"));
+    assert!(msgs[2].pinned);
+    assert_eq!(msgs[3].tool_calls.as_ref().unwrap()[0].name, "read_file");
+    assert_eq!(msgs[4].tool_call_id.as_deref(), Some("c1"));
+    assert!(msgs.iter().all(|msg| !msg.content.contains("README.md")));
+    assert_eq!(
+        msgs.last().unwrap().content,
+        "Looking at parse_expression in parser.rs"
+    );
+}
+
+#[tokio::test]
+async fn compact_keeps_pinned_tool_results() {
+    let mock = MockLlmClient::from_steps("mock", vec![MockStep::text("Read the parser.")]);
+    let mut msgs = parser_session();
+    let config = ContextManagerConfig {
+        compact_preserve_tokens: 20,
+        compact_relevance_keep_ratio: 0.0,
+        compact_pinned_tools: vec!["bash".to_string()],
+        ..Default::default()
+    };
+
+    let stats = compact(&mut msgs, &config, &mock).await.unwrap();
+
+    assert_eq!(stats.messages_replaced, 2);
+    assert_eq!(msgs[3].tool_calls.as_ref().unwrap()[0].name, "bash");
+    assert_eq!(msgs[4].content, "README.md CHANGELOG.md LICENSE");
+}
+
+#[tokio::test]
+async fn compact_falls_back_to_position_when_embedding_fails() {
+    let mock = MockLlmClient::from_steps("mock", vec![MockStep::text("Explored files.")]);
+    let mut msgs = parser_session();
+    let config = ContextManagerConfig {
+        compact_preserve_tokens: 20,
+        compact_relevance_keep_ratio: 10.0,
+        ..Default::default()
+    };
+
+    let stats = compact_with_embedder(&mut msgs, &config, &mock, &FailingEmbedder)
+        .await
+        .unwrap();
+
+    assert_eq!(stats.messages_replaced, 4);
+    assert!(msgs[2].pinned);
+    assert_eq!(msgs[3].role, Role::User);
+}
+
+#[tokio::test]
+async fn compact_noop_when_everything_old_is_pinned() {
+    let mock = MockLlmClient::new("mock");
+    let mut msgs = vec![
+        Message::system("sys"),
+        Message::user("goal").pinned(),
+        Message::user("recent question ".repeat(10)),
+    ];
+    let config = ContextManagerConfig {
+        compact_preserve_tokens: 5,
+        ..Default::default()
+    };
+
+    let stats = compact(&mut msgs, &config, &mock).await.unwrap();
+
+    assert_eq!(stats.messages_replaced, 0);
+    assert_eq!(msgs.len(), 3);
+}
+
 // ======================================================================
 // compact_was_effective
 // ======================================================================
@@ -1071,6 +1274,7 @@ async fn full_scenario_prune_avoids_compact() {
         prune_tool_max: 2048,
         min_prune_savings_tokens: 100,
         compact_preserve_tokens: 20_000,
+        ..Default::default()
     };
 
     let est_before = estimate_tokens(&msgs);
@@ -1084,3 +1288,125 @@ async fn full_scenario_prune_avoids_compact() {
         "after prune, should not need compact"
     );
 }
+
+// ======================================================================
+// tool schema slimming
+// ======================================================================
+
+fn sample_schemas(count: usize) -> Vec<crate::tools::ToolSchema> {
+    (0..count)
+        .map(|i| crate::tools::ToolSchema {
+            name: format!("tool_{i}"),
+            description: format!("Tool number {i}.\nLong usage notes that are dropped when slim."),
+            parameters: json!({
+                "type": "object",
+                "properties": {"input": {"type": "string", "description": "x".repeat(200)}},
+                "required": ["input"]
+            }),
+        })
+        .collect()
+}
+
+fn slim_config(min_tools: usize, idle_iterations: usize) -> ContextManagerConfig {
+    ContextManagerConfig::default()
+        .with_tool_schema_slim_min_tools(min_tools)
+        .with_tool_idle_exclusion_iterations(idle_iterations)
+}
+
+#[test]
+fn tool_schemas_pass_through_below_threshold() {
+    let mut slimmer = ToolSchemaSlimmer::new(&slim_config(40, 10));
+    let schemas = sample_schemas(3);
+    let selected = slimmer.select(schemas.clone(), 0);
+
+    assert_eq!(selected.len(), 3);
+    assert_eq!(selected[0].parameters, schemas[0].parameters);
+    assert_eq!(slimmer.stats().tokens_saved, 0);
+}
+
+#[test]
+fn tool_schemas_slimmed_until_first_use() {
+    let mut slimmer = ToolSchemaSlimmer::new(&slim_config(3, 0));
+    let selected = slimmer.select(sample_schemas(3), 0);
+
+    assert_eq!(selected.len(), 4);
+    assert_eq!(selected[3].name, TOOL_MANUAL_TOOL_NAME);
+    assert!(selected[0].description.starts_with("Tool number 0."));
+    assert!(!selected[0].description.contains("Long usage notes"));
+    assert!(selected[0].parameters.get("properties").is_none());
+    assert!(slimmer.stats().tokens_saved > 0);
+
+    slimmer.observe_tool_calls(
+        &[
+            ToolCall {
+                id: "c1".to_string(),
+                name: "tool_1".to_string(),
+                arguments: json!({"input": "a"}),
+            },
+            ToolCall {
+                id: "c2".to_string(),
+                name: TOOL_MANUAL_TOOL_NAME.to_string(),
+                arguments: json!({"names": ["tool_2"]}),
+            },
+        ],
+        0,
+    );
+    let selected = slimmer.select(sample_schemas(3), 1);
+    assert!(selected[0].parameters.get("properties").is_none());
+    assert!(selected[1].parameters.get("properties").is_some());
+    assert!(selected[2].parameters.get("properties").is_some());
+    assert_eq!(slimmer.stats().expanded_tools, 2);
+    assert_eq!(slimmer.stats().requests, 2);
+}
+
+#[test]
+fn idle_tools_are_excluded_until_fetched() {
+    let mut slimmer = ToolSchemaSlimmer::new(&slim_config(2, 3));
+    slimmer.observe_tool_calls(
+        &[ToolCall {
+            id: "c1".to_string(),
+            name: "tool_0".to_string(),
+            arguments: json!({}),
+        }],
+        2,
+    );
+
+    let selected = slimmer.select(sample_schemas(2), 3);
+    let names: Vec<&str> = selected.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["tool_0", TOOL_MANUAL_TOOL_NAME]);
+    assert_eq!(slimmer.stats().excluded_tools, 1);
+
+    slimmer.observe_tool_calls(
+        &[ToolCall {
+            id: "c2".to_string(),
+            name: TOOL_MANUAL_TOOL_NAME.to_string(),
+            arguments: json!({"name": "tool_1"}),
+        }],
+        3,
+    );
+    let selected = slimmer.select(sample_schemas(2), 4);
+    assert_eq!(selected.len(), 3);
+    assert_eq!(slimmer.stats().excluded_tools, 0);
+}
+
+#[test]
+fn tool_manual_reports_unknown_tools() {
+    let registry = crate::tools::ToolRegistry::new();
+    let output = tool_manual_output(&registry, &json!({"name": "missing"}));
+    assert!(output.success);
+    assert_eq!(output.result["unknown"], json!(["missing"]));
+
+    let listing = tool_manual_output(&registry, &json!({}));
+    assert_eq!(listing.result["tools"], json!([]));
+}
+
+#[test]
+fn short_description_keeps_first_line_within_limit() {
+    assert_eq!(
+        short_description("Read a file.\nDetails", 100),
+        "Read a file."
+    );
+    assert_eq!(short_description("abcdef", 3), "abc...");
+    let schema = &sample_schemas(1)[0];
+    assert!(estimate_schema_tokens(schema) > 50);
+}
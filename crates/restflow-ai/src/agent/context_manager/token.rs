@@ -1,6 +1,7 @@
 use crate::llm::Message;
 
 use super::constants::{CHARS_PER_TOKEN, ROLE_OVERHEAD_TOKENS};
+use super::tokenizer::TokenizerKind;
 
 /// Estimate tokens for a single message (bytes / CHARS_PER_TOKEN + role overhead).
 pub(crate) fn estimate_message_tokens(msg: &Message) -> usize {
@@ -22,17 +23,42 @@ pub fn estimate_tokens(messages: &[Message]) -> usize {
     messages.iter().map(estimate_message_tokens).sum()
 }
 
+/// Count tokens for a single message with a model tokenizer, plus role overhead.
+pub(crate) fn count_message_tokens(msg: &Message, tokenizer: TokenizerKind) -> usize {
+    let mut tokens = tokenizer.count(&msg.content);
+    if let Some(calls) = &msg.tool_calls {
+        for call in calls {
+            tokens += tokenizer.count(&call.id) + tokenizer.count(&call.name);
+            tokens += tokenizer.count(&call.arguments.to_string());
+        }
+    }
+    if let Some(id) = &msg.tool_call_id {
+        tokens += tokenizer.count(id);
+    }
+    tokens + ROLE_OVERHEAD_TOKENS
+}
+
+/// Count total tokens for a message list with a model tokenizer.
+pub fn count_tokens(messages: &[Message], tokenizer: TokenizerKind) -> usize {
+    messages
+        .iter()
+        .map(|msg| count_message_tokens(msg, tokenizer))
+        .sum()
+}
+
 /// Exponential-moving-average calibrated token estimator.
 ///
-/// Tracks a rolling `calibration_factor` (ratio of actual to heuristic tokens)
-/// and applies it to future estimates. Also provides a compaction cooldown
-/// to prevent compaction loops.
+/// Counts tokens with the active model's tokenizer, tracks a rolling
+/// `calibration_factor` (ratio of actual to counted tokens) and applies it
+/// to future estimates. Also provides a compaction cooldown to prevent
+/// compaction loops.
 #[derive(Debug, Clone)]
 pub struct TokenEstimator {
     pub(crate) calibration_factor: f64,
     pub(crate) samples: usize,
     /// Iterations remaining before compact is allowed again.
     pub(crate) compact_cooldown: usize,
+    pub(crate) tokenizer: TokenizerKind,
 }
 
 impl Default for TokenEstimator {
@@ -41,11 +67,36 @@ impl Default for TokenEstimator {
             calibration_factor: 1.0,
             samples: 0,
             compact_cooldown: 0,
+            tokenizer: TokenizerKind::default(),
         }
     }
 }
 
 impl TokenEstimator {
+    /// Create an estimator using the tokenizer for `model`.
+    pub fn for_model(model: &str) -> Self {
+        Self {
+            tokenizer: TokenizerKind::for_model(model),
+            ..Self::default()
+        }
+    }
+
+    /// Switch tokenizer when the active model changes. Calibration is reset
+    /// because it was measured against the previous tokenizer and provider.
+    pub fn set_model(&mut self, model: &str) {
+        let tokenizer = TokenizerKind::for_model(model);
+        if tokenizer != self.tokenizer {
+            self.tokenizer = tokenizer;
+            self.calibration_factor = 1.0;
+            self.samples = 0;
+        }
+    }
+
+    /// Uncalibrated token count for `messages`.
+    pub fn count(&self, messages: &[Message]) -> usize {
+        count_tokens(messages, self.tokenizer)
+    }
+
     /// Calibrate using the actual prompt_tokens returned by the API.
     pub fn calibrate(&mut self, estimated: usize, actual_prompt_tokens: u32) {
         if estimated == 0 || actual_prompt_tokens == 0 {
@@ -59,7 +110,7 @@ impl TokenEstimator {
 
     /// Return a calibrated token estimate.
     pub fn estimate(&self, messages: &[Message]) -> usize {
-        let raw = estimate_tokens(messages);
+        let raw = self.count(messages);
         (raw as f64 * self.calibration_factor).ceil() as usize
     }
 
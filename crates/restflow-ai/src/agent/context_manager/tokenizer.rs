@@ -0,0 +1,65 @@
+use tiktoken_rs::{CoreBPE, cl100k_base_singleton, o200k_base_singleton};
+
+use super::constants::CHARS_PER_TOKEN;
+
+/// BPE tokenizer used to count prompt tokens for a model family.
+///
+/// OpenAI models use their exact encoding. Other families (Claude, Gemini,
+/// open-weight models) have no public tokenizer here and use `cl100k_base`,
+/// which tracks them far closer than a byte heuristic; the estimator's
+/// calibration factor absorbs the remaining difference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TokenizerKind {
+    /// `o200k_base`: GPT-4o, GPT-4.1, GPT-5 and o-series models.
+    O200k,
+    /// `cl100k_base`: GPT-4, GPT-3.5 and the approximation for other families.
+    Cl100k,
+    /// Byte-length heuristic, used when the model is unknown.
+    #[default]
+    Heuristic,
+}
+
+impl TokenizerKind {
+    /// Pick the tokenizer for a model name, ignoring any provider prefix.
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_ascii_lowercase();
+        let name = model.rsplit(['/', ':']).next().unwrap_or(&model);
+        let name = if name.is_empty() {
+            model.as_str()
+        } else {
+            name
+        };
+        if name.starts_with("gpt-4o")
+            || name.starts_with("gpt-4.1")
+            || name.starts_with("gpt-5")
+            || name.starts_with("chatgpt-4o")
+            || name.starts_with("codex")
+            || ["o1", "o3", "o4"]
+                .iter()
+                .any(|prefix| name == *prefix || name.starts_with(&format!("{prefix}-")))
+        {
+            Self::O200k
+        } else {
+            Self::Cl100k
+        }
+    }
+
+    /// Count tokens in `text`.
+    pub fn count(self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        match self.bpe() {
+            Some(bpe) => bpe.encode_ordinary(text).len(),
+            None => text.len() / CHARS_PER_TOKEN,
+        }
+    }
+
+    fn bpe(self) -> Option<&'static CoreBPE> {
+        match self {
+            Self::O200k => Some(o200k_base_singleton()),
+            Self::Cl100k => Some(cl100k_base_singleton()),
+            Self::Heuristic => None,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{Value, json};
+
+use crate::llm::ToolCall;
+use crate::tools::{ToolOutput, ToolRegistry, ToolSchema};
+
+use super::config::ContextManagerConfig;
+use super::constants::CHARS_PER_TOKEN;
+
+/// Name of the built-in tool that returns full schemas for slimmed tools.
+pub const TOOL_MANUAL_TOOL_NAME: &str = "tool_manual";
+
+/// Accumulated tool schema token usage for one run.
+#[derive(Debug, Clone, Default)]
+pub struct ToolSchemaStats {
+    pub requests: usize,
+    /// Estimated tokens had every full schema been sent on every request.
+    pub full_schema_tokens: usize,
+    /// Estimated tokens actually sent.
+    pub sent_schema_tokens: usize,
+    pub tokens_saved: usize,
+    pub expanded_tools: usize,
+    /// Tools excluded from the most recent request for being idle.
+    pub excluded_tools: usize,
+}
+
+/// Chooses which tool schemas are sent on each request.
+///
+/// Once the registry reaches `tool_schema_slim_min_tools`, unused tools are
+/// sent with a short description and an open parameter schema. A tool's full
+/// schema is sent after it is first called or fetched via `tool_manual`, and
+/// tools idle for `tool_idle_exclusion_iterations` are dropped until fetched
+/// again.
+#[derive(Debug, Clone)]
+pub struct ToolSchemaSlimmer {
+    min_tools: usize,
+    short_description_chars: usize,
+    idle_iterations: usize,
+    expanded: HashSet<String>,
+    last_used: HashMap<String, usize>,
+    stats: ToolSchemaStats,
+}
+
+impl ToolSchemaSlimmer {
+    pub fn new(config: &ContextManagerConfig) -> Self {
+        Self {
+            min_tools: config.tool_schema_slim_min_tools,
+            short_description_chars: config.tool_short_description_chars,
+            idle_iterations: config.tool_idle_exclusion_iterations,
+            expanded: HashSet::new(),
+            last_used: HashMap::new(),
+            stats: ToolSchemaStats::default(),
+        }
+    }
+
+    /// Whether slimming applies to a registry of `tool_count` tools.
+    pub fn is_active(&self, tool_count: usize) -> bool {
+        self.min_tools > 0 && tool_count >= self.min_tools
+    }
+
+    /// Select the schemas to send for the request at `iteration`.
+    pub fn select(&mut self, schemas: Vec<ToolSchema>, iteration: usize) -> Vec<ToolSchema> {
+        let full_tokens: usize = schemas.iter().map(estimate_schema_tokens).sum();
+        self.stats.requests += 1;
+        self.stats.full_schema_tokens += full_tokens;
+
+        if !self.is_active(schemas.len()) {
+            self.stats.sent_schema_tokens += full_tokens;
+            return schemas;
+        }
+
+        let mut selected = Vec::with_capacity(schemas.len() + 1);
+        let mut excluded = 0;
+        for schema in schemas {
+            if self.is_idle(&schema.name, iteration) {
+                excluded += 1;
+            } else if self.expanded.contains(&schema.name) {
+                selected.push(schema);
+            } else {
+                selected.push(slim_schema(&schema, self.short_description_chars));
+            }
+        }
+        selected.push(tool_manual_schema());
+
+        let sent_tokens: usize = selected.iter().map(estimate_schema_tokens).sum();
+        self.stats.sent_schema_tokens += sent_tokens;
+        self.stats.tokens_saved += full_tokens.saturating_sub(sent_tokens);
+        self.stats.excluded_tools = excluded;
+        selected
+    }
+
+    /// Record the tool calls made at `iteration`, expanding called tools and
+    /// any tools fetched through `tool_manual`.
+    pub fn observe_tool_calls(&mut self, calls: &[ToolCall], iteration: usize) {
+        for call in calls {
+            if call.name == TOOL_MANUAL_TOOL_NAME {
+                for name in requested_tool_names(&call.arguments) {
+                    self.mark_used(name, iteration);
+                }
+            } else {
+                self.mark_used(call.name.clone(), iteration);
+            }
+        }
+    }
+
+    pub fn stats(&self) -> &ToolSchemaStats {
+        &self.stats
+    }
+
+    fn mark_used(&mut self, name: String, iteration: usize) {
+        self.last_used.insert(name.clone(), iteration);
+        if self.expanded.insert(name) {
+            self.stats.expanded_tools += 1;
+        }
+    }
+
+    fn is_idle(&self, name: &str, iteration: usize) -> bool {
+        if self.idle_iterations == 0 {
+            return false;
+        }
+        let last_active = self.last_used.get(name).copied().unwrap_or(0);
+        iteration.saturating_sub(last_active) >= self.idle_iterations
+    }
+}
+
+/// Build the `tool_manual` response from the full registry.
+pub fn tool_manual_output(registry: &ToolRegistry, args: &Value) -> ToolOutput {
+    let names = requested_tool_names(args);
+    if names.is_empty() {
+        let mut tools: Vec<Value> = registry
+            .schemas()
+            .into_iter()
+            .map(|schema| {
+                json!({
+                    "name": schema.name,
+                    "description": short_description(&schema.description, 200),
+                })
+            })
+            .collect();
+        tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        return ToolOutput::success(json!({ "tools": tools }));
+    }
+
+    let mut schemas = Vec::new();
+    let mut unknown = Vec::new();
+    for name in names {
+        match registry.get(&name) {
+            Some(tool) => schemas.push(tool.schema()),
+            None => unknown.push(name),
+        }
+    }
+    ToolOutput::success(json!({ "schemas": schemas, "unknown": unknown }))
+}
+
+fn requested_tool_names(args: &Value) -> Vec<String> {
+    let mut names: Vec<String> = args
+        .get("names")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(name) = args.get("name").and_then(Value::as_str) {
+        names.push(name.to_string());
+    }
+    names
+}
+
+fn tool_manual_schema() -> ToolSchema {
+    ToolSchema {
+        name: TOOL_MANUAL_TOOL_NAME.to_string(),
+        description: "Fetch the full parameter schema for one or more tools before calling them. \
+                      Call without arguments to list every available tool, including tools \
+                      hidden from this request."
+            .to_string(),
+        parameters: json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "description": "Tool name to describe"},
+                "names": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Several tool names to describe"
+                }
+            }
+        }),
+    }
+}
+
+fn slim_schema(schema: &ToolSchema, max_chars: usize) -> ToolSchema {
+    ToolSchema {
+        name: schema.name.clone(),
+        description: format!(
+            "{} (Call {} for parameters.)",
+            short_description(&schema.description, max_chars),
+            TOOL_MANUAL_TOOL_NAME
+        ),
+        parameters: json!({"type": "object", "additionalProperties": true}),
+    }
+}
+
+/// First line of a description, cut to `max_chars`.
+pub(crate) fn short_description(description: &str, max_chars: usize) -> String {
+    let first_line = description.lines().next().unwrap_or_default().trim();
+    match first_line.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &first_line[..end]),
+        None => first_line.to_string(),
+    }
+}
+
+pub(crate) fn estimate_schema_tokens(schema: &ToolSchema) -> usize {
+    let bytes = schema.name.len() + schema.description.len() + schema.parameters.to_string().len();
+    bytes.div_ceil(CHARS_PER_TOKEN)
+}
@@ -11,20 +11,24 @@ use restflow_traits::{
     DEFAULT_AGENT_LLM_TIMEOUT_SECS, DEFAULT_AGENT_MAX_ITERATIONS,
     DEFAULT_AGENT_MAX_TOOL_CONCURRENCY, DEFAULT_AGENT_MAX_TOOL_RESULT_LENGTH,
     DEFAULT_AGENT_PRUNE_TOOL_MAX_CHARS, DEFAULT_AGENT_TOOL_TIMEOUT_SECS, TeamCoordinator,
-    llm::LlmSwitcher,
+    llm::LlmSwitcher, store::WorkItemProvider,
 };
 use serde_json::Value;
 
 use crate::agent::PromptFlags;
 use crate::agent::context::AgentContext;
+use crate::agent::context_manager::{ContextManagerConfig, MessageEmbedder, ToolSchemaStats};
 use crate::agent::model_router::ModelRoutingConfig;
 use crate::agent::resource::{ResourceLimits, ResourceUsage};
 use crate::agent::state::AgentState;
-use crate::agent::stuck::StuckDetectorConfig;
 use crate::agent::streaming_buffer::StreamDisplayMode;
+use crate::agent::stuck::StuckDetectorConfig;
 use crate::error::Result;
 
+use super::reflection::{ReflectionConfig, ReflectionStats};
+
 pub const MAX_TOOL_RETRIES: usize = 2;
+pub const DEFAULT_MAX_REPLANS: usize = 2;
 #[cfg(test)]
 pub const DEFAULT_MAX_TOOL_CONCURRENCY: usize = DEFAULT_AGENT_MAX_TOOL_CONCURRENCY;
 
@@ -39,6 +43,17 @@ pub enum CheckpointDurability {
     OnComplete,
 }
 
+/// How the executor works towards the goal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecutionStrategy {
+    /// Single ReAct loop over the whole goal.
+    #[default]
+    React,
+    /// Draft an explicit plan, run each step as its own ReAct loop, verify
+    /// the outcome and re-plan the remaining work when a step fails.
+    PlanAndExecute,
+}
+
 impl Default for CheckpointDurability {
     fn default() -> Self {
         Self::Periodic { interval: 5 }
@@ -75,6 +90,10 @@ pub struct AgentConfig {
     pub prune_tool_max_chars: usize,
     /// Tokens preserved from the recent tail during context compaction.
     pub compact_preserve_tokens: usize,
+    /// Registry size at which tool schemas are slimmed (0 disables slimming).
+    pub tool_schema_slim_min_tools: usize,
+    /// Iterations without use before a tool schema is excluded (0 disables exclusion).
+    pub tool_idle_exclusion_iterations: usize,
     /// Optional maximum output tokens for each LLM completion request.
     pub max_output_tokens: Option<u32>,
     /// Optional agent context injected into the system prompt.
@@ -93,6 +112,9 @@ pub struct AgentConfig {
     pub model_routing: Option<ModelRoutingConfig>,
     /// Optional model switcher used when model routing is enabled.
     pub model_switcher: Option<Arc<dyn LlmSwitcher>>,
+    /// Embedder used to score message relevance during compaction
+    /// (default: lexical similarity).
+    pub compaction_embedder: Option<Arc<dyn MessageEmbedder>>,
     /// Optional telemetry sink for execution-scoped events.
     pub telemetry_sink: Option<Arc<dyn TelemetrySink>>,
     /// Optional telemetry context shared across emitted events.
@@ -101,21 +123,37 @@ pub struct AgentConfig {
     pub team_coordinator: Option<Arc<dyn TeamCoordinator>>,
     /// Auto-approve security-gated tool calls (scheduled automation mode).
     pub yolo_mode: bool,
+    /// Simulate side-effecting tools instead of running them (default: false).
+    ///
+    /// Read-only tools still run, so the run previews the agent's plan.
+    pub dry_run: bool,
     /// Checkpoint persistence policy.
     pub checkpoint_durability: CheckpointDurability,
     /// Optional callback to persist agent state checkpoints.
     pub checkpoint_callback: Option<CheckpointCallback>,
+    /// Optional callback receiving a state snapshot after every turn and on
+    /// completion, used to record runs for step-by-step replay.
+    pub replay_callback: Option<CheckpointCallback>,
     /// Feature flags for conditional prompt section inclusion.
     pub prompt_flags: PromptFlags,
     /// Maximum number of tool calls that can execute concurrently (default: 100).
     pub max_tool_concurrency: usize,
     /// Controls how aggressively text deltas are flushed to interactive consumers.
     pub stream_display_mode: StreamDisplayMode,
+    /// Execution strategy (default: ReAct).
+    pub execution_strategy: ExecutionStrategy,
+    /// Optional store where plan-and-execute persists plan steps as work items.
+    pub plan_store: Option<Arc<dyn WorkItemProvider>>,
+    /// Times plan-and-execute may re-plan after a failed step (default: 2).
+    pub max_replans: usize,
+    /// Optional critique of the final answer before it is accepted.
+    pub reflection: Option<ReflectionConfig>,
 }
 
 impl AgentConfig {
     /// Create a new agent config with a goal
     pub fn new(goal: impl Into<String>) -> Self {
+        let context_defaults = ContextManagerConfig::default();
         Self {
             goal: goal.into(),
             system_prompt: None,
@@ -128,6 +166,8 @@ impl AgentConfig {
             context_window: DEFAULT_AGENT_CONTEXT_WINDOW_TOKENS,
             prune_tool_max_chars: DEFAULT_AGENT_PRUNE_TOOL_MAX_CHARS,
             compact_preserve_tokens: DEFAULT_AGENT_COMPACT_PRESERVE_TOKENS,
+            tool_schema_slim_min_tools: context_defaults.tool_schema_slim_min_tools,
+            tool_idle_exclusion_iterations: context_defaults.tool_idle_exclusion_iterations,
             max_output_tokens: None,
             agent_context: None,
             inject_agent_context: true,
@@ -136,15 +176,22 @@ impl AgentConfig {
             tool_output_dir: None,
             model_routing: None,
             model_switcher: None,
+            compaction_embedder: None,
             telemetry_sink: None,
             telemetry_context: None,
             team_coordinator: None,
             yolo_mode: false,
+            dry_run: false,
             checkpoint_durability: CheckpointDurability::Periodic { interval: 5 },
             checkpoint_callback: None,
+            replay_callback: None,
             prompt_flags: PromptFlags::default(),
             max_tool_concurrency: DEFAULT_AGENT_MAX_TOOL_CONCURRENCY,
             stream_display_mode: StreamDisplayMode::Buffered,
+            execution_strategy: ExecutionStrategy::React,
+            plan_store: None,
+            max_replans: DEFAULT_MAX_REPLANS,
+            reflection: None,
         }
     }
 
@@ -217,6 +264,18 @@ impl AgentConfig {
         self
     }
 
+    /// Set the registry size at which tool schemas are slimmed (0 disables).
+    pub fn with_tool_schema_slim_min_tools(mut self, min_tools: usize) -> Self {
+        self.tool_schema_slim_min_tools = min_tools;
+        self
+    }
+
+    /// Set how many idle iterations exclude a tool schema (0 disables).
+    pub fn with_tool_idle_exclusion_iterations(mut self, iterations: usize) -> Self {
+        self.tool_idle_exclusion_iterations = iterations;
+        self
+    }
+
     /// Set temperature
     pub fn with_temperature(mut self, temp: f32) -> Self {
         self.temperature = Some(temp);
@@ -294,6 +353,12 @@ impl AgentConfig {
         self
     }
 
+    /// Set the embedder used to score message relevance during compaction.
+    pub fn with_compaction_embedder(mut self, embedder: Arc<dyn MessageEmbedder>) -> Self {
+        self.compaction_embedder = Some(embedder);
+        self
+    }
+
     /// Enable or disable yolo mode (auto-approval execution mode).
     /// Set prompt flags for conditional section inclusion.
     pub fn with_prompt_flags(mut self, flags: PromptFlags) -> Self {
@@ -312,6 +377,36 @@ impl AgentConfig {
         self
     }
 
+    /// Enable or disable dry-run mode.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the execution strategy.
+    pub fn with_execution_strategy(mut self, strategy: ExecutionStrategy) -> Self {
+        self.execution_strategy = strategy;
+        self
+    }
+
+    /// Persist plan-and-execute steps as work items in `store`.
+    pub fn with_plan_store(mut self, store: Arc<dyn WorkItemProvider>) -> Self {
+        self.plan_store = Some(store);
+        self
+    }
+
+    /// Set how many times plan-and-execute may re-plan after a failed step.
+    pub fn with_max_replans(mut self, max_replans: usize) -> Self {
+        self.max_replans = max_replans;
+        self
+    }
+
+    /// Critique the final answer and allow revisions when it falls short.
+    pub fn with_reflection(mut self, reflection: ReflectionConfig) -> Self {
+        self.reflection = Some(reflection);
+        self
+    }
+
     /// Set checkpoint durability policy.
     pub fn with_checkpoint_durability(mut self, durability: CheckpointDurability) -> Self {
         self.checkpoint_durability = durability;
@@ -327,6 +422,19 @@ impl AgentConfig {
         self.checkpoint_callback = Some(Arc::new(move |state| Box::pin(callback(state))));
         self
     }
+
+    /// Set asynchronous replay snapshot callback.
+    ///
+    /// Unlike checkpoints, snapshots are taken after every turn regardless of
+    /// durability, and a failing callback does not abort the run.
+    pub fn with_replay_callback<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: Fn(&AgentState) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        self.replay_callback = Some(Arc::new(move |state| Box::pin(callback(state))));
+        self
+    }
 }
 
 /// Result of agent execution
@@ -341,4 +449,8 @@ pub struct AgentResult {
     pub state: AgentState,
     /// Resource usage snapshot at end of run.
     pub resource_usage: ResourceUsage,
+    /// Tool schema tokens sent and saved by schema slimming.
+    pub tool_schema_stats: ToolSchemaStats,
+    /// Final-answer critiques and revisions performed.
+    pub reflection: ReflectionStats,
 }
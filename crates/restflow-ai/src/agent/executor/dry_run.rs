@@ -0,0 +1,159 @@
+//! Simulated tool results for dry-run executions.
+//!
+//! In dry-run mode tools that change the outside world (run commands, write
+//! files, send messages, spawn agents) are not invoked. The model instead
+//! receives a description of what the call would have done, so the run
+//! produces a reviewable plan. Read-only tools keep running normally so the
+//! plan is grounded in real data.
+
+use serde_json::{Value, json};
+
+use crate::tools::ToolOutput;
+
+/// Prompt section appended to the system prompt of dry-run executions.
+pub(crate) const DRY_RUN_PROMPT_SECTION: &str = "## Dry Run\n\n\
+This is a dry run. Tools that modify files, run commands, send messages or \
+make non-GET HTTP requests are simulated: their results only describe what \
+would have happened. Read-only tools run normally. Work through the task as \
+usual so the user can review the planned actions.";
+
+/// HTTP methods that are treated as read-only.
+const READ_ONLY_HTTP_METHODS: &[&str] = &["GET", "HEAD", "OPTIONS"];
+
+/// Return a simulated result when `name` would cause side effects, or `None`
+/// when the call is read-only and should run normally.
+pub(crate) fn simulate_tool_call(name: &str, args: &Value) -> Option<ToolOutput> {
+    let action = side_effect_description(name, args)?;
+    Some(ToolOutput::success(json!({
+        "dry_run": true,
+        "executed": false,
+        "tool": name,
+        "would": action,
+        "arguments": args,
+    })))
+}
+
+fn side_effect_description(name: &str, args: &Value) -> Option<String> {
+    let str_arg = |key: &str| args.get(key).and_then(Value::as_str).unwrap_or("?");
+    let action = args.get("action").and_then(Value::as_str).unwrap_or("");
+    match name {
+        "bash" => Some(format!("run shell command `{}`", str_arg("command"))),
+        "python" | "run_python" => Some("run a Python script".to_string()),
+        "file" => match action {
+            "write" if args.get("append").and_then(Value::as_bool) == Some(true) => {
+                Some(format!("append to file {}", str_arg("path")))
+            }
+            "write" => Some(format!("write file {}", str_arg("path"))),
+            "delete" => Some(format!("delete file {}", str_arg("path"))),
+            _ => None,
+        },
+        "edit" | "multiedit" => Some(format!("edit file {}", str_arg("file_path"))),
+        "patch" => Some("apply a multi-file patch".to_string()),
+        "process" => match action {
+            "spawn" => Some(format!("spawn process `{}`", str_arg("command"))),
+            "write" => Some(format!(
+                "write to process session {}",
+                str_arg("session_id")
+            )),
+            "kill" => Some(format!("kill process session {}", str_arg("session_id"))),
+            _ => None,
+        },
+        "shell_session" => match action {
+            "open" => Some("open an interactive shell".to_string()),
+            "send" => Some(format!(
+                "send input to shell session {}",
+                str_arg("session_id")
+            )),
+            "close" => Some(format!("close shell session {}", str_arg("session_id"))),
+            _ => None,
+        },
+        "git" => {
+            let operation = args.get("operation").and_then(Value::as_str).unwrap_or("");
+            let listing = matches!(action, "" | "list");
+            match operation {
+                "status" | "diff" | "log" => None,
+                "branch" | "stash" | "worktree" if listing => None,
+                "commit" => Some(format!("create git commit \"{}\"", str_arg("message"))),
+                "push" => Some(format!("push to git remote {}", str_arg("remote"))),
+                other if action.is_empty() => Some(format!("run git {}", other)),
+                other => Some(format!("run git {} {}", other, action)),
+            }
+        }
+        "github" => {
+            let operation = args.get("operation").and_then(Value::as_str).unwrap_or("");
+            let repo = str_arg("repo");
+            match operation {
+                "create_issue" => Some(format!("open issue \"{}\" in {}", str_arg("title"), repo)),
+                "create_pull_request" => Some(format!(
+                    "open pull request \"{}\" in {}",
+                    str_arg("title"),
+                    repo
+                )),
+                "comment" | "reply_review_comment" => {
+                    Some(format!("comment on #{} in {}", args["number"], repo))
+                }
+                _ => None,
+            }
+        }
+        "calendar" => match args.get("operation").and_then(Value::as_str) {
+            Some("create_event") => Some(format!(
+                "create calendar event \"{}\" at {}",
+                str_arg("title"),
+                str_arg("start")
+            )),
+            Some("update_event") => Some(format!("update calendar event {}", str_arg("event_id"))),
+            _ => None,
+        },
+        "sql" if args.get("operation").and_then(Value::as_str) == Some("execute") => {
+            Some(format!("execute SQL `{}`", str_arg("sql")))
+        }
+        "spreadsheet" if args.get("operation").and_then(Value::as_str) == Some("write") => {
+            Some(format!("write spreadsheet {}", str_arg("path")))
+        }
+        "http_request" => {
+            let method = str_arg("method").to_ascii_uppercase();
+            if READ_ONLY_HTTP_METHODS.contains(&method.as_str()) {
+                None
+            } else {
+                Some(format!(
+                    "send HTTP {} request to {}",
+                    method,
+                    str_arg("url")
+                ))
+            }
+        }
+        "send_email" => Some(format!("send email to {}", recipients(args.get("to")))),
+        "telegram_send" => Some("send a Telegram message".to_string()),
+        "discord_send" => Some("send a Discord message".to_string()),
+        "slack_send" => Some("send a Slack message".to_string()),
+        "spawn_subagent" | "spawn_subagent_batch" => {
+            let operation = args.get("operation").and_then(Value::as_str);
+            if !matches!(operation, None | Some("spawn")) {
+                None
+            } else if name == "spawn_subagent" {
+                Some(format!("spawn subagent {}", str_arg("agent")))
+            } else {
+                Some("spawn a batch of subagents".to_string())
+            }
+        }
+        "fanout" => Some(format!(
+            "spawn {} subagents over the given items",
+            args.get("items")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len)
+        )),
+        _ => None,
+    }
+}
+
+fn recipients(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(to)) => to.clone(),
+        Some(Value::Array(list)) => list
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => "?".to_string(),
+    }
+}
@@ -0,0 +1,113 @@
+//! Request-level LLM failover.
+//!
+//! When `ModelRoutingConfig::failover` is set and a model switcher is
+//! available, an LLM request that fails with a rate-limit or outage error is
+//! retried on the next fallback model instead of failing the whole run.
+
+use restflow_telemetry::ExecutionEvent;
+use tracing::warn;
+
+use super::{AgentConfig, AgentExecutor};
+use crate::agent::stream::StreamEmitter;
+use crate::agent::streaming_buffer::StreamingBuffer;
+use crate::error::Result;
+use crate::llm::{CompletionRequest, CompletionResponse};
+
+impl AgentExecutor {
+    /// Run an LLM completion, failing over to fallback models on
+    /// rate-limit, overload or outage errors.
+    ///
+    /// Each failover is recorded as a `ModelSwitch` event with reason
+    /// `failover`. The switched model stays active for later iterations.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn complete_with_failover(
+        &self,
+        request: CompletionRequest,
+        stream_llm: bool,
+        emitter: &mut dyn StreamEmitter,
+        iteration: usize,
+        execution_id: &str,
+        streaming_buffer: &mut StreamingBuffer,
+        config: &AgentConfig,
+    ) -> Result<CompletionResponse> {
+        let policy = config
+            .model_routing
+            .as_ref()
+            .and_then(|routing| routing.failover.as_ref());
+        let (Some(policy), Some(switcher)) = (policy, config.model_switcher.as_ref()) else {
+            return self
+                .execute_llm_completion(
+                    request,
+                    stream_llm,
+                    emitter,
+                    iteration,
+                    execution_id,
+                    streaming_buffer,
+                    config.llm_timeout,
+                )
+                .await;
+        };
+
+        let mut fallbacks = policy.fallback_models.iter();
+        let mut attempt = 0u32;
+        loop {
+            let error = match self
+                .execute_llm_completion(
+                    request.clone(),
+                    stream_llm,
+                    emitter,
+                    iteration,
+                    execution_id,
+                    streaming_buffer,
+                    config.llm_timeout,
+                )
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(error) if error.is_failover_eligible() => error,
+                Err(error) => return Err(error),
+            };
+
+            let current_model = switcher.current_model();
+            let Some(next_model) = fallbacks.find(|model| **model != current_model) else {
+                return Err(error);
+            };
+            attempt += 1;
+            let delay = policy.delay_for(attempt, error.retry_after());
+            warn!(
+                from_model = %current_model,
+                to_model = %next_model,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "LLM request failed, failing over to fallback model"
+            );
+            tokio::time::sleep(delay).await;
+
+            let switched = switcher.switch_model(next_model);
+            let effective_model = if switched.is_ok() {
+                next_model.as_str()
+            } else {
+                current_model.as_str()
+            };
+            Self::emit_execution_event(
+                config.telemetry_sink.as_ref(),
+                config.telemetry_context.as_ref(),
+                ExecutionEvent::ModelSwitch {
+                    from_model: current_model.clone(),
+                    to_model: next_model.clone(),
+                    reason: Some("failover".to_string()),
+                    success: switched.is_ok(),
+                },
+                Some(effective_model),
+            )
+            .await;
+            if let Err(switch_error) = switched {
+                warn!(
+                    to_model = %next_model,
+                    error = %switch_error,
+                    "Failed to switch to fallback model"
+                );
+            }
+        }
+    }
+}
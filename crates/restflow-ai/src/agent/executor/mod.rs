@@ -47,11 +47,16 @@
 //!   (set to `None` to disable).
 
 mod config;
+mod dry_run;
+mod failover;
+mod plan;
 mod prompt;
+mod reflection;
 mod steer;
 mod streaming;
 mod tool_exec;
 pub use config::*;
+pub use reflection::{ReflectionConfig, ReflectionStats};
 use tool_exec::{ToolExecutionOptions, ToolInvocationContext};
 
 use std::sync::Arc;
@@ -67,7 +72,9 @@ use restflow_telemetry::{
 use serde_json::Value;
 
 use crate::agent::context::{ContextDiscoveryConfig, WorkspaceContextCache};
-use crate::agent::context_manager::{self, ContextManagerConfig, TokenEstimator};
+use crate::agent::context_manager::{
+    self, ContextManagerConfig, LexicalEmbedder, MessageEmbedder, TokenEstimator, ToolSchemaSlimmer,
+};
 use crate::agent::deferred::DeferredExecutionManager;
 use crate::agent::model_router::{classify_task, select_model};
 use crate::agent::resource::ResourceTracker;
@@ -86,7 +93,8 @@ use crate::tools::ToolRegistry;
 use dashmap::DashMap;
 use tokio::sync::{Mutex, mpsc};
 use tokio::task::AbortHandle;
-use tracing::debug;
+use tracing::field::Empty;
+use tracing::{Instrument, debug};
 
 const USER_INSTRUCTIONS_PREFIX: &str = "# AGENTS.md instructions for ";
 
@@ -301,6 +309,55 @@ impl AgentExecutor {
         execution_id: &str,
         streaming_buffer: &mut StreamingBuffer,
         llm_timeout: Option<Duration>,
+    ) -> Result<CompletionResponse> {
+        let span = tracing::info_span!(
+            "llm.completion",
+            execution_id,
+            iteration,
+            provider = self.llm.provider(),
+            model = self.llm.model(),
+            stream = stream_llm,
+            prompt_tokens = Empty,
+            completion_tokens = Empty,
+            total_tokens = Empty,
+            cost_usd = Empty,
+        );
+        let result = self
+            .complete_llm(
+                request,
+                stream_llm,
+                emitter,
+                iteration,
+                execution_id,
+                streaming_buffer,
+                llm_timeout,
+            )
+            .instrument(span.clone())
+            .await;
+        if let Ok(CompletionResponse {
+            usage: Some(usage), ..
+        }) = &result
+        {
+            span.record("prompt_tokens", usage.prompt_tokens);
+            span.record("completion_tokens", usage.completion_tokens);
+            span.record("total_tokens", usage.total_tokens);
+            if let Some(cost_usd) = usage.cost_usd {
+                span.record("cost_usd", cost_usd);
+            }
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn complete_llm(
+        &self,
+        request: CompletionRequest,
+        stream_llm: bool,
+        emitter: &mut dyn StreamEmitter,
+        iteration: usize,
+        execution_id: &str,
+        streaming_buffer: &mut StreamingBuffer,
+        llm_timeout: Option<Duration>,
     ) -> Result<CompletionResponse> {
         let completion = async {
             if stream_llm {
@@ -404,6 +461,18 @@ impl AgentExecutor {
             .await
     }
 
+    #[tracing::instrument(
+        name = "agent.run",
+        skip_all,
+        fields(
+            execution_id = Empty,
+            run_id = Empty,
+            actor_id = Empty,
+            iterations = Empty,
+            total_tokens = Empty,
+            success = Empty,
+        )
+    )]
     async fn execute_with_mode(
         &self,
         config: AgentConfig,
@@ -414,6 +483,18 @@ impl AgentExecutor {
     ) -> Result<AgentResult> {
         let execution_id =
             execution_id_override.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        if config.execution_strategy == ExecutionStrategy::PlanAndExecute && initial_state.is_none()
+        {
+            return self
+                .execute_plan(config, emitter, stream_llm, execution_id)
+                .await;
+        }
+        let run_span = tracing::Span::current();
+        run_span.record("execution_id", execution_id.as_str());
+        if let Some(context) = &config.telemetry_context {
+            run_span.record("run_id", context.trace.run_id.as_str());
+            run_span.record("actor_id", context.trace.actor_id.as_str());
+        }
         let mut streaming_buffer = StreamingBuffer::for_mode(config.stream_display_mode);
         let mut state =
             initial_state.unwrap_or_else(|| AgentState::new(execution_id, config.max_iterations));
@@ -425,8 +506,12 @@ impl AgentExecutor {
         let context_config = ContextManagerConfig::default()
             .with_context_window(config.context_window)
             .with_prune_tool_max(config.prune_tool_max_chars)
-            .with_compact_preserve_tokens(config.compact_preserve_tokens);
+            .with_compact_preserve_tokens(config.compact_preserve_tokens)
+            .with_tool_schema_slim_min_tools(config.tool_schema_slim_min_tools)
+            .with_tool_idle_exclusion_iterations(config.tool_idle_exclusion_iterations);
         let mut token_estimator = TokenEstimator::default();
+        let mut schema_slimmer = ToolSchemaSlimmer::new(&context_config);
+        let mut reflection_stats = ReflectionStats::default();
 
         // Initialize stuck detector
         let mut stuck_detector = config.stuck_detection.clone().map(StuckDetector::new);
@@ -438,7 +523,7 @@ impl AgentExecutor {
         if state.messages.is_empty() {
             let system_prompt = self.build_system_prompt(&config).await;
             let system_msg = Message::system(&system_prompt);
-            let user_msg = Message::user(&config.goal);
+            let user_msg = Message::user(&config.goal).pinned();
 
             state.add_message(system_msg);
             state.add_message(user_msg);
@@ -469,6 +554,7 @@ impl AgentExecutor {
                 state.resource_exhaust(e.to_string());
                 break;
             }
+            emitter.emit_iteration_start(state.iteration + 1).await;
 
             // 1. LLM call
             if let Some(routing) = config
@@ -524,14 +610,37 @@ impl AgentExecutor {
 
             // Context management: compact if approaching context window limit
             token_estimator.tick_cooldown();
-            let estimated = token_estimator.estimate(&state.messages);
+            let active_model = config
+                .model_switcher
+                .as_ref()
+                .map(|switcher| switcher.current_model())
+                .unwrap_or_else(|| self.llm.model().to_string());
+            token_estimator.set_model(&active_model);
+            let mut estimated = token_estimator.estimate(&state.messages);
+            if token_estimator.compact_allowed()
+                && context_manager::near_compact_threshold(estimated, &context_config)
+            {
+                // Close to the limit: prefer the provider's exact count.
+                let probe =
+                    CompletionRequest::new(sanitize_tool_call_history(state.messages.clone()));
+                match self.llm.count_tokens(&probe).await {
+                    Ok(Some(exact)) => estimated = exact as usize,
+                    Ok(None) => {}
+                    Err(error) => debug!(error = %error, "Exact token count unavailable"),
+                }
+            }
             if token_estimator.compact_allowed()
                 && context_manager::should_compact(estimated, &context_config)
             {
-                match context_manager::compact(
+                let embedder: &dyn MessageEmbedder = match &config.compaction_embedder {
+                    Some(embedder) => embedder.as_ref(),
+                    None => &LexicalEmbedder,
+                };
+                match context_manager::compact_with_embedder(
                     &mut state.messages,
                     &context_config,
                     self.llm.as_ref(),
+                    embedder,
                 )
                 .await
                 {
@@ -558,13 +667,8 @@ impl AgentExecutor {
             }
 
             let request_messages = sanitize_tool_call_history(state.messages.clone());
-            // TODO(ToolSearch): Currently sends ALL tool schemas to the LLM every turn.
-            // With 58+ tools this costs ~46K tokens per request. Instead:
-            //   let loaded_tools = self.tools.partition_for_deferred_loading();
-            //   let request = CompletionRequest::new(request_messages).with_tools(loaded_tools.schemas());
-            // See restflow-traits/src/tool.rs Tool trait TODO for the full plan.
-            let mut request =
-                CompletionRequest::new(request_messages).with_tools(self.tools.schemas());
+            let tool_schemas = schema_slimmer.select(self.tools.schemas(), state.iteration);
+            let mut request = CompletionRequest::new(request_messages).with_tools(tool_schemas);
 
             // Only set temperature if explicitly configured (some models don't support it)
             if let Some(temp) = config.temperature {
@@ -576,14 +680,14 @@ impl AgentExecutor {
 
             let llm_started_at = Instant::now();
             let response = self
-                .execute_llm_completion(
+                .complete_with_failover(
                     request,
                     stream_llm,
                     emitter,
                     state.iteration + 1,
                     &state.execution_id,
                     &mut streaming_buffer,
-                    config.llm_timeout,
+                    &config,
                 )
                 .await?;
             let llm_duration_ms = llm_started_at.elapsed().as_millis() as u64;
@@ -608,6 +712,9 @@ impl AgentExecutor {
                     duration_ms: Some(llm_duration_ms),
                     is_reasoning: None,
                     message_count: Some(request_message_count),
+                    cache_read_tokens: usage.map(|value| value.cache_read_tokens),
+                    cache_write_tokens: usage.map(|value| value.cache_write_tokens),
+                    cache_savings_usd: usage.and_then(|value| value.cache_savings_usd),
                 }),
                 Some(&emitted_model),
             )
@@ -618,7 +725,7 @@ impl AgentExecutor {
                 total_tokens += usage.total_tokens;
                 // Calibrate token estimator with actual prompt tokens
                 if usage.prompt_tokens > 0 {
-                    let est = context_manager::estimate_tokens(&state.messages);
+                    let est = token_estimator.count(&state.messages);
                     token_estimator.calibrate(est, usage.prompt_tokens);
                 }
                 if let Some(cost) = usage.cost_usd {
@@ -653,6 +760,29 @@ impl AgentExecutor {
                             state.iteration += 1;
                             continue;
                         }
+                        if let Some(reflection) = &config.reflection
+                            && reflection_stats.revisions < reflection.max_revisions
+                            && state.iteration + 1 < state.max_iterations
+                            && let Some(critique) = self
+                                .critique_answer(reflection, &config.goal, &state.messages, &answer)
+                                .await
+                        {
+                            reflection_stats.critiques += 1;
+                            if !critique.passed {
+                                reflection_stats.rejections += 1;
+                                reflection_stats.revisions += 1;
+                                tracing::info!(
+                                    issues = critique.issues.len(),
+                                    revision = reflection_stats.revisions,
+                                    "Final answer failed critique, requesting revision"
+                                );
+                                state.add_message(Message::user(reflection::revision_request(
+                                    &critique,
+                                )));
+                                state.iteration += 1;
+                                continue;
+                            }
+                        }
                         emitter.emit_complete().await;
                         state.complete(&answer);
                         break;
@@ -699,6 +829,7 @@ impl AgentExecutor {
                     ToolExecutionOptions {
                         tool_timeout: config.tool_timeout,
                         yolo_mode: config.yolo_mode,
+                        dry_run: config.dry_run,
                         max_concurrency: config.max_tool_concurrency,
                         telemetry_sink: config.telemetry_sink.as_ref(),
                         telemetry_context: config.telemetry_context.as_ref(),
@@ -712,6 +843,7 @@ impl AgentExecutor {
                 )
                 .await;
             tracker.record_tool_calls(results.len());
+            schema_slimmer.observe_tool_calls(&response.tool_calls, state.iteration);
             last_tool_names = response
                 .tool_calls
                 .iter()
@@ -848,6 +980,7 @@ impl AgentExecutor {
 
             state.increment_iteration();
             self.maybe_checkpoint(&config, &state, false).await?;
+            self.record_replay_step(&config, &state).await;
 
             for (_id, content) in streaming_buffer.flush_all() {
                 emitter.emit_text_delta(&content).await;
@@ -857,6 +990,8 @@ impl AgentExecutor {
         for (_id, content) in streaming_buffer.flush_all() {
             emitter.emit_text_delta(&content).await;
         }
+        // Snapshot before pruning so replays see full tool outputs.
+        self.record_replay_step(&config, &state).await;
 
         // Context management: prune old tool results for checkpoint/resume
         let prune_stats = context_manager::prune(&mut state.messages, &context_config);
@@ -868,7 +1003,20 @@ impl AgentExecutor {
             );
         }
 
+        let tool_schema_stats = schema_slimmer.stats().clone();
+        if tool_schema_stats.tokens_saved > 0 {
+            tracing::info!(
+                requests = tool_schema_stats.requests,
+                tokens_saved = tool_schema_stats.tokens_saved,
+                expanded_tools = tool_schema_stats.expanded_tools,
+                "Tool schema slimming applied"
+            );
+        }
+
         // Build result
+        run_span.record("iterations", state.iteration);
+        run_span.record("total_tokens", total_tokens);
+        run_span.record("success", matches!(state.status, AgentStatus::Completed));
         let resource_usage = tracker.usage_snapshot();
         self.maybe_checkpoint(&config, &state, true).await?;
         Ok(AgentResult {
@@ -886,6 +1034,8 @@ impl AgentExecutor {
             total_cost_usd,
             state,
             resource_usage,
+            tool_schema_stats,
+            reflection: reflection_stats,
         })
     }
 }
@@ -0,0 +1,434 @@
+//! Plan-and-execute strategy.
+//!
+//! The model first drafts an explicit plan. Each step then runs as its own
+//! ReAct loop, its outcome is verified, and the remaining work is re-planned
+//! when a step fails. With a plan store configured, steps are persisted as
+//! work items so progress is visible outside the run.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use restflow_traits::store::{WorkItemPatch, WorkItemProvider, WorkItemSpec, WorkItemStatus};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use super::{AgentConfig, AgentExecutor, AgentResult, ExecutionStrategy};
+use crate::agent::context_manager::middle_truncate;
+use crate::agent::state::AgentStatus;
+use crate::agent::stream::StreamEmitter;
+use crate::agent::sub_agent::SubagentProgress;
+use crate::error::Result;
+use crate::llm::{CompletionRequest, LlmClientExt, Message};
+
+/// Upper bound on steps kept from a drafted plan.
+const MAX_PLAN_STEPS: usize = 12;
+/// Characters of each step's answer carried into later prompts.
+const STEP_RESULT_CHARS: usize = 2_000;
+/// Work item folder holding persisted plans.
+const PLAN_FOLDER: &str = "plans";
+
+const PLAN_PROMPT: &str = "You are planning how to accomplish a task. Break the goal into a short \
+     sequence of concrete, verifiable steps (at most 12). Each step must be completable with the \
+     available tools and should state what done looks like. Do not include steps that were \
+     already completed.";
+
+const REVIEW_PROMPT: &str = "You are verifying one step of a plan. Decide whether the step's \
+     result shows that the step was actually completed. Be strict: claims without evidence, \
+     errors, or partial work mean the step is not complete.";
+
+const FINAL_PROMPT: &str = "You are finishing a task that was carried out as a sequence of \
+     steps. Using the step results, write the final answer to the original goal for the user.";
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+struct Plan {
+    steps: Vec<PlanStep>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+struct PlanStep {
+    /// Short imperative title.
+    title: String,
+    /// What to do and how to tell the step is done.
+    instructions: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct StepReview {
+    /// Whether the step was completed.
+    completed: bool,
+    /// Evidence for the verdict, or what is still missing.
+    notes: String,
+}
+
+struct StepOutcome {
+    title: String,
+    answer: String,
+}
+
+struct PendingStep {
+    step: PlanStep,
+    item_id: Option<String>,
+}
+
+impl AgentExecutor {
+    /// Run the goal with [`ExecutionStrategy::PlanAndExecute`].
+    ///
+    /// Falls back to a single ReAct loop when the model produces no plan.
+    pub(super) async fn execute_plan(
+        &self,
+        mut config: AgentConfig,
+        emitter: &mut dyn StreamEmitter,
+        stream_llm: bool,
+        execution_id: String,
+    ) -> Result<AgentResult> {
+        config.execution_strategy = ExecutionStrategy::React;
+        let store = PlanStore::new(config.plan_store.clone(), &execution_id);
+
+        let steps = self.draft_plan(&config, &[], None).await?;
+        if steps.is_empty() {
+            warn!("Planner returned no steps, running goal as a single ReAct loop");
+            return Box::pin(self.execute_with_mode(
+                config,
+                emitter,
+                stream_llm,
+                Some(execution_id),
+                None,
+            ))
+            .await;
+        }
+        info!(steps = steps.len(), "Plan drafted");
+        let mut pending = store.record(steps);
+
+        let mut completed: Vec<StepOutcome> = Vec::new();
+        let mut replans = 0;
+        let mut step_runs = 0;
+        let mut iterations = 0;
+        let mut total_tokens = 0u32;
+        let mut total_cost_usd = 0.0;
+        let mut step_emitter = StepEmitter { inner: emitter };
+        let mut last: Option<AgentResult> = None;
+
+        while let Some(current) = pending.pop_front() {
+            let remaining_iterations = config.max_iterations.saturating_sub(iterations);
+            if remaining_iterations == 0
+                && let Some(result) = last.take()
+            {
+                return Ok(plan_failed(result, "Max iterations reached"));
+            }
+            store.set_status(&current, WorkItemStatus::InProgress, None);
+
+            let mut step_config = config.clone();
+            step_config.goal = step_goal(&config.goal, &completed, &current, &pending);
+            step_config.max_iterations = remaining_iterations;
+            // Steps are verified by the plan review instead.
+            step_config.reflection = None;
+            step_runs += 1;
+            let step_id = format!("{execution_id}:step-{step_runs}");
+            let result = Box::pin(self.execute_with_mode(
+                step_config,
+                &mut step_emitter,
+                stream_llm,
+                Some(step_id),
+                None,
+            ))
+            .await?;
+            iterations += result.iterations;
+            total_tokens = total_tokens.saturating_add(result.total_tokens);
+            total_cost_usd += result.total_cost_usd;
+
+            let review = if result.success {
+                self.review_step(&config, &current.step, result.answer.as_deref())
+                    .await?
+            } else {
+                StepReview {
+                    completed: false,
+                    notes: result
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| "Step did not complete".to_string()),
+                }
+            };
+
+            if review.completed {
+                store.set_status(&current, WorkItemStatus::Done, Some(&review.notes));
+                completed.push(StepOutcome {
+                    title: current.step.title.clone(),
+                    answer: result.answer.clone().unwrap_or_default(),
+                });
+                last = Some(result);
+                continue;
+            }
+
+            warn!(step = %current.step.title, notes = %review.notes, "Plan step failed verification");
+            store.set_status(&current, WorkItemStatus::Archived, Some(&review.notes));
+            if replans >= config.max_replans {
+                return Ok(plan_failed(
+                    result,
+                    &format!(
+                        "Step '{}' failed after {replans} re-plan(s): {}",
+                        current.step.title, review.notes
+                    ),
+                ));
+            }
+            replans += 1;
+            let failure = format!("Step '{}' failed: {}", current.step.title, review.notes);
+            let steps = self.draft_plan(&config, &completed, Some(&failure)).await?;
+            for abandoned in pending.drain(..) {
+                store.set_status(
+                    &abandoned,
+                    WorkItemStatus::Archived,
+                    Some("Replaced by re-plan"),
+                );
+            }
+            info!(replans, steps = steps.len(), "Re-planned remaining work");
+            pending = store.record(steps);
+            last = Some(result);
+        }
+
+        let mut result = last.expect("plan has at least one step");
+        let answer = self.final_answer(&config, &completed).await;
+        emitter.emit_complete().await;
+        result.state.execution_id = execution_id;
+        result.state.complete(&answer);
+        result.success = true;
+        result.error = None;
+        result.answer = Some(answer);
+        result.iterations = iterations;
+        result.total_tokens = total_tokens;
+        result.total_cost_usd = total_cost_usd;
+        Ok(result)
+    }
+
+    async fn draft_plan(
+        &self,
+        config: &AgentConfig,
+        completed: &[StepOutcome],
+        failure: Option<&str>,
+    ) -> Result<Vec<PlanStep>> {
+        let tools = self
+            .tools
+            .schemas()
+            .into_iter()
+            .map(|schema| format!("- {}: {}", schema.name, schema.description))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut prompt = format!("Goal:\n{}\n\nAvailable tools:\n{tools}\n", config.goal);
+        if !completed.is_empty() {
+            prompt.push_str(&format!(
+                "\nCompleted steps:\n{}",
+                format_outcomes(completed)
+            ));
+        }
+        if let Some(failure) = failure {
+            prompt.push_str(&format!(
+                "\n{failure}\n\nPlan the remaining work, working around the failure."
+            ));
+        }
+
+        let request =
+            CompletionRequest::new(vec![Message::system(PLAN_PROMPT), Message::user(prompt)]);
+        let mut plan: Plan = self.llm.complete_structured(request).await?;
+        plan.steps.truncate(MAX_PLAN_STEPS);
+        Ok(plan.steps)
+    }
+
+    async fn review_step(
+        &self,
+        config: &AgentConfig,
+        step: &PlanStep,
+        answer: Option<&str>,
+    ) -> Result<StepReview> {
+        let prompt = format!(
+            "Goal:\n{}\n\nStep: {}\n{}\n\nStep result:\n{}",
+            config.goal,
+            step.title,
+            step.instructions,
+            truncate_result(answer.unwrap_or_default())
+        );
+        let request =
+            CompletionRequest::new(vec![Message::system(REVIEW_PROMPT), Message::user(prompt)]);
+        self.llm.complete_structured(request).await
+    }
+
+    /// Compose the final answer from step results, falling back to the last
+    /// step's answer when the model call fails.
+    async fn final_answer(&self, config: &AgentConfig, completed: &[StepOutcome]) -> String {
+        let fallback = completed
+            .last()
+            .map(|outcome| outcome.answer.clone())
+            .unwrap_or_default();
+        let prompt = format!(
+            "Goal:\n{}\n\nStep results:\n{}",
+            config.goal,
+            format_outcomes(completed)
+        );
+        let request =
+            CompletionRequest::new(vec![Message::system(FINAL_PROMPT), Message::user(prompt)]);
+        match self.llm.complete(request).await {
+            Ok(response) => response
+                .content
+                .filter(|content| !content.trim().is_empty())
+                .unwrap_or(fallback),
+            Err(error) => {
+                warn!(error = %error, "Final answer synthesis failed, using last step answer");
+                fallback
+            }
+        }
+    }
+}
+
+/// Goal given to the ReAct loop running one step.
+fn step_goal(
+    goal: &str,
+    completed: &[StepOutcome],
+    current: &PendingStep,
+    pending: &VecDeque<PendingStep>,
+) -> String {
+    let mut plan = String::new();
+    for outcome in completed {
+        plan.push_str(&format!("- [done] {}\n", outcome.title));
+    }
+    plan.push_str(&format!("- [current] {}\n", current.step.title));
+    for next in pending {
+        plan.push_str(&format!("- {}\n", next.step.title));
+    }
+
+    let mut out = format!("Overall goal:\n{goal}\n\nPlan:\n{plan}");
+    if !completed.is_empty() {
+        out.push_str(&format!(
+            "\nCompleted step results:\n{}",
+            format_outcomes(completed)
+        ));
+    }
+    out.push_str(&format!(
+        "\nCurrent step: {}\n{}\n\nComplete only the current step, then reply with its outcome \
+         and the evidence that it is done.",
+        current.step.title, current.step.instructions
+    ));
+    out
+}
+
+fn format_outcomes(completed: &[StepOutcome]) -> String {
+    completed
+        .iter()
+        .map(|outcome| {
+            format!(
+                "- {}: {}\n",
+                outcome.title,
+                truncate_result(&outcome.answer)
+            )
+        })
+        .collect()
+}
+
+fn truncate_result(answer: &str) -> String {
+    if answer.len() > STEP_RESULT_CHARS {
+        middle_truncate(answer, STEP_RESULT_CHARS)
+    } else {
+        answer.to_string()
+    }
+}
+
+fn plan_failed(mut result: AgentResult, error: &str) -> AgentResult {
+    result.state.status = AgentStatus::Failed {
+        error: error.to_string(),
+    };
+    result.success = false;
+    result.error = Some(error.to_string());
+    result
+}
+
+/// Persists plan steps as work items. Store errors are logged and do not
+/// interrupt the run.
+struct PlanStore {
+    provider: Option<Arc<dyn WorkItemProvider>>,
+    execution_id: String,
+}
+
+impl PlanStore {
+    fn new(provider: Option<Arc<dyn WorkItemProvider>>, execution_id: &str) -> Self {
+        Self {
+            provider,
+            execution_id: execution_id.to_string(),
+        }
+    }
+
+    fn record(&self, steps: Vec<PlanStep>) -> VecDeque<PendingStep> {
+        steps
+            .into_iter()
+            .map(|step| {
+                let item_id = self.provider.as_ref().and_then(|provider| {
+                    provider
+                        .create(WorkItemSpec {
+                            folder: PLAN_FOLDER.to_string(),
+                            title: step.title.clone(),
+                            content: step.instructions.clone(),
+                            priority: None,
+                            tags: vec!["plan".to_string(), self.execution_id.clone()],
+                        })
+                        .map(|item| item.id)
+                        .inspect_err(|error| warn!(error = %error, "Failed to persist plan step"))
+                        .ok()
+                });
+                PendingStep { step, item_id }
+            })
+            .collect()
+    }
+
+    fn set_status(&self, pending: &PendingStep, status: WorkItemStatus, notes: Option<&str>) {
+        let (Some(provider), Some(id)) = (&self.provider, &pending.item_id) else {
+            return;
+        };
+        let patch = WorkItemPatch {
+            status: Some(status),
+            content: notes.map(|notes| format!("{}\n\n{notes}", pending.step.instructions)),
+            ..Default::default()
+        };
+        if let Err(error) = provider.update(id, patch) {
+            warn!(error = %error, item_id = %id, "Failed to update plan step");
+        }
+    }
+}
+
+/// Forwards step output to the caller's emitter but holds completion until
+/// the whole plan has finished.
+struct StepEmitter<'a> {
+    inner: &'a mut dyn StreamEmitter,
+}
+
+#[async_trait]
+impl StreamEmitter for StepEmitter<'_> {
+    async fn emit_text_delta(&mut self, text: &str) {
+        self.inner.emit_text_delta(text).await;
+    }
+
+    async fn emit_thinking_delta(&mut self, text: &str) {
+        self.inner.emit_thinking_delta(text).await;
+    }
+
+    async fn emit_tool_call_start(&mut self, id: &str, name: &str, arguments: &str) {
+        self.inner.emit_tool_call_start(id, name, arguments).await;
+    }
+
+    async fn emit_tool_call_result(&mut self, id: &str, name: &str, result: &str, success: bool) {
+        self.inner
+            .emit_tool_call_result(id, name, result, success)
+            .await;
+    }
+
+    async fn emit_tool_call_output(&mut self, id: &str, name: &str, output: &str) {
+        self.inner.emit_tool_call_output(id, name, output).await;
+    }
+
+    async fn emit_iteration_start(&mut self, iteration: usize) {
+        self.inner.emit_iteration_start(iteration).await;
+    }
+
+    async fn emit_subagent_progress(&mut self, progress: &SubagentProgress) {
+        self.inner.emit_subagent_progress(progress).await;
+    }
+
+    async fn emit_complete(&mut self) {}
+}
@@ -1,3 +1,4 @@
+use super::dry_run::DRY_RUN_PROMPT_SECTION;
 use super::{AgentConfig, AgentExecutor, CheckpointDurability};
 use crate::agent::state::AgentState;
 use crate::error::Result;
@@ -42,6 +43,10 @@ impl AgentExecutor {
             }
         }
 
+        if config.dry_run {
+            sections.push(DRY_RUN_PROMPT_SECTION.to_string());
+        }
+
         // Security policy section (placeholder for future integration)
         // When XPIA Security Policy is implemented, this section will be populated
         // from the security module based on flags.include_security_policy
@@ -78,4 +83,18 @@ impl AgentExecutor {
         }
         Ok(())
     }
+
+    pub(crate) async fn record_replay_step(&self, config: &AgentConfig, state: &AgentState) {
+        let Some(callback) = &config.replay_callback else {
+            return;
+        };
+        if let Err(error) = callback(state).await {
+            tracing::warn!(
+                execution_id = %state.execution_id,
+                iteration = state.iteration,
+                error = %error,
+                "Failed to record replay step"
+            );
+        }
+    }
 }
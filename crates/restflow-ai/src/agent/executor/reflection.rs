@@ -0,0 +1,171 @@
+//! Self-critique pass on final answers.
+//!
+//! Before a ReAct run accepts its final answer, a critic model (usually a
+//! cheaper one) reviews the answer against the goal and the tool evidence
+//! gathered during the run. When the critique fails, its issues are fed back
+//! and the agent gets a bounded number of revision rounds.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tracing::warn;
+
+use super::AgentExecutor;
+use crate::agent::context_manager::middle_truncate;
+use crate::llm::{CompletionRequest, LlmClient, LlmClientExt, Message, Role};
+
+/// Revision rounds granted after a failed critique by default.
+const DEFAULT_MAX_REVISIONS: usize = 1;
+/// Most recent tool results shown to the critic.
+const EVIDENCE_RESULTS: usize = 8;
+/// Characters kept from each tool result shown to the critic.
+const EVIDENCE_RESULT_CHARS: usize = 1_500;
+/// Characters kept from the answer under review.
+const ANSWER_CHARS: usize = 8_000;
+
+const CRITIQUE_PROMPT: &str = "You are reviewing an AI agent's final answer before it is \
+     returned to the user. Check it against the goal and the tool evidence. Fail the answer if \
+     it does not address the goal, claims results the evidence does not support, ignores tool \
+     errors, or leaves required parts unfinished. Do not fail it for style alone. List each \
+     concrete issue the agent must fix.";
+
+/// Post-answer critique settings.
+#[derive(Clone)]
+pub struct ReflectionConfig {
+    /// Model used for the critique; the agent's own model when `None`.
+    pub critic: Option<Arc<dyn LlmClient>>,
+    /// Revision rounds allowed after a failed critique (default: 1).
+    pub max_revisions: usize,
+}
+
+impl Default for ReflectionConfig {
+    fn default() -> Self {
+        Self {
+            critic: None,
+            max_revisions: DEFAULT_MAX_REVISIONS,
+        }
+    }
+}
+
+impl ReflectionConfig {
+    /// Critique with the agent's own model and one revision round.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `critic` for the critique.
+    pub fn with_critic(mut self, critic: Arc<dyn LlmClient>) -> Self {
+        self.critic = Some(critic);
+        self
+    }
+
+    /// Set how many revision rounds a failed critique may trigger.
+    pub fn with_max_revisions(mut self, max_revisions: usize) -> Self {
+        self.max_revisions = max_revisions;
+        self
+    }
+}
+
+/// Critique activity during a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReflectionStats {
+    /// Critiques that returned a verdict.
+    pub critiques: usize,
+    /// Critiques that failed the answer.
+    pub rejections: usize,
+    /// Revision rounds the agent was given.
+    pub revisions: usize,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(super) struct Critique {
+    /// Whether the answer can be returned as-is.
+    pub(super) passed: bool,
+    /// Concrete problems the agent must fix.
+    #[serde(default)]
+    pub(super) issues: Vec<String>,
+}
+
+impl AgentExecutor {
+    /// Critique `answer`, returning `None` when the critic call fails so the
+    /// answer is accepted rather than blocking the run.
+    pub(super) async fn critique_answer(
+        &self,
+        reflection: &ReflectionConfig,
+        goal: &str,
+        messages: &[Message],
+        answer: &str,
+    ) -> Option<Critique> {
+        let prompt = format!(
+            "Goal:\n{}\n\nTool evidence:\n{}\n\nFinal answer:\n{}",
+            goal,
+            format_evidence(messages),
+            middle_truncate(answer, ANSWER_CHARS)
+        );
+        let request = CompletionRequest::new(vec![
+            Message::system(CRITIQUE_PROMPT),
+            Message::user(prompt),
+        ]);
+        let critic = reflection.critic.as_ref().unwrap_or(&self.llm);
+        match critic.complete_structured::<Critique>(request).await {
+            Ok(critique) => Some(critique),
+            Err(error) => {
+                warn!(error = %error, "Answer critique failed, accepting answer");
+                None
+            }
+        }
+    }
+}
+
+/// User message asking the agent to revise its answer.
+pub(super) fn revision_request(critique: &Critique) -> String {
+    let issues = if critique.issues.is_empty() {
+        "- The answer does not fully accomplish the goal.".to_string()
+    } else {
+        critique
+            .issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!(
+        "A review of your final answer found problems:\n{issues}\n\nFix them, using tools if \
+         needed, then give the complete revised final answer."
+    )
+}
+
+/// Most recent tool results, labelled with the tool that produced them.
+fn format_evidence(messages: &[Message]) -> String {
+    let tool_names: HashMap<&str, &str> = messages
+        .iter()
+        .filter_map(|msg| msg.tool_calls.as_ref())
+        .flatten()
+        .map(|call| (call.id.as_str(), call.name.as_str()))
+        .collect();
+    let results: Vec<&Message> = messages
+        .iter()
+        .filter(|msg| msg.role == Role::Tool)
+        .collect();
+    if results.is_empty() {
+        return "(no tools were called)".to_string();
+    }
+    results[results.len().saturating_sub(EVIDENCE_RESULTS)..]
+        .iter()
+        .map(|msg| {
+            let name = msg
+                .tool_call_id
+                .as_deref()
+                .and_then(|id| tool_names.get(id))
+                .copied()
+                .unwrap_or("tool");
+            format!(
+                "[{name}]\n{}",
+                middle_truncate(&msg.content, EVIDENCE_RESULT_CHARS)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -5,6 +5,7 @@ use crate::agent::ExecutionStep;
 use crate::agent::StreamDisplayMode;
 use crate::agent::PromptFlags;
 use crate::agent::context::{ContextDiscoveryConfig, WorkspaceContextCache};
+use crate::agent::sub_agent::{SubagentProgress, SubagentProgressEvent, SubagentTracker};
 use crate::llm::{
     CompletionRequest, CompletionResponse, FinishReason, Role, StreamChunk, StreamResult,
     TokenUsage, ToolCall,
@@ -108,6 +109,7 @@ impl LlmClient for MockLlmClient {
                     completion_tokens: 5,
                     total_tokens: 15,
                     cost_usd: None,
+                    ..Default::default()
                 }),
             })
         } else {
@@ -321,6 +323,64 @@ impl Tool for EchoTool {
     }
 }
 
+struct StreamingOutputTool;
+
+#[async_trait]
+impl Tool for StreamingOutputTool {
+    fn name(&self) -> &str {
+        "stream_output"
+    }
+
+    fn description(&self) -> &str {
+        "Stream partial output before returning"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        serde_json::json!({ "type": "object", "properties": {} })
+    }
+
+    async fn execute(&self, _input: Value) -> ToolResult<ToolOutput> {
+        restflow_traits::emit_tool_output("line 1\n");
+        restflow_traits::emit_tool_output("line 2\n");
+        Ok(ToolOutput::success(serde_json::json!({ "done": true })))
+    }
+}
+
+/// Reports progress for two tracked sub-agents while it runs.
+struct ChildProgressTool {
+    tracker: Arc<SubagentTracker>,
+}
+
+#[async_trait]
+impl Tool for ChildProgressTool {
+    fn name(&self) -> &str {
+        "child_progress"
+    }
+
+    fn description(&self) -> &str {
+        "Report sub-agent progress before returning"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        serde_json::json!({ "type": "object", "properties": {} })
+    }
+
+    async fn execute(&self, _input: Value) -> ToolResult<ToolOutput> {
+        for id in ["own-child", "other-child"] {
+            self.tracker
+                .report_progress(id, SubagentProgressEvent::IterationStarted);
+            self.tracker.report_progress(
+                id,
+                SubagentProgressEvent::ToolStarted {
+                    tool_name: "web_search".to_string(),
+                },
+            );
+        }
+        sleep(Duration::from_millis(20)).await;
+        Ok(ToolOutput::success(serde_json::json!({ "done": true })))
+    }
+}
+
 struct PendingApprovalTool;
 
 #[async_trait]
@@ -433,6 +493,9 @@ struct CapturingEmitter {
     text: Arc<AsyncMutex<Vec<String>>>,
     tool_starts: Arc<AsyncMutex<Vec<ToolStartRecord>>>,
     tool_results: Arc<AsyncMutex<Vec<ToolResultRecord>>>,
+    tool_outputs: Arc<AsyncMutex<Vec<(String, String)>>>,
+    iterations: Arc<AsyncMutex<Vec<usize>>>,
+    subagent_progress: Arc<AsyncMutex<Vec<SubagentProgress>>>,
     completed: Arc<AtomicUsize>,
 }
 
@@ -442,6 +505,9 @@ impl CapturingEmitter {
             text: Arc::new(AsyncMutex::new(Vec::new())),
             tool_starts: Arc::new(AsyncMutex::new(Vec::new())),
             tool_results: Arc::new(AsyncMutex::new(Vec::new())),
+            tool_outputs: Arc::new(AsyncMutex::new(Vec::new())),
+            iterations: Arc::new(AsyncMutex::new(Vec::new())),
+            subagent_progress: Arc::new(AsyncMutex::new(Vec::new())),
             completed: Arc::new(AtomicUsize::new(0)),
         }
     }
@@ -522,6 +588,21 @@ impl StreamEmitter for CapturingEmitter {
         ));
     }
 
+    async fn emit_tool_call_output(&mut self, id: &str, _name: &str, output: &str) {
+        self.tool_outputs
+            .lock()
+            .await
+            .push((id.to_string(), output.to_string()));
+    }
+
+    async fn emit_iteration_start(&mut self, iteration: usize) {
+        self.iterations.lock().await.push(iteration);
+    }
+
+    async fn emit_subagent_progress(&mut self, progress: &SubagentProgress) {
+        self.subagent_progress.lock().await.push(progress.clone());
+    }
+
     async fn emit_complete(&mut self) {
         self.completed.fetch_add(1, Ordering::SeqCst);
     }
@@ -538,6 +619,7 @@ async fn test_executor_simple_completion() {
             completion_tokens: 10,
             total_tokens: 30,
             cost_usd: None,
+            ..Default::default()
         }),
     };
 
@@ -665,6 +747,51 @@ async fn test_checkpoint_durability_per_turn_triggers_callback() {
     assert_eq!(checkpoint_count.load(Ordering::SeqCst), 1);
 }
 
+#[tokio::test]
+async fn test_replay_callback_records_every_turn_and_completion() {
+    let responses = vec![
+        CompletionResponse {
+            content: Some("Tool".to_string()),
+            tool_calls: vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "echo".to_string(),
+                arguments: serde_json::json!({"message":"hello"}),
+            }],
+            finish_reason: FinishReason::ToolCalls,
+            usage: None,
+        },
+        CompletionResponse {
+            content: Some("Done".to_string()),
+            tool_calls: vec![],
+            finish_reason: FinishReason::Stop,
+            usage: None,
+        },
+    ];
+    let mock_llm = Arc::new(MockLlmClient::new(responses));
+    let mut registry = ToolRegistry::new();
+    registry.register(EchoTool);
+    let executor = AgentExecutor::new(mock_llm, Arc::new(registry));
+
+    let snapshots = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let snapshots_ref = snapshots.clone();
+    let config = AgentConfig::new("replay")
+        .with_checkpoint_durability(CheckpointDurability::OnComplete)
+        .with_replay_callback(move |state| {
+            snapshots_ref
+                .lock()
+                .unwrap()
+                .push((state.iteration, state.messages.len()));
+            async { Err(crate::error::AiError::Agent("storage offline".to_string())) }
+        });
+
+    let result = executor.run(config).await.unwrap();
+    assert!(result.success);
+    let snapshots = snapshots.lock().unwrap();
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0].0, 1);
+    assert!(snapshots[1].1 > snapshots[0].1);
+}
+
 #[tokio::test]
 async fn test_executor_uses_working_memory() {
     // Create a response that completes immediately
@@ -1233,6 +1360,7 @@ async fn test_non_stream_run_with_emitter_emits_tool_events() {
                 completion_tokens: 6,
                 total_tokens: 18,
                 cost_usd: Some(0.02),
+                ..Default::default()
             }),
         },
         CompletionResponse {
@@ -1244,6 +1372,7 @@ async fn test_non_stream_run_with_emitter_emits_tool_events() {
                 completion_tokens: 4,
                 total_tokens: 12,
                 cost_usd: Some(0.01),
+                ..Default::default()
             }),
         },
     ];
@@ -1274,6 +1403,116 @@ async fn test_non_stream_run_with_emitter_emits_tool_events() {
     assert!(tool_result[0].3);
 }
 
+#[tokio::test]
+async fn test_tool_output_is_streamed_before_result() {
+    let responses = vec![
+        CompletionResponse {
+            content: None,
+            tool_calls: vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "stream_output".to_string(),
+                arguments: serde_json::json!({}),
+            }],
+            finish_reason: FinishReason::ToolCalls,
+            usage: None,
+        },
+        CompletionResponse {
+            content: Some("done".to_string()),
+            tool_calls: vec![],
+            finish_reason: FinishReason::Stop,
+            usage: None,
+        },
+    ];
+
+    let llm = Arc::new(MockLlmClient::new(responses));
+    let mut tools = ToolRegistry::new();
+    tools.register(StreamingOutputTool);
+    let executor = AgentExecutor::new(llm, Arc::new(tools));
+    let mut emitter = CapturingEmitter::new();
+
+    let result = executor
+        .run_with_emitter(AgentConfig::new("stream tool output"), &mut emitter)
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    let outputs = emitter.tool_outputs.lock().await;
+    assert_eq!(
+        *outputs,
+        vec![
+            ("call_1".to_string(), "line 1\n".to_string()),
+            ("call_1".to_string(), "line 2\n".to_string()),
+        ]
+    );
+    assert_eq!(emitter.tool_results.lock().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_subagent_progress_is_streamed_to_parent_emitter() {
+    let responses = vec![
+        CompletionResponse {
+            content: None,
+            tool_calls: vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "child_progress".to_string(),
+                arguments: serde_json::json!({}),
+            }],
+            finish_reason: FinishReason::ToolCalls,
+            usage: None,
+        },
+        CompletionResponse {
+            content: Some("done".to_string()),
+            tool_calls: vec![],
+            finish_reason: FinishReason::Stop,
+            usage: None,
+        },
+    ];
+
+    let (completion_tx, completion_rx) = tokio::sync::mpsc::channel(4);
+    let tracker = Arc::new(SubagentTracker::new(completion_tx, completion_rx));
+    for (id, parent) in [("own-child", "parent-exec"), ("other-child", "other-exec")] {
+        tracker
+            .try_reserve(
+                4,
+                id.to_string(),
+                "researcher".to_string(),
+                "task".to_string(),
+                Some(parent.to_string()),
+            )
+            .unwrap();
+    }
+    let llm = Arc::new(MockLlmClient::new(responses));
+    let mut tools = ToolRegistry::new();
+    tools.register(ChildProgressTool {
+        tracker: tracker.clone(),
+    });
+    let executor = AgentExecutor::new(llm, Arc::new(tools)).with_subagent_tracker(tracker);
+    let mut emitter = CapturingEmitter::new();
+    let mut state = AgentState::new("parent-exec".to_string(), 8);
+    state.add_message(Message::system("system"));
+    state.add_message(Message::user("delegate"));
+
+    let result = executor
+        .run_from_state_with_emitter(AgentConfig::new("unused-goal"), state, &mut emitter)
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(*emitter.iterations.lock().await, vec![1, 2]);
+    let progress = emitter.subagent_progress.lock().await;
+    assert_eq!(progress.len(), 2);
+    assert!(progress.iter().all(|update| update.id == "own-child"));
+    assert_eq!(progress[0].agent_name, "researcher");
+    assert_eq!(progress[0].event, SubagentProgressEvent::IterationStarted);
+    assert_eq!(progress[1].iteration, 1);
+    assert_eq!(
+        progress[1].event,
+        SubagentProgressEvent::ToolStarted {
+            tool_name: "web_search".to_string(),
+        }
+    );
+}
+
 #[tokio::test]
 async fn test_non_stream_run_from_state_with_emitter_emits_tool_events() {
     let responses = vec![
@@ -1334,6 +1573,7 @@ async fn test_non_stream_run_with_emitter_records_llm_usage() {
             completion_tokens: 5,
             total_tokens: 15,
             cost_usd: Some(0.03),
+            ..Default::default()
         }),
     };
 
@@ -1445,6 +1685,7 @@ async fn test_run_with_emitter_emits_model_switch_for_routing() {
                     moderate_model: None,
                     complex_model: None,
                     escalate_on_failure: true,
+                    failover: None,
                 })
                 .with_model_switcher(switcher)
                 .with_telemetry_sink(Arc::new(telemetry_sink.clone()))
@@ -1467,6 +1708,135 @@ async fn test_run_with_emitter_emits_model_switch_for_routing() {
     );
 }
 
+#[tokio::test]
+async fn test_run_with_emitter_fails_over_on_rate_limit() {
+    struct RateLimitedOnceLlmClient {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LlmClient for RateLimitedOnceLlmClient {
+        fn provider(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(AiError::LlmHttp {
+                    provider: "mock".to_string(),
+                    status: 429,
+                    message: "rate limited".to_string(),
+                    retry_after_secs: Some(0),
+                });
+            }
+            Ok(CompletionResponse {
+                content: Some("done".to_string()),
+                tool_calls: vec![],
+                finish_reason: FinishReason::Stop,
+                usage: None,
+            })
+        }
+
+        fn complete_stream(&self, _request: CompletionRequest) -> StreamResult {
+            Box::pin(stream::empty())
+        }
+    }
+
+    struct FixedSwitcher {
+        current: Mutex<String>,
+    }
+
+    impl restflow_traits::llm::LlmSwitcher for FixedSwitcher {
+        fn current_model(&self) -> String {
+            self.current.lock().unwrap().clone()
+        }
+
+        fn current_provider(&self) -> String {
+            "mock".to_string()
+        }
+
+        fn available_models(&self) -> Vec<String> {
+            vec!["gpt-5".to_string(), "claude-sonnet-4-5".to_string()]
+        }
+
+        fn provider_for_model(&self, _model: &str) -> Option<LlmProvider> {
+            Some(LlmProvider::OpenAI)
+        }
+
+        fn resolve_api_key(&self, _provider: LlmProvider) -> Option<String> {
+            Some("test-key".to_string())
+        }
+
+        fn client_kind_for_model(&self, _model: &str) -> Option<ClientKind> {
+            Some(ClientKind::Http)
+        }
+
+        fn create_and_swap(
+            &self,
+            model: &str,
+            _api_key: Option<&str>,
+        ) -> std::result::Result<restflow_traits::llm::SwapResult, restflow_traits::ToolError>
+        {
+            let previous_model =
+                std::mem::replace(&mut *self.current.lock().unwrap(), model.to_string());
+            Ok(restflow_traits::llm::SwapResult {
+                previous_provider: "openai".to_string(),
+                previous_model,
+                previous_runtime_provider: Some(LlmProvider::OpenAI),
+                new_provider: "openai".to_string(),
+                new_model: model.to_string(),
+                new_runtime_provider: LlmProvider::OpenAI,
+            })
+        }
+    }
+
+    let llm = Arc::new(RateLimitedOnceLlmClient {
+        calls: AtomicUsize::new(0),
+    });
+    let executor = AgentExecutor::new(llm.clone(), Arc::new(ToolRegistry::new()));
+    let switcher = Arc::new(FixedSwitcher {
+        current: Mutex::new("gpt-5".to_string()),
+    });
+    let mut emitter = CapturingEmitter::new();
+    let telemetry_sink = CapturingTelemetrySink::default();
+
+    let result = executor
+        .run_with_emitter(
+            AgentConfig::new("summarize the report")
+                .with_model_routing(crate::agent::ModelRoutingConfig {
+                    enabled: false,
+                    failover: Some(crate::agent::FailoverPolicy::new(vec![
+                        "gpt-5".to_string(),
+                        "claude-sonnet-4-5".to_string(),
+                    ])),
+                    ..Default::default()
+                })
+                .with_model_switcher(switcher.clone())
+                .with_telemetry_sink(Arc::new(telemetry_sink.clone()))
+                .with_telemetry_context(telemetry_context("gpt-5")),
+            &mut emitter,
+        )
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(llm.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(*switcher.current.lock().unwrap(), "claude-sonnet-4-5");
+    let switches = telemetry_sink.model_switches.lock().await;
+    assert_eq!(
+        *switches,
+        vec![(
+            "gpt-5".to_string(),
+            "claude-sonnet-4-5".to_string(),
+            Some("failover".to_string())
+        )]
+    );
+}
+
 #[test]
 fn test_parse_approval_resolution() {
     assert_eq!(
@@ -1832,6 +2202,7 @@ async fn test_parallel_tools_returns_results_in_submission_order() {
             ToolExecutionOptions {
                 tool_timeout: timeout,
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -1894,6 +2265,7 @@ async fn test_parallel_tools_true_concurrency() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(10),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -1947,6 +2319,7 @@ async fn test_parallel_tools_panic_recovery() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(10),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2009,6 +2382,7 @@ async fn test_parallel_tools_timeout_in_spawned_task() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_millis(200),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2061,6 +2435,7 @@ async fn test_spawn_subagent_tool_call_injects_parent_run_id() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2119,6 +2494,7 @@ async fn test_spawn_subagent_tool_call_overrides_explicit_parent_run_id() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2171,6 +2547,7 @@ async fn test_spawn_subagent_tool_call_overrides_explicit_trace_context() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2228,6 +2605,7 @@ async fn test_spawn_subagent_batch_overrides_explicit_parent_and_trace_context()
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2283,6 +2661,7 @@ async fn test_promote_to_background_injects_chat_session_id() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2335,6 +2714,7 @@ async fn test_promote_to_background_overrides_explicit_session_id() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2379,6 +2759,7 @@ async fn test_list_subagents_injects_parent_run_id() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2429,6 +2810,7 @@ async fn test_wait_subagents_overrides_explicit_parent_run_id() {
             ToolExecutionOptions {
                 tool_timeout: Duration::from_secs(5),
                 yolo_mode: false,
+                dry_run: false,
                 max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
                 telemetry_sink: None,
                 telemetry_context: None,
@@ -2490,3 +2872,362 @@ fn test_truncate_tool_output_exact_boundary() {
     let result = truncate_tool_output(&exact, 100, None, "c1", "test");
     assert_eq!(result, exact);
 }
+
+#[tokio::test]
+async fn test_dry_run_simulates_side_effecting_tools_only() {
+    let mut tools = ToolRegistry::new();
+    for name in ["bash", "file", "http_request"] {
+        tools.register(DelayTool {
+            tool_name: name.to_string(),
+            delay_ms: 0,
+        });
+    }
+    let llm = Arc::new(MockLlmClient::new(vec![]));
+    let executor = AgentExecutor::new(llm, Arc::new(tools));
+
+    let call = |id: &str, name: &str, arguments: Value| ToolCall {
+        id: id.to_string(),
+        name: name.to_string(),
+        arguments,
+    };
+    let calls = vec![
+        call(
+            "bash",
+            "bash",
+            serde_json::json!({"command": "rm -rf build"}),
+        ),
+        call(
+            "write",
+            "file",
+            serde_json::json!({"action": "write", "path": "a.txt", "content": "x"}),
+        ),
+        call(
+            "read",
+            "file",
+            serde_json::json!({"action": "read", "path": "a.txt"}),
+        ),
+        call(
+            "post",
+            "http_request",
+            serde_json::json!({"method": "post", "url": "https://example.com"}),
+        ),
+        call(
+            "get",
+            "http_request",
+            serde_json::json!({"method": "GET", "url": "https://example.com"}),
+        ),
+    ];
+
+    let mut emitter = NullEmitter;
+    let results = executor
+        .execute_tools_parallel(
+            &calls,
+            &mut emitter,
+            ToolExecutionOptions {
+                tool_timeout: Duration::from_secs(10),
+                yolo_mode: false,
+                dry_run: true,
+                max_concurrency: DEFAULT_MAX_TOOL_CONCURRENCY,
+                telemetry_sink: None,
+                telemetry_context: None,
+                invocation: ToolInvocationContext::default(),
+            },
+        )
+        .await;
+
+    let outputs: std::collections::HashMap<_, _> = results
+        .into_iter()
+        .map(|(id, result)| (id, result.expect("tool result").result))
+        .collect();
+    assert_eq!(outputs["bash"]["dry_run"], true);
+    assert_eq!(outputs["bash"]["would"], "run shell command `rm -rf build`");
+    assert_eq!(outputs["write"]["would"], "write file a.txt");
+    assert_eq!(
+        outputs["post"]["would"],
+        "send HTTP POST request to https://example.com"
+    );
+    assert_eq!(outputs["read"], serde_json::json!({"tool": "file"}));
+    assert_eq!(outputs["get"], serde_json::json!({"tool": "http_request"}));
+}
+
+#[tokio::test]
+async fn test_dry_run_adds_prompt_section() {
+    let llm = Arc::new(MockLlmClient::new(vec![]));
+    let executor = AgentExecutor::new(llm, Arc::new(ToolRegistry::new()));
+
+    let prompt = executor
+        .build_system_prompt(&AgentConfig::new("goal").with_dry_run(true))
+        .await;
+    assert!(prompt.contains("## Dry Run"));
+    let prompt = executor
+        .build_system_prompt(&AgentConfig::new("goal"))
+        .await;
+    assert!(!prompt.contains("## Dry Run"));
+}
+
+fn structured_reply(arguments: serde_json::Value) -> CompletionResponse {
+    CompletionResponse {
+        content: None,
+        tool_calls: vec![ToolCall {
+            id: "respond-1".to_string(),
+            name: crate::llm::DEFAULT_STRUCTURED_TOOL.to_string(),
+            arguments,
+        }],
+        finish_reason: FinishReason::ToolCalls,
+        usage: None,
+    }
+}
+
+fn text_reply(content: &str) -> CompletionResponse {
+    CompletionResponse {
+        content: Some(content.to_string()),
+        tool_calls: vec![],
+        finish_reason: FinishReason::Stop,
+        usage: None,
+    }
+}
+
+#[derive(Default)]
+struct MemoryWorkItems {
+    items: Mutex<Vec<restflow_traits::store::WorkItemRecord>>,
+}
+
+impl restflow_traits::store::WorkItemProvider for MemoryWorkItems {
+    fn create(
+        &self,
+        spec: restflow_traits::store::WorkItemSpec,
+    ) -> std::result::Result<restflow_traits::store::WorkItemRecord, String> {
+        let mut items = self.items.lock().unwrap();
+        let record = restflow_traits::store::WorkItemRecord {
+            id: format!("item-{}", items.len() + 1),
+            folder: spec.folder,
+            title: spec.title,
+            content: spec.content,
+            priority: spec.priority,
+            status: restflow_traits::store::WorkItemStatus::Open,
+            tags: spec.tags,
+            assignee: None,
+            created_at: 0,
+            updated_at: 0,
+        };
+        items.push(record.clone());
+        Ok(record)
+    }
+
+    fn get(
+        &self,
+        id: &str,
+    ) -> std::result::Result<Option<restflow_traits::store::WorkItemRecord>, String> {
+        Ok(self
+            .items
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|item| item.id == id)
+            .cloned())
+    }
+
+    fn update(
+        &self,
+        id: &str,
+        patch: restflow_traits::store::WorkItemPatch,
+    ) -> std::result::Result<restflow_traits::store::WorkItemRecord, String> {
+        let mut items = self.items.lock().unwrap();
+        let item = items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .ok_or_else(|| format!("no item {id}"))?;
+        if let Some(status) = patch.status {
+            item.status = status;
+        }
+        if let Some(content) = patch.content {
+            item.content = content;
+        }
+        Ok(item.clone())
+    }
+
+    fn delete(&self, _id: &str) -> std::result::Result<bool, String> {
+        Ok(false)
+    }
+
+    fn list(
+        &self,
+        _query: restflow_traits::store::WorkItemQuery,
+    ) -> std::result::Result<Vec<restflow_traits::store::WorkItemRecord>, String> {
+        Ok(self.items.lock().unwrap().clone())
+    }
+
+    fn list_folders(&self) -> std::result::Result<Vec<String>, String> {
+        Ok(vec![])
+    }
+}
+
+#[tokio::test]
+async fn test_plan_and_execute_runs_and_persists_steps() {
+    use restflow_traits::store::WorkItemStatus;
+
+    let llm = Arc::new(MockLlmClient::new(vec![
+        structured_reply(serde_json::json!({"steps": [
+            {"title": "Inspect", "instructions": "Read the failing module"},
+            {"title": "Fix", "instructions": "Patch the bug"},
+        ]})),
+        text_reply("Found the bug in parse()"),
+        structured_reply(serde_json::json!({"completed": true, "notes": "bug located"})),
+        text_reply("Patched parse()"),
+        structured_reply(serde_json::json!({"completed": true, "notes": "patch applied"})),
+        text_reply("The parser bug is fixed."),
+    ]));
+    let store = Arc::new(MemoryWorkItems::default());
+    let executor = AgentExecutor::new(llm.clone(), Arc::new(ToolRegistry::new()));
+    let config = AgentConfig::new("Fix the parser")
+        .with_execution_strategy(ExecutionStrategy::PlanAndExecute)
+        .with_plan_store(store.clone());
+
+    let result = executor.run(config).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.answer.as_deref(), Some("The parser bug is fixed."));
+    assert_eq!(llm.call_count(), 6);
+    let step_goal = &llm.captured_requests()[3];
+    assert!(
+        step_goal
+            .iter()
+            .any(|msg| msg.content.contains("- [done] Inspect")
+                && msg.content.contains("Current step: Fix"))
+    );
+
+    let items = store.items.lock().unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| item.status == WorkItemStatus::Done));
+    assert!(items[0].tags.contains(&"plan".to_string()));
+}
+
+#[tokio::test]
+async fn test_plan_and_execute_replans_after_failed_step() {
+    use restflow_traits::store::WorkItemStatus;
+
+    let llm = Arc::new(MockLlmClient::new(vec![
+        structured_reply(serde_json::json!({"steps": [
+            {"title": "Guess", "instructions": "Guess the answer"},
+        ]})),
+        text_reply("Probably 42"),
+        structured_reply(serde_json::json!({"completed": false, "notes": "no evidence"})),
+        structured_reply(serde_json::json!({"steps": [
+            {"title": "Compute", "instructions": "Compute the answer"},
+        ]})),
+        text_reply("Computed 42"),
+        structured_reply(serde_json::json!({"completed": true, "notes": "computed"})),
+        text_reply("The answer is 42."),
+    ]));
+    let store = Arc::new(MemoryWorkItems::default());
+    let executor = AgentExecutor::new(llm.clone(), Arc::new(ToolRegistry::new()));
+    let config = AgentConfig::new("Find the answer")
+        .with_execution_strategy(ExecutionStrategy::PlanAndExecute)
+        .with_plan_store(store.clone());
+
+    let result = executor.run(config).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.answer.as_deref(), Some("The answer is 42."));
+    let replan_request = &llm.captured_requests()[3];
+    assert!(
+        replan_request
+            .iter()
+            .any(|msg| msg.content.contains("Step 'Guess' failed: no evidence"))
+    );
+
+    let items = store.items.lock().unwrap();
+    assert_eq!(items[0].status, WorkItemStatus::Archived);
+    assert_eq!(items[1].status, WorkItemStatus::Done);
+}
+
+#[tokio::test]
+async fn test_plan_and_execute_fails_after_max_replans() {
+    let llm = Arc::new(MockLlmClient::new(vec![
+        structured_reply(serde_json::json!({"steps": [
+            {"title": "Deploy", "instructions": "Deploy the service"},
+        ]})),
+        text_reply("Deploy failed"),
+        structured_reply(serde_json::json!({"completed": false, "notes": "deploy errored"})),
+    ]));
+    let executor = AgentExecutor::new(llm, Arc::new(ToolRegistry::new()));
+    let config = AgentConfig::new("Ship it")
+        .with_execution_strategy(ExecutionStrategy::PlanAndExecute)
+        .with_max_replans(0);
+
+    let result = executor.run(config).await.unwrap();
+
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("deploy errored"));
+}
+
+#[tokio::test]
+async fn test_reflection_revises_answer_after_failed_critique() {
+    let llm = Arc::new(MockLlmClient::new(vec![
+        text_reply("Done."),
+        text_reply("The report lists all three regions with totals."),
+    ]));
+    let critic = Arc::new(MockLlmClient::new(vec![structured_reply(
+        serde_json::json!({"passed": false, "issues": ["No regional totals given"]}),
+    )]));
+    let executor = AgentExecutor::new(llm.clone(), Arc::new(ToolRegistry::new()));
+    let config = AgentConfig::new("Summarize sales by region")
+        .with_reflection(ReflectionConfig::new().with_critic(critic.clone()));
+
+    let result = executor.run(config).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(
+        result.answer.as_deref(),
+        Some("The report lists all three regions with totals.")
+    );
+    assert_eq!(
+        result.reflection,
+        ReflectionStats {
+            critiques: 1,
+            rejections: 1,
+            revisions: 1,
+        }
+    );
+    assert_eq!(critic.call_count(), 1);
+    let revision_request = &llm.captured_requests()[1];
+    assert!(
+        revision_request
+            .iter()
+            .any(|msg| msg.content.contains("- No regional totals given"))
+    );
+}
+
+#[tokio::test]
+async fn test_reflection_accepts_answer_that_passes_critique() {
+    let llm = Arc::new(MockLlmClient::new(vec![text_reply("42")]));
+    let critic = Arc::new(MockLlmClient::new(vec![structured_reply(
+        serde_json::json!({"passed": true, "issues": []}),
+    )]));
+    let executor = AgentExecutor::new(llm.clone(), Arc::new(ToolRegistry::new()));
+    let config = AgentConfig::new("What is 6 * 7?")
+        .with_reflection(ReflectionConfig::new().with_critic(critic));
+
+    let result = executor.run(config).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.answer.as_deref(), Some("42"));
+    assert_eq!(result.reflection.critiques, 1);
+    assert_eq!(result.reflection.revisions, 0);
+    assert_eq!(llm.call_count(), 1);
+}
+
+#[tokio::test]
+async fn test_reflection_accepts_answer_when_critic_fails() {
+    let llm = Arc::new(MockLlmClient::new(vec![text_reply("42")]));
+    let critic = Arc::new(MockLlmClient::new(vec![text_reply("not json")]));
+    let executor = AgentExecutor::new(llm, Arc::new(ToolRegistry::new()));
+    let config = AgentConfig::new("What is 6 * 7?")
+        .with_reflection(ReflectionConfig::new().with_critic(critic));
+
+    let result = executor.run(config).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.answer.as_deref(), Some("42"));
+    assert_eq!(result.reflection, ReflectionStats::default());
+}
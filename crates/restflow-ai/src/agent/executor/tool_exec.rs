@@ -8,17 +8,21 @@ use restflow_telemetry::{
     ToolCallCompletedPayload, ToolCallStartedPayload,
 };
 use serde_json::Value;
-use tokio::sync::Semaphore;
+use tokio::sync::{Semaphore, broadcast, mpsc};
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 
 use restflow_traits::store::is_task_management_tool_name;
+use restflow_traits::{ToolOutputSink, with_tool_output_sink};
 
+use crate::agent::context_manager::{TOOL_MANUAL_TOOL_NAME, tool_manual_output};
 use crate::agent::stream::StreamEmitter;
+use crate::agent::sub_agent::SubagentProgress;
 use crate::error::{AiError, Result};
 use crate::llm::ToolCall;
 use crate::tools::{ToolErrorCategory, ToolRegistry};
 
+use super::dry_run::simulate_tool_call;
 use super::{AgentExecutor, MAX_TOOL_RETRIES};
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -40,6 +44,7 @@ impl<'a> ToolInvocationContext<'a> {
 pub(crate) struct ToolExecutionOptions<'a> {
     pub tool_timeout: Duration,
     pub yolo_mode: bool,
+    pub dry_run: bool,
     pub max_concurrency: usize,
     pub telemetry_sink: Option<&'a Arc<dyn TelemetrySink>>,
     pub telemetry_context: Option<&'a TelemetryContext>,
@@ -48,7 +53,10 @@ pub(crate) struct ToolExecutionOptions<'a> {
 
 impl AgentExecutor {
     fn is_subagent_spawn_tool(tool_name: &str) -> bool {
-        tool_name == "spawn_subagent" || tool_name == "spawn_subagent_batch"
+        matches!(
+            tool_name,
+            "spawn_subagent" | "spawn_subagent_batch" | "fanout"
+        )
     }
 
     fn inject_spawn_parent_run_id(tool_name: &str, args: &mut Value, parent_run_id: Option<&str>) {
@@ -224,7 +232,14 @@ impl AgentExecutor {
         mut args: Value,
         tool_timeout: Duration,
         yolo_mode: bool,
+        dry_run: bool,
     ) -> Result<crate::tools::ToolOutput> {
+        if name == TOOL_MANUAL_TOOL_NAME && !tools.has(&name) {
+            return Ok(tool_manual_output(&tools, &args));
+        }
+        if dry_run && let Some(simulated) = simulate_tool_call(&name, &args) {
+            return Ok(simulated);
+        }
         if yolo_mode
             && name == "bash"
             && let Some(map) = args.as_object_mut()
@@ -294,6 +309,7 @@ impl AgentExecutor {
         let ToolExecutionOptions {
             tool_timeout,
             yolo_mode,
+            dry_run,
             max_concurrency,
             telemetry_sink,
             telemetry_context,
@@ -335,6 +351,8 @@ impl AgentExecutor {
         // 2. Spawn each tool as an independent Tokio task with semaphore-bounded concurrency
         let semaphore = Arc::new(Semaphore::new(max_concurrency));
         let mut ordered = FuturesOrdered::new();
+        let (output_tx, mut output_rx) = mpsc::unbounded_channel::<ToolOutputChunk>();
+        let mut progress = self.subagent_progress_feed(context.parent_run_id());
 
         for call in tool_calls {
             let tools = Arc::clone(&self.tools);
@@ -352,13 +370,25 @@ impl AgentExecutor {
             Self::inject_subagent_parent_scope(&call.name, &mut args, context.parent_run_id());
             let tool_call_id = call.id.clone();
             let tool_name = call.name.clone();
+            let sink = Self::tool_output_sink(output_tx.clone(), &tool_call_id, &tool_name);
 
             let handle: JoinHandle<Result<crate::tools::ToolOutput>> = tokio::spawn(async move {
                 let _permit = sem
                     .acquire()
                     .await
                     .map_err(|_| AiError::Tool("Tool concurrency semaphore closed".to_string()))?;
-                Self::execute_tool_with_retry(tools, name, args, tool_timeout, yolo_mode).await
+                with_tool_output_sink(
+                    sink,
+                    Self::execute_tool_with_retry(
+                        tools,
+                        name,
+                        args,
+                        tool_timeout,
+                        yolo_mode,
+                        dry_run,
+                    ),
+                )
+                .await
             });
 
             // Capture abort handle for cancellation support
@@ -379,8 +409,11 @@ impl AgentExecutor {
 
         // 3. Drain results in submission order, emitting events as each completes.
         //    Between each result, check for cancellation steer commands.
+        drop(output_tx);
         let mut output = Vec::with_capacity(tool_calls.len());
-        while let Some((id, name, result)) = ordered.next().await {
+        while let Some((id, name, result)) =
+            Self::next_tool_result(&mut ordered, &mut output_rx, &mut progress, emitter).await
+        {
             // Remove from active set now that it has completed
             self.active_tool_calls.remove(&id);
 
@@ -430,4 +463,88 @@ impl AgentExecutor {
 
         output
     }
+
+    /// Sink forwarding output streamed by one tool call to the drain loop.
+    fn tool_output_sink(
+        tx: mpsc::UnboundedSender<ToolOutputChunk>,
+        id: &str,
+        name: &str,
+    ) -> ToolOutputSink {
+        let id = id.to_string();
+        let name = name.to_string();
+        Arc::new(move |output: &str| {
+            let _ = tx.send(ToolOutputChunk {
+                id: id.clone(),
+                name: name.clone(),
+                output: output.to_string(),
+            });
+        })
+    }
+
+    /// Progress of sub-agents spawned by this run, when a tracker is attached.
+    fn subagent_progress_feed(&self, parent_run_id: Option<&str>) -> Option<SubagentProgressFeed> {
+        let tracker = self.subagent_tracker.as_ref()?;
+        Some(SubagentProgressFeed {
+            rx: tracker.subscribe_progress(),
+            parent_run_id: parent_run_id?.to_string(),
+        })
+    }
+
+    /// Wait for the next finished tool call, emitting streamed tool output
+    /// and sub-agent progress while the calls run.
+    async fn next_tool_result<F: Future>(
+        ordered: &mut FuturesOrdered<F>,
+        output_rx: &mut mpsc::UnboundedReceiver<ToolOutputChunk>,
+        progress: &mut Option<SubagentProgressFeed>,
+        emitter: &mut dyn StreamEmitter,
+    ) -> Option<F::Output> {
+        loop {
+            tokio::select! {
+                biased;
+                Some(chunk) = output_rx.recv() => {
+                    emitter
+                        .emit_tool_call_output(&chunk.id, &chunk.name, &chunk.output)
+                        .await;
+                }
+                Some(update) = SubagentProgressFeed::next(progress) => {
+                    emitter.emit_subagent_progress(&update).await;
+                }
+                next = ordered.next() => return next,
+            }
+        }
+    }
+}
+
+/// Live progress of the sub-agents owned by one parent run.
+struct SubagentProgressFeed {
+    rx: broadcast::Receiver<SubagentProgress>,
+    parent_run_id: String,
+}
+
+impl SubagentProgressFeed {
+    /// Next progress for this parent; `None` once the feed is closed.
+    async fn next(feed: &mut Option<Self>) -> Option<SubagentProgress> {
+        let this = feed.as_mut()?;
+        loop {
+            match this.rx.recv().await {
+                Ok(progress)
+                    if progress.parent_run_id.as_deref() == Some(this.parent_run_id.as_str()) =>
+                {
+                    return Some(progress);
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => {
+                    *feed = None;
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Output streamed by a running tool call.
+struct ToolOutputChunk {
+    id: String,
+    name: String,
+    output: String,
 }
@@ -7,6 +7,18 @@
 //! 3. Act - Execute the chosen tool
 //! 4. Observe - Record the result
 //! 5. Repeat until goal is achieved or max iterations
+//!
+//! ## Plan-and-execute
+//!
+//! With `ExecutionStrategy::PlanAndExecute` the model drafts a plan first,
+//! then each step runs as its own ReAct loop with verification and
+//! re-planning on failure.
+//!
+//! ## Reflection
+//!
+//! With `AgentConfig::with_reflection` a critic model reviews the final
+//! answer against the goal and tool evidence before it is accepted; a failed
+//! critique sends the issues back for a bounded revision round.
 
 mod checkpoint;
 mod context;
@@ -33,8 +45,11 @@ pub use context::{
     SkillSummary, WorkspaceContextCache,
 };
 pub use deferred::{DeferredExecutionManager, DeferredStatus, DeferredToolCall};
-pub use executor::{AgentConfig, AgentExecutor, AgentResult, CheckpointDurability};
-pub use model_router::{ModelRoutingConfig, TaskTier, classify_task, select_model};
+pub use executor::{
+    AgentConfig, AgentExecutor, AgentResult, CheckpointDurability, ExecutionStrategy,
+    ReflectionConfig, ReflectionStats,
+};
+pub use model_router::{FailoverPolicy, ModelRoutingConfig, TaskTier, classify_task, select_model};
 pub use prompt_flags::PromptFlags;
 pub use resource::{ResourceError, ResourceLimits, ResourceTracker, ResourceUsage};
 pub use state::{AgentState, AgentStatus};
@@ -47,7 +62,8 @@ pub use stuck::{StuckAction, StuckDetector, StuckDetectorConfig, StuckInfo};
 pub use sub_agent::{
     RunTraceContext, SpawnHandle, SpawnPriority, SubagentCompletion, SubagentConfig,
     SubagentDefLookup, SubagentDefSnapshot, SubagentDefSummary, SubagentDeps,
-    SubagentExecutionBridge, SubagentManagerImpl, SubagentResult, SubagentSpawner, SubagentState,
-    SubagentStatus, SubagentTracker, execute_subagent_plan,
+    SubagentExecutionBridge, SubagentManagerImpl, SubagentProgress, SubagentProgressEvent,
+    SubagentResult, SubagentSpawner, SubagentState, SubagentStatus, SubagentTracker,
+    execute_subagent_plan,
 };
 pub use team::{extract_team_execution_context, inject_team_execution_context, record_pending_team_approval};
@@ -1,6 +1,7 @@
 //! Model routing helpers for choosing a model tier based on task complexity.
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Task complexity tier for model routing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,6 +31,10 @@ pub struct ModelRoutingConfig {
     pub complex_model: Option<String>,
     /// Auto-escalate to complex tier when previous iteration failed.
     pub escalate_on_failure: bool,
+    /// Retry failed LLM requests on fallback models. Applies even when tier
+    /// routing is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverPolicy>,
 }
 
 impl Default for ModelRoutingConfig {
@@ -40,10 +45,54 @@ impl Default for ModelRoutingConfig {
             moderate_model: None,
             complex_model: None,
             escalate_on_failure: true,
+            failover: None,
         }
     }
 }
 
+/// Request-level failover policy.
+///
+/// When an LLM request fails with a rate-limit, overload or outage error, the
+/// same request is retried on the next fallback model after a backoff delay.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FailoverPolicy {
+    /// Fallback models tried in order.
+    pub fallback_models: Vec<String>,
+    /// Delay before the first failover in milliseconds, doubled for each
+    /// further fallback. A provider `retry-after` hint takes precedence.
+    #[serde(default = "default_failover_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+/// Cap on the delay between failover attempts.
+const MAX_FAILOVER_BACKOFF_MS: u64 = 30_000;
+
+fn default_failover_backoff_ms() -> u64 {
+    1_000
+}
+
+impl FailoverPolicy {
+    pub fn new(fallback_models: Vec<String>) -> Self {
+        Self {
+            fallback_models,
+            backoff_ms: default_failover_backoff_ms(),
+        }
+    }
+
+    /// Delay before the `attempt`-th failover (1-based).
+    pub fn delay_for(&self, attempt: u32, retry_after_secs: Option<u64>) -> Duration {
+        if let Some(seconds) = retry_after_secs {
+            return Duration::from_millis((seconds * 1000).min(MAX_FAILOVER_BACKOFF_MS));
+        }
+        let multiplier = 1u64 << attempt.saturating_sub(1).min(16);
+        Duration::from_millis(
+            self.backoff_ms
+                .saturating_mul(multiplier)
+                .min(MAX_FAILOVER_BACKOFF_MS),
+        )
+    }
+}
+
 /// Classify the complexity of a pending agent action.
 pub fn classify_task(
     tool_names: &[&str],
@@ -127,7 +176,8 @@ pub fn select_model(config: &ModelRoutingConfig, tier: TaskTier, default_model:
 
 #[cfg(test)]
 mod tests {
-    use super::{ModelRoutingConfig, TaskTier, classify_task, select_model};
+    use super::{FailoverPolicy, ModelRoutingConfig, TaskTier, classify_task, select_model};
+    use std::time::Duration;
 
     #[test]
     fn classify_routine_task() {
@@ -166,6 +216,7 @@ mod tests {
             moderate_model: None,
             complex_model: Some("gpt-5".to_string()),
             escalate_on_failure: true,
+            failover: None,
         };
         assert_eq!(
             select_model(&config, TaskTier::Routine, "claude-sonnet-4-5"),
@@ -180,4 +231,25 @@ mod tests {
             "gpt-5"
         );
     }
+
+    #[test]
+    fn failover_delay_doubles_and_honours_retry_after() {
+        let policy = FailoverPolicy::new(vec!["gpt-5".to_string()]);
+        assert_eq!(policy.delay_for(1, None), Duration::from_millis(1_000));
+        assert_eq!(policy.delay_for(3, None), Duration::from_millis(4_000));
+        assert_eq!(policy.delay_for(10, None), Duration::from_millis(30_000));
+        assert_eq!(policy.delay_for(1, Some(7)), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn failover_policy_defaults_backoff() {
+        let config: ModelRoutingConfig = serde_json::from_str(
+            r#"{"enabled":false,"escalate_on_failure":true,"failover":{"fallback_models":["gpt-5"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.failover,
+            Some(FailoverPolicy::new(vec!["gpt-5".to_string()]))
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use super::AgentResult;
+use super::sub_agent::SubagentProgress;
 
 /// Stream step emitted during agent execution.
 #[derive(Debug)]
@@ -29,6 +30,15 @@ pub enum ExecutionStep {
         result: String,
         success: bool,
     },
+    ToolCallOutput {
+        id: String,
+        name: String,
+        output: String,
+    },
+    // Sub-agents
+    SubagentProgress {
+        progress: SubagentProgress,
+    },
     // Completion
     Completed {
         result: Box<AgentResult>,
@@ -59,7 +69,8 @@ pub enum ExecutionStep {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::agent::{AgentState, ResourceUsage};
+    use crate::agent::context_manager::ToolSchemaStats;
+    use crate::agent::{AgentState, ReflectionStats, ResourceUsage};
     use std::time::Duration;
 
     fn sample_result() -> AgentResult {
@@ -77,6 +88,8 @@ mod tests {
                 depth: 0,
                 total_cost_usd: 0.0,
             },
+            tool_schema_stats: ToolSchemaStats::default(),
+            reflection: ReflectionStats::default(),
         }
     }
 
@@ -104,6 +117,20 @@ mod tests {
                 result: "{\"ok\":true}".to_string(),
                 success: true,
             },
+            ExecutionStep::ToolCallOutput {
+                id: "call_1".to_string(),
+                name: "echo".to_string(),
+                output: "partial".to_string(),
+            },
+            ExecutionStep::SubagentProgress {
+                progress: SubagentProgress {
+                    id: "task-1".to_string(),
+                    agent_name: "researcher".to_string(),
+                    parent_run_id: Some("exec-1".to_string()),
+                    iteration: 2,
+                    event: crate::agent::SubagentProgressEvent::IterationStarted,
+                },
+            },
             ExecutionStep::Completed {
                 result: Box::new(sample_result()),
             },
@@ -128,6 +155,6 @@ mod tests {
             },
         ];
 
-        assert_eq!(steps.len(), 12);
+        assert_eq!(steps.len(), 14);
     }
 }
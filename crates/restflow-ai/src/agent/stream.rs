@@ -6,6 +6,7 @@ use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 
 use crate::agent::ExecutionStep;
+use crate::agent::sub_agent::SubagentProgress;
 use crate::llm::{ToolCall, ToolCallDelta};
 
 #[async_trait]
@@ -14,6 +15,12 @@ pub trait StreamEmitter: Send + Sync {
     async fn emit_thinking_delta(&mut self, text: &str);
     async fn emit_tool_call_start(&mut self, id: &str, name: &str, arguments: &str);
     async fn emit_tool_call_result(&mut self, id: &str, name: &str, result: &str, success: bool);
+    /// Partial output streamed by a running tool call, e.g. command output.
+    async fn emit_tool_call_output(&mut self, _id: &str, _name: &str, _output: &str) {}
+    /// A new ReAct iteration started (1-based).
+    async fn emit_iteration_start(&mut self, _iteration: usize) {}
+    /// Live progress from a sub-agent spawned by this run.
+    async fn emit_subagent_progress(&mut self, _progress: &SubagentProgress) {}
     async fn emit_complete(&mut self);
 }
 
@@ -88,6 +95,33 @@ impl StreamEmitter for ChannelEmitter {
             .await;
     }
 
+    async fn emit_tool_call_output(&mut self, id: &str, name: &str, output: &str) {
+        let _ = self
+            .tx
+            .send(ExecutionStep::ToolCallOutput {
+                id: id.to_string(),
+                name: name.to_string(),
+                output: output.to_string(),
+            })
+            .await;
+    }
+
+    async fn emit_iteration_start(&mut self, iteration: usize) {
+        let _ = self
+            .tx
+            .send(ExecutionStep::IterationBegin { iteration })
+            .await;
+    }
+
+    async fn emit_subagent_progress(&mut self, progress: &SubagentProgress) {
+        let _ = self
+            .tx
+            .send(ExecutionStep::SubagentProgress {
+                progress: progress.clone(),
+            })
+            .await;
+    }
+
     async fn emit_complete(&mut self) {}
 }
 
@@ -126,6 +160,21 @@ impl StreamEmitter for SharedStreamEmitter {
         inner.emit_tool_call_result(id, name, result, success).await;
     }
 
+    async fn emit_tool_call_output(&mut self, id: &str, name: &str, output: &str) {
+        let mut inner = self.inner.lock().await;
+        inner.emit_tool_call_output(id, name, output).await;
+    }
+
+    async fn emit_iteration_start(&mut self, iteration: usize) {
+        let mut inner = self.inner.lock().await;
+        inner.emit_iteration_start(iteration).await;
+    }
+
+    async fn emit_subagent_progress(&mut self, progress: &SubagentProgress) {
+        let mut inner = self.inner.lock().await;
+        inner.emit_subagent_progress(progress).await;
+    }
+
     async fn emit_complete(&mut self) {
         let mut inner = self.inner.lock().await;
         inner.emit_complete().await;
@@ -12,6 +12,6 @@ pub use tracker::SubagentTracker;
 
 pub use restflow_traits::subagent::{
     SpawnHandle, SpawnPriority, SubagentCompletion, SubagentConfig, SubagentDefLookup,
-    SubagentDefSnapshot, SubagentDefSummary, SubagentResult, SubagentSpawner, SubagentState,
-    SubagentStatus,
+    SubagentDefSnapshot, SubagentDefSummary, SubagentProgress, SubagentProgressEvent,
+    SubagentResult, SubagentSpawner, SubagentState, SubagentStatus,
 };
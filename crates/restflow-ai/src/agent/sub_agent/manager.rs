@@ -141,6 +141,10 @@ impl SubagentManager for SubagentManagerImpl {
         self.tracker.wait_for_parent(task_id, parent_run_id).await
     }
 
+    async fn wait_first(&self, task_ids: &[String]) -> Option<SubagentCompletion> {
+        self.tracker.wait_first(task_ids).await
+    }
+
     fn config(&self) -> &SubagentConfig {
         &self.config
     }
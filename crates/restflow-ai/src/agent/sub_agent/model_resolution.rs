@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::error::{AiError, Result};
 use crate::llm::{LlmClient, LlmClientFactory};
 use restflow_models::{
-    parse_model_reference, parse_provider_selector, resolve_available_model_name,
+    parse_local_model, parse_model_reference, parse_provider_selector, resolve_available_model_name,
 };
 
 pub(crate) fn resolve_llm_client(
@@ -80,6 +80,11 @@ fn resolve_model_name(model: &str, factory: &dyn LlmClientFactory) -> Result<Str
         ));
     }
 
+    // Local models are discovered at runtime rather than listed in the catalog.
+    if parse_local_model(query).is_some() && factory.provider_for_model(query).is_some() {
+        return Ok(query.to_string());
+    }
+
     let available = factory.available_models();
     if available.is_empty() {
         return Err(AiError::Agent(format!(
@@ -187,6 +192,20 @@ mod tests {
         assert_eq!(resolved, "gpt-5");
     }
 
+    #[test]
+    fn resolve_model_name_accepts_local_model_reference() {
+        let factory = crate::llm::DefaultLlmClientFactory::new(
+            std::collections::HashMap::new(),
+            vec![restflow_models::ModelSpec::new(
+                "gpt-5",
+                LlmProvider::OpenAI,
+                "gpt-5",
+            )],
+        );
+        let resolved = resolve_model_name("ollama:llama3.1:8b", &factory).unwrap();
+        assert_eq!(resolved, "ollama:llama3.1:8b");
+    }
+
     #[test]
     fn resolve_model_name_maps_minimax_coding_plan_alias() {
         let factory =
@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use serde_json::json;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tokio::time::{Duration, timeout};
 
 use crate::agent::PromptFlags;
-use crate::agent::executor::{AgentConfig, AgentExecutor, AgentResult};
+use crate::agent::context_manager::ToolSchemaStats;
+use crate::agent::executor::{AgentConfig, AgentExecutor, AgentResult, ReflectionStats};
 use crate::agent::stream::StreamEmitter;
 use crate::agent::team::inject_team_execution_context;
 use crate::agent::{AgentState, ResourceUsage};
@@ -21,7 +23,7 @@ use restflow_telemetry::{
 use restflow_traits::{AgentOrchestrator, ExecutionMode, ExecutionOutcome, ExecutionPlan, Toolset};
 
 use super::model_resolution::resolve_llm_client;
-use super::tracker::SubagentTracker;
+use super::tracker::{SubagentProgressEvent, SubagentTracker};
 
 pub use restflow_traits::SubagentConfig;
 pub use restflow_traits::subagent::{
@@ -560,13 +562,17 @@ pub(crate) fn spawn_subagent(
             };
         }
         let start = std::time::Instant::now();
+        let mut progress = ProgressEmitter {
+            tracker: tracker_clone.clone(),
+            task_id: task_id.clone(),
+        };
         let future = execute_subagent_entry(
             invocation.clone(),
             agent_def,
             task.clone(),
             execution.clone(),
             Some(steer_rx),
-            None,
+            Some(&mut progress),
         );
         let result = timeout(Duration::from_secs(timeout_secs), future).await;
 
@@ -687,6 +693,46 @@ pub(crate) fn spawn_subagent(
     })
 }
 
+/// Reports a spawned sub-agent's iterations and tool calls to the tracker so
+/// its parent can stream them.
+struct ProgressEmitter {
+    tracker: Arc<SubagentTracker>,
+    task_id: String,
+}
+
+#[async_trait]
+impl StreamEmitter for ProgressEmitter {
+    async fn emit_text_delta(&mut self, _text: &str) {}
+
+    async fn emit_thinking_delta(&mut self, _text: &str) {}
+
+    async fn emit_tool_call_start(&mut self, _id: &str, name: &str, _arguments: &str) {
+        self.tracker.report_progress(
+            &self.task_id,
+            SubagentProgressEvent::ToolStarted {
+                tool_name: name.to_string(),
+            },
+        );
+    }
+
+    async fn emit_tool_call_result(&mut self, _id: &str, name: &str, _result: &str, success: bool) {
+        self.tracker.report_progress(
+            &self.task_id,
+            SubagentProgressEvent::ToolCompleted {
+                tool_name: name.to_string(),
+                success,
+            },
+        );
+    }
+
+    async fn emit_iteration_start(&mut self, _iteration: usize) {
+        self.tracker
+            .report_progress(&self.task_id, SubagentProgressEvent::IterationStarted);
+    }
+
+    async fn emit_complete(&mut self) {}
+}
+
 fn resolve_subagent_definition(
     definitions: &Arc<dyn SubagentDefLookup>,
     tool_registry: &Arc<ToolRegistry>,
@@ -903,6 +949,8 @@ fn agent_result_from_outcome(outcome: ExecutionOutcome) -> AgentResult {
             depth: 0,
             total_cost_usd: 0.0,
         },
+        tool_schema_stats: ToolSchemaStats::default(),
+        reflection: ReflectionStats::default(),
     }
 }
 
@@ -953,6 +1001,7 @@ fn build_registry_for_agent(
         "spawn_subagent",
         "wait_subagents",
         "list_subagents",
+        "fanout",
         "cancel_agent",
         "send_input",
     ];
@@ -1657,6 +1706,7 @@ mod tests {
                     completion_tokens: 0,
                     total_tokens: 1,
                     cost_usd: Some(0.0),
+                    ..Default::default()
                 }),
             })
         }
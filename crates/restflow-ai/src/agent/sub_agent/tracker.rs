@@ -1,7 +1,7 @@
 use std::sync::{Arc, RwLock};
 
 use dashmap::DashMap;
-use tokio::sync::{Mutex, mpsc, oneshot};
+use tokio::sync::{Mutex, broadcast, mpsc, oneshot};
 use tokio::task::{AbortHandle, JoinHandle};
 use tokio::time::Duration;
 
@@ -11,9 +11,13 @@ use crate::steer::SteerMessage;
 use restflow_telemetry::TelemetrySink;
 
 pub use restflow_traits::subagent::{
-    SubagentCompletion, SubagentResult, SubagentState, SubagentStatus,
+    SubagentCompletion, SubagentProgress, SubagentProgressEvent, SubagentResult, SubagentState,
+    SubagentStatus,
 };
 
+/// Progress notifications buffered per subscriber before it starts lagging.
+const PROGRESS_CHANNEL_CAPACITY: usize = 256;
+
 /// Sub-agent tracker with concurrent access support.
 pub struct SubagentTracker {
     /// All sub-agent states.
@@ -37,6 +41,12 @@ pub struct SubagentTracker {
     /// Completion notification receiver.
     completion_rx: Mutex<mpsc::Receiver<SubagentCompletion>>,
 
+    /// Live progress notifications for subscribed parents.
+    progress_tx: broadcast::Sender<SubagentProgress>,
+
+    /// Current iteration of each running sub-agent.
+    iterations: DashMap<String, usize>,
+
     /// Lock to prevent TOCTOU race between running_count() check and register().
     spawn_lock: std::sync::Mutex<()>,
 
@@ -120,6 +130,15 @@ impl SubagentTracker {
                 result,
             };
             self.record_parent_completion(&completion);
+            let _ = self.progress_tx.send(SubagentProgress {
+                id: id.to_string(),
+                agent_name: state.agent_name.clone(),
+                parent_run_id: state.parent_run_id.clone(),
+                iteration: self.iterations.remove(id).map(|(_, n)| n).unwrap_or(0),
+                event: SubagentProgressEvent::Finished {
+                    status: completion.status.clone(),
+                },
+            });
             let _ = self.completion_tx.try_send(completion);
             return true;
         }
@@ -140,6 +159,8 @@ impl SubagentTracker {
             steer_senders: DashMap::new(),
             completion_tx,
             completion_rx: Mutex::new(completion_rx),
+            progress_tx: broadcast::channel(PROGRESS_CHANNEL_CAPACITY).0,
+            iterations: DashMap::new(),
             spawn_lock: std::sync::Mutex::new(()),
             telemetry_sink: RwLock::new(None),
         }
@@ -405,6 +426,33 @@ impl SubagentTracker {
         }
     }
 
+    /// Wait for whichever of `ids` finishes first.
+    ///
+    /// Unknown IDs are skipped; returns `None` when none of `ids` is tracked.
+    pub async fn wait_first(&self, ids: &[String]) -> Option<SubagentCompletion> {
+        loop {
+            let mut tracked = false;
+            for id in ids {
+                let Some(state) = self.states.get(id) else {
+                    continue;
+                };
+                tracked = true;
+                if state.result.is_some()
+                    || !matches!(
+                        state.status,
+                        SubagentStatus::Pending | SubagentStatus::Running
+                    )
+                {
+                    return Some(Self::completion_for_state(id, &state));
+                }
+            }
+            if !tracked {
+                return None;
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+    }
+
     /// Wait for all running sub-agents to complete.
     pub async fn wait_all(&self) -> Vec<SubagentCompletion> {
         let ids: Vec<String> = self
@@ -534,6 +582,35 @@ impl SubagentTracker {
         completions
     }
 
+    /// Subscribe to live progress of all tracked sub-agents.
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<SubagentProgress> {
+        self.progress_tx.subscribe()
+    }
+
+    /// Publish progress for a running sub-agent. Ignored once it is terminal.
+    pub fn report_progress(&self, id: &str, event: SubagentProgressEvent) {
+        let Some(state) = self.states.get(id) else {
+            return;
+        };
+        if Self::is_terminal_status(&state.status) {
+            return;
+        }
+        let iteration = {
+            let mut iteration = self.iterations.entry(id.to_string()).or_insert(0);
+            if event == SubagentProgressEvent::IterationStarted {
+                *iteration += 1;
+            }
+            *iteration
+        };
+        let _ = self.progress_tx.send(SubagentProgress {
+            id: id.to_string(),
+            agent_name: state.agent_name.clone(),
+            parent_run_id: state.parent_run_id.clone(),
+            iteration,
+            event,
+        });
+    }
+
     pub fn poll_completions_for_parent(&self, parent_run_id: &str) -> Vec<SubagentCompletion> {
         let Some(parent_run_id) = Self::normalized_parent_run_id(Some(parent_run_id)) else {
             return Vec::new();
@@ -711,6 +788,105 @@ mod tests {
         assert_eq!(final_state.status, SubagentStatus::TimedOut);
     }
 
+    #[tokio::test]
+    async fn wait_first_returns_earliest_finished_task() {
+        let (tx, _rx) = mpsc::channel(4);
+        let (_completion_tx, completion_rx) = mpsc::channel(1);
+        let tracker = Arc::new(SubagentTracker::new(tx, completion_rx));
+        for id in ["slow", "fast"] {
+            tracker
+                .insert_running_state(
+                    id.to_string(),
+                    "agent".to_string(),
+                    "task".to_string(),
+                    None,
+                )
+                .unwrap();
+        }
+
+        let waiter = {
+            let tracker = tracker.clone();
+            tokio::spawn(async move {
+                tracker
+                    .wait_first(&[
+                        "missing".to_string(),
+                        "slow".to_string(),
+                        "fast".to_string(),
+                    ])
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tracker.mark_completed(
+            "fast",
+            SubagentResult {
+                success: true,
+                output: "done".to_string(),
+                summary: None,
+                duration_ms: 10,
+                tokens_used: None,
+                cost_usd: None,
+                error: None,
+            },
+        );
+
+        let completion = waiter.await.unwrap().unwrap();
+        assert_eq!(completion.id, "fast");
+        assert_eq!(completion.status, SubagentStatus::Completed);
+        assert!(tracker.wait_first(&["missing".to_string()]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn report_progress_counts_iterations_until_finished() {
+        let (tx, _rx) = mpsc::channel(4);
+        let (_completion_tx, completion_rx) = mpsc::channel(1);
+        let tracker = Arc::new(SubagentTracker::new(tx, completion_rx));
+        tracker
+            .insert_running_state(
+                "child".to_string(),
+                "researcher".to_string(),
+                "task".to_string(),
+                Some("parent-run".to_string()),
+            )
+            .unwrap();
+        let mut progress = tracker.subscribe_progress();
+
+        tracker.report_progress("child", SubagentProgressEvent::IterationStarted);
+        tracker.report_progress(
+            "child",
+            SubagentProgressEvent::ToolStarted {
+                tool_name: "web_search".to_string(),
+            },
+        );
+        tracker.report_progress("child", SubagentProgressEvent::IterationStarted);
+        tracker.report_progress("missing", SubagentProgressEvent::IterationStarted);
+        tracker.mark_timed_out("child");
+        tracker.report_progress("child", SubagentProgressEvent::IterationStarted);
+
+        let mut events = Vec::new();
+        while let Ok(event) = progress.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].agent_name, "researcher");
+        assert_eq!(events[0].parent_run_id.as_deref(), Some("parent-run"));
+        assert_eq!(events[0].iteration, 1);
+        assert_eq!(
+            events[1].event,
+            SubagentProgressEvent::ToolStarted {
+                tool_name: "web_search".to_string(),
+            }
+        );
+        assert_eq!(events[2].iteration, 2);
+        assert_eq!(events[3].iteration, 2);
+        assert_eq!(
+            events[3].event,
+            SubagentProgressEvent::Finished {
+                status: SubagentStatus::TimedOut,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn mark_timed_out_does_not_overwrite_interrupted() {
         let (tx, _rx) = mpsc::channel(1);
@@ -79,6 +79,12 @@ impl AiError {
         }
     }
 
+    /// Whether the request may succeed on a different provider: rate limits,
+    /// overload and outages, but not invalid requests or missing credentials.
+    pub fn is_failover_eligible(&self) -> bool {
+        self.is_retryable() || matches!(self, Self::LlmHttp { status: 529, .. })
+    }
+
     pub fn retry_after(&self) -> Option<u64> {
         match self {
             Self::LlmHttp {
@@ -148,4 +154,27 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_failover_eligible_errors() {
+        let overloaded = AiError::LlmHttp {
+            provider: "anthropic".to_string(),
+            status: 529,
+            message: "overloaded".to_string(),
+            retry_after_secs: None,
+        };
+        assert!(!overloaded.is_retryable());
+        assert!(overloaded.is_failover_eligible());
+
+        let rate_limited = AiError::Llm("rate limit exceeded".to_string());
+        assert!(rate_limited.is_failover_eligible());
+
+        let bad_request = AiError::LlmHttp {
+            provider: "openai".to_string(),
+            status: 400,
+            message: "invalid".to_string(),
+            retry_after_secs: None,
+        };
+        assert!(!bad_request.is_failover_eligible());
+    }
 }
@@ -16,10 +16,13 @@ pub mod text_utils;
 pub mod tools;
 
 // Re-export commonly used types
-pub use agent::context_manager::{CompactStats, ContextManagerConfig, PruneStats, TokenEstimator};
+pub use agent::context_manager::{
+    CompactStats, ContextManagerConfig, PruneStats, TokenEstimator, ToolSchemaSlimmer,
+    ToolSchemaStats,
+};
 pub use agent::{
     AgentConfig, AgentExecutor, AgentResult, AgentState, AgentStatus, CheckpointDurability,
-    ExecutionStep, ResourceLimits, ResourceUsage, StreamDisplayMode, SubagentDeps, SubagentExecutionBridge,
+    ExecutionStep, ExecutionStrategy, ReflectionConfig, ReflectionStats, ResourceLimits, ResourceUsage, StreamDisplayMode, SubagentDeps, SubagentExecutionBridge,
     SubagentManagerImpl, SubagentSpawner,
 };
 pub use error::{AiError, Result};
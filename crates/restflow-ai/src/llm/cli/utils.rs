@@ -165,6 +165,7 @@ mod tests {
             tool_calls: None,
             tool_call_id: None,
             name: None,
+            pinned: false,
         }
     }
 
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::error::Result;
+use crate::llm::pricing::{calculate_cache_savings, calculate_cost_with_cache};
 use crate::tools::ToolSchema;
 
 /// Chat message role
@@ -32,6 +33,9 @@ pub struct Message {
     /// Tool calls made by the assistant (for assistant messages)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
+    /// Kept verbatim when the context is compacted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
 }
 
 impl Message {
@@ -43,6 +47,7 @@ impl Message {
             tool_call_id: None,
             name: None,
             tool_calls: None,
+            pinned: false,
         }
     }
 
@@ -54,6 +59,7 @@ impl Message {
             tool_call_id: None,
             name: None,
             tool_calls: None,
+            pinned: false,
         }
     }
 
@@ -65,6 +71,7 @@ impl Message {
             tool_call_id: None,
             name: None,
             tool_calls: None,
+            pinned: false,
         }
     }
 
@@ -76,6 +83,7 @@ impl Message {
             tool_call_id: None,
             name: None,
             tool_calls: Some(tool_calls),
+            pinned: false,
         }
     }
 
@@ -87,8 +95,15 @@ impl Message {
             tool_call_id: Some(tool_call_id.into()),
             name: None,
             tool_calls: None,
+            pinned: false,
         }
     }
+
+    /// Mark the message as pinned so compaction never summarizes it away.
+    pub fn pinned(mut self) -> Self {
+        self.pinned = true;
+        self
+    }
 }
 
 /// Tool call request from LLM
@@ -124,6 +139,44 @@ pub struct TokenUsage {
     pub completion_tokens: u32,
     pub total_tokens: u32,
     pub cost_usd: Option<f64>,
+    /// Prompt tokens served from the provider's prompt cache (included in `prompt_tokens`)
+    pub cache_read_tokens: u32,
+    /// Prompt tokens written to the provider's prompt cache (included in `prompt_tokens`)
+    pub cache_write_tokens: u32,
+    /// USD saved by prompt caching, net of cache write surcharges
+    pub cache_savings_usd: Option<f64>,
+}
+
+impl TokenUsage {
+    /// Build usage for `model`, pricing cached prompt tokens at the cache rates.
+    /// `prompt_tokens` includes the cache read and write tokens.
+    pub fn with_cache(
+        model: &str,
+        prompt_tokens: u32,
+        completion_tokens: u32,
+        cache_read_tokens: u32,
+        cache_write_tokens: u32,
+    ) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+            cost_usd: calculate_cost_with_cache(
+                model,
+                prompt_tokens,
+                completion_tokens,
+                cache_read_tokens,
+                cache_write_tokens,
+            ),
+            cache_read_tokens,
+            cache_write_tokens,
+            cache_savings_usd: calculate_cache_savings(
+                model,
+                cache_read_tokens,
+                cache_write_tokens,
+            ),
+        }
+    }
 }
 
 /// A chunk of streamed response
@@ -207,6 +260,9 @@ pub struct CompletionRequest {
     pub tools: Vec<ToolSchema>,
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
+    /// Name of a tool the model must call. HTTP clients map this to the
+    /// provider's native tool choice; CLI clients ignore it.
+    pub forced_tool: Option<String>,
 }
 
 impl CompletionRequest {
@@ -217,6 +273,7 @@ impl CompletionRequest {
             tools: vec![],
             temperature: None,
             max_tokens: None,
+            forced_tool: None,
         }
     }
 
@@ -237,6 +294,12 @@ impl CompletionRequest {
         self.max_tokens = Some(tokens);
         self
     }
+
+    /// Force the model to call the named tool
+    pub fn with_forced_tool(mut self, name: impl Into<String>) -> Self {
+        self.forced_tool = Some(name.into());
+        self
+    }
 }
 
 /// LLM client trait
@@ -257,6 +320,13 @@ pub trait LlmClient: Send + Sync {
     /// The final chunk will contain the finish_reason and usage statistics.
     fn complete_stream(&self, request: CompletionRequest) -> StreamResult;
 
+    /// Count prompt tokens for `request` with the provider's own tokenizer.
+    ///
+    /// Returns `None` when the provider has no counting endpoint.
+    async fn count_tokens(&self, _request: &CompletionRequest) -> Result<Option<u32>> {
+        Ok(None)
+    }
+
     /// Check if this client supports streaming
     fn supports_streaming(&self) -> bool {
         true
@@ -6,8 +6,8 @@ use std::sync::Arc;
 use crate::error::{AiError, Result};
 use crate::llm::retry::RetryingLlmClient;
 use crate::llm::{
-    AnthropicClient, ClaudeCodeClient, CodexClient, GeminiCliClient, LlmClient, OpenAIClient,
-    OpenCodeClient,
+    AnthropicClient, BedrockClient, ClaudeCodeClient, CodexClient, GeminiCliClient, GeminiClient,
+    LlmClient, OllamaClient, OpenAIClient, OpenCodeClient,
 };
 use restflow_models::{ClientKind, LlmProvider, ModelSpec};
 
@@ -36,11 +36,18 @@ impl DefaultLlmClientFactory {
         }
     }
 
-    fn model_spec(&self, model: &str) -> Result<ModelSpec> {
+    /// Look up a catalog model, falling back to local references such as
+    /// `ollama:llama3.1` that are resolved without being registered.
+    fn find_spec(&self, model: &str) -> Option<ModelSpec> {
         let key = normalize_model_name(model);
         self.models
             .get(&key)
             .cloned()
+            .or_else(|| ModelSpec::local(model))
+    }
+
+    fn model_spec(&self, model: &str) -> Result<ModelSpec> {
+        self.find_spec(model)
             .ok_or_else(|| AiError::Llm(format!("Unknown model '{model}'")))
     }
 }
@@ -71,6 +78,22 @@ impl LlmClientFactory for DefaultLlmClientFactory {
                     .ok_or_else(|| AiError::Llm("claude-code API key is required".to_string()))?;
                 Arc::new(ClaudeCodeClient::new(key).with_model(spec.client_model))
             }
+            ClientKind::Http if spec.provider == LlmProvider::Ollama => {
+                let mut c = OllamaClient::new()?.with_model(spec.client_model);
+                if let Some(host) = spec.base_url.as_deref() {
+                    c = c.with_host(host);
+                }
+                Arc::new(c)
+            }
+            ClientKind::Http if spec.provider == LlmProvider::Local => {
+                let base_url = spec.base_url.as_deref().unwrap_or(spec.provider.base_url());
+                // Local servers usually run without authentication.
+                Arc::new(
+                    OpenAIClient::new(api_key.unwrap_or("local"))?
+                        .with_model(spec.client_model)
+                        .with_base_url(base_url),
+                )
+            }
             ClientKind::Http => {
                 let key = api_key.ok_or_else(|| {
                     AiError::Llm(format!("{} API key is required", spec.provider.as_str()))
@@ -84,6 +107,20 @@ impl LlmClientFactory for DefaultLlmClientFactory {
                             Arc::new(AnthropicClient::new(key)?.with_model(spec.client_model))
                         }
                     }
+                    LlmProvider::Google => {
+                        let mut c = GeminiClient::new(key)?.with_model(spec.client_model);
+                        if let Some(base_url) = spec.base_url.as_deref() {
+                            c = c.with_base_url(base_url);
+                        }
+                        Arc::new(c)
+                    }
+                    LlmProvider::Bedrock => {
+                        let mut c = BedrockClient::new(key)?.with_model(spec.client_model);
+                        if let Some(base_url) = spec.base_url.as_deref() {
+                            c = c.with_base_url(base_url);
+                        }
+                        Arc::new(c)
+                    }
                     LlmProvider::MiniMax | LlmProvider::MiniMaxCodingPlan => Arc::new(
                         AnthropicClient::new(key)?
                             .with_model(spec.client_model)
@@ -115,13 +152,11 @@ impl LlmClientFactory for DefaultLlmClientFactory {
     }
 
     fn provider_for_model(&self, model: &str) -> Option<LlmProvider> {
-        let key = normalize_model_name(model);
-        self.models.get(&key).map(|spec| spec.provider)
+        self.find_spec(model).map(|spec| spec.provider)
     }
 
     fn client_kind_for_model(&self, model: &str) -> Option<ClientKind> {
-        let key = normalize_model_name(model);
-        self.models.get(&key).map(|spec| spec.client_kind)
+        self.find_spec(model).map(|spec| spec.client_kind)
     }
 }
 
@@ -185,4 +220,58 @@ mod tests {
         );
         assert_eq!(factory.client_kind_for_model("missing"), None);
     }
+
+    #[test]
+    fn factory_resolves_unregistered_local_models_without_api_key() {
+        let factory = DefaultLlmClientFactory::new(HashMap::new(), Vec::new());
+
+        assert_eq!(
+            factory.provider_for_model("ollama:llama3.1:8b"),
+            Some(LlmProvider::Ollama)
+        );
+        assert_eq!(
+            factory.provider_for_model("local:qwen2.5"),
+            Some(LlmProvider::Local)
+        );
+        assert_eq!(
+            factory.client_kind_for_model("ollama:llama3.1:8b"),
+            Some(ClientKind::Http)
+        );
+
+        let client = factory.create_client("ollama:llama3.1:8b", None).unwrap();
+        assert_eq!(client.provider(), "ollama");
+        assert_eq!(client.model(), "llama3.1:8b");
+        let client = factory.create_client("local:qwen2.5", None).unwrap();
+        assert_eq!(client.model(), "qwen2.5");
+    }
+
+    #[test]
+    fn factory_uses_native_gemini_and_bedrock_clients() {
+        let factory = DefaultLlmClientFactory::new(
+            HashMap::from([
+                (LlmProvider::Google, "AIza-test".to_string()),
+                (LlmProvider::Bedrock, "bedrock-test".to_string()),
+            ]),
+            vec![
+                ModelSpec::new("gemini-2-5-pro", LlmProvider::Google, "gemini-2.5-pro"),
+                ModelSpec::new(
+                    "bedrock-nova-pro",
+                    LlmProvider::Bedrock,
+                    "us.amazon.nova-pro-v1:0",
+                ),
+            ],
+        );
+
+        let client = factory
+            .create_client("gemini-2-5-pro", Some("AIza-test"))
+            .unwrap();
+        assert_eq!(client.provider(), "google");
+        assert_eq!(client.model(), "gemini-2.5-pro");
+
+        let client = factory
+            .create_client("bedrock-nova-pro", Some("bedrock-test"))
+            .unwrap();
+        assert_eq!(client.provider(), "bedrock");
+        assert_eq!(client.model(), "us.amazon.nova-pro-v1:0");
+    }
 }
@@ -12,7 +12,6 @@ use crate::llm::client::{
     CompletionRequest, CompletionResponse, FinishReason, LlmClient, Role, StreamChunk,
     StreamResult, TokenUsage, ToolCall, ToolCallDelta,
 };
-use crate::llm::pricing::calculate_cost;
 use crate::llm::retry::response_to_error;
 use restflow_traits::http_client::build_http_client;
 
@@ -110,10 +109,30 @@ struct AnthropicRequest {
     model: String,
     max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    system: Option<Vec<AnthropicSystemBlock>>,
     messages: Vec<AnthropicMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<AnthropicTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<Value>,
+}
+
+/// Prompt caching breakpoint. Everything up to and including the marked block
+/// is cached for five minutes and billed at the cache read rate on reuse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct CacheControl {
+    r#type: &'static str,
+}
+
+const EPHEMERAL_CACHE: CacheControl = CacheControl {
+    r#type: "ephemeral",
+};
+
+#[derive(Serialize)]
+struct AnthropicSystemBlock {
+    r#type: &'static str,
+    text: String,
+    cache_control: CacheControl,
 }
 
 #[derive(Serialize)]
@@ -145,6 +164,8 @@ struct AnthropicContentBlock {
     name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     input: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
 }
 
 #[derive(Serialize)]
@@ -152,6 +173,23 @@ struct AnthropicTool {
     name: String,
     description: String,
     input_schema: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
+}
+
+#[derive(Serialize)]
+struct AnthropicCountTokensRequest {
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<Vec<AnthropicSystemBlock>>,
+    messages: Vec<AnthropicMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<AnthropicTool>>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicCountTokensResponse {
+    input_tokens: u32,
 }
 
 #[derive(Deserialize)]
@@ -178,6 +216,10 @@ struct AnthropicResponseContent {
 struct AnthropicUsage {
     input_tokens: u32,
     output_tokens: u32,
+    #[serde(default)]
+    cache_creation_input_tokens: u32,
+    #[serde(default)]
+    cache_read_input_tokens: u32,
 }
 
 // Streaming response types
@@ -225,6 +267,28 @@ struct MessageStartPayload {
 #[derive(Debug, Deserialize)]
 struct InputUsage {
     input_tokens: u32,
+    #[serde(default)]
+    cache_creation_input_tokens: u32,
+    #[serde(default)]
+    cache_read_input_tokens: u32,
+}
+
+/// Anthropic reports uncached input tokens only; the cached ones are added so
+/// `prompt_tokens` covers the whole prompt like the other providers.
+fn token_usage(
+    model: &str,
+    input_tokens: u32,
+    output_tokens: u32,
+    cache_read_tokens: u32,
+    cache_write_tokens: u32,
+) -> TokenUsage {
+    TokenUsage::with_cache(
+        model,
+        input_tokens + cache_read_tokens + cache_write_tokens,
+        output_tokens,
+        cache_read_tokens,
+        cache_write_tokens,
+    )
 }
 
 #[derive(Debug, Deserialize)]
@@ -260,10 +324,14 @@ struct ErrorPayload {
 }
 
 /// Convert a CompletionRequest into Anthropic API request parts.
+///
+/// Cache breakpoints are placed on the last tool, the system prompt and the
+/// last message, so the stable prefix (tool schemas, system prompt with skill
+/// content, earlier turns) is read from the prompt cache on the next iteration.
 fn prepare_request_parts(
     request: &CompletionRequest,
 ) -> (
-    Option<String>,
+    Option<Vec<AnthropicSystemBlock>>,
     Vec<AnthropicMessage>,
     Option<Vec<AnthropicTool>>,
 ) {
@@ -272,10 +340,16 @@ fn prepare_request_parts(
         .messages
         .iter()
         .find(|m| m.role == Role::System)
-        .map(|m| m.content.clone());
+        .map(|m| {
+            vec![AnthropicSystemBlock {
+                r#type: "text",
+                text: m.content.clone(),
+                cache_control: EPHEMERAL_CACHE,
+            }]
+        });
 
     // Convert messages (excluding system)
-    let messages: Vec<AnthropicMessage> = request
+    let mut messages: Vec<AnthropicMessage> = request
         .messages
         .iter()
         .filter(|m| m.role != Role::System)
@@ -296,6 +370,7 @@ fn prepare_request_parts(
                     id: None,
                     name: None,
                     input: None,
+                    cache_control: None,
                 }])
             } else if let Some(tool_calls) = &m.tool_calls {
                 let mut blocks = Vec::new();
@@ -308,6 +383,7 @@ fn prepare_request_parts(
                         id: None,
                         name: None,
                         input: None,
+                        cache_control: None,
                     });
                 }
                 for tc in tool_calls {
@@ -319,6 +395,7 @@ fn prepare_request_parts(
                         id: Some(tc.id.clone()),
                         name: Some(tc.name.clone()),
                         input: Some(tc.arguments.clone()),
+                        cache_control: None,
                     });
                 }
                 AnthropicContent::Blocks(blocks)
@@ -330,7 +407,7 @@ fn prepare_request_parts(
         })
         .collect();
 
-    let tools: Option<Vec<AnthropicTool>> = if request.tools.is_empty() {
+    let mut tools: Option<Vec<AnthropicTool>> = if request.tools.is_empty() {
         None
     } else {
         Some(
@@ -341,14 +418,55 @@ fn prepare_request_parts(
                     name: t.name.clone(),
                     description: t.description.clone(),
                     input_schema: t.parameters.clone(),
+                    cache_control: None,
                 })
                 .collect(),
         )
     };
 
+    if let Some(last) = tools.as_mut().and_then(|tools| tools.last_mut()) {
+        last.cache_control = Some(EPHEMERAL_CACHE);
+    }
+    if let Some(last) = messages.last_mut() {
+        mark_cache_breakpoint(last);
+    }
+
     (system, messages, tools)
 }
 
+/// Mark the last content block of a message as a cache breakpoint.
+/// Empty text blocks cannot carry `cache_control`, so they are left unmarked.
+fn mark_cache_breakpoint(message: &mut AnthropicMessage) {
+    if let AnthropicContent::Text(text) = &message.content {
+        if text.is_empty() {
+            return;
+        }
+        message.content = AnthropicContent::Blocks(vec![AnthropicContentBlock {
+            r#type: "text".to_string(),
+            text: Some(text.clone()),
+            tool_use_id: None,
+            content: None,
+            id: None,
+            name: None,
+            input: None,
+            cache_control: None,
+        }]);
+    }
+    if let AnthropicContent::Blocks(blocks) = &mut message.content
+        && let Some(block) = blocks.last_mut()
+    {
+        block.cache_control = Some(EPHEMERAL_CACHE);
+    }
+}
+
+/// Native `tool_choice` for a forced tool call.
+fn tool_choice(request: &CompletionRequest) -> Option<Value> {
+    request
+        .forced_tool
+        .as_ref()
+        .map(|name| serde_json::json!({ "type": "tool", "name": name }))
+}
+
 #[async_trait]
 impl LlmClient for AnthropicClient {
     fn provider(&self) -> &str {
@@ -368,6 +486,7 @@ impl LlmClient for AnthropicClient {
             system,
             messages,
             tools,
+            tool_choice: tool_choice(&request),
         };
 
         let response = self
@@ -412,25 +531,54 @@ impl LlmClient for AnthropicClient {
             _ => FinishReason::Stop,
         };
 
-        let cost_usd = calculate_cost(
-            &self.model,
-            data.usage.input_tokens,
-            data.usage.output_tokens,
-        );
-
         Ok(CompletionResponse {
             content,
             tool_calls,
             finish_reason,
-            usage: Some(TokenUsage {
-                prompt_tokens: data.usage.input_tokens,
-                completion_tokens: data.usage.output_tokens,
-                total_tokens: data.usage.input_tokens + data.usage.output_tokens,
-                cost_usd,
-            }),
+            usage: Some(token_usage(
+                &self.model,
+                data.usage.input_tokens,
+                data.usage.output_tokens,
+                data.usage.cache_read_input_tokens,
+                data.usage.cache_creation_input_tokens,
+            )),
         })
     }
 
+    /// Exact prompt size via `/v1/messages/count_tokens`. Unavailable with
+    /// OAuth tokens and on compatible APIs that lack the endpoint.
+    async fn count_tokens(&self, request: &CompletionRequest) -> Result<Option<u32>> {
+        if self.auth_type != AnthropicAuthType::ApiKey {
+            return Ok(None);
+        }
+        let (system, messages, tools) = prepare_request_parts(request);
+        let body = AnthropicCountTokensRequest {
+            model: self.model.clone(),
+            system,
+            messages,
+            tools,
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/v1/messages/count_tokens", self.api_base_url()))
+            .headers(self.build_auth_headers())
+            .json(&body)
+            .send()
+            .await
+            .map_err(AiError::Http)?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(response_to_error(response, "Anthropic").await);
+        }
+
+        let data: AnthropicCountTokensResponse = response.json().await?;
+        Ok(Some(data.input_tokens))
+    }
+
     fn complete_stream(&self, request: CompletionRequest) -> StreamResult {
         let client = self.client.clone();
         let api_key = self.api_key.clone();
@@ -450,7 +598,7 @@ impl LlmClient for AnthropicClient {
             let (system, messages, tools) = prepare_request_parts(&request);
 
             // Build streaming request body
-            let body = serde_json::json!({
+            let mut body = serde_json::json!({
                 "model": model,
                 "max_tokens": request.max_tokens.unwrap_or(4096),
                 "system": system,
@@ -458,6 +606,9 @@ impl LlmClient for AnthropicClient {
                 "tools": tools,
                 "stream": true
             });
+            if let Some(choice) = tool_choice(&request) {
+                body["tool_choice"] = choice;
+            }
 
             let response = match client
                 .post(format!("{}/v1/messages", base_url))
@@ -482,6 +633,8 @@ impl LlmClient for AnthropicClient {
             let mut buffer = String::new();
             let mut input_tokens = 0u32;
             let mut output_tokens = 0u32;
+            let mut cache_read_tokens = 0u32;
+            let mut cache_write_tokens = 0u32;
             let mut _current_tool_index: Option<usize> = None;
             let mut current_tool_id: Option<String> = None;
             let mut current_tool_name: Option<String> = None;
@@ -518,6 +671,8 @@ impl LlmClient for AnthropicClient {
                                 AnthropicStreamEvent::MessageStart { message } => {
                                     if let Some(usage) = message.usage {
                                         input_tokens = usage.input_tokens;
+                                        cache_read_tokens = usage.cache_read_input_tokens;
+                                        cache_write_tokens = usage.cache_creation_input_tokens;
                                     }
                                 }
                                 AnthropicStreamEvent::ContentBlockStart { index, content_block } => {
@@ -591,15 +746,15 @@ impl LlmClient for AnthropicClient {
                                             "max_tokens" => FinishReason::MaxTokens,
                                             _ => FinishReason::Stop,
                                         };
-                                        let cost_usd = calculate_cost(&model, input_tokens, output_tokens);
                                         yield Ok(StreamChunk::final_chunk(
                                             finish_reason,
-                                            Some(TokenUsage {
-                                                prompt_tokens: input_tokens,
-                                                completion_tokens: output_tokens,
-                                                total_tokens: input_tokens + output_tokens,
-                                                cost_usd,
-                                            }),
+                                            Some(token_usage(
+                                                &model,
+                                                input_tokens,
+                                                output_tokens,
+                                                cache_read_tokens,
+                                                cache_write_tokens,
+                                            )),
                                         ));
                                     }
                                 }
@@ -642,6 +797,8 @@ impl LlmClient for AnthropicClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::client::Message;
+    use crate::tools::ToolSchema;
 
     #[test]
     fn test_auth_type_detection() {
@@ -655,10 +812,91 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_count_tokens_uses_counting_endpoint() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages/count_tokens"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "input_tokens": 42 })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = AnthropicClient::new("sk-ant-api03-test")
+            .unwrap()
+            .with_base_url(server.uri());
+        let request = CompletionRequest::new(vec![Message::user("Hello")]);
+        assert_eq!(client.count_tokens(&request).await.unwrap(), Some(42));
+
+        let oauth = AnthropicClient::new("sk-ant-oat01-test")
+            .unwrap()
+            .with_base_url(server.uri());
+        assert_eq!(oauth.count_tokens(&request).await.unwrap(), None);
+    }
+
     #[test]
     fn test_oauth_headers() {
         let headers = build_auth_headers("sk-ant-oat01-test", AnthropicAuthType::OAuth);
         assert!(headers.contains_key(AUTHORIZATION));
         assert!(!headers.contains_key("x-api-key"));
     }
+
+    #[test]
+    fn test_prepare_request_parts_marks_cache_breakpoints() {
+        let request = CompletionRequest::new(vec![
+            Message::system("You are helpful"),
+            Message::user("Hello"),
+        ])
+        .with_tools(vec![
+            ToolSchema {
+                name: "read".to_string(),
+                description: "Read a file".to_string(),
+                parameters: serde_json::json!({"type": "object"}),
+            },
+            ToolSchema {
+                name: "write".to_string(),
+                description: "Write a file".to_string(),
+                parameters: serde_json::json!({"type": "object"}),
+            },
+        ]);
+
+        let (system, messages, tools) = prepare_request_parts(&request);
+        let body = serde_json::json!({
+            "system": system,
+            "messages": messages,
+            "tools": tools,
+        });
+
+        assert_eq!(body["system"][0]["text"], "You are helpful");
+        assert_eq!(body["system"][0]["cache_control"]["type"], "ephemeral");
+        assert!(body["tools"][0].get("cache_control").is_none());
+        assert_eq!(body["tools"][1]["cache_control"]["type"], "ephemeral");
+        assert_eq!(body["messages"][0]["content"][0]["text"], "Hello");
+        assert_eq!(
+            body["messages"][0]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+    }
+
+    #[test]
+    fn test_usage_includes_cached_prompt_tokens() {
+        let json = r#"{"input_tokens":50,"output_tokens":20,"cache_creation_input_tokens":1000,"cache_read_input_tokens":4000}"#;
+        let usage: AnthropicUsage = serde_json::from_str(json).unwrap();
+        let usage = token_usage(
+            "claude-sonnet-4-20250514",
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_input_tokens,
+            usage.cache_creation_input_tokens,
+        );
+        assert_eq!(usage.prompt_tokens, 5050);
+        assert_eq!(usage.total_tokens, 5070);
+        assert_eq!(usage.cache_read_tokens, 4000);
+        assert_eq!(usage.cache_write_tokens, 1000);
+        assert!(usage.cache_savings_usd.unwrap() > 0.0);
+    }
 }
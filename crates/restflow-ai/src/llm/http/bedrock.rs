@@ -0,0 +1,610 @@
+//! AWS Bedrock LLM provider (Converse API)
+//!
+//! Authenticates with a Bedrock API key sent as a bearer token
+//! (`AWS_BEARER_TOKEN_BEDROCK`). Streaming uses `converse-stream`, whose body is
+//! framed with the AWS event-stream encoding rather than SSE.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::{AiError, Result};
+use crate::llm::client::{
+    CompletionRequest, CompletionResponse, FinishReason, LlmClient, Role, StreamChunk,
+    StreamResult, TokenUsage, ToolCall, ToolCallDelta,
+};
+use crate::llm::pricing::calculate_cost;
+use crate::llm::retry::response_to_error;
+use restflow_traits::http_client::build_http_client;
+
+/// Region used when neither `AWS_REGION` nor `AWS_DEFAULT_REGION` is set.
+pub const DEFAULT_BEDROCK_REGION: &str = "us-east-1";
+
+/// Region from `AWS_REGION` / `AWS_DEFAULT_REGION`, defaulting to `us-east-1`.
+pub fn bedrock_region() -> String {
+    ["AWS_REGION", "AWS_DEFAULT_REGION"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_BEDROCK_REGION.to_string())
+}
+
+/// Bedrock runtime client
+pub struct BedrockClient {
+    client: Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl BedrockClient {
+    /// Create a new Bedrock client for the region in the environment
+    pub fn new(api_key: impl Into<String>) -> std::result::Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: build_http_client()?,
+            api_key: api_key.into(),
+            model: "us.anthropic.claude-sonnet-4-5-20250929-v1:0".to_string(),
+            base_url: runtime_url(&bedrock_region()),
+        })
+    }
+
+    /// Set the model or inference profile ID
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Use the runtime endpoint of another region
+    pub fn with_region(mut self, region: &str) -> Self {
+        self.base_url = runtime_url(region);
+        self
+    }
+
+    /// Set a custom endpoint (e.g. a VPC endpoint)
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn endpoint(&self, operation: &str) -> String {
+        // Model IDs contain ':' which must be escaped in the path.
+        format!(
+            "{}/model/{}/{}",
+            self.base_url,
+            self.model.replace(':', "%3A"),
+            operation
+        )
+    }
+}
+
+fn runtime_url(region: &str) -> String {
+    format!("https://bedrock-runtime.{}.amazonaws.com", region.trim())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseRequest {
+    messages: Vec<BedrockMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    system: Vec<Value>,
+    inference_config: InferenceConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_config: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BedrockMessage {
+    role: String,
+    content: Vec<Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InferenceConfig {
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseResponse {
+    output: ConverseOutput,
+    #[serde(default)]
+    stop_reason: Option<String>,
+    #[serde(default)]
+    usage: Option<BedrockUsage>,
+}
+
+#[derive(Deserialize)]
+struct ConverseOutput {
+    message: BedrockMessage,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BedrockUsage {
+    #[serde(default)]
+    input_tokens: u32,
+    #[serde(default)]
+    output_tokens: u32,
+}
+
+impl BedrockUsage {
+    fn to_token_usage(&self, model: &str) -> TokenUsage {
+        TokenUsage {
+            prompt_tokens: self.input_tokens,
+            completion_tokens: self.output_tokens,
+            total_tokens: self.input_tokens + self.output_tokens,
+            cost_usd: calculate_cost(model, self.input_tokens, self.output_tokens),
+            ..Default::default()
+        }
+    }
+}
+
+/// Convert a CompletionRequest into a Converse request body.
+fn build_request(request: &CompletionRequest) -> ConverseRequest {
+    let system = request
+        .messages
+        .iter()
+        .filter(|m| m.role == Role::System && !m.content.is_empty())
+        .map(|m| json!({ "text": m.content }))
+        .collect();
+
+    let mut messages: Vec<BedrockMessage> = Vec::new();
+    for message in request.messages.iter().filter(|m| m.role != Role::System) {
+        let (role, content) = match message.role {
+            Role::Assistant => {
+                let mut blocks = Vec::new();
+                if !message.content.is_empty() {
+                    blocks.push(json!({ "text": message.content }));
+                }
+                for call in message.tool_calls.iter().flatten() {
+                    blocks.push(json!({
+                        "toolUse": {
+                            "toolUseId": call.id,
+                            "name": call.name,
+                            "input": call.arguments,
+                        }
+                    }));
+                }
+                ("assistant", blocks)
+            }
+            Role::Tool => (
+                "user",
+                vec![json!({
+                    "toolResult": {
+                        "toolUseId": message.tool_call_id.clone().unwrap_or_default(),
+                        "content": [{ "text": message.content }],
+                    }
+                })],
+            ),
+            Role::User | Role::System => ("user", vec![json!({ "text": message.content })]),
+        };
+
+        if content.is_empty() {
+            continue;
+        }
+        // Converse requires alternating roles, so merge consecutive turns.
+        match messages.last_mut() {
+            Some(last) if last.role == role => last.content.extend(content),
+            _ => messages.push(BedrockMessage {
+                role: role.to_string(),
+                content,
+            }),
+        }
+    }
+
+    let tool_config = (!request.tools.is_empty()).then(|| {
+        let tools: Vec<Value> = request
+            .tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "toolSpec": {
+                        "name": tool.name,
+                        "description": tool.description,
+                        "inputSchema": { "json": tool.parameters },
+                    }
+                })
+            })
+            .collect();
+        match &request.forced_tool {
+            Some(name) => json!({ "tools": tools, "toolChoice": { "tool": { "name": name } } }),
+            None => json!({ "tools": tools }),
+        }
+    });
+
+    ConverseRequest {
+        messages,
+        system,
+        inference_config: InferenceConfig {
+            max_tokens: request.max_tokens.unwrap_or(4096),
+            temperature: request.temperature,
+        },
+        tool_config,
+    }
+}
+
+fn map_stop_reason(reason: Option<&str>) -> FinishReason {
+    match reason {
+        Some("tool_use") => FinishReason::ToolCalls,
+        Some("max_tokens") => FinishReason::MaxTokens,
+        Some("guardrail_intervened" | "content_filtered") => FinishReason::Error,
+        _ => FinishReason::Stop,
+    }
+}
+
+/// One decoded frame of an AWS event stream.
+#[derive(Debug, PartialEq)]
+struct EventFrame {
+    event_type: Option<String>,
+    message_type: Option<String>,
+    exception_type: Option<String>,
+    payload: Vec<u8>,
+}
+
+/// Decode the next complete frame from `buffer`, draining its bytes.
+///
+/// Returns `Ok(None)` when more bytes are needed. CRCs are not verified; the
+/// transport is already TLS-protected.
+fn decode_event_frame(buffer: &mut Vec<u8>) -> Result<Option<EventFrame>> {
+    const PRELUDE_LEN: usize = 12;
+    const TRAILER_LEN: usize = 4;
+
+    if buffer.len() < PRELUDE_LEN {
+        return Ok(None);
+    }
+    let total_len = u32::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as usize;
+    let headers_len = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]) as usize;
+    if total_len < PRELUDE_LEN + headers_len + TRAILER_LEN {
+        return Err(AiError::Llm(
+            "Malformed Bedrock event stream frame".to_string(),
+        ));
+    }
+    if buffer.len() < total_len {
+        return Ok(None);
+    }
+
+    let frame: Vec<u8> = buffer.drain(..total_len).collect();
+    let headers = &frame[PRELUDE_LEN..PRELUDE_LEN + headers_len];
+    let payload = frame[PRELUDE_LEN + headers_len..total_len - TRAILER_LEN].to_vec();
+
+    let mut decoded = EventFrame {
+        event_type: None,
+        message_type: None,
+        exception_type: None,
+        payload,
+    };
+    let malformed = || AiError::Llm("Malformed Bedrock event stream header".to_string());
+    let mut pos = 0;
+    while pos < headers.len() {
+        let name_len = *headers.get(pos).ok_or_else(malformed)? as usize;
+        let name = headers
+            .get(pos + 1..pos + 1 + name_len)
+            .ok_or_else(malformed)?;
+        pos += 1 + name_len;
+        let value_type = *headers.get(pos).ok_or_else(malformed)?;
+        pos += 1;
+        let value_len = match value_type {
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            9 => 16,
+            6 | 7 => {
+                let len = headers.get(pos..pos + 2).ok_or_else(malformed)?;
+                pos += 2;
+                u16::from_be_bytes([len[0], len[1]]) as usize
+            }
+            _ => return Err(malformed()),
+        };
+        let value = headers.get(pos..pos + value_len).ok_or_else(malformed)?;
+        pos += value_len;
+
+        if value_type == 7 {
+            let value = String::from_utf8_lossy(value).to_string();
+            match name {
+                b":event-type" => decoded.event_type = Some(value),
+                b":message-type" => decoded.message_type = Some(value),
+                b":exception-type" => decoded.exception_type = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Some(decoded))
+}
+
+#[async_trait]
+impl LlmClient for BedrockClient {
+    fn provider(&self) -> &str {
+        "bedrock"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let body = build_request(&request);
+
+        let response = self
+            .client
+            .post(self.endpoint("converse"))
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(AiError::Http)?;
+
+        if !response.status().is_success() {
+            return Err(response_to_error(response, "Bedrock").await);
+        }
+
+        let data: ConverseResponse = response.json().await?;
+
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for block in data.output.message.content {
+            if let Some(part) = block.get("text").and_then(Value::as_str) {
+                text.push_str(part);
+            } else if let Some(tool_use) = block.get("toolUse") {
+                tool_calls.push(ToolCall {
+                    id: tool_use["toolUseId"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    name: tool_use["name"].as_str().unwrap_or_default().to_string(),
+                    arguments: tool_use.get("input").cloned().unwrap_or(json!({})),
+                });
+            }
+        }
+
+        Ok(CompletionResponse {
+            content: (!text.is_empty()).then_some(text),
+            tool_calls,
+            finish_reason: map_stop_reason(data.stop_reason.as_deref()),
+            usage: data.usage.map(|usage| usage.to_token_usage(&self.model)),
+        })
+    }
+
+    fn complete_stream(&self, request: CompletionRequest) -> StreamResult {
+        let client = self.client.clone();
+        let api_key = self.api_key.clone();
+        let model = self.model.clone();
+        let url = self.endpoint("converse-stream");
+
+        Box::pin(async_stream::stream! {
+            let body = build_request(&request);
+
+            let response = match client
+                .post(url)
+                .bearer_auth(&api_key)
+                .json(&body)
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    yield Err(AiError::Http(e));
+                    return;
+                }
+            };
+
+            if !response.status().is_success() {
+                yield Err(response_to_error(response, "Bedrock").await);
+                return;
+            }
+
+            let mut byte_stream = response.bytes_stream();
+            let mut buffer: Vec<u8> = Vec::new();
+            let mut finish_reason = FinishReason::Stop;
+            let mut usage: Option<BedrockUsage> = None;
+            let mut current_tool: Option<(String, String)> = None;
+
+            while let Some(chunk_result) = byte_stream.next().await {
+                match chunk_result {
+                    Ok(bytes) => buffer.extend_from_slice(&bytes),
+                    Err(e) => {
+                        yield Err(AiError::Http(e));
+                        return;
+                    }
+                }
+
+                loop {
+                    let frame = match decode_event_frame(&mut buffer) {
+                        Ok(Some(frame)) => frame,
+                        Ok(None) => break,
+                        Err(e) => {
+                            yield Err(e);
+                            return;
+                        }
+                    };
+
+                    let payload: Value = serde_json::from_slice(&frame.payload).unwrap_or(Value::Null);
+                    if frame.message_type.as_deref() == Some("exception") {
+                        let message = payload["message"].as_str().unwrap_or("unknown error");
+                        yield Err(AiError::Llm(format!(
+                            "Stream error: {}: {}",
+                            frame.exception_type.as_deref().unwrap_or("exception"),
+                            message
+                        )));
+                        return;
+                    }
+
+                    let index = payload["contentBlockIndex"].as_u64().unwrap_or(0) as usize;
+                    match frame.event_type.as_deref() {
+                        Some("contentBlockStart") => {
+                            if let Some(tool_use) = payload["start"].get("toolUse") {
+                                let id = tool_use["toolUseId"].as_str().unwrap_or_default().to_string();
+                                let name = tool_use["name"].as_str().unwrap_or_default().to_string();
+                                current_tool = Some((id.clone(), name.clone()));
+                                yield Ok(StreamChunk {
+                                    text: String::new(),
+                                    thinking: None,
+                                    tool_call_delta: Some(ToolCallDelta {
+                                        index,
+                                        id: Some(id),
+                                        name: Some(name),
+                                        arguments: None,
+                                    }),
+                                    finish_reason: None,
+                                    usage: None,
+                                });
+                            }
+                        }
+                        Some("contentBlockDelta") => {
+                            let delta = &payload["delta"];
+                            if let Some(text) = delta["text"].as_str() {
+                                yield Ok(StreamChunk::text(text));
+                            } else if let Some(input) = delta["toolUse"]["input"].as_str() {
+                                yield Ok(StreamChunk {
+                                    text: String::new(),
+                                    thinking: None,
+                                    tool_call_delta: Some(ToolCallDelta {
+                                        index,
+                                        id: current_tool.as_ref().map(|(id, _)| id.clone()),
+                                        name: current_tool.as_ref().map(|(_, name)| name.clone()),
+                                        arguments: Some(input.to_string()),
+                                    }),
+                                    finish_reason: None,
+                                    usage: None,
+                                });
+                            } else if let Some(thinking) = delta["reasoningContent"]["text"].as_str() {
+                                yield Ok(StreamChunk::thinking(thinking));
+                            }
+                        }
+                        Some("contentBlockStop") => current_tool = None,
+                        Some("messageStop") => {
+                            finish_reason = map_stop_reason(payload["stopReason"].as_str());
+                        }
+                        Some("metadata") => {
+                            usage = serde_json::from_value(payload["usage"].clone()).ok();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            yield Ok(StreamChunk::final_chunk(
+                finish_reason,
+                usage.map(|usage| usage.to_token_usage(&model)),
+            ));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::client::Message;
+    use crate::tools::ToolSchema;
+
+    fn encode_frame(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+        let mut header_bytes = Vec::new();
+        for (name, value) in headers {
+            header_bytes.push(name.len() as u8);
+            header_bytes.extend_from_slice(name.as_bytes());
+            header_bytes.push(7);
+            header_bytes.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            header_bytes.extend_from_slice(value.as_bytes());
+        }
+        let total = 12 + header_bytes.len() + payload.len() + 4;
+        let mut frame = Vec::new();
+        frame.extend_from_slice(&(total as u32).to_be_bytes());
+        frame.extend_from_slice(&(header_bytes.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&[0; 4]);
+        frame.extend_from_slice(&header_bytes);
+        frame.extend_from_slice(payload);
+        frame.extend_from_slice(&[0; 4]);
+        frame
+    }
+
+    #[test]
+    fn test_decode_event_frame_waits_for_full_frame() {
+        let frame = encode_frame(
+            &[
+                (":event-type", "contentBlockDelta"),
+                (":message-type", "event"),
+            ],
+            br#"{"contentBlockIndex":0,"delta":{"text":"Hi"}}"#,
+        );
+        let mut buffer = frame[..10].to_vec();
+        assert!(decode_event_frame(&mut buffer).unwrap().is_none());
+
+        buffer = frame.clone();
+        buffer.extend_from_slice(&frame[..5]);
+        let decoded = decode_event_frame(&mut buffer).unwrap().unwrap();
+        assert_eq!(decoded.event_type.as_deref(), Some("contentBlockDelta"));
+        assert_eq!(decoded.message_type.as_deref(), Some("event"));
+        let payload: Value = serde_json::from_slice(&decoded.payload).unwrap();
+        assert_eq!(payload["delta"]["text"], "Hi");
+        assert_eq!(buffer.len(), 5);
+    }
+
+    #[test]
+    fn test_build_request_merges_tool_results_into_one_turn() {
+        let request = CompletionRequest::new(vec![
+            Message::system("Be brief."),
+            Message::user("Check both"),
+            Message::assistant_with_tool_calls(
+                Some("On it".to_string()),
+                vec![
+                    ToolCall {
+                        id: "t1".to_string(),
+                        name: "read".to_string(),
+                        arguments: json!({ "path": "a" }),
+                    },
+                    ToolCall {
+                        id: "t2".to_string(),
+                        name: "read".to_string(),
+                        arguments: json!({ "path": "b" }),
+                    },
+                ],
+            ),
+            Message::tool_result("t1", "A"),
+            Message::tool_result("t2", "B"),
+        ]);
+
+        let body = serde_json::to_value(build_request(&request)).unwrap();
+        assert_eq!(body["system"][0]["text"], "Be brief.");
+        assert_eq!(body["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(body["messages"][1]["content"][1]["toolUse"]["name"], "read");
+        let results = body["messages"][2]["content"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1]["toolResult"]["toolUseId"], "t2");
+        assert_eq!(body["inferenceConfig"]["maxTokens"], 4096);
+        assert!(body.get("toolConfig").is_none());
+    }
+
+    #[test]
+    fn test_build_request_forces_named_tool() {
+        let request = CompletionRequest::new(vec![Message::user("hi")])
+            .with_tools(vec![ToolSchema {
+                name: "respond".to_string(),
+                description: "Reply".to_string(),
+                parameters: json!({ "type": "object" }),
+            }])
+            .with_forced_tool("respond");
+
+        let body = serde_json::to_value(build_request(&request)).unwrap();
+        assert_eq!(body["toolConfig"]["toolChoice"]["tool"]["name"], "respond");
+    }
+
+    #[test]
+    fn test_endpoint_escapes_model_id() {
+        let client = BedrockClient::new("key")
+            .unwrap()
+            .with_region("eu-west-1")
+            .with_model("anthropic.claude-haiku-4-5-20251001-v1:0");
+        assert_eq!(
+            client.endpoint("converse"),
+            "https://bedrock-runtime.eu-west-1.amazonaws.com/model/anthropic.claude-haiku-4-5-20251001-v1%3A0/converse"
+        );
+    }
+}
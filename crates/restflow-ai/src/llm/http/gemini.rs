@@ -0,0 +1,658 @@
+//! Google Gemini LLM provider (native `generateContent` API)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::{AiError, Result};
+use crate::llm::client::{
+    CompletionRequest, CompletionResponse, FinishReason, LlmClient, Role, StreamChunk,
+    StreamResult, TokenUsage, ToolCall, ToolCallDelta,
+};
+use crate::llm::retry::response_to_error;
+use restflow_traits::http_client::build_http_client;
+use uuid::Uuid;
+
+const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+/// Placeholder Gemini accepts for function calls it did not sign itself
+/// (e.g. calls replayed from another provider after a model switch).
+const SKIP_SIGNATURE: &str = "skip_thought_signature_validator";
+
+/// Thought signatures returned with function calls, keyed by tool call ID.
+/// Gemini 3 rejects follow-up turns that do not echo them back.
+type SignatureCache = Arc<Mutex<HashMap<String, String>>>;
+
+/// Gemini client
+pub struct GeminiClient {
+    client: Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+    signatures: SignatureCache,
+}
+
+impl GeminiClient {
+    /// Create a new Gemini client
+    pub fn new(api_key: impl Into<String>) -> std::result::Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: build_http_client()?,
+            api_key: api_key.into(),
+            model: "gemini-2.5-pro".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            signatures: SignatureCache::default(),
+        })
+    }
+
+    /// Set the model to use
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Set a custom base URL (e.g. a regional or proxy endpoint)
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn endpoint(&self, method: &str) -> String {
+        format!("{}/models/{}:{}", self.base_url, self.model, method)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<GeminiTools>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_config: Option<Value>,
+    generation_config: GenerationConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GeminiContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPart {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    /// Set on parts that carry model reasoning instead of answer text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thought: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    function_call: Option<FunctionCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    function_response: Option<FunctionResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thought_signature: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FunctionCall {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    args: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FunctionResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    name: String,
+    response: Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiTools {
+    function_declarations: Vec<FunctionDeclaration>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FunctionDeclaration {
+    name: String,
+    description: String,
+    /// Accepts full JSON Schema, unlike the OpenAPI subset in `parameters`.
+    parameters_json_schema: Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    #[serde(default)]
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiCandidate {
+    #[serde(default)]
+    content: Option<GeminiContent>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u32,
+    #[serde(default)]
+    candidates_token_count: u32,
+    #[serde(default)]
+    thoughts_token_count: u32,
+    /// Prompt tokens served from the implicit context cache
+    #[serde(default)]
+    cached_content_token_count: u32,
+}
+
+impl UsageMetadata {
+    fn to_token_usage(&self, model: &str) -> TokenUsage {
+        // Thinking tokens are billed as output tokens.
+        let completion_tokens = self.candidates_token_count + self.thoughts_token_count;
+        TokenUsage::with_cache(
+            model,
+            self.prompt_token_count,
+            completion_tokens,
+            self.cached_content_token_count,
+            0,
+        )
+    }
+}
+
+/// Convert a CompletionRequest into a Gemini request body.
+fn build_request(
+    request: &CompletionRequest,
+    signatures: &HashMap<String, String>,
+) -> GeminiRequest {
+    let system = request
+        .messages
+        .iter()
+        .filter(|m| m.role == Role::System)
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    // Gemini matches function responses by name, so remember which tool each call ID used.
+    let mut call_names: HashMap<&str, &str> = HashMap::new();
+    let mut contents: Vec<GeminiContent> = Vec::new();
+
+    for message in request.messages.iter().filter(|m| m.role != Role::System) {
+        let (role, parts) = match message.role {
+            Role::Assistant => {
+                let mut parts = Vec::new();
+                if !message.content.is_empty() {
+                    parts.push(GeminiPart {
+                        text: Some(message.content.clone()),
+                        ..GeminiPart::default()
+                    });
+                }
+                for (index, call) in message.tool_calls.iter().flatten().enumerate() {
+                    call_names.insert(call.id.as_str(), call.name.as_str());
+                    // Only the first call of a turn carries the signature.
+                    let thought_signature = signatures
+                        .get(&call.id)
+                        .cloned()
+                        .or_else(|| (index == 0).then(|| SKIP_SIGNATURE.to_string()));
+                    parts.push(GeminiPart {
+                        function_call: Some(FunctionCall {
+                            id: Some(call.id.clone()),
+                            name: call.name.clone(),
+                            args: call.arguments.clone(),
+                        }),
+                        thought_signature,
+                        ..GeminiPart::default()
+                    });
+                }
+                ("model", parts)
+            }
+            Role::Tool => {
+                let id = message.tool_call_id.clone();
+                let name = id
+                    .as_deref()
+                    .and_then(|id| call_names.get(id).copied())
+                    .map(str::to_string)
+                    .or_else(|| message.name.clone())
+                    .unwrap_or_default();
+                let part = GeminiPart {
+                    function_response: Some(FunctionResponse {
+                        id,
+                        name,
+                        response: tool_response_value(&message.content),
+                    }),
+                    ..GeminiPart::default()
+                };
+                ("user", vec![part])
+            }
+            Role::User | Role::System => (
+                "user",
+                vec![GeminiPart {
+                    text: Some(message.content.clone()),
+                    ..GeminiPart::default()
+                }],
+            ),
+        };
+
+        if parts.is_empty() {
+            continue;
+        }
+        // Parallel tool results must share one turn.
+        match contents.last_mut() {
+            Some(last) if last.role.as_deref() == Some(role) => last.parts.extend(parts),
+            _ => contents.push(GeminiContent {
+                role: Some(role.to_string()),
+                parts,
+            }),
+        }
+    }
+
+    let tools = if request.tools.is_empty() {
+        Vec::new()
+    } else {
+        vec![GeminiTools {
+            function_declarations: request
+                .tools
+                .iter()
+                .map(|tool| FunctionDeclaration {
+                    name: tool.name.clone(),
+                    description: tool.description.clone(),
+                    parameters_json_schema: tool.parameters.clone(),
+                })
+                .collect(),
+        }]
+    };
+
+    GeminiRequest {
+        contents,
+        system_instruction: (!system.is_empty()).then(|| GeminiContent {
+            role: None,
+            parts: vec![GeminiPart {
+                text: Some(system),
+                ..GeminiPart::default()
+            }],
+        }),
+        tools,
+        tool_config: request.forced_tool.as_ref().map(|name| {
+            json!({
+                "functionCallingConfig": { "mode": "ANY", "allowedFunctionNames": [name] }
+            })
+        }),
+        generation_config: GenerationConfig {
+            temperature: request.temperature,
+            max_output_tokens: request.max_tokens,
+        },
+    }
+}
+
+/// Function responses must be JSON objects; wrap anything else.
+fn tool_response_value(content: &str) -> Value {
+    match serde_json::from_str::<Value>(content) {
+        Ok(value @ Value::Object(_)) => value,
+        Ok(value) => json!({ "result": value }),
+        Err(_) => json!({ "result": content }),
+    }
+}
+
+fn map_finish_reason(reason: Option<&str>, has_tool_calls: bool) -> FinishReason {
+    if has_tool_calls {
+        return FinishReason::ToolCalls;
+    }
+    match reason {
+        Some("MAX_TOKENS") => FinishReason::MaxTokens,
+        Some("SAFETY" | "RECITATION" | "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII") => {
+            FinishReason::Error
+        }
+        _ => FinishReason::Stop,
+    }
+}
+
+/// Tool call ID for a function call, generating one when Gemini omits it.
+fn call_id(call: &FunctionCall) -> String {
+    call.id
+        .clone()
+        .unwrap_or_else(|| format!("call_{}", Uuid::new_v4().simple()))
+}
+
+#[async_trait]
+impl LlmClient for GeminiClient {
+    fn provider(&self) -> &str {
+        "google"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let body = build_request(&request, &self.signatures.lock());
+
+        let response = self
+            .client
+            .post(self.endpoint("generateContent"))
+            .header("x-goog-api-key", &self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(AiError::Http)?;
+
+        if !response.status().is_success() {
+            return Err(response_to_error(response, "Gemini").await);
+        }
+
+        let data: GeminiResponse = response.json().await?;
+        let candidate = data
+            .candidates
+            .into_iter()
+            .next()
+            .ok_or_else(|| AiError::Llm("Gemini returned no candidates".to_string()))?;
+
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for part in candidate.content.map(|c| c.parts).unwrap_or_default() {
+            if let Some(call) = part.function_call {
+                let id = call_id(&call);
+                if let Some(signature) = part.thought_signature {
+                    self.signatures.lock().insert(id.clone(), signature);
+                }
+                tool_calls.push(ToolCall {
+                    id,
+                    name: call.name,
+                    arguments: call.args,
+                });
+            } else if part.thought != Some(true)
+                && let Some(part_text) = part.text
+            {
+                text.push_str(&part_text);
+            }
+        }
+
+        let finish_reason =
+            map_finish_reason(candidate.finish_reason.as_deref(), !tool_calls.is_empty());
+        Ok(CompletionResponse {
+            content: (!text.is_empty()).then_some(text),
+            tool_calls,
+            finish_reason,
+            usage: data
+                .usage_metadata
+                .map(|usage| usage.to_token_usage(&self.model)),
+        })
+    }
+
+    fn complete_stream(&self, request: CompletionRequest) -> StreamResult {
+        let client = self.client.clone();
+        let api_key = self.api_key.clone();
+        let model = self.model.clone();
+        let url = format!("{}?alt=sse", self.endpoint("streamGenerateContent"));
+        let signatures = self.signatures.clone();
+
+        Box::pin(async_stream::stream! {
+            let body = build_request(&request, &signatures.lock());
+
+            let response = match client
+                .post(url)
+                .header("x-goog-api-key", &api_key)
+                .json(&body)
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    yield Err(AiError::Http(e));
+                    return;
+                }
+            };
+
+            if !response.status().is_success() {
+                yield Err(response_to_error(response, "Gemini").await);
+                return;
+            }
+
+            let mut byte_stream = response.bytes_stream();
+            let mut buffer = String::new();
+            let mut tool_index = 0usize;
+            let mut finish: Option<String> = None;
+            let mut usage: Option<UsageMetadata> = None;
+
+            while let Some(chunk_result) = byte_stream.next().await {
+                let chunk = match chunk_result {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        yield Err(AiError::Http(e));
+                        return;
+                    }
+                };
+
+                buffer.push_str(&String::from_utf8_lossy(&chunk).replace("\r\n", "\n"));
+
+                while let Some(pos) = buffer.find("\n\n") {
+                    let event_str = buffer[..pos].to_string();
+                    buffer = buffer[pos + 2..].to_string();
+
+                    for line in event_str.lines() {
+                        let Some(data) = line.strip_prefix("data:") else {
+                            continue;
+                        };
+                        let event: GeminiResponse = match serde_json::from_str(data.trim()) {
+                            Ok(event) => event,
+                            Err(_) => continue,
+                        };
+
+                        if let Some(metadata) = event.usage_metadata {
+                            usage = Some(metadata);
+                        }
+                        for candidate in event.candidates {
+                            if let Some(reason) = candidate.finish_reason {
+                                finish = Some(reason);
+                            }
+                            for part in candidate.content.map(|c| c.parts).unwrap_or_default() {
+                                if let Some(call) = part.function_call {
+                                    let id = call_id(&call);
+                                    if let Some(signature) = part.thought_signature {
+                                        signatures.lock().insert(id.clone(), signature);
+                                    }
+                                    let arguments = serde_json::to_string(&call.args)
+                                        .unwrap_or_else(|_| "{}".to_string());
+                                    yield Ok(StreamChunk {
+                                        text: String::new(),
+                                        thinking: None,
+                                        tool_call_delta: Some(ToolCallDelta {
+                                            index: tool_index,
+                                            id: Some(id),
+                                            name: Some(call.name),
+                                            arguments: Some(arguments),
+                                        }),
+                                        finish_reason: None,
+                                        usage: None,
+                                    });
+                                    tool_index += 1;
+                                } else if let Some(text) = part.text {
+                                    if text.is_empty() {
+                                        continue;
+                                    }
+                                    if part.thought == Some(true) {
+                                        yield Ok(StreamChunk::thinking(text));
+                                    } else {
+                                        yield Ok(StreamChunk::text(text));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            yield Ok(StreamChunk::final_chunk(
+                map_finish_reason(finish.as_deref(), tool_index > 0),
+                usage.map(|usage| usage.to_token_usage(&model)),
+            ));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::client::Message;
+    use crate::tools::ToolSchema;
+
+    #[test]
+    fn test_build_request_maps_roles_and_tool_results() {
+        let request = CompletionRequest::new(vec![
+            Message::system("Be brief."),
+            Message::user("Weather in Paris and Rome?"),
+            Message::assistant_with_tool_calls(
+                None,
+                vec![
+                    ToolCall {
+                        id: "call_1".to_string(),
+                        name: "weather".to_string(),
+                        arguments: json!({ "city": "Paris" }),
+                    },
+                    ToolCall {
+                        id: "call_2".to_string(),
+                        name: "weather".to_string(),
+                        arguments: json!({ "city": "Rome" }),
+                    },
+                ],
+            ),
+            Message::tool_result("call_1", r#"{"temp":21}"#),
+            Message::tool_result("call_2", "sunny"),
+        ])
+        .with_tools(vec![ToolSchema {
+            name: "weather".to_string(),
+            description: "Get weather".to_string(),
+            parameters: json!({ "type": "object", "additionalProperties": false }),
+        }]);
+
+        let signatures = HashMap::from([("call_1".to_string(), "sig-1".to_string())]);
+        let body = serde_json::to_value(build_request(&request, &signatures)).unwrap();
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert_eq!(body["contents"].as_array().unwrap().len(), 3);
+        assert_eq!(body["contents"][1]["role"], "model");
+        assert_eq!(
+            body["contents"][1]["parts"][1]["functionCall"]["args"]["city"],
+            "Rome"
+        );
+        assert_eq!(body["contents"][1]["parts"][0]["thoughtSignature"], "sig-1");
+        assert!(
+            body["contents"][1]["parts"][1]
+                .get("thoughtSignature")
+                .is_none()
+        );
+
+        let results = &body["contents"][2];
+        assert_eq!(results["role"], "user");
+        assert_eq!(results["parts"][0]["functionResponse"]["name"], "weather");
+        assert_eq!(
+            results["parts"][0]["functionResponse"]["response"]["temp"],
+            21
+        );
+        assert_eq!(
+            results["parts"][1]["functionResponse"]["response"]["result"],
+            "sunny"
+        );
+        assert_eq!(
+            body["tools"][0]["functionDeclarations"][0]["parametersJsonSchema"]["additionalProperties"],
+            false
+        );
+    }
+
+    #[test]
+    fn test_build_request_forces_named_tool() {
+        let request = CompletionRequest::new(vec![Message::user("hi")])
+            .with_tools(vec![ToolSchema {
+                name: "respond".to_string(),
+                description: "Reply".to_string(),
+                parameters: json!({ "type": "object" }),
+            }])
+            .with_forced_tool("respond");
+
+        let body = serde_json::to_value(build_request(&request, &HashMap::new())).unwrap();
+        let config = &body["toolConfig"]["functionCallingConfig"];
+        assert_eq!(config["mode"], "ANY");
+        assert_eq!(config["allowedFunctionNames"], json!(["respond"]));
+    }
+
+    #[test]
+    fn test_unsigned_calls_use_placeholder_signature() {
+        let request = CompletionRequest::new(vec![
+            Message::user("hi"),
+            Message::assistant_with_tool_calls(
+                None,
+                vec![ToolCall {
+                    id: "toolu_1".to_string(),
+                    name: "echo".to_string(),
+                    arguments: json!({}),
+                }],
+            ),
+            Message::tool_result("toolu_1", "ok"),
+        ]);
+
+        let body = serde_json::to_value(build_request(&request, &HashMap::new())).unwrap();
+        assert_eq!(
+            body["contents"][1]["parts"][0]["thoughtSignature"],
+            SKIP_SIGNATURE
+        );
+    }
+
+    #[test]
+    fn test_response_parsing_extracts_calls_and_usage() {
+        let json = r#"{
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "thinking...", "thought": true},
+                    {"functionCall": {"name": "weather", "args": {"city": "Paris"}}}
+                ]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 10, "candidatesTokenCount": 5, "thoughtsTokenCount": 3}
+        }"#;
+        let data: GeminiResponse = serde_json::from_str(json).unwrap();
+        let usage = data.usage_metadata.as_ref().unwrap().to_token_usage("x");
+        assert_eq!(usage.completion_tokens, 8);
+        assert_eq!(usage.total_tokens, 18);
+
+        let parts = &data.candidates[0].content.as_ref().unwrap().parts;
+        assert_eq!(parts[0].thought, Some(true));
+        let call = parts[1].function_call.as_ref().unwrap();
+        assert!(call_id(call).starts_with("call_"));
+        assert_eq!(
+            map_finish_reason(Some("STOP"), true),
+            FinishReason::ToolCalls
+        );
+        assert_eq!(
+            map_finish_reason(Some("MAX_TOKENS"), false),
+            FinishReason::MaxTokens
+        );
+    }
+}
@@ -1,5 +1,11 @@
 mod anthropic;
+mod bedrock;
+mod gemini;
+mod ollama;
 mod openai;
 
 pub use anthropic::AnthropicClient;
+pub use bedrock::{BedrockClient, DEFAULT_BEDROCK_REGION, bedrock_region};
+pub use gemini::GeminiClient;
+pub use ollama::{LocalModel, OllamaClient, list_openai_compatible_models};
 pub use openai::OpenAIClient;
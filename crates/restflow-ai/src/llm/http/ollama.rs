@@ -0,0 +1,186 @@
+//! Local model providers: Ollama and OpenAI-compatible local servers.
+//!
+//! Both speak the OpenAI chat completions protocol, so completions are
+//! delegated to [`OpenAIClient`]. This module adds local model discovery.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AiError, Result};
+use crate::llm::client::{CompletionRequest, CompletionResponse, LlmClient, StreamResult};
+use crate::llm::http::OpenAIClient;
+use crate::llm::retry::response_to_error;
+use restflow_models::{LocalBackend, ollama_host};
+use restflow_traits::http_client::build_http_client;
+
+/// A model served by a local endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalModel {
+    /// Reference accepted by the LLM factory, e.g. `ollama:llama3.1:8b`.
+    pub reference: String,
+    /// Model name as reported by the server.
+    pub name: String,
+    /// Size of the model weights, when the server reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+/// Ollama client.
+pub struct OllamaClient {
+    inner: OpenAIClient,
+    client: Client,
+    host: String,
+}
+
+impl OllamaClient {
+    /// Create a client for the host in `OLLAMA_HOST` (default `http://localhost:11434`).
+    pub fn new() -> std::result::Result<Self, reqwest::Error> {
+        let host = ollama_host();
+        Ok(Self {
+            // Ollama ignores the bearer token, but the OpenAI client always sends one.
+            inner: OpenAIClient::new("ollama")?
+                .with_model("llama3.1")
+                .with_base_url(format!("{}/v1", host)),
+            client: build_http_client()?,
+            host,
+        })
+    }
+
+    /// Set the model to use
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.inner = self.inner.with_model(model);
+        self
+    }
+
+    /// Set the Ollama host (without the `/v1` suffix)
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into().trim_end_matches('/').to_string();
+        self.inner = self.inner.with_base_url(format!("{}/v1", self.host));
+        self
+    }
+
+    /// List the models pulled into this Ollama instance.
+    pub async fn list_models(&self) -> Result<Vec<LocalModel>> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.host))
+            .send()
+            .await
+            .map_err(AiError::Http)?;
+        if !response.status().is_success() {
+            return Err(response_to_error(response, "Ollama").await);
+        }
+
+        let tags: OllamaTags = response.json().await?;
+        Ok(tags
+            .models
+            .into_iter()
+            .map(|model| LocalModel {
+                reference: LocalBackend::Ollama.reference(&model.name),
+                name: model.name,
+                size_bytes: model.size,
+            })
+            .collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<OllamaTag>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTag {
+    name: String,
+    #[serde(default)]
+    size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIModelList {
+    #[serde(default)]
+    data: Vec<OpenAIModelEntry>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIModelEntry {
+    id: String,
+}
+
+/// List the models served by an OpenAI-compatible local server
+/// (llama.cpp, LM Studio, vLLM) via `GET {base_url}/models`.
+pub async fn list_openai_compatible_models(base_url: &str) -> Result<Vec<LocalModel>> {
+    let client = build_http_client().map_err(AiError::Http)?;
+    let response = client
+        .get(format!("{}/models", base_url.trim_end_matches('/')))
+        .send()
+        .await
+        .map_err(AiError::Http)?;
+    if !response.status().is_success() {
+        return Err(response_to_error(response, "local model server").await);
+    }
+
+    let list: OpenAIModelList = response.json().await?;
+    Ok(list
+        .data
+        .into_iter()
+        .map(|model| LocalModel {
+            reference: LocalBackend::OpenAICompatible.reference(&model.id),
+            name: model.id,
+            size_bytes: None,
+        })
+        .collect())
+}
+
+#[async_trait]
+impl LlmClient for OllamaClient {
+    fn provider(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        self.inner.complete(request).await
+    }
+
+    fn complete_stream(&self, request: CompletionRequest) -> StreamResult {
+        self.inner.complete_stream(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ollama_tags_deserialize() {
+        let json = r#"{"models":[{"name":"llama3.1:8b","size":4661224676,"digest":"abc"}]}"#;
+        let tags: OllamaTags = serde_json::from_str(json).unwrap();
+        assert_eq!(tags.models.len(), 1);
+        assert_eq!(tags.models[0].name, "llama3.1:8b");
+        assert_eq!(tags.models[0].size, Some(4661224676));
+    }
+
+    #[test]
+    fn test_openai_model_list_deserialize() {
+        let json = r#"{"object":"list","data":[{"id":"qwen2.5-7b-instruct","object":"model"}]}"#;
+        let list: OpenAIModelList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.data[0].id, "qwen2.5-7b-instruct");
+    }
+
+    #[test]
+    fn test_with_host_strips_trailing_slash() {
+        let client = OllamaClient::new()
+            .unwrap()
+            .with_host("http://gpu-box:11434/")
+            .with_model("qwen2.5-coder");
+        assert_eq!(client.host, "http://gpu-box:11434");
+        assert_eq!(client.model(), "qwen2.5-coder");
+        assert_eq!(client.provider(), "ollama");
+    }
+}
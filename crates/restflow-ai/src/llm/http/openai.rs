@@ -11,7 +11,6 @@ use crate::llm::client::{
     CompletionRequest, CompletionResponse, FinishReason, LlmClient, Role, StreamChunk,
     StreamResult, TokenUsage, ToolCall, ToolCallDelta,
 };
-use crate::llm::pricing::calculate_cost;
 use crate::llm::retry::response_to_error;
 use restflow_traits::http_client::build_http_client;
 
@@ -54,6 +53,8 @@ struct OpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<OpenAITool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
@@ -130,7 +131,36 @@ struct OpenAIFunctionCall {
 struct OpenAIUsage {
     prompt_tokens: u32,
     completion_tokens: u32,
-    total_tokens: u32,
+    #[serde(default)]
+    prompt_tokens_details: Option<OpenAIPromptTokensDetails>,
+    /// DeepSeek reports cache hits here instead of in `prompt_tokens_details`.
+    #[serde(default)]
+    prompt_cache_hit_tokens: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OpenAIPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: u32,
+}
+
+impl OpenAIUsage {
+    /// Prompt caching is automatic for OpenAI-compatible APIs; only hits are reported.
+    fn to_token_usage(&self, model: &str) -> TokenUsage {
+        let cached_tokens = self
+            .prompt_tokens_details
+            .as_ref()
+            .map(|details| details.cached_tokens)
+            .or(self.prompt_cache_hit_tokens)
+            .unwrap_or(0);
+        TokenUsage::with_cache(
+            model,
+            self.prompt_tokens,
+            self.completion_tokens,
+            cached_tokens,
+            0,
+        )
+    }
 }
 
 // Streaming types
@@ -166,6 +196,14 @@ struct OpenAIStreamFunction {
     arguments: Option<String>,
 }
 
+/// Native `tool_choice` for a forced function call.
+fn tool_choice(request: &CompletionRequest) -> Option<Value> {
+    request
+        .forced_tool
+        .as_ref()
+        .map(|name| serde_json::json!({ "type": "function", "function": { "name": name } }))
+}
+
 #[async_trait]
 impl LlmClient for OpenAIClient {
     fn provider(&self) -> &str {
@@ -242,6 +280,7 @@ impl LlmClient for OpenAIClient {
             model: self.model.clone(),
             messages,
             tools,
+            tool_choice: tool_choice(&request),
             temperature: request.temperature,
             max_tokens: request.max_tokens,
         };
@@ -286,15 +325,7 @@ impl LlmClient for OpenAIClient {
             _ => FinishReason::Error,
         };
 
-        let usage = data.usage.map(|u| {
-            let cost_usd = calculate_cost(&self.model, u.prompt_tokens, u.completion_tokens);
-            TokenUsage {
-                prompt_tokens: u.prompt_tokens,
-                completion_tokens: u.completion_tokens,
-                total_tokens: u.total_tokens,
-                cost_usd,
-            }
-        });
+        let usage = data.usage.map(|u| u.to_token_usage(&self.model));
 
         Ok(CompletionResponse {
             content: choice.message.content,
@@ -370,7 +401,7 @@ impl LlmClient for OpenAIClient {
                 )
             };
 
-            let body = serde_json::json!({
+            let mut body = serde_json::json!({
                 "model": model,
                 "messages": messages,
                 "tools": tools,
@@ -379,6 +410,9 @@ impl LlmClient for OpenAIClient {
                 "stream": true,
                 "stream_options": { "include_usage": true }
             });
+            if let Some(choice) = tool_choice(&request) {
+                body["tool_choice"] = choice;
+            }
 
             let response = match client
                 .post(format!("{}/chat/completions", base_url))
@@ -436,16 +470,7 @@ impl LlmClient for OpenAIClient {
                             if let Some(usage) = parsed.usage {
                                 yield Ok(StreamChunk::final_chunk(
                                     FinishReason::Stop,
-                                    Some(TokenUsage {
-                                        prompt_tokens: usage.prompt_tokens,
-                                        completion_tokens: usage.completion_tokens,
-                                        total_tokens: usage.total_tokens,
-                                        cost_usd: calculate_cost(
-                                            &model,
-                                            usage.prompt_tokens,
-                                            usage.completion_tokens,
-                                        ),
-                                    }),
+                                    Some(usage.to_token_usage(&model)),
                                 ));
                                 continue;
                             }
@@ -522,16 +547,7 @@ impl LlmClient for OpenAIClient {
                         {
                             yield Ok(StreamChunk::final_chunk(
                                 FinishReason::Stop,
-                                Some(TokenUsage {
-                                    prompt_tokens: usage.prompt_tokens,
-                                    completion_tokens: usage.completion_tokens,
-                                    total_tokens: usage.total_tokens,
-                                    cost_usd: calculate_cost(
-                                        &model,
-                                        usage.prompt_tokens,
-                                        usage.completion_tokens,
-                                    ),
-                                }),
+                                Some(usage.to_token_usage(&model)),
                             ));
                         }
                     }
@@ -567,4 +583,20 @@ mod tests {
         assert_eq!(map("length"), FinishReason::MaxTokens);
         assert_eq!(map("unknown"), FinishReason::Error);
     }
+
+    #[test]
+    fn test_usage_reports_cached_prompt_tokens() {
+        let json = r#"{"prompt_tokens":2000,"completion_tokens":100,"total_tokens":2100,"prompt_tokens_details":{"cached_tokens":1536}}"#;
+        let usage: OpenAIUsage = serde_json::from_str(json).unwrap();
+        let usage = usage.to_token_usage("gpt-5");
+        assert_eq!(usage.prompt_tokens, 2000);
+        assert_eq!(usage.total_tokens, 2100);
+        assert_eq!(usage.cache_read_tokens, 1536);
+        assert_eq!(usage.cache_write_tokens, 0);
+        assert!(usage.cache_savings_usd.unwrap() > 0.0);
+
+        let json = r#"{"prompt_tokens":900,"completion_tokens":10,"prompt_cache_hit_tokens":640}"#;
+        let usage: OpenAIUsage = serde_json::from_str(json).unwrap();
+        assert_eq!(usage.to_token_usage("deepseek-chat").cache_read_tokens, 640);
+    }
 }
@@ -119,6 +119,7 @@ impl MockLlmClient {
             completion_tokens,
             total_tokens: 1 + completion_tokens,
             cost_usd: Some(0.0),
+            ..Default::default()
         }
     }
 
@@ -7,7 +7,9 @@ pub mod http;
 #[cfg(any(test, feature = "test-utils"))]
 mod mock_client;
 pub mod pricing;
+mod response_cache;
 mod retry;
+mod structured;
 mod swappable;
 mod switcher;
 
@@ -17,10 +19,19 @@ pub use client::{
     StreamResult, TokenUsage, ToolCall, ToolCallDelta,
 };
 pub use factory::{DefaultLlmClientFactory, LlmClientFactory};
-pub use http::{AnthropicClient, OpenAIClient};
+pub use http::{
+    AnthropicClient, BedrockClient, GeminiClient, LocalModel, OllamaClient, OpenAIClient,
+    list_openai_compatible_models,
+};
 #[cfg(any(test, feature = "test-utils"))]
 pub use mock_client::{MockLlmClient, MockStep, MockStepKind};
-pub use restflow_models::{ClientKind, LlmProvider, ModelSpec};
+pub use response_cache::{
+    CachingLlmClient, LlmResponseCache, LlmResponseCacheConfig, LlmResponseCacheStats,
+};
+pub use restflow_models::{ClientKind, LlmProvider, LocalBackend, ModelSpec};
 pub use retry::{LlmRetryConfig, RetryingLlmClient};
+pub use structured::{
+    DEFAULT_STRUCTURED_TOOL, LlmClientExt, StructuredOutputOptions, extract_json,
+};
 pub use swappable::SwappableLlm;
 pub use switcher::LlmSwitcherImpl;
@@ -36,6 +36,10 @@ struct ModelsDevModel {
 struct ModelsDevCost {
     input: f64,
     output: f64,
+    #[serde(default)]
+    cache_read: Option<f64>,
+    #[serde(default)]
+    cache_write: Option<f64>,
 }
 
 static DYNAMIC_PRICING_CACHE: Lazy<RwLock<DynamicPricingCache>> =
@@ -138,8 +142,8 @@ fn load_dynamic_pricing_cache() -> HashMap<String, ModelPricing> {
             let pricing = ModelPricing {
                 cost_per_1m_input: cost.input,
                 cost_per_1m_output: cost.output,
-                cache_read_per_1m: None,
-                cache_write_per_1m: None,
+                cache_read_per_1m: cost.cache_read,
+                cache_write_per_1m: cost.cache_write,
             };
             let key = normalize(model_key);
             by_model.insert(key.clone(), pricing);
@@ -167,8 +171,8 @@ fn load_dynamic_pricing_cache() -> HashMap<String, ModelPricing> {
             let pricing = ModelPricing {
                 cost_per_1m_input: cost.input,
                 cost_per_1m_output: cost.output,
-                cache_read_per_1m: None,
-                cache_write_per_1m: None,
+                cache_read_per_1m: cost.cache_read,
+                cache_write_per_1m: cost.cache_write,
             };
             let key = normalize(model_key);
             if !canonical_keys.contains(&key) {
@@ -251,7 +255,7 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
         return Some(ModelPricing {
             cost_per_1m_input: 0.4,
             cost_per_1m_output: 1.6,
-            cache_read_per_1m: None,
+            cache_read_per_1m: Some(0.04),
             cache_write_per_1m: None,
         });
     }
@@ -259,7 +263,7 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
         return Some(ModelPricing {
             cost_per_1m_input: 0.1,
             cost_per_1m_output: 0.4,
-            cache_read_per_1m: None,
+            cache_read_per_1m: Some(0.01),
             cache_write_per_1m: None,
         });
     }
@@ -267,7 +271,7 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
         return Some(ModelPricing {
             cost_per_1m_input: 1.25,
             cost_per_1m_output: 10.0,
-            cache_read_per_1m: None,
+            cache_read_per_1m: Some(0.125),
             cache_write_per_1m: None,
         });
     }
@@ -277,24 +281,24 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
         return Some(ModelPricing {
             cost_per_1m_input: 15.0,
             cost_per_1m_output: 75.0,
-            cache_read_per_1m: None,
-            cache_write_per_1m: None,
+            cache_read_per_1m: Some(1.5),
+            cache_write_per_1m: Some(18.75),
         });
     }
     if model_name.starts_with("claude-sonnet-4") {
         return Some(ModelPricing {
             cost_per_1m_input: 3.0,
             cost_per_1m_output: 15.0,
-            cache_read_per_1m: None,
-            cache_write_per_1m: None,
+            cache_read_per_1m: Some(0.3),
+            cache_write_per_1m: Some(3.75),
         });
     }
     if model_name.starts_with("claude-haiku-4") {
         return Some(ModelPricing {
             cost_per_1m_input: 0.8,
             cost_per_1m_output: 4.0,
-            cache_read_per_1m: None,
-            cache_write_per_1m: None,
+            cache_read_per_1m: Some(0.08),
+            cache_write_per_1m: Some(1.0),
         });
     }
 
@@ -303,7 +307,7 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
         return Some(ModelPricing {
             cost_per_1m_input: 0.55,
             cost_per_1m_output: 2.19,
-            cache_read_per_1m: None,
+            cache_read_per_1m: Some(0.14),
             cache_write_per_1m: None,
         });
     }
@@ -311,7 +315,7 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
         return Some(ModelPricing {
             cost_per_1m_input: 0.27,
             cost_per_1m_output: 1.10,
-            cache_read_per_1m: None,
+            cache_read_per_1m: Some(0.07),
             cache_write_per_1m: None,
         });
     }
@@ -320,14 +324,60 @@ pub fn get_pricing(model_name: &str) -> Option<ModelPricing> {
     None
 }
 
+impl ModelPricing {
+    /// Price of prompt tokens served from the prompt cache, defaulting to the input price.
+    pub fn cache_read_rate(&self) -> f64 {
+        self.cache_read_per_1m.unwrap_or(self.cost_per_1m_input)
+    }
+
+    /// Price of prompt tokens written to the prompt cache, defaulting to the input price.
+    pub fn cache_write_rate(&self) -> f64 {
+        self.cache_write_per_1m.unwrap_or(self.cost_per_1m_input)
+    }
+}
+
 /// Calculate cost in USD from token usage and model name.
 pub fn calculate_cost(model_name: &str, input_tokens: u32, output_tokens: u32) -> Option<f64> {
+    calculate_cost_with_cache(model_name, input_tokens, output_tokens, 0, 0)
+}
+
+/// Calculate cost in USD when part of the prompt was read from or written to
+/// the prompt cache. `input_tokens` includes the cached tokens.
+pub fn calculate_cost_with_cache(
+    model_name: &str,
+    input_tokens: u32,
+    output_tokens: u32,
+    cache_read_tokens: u32,
+    cache_write_tokens: u32,
+) -> Option<f64> {
     let pricing = get_pricing(model_name)?;
-    let cost = (input_tokens as f64 / 1_000_000.0) * pricing.cost_per_1m_input
+    let uncached =
+        input_tokens.saturating_sub(cache_read_tokens.saturating_add(cache_write_tokens));
+    let cost = (uncached as f64 / 1_000_000.0) * pricing.cost_per_1m_input
+        + (cache_read_tokens as f64 / 1_000_000.0) * pricing.cache_read_rate()
+        + (cache_write_tokens as f64 / 1_000_000.0) * pricing.cache_write_rate()
         + (output_tokens as f64 / 1_000_000.0) * pricing.cost_per_1m_output;
     Some(cost)
 }
 
+/// USD saved by prompt caching compared to sending the same prompt uncached.
+/// Cache write surcharges are subtracted, so the result can be negative.
+pub fn calculate_cache_savings(
+    model_name: &str,
+    cache_read_tokens: u32,
+    cache_write_tokens: u32,
+) -> Option<f64> {
+    if cache_read_tokens == 0 && cache_write_tokens == 0 {
+        return None;
+    }
+    let pricing = get_pricing(model_name)?;
+    let savings = (cache_read_tokens as f64 / 1_000_000.0)
+        * (pricing.cost_per_1m_input - pricing.cache_read_rate())
+        - (cache_write_tokens as f64 / 1_000_000.0)
+            * (pricing.cache_write_rate() - pricing.cost_per_1m_input);
+    Some(savings)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +444,35 @@ mod tests {
         assert_eq!(cost, 0.0);
     }
 
+    #[test]
+    fn test_calculate_cost_with_cache() {
+        let pricing = get_pricing("claude-sonnet-4-20250514").unwrap();
+        let expected = (200.0 / 1_000_000.0) * pricing.cost_per_1m_input
+            + (700.0 / 1_000_000.0) * pricing.cache_read_rate()
+            + (100.0 / 1_000_000.0) * pricing.cache_write_rate()
+            + (500.0 / 1_000_000.0) * pricing.cost_per_1m_output;
+        let cost =
+            calculate_cost_with_cache("claude-sonnet-4-20250514", 1000, 500, 700, 100).unwrap();
+        assert!(
+            (cost - expected).abs() < 1e-10,
+            "cost={cost}, expected={expected}"
+        );
+        assert_eq!(
+            calculate_cost_with_cache("claude-sonnet-4-20250514", 1000, 500, 0, 0),
+            calculate_cost("claude-sonnet-4-20250514", 1000, 500)
+        );
+    }
+
+    #[test]
+    fn test_calculate_cache_savings() {
+        assert!(calculate_cache_savings("claude-sonnet-4-20250514", 0, 0).is_none());
+        let pricing = get_pricing("claude-sonnet-4-20250514").unwrap();
+        let expected = pricing.cost_per_1m_input - pricing.cache_read_rate();
+        let savings = calculate_cache_savings("claude-sonnet-4-20250514", 1_000_000, 0).unwrap();
+        assert!((savings - expected).abs() < 1e-10);
+        assert!(calculate_cache_savings("opus", 1000, 0).is_none());
+    }
+
     #[test]
     fn test_canonical_provider_priority() {
         // Verify the canonical provider list is reasonable
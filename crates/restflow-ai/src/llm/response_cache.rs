@@ -0,0 +1,417 @@
+//! Content-addressed response cache for idempotent LLM calls.
+//!
+//! Summarization-style calls (memory consolidation, compaction, titles) are
+//! often repeated with byte-identical input. [`CachingLlmClient`] keys each
+//! non-streaming request on model + messages + tools + sampling settings and
+//! replays the stored response instead of paying for the call again. Only
+//! requests that pin the temperature to zero are cached; a request without a
+//! temperature uses the provider default, which usually samples.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::Result;
+use crate::llm::client::{
+    CompletionRequest, CompletionResponse, FinishReason, LlmClient, Message, StreamResult,
+};
+use crate::tools::ToolSchema;
+
+/// Limits for [`LlmResponseCache`].
+#[derive(Debug, Clone)]
+pub struct LlmResponseCacheConfig {
+    /// How long a stored response stays valid
+    pub ttl: Duration,
+    /// Maximum number of stored responses
+    pub max_entries: usize,
+    /// Maximum total size of stored response content in bytes
+    pub max_bytes: usize,
+}
+
+impl Default for LlmResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(24 * 60 * 60),
+            max_entries: 512,
+            max_bytes: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Hit/miss counters and current size of an [`LlmResponseCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LlmResponseCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    response: CompletionResponse,
+    size: usize,
+    created_at: Instant,
+}
+
+#[derive(Serialize)]
+struct CacheKeyInput<'a> {
+    model: &'a str,
+    messages: &'a [Message],
+    tools: &'a [ToolSchema],
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forced_tool: Option<&'a str>,
+}
+
+/// In-memory LLM response cache with TTL and size limits.
+#[derive(Debug)]
+pub struct LlmResponseCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    config: LlmResponseCacheConfig,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+static SHARED_CACHE: Lazy<Arc<LlmResponseCache>> =
+    Lazy::new(|| Arc::new(LlmResponseCache::new(LlmResponseCacheConfig::default())));
+
+impl LlmResponseCache {
+    pub fn new(config: LlmResponseCacheConfig) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            config,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Process-wide cache shared by background summarization jobs.
+    pub fn shared() -> Arc<Self> {
+        SHARED_CACHE.clone()
+    }
+
+    /// Cache key for `request` sent to `model`, or `None` unless the request
+    /// sets temperature to zero; anything else may sample and must not be
+    /// replayed.
+    pub fn key(model: &str, request: &CompletionRequest) -> Option<String> {
+        if request.temperature != Some(0.0) {
+            return None;
+        }
+        let input = CacheKeyInput {
+            model,
+            messages: &request.messages,
+            tools: &request.tools,
+            temperature: request.temperature,
+            max_tokens: request.max_tokens,
+            forced_tool: request.forced_tool.as_deref(),
+        };
+        let bytes = serde_json::to_vec(&input).ok()?;
+        Some(hex::encode(Sha256::digest(&bytes)))
+    }
+
+    /// Stored response for `key`, if present and not expired.
+    pub fn get(&self, key: &str) -> Option<CompletionResponse> {
+        let mut entries = self.entries.lock();
+        let expired = match entries.get(key) {
+            Some(entry) if entry.created_at.elapsed() < self.config.ttl => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Some(entry.response.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            entries.remove(key);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+
+    /// Store a completed response. Truncated or failed responses are skipped,
+    /// as are responses larger than the whole cache.
+    pub fn put(&self, key: String, response: &CompletionResponse) {
+        if response.finish_reason != FinishReason::Stop {
+            return;
+        }
+        let size = response_size(response);
+        if size > self.config.max_bytes || self.config.max_entries == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock();
+        entries.remove(&key);
+        let ttl = self.config.ttl;
+        entries.retain(|_, entry| entry.created_at.elapsed() < ttl);
+
+        let mut bytes: usize = entries.values().map(|entry| entry.size).sum();
+        while !entries.is_empty()
+            && (entries.len() >= self.config.max_entries || bytes + size > self.config.max_bytes)
+        {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.created_at)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(removed) = entries.remove(&oldest) {
+                bytes -= removed.size;
+            }
+        }
+
+        entries.insert(
+            key,
+            CacheEntry {
+                response: response.clone(),
+                size,
+                created_at: Instant::now(),
+            },
+        );
+    }
+
+    pub fn stats(&self) -> LlmResponseCacheStats {
+        let entries = self.entries.lock();
+        LlmResponseCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: entries.len(),
+            bytes: entries.values().map(|entry| entry.size).sum(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+}
+
+fn response_size(response: &CompletionResponse) -> usize {
+    response.content.as_ref().map_or(0, String::len)
+        + response
+            .tool_calls
+            .iter()
+            .map(|call| call.id.len() + call.name.len() + call.arguments.to_string().len())
+            .sum::<usize>()
+}
+
+/// Decorator that serves repeated non-streaming requests from an
+/// [`LlmResponseCache`].
+///
+/// Cache hits return the stored response without usage, since no tokens were
+/// spent. Streaming requests always go to the inner client.
+pub struct CachingLlmClient {
+    inner: Arc<dyn LlmClient>,
+    cache: Arc<LlmResponseCache>,
+}
+
+impl CachingLlmClient {
+    pub fn new(inner: Arc<dyn LlmClient>, cache: Arc<LlmResponseCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Wrap `inner` with the process-wide [`LlmResponseCache::shared`] cache.
+    pub fn shared(inner: Arc<dyn LlmClient>) -> Self {
+        Self::new(inner, LlmResponseCache::shared())
+    }
+}
+
+#[async_trait]
+impl LlmClient for CachingLlmClient {
+    fn provider(&self) -> &str {
+        self.inner.provider()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let Some(key) = LlmResponseCache::key(self.inner.model(), &request) else {
+            return self.inner.complete(request).await;
+        };
+        if let Some(mut response) = self.cache.get(&key) {
+            response.usage = None;
+            return Ok(response);
+        }
+
+        let response = self.inner.complete(request).await?;
+        self.cache.put(key, &response);
+        Ok(response)
+    }
+
+    fn complete_stream(&self, request: CompletionRequest) -> StreamResult {
+        self.inner.complete_stream(request)
+    }
+
+    async fn count_tokens(&self, request: &CompletionRequest) -> Result<Option<u32>> {
+        self.inner.count_tokens(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::client::TokenUsage;
+    use std::sync::atomic::AtomicUsize;
+
+    struct CountingClient {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LlmClient for CountingClient {
+        fn provider(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            "mock-model"
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(response(&format!("summary {call}")))
+        }
+
+        fn complete_stream(&self, _request: CompletionRequest) -> StreamResult {
+            Box::pin(futures::stream::empty())
+        }
+    }
+
+    fn response(content: &str) -> CompletionResponse {
+        CompletionResponse {
+            content: Some(content.to_string()),
+            tool_calls: vec![],
+            finish_reason: FinishReason::Stop,
+            usage: Some(TokenUsage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+                cost_usd: Some(0.01),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn request(text: &str) -> CompletionRequest {
+        CompletionRequest::new(vec![Message::system("Summarize"), Message::user(text)])
+            .with_temperature(0.0)
+    }
+
+    #[tokio::test]
+    async fn test_caching_client_replays_identical_requests() {
+        let inner = Arc::new(CountingClient {
+            calls: AtomicUsize::new(0),
+        });
+        let cache = Arc::new(LlmResponseCache::new(LlmResponseCacheConfig::default()));
+        let client = CachingLlmClient::new(inner.clone(), cache.clone());
+
+        let first = client.complete(request("notes")).await.unwrap();
+        let second = client.complete(request("notes")).await.unwrap();
+        let other = client.complete(request("other notes")).await.unwrap();
+
+        assert_eq!(first.content, second.content);
+        assert!(first.usage.is_some());
+        assert!(second.usage.is_none());
+        assert_ne!(first.content, other.content);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_caching_client_skips_sampled_requests() {
+        let inner = Arc::new(CountingClient {
+            calls: AtomicUsize::new(0),
+        });
+        let client = CachingLlmClient::new(
+            inner.clone(),
+            Arc::new(LlmResponseCache::new(LlmResponseCacheConfig::default())),
+        );
+
+        for _ in 0..2 {
+            client
+                .complete(request("notes").with_temperature(0.7))
+                .await
+                .unwrap();
+        }
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        // No temperature means the provider default, which may sample.
+        let unpinned = CompletionRequest::new(vec![Message::user("notes")]);
+        assert!(LlmResponseCache::key("model-a", &unpinned).is_none());
+        for _ in 0..2 {
+            client.complete(unpinned.clone()).await.unwrap();
+        }
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_key_depends_on_model_and_tools() {
+        let base = request("notes");
+        let key = LlmResponseCache::key("model-a", &base).unwrap();
+        assert_eq!(LlmResponseCache::key("model-a", &base).unwrap(), key);
+        assert_ne!(LlmResponseCache::key("model-b", &base).unwrap(), key);
+
+        let with_tools = base.with_tools(vec![ToolSchema {
+            name: "read".to_string(),
+            description: "Read a file".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }]);
+        assert_ne!(LlmResponseCache::key("model-a", &with_tools).unwrap(), key);
+    }
+
+    #[test]
+    fn test_cache_expires_and_evicts_oldest() {
+        let cache = LlmResponseCache::new(LlmResponseCacheConfig {
+            ttl: Duration::ZERO,
+            ..LlmResponseCacheConfig::default()
+        });
+        cache.put("a".to_string(), &response("one"));
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.stats().entries, 0);
+
+        let cache = LlmResponseCache::new(LlmResponseCacheConfig {
+            max_entries: 2,
+            ..LlmResponseCacheConfig::default()
+        });
+        cache.put("a".to_string(), &response("one"));
+        cache.put("b".to_string(), &response("two"));
+        cache.put("c".to_string(), &response("three"));
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("c").is_some());
+
+        let cache = LlmResponseCache::new(LlmResponseCacheConfig {
+            max_bytes: 8,
+            ..LlmResponseCacheConfig::default()
+        });
+        cache.put("a".to_string(), &response("12345"));
+        cache.put("b".to_string(), &response("67890"));
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.stats().bytes, 5);
+        cache.put("c".to_string(), &response("far too long"));
+        assert!(cache.get("c").is_none());
+    }
+
+    #[test]
+    fn test_cache_skips_incomplete_responses() {
+        let cache = LlmResponseCache::new(LlmResponseCacheConfig::default());
+        let mut truncated = response("partial");
+        truncated.finish_reason = FinishReason::MaxTokens;
+        cache.put("a".to_string(), &truncated);
+        assert!(cache.get("a").is_none());
+    }
+}
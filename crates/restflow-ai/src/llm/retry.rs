@@ -149,6 +149,10 @@ impl LlmClient for RetryingLlmClient {
         }))
     }
 
+    async fn count_tokens(&self, request: &CompletionRequest) -> crate::error::Result<Option<u32>> {
+        self.inner.count_tokens(request).await
+    }
+
     fn complete_stream(&self, request: CompletionRequest) -> StreamResult {
         let inner = Arc::clone(&self.inner);
         let config = self.config.clone();
@@ -245,6 +249,7 @@ mod tests {
                     completion_tokens: 1,
                     total_tokens: 2,
                     cost_usd: None,
+                    ..Default::default()
                 }),
             })
         }
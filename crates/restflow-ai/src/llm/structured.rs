@@ -0,0 +1,338 @@
+//! Schema-constrained structured output.
+//!
+//! [`LlmClientExt::complete_structured`] asks the model for a value of type
+//! `T` by forcing a call to a single tool whose parameters are `T`'s JSON
+//! schema. Providers without native tool forcing (CLI clients) fall back to
+//! a JSON reply, which is extracted from the text. Output that does not
+//! deserialize into `T` is sent back to the model with the error for repair.
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::error::{AiError, Result};
+use crate::tools::ToolSchema;
+
+use super::{CompletionRequest, CompletionResponse, LlmClient, Message};
+
+/// Tool name used for structured replies unless overridden.
+pub const DEFAULT_STRUCTURED_TOOL: &str = "respond";
+
+/// Options for [`LlmClientExt::complete_structured_with`].
+#[derive(Debug, Clone)]
+pub struct StructuredOutputOptions {
+    /// Name of the forced tool carrying the reply.
+    pub tool_name: String,
+    /// Description of the forced tool, shown to the model.
+    pub description: String,
+    /// Repair round-trips allowed after the first attempt.
+    pub max_repairs: usize,
+}
+
+impl Default for StructuredOutputOptions {
+    fn default() -> Self {
+        Self {
+            tool_name: DEFAULT_STRUCTURED_TOOL.to_string(),
+            description: "Return the final answer as structured data.".to_string(),
+            max_repairs: 2,
+        }
+    }
+}
+
+/// Structured-output helpers available on every [`LlmClient`].
+#[async_trait]
+pub trait LlmClientExt: LlmClient {
+    /// Complete `request` and deserialize the reply into `T`.
+    async fn complete_structured<T>(&self, request: CompletionRequest) -> Result<T>
+    where
+        T: JsonSchema + DeserializeOwned + Send,
+    {
+        self.complete_structured_with(request, StructuredOutputOptions::default())
+            .await
+    }
+
+    /// Like [`complete_structured`](Self::complete_structured) with explicit options.
+    async fn complete_structured_with<T>(
+        &self,
+        request: CompletionRequest,
+        options: StructuredOutputOptions,
+    ) -> Result<T>
+    where
+        T: JsonSchema + DeserializeOwned + Send,
+    {
+        let schema = OutputSchema::for_type::<T>();
+        let mut request = schema.prepare(request, &options);
+
+        let mut attempt = 0;
+        loop {
+            let response = self.complete(request.clone()).await?;
+            let error = match schema.decode::<T>(&response, &options.tool_name) {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if attempt >= options.max_repairs {
+                return Err(AiError::InvalidFormat(format!(
+                    "structured output did not match schema after {} attempt(s): {error}",
+                    attempt + 1
+                )));
+            }
+            attempt += 1;
+            tracing::debug!(attempt, error = %error, "Repairing structured output");
+            push_repair_turn(&mut request, response, &options.tool_name, &error);
+        }
+    }
+}
+
+impl<C: LlmClient + ?Sized> LlmClientExt for C {}
+
+/// JSON schema for `T`, wrapped in an object when `T` is not one, since
+/// tool parameters must be objects.
+struct OutputSchema {
+    parameters: Value,
+    wrapped: bool,
+}
+
+impl OutputSchema {
+    fn for_type<T: JsonSchema>() -> Self {
+        let mut schema = schemars::schema_for!(T).to_value();
+        if let Some(object) = schema.as_object_mut() {
+            object.remove("$schema");
+        }
+        if schema.get("type").and_then(Value::as_str) == Some("object") {
+            return Self {
+                parameters: schema,
+                wrapped: false,
+            };
+        }
+        let definitions = schema
+            .as_object_mut()
+            .and_then(|object| object.remove("$defs"));
+        let mut parameters = json!({
+            "type": "object",
+            "properties": { "value": schema },
+            "required": ["value"],
+        });
+        if let Some(definitions) = definitions {
+            parameters["$defs"] = definitions;
+        }
+        Self {
+            parameters,
+            wrapped: true,
+        }
+    }
+
+    fn prepare(
+        &self,
+        request: CompletionRequest,
+        options: &StructuredOutputOptions,
+    ) -> CompletionRequest {
+        let mut request = request
+            .with_tools(vec![ToolSchema {
+                name: options.tool_name.clone(),
+                description: options.description.clone(),
+                parameters: self.parameters.clone(),
+            }])
+            .with_forced_tool(options.tool_name.clone());
+        request.messages.push(Message::system(format!(
+            "Answer by calling the `{}` tool. If tools are unavailable, reply with only a JSON \
+             object matching this schema:\n{}",
+            options.tool_name, self.parameters
+        )));
+        request
+    }
+
+    fn decode<T: DeserializeOwned>(
+        &self,
+        response: &CompletionResponse,
+        tool_name: &str,
+    ) -> std::result::Result<T, String> {
+        let raw = match response
+            .tool_calls
+            .iter()
+            .find(|call| call.name == tool_name)
+        {
+            Some(call) => match &call.arguments {
+                Value::String(text) => extract_json(text)
+                    .ok_or_else(|| "tool arguments are not valid JSON".to_string())?,
+                arguments => arguments.clone(),
+            },
+            None => {
+                let text = response.content.as_deref().unwrap_or_default();
+                extract_json(text)
+                    .ok_or_else(|| "reply did not contain a JSON value".to_string())?
+            }
+        };
+        let value = match (self.wrapped, raw) {
+            (true, Value::Object(mut object)) if object.contains_key("value") => {
+                object.remove("value").unwrap_or(Value::Null)
+            }
+            (_, raw) => raw,
+        };
+        serde_json::from_value(value).map_err(|error| error.to_string())
+    }
+}
+
+/// Feed a rejected reply back to the model along with the decode error.
+fn push_repair_turn(
+    request: &mut CompletionRequest,
+    response: CompletionResponse,
+    tool_name: &str,
+    error: &str,
+) {
+    let feedback = format!(
+        "The output did not match the required schema: {error}. Call `{tool_name}` again with \
+         corrected arguments."
+    );
+    match response
+        .tool_calls
+        .iter()
+        .find(|call| call.name == tool_name)
+    {
+        Some(call) => {
+            let call_id = call.id.clone();
+            request.messages.push(Message::assistant_with_tool_calls(
+                response.content,
+                response.tool_calls,
+            ));
+            request
+                .messages
+                .push(Message::tool_result(call_id, feedback));
+        }
+        None => {
+            request
+                .messages
+                .push(Message::assistant(response.content.unwrap_or_default()));
+            request.messages.push(Message::user(feedback));
+        }
+    }
+}
+
+/// Extract a JSON value from model text, tolerating code fences and prose
+/// around a single JSON object or array.
+pub fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+    let unfenced = strip_code_fence(trimmed);
+    if let Ok(value) = serde_json::from_str(unfenced) {
+        return Some(value);
+    }
+    [('{', '}'), ('[', ']')]
+        .into_iter()
+        .filter_map(|(open, close)| {
+            let start = unfenced.find(open)?;
+            let end = unfenced.rfind(close)?;
+            (start < end).then(|| (start, &unfenced[start..=end]))
+        })
+        .min_by_key(|(start, _)| *start)
+        .and_then(|(_, candidate)| serde_json::from_str(candidate).ok())
+}
+
+fn strip_code_fence(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("```") else {
+        return text;
+    };
+    let body = rest.split_once('\n').map_or("", |(_, body)| body);
+    body.trim_end().strip_suffix("```").unwrap_or(body).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{MockLlmClient, MockStep};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Verdict {
+        approved: bool,
+        reason: String,
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new(vec![Message::user("review the change")])
+    }
+
+    #[test]
+    fn extract_json_handles_fences_and_prose() {
+        assert_eq!(extract_json(r#"{"a": 1}"#), Some(json!({ "a": 1 })));
+        assert_eq!(
+            extract_json("```json\n{\"a\": 1}\n```"),
+            Some(json!({ "a": 1 }))
+        );
+        assert_eq!(
+            extract_json("Sure! Here it is: [1, 2] hope that helps"),
+            Some(json!([1, 2]))
+        );
+        assert_eq!(extract_json("no json here"), None);
+    }
+
+    #[test]
+    fn non_object_schemas_are_wrapped() {
+        let schema = OutputSchema::for_type::<Vec<String>>();
+        assert!(schema.wrapped);
+        assert_eq!(schema.parameters["required"], json!(["value"]));
+
+        let schema = OutputSchema::for_type::<Verdict>();
+        assert!(!schema.wrapped);
+        assert!(schema.parameters.get("$schema").is_none());
+    }
+
+    #[tokio::test]
+    async fn complete_structured_reads_forced_tool_call() {
+        let client = MockLlmClient::from_steps(
+            "mock",
+            vec![MockStep::tool_call(
+                "call-1",
+                DEFAULT_STRUCTURED_TOOL,
+                json!({ "approved": true, "reason": "looks good" }),
+            )],
+        );
+
+        let verdict: Verdict = client.complete_structured(request()).await.unwrap();
+        assert_eq!(
+            verdict,
+            Verdict {
+                approved: true,
+                reason: "looks good".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn complete_structured_repairs_invalid_output() {
+        let client = MockLlmClient::from_steps(
+            "mock",
+            vec![
+                MockStep::text("I think it is fine."),
+                MockStep::text("```json\n{\"approved\": false, \"reason\": \"no tests\"}\n```"),
+            ],
+        );
+
+        let verdict: Verdict = client.complete_structured(request()).await.unwrap();
+        assert!(!verdict.approved);
+        assert_eq!(verdict.reason, "no tests");
+    }
+
+    #[tokio::test]
+    async fn complete_structured_gives_up_after_max_repairs() {
+        let client = MockLlmClient::from_steps(
+            "mock",
+            vec![
+                MockStep::text(r#"{"approved": "yes"}"#),
+                MockStep::text(r#"{"approved": "still yes"}"#),
+            ],
+        );
+        let options = StructuredOutputOptions {
+            max_repairs: 1,
+            ..Default::default()
+        };
+
+        let error = client
+            .complete_structured_with::<Verdict>(request(), options)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AiError::InvalidFormat(_)));
+    }
+}
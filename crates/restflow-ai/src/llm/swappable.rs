@@ -64,4 +64,12 @@ impl LlmClient for SwappableLlm {
         };
         client.complete_stream(request)
     }
+
+    async fn count_tokens(&self, request: &CompletionRequest) -> Result<Option<u32>> {
+        let client = {
+            let guard = self.inner.read();
+            guard.clone()
+        };
+        client.count_tokens(request).await
+    }
 }
@@ -86,6 +86,7 @@ async fn stress_prune_large_conversation() {
         min_prune_savings_tokens: 100,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 20_000,
+        ..Default::default()
     };
 
     let tokens_before = estimate_tokens(&msgs);
@@ -170,6 +171,7 @@ async fn stress_compact_multi_cycle() {
         min_prune_savings_tokens: 10,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 200,
+        ..Default::default()
     };
 
     let cycles = 10;
@@ -396,6 +398,7 @@ async fn stress_full_pipeline_prune_then_compact() {
         min_prune_savings_tokens: 50,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 500,
+        ..Default::default()
     };
 
     let tokens_initial = estimate_tokens(&msgs);
@@ -471,6 +474,7 @@ async fn stress_compact_cooldown_prevents_loops() {
         min_prune_savings_tokens: 10,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 200,
+        ..Default::default()
     };
 
     let mut estimator = TokenEstimator::default();
@@ -547,6 +551,7 @@ async fn stress_alternating_grow_prune_compact() {
         min_prune_savings_tokens: 50,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 800,
+        ..Default::default()
     };
 
     let agent_iterations = 50;
@@ -676,6 +681,7 @@ fn stress_prune_idempotent_multiple_passes() {
         min_prune_savings_tokens: 100,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 20_000,
+        ..Default::default()
     };
 
     // First pass should apply.
@@ -708,6 +714,7 @@ async fn stress_compact_error_resilience() {
         min_prune_savings_tokens: 10,
         compact_trigger_ratio: 0.90,
         compact_preserve_tokens: 100,
+        ..Default::default()
     };
 
     for trial in 0..20 {
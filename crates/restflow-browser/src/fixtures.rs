@@ -0,0 +1,253 @@
+//! Recorded network fixtures for deterministic browser sessions.
+//!
+//! A session in [`NetworkMode::Record`] stores every response its pages load
+//! in `network_fixtures.json`. A session in [`NetworkMode::Replay`] intercepts
+//! requests with the CDP `Fetch` domain and fulfils them from that file, so
+//! action plans can be replayed in CI without network access. Requests with
+//! no recorded response fail as if the network were down.
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub(crate) const FIXTURES_FILE_NAME: &str = "network_fixtures.json";
+const FIXTURES_VERSION: u32 = 1;
+
+/// How a session's pages reach the network.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkMode {
+    #[default]
+    Live,
+    /// Use the network and save every response as a fixture.
+    Record,
+    /// Serve recorded fixtures and never touch the network.
+    Replay,
+}
+
+/// One recorded response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkFixture {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body_base64: String,
+}
+
+impl NetworkFixture {
+    pub(crate) fn new(
+        method: &str,
+        url: &str,
+        status: u16,
+        headers: BTreeMap<String, String>,
+        body: &[u8],
+    ) -> Self {
+        Self {
+            method: method.to_ascii_uppercase(),
+            url: normalize_url(url),
+            status,
+            headers,
+            body_base64: BASE64_STANDARD.encode(body),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NetworkFixtureFile {
+    version: u32,
+    #[serde(default)]
+    fixtures: Vec<NetworkFixture>,
+}
+
+/// Fixture file shared by every page of a session.
+pub(crate) struct FixtureStore {
+    path: PathBuf,
+    fixtures: Mutex<Vec<NetworkFixture>>,
+    /// Replay position per request key, so repeated requests are answered in
+    /// the order they were recorded.
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+impl FixtureStore {
+    /// Open the fixture file in `dir`; a missing file starts an empty set.
+    pub(crate) fn open(dir: &Path) -> Result<Self> {
+        let path = dir.join(FIXTURES_FILE_NAME);
+        let fixtures = if path.exists() {
+            let raw = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let file: NetworkFixtureFile = serde_json::from_slice(&raw)
+                .with_context(|| format!("Invalid network fixtures in {}", path.display()))?;
+            file.fixtures
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            path,
+            fixtures: Mutex::new(fixtures),
+            cursors: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        lock(&self.fixtures).len()
+    }
+
+    /// Next recorded response for a request. Exact URL matches win; otherwise
+    /// the query string is ignored so cache-busting parameters still match.
+    pub(crate) fn next_response(&self, method: &str, url: &str) -> Option<NetworkFixture> {
+        let method = method.to_ascii_uppercase();
+        let url = normalize_url(url);
+        let fixtures = lock(&self.fixtures);
+
+        let exact: Vec<&NetworkFixture> = fixtures
+            .iter()
+            .filter(|fixture| fixture.method == method && fixture.url == url)
+            .collect();
+        let (key, candidates) = if exact.is_empty() {
+            let stripped = strip_query(&url);
+            let loose: Vec<&NetworkFixture> = fixtures
+                .iter()
+                .filter(|fixture| fixture.method == method && strip_query(&fixture.url) == stripped)
+                .collect();
+            (format!("{method} {stripped}?*"), loose)
+        } else {
+            (format!("{method} {url}"), exact)
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let mut cursors = lock(&self.cursors);
+        let cursor = cursors.entry(key).or_insert(0);
+        // Once the recording is exhausted keep answering with the last response.
+        let fixture = candidates[(*cursor).min(candidates.len() - 1)].clone();
+        *cursor += 1;
+        Some(fixture)
+    }
+
+    pub(crate) fn record(&self, fixture: NetworkFixture) {
+        lock(&self.fixtures).push(fixture);
+    }
+
+    pub(crate) fn save(&self) -> Result<()> {
+        let file = NetworkFixtureFile {
+            version: FIXTURES_VERSION,
+            fixtures: lock(&self.fixtures).clone(),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&file)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+/// Whether a request URL can be recorded or replayed at all.
+pub(crate) fn is_fixture_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn normalize_url(url: &str) -> String {
+    url.split('#').next().unwrap_or(url).to_string()
+}
+
+fn strip_query(url: &str) -> &str {
+    url.split('?').next().unwrap_or(url)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn fixture(url: &str, body: &str) -> NetworkFixture {
+        NetworkFixture::new("get", url, 200, BTreeMap::new(), body.as_bytes())
+    }
+
+    #[test]
+    fn fixtures_round_trip_through_file() {
+        let temp = tempdir().unwrap();
+        let store = FixtureStore::open(temp.path()).unwrap();
+        assert_eq!(store.len(), 0);
+        store.record(fixture("https://example.com/#top", "<html></html>"));
+        store.save().unwrap();
+
+        let reopened = FixtureStore::open(temp.path()).unwrap();
+        assert_eq!(reopened.len(), 1);
+        let served = reopened
+            .next_response("GET", "https://example.com/")
+            .unwrap();
+        assert_eq!(
+            BASE64_STANDARD.decode(served.body_base64).unwrap(),
+            b"<html></html>"
+        );
+    }
+
+    #[test]
+    fn repeated_requests_replay_in_recorded_order() {
+        let temp = tempdir().unwrap();
+        let store = FixtureStore::open(temp.path()).unwrap();
+        store.record(fixture("https://api.test/items", "first"));
+        store.record(fixture("https://api.test/items", "second"));
+
+        let bodies: Vec<String> = (0..3)
+            .map(|_| {
+                store
+                    .next_response("GET", "https://api.test/items")
+                    .unwrap()
+                    .body_base64
+            })
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                BASE64_STANDARD.encode("first"),
+                BASE64_STANDARD.encode("second"),
+                BASE64_STANDARD.encode("second"),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_string_falls_back_to_path_match() {
+        let temp = tempdir().unwrap();
+        let store = FixtureStore::open(temp.path()).unwrap();
+        store.record(fixture("https://api.test/feed?ts=1", "feed"));
+
+        assert!(
+            store
+                .next_response("GET", "https://api.test/feed?ts=2")
+                .is_some()
+        );
+        assert!(
+            store
+                .next_response("POST", "https://api.test/feed?ts=1")
+                .is_none()
+        );
+        assert!(
+            store
+                .next_response("GET", "https://api.test/other")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn only_http_urls_are_fixtures() {
+        assert!(is_fixture_url("https://example.com"));
+        assert!(!is_fixture_url("data:text/plain,hi"));
+        assert!(!is_fixture_url("about:blank"));
+    }
+}
@@ -5,8 +5,30 @@
 //! (without Playwright runtime dependency) and exposes:
 //! - Runtime probing
 //! - Session lifecycle management
-//! - JavaScript/TypeScript execution in page context
+//! - JavaScript/TypeScript execution in page context, with JSON arguments and
+//!   `rf.emit` callbacks streamed back while the script runs
 //! - Structured browser action plans
+//! - Provisioning of a bundled chrome-headless-shell runtime
+//! - Per-domain navigation policy with SSRF checks
+//! - Artifact retention quotas with automatic rotation
+//! - Network record/replay from fixtures for deterministic runs
+//! - A local test site for integration tests (`test-harness` feature)
+
+mod fixtures;
+mod policy;
+mod provision;
+mod retention;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
+
+pub use fixtures::{NetworkFixture, NetworkMode};
+pub use policy::NavigationPolicy;
+pub use retention::{ArtifactPurgeReport, ArtifactRetentionPolicy, ArtifactRetentionStats};
+
+pub use provision::{
+    ChromiumProvisioner, HeadlessShellRelease, InstalledRuntime, PINNED_HEADLESS_SHELL_VERSION,
+    ProvisionEvent,
+};
 
 use anyhow::{Result, anyhow, bail};
 use async_trait::async_trait;
@@ -15,7 +37,7 @@ use chrono::Utc;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
@@ -28,10 +50,18 @@ use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
 use uuid::Uuid;
 
+use crate::fixtures::{FixtureStore, is_fixture_url};
+
 const DEFAULT_TIMEOUT_SECS: u64 = 120;
 const CDP_POLL_INTERVAL_MS: u64 = 100;
 const CDP_SHUTDOWN_TIMEOUT_SECS: u64 = 5;
 const NETWORK_IDLE_GRACE_MS: u64 = 500;
+const DEFAULT_MAX_PARALLEL_TABS: usize = 4;
+const RUNTIME_STATS_LOCK_TIMEOUT_SECS: u64 = 2;
+const ISOLATED_WORLD_NAME: &str = "restflow_isolated";
+const RESTRICTED_WORLD_NAME: &str = "restflow_restricted";
+const SCRIPT_BINDING_PREFIX: &str = "__restflowEmit_";
+const MAX_COLLECTED_SCRIPT_EVENTS: usize = 256;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -56,6 +86,31 @@ pub enum ScriptRuntime {
     Node,
 }
 
+/// Where user scripts and `evaluate` expressions run.
+///
+/// `isolated` runs in a separate JavaScript world (`Page.createIsolatedWorld`)
+/// that shares the DOM but not page globals. `restricted` additionally blocks
+/// cookie, storage and credential APIs inside that world.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptSandbox {
+    #[default]
+    None,
+    Isolated,
+    Restricted,
+}
+
+impl ScriptSandbox {
+    /// Name of the world scripts run in, reported alongside results.
+    pub fn world_name(self) -> &'static str {
+        match self {
+            Self::None => "main",
+            Self::Isolated => ISOLATED_WORLD_NAME,
+            Self::Restricted => RESTRICTED_WORLD_NAME,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum InputModifier {
@@ -99,6 +154,46 @@ impl RuntimeProbe {
             notes: Vec::new(),
         }
     }
+
+    /// Steps that fix whatever this probe found missing, most important first.
+    pub fn remediation(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        if !self.chromium_cache_detected {
+            steps.push(
+                "Install the bundled chrome-headless-shell with the browser tool's install_runtime action, install Chrome/Chromium, or set RESTFLOW_CHROMIUM_PATH to an existing executable."
+                    .to_string(),
+            );
+        }
+        if !self.node_available {
+            steps.push(
+                "Install Node.js to run scripts with the node runtime. Page-context scripts and action plans work without it."
+                    .to_string(),
+            );
+        } else if !self.node_typescript_available {
+            steps.push(format!(
+                "Upgrade Node.js ({}) to 22.6 or newer to run TypeScript scripts with the node runtime.",
+                self.node_version.as_deref().unwrap_or("unknown version")
+            ));
+        }
+        steps
+    }
+}
+
+/// Runtime probe together with the steps needed to make the browser usable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserDoctorReport {
+    pub probe: RuntimeProbe,
+    /// Actionable fixes for anything missing; empty when nothing is.
+    pub remediation: Vec<String>,
+}
+
+impl From<RuntimeProbe> for BrowserDoctorReport {
+    fn from(probe: RuntimeProbe) -> Self {
+        Self {
+            remediation: probe.remediation(),
+            probe,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +202,16 @@ pub struct NewSessionRequest {
     pub browser: BrowserKind,
     #[serde(default = "default_headless")]
     pub headless: bool,
+    /// Attach to an already running browser instead of launching one.
+    /// Accepts a `ws://` debugger URL or an `http://host:port` endpoint.
+    #[serde(default, alias = "connect_over_cdp")]
+    pub cdp_endpoint: Option<String>,
+    #[serde(default)]
+    pub network_mode: NetworkMode,
+    /// Directory holding `network_fixtures.json`. Required for replay; record
+    /// mode defaults to the session directory.
+    #[serde(default)]
+    pub fixtures_dir: Option<String>,
 }
 
 impl Default for NewSessionRequest {
@@ -114,6 +219,9 @@ impl Default for NewSessionRequest {
         Self {
             browser: BrowserKind::Chromium,
             headless: true,
+            cdp_endpoint: None,
+            network_mode: NetworkMode::Live,
+            fixtures_dir: None,
         }
     }
 }
@@ -127,6 +235,14 @@ pub struct BrowserSession {
     pub session_dir: String,
     pub profile_dir: String,
     pub artifacts_dir: String,
+    /// Remote CDP endpoint when the session attaches to an existing browser.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cdp_endpoint: Option<String>,
+    #[serde(default)]
+    pub network_mode: NetworkMode,
+    /// Fixture directory used by record and replay modes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixtures_dir: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,8 +257,26 @@ pub struct RunScriptRequest {
     pub timeout_secs: u64,
     #[serde(default)]
     pub cwd: Option<String>,
+    #[serde(default)]
+    pub sandbox: ScriptSandbox,
+    /// JSON object exposed to the script as `args`.
+    #[serde(default)]
+    pub args: Option<Value>,
+}
+
+/// Event a page script sent back through `rf.emit` while it was running.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScriptEvent {
+    pub session_id: String,
+    /// Position of the event within its script run, starting at 0.
+    pub sequence: u64,
+    /// Milliseconds since the script started.
+    pub offset_ms: u64,
+    pub payload: Value,
 }
 
+pub type ScriptEventSender = tokio::sync::mpsc::UnboundedSender<ScriptEvent>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunActionsRequest {
     pub session_id: String,
@@ -167,12 +301,18 @@ pub enum BrowserAction {
         selector: String,
         #[serde(default)]
         timeout_ms: Option<u64>,
+        /// Plain-language description of the target, used to locate it from
+        /// a screenshot when the selector no longer matches.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
     },
     Fill {
         selector: String,
         text: String,
         #[serde(default)]
         timeout_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
     },
     Type {
         selector: String,
@@ -260,8 +400,24 @@ pub enum BrowserAction {
         #[serde(default)]
         full_page: bool,
     },
+    /// Write `text` to the clipboard through `navigator.clipboard`.
+    SetClipboard { text: String },
+    /// Read the clipboard text, e.g. after triggering a page's copy button.
+    ReadClipboard,
     Evaluate {
         expression: String,
+        #[serde(default)]
+        sandbox: ScriptSandbox,
+        /// JSON object bound to `args` while the expression is evaluated.
+        #[serde(default)]
+        args: Option<Value>,
+    },
+    /// Run each branch in its own tab concurrently. Branches execute their
+    /// actions in order; `max_concurrency` is clamped to the executor cap.
+    Parallel {
+        branches: Vec<Vec<BrowserAction>>,
+        #[serde(default)]
+        max_concurrency: Option<usize>,
     },
 }
 
@@ -273,6 +429,105 @@ pub struct BrowserExecutionResult {
     pub stdout: String,
     pub stderr: String,
     pub payload: Option<Value>,
+    /// Path to the `trace.json` timeline recorded for action runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_path: Option<String>,
+}
+
+/// Timeline of a single `run_actions` call, written to `trace.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserTrace {
+    pub session_id: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub actions: Vec<BrowserTraceAction>,
+    /// CDP `Performance.getMetrics` snapshot taken when the run ended.
+    #[serde(default)]
+    pub performance_metrics: Option<serde_json::Map<String, Value>>,
+    #[serde(default)]
+    pub failure: Option<BrowserFailureSnapshot>,
+}
+
+/// Resource usage snapshot for a browser session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserSessionStats {
+    pub session_id: String,
+    pub uptime_ms: i64,
+    /// Total size of files under the session artifacts directory.
+    pub artifacts_bytes: u64,
+    /// Live Chromium metrics, absent when no runtime has been started.
+    #[serde(default)]
+    pub runtime: Option<BrowserRuntimeStats>,
+}
+
+/// Chromium process metrics for a running session runtime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BrowserRuntimeStats {
+    #[serde(default)]
+    pub browser_pid: Option<u32>,
+    /// Browser, renderer, GPU and utility processes reported by CDP.
+    pub process_count: usize,
+    /// Resident set size summed over all Chromium processes.
+    #[serde(default)]
+    pub rss_bytes: Option<u64>,
+    pub open_targets: usize,
+    pub runtime_uptime_ms: u64,
+    /// True when the runtime was busy running actions and could not be queried.
+    #[serde(default)]
+    pub busy: bool,
+}
+
+/// Page state captured when an action fails, embedded in the error payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserFailureSnapshot {
+    pub action_index: usize,
+    pub action_type: String,
+    pub error: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub screenshot_path: Option<String>,
+    /// JSON file holding this snapshot next to the screenshot.
+    #[serde(default)]
+    pub snapshot_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserTraceAction {
+    pub index: usize,
+    pub action_type: String,
+    /// Milliseconds since the start of the run.
+    pub started_offset_ms: u64,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub screenshot_path: Option<String>,
+}
+
+impl BrowserTrace {
+    fn new(session_id: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            started_at: Utc::now().to_rfc3339(),
+            duration_ms: 0,
+            success: false,
+            actions: Vec::new(),
+            performance_metrics: None,
+            failure: None,
+        }
+    }
+
+    fn write(&self, trace_dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(trace_dir)?;
+        let path = trace_dir.join("trace.json");
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
 }
 
 impl BrowserExecutionResult {
@@ -302,6 +557,17 @@ pub trait BrowserExecutor: Send + Sync {
         request: &RunScriptRequest,
     ) -> Result<BrowserExecutionResult>;
 
+    /// Run a script and forward its `rf.emit` events to `events` as they
+    /// arrive. Executors without callback support ignore the sender.
+    async fn run_script_with_events(
+        &self,
+        session: &BrowserSession,
+        request: &RunScriptRequest,
+        _events: ScriptEventSender,
+    ) -> Result<BrowserExecutionResult> {
+        self.run_script(session, request).await
+    }
+
     async fn run_actions(
         &self,
         session: &BrowserSession,
@@ -311,6 +577,13 @@ pub trait BrowserExecutor: Send + Sync {
     async fn close_session(&self, _session_id: &str) -> Result<()> {
         Ok(())
     }
+
+    async fn runtime_stats(
+        &self,
+        _session: &BrowserSession,
+    ) -> Result<Option<BrowserRuntimeStats>> {
+        Ok(None)
+    }
 }
 
 /// Browser automation service with session lifecycle and executor delegation.
@@ -318,6 +591,9 @@ pub struct BrowserService {
     root_dir: PathBuf,
     sessions: RwLock<HashMap<String, BrowserSession>>,
     executor: Arc<dyn BrowserExecutor>,
+    navigation_policy: NavigationPolicy,
+    artifact_retention: ArtifactRetentionPolicy,
+    retention_stats: std::sync::Mutex<ArtifactRetentionStats>,
 }
 
 impl BrowserService {
@@ -335,23 +611,151 @@ impl BrowserService {
             root_dir,
             sessions: RwLock::new(HashMap::new()),
             executor,
+            navigation_policy: NavigationPolicy::default(),
+            artifact_retention: ArtifactRetentionPolicy::default(),
+            retention_stats: std::sync::Mutex::new(ArtifactRetentionStats::default()),
+        })
+    }
+
+    /// Restrict which domains action plans may navigate to.
+    pub fn with_navigation_policy(mut self, policy: NavigationPolicy) -> Self {
+        self.navigation_policy = policy;
+        self
+    }
+
+    pub fn navigation_policy(&self) -> &NavigationPolicy {
+        &self.navigation_policy
+    }
+
+    /// Bound artifact growth; the policy is enforced after every script or
+    /// action run.
+    pub fn with_artifact_retention(mut self, policy: ArtifactRetentionPolicy) -> Self {
+        self.artifact_retention = policy;
+        self
+    }
+
+    pub fn artifact_retention(&self) -> &ArtifactRetentionPolicy {
+        &self.artifact_retention
+    }
+
+    /// Space reclaimed by artifact purges since the service started.
+    pub fn artifact_retention_stats(&self) -> ArtifactRetentionStats {
+        *self
+            .retention_stats
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Apply the retention policy to one session, or to every session when
+    /// `session_id` is `None`. The total size quota is only enforced for the
+    /// all-sessions pass.
+    pub async fn purge_artifacts(&self, session_id: Option<&str>) -> Result<ArtifactPurgeReport> {
+        let mut policy = self.artifact_retention;
+        let dirs: Vec<PathBuf> = match session_id {
+            Some(session_id) => {
+                policy.max_total_bytes = 0;
+                vec![PathBuf::from(
+                    self.get_session(session_id).await?.artifacts_dir,
+                )]
+            }
+            None => self
+                .sessions
+                .read()
+                .await
+                .values()
+                .map(|session| PathBuf::from(&session.artifacts_dir))
+                .collect(),
+        };
+
+        let report = tokio::task::spawn_blocking(move || {
+            retention::purge_artifact_dirs(&dirs, &policy, std::time::SystemTime::now())
         })
+        .await?;
+
+        if report.files_removed > 0 {
+            tracing::info!(
+                files_removed = report.files_removed,
+                bytes_reclaimed = report.bytes_reclaimed,
+                bytes_remaining = report.bytes_remaining,
+                "Purged browser artifacts"
+            );
+        }
+        self.retention_stats
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .record(&report);
+        Ok(report)
+    }
+
+    async fn rotate_artifacts(&self) {
+        if self.artifact_retention.is_unlimited() {
+            return;
+        }
+        if let Err(error) = self.purge_artifacts(None).await {
+            tracing::warn!("Browser artifact rotation failed: {}", error);
+        }
     }
 
     pub async fn probe_runtime(&self) -> Result<RuntimeProbe> {
         self.executor.probe_runtime().await
     }
 
+    /// Probe the runtime and attach remediation steps for anything missing.
+    pub async fn doctor(&self) -> Result<BrowserDoctorReport> {
+        Ok(self.probe_runtime().await?.into())
+    }
+
+    /// Download the pinned chrome-headless-shell build if it is not installed.
+    pub async fn install_runtime(
+        &self,
+        progress: Option<tokio::sync::mpsc::UnboundedSender<ProvisionEvent>>,
+    ) -> Result<InstalledRuntime> {
+        let mut provisioner = ChromiumProvisioner::new()?;
+        if let Some(sender) = progress {
+            provisioner = provisioner.with_progress(sender);
+        }
+        provisioner.ensure_installed().await
+    }
+
     pub async fn new_session(&self, request: NewSessionRequest) -> Result<BrowserSession> {
         if request.browser != BrowserKind::Chromium {
             bail!("Only chromium is supported in this version");
         }
+        let cdp_endpoint = request
+            .cdp_endpoint
+            .as_deref()
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty())
+            .map(validate_cdp_endpoint)
+            .transpose()?;
 
         let id = Uuid::new_v4().to_string();
         let session_dir = self.root_dir.join(&id);
         let profile_dir = session_dir.join("profile");
         let artifacts_dir = session_dir.join("artifacts");
 
+        let requested_fixtures_dir = request
+            .fixtures_dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+        let fixtures_dir = match request.network_mode {
+            NetworkMode::Live => None,
+            NetworkMode::Record => {
+                Some(requested_fixtures_dir.unwrap_or_else(|| session_dir.join("fixtures")))
+            }
+            NetworkMode::Replay => {
+                let dir = requested_fixtures_dir
+                    .ok_or_else(|| anyhow!("Replay sessions require a fixtures_dir"))?;
+                let store = FixtureStore::open(&dir)?;
+                if store.len() == 0 {
+                    bail!("No recorded network fixtures found in {}", dir.display());
+                }
+                Some(dir)
+            }
+        };
+
         std::fs::create_dir_all(&profile_dir)?;
         std::fs::create_dir_all(&artifacts_dir)?;
 
@@ -363,6 +767,9 @@ impl BrowserService {
             session_dir: session_dir.display().to_string(),
             profile_dir: profile_dir.display().to_string(),
             artifacts_dir: artifacts_dir.display().to_string(),
+            cdp_endpoint,
+            network_mode: request.network_mode,
+            fixtures_dir: fixtures_dir.map(|dir| dir.display().to_string()),
         };
 
         let mut sessions = self.sessions.write().await;
@@ -396,12 +803,47 @@ impl BrowserService {
 
     pub async fn run_script(&self, request: &RunScriptRequest) -> Result<BrowserExecutionResult> {
         let session = self.get_session(&request.session_id).await?;
-        self.executor.run_script(&session, request).await
+        let result = self.executor.run_script(&session, request).await;
+        self.rotate_artifacts().await;
+        result
+    }
+
+    /// Like [`Self::run_script`], streaming `rf.emit` events to `events`
+    /// before the script finishes.
+    pub async fn run_script_with_events(
+        &self,
+        request: &RunScriptRequest,
+        events: ScriptEventSender,
+    ) -> Result<BrowserExecutionResult> {
+        let session = self.get_session(&request.session_id).await?;
+        let result = self
+            .executor
+            .run_script_with_events(&session, request, events)
+            .await;
+        self.rotate_artifacts().await;
+        result
     }
 
     pub async fn run_actions(&self, request: &RunActionsRequest) -> Result<BrowserExecutionResult> {
         let session = self.get_session(&request.session_id).await?;
-        self.executor.run_actions(&session, request).await
+        self.navigation_policy
+            .check_actions(&request.actions)
+            .await?;
+        let result = self.executor.run_actions(&session, request).await;
+        self.rotate_artifacts().await;
+        result
+    }
+
+    /// Report process, target and disk usage for a session.
+    pub async fn session_stats(&self, session_id: &str) -> Result<BrowserSessionStats> {
+        let session = self.get_session(session_id).await?;
+        let runtime = self.executor.runtime_stats(&session).await?;
+        Ok(BrowserSessionStats {
+            session_id: session.id.clone(),
+            uptime_ms: (Utc::now().timestamp_millis() - session.created_at_ms).max(0),
+            artifacts_bytes: directory_size_bytes(Path::new(&session.artifacts_dir)),
+            runtime,
+        })
     }
 
     async fn get_session(&self, session_id: &str) -> Result<BrowserSession> {
@@ -415,15 +857,23 @@ impl BrowserService {
 
 pub struct CdpExecutor {
     runtimes: Mutex<HashMap<String, Arc<Mutex<CdpRuntime>>>>,
+    max_parallel_tabs: usize,
 }
 
 impl CdpExecutor {
     pub fn new() -> Self {
         Self {
             runtimes: Mutex::new(HashMap::new()),
+            max_parallel_tabs: DEFAULT_MAX_PARALLEL_TABS,
         }
     }
 
+    /// Cap the number of tabs a parallel action group may drive at once.
+    pub fn with_max_parallel_tabs(mut self, max_parallel_tabs: usize) -> Self {
+        self.max_parallel_tabs = max_parallel_tabs.max(1);
+        self
+    }
+
     async fn get_or_start_runtime(
         &self,
         session: &BrowserSession,
@@ -433,9 +883,7 @@ impl CdpExecutor {
             return Ok(existing);
         }
 
-        let started = Arc::new(Mutex::new(
-            CdpRuntime::start(session.headless, &session.profile_dir, timeout_secs).await?,
-        ));
+        let started = Arc::new(Mutex::new(CdpRuntime::start(session, timeout_secs).await?));
 
         let mut runtimes = self.runtimes.lock().await;
         if let Some(existing) = runtimes.get(&session.id).cloned() {
@@ -476,11 +924,48 @@ impl CdpExecutor {
         );
     }
 
+    async fn run_script_outcome(
+        &self,
+        session: &BrowserSession,
+        request: &RunScriptRequest,
+        events: Option<ScriptEventSender>,
+    ) -> Result<BrowserExecutionResult> {
+        let started = Instant::now();
+        match self.run_script_inner(session, request, events).await {
+            Ok(output) => Ok(BrowserExecutionResult {
+                runtime: "cdp_chromium".to_string(),
+                exit_code: 0,
+                duration_ms: started.elapsed().as_millis() as u64,
+                stdout: String::new(),
+                stderr: String::new(),
+                payload: Some(json!({
+                    "success": true,
+                    "result": output.value,
+                    "world": request.sandbox.world_name(),
+                    "events": output.events,
+                    "events_emitted": output.events_emitted,
+                })),
+                trace_path: None,
+            }),
+            Err(error) => Ok(BrowserExecutionResult {
+                runtime: "cdp_chromium".to_string(),
+                exit_code: 1,
+                duration_ms: started.elapsed().as_millis() as u64,
+                stdout: String::new(),
+                stderr: error.to_string(),
+                payload: Some(json!({"success": false, "error": error.to_string()})),
+                trace_path: None,
+            }),
+        }
+    }
+
     async fn run_script_inner(
         &self,
         session: &BrowserSession,
         request: &RunScriptRequest,
-    ) -> Result<Value> {
+        events: Option<ScriptEventSender>,
+    ) -> Result<ScriptOutput> {
+        let args = script_args_json(request.args.as_ref())?;
         let script_source = if request.language == ScriptLanguage::Ts {
             let cwd = request.cwd.as_deref().map(Path::new);
             transpile_typescript_source(&request.code, cwd, request.timeout_secs).await?
@@ -493,34 +978,95 @@ impl CdpExecutor {
             .await?;
         let mut runtime = runtime.lock().await;
 
-        let eval_script = build_user_script_wrapper(&script_source)?;
-        let value = runtime
-            .evaluate_page_script(&eval_script)
-            .await
-            .and_then(extract_action_result)?;
+        let binding_name = format!(
+            "{}{}",
+            SCRIPT_BINDING_PREFIX,
+            &Uuid::new_v4().simple().to_string()[..12]
+        );
+        runtime
+            .page
+            .add_script_binding(&binding_name, request.sandbox)
+            .await?;
+        runtime.page.cdp.binding = Some(ScriptBinding::new(&binding_name, &session.id, events));
+
+        let value = match build_user_script_wrapper(&script_source, &args, &binding_name) {
+            Ok(eval_script) => runtime
+                .page
+                .evaluate_sandboxed(&eval_script, request.sandbox)
+                .await
+                .and_then(extract_action_result),
+            Err(error) => Err(error),
+        };
 
-        Ok(value)
+        let binding = runtime.page.cdp.binding.take();
+        if let Err(error) = runtime.page.remove_script_binding(&binding_name).await {
+            tracing::debug!("Failed to remove script binding: {}", error);
+        }
+        runtime.page.cdp.flush_recording().await;
+        let value = value?;
+
+        let (events, events_emitted) = binding
+            .map(|binding| (binding.events, binding.emitted))
+            .unwrap_or_default();
+        Ok(ScriptOutput {
+            value,
+            events,
+            events_emitted,
+        })
     }
 
     async fn run_actions_inner(
         &self,
         session: &BrowserSession,
         request: &RunActionsRequest,
+        trace: &mut BrowserTrace,
+        trace_dir: &Path,
     ) -> Result<Vec<Value>> {
         let runtime = self
             .get_or_start_runtime(session, request.timeout_secs)
             .await?;
         let mut runtime = runtime.lock().await;
 
+        let run_started = Instant::now();
         let mut outputs = Vec::with_capacity(request.actions.len());
 
-        for action in &request.actions {
-            let output = runtime
-                .execute_action(action, &session.artifacts_dir)
-                .await?;
-            outputs.push(output);
+        for (index, action) in request.actions.iter().enumerate() {
+            let started_offset_ms = run_started.elapsed().as_millis() as u64;
+            let action_started = Instant::now();
+            let result = runtime
+                .execute_action(action, &session.artifacts_dir, self.max_parallel_tabs)
+                .await;
+            let mut entry = BrowserTraceAction {
+                index,
+                action_type: action_type_name(action),
+                started_offset_ms,
+                duration_ms: action_started.elapsed().as_millis() as u64,
+                success: result.is_ok(),
+                error: None,
+                screenshot_path: None,
+            };
+
+            match result {
+                Ok(output) => {
+                    trace.actions.push(entry);
+                    outputs.push(output);
+                }
+                Err(error) => {
+                    let failure = runtime
+                        .page
+                        .capture_failure(trace_dir, index, action, &error)
+                        .await;
+                    entry.error = Some(error.to_string());
+                    entry.screenshot_path = failure.screenshot_path.clone();
+                    trace.actions.push(entry);
+                    trace.failure = Some(failure);
+                    trace.performance_metrics = runtime.page.performance_metrics().await.ok();
+                    return Err(error);
+                }
+            }
         }
 
+        trace.performance_metrics = runtime.page.performance_metrics().await.ok();
         Ok(outputs)
     }
 }
@@ -587,7 +1133,7 @@ impl BrowserExecutor for CdpExecutor {
                 .push(format!("Chromium executable detected: {}", path));
         } else {
             probe.notes.push(
-                "Chromium executable not found. Set RESTFLOW_CHROMIUM_PATH or install Chrome/Chromium in PATH."
+                "Chromium executable not found. Set RESTFLOW_CHROMIUM_PATH, install Chrome/Chromium in PATH, or provision the bundled chrome-headless-shell."
                     .to_string(),
             );
         }
@@ -604,25 +1150,17 @@ impl BrowserExecutor for CdpExecutor {
         session: &BrowserSession,
         request: &RunScriptRequest,
     ) -> Result<BrowserExecutionResult> {
-        let started = Instant::now();
-        match self.run_script_inner(session, request).await {
-            Ok(value) => Ok(BrowserExecutionResult {
-                runtime: "cdp_chromium".to_string(),
-                exit_code: 0,
-                duration_ms: started.elapsed().as_millis() as u64,
-                stdout: String::new(),
-                stderr: String::new(),
-                payload: Some(json!({"success": true, "result": value})),
-            }),
-            Err(error) => Ok(BrowserExecutionResult {
-                runtime: "cdp_chromium".to_string(),
-                exit_code: 1,
-                duration_ms: started.elapsed().as_millis() as u64,
-                stdout: String::new(),
-                stderr: error.to_string(),
-                payload: Some(json!({"success": false, "error": error.to_string()})),
-            }),
-        }
+        self.run_script_outcome(session, request, None).await
+    }
+
+    async fn run_script_with_events(
+        &self,
+        session: &BrowserSession,
+        request: &RunScriptRequest,
+        events: ScriptEventSender,
+    ) -> Result<BrowserExecutionResult> {
+        self.run_script_outcome(session, request, Some(events))
+            .await
     }
 
     async fn run_actions(
@@ -631,22 +1169,38 @@ impl BrowserExecutor for CdpExecutor {
         request: &RunActionsRequest,
     ) -> Result<BrowserExecutionResult> {
         let started = Instant::now();
-        match self.run_actions_inner(session, request).await {
+        let trace_dir = trace_dir_for_run(&session.artifacts_dir);
+        let mut trace = BrowserTrace::new(&session.id);
+        let outcome = self
+            .run_actions_inner(session, request, &mut trace, &trace_dir)
+            .await;
+
+        let duration_ms = started.elapsed().as_millis() as u64;
+        trace.duration_ms = duration_ms;
+        trace.success = outcome.is_ok();
+        let trace_path = trace
+            .write(&trace_dir)
+            .map(|path| path.display().to_string())
+            .ok();
+
+        match outcome {
             Ok(values) => Ok(BrowserExecutionResult {
                 runtime: "cdp_chromium".to_string(),
                 exit_code: 0,
-                duration_ms: started.elapsed().as_millis() as u64,
+                duration_ms,
                 stdout: String::new(),
                 stderr: String::new(),
                 payload: Some(json!({"success": true, "result": values})),
+                trace_path,
             }),
             Err(error) => Ok(BrowserExecutionResult {
                 runtime: "cdp_chromium".to_string(),
                 exit_code: 1,
-                duration_ms: started.elapsed().as_millis() as u64,
+                duration_ms,
                 stdout: String::new(),
                 stderr: error.to_string(),
-                payload: Some(json!({"success": false, "error": error.to_string()})),
+                payload: Some(failure_payload(&error, trace.failure.as_ref())),
+                trace_path,
             }),
         }
     }
@@ -654,46 +1208,196 @@ impl BrowserExecutor for CdpExecutor {
     async fn close_session(&self, session_id: &str) -> Result<()> {
         self.close_runtime(session_id).await
     }
+
+    async fn runtime_stats(&self, session: &BrowserSession) -> Result<Option<BrowserRuntimeStats>> {
+        let Some(runtime) = self.runtimes.lock().await.get(&session.id).cloned() else {
+            return Ok(None);
+        };
+
+        match timeout(
+            Duration::from_secs(RUNTIME_STATS_LOCK_TIMEOUT_SECS),
+            runtime.lock(),
+        )
+        .await
+        {
+            Ok(mut runtime) => runtime.stats().await.map(Some),
+            Err(_) => Ok(Some(BrowserRuntimeStats {
+                busy: true,
+                ..BrowserRuntimeStats::default()
+            })),
+        }
+    }
 }
 
 struct CdpRuntime {
     process: ChromiumProcess,
-    cdp: CdpClient,
-    page_session_id: String,
+    page: CdpPage,
+    network: Option<PageNetwork>,
+    started_at: Instant,
 }
 
 impl CdpRuntime {
-    async fn start(headless: bool, profile_dir: &str, timeout_secs: u64) -> Result<Self> {
-        let process = ChromiumProcess::launch(headless, profile_dir, timeout_secs).await?;
-        let mut cdp = CdpClient::connect(&process.ws_endpoint).await?;
+    async fn start(session: &BrowserSession, timeout_secs: u64) -> Result<Self> {
+        let process = match session.cdp_endpoint.as_deref() {
+            Some(endpoint) => ChromiumProcess::connect(endpoint, timeout_secs).await?,
+            None => {
+                ChromiumProcess::launch(session.headless, &session.profile_dir, timeout_secs)
+                    .await?
+            }
+        };
+        let network = PageNetwork::for_session(session)?;
+        let page = CdpPage::open(&process.ws_endpoint, network.as_ref()).await?;
+        Ok(Self {
+            process,
+            page,
+            network,
+            started_at: Instant::now(),
+        })
+    }
 
-        let create_result = cdp
-            .send_command(None, "Target.createTarget", json!({"url": "about:blank"}))
-            .await?;
-        let target_id = create_result
-            .get("targetId")
-            .and_then(Value::as_str)
-            .ok_or_else(|| anyhow!("CDP Target.createTarget did not return targetId"))?
-            .to_string();
+    async fn shutdown(mut self) -> Result<()> {
+        if self.process.child.is_none() {
+            // Attached browsers are shared; only close the tab we opened.
+            return self.page.close().await;
+        }
+        let _ = self
+            .page
+            .cdp
+            .send_command(None, "Browser.close", json!({}))
+            .await;
+        self.process.shutdown().await
+    }
 
-        let attach_result = cdp
-            .send_command(
-                None,
-                "Target.attachToTarget",
-                json!({"targetId": target_id, "flatten": true}),
-            )
-            .await?;
-        let page_session_id = attach_result
-            .get("sessionId")
-            .and_then(Value::as_str)
-            .ok_or_else(|| anyhow!("CDP Target.attachToTarget did not return sessionId"))?
-            .to_string();
+    async fn stats(&mut self) -> Result<BrowserRuntimeStats> {
+        let browser_pid = self.process.child.as_ref().and_then(Child::id);
+        let remote = self.process.child.is_none();
 
-        cdp.send_command(Some(&page_session_id), "Runtime.enable", json!({}))
-            .await?;
-        cdp.send_command(Some(&page_session_id), "Page.enable", json!({}))
+        let process_info = self
+            .page
+            .cdp
+            .send_command(None, "SystemInfo.getProcessInfo", json!({}))
             .await?;
-        cdp.send_command(
+        let mut pids: Vec<u32> = process_info
+            .get("processInfo")
+            .and_then(Value::as_array)
+            .map(|processes| {
+                processes
+                    .iter()
+                    .filter_map(|process| process.get("id").and_then(Value::as_u64))
+                    .filter_map(|pid| u32::try_from(pid).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(pid) = browser_pid
+            && !pids.contains(&pid)
+        {
+            pids.push(pid);
+        }
+
+        let targets = self
+            .page
+            .cdp
+            .send_command(None, "Target.getTargets", json!({}))
+            .await?;
+        let open_targets = targets
+            .get("targetInfos")
+            .and_then(Value::as_array)
+            .map(|infos| {
+                infos
+                    .iter()
+                    .filter(|info| info.get("type").and_then(Value::as_str) == Some("page"))
+                    .count()
+            })
+            .unwrap_or_default();
+
+        // Process ids reported by a remote browser do not belong to this host.
+        let rss_bytes = if remote {
+            None
+        } else {
+            process_rss_bytes(&pids).await
+        };
+
+        Ok(BrowserRuntimeStats {
+            browser_pid,
+            process_count: pids.len(),
+            rss_bytes,
+            open_targets,
+            runtime_uptime_ms: self.started_at.elapsed().as_millis() as u64,
+            busy: false,
+        })
+    }
+
+    async fn execute_action(
+        &mut self,
+        action: &BrowserAction,
+        artifacts_dir: &str,
+        max_parallel_tabs: usize,
+    ) -> Result<Value> {
+        match action {
+            BrowserAction::Parallel {
+                branches,
+                max_concurrency,
+            } => {
+                let limit = parallel_concurrency_limit(*max_concurrency, max_parallel_tabs);
+                let ws_endpoint = self.process.ws_endpoint.clone();
+                let results = run_parallel_branches(
+                    &ws_endpoint,
+                    branches,
+                    limit,
+                    artifacts_dir,
+                    self.network.as_ref(),
+                )
+                .await?;
+                Ok(json!({"type": "parallel", "branches": results}))
+            }
+            _ => {
+                let result = self.page.execute_action(action, artifacts_dir).await;
+                self.page.cdp.flush_recording().await;
+                result
+            }
+        }
+    }
+}
+
+/// A single attached page target. The main session page and every branch of a
+/// parallel group each own one, so branches never contend for a websocket.
+struct CdpPage {
+    cdp: CdpClient,
+    target_id: String,
+    page_session_id: String,
+}
+
+impl CdpPage {
+    async fn open(ws_endpoint: &str, network: Option<&PageNetwork>) -> Result<Self> {
+        let mut cdp = CdpClient::connect(ws_endpoint).await?;
+
+        let create_result = cdp
+            .send_command(None, "Target.createTarget", json!({"url": "about:blank"}))
+            .await?;
+        let target_id = create_result
+            .get("targetId")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("CDP Target.createTarget did not return targetId"))?
+            .to_string();
+
+        let attach_result = cdp
+            .send_command(
+                None,
+                "Target.attachToTarget",
+                json!({"targetId": target_id, "flatten": true}),
+            )
+            .await?;
+        let page_session_id = attach_result
+            .get("sessionId")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("CDP Target.attachToTarget did not return sessionId"))?
+            .to_string();
+
+        cdp.send_command(Some(&page_session_id), "Runtime.enable", json!({}))
+            .await?;
+        cdp.send_command(Some(&page_session_id), "Page.enable", json!({}))
+            .await?;
+        cdp.send_command(
             Some(&page_session_id),
             "Page.setLifecycleEventsEnabled",
             json!({"enabled": true}),
@@ -701,20 +1405,28 @@ impl CdpRuntime {
         .await?;
         cdp.send_command(Some(&page_session_id), "Network.enable", json!({}))
             .await?;
+        cdp.send_command(Some(&page_session_id), "Performance.enable", json!({}))
+            .await?;
+        if let Some(network) = network {
+            cdp.attach_network(&page_session_id, network).await?;
+        }
 
         Ok(Self {
-            process,
             cdp,
+            target_id,
             page_session_id,
         })
     }
 
-    async fn shutdown(mut self) -> Result<()> {
-        let _ = self
-            .cdp
-            .send_command(None, "Browser.close", json!({}))
-            .await;
-        self.process.shutdown().await
+    async fn close(mut self) -> Result<()> {
+        self.cdp
+            .send_command(
+                None,
+                "Target.closeTarget",
+                json!({"targetId": self.target_id}),
+            )
+            .await?;
+        Ok(())
     }
 
     async fn execute_action(
@@ -748,6 +1460,7 @@ impl CdpRuntime {
             BrowserAction::Click {
                 selector,
                 timeout_ms,
+                ..
             } => {
                 self.wait_for_selector(selector, "visible", timeout_ms.unwrap_or(10_000))
                     .await?;
@@ -763,6 +1476,7 @@ impl CdpRuntime {
                 selector,
                 text,
                 timeout_ms,
+                ..
             } => {
                 self.wait_for_selector(selector, "visible", timeout_ms.unwrap_or(10_000))
                     .await?;
@@ -937,60 +1651,268 @@ impl CdpRuntime {
             }
             BrowserAction::Screenshot { path, full_page } => {
                 let target = resolve_artifact_path(artifacts_dir, path);
-                if let Some(parent) = target.parent() {
-                    std::fs::create_dir_all(parent)?;
-                }
-
-                let result = self
-                    .cdp
-                    .send_command(
-                        Some(&self.page_session_id),
-                        "Page.captureScreenshot",
-                        json!({
-                            "format": "png",
-                            "captureBeyondViewport": full_page,
-                            "fromSurface": true
-                        }),
-                    )
-                    .await?;
-
-                let data = result
-                    .get("data")
-                    .and_then(Value::as_str)
-                    .ok_or_else(|| anyhow!("Page.captureScreenshot did not return image data"))?;
-                let bytes = BASE64_STANDARD
-                    .decode(data)
-                    .map_err(|error| anyhow!("Failed to decode screenshot data: {}", error))?;
-                std::fs::write(&target, bytes)?;
+                self.capture_screenshot_to(&target, *full_page).await?;
 
                 Ok(json!({
                     "type": "screenshot",
                     "path": target.display().to_string()
                 }))
             }
-            BrowserAction::Evaluate { expression } => {
-                let script = build_dynamic_eval_script(expression)?;
+            BrowserAction::SetClipboard { text } => {
+                self.grant_clipboard_access().await?;
+                let script = format!(
+                    "(async () => {{\n  try {{\n    await navigator.clipboard.writeText({});\n    return {{ ok: true, value: true }};\n  }} catch (error) {{\n    return {{ ok: false, error: `Clipboard write failed: ${{error?.message ?? error}}` }};\n  }}\n}})()",
+                    serde_json::to_string(text)?
+                );
                 let result = self.evaluate_page_script(&script).await?;
+                extract_action_result(result)?;
+                Ok(json!({"type": "set_clipboard", "length": text.chars().count()}))
+            }
+            BrowserAction::ReadClipboard => {
+                self.grant_clipboard_access().await?;
+                let script = "(async () => {\n  try {\n    return { ok: true, value: await navigator.clipboard.readText() };\n  } catch (error) {\n    return { ok: false, error: `Clipboard read failed: ${error?.message ?? error}` };\n  }\n})()";
+                let result = self.evaluate_page_script(script).await?;
+                let text = extract_action_result(result)?;
+                Ok(json!({"type": "read_clipboard", "text": text}))
+            }
+            BrowserAction::Evaluate {
+                expression,
+                sandbox,
+                args,
+            } => {
+                let script = build_dynamic_eval_script(expression, args.as_ref())?;
+                let result = self.evaluate_sandboxed(&script, *sandbox).await?;
                 let value = extract_action_result(result)?;
-                Ok(json!({"type": "evaluate", "value": value}))
+                Ok(json!({
+                    "type": "evaluate",
+                    "value": value,
+                    "world": sandbox.world_name(),
+                }))
+            }
+            BrowserAction::Parallel { .. } => {
+                bail!("Parallel action groups cannot be nested inside another parallel group")
             }
         }
     }
 
-    async fn evaluate_page_script(&mut self, expression: &str) -> Result<Value> {
+    /// Grant clipboard permissions for the current origin and emulate focus,
+    /// since `navigator.clipboard` rejects calls from unfocused documents.
+    async fn grant_clipboard_access(&mut self) -> Result<()> {
+        let origin = self.evaluate_page_script("location.origin").await?;
+        let origin = origin
+            .as_str()
+            .filter(|origin| origin.starts_with("http://") || origin.starts_with("https://"))
+            .ok_or_else(|| {
+                anyhow!("Clipboard actions need an http(s) page; navigate to the target site first")
+            })?
+            .to_string();
+
+        self.cdp
+            .send_command(
+                None,
+                "Browser.grantPermissions",
+                json!({
+                    "origin": origin,
+                    "permissions": ["clipboardReadWrite", "clipboardSanitizedWrite"],
+                }),
+            )
+            .await?;
+        self.cdp
+            .send_command(
+                Some(&self.page_session_id),
+                "Emulation.setFocusEmulationEnabled",
+                json!({"enabled": true}),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn capture_screenshot_to(&mut self, target: &Path, full_page: bool) -> Result<()> {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let result = self
+            .cdp
+            .send_command(
+                Some(&self.page_session_id),
+                "Page.captureScreenshot",
+                json!({
+                    "format": "png",
+                    "captureBeyondViewport": full_page,
+                    "fromSurface": true
+                }),
+            )
+            .await?;
+
+        let data = result
+            .get("data")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Page.captureScreenshot did not return image data"))?;
+        let bytes = BASE64_STANDARD
+            .decode(data)
+            .map_err(|error| anyhow!("Failed to decode screenshot data: {}", error))?;
+        std::fs::write(target, bytes)?;
+        Ok(())
+    }
+
+    /// Best-effort capture of the page at the moment an action failed.
+    async fn capture_failure(
+        &mut self,
+        output_dir: &Path,
+        index: usize,
+        action: &BrowserAction,
+        error: &anyhow::Error,
+    ) -> BrowserFailureSnapshot {
+        let (url, title) = match self
+            .evaluate_page_script("({url: location.href, title: document.title})")
+            .await
+        {
+            Ok(state) => (
+                state.get("url").and_then(Value::as_str).map(str::to_string),
+                state
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            ),
+            Err(_) => (None, None),
+        };
+
+        let screenshot = output_dir.join(format!("action-{index}-failure.png"));
+        let screenshot_path = self
+            .capture_screenshot_to(&screenshot, false)
+            .await
+            .ok()
+            .map(|_| screenshot.display().to_string());
+
+        let mut snapshot = BrowserFailureSnapshot {
+            action_index: index,
+            action_type: action_type_name(action),
+            error: error.to_string(),
+            url,
+            title,
+            screenshot_path,
+            snapshot_path: None,
+        };
+        let snapshot_file = output_dir.join(format!("action-{index}-failure.json"));
+        if std::fs::create_dir_all(output_dir).is_ok()
+            && let Ok(bytes) = serde_json::to_vec_pretty(&snapshot)
+            && std::fs::write(&snapshot_file, bytes).is_ok()
+        {
+            snapshot.snapshot_path = Some(snapshot_file.display().to_string());
+        }
+        snapshot
+    }
+
+    async fn performance_metrics(&mut self) -> Result<serde_json::Map<String, Value>> {
         let result = self
             .cdp
             .send_command(
                 Some(&self.page_session_id),
-                "Runtime.evaluate",
+                "Performance.getMetrics",
+                json!({}),
+            )
+            .await?;
+        Ok(performance_metrics_from_cdp(&result))
+    }
+
+    async fn evaluate_page_script(&mut self, expression: &str) -> Result<Value> {
+        self.evaluate_in_context(expression, None).await
+    }
+
+    /// Evaluate `expression` in the world selected by `sandbox`.
+    async fn evaluate_sandboxed(
+        &mut self,
+        expression: &str,
+        sandbox: ScriptSandbox,
+    ) -> Result<Value> {
+        if sandbox == ScriptSandbox::None {
+            return self.evaluate_page_script(expression).await;
+        }
+
+        let context_id = self.create_isolated_world(sandbox.world_name()).await?;
+        let expression = if sandbox == ScriptSandbox::Restricted {
+            format!("{}\n{}", RESTRICTED_WORLD_PRELUDE, expression)
+        } else {
+            expression.to_string()
+        };
+        self.evaluate_in_context(&expression, Some(context_id))
+            .await
+    }
+
+    /// Create a fresh isolated world in the main frame and return its
+    /// execution context id.
+    async fn create_isolated_world(&mut self, world_name: &str) -> Result<i64> {
+        let frame_tree = self
+            .cdp
+            .send_command(Some(&self.page_session_id), "Page.getFrameTree", json!({}))
+            .await?;
+        let frame_id = frame_tree
+            .pointer("/frameTree/frame/id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("CDP Page.getFrameTree did not return a frame id"))?
+            .to_string();
+
+        let world = self
+            .cdp
+            .send_command(
+                Some(&self.page_session_id),
+                "Page.createIsolatedWorld",
                 json!({
-                    "expression": expression,
-                    "awaitPromise": true,
-                    "returnByValue": true,
-                    "replMode": false,
+                    "frameId": frame_id,
+                    "worldName": world_name,
+                    "grantUniveralAccess": false,
                 }),
             )
             .await?;
+        world
+            .get("executionContextId")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| anyhow!("CDP Page.createIsolatedWorld did not return a context id"))
+    }
+
+    /// Expose `name` as a page binding in the world selected by `sandbox`.
+    /// Worlds created afterwards with a matching name receive it as well.
+    async fn add_script_binding(&mut self, name: &str, sandbox: ScriptSandbox) -> Result<()> {
+        let mut params = json!({"name": name});
+        if sandbox != ScriptSandbox::None {
+            params["executionContextName"] = json!(sandbox.world_name());
+        }
+        self.cdp
+            .send_command(Some(&self.page_session_id), "Runtime.addBinding", params)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_script_binding(&mut self, name: &str) -> Result<()> {
+        self.cdp
+            .send_command(
+                Some(&self.page_session_id),
+                "Runtime.removeBinding",
+                json!({"name": name}),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn evaluate_in_context(
+        &mut self,
+        expression: &str,
+        context_id: Option<i64>,
+    ) -> Result<Value> {
+        let mut params = json!({
+            "expression": expression,
+            "awaitPromise": true,
+            "returnByValue": true,
+            "replMode": false,
+        });
+        if let Some(context_id) = context_id {
+            params["contextId"] = json!(context_id);
+        }
+        let result = self
+            .cdp
+            .send_command(Some(&self.page_session_id), "Runtime.evaluate", params)
+            .await?;
 
         if let Some(exception) = result.get("exceptionDetails") {
             let message = exception
@@ -1291,14 +2213,19 @@ impl CdpRuntime {
 }
 
 struct ChromiumProcess {
-    child: Child,
+    /// `None` when attached to a browser this service did not launch.
+    child: Option<Child>,
     ws_endpoint: String,
 }
 
 impl ChromiumProcess {
     async fn launch(headless: bool, profile_dir: &str, timeout_secs: u64) -> Result<Self> {
         let chromium = resolve_chromium_binary()
-            .ok_or_else(|| anyhow!("Chromium executable not found. Set RESTFLOW_CHROMIUM_PATH"))?;
+            .ok_or_else(|| {
+            anyhow!(
+                "Chromium executable not found. Set RESTFLOW_CHROMIUM_PATH or install the bundled chrome-headless-shell"
+            )
+        })?;
         let debug_port = allocate_free_port()?;
 
         let mut args = vec![
@@ -1339,20 +2266,36 @@ impl ChromiumProcess {
 
         let ws_endpoint = wait_for_debugger_ws_url(debug_port, timeout_secs, &mut child).await?;
 
-        Ok(Self { child, ws_endpoint })
+        Ok(Self {
+            child: Some(child),
+            ws_endpoint,
+        })
+    }
+
+    /// Attach to a browser that is already running at `endpoint`.
+    async fn connect(endpoint: &str, timeout_secs: u64) -> Result<Self> {
+        let ws_endpoint = if endpoint.starts_with("ws://") || endpoint.starts_with("wss://") {
+            endpoint.to_string()
+        } else {
+            resolve_remote_ws_url(endpoint, timeout_secs).await?
+        };
+        Ok(Self {
+            child: None,
+            ws_endpoint,
+        })
     }
 
     async fn shutdown(&mut self) -> Result<()> {
-        let wait_result = timeout(
-            Duration::from_secs(CDP_SHUTDOWN_TIMEOUT_SECS),
-            self.child.wait(),
-        )
-        .await;
+        let Some(child) = self.child.as_mut() else {
+            return Ok(());
+        };
+        let wait_result =
+            timeout(Duration::from_secs(CDP_SHUTDOWN_TIMEOUT_SECS), child.wait()).await;
 
         match wait_result {
             Ok(_) => Ok(()),
             Err(_) => {
-                self.child.kill().await?;
+                child.kill().await?;
                 Ok(())
             }
         }
@@ -1361,63 +2304,399 @@ impl ChromiumProcess {
 
 type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
 
-struct CdpClient {
-    socket: WsStream,
-    next_id: i64,
-    queued_events: VecDeque<Value>,
-    queued_responses: HashMap<i64, Value>,
+/// Fixture-backed network behaviour shared by every page of a session.
+#[derive(Clone)]
+struct PageNetwork {
+    mode: NetworkMode,
+    store: Arc<FixtureStore>,
 }
 
-impl CdpClient {
-    async fn connect(ws_endpoint: &str) -> Result<Self> {
-        let (socket, _) = connect_async(ws_endpoint)
-            .await
-            .map_err(|error| anyhow!("Failed to connect to CDP endpoint: {}", error))?;
-        Ok(Self {
-            socket,
-            next_id: 0,
-            queued_events: VecDeque::new(),
-            queued_responses: HashMap::new(),
-        })
+impl PageNetwork {
+    fn for_session(session: &BrowserSession) -> Result<Option<Self>> {
+        let Some(dir) = session.fixtures_dir.as_deref() else {
+            return Ok(None);
+        };
+        if session.network_mode == NetworkMode::Live {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            mode: session.network_mode,
+            store: Arc::new(FixtureStore::open(Path::new(dir))?),
+        }))
     }
+}
 
-    async fn send_command(
-        &mut self,
-        session_id: Option<&str>,
-        method: &str,
-        params: Value,
-    ) -> Result<Value> {
-        self.next_id += 1;
-        let request_id = self.next_id;
+/// Response headers that no longer describe the body once it is decoded by
+/// `Network.getResponseBody`.
+const UNREPLAYABLE_HEADERS: &[&str] = &["content-encoding", "content-length", "transfer-encoding"];
 
-        let mut request = serde_json::Map::new();
-        request.insert("id".to_string(), json!(request_id));
-        request.insert("method".to_string(), Value::String(method.to_string()));
-        request.insert("params".to_string(), params);
-        if let Some(session_id) = session_id {
-            request.insert(
-                "sessionId".to_string(),
-                Value::String(session_id.to_string()),
-            );
-        }
+struct RecordedResponse {
+    session_id: Option<String>,
+    method: String,
+    url: String,
+    status: u16,
+    headers: BTreeMap<String, String>,
+}
 
-        self.socket
-            .send(Message::Text(Value::Object(request).to_string().into()))
-            .await
-            .map_err(|error| anyhow!("Failed to send CDP command '{}': {}", method, error))?;
+/// Per-connection record/replay state, driven by events as they are read.
+struct NetworkHook {
+    mode: NetworkMode,
+    store: Arc<FixtureStore>,
+    request_methods: HashMap<String, String>,
+    pending: HashMap<String, RecordedResponse>,
+    finished: Vec<(String, RecordedResponse)>,
+}
 
-        if let Some(payload) = self.queued_responses.remove(&request_id) {
-            return Self::extract_command_result(method, payload);
+impl NetworkHook {
+    fn new(network: &PageNetwork) -> Self {
+        Self {
+            mode: network.mode,
+            store: network.store.clone(),
+            request_methods: HashMap::new(),
+            pending: HashMap::new(),
+            finished: Vec::new(),
         }
+    }
 
-        loop {
-            let payload = self.read_json_message().await?;
+    /// Track an event; returns a command to send when a paused request must
+    /// be answered.
+    fn observe(&mut self, event: &Value) -> Option<(Option<String>, &'static str, Value)> {
+        let method = event.get("method").and_then(Value::as_str)?;
+        let params = event.get("params")?;
+        let session_id = event
+            .get("sessionId")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let request_id = params.get("requestId").and_then(Value::as_str)?;
+
+        match (self.mode, method) {
+            (NetworkMode::Replay, "Fetch.requestPaused") => {
+                let request = params.get("request")?;
+                let url = request.get("url").and_then(Value::as_str)?;
+                let http_method = request
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .unwrap_or("GET");
+                match self.store.next_response(http_method, url) {
+                    Some(fixture) => {
+                        let headers: Vec<Value> = fixture
+                            .headers
+                            .iter()
+                            .map(|(name, value)| json!({"name": name, "value": value}))
+                            .collect();
+                        Some((
+                            session_id,
+                            "Fetch.fulfillRequest",
+                            json!({
+                                "requestId": request_id,
+                                "responseCode": fixture.status,
+                                "responseHeaders": headers,
+                                "body": fixture.body_base64,
+                            }),
+                        ))
+                    }
+                    None => {
+                        tracing::debug!(url, "No recorded fixture; failing request in replay");
+                        Some((
+                            session_id,
+                            "Fetch.failRequest",
+                            json!({"requestId": request_id, "errorReason": "InternetDisconnected"}),
+                        ))
+                    }
+                }
+            }
+            (NetworkMode::Record, "Network.requestWillBeSent") => {
+                let request = params.get("request")?;
+                let http_method = request
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .unwrap_or("GET");
+                // Redirect hops never reach `responseReceived`; record them here.
+                if let Some(redirect) = params.get("redirectResponse")
+                    && let Some(response) =
+                        recorded_response(None, self.request_methods.get(request_id), redirect)
+                {
+                    self.store.record(NetworkFixture::new(
+                        &response.method,
+                        &response.url,
+                        response.status,
+                        response.headers,
+                        &[],
+                    ));
+                }
+                self.request_methods
+                    .insert(request_id.to_string(), http_method.to_string());
+                None
+            }
+            (NetworkMode::Record, "Network.responseReceived") => {
+                let response = params.get("response")?;
+                if let Some(recorded) =
+                    recorded_response(session_id, self.request_methods.get(request_id), response)
+                {
+                    self.pending.insert(request_id.to_string(), recorded);
+                }
+                None
+            }
+            (NetworkMode::Record, "Network.loadingFinished") => {
+                self.request_methods.remove(request_id);
+                if let Some(recorded) = self.pending.remove(request_id) {
+                    self.finished.push((request_id.to_string(), recorded));
+                }
+                None
+            }
+            (NetworkMode::Record, "Network.loadingFailed") => {
+                self.request_methods.remove(request_id);
+                self.pending.remove(request_id);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+fn recorded_response(
+    session_id: Option<String>,
+    method: Option<&String>,
+    response: &Value,
+) -> Option<RecordedResponse> {
+    let url = response.get("url").and_then(Value::as_str)?;
+    if !is_fixture_url(url) {
+        return None;
+    }
+    let status = response
+        .get("status")
+        .and_then(Value::as_u64)
+        .and_then(|status| u16::try_from(status).ok())?;
+    let headers = response
+        .get("headers")
+        .and_then(Value::as_object)
+        .map(|headers| {
+            headers
+                .iter()
+                .filter(|(name, _)| {
+                    !UNREPLAYABLE_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+                })
+                .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(RecordedResponse {
+        session_id,
+        method: method.cloned().unwrap_or_else(|| "GET".to_string()),
+        url: url.to_string(),
+        status,
+        headers,
+    })
+}
+
+struct ScriptOutput {
+    value: Value,
+    events: Vec<Value>,
+    events_emitted: u64,
+}
+
+/// Collects `Runtime.bindingCalled` events for the script currently running
+/// and forwards them to the caller's sender.
+struct ScriptBinding {
+    name: String,
+    session_id: String,
+    started: Instant,
+    sender: Option<ScriptEventSender>,
+    events: Vec<Value>,
+    emitted: u64,
+}
+
+impl ScriptBinding {
+    fn new(name: &str, session_id: &str, sender: Option<ScriptEventSender>) -> Self {
+        Self {
+            name: name.to_string(),
+            session_id: session_id.to_string(),
+            started: Instant::now(),
+            sender,
+            events: Vec::new(),
+            emitted: 0,
+        }
+    }
+
+    /// Consume `event` if it is a call to this binding.
+    fn accept(&mut self, event: &Value) -> bool {
+        if event.get("method").and_then(Value::as_str) != Some("Runtime.bindingCalled") {
+            return false;
+        }
+        let Some(params) = event.get("params") else {
+            return false;
+        };
+        if params.get("name").and_then(Value::as_str) != Some(self.name.as_str()) {
+            return false;
+        }
+
+        let raw = params
+            .get("payload")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let payload = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+        if self.events.len() < MAX_COLLECTED_SCRIPT_EVENTS {
+            self.events.push(payload.clone());
+        }
+        if let Some(sender) = &self.sender {
+            // A dropped receiver only stops streaming; the script keeps running.
+            let _ = sender.send(ScriptEvent {
+                session_id: self.session_id.clone(),
+                sequence: self.emitted,
+                offset_ms: self.started.elapsed().as_millis() as u64,
+                payload,
+            });
+        }
+        self.emitted += 1;
+        true
+    }
+}
+
+struct CdpClient {
+    socket: WsStream,
+    next_id: i64,
+    queued_events: VecDeque<Value>,
+    queued_responses: HashMap<i64, Value>,
+    /// Commands sent without waiting; their responses are discarded.
+    detached_requests: HashSet<i64>,
+    network: Option<NetworkHook>,
+    /// Binding of the script currently running, if any.
+    binding: Option<ScriptBinding>,
+}
+
+impl CdpClient {
+    async fn connect(ws_endpoint: &str) -> Result<Self> {
+        let (socket, _) = connect_async(ws_endpoint)
+            .await
+            .map_err(|error| anyhow!("Failed to connect to CDP endpoint: {}", error))?;
+        Ok(Self {
+            socket,
+            next_id: 0,
+            queued_events: VecDeque::new(),
+            queued_responses: HashMap::new(),
+            detached_requests: HashSet::new(),
+            network: None,
+            binding: None,
+        })
+    }
+
+    /// Start recording or replaying network traffic for a page target.
+    async fn attach_network(&mut self, page_session_id: &str, network: &PageNetwork) -> Result<()> {
+        self.network = Some(NetworkHook::new(network));
+        match network.mode {
+            NetworkMode::Live => {}
+            NetworkMode::Record => {
+                // Cached responses would be missing from the recording.
+                self.send_command(
+                    Some(page_session_id),
+                    "Network.setCacheDisabled",
+                    json!({"cacheDisabled": true}),
+                )
+                .await?;
+            }
+            NetworkMode::Replay => {
+                self.send_command(
+                    Some(page_session_id),
+                    "Fetch.enable",
+                    json!({"patterns": [{"urlPattern": "*", "requestStage": "Request"}]}),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Save responses that finished loading since the last flush.
+    async fn flush_recording(&mut self) {
+        let Some(hook) = self.network.as_mut() else {
+            return;
+        };
+        if hook.mode != NetworkMode::Record {
+            return;
+        }
+        let store = hook.store.clone();
+        let finished = std::mem::take(&mut hook.finished);
+
+        for (request_id, response) in finished {
+            let body = match self
+                .send_command(
+                    response.session_id.as_deref(),
+                    "Network.getResponseBody",
+                    json!({"requestId": request_id}),
+                )
+                .await
+            {
+                Ok(result) => decode_response_body(&result),
+                Err(error) => {
+                    tracing::debug!(url = %response.url, "Skipping fixture body: {}", error);
+                    continue;
+                }
+            };
+            store.record(NetworkFixture::new(
+                &response.method,
+                &response.url,
+                response.status,
+                response.headers,
+                &body,
+            ));
+        }
+        if let Err(error) = store.save() {
+            tracing::warn!("Failed to save network fixtures: {}", error);
+        }
+    }
+
+    async fn observe_event(&mut self, event: &Value) -> Result<()> {
+        let Some(hook) = self.network.as_mut() else {
+            return Ok(());
+        };
+        if let Some((session_id, method, params)) = hook.observe(event) {
+            self.send_detached(session_id.as_deref(), method, params)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn send_detached(
+        &mut self,
+        session_id: Option<&str>,
+        method: &str,
+        params: Value,
+    ) -> Result<()> {
+        let request_id = self.write_command(session_id, method, params).await?;
+        self.detached_requests.insert(request_id);
+        Ok(())
+    }
+
+    async fn send_command(
+        &mut self,
+        session_id: Option<&str>,
+        method: &str,
+        params: Value,
+    ) -> Result<Value> {
+        let request_id = self.write_command(session_id, method, params).await?;
+
+        if let Some(payload) = self.queued_responses.remove(&request_id) {
+            return Self::extract_command_result(method, payload);
+        }
+
+        loop {
+            let payload = self.read_json_message().await?;
 
             let Some(response_id) = payload.get("id").and_then(Value::as_i64) else {
+                if let Some(binding) = self.binding.as_mut()
+                    && binding.accept(&payload)
+                {
+                    continue;
+                }
+                self.observe_event(&payload).await?;
                 self.queued_events.push_back(payload);
                 continue;
             };
 
+            if self.detached_requests.remove(&response_id) {
+                continue;
+            }
+
             if response_id != request_id {
                 self.queued_responses.insert(response_id, payload);
                 continue;
@@ -1427,6 +2706,33 @@ impl CdpClient {
         }
     }
 
+    async fn write_command(
+        &mut self,
+        session_id: Option<&str>,
+        method: &str,
+        params: Value,
+    ) -> Result<i64> {
+        self.next_id += 1;
+        let request_id = self.next_id;
+
+        let mut request = serde_json::Map::new();
+        request.insert("id".to_string(), json!(request_id));
+        request.insert("method".to_string(), Value::String(method.to_string()));
+        request.insert("params".to_string(), params);
+        if let Some(session_id) = session_id {
+            request.insert(
+                "sessionId".to_string(),
+                Value::String(session_id.to_string()),
+            );
+        }
+
+        self.socket
+            .send(Message::Text(Value::Object(request).to_string().into()))
+            .await
+            .map_err(|error| anyhow!("Failed to send CDP command '{}': {}", method, error))?;
+        Ok(request_id)
+    }
+
     async fn poll_event(&mut self, timeout_window: Duration) -> Result<Option<Value>> {
         if let Some(event) = self.queued_events.pop_front() {
             return Ok(Some(event));
@@ -1438,10 +2744,13 @@ impl CdpClient {
         };
 
         if let Some(response_id) = payload.get("id").and_then(Value::as_i64) {
-            self.queued_responses.insert(response_id, payload);
+            if !self.detached_requests.remove(&response_id) {
+                self.queued_responses.insert(response_id, payload);
+            }
             return Ok(None);
         }
 
+        self.observe_event(&payload).await?;
         Ok(Some(payload))
     }
 
@@ -1487,6 +2796,89 @@ impl CdpClient {
     }
 }
 
+fn decode_response_body(result: &Value) -> Vec<u8> {
+    let body = result
+        .get("body")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if result
+        .get("base64Encoded")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        BASE64_STANDARD.decode(body).unwrap_or_default()
+    } else {
+        body.as_bytes().to_vec()
+    }
+}
+
+fn parallel_concurrency_limit(requested: Option<usize>, executor_cap: usize) -> usize {
+    let cap = executor_cap.max(1);
+    requested.map_or(cap, |requested| requested.clamp(1, cap))
+}
+
+/// Run each branch of a parallel group in its own tab, at most `limit` at a
+/// time. Branch failures are reported per branch instead of aborting siblings.
+async fn run_parallel_branches(
+    ws_endpoint: &str,
+    branches: &[Vec<BrowserAction>],
+    limit: usize,
+    artifacts_dir: &str,
+    network: Option<&PageNetwork>,
+) -> Result<Vec<Value>> {
+    if branches.is_empty() {
+        bail!("Parallel action group requires at least one branch");
+    }
+
+    // Build the futures up front: a closure inside the stream trips the
+    // higher-ranked lifetime check when this runs under an `async_trait`.
+    let branches: Vec<_> = branches
+        .iter()
+        .enumerate()
+        .map(|(index, actions)| async move {
+            match run_parallel_branch(ws_endpoint, actions, artifacts_dir, network).await {
+                Ok(values) => json!({"index": index, "success": true, "result": values}),
+                Err(error) => {
+                    json!({"index": index, "success": false, "error": error.to_string()})
+                }
+            }
+        })
+        .collect();
+    let results = futures_util::stream::iter(branches)
+        .buffered(limit)
+        .collect::<Vec<_>>()
+        .await;
+
+    Ok(results)
+}
+
+async fn run_parallel_branch(
+    ws_endpoint: &str,
+    actions: &[BrowserAction],
+    artifacts_dir: &str,
+    network: Option<&PageNetwork>,
+) -> Result<Vec<Value>> {
+    let mut page = CdpPage::open(ws_endpoint, network).await?;
+    let mut outputs = Vec::with_capacity(actions.len());
+    let mut outcome = Ok(());
+    for action in actions {
+        let result = page.execute_action(action, artifacts_dir).await;
+        page.cdp.flush_recording().await;
+        match result {
+            Ok(output) => outputs.push(output),
+            Err(error) => {
+                outcome = Err(error);
+                break;
+            }
+        }
+    }
+
+    if let Err(error) = page.close().await {
+        tracing::warn!("Failed to close parallel branch tab: {}", error);
+    }
+    outcome.map(|_| outputs)
+}
+
 fn cdp_event_matches_session(event: &Value, expected_session: &str) -> bool {
     match event.get("sessionId").and_then(Value::as_str) {
         Some(session_id) => session_id == expected_session,
@@ -1610,6 +3002,64 @@ async fn wait_for_debugger_ws_url(
     }
 }
 
+/// Read `webSocketDebuggerUrl` from `<endpoint>/json/version`.
+async fn resolve_remote_ws_url(endpoint: &str, timeout_secs: u64) -> Result<String> {
+    let version_url = format!("{}/json/version", endpoint.trim_end_matches('/'));
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs.clamp(1, 30)))
+        .build()?;
+    let body: Value = client
+        .get(&version_url)
+        .send()
+        .await
+        .map_err(|error| anyhow!("Failed to reach CDP endpoint {}: {}", version_url, error))?
+        .error_for_status()?
+        .json()
+        .await?;
+    let ws_url = body
+        .get("webSocketDebuggerUrl")
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            anyhow!(
+                "CDP endpoint {} did not report webSocketDebuggerUrl",
+                version_url
+            )
+        })?;
+    Ok(rewrite_remote_ws_host(ws_url, endpoint))
+}
+
+/// Remote browsers often report `ws://localhost:9222/...` or a container-local
+/// address; point the websocket at the host the caller used instead.
+fn rewrite_remote_ws_host(ws_url: &str, endpoint: &str) -> String {
+    let Some((_, endpoint_rest)) = endpoint.split_once("://") else {
+        return ws_url.to_string();
+    };
+    let endpoint_host = endpoint_rest.split('/').next().unwrap_or_default();
+    let Some((ws_scheme, ws_rest)) = ws_url.split_once("://") else {
+        return ws_url.to_string();
+    };
+    let Some((_, ws_path)) = ws_rest.split_once('/') else {
+        return ws_url.to_string();
+    };
+    let scheme = if endpoint.starts_with("https://") {
+        "wss"
+    } else {
+        ws_scheme
+    };
+    format!("{}://{}/{}", scheme, endpoint_host, ws_path)
+}
+
+fn validate_cdp_endpoint(endpoint: &str) -> Result<String> {
+    const SCHEMES: [&str; 4] = ["ws://", "wss://", "http://", "https://"];
+    if !SCHEMES.iter().any(|scheme| endpoint.starts_with(scheme)) {
+        bail!(
+            "Unsupported CDP endpoint '{}': expected ws://, wss://, http:// or https://",
+            endpoint
+        );
+    }
+    Ok(endpoint.to_string())
+}
+
 async fn transpile_typescript_source(
     source: &str,
     cwd: Option<&Path>,
@@ -1796,19 +3246,56 @@ fn mouse_button_mask(button: MouseButton) -> u8 {
     }
 }
 
-fn build_user_script_wrapper(source: &str) -> Result<String> {
+fn build_user_script_wrapper(source: &str, args: &str, binding_name: &str) -> Result<String> {
     let source = serde_json::to_string(source)?;
+    let binding_name = serde_json::to_string(binding_name)?;
     Ok(format!(
-        "(async () => {{\n  const rf = {{\n    url: () => location.href,\n    title: () => document.title,\n    text: (selector) => document.querySelector(selector)?.textContent ?? null,\n    click: (selector) => {{ const el = document.querySelector(selector); if (!el) throw new Error(`Selector not found: ${{selector}}`); el.click(); return true; }},\n    fill: (selector, value) => {{ const el = document.querySelector(selector); if (!el) throw new Error(`Selector not found: ${{selector}}`); el.focus?.(); el.value = value; el.dispatchEvent(new Event('input', {{ bubbles: true }})); el.dispatchEvent(new Event('change', {{ bubbles: true }})); return true; }},\n  }};\n  let __restflowResult = null;\n  const setRestflowResult = (value) => {{ __restflowResult = value; }};\n  const __source = {};\n  const __userMain = new Function('rf', 'setRestflowResult', `return (async () => {{\\n${{__source}}\\n}})();`);\n  const returned = await __userMain(rf, setRestflowResult);\n  return {{ ok: true, value: returned === undefined ? __restflowResult : returned }};\n}})()",
-        source
+        "(async () => {{\n  const __emit = (() => {{ const fn = globalThis[{binding_name}]; try {{ delete globalThis[{binding_name}]; }} catch (_ignored) {{}} return typeof fn === 'function' ? fn : null; }})();\n  const rf = {{\n    url: () => location.href,\n    title: () => document.title,\n    text: (selector) => document.querySelector(selector)?.textContent ?? null,\n    click: (selector) => {{ const el = document.querySelector(selector); if (!el) throw new Error(`Selector not found: ${{selector}}`); el.click(); return true; }},\n    fill: (selector, value) => {{ const el = document.querySelector(selector); if (!el) throw new Error(`Selector not found: ${{selector}}`); el.focus?.(); el.value = value; el.dispatchEvent(new Event('input', {{ bubbles: true }})); el.dispatchEvent(new Event('change', {{ bubbles: true }})); return true; }},\n    emit: (event) => {{ if (!__emit) return false; __emit(JSON.stringify(event === undefined ? null : event)); return true; }},\n  }};\n  let __restflowResult = null;\n  const setRestflowResult = (value) => {{ __restflowResult = value; }};\n  const __source = {source};\n  const __userMain = new Function('rf', 'setRestflowResult', 'args', `return (async () => {{\\n${{__source}}\\n}})();`);\n  const returned = await __userMain(rf, setRestflowResult, {args});\n  return {{ ok: true, value: returned === undefined ? __restflowResult : returned }};\n}})()"
     ))
 }
 
-fn build_dynamic_eval_script(source: &str) -> Result<String> {
+/// Serialize script `args`, which must be a JSON object when present.
+fn script_args_json(args: Option<&Value>) -> Result<String> {
+    match args {
+        None | Some(Value::Null) => Ok("{}".to_string()),
+        Some(value @ Value::Object(_)) => Ok(serde_json::to_string(value)?),
+        Some(_) => bail!("Script args must be a JSON object"),
+    }
+}
+
+/// Prelude evaluated before restricted-world scripts. Isolated worlds have
+/// their own DOM wrappers, so these overrides never leak into the page.
+const RESTRICTED_WORLD_PRELUDE: &str = r#"(() => {
+  const blocked = (name) => () => { throw new Error(`${name} is blocked in the restricted script sandbox`); };
+  const deny = (target, name, label) => {
+    try {
+      Object.defineProperty(target, name, { get: blocked(label), set: blocked(label), configurable: false });
+    } catch (_ignored) {}
+  };
+  deny(document, 'cookie', 'document.cookie');
+  for (const name of ['localStorage', 'sessionStorage', 'indexedDB', 'caches', 'cookieStore']) {
+    deny(globalThis, name, name);
+  }
+  if (globalThis.navigator) {
+    for (const name of ['credentials', 'serviceWorker', 'storage']) {
+      deny(navigator, name, `navigator.${name}`);
+    }
+  }
+})();"#;
+
+/// Without `args` the source is evaluated globally via indirect eval. With
+/// `args` it needs a direct eval so the local `args` binding is in scope.
+fn build_dynamic_eval_script(source: &str, args: Option<&Value>) -> Result<String> {
     let source = serde_json::to_string(source)?;
+    let (prelude, eval) = match args {
+        Some(args) => (
+            format!("  const args = {};\n", script_args_json(Some(args))?),
+            "eval",
+        ),
+        None => (String::new(), "(0, eval)"),
+    };
     Ok(format!(
-        "(async () => {{\n  const __source = {};\n  try {{\n    const expressionResult = await (0, eval)('(' + __source + ')');\n    return {{ ok: true, value: expressionResult }};\n  }} catch (_ignored) {{}}\n  try {{\n    const statementResult = await (0, eval)(__source);\n    return {{ ok: true, value: statementResult }};\n  }} catch (error) {{\n    return {{ ok: false, error: error?.stack ?? String(error) }};\n  }}\n}})()",
-        source
+        "(async () => {{\n{prelude}  const __source = {source};\n  try {{\n    const expressionResult = await {eval}('(' + __source + ')');\n    return {{ ok: true, value: expressionResult }};\n  }} catch (_ignored) {{}}\n  try {{\n    const statementResult = await {eval}(__source);\n    return {{ ok: true, value: statementResult }};\n  }} catch (error) {{\n    return {{ ok: false, error: error?.stack ?? String(error) }};\n  }}\n}})()"
     ))
 }
 
@@ -1837,6 +3324,54 @@ fn selector_state_matches(state: &str, present: bool, visible: bool) -> bool {
     }
 }
 
+fn trace_dir_for_run(artifacts_dir: &str) -> PathBuf {
+    let run_id = format!(
+        "{}-{}",
+        Utc::now().format("%Y%m%dT%H%M%S%3f"),
+        &Uuid::new_v4().simple().to_string()[..8]
+    );
+    Path::new(artifacts_dir).join("traces").join(run_id)
+}
+
+fn failure_payload(error: &anyhow::Error, failure: Option<&BrowserFailureSnapshot>) -> Value {
+    let mut payload = json!({"success": false, "error": error.to_string()});
+    if let Some(failure) = failure
+        && let Ok(value) = serde_json::to_value(failure)
+    {
+        payload["failure"] = value;
+    }
+    payload
+}
+
+fn action_type_name(action: &BrowserAction) -> String {
+    serde_json::to_value(action)
+        .ok()
+        .and_then(|value| {
+            value
+                .get("type")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn performance_metrics_from_cdp(result: &Value) -> serde_json::Map<String, Value> {
+    result
+        .get("metrics")
+        .and_then(Value::as_array)
+        .map(|metrics| {
+            metrics
+                .iter()
+                .filter_map(|metric| {
+                    let name = metric.get("name")?.as_str()?;
+                    let value = metric.get("value")?.clone();
+                    Some((name.to_string(), value))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn resolve_artifact_path(artifacts_dir: &str, path: &str) -> PathBuf {
     let target = PathBuf::from(path);
     if target.is_absolute() {
@@ -1876,6 +3411,73 @@ async fn run_command_capture(
     })
 }
 
+/// Sum the resident set size of `pids`, via procfs on Linux and `ps` elsewhere.
+async fn process_rss_bytes(pids: &[u32]) -> Option<u64> {
+    if pids.is_empty() || cfg!(windows) {
+        return None;
+    }
+
+    if cfg!(target_os = "linux") {
+        let sizes: Vec<u64> = pids
+            .iter()
+            .filter_map(|pid| std::fs::read_to_string(format!("/proc/{pid}/status")).ok())
+            .filter_map(|status| parse_proc_status_rss_kb(&status))
+            .collect();
+        if sizes.is_empty() {
+            return None;
+        }
+        return Some(sizes.iter().sum::<u64>() * 1024);
+    }
+
+    let pid_list = pids
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let args = vec![
+        "-o".to_string(),
+        "rss=".to_string(),
+        "-p".to_string(),
+        pid_list,
+    ];
+    let capture = run_command_capture("ps", &args, None, 5).await.ok()?;
+    Some(parse_ps_rss_kb(&capture.stdout) * 1024)
+}
+
+fn parse_proc_status_rss_kb(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+fn parse_ps_rss_kb(output: &str) -> u64 {
+    output
+        .lines()
+        .filter_map(|line| line.trim().parse::<u64>().ok())
+        .sum()
+}
+
+fn directory_size_bytes(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = std::fs::symlink_metadata(entry.path()).ok()?;
+            if metadata.is_dir() {
+                Some(directory_size_bytes(&entry.path()))
+            } else if metadata.is_file() {
+                Some(metadata.len())
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
 struct CommandCapture {
     exit_code: i32,
     stdout: String,
@@ -1936,7 +3538,7 @@ fn resolve_chromium_binary() -> Option<String> {
         }
     }
 
-    None
+    provision::installed_headless_shell()
 }
 
 fn is_executable_in_path(name: &str) -> bool {
@@ -2035,6 +3637,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 payload: Some(json!({"success": true, "result": {"kind": "script"}})),
+                trace_path: None,
             })
         }
 
@@ -2051,6 +3654,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 payload: Some(json!({"success": true, "result": [{"ok": true}]})),
+                trace_path: None,
             })
         }
 
@@ -2084,6 +3688,309 @@ mod tests {
         assert!(service.list_sessions().await.is_empty());
     }
 
+    #[tokio::test]
+    async fn new_session_records_remote_cdp_endpoint() {
+        let temp = tempdir().unwrap();
+        let service = BrowserService::new_with_executor(
+            temp.path().join("browser"),
+            Arc::new(MockExecutor::default()),
+        )
+        .unwrap();
+
+        let session = service
+            .new_session(NewSessionRequest {
+                cdp_endpoint: Some(" http://grid.local:9222 ".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            session.cdp_endpoint.as_deref(),
+            Some("http://grid.local:9222")
+        );
+
+        let error = service
+            .new_session(NewSessionRequest {
+                cdp_endpoint: Some("grid.local:9222".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Unsupported CDP endpoint"));
+    }
+
+    #[tokio::test]
+    async fn new_session_resolves_fixture_directories() {
+        let temp = tempdir().unwrap();
+        let service = BrowserService::new_with_executor(
+            temp.path().join("browser"),
+            Arc::new(MockExecutor::default()),
+        )
+        .unwrap();
+
+        let recording = service
+            .new_session(NewSessionRequest {
+                network_mode: NetworkMode::Record,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            recording.fixtures_dir.as_deref().map(Path::new),
+            Some(Path::new(&recording.session_dir).join("fixtures").as_path())
+        );
+
+        let fixtures_dir = temp.path().join("fixtures");
+        let missing = service
+            .new_session(NewSessionRequest {
+                network_mode: NetworkMode::Replay,
+                fixtures_dir: Some(fixtures_dir.display().to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(missing.to_string().contains("No recorded network fixtures"));
+
+        let store = FixtureStore::open(&fixtures_dir).unwrap();
+        store.record(NetworkFixture::new(
+            "GET",
+            "https://example.com/",
+            200,
+            BTreeMap::new(),
+            b"ok",
+        ));
+        store.save().unwrap();
+        let replay = service
+            .new_session(NewSessionRequest {
+                network_mode: NetworkMode::Replay,
+                fixtures_dir: Some(fixtures_dir.display().to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(replay.network_mode, NetworkMode::Replay);
+    }
+
+    #[test]
+    fn doctor_report_lists_remediation_for_missing_runtime() {
+        let report = BrowserDoctorReport::from(RuntimeProbe {
+            node_available: true,
+            node_version: Some("v20.11.0".to_string()),
+            ..RuntimeProbe::empty()
+        });
+        assert_eq!(report.remediation.len(), 2);
+        assert!(report.remediation[0].contains("install_runtime"));
+        assert!(report.remediation[1].contains("v20.11.0"));
+
+        let ready = BrowserDoctorReport::from(RuntimeProbe {
+            node_available: true,
+            node_typescript_available: true,
+            chromium_cache_detected: true,
+            ready: true,
+            ..RuntimeProbe::empty()
+        });
+        assert!(ready.remediation.is_empty());
+    }
+
+    #[test]
+    fn network_hook_records_responses_and_redirects() {
+        let temp = tempdir().unwrap();
+        let network = PageNetwork {
+            mode: NetworkMode::Record,
+            store: Arc::new(FixtureStore::open(temp.path()).unwrap()),
+        };
+        let mut hook = NetworkHook::new(&network);
+
+        let sent = json!({
+            "method": "Network.requestWillBeSent",
+            "sessionId": "page",
+            "params": {"requestId": "r1", "request": {"url": "http://a.test/", "method": "GET"}}
+        });
+        assert!(hook.observe(&sent).is_none());
+        let redirected = json!({
+            "method": "Network.requestWillBeSent",
+            "sessionId": "page",
+            "params": {
+                "requestId": "r1",
+                "request": {"url": "https://a.test/", "method": "GET"},
+                "redirectResponse": {
+                    "url": "http://a.test/",
+                    "status": 301,
+                    "headers": {"Location": "https://a.test/"}
+                }
+            }
+        });
+        hook.observe(&redirected);
+        let received = json!({
+            "method": "Network.responseReceived",
+            "sessionId": "page",
+            "params": {
+                "requestId": "r1",
+                "response": {
+                    "url": "https://a.test/",
+                    "status": 200,
+                    "headers": {"Content-Type": "text/html", "Content-Encoding": "gzip"}
+                }
+            }
+        });
+        hook.observe(&received);
+        hook.observe(&json!({
+            "method": "Network.loadingFinished",
+            "sessionId": "page",
+            "params": {"requestId": "r1"}
+        }));
+
+        assert_eq!(network.store.len(), 1);
+        let redirect = network
+            .store
+            .next_response("GET", "http://a.test/")
+            .unwrap();
+        assert_eq!(redirect.status, 301);
+        assert_eq!(hook.finished.len(), 1);
+        let (request_id, response) = &hook.finished[0];
+        assert_eq!(request_id, "r1");
+        assert_eq!(response.session_id.as_deref(), Some("page"));
+        assert!(response.headers.contains_key("Content-Type"));
+        assert!(!response.headers.contains_key("Content-Encoding"));
+    }
+
+    #[test]
+    fn network_hook_replays_or_fails_paused_requests() {
+        let temp = tempdir().unwrap();
+        let store = FixtureStore::open(temp.path()).unwrap();
+        store.record(NetworkFixture::new(
+            "GET",
+            "https://a.test/data.json",
+            200,
+            BTreeMap::from([("Content-Type".to_string(), "application/json".to_string())]),
+            b"{}",
+        ));
+        let network = PageNetwork {
+            mode: NetworkMode::Replay,
+            store: Arc::new(store),
+        };
+        let mut hook = NetworkHook::new(&network);
+
+        let paused = |url: &str| {
+            json!({
+                "method": "Fetch.requestPaused",
+                "sessionId": "page",
+                "params": {"requestId": "f1", "request": {"url": url, "method": "GET"}}
+            })
+        };
+        let (session_id, method, params) =
+            hook.observe(&paused("https://a.test/data.json")).unwrap();
+        assert_eq!(session_id.as_deref(), Some("page"));
+        assert_eq!(method, "Fetch.fulfillRequest");
+        assert_eq!(params["responseCode"], json!(200));
+        assert_eq!(params["body"], json!(BASE64_STANDARD.encode("{}")));
+
+        let (_, method, params) = hook.observe(&paused("https://b.test/")).unwrap();
+        assert_eq!(method, "Fetch.failRequest");
+        assert_eq!(params["errorReason"], json!("InternetDisconnected"));
+    }
+
+    #[test]
+    fn remote_ws_url_uses_caller_host() {
+        assert_eq!(
+            rewrite_remote_ws_host(
+                "ws://localhost:9222/devtools/browser/abc",
+                "http://10.0.0.5:9222"
+            ),
+            "ws://10.0.0.5:9222/devtools/browser/abc"
+        );
+        assert_eq!(
+            rewrite_remote_ws_host(
+                "ws://0.0.0.0:3000/devtools/browser/abc",
+                "https://chrome.example.com/"
+            ),
+            "wss://chrome.example.com/devtools/browser/abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn session_stats_reports_artifacts_usage() {
+        let temp = tempdir().unwrap();
+        let service = BrowserService::new_with_executor(
+            temp.path().join("browser"),
+            Arc::new(MockExecutor::default()),
+        )
+        .unwrap();
+        let session = service
+            .new_session(NewSessionRequest::default())
+            .await
+            .unwrap();
+
+        let nested = Path::new(&session.artifacts_dir).join("shots");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("a.png"), vec![0u8; 100]).unwrap();
+        std::fs::write(Path::new(&session.artifacts_dir).join("b.json"), b"{}").unwrap();
+
+        let stats = service.session_stats(&session.id).await.unwrap();
+        assert_eq!(stats.session_id, session.id);
+        assert_eq!(stats.artifacts_bytes, 102);
+        assert!(stats.uptime_ms >= 0);
+        assert!(stats.runtime.is_none());
+
+        assert!(service.session_stats("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn run_actions_rotates_artifacts_over_quota() {
+        let temp = tempdir().unwrap();
+        let service = BrowserService::new_with_executor(
+            temp.path().join("browser"),
+            Arc::new(MockExecutor::default()),
+        )
+        .unwrap()
+        .with_artifact_retention(ArtifactRetentionPolicy {
+            max_session_bytes: 100,
+            ..Default::default()
+        });
+        let session = service
+            .new_session(NewSessionRequest::default())
+            .await
+            .unwrap();
+        let artifacts = Path::new(&session.artifacts_dir);
+        std::fs::write(artifacts.join("old.png"), vec![0u8; 80]).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(artifacts.join("old.png"))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(60))
+            .unwrap();
+        std::fs::write(artifacts.join("new.png"), vec![0u8; 80]).unwrap();
+
+        service
+            .run_actions(&RunActionsRequest {
+                session_id: session.id.clone(),
+                actions: Vec::new(),
+                runtime: ScriptRuntime::Auto,
+                timeout_secs: 5,
+                cwd: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(!artifacts.join("old.png").exists());
+        assert!(artifacts.join("new.png").exists());
+        let stats = service.artifact_retention_stats();
+        assert_eq!(stats.files_removed, 1);
+        assert_eq!(stats.bytes_reclaimed, 80);
+
+        let report = service.purge_artifacts(Some(&session.id)).await.unwrap();
+        assert_eq!(report.files_removed, 0);
+        assert_eq!(report.bytes_remaining, 80);
+    }
+
+    #[test]
+    fn rss_parsers_read_kilobytes() {
+        let status = "Name:\tchrome\nVmPeak:\t  900 kB\nVmRSS:\t  51234 kB\n";
+        assert_eq!(parse_proc_status_rss_kb(status), Some(51234));
+        assert_eq!(parse_proc_status_rss_kb("Name:\tchrome\n"), None);
+        assert_eq!(parse_ps_rss_kb(" 1200\n  300\n\n"), 1500);
+    }
+
     #[tokio::test]
     async fn close_session_forwards_cleanup_to_executor() {
         let temp = tempdir().unwrap();
@@ -2122,6 +4029,8 @@ mod tests {
                 runtime: ScriptRuntime::Auto,
                 timeout_secs: 30,
                 cwd: None,
+                sandbox: ScriptSandbox::None,
+                args: None,
             })
             .await
             .unwrap();
@@ -2130,6 +4039,35 @@ mod tests {
         assert_eq!(executor.script_calls.load(Ordering::Relaxed), 1);
     }
 
+    #[tokio::test]
+    async fn run_script_with_events_falls_back_for_executors_without_bindings() {
+        let temp = tempdir().unwrap();
+        let executor = Arc::new(MockExecutor::default());
+        let service =
+            BrowserService::new_with_executor(temp.path().join("browser"), executor.clone())
+                .unwrap();
+
+        let session = service
+            .new_session(NewSessionRequest::default())
+            .await
+            .unwrap();
+        let request: RunScriptRequest = serde_json::from_value(json!({
+            "session_id": session.id,
+            "code": "rf.emit(args.step); return args.step;",
+            "args": {"step": 1}
+        }))
+        .unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let output = service
+            .run_script_with_events(&request, sender)
+            .await
+            .unwrap();
+
+        assert_eq!(output.runtime, "mock");
+        assert_eq!(executor.script_calls.load(Ordering::Relaxed), 1);
+        assert!(receiver.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn run_actions_requires_existing_session() {
         let temp = tempdir().unwrap();
@@ -2161,6 +4099,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_actions_rejects_navigation_outside_policy() {
+        let temp = tempdir().unwrap();
+        let executor = Arc::new(MockExecutor::default());
+        let service =
+            BrowserService::new_with_executor(temp.path().join("browser"), executor.clone())
+                .unwrap()
+                .with_navigation_policy(NavigationPolicy {
+                    allowed_domains: vec!["example.com".to_string()],
+                    ..Default::default()
+                });
+
+        let session = service
+            .new_session(NewSessionRequest::default())
+            .await
+            .unwrap();
+        let result = service
+            .run_actions(&RunActionsRequest {
+                session_id: session.id,
+                actions: vec![BrowserAction::Navigate {
+                    url: "https://intranet.corp/".to_string(),
+                    wait_until: None,
+                }],
+                runtime: ScriptRuntime::Auto,
+                timeout_secs: 30,
+                cwd: None,
+            })
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("allowlist"));
+        assert_eq!(executor.action_calls.load(Ordering::Relaxed), 0);
+    }
+
     #[test]
     fn resolve_default_root_dir_uses_temp_dir() {
         let _guard = env_lock();
@@ -2261,6 +4232,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn evaluate_action_accepts_script_sandbox() {
+        let actions: Vec<BrowserAction> = serde_json::from_value(json!([
+            {"type": "evaluate", "expression": "1 + 1"},
+            {"type": "evaluate", "expression": "document.title", "sandbox": "restricted"}
+        ]))
+        .unwrap();
+
+        assert!(matches!(
+            actions[0],
+            BrowserAction::Evaluate {
+                sandbox: ScriptSandbox::None,
+                ..
+            }
+        ));
+        assert!(matches!(
+            actions[1],
+            BrowserAction::Evaluate {
+                sandbox: ScriptSandbox::Restricted,
+                ..
+            }
+        ));
+        assert_eq!(ScriptSandbox::None.world_name(), "main");
+        assert_eq!(ScriptSandbox::Isolated.world_name(), ISOLATED_WORLD_NAME);
+        assert!(RESTRICTED_WORLD_PRELUDE.contains("'cookie'"));
+        assert!(RESTRICTED_WORLD_PRELUDE.contains("localStorage"));
+    }
+
+    #[test]
+    fn clipboard_actions_deserialize() {
+        let actions: Vec<BrowserAction> = serde_json::from_value(json!([
+            {"type": "set_clipboard", "text": "order-42"},
+            {"type": "read_clipboard"}
+        ]))
+        .unwrap();
+
+        assert!(matches!(
+            &actions[0],
+            BrowserAction::SetClipboard { text } if text == "order-42"
+        ));
+        assert!(matches!(actions[1], BrowserAction::ReadClipboard));
+        assert_eq!(action_type_name(&actions[1]), "read_clipboard");
+    }
+
+    #[test]
+    fn browser_action_deserializes_parallel_branches() {
+        let parallel: BrowserAction = serde_json::from_value(json!({
+            "type": "parallel",
+            "branches": [
+                [
+                    { "type": "navigate", "url": "https://example.com/a" },
+                    { "type": "extract_text", "selector": "h1" }
+                ],
+                [{ "type": "navigate", "url": "https://example.com/b" }]
+            ]
+        }))
+        .unwrap();
+        match parallel {
+            BrowserAction::Parallel {
+                branches,
+                max_concurrency,
+            } => {
+                assert_eq!(branches.len(), 2);
+                assert_eq!(branches[0].len(), 2);
+                assert!(matches!(
+                    branches[0][1],
+                    BrowserAction::ExtractText { all: false, .. }
+                ));
+                assert!(max_concurrency.is_none());
+            }
+            _ => panic!("expected parallel action"),
+        }
+    }
+
+    #[test]
+    fn parallel_concurrency_limit_is_clamped_to_executor_cap() {
+        assert_eq!(parallel_concurrency_limit(None, 4), 4);
+        assert_eq!(parallel_concurrency_limit(Some(2), 4), 2);
+        assert_eq!(parallel_concurrency_limit(Some(16), 4), 4);
+        assert_eq!(parallel_concurrency_limit(Some(0), 4), 1);
+        assert_eq!(parallel_concurrency_limit(None, 0), 1);
+    }
+
+    #[tokio::test]
+    async fn parallel_group_requires_branches() {
+        let error = run_parallel_branches("ws://127.0.0.1:1", &[], 2, "/tmp", None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("at least one branch"));
+    }
+
     #[test]
     fn modifier_masks_match_cdp_bitflags() {
         assert_eq!(modifier_mask(&[]), 0);
@@ -2313,11 +4375,136 @@ mod tests {
         assert_eq!(absolute, PathBuf::from("/var/tmp/page.png"));
     }
 
+    #[test]
+    fn trace_helpers_name_actions_and_collect_metrics() {
+        let action = BrowserAction::Screenshot {
+            path: "page.png".to_string(),
+            full_page: false,
+        };
+        assert_eq!(action_type_name(&action), "screenshot");
+
+        let metrics = performance_metrics_from_cdp(&json!({
+            "metrics": [
+                {"name": "JSHeapUsedSize", "value": 1024},
+                {"name": "Nodes", "value": 12},
+                {"value": 3}
+            ]
+        }));
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics.get("Nodes"), Some(&json!(12)));
+        assert!(performance_metrics_from_cdp(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn failure_payload_embeds_snapshot_paths() {
+        let error = anyhow!("selector not found");
+        let payload = failure_payload(&error, None);
+        assert_eq!(payload["error"], "selector not found");
+        assert!(payload.get("failure").is_none());
+
+        let snapshot = BrowserFailureSnapshot {
+            action_index: 2,
+            action_type: "click".to_string(),
+            error: error.to_string(),
+            url: Some("https://example.com/".to_string()),
+            title: Some("Example".to_string()),
+            screenshot_path: Some("/tmp/action-2-failure.png".to_string()),
+            snapshot_path: Some("/tmp/action-2-failure.json".to_string()),
+        };
+        let payload = failure_payload(&error, Some(&snapshot));
+        assert_eq!(payload["success"], false);
+        assert_eq!(payload["failure"]["action_index"], 2);
+        assert_eq!(payload["failure"]["title"], "Example");
+        assert_eq!(
+            payload["failure"]["screenshot_path"],
+            "/tmp/action-2-failure.png"
+        );
+    }
+
+    #[test]
+    fn trace_is_written_under_run_directory() {
+        let temp = tempdir().unwrap();
+        let artifacts_dir = temp.path().to_string_lossy().to_string();
+        let trace_dir = trace_dir_for_run(&artifacts_dir);
+        assert!(trace_dir.starts_with(temp.path().join("traces")));
+
+        let mut trace = BrowserTrace::new("session-1");
+        trace.success = true;
+        trace.actions.push(BrowserTraceAction {
+            index: 0,
+            action_type: "navigate".to_string(),
+            started_offset_ms: 0,
+            duration_ms: 42,
+            success: true,
+            error: None,
+            screenshot_path: None,
+        });
+
+        let path = trace.write(&trace_dir).unwrap();
+        assert_eq!(path, trace_dir.join("trace.json"));
+        let stored: BrowserTrace = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(stored.session_id, "session-1");
+        assert_eq!(stored.actions[0].duration_ms, 42);
+    }
+
     #[test]
     fn dynamic_eval_script_contains_user_source() {
-        let script = build_dynamic_eval_script("1 + 2").unwrap();
+        let script = build_dynamic_eval_script("1 + 2", None).unwrap();
         assert!(script.contains("1 + 2"));
-        assert!(script.contains("eval"));
+        assert!(script.contains("(0, eval)"));
+        assert!(!script.contains("const args"));
+    }
+
+    #[test]
+    fn dynamic_eval_script_binds_args_in_local_scope() {
+        let script = build_dynamic_eval_script("args.a + 1", Some(&json!({"a": 41}))).unwrap();
+        assert!(script.contains(r#"const args = {"a":41};"#));
+        assert!(!script.contains("(0, eval)"));
+
+        let error = build_dynamic_eval_script("args", Some(&json!([1, 2]))).unwrap_err();
+        assert!(error.to_string().contains("JSON object"));
+    }
+
+    #[test]
+    fn user_script_wrapper_passes_args_and_emit_binding() {
+        let args = script_args_json(Some(&json!({"query": "rust"}))).unwrap();
+        let script =
+            build_user_script_wrapper("return args.query;", &args, "__restflowEmit_test").unwrap();
+        assert!(script.contains(r#"globalThis["__restflowEmit_test"]"#));
+        assert!(script.contains("'setRestflowResult', 'args'"));
+        assert!(script.contains(r#"setRestflowResult, {"query":"rust"})"#));
+        assert!(script.contains("emit: (event)"));
+        assert_eq!(script_args_json(None).unwrap(), "{}");
+    }
+
+    #[test]
+    fn script_binding_forwards_matching_calls_only() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut binding = ScriptBinding::new("__restflowEmit_a", "session-1", Some(sender));
+
+        assert!(!binding.accept(&json!({"method": "Page.loadEventFired", "params": {}})));
+        assert!(!binding.accept(&json!({
+            "method": "Runtime.bindingCalled",
+            "params": {"name": "__restflowEmit_b", "payload": "1"}
+        })));
+        assert!(binding.accept(&json!({
+            "method": "Runtime.bindingCalled",
+            "params": {"name": "__restflowEmit_a", "payload": "{\"progress\":0.5}"}
+        })));
+        assert!(binding.accept(&json!({
+            "method": "Runtime.bindingCalled",
+            "params": {"name": "__restflowEmit_a", "payload": "not json"}
+        })));
+
+        let first = receiver.try_recv().unwrap();
+        assert_eq!(first.session_id, "session-1");
+        assert_eq!(first.sequence, 0);
+        assert_eq!(first.payload, json!({"progress": 0.5}));
+        let second = receiver.try_recv().unwrap();
+        assert_eq!(second.sequence, 1);
+        assert_eq!(second.payload, json!("not json"));
+        assert_eq!(binding.emitted, 2);
+        assert_eq!(binding.events.len(), 2);
     }
 
     #[test]
@@ -0,0 +1,228 @@
+//! Navigation policy for agent browsing.
+//!
+//! [`NavigationPolicy`] is checked before an action plan runs, so a plan that
+//! would visit a disallowed domain is rejected before any tab is touched.
+//! Private network checks reuse the SSRF rules from `restflow_traits::network`,
+//! including DNS resolution, so a public-looking name that resolves to an
+//! internal address is refused as well.
+
+use crate::BrowserAction;
+use anyhow::{Result, anyhow, bail};
+use restflow_traits::network::{resolve_and_validate_url, validate_url};
+use serde::{Deserialize, Serialize};
+
+/// Domains and address ranges browser sessions may navigate to.
+///
+/// The default policy imposes no restrictions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NavigationPolicy {
+    /// Domains navigation may target; subdomains match. Empty allows any domain.
+    pub allowed_domains: Vec<String>,
+    /// Domains navigation may never target. Checked before the allowlist.
+    pub denied_domains: Vec<String>,
+    /// Refuse hosts that are, or resolve to, loopback, private or metadata
+    /// addresses.
+    pub block_private_networks: bool,
+}
+
+impl NavigationPolicy {
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed_domains.is_empty()
+            && self.denied_domains.is_empty()
+            && !self.block_private_networks
+    }
+
+    /// Check a URL against the domain rules and literal IP restrictions
+    /// without resolving DNS.
+    pub fn check_url(&self, url: &str) -> Result<()> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+
+        let parsed = url::Url::parse(url)
+            .map_err(|error| anyhow!("Navigation to '{}' blocked: invalid URL: {}", url, error))?;
+        match parsed.scheme() {
+            "http" | "https" => {}
+            // about:blank and friends never leave the browser.
+            "about" => return Ok(()),
+            scheme => bail!(
+                "Navigation to '{}' blocked: scheme '{}' is not allowed by the browser policy",
+                url,
+                scheme
+            ),
+        }
+
+        let host = parsed
+            .host_str()
+            .map(|host| {
+                host.trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .trim_end_matches('.')
+                    .to_ascii_lowercase()
+            })
+            .ok_or_else(|| anyhow!("Navigation to '{}' blocked: URL has no host", url))?;
+
+        if let Some(rule) = self
+            .denied_domains
+            .iter()
+            .find(|rule| domain_matches(&host, rule))
+        {
+            bail!(
+                "Navigation to '{}' blocked: domain '{}' is denied by the browser policy",
+                host,
+                rule.trim()
+            );
+        }
+        if !self.allowed_domains.is_empty()
+            && !self
+                .allowed_domains
+                .iter()
+                .any(|rule| domain_matches(&host, rule))
+        {
+            bail!(
+                "Navigation to '{}' blocked: domain is not in the browser allowlist",
+                host
+            );
+        }
+        if self.block_private_networks {
+            validate_url(url)
+                .map_err(|error| anyhow!("Navigation to '{}' blocked: {}", host, error))?;
+        }
+        Ok(())
+    }
+
+    /// Check a URL, resolving DNS when private networks are blocked.
+    pub async fn check_navigation(&self, url: &str) -> Result<()> {
+        self.check_url(url)?;
+        if self.block_private_networks && is_http_url(url) {
+            resolve_and_validate_url(url)
+                .await
+                .map_err(|error| anyhow!("Navigation to '{}' blocked: {}", url, error))?;
+        }
+        Ok(())
+    }
+
+    /// Check every navigation in an action plan, including parallel branches.
+    pub async fn check_actions(&self, actions: &[BrowserAction]) -> Result<()> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+        let mut urls = Vec::new();
+        collect_navigation_urls(actions, &mut urls);
+        for url in urls {
+            self.check_navigation(url).await?;
+        }
+        Ok(())
+    }
+}
+
+fn collect_navigation_urls<'a>(actions: &'a [BrowserAction], urls: &mut Vec<&'a str>) {
+    for action in actions {
+        match action {
+            BrowserAction::Navigate { url, .. } => urls.push(url),
+            BrowserAction::Parallel { branches, .. } => {
+                for branch in branches {
+                    collect_navigation_urls(branch, urls);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|parsed| matches!(parsed.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+/// Match `host` against a domain rule. Rules match the domain itself and its
+/// subdomains; a leading `*.` or `.` is accepted and ignored.
+fn domain_matches(host: &str, rule: &str) -> bool {
+    let rule = rule.trim().trim_start_matches("*.").trim_start_matches('.');
+    let rule = rule.trim_end_matches('.').to_ascii_lowercase();
+    if rule.is_empty() {
+        return false;
+    }
+    host == rule
+        || host
+            .strip_suffix(rule.as_str())
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> NavigationPolicy {
+        NavigationPolicy {
+            allowed_domains: allowed.iter().map(|value| value.to_string()).collect(),
+            denied_domains: denied.iter().map(|value| value.to_string()).collect(),
+            block_private_networks: false,
+        }
+    }
+
+    #[test]
+    fn default_policy_allows_everything() {
+        let policy = NavigationPolicy::default();
+        assert!(policy.is_unrestricted());
+        assert!(policy.check_url("http://127.0.0.1:3000/").is_ok());
+        assert!(policy.check_url("file:///etc/passwd").is_ok());
+    }
+
+    #[test]
+    fn allowlist_matches_domain_and_subdomains() {
+        let policy = policy(&["example.com", "*.docs.rs"], &[]);
+        assert!(policy.check_url("https://example.com/a").is_ok());
+        assert!(policy.check_url("https://www.Example.com/").is_ok());
+        assert!(policy.check_url("https://docs.rs/serde").is_ok());
+        assert!(policy.check_url("https://notexample.com/").is_err());
+        assert!(policy.check_url("https://example.com.evil.io/").is_err());
+        assert!(policy.check_url("about:blank").is_ok());
+        assert!(policy.check_url("file:///etc/passwd").is_err());
+    }
+
+    #[test]
+    fn denylist_wins_over_allowlist() {
+        let policy = policy(&["example.com"], &["admin.example.com"]);
+        assert!(policy.check_url("https://example.com/").is_ok());
+        let error = policy
+            .check_url("https://eu.admin.example.com/")
+            .unwrap_err();
+        assert!(error.to_string().contains("denied"));
+    }
+
+    #[test]
+    fn private_network_block_rejects_literal_addresses() {
+        let policy = NavigationPolicy {
+            block_private_networks: true,
+            ..Default::default()
+        };
+        assert!(policy.check_url("http://localhost:8080/").is_err());
+        assert!(policy.check_url("http://10.0.0.5/").is_err());
+        assert!(policy.check_url("http://169.254.169.254/latest/").is_err());
+        assert!(policy.check_url("http://[::1]/").is_err());
+        assert!(policy.check_url("http://8.8.8.8/").is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_actions_covers_parallel_branches() {
+        let policy = policy(&[], &["blocked.test"]);
+        let actions = vec![
+            BrowserAction::Navigate {
+                url: "https://example.com".to_string(),
+                wait_until: None,
+            },
+            BrowserAction::Parallel {
+                branches: vec![vec![BrowserAction::Navigate {
+                    url: "https://blocked.test/page".to_string(),
+                    wait_until: None,
+                }]],
+                max_concurrency: None,
+            },
+        ];
+        assert!(policy.check_actions(&actions).await.is_err());
+        assert!(policy.check_actions(&actions[..1]).await.is_ok());
+    }
+}
@@ -0,0 +1,507 @@
+//! Provisioning of a bundled chrome-headless-shell runtime.
+//!
+//! When no Chrome/Chromium is installed, RestFlow can download a pinned
+//! chrome-headless-shell build from Chrome for Testing into the app data dir.
+//! Downloads are hashed while streaming and must match the SHA-256 pinned for
+//! the platform. Installs are refused when no digest is pinned, and an
+//! existing install is only reused when its recorded digest matches the pin.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Chrome for Testing release pinned for bundled installs.
+pub const PINNED_HEADLESS_SHELL_VERSION: &str = "131.0.6778.85";
+
+const DOWNLOAD_BASE_URL: &str = "https://storage.googleapis.com/chrome-for-testing-public";
+const INSTALL_DIR_ENV: &str = "RESTFLOW_BROWSER_INSTALL_DIR";
+const PINNED_SHA256_ENV: &str = "RESTFLOW_HEADLESS_SHELL_SHA256";
+
+/// SHA-256 of the pinned release archive for each platform, lowercase hex.
+///
+/// Entries are the digests of the Chrome for Testing zips for
+/// [`PINNED_HEADLESS_SHELL_VERSION`] and must be replaced whenever the
+/// version changes. Platforms without an entry can only be provisioned by
+/// supplying a digest in `RESTFLOW_HEADLESS_SHELL_SHA256`.
+const PINNED_HEADLESS_SHELL_SHA256: &[(&str, &str)] = &[];
+const INSTALL_MARKER_FILE: &str = "install.json";
+const PRODUCT_DIR: &str = "chrome-headless-shell";
+
+/// Progress events emitted while provisioning a runtime.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ProvisionEvent {
+    AlreadyInstalled {
+        executable: String,
+    },
+    Downloading {
+        url: String,
+        downloaded_bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    Verifying {
+        sha256: String,
+    },
+    Extracting,
+    Completed {
+        executable: String,
+    },
+}
+
+/// A chrome-headless-shell build for one platform.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HeadlessShellRelease {
+    pub version: String,
+    /// Chrome for Testing platform name (`linux64`, `mac-arm64`, ...).
+    pub platform: String,
+    pub url: String,
+    /// Expected SHA-256 of the archive, lowercase hex.
+    pub sha256: Option<String>,
+}
+
+impl HeadlessShellRelease {
+    /// The pinned release for the current platform.
+    pub fn pinned() -> Result<Self> {
+        let platform = current_platform()
+            .ok_or_else(|| anyhow!("chrome-headless-shell is not available for this platform"))?;
+        let mut release = Self::for_platform(PINNED_HEADLESS_SHELL_VERSION, platform);
+        release.sha256 = pinned_sha256(platform).map(str::to_string).or_else(|| {
+            std::env::var(PINNED_SHA256_ENV)
+                .ok()
+                .map(|value| value.trim().to_ascii_lowercase())
+                .filter(|value| !value.is_empty())
+        });
+        Ok(release)
+    }
+
+    pub fn for_platform(version: &str, platform: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            platform: platform.to_string(),
+            url: format!(
+                "{}/{}/{}/{}-{}.zip",
+                DOWNLOAD_BASE_URL, version, platform, PRODUCT_DIR, platform
+            ),
+            sha256: None,
+        }
+    }
+
+    pub fn with_sha256(mut self, sha256: impl Into<String>) -> Self {
+        self.sha256 = Some(sha256.into().to_ascii_lowercase());
+        self
+    }
+
+    fn executable_relative_path(&self) -> PathBuf {
+        let name = if self.platform.starts_with("win") {
+            format!("{}.exe", PRODUCT_DIR)
+        } else {
+            PRODUCT_DIR.to_string()
+        };
+        PathBuf::from(format!("{}-{}", PRODUCT_DIR, self.platform)).join(name)
+    }
+}
+
+/// Marker written next to a completed installation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledRuntime {
+    pub version: String,
+    pub platform: String,
+    pub sha256: String,
+    pub executable: String,
+    pub installed_at: DateTime<Utc>,
+}
+
+/// Downloads and installs chrome-headless-shell builds.
+pub struct ChromiumProvisioner {
+    install_root: PathBuf,
+    release: HeadlessShellRelease,
+    progress: Option<mpsc::UnboundedSender<ProvisionEvent>>,
+}
+
+impl ChromiumProvisioner {
+    /// Provisioner for the pinned release in the default install directory.
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_release(
+            default_install_root()?,
+            HeadlessShellRelease::pinned()?,
+        ))
+    }
+
+    pub fn with_release(install_root: impl Into<PathBuf>, release: HeadlessShellRelease) -> Self {
+        Self {
+            install_root: install_root.into(),
+            release,
+            progress: None,
+        }
+    }
+
+    pub fn with_progress(mut self, sender: mpsc::UnboundedSender<ProvisionEvent>) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    pub fn release(&self) -> &HeadlessShellRelease {
+        &self.release
+    }
+
+    fn install_dir(&self) -> PathBuf {
+        self.install_root
+            .join(PRODUCT_DIR)
+            .join(&self.release.version)
+            .join(&self.release.platform)
+    }
+
+    /// Return the installed runtime if it exists and its marker is consistent.
+    pub fn installed(&self) -> Option<InstalledRuntime> {
+        let marker_path = self.install_dir().join(INSTALL_MARKER_FILE);
+        let raw = std::fs::read_to_string(marker_path).ok()?;
+        let installed: InstalledRuntime = serde_json::from_str(&raw).ok()?;
+        if self.release.sha256.as_ref() != Some(&installed.sha256) {
+            return None;
+        }
+        Path::new(&installed.executable)
+            .is_file()
+            .then_some(installed)
+    }
+
+    /// Ensure the release is installed, downloading it when missing.
+    pub async fn ensure_installed(&self) -> Result<InstalledRuntime> {
+        if let Some(installed) = self.installed() {
+            self.emit(ProvisionEvent::AlreadyInstalled {
+                executable: installed.executable.clone(),
+            });
+            return Ok(installed);
+        }
+
+        std::fs::create_dir_all(&self.install_root).with_context(|| {
+            format!(
+                "Failed to create browser install dir {}",
+                self.install_root.display()
+            )
+        })?;
+        let archive_path = self
+            .install_root
+            .join(format!(".download-{}.zip", Uuid::new_v4()));
+        let result = self.download_and_install(&archive_path).await;
+        let _ = std::fs::remove_file(&archive_path);
+        result
+    }
+
+    async fn download_and_install(&self, archive_path: &Path) -> Result<InstalledRuntime> {
+        self.expected_sha256()?;
+        let sha256 = self.download(archive_path).await?;
+        self.install_archive(archive_path, sha256).await
+    }
+
+    async fn download(&self, archive_path: &Path) -> Result<String> {
+        let url = &self.release.url;
+        let mut response = reqwest::get(url)
+            .await
+            .with_context(|| format!("Failed to download {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Failed to download {}", url))?;
+        let total_bytes = response.content_length();
+
+        let mut file = File::create(archive_path)?;
+        let mut hasher = Sha256::new();
+        let mut downloaded_bytes = 0u64;
+        self.emit(ProvisionEvent::Downloading {
+            url: url.clone(),
+            downloaded_bytes,
+            total_bytes,
+        });
+        while let Some(chunk) = response.chunk().await? {
+            hasher.update(&chunk);
+            file.write_all(&chunk)?;
+            downloaded_bytes += chunk.len() as u64;
+            self.emit(ProvisionEvent::Downloading {
+                url: url.clone(),
+                downloaded_bytes,
+                total_bytes,
+            });
+        }
+        file.flush()?;
+
+        Ok(hex_digest(hasher.finalize().as_slice()))
+    }
+
+    /// Verify and extract a downloaded archive whose digest is `sha256`.
+    async fn install_archive(
+        &self,
+        archive_path: &Path,
+        sha256: String,
+    ) -> Result<InstalledRuntime> {
+        self.emit(ProvisionEvent::Verifying {
+            sha256: sha256.clone(),
+        });
+        let expected = self.expected_sha256()?;
+        if expected != sha256 {
+            bail!(
+                "Checksum mismatch for chrome-headless-shell {}: expected {}, got {}",
+                self.release.version,
+                expected,
+                sha256
+            );
+        }
+
+        self.emit(ProvisionEvent::Extracting);
+        let install_dir = self.install_dir();
+        let staging_dir = self
+            .install_root
+            .join(format!(".staging-{}", Uuid::new_v4()));
+        let archive = archive_path.to_path_buf();
+        let staging = staging_dir.clone();
+        let extracted = tokio::task::spawn_blocking(move || extract_zip(&archive, &staging))
+            .await
+            .map_err(|error| anyhow!("Extraction task failed: {}", error))?;
+        if let Err(error) = extracted {
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            return Err(error);
+        }
+
+        let relative_executable = self.release.executable_relative_path();
+        if !staging_dir.join(&relative_executable).is_file() {
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            bail!("Archive does not contain {}", relative_executable.display());
+        }
+        mark_executable(&staging_dir.join(&relative_executable))?;
+
+        if install_dir.exists() {
+            std::fs::remove_dir_all(&install_dir)?;
+        }
+        if let Some(parent) = install_dir.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&staging_dir, &install_dir)?;
+
+        let installed = InstalledRuntime {
+            version: self.release.version.clone(),
+            platform: self.release.platform.clone(),
+            sha256,
+            executable: install_dir.join(relative_executable).display().to_string(),
+            installed_at: Utc::now(),
+        };
+        std::fs::write(
+            install_dir.join(INSTALL_MARKER_FILE),
+            serde_json::to_vec_pretty(&installed)?,
+        )?;
+
+        self.emit(ProvisionEvent::Completed {
+            executable: installed.executable.clone(),
+        });
+        Ok(installed)
+    }
+
+    /// Digest the archive must match; there is no unverified install.
+    fn expected_sha256(&self) -> Result<&str> {
+        self.release.sha256.as_deref().ok_or_else(|| {
+            anyhow!(
+                "No pinned checksum for chrome-headless-shell {} on {}; set {} to the archive's SHA-256",
+                self.release.version,
+                self.release.platform,
+                PINNED_SHA256_ENV
+            )
+        })
+    }
+
+    fn emit(&self, event: ProvisionEvent) {
+        if let Some(sender) = &self.progress {
+            let _ = sender.send(event);
+        }
+    }
+}
+
+/// Executable path of the pinned release if it has been provisioned.
+pub fn installed_headless_shell() -> Option<String> {
+    ChromiumProvisioner::new()
+        .ok()?
+        .installed()
+        .map(|installed| installed.executable)
+}
+
+fn pinned_sha256(platform: &str) -> Option<&'static str> {
+    PINNED_HEADLESS_SHELL_SHA256
+        .iter()
+        .find(|(name, _)| *name == platform)
+        .map(|(_, sha256)| *sha256)
+}
+
+/// Chrome for Testing platform name for the running target.
+pub fn current_platform() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Some("linux64"),
+        ("macos", "aarch64") => Some("mac-arm64"),
+        ("macos", "x86_64") => Some("mac-x64"),
+        ("windows", "x86_64") => Some("win64"),
+        ("windows", "x86") => Some("win32"),
+        _ => None,
+    }
+}
+
+fn default_install_root() -> Result<PathBuf> {
+    if let Ok(path) = std::env::var(INSTALL_DIR_ENV)
+        && !path.trim().is_empty()
+    {
+        return Ok(PathBuf::from(path.trim()));
+    }
+    if let Ok(path) = std::env::var("RESTFLOW_DIR")
+        && !path.trim().is_empty()
+    {
+        return Ok(PathBuf::from(path.trim()).join("browsers"));
+    }
+    dirs::home_dir()
+        .map(|home| home.join(".restflow").join("browsers"))
+        .ok_or_else(|| anyhow!("Unable to resolve home directory for browser installs"))
+}
+
+fn extract_zip(archive_path: &Path, target_dir: &Path) -> Result<()> {
+    let file = File::open(archive_path)?;
+    let mut archive = zip::ZipArchive::new(file).context("Downloaded archive is not a zip file")?;
+    std::fs::create_dir_all(target_dir)?;
+    archive
+        .extract(target_dir)
+        .context("Failed to extract chrome-headless-shell archive")?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn mark_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    std::fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn mark_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_test_archive(path: &Path, platform: &str) -> String {
+        let file = File::create(path).unwrap();
+        let mut writer = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        writer
+            .start_file(format!("{0}-{1}/{0}", PRODUCT_DIR, platform), options)
+            .unwrap();
+        writer.write_all(b"#!/bin/sh\n").unwrap();
+        writer.finish().unwrap();
+        hex_digest(Sha256::digest(std::fs::read(path).unwrap()).as_slice())
+    }
+
+    #[tokio::test]
+    async fn install_archive_verifies_checksum_and_writes_marker() {
+        let temp = tempdir().unwrap();
+        let archive = temp.path().join("shell.zip");
+        let sha256 = write_test_archive(&archive, "linux64");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let provisioner = ChromiumProvisioner::with_release(
+            temp.path().join("browsers"),
+            HeadlessShellRelease::for_platform("1.0.0", "linux64").with_sha256(sha256.clone()),
+        )
+        .with_progress(tx);
+
+        let installed = provisioner
+            .install_archive(&archive, sha256.clone())
+            .await
+            .unwrap();
+        assert_eq!(installed.sha256, sha256);
+        assert!(Path::new(&installed.executable).is_file());
+        assert_eq!(
+            provisioner.installed().map(|runtime| runtime.executable),
+            Some(installed.executable.clone())
+        );
+
+        let mut stages = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            stages.push(event);
+        }
+        assert!(matches!(
+            stages.first(),
+            Some(ProvisionEvent::Verifying { .. })
+        ));
+        assert!(matches!(
+            stages.last(),
+            Some(ProvisionEvent::Completed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn install_archive_rejects_checksum_mismatch() {
+        let temp = tempdir().unwrap();
+        let archive = temp.path().join("shell.zip");
+        let sha256 = write_test_archive(&archive, "linux64");
+        let provisioner = ChromiumProvisioner::with_release(
+            temp.path().join("browsers"),
+            HeadlessShellRelease::for_platform("1.0.0", "linux64").with_sha256("00".repeat(32)),
+        );
+
+        let error = provisioner
+            .install_archive(&archive, sha256)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"));
+        assert!(provisioner.installed().is_none());
+    }
+
+    #[tokio::test]
+    async fn install_archive_refuses_release_without_checksum() {
+        let temp = tempdir().unwrap();
+        let archive = temp.path().join("shell.zip");
+        let sha256 = write_test_archive(&archive, "linux64");
+        let provisioner = ChromiumProvisioner::with_release(
+            temp.path().join("browsers"),
+            HeadlessShellRelease::for_platform("1.0.0", "linux64"),
+        );
+
+        let error = provisioner
+            .install_archive(&archive, sha256)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("No pinned checksum"));
+        assert!(provisioner.installed().is_none());
+    }
+
+    #[test]
+    fn pinned_checksums_are_lowercase_sha256() {
+        for (platform, sha256) in PINNED_HEADLESS_SHELL_SHA256 {
+            assert!(
+                sha256.len() == 64
+                    && sha256
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)),
+                "bad digest for {platform}"
+            );
+        }
+    }
+
+    #[test]
+    fn release_urls_follow_chrome_for_testing_layout() {
+        let release = HeadlessShellRelease::for_platform("131.0.6778.85", "win64");
+        assert_eq!(
+            release.url,
+            "https://storage.googleapis.com/chrome-for-testing-public/131.0.6778.85/win64/chrome-headless-shell-win64.zip"
+        );
+        assert_eq!(
+            release.executable_relative_path(),
+            PathBuf::from("chrome-headless-shell-win64").join("chrome-headless-shell.exe")
+        );
+    }
+}
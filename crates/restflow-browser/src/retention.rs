@@ -0,0 +1,278 @@
+//! Artifact retention for browser sessions.
+//!
+//! Screenshots, traces and failure snapshots are written under each session's
+//! `artifacts` directory. [`ArtifactRetentionPolicy`] bounds that growth by
+//! age, per-session size and total size across sessions; files are removed
+//! oldest first until every configured limit holds again.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Limits applied to session artifacts. A value of `0` disables that limit.
+///
+/// The default policy keeps everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtifactRetentionPolicy {
+    /// Maximum bytes a single session may keep under its artifacts directory.
+    pub max_session_bytes: u64,
+    /// Maximum bytes kept across all sessions.
+    pub max_total_bytes: u64,
+    /// Artifacts older than this many seconds are removed.
+    pub max_age_secs: u64,
+}
+
+impl ArtifactRetentionPolicy {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_session_bytes == 0 && self.max_total_bytes == 0 && self.max_age_secs == 0
+    }
+}
+
+/// Outcome of a single purge pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactPurgeReport {
+    pub sessions_scanned: usize,
+    pub files_removed: usize,
+    pub bytes_reclaimed: u64,
+    /// Artifact bytes left across the scanned sessions.
+    pub bytes_remaining: u64,
+}
+
+/// Cumulative purge metrics for a [`crate::BrowserService`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactRetentionStats {
+    pub purges: u64,
+    pub files_removed: u64,
+    pub bytes_reclaimed: u64,
+}
+
+impl ArtifactRetentionStats {
+    pub(crate) fn record(&mut self, report: &ArtifactPurgeReport) {
+        self.purges += 1;
+        self.files_removed += report.files_removed as u64;
+        self.bytes_reclaimed += report.bytes_reclaimed;
+    }
+}
+
+struct ArtifactFile {
+    session: usize,
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+}
+
+/// Apply `policy` to the given session artifact directories.
+///
+/// The total size limit is only meaningful when `dirs` covers every session.
+pub(crate) fn purge_artifact_dirs(
+    dirs: &[PathBuf],
+    policy: &ArtifactRetentionPolicy,
+    now: SystemTime,
+) -> ArtifactPurgeReport {
+    let mut report = ArtifactPurgeReport {
+        sessions_scanned: dirs.len(),
+        ..Default::default()
+    };
+
+    let mut files = Vec::new();
+    for (session, dir) in dirs.iter().enumerate() {
+        collect_files(session, dir, &mut files);
+    }
+    // Oldest first, so quota rotation always evicts the stalest artifacts.
+    files.sort_by_key(|file| file.modified);
+
+    let mut kept = Vec::with_capacity(files.len());
+    for file in files {
+        let expired = policy.max_age_secs > 0
+            && now
+                .duration_since(file.modified)
+                .is_ok_and(|age| age > Duration::from_secs(policy.max_age_secs));
+        if expired {
+            remove_file(file, &mut report);
+        } else {
+            kept.push(file);
+        }
+    }
+
+    if policy.max_session_bytes > 0 {
+        let mut session_bytes = vec![0u64; dirs.len()];
+        for file in &kept {
+            session_bytes[file.session] += file.bytes;
+        }
+        let mut survivors = Vec::with_capacity(kept.len());
+        for file in kept {
+            if session_bytes[file.session] > policy.max_session_bytes {
+                session_bytes[file.session] -= file.bytes;
+                remove_file(file, &mut report);
+            } else {
+                survivors.push(file);
+            }
+        }
+        kept = survivors;
+    }
+
+    let mut total_bytes: u64 = kept.iter().map(|file| file.bytes).sum();
+    if policy.max_total_bytes > 0 {
+        let mut survivors = Vec::with_capacity(kept.len());
+        for file in kept {
+            if total_bytes > policy.max_total_bytes {
+                total_bytes -= file.bytes;
+                remove_file(file, &mut report);
+            } else {
+                survivors.push(file);
+            }
+        }
+        kept = survivors;
+    }
+
+    for dir in dirs {
+        remove_empty_subdirs(dir);
+    }
+    report.bytes_remaining = kept.iter().map(|file| file.bytes).sum();
+    report
+}
+
+fn collect_files(session: usize, dir: &Path, files: &mut Vec<ArtifactFile>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_files(session, &entry.path(), files);
+        } else if metadata.is_file() {
+            files.push(ArtifactFile {
+                session,
+                path: entry.path(),
+                bytes: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+}
+
+fn remove_file(file: ArtifactFile, report: &mut ArtifactPurgeReport) {
+    match std::fs::remove_file(&file.path) {
+        Ok(()) => {
+            report.files_removed += 1;
+            report.bytes_reclaimed += file.bytes;
+        }
+        Err(error) => {
+            tracing::warn!(
+                path = %file.path.display(),
+                error = %error,
+                "Failed to remove browser artifact"
+            );
+        }
+    }
+}
+
+/// Remove directories left empty by a purge, keeping `dir` itself.
+fn remove_empty_subdirs(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_subdirs(&path);
+            let _ = std::fs::remove_dir(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn write_artifact(dir: &Path, name: &str, bytes: usize, age_secs: u64) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, vec![0u8; bytes]).unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(age_secs);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn unlimited_policy_keeps_everything() {
+        let temp = tempfile::tempdir().unwrap();
+        write_artifact(temp.path(), "a.png", 10, 10_000);
+
+        let report = purge_artifact_dirs(
+            &[temp.path().to_path_buf()],
+            &ArtifactRetentionPolicy::default(),
+            SystemTime::now(),
+        );
+        assert_eq!(report.files_removed, 0);
+        assert_eq!(report.bytes_remaining, 10);
+    }
+
+    #[test]
+    fn expired_artifacts_are_removed() {
+        let temp = tempfile::tempdir().unwrap();
+        let old = write_artifact(temp.path(), "traces/run/trace.json", 40, 7_200);
+        let fresh = write_artifact(temp.path(), "shot.png", 20, 10);
+
+        let policy = ArtifactRetentionPolicy {
+            max_age_secs: 3_600,
+            ..Default::default()
+        };
+        let report = purge_artifact_dirs(&[temp.path().to_path_buf()], &policy, SystemTime::now());
+
+        assert_eq!(report.files_removed, 1);
+        assert_eq!(report.bytes_reclaimed, 40);
+        assert!(!old.exists());
+        assert!(!temp.path().join("traces").exists());
+        assert!(fresh.exists());
+    }
+
+    #[test]
+    fn session_quota_rotates_oldest_first() {
+        let temp = tempfile::tempdir().unwrap();
+        let oldest = write_artifact(temp.path(), "1.png", 50, 300);
+        let middle = write_artifact(temp.path(), "2.png", 50, 200);
+        let newest = write_artifact(temp.path(), "3.png", 50, 100);
+
+        let policy = ArtifactRetentionPolicy {
+            max_session_bytes: 100,
+            ..Default::default()
+        };
+        let report = purge_artifact_dirs(&[temp.path().to_path_buf()], &policy, SystemTime::now());
+
+        assert_eq!(report.bytes_reclaimed, 50);
+        assert_eq!(report.bytes_remaining, 100);
+        assert!(!oldest.exists());
+        assert!(middle.exists());
+        assert!(newest.exists());
+    }
+
+    #[test]
+    fn total_quota_spans_sessions() {
+        let temp = tempfile::tempdir().unwrap();
+        let first = temp.path().join("first");
+        let second = temp.path().join("second");
+        let stale = write_artifact(&first, "a.png", 60, 500);
+        let recent = write_artifact(&second, "b.png", 60, 50);
+
+        let policy = ArtifactRetentionPolicy {
+            max_total_bytes: 100,
+            ..Default::default()
+        };
+        let report = purge_artifact_dirs(&[first, second], &policy, SystemTime::now());
+
+        assert_eq!(report.sessions_scanned, 2);
+        assert_eq!(report.files_removed, 1);
+        assert!(!stale.exists());
+        assert!(recent.exists());
+    }
+}
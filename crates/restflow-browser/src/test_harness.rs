@@ -0,0 +1,145 @@
+//! Local test site for exercising [`CdpExecutor`](crate::CdpExecutor) against
+//! a real headless Chromium.
+//!
+//! [`TestSite`] serves a handful of fixed pages from an axum server bound to
+//! loopback, each covering one piece of executor behaviour: page readiness,
+//! delayed and hidden elements, form input and pending network requests.
+//! [`chromium_service`] returns `None` when no Chromium executable is found so
+//! integration tests can skip instead of failing on machines without a
+//! browser.
+
+use anyhow::Result;
+use axum::Router;
+use axum::response::Html;
+use axum::routing::get;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::{BrowserService, CdpExecutor};
+
+const INDEX_PAGE: &str = r#"<!doctype html>
+<html>
+  <head><title>RestFlow test site</title></head>
+  <body>
+    <h1 id="title">RestFlow test site</h1>
+    <a id="form-link" href="/form">Form</a>
+  </body>
+</html>"#;
+
+const FORM_PAGE: &str = r#"<!doctype html>
+<html>
+  <head><title>Form</title></head>
+  <body>
+    <input id="name" type="text" />
+    <button id="submit" onclick="document.querySelector('#greeting').textContent = 'Hello, ' + document.querySelector('#name').value">Submit</button>
+    <p id="greeting"></p>
+  </body>
+</html>"#;
+
+const DELAYED_PAGE: &str = r#"<!doctype html>
+<html>
+  <head><title>Delayed</title></head>
+  <body>
+    <div id="hidden" style="display: none">hidden</div>
+    <p id="clicked"></p>
+    <script>
+      setTimeout(() => {
+        const button = document.createElement('button');
+        button.id = 'late';
+        button.textContent = 'Late';
+        button.onclick = () => { document.querySelector('#clicked').textContent = 'clicked'; };
+        document.body.appendChild(button);
+      }, 400);
+    </script>
+  </body>
+</html>"#;
+
+const PENDING_REQUEST_PAGE: &str = r#"<!doctype html>
+<html>
+  <head><title>Pending request</title></head>
+  <body>
+    <p id="status">loading</p>
+    <script>
+      window.addEventListener('load', () => {
+        fetch('/api/slow')
+          .then((response) => response.text())
+          .then((text) => { document.querySelector('#status').textContent = text; });
+      });
+    </script>
+  </body>
+</html>"#;
+
+/// Delay before `/api/slow` responds, long enough that `networkidle` has to
+/// wait for it.
+pub const SLOW_RESPONSE_DELAY: Duration = Duration::from_millis(500);
+
+/// Local HTTP server with fixed pages for browser integration tests.
+///
+/// Pages:
+/// - `/`: static page with `#title` and a link to `/form`
+/// - `/form`: `#name` input and `#submit` button writing to `#greeting`
+/// - `/delayed`: `#late` button inserted after 400ms, plus a hidden `#hidden`
+/// - `/pending`: fetches `/api/slow` after load and writes the body to `#status`
+///
+/// The server stops when the site is dropped.
+pub struct TestSite {
+    base_url: String,
+    handle: JoinHandle<()>,
+}
+
+impl TestSite {
+    /// Bind to an ephemeral loopback port and start serving.
+    pub async fn start() -> Result<Self> {
+        let router = Router::new()
+            .route("/", get(|| async { Html(INDEX_PAGE) }))
+            .route("/form", get(|| async { Html(FORM_PAGE) }))
+            .route("/delayed", get(|| async { Html(DELAYED_PAGE) }))
+            .route("/pending", get(|| async { Html(PENDING_REQUEST_PAGE) }))
+            .route(
+                "/api/slow",
+                get(|| async {
+                    tokio::time::sleep(SLOW_RESPONSE_DELAY).await;
+                    "done"
+                }),
+            );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let handle = tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, router).await {
+                tracing::warn!(error = %error, "Browser test site stopped");
+            }
+        });
+
+        Ok(Self { base_url, handle })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Absolute URL for `path` on this site.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+}
+
+impl Drop for TestSite {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Build a service on the real CDP executor rooted at `root_dir`, or `None`
+/// when no Chromium executable is available.
+pub async fn chromium_service(root_dir: PathBuf) -> Result<Option<BrowserService>> {
+    let service = BrowserService::new_with_executor(root_dir, Arc::new(CdpExecutor::new()))?;
+    let probe = service.probe_runtime().await?;
+    if !probe.ready {
+        return Ok(None);
+    }
+    Ok(Some(service))
+}
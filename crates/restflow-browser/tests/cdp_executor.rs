@@ -0,0 +1,179 @@
+//! End-to-end tests for `CdpExecutor` against a real headless Chromium.
+//!
+//! Run with `cargo test -p restflow-browser --features test-harness --test cdp_executor`.
+//! Each test is skipped when no Chromium executable is found.
+#![cfg(feature = "test-harness")]
+
+use restflow_browser::test_harness::{TestSite, chromium_service};
+use restflow_browser::{
+    BrowserAction, BrowserExecutionResult, BrowserService, NewSessionRequest, RunActionsRequest,
+};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+struct Harness {
+    site: TestSite,
+    service: BrowserService,
+    session_id: String,
+    _temp: TempDir,
+}
+
+impl Harness {
+    async fn start() -> Option<Self> {
+        let temp = tempfile::tempdir().unwrap();
+        let Some(service) = chromium_service(temp.path().join("browser")).await.unwrap() else {
+            eprintln!("Skipping browser integration test: no Chromium executable found");
+            return None;
+        };
+        let site = TestSite::start().await.unwrap();
+        let session = service
+            .new_session(NewSessionRequest::default())
+            .await
+            .unwrap();
+        Some(Self {
+            site,
+            service,
+            session_id: session.id,
+            _temp: temp,
+        })
+    }
+
+    async fn run(&self, actions: Value) -> BrowserExecutionResult {
+        let actions: Vec<BrowserAction> = serde_json::from_value(actions).unwrap();
+        self.service
+            .run_actions(&RunActionsRequest {
+                session_id: self.session_id.clone(),
+                actions,
+                runtime: Default::default(),
+                timeout_secs: 60,
+                cwd: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn close(self) {
+        self.service.close_session(&self.session_id).await.unwrap();
+    }
+}
+
+fn results(execution: &BrowserExecutionResult) -> &Vec<Value> {
+    assert_eq!(
+        execution.exit_code,
+        0,
+        "run failed: {}",
+        execution.failed_message()
+    );
+    execution
+        .payload
+        .as_ref()
+        .and_then(|payload| payload.get("result"))
+        .and_then(Value::as_array)
+        .expect("successful run should return action results")
+}
+
+#[tokio::test]
+async fn navigate_waits_for_load_and_extracts_text() {
+    let Some(harness) = Harness::start().await else {
+        return;
+    };
+
+    let execution = harness
+        .run(json!([
+            { "type": "navigate", "url": harness.site.url("/") },
+            { "type": "extract_text", "selector": "#title" },
+            { "type": "click", "selector": "#form-link" },
+            { "type": "wait_for_selector", "selector": "#name" }
+        ]))
+        .await;
+
+    let results = results(&execution);
+    assert_eq!(results[1]["value"], "RestFlow test site");
+    assert_eq!(results[3]["type"], "wait_for_selector");
+    harness.close().await;
+}
+
+#[tokio::test]
+async fn wait_for_selector_covers_delayed_and_hidden_elements() {
+    let Some(harness) = Harness::start().await else {
+        return;
+    };
+
+    let execution = harness
+        .run(json!([
+            { "type": "navigate", "url": harness.site.url("/delayed") },
+            { "type": "wait_for_selector", "selector": "#hidden", "state": "hidden" },
+            { "type": "wait_for_selector", "selector": "#late", "timeout_ms": 5000 },
+            { "type": "click", "selector": "#late" },
+            { "type": "extract_text", "selector": "#clicked" }
+        ]))
+        .await;
+
+    let results = results(&execution);
+    assert_eq!(results[4]["value"], "clicked");
+    harness.close().await;
+}
+
+#[tokio::test]
+async fn fill_and_click_update_form_state() {
+    let Some(harness) = Harness::start().await else {
+        return;
+    };
+
+    let execution = harness
+        .run(json!([
+            { "type": "navigate", "url": harness.site.url("/form") },
+            { "type": "fill", "selector": "#name", "text": "Ada" },
+            { "type": "click", "selector": "#submit" },
+            { "type": "extract_text", "selector": "#greeting" }
+        ]))
+        .await;
+
+    let results = results(&execution);
+    assert_eq!(results[3]["value"], "Hello, Ada");
+    harness.close().await;
+}
+
+#[tokio::test]
+async fn networkidle_waits_for_pending_requests() {
+    let Some(harness) = Harness::start().await else {
+        return;
+    };
+
+    let execution = harness
+        .run(json!([
+            { "type": "navigate", "url": harness.site.url("/pending"), "wait_until": "networkidle" },
+            { "type": "extract_text", "selector": "#status" }
+        ]))
+        .await;
+
+    let results = results(&execution);
+    assert_eq!(results[1]["value"], "done");
+    harness.close().await;
+}
+
+#[tokio::test]
+async fn missing_selector_reports_failure_snapshot() {
+    let Some(harness) = Harness::start().await else {
+        return;
+    };
+
+    let execution = harness
+        .run(json!([
+            { "type": "navigate", "url": harness.site.url("/") },
+            { "type": "click", "selector": "#missing", "timeout_ms": 500 }
+        ]))
+        .await;
+
+    assert_ne!(execution.exit_code, 0);
+    let failure = &execution.payload.as_ref().unwrap()["failure"];
+    assert_eq!(failure["action_index"], 1);
+    assert_eq!(failure["action_type"], "click");
+    assert!(
+        failure["error"]
+            .as_str()
+            .unwrap()
+            .contains("Timed out waiting for selector")
+    );
+    harness.close().await;
+}
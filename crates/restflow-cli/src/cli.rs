@@ -15,6 +15,28 @@ pub enum CodexExecutionModeArg {
     Bypass,
 }
 
+/// Role of an API token on the daemon HTTP API
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiTokenRoleArg {
+    ReadOnly,
+    Execute,
+    Admin,
+}
+
+/// Execution trace event category for audit queries
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditCategoryArg {
+    LlmCall,
+    ToolCall,
+    ModelSwitch,
+    Lifecycle,
+    Message,
+    MetricSample,
+    ProviderHealth,
+    LogRecord,
+    SecurityDecision,
+}
+
 impl OutputFormat {
     #[allow(dead_code)]
     pub fn is_json(self) -> bool {
@@ -131,6 +153,12 @@ pub enum Commands {
         command: MaintenanceCommands,
     },
 
+    /// Declarative project configuration (restflow.yaml)
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommands,
+    },
+
     /// MCP server management
     Mcp {
         #[command(subcommand)]
@@ -164,6 +192,18 @@ pub enum Commands {
         command: RouteCommands,
     },
 
+    /// Scoped API tokens for external MCP and webhook callers
+    Token {
+        #[command(subcommand)]
+        command: TokenCommands,
+    },
+
+    /// Workspace users for multi-user server mode
+    User {
+        #[command(subcommand)]
+        command: UserCommands,
+    },
+
     /// Task management
     #[command(visible_alias = "background-agent")]
     Task {
@@ -194,6 +234,39 @@ pub enum Commands {
         #[command(subcommand)]
         command: TriggerCommands,
     },
+
+    /// Saved browser action plans
+    BrowserPlan {
+        #[command(subcommand)]
+        command: BrowserPlanCommands,
+    },
+
+    /// REST API connectors imported from OpenAPI documents
+    Connector {
+        #[command(subcommand)]
+        command: ConnectorCommands,
+    },
+
+    /// Show LLM token usage and cost
+    Usage(UsageArgs),
+
+    /// Query the audit log of tool calls, model switches and security decisions
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+
+    /// Step through a recorded agent run or re-execute it from one of its steps
+    Replay {
+        #[command(subcommand)]
+        command: ReplayCommands,
+    },
+
+    /// Discover models served by local LLM runtimes
+    Model {
+        #[command(subcommand)]
+        command: ModelCommands,
+    },
 }
 
 #[derive(Args, Default, Clone, Copy)]
@@ -209,6 +282,100 @@ pub struct UpgradeArgs {
     pub force: bool,
 }
 
+#[derive(Args, Clone, Default)]
+pub struct UsageArgs {
+    /// Only count calls made by this agent
+    #[arg(long)]
+    pub agent: Option<String>,
+
+    /// Only count calls made in this session
+    #[arg(long)]
+    pub session: Option<String>,
+
+    /// Only count the last N days
+    #[arg(long)]
+    pub days: Option<u32>,
+}
+
+#[derive(Args, Clone, Default)]
+pub struct AuditFilterArgs {
+    /// Only include events of this category
+    #[arg(long, value_enum)]
+    pub category: Option<AuditCategoryArg>,
+
+    /// Only include events from this agent
+    #[arg(long)]
+    pub agent: Option<String>,
+
+    /// Only include events from this run
+    #[arg(long)]
+    pub run: Option<String>,
+
+    /// Only include the last N days
+    #[arg(long)]
+    pub days: Option<u32>,
+}
+
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// List audit events, newest first
+    List {
+        #[command(flatten)]
+        filter: AuditFilterArgs,
+
+        /// Maximum number of events to show
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+
+    /// Count audit events per category
+    Stats {
+        #[command(flatten)]
+        filter: AuditFilterArgs,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReplayCommands {
+    /// Show the recorded steps of an execution
+    Show {
+        /// Execution ID or run ID
+        id: String,
+
+        /// Only show this step
+        #[arg(long)]
+        step: Option<u32>,
+    },
+
+    /// Re-execute an execution from one of its recorded steps
+    Run {
+        /// Execution ID
+        execution_id: String,
+
+        /// Step to continue from
+        #[arg(long)]
+        from_step: u32,
+
+        /// Run with this agent instead of the recorded one
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// User message to add before continuing
+        #[arg(long)]
+        message: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ModelCommands {
+    /// List models served by Ollama and OpenAI-compatible local servers
+    Local {
+        /// Only query this OpenAI-compatible base URL (e.g. http://localhost:8080/v1)
+        #[arg(long)]
+        endpoint: Option<String>,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::Cli;
@@ -293,6 +460,21 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parses_daemon_install_command() {
+        let cli = Cli::try_parse_from(["restflow", "daemon", "install", "--no-start"])
+            .expect("parse daemon install");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Daemon {
+                command: super::DaemonCommands::Install {
+                    mcp_port: None,
+                    no_start: true
+                }
+            })
+        ));
+    }
+
     #[test]
     fn parses_daemon_restart_with_foreground_and_mcp_port() {
         let cli = Cli::try_parse_from([
@@ -315,6 +497,62 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parses_memory_export_encrypt_flag() {
+        let cli = Cli::try_parse_from(["restflow", "memory", "export", "--encrypt"])
+            .expect("parse memory export --encrypt");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Memory {
+                command: super::MemoryCommands::Export { encrypt: true, .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_memory_tag_merge() {
+        let cli = Cli::try_parse_from([
+            "restflow", "memory", "tag", "merge", "todo", "todos", "--into", "task",
+        ])
+        .expect("parse memory tag merge");
+        match cli.command {
+            Some(super::Commands::Memory {
+                command:
+                    super::MemoryCommands::Tag {
+                        command: super::MemoryTagCommands::Merge { sources, into },
+                    },
+            }) => {
+                assert_eq!(sources, vec!["todo".to_string(), "todos".to_string()]);
+                assert_eq!(into, "task");
+            }
+            _ => panic!("expected memory tag merge"),
+        }
+    }
+
+    #[test]
+    fn parses_session_export_encrypt_flag() {
+        let cli = Cli::try_parse_from(["restflow", "session", "export", "abc123", "--encrypt"])
+            .expect("parse session export --encrypt");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Session {
+                command: super::SessionCommands::Export { ref id, encrypt: true, .. }
+            }) if id == "abc123"
+        ));
+    }
+
+    #[test]
+    fn parses_skill_export_without_encrypt_flag() {
+        let cli = Cli::try_parse_from(["restflow", "skill", "export", "my-skill"])
+            .expect("parse skill export");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Skill {
+                command: super::SkillCommands::Export { encrypt: false, .. }
+            })
+        ));
+    }
+
     #[test]
     fn parses_hook_list_command() {
         let cli = Cli::try_parse_from(["restflow", "hook", "list"]).expect("parse hook list");
@@ -537,6 +775,142 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parses_maintenance_purge_artifacts_command() {
+        let cli = Cli::try_parse_from(["restflow", "maintenance", "purge-artifacts", "--dry-run"])
+            .expect("parse purge artifacts");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Maintenance {
+                command: super::MaintenanceCommands::PurgeArtifacts { dry_run: true }
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_maintenance_export_and_import_commands() {
+        let cli = Cli::try_parse_from(["restflow", "maintenance", "export", "--include-secrets"])
+            .expect("parse export");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Maintenance {
+                command: super::MaintenanceCommands::Export {
+                    output: None,
+                    include_secrets: true,
+                    encrypt: false
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["restflow", "maintenance", "export", "--encrypt"])
+            .expect("parse encrypted export");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Maintenance {
+                command: super::MaintenanceCommands::Export {
+                    include_secrets: false,
+                    encrypt: true,
+                    ..
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "maintenance",
+            "import",
+            "bundle.json",
+            "--overwrite",
+        ])
+        .expect("parse import");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Maintenance {
+                command: super::MaintenanceCommands::Import { ref path, overwrite: true }
+            }) if path == "bundle.json"
+        ));
+    }
+
+    #[test]
+    fn parses_maintenance_backup_and_restore_commands() {
+        let cli = Cli::try_parse_from(["restflow", "maintenance", "backup", "snapshot.redb"])
+            .expect("parse backup");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Maintenance {
+                command: super::MaintenanceCommands::Backup { ref path }
+            }) if path == "snapshot.redb"
+        ));
+
+        let cli = Cli::try_parse_from(["restflow", "maintenance", "restore", "snapshot.redb"])
+            .expect("parse restore");
+        assert!(matches!(
+            cli.command,
+            Some(super::Commands::Maintenance {
+                command: super::MaintenanceCommands::Restore { ref path }
+            }) if path == "snapshot.redb"
+        ));
+    }
+
+    #[test]
+    fn parses_token_create_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "token",
+            "create",
+            "--name",
+            "ci",
+            "--agent",
+            "agent-1",
+            "--tool",
+            "list_skills",
+            "--tool",
+            "get_skill",
+            "--max-calls",
+            "50",
+            "--no-webhooks",
+            "--role",
+            "read-only",
+            "--rate-limit",
+            "30",
+            "--user",
+            "user-1",
+        ])
+        .expect("parse token create");
+
+        match cli.command {
+            Some(super::Commands::Token {
+                command:
+                    super::TokenCommands::Create {
+                        name,
+                        agents,
+                        tools,
+                        max_calls,
+                        no_mcp,
+                        no_webhooks,
+                        no_openai_api,
+                        role,
+                        rate_limit,
+                        user_id,
+                        expires_in_days,
+                    },
+            }) => {
+                assert_eq!(name, "ci");
+                assert_eq!(agents, vec!["agent-1".to_string()]);
+                assert_eq!(tools.len(), 2);
+                assert_eq!(max_calls, Some(50));
+                assert!(!no_mcp);
+                assert!(no_webhooks);
+                assert!(!no_openai_api);
+                assert_eq!(role, Some(super::ApiTokenRoleArg::ReadOnly));
+                assert_eq!(rate_limit, Some(30));
+                assert_eq!(user_id.as_deref(), Some("user-1"));
+                assert_eq!(expires_in_days, None);
+            }
+            _ => panic!("expected token create command"),
+        }
+    }
+
     #[test]
     fn parses_mcp_sync_command() {
         let cli = Cli::try_parse_from(["restflow", "mcp", "sync", "--port", "9900"])
@@ -548,6 +922,208 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn parses_browser_plan_run_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "browser-plan",
+            "run",
+            "prices",
+            "--version",
+            "2",
+            "--payload",
+            r#"{"sku":"42"}"#,
+        ])
+        .expect("parse browser plan run");
+
+        match cli.command {
+            Some(super::Commands::BrowserPlan {
+                command:
+                    super::BrowserPlanCommands::Run {
+                        name,
+                        version,
+                        payload,
+                    },
+            }) => {
+                assert_eq!(name, "prices");
+                assert_eq!(version, Some(2));
+                assert_eq!(payload.as_deref(), Some(r#"{"sku":"42"}"#));
+            }
+            _ => panic!("expected browser plan run command"),
+        }
+    }
+
+    #[test]
+    fn parses_connector_import_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "connector",
+            "import",
+            "github",
+            "--file",
+            "openapi.yaml",
+            "--auth-secret",
+            "GITHUB_TOKEN",
+        ])
+        .expect("parse connector import");
+
+        match cli.command {
+            Some(super::Commands::Connector {
+                command:
+                    super::ConnectorCommands::Import {
+                        name,
+                        file,
+                        base_url,
+                        auth_secret,
+                        auth_profile,
+                    },
+            }) => {
+                assert_eq!(name, "github");
+                assert_eq!(file, "openapi.yaml");
+                assert_eq!(base_url, None);
+                assert_eq!(auth_secret.as_deref(), Some("GITHUB_TOKEN"));
+                assert_eq!(auth_profile, None);
+            }
+            _ => panic!("expected connector import command"),
+        }
+    }
+
+    #[test]
+    fn parses_auth_login_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "auth",
+            "login",
+            "--provider",
+            "github",
+            "--client-id",
+            "client-1",
+            "--scope",
+            "repo",
+            "--scope",
+            "read:org",
+        ])
+        .expect("parse auth login");
+
+        match cli.command {
+            Some(super::Commands::Auth {
+                command:
+                    super::AuthCommands::Login {
+                        provider,
+                        client_id,
+                        client_secret,
+                        scopes,
+                        name,
+                    },
+            }) => {
+                assert_eq!(provider, "github");
+                assert_eq!(client_id, "client-1");
+                assert_eq!(client_secret, None);
+                assert_eq!(scopes, vec!["repo", "read:org"]);
+                assert_eq!(name, None);
+            }
+            _ => panic!("expected auth login command"),
+        }
+    }
+
+    #[test]
+    fn parses_usage_command() {
+        let cli = Cli::try_parse_from(["restflow", "usage", "--agent", "writer", "--days", "7"])
+            .expect("parse usage");
+
+        match cli.command {
+            Some(super::Commands::Usage(args)) => {
+                assert_eq!(args.agent.as_deref(), Some("writer"));
+                assert_eq!(args.session, None);
+                assert_eq!(args.days, Some(7));
+            }
+            _ => panic!("expected usage command"),
+        }
+    }
+
+    #[test]
+    fn parses_model_local_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "model",
+            "local",
+            "--endpoint",
+            "http://localhost:1234/v1",
+        ])
+        .expect("parse model local");
+
+        match cli.command {
+            Some(super::Commands::Model {
+                command: super::ModelCommands::Local { endpoint },
+            }) => assert_eq!(endpoint.as_deref(), Some("http://localhost:1234/v1")),
+            _ => panic!("expected model local command"),
+        }
+    }
+
+    #[test]
+    fn parses_audit_list_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "audit",
+            "list",
+            "--category",
+            "security-decision",
+            "--days",
+            "1",
+            "--limit",
+            "10",
+        ])
+        .expect("parse audit list");
+
+        match cli.command {
+            Some(super::Commands::Audit {
+                command: super::AuditCommands::List { filter, limit },
+            }) => {
+                assert_eq!(
+                    filter.category,
+                    Some(super::AuditCategoryArg::SecurityDecision)
+                );
+                assert_eq!(filter.agent, None);
+                assert_eq!(filter.days, Some(1));
+                assert_eq!(limit, 10);
+            }
+            _ => panic!("expected audit list command"),
+        }
+    }
+
+    #[test]
+    fn parses_replay_run_command() {
+        let cli = Cli::try_parse_from([
+            "restflow",
+            "replay",
+            "run",
+            "exec-1",
+            "--from-step",
+            "3",
+            "--agent",
+            "agent-2",
+        ])
+        .expect("parse replay run");
+
+        match cli.command {
+            Some(super::Commands::Replay {
+                command:
+                    super::ReplayCommands::Run {
+                        execution_id,
+                        from_step,
+                        agent,
+                        message,
+                    },
+            }) => {
+                assert_eq!(execution_id, "exec-1");
+                assert_eq!(from_step, 3);
+                assert_eq!(agent.as_deref(), Some("agent-2"));
+                assert_eq!(message, None);
+            }
+            _ => panic!("expected replay run command"),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -601,6 +1177,18 @@ pub enum AgentCommands {
 
     /// Delete agent
     Delete { id: String },
+
+    /// List built-in agent templates
+    Templates,
+
+    /// Create an agent and its scheduled tasks from a template
+    Provision {
+        /// Template ID (see `agent templates`)
+        template: String,
+
+        #[arg(short, long)]
+        name: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -793,6 +1381,20 @@ pub enum DaemonCommands {
         #[arg(long)]
         mcp_port: Option<u16>,
     },
+
+    /// Install the daemon as a login service (launchd, systemd or Task Scheduler)
+    Install {
+        /// MCP HTTP server port (default: 8787, MCP is always enabled)
+        #[arg(long)]
+        mcp_port: Option<u16>,
+
+        /// Write the service definition without enabling or starting it
+        #[arg(long)]
+        no_start: bool,
+    },
+
+    /// Stop and remove the installed login service
+    Uninstall,
 }
 
 #[derive(Subcommand)]
@@ -847,6 +1449,10 @@ pub enum SkillCommands {
 
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Encrypt the export with a password (RESTFLOW_ARCHIVE_PASSWORD or prompt)
+        #[arg(long)]
+        encrypt: bool,
     },
 
     /// Search marketplace
@@ -888,6 +1494,10 @@ pub enum MemoryCommands {
 
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Encrypt the export with a password (RESTFLOW_ARCHIVE_PASSWORD or prompt)
+        #[arg(long)]
+        encrypt: bool,
     },
 
     /// Show memory stats
@@ -912,6 +1522,57 @@ pub enum MemoryCommands {
         #[arg(long)]
         tags: Option<String>,
     },
+
+    /// Manage memory tags in bulk
+    Tag {
+        #[command(subcommand)]
+        command: MemoryTagCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MemoryTagCommands {
+    /// List tags with chunk counts
+    List {
+        #[arg(long)]
+        agent: Option<String>,
+    },
+
+    /// Add tags to memory chunks
+    Add {
+        /// Chunk IDs to tag
+        #[arg(required = true)]
+        chunk_ids: Vec<String>,
+
+        /// Tags to add (comma-separated)
+        #[arg(long)]
+        tags: String,
+    },
+
+    /// Remove tags from memory chunks
+    Remove {
+        /// Chunk IDs to untag
+        #[arg(required = true)]
+        chunk_ids: Vec<String>,
+
+        /// Tags to remove (comma-separated)
+        #[arg(long)]
+        tags: String,
+    },
+
+    /// Rename a tag on every chunk
+    Rename { from: String, to: String },
+
+    /// Merge several tags into one on every chunk
+    Merge {
+        /// Tags to merge away
+        #[arg(required = true)]
+        sources: Vec<String>,
+
+        /// Tag that replaces the sources
+        #[arg(long)]
+        into: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -927,6 +1588,35 @@ pub enum SecretCommands {
 
     /// Check if secret exists
     Has { key: String },
+
+    /// Replace a secret's value and record the rotation
+    Rotate {
+        key: String,
+        value: String,
+
+        /// Expire the new value after this many days
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+
+    /// Set or clear a secret's expiry
+    Expiry {
+        key: String,
+
+        /// Days from now until expiry (omit to clear)
+        #[arg(long)]
+        days: Option<u32>,
+    },
+
+    /// List secrets expiring within the given window
+    Expiring {
+        /// Window in days
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+    },
+
+    /// Show which tools and agents read a secret
+    Usage { key: String },
 }
 
 #[derive(Subcommand)]
@@ -1005,6 +1695,28 @@ pub enum AuthCommands {
         name: Option<String>,
     },
 
+    /// Sign in with OAuth using the device-code flow
+    Login {
+        /// OAuth provider: google, microsoft or github
+        #[arg(long)]
+        provider: String,
+
+        /// OAuth client ID registered with the provider
+        #[arg(long)]
+        client_id: String,
+
+        /// OAuth client secret (only for confidential clients)
+        #[arg(long)]
+        client_secret: Option<String>,
+
+        /// Scope to request; repeat to request several (defaults to the provider preset)
+        #[arg(long = "scope")]
+        scopes: Vec<String>,
+
+        #[arg(long)]
+        name: Option<String>,
+    },
+
     /// Remove a profile
     Remove { id: String },
 }
@@ -1024,6 +1736,20 @@ pub enum ConfigCommands {
     Reset,
 }
 
+#[derive(Subcommand)]
+pub enum ProjectCommands {
+    /// Create or update agents, skills and triggers from restflow.yaml
+    Apply {
+        /// Workspace directory containing restflow.yaml
+        #[arg(default_value = ".")]
+        dir: String,
+
+        /// Show the planned changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum MaintenanceCommands {
     /// Run storage cleanup immediately
@@ -1035,6 +1761,48 @@ pub enum MaintenanceCommands {
         #[arg(long)]
         dry_run: bool,
     },
+
+    /// Delete orphaned, expired or over-quota tool output, media and browser artifacts
+    PurgeArtifacts {
+        /// List what would be deleted without removing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Snapshot the database to a new file while RestFlow keeps running
+    Backup {
+        /// Destination file (must not exist)
+        path: String,
+    },
+
+    /// Replace the database contents with a backup file
+    Restore {
+        /// Backup file created by `maintenance backup`
+        path: String,
+    },
+
+    /// Export agents, skills, triggers, sessions and memory as a portable JSON bundle
+    Export {
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Include secrets and auth profiles, encrypted with a password (RESTFLOW_ARCHIVE_PASSWORD or prompt)
+        #[arg(long)]
+        include_secrets: bool,
+
+        /// Encrypt the whole bundle with a password (RESTFLOW_ARCHIVE_PASSWORD or prompt)
+        #[arg(long)]
+        encrypt: bool,
+    },
+
+    /// Import a bundle created by `maintenance export`
+    Import {
+        path: String,
+
+        /// Replace records that already exist instead of skipping them
+        #[arg(long)]
+        overwrite: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1053,6 +1821,40 @@ pub enum SecurityCommands {
         #[command(subcommand)]
         action: AllowlistAction,
     },
+
+    /// Manage agent tool call approvals and the tool approval policy
+    Tools {
+        #[command(subcommand)]
+        action: ToolApprovalAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ToolApprovalAction {
+    /// List tool calls waiting for approval
+    List {
+        /// Include approved, rejected and expired requests
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Approve a tool call and resume the waiting run
+    Approve { id: String },
+
+    /// Reject a tool call
+    Reject {
+        id: String,
+
+        /// Reason passed back to the agent
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
+
+    /// Show the tool approval policy
+    Policy,
+
+    /// Replace the tool approval policy with the contents of a JSON file
+    SetPolicy { path: String },
 }
 
 #[derive(Subcommand)]
@@ -1128,14 +1930,39 @@ pub enum SessionCommands {
         id: String,
     },
 
-    /// Search across sessions
+    /// Export a session's conversation as JSON
+    Export {
+        /// Session ID
+        id: String,
+
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Encrypt the export with a password (RESTFLOW_ARCHIVE_PASSWORD or prompt)
+        #[arg(long)]
+        encrypt: bool,
+    },
+
+    /// Full-text search over session messages
     Search {
-        /// Search query
+        /// Search query; wrap words in quotes to match a phrase
         query: String,
 
         /// Agent ID to filter by
         #[arg(long)]
         agent: Option<String>,
+
+        /// Also search archived sessions
+        #[arg(long)]
+        archived: bool,
+
+        /// Max hits to show
+        #[arg(short, long, default_value = "20")]
+        limit: u32,
+
+        /// Hits to skip, for paging through results
+        #[arg(long, default_value = "0")]
+        offset: u32,
     },
 }
 
@@ -1248,11 +2075,19 @@ pub enum RouteCommands {
 
     /// Bind a target to an agent
     Bind {
-        /// Binding type: peer, group, or default
+        /// Binding type: peer, account, channel, group, or default
         #[arg(long)]
         peer: Option<String>,
 
-        /// Group/chat ID
+        /// Account ID (Slack workspace, Discord guild or bot account)
+        #[arg(long)]
+        account: Option<String>,
+
+        /// Channel type: telegram, discord, slack, or matrix
+        #[arg(long)]
+        channel: Option<String>,
+
+        /// Group/chat ID (e.g. a Slack or Discord channel ID)
         #[arg(long)]
         group: Option<String>,
 
@@ -1272,11 +2107,92 @@ pub enum RouteCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum TokenCommands {
+    /// List active API tokens
+    List {
+        /// Include revoked and expired tokens
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Issue a new API token (the secret is shown only once)
+    Create {
+        /// Caller name
+        #[arg(long)]
+        name: String,
+
+        /// Restrict to these agent IDs (repeatable)
+        #[arg(long = "agent")]
+        agents: Vec<String>,
+
+        /// Restrict to these MCP tool names (repeatable)
+        #[arg(long = "tool")]
+        tools: Vec<String>,
+
+        /// Maximum number of calls over the token lifetime
+        #[arg(long)]
+        max_calls: Option<u32>,
+
+        /// Disallow MCP access
+        #[arg(long)]
+        no_mcp: bool,
+
+        /// Disallow webhook triggers
+        #[arg(long)]
+        no_webhooks: bool,
+
+        /// Disallow the OpenAI-compatible /v1 endpoints
+        #[arg(long)]
+        no_openai_api: bool,
+
+        /// Grant access to the daemon HTTP API (/api/...) with this role
+        #[arg(long, value_enum)]
+        role: Option<ApiTokenRoleArg>,
+
+        /// Maximum number of requests per minute
+        #[arg(long)]
+        rate_limit: Option<u32>,
+
+        /// Bind the token to this workspace user's agents, skills and sessions
+        #[arg(long = "user")]
+        user_id: Option<String>,
+
+        /// Expire the token after this many days
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+
+    /// Revoke an API token
+    Revoke {
+        /// Token ID
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum UserCommands {
+    /// List workspace users
+    List,
+
+    /// Create a workspace user
+    Create {
+        /// Unique user name
+        name: String,
+    },
+
+    /// Delete a workspace user; their agents and skills are kept unowned
+    Delete {
+        /// User ID
+        id: String,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum TaskCommands {
     /// List tasks
     List {
-        /// Filter by status: active, paused, running, completed, failed, interrupted
+        /// Filter by status: active, paused, running, completed, failed, interrupted, dead_letter
         #[arg(long)]
         status: Option<String>,
     },
@@ -1387,6 +2303,36 @@ pub enum TaskCommands {
         /// Action: start, pause, resume, stop, run_now
         #[arg(short, long)]
         action: String,
+
+        /// Secret available only to this run (NAME=VALUE, repeatable, run_now only)
+        #[arg(long = "secret", value_name = "NAME=VALUE")]
+        secrets: Vec<String>,
+    },
+
+    /// Resume a paused task when a condition is met
+    Wake {
+        /// Task ID
+        id: String,
+
+        /// Condition: webhook, file, work_item, time
+        #[arg(long = "on", required_unless_present = "clear")]
+        condition: Option<String>,
+
+        /// File path, work item ID, or time (ms since epoch) for the condition
+        #[arg(long)]
+        value: Option<String>,
+
+        /// Work item status that wakes the task (work_item only)
+        #[arg(long, default_value = "done")]
+        status: String,
+
+        /// Checkpoint to resume from (defaults to the task's latest checkpoint)
+        #[arg(long)]
+        checkpoint: Option<String>,
+
+        /// Remove the registered wake condition
+        #[arg(long, conflicts_with = "condition")]
+        clear: bool,
     },
 
     /// Show task progress
@@ -1490,3 +2436,104 @@ pub enum TriggerCommands {
         id: String,
     },
 }
+
+#[derive(Subcommand)]
+pub enum BrowserPlanCommands {
+    /// List saved plans
+    List,
+
+    /// Show a saved plan
+    Show {
+        /// Plan name
+        name: String,
+
+        /// Plan version (defaults to the latest)
+        #[arg(long)]
+        version: Option<u32>,
+    },
+
+    /// List all versions of a plan
+    Versions {
+        /// Plan name
+        name: String,
+    },
+
+    /// Save a plan from a JSON file, creating a new version if it exists
+    Save {
+        /// Plan name
+        name: String,
+
+        /// JSON file with an actions array or {"actions": [...], "timeout_secs": N}
+        #[arg(short, long)]
+        file: String,
+
+        /// Plan description
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Overall timeout in seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+
+    /// Delete a plan and all of its versions
+    Delete {
+        /// Plan name
+        name: String,
+    },
+
+    /// Run a saved plan
+    Run {
+        /// Plan name
+        name: String,
+
+        /// Plan version (defaults to the latest)
+        #[arg(long)]
+        version: Option<u32>,
+
+        /// JSON payload for ${payload.<path>} placeholders
+        #[arg(long)]
+        payload: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConnectorCommands {
+    /// List imported connectors
+    List,
+
+    /// Show a connector and its operations
+    Show {
+        /// Connector name
+        name: String,
+    },
+
+    /// Import an OpenAPI 3 or Swagger 2 document (JSON or YAML), replacing any
+    /// connector with the same name
+    Import {
+        /// Connector name, used as the tool name prefix
+        name: String,
+
+        /// OpenAPI document file
+        #[arg(short, long)]
+        file: String,
+
+        /// Base URL (defaults to the first server in the document)
+        #[arg(long)]
+        base_url: Option<String>,
+
+        /// Name of the secret used to authenticate requests
+        #[arg(long)]
+        auth_secret: Option<String>,
+
+        /// Authenticate with the access token of an OAuth auth profile
+        #[arg(long, conflicts_with = "auth_secret")]
+        auth_profile: Option<String>,
+    },
+
+    /// Delete a connector
+    Delete {
+        /// Connector name
+        name: String,
+    },
+}
@@ -59,6 +59,10 @@ pub async fn run(
             .await
         }
         AgentCommands::Delete { id } => delete_agent(executor, &id, format).await,
+        AgentCommands::Templates => list_templates(executor, format).await,
+        AgentCommands::Provision { template, name } => {
+            provision_template(executor, &template, name, format).await
+        }
     }
 }
 
@@ -249,6 +253,62 @@ async fn delete_agent(
     Ok(())
 }
 
+async fn list_templates(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -> Result<()> {
+    let templates = executor.list_agent_templates().await?;
+
+    if format.is_json() {
+        return print_json(&templates);
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Name", "Tasks", "Description"]);
+
+    for template in templates {
+        table.add_row(vec![
+            Cell::new(template.id),
+            Cell::new(template.name),
+            Cell::new(template.tasks.len()),
+            Cell::new(template.description),
+        ]);
+    }
+
+    crate::output::table::print_table(table)
+}
+
+async fn provision_template(
+    executor: Arc<dyn CommandExecutor>,
+    template: &str,
+    name: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let provisioned = executor.provision_agent_template(template, name).await?;
+
+    if format.is_json() {
+        return print_json(&provisioned);
+    }
+
+    println!(
+        "Agent created: {} ({})",
+        provisioned.agent.name, provisioned.agent.id
+    );
+    for task in &provisioned.tasks {
+        println!("Task created:  {} ({})", task.name, task.id);
+    }
+    if !provisioned.missing_skills.is_empty() {
+        println!(
+            "Skills not installed (left off the agent): {}",
+            provisioned.missing_skills.join(", ")
+        );
+    }
+    if !provisioned.missing_secrets.is_empty() {
+        println!(
+            "Missing secrets: {} (set with `restflow secret set <NAME> <value>`)",
+            provisioned.missing_secrets.join(", ")
+        );
+    }
+    Ok(())
+}
+
 fn format_tools(tools: &Option<Vec<String>>) -> String {
     match tools {
         Some(tool_list) if !tool_list.is_empty() => tool_list.join(", "),
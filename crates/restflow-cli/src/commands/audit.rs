@@ -0,0 +1,187 @@
+//! CLI commands for the audit log of execution trace events.
+
+use anyhow::Result;
+use chrono::Utc;
+use comfy_table::{Cell, Table};
+use restflow_core::models::{ExecutionTraceCategory, ExecutionTraceEvent, ExecutionTraceQuery};
+use std::sync::Arc;
+
+use crate::cli::{AuditCategoryArg, AuditCommands, AuditFilterArgs, OutputFormat};
+use crate::commands::utils::{format_timestamp, preview_text, short_id};
+use crate::executor::CommandExecutor;
+use crate::output::json::print_json;
+use crate::output::table::print_table;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const DETAIL_PREVIEW_LEN: usize = 60;
+
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: AuditCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        AuditCommands::List { filter, limit } => {
+            let query = ExecutionTraceQuery {
+                limit: Some(limit),
+                ..trace_query(filter)
+            };
+            list_events(executor, query, format).await
+        }
+        AuditCommands::Stats { filter } => show_stats(executor, trace_query(filter), format).await,
+    }
+}
+
+async fn list_events(
+    executor: Arc<dyn CommandExecutor>,
+    query: ExecutionTraceQuery,
+    format: OutputFormat,
+) -> Result<()> {
+    let events = executor.query_execution_traces(query).await?;
+
+    if format.is_json() {
+        return print_json(&events);
+    }
+
+    if events.is_empty() {
+        println!("No audit events found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Time", "Category", "Agent", "Run", "Detail"]);
+    for event in &events {
+        table.add_row(vec![
+            Cell::new(format_timestamp(Some(event.timestamp))),
+            Cell::new(category_name(&event.category)),
+            Cell::new(short_id(&event.agent_id)),
+            Cell::new(event.run_id.as_deref().map(short_id).unwrap_or_default()),
+            Cell::new(preview_text(&event_detail(event), DETAIL_PREVIEW_LEN)),
+        ]);
+    }
+    print_table(table)
+}
+
+async fn show_stats(
+    executor: Arc<dyn CommandExecutor>,
+    query: ExecutionTraceQuery,
+    format: OutputFormat,
+) -> Result<()> {
+    let stats = executor.query_execution_trace_stats(query).await?;
+
+    if format.is_json() {
+        return print_json(&stats);
+    }
+
+    if stats.total_events == 0 {
+        println!("No audit events found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Category", "Events"]);
+    for (category, count) in [
+        ("llm_call", stats.llm_call_count),
+        ("tool_call", stats.tool_call_count),
+        ("model_switch", stats.model_switch_count),
+        ("security_decision", stats.security_decision_count),
+        ("lifecycle", stats.lifecycle_count),
+        ("message", stats.message_count),
+        ("metric_sample", stats.metric_sample_count),
+        ("provider_health", stats.provider_health_count),
+        ("log_record", stats.log_record_count),
+    ] {
+        if count > 0 {
+            table.add_row(vec![Cell::new(category), Cell::new(count)]);
+        }
+    }
+    table.add_row(vec![Cell::new("total"), Cell::new(stats.total_events)]);
+    print_table(table)?;
+
+    if let Some(range) = stats.time_range {
+        println!(
+            "From {} to {}",
+            format_timestamp(Some(range.earliest)),
+            format_timestamp(Some(range.latest))
+        );
+    }
+    Ok(())
+}
+
+fn trace_query(filter: AuditFilterArgs) -> ExecutionTraceQuery {
+    ExecutionTraceQuery {
+        category: filter.category.map(to_trace_category),
+        agent_id: filter.agent,
+        run_id: filter.run,
+        from_timestamp: filter
+            .days
+            .map(|days| Utc::now().timestamp_millis() - i64::from(days) * DAY_MS),
+        ..ExecutionTraceQuery::default()
+    }
+}
+
+fn to_trace_category(category: AuditCategoryArg) -> ExecutionTraceCategory {
+    match category {
+        AuditCategoryArg::LlmCall => ExecutionTraceCategory::LlmCall,
+        AuditCategoryArg::ToolCall => ExecutionTraceCategory::ToolCall,
+        AuditCategoryArg::ModelSwitch => ExecutionTraceCategory::ModelSwitch,
+        AuditCategoryArg::Lifecycle => ExecutionTraceCategory::Lifecycle,
+        AuditCategoryArg::Message => ExecutionTraceCategory::Message,
+        AuditCategoryArg::MetricSample => ExecutionTraceCategory::MetricSample,
+        AuditCategoryArg::ProviderHealth => ExecutionTraceCategory::ProviderHealth,
+        AuditCategoryArg::LogRecord => ExecutionTraceCategory::LogRecord,
+        AuditCategoryArg::SecurityDecision => ExecutionTraceCategory::SecurityDecision,
+    }
+}
+
+fn category_name(category: &ExecutionTraceCategory) -> &'static str {
+    match category {
+        ExecutionTraceCategory::LlmCall => "llm_call",
+        ExecutionTraceCategory::ToolCall => "tool_call",
+        ExecutionTraceCategory::ModelSwitch => "model_switch",
+        ExecutionTraceCategory::Lifecycle => "lifecycle",
+        ExecutionTraceCategory::Message => "message",
+        ExecutionTraceCategory::MetricSample => "metric_sample",
+        ExecutionTraceCategory::ProviderHealth => "provider_health",
+        ExecutionTraceCategory::LogRecord => "log_record",
+        ExecutionTraceCategory::SecurityDecision => "security_decision",
+    }
+}
+
+/// One-line summary of the category-specific payload of an event.
+fn event_detail(event: &ExecutionTraceEvent) -> String {
+    if let Some(decision) = &event.security_decision {
+        let verdict = if decision.allowed {
+            "allowed"
+        } else if decision.requires_approval {
+            "approval required"
+        } else {
+            "blocked"
+        };
+        return format!(
+            "{verdict}: {} {} {}",
+            decision.tool_name, decision.operation, decision.target
+        );
+    }
+    if let Some(tool) = &event.tool_call {
+        let status = match tool.success {
+            Some(true) => "ok",
+            Some(false) => "failed",
+            None => "started",
+        };
+        return format!("{} ({status})", tool.tool_name);
+    }
+    if let Some(switch) = &event.model_switch {
+        return format!("{} -> {}", switch.from_model, switch.to_model);
+    }
+    if let Some(llm) = &event.llm_call {
+        return llm.model.clone();
+    }
+    if let Some(lifecycle) = &event.lifecycle {
+        return lifecycle.status.clone();
+    }
+    if let Some(log) = &event.log_record {
+        return log.message.clone();
+    }
+    String::new()
+}
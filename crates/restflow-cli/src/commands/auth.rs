@@ -1,7 +1,8 @@
 use anyhow::{Result, bail};
 use comfy_table::{Cell, Table};
 use restflow_core::auth::{
-    AuthProvider, Credential, CredentialSource, ManagerSummary, ProfileHealth, SecureCredential,
+    AuthProvider, Credential, CredentialSource, ManagerSummary, OAuthClientConfig, OAuthFlow,
+    ProfileHealth, SecureCredential,
 };
 use restflow_core::daemon::{IpcClient, is_daemon_available};
 use restflow_core::paths;
@@ -41,6 +42,24 @@ async fn run_ipc(socket_path: &Path, command: AuthCommands, format: OutputFormat
             key,
             name,
         } => add_profile_ipc(&mut client, &provider, &key, name, format).await,
+        AuthCommands::Login {
+            provider,
+            client_id,
+            client_secret,
+            scopes,
+            name,
+        } => {
+            login_ipc(
+                &mut client,
+                &provider,
+                client_id,
+                client_secret,
+                scopes,
+                name,
+                format,
+            )
+            .await
+        }
         AuthCommands::Remove { id } => remove_profile_ipc(&mut client, &id, format).await,
     }
 }
@@ -167,6 +186,10 @@ async fn show_profile_ipc(client: &mut IpcClient, id: &str, format: OutputFormat
         println!("Email:        {}", email);
     }
 
+    if let Some(oauth) = &profile.oauth {
+        println!("OAuth client: {} ({})", oauth.client_id, oauth.token_url);
+    }
+
     Ok(())
 }
 
@@ -195,6 +218,61 @@ async fn add_profile_ipc(
     Ok(())
 }
 
+async fn login_ipc(
+    client: &mut IpcClient,
+    provider: &str,
+    client_id: String,
+    client_secret: Option<String>,
+    scopes: Vec<String>,
+    name: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut config = OAuthClientConfig::preset(provider, client_id)?;
+    if !scopes.is_empty() {
+        config.scopes = scopes;
+    }
+    let flow = OAuthFlow::new(config.clone(), client_secret.clone());
+
+    let device = flow.start_device_authorization().await?;
+    // Instructions go to stderr so JSON output stays machine-readable.
+    eprintln!(
+        "Open {} and enter code {}",
+        device
+            .verification_uri_complete
+            .as_deref()
+            .unwrap_or(&device.verification_uri),
+        device.user_code
+    );
+    eprintln!("Waiting for authorization...");
+    let credential = flow.poll_device_token(&device).await?.into_credential(None);
+
+    let auth_provider = match provider.to_lowercase().as_str() {
+        "google" => AuthProvider::Google,
+        _ => AuthProvider::Other,
+    };
+    let display_name = name.unwrap_or_else(|| format!("{} (oauth)", provider.to_lowercase()));
+    let profile = client
+        .add_oauth_profile(
+            display_name,
+            credential,
+            auth_provider,
+            config,
+            client_secret,
+        )
+        .await?;
+
+    if format.is_json() {
+        return print_json(&serde_json::json!({ "id": profile.id }));
+    }
+
+    println!("Profile added: {}", profile.id);
+    println!(
+        "Use it for API connectors with: restflow connector import <name> --file <spec> --auth-profile {}",
+        profile.id
+    );
+    Ok(())
+}
+
 async fn remove_profile_ipc(client: &mut IpcClient, id: &str, format: OutputFormat) -> Result<()> {
     let resolved_id = resolve_profile_id_ipc(client, id).await?;
     let removed = client.remove_auth_profile(resolved_id).await?;
@@ -214,9 +292,10 @@ fn parse_provider(value: &str) -> Result<AuthProvider> {
         "openai" => Ok(AuthProvider::OpenAI),
         "openai-codex" | "openai_codex" | "codex" => Ok(AuthProvider::OpenAICodex),
         "google" | "gemini" => Ok(AuthProvider::Google),
+        "bedrock" | "aws-bedrock" => Ok(AuthProvider::Bedrock),
         "other" => Ok(AuthProvider::Other),
         _ => bail!(
-            "Unsupported provider: {value}. Valid options: anthropic, claude-code, openai, openai-codex, google, other"
+            "Unsupported provider: {value}. Valid options: anthropic, claude-code, openai, openai-codex, google, bedrock, other"
         ),
     }
 }
@@ -0,0 +1,223 @@
+use anyhow::{Context, Result, bail};
+use comfy_table::{Cell, Table};
+use restflow_core::models::{BrowserPlan, SavedBrowserPlan};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::cli::{BrowserPlanCommands, OutputFormat};
+use crate::commands::utils::format_timestamp;
+use crate::executor::CommandExecutor;
+use crate::output::json::print_json;
+use crate::output::table::print_table;
+
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: BrowserPlanCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        BrowserPlanCommands::List => list_plans(executor, format).await,
+        BrowserPlanCommands::Show { name, version } => {
+            show_plan(executor, &name, version, format).await
+        }
+        BrowserPlanCommands::Versions { name } => list_versions(executor, &name, format).await,
+        BrowserPlanCommands::Save {
+            name,
+            file,
+            description,
+            timeout,
+        } => save_plan(executor, &name, &file, description, timeout, format).await,
+        BrowserPlanCommands::Delete { name } => delete_plan(executor, &name, format).await,
+        BrowserPlanCommands::Run {
+            name,
+            version,
+            payload,
+        } => run_plan(executor, &name, version, payload.as_deref(), format).await,
+    }
+}
+
+async fn list_plans(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -> Result<()> {
+    let plans = executor.list_browser_plans().await?;
+
+    if format.is_json() {
+        return print_json(&plans);
+    }
+
+    if plans.is_empty() {
+        println!("No browser plans found.");
+        return Ok(());
+    }
+
+    print_plans(plans)
+}
+
+async fn show_plan(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    version: Option<u32>,
+    format: OutputFormat,
+) -> Result<()> {
+    let Some(plan) = executor.get_browser_plan(name, version).await? else {
+        bail!("Browser plan not found: {}", name);
+    };
+
+    if format.is_json() {
+        return print_json(&plan);
+    }
+
+    println!("Name:        {}", plan.name);
+    println!("Version:     {}", plan.version);
+    if let Some(description) = &plan.description {
+        println!("Description: {}", description);
+    }
+    println!("Updated:     {}", format_timestamp(Some(plan.updated_at)));
+    if let Some(timeout_secs) = plan.plan.timeout_secs {
+        println!("Timeout:     {}s", timeout_secs);
+    }
+    println!("{}", serde_json::to_string_pretty(&plan.plan.actions)?);
+    Ok(())
+}
+
+async fn list_versions(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let versions = executor.list_browser_plan_versions(name).await?;
+
+    if format.is_json() {
+        return print_json(&versions);
+    }
+
+    print_plans(versions)
+}
+
+async fn save_plan(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    file: &str,
+    description: Option<String>,
+    timeout: Option<u64>,
+    format: OutputFormat,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read plan file {}", file))?;
+    let mut plan = parse_plan(&content)?;
+    if timeout.is_some() {
+        plan.timeout_secs = timeout;
+    }
+
+    let saved = executor.save_browser_plan(name, description, plan).await?;
+
+    if format.is_json() {
+        return print_json(&saved);
+    }
+
+    println!(
+        "Saved browser plan {} (version {})",
+        saved.name, saved.version
+    );
+    Ok(())
+}
+
+async fn delete_plan(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let deleted = executor.delete_browser_plan(name).await?;
+
+    if format.is_json() {
+        return print_json(&serde_json::json!({ "name": name, "deleted": deleted }));
+    }
+
+    if deleted {
+        println!("Deleted browser plan: {}", name);
+    } else {
+        println!("Browser plan not found: {}", name);
+    }
+    Ok(())
+}
+
+async fn run_plan(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    version: Option<u32>,
+    payload: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let payload = payload
+        .map(|payload| {
+            serde_json::from_str::<Value>(payload)
+                .map_err(|e| anyhow::anyhow!("Invalid JSON payload: {}", e))
+        })
+        .transpose()?;
+
+    let outcome = executor.run_browser_plan(name, version, payload).await?;
+
+    if format.is_json() {
+        return print_json(&outcome);
+    }
+
+    if !outcome.success {
+        bail!(
+            "Browser plan {} failed: {}",
+            name,
+            outcome.error.as_deref().unwrap_or("unknown error")
+        );
+    }
+    println!(
+        "Browser plan {} completed in {}ms",
+        name, outcome.duration_ms
+    );
+    println!("{}", serde_json::to_string_pretty(&outcome.results)?);
+    Ok(())
+}
+
+fn print_plans(plans: Vec<SavedBrowserPlan>) -> Result<()> {
+    let mut table = Table::new();
+    table.set_header(vec!["Name", "Version", "Actions", "Description", "Updated"]);
+
+    for plan in plans {
+        let action_count = plan.plan.actions.as_array().map_or(0, Vec::len);
+        table.add_row(vec![
+            Cell::new(&plan.name),
+            Cell::new(plan.version),
+            Cell::new(action_count),
+            Cell::new(plan.description.as_deref().unwrap_or("-")),
+            Cell::new(format_timestamp(Some(plan.updated_at))),
+        ]);
+    }
+
+    print_table(table)
+}
+
+/// Parse a plan file holding either a bare actions array or a full plan.
+fn parse_plan(content: &str) -> Result<BrowserPlan> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| anyhow::anyhow!("Invalid plan JSON: {}", e))?;
+    if value.is_array() {
+        return Ok(BrowserPlan {
+            actions: value,
+            timeout_secs: None,
+        });
+    }
+    serde_json::from_value(value).map_err(|e| anyhow::anyhow!("Invalid browser plan: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plan_accepts_actions_array_and_full_plan() {
+        let plan = parse_plan(r#"[{"type":"navigate","url":"https://a.test"}]"#).unwrap();
+        assert_eq!(plan.actions[0]["type"], "navigate");
+        assert_eq!(plan.timeout_secs, None);
+
+        let plan = parse_plan(r#"{"actions":[],"timeout_secs":30}"#).unwrap();
+        assert_eq!(plan.timeout_secs, Some(30));
+
+        assert!(parse_plan(r#"{"steps":[]}"#).is_err());
+    }
+}
@@ -8,8 +8,8 @@ use crate::executor::CommandExecutor;
 use crate::output::{OutputFormat, json::print_json};
 use restflow_core::storage::SystemConfig;
 use restflow_storage::{
-    CliConfig, ConfigDocument, effective_config_sources, load_cli_config, load_global_cli_config,
-    write_cli_config,
+    CliConfig, ConfigDocument, TagRetentionRule, WebhookSinkConfig, effective_config_sources,
+    load_cli_config, load_global_cli_config, write_cli_config,
 };
 
 pub async fn run(
@@ -93,6 +93,26 @@ async fn show_config(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
         Cell::new("system.log_file_retention_days"),
         Cell::new(config.system.log_file_retention_days),
     ]);
+    table.add_row(vec![
+        Cell::new("system.artifact_retention_days"),
+        Cell::new(config.system.artifact_retention_days),
+    ]);
+    table.add_row(vec![
+        Cell::new("system.artifact_max_total_mb"),
+        Cell::new(config.system.artifact_max_total_mb),
+    ]);
+    table.add_row(vec![
+        Cell::new("system.daily_budget_usd"),
+        Cell::new(format_optional_usd(config.system.daily_budget_usd)),
+    ]);
+    table.add_row(vec![
+        Cell::new("system.monthly_budget_usd"),
+        Cell::new(format_optional_usd(config.system.monthly_budget_usd)),
+    ]);
+    table.add_row(vec![
+        Cell::new("system.otlp_endpoint"),
+        Cell::new(config.system.otlp_endpoint.as_deref().unwrap_or("none")),
+    ]);
     table.add_row(vec![
         Cell::new("system.experimental_features"),
         Cell::new(format_string_list(&config.system.experimental_features)),
@@ -125,6 +145,68 @@ async fn show_config(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
         Cell::new("agent.browser_timeout_secs"),
         Cell::new(config.agent.browser_timeout_secs),
     ]);
+    table.add_row(vec![
+        Cell::new("agent.browser_allowed_domains"),
+        Cell::new(format_string_list(&config.agent.browser_allowed_domains)),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.browser_denied_domains"),
+        Cell::new(format_string_list(&config.agent.browser_denied_domains)),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.browser_block_private_networks"),
+        Cell::new(config.agent.browser_block_private_networks),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.browser_artifact_max_session_bytes"),
+        Cell::new(config.agent.browser_artifact_max_session_bytes),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.browser_artifact_max_total_bytes"),
+        Cell::new(config.agent.browser_artifact_max_total_bytes),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.browser_artifact_max_age_secs"),
+        Cell::new(config.agent.browser_artifact_max_age_secs),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.subprocess_memory_limit_mb"),
+        Cell::new(config.agent.subprocess_memory_limit_mb),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.subprocess_cpu_weight"),
+        Cell::new(config.agent.subprocess_cpu_weight),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.subprocess_max_processes"),
+        Cell::new(config.agent.subprocess_max_processes),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.subprocess_cpu_time_secs"),
+        Cell::new(config.agent.subprocess_cpu_time_secs),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.container_runtime"),
+        Cell::new(config.agent.container_runtime.as_deref().unwrap_or("none")),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.container_image"),
+        Cell::new(&config.agent.container_image),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.container_network"),
+        Cell::new(config.agent.container_network),
+    ]);
+    table.add_row(vec![
+        Cell::new("agent.subprocess_network_allow"),
+        Cell::new(
+            config
+                .agent
+                .subprocess_network_allow
+                .as_deref()
+                .map_or_else(|| "none".to_string(), format_string_list),
+        ),
+    ]);
     table.add_row(vec![
         Cell::new("agent.process_session_ttl_secs"),
         Cell::new(config.agent.process_session_ttl_secs),
@@ -224,6 +306,10 @@ async fn show_config(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
         Cell::new("runtime.background_runner_max_concurrent_tasks"),
         Cell::new(config.runtime.background_runner_max_concurrent_tasks),
     ]);
+    table.add_row(vec![
+        Cell::new("runtime.background_runner_auto_resume"),
+        Cell::new(config.runtime.background_runner_auto_resume),
+    ]);
     table.add_row(vec![
         Cell::new("runtime.chat_max_session_history"),
         Cell::new(config.runtime.chat_max_session_history),
@@ -236,6 +322,14 @@ async fn show_config(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
         Cell::new("channel.telegram_polling_timeout_secs"),
         Cell::new(config.channel.telegram_polling_timeout_secs),
     ]);
+    table.add_row(vec![
+        Cell::new("channel.voice_replies"),
+        Cell::new(config.channel.voice_replies),
+    ]);
+    table.add_row(vec![
+        Cell::new("channel.webhooks"),
+        Cell::new(format_webhook_sinks(&config.channel.webhooks)),
+    ]);
     table.add_row(vec![
         Cell::new("registry.github_cache_ttl_secs"),
         Cell::new(config.registry.github_cache_ttl_secs),
@@ -244,6 +338,66 @@ async fn show_config(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
         Cell::new("registry.marketplace_cache_ttl_secs"),
         Cell::new(config.registry.marketplace_cache_ttl_secs),
     ]);
+    table.add_row(vec![
+        Cell::new("registry.trusted_skill_keys"),
+        Cell::new(format_string_list(&config.registry.trusted_skill_keys)),
+    ]);
+    table.add_row(vec![
+        Cell::new("registry.require_signed_skills"),
+        Cell::new(config.registry.require_signed_skills),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.auto_tag"),
+        Cell::new(config.memory.auto_tag),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.max_suggested_tags"),
+        Cell::new(config.memory.max_suggested_tags),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.tag_retention"),
+        Cell::new(format_tag_retention(&config.memory.tag_retention)),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.embedding_model"),
+        Cell::new(format_optional_string(
+            config.memory.embedding_model.as_deref(),
+        )),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.embedding_dimension"),
+        Cell::new(config.memory.embedding_dimension),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.embedding_base_url"),
+        Cell::new(format_optional_string(
+            config.memory.embedding_base_url.as_deref(),
+        )),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.hybrid_vector_weight"),
+        Cell::new(config.memory.hybrid_vector_weight),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.hybrid_rrf_k"),
+        Cell::new(config.memory.hybrid_rrf_k),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.consolidation_enabled"),
+        Cell::new(config.memory.consolidation_enabled),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.consolidation_interval_hours"),
+        Cell::new(config.memory.consolidation_interval_hours),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.session_retention_days"),
+        Cell::new(config.memory.session_retention_days),
+    ]);
+    table.add_row(vec![
+        Cell::new("memory.max_chunks_per_agent"),
+        Cell::new(config.memory.max_chunks_per_agent),
+    ]);
     table.add_row(vec![
         Cell::new("cli.version"),
         Cell::new(config.cli.version),
@@ -321,6 +475,11 @@ async fn get_config_value(
         "system.checkpoint_retention_days" => json!(config.system.checkpoint_retention_days),
         "system.memory_chunk_retention_days" => json!(config.system.memory_chunk_retention_days),
         "system.log_file_retention_days" => json!(config.system.log_file_retention_days),
+        "system.artifact_retention_days" => json!(config.system.artifact_retention_days),
+        "system.artifact_max_total_mb" => json!(config.system.artifact_max_total_mb),
+        "system.daily_budget_usd" => json!(config.system.daily_budget_usd),
+        "system.monthly_budget_usd" => json!(config.system.monthly_budget_usd),
+        "system.otlp_endpoint" => json!(config.system.otlp_endpoint),
         "system.experimental_features" => json!(config.system.experimental_features),
         "agent" => json!(config.agent),
         "agent.tool_timeout_secs" => json!(config.agent.tool_timeout_secs),
@@ -328,6 +487,28 @@ async fn get_config_value(
         "agent.bash_timeout_secs" => json!(config.agent.bash_timeout_secs),
         "agent.python_timeout_secs" => json!(config.agent.python_timeout_secs),
         "agent.browser_timeout_secs" => json!(config.agent.browser_timeout_secs),
+        "agent.browser_allowed_domains" => json!(config.agent.browser_allowed_domains),
+        "agent.browser_denied_domains" => json!(config.agent.browser_denied_domains),
+        "agent.browser_block_private_networks" => {
+            json!(config.agent.browser_block_private_networks)
+        }
+        "agent.browser_artifact_max_session_bytes" => {
+            json!(config.agent.browser_artifact_max_session_bytes)
+        }
+        "agent.browser_artifact_max_total_bytes" => {
+            json!(config.agent.browser_artifact_max_total_bytes)
+        }
+        "agent.browser_artifact_max_age_secs" => {
+            json!(config.agent.browser_artifact_max_age_secs)
+        }
+        "agent.subprocess_memory_limit_mb" => json!(config.agent.subprocess_memory_limit_mb),
+        "agent.subprocess_cpu_weight" => json!(config.agent.subprocess_cpu_weight),
+        "agent.subprocess_max_processes" => json!(config.agent.subprocess_max_processes),
+        "agent.subprocess_cpu_time_secs" => json!(config.agent.subprocess_cpu_time_secs),
+        "agent.container_runtime" => json!(config.agent.container_runtime),
+        "agent.container_image" => json!(config.agent.container_image),
+        "agent.container_network" => json!(config.agent.container_network),
+        "agent.subprocess_network_allow" => json!(config.agent.subprocess_network_allow),
         "agent.process_session_ttl_secs" => json!(config.agent.process_session_ttl_secs),
         "agent.approval_timeout_secs" => json!(config.agent.approval_timeout_secs),
         "agent.max_iterations" => json!(config.agent.max_iterations),
@@ -369,6 +550,9 @@ async fn get_config_value(
         "runtime.background_runner_max_concurrent_tasks" => {
             json!(config.runtime.background_runner_max_concurrent_tasks)
         }
+        "runtime.background_runner_auto_resume" => {
+            json!(config.runtime.background_runner_auto_resume)
+        }
         "runtime.chat_max_session_history" => {
             json!(config.runtime.chat_max_session_history)
         }
@@ -379,6 +563,8 @@ async fn get_config_value(
         "channel.telegram_polling_timeout_secs" => {
             json!(config.channel.telegram_polling_timeout_secs)
         }
+        "channel.voice_replies" => json!(config.channel.voice_replies),
+        "channel.webhooks" => json!(config.channel.webhooks),
         "registry" => json!(config.registry),
         "registry.github_cache_ttl_secs" => {
             json!(config.registry.github_cache_ttl_secs)
@@ -386,6 +572,29 @@ async fn get_config_value(
         "registry.marketplace_cache_ttl_secs" => {
             json!(config.registry.marketplace_cache_ttl_secs)
         }
+        "registry.trusted_skill_keys" => json!(config.registry.trusted_skill_keys),
+        "registry.require_signed_skills" => json!(config.registry.require_signed_skills),
+        "memory" => json!(config.memory),
+        "memory.auto_tag" => json!(config.memory.auto_tag),
+        "memory.max_suggested_tags" => json!(config.memory.max_suggested_tags),
+        "memory.tag_retention" => json!(config.memory.tag_retention),
+        "memory.embedding_model" => json!(config.memory.embedding_model),
+        "memory.embedding_dimension" => json!(config.memory.embedding_dimension),
+        "memory.embedding_base_url" => json!(config.memory.embedding_base_url),
+        "memory.embedding_api_key_secret" => json!(config.memory.embedding_api_key_secret),
+        "memory.hybrid_vector_weight" => json!(config.memory.hybrid_vector_weight),
+        "memory.hybrid_rrf_k" => json!(config.memory.hybrid_rrf_k),
+        "memory.consolidation_enabled" => json!(config.memory.consolidation_enabled),
+        "memory.consolidation_interval_hours" => {
+            json!(config.memory.consolidation_interval_hours)
+        }
+        "memory.consolidation_model" => json!(config.memory.consolidation_model),
+        "memory.consolidation_min_cluster_size" => {
+            json!(config.memory.consolidation_min_cluster_size)
+        }
+        "memory.consolidation_similarity" => json!(config.memory.consolidation_similarity),
+        "memory.session_retention_days" => json!(config.memory.session_retention_days),
+        "memory.max_chunks_per_agent" => json!(config.memory.max_chunks_per_agent),
         "cli" => json!(config.cli),
         "cli.version" => json!(config.cli.version),
         "cli.agent" => json!(config.cli.agent),
@@ -487,6 +696,21 @@ async fn set_config_value(
             "system.log_file_retention_days" => {
                 config.log_file_retention_days = parse_value(value)?;
             }
+            "system.artifact_retention_days" => {
+                config.artifact_retention_days = parse_value(value)?;
+            }
+            "system.artifact_max_total_mb" => {
+                config.artifact_max_total_mb = parse_value(value)?;
+            }
+            "system.daily_budget_usd" => {
+                config.daily_budget_usd = parse_optional_f64(value)?;
+            }
+            "system.monthly_budget_usd" => {
+                config.monthly_budget_usd = parse_optional_f64(value)?;
+            }
+            "system.otlp_endpoint" => {
+                config.otlp_endpoint = parse_optional_string(value);
+            }
             "system.experimental_features" => {
                 config.experimental_features = parse_string_list(value)?;
             }
@@ -505,6 +729,48 @@ async fn set_config_value(
             "agent.browser_timeout_secs" => {
                 config.agent.browser_timeout_secs = parse_value(value)?;
             }
+            "agent.browser_allowed_domains" => {
+                config.agent.browser_allowed_domains = parse_string_list(value)?;
+            }
+            "agent.browser_denied_domains" => {
+                config.agent.browser_denied_domains = parse_string_list(value)?;
+            }
+            "agent.browser_block_private_networks" => {
+                config.agent.browser_block_private_networks = parse_value(value)?;
+            }
+            "agent.browser_artifact_max_session_bytes" => {
+                config.agent.browser_artifact_max_session_bytes = parse_value(value)?;
+            }
+            "agent.browser_artifact_max_total_bytes" => {
+                config.agent.browser_artifact_max_total_bytes = parse_value(value)?;
+            }
+            "agent.browser_artifact_max_age_secs" => {
+                config.agent.browser_artifact_max_age_secs = parse_value(value)?;
+            }
+            "agent.subprocess_memory_limit_mb" => {
+                config.agent.subprocess_memory_limit_mb = parse_value(value)?;
+            }
+            "agent.subprocess_cpu_weight" => {
+                config.agent.subprocess_cpu_weight = parse_value(value)?;
+            }
+            "agent.subprocess_max_processes" => {
+                config.agent.subprocess_max_processes = parse_value(value)?;
+            }
+            "agent.subprocess_cpu_time_secs" => {
+                config.agent.subprocess_cpu_time_secs = parse_value(value)?;
+            }
+            "agent.container_runtime" => {
+                config.agent.container_runtime = parse_optional_string(value);
+            }
+            "agent.container_image" => {
+                config.agent.container_image = value.trim().to_string();
+            }
+            "agent.container_network" => {
+                config.agent.container_network = parse_value(value)?;
+            }
+            "agent.subprocess_network_allow" => {
+                config.agent.subprocess_network_allow = parse_optional_string_list(value)?;
+            }
             "agent.process_session_ttl_secs" => {
                 config.agent.process_session_ttl_secs = parse_value(value)?;
             }
@@ -582,6 +848,9 @@ async fn set_config_value(
                     .runtime_defaults
                     .background_runner_max_concurrent_tasks = parse_value(value)?;
             }
+            "runtime.background_runner_auto_resume" => {
+                config.runtime_defaults.background_runner_auto_resume = parse_value(value)?;
+            }
             "runtime.chat_max_session_history" => {
                 config.runtime_defaults.chat_max_session_history = parse_value(value)?;
             }
@@ -591,12 +860,66 @@ async fn set_config_value(
             "channel.telegram_polling_timeout_secs" => {
                 config.channel_defaults.telegram_polling_timeout_secs = parse_value(value)?;
             }
+            "channel.voice_replies" => {
+                config.channel_defaults.voice_replies = parse_value(value)?;
+            }
             "registry.github_cache_ttl_secs" => {
                 config.registry_defaults.github_cache_ttl_secs = parse_value(value)?;
             }
             "registry.marketplace_cache_ttl_secs" => {
                 config.registry_defaults.marketplace_cache_ttl_secs = parse_value(value)?;
             }
+            "registry.trusted_skill_keys" => {
+                config.registry_defaults.trusted_skill_keys = parse_string_list(value)?;
+            }
+            "registry.require_signed_skills" => {
+                config.registry_defaults.require_signed_skills = parse_value(value)?;
+            }
+            "memory.auto_tag" => {
+                config.memory_defaults.auto_tag = parse_value(value)?;
+            }
+            "memory.max_suggested_tags" => {
+                config.memory_defaults.max_suggested_tags = parse_value(value)?;
+            }
+            "memory.embedding_model" => {
+                config.memory_defaults.embedding_model = parse_optional_string(value);
+            }
+            "memory.embedding_dimension" => {
+                config.memory_defaults.embedding_dimension = parse_value(value)?;
+            }
+            "memory.embedding_base_url" => {
+                config.memory_defaults.embedding_base_url = parse_optional_string(value);
+            }
+            "memory.embedding_api_key_secret" => {
+                config.memory_defaults.embedding_api_key_secret = parse_optional_string(value);
+            }
+            "memory.hybrid_vector_weight" => {
+                config.memory_defaults.hybrid_vector_weight = parse_value(value)?;
+            }
+            "memory.hybrid_rrf_k" => {
+                config.memory_defaults.hybrid_rrf_k = parse_value(value)?;
+            }
+            "memory.consolidation_enabled" => {
+                config.memory_defaults.consolidation_enabled = parse_value(value)?;
+            }
+            "memory.consolidation_interval_hours" => {
+                config.memory_defaults.consolidation_interval_hours = parse_value(value)?;
+            }
+            "memory.consolidation_model" => {
+                config.memory_defaults.consolidation_model = parse_optional_string(value);
+            }
+            "memory.consolidation_min_cluster_size" => {
+                config.memory_defaults.consolidation_min_cluster_size = parse_value(value)?;
+            }
+            "memory.consolidation_similarity" => {
+                config.memory_defaults.consolidation_similarity = parse_value(value)?;
+            }
+            "memory.session_retention_days" => {
+                config.memory_defaults.session_retention_days = parse_value(value)?;
+            }
+            "memory.max_chunks_per_agent" => {
+                config.memory_defaults.max_chunks_per_agent = parse_value(value)?;
+            }
             _ => bail!("Unsupported config key: {key}"),
         }
 
@@ -645,6 +968,17 @@ fn parse_optional_u64(value: &str) -> Result<Option<u64>> {
     parse_value::<u64>(normalized).map(Some)
 }
 
+fn parse_optional_f64(value: &str) -> Result<Option<f64>> {
+    let normalized = value.trim();
+    if normalized.eq_ignore_ascii_case("none")
+        || normalized.eq_ignore_ascii_case("null")
+        || normalized.eq_ignore_ascii_case("unset")
+    {
+        return Ok(None);
+    }
+    parse_value::<f64>(normalized).map(Some)
+}
+
 fn parse_optional_string(value: &str) -> Option<String> {
     let normalized = value.trim();
     if normalized.eq_ignore_ascii_case("none")
@@ -679,6 +1013,37 @@ fn format_string_list(values: &[String]) -> String {
     serde_json::to_string(values).unwrap_or_else(|_| "[]".to_string())
 }
 
+fn format_tag_retention(rules: &[TagRetentionRule]) -> String {
+    if rules.is_empty() {
+        return "none".to_string();
+    }
+    rules
+        .iter()
+        .map(|rule| match rule.retention_days {
+            0 => format!("{}=forever", rule.tag),
+            days => format!("{}={}d", rule.tag, days),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_webhook_sinks(sinks: &[WebhookSinkConfig]) -> String {
+    if sinks.is_empty() {
+        return "none".to_string();
+    }
+    sinks
+        .iter()
+        .map(|sink| {
+            if sink.events.is_empty() {
+                format!("{} [*]", sink.url)
+            } else {
+                format!("{} [{}]", sink.url, sink.events.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 async fn load_effective_config_document(
     executor: Arc<dyn CommandExecutor>,
 ) -> Result<ConfigDocument> {
@@ -698,6 +1063,12 @@ fn format_source_info(source: &Option<restflow_storage::ConfigSourcePathInfo>) -
     }
 }
 
+fn format_optional_usd(value: Option<f64>) -> String {
+    value
+        .map(|usd| format!("${usd:.2}"))
+        .unwrap_or_else(|| "none".to_string())
+}
+
 fn format_optional_u64(value: Option<u64>) -> String {
     value
         .map(|secs| secs.to_string())
@@ -815,6 +1186,52 @@ mod tests {
         assert_eq!(config.log_file_retention_days, 45);
     }
 
+    #[tokio::test]
+    async fn test_set_config_supports_artifact_retention() {
+        let ctx = setup_executor().await;
+
+        set_config_value(
+            ctx.executor.clone(),
+            "system.artifact_max_total_mb",
+            "512",
+            OutputFormat::Json,
+        )
+        .await
+        .expect("set config should support system.artifact_max_total_mb");
+
+        let config = ctx.executor.get_config().await.expect("get config");
+        assert_eq!(config.artifact_max_total_mb, 512);
+    }
+
+    #[tokio::test]
+    async fn test_set_config_supports_budgets() {
+        let ctx = setup_executor().await;
+
+        set_config_value(
+            ctx.executor.clone(),
+            "system.daily_budget_usd",
+            "2.5",
+            OutputFormat::Json,
+        )
+        .await
+        .expect("set config should support system.daily_budget_usd");
+
+        let config = ctx.executor.get_config().await.expect("get config");
+        assert_eq!(config.daily_budget_usd, Some(2.5));
+
+        set_config_value(
+            ctx.executor.clone(),
+            "system.daily_budget_usd",
+            "none",
+            OutputFormat::Json,
+        )
+        .await
+        .expect("set config should clear system.daily_budget_usd");
+
+        let config = ctx.executor.get_config().await.expect("get config");
+        assert_eq!(config.daily_budget_usd, None);
+    }
+
     #[tokio::test]
     async fn test_set_config_supports_agent_max_depth() {
         let ctx = setup_executor().await;
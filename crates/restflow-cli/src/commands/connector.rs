@@ -0,0 +1,160 @@
+use anyhow::{Context, Result, bail};
+use comfy_table::{Cell, Table};
+use restflow_core::auth::secret_key;
+use restflow_core::models::{ApiConnector, ConnectorAuth};
+use std::sync::Arc;
+
+use crate::cli::{ConnectorCommands, OutputFormat};
+use crate::commands::utils::format_timestamp;
+use crate::executor::CommandExecutor;
+use crate::output::json::print_json;
+use crate::output::table::print_table;
+
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: ConnectorCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        ConnectorCommands::List => list_connectors(executor, format).await,
+        ConnectorCommands::Show { name } => show_connector(executor, &name, format).await,
+        ConnectorCommands::Import {
+            name,
+            file,
+            base_url,
+            auth_secret,
+            auth_profile,
+        } => {
+            // OAuth profiles keep their refreshed access token under a fixed secret name.
+            let auth_secret =
+                auth_secret.or_else(|| auth_profile.map(|id| secret_key(&id, "access_token")));
+            import_connector(executor, &name, &file, base_url, auth_secret, format).await
+        }
+        ConnectorCommands::Delete { name } => delete_connector(executor, &name, format).await,
+    }
+}
+
+async fn list_connectors(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -> Result<()> {
+    let connectors = executor.list_api_connectors().await?;
+
+    if format.is_json() {
+        return print_json(&connectors);
+    }
+
+    if connectors.is_empty() {
+        println!("No API connectors found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Name", "Base URL", "Auth", "Operations", "Updated"]);
+    for connector in connectors {
+        table.add_row(vec![
+            Cell::new(&connector.name),
+            Cell::new(&connector.base_url),
+            Cell::new(describe_auth(&connector.auth)),
+            Cell::new(connector.operations.len()),
+            Cell::new(format_timestamp(Some(connector.updated_at))),
+        ]);
+    }
+    print_table(table)
+}
+
+async fn show_connector(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let Some(connector) = executor.get_api_connector(name).await? else {
+        bail!("API connector not found: {}", name);
+    };
+
+    if format.is_json() {
+        return print_json(&connector);
+    }
+
+    print_connector(&connector);
+    let mut table = Table::new();
+    table.set_header(vec!["Operation", "Method", "Path", "Summary"]);
+    for operation in &connector.operations {
+        table.add_row(vec![
+            Cell::new(&operation.id),
+            Cell::new(&operation.method),
+            Cell::new(&operation.path),
+            Cell::new(operation.summary.as_deref().unwrap_or("-")),
+        ]);
+    }
+    print_table(table)
+}
+
+async fn import_connector(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    file: &str,
+    base_url: Option<String>,
+    auth_secret: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let spec = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read OpenAPI document {}", file))?;
+
+    let connector = executor
+        .import_api_connector(name, spec, base_url, auth_secret)
+        .await?;
+
+    if format.is_json() {
+        return print_json(&connector);
+    }
+
+    println!(
+        "Imported API connector {} with {} operations",
+        connector.name,
+        connector.operations.len()
+    );
+    println!(
+        "Allow its tools for an agent with \"connector:{}\" (or \"api_connectors\" for all connectors).",
+        connector.name
+    );
+    Ok(())
+}
+
+async fn delete_connector(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let deleted = executor.delete_api_connector(name).await?;
+
+    if format.is_json() {
+        return print_json(&serde_json::json!({ "name": name, "deleted": deleted }));
+    }
+
+    if deleted {
+        println!("Deleted API connector: {}", name);
+    } else {
+        println!("API connector not found: {}", name);
+    }
+    Ok(())
+}
+
+fn print_connector(connector: &ApiConnector) {
+    println!("Name:        {}", connector.name);
+    if let Some(description) = &connector.description {
+        println!("Description: {}", description);
+    }
+    println!("Base URL:    {}", connector.base_url);
+    println!("Auth:        {}", describe_auth(&connector.auth));
+    println!(
+        "Updated:     {}",
+        format_timestamp(Some(connector.updated_at))
+    );
+}
+
+fn describe_auth(auth: &ConnectorAuth) -> String {
+    match auth {
+        ConnectorAuth::None => "none".to_string(),
+        ConnectorAuth::Bearer { secret } => format!("bearer ({})", secret),
+        ConnectorAuth::ApiKey { secret, name, .. } => format!("api key {} ({})", name, secret),
+        ConnectorAuth::Basic { secret } => format!("basic ({})", secret),
+    }
+}
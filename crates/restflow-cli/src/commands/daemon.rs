@@ -3,8 +3,14 @@ use crate::commands::daemon_state::{self, EffectiveDaemonStatus, RunningSource};
 use crate::daemon::CliTaskRunner;
 use anyhow::{Context, Result};
 use restflow_core::AppCore;
-use restflow_core::daemon::{DaemonConfig, IpcServer, start_daemon_with_config, stop_daemon};
+use restflow_core::daemon::{
+    DaemonComponentState, DaemonConfig, IpcClient, IpcServer, ServiceManager, ServiceSpec,
+    install_service, set_daemon_component, start_daemon_with_config, stop_daemon,
+    uninstall_service,
+};
 use restflow_core::paths;
+use restflow_core::services::memory_consolidation::run_memory_consolidation;
+use restflow_core::storage::MemoryDefaults;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
@@ -61,6 +67,8 @@ pub async fn run(core: Arc<AppCore>, command: DaemonCommands) -> Result<()> {
         } => restart(core, foreground, mcp_port).await,
         DaemonCommands::Stop => stop().await,
         DaemonCommands::Status => status().await,
+        DaemonCommands::Install { mcp_port, no_start } => install(mcp_port, !no_start).await,
+        DaemonCommands::Uninstall => uninstall().await,
     }
 }
 
@@ -94,6 +102,14 @@ pub async fn run_without_core(command: &DaemonCommands) -> Result<bool> {
             status().await?;
             Ok(true)
         }
+        DaemonCommands::Install { mcp_port, no_start } => {
+            install(*mcp_port, !no_start).await?;
+            Ok(true)
+        }
+        DaemonCommands::Uninstall => {
+            uninstall().await?;
+            Ok(true)
+        }
         DaemonCommands::Start { .. } | DaemonCommands::Restart { .. } => Ok(false),
     }
 }
@@ -109,6 +125,8 @@ fn should_run_without_core(command: &DaemonCommands) -> bool {
             ..
         } | DaemonCommands::Stop
             | DaemonCommands::Status
+            | DaemonCommands::Install { .. }
+            | DaemonCommands::Uninstall
     )
 }
 
@@ -234,9 +252,11 @@ async fn run_daemon(core: Arc<AppCore>, config: DaemonConfig) -> Result<()> {
     let socket_path = paths::socket_path()?;
     let ipc_server = IpcServer::new(core.clone(), socket_path);
     let ipc_shutdown = shutdown_tx.subscribe();
+    set_daemon_component("ipc", DaemonComponentState::Running, None);
     let ipc_handle = tokio::spawn(async move {
         if let Err(err) = ipc_server.run(ipc_shutdown).await {
             error!(error = %err, "IPC server stopped unexpectedly");
+            set_daemon_component("ipc", DaemonComponentState::Failed, Some(err.to_string()));
         }
     });
 
@@ -245,17 +265,31 @@ async fn run_daemon(core: Arc<AppCore>, config: DaemonConfig) -> Result<()> {
     let addr = std::net::SocketAddr::new(mcp_bind_addr, config.mcp_port.unwrap_or(8787));
     let mcp_shutdown = shutdown_tx.subscribe();
     let mcp_core = core.clone();
+    set_daemon_component(
+        "http",
+        DaemonComponentState::Running,
+        Some(addr.to_string()),
+    );
     let mcp_handle = tokio::spawn(async move {
         if let Err(err) =
             restflow_core::daemon::run_mcp_http_server(mcp_core, addr, mcp_shutdown).await
         {
             error!(error = %err, "MCP server stopped unexpectedly");
+            set_daemon_component("http", DaemonComponentState::Failed, Some(err.to_string()));
         }
     });
 
     let mut task_runner = CliTaskRunner::new(core.clone());
-    if let Err(err) = task_runner.start().await {
-        error!(error = %err, "Task runner failed to start; continuing without runner");
+    match task_runner.start().await {
+        Ok(()) => set_daemon_component("task_runner", DaemonComponentState::Running, None),
+        Err(err) => {
+            error!(error = %err, "Task runner failed to start; continuing without runner");
+            set_daemon_component(
+                "task_runner",
+                DaemonComponentState::Failed,
+                Some(err.to_string()),
+            );
+        }
     }
 
     if let Err(err) = run_and_log_cleanup(core.clone()).await {
@@ -268,6 +302,12 @@ async fn run_daemon(core: Arc<AppCore>, config: DaemonConfig) -> Result<()> {
         run_cleanup_loop(cleanup_core, cleanup_shutdown).await;
     });
 
+    let consolidation_shutdown = shutdown_tx.subscribe();
+    let consolidation_core = core.clone();
+    let consolidation_handle = tokio::spawn(async move {
+        run_consolidation_loop(consolidation_core, consolidation_shutdown).await;
+    });
+
     // Ensure core services did not fail immediately before declaring daemon as running.
     sleep(Duration::from_millis(120)).await;
     ensure_startup_services_and_cleanup(ipc_handle.is_finished(), mcp_handle.is_finished(), || {
@@ -288,6 +328,10 @@ async fn run_daemon(core: Arc<AppCore>, config: DaemonConfig) -> Result<()> {
     let _ = ipc_handle.await;
     let _ = mcp_handle.await;
     let _ = cleanup_handle.await;
+    let _ = consolidation_handle.await;
+    if let Err(err) = core.storage.secrets.flush_secret_usage() {
+        warn!(error = %err, "Failed to flush secret usage");
+    }
 
     println!("Daemon stopped");
     Ok(())
@@ -335,6 +379,39 @@ async fn run_cleanup_loop(core: Arc<AppCore>, mut shutdown: tokio::sync::broadca
     }
 }
 
+async fn run_consolidation_loop(
+    core: Arc<AppCore>,
+    mut shutdown: tokio::sync::broadcast::Receiver<()>,
+) {
+    let hours = core
+        .storage
+        .config
+        .get_effective_config()
+        .map(|config| config.memory_defaults.consolidation_interval_hours)
+        .unwrap_or(MemoryDefaults::default().consolidation_interval_hours)
+        .max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(hours * 60 * 60));
+    interval.tick().await;
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => break,
+            _ = interval.tick() => {
+                match run_memory_consolidation(&core).await {
+                    Ok(report) if report.clusters > 0 => info!(
+                        agents = report.agents,
+                        clusters = report.clusters,
+                        chunks_archived = report.chunks_archived,
+                        chunks_created = report.chunks_created,
+                        "Memory consolidation completed"
+                    ),
+                    Ok(_) => {}
+                    Err(err) => warn!(error = %err, "Scheduled memory consolidation failed"),
+                }
+            }
+        }
+    }
+}
+
 async fn run_and_log_cleanup(core: Arc<AppCore>) -> Result<()> {
     let report = restflow_core::services::cleanup::run_cleanup(&core).await?;
     info!(
@@ -345,6 +422,8 @@ async fn run_and_log_cleanup(core: Arc<AppCore>) -> Result<()> {
         memory_sessions = report.memory_sessions,
         vector_orphans = report.vector_orphans,
         daemon_logs = report.daemon_log_files,
+        artifacts = report.artifacts,
+        artifact_bytes = report.artifact_bytes_reclaimed,
         "Storage cleanup completed"
     );
     Ok(())
@@ -404,6 +483,39 @@ fn configure_nofile_limit() {
     }
 }
 
+async fn install(mcp_port: Option<u16>, start: bool) -> Result<()> {
+    let spec = ServiceSpec::current(mcp_port)?;
+    // The service manager starts its own daemon, so hand over from a running one.
+    if start && stop_daemon_effective().await? {
+        println!("Sent stop signal to daemon");
+        wait_for_daemon_exit().await?;
+    }
+
+    let installation = tokio::task::spawn_blocking(move || install_service(&spec, start)).await??;
+    println!(
+        "Installed {} service: {}",
+        installation.manager.as_str(),
+        installation.path.display()
+    );
+    if installation.started {
+        println!("Daemon started; it will start at login and restart after a crash");
+        if installation.manager == ServiceManager::Systemd {
+            println!("  Hint: run `loginctl enable-linger` to keep it running after logout");
+        }
+    } else {
+        println!("Service not started (run `daemon install` without --no-start to enable it)");
+    }
+    Ok(())
+}
+
+async fn uninstall() -> Result<()> {
+    match tokio::task::spawn_blocking(uninstall_service).await?? {
+        Some(path) => println!("Removed daemon service: {}", path.display()),
+        None => println!("Daemon service not installed"),
+    }
+    Ok(())
+}
+
 async fn stop() -> Result<()> {
     if stop_daemon_effective().await? {
         println!("Sent stop signal to daemon");
@@ -472,6 +584,7 @@ async fn status() -> Result<()> {
                     println!("Daemon running (PID: unknown)");
                 }
             };
+            print_components().await;
         }
         EffectiveDaemonStatus::NotRunning => {
             println!("Daemon not running");
@@ -497,6 +610,34 @@ async fn status() -> Result<()> {
     Ok(())
 }
 
+/// Print the subsystems reported by a running daemon. Best effort: older
+/// daemons and unreachable sockets print nothing.
+async fn print_components() {
+    let Ok(socket_path) = paths::socket_path() else {
+        return;
+    };
+    let Ok(mut client) = IpcClient::connect(&socket_path).await else {
+        return;
+    };
+    let Ok(daemon_status) = client.get_status().await else {
+        return;
+    };
+    if daemon_status.status != "running" {
+        println!("  Status: {}", daemon_status.status);
+    }
+    for component in daemon_status.components {
+        let state = match component.state {
+            DaemonComponentState::Running => "running",
+            DaemonComponentState::Disabled => "disabled",
+            DaemonComponentState::Failed => "failed",
+        };
+        match component.detail {
+            Some(detail) => println!("  {}: {} ({})", component.name, state, detail),
+            None => println!("  {}: {}", component.name, state),
+        }
+    }
+}
+
 async fn wait_for_daemon_exit() -> Result<()> {
     let deadline = tokio::time::Instant::now() + DAEMON_STOP_TIMEOUT;
     loop {
@@ -819,6 +960,15 @@ mod tests {
         assert!(should_run_without_core(&DaemonCommands::Status));
     }
 
+    #[test]
+    fn no_core_routing_accepts_service_install_and_uninstall() {
+        assert!(should_run_without_core(&DaemonCommands::Install {
+            mcp_port: None,
+            no_start: false,
+        }));
+        assert!(should_run_without_core(&DaemonCommands::Uninstall));
+    }
+
     #[tokio::test]
     async fn startup_check_stops_runner_before_bailing_on_ipc_failure() {
         let stop_calls = Arc::new(AtomicUsize::new(0));
@@ -259,16 +259,22 @@ mod tests {
     use async_trait::async_trait;
     use restflow_contracts::request::TaskFromSessionRequest;
     use restflow_contracts::{
-        CleanupReportResponse, PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse,
-        RouteBindingResponse, SessionSourceMigrationResponse,
+        ArtifactPurgeResponse, CleanupReportResponse, DatabaseBackupResponse,
+        PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse, RouteBindingResponse,
+        SessionSourceMigrationResponse,
     };
     use restflow_core::memory::ExportResult;
     use restflow_core::models::{
-        AgentNode, ChatSession, ChatSessionSummary, Deliverable, ExecutionTimeline, ItemQuery,
-        MemoryChunk, MemorySearchResult, MemoryStats, RunListQuery, RunSummary, Secret,
-        SharedEntry, Skill, Task, TaskControlAction, TaskConversionResult, TaskPatch, TaskProgress,
-        TaskSpec, WorkItem, WorkItemPatch, WorkItemSpec,
+        AgentNode, AgentTemplate, ApiConnector, BrowserPlan, ChatSearchQuery, ChatSearchResult,
+        ChatSession, ChatSessionSummary, Deliverable, ExecutionTimeline, ItemQuery, MemoryChunk,
+        MemorySearchResult, MemoryStats, RunListQuery, RunSummary, SavedBrowserPlan, Secret,
+        SecretAccess, SharedEntry, Skill, TagCount, Task, TaskControlAction, TaskConversionResult,
+        TaskPatch, TaskProgress, TaskSpec, WorkItem, WorkItemPatch, WorkItemSpec,
     };
+    use restflow_core::services::agent_template::ProvisionedAgentTemplate;
+    use restflow_core::services::browser_webhook::BrowserWebhookOutcome;
+    use restflow_core::services::data_bundle::{BundleImportReport, DataBundle};
+    use restflow_core::services::project_config::{ProjectApplyReport, ProjectConfig};
     use restflow_core::storage::SystemConfig;
     use restflow_core::storage::agent::StoredAgent;
     use std::sync::Mutex;
@@ -341,6 +347,18 @@ mod tests {
             panic!("unexpected executor call")
         }
 
+        async fn list_agent_templates(&self) -> anyhow::Result<Vec<AgentTemplate>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn provision_agent_template(
+            &self,
+            _template_id: &str,
+            _name: Option<String>,
+        ) -> anyhow::Result<ProvisionedAgentTemplate> {
+            panic!("unexpected executor call")
+        }
+
         async fn list_skills(&self) -> anyhow::Result<Vec<Skill>> {
             panic!("unexpected executor call")
         }
@@ -399,6 +417,30 @@ mod tests {
             panic!("unexpected executor call")
         }
 
+        async fn tag_memory(
+            &self,
+            _chunk_ids: Vec<String>,
+            _add_tags: Vec<String>,
+            _remove_tags: Vec<String>,
+        ) -> anyhow::Result<u32> {
+            panic!("unexpected executor call")
+        }
+
+        async fn merge_memory_tags(
+            &self,
+            _sources: Vec<String>,
+            _target: String,
+        ) -> anyhow::Result<u32> {
+            panic!("unexpected executor call")
+        }
+
+        async fn list_memory_tags(
+            &self,
+            _agent_id: Option<String>,
+        ) -> anyhow::Result<Vec<TagCount>> {
+            panic!("unexpected executor call")
+        }
+
         async fn list_sessions(&self) -> anyhow::Result<Vec<ChatSessionSummary>> {
             panic!("unexpected executor call")
         }
@@ -419,7 +461,77 @@ mod tests {
             panic!("unexpected executor call")
         }
 
-        async fn search_sessions(&self, _query: String) -> anyhow::Result<Vec<ChatSessionSummary>> {
+        async fn search_chat_sessions(
+            &self,
+            _query: ChatSearchQuery,
+        ) -> anyhow::Result<ChatSearchResult> {
+            panic!("unexpected executor call")
+        }
+
+        async fn get_usage_stats(
+            &self,
+            _query: restflow_core::models::UsageQuery,
+        ) -> anyhow::Result<restflow_core::models::UsageStats> {
+            panic!("unexpected executor call")
+        }
+
+        async fn query_execution_traces(
+            &self,
+            _query: restflow_core::models::ExecutionTraceQuery,
+        ) -> anyhow::Result<Vec<restflow_core::models::ExecutionTraceEvent>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn query_execution_trace_stats(
+            &self,
+            _query: restflow_core::models::ExecutionTraceQuery,
+        ) -> anyhow::Result<restflow_core::models::ExecutionTraceStats> {
+            panic!("unexpected executor call")
+        }
+
+        async fn get_execution_replay(
+            &self,
+            _id: &str,
+        ) -> anyhow::Result<Option<restflow_core::models::ExecutionReplay>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn replay_execution(
+            &self,
+            _execution_id: &str,
+            _step: u32,
+            _agent_id: Option<String>,
+            _message: Option<String>,
+        ) -> anyhow::Result<restflow_core::models::ReplayRunResult> {
+            panic!("unexpected executor call")
+        }
+
+        async fn list_tool_approvals(
+            &self,
+            _pending_only: bool,
+        ) -> anyhow::Result<Vec<restflow_core::models::PendingApproval>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn resolve_tool_approval(
+            &self,
+            _id: &str,
+            _approved: bool,
+            _reason: Option<String>,
+        ) -> anyhow::Result<restflow_core::models::PendingApproval> {
+            panic!("unexpected executor call")
+        }
+
+        async fn get_tool_approval_policy(
+            &self,
+        ) -> anyhow::Result<restflow_core::models::ToolApprovalPolicy> {
+            panic!("unexpected executor call")
+        }
+
+        async fn set_tool_approval_policy(
+            &self,
+            _policy: restflow_core::models::ToolApprovalPolicy,
+        ) -> anyhow::Result<()> {
             panic!("unexpected executor call")
         }
 
@@ -486,6 +598,31 @@ mod tests {
             panic!("unexpected executor call")
         }
 
+        async fn rotate_secret(
+            &self,
+            _key: &str,
+            _value: &str,
+            _expires_at: Option<i64>,
+        ) -> anyhow::Result<()> {
+            panic!("unexpected executor call")
+        }
+
+        async fn set_secret_expiry(
+            &self,
+            _key: &str,
+            _expires_at: Option<i64>,
+        ) -> anyhow::Result<()> {
+            panic!("unexpected executor call")
+        }
+
+        async fn list_expiring_secrets(&self, _before: i64) -> anyhow::Result<Vec<Secret>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn get_secret_usage(&self, _key: &str) -> anyhow::Result<Vec<SecretAccess>> {
+            panic!("unexpected executor call")
+        }
+
         async fn get_config(&self) -> anyhow::Result<SystemConfig> {
             panic!("unexpected executor call")
         }
@@ -564,6 +701,41 @@ mod tests {
             panic!("unexpected executor call")
         }
 
+        async fn list_api_tokens(
+            &self,
+            _include_revoked: bool,
+        ) -> anyhow::Result<Vec<restflow_contracts::ApiTokenResponse>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn create_api_token(
+            &self,
+            _name: String,
+            _scope: restflow_contracts::request::ApiTokenScope,
+            _expires_at: Option<i64>,
+        ) -> anyhow::Result<restflow_contracts::IssuedApiTokenResponse> {
+            panic!("unexpected executor call")
+        }
+
+        async fn revoke_api_token(&self, _id: &str) -> anyhow::Result<()> {
+            panic!("unexpected executor call")
+        }
+
+        async fn list_users(&self) -> anyhow::Result<Vec<restflow_contracts::UserResponse>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn create_user(
+            &self,
+            _name: &str,
+        ) -> anyhow::Result<restflow_contracts::UserResponse> {
+            panic!("unexpected executor call")
+        }
+
+        async fn delete_user(&self, _id: &str) -> anyhow::Result<()> {
+            panic!("unexpected executor call")
+        }
+
         async fn run_cleanup(&self) -> anyhow::Result<CleanupReportResponse> {
             panic!("unexpected executor call")
         }
@@ -575,6 +747,42 @@ mod tests {
             panic!("unexpected executor call")
         }
 
+        async fn purge_artifacts(&self, _dry_run: bool) -> anyhow::Result<ArtifactPurgeResponse> {
+            panic!("unexpected executor call")
+        }
+
+        async fn backup_database(&self, _path: String) -> anyhow::Result<DatabaseBackupResponse> {
+            panic!("unexpected executor call")
+        }
+
+        async fn restore_database(&self, _path: String) -> anyhow::Result<DatabaseBackupResponse> {
+            panic!("unexpected executor call")
+        }
+
+        async fn export_bundle(
+            &self,
+            _secrets_password: Option<String>,
+        ) -> anyhow::Result<DataBundle> {
+            panic!("unexpected executor call")
+        }
+
+        async fn import_bundle(
+            &self,
+            _bundle: DataBundle,
+            _secrets_password: Option<String>,
+            _overwrite: bool,
+        ) -> anyhow::Result<BundleImportReport> {
+            panic!("unexpected executor call")
+        }
+
+        async fn apply_project_config(
+            &self,
+            _config: ProjectConfig,
+            _dry_run: bool,
+        ) -> anyhow::Result<ProjectApplyReport> {
+            panic!("unexpected executor call")
+        }
+
         async fn list_tasks(&self, _status: Option<String>) -> anyhow::Result<Vec<Task>> {
             panic!("unexpected executor call")
         }
@@ -613,6 +821,23 @@ mod tests {
             panic!("unexpected executor call")
         }
 
+        async fn run_task_with_secrets(
+            &self,
+            _id: &str,
+            _secrets: std::collections::HashMap<String, String>,
+        ) -> anyhow::Result<Task> {
+            panic!("unexpected executor call")
+        }
+
+        async fn set_task_wake_condition(
+            &self,
+            _id: &str,
+            _condition: Option<restflow_core::models::WakeCondition>,
+            _checkpoint_id: Option<String>,
+        ) -> anyhow::Result<Task> {
+            panic!("unexpected executor call")
+        }
+
         async fn get_task_progress(
             &self,
             _id: &str,
@@ -674,6 +899,69 @@ mod tests {
         async fn list_deliverables(&self, _task_id: &str) -> anyhow::Result<Vec<Deliverable>> {
             panic!("unexpected executor call")
         }
+
+        async fn list_browser_plans(&self) -> anyhow::Result<Vec<SavedBrowserPlan>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn get_browser_plan(
+            &self,
+            _name: &str,
+            _version: Option<u32>,
+        ) -> anyhow::Result<Option<SavedBrowserPlan>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn list_browser_plan_versions(
+            &self,
+            _name: &str,
+        ) -> anyhow::Result<Vec<SavedBrowserPlan>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn save_browser_plan(
+            &self,
+            _name: &str,
+            _description: Option<String>,
+            _plan: BrowserPlan,
+        ) -> anyhow::Result<SavedBrowserPlan> {
+            panic!("unexpected executor call")
+        }
+
+        async fn delete_browser_plan(&self, _name: &str) -> anyhow::Result<bool> {
+            panic!("unexpected executor call")
+        }
+
+        async fn run_browser_plan(
+            &self,
+            _name: &str,
+            _version: Option<u32>,
+            _payload: Option<serde_json::Value>,
+        ) -> anyhow::Result<BrowserWebhookOutcome> {
+            panic!("unexpected executor call")
+        }
+
+        async fn list_api_connectors(&self) -> anyhow::Result<Vec<ApiConnector>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn get_api_connector(&self, _name: &str) -> anyhow::Result<Option<ApiConnector>> {
+            panic!("unexpected executor call")
+        }
+
+        async fn import_api_connector(
+            &self,
+            _name: &str,
+            _spec: String,
+            _base_url: Option<String>,
+            _auth_secret: Option<String>,
+        ) -> anyhow::Result<ApiConnector> {
+            panic!("unexpected executor call")
+        }
+
+        async fn delete_api_connector(&self, _name: &str) -> anyhow::Result<bool> {
+            panic!("unexpected executor call")
+        }
     }
 
     #[test]
@@ -295,9 +295,11 @@ fn parse_provider(value: &str) -> Result<AuthProvider> {
         "claude-code" | "claudecode" | "cc" => Ok(AuthProvider::ClaudeCode),
         "openai" | "gpt" => Ok(AuthProvider::OpenAI),
         "openai-codex" | "openai_codex" | "codex" => Ok(AuthProvider::OpenAICodex),
+        "google" | "gemini" => Ok(AuthProvider::Google),
+        "bedrock" | "aws-bedrock" => Ok(AuthProvider::Bedrock),
         "deepseek" => Ok(AuthProvider::Other),
         _ => bail!(
-            "Unknown provider: {value}. Use: anthropic, claude-code, openai, openai-codex, deepseek"
+            "Unknown provider: {value}. Use: anthropic, claude-code, openai, openai-codex, google, bedrock, deepseek"
         ),
     }
 }
@@ -1,8 +1,13 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use restflow_core::archive::{
+    ENCRYPTED_ARCHIVE_EXTENSION, decrypt_archive, encrypt_archive, is_encrypted_archive,
+};
+use restflow_core::services::data_bundle::DataBundle;
 use serde_json::json;
 use std::sync::Arc;
 
 use crate::cli::MaintenanceCommands;
+use crate::commands::utils::read_archive_password;
 use crate::executor::CommandExecutor;
 use crate::output::{OutputFormat, json::print_json};
 
@@ -16,6 +21,19 @@ pub async fn run(
         MaintenanceCommands::MigrateSessionSources { dry_run } => {
             run_migrate_session_sources(executor, format, dry_run).await
         }
+        MaintenanceCommands::PurgeArtifacts { dry_run } => {
+            run_purge_artifacts(executor, format, dry_run).await
+        }
+        MaintenanceCommands::Backup { path } => run_backup(executor, format, path).await,
+        MaintenanceCommands::Restore { path } => run_restore(executor, format, path).await,
+        MaintenanceCommands::Export {
+            output,
+            include_secrets,
+            encrypt,
+        } => run_export(executor, format, output, include_secrets, encrypt).await,
+        MaintenanceCommands::Import { path, overwrite } => {
+            run_import(executor, format, path, overwrite).await
+        }
     }
 }
 
@@ -30,7 +48,9 @@ async fn run_cleanup(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
             "memory_chunks": report.memory_chunks,
             "memory_sessions": report.memory_sessions,
             "vector_orphans": report.vector_orphans,
-            "daemon_log_files": report.daemon_log_files
+            "daemon_log_files": report.daemon_log_files,
+            "artifacts": report.artifacts,
+            "artifact_bytes_reclaimed": report.artifact_bytes_reclaimed
         }));
     }
 
@@ -42,6 +62,11 @@ async fn run_cleanup(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -
     println!("  memory_sessions: {}", report.memory_sessions);
     println!("  vector_orphans: {}", report.vector_orphans);
     println!("  daemon_log_files: {}", report.daemon_log_files);
+    println!(
+        "  artifacts: {} ({} reclaimed)",
+        report.artifacts,
+        format_bytes(report.artifact_bytes_reclaimed)
+    );
     Ok(())
 }
 
@@ -73,3 +98,199 @@ async fn run_migrate_session_sources(
     println!("  failed: {}", stats.failed);
     Ok(())
 }
+
+async fn run_purge_artifacts(
+    executor: Arc<dyn CommandExecutor>,
+    format: OutputFormat,
+    dry_run: bool,
+) -> Result<()> {
+    let report = executor.purge_artifacts(dry_run).await?;
+
+    if format.is_json() {
+        return print_json(&report);
+    }
+
+    if dry_run {
+        println!("Artifact purge dry run:");
+    } else {
+        println!("Artifact purge completed:");
+    }
+    for removal in &report.removed {
+        println!(
+            "  {} [{}] {} ({})",
+            removal.kind,
+            removal.reason,
+            removal.path,
+            format_bytes(removal.size_bytes)
+        );
+    }
+    println!("  scanned: {}", report.scanned);
+    println!("  removed: {}", report.removed.len());
+    println!("  preserved: {}", report.preserved);
+    println!("  reclaimed: {}", format_bytes(report.bytes_reclaimed));
+    println!("  remaining: {}", format_bytes(report.bytes_remaining));
+    Ok(())
+}
+
+async fn run_backup(
+    executor: Arc<dyn CommandExecutor>,
+    format: OutputFormat,
+    path: String,
+) -> Result<()> {
+    let backup = executor.backup_database(absolute_path(&path)?).await?;
+
+    if format.is_json() {
+        return print_json(&backup);
+    }
+
+    println!("Backup written to {}", backup.path);
+    println!("  tables: {}", backup.tables);
+    println!("  entries: {}", backup.entries);
+    Ok(())
+}
+
+async fn run_restore(
+    executor: Arc<dyn CommandExecutor>,
+    format: OutputFormat,
+    path: String,
+) -> Result<()> {
+    let restored = executor.restore_database(absolute_path(&path)?).await?;
+
+    if format.is_json() {
+        return print_json(&restored);
+    }
+
+    println!("Restored from {}", restored.path);
+    println!("  tables: {}", restored.tables);
+    println!("  entries: {}", restored.entries);
+    println!("Restart the daemon to rebuild the memory vector index.");
+    Ok(())
+}
+
+async fn run_export(
+    executor: Arc<dyn CommandExecutor>,
+    format: OutputFormat,
+    output: Option<String>,
+    include_secrets: bool,
+    encrypt: bool,
+) -> Result<()> {
+    // One password covers both the secrets section and the bundle file.
+    let password = if include_secrets || encrypt {
+        Some(read_archive_password(true)?)
+    } else {
+        None
+    };
+    let secrets_password = password.clone().filter(|_| include_secrets);
+    let bundle = executor.export_bundle(secrets_password).await?;
+
+    let output_path = output.unwrap_or_else(|| {
+        let name = format!(
+            "restflow-bundle-{}.json",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        );
+        if encrypt {
+            format!("{name}.{ENCRYPTED_ARCHIVE_EXTENSION}")
+        } else {
+            name
+        }
+    });
+    let contents = serde_json::to_vec_pretty(&bundle)?;
+    match password.as_deref().filter(|_| encrypt) {
+        Some(password) => std::fs::write(&output_path, encrypt_archive(&contents, password)?)?,
+        None => std::fs::write(&output_path, contents)?,
+    }
+
+    if format.is_json() {
+        return print_json(&json!({
+            "output": output_path,
+            "agents": bundle.agents.len(),
+            "skills": bundle.skills.len(),
+            "triggers": bundle.triggers.len(),
+            "chat_sessions": bundle.chat_sessions.len(),
+            "memory_sessions": bundle.memory_sessions.len(),
+            "memory_chunks": bundle.memory_chunks.len(),
+            "secrets": bundle.has_secrets(),
+            "auth_profiles": bundle.auth_profiles.is_some(),
+            "encrypted": encrypt
+        }));
+    }
+
+    println!("Exported to: {}", output_path);
+    println!("  agents: {}", bundle.agents.len());
+    println!("  skills: {}", bundle.skills.len());
+    println!("  triggers: {}", bundle.triggers.len());
+    println!("  chat_sessions: {}", bundle.chat_sessions.len());
+    println!("  memory_sessions: {}", bundle.memory_sessions.len());
+    println!("  memory_chunks: {}", bundle.memory_chunks.len());
+    if bundle.has_secrets() {
+        println!("  secrets: included (encrypted)");
+    }
+    if bundle.auth_profiles.is_some() {
+        println!("  auth_profiles: included (encrypted)");
+    }
+    if encrypt {
+        println!("  bundle: encrypted");
+    }
+    Ok(())
+}
+
+async fn run_import(
+    executor: Arc<dyn CommandExecutor>,
+    format: OutputFormat,
+    path: String,
+    overwrite: bool,
+) -> Result<()> {
+    let mut data = std::fs::read(&path)?;
+    let mut password = None;
+    if is_encrypted_archive(&data) {
+        let entered = read_archive_password(false)?;
+        data = decrypt_archive(&data, &entered)?;
+        password = Some(entered);
+    }
+    let bundle: DataBundle = serde_json::from_slice(&data)
+        .with_context(|| format!("{path} is not a RestFlow data bundle"))?;
+    // Bundles are exported with a single password, so reuse it if the file
+    // itself was encrypted.
+    let password = match password {
+        Some(password) if bundle.needs_password() => Some(password),
+        None if bundle.needs_password() => Some(read_archive_password(false)?),
+        _ => None,
+    };
+    let report = executor.import_bundle(bundle, password, overwrite).await?;
+
+    if format.is_json() {
+        return print_json(&report);
+    }
+
+    println!("Imported from: {}", path);
+    println!("  agents: {}", report.agents);
+    println!("  skills: {}", report.skills);
+    println!("  triggers: {}", report.triggers);
+    println!("  chat_sessions: {}", report.chat_sessions);
+    println!("  memory_sessions: {}", report.memory_sessions);
+    println!("  memory_chunks: {}", report.memory_chunks);
+    println!("  secrets: {}", report.secrets);
+    println!("  auth_profiles: {}", report.auth_profiles);
+    println!("  skipped (already present): {}", report.skipped);
+    Ok(())
+}
+
+/// The daemon resolves paths against its own working directory.
+fn absolute_path(path: &str) -> Result<String> {
+    Ok(std::path::absolute(path)?.to_string_lossy().to_string())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
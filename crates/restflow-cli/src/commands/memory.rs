@@ -2,10 +2,11 @@ use anyhow::{Result, bail};
 use comfy_table::{Cell, Table};
 use std::sync::Arc;
 
-use crate::cli::MemoryCommands;
-use crate::commands::utils::{format_timestamp, preview_text};
+use crate::cli::{MemoryCommands, MemoryTagCommands};
+use crate::commands::utils::{format_timestamp, preview_text, write_export};
 use crate::executor::CommandExecutor;
 use crate::output::{OutputFormat, json::print_json};
+use restflow_core::archive::ENCRYPTED_ARCHIVE_EXTENSION;
 use restflow_core::models::memory::MemoryChunk;
 use serde_json::json;
 
@@ -17,9 +18,11 @@ pub async fn run(
     match command {
         MemoryCommands::Search { query } => search_memory(executor, &query, format).await,
         MemoryCommands::List { agent, tag } => list_memory(executor, agent, tag, format).await,
-        MemoryCommands::Export { agent, output } => {
-            export_memory(executor, agent, output, format).await
-        }
+        MemoryCommands::Export {
+            agent,
+            output,
+            encrypt,
+        } => export_memory(executor, agent, output, encrypt, format).await,
         MemoryCommands::Stats => memory_stats(executor, format).await,
         MemoryCommands::Clear { agent } => clear_memory(executor, agent, format).await,
         MemoryCommands::Store {
@@ -27,9 +30,103 @@ pub async fn run(
             agent,
             tags,
         } => store_memory(executor, content, agent, tags, format).await,
+        MemoryCommands::Tag { command } => run_tag_command(executor, command, format).await,
     }
 }
 
+async fn run_tag_command(
+    executor: Arc<dyn CommandExecutor>,
+    command: MemoryTagCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        MemoryTagCommands::List { agent } => {
+            let tags = executor.list_memory_tags(agent).await?;
+            if format.is_json() {
+                return print_json(&tags);
+            }
+
+            let mut table = Table::new();
+            table.set_header(vec!["Tag", "Chunks"]);
+            for tag in tags {
+                table.add_row(vec![Cell::new(tag.tag), Cell::new(tag.count)]);
+            }
+            crate::output::table::print_table(table)
+        }
+        MemoryTagCommands::Add { chunk_ids, tags } => {
+            let tags = parse_tag_list(&tags)?;
+            let updated = executor
+                .tag_memory(chunk_ids, tags.clone(), Vec::new())
+                .await?;
+            print_tag_update(format, "add", &tags, updated)
+        }
+        MemoryTagCommands::Remove { chunk_ids, tags } => {
+            let tags = parse_tag_list(&tags)?;
+            let updated = executor
+                .tag_memory(chunk_ids, Vec::new(), tags.clone())
+                .await?;
+            print_tag_update(format, "remove", &tags, updated)
+        }
+        MemoryTagCommands::Rename { from, to } => {
+            let updated = executor
+                .merge_memory_tags(vec![from.clone()], to.clone())
+                .await?;
+            if format.is_json() {
+                return print_json(&json!({ "from": from, "to": to, "updated": updated }));
+            }
+            println!("Renamed '{}' to '{}' on {} chunks", from, to, updated);
+            Ok(())
+        }
+        MemoryTagCommands::Merge { sources, into } => {
+            let updated = executor
+                .merge_memory_tags(sources.clone(), into.clone())
+                .await?;
+            if format.is_json() {
+                return print_json(
+                    &json!({ "sources": sources, "into": into, "updated": updated }),
+                );
+            }
+            println!(
+                "Merged {} into '{}' on {} chunks",
+                sources.join(", "),
+                into,
+                updated
+            );
+            Ok(())
+        }
+    }
+}
+
+fn parse_tag_list(tags: &str) -> Result<Vec<String>> {
+    let tags: Vec<String> = tags
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    if tags.is_empty() {
+        bail!("--tags must name at least one tag");
+    }
+    Ok(tags)
+}
+
+fn print_tag_update(
+    format: OutputFormat,
+    action: &str,
+    tags: &[String],
+    updated: u32,
+) -> Result<()> {
+    if format.is_json() {
+        return print_json(&json!({ "action": action, "tags": tags, "updated": updated }));
+    }
+    println!(
+        "Updated {} chunks ({} {})",
+        updated,
+        action,
+        tags.join(", ")
+    );
+    Ok(())
+}
+
 async fn search_memory(
     executor: Arc<dyn CommandExecutor>,
     query: &str,
@@ -69,19 +166,30 @@ async fn export_memory(
     executor: Arc<dyn CommandExecutor>,
     agent: Option<String>,
     output: Option<String>,
+    encrypt: bool,
     format: OutputFormat,
 ) -> Result<()> {
     let result = executor.export_memory(agent.clone()).await?;
 
-    let output_path = output.unwrap_or_else(|| result.suggested_filename.clone());
-    std::fs::write(&output_path, &result.markdown)?;
+    let output_path = output.unwrap_or_else(|| {
+        if encrypt {
+            format!(
+                "{}.{}",
+                result.suggested_filename, ENCRYPTED_ARCHIVE_EXTENSION
+            )
+        } else {
+            result.suggested_filename.clone()
+        }
+    });
+    write_export(&output_path, result.markdown.as_bytes(), encrypt)?;
 
     if format.is_json() {
         return print_json(&json!({
             "agent_id": result.agent_id,
             "output": output_path,
             "chunk_count": result.chunk_count,
-            "session_count": result.session_count
+            "session_count": result.session_count,
+            "encrypted": encrypt
         }));
     }
 
@@ -112,12 +220,25 @@ async fn memory_stats(executor: Arc<dyn CommandExecutor>, format: OutputFormat)
         println!("  Tokens:   {}", stat.total_tokens);
         println!("  Oldest:   {}", format_timestamp(stat.oldest_memory));
         println!("  Newest:   {}", format_timestamp(stat.newest_memory));
+        println!("  Limit:    {}", format_limit(stat.max_chunks, "chunks"));
+        println!(
+            "  Expiry:   {}",
+            format_limit(stat.session_retention_days, "days for session chunks")
+        );
         println!();
     }
 
     Ok(())
 }
 
+fn format_limit(value: u32, unit: &str) -> String {
+    if value == 0 {
+        "none".to_string()
+    } else {
+        format!("{value} {unit}")
+    }
+}
+
 async fn clear_memory(
     executor: Arc<dyn CommandExecutor>,
     agent: Option<String>,
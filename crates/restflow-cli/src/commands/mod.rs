@@ -1,9 +1,12 @@
 pub mod agent;
+pub mod audit;
 pub mod auth;
 pub mod background_agent;
+pub mod browser_plan;
 pub mod claude_mcp;
 pub mod codex_mcp;
 pub mod config;
+pub mod connector;
 pub mod daemon;
 pub mod daemon_state;
 pub mod deliverable;
@@ -13,8 +16,11 @@ pub mod key;
 pub mod maintenance;
 pub mod mcp;
 pub mod memory;
+pub mod model;
 pub mod note;
 pub mod pairing;
+pub mod project;
+pub mod replay;
 pub mod restart;
 pub mod secret;
 pub mod security;
@@ -26,6 +32,9 @@ pub mod status;
 pub mod stop;
 pub mod task;
 pub mod team;
+pub mod token;
 pub mod trigger;
 pub mod upgrade;
+pub mod usage;
+pub mod user;
 pub mod utils;
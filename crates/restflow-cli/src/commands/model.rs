@@ -0,0 +1,64 @@
+use anyhow::{Result, bail};
+use comfy_table::{Cell, Table};
+use restflow_ai::llm::{LocalBackend, LocalModel, OllamaClient, list_openai_compatible_models};
+
+use crate::cli::ModelCommands;
+use crate::output::{OutputFormat, json::print_json, table::print_table};
+
+pub async fn run(command: ModelCommands, format: OutputFormat) -> Result<()> {
+    match command {
+        ModelCommands::Local { endpoint } => list_local_models(endpoint, format).await,
+    }
+}
+
+async fn list_local_models(endpoint: Option<String>, format: OutputFormat) -> Result<()> {
+    let mut models = Vec::new();
+    let mut errors = Vec::new();
+
+    if let Some(endpoint) = endpoint {
+        models.extend(list_openai_compatible_models(&endpoint).await?);
+    } else {
+        match OllamaClient::new()?.list_models().await {
+            Ok(found) => models.extend(found),
+            Err(error) => errors.push(format!("Ollama: {}", error)),
+        }
+        let base_url = LocalBackend::OpenAICompatible.base_url();
+        match list_openai_compatible_models(&base_url).await {
+            Ok(found) => models.extend(found),
+            Err(error) => errors.push(format!("{}: {}", base_url, error)),
+        }
+        if models.is_empty() && !errors.is_empty() {
+            bail!("No local model server reachable ({})", errors.join("; "));
+        }
+    }
+
+    if format.is_json() {
+        return print_json(&models);
+    }
+
+    for error in &errors {
+        eprintln!("Skipped {}", error);
+    }
+    if models.is_empty() {
+        println!("No local models found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Reference", "Name", "Size"]);
+    for model in &models {
+        table.add_row(vec![
+            Cell::new(&model.reference),
+            Cell::new(&model.name),
+            Cell::new(format_size(model)),
+        ]);
+    }
+    print_table(table)
+}
+
+fn format_size(model: &LocalModel) -> String {
+    match model.size_bytes {
+        Some(bytes) => format!("{:.1} GB", bytes as f64 / 1_000_000_000.0),
+        None => "-".to_string(),
+    }
+}
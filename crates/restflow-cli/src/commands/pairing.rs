@@ -36,11 +36,14 @@ pub async fn run_route(
         RouteCommands::List => list_routes(executor, format).await,
         RouteCommands::Bind {
             peer,
+            account,
+            channel,
             group,
             default,
             agent,
         } => {
-            let (binding_type, target_id) = route_binding_input(peer, group, default)?;
+            let (binding_type, target_id) =
+                route_binding_input(peer, account, channel, group, default)?;
             bind_route(executor, binding_type, &target_id, &agent, format).await
         }
         RouteCommands::Unbind { id } => unbind_route(executor, &id, format).await,
@@ -281,6 +284,8 @@ async fn unbind_route(
 
 fn route_binding_input(
     peer: Option<String>,
+    account: Option<String>,
+    channel: Option<String>,
     group: Option<String>,
     default: bool,
 ) -> Result<(&'static str, String)> {
@@ -288,6 +293,23 @@ fn route_binding_input(
         return Ok(("peer", peer_id));
     }
 
+    if let Some(account_id) = account {
+        return Ok(("account", account_id));
+    }
+
+    if let Some(channel) = channel {
+        let channel = channel.trim().to_ascii_lowercase();
+        if !matches!(
+            channel.as_str(),
+            "telegram" | "discord" | "slack" | "matrix"
+        ) {
+            return Err(anyhow!(
+                "Unsupported channel '{channel}'. Use telegram, discord, slack, or matrix"
+            ));
+        }
+        return Ok(("channel", channel));
+    }
+
     if let Some(group_id) = group {
         return Ok(("group", group_id));
     }
@@ -296,7 +318,9 @@ fn route_binding_input(
         return Ok(("default", "*".to_string()));
     }
 
-    Err(anyhow!("Must specify --peer, --group, or --default"))
+    Err(anyhow!(
+        "Must specify --peer, --account, --channel, --group, or --default"
+    ))
 }
 
 fn short_id(value: &str) -> &str {
@@ -310,7 +334,8 @@ mod tests {
     #[test]
     fn route_binding_input_prefers_peer() {
         let (binding_type, target_id) =
-            route_binding_input(Some("peer-1".to_string()), None, false).expect("peer input");
+            route_binding_input(Some("peer-1".to_string()), None, None, None, false)
+                .expect("peer input");
         assert_eq!(binding_type, "peer");
         assert_eq!(target_id, "peer-1");
     }
@@ -318,15 +343,33 @@ mod tests {
     #[test]
     fn route_binding_input_preserves_group_legacy_type() {
         let (binding_type, target_id) =
-            route_binding_input(None, Some("chat-1".to_string()), false).expect("group input");
+            route_binding_input(None, None, None, Some("chat-1".to_string()), false)
+                .expect("group input");
         assert_eq!(binding_type, "group");
         assert_eq!(target_id, "chat-1");
     }
 
+    #[test]
+    fn route_binding_input_supports_channel_and_account() {
+        let (binding_type, target_id) =
+            route_binding_input(None, None, Some("Slack".to_string()), None, false)
+                .expect("channel input");
+        assert_eq!(binding_type, "channel");
+        assert_eq!(target_id, "slack");
+
+        let (binding_type, target_id) =
+            route_binding_input(None, Some("T123".to_string()), None, None, false)
+                .expect("account input");
+        assert_eq!(binding_type, "account");
+        assert_eq!(target_id, "T123");
+
+        assert!(route_binding_input(None, None, Some("irc".to_string()), None, false).is_err());
+    }
+
     #[test]
     fn route_binding_input_supports_default() {
         let (binding_type, target_id) =
-            route_binding_input(None, None, true).expect("default input");
+            route_binding_input(None, None, None, None, true).expect("default input");
         assert_eq!(binding_type, "default");
         assert_eq!(target_id, "*");
     }
@@ -0,0 +1,71 @@
+use anyhow::{Result, bail};
+use comfy_table::{Cell, Table};
+use restflow_core::services::project_config::{
+    PROJECT_CONFIG_FILE, ProjectAction, ProjectResource, load_project_config,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::cli::ProjectCommands;
+use crate::executor::CommandExecutor;
+use crate::output::{OutputFormat, json::print_json};
+
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: ProjectCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        ProjectCommands::Apply { dir, dry_run } => apply(executor, &dir, dry_run, format).await,
+    }
+}
+
+async fn apply(
+    executor: Arc<dyn CommandExecutor>,
+    dir: &str,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let Some(config) = load_project_config(Path::new(dir))? else {
+        bail!("No {} found in {}", PROJECT_CONFIG_FILE, dir);
+    };
+    let report = executor.apply_project_config(config, dry_run).await?;
+
+    if format.is_json() {
+        return print_json(&report);
+    }
+
+    if report.changes.is_empty() {
+        println!("{} defines nothing to apply", PROJECT_CONFIG_FILE);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Kind", "Name", "Action"]);
+    for change in &report.changes {
+        let kind = match change.resource {
+            ProjectResource::Agent => "agent",
+            ProjectResource::Skill => "skill",
+            ProjectResource::Trigger => "trigger",
+        };
+        let action = match (change.resource, change.action) {
+            (_, ProjectAction::Unchanged) => "unchanged",
+            (_, ProjectAction::Create) => "create",
+            (ProjectResource::Skill, ProjectAction::Update) => "activate",
+            (_, ProjectAction::Update) => "update",
+        };
+        table.add_row(vec![
+            Cell::new(kind),
+            Cell::new(&change.name),
+            Cell::new(action),
+        ]);
+    }
+    crate::output::table::print_table(table)?;
+
+    if report.dry_run {
+        println!("Dry run: {} change(s) planned", report.changed());
+    } else {
+        println!("Applied {} change(s)", report.changed());
+    }
+    Ok(())
+}
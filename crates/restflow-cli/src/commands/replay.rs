@@ -0,0 +1,141 @@
+//! CLI commands for stepping through and re-executing recorded agent runs.
+
+use anyhow::{Result, anyhow};
+use comfy_table::{Cell, Table};
+use restflow_core::models::{ExecutionStep, ReplayStep};
+use std::sync::Arc;
+
+use crate::cli::{OutputFormat, ReplayCommands};
+use crate::commands::utils::{format_timestamp, preview_text};
+use crate::executor::CommandExecutor;
+use crate::output::json::print_json;
+use crate::output::table::print_table;
+
+const CONTENT_PREVIEW_LEN: usize = 80;
+
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: ReplayCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        ReplayCommands::Show { id, step } => show_replay(executor, &id, step, format).await,
+        ReplayCommands::Run {
+            execution_id,
+            from_step,
+            agent,
+            message,
+        } => run_replay(executor, &execution_id, from_step, agent, message, format).await,
+    }
+}
+
+async fn show_replay(
+    executor: Arc<dyn CommandExecutor>,
+    id: &str,
+    step: Option<u32>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut replay = executor
+        .get_execution_replay(id)
+        .await?
+        .ok_or_else(|| anyhow!("No replay recorded for: {}", id))?;
+    if let Some(step) = step {
+        replay.steps.retain(|recorded| recorded.step == step);
+        if replay.steps.is_empty() {
+            return Err(anyhow!(
+                "Execution {} has no step {}",
+                replay.execution_id,
+                step
+            ));
+        }
+    }
+
+    if format.is_json() {
+        return print_json(&replay);
+    }
+
+    println!(
+        "Execution {} (agent: {}, run: {})",
+        replay.execution_id,
+        replay.agent_id.as_deref().unwrap_or("-"),
+        replay.run_id.as_deref().unwrap_or("-")
+    );
+
+    // A single step is shown in full; the overview truncates long outputs.
+    let max_len = if step.is_some() {
+        usize::MAX
+    } else {
+        CONTENT_PREVIEW_LEN
+    };
+    let mut table = Table::new();
+    table.set_header(vec!["Step", "Status", "Time", "Type", "Content"]);
+    for recorded in &replay.steps {
+        add_step_rows(&mut table, recorded, max_len);
+    }
+    print_table(table)
+}
+
+fn add_step_rows(table: &mut Table, recorded: &ReplayStep, max_len: usize) {
+    let step_cells = [
+        recorded.step.to_string(),
+        recorded.status.clone(),
+        format_timestamp(Some(recorded.created_at)),
+    ];
+    if recorded.messages.is_empty() {
+        let mut row: Vec<Cell> = step_cells.iter().map(Cell::new).collect();
+        row.extend([Cell::new("-"), Cell::new("")]);
+        table.add_row(row);
+        return;
+    }
+    for (index, message) in recorded.messages.iter().enumerate() {
+        let mut row: Vec<Cell> = if index == 0 {
+            step_cells.iter().map(Cell::new).collect()
+        } else {
+            vec![Cell::new(""), Cell::new(""), Cell::new("")]
+        };
+        row.push(Cell::new(&message.step_type));
+        row.push(Cell::new(preview_text(&message_content(message), max_len)));
+        table.add_row(row);
+    }
+}
+
+fn message_content(message: &ExecutionStep) -> String {
+    let calls = message
+        .tool_calls
+        .iter()
+        .flatten()
+        .map(|call| format!("{}({})", call.name, call.arguments))
+        .collect::<Vec<_>>();
+    match (message.content.trim().is_empty(), calls.is_empty()) {
+        (_, true) => message.content.clone(),
+        (true, false) => calls.join(", "),
+        (false, false) => format!("{}\n{}", message.content, calls.join(", ")),
+    }
+}
+
+async fn run_replay(
+    executor: Arc<dyn CommandExecutor>,
+    execution_id: &str,
+    from_step: u32,
+    agent: Option<String>,
+    message: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let result = executor
+        .replay_execution(execution_id, from_step, agent, message)
+        .await?;
+
+    if format.is_json() {
+        return print_json(&result);
+    }
+
+    println!(
+        "Replayed {} from step {} with agent {} as execution {}",
+        result.source_execution_id, result.from_step, result.agent_id, result.execution_id
+    );
+    if !result.output.is_empty() {
+        println!();
+        println!("{}", result.output);
+    }
+    Ok(())
+}
@@ -18,6 +18,27 @@ pub async fn run(
         SecretCommands::Set { key, value } => set_secret(executor, &key, &value, format).await,
         SecretCommands::Delete { key } => delete_secret(executor, &key, format).await,
         SecretCommands::Has { key } => has_secret(executor, &key, format).await,
+        SecretCommands::Rotate {
+            key,
+            value,
+            expires_in_days,
+        } => rotate_secret(executor, &key, &value, expires_in_days, format).await,
+        SecretCommands::Expiry { key, days } => set_expiry(executor, &key, days, format).await,
+        SecretCommands::Expiring { days } => list_expiring(executor, days, format).await,
+        SecretCommands::Usage { key } => show_usage(executor, &key, format).await,
+    }
+}
+
+const DAY_MS: i64 = 86_400_000;
+
+fn days_from_now(days: u32) -> i64 {
+    chrono::Utc::now().timestamp_millis() + i64::from(days) * DAY_MS
+}
+
+fn format_expiry(expires_at: Option<i64>, now: i64) -> String {
+    match expires_at {
+        Some(ts) if ts <= now => format!("{} (expired)", format_timestamp(Some(ts))),
+        other => format_timestamp(other),
     }
 }
 
@@ -28,13 +49,16 @@ async fn list_secrets(executor: Arc<dyn CommandExecutor>, format: OutputFormat)
         return print_json(&secrets);
     }
 
+    let now = chrono::Utc::now().timestamp_millis();
     let mut table = Table::new();
-    table.set_header(vec!["Key", "Updated"]);
+    table.set_header(vec!["Key", "Updated", "Rotated", "Expires"]);
 
     for secret in secrets {
         table.add_row(vec![
             Cell::new(secret.key),
             Cell::new(format_timestamp(Some(secret.updated_at))),
+            Cell::new(format_timestamp(secret.rotated_at)),
+            Cell::new(format_expiry(secret.expires_at, now)),
         ]);
     }
 
@@ -90,3 +114,106 @@ async fn has_secret(
     }
     Ok(())
 }
+
+async fn rotate_secret(
+    executor: Arc<dyn CommandExecutor>,
+    key: &str,
+    value: &str,
+    expires_in_days: Option<u32>,
+    format: OutputFormat,
+) -> Result<()> {
+    let expires_at = expires_in_days.map(days_from_now);
+    executor.rotate_secret(key, value, expires_at).await?;
+
+    if format.is_json() {
+        return print_json(&json!({ "rotated": true, "key": key, "expires_at": expires_at }));
+    }
+
+    println!("Secret rotated: {key}");
+    if let Some(ts) = expires_at {
+        println!("Expires: {}", format_timestamp(Some(ts)));
+    }
+    Ok(())
+}
+
+async fn set_expiry(
+    executor: Arc<dyn CommandExecutor>,
+    key: &str,
+    days: Option<u32>,
+    format: OutputFormat,
+) -> Result<()> {
+    let expires_at = days.map(days_from_now);
+    executor.set_secret_expiry(key, expires_at).await?;
+
+    if format.is_json() {
+        return print_json(&json!({ "key": key, "expires_at": expires_at }));
+    }
+
+    match expires_at {
+        Some(ts) => println!("Secret {key} expires {}", format_timestamp(Some(ts))),
+        None => println!("Expiry cleared: {key}"),
+    }
+    Ok(())
+}
+
+async fn list_expiring(
+    executor: Arc<dyn CommandExecutor>,
+    days: u32,
+    format: OutputFormat,
+) -> Result<()> {
+    let secrets = executor.list_expiring_secrets(days_from_now(days)).await?;
+
+    if format.is_json() {
+        return print_json(&secrets);
+    }
+
+    if secrets.is_empty() {
+        println!("No secrets expire within {days} days");
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut table = Table::new();
+    table.set_header(vec!["Key", "Expires", "Rotated"]);
+
+    for secret in secrets {
+        table.add_row(vec![
+            Cell::new(secret.key),
+            Cell::new(format_expiry(secret.expires_at, now)),
+            Cell::new(format_timestamp(secret.rotated_at)),
+        ]);
+    }
+
+    crate::output::table::print_table(table)
+}
+
+async fn show_usage(
+    executor: Arc<dyn CommandExecutor>,
+    key: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let usage = executor.get_secret_usage(key).await?;
+
+    if format.is_json() {
+        return print_json(&usage);
+    }
+
+    if usage.is_empty() {
+        println!("No recorded reads of {key}");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Tool", "Agent", "Reads", "Last used"]);
+
+    for access in usage {
+        table.add_row(vec![
+            Cell::new(access.tool),
+            Cell::new(access.agent_id.unwrap_or_else(|| "-".to_string())),
+            Cell::new(access.count),
+            Cell::new(format_timestamp(Some(access.last_used_at))),
+        ]);
+    }
+
+    crate::output::table::print_table(table)
+}
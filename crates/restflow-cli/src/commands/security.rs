@@ -1,24 +1,31 @@
 use anyhow::{Result, bail};
 use comfy_table::{Cell, Table};
 use serde_json::json;
+use std::sync::Arc;
 
-use crate::cli::{AllowlistAction, SecurityCommands};
-use crate::commands::utils::short_id;
+use crate::cli::{AllowlistAction, SecurityCommands, ToolApprovalAction};
+use crate::commands::utils::{format_timestamp, preview_text, short_id};
+use crate::executor::CommandExecutor;
 use crate::output::{OutputFormat, json::print_json};
 use restflow_core::models::security::{
-    ApprovalStatus, CommandPattern, PendingApproval, SecurityPolicy,
+    ApprovalStatus, CommandPattern, PendingApproval, SecurityPolicy, ToolApprovalPolicy,
 };
 use restflow_core::paths;
 
 const POLICY_FILE: &str = "security_policy.json";
 const APPROVALS_FILE: &str = "security_approvals.json";
 
-pub async fn run(command: SecurityCommands, format: OutputFormat) -> Result<()> {
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: SecurityCommands,
+    format: OutputFormat,
+) -> Result<()> {
     match command {
         SecurityCommands::Approvals => list_pending_approvals(format).await,
         SecurityCommands::Approve { id } => approve_request(&id, format).await,
         SecurityCommands::Reject { id } => reject_request(&id, format).await,
         SecurityCommands::Allowlist { action } => manage_allowlist(action, format).await,
+        SecurityCommands::Tools { action } => manage_tool_approvals(executor, action, format).await,
     }
 }
 
@@ -85,6 +92,160 @@ fn update_approval_status(id: &str, status: ApprovalStatus, format: OutputFormat
     Ok(())
 }
 
+async fn manage_tool_approvals(
+    executor: Arc<dyn CommandExecutor>,
+    action: ToolApprovalAction,
+    format: OutputFormat,
+) -> Result<()> {
+    match action {
+        ToolApprovalAction::List { all } => list_tool_approvals(executor, all, format).await,
+        ToolApprovalAction::Approve { id } => {
+            resolve_tool_approval(executor, &id, true, None, format).await
+        }
+        ToolApprovalAction::Reject { id, reason } => {
+            resolve_tool_approval(executor, &id, false, reason, format).await
+        }
+        ToolApprovalAction::Policy => show_tool_approval_policy(executor, format).await,
+        ToolApprovalAction::SetPolicy { path } => {
+            set_tool_approval_policy(executor, &path, format).await
+        }
+    }
+}
+
+async fn list_tool_approvals(
+    executor: Arc<dyn CommandExecutor>,
+    all: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let approvals = executor.list_tool_approvals(!all).await?;
+
+    if format.is_json() {
+        return print_json(&approvals);
+    }
+
+    if approvals.is_empty() {
+        println!("No pending tool approvals.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Tool", "Arguments", "Run", "Status", "Expires"]);
+
+    for approval in approvals {
+        let arguments = approval
+            .arguments
+            .as_ref()
+            .map(|arguments| preview_text(&arguments.to_string(), 60))
+            .unwrap_or_default();
+        let run = if approval.task_id.is_empty() {
+            approval
+                .session_id
+                .as_deref()
+                .map(|id| format!("session {}", short_id(id)))
+                .unwrap_or_default()
+        } else {
+            format!("task {}", short_id(&approval.task_id))
+        };
+        table.add_row(vec![
+            Cell::new(short_id(&approval.id)),
+            Cell::new(approval.tool_name.unwrap_or_default()),
+            Cell::new(arguments),
+            Cell::new(run),
+            Cell::new(format!("{:?}", approval.status)),
+            Cell::new(format_timestamp(Some(approval.expires_at))),
+        ]);
+    }
+
+    crate::output::table::print_table(table)
+}
+
+async fn resolve_tool_approval(
+    executor: Arc<dyn CommandExecutor>,
+    id: &str,
+    approved: bool,
+    reason: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let approvals = executor.list_tool_approvals(true).await?;
+    let index = resolve_approval_index(&approvals, id)?;
+    let approval = executor
+        .resolve_tool_approval(&approvals[index].id, approved, reason)
+        .await?;
+
+    if format.is_json() {
+        return print_json(&approval);
+    }
+
+    println!(
+        "Updated tool approval {}: {:?}",
+        short_id(&approval.id),
+        approval.status
+    );
+    Ok(())
+}
+
+async fn show_tool_approval_policy(
+    executor: Arc<dyn CommandExecutor>,
+    format: OutputFormat,
+) -> Result<()> {
+    let policy = executor.get_tool_approval_policy().await?;
+
+    if format.is_json() {
+        return print_json(&policy);
+    }
+
+    println!("Default action: {:?}", policy.default_action);
+    println!("Approval timeout: {}s", policy.approval_timeout_secs);
+
+    if policy.rules.is_empty() {
+        println!("No tool rules configured.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Tool",
+        "Operation",
+        "Argument",
+        "Pattern",
+        "Action",
+        "Priority",
+    ]);
+
+    for rule in policy.rules {
+        table.add_row(vec![
+            Cell::new(rule.id),
+            Cell::new(rule.tool_name),
+            Cell::new(rule.operation.unwrap_or_default()),
+            Cell::new(rule.argument.unwrap_or_default()),
+            Cell::new(rule.target_pattern),
+            Cell::new(format!("{:?}", rule.action)),
+            Cell::new(rule.priority),
+        ]);
+    }
+
+    crate::output::table::print_table(table)
+}
+
+async fn set_tool_approval_policy(
+    executor: Arc<dyn CommandExecutor>,
+    path: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let bytes = std::fs::read(path)?;
+    let policy: ToolApprovalPolicy = serde_json::from_slice(&bytes)?;
+    let rules = policy.rules.len();
+    executor.set_tool_approval_policy(policy).await?;
+
+    if format.is_json() {
+        return print_json(&json!({ "ok": true, "rules": rules }));
+    }
+
+    println!("Tool approval policy updated ({rules} rules).");
+    Ok(())
+}
+
 async fn manage_allowlist(action: AllowlistAction, format: OutputFormat) -> Result<()> {
     match action {
         AllowlistAction::Show => show_allowlist(format).await,
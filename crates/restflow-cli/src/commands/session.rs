@@ -1,25 +1,14 @@
 use anyhow::{Result, bail};
 use comfy_table::{Cell, Table};
-use serde::Serialize;
 use serde_json::json;
 use std::sync::Arc;
 
 use crate::cli::SessionCommands;
-use crate::commands::utils::{format_timestamp, preview_text, short_id};
+use crate::commands::utils::{format_timestamp, short_id, write_export};
 use crate::executor::CommandExecutor;
 use crate::output::{OutputFormat, json::print_json};
-use restflow_core::models::chat_session::{ChatRole, ChatSession};
-
-#[derive(Debug, Serialize)]
-struct SessionSearchResult {
-    id: String,
-    name: String,
-    agent_id: String,
-    model: String,
-    updated_at: i64,
-    match_count: usize,
-    preview: Option<String>,
-}
+use restflow_core::archive::ENCRYPTED_ARCHIVE_EXTENSION;
+use restflow_core::models::chat_session::{ChatRole, ChatSearchHighlight, ChatSearchQuery};
 
 pub async fn run(
     executor: Arc<dyn CommandExecutor>,
@@ -33,8 +22,24 @@ pub async fn run(
             create_session(executor, &agent, &model, format).await
         }
         SessionCommands::Delete { id } => delete_session(executor, &id, format).await,
-        SessionCommands::Search { query, agent } => {
-            search_sessions(executor, &query, agent.as_deref(), format).await
+        SessionCommands::Export {
+            id,
+            output,
+            encrypt,
+        } => export_session(executor, &id, output, encrypt, format).await,
+        SessionCommands::Search {
+            query,
+            agent,
+            archived,
+            limit,
+            offset,
+        } => {
+            let mut query = ChatSearchQuery::new(query).paginate(limit, offset);
+            if let Some(agent) = agent {
+                query = query.with_agent(agent);
+            }
+            query.include_archived = archived;
+            search_chat_sessions(executor, query, format).await
         }
     }
 }
@@ -83,13 +88,7 @@ async fn show_session(
     println!();
 
     for msg in &session.messages {
-        let role = match msg.role {
-            ChatRole::User => "User",
-            ChatRole::Assistant => "Assistant",
-            ChatRole::System => "System",
-        };
-
-        println!("{}", role);
+        println!("{}", role_label(&msg.role));
         println!("{}", msg.content);
         println!();
     }
@@ -97,6 +96,41 @@ async fn show_session(
     Ok(())
 }
 
+async fn export_session(
+    executor: Arc<dyn CommandExecutor>,
+    id: &str,
+    output: Option<String>,
+    encrypt: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let resolved_id = resolve_session_id(&executor, id).await?;
+    let session = executor.get_session(&resolved_id).await?;
+
+    let path = output.unwrap_or_else(|| {
+        if encrypt {
+            format!(
+                "session-{}.json.{}",
+                session.id, ENCRYPTED_ARCHIVE_EXTENSION
+            )
+        } else {
+            format!("session-{}.json", session.id)
+        }
+    });
+    write_export(&path, &serde_json::to_vec_pretty(&session)?, encrypt)?;
+
+    if format.is_json() {
+        return print_json(&json!({
+            "id": session.id,
+            "output": path,
+            "messages": session.messages.len(),
+            "encrypted": encrypt
+        }));
+    }
+
+    println!("Exported to: {}", path);
+    Ok(())
+}
+
 async fn create_session(
     executor: Arc<dyn CommandExecutor>,
     agent: &str,
@@ -149,98 +183,79 @@ async fn delete_session(
     Ok(())
 }
 
-async fn search_sessions(
+async fn search_chat_sessions(
     executor: Arc<dyn CommandExecutor>,
-    query: &str,
-    agent: Option<&str>,
+    query: ChatSearchQuery,
     format: OutputFormat,
 ) -> Result<()> {
-    let normalized = query.trim().to_lowercase();
-    if normalized.is_empty() {
+    if query.query.trim().is_empty() {
         bail!("Search query cannot be empty");
     }
 
-    // Get session summaries from executor
-    let summaries = executor.search_sessions(query.to_string()).await?;
-
-    // Filter by agent if specified
-    let summaries: Vec<_> = if let Some(agent_id) = agent {
-        summaries
-            .into_iter()
-            .filter(|s| s.agent_id == agent_id)
-            .collect()
-    } else {
-        summaries
-    };
-
-    // For detailed results with match counts, we need to fetch full sessions
-    let mut results = Vec::new();
-    for summary in summaries {
-        match executor.get_session(&summary.id).await {
-            Ok(session) => {
-                let (match_count, preview) = count_matches(&session, &normalized);
-                if match_count > 0 {
-                    results.push(SessionSearchResult {
-                        id: session.id,
-                        name: session.name,
-                        agent_id: session.agent_id,
-                        model: session.model,
-                        updated_at: session.updated_at,
-                        match_count,
-                        preview,
-                    });
-                }
-            }
-            Err(_) => continue,
-        }
-    }
-
-    results.sort_by(|a, b| b.match_count.cmp(&a.match_count));
+    let result = executor.search_chat_sessions(query.clone()).await?;
 
     if format.is_json() {
-        return print_json(&results);
+        return print_json(&result);
     }
 
-    if results.is_empty() {
-        println!("No sessions matched: {}", query);
+    if result.hits.is_empty() {
+        println!("No messages matched: {}", query.query);
         return Ok(());
     }
 
-    for (index, result) in results.iter().enumerate() {
-        println!("{}. {} ({})", index + 1, result.name, result.id);
-        println!("   Agent: {}", result.agent_id);
-        println!("   Model: {}", result.model);
-        println!("   Matches: {}", result.match_count);
-        println!("   Updated: {}", format_timestamp(Some(result.updated_at)));
-        if let Some(ref preview) = result.preview {
-            println!("   Preview: {}", preview);
-        }
+    for (index, hit) in result.hits.iter().enumerate() {
+        println!(
+            "{}. {} ({})",
+            query.offset as usize + index + 1,
+            hit.session_name,
+            short_id(&hit.session_id)
+        );
+        println!(
+            "   {} · {} · {}",
+            hit.agent_id,
+            role_label(&hit.role),
+            format_timestamp(Some(hit.timestamp))
+        );
+        println!("   {}", emphasize(&hit.snippet, &hit.highlights));
         println!();
     }
 
+    let shown = query.offset as usize + result.hits.len();
+    println!("Showing {} of {} matches", shown, result.total_count);
+    if result.has_more {
+        println!("Use --offset {} to see more", shown);
+    }
+
     Ok(())
 }
 
-fn count_matches(session: &ChatSession, query: &str) -> (usize, Option<String>) {
-    let mut count = 0;
-    let mut preview = None;
-
-    if session.name.to_lowercase().contains(query) {
-        count += 1;
-        preview = Some(preview_text(&session.name, 80));
+fn role_label(role: &ChatRole) -> &'static str {
+    match role {
+        ChatRole::User => "User",
+        ChatRole::Assistant => "Assistant",
+        ChatRole::System => "System",
     }
+}
 
-    for message in &session.messages {
-        let content = message.content.to_lowercase();
-        if content.contains(query) {
-            count += 1;
-            if preview.is_none() {
-                preview = Some(preview_text(&message.content, 120));
-            }
+/// Wrap highlighted ranges of `snippet` in `**` markers.
+fn emphasize(snippet: &str, highlights: &[ChatSearchHighlight]) -> String {
+    let chars: Vec<char> = snippet.chars().collect();
+    let mut output = String::with_capacity(snippet.len() + highlights.len() * 4);
+    let mut cursor = 0;
+    for highlight in highlights {
+        let start = (highlight.start as usize).min(chars.len());
+        let end = (highlight.end as usize).min(chars.len());
+        if start < cursor {
+            continue;
         }
+        output.extend(&chars[cursor..start]);
+        output.push_str("**");
+        output.extend(&chars[start..end]);
+        output.push_str("**");
+        cursor = end;
     }
-
-    (count, preview)
+    output.extend(&chars[cursor..]);
+    output
 }
 
 async fn resolve_session_id_optional(
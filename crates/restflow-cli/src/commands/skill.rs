@@ -4,15 +4,18 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::cli::SkillCommands;
-use crate::commands::utils::{format_timestamp, preview_text, slugify};
+use crate::commands::utils::{format_timestamp, preview_text, read_export, slugify, write_export};
 use crate::executor::CommandExecutor;
 use crate::output::{OutputFormat, json::print_json};
+use restflow_core::archive::ENCRYPTED_ARCHIVE_EXTENSION;
 use restflow_core::loader::git_source::GitSkillSource;
 use restflow_core::loader::skill_folder::{SkillFolderLoader, discover_skill_dirs};
 use restflow_core::loader::skill_package::SkillPackageImporter;
-use restflow_core::models::{Skill, StorageMode};
+use restflow_core::models::{InstallStatus, Skill, SkillStatus, StorageMode};
 use restflow_core::paths;
-use restflow_core::registry::{MarketplaceProvider, SkillRegistry, SkillSearchQuery};
+use restflow_core::registry::{
+    MarketplaceProvider, SkillRegistry, SkillRegistryConfig, SkillSearchQuery,
+};
 use restflow_core::services::skills as skill_service;
 use restflow_storage::RegistrySettings;
 use serde_json::json;
@@ -48,7 +51,11 @@ pub async fn run(
         }
         SkillCommands::Delete { id } => delete_skill(executor, &id, format).await,
         SkillCommands::Import { path } => import_skill(executor, &path, format).await,
-        SkillCommands::Export { id, output } => export_skill(executor, &id, output, format).await,
+        SkillCommands::Export {
+            id,
+            output,
+            encrypt,
+        } => export_skill(executor, &id, output, encrypt, format).await,
         SkillCommands::Search { query } => search_skills(executor, &query, format).await,
         SkillCommands::Install {
             source,
@@ -198,8 +205,9 @@ async fn import_skill(
     path: &str,
     format: OutputFormat,
 ) -> Result<()> {
-    let content = std::fs::read_to_string(path)?;
-    let filename = Path::new(path)
+    let content = String::from_utf8(read_export(path)?)
+        .map_err(|_| anyhow::anyhow!("Skill file is not valid UTF-8: {}", path))?;
+    let filename = Path::new(path.trim_end_matches(&format!(".{ENCRYPTED_ARCHIVE_EXTENSION}")))
         .file_stem()
         .and_then(|value| value.to_str())
         .unwrap_or("imported-skill");
@@ -220,6 +228,7 @@ async fn export_skill(
     executor: Arc<dyn CommandExecutor>,
     id: &str,
     output: Option<String>,
+    encrypt: bool,
     format: OutputFormat,
 ) -> Result<()> {
     let skill = executor
@@ -228,11 +237,17 @@ async fn export_skill(
         .ok_or_else(|| anyhow::anyhow!("Skill not found: {}", id))?;
 
     let markdown = skill_service::export_skill_to_markdown(&skill);
-    let path = output.unwrap_or_else(|| format!("{}.md", id));
-    std::fs::write(&path, markdown)?;
+    let path = output.unwrap_or_else(|| {
+        if encrypt {
+            format!("{}.md.{}", id, ENCRYPTED_ARCHIVE_EXTENSION)
+        } else {
+            format!("{}.md", id)
+        }
+    });
+    write_export(&path, markdown.as_bytes(), encrypt)?;
 
     if format.is_json() {
-        return print_json(&json!({ "id": id, "output": path }));
+        return print_json(&json!({ "id": id, "output": path, "encrypted": encrypt }));
     }
 
     println!("Exported to: {}", path);
@@ -315,12 +330,16 @@ async fn install_from_marketplace(
     name: &str,
     format: OutputFormat,
 ) -> Result<()> {
-    let mut registry = SkillRegistry::with_defaults();
     let registry_defaults = executor
         .get_config()
         .await
         .map(|config| config.registry_defaults)
         .unwrap_or_else(|_| RegistrySettings::default());
+    let mut registry = SkillRegistry::new(SkillRegistryConfig {
+        trusted_skill_keys: registry_defaults.trusted_skill_keys.clone(),
+        require_signed_skills: registry_defaults.require_signed_skills,
+        ..SkillRegistryConfig::default()
+    });
     registry.add_provider(Arc::new(
         MarketplaceProvider::new()
             .with_cache_ttl_secs(registry_defaults.marketplace_cache_ttl_secs),
@@ -334,13 +353,20 @@ async fn install_from_marketplace(
         Some(installed.manifest.keywords.clone())
     };
 
-    let skill = Skill::new(
+    let mut skill = Skill::new(
         installed.manifest.id.clone(),
         installed.manifest.name.clone(),
         installed.manifest.description.clone(),
         tags,
         installed.content.clone(),
     );
+    skill.version = Some(installed.manifest.version.to_string());
+    skill.gating = installed.manifest.gating.to_skill_gating();
+    // Keep the skill inactive until its gating requirements are met.
+    let requirements_met = installed.status == InstallStatus::Installed;
+    if !requirements_met {
+        skill.status = SkillStatus::Draft;
+    }
 
     let existing = executor.get_skill(&installed.manifest.id).await?;
     if let Some(mut existing_skill) = existing {
@@ -350,6 +376,9 @@ async fn install_from_marketplace(
             Some(skill.tags),
             Some(skill.content),
         );
+        existing_skill.version = skill.version;
+        existing_skill.gating = skill.gating;
+        existing_skill.status = skill.status;
         executor
             .update_skill(&installed.manifest.id, existing_skill)
             .await?;
@@ -365,6 +394,12 @@ async fn install_from_marketplace(
         "Skill installed from marketplace: {} ({})",
         installed.manifest.name, installed.manifest.id
     );
+    if !requirements_met && let Some(gating_result) = installed.gating_result.as_ref() {
+        println!(
+            "Skill is inactive until requirements are met: {}",
+            gating_result.summary
+        );
+    }
     Ok(())
 }
 
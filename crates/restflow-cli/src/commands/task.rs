@@ -1,6 +1,7 @@
 use anyhow::Result;
 use comfy_table::{Cell, Table};
 use restflow_contracts::{DeleteWithIdResponse, request::TaskFromSessionRequest};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::cli::{OutputFormat, TaskCommands};
@@ -11,8 +12,8 @@ use crate::output::table::print_table;
 #[cfg(test)]
 use restflow_core::models::RunKind;
 use restflow_core::models::{
-    ExecutionContainerKind, ExecutionContainerRef, RunListQuery, RunSummary, TaskControlAction,
-    TaskPatch, TaskSchedule, TaskSpec,
+    ExecutionContainerKind, ExecutionContainerRef, ItemStatus, RunListQuery, RunSummary,
+    TaskControlAction, TaskPatch, TaskSchedule, TaskSpec, WakeCondition,
 };
 #[cfg(test)]
 use restflow_core::services::background_agent_conversion::{
@@ -94,7 +95,28 @@ pub async fn run(
             .await
         }
         TaskCommands::Delete { id } => delete_task(executor, &id, format).await,
-        TaskCommands::Control { id, action } => control_task(executor, &id, &action, format).await,
+        TaskCommands::Control {
+            id,
+            action,
+            secrets,
+        } => control_task(executor, &id, &action, &secrets, format).await,
+        TaskCommands::Wake {
+            id,
+            condition,
+            value,
+            status,
+            checkpoint,
+            clear,
+        } => {
+            let condition = if clear {
+                None
+            } else {
+                let condition = condition
+                    .ok_or_else(|| anyhow::anyhow!("--on is required unless --clear is set"))?;
+                Some(parse_wake_condition(&condition, value, &status)?)
+            };
+            set_wake_condition(executor, &id, condition, checkpoint, format).await
+        }
         TaskCommands::Progress { id, limit } => show_progress(executor, &id, limit, format).await,
         TaskCommands::RunLog { id, run_id, limit } => {
             show_run_log(executor, &id, run_id.as_deref(), limit, format).await
@@ -267,6 +289,7 @@ async fn create_task(
         resource_limits: None,
         prerequisites: vec![],
         continuation: None,
+        concurrency: None,
     };
 
     let task = executor.create_task(spec).await?;
@@ -316,6 +339,9 @@ async fn update_task(
         resource_limits: None,
         prerequisites: None,
         continuation: None,
+        callbacks: None,
+        queue: None,
+        concurrency: None,
     };
 
     let task = executor.update_task(id, patch).await?;
@@ -351,10 +377,20 @@ async fn control_task(
     executor: Arc<dyn CommandExecutor>,
     id: &str,
     action: &str,
+    secrets: &[String],
     format: OutputFormat,
 ) -> Result<()> {
     let parsed_action = parse_control_action(action)?;
-    let task = executor.control_task(id, parsed_action.clone()).await?;
+    let task = if secrets.is_empty() {
+        executor.control_task(id, parsed_action.clone()).await?
+    } else {
+        if !matches!(parsed_action, TaskControlAction::RunNow) {
+            anyhow::bail!("--secret can only be used with --action run_now");
+        }
+        executor
+            .run_task_with_secrets(id, parse_secret_args(secrets)?)
+            .await?
+    };
 
     if format.is_json() {
         return print_json(&task);
@@ -367,6 +403,28 @@ async fn control_task(
     Ok(())
 }
 
+async fn set_wake_condition(
+    executor: Arc<dyn CommandExecutor>,
+    id: &str,
+    condition: Option<WakeCondition>,
+    checkpoint_id: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let task = executor
+        .set_task_wake_condition(id, condition, checkpoint_id)
+        .await?;
+
+    if format.is_json() {
+        return print_json(&task);
+    }
+
+    match &task.wake {
+        Some(wake) => println!("Task {} waits for: {}", id, wake.condition.kind()),
+        None => println!("Task {} wake condition cleared", id),
+    }
+    Ok(())
+}
+
 async fn show_progress(
     executor: Arc<dyn CommandExecutor>,
     id: &str,
@@ -540,6 +598,61 @@ fn parse_control_action(action: &str) -> Result<TaskControlAction> {
     }
 }
 
+fn parse_wake_condition(
+    condition: &str,
+    value: Option<String>,
+    status: &str,
+) -> Result<WakeCondition> {
+    let kind = condition.to_lowercase();
+    if kind == "webhook" {
+        return Ok(WakeCondition::WebhookReceived);
+    }
+    let value = value
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("--value is required for wake condition: {}", condition))?;
+
+    match kind.as_str() {
+        "file" => Ok(WakeCondition::FileChanged {
+            path: value,
+            baseline_modified_at: None,
+        }),
+        "work_item" => {
+            let status: ItemStatus = serde_json::from_value(serde_json::Value::String(
+                status.to_lowercase(),
+            ))
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid work item status: {}. Use: open, in_progress, done, archived",
+                    status
+                )
+            })?;
+            Ok(WakeCondition::WorkItemStatus {
+                item_id: value,
+                status,
+            })
+        }
+        "time" => Ok(WakeCondition::TimeReached {
+            at: value.parse::<i64>()?,
+        }),
+        _ => anyhow::bail!(
+            "Invalid wake condition: {}. Use: webhook, file, work_item, time",
+            condition
+        ),
+    }
+}
+
+fn parse_secret_args(secrets: &[String]) -> Result<HashMap<String, String>> {
+    secrets
+        .iter()
+        .map(|entry| match entry.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                Ok((name.trim().to_string(), value.to_string()))
+            }
+            _ => anyhow::bail!("Invalid secret '{}': expected NAME=VALUE", entry),
+        })
+        .collect()
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() > max_len {
         format!("{}...", &s[..max_len])
@@ -553,6 +666,39 @@ mod tests {
     use super::*;
     use restflow_core::models::ChatMessage;
 
+    #[test]
+    fn parse_secret_args_splits_on_first_equals() {
+        let parsed = parse_secret_args(&["TOKEN=a=b".to_string(), " KEY =v".to_string()]).unwrap();
+        assert_eq!(parsed.get("TOKEN").map(String::as_str), Some("a=b"));
+        assert_eq!(parsed.get("KEY").map(String::as_str), Some("v"));
+        assert!(parse_secret_args(&["missing".to_string()]).is_err());
+        assert!(parse_secret_args(&["=value".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_wake_condition_maps_kinds() {
+        assert_eq!(
+            parse_wake_condition("webhook", None, "done").unwrap(),
+            WakeCondition::WebhookReceived
+        );
+        assert_eq!(
+            parse_wake_condition("work_item", Some("item-1".to_string()), "in_progress").unwrap(),
+            WakeCondition::WorkItemStatus {
+                item_id: "item-1".to_string(),
+                status: ItemStatus::InProgress,
+            }
+        );
+        assert_eq!(
+            parse_wake_condition("time", Some("1700000000000".to_string()), "done").unwrap(),
+            WakeCondition::TimeReached {
+                at: 1_700_000_000_000
+            }
+        );
+        assert!(parse_wake_condition("file", None, "done").is_err());
+        assert!(parse_wake_condition("work_item", Some("item-1".to_string()), "later").is_err());
+        assert!(parse_wake_condition("never", Some("x".to_string()), "done").is_err());
+    }
+
     #[test]
     fn derive_conversion_name_prefers_explicit_name() {
         let name = derive_conversion_name(
@@ -141,17 +141,21 @@ mod tests {
     use crate::executor::CommandExecutor;
     use async_trait::async_trait;
     use restflow_contracts::{
-        CleanupReportResponse, PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse,
-        RouteBindingResponse, SessionSourceMigrationResponse,
-        request::TaskFromSessionRequest,
+        ArtifactPurgeResponse, CleanupReportResponse, DatabaseBackupResponse,
+        PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse, RouteBindingResponse,
+        SessionSourceMigrationResponse, request::TaskFromSessionRequest,
     };
     use restflow_core::memory::ExportResult;
     use restflow_core::models::{
-        AgentNode, ChatSession, ChatSessionSummary, Deliverable, ExecutionTimeline, Hook,
-        ItemQuery, MemoryChunk, MemorySearchResult, MemoryStats, RunListQuery, RunSummary, Secret,
-        SharedEntry, Skill, Task, TaskControlAction, TaskConversionResult, TaskPatch, TaskProgress,
+        AgentNode, AgentTemplate, ChatSearchQuery, ChatSearchResult, ChatSession, ChatSessionSummary,
+        Deliverable, ExecutionTimeline, Hook, ItemQuery, MemoryChunk, MemorySearchResult,
+        MemoryStats, RunListQuery, RunSummary, SavedBrowserPlan, Secret, SecretAccess, SharedEntry, Skill,
+        TagCount, Task, TaskControlAction, TaskConversionResult, TaskPatch, TaskProgress,
         TaskSpec, WorkItem, WorkItemPatch, WorkItemSpec,
     };
+    use restflow_core::services::agent_template::ProvisionedAgentTemplate;
+    use restflow_core::services::data_bundle::{BundleImportReport, DataBundle};
+    use restflow_core::services::project_config::{ProjectApplyReport, ProjectConfig};
     use restflow_core::storage::SystemConfig;
     use restflow_core::storage::agent::StoredAgent;
     use std::sync::Mutex;
@@ -177,6 +181,8 @@ mod tests {
         async fn create_agent(&self, _name: String, _agent: AgentNode) -> Result<StoredAgent> { unreachable!() }
         async fn update_agent(&self, _id: &str, _name: Option<String>, _agent: Option<AgentNode>) -> Result<StoredAgent> { unreachable!() }
         async fn delete_agent(&self, _id: &str) -> Result<()> { unreachable!() }
+        async fn list_agent_templates(&self) -> Result<Vec<AgentTemplate>> { unreachable!() }
+        async fn provision_agent_template(&self, _template_id: &str, _name: Option<String>) -> Result<ProvisionedAgentTemplate> { unreachable!() }
         async fn list_skills(&self) -> Result<Vec<Skill>> { unreachable!() }
         async fn get_skill(&self, _id: &str) -> Result<Option<Skill>> { unreachable!() }
         async fn create_skill(&self, _skill: Skill) -> Result<()> { unreachable!() }
@@ -188,11 +194,23 @@ mod tests {
         async fn get_memory_stats(&self, _agent_id: Option<String>) -> Result<MemoryStats> { unreachable!() }
         async fn export_memory(&self, _agent_id: Option<String>) -> Result<ExportResult> { unreachable!() }
         async fn store_memory(&self, _agent_id: &str, _content: &str, _tags: Vec<String>) -> Result<String> { unreachable!() }
+        async fn tag_memory(&self, _chunk_ids: Vec<String>, _add_tags: Vec<String>, _remove_tags: Vec<String>) -> Result<u32> { unreachable!() }
+        async fn merge_memory_tags(&self, _sources: Vec<String>, _target: String) -> Result<u32> { unreachable!() }
+        async fn list_memory_tags(&self, _agent_id: Option<String>) -> Result<Vec<TagCount>> { unreachable!() }
         async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>> { unreachable!() }
         async fn get_session(&self, _id: &str) -> Result<ChatSession> { unreachable!() }
         async fn create_session(&self, _agent_id: String, _model: String) -> Result<ChatSession> { unreachable!() }
         async fn delete_session(&self, _id: &str) -> Result<bool> { unreachable!() }
-        async fn search_sessions(&self, _query: String) -> Result<Vec<ChatSessionSummary>> { unreachable!() }
+        async fn search_chat_sessions(&self, _query: ChatSearchQuery) -> Result<ChatSearchResult> { unreachable!() }
+        async fn get_usage_stats(&self, _query: restflow_core::models::UsageQuery) -> Result<restflow_core::models::UsageStats> { unreachable!() }
+        async fn query_execution_traces(&self, _query: restflow_core::models::ExecutionTraceQuery) -> Result<Vec<restflow_core::models::ExecutionTraceEvent>> { unreachable!() }
+        async fn query_execution_trace_stats(&self, _query: restflow_core::models::ExecutionTraceQuery) -> Result<restflow_core::models::ExecutionTraceStats> { unreachable!() }
+        async fn get_execution_replay(&self, _id: &str) -> Result<Option<restflow_core::models::ExecutionReplay>> { unreachable!() }
+        async fn replay_execution(&self, _execution_id: &str, _step: u32, _agent_id: Option<String>, _message: Option<String>) -> Result<restflow_core::models::ReplayRunResult> { unreachable!() }
+        async fn list_tool_approvals(&self, _pending_only: bool) -> Result<Vec<restflow_core::models::PendingApproval>> { unreachable!() }
+        async fn resolve_tool_approval(&self, _id: &str, _approved: bool, _reason: Option<String>) -> Result<restflow_core::models::PendingApproval> { unreachable!() }
+        async fn get_tool_approval_policy(&self) -> Result<restflow_core::models::ToolApprovalPolicy> { unreachable!() }
+        async fn set_tool_approval_policy(&self, _policy: restflow_core::models::ToolApprovalPolicy) -> Result<()> { unreachable!() }
         async fn list_notes(&self, _query: ItemQuery) -> Result<Vec<WorkItem>> { unreachable!() }
         async fn get_note(&self, _id: &str) -> Result<Option<WorkItem>> { unreachable!() }
         async fn create_note(&self, _spec: WorkItemSpec) -> Result<WorkItem> { unreachable!() }
@@ -205,6 +223,10 @@ mod tests {
         async fn update_secret(&self, _key: &str, _value: &str, _description: Option<String>) -> Result<()> { unreachable!() }
         async fn delete_secret(&self, _key: &str) -> Result<()> { unreachable!() }
         async fn has_secret(&self, _key: &str) -> Result<bool> { unreachable!() }
+        async fn rotate_secret(&self, _key: &str, _value: &str, _expires_at: Option<i64>) -> Result<()> { unreachable!() }
+        async fn set_secret_expiry(&self, _key: &str, _expires_at: Option<i64>) -> Result<()> { unreachable!() }
+        async fn list_expiring_secrets(&self, _before: i64) -> Result<Vec<Secret>> { unreachable!() }
+        async fn get_secret_usage(&self, _key: &str) -> Result<Vec<SecretAccess>> { unreachable!() }
         async fn get_config(&self) -> Result<SystemConfig> { unreachable!() }
         async fn get_global_config(&self) -> Result<SystemConfig> { unreachable!() }
         async fn set_config(&self, _config: SystemConfig) -> Result<()> { unreachable!() }
@@ -222,8 +244,20 @@ mod tests {
         async fn list_route_bindings(&self) -> Result<Vec<RouteBindingResponse>> { unreachable!() }
         async fn bind_route(&self, _binding_type: &str, _target_id: &str, _agent_id: &str) -> Result<RouteBindingResponse> { unreachable!() }
         async fn unbind_route(&self, _id: &str) -> Result<bool> { unreachable!() }
+        async fn list_api_tokens(&self, _include_revoked: bool) -> Result<Vec<restflow_contracts::ApiTokenResponse>> { unreachable!() }
+        async fn create_api_token(&self, _name: String, _scope: restflow_contracts::request::ApiTokenScope, _expires_at: Option<i64>) -> Result<restflow_contracts::IssuedApiTokenResponse> { unreachable!() }
+        async fn revoke_api_token(&self, _id: &str) -> Result<()> { unreachable!() }
+        async fn list_users(&self) -> Result<Vec<restflow_contracts::UserResponse>> { unreachable!() }
+        async fn create_user(&self, _name: &str) -> Result<restflow_contracts::UserResponse> { unreachable!() }
+        async fn delete_user(&self, _id: &str) -> Result<()> { unreachable!() }
         async fn run_cleanup(&self) -> Result<CleanupReportResponse> { unreachable!() }
         async fn migrate_session_sources(&self, _dry_run: bool) -> Result<SessionSourceMigrationResponse> { unreachable!() }
+        async fn purge_artifacts(&self, _dry_run: bool) -> Result<ArtifactPurgeResponse> { unreachable!() }
+        async fn backup_database(&self, _path: String) -> Result<DatabaseBackupResponse> { unreachable!() }
+        async fn restore_database(&self, _path: String) -> Result<DatabaseBackupResponse> { unreachable!() }
+        async fn export_bundle(&self, _secrets_password: Option<String>) -> Result<DataBundle> { unreachable!() }
+        async fn import_bundle(&self, _bundle: DataBundle, _secrets_password: Option<String>, _overwrite: bool) -> Result<BundleImportReport> { unreachable!() }
+        async fn apply_project_config(&self, _config: ProjectConfig, _dry_run: bool) -> Result<ProjectApplyReport> { unreachable!() }
         async fn list_tasks(&self, _status: Option<String>) -> Result<Vec<Task>> { unreachable!() }
         async fn get_task(&self, _id: &str) -> Result<Task> { unreachable!() }
         async fn create_task(&self, _spec: TaskSpec) -> Result<Task> { unreachable!() }
@@ -231,6 +265,8 @@ mod tests {
         async fn update_task(&self, _id: &str, _patch: TaskPatch) -> Result<Task> { unreachable!() }
         async fn delete_task(&self, _id: &str) -> Result<restflow_contracts::DeleteWithIdResponse> { unreachable!() }
         async fn control_task(&self, _id: &str, _action: TaskControlAction) -> Result<Task> { unreachable!() }
+        async fn run_task_with_secrets(&self, _id: &str, _secrets: std::collections::HashMap<String, String>) -> Result<Task> { unreachable!() }
+        async fn set_task_wake_condition(&self, _id: &str, _condition: Option<restflow_core::models::WakeCondition>, _checkpoint_id: Option<String>) -> Result<Task> { unreachable!() }
         async fn get_task_progress(&self, _id: &str, _event_limit: Option<usize>) -> Result<TaskProgress> { unreachable!() }
         async fn send_task_message(&self, _id: &str, _message: &str) -> Result<()> { unreachable!() }
         async fn list_execution_sessions(&self, _query: RunListQuery) -> Result<Vec<RunSummary>> { unreachable!() }
@@ -254,6 +290,16 @@ mod tests {
         async fn set_kv_store(&self, _key: &str, _value: &str, _visibility: &str) -> Result<SharedEntry> { unreachable!() }
         async fn delete_kv_store(&self, _key: &str) -> Result<bool> { unreachable!() }
         async fn list_deliverables(&self, _task_id: &str) -> Result<Vec<Deliverable>> { unreachable!() }
+        async fn list_browser_plans(&self) -> Result<Vec<SavedBrowserPlan>> { unreachable!() }
+        async fn get_browser_plan(&self, _name: &str, _version: Option<u32>) -> Result<Option<SavedBrowserPlan>> { unreachable!() }
+        async fn list_browser_plan_versions(&self, _name: &str) -> Result<Vec<SavedBrowserPlan>> { unreachable!() }
+        async fn save_browser_plan(&self, _name: &str, _description: Option<String>, _plan: restflow_core::models::BrowserPlan) -> Result<SavedBrowserPlan> { unreachable!() }
+        async fn delete_browser_plan(&self, _name: &str) -> Result<bool> { unreachable!() }
+        async fn run_browser_plan(&self, _name: &str, _version: Option<u32>, _payload: Option<serde_json::Value>) -> Result<restflow_core::services::browser_webhook::BrowserWebhookOutcome> { unreachable!() }
+        async fn list_api_connectors(&self) -> Result<Vec<restflow_core::models::ApiConnector>> { unreachable!() }
+        async fn get_api_connector(&self, _name: &str) -> Result<Option<restflow_core::models::ApiConnector>> { unreachable!() }
+        async fn import_api_connector(&self, _name: &str, _spec: String, _base_url: Option<String>, _auth_secret: Option<String>) -> Result<restflow_core::models::ApiConnector> { unreachable!() }
+        async fn delete_api_connector(&self, _name: &str) -> Result<bool> { unreachable!() }
     }
 
     #[tokio::test]
@@ -0,0 +1,232 @@
+//! CLI commands for scoped API tokens used by external MCP and webhook callers.
+
+use anyhow::Result;
+use comfy_table::{Cell, Table};
+use restflow_contracts::ApiTokenResponse;
+use restflow_contracts::request::{ApiTokenRole, ApiTokenScope};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::cli::{ApiTokenRoleArg, TokenCommands};
+use crate::commands::utils::format_timestamp;
+use crate::executor::CommandExecutor;
+use crate::output::OutputFormat;
+use crate::output::json::print_json;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Run API token commands.
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: TokenCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        TokenCommands::List { all } => list_tokens(executor, all, format).await,
+        TokenCommands::Create {
+            name,
+            agents,
+            tools,
+            max_calls,
+            no_mcp,
+            no_webhooks,
+            no_openai_api,
+            role,
+            rate_limit,
+            user_id,
+            expires_in_days,
+        } => {
+            let scope = ApiTokenScope {
+                allowed_agents: non_empty(agents),
+                allowed_tools: non_empty(tools),
+                max_calls,
+                mcp: !no_mcp,
+                webhooks: !no_webhooks,
+                openai_api: !no_openai_api,
+                role: role.map(to_api_token_role),
+                rate_limit_per_minute: rate_limit,
+                user_id,
+            };
+            let expires_at = expires_in_days
+                .map(|days| chrono::Utc::now().timestamp_millis() + i64::from(days) * DAY_MS);
+            create_token(executor, name, scope, expires_at, format).await
+        }
+        TokenCommands::Revoke { id } => revoke_token(executor, &id, format).await,
+    }
+}
+
+async fn list_tokens(
+    executor: Arc<dyn CommandExecutor>,
+    include_revoked: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let tokens = executor.list_api_tokens(include_revoked).await?;
+
+    if format.is_json() {
+        return print_json(&tokens);
+    }
+
+    if tokens.is_empty() {
+        println!("No API tokens found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Name",
+        "Prefix",
+        "Role",
+        "Agents",
+        "Tools",
+        "Calls",
+        "Last Used",
+        "Status",
+    ]);
+    for token in &tokens {
+        table.add_row(vec![
+            Cell::new(&token.id),
+            Cell::new(&token.name),
+            Cell::new(&token.token_prefix),
+            Cell::new(format_role(token.scope.role)),
+            Cell::new(format_allow_list(token.scope.allowed_agents.as_deref())),
+            Cell::new(format_allow_list(token.scope.allowed_tools.as_deref())),
+            Cell::new(format_calls(token)),
+            Cell::new(format_timestamp(token.last_used_at)),
+            Cell::new(token_status(token)),
+        ]);
+    }
+    crate::output::table::print_table(table)
+}
+
+async fn create_token(
+    executor: Arc<dyn CommandExecutor>,
+    name: String,
+    scope: ApiTokenScope,
+    expires_at: Option<i64>,
+    format: OutputFormat,
+) -> Result<()> {
+    let issued = executor.create_api_token(name, scope, expires_at).await?;
+
+    if format.is_json() {
+        return print_json(&issued);
+    }
+
+    println!(
+        "Created API token {} ({})",
+        issued.token.name, issued.token.id
+    );
+    println!("Token: {}", issued.secret);
+    println!("Store this token now; it will not be shown again.");
+    Ok(())
+}
+
+async fn revoke_token(
+    executor: Arc<dyn CommandExecutor>,
+    id: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    executor.revoke_api_token(id).await?;
+
+    if format.is_json() {
+        return print_json(&json!({ "revoked": true, "id": id }));
+    }
+
+    println!("Revoked API token: {id}");
+    Ok(())
+}
+
+fn to_api_token_role(role: ApiTokenRoleArg) -> ApiTokenRole {
+    match role {
+        ApiTokenRoleArg::ReadOnly => ApiTokenRole::ReadOnly,
+        ApiTokenRoleArg::Execute => ApiTokenRole::Execute,
+        ApiTokenRoleArg::Admin => ApiTokenRole::Admin,
+    }
+}
+
+fn format_role(role: Option<ApiTokenRole>) -> &'static str {
+    match role {
+        Some(ApiTokenRole::ReadOnly) => "read-only",
+        Some(ApiTokenRole::Execute) => "execute",
+        Some(ApiTokenRole::Admin) => "admin",
+        None => "-",
+    }
+}
+
+fn non_empty(values: Vec<String>) -> Option<Vec<String>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+fn format_allow_list(values: Option<&[String]>) -> String {
+    match values {
+        Some(values) => values.join(", "),
+        None => "*".to_string(),
+    }
+}
+
+fn format_calls(token: &ApiTokenResponse) -> String {
+    match token.scope.max_calls {
+        Some(max_calls) => format!("{}/{}", token.call_count, max_calls),
+        None => token.call_count.to_string(),
+    }
+}
+
+fn token_status(token: &ApiTokenResponse) -> &'static str {
+    let now = chrono::Utc::now().timestamp_millis();
+    if token.revoked_at.is_some() {
+        "revoked"
+    } else if token.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        "expired"
+    } else {
+        "active"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(call_count: u32, max_calls: Option<u32>) -> ApiTokenResponse {
+        ApiTokenResponse {
+            id: "tok-1".to_string(),
+            name: "ci".to_string(),
+            token_prefix: "rft_abcdefgh".to_string(),
+            scope: ApiTokenScope {
+                max_calls,
+                ..ApiTokenScope::default()
+            },
+            call_count,
+            created_at: 0,
+            last_used_at: None,
+            expires_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn formats_scope_columns() {
+        assert_eq!(format_allow_list(None), "*");
+        assert_eq!(
+            format_allow_list(Some(&["a".to_string(), "b".to_string()])),
+            "a, b"
+        );
+        assert_eq!(format_calls(&token(3, Some(10))), "3/10");
+        assert_eq!(format_calls(&token(3, None)), "3");
+        assert_eq!(format_role(None), "-");
+        assert_eq!(format_role(Some(ApiTokenRole::Execute)), "execute");
+    }
+
+    #[test]
+    fn reports_token_status() {
+        let mut value = token(0, None);
+        assert_eq!(token_status(&value), "active");
+        value.expires_at = Some(1);
+        assert_eq!(token_status(&value), "expired");
+        value.revoked_at = Some(1);
+        assert_eq!(token_status(&value), "revoked");
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use chrono::Utc;
+use comfy_table::{Cell, Table};
+use std::sync::Arc;
+
+use crate::cli::{OutputFormat, UsageArgs};
+use crate::commands::utils::short_id;
+use crate::executor::CommandExecutor;
+use crate::output::json::print_json;
+use crate::output::table::print_table;
+use restflow_core::models::{UsageBucket, UsageQuery, UsageTotals};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    args: UsageArgs,
+    format: OutputFormat,
+) -> Result<()> {
+    let query = UsageQuery {
+        agent_id: args.agent,
+        session_id: args.session,
+        from_time: args
+            .days
+            .map(|days| Utc::now().timestamp_millis() - i64::from(days) * DAY_MS),
+        to_time: None,
+    };
+    let stats = executor.get_usage_stats(query).await?;
+
+    if format.is_json() {
+        return print_json(&stats);
+    }
+
+    if stats.totals.call_count == 0 {
+        println!("No LLM usage recorded.");
+        return Ok(());
+    }
+
+    println!(
+        "{} calls, {} tokens ({} prompt / {} completion), ${:.4}",
+        stats.totals.call_count,
+        stats.totals.total_tokens,
+        stats.totals.prompt_tokens,
+        stats.totals.completion_tokens,
+        stats.totals.cost_usd
+    );
+    if stats.totals.cache_read_tokens > 0 || stats.totals.cache_write_tokens > 0 {
+        println!(
+            "Prompt cache: {} read / {} written, ${:.4} saved",
+            stats.totals.cache_read_tokens,
+            stats.totals.cache_write_tokens,
+            stats.totals.cache_savings_usd
+        );
+    }
+
+    print_buckets("Day", &stats.by_day, |key| key.to_string())?;
+    print_buckets("Agent", &stats.by_agent, |key| key.to_string())?;
+    print_buckets("Session", &stats.by_session, short_id)
+}
+
+fn print_buckets(
+    label: &str,
+    buckets: &[UsageBucket],
+    format_key: impl Fn(&str) -> String,
+) -> Result<()> {
+    if buckets.is_empty() {
+        return Ok(());
+    }
+    println!();
+    let mut table = Table::new();
+    table.set_header(vec![
+        label,
+        "Calls",
+        "Prompt",
+        "Cached",
+        "Completion",
+        "Cost (USD)",
+    ]);
+    for bucket in buckets {
+        table.add_row(bucket_row(format_key(&bucket.key), &bucket.totals));
+    }
+    print_table(table)
+}
+
+fn bucket_row(key: String, totals: &UsageTotals) -> Vec<Cell> {
+    vec![
+        Cell::new(key),
+        Cell::new(totals.call_count),
+        Cell::new(totals.prompt_tokens),
+        Cell::new(totals.cache_read_tokens),
+        Cell::new(totals.completion_tokens),
+        Cell::new(format!("{:.4}", totals.cost_usd)),
+    ]
+}
@@ -0,0 +1,83 @@
+//! CLI commands for workspace users in multi-user server mode.
+
+use anyhow::Result;
+use comfy_table::{Cell, Table};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::cli::UserCommands;
+use crate::commands::utils::format_timestamp;
+use crate::executor::CommandExecutor;
+use crate::output::OutputFormat;
+use crate::output::json::print_json;
+
+/// Run workspace user commands.
+pub async fn run(
+    executor: Arc<dyn CommandExecutor>,
+    command: UserCommands,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        UserCommands::List => list_users(executor, format).await,
+        UserCommands::Create { name } => create_user(executor, &name, format).await,
+        UserCommands::Delete { id } => delete_user(executor, &id, format).await,
+    }
+}
+
+async fn list_users(executor: Arc<dyn CommandExecutor>, format: OutputFormat) -> Result<()> {
+    let users = executor.list_users().await?;
+
+    if format.is_json() {
+        return print_json(&users);
+    }
+
+    if users.is_empty() {
+        println!("No users found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Name", "Created"]);
+    for user in &users {
+        table.add_row(vec![
+            Cell::new(&user.id),
+            Cell::new(&user.name),
+            Cell::new(format_timestamp(Some(user.created_at))),
+        ]);
+    }
+    crate::output::table::print_table(table)
+}
+
+async fn create_user(
+    executor: Arc<dyn CommandExecutor>,
+    name: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let user = executor.create_user(name).await?;
+
+    if format.is_json() {
+        return print_json(&user);
+    }
+
+    println!("Created user {} ({})", user.name, user.id);
+    println!(
+        "Issue tokens for this user with: restflow token create --user {}",
+        user.id
+    );
+    Ok(())
+}
+
+async fn delete_user(
+    executor: Arc<dyn CommandExecutor>,
+    id: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    executor.delete_user(id).await?;
+
+    if format.is_json() {
+        return print_json(&json!({ "deleted": true, "id": id }));
+    }
+
+    println!("Deleted user: {id}");
+    Ok(())
+}
@@ -93,6 +93,54 @@ pub fn preview_text(input: &str, max_len: usize) -> String {
     preview
 }
 
+/// Environment variable used to supply the export archive password non-interactively.
+pub const ARCHIVE_PASSWORD_ENV: &str = "RESTFLOW_ARCHIVE_PASSWORD";
+
+/// Resolve the archive password from the environment or an interactive prompt.
+///
+/// When `confirm` is set the prompt asks twice, which is used for encryption.
+pub fn read_archive_password(confirm: bool) -> Result<String> {
+    if let Ok(password) = std::env::var(ARCHIVE_PASSWORD_ENV)
+        && !password.is_empty()
+    {
+        return Ok(password);
+    }
+
+    let password = rpassword::prompt_password("Archive password: ")?;
+    if password.is_empty() {
+        bail!("Archive password must not be empty");
+    }
+    if confirm {
+        let repeated = rpassword::prompt_password("Confirm archive password: ")?;
+        if repeated != password {
+            bail!("Archive passwords do not match");
+        }
+    }
+    Ok(password)
+}
+
+/// Write an export to disk, encrypting it with a password when requested.
+pub fn write_export(path: &str, contents: &[u8], encrypt: bool) -> Result<()> {
+    if encrypt {
+        let password = read_archive_password(true)?;
+        let encrypted = restflow_core::archive::encrypt_archive(contents, &password)?;
+        std::fs::write(path, encrypted)?;
+    } else {
+        std::fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Read an exported file, transparently decrypting password-protected archives.
+pub fn read_export(path: &str) -> Result<Vec<u8>> {
+    let data = std::fs::read(path)?;
+    if !restflow_core::archive::is_encrypted_archive(&data) {
+        return Ok(data);
+    }
+    let password = read_archive_password(false)?;
+    restflow_core::archive::decrypt_archive(&data, &password)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{parse_model, parse_provider};
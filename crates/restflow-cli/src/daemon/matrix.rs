@@ -0,0 +1,126 @@
+use anyhow::Result;
+use restflow_core::channel::MatrixChannel;
+use restflow_core::storage::SecretStorage;
+
+/// Set up the Matrix channel, returning the channel and optional default room ID.
+///
+/// Requires both `MATRIX_HOMESERVER_URL` and `MATRIX_ACCESS_TOKEN` to be configured.
+pub fn setup_matrix_channel(
+    secrets: &SecretStorage,
+) -> Result<Option<(MatrixChannel, Option<String>)>> {
+    let homeserver_url = secrets.get_non_empty("MATRIX_HOMESERVER_URL")?;
+    let access_token = secrets.get_non_empty("MATRIX_ACCESS_TOKEN")?;
+
+    let (Some(homeserver_url), Some(access_token)) = (homeserver_url, access_token) else {
+        return Ok(None);
+    };
+
+    let default_room_id = secrets.get_non_empty("MATRIX_ROOM_ID")?;
+
+    let channel = MatrixChannel::with_credentials(&homeserver_url, &access_token);
+
+    Ok(Some((channel, default_room_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redb::Database;
+    use std::env;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    struct EnvGuard {
+        key: &'static str,
+        original: Option<std::ffi::OsString>,
+    }
+
+    impl EnvGuard {
+        fn set_path(key: &'static str, path: &Path) -> Self {
+            let original = env::var_os(key);
+            unsafe {
+                env::set_var(key, path);
+            }
+            Self { key, original }
+        }
+
+        fn clear(key: &'static str) -> Self {
+            let original = env::var_os(key);
+            unsafe {
+                env::remove_var(key);
+            }
+            Self { key, original }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            if let Some(value) = &self.original {
+                unsafe {
+                    env::set_var(self.key, value);
+                }
+            } else {
+                unsafe {
+                    env::remove_var(self.key);
+                }
+            }
+        }
+    }
+
+    fn env_lock() -> std::sync::MutexGuard<'static, ()> {
+        crate::test_support::env_lock()
+    }
+
+    fn setup_secrets() -> (SecretStorage, tempfile::TempDir, EnvGuard, EnvGuard) {
+        let temp_dir = tempdir().unwrap();
+        let restflow_dir = temp_dir.path().join("state");
+        std::fs::create_dir_all(&restflow_dir).unwrap();
+        let restflow_dir_guard = EnvGuard::set_path("RESTFLOW_DIR", &restflow_dir);
+        let master_key_guard = EnvGuard::clear("RESTFLOW_MASTER_KEY");
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let secrets = SecretStorage::new(db).unwrap();
+        (secrets, temp_dir, restflow_dir_guard, master_key_guard)
+    }
+
+    #[test]
+    fn test_setup_matrix_without_credentials() {
+        let _lock = env_lock();
+        let (secrets, _temp_dir, _restflow_dir_guard, _master_key_guard) = setup_secrets();
+
+        let result = setup_matrix_channel(&secrets).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_setup_matrix_requires_access_token() {
+        let _lock = env_lock();
+        let (secrets, _temp_dir, _restflow_dir_guard, _master_key_guard) = setup_secrets();
+
+        secrets
+            .set_secret("MATRIX_HOMESERVER_URL", "https://matrix.example.org", None)
+            .unwrap();
+
+        let result = setup_matrix_channel(&secrets).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_setup_matrix_with_default_room() {
+        let _lock = env_lock();
+        let (secrets, _temp_dir, _restflow_dir_guard, _master_key_guard) = setup_secrets();
+
+        secrets
+            .set_secret("MATRIX_HOMESERVER_URL", "https://matrix.example.org", None)
+            .unwrap();
+        secrets
+            .set_secret("MATRIX_ACCESS_TOKEN", "syt_token", None)
+            .unwrap();
+        secrets
+            .set_secret("MATRIX_ROOM_ID", "!ops:example.org", None)
+            .unwrap();
+
+        let (_, default_room_id) = setup_matrix_channel(&secrets).unwrap().unwrap();
+        assert_eq!(default_room_id, Some("!ops:example.org".to_string()));
+    }
+}
@@ -1,4 +1,5 @@
 mod discord;
+mod matrix;
 mod runner;
 mod slack;
 mod telegram;
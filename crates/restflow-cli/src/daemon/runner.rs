@@ -3,29 +3,31 @@ use async_trait::async_trait;
 use restflow_ai::agent::{SubagentConfig, SubagentTracker};
 use restflow_core::AppCore;
 use restflow_core::auth::{AuthManagerConfig, AuthProfileManager};
-use restflow_core::channel::{ChannelRouter, PairingManager};
-use restflow_core::daemon::publish_background_event;
+use restflow_core::channel::{ChannelRouter, PairingManager, RouteResolver};
+use restflow_core::daemon::{DaemonComponentState, publish_background_event, set_daemon_component};
 use restflow_core::hooks::HookExecutor;
 use restflow_core::models::{Task, TaskControlAction, TaskMessageSource, TaskStatus};
 use restflow_core::paths;
 use restflow_core::process::ProcessRegistry;
-use restflow_core::runtime::background_agent::BackgroundReplySenderFactory;
+use restflow_core::runtime::background_agent::{BackgroundReplySenderFactory, CallbackDispatcher};
 use restflow_core::runtime::channel::start_message_handler_with_pairing;
 use restflow_core::runtime::{
-    AgentRuntimeExecutor, ChatDispatcher, ChatDispatcherConfig, ChatSessionManager,
-    MessageDebouncer, MessageHandlerConfig, MessageHandlerHandle, NoopHeartbeatEmitter,
-    OrchestratingAgentExecutor, StorageBackedSubagentLookup, SystemStatus, TaskRunner,
-    TaskRunnerConfig, TaskRunnerHandle, TaskTrigger, TelegramNotifier,
+    AgentRuntimeExecutor, BrowserScheduleRunner, ChatDispatcher, ChatDispatcherConfig,
+    ChatSessionManager, DigestRunner, MessageDebouncer, MessageHandlerConfig, MessageHandlerHandle,
+    NoopHeartbeatEmitter, OrchestratingAgentExecutor, StorageBackedSubagentLookup, SystemStatus,
+    TaskRunner, TaskRunnerConfig, TaskRunnerHandle, TaskTrigger, TelegramNotifier,
+    WebhookSinkRunner,
 };
 use restflow_core::runtime::{TaskEventEmitter, TaskStreamEvent};
+use restflow_core::services::budget::BudgetGuard;
 use restflow_core::steer::SteerRegistry;
 use restflow_core::storage::{SecretStorage, SystemConfig};
-use restflow_storage::{AgentDefaults, AuthProfileStorage};
+use restflow_storage::{AgentDefaults, AuthProfileStorage, StorageBackend};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
-use super::{discord, slack, telegram};
+use super::{discord, matrix, slack, telegram};
 
 struct TaskIpcEventEmitter;
 
@@ -42,14 +44,23 @@ pub struct CliTaskRunner {
     runner: Arc<RwLock<Option<Arc<TaskRunner>>>>,
     router: Arc<RwLock<Option<Arc<ChannelRouter>>>>,
     message_handler: Option<MessageHandlerHandle>,
+    browser_schedule: Option<tokio::task::JoinHandle<()>>,
+    digests: Option<tokio::task::JoinHandle<()>>,
+    webhooks: Option<tokio::task::JoinHandle<()>>,
+    oauth_refresh: Option<tokio::task::JoinHandle<()>>,
 }
 
+/// How often OAuth profiles are checked for upcoming expiry.
+const OAUTH_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+/// Tokens expiring within this window are refreshed ahead of time.
+const OAUTH_REFRESH_WINDOW_MINUTES: i64 = 10;
+
 fn create_auth_manager(
     secrets: Arc<SecretStorage>,
-    db: Arc<redb::Database>,
+    backend: Arc<dyn StorageBackend>,
 ) -> Result<AuthProfileManager> {
     let config = AuthManagerConfig::default();
-    let storage = AuthProfileStorage::new(db)?;
+    let storage = AuthProfileStorage::with_backend(backend)?;
     Ok(AuthProfileManager::with_storage(
         config,
         secrets,
@@ -57,6 +68,28 @@ fn create_auth_manager(
     ))
 }
 
+/// Keep OAuth tokens fresh so connectors reading them from SecretStorage
+/// never see an expired access token.
+fn start_oauth_refresh(auth_manager: Arc<AuthProfileManager>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(OAUTH_REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = auth_manager.reload_from_storage().await {
+                warn!(error = %e, "Failed to reload auth profiles");
+            }
+            match auth_manager
+                .refresh_expiring_profiles(chrono::Duration::minutes(OAUTH_REFRESH_WINDOW_MINUTES))
+                .await
+            {
+                Ok(0) => {}
+                Ok(count) => info!(count, "Refreshed OAuth tokens"),
+                Err(e) => warn!(error = %e, "Failed to refresh OAuth tokens"),
+            }
+        }
+    })
+}
+
 impl CliTaskRunner {
     pub fn new(core: Arc<AppCore>) -> Self {
         Self {
@@ -65,6 +98,10 @@ impl CliTaskRunner {
             runner: Arc::new(RwLock::new(None)),
             router: Arc::new(RwLock::new(None)),
             message_handler: None,
+            browser_schedule: None,
+            digests: None,
+            webhooks: None,
+            oauth_refresh: None,
         }
     }
 
@@ -80,7 +117,7 @@ impl CliTaskRunner {
             ProcessRegistry::new().with_ttl_seconds(system_config.agent.process_session_ttl_secs),
         );
 
-        let auth_manager = Arc::new(create_auth_manager(secrets.clone(), storage.get_db())?);
+        let auth_manager = Arc::new(create_auth_manager(secrets.clone(), storage.backend())?);
         if let Ok(data_dir) = paths::ensure_restflow_dir() {
             let old_json = data_dir.join("auth_profiles.json");
             if let Err(e) = auth_manager.migrate_from_json(&old_json).await {
@@ -89,6 +126,7 @@ impl CliTaskRunner {
         }
         auth_manager.initialize().await?;
         auth_manager.discover().await?;
+        self.oauth_refresh = Some(start_oauth_refresh(auth_manager.clone()));
 
         // Create task runtime components
         let (completion_tx, completion_rx) = tokio::sync::mpsc::channel(100);
@@ -117,15 +155,21 @@ impl CliTaskRunner {
             task_config.clone(),
         )
         .with_reply_sender_factory(reply_sender_factory);
-        let notifier = TelegramNotifier::new(secrets);
+        let notifier = Arc::new(TelegramNotifier::new(secrets));
+        let budget_guard =
+            Arc::new(BudgetGuard::from_storage(storage.as_ref()).with_notifier(notifier.clone()));
         let steer_registry = Arc::new(SteerRegistry::new());
         let hook_executor = Arc::new(HookExecutor::with_storage(storage.hooks.clone()));
+        let callback_dispatcher = Arc::new(
+            CallbackDispatcher::new(Arc::new(storage.background_agents.clone()))
+                .with_deliverables(storage.deliverables.clone()),
+        );
 
         let runner = Arc::new(
             TaskRunner::with_memory_persistence(
                 Arc::new(storage.background_agents.clone()),
                 Arc::new(OrchestratingAgentExecutor::from_runtime_executor(executor)),
-                Arc::new(notifier),
+                notifier.clone(),
                 build_runner_config(&system_config),
                 Arc::new(NoopHeartbeatEmitter),
                 storage.memory.clone(),
@@ -133,10 +177,35 @@ impl CliTaskRunner {
             )
             .with_event_emitter(event_emitter)
             .with_channel_router_handle(channel_router.clone())
-            .with_hook_executor(hook_executor),
+            .with_hook_executor(hook_executor)
+            .with_callback_dispatcher(callback_dispatcher)
+            .with_work_item_storage(storage.work_items.clone())
+            .with_budget_guard(budget_guard.clone()),
         );
 
         let handle = runner.clone().start();
+        self.browser_schedule = Some(
+            Arc::new(BrowserScheduleRunner::new(storage.clone()).with_notifier(notifier.clone()))
+                .start(),
+        );
+        self.digests = Some(Arc::new(DigestRunner::new(storage.clone(), notifier)).start());
+        set_daemon_component("browser_schedules", DaemonComponentState::Running, None);
+        set_daemon_component("digests", DaemonComponentState::Running, None);
+        if !system_config.channel_defaults.webhooks.is_empty() {
+            let webhooks = Arc::new(WebhookSinkRunner::new(
+                storage.clone(),
+                &system_config.channel_defaults.webhooks,
+            ));
+            info!(sinks = webhooks.sink_count(), "Webhook sinks started");
+            set_daemon_component(
+                "webhook_sinks",
+                DaemonComponentState::Running,
+                Some(format!("{} sink(s)", webhooks.sink_count())),
+            );
+            self.webhooks = Some(webhooks.start());
+        } else {
+            set_daemon_component("webhook_sinks", DaemonComponentState::Disabled, None);
+        }
 
         {
             let mut handle_guard = self.handle.write().await;
@@ -150,7 +219,7 @@ impl CliTaskRunner {
 
         // Build a shared ChannelRouter from all configured channels
         let mut channel_router = ChannelRouter::new();
-        let mut any_channel_configured = false;
+        let mut configured_channels = Vec::new();
 
         // Try Telegram
         if let Some((tg_channel, default_chat_id)) = telegram::setup_telegram_channel(
@@ -166,7 +235,7 @@ impl CliTaskRunner {
                 );
                 channel_router.register(tg_channel);
             }
-            any_channel_configured = true;
+            configured_channels.push("telegram");
             info!("Telegram channel configured");
         }
 
@@ -179,7 +248,7 @@ impl CliTaskRunner {
             } else {
                 channel_router.register(dc_channel);
             }
-            any_channel_configured = true;
+            configured_channels.push("discord");
             info!("Discord channel configured");
         }
 
@@ -192,11 +261,24 @@ impl CliTaskRunner {
             } else {
                 channel_router.register(sk_channel);
             }
-            any_channel_configured = true;
+            configured_channels.push("slack");
             info!("Slack channel configured");
         }
 
-        if any_channel_configured {
+        // Try Matrix
+        if let Some((mx_channel, default_room_id)) =
+            matrix::setup_matrix_channel(&self.core.storage.secrets)?
+        {
+            if let Some(room_id) = default_room_id {
+                channel_router.register_with_default(mx_channel, room_id);
+            } else {
+                channel_router.register(mx_channel);
+            }
+            configured_channels.push("matrix");
+            info!("Matrix channel configured");
+        }
+
+        if !configured_channels.is_empty() {
             let router = Arc::new(channel_router);
 
             let trigger = Arc::new(CliTaskTrigger::new(
@@ -212,25 +294,30 @@ impl CliTaskRunner {
                     storage.clone(),
                     system_config.runtime_defaults.chat_max_session_history,
                 )
-                .with_default_agent(default_chat_agent_id),
+                .with_default_agent(default_chat_agent_id)
+                .with_route_resolver(RouteResolver::new(Arc::new(storage.pairing.clone()))),
             );
             let debouncer = Arc::new(MessageDebouncer::default_timeout());
             let chat_dispatcher_config = ChatDispatcherConfig {
                 max_session_history: system_config.runtime_defaults.chat_max_session_history,
                 response_timeout_secs: system_config.chat_response_timeout_seconds,
+                voice_replies: system_config.channel_defaults.voice_replies,
                 ..ChatDispatcherConfig::default()
             };
-            let chat_dispatcher = Arc::new(ChatDispatcher::new(
-                session_manager,
-                storage.clone(),
-                auth_manager.clone(),
-                debouncer,
-                router.clone(),
-                chat_dispatcher_config,
-                subagent_tracker.clone(),
-                subagent_definitions.clone(),
-                task_config.clone(),
-            ));
+            let chat_dispatcher = Arc::new(
+                ChatDispatcher::new(
+                    session_manager,
+                    storage.clone(),
+                    auth_manager.clone(),
+                    debouncer,
+                    router.clone(),
+                    chat_dispatcher_config,
+                    subagent_tracker.clone(),
+                    subagent_definitions.clone(),
+                    task_config.clone(),
+                )
+                .with_budget_guard(budget_guard),
+            );
 
             let pairing_manager = Arc::new(PairingManager::new(Arc::new(storage.pairing.clone())));
             bootstrap_default_chat_pairing(&storage.secrets, pairing_manager.as_ref())?;
@@ -254,6 +341,17 @@ impl CliTaskRunner {
             let mut router_guard = self.router.write().await;
             *router_guard = Some(router);
             info!("Channel message handler started with pairing access control");
+            set_daemon_component(
+                "channels",
+                DaemonComponentState::Running,
+                Some(configured_channels.join(", ")),
+            );
+        } else {
+            set_daemon_component(
+                "channels",
+                DaemonComponentState::Disabled,
+                Some("No channel credentials stored".to_string()),
+            );
         }
 
         info!("Task runner started");
@@ -266,6 +364,22 @@ impl CliTaskRunner {
             info!("Message handler stopped");
         }
 
+        if let Some(browser_schedule) = self.browser_schedule.take() {
+            browser_schedule.abort();
+        }
+
+        if let Some(digests) = self.digests.take() {
+            digests.abort();
+        }
+
+        if let Some(webhooks) = self.webhooks.take() {
+            webhooks.abort();
+        }
+
+        if let Some(oauth_refresh) = self.oauth_refresh.take() {
+            oauth_refresh.abort();
+        }
+
         if let Some(handle) = self.handle.write().await.take() {
             handle.stop().await?;
             info!("Task runner stopped");
@@ -301,6 +415,7 @@ fn build_runner_config(system_config: &SystemConfig) -> TaskRunnerConfig {
         worker_count: system_config.worker_count,
         task_timeout_secs: system_config.background_api_timeout_seconds,
         stall_timeout_secs: Some(system_config.stall_timeout_seconds),
+        auto_resume: system_config.runtime_defaults.background_runner_auto_resume,
     }
 }
 
@@ -509,6 +624,7 @@ mod tests {
             runtime_defaults: RuntimeDefaults {
                 background_runner_poll_interval_ms: 12_000,
                 background_runner_max_concurrent_tasks: 4,
+                background_runner_auto_resume: true,
                 ..RuntimeDefaults::default()
             },
             ..SystemConfig::default()
@@ -521,6 +637,7 @@ mod tests {
         assert_eq!(config.worker_count, 6);
         assert_eq!(config.task_timeout_secs, Some(1800));
         assert_eq!(config.stall_timeout_secs, Some(900));
+        assert!(config.auto_resume);
     }
 
     fn env_lock() -> std::sync::MutexGuard<'static, ()> {
@@ -596,6 +713,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("failed to create background agent");
 
@@ -209,6 +209,8 @@ mod tests {
         let defaults = ChannelSettings {
             telegram_api_timeout_secs: 45,
             telegram_polling_timeout_secs: 60,
+            voice_replies: false,
+            webhooks: Vec::new(),
         };
 
         let (channel, _) = setup_telegram_channel(&secrets, &daemon_state, &defaults)
@@ -1,32 +1,56 @@
 use anyhow::{Result, bail};
 use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
 use crate::executor::CommandExecutor;
 use crate::setup;
 use restflow_contracts::{
-    AllowedPeerResponse, CleanupReportResponse, PairingApprovalResponse, PairingOwnerResponse,
-    PairingRequestResponse, PairingStateResponse, RouteBindingResponse,
-    SessionSourceMigrationResponse, request::TaskFromSessionRequest,
+    AllowedPeerResponse, ApiTokenResponse, ArtifactPurgeResponse, ArtifactRemovalResponse,
+    CleanupReportResponse, DatabaseBackupResponse, IssuedApiTokenResponse, PairingApprovalResponse,
+    PairingOwnerResponse, PairingRequestResponse, PairingStateResponse, RouteBindingResponse,
+    SessionSourceMigrationResponse, UserResponse,
+    request::{ApiTokenScope, TaskFromSessionRequest},
 };
 use restflow_core::channel::pairing::PairingManager;
 use restflow_core::channel::route_binding::{RouteBindingType, RouteResolver};
+use restflow_core::daemon::request_mapper::{from_contract, to_contract};
 use restflow_core::memory::{ExportResult, MemoryExporter};
 use restflow_core::models::{
-    AgentNode, Deliverable, ExecutionTimeline, ExecutionTraceQuery, Hook, RunListQuery, RunSummary,
-    SharedEntry, Task, TaskControlAction, TaskConversionResult, TaskPatch, TaskProgress, TaskSpec,
+    AgentNode, AgentTemplate, ApiConnector, BrowserPlan, Deliverable, ExecutionReplay,
+    ExecutionTimeline, ExecutionTraceEvent, ExecutionTraceQuery, ExecutionTraceStats, Hook,
+    PendingApproval, ReplayRunResult, RunListQuery, RunSummary, SavedBrowserPlan, SharedEntry,
+    Task, TaskControlAction, TaskConversionResult, TaskPatch, TaskProgress, TaskSpec,
+    ToolApprovalPolicy, WakeCondition,
 };
 use restflow_core::services::{
-    agent as agent_service, config as config_service, execution_console::ExecutionConsoleService,
-    secrets as secrets_service, session::SessionService, skills as skills_service,
+    agent as agent_service,
+    agent_template::{self as agent_template_service, ProvisionedAgentTemplate},
+    api_connector as api_connector_service,
+    api_tokens::ApiTokenService,
+    browser_plan as browser_plan_service,
+    browser_webhook::BrowserWebhookOutcome,
+    config as config_service,
+    data_bundle::{self, BundleImportReport, DataBundle},
+    execution_console::ExecutionConsoleService,
+    project_config::{self, ProjectApplyReport, ProjectConfig},
+    replay::{ReplayError, ReplayService},
+    secrets as secrets_service,
+    session::SessionService,
+    skills as skills_service,
+    workspace::WorkspaceService,
 };
 use restflow_core::storage::SystemConfig;
 use restflow_core::storage::agent::StoredAgent;
 use restflow_core::{
     AppCore,
     models::{
-        ChatSession, ChatSessionSource, ChatSessionSummary, ItemQuery, MemoryChunk,
-        MemorySearchResult, MemoryStats, Secret, Skill, WorkItem, WorkItemPatch, WorkItemSpec,
+        ApprovalStatus, ChatSearchQuery, ChatSearchResult, ChatSession, ChatSessionSource,
+        ChatSessionSummary, ItemQuery, MemoryChunk, MemorySearchResult, MemoryStats, Secret,
+        SecretAccess, Skill, TagCount, UsageQuery, UsageStats, WorkItem, WorkItemPatch,
+        WorkItemSpec,
     },
 };
 use restflow_storage::PairingStorage;
@@ -79,6 +103,18 @@ impl CommandExecutor for DirectExecutor {
         agent_service::delete_agent(&self.core, id).await
     }
 
+    async fn list_agent_templates(&self) -> Result<Vec<AgentTemplate>> {
+        agent_template_service::list_agent_templates()
+    }
+
+    async fn provision_agent_template(
+        &self,
+        template_id: &str,
+        name: Option<String>,
+    ) -> Result<ProvisionedAgentTemplate> {
+        agent_template_service::provision_agent_template(&self.core, template_id, name).await
+    }
+
     async fn list_skills(&self) -> Result<Vec<Skill>> {
         skills_service::list_skills(&self.core).await
     }
@@ -163,10 +199,45 @@ impl CommandExecutor for DirectExecutor {
         if !tags.is_empty() {
             chunk = chunk.with_tags(tags);
         }
+        let memory_defaults = self
+            .core
+            .storage
+            .config
+            .get_effective_config()
+            .map(|config| config.memory_defaults)
+            .unwrap_or_default();
+        restflow_core::memory::suggest_tags_for_chunk(
+            &self.core.storage.memory,
+            &memory_defaults,
+            &mut chunk,
+        )?;
         let id = self.core.storage.memory.store_chunk(&chunk)?;
         Ok(id)
     }
 
+    async fn tag_memory(
+        &self,
+        chunk_ids: Vec<String>,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+    ) -> Result<u32> {
+        let updated = self
+            .core
+            .storage
+            .memory
+            .update_tags(&chunk_ids, &add_tags, &remove_tags)?;
+        Ok(updated as u32)
+    }
+
+    async fn merge_memory_tags(&self, sources: Vec<String>, target: String) -> Result<u32> {
+        let updated = self.core.storage.memory.merge_tags(&sources, &target)?;
+        Ok(updated as u32)
+    }
+
+    async fn list_memory_tags(&self, agent_id: Option<String>) -> Result<Vec<TagCount>> {
+        self.core.storage.memory.list_tags(agent_id.as_deref())
+    }
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>> {
         self.core.storage.chat_sessions.list_summaries()
     }
@@ -191,21 +262,66 @@ impl CommandExecutor for DirectExecutor {
         sessions.delete_workspace_session(id)
     }
 
-    async fn search_sessions(&self, query: String) -> Result<Vec<ChatSessionSummary>> {
-        let query = query.to_lowercase();
-        let sessions = self.core.storage.chat_sessions.list()?;
-        let matches: Vec<ChatSessionSummary> = sessions
-            .into_iter()
-            .filter(|session| {
-                session.name.to_lowercase().contains(&query)
-                    || session
-                        .messages
-                        .iter()
-                        .any(|message| message.content.to_lowercase().contains(&query))
-            })
-            .map(|session| ChatSessionSummary::from(&session))
-            .collect();
-        Ok(matches)
+    async fn search_chat_sessions(&self, query: ChatSearchQuery) -> Result<ChatSearchResult> {
+        SessionService::from_storage(&self.core.storage).search_messages(&query)
+    }
+
+    async fn get_usage_stats(&self, query: UsageQuery) -> Result<UsageStats> {
+        self.core.storage.usage.stats(&query)
+    }
+
+    async fn query_execution_traces(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<Vec<ExecutionTraceEvent>> {
+        self.core.storage.execution_traces.query(&query)
+    }
+
+    async fn query_execution_trace_stats(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<ExecutionTraceStats> {
+        self.core.storage.execution_traces.stats_for_query(&query)
+    }
+
+    async fn get_execution_replay(&self, id: &str) -> Result<Option<ExecutionReplay>> {
+        match ReplayService::from_storage(&self.core.storage).get_replay(id) {
+            Ok(replay) => Ok(Some(replay)),
+            Err(ReplayError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn replay_execution(
+        &self,
+        _execution_id: &str,
+        _step: u32,
+        _agent_id: Option<String>,
+        _message: Option<String>,
+    ) -> Result<ReplayRunResult> {
+        bail!("Replaying executions requires daemon mode. Use 'restflow daemon start' first.")
+    }
+
+    async fn list_tool_approvals(&self, pending_only: bool) -> Result<Vec<PendingApproval>> {
+        let status = pending_only.then_some(ApprovalStatus::Pending);
+        self.core.storage.tool_approvals.list(status)
+    }
+
+    async fn resolve_tool_approval(
+        &self,
+        _id: &str,
+        _approved: bool,
+        _reason: Option<String>,
+    ) -> Result<PendingApproval> {
+        bail!("Resolving tool approvals requires daemon mode. Use 'restflow daemon start' first.")
+    }
+
+    async fn get_tool_approval_policy(&self) -> Result<ToolApprovalPolicy> {
+        self.core.storage.tool_approvals.get_policy()
+    }
+
+    async fn set_tool_approval_policy(&self, policy: ToolApprovalPolicy) -> Result<()> {
+        self.core.storage.tool_approvals.set_policy(&policy)
     }
 
     async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>> {
@@ -268,6 +384,22 @@ impl CommandExecutor for DirectExecutor {
             .is_some())
     }
 
+    async fn rotate_secret(&self, key: &str, value: &str, expires_at: Option<i64>) -> Result<()> {
+        secrets_service::rotate_secret(&self.core, key, value, expires_at).await
+    }
+
+    async fn set_secret_expiry(&self, key: &str, expires_at: Option<i64>) -> Result<()> {
+        secrets_service::set_secret_expiry(&self.core, key, expires_at).await
+    }
+
+    async fn list_expiring_secrets(&self, before: i64) -> Result<Vec<Secret>> {
+        secrets_service::list_expiring_secrets(&self.core, before).await
+    }
+
+    async fn get_secret_usage(&self, key: &str) -> Result<Vec<SecretAccess>> {
+        secrets_service::get_secret_usage(&self.core, key).await
+    }
+
     async fn get_config(&self) -> Result<SystemConfig> {
         config_service::get_config(&self.core).await
     }
@@ -397,6 +529,57 @@ impl CommandExecutor for DirectExecutor {
         route_resolver(&self.core)?.unbind(id)
     }
 
+    async fn list_api_tokens(&self, include_revoked: bool) -> Result<Vec<ApiTokenResponse>> {
+        ApiTokenService::from_storage(self.core.storage.as_ref())
+            .list(include_revoked)?
+            .into_iter()
+            .map(to_contract)
+            .collect()
+    }
+
+    async fn create_api_token(
+        &self,
+        name: String,
+        scope: ApiTokenScope,
+        expires_at: Option<i64>,
+    ) -> Result<IssuedApiTokenResponse> {
+        let issued = ApiTokenService::from_storage(self.core.storage.as_ref()).issue(
+            name,
+            from_contract(scope)?,
+            expires_at,
+        )?;
+        Ok(IssuedApiTokenResponse {
+            token: to_contract(issued.token)?,
+            secret: issued.secret,
+        })
+    }
+
+    async fn revoke_api_token(&self, id: &str) -> Result<()> {
+        if !ApiTokenService::from_storage(self.core.storage.as_ref()).revoke(id)? {
+            bail!("API token not found: {id}");
+        }
+        Ok(())
+    }
+
+    async fn list_users(&self) -> Result<Vec<UserResponse>> {
+        WorkspaceService::from_storage(self.core.storage.as_ref())
+            .list_users()?
+            .into_iter()
+            .map(to_contract)
+            .collect()
+    }
+
+    async fn create_user(&self, name: &str) -> Result<UserResponse> {
+        to_contract(WorkspaceService::from_storage(self.core.storage.as_ref()).create_user(name)?)
+    }
+
+    async fn delete_user(&self, id: &str) -> Result<()> {
+        if !WorkspaceService::from_storage(self.core.storage.as_ref()).delete_user(id)? {
+            bail!("User not found: {id}");
+        }
+        Ok(())
+    }
+
     async fn run_cleanup(&self) -> Result<CleanupReportResponse> {
         let report = restflow_core::services::cleanup::run_cleanup(&self.core).await?;
         Ok(CleanupReportResponse {
@@ -407,6 +590,8 @@ impl CommandExecutor for DirectExecutor {
             memory_sessions: report.memory_sessions,
             vector_orphans: report.vector_orphans,
             daemon_log_files: report.daemon_log_files,
+            artifacts: report.artifacts,
+            artifact_bytes_reclaimed: report.artifact_bytes_reclaimed,
         })
     }
 
@@ -428,6 +613,73 @@ impl CommandExecutor for DirectExecutor {
         })
     }
 
+    async fn purge_artifacts(&self, dry_run: bool) -> Result<ArtifactPurgeResponse> {
+        let report =
+            restflow_core::services::artifacts::run_artifact_gc(&self.core, dry_run).await?;
+        Ok(ArtifactPurgeResponse {
+            dry_run: report.dry_run,
+            scanned: report.scanned,
+            preserved: report.preserved,
+            removed: report
+                .removed
+                .into_iter()
+                .map(|removal| ArtifactRemovalResponse {
+                    kind: removal.kind.as_str().to_string(),
+                    owner_id: removal.owner_id,
+                    path: removal.path,
+                    size_bytes: removal.size_bytes,
+                    reason: removal.reason.as_str().to_string(),
+                })
+                .collect(),
+            bytes_reclaimed: report.bytes_reclaimed,
+            bytes_remaining: report.bytes_remaining,
+        })
+    }
+
+    async fn backup_database(&self, path: String) -> Result<DatabaseBackupResponse> {
+        let summary = self.core.storage.backup_to(Path::new(&path))?;
+        Ok(DatabaseBackupResponse {
+            path,
+            tables: summary.tables,
+            entries: summary.entries,
+        })
+    }
+
+    async fn restore_database(&self, path: String) -> Result<DatabaseBackupResponse> {
+        let summary = self.core.storage.restore_from(Path::new(&path))?;
+        Ok(DatabaseBackupResponse {
+            path,
+            tables: summary.tables,
+            entries: summary.entries,
+        })
+    }
+
+    async fn export_bundle(&self, secrets_password: Option<String>) -> Result<DataBundle> {
+        data_bundle::export_bundle(&self.core.storage, secrets_password.as_deref())
+    }
+
+    async fn import_bundle(
+        &self,
+        bundle: DataBundle,
+        secrets_password: Option<String>,
+        overwrite: bool,
+    ) -> Result<BundleImportReport> {
+        data_bundle::import_bundle(
+            &self.core.storage,
+            &bundle,
+            secrets_password.as_deref(),
+            overwrite,
+        )
+    }
+
+    async fn apply_project_config(
+        &self,
+        config: ProjectConfig,
+        dry_run: bool,
+    ) -> Result<ProjectApplyReport> {
+        project_config::apply_project_config(&self.core, &config, dry_run).await
+    }
+
     // Task operations - require daemon
     async fn list_tasks(&self, _status: Option<String>) -> Result<Vec<Task>> {
         bail!("Task operations require daemon mode. Use 'restflow daemon start' first.")
@@ -460,6 +712,23 @@ impl CommandExecutor for DirectExecutor {
         bail!("Task operations require daemon mode. Use 'restflow daemon start' first.")
     }
 
+    async fn run_task_with_secrets(
+        &self,
+        _id: &str,
+        _secrets: HashMap<String, String>,
+    ) -> Result<Task> {
+        bail!("Task operations require daemon mode. Use 'restflow daemon start' first.")
+    }
+
+    async fn set_task_wake_condition(
+        &self,
+        _id: &str,
+        _condition: Option<WakeCondition>,
+        _checkpoint_id: Option<String>,
+    ) -> Result<Task> {
+        bail!("Task operations require daemon mode. Use 'restflow daemon start' first.")
+    }
+
     async fn get_task_progress(
         &self,
         _id: &str,
@@ -529,6 +798,67 @@ impl CommandExecutor for DirectExecutor {
     async fn list_deliverables(&self, _task_id: &str) -> Result<Vec<Deliverable>> {
         bail!("Deliverable operations require daemon mode. Use 'restflow daemon start' first.")
     }
+
+    async fn list_browser_plans(&self) -> Result<Vec<SavedBrowserPlan>> {
+        browser_plan_service::list_browser_plans(&self.core).await
+    }
+
+    async fn get_browser_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<SavedBrowserPlan>> {
+        browser_plan_service::get_browser_plan(&self.core, name, version).await
+    }
+
+    async fn list_browser_plan_versions(&self, name: &str) -> Result<Vec<SavedBrowserPlan>> {
+        browser_plan_service::list_browser_plan_versions(&self.core, name).await
+    }
+
+    async fn save_browser_plan(
+        &self,
+        name: &str,
+        description: Option<String>,
+        plan: BrowserPlan,
+    ) -> Result<SavedBrowserPlan> {
+        browser_plan_service::save_browser_plan(&self.core, name, description, plan).await
+    }
+
+    async fn delete_browser_plan(&self, name: &str) -> Result<bool> {
+        browser_plan_service::delete_browser_plan(&self.core, name).await
+    }
+
+    async fn run_browser_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        payload: Option<Value>,
+    ) -> Result<BrowserWebhookOutcome> {
+        browser_plan_service::run_saved_browser_plan(&self.core, name, version, payload).await
+    }
+
+    async fn list_api_connectors(&self) -> Result<Vec<ApiConnector>> {
+        api_connector_service::list_api_connectors(&self.core).await
+    }
+
+    async fn get_api_connector(&self, name: &str) -> Result<Option<ApiConnector>> {
+        api_connector_service::get_api_connector(&self.core, name).await
+    }
+
+    async fn import_api_connector(
+        &self,
+        name: &str,
+        spec: String,
+        base_url: Option<String>,
+        auth_secret: Option<String>,
+    ) -> Result<ApiConnector> {
+        api_connector_service::import_api_connector(&self.core, name, &spec, base_url, auth_secret)
+            .await
+    }
+
+    async fn delete_api_connector(&self, name: &str) -> Result<bool> {
+        api_connector_service::delete_api_connector(&self.core, name).await
+    }
 }
 
 async fn resolve_agent_id(core: &Arc<AppCore>, agent_id: Option<String>) -> Result<String> {
@@ -545,12 +875,12 @@ async fn resolve_agent_id(core: &Arc<AppCore>, agent_id: Option<String>) -> Resu
 }
 
 fn pairing_manager(core: &Arc<AppCore>) -> Result<PairingManager> {
-    let storage = Arc::new(PairingStorage::new(core.storage.get_db())?);
+    let storage = Arc::new(PairingStorage::with_backend(core.storage.backend())?);
     Ok(PairingManager::new(storage))
 }
 
 fn route_resolver(core: &Arc<AppCore>) -> Result<RouteResolver> {
-    let storage = Arc::new(PairingStorage::new(core.storage.get_db())?);
+    let storage = Arc::new(PairingStorage::with_backend(core.storage.backend())?);
     Ok(RouteResolver::new(storage))
 }
 
@@ -582,13 +912,14 @@ fn auto_bind_owner_chat_id_if_missing(
 fn normalize_route_binding_type(binding_type: &str, target_id: &str) -> RouteBindingType {
     match binding_type {
         "peer" => RouteBindingType::Peer,
+        "account" => RouteBindingType::Account,
         "default" => RouteBindingType::Default,
         "group" => {
             tracing::warn!(
                 target_id = %target_id,
                 "Using deprecated --group flag, consider using --channel instead"
             );
-            RouteBindingType::Channel
+            RouteBindingType::Group
         }
         _ => RouteBindingType::Channel,
     }
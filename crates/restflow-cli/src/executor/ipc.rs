@@ -1,10 +1,14 @@
 use anyhow::{Result, bail};
 use async_trait::async_trait;
 use restflow_contracts::{
-    CleanupReportResponse, ClearResponse, IdResponse, OkResponse, PairingApprovalResponse,
-    PairingOwnerResponse, PairingStateResponse, RouteBindingResponse,
-    SessionSourceMigrationResponse, request::TaskFromSessionRequest,
+    ApiTokenResponse, ArtifactPurgeResponse, CleanupReportResponse, ClearResponse,
+    DatabaseBackupResponse, IdResponse, IssuedApiTokenResponse, OkResponse,
+    PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse, RouteBindingResponse,
+    SessionSourceMigrationResponse, UpdatedResponse, UserResponse,
+    request::{ApiTokenScope, TaskFromSessionRequest},
 };
+use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 use tokio::sync::Mutex;
 
@@ -13,11 +17,18 @@ use restflow_core::daemon::request_mapper::to_contract;
 use restflow_core::daemon::{IpcClient, IpcRequest};
 use restflow_core::memory::ExportResult;
 use restflow_core::models::{
-    AgentNode, ChatSession, ChatSessionSummary, Deliverable, ExecutionTimeline, ItemQuery,
-    MemoryChunk, MemorySearchResult, MemoryStats, RunListQuery, RunSummary, Secret, SharedEntry,
-    Skill, Task, TaskControlAction, TaskConversionResult, TaskMessage, TaskPatch, TaskProgress,
-    TaskSpec, WorkItem, WorkItemPatch, WorkItemSpec,
+    AgentNode, AgentTemplate, ApiConnector, BrowserPlan, ChatSearchQuery, ChatSearchResult,
+    ChatSession, ChatSessionSummary, Deliverable, ExecutionReplay, ExecutionTimeline,
+    ExecutionTraceEvent, ExecutionTraceQuery, ExecutionTraceStats, ItemQuery, MemoryChunk,
+    MemorySearchResult, MemoryStats, PendingApproval, ReplayRunResult, RunListQuery, RunSummary,
+    SavedBrowserPlan, Secret, SecretAccess, SharedEntry, Skill, TagCount, Task, TaskControlAction,
+    TaskConversionResult, TaskMessage, TaskPatch, TaskProgress, TaskSpec, ToolApprovalPolicy,
+    UsageQuery, UsageStats, WakeCondition, WorkItem, WorkItemPatch, WorkItemSpec,
 };
+use restflow_core::services::agent_template::ProvisionedAgentTemplate;
+use restflow_core::services::browser_webhook::BrowserWebhookOutcome;
+use restflow_core::services::data_bundle::{BundleImportReport, DataBundle};
+use restflow_core::services::project_config::{ProjectApplyReport, ProjectConfig};
 use restflow_core::storage::SystemConfig;
 use restflow_core::storage::agent::StoredAgent;
 
@@ -86,6 +97,22 @@ impl CommandExecutor for IpcExecutor {
         Ok(())
     }
 
+    async fn list_agent_templates(&self) -> Result<Vec<AgentTemplate>> {
+        self.request_typed(IpcRequest::ListAgentTemplates).await
+    }
+
+    async fn provision_agent_template(
+        &self,
+        template_id: &str,
+        name: Option<String>,
+    ) -> Result<ProvisionedAgentTemplate> {
+        self.request_typed(IpcRequest::ProvisionAgentTemplate {
+            template_id: template_id.to_string(),
+            name,
+        })
+        .await
+    }
+
     async fn list_skills(&self) -> Result<Vec<Skill>> {
         self.request_typed(IpcRequest::ListSkills).await
     }
@@ -177,6 +204,34 @@ impl CommandExecutor for IpcExecutor {
         Ok(resp.id)
     }
 
+    async fn tag_memory(
+        &self,
+        chunk_ids: Vec<String>,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+    ) -> Result<u32> {
+        let resp: UpdatedResponse = self
+            .request_typed(IpcRequest::TagMemory {
+                chunk_ids,
+                add_tags,
+                remove_tags,
+            })
+            .await?;
+        Ok(resp.updated)
+    }
+
+    async fn merge_memory_tags(&self, sources: Vec<String>, target: String) -> Result<u32> {
+        let resp: UpdatedResponse = self
+            .request_typed(IpcRequest::MergeMemoryTags { sources, target })
+            .await?;
+        Ok(resp.updated)
+    }
+
+    async fn list_memory_tags(&self, agent_id: Option<String>) -> Result<Vec<TagCount>> {
+        self.request_typed(IpcRequest::ListMemoryTags { agent_id })
+            .await
+    }
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>> {
         let mut client = self.client.lock().await;
         client.list_sessions().await
@@ -199,9 +254,75 @@ impl CommandExecutor for IpcExecutor {
         client.delete_session(id.to_string()).await
     }
 
-    async fn search_sessions(&self, query: String) -> Result<Vec<ChatSessionSummary>> {
+    async fn search_chat_sessions(&self, query: ChatSearchQuery) -> Result<ChatSearchResult> {
+        let mut client = self.client.lock().await;
+        client.search_chat_sessions(query).await
+    }
+
+    async fn get_usage_stats(&self, query: UsageQuery) -> Result<UsageStats> {
         let mut client = self.client.lock().await;
-        client.search_sessions(query).await
+        client.get_usage_stats(query).await
+    }
+
+    async fn query_execution_traces(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<Vec<ExecutionTraceEvent>> {
+        let mut client = self.client.lock().await;
+        client.query_execution_traces(query).await
+    }
+
+    async fn query_execution_trace_stats(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<ExecutionTraceStats> {
+        let mut client = self.client.lock().await;
+        client.query_execution_trace_stats(query).await
+    }
+
+    async fn get_execution_replay(&self, id: &str) -> Result<Option<ExecutionReplay>> {
+        let mut client = self.client.lock().await;
+        client.get_execution_replay(id.to_string()).await
+    }
+
+    async fn replay_execution(
+        &self,
+        execution_id: &str,
+        step: u32,
+        agent_id: Option<String>,
+        message: Option<String>,
+    ) -> Result<ReplayRunResult> {
+        let mut client = self.client.lock().await;
+        client
+            .replay_execution(execution_id.to_string(), step, agent_id, message)
+            .await
+    }
+
+    async fn list_tool_approvals(&self, pending_only: bool) -> Result<Vec<PendingApproval>> {
+        let mut client = self.client.lock().await;
+        client.list_tool_approvals(pending_only).await
+    }
+
+    async fn resolve_tool_approval(
+        &self,
+        id: &str,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Result<PendingApproval> {
+        let mut client = self.client.lock().await;
+        client
+            .resolve_tool_approval(id.to_string(), approved, reason)
+            .await
+    }
+
+    async fn get_tool_approval_policy(&self) -> Result<ToolApprovalPolicy> {
+        let mut client = self.client.lock().await;
+        client.get_tool_approval_policy().await
+    }
+
+    async fn set_tool_approval_policy(&self, policy: ToolApprovalPolicy) -> Result<()> {
+        let mut client = self.client.lock().await;
+        client.set_tool_approval_policy(policy).await
     }
 
     async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>> {
@@ -306,6 +427,39 @@ impl CommandExecutor for IpcExecutor {
         Ok(response.is_some())
     }
 
+    async fn rotate_secret(&self, key: &str, value: &str, expires_at: Option<i64>) -> Result<()> {
+        let _: OkResponse = self
+            .request_typed(IpcRequest::RotateSecret {
+                key: key.to_string(),
+                value: value.to_string(),
+                expires_at,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn set_secret_expiry(&self, key: &str, expires_at: Option<i64>) -> Result<()> {
+        let _: OkResponse = self
+            .request_typed(IpcRequest::SetSecretExpiry {
+                key: key.to_string(),
+                expires_at,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn list_expiring_secrets(&self, before: i64) -> Result<Vec<Secret>> {
+        self.request_typed(IpcRequest::ListExpiringSecrets { before })
+            .await
+    }
+
+    async fn get_secret_usage(&self, key: &str) -> Result<Vec<SecretAccess>> {
+        self.request_typed(IpcRequest::GetSecretUsage {
+            key: key.to_string(),
+        })
+        .await
+    }
+
     async fn get_config(&self) -> Result<SystemConfig> {
         self.request_typed(IpcRequest::GetConfig).await
     }
@@ -424,6 +578,50 @@ impl CommandExecutor for IpcExecutor {
         Ok(resp.deleted)
     }
 
+    async fn list_api_tokens(&self, include_revoked: bool) -> Result<Vec<ApiTokenResponse>> {
+        self.request_typed(IpcRequest::ListApiTokens { include_revoked })
+            .await
+    }
+
+    async fn create_api_token(
+        &self,
+        name: String,
+        scope: ApiTokenScope,
+        expires_at: Option<i64>,
+    ) -> Result<IssuedApiTokenResponse> {
+        self.request_typed(IpcRequest::CreateApiToken {
+            name,
+            scope,
+            expires_at,
+        })
+        .await
+    }
+
+    async fn revoke_api_token(&self, id: &str) -> Result<()> {
+        let _: OkResponse = self
+            .request_typed(IpcRequest::RevokeApiToken { id: id.to_string() })
+            .await?;
+        Ok(())
+    }
+
+    async fn list_users(&self) -> Result<Vec<UserResponse>> {
+        self.request_typed(IpcRequest::ListUsers).await
+    }
+
+    async fn create_user(&self, name: &str) -> Result<UserResponse> {
+        self.request_typed(IpcRequest::CreateUser {
+            name: name.to_string(),
+        })
+        .await
+    }
+
+    async fn delete_user(&self, id: &str) -> Result<()> {
+        let _: restflow_contracts::DeleteResponse = self
+            .request_typed(IpcRequest::DeleteUser { id: id.to_string() })
+            .await?;
+        Ok(())
+    }
+
     async fn run_cleanup(&self) -> Result<CleanupReportResponse> {
         self.request_typed(IpcRequest::RunCleanup).await
     }
@@ -436,6 +634,52 @@ impl CommandExecutor for IpcExecutor {
             .await
     }
 
+    async fn purge_artifacts(&self, dry_run: bool) -> Result<ArtifactPurgeResponse> {
+        self.request_typed(IpcRequest::PurgeArtifacts { dry_run })
+            .await
+    }
+
+    async fn backup_database(&self, path: String) -> Result<DatabaseBackupResponse> {
+        self.request_typed(IpcRequest::BackupDatabase { path })
+            .await
+    }
+
+    async fn restore_database(&self, path: String) -> Result<DatabaseBackupResponse> {
+        self.request_typed(IpcRequest::RestoreDatabase { path })
+            .await
+    }
+
+    async fn export_bundle(&self, secrets_password: Option<String>) -> Result<DataBundle> {
+        self.request_typed(IpcRequest::ExportBundle { secrets_password })
+            .await
+    }
+
+    async fn import_bundle(
+        &self,
+        bundle: DataBundle,
+        secrets_password: Option<String>,
+        overwrite: bool,
+    ) -> Result<BundleImportReport> {
+        self.request_typed(IpcRequest::ImportBundle {
+            bundle: serde_json::to_value(bundle)?,
+            secrets_password,
+            overwrite,
+        })
+        .await
+    }
+
+    async fn apply_project_config(
+        &self,
+        config: ProjectConfig,
+        dry_run: bool,
+    ) -> Result<ProjectApplyReport> {
+        self.request_typed(IpcRequest::ApplyProjectConfig {
+            config: serde_json::to_value(config)?,
+            dry_run,
+        })
+        .await
+    }
+
     // Task operations - use IPC client methods
     async fn list_tasks(&self, status: Option<String>) -> Result<Vec<Task>> {
         let mut client = self.client.lock().await;
@@ -478,6 +722,27 @@ impl CommandExecutor for IpcExecutor {
         client.control_task(id.to_string(), action).await
     }
 
+    async fn run_task_with_secrets(
+        &self,
+        id: &str,
+        secrets: HashMap<String, String>,
+    ) -> Result<Task> {
+        let mut client = self.client.lock().await;
+        client.run_task_with_secrets(id.to_string(), secrets).await
+    }
+
+    async fn set_task_wake_condition(
+        &self,
+        id: &str,
+        condition: Option<WakeCondition>,
+        checkpoint_id: Option<String>,
+    ) -> Result<Task> {
+        let mut client = self.client.lock().await;
+        client
+            .set_task_wake_condition(id.to_string(), condition, checkpoint_id)
+            .await
+    }
+
     async fn get_task_progress(
         &self,
         id: &str,
@@ -546,4 +811,93 @@ impl CommandExecutor for IpcExecutor {
     async fn list_deliverables(&self, _task_id: &str) -> Result<Vec<Deliverable>> {
         bail!("Deliverable operations are not yet available via CLI. Use MCP tools instead.")
     }
+
+    // Browser plan operations
+    async fn list_browser_plans(&self) -> Result<Vec<SavedBrowserPlan>> {
+        self.request_typed(IpcRequest::ListBrowserPlans).await
+    }
+
+    async fn get_browser_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<SavedBrowserPlan>> {
+        self.request_optional(IpcRequest::GetBrowserPlan {
+            name: name.to_string(),
+            version,
+        })
+        .await
+    }
+
+    async fn list_browser_plan_versions(&self, name: &str) -> Result<Vec<SavedBrowserPlan>> {
+        self.request_typed(IpcRequest::ListBrowserPlanVersions {
+            name: name.to_string(),
+        })
+        .await
+    }
+
+    async fn save_browser_plan(
+        &self,
+        name: &str,
+        description: Option<String>,
+        plan: BrowserPlan,
+    ) -> Result<SavedBrowserPlan> {
+        self.request_typed(IpcRequest::SaveBrowserPlan {
+            name: name.to_string(),
+            description,
+            actions: plan.actions,
+            timeout_secs: plan.timeout_secs,
+        })
+        .await
+    }
+
+    async fn delete_browser_plan(&self, name: &str) -> Result<bool> {
+        let mut client = self.client.lock().await;
+        client.delete_browser_plan(name.to_string()).await
+    }
+
+    async fn run_browser_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        payload: Option<Value>,
+    ) -> Result<BrowserWebhookOutcome> {
+        let mut client = self.client.lock().await;
+        client
+            .run_browser_plan(name.to_string(), version, payload)
+            .await
+    }
+
+    // API connector operations
+    async fn list_api_connectors(&self) -> Result<Vec<ApiConnector>> {
+        self.request_typed(IpcRequest::ListApiConnectors).await
+    }
+
+    async fn get_api_connector(&self, name: &str) -> Result<Option<ApiConnector>> {
+        self.request_optional(IpcRequest::GetApiConnector {
+            name: name.to_string(),
+        })
+        .await
+    }
+
+    async fn import_api_connector(
+        &self,
+        name: &str,
+        spec: String,
+        base_url: Option<String>,
+        auth_secret: Option<String>,
+    ) -> Result<ApiConnector> {
+        self.request_typed(IpcRequest::ImportApiConnector {
+            name: name.to_string(),
+            spec,
+            base_url,
+            auth_secret,
+        })
+        .await
+    }
+
+    async fn delete_api_connector(&self, name: &str) -> Result<bool> {
+        let mut client = self.client.lock().await;
+        client.delete_api_connector(name.to_string()).await
+    }
 }
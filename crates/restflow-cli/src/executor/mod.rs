@@ -1,21 +1,31 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use restflow_contracts::{
-    CleanupReportResponse, PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse,
-    RouteBindingResponse, SessionSourceMigrationResponse, ToolExecutionResult,
-    request::TaskFromSessionRequest,
+    ApiTokenResponse, ArtifactPurgeResponse, CleanupReportResponse, DatabaseBackupResponse,
+    IssuedApiTokenResponse, PairingApprovalResponse, PairingOwnerResponse, PairingStateResponse,
+    RouteBindingResponse, SessionSourceMigrationResponse, ToolExecutionResult, UserResponse,
+    request::{ApiTokenScope, TaskFromSessionRequest},
 };
 use restflow_core::daemon::is_daemon_available;
 use restflow_core::memory::ExportResult;
 use restflow_core::models::{
-    AgentNode, ChatSession, ChatSessionSummary, Deliverable, ExecutionTimeline, Hook, ItemQuery,
-    MemoryChunk, MemorySearchResult, MemoryStats, RunListQuery, RunSummary, Secret, SharedEntry,
-    Skill, Task, TaskControlAction, TaskConversionResult, TaskPatch, TaskProgress, TaskSpec,
-    WorkItem, WorkItemPatch, WorkItemSpec,
+    AgentNode, AgentTemplate, ApiConnector, BrowserPlan, ChatSearchQuery, ChatSearchResult,
+    ChatSession, ChatSessionSummary, Deliverable, ExecutionReplay, ExecutionTimeline,
+    ExecutionTraceEvent, ExecutionTraceQuery, ExecutionTraceStats, Hook, ItemQuery, MemoryChunk,
+    MemorySearchResult, MemoryStats, PendingApproval, ReplayRunResult, RunListQuery, RunSummary,
+    SavedBrowserPlan, Secret, SecretAccess, SharedEntry, Skill, TagCount, Task, TaskControlAction,
+    TaskConversionResult, TaskPatch, TaskProgress, TaskSpec, ToolApprovalPolicy, UsageQuery,
+    UsageStats, WakeCondition, WorkItem, WorkItemPatch, WorkItemSpec,
 };
 use restflow_core::paths;
+use restflow_core::services::agent_template::ProvisionedAgentTemplate;
+use restflow_core::services::browser_webhook::BrowserWebhookOutcome;
+use restflow_core::services::data_bundle::{BundleImportReport, DataBundle};
+use restflow_core::services::project_config::{ProjectApplyReport, ProjectConfig};
 use restflow_core::storage::SystemConfig;
 use restflow_core::storage::agent::StoredAgent;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // DirectExecutor exists only for isolated command tests. Production CLI commands always
@@ -38,6 +48,12 @@ pub trait CommandExecutor: Send + Sync {
         agent: Option<AgentNode>,
     ) -> Result<StoredAgent>;
     async fn delete_agent(&self, id: &str) -> Result<()>;
+    async fn list_agent_templates(&self) -> Result<Vec<AgentTemplate>>;
+    async fn provision_agent_template(
+        &self,
+        template_id: &str,
+        name: Option<String>,
+    ) -> Result<ProvisionedAgentTemplate>;
 
     async fn list_skills(&self) -> Result<Vec<Skill>>;
     async fn get_skill(&self, id: &str) -> Result<Option<Skill>>;
@@ -65,12 +81,47 @@ pub trait CommandExecutor: Send + Sync {
         content: &str,
         tags: Vec<String>,
     ) -> Result<String>;
+    async fn tag_memory(
+        &self,
+        chunk_ids: Vec<String>,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+    ) -> Result<u32>;
+    async fn merge_memory_tags(&self, sources: Vec<String>, target: String) -> Result<u32>;
+    async fn list_memory_tags(&self, agent_id: Option<String>) -> Result<Vec<TagCount>>;
 
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>>;
     async fn get_session(&self, id: &str) -> Result<ChatSession>;
     async fn create_session(&self, agent_id: String, model: String) -> Result<ChatSession>;
     async fn delete_session(&self, id: &str) -> Result<bool>;
-    async fn search_sessions(&self, query: String) -> Result<Vec<ChatSessionSummary>>;
+    async fn search_chat_sessions(&self, query: ChatSearchQuery) -> Result<ChatSearchResult>;
+    async fn get_usage_stats(&self, query: UsageQuery) -> Result<UsageStats>;
+    async fn query_execution_traces(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<Vec<ExecutionTraceEvent>>;
+    async fn query_execution_trace_stats(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<ExecutionTraceStats>;
+    async fn get_execution_replay(&self, id: &str) -> Result<Option<ExecutionReplay>>;
+    async fn replay_execution(
+        &self,
+        execution_id: &str,
+        step: u32,
+        agent_id: Option<String>,
+        message: Option<String>,
+    ) -> Result<ReplayRunResult>;
+
+    async fn list_tool_approvals(&self, pending_only: bool) -> Result<Vec<PendingApproval>>;
+    async fn resolve_tool_approval(
+        &self,
+        id: &str,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Result<PendingApproval>;
+    async fn get_tool_approval_policy(&self) -> Result<ToolApprovalPolicy>;
+    async fn set_tool_approval_policy(&self, policy: ToolApprovalPolicy) -> Result<()>;
 
     async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>>;
     async fn get_note(&self, id: &str) -> Result<Option<WorkItem>>;
@@ -97,6 +148,10 @@ pub trait CommandExecutor: Send + Sync {
     ) -> Result<()>;
     async fn delete_secret(&self, key: &str) -> Result<()>;
     async fn has_secret(&self, key: &str) -> Result<bool>;
+    async fn rotate_secret(&self, key: &str, value: &str, expires_at: Option<i64>) -> Result<()>;
+    async fn set_secret_expiry(&self, key: &str, expires_at: Option<i64>) -> Result<()>;
+    async fn list_expiring_secrets(&self, before: i64) -> Result<Vec<Secret>>;
+    async fn get_secret_usage(&self, key: &str) -> Result<Vec<SecretAccess>>;
 
     async fn get_config(&self) -> Result<SystemConfig>;
     async fn get_global_config(&self) -> Result<SystemConfig>;
@@ -127,11 +182,39 @@ pub trait CommandExecutor: Send + Sync {
     ) -> Result<RouteBindingResponse>;
     async fn unbind_route(&self, id: &str) -> Result<bool>;
 
+    async fn list_api_tokens(&self, include_revoked: bool) -> Result<Vec<ApiTokenResponse>>;
+    async fn create_api_token(
+        &self,
+        name: String,
+        scope: ApiTokenScope,
+        expires_at: Option<i64>,
+    ) -> Result<IssuedApiTokenResponse>;
+    async fn revoke_api_token(&self, id: &str) -> Result<()>;
+
+    async fn list_users(&self) -> Result<Vec<UserResponse>>;
+    async fn create_user(&self, name: &str) -> Result<UserResponse>;
+    async fn delete_user(&self, id: &str) -> Result<()>;
+
     async fn run_cleanup(&self) -> Result<CleanupReportResponse>;
     async fn migrate_session_sources(
         &self,
         dry_run: bool,
     ) -> Result<SessionSourceMigrationResponse>;
+    async fn purge_artifacts(&self, dry_run: bool) -> Result<ArtifactPurgeResponse>;
+    async fn backup_database(&self, path: String) -> Result<DatabaseBackupResponse>;
+    async fn restore_database(&self, path: String) -> Result<DatabaseBackupResponse>;
+    async fn export_bundle(&self, secrets_password: Option<String>) -> Result<DataBundle>;
+    async fn import_bundle(
+        &self,
+        bundle: DataBundle,
+        secrets_password: Option<String>,
+        overwrite: bool,
+    ) -> Result<BundleImportReport>;
+    async fn apply_project_config(
+        &self,
+        config: ProjectConfig,
+        dry_run: bool,
+    ) -> Result<ProjectApplyReport>;
 
     // Task operations
     async fn list_tasks(&self, status: Option<String>) -> Result<Vec<Task>>;
@@ -144,6 +227,17 @@ pub trait CommandExecutor: Send + Sync {
     async fn update_task(&self, id: &str, patch: TaskPatch) -> Result<Task>;
     async fn delete_task(&self, id: &str) -> Result<restflow_contracts::DeleteWithIdResponse>;
     async fn control_task(&self, id: &str, action: TaskControlAction) -> Result<Task>;
+    async fn run_task_with_secrets(
+        &self,
+        id: &str,
+        secrets: HashMap<String, String>,
+    ) -> Result<Task>;
+    async fn set_task_wake_condition(
+        &self,
+        id: &str,
+        condition: Option<WakeCondition>,
+        checkpoint_id: Option<String>,
+    ) -> Result<Task>;
     async fn get_task_progress(&self, id: &str, event_limit: Option<usize>)
     -> Result<TaskProgress>;
     async fn send_task_message(&self, id: &str, message: &str) -> Result<()>;
@@ -163,6 +257,40 @@ pub trait CommandExecutor: Send + Sync {
 
     // Deliverable operations
     async fn list_deliverables(&self, task_id: &str) -> Result<Vec<Deliverable>>;
+
+    // Browser plan operations
+    async fn list_browser_plans(&self) -> Result<Vec<SavedBrowserPlan>>;
+    async fn get_browser_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<SavedBrowserPlan>>;
+    async fn list_browser_plan_versions(&self, name: &str) -> Result<Vec<SavedBrowserPlan>>;
+    async fn save_browser_plan(
+        &self,
+        name: &str,
+        description: Option<String>,
+        plan: BrowserPlan,
+    ) -> Result<SavedBrowserPlan>;
+    async fn delete_browser_plan(&self, name: &str) -> Result<bool>;
+    async fn run_browser_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        payload: Option<Value>,
+    ) -> Result<BrowserWebhookOutcome>;
+
+    // API connector operations
+    async fn list_api_connectors(&self) -> Result<Vec<ApiConnector>>;
+    async fn get_api_connector(&self, name: &str) -> Result<Option<ApiConnector>>;
+    async fn import_api_connector(
+        &self,
+        name: &str,
+        spec: String,
+        base_url: Option<String>,
+        auth_secret: Option<String>,
+    ) -> Result<ApiConnector>;
+    async fn delete_api_connector(&self, name: &str) -> Result<bool>;
 }
 
 pub async fn create(db_path: Option<String>) -> Result<Arc<dyn CommandExecutor>> {
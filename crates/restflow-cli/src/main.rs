@@ -6,6 +6,7 @@ mod error;
 mod executor;
 mod output;
 mod setup;
+mod telemetry;
 #[cfg(test)]
 mod test_support;
 
@@ -18,45 +19,56 @@ use std::io::IsTerminal;
 use restflow_core::paths;
 use std::io;
 use restflow_tui::{TuiLaunchOptions, run_tui};
+use telemetry::OtlpExport;
 use tracing_appender::non_blocking::WorkerGuard;
-
-fn init_logging(verbose: bool) -> Option<WorkerGuard> {
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Returns the exporter before the log guard so spans flush while file logging
+/// is still running.
+fn init_logging(verbose: bool) -> (Option<OtlpExport>, Option<WorkerGuard>) {
     let level = if verbose { "debug" } else { "info" };
-
-    if let Ok(base_dir) = paths::ensure_restflow_dir() {
-        let log_dir = base_dir.join("logs");
-        if std::fs::create_dir_all(&log_dir).is_ok() {
-            let probe_path = log_dir.join(".write-probe");
-            let probe_result = std::fs::OpenOptions::new()
-                .create(true)
-                .write(true)
-                .truncate(true)
-                .open(&probe_path);
-
-            if probe_result.is_ok() {
-                let _ = std::fs::remove_file(&probe_path);
-                let file_appender = tracing_appender::rolling::daily(log_dir, "restflow.log");
-                let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-                tracing_subscriber::fmt()
-                    .with_writer(non_blocking)
-                    .with_ansi(false)
-                    .with_target(false)
-                    .with_level(true)
-                    .with_env_filter(level)
-                    .init();
-                return Some(guard);
-            }
-        }
-    }
-
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_ansi(false)
-        .with_target(false)
-        .with_level(true)
-        .with_env_filter(level)
+    let (writer, guard) = match log_file_writer() {
+        Some((writer, guard)) => (writer, Some(guard)),
+        None => (BoxMakeWriter::new(std::io::stderr), None),
+    };
+    let otlp = OtlpExport::from_config();
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(level))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .with_ansi(false)
+                .with_target(false)
+                .with_level(true),
+        )
+        .with(
+            otlp.as_ref()
+                .map(|export| tracing_opentelemetry::layer().with_tracer(export.tracer())),
+        )
         .init();
-    None
+    (otlp, guard)
+}
+
+/// Daily rolling writer under `~/.restflow/logs`, if that directory is writable.
+fn log_file_writer() -> Option<(BoxMakeWriter, WorkerGuard)> {
+    let log_dir = paths::ensure_restflow_dir().ok()?.join("logs");
+    std::fs::create_dir_all(&log_dir).ok()?;
+    let probe_path = log_dir.join(".write-probe");
+    std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&probe_path)
+        .ok()?;
+    let _ = std::fs::remove_file(&probe_path);
+
+    let file_appender = tracing_appender::rolling::daily(log_dir, "restflow.log");
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+    Some((BoxMakeWriter::new(non_blocking), guard))
 }
 
 fn command_needs_direct_core(command: &Option<Commands>) -> bool {
@@ -81,6 +93,9 @@ fn should_launch_tui_by_default(
 
 #[tokio::main]
 async fn main() {
+    // Sandboxed commands re-execute this binary as their launcher on Windows.
+    restflow_sandbox::run_launcher_if_requested();
+
     if let Err(err) = run().await {
         error::handle_error(err);
     }
@@ -89,7 +104,7 @@ async fn main() {
 async fn run() -> Result<()> {
     let cli = Cli::parse();
     let _config = config::CliConfig::load();
-    let _log_guard = init_logging(cli.verbose);
+    let _log_guards = init_logging(cli.verbose);
 
     if should_launch_tui_by_default(
         &cli.command,
@@ -153,6 +168,10 @@ async fn run() -> Result<()> {
         };
     }
 
+    if let Some(Commands::Model { command }) = &cli.command {
+        return commands::model::run(command.clone(), cli.format).await;
+    }
+
     if let Some(Commands::Mcp { command }) = &cli.command {
         return commands::mcp::run(command.clone(), cli.format).await;
     }
@@ -209,11 +228,20 @@ async fn run() -> Result<()> {
             Some(Commands::Route { command }) => {
                 commands::pairing::run_route(exec, command, cli.format).await
             }
+            Some(Commands::Token { command }) => {
+                commands::token::run(exec, command, cli.format).await
+            }
+            Some(Commands::User { command }) => {
+                commands::user::run(exec, command, cli.format).await
+            }
             Some(Commands::Maintenance { command }) => {
                 commands::maintenance::run(exec, command, cli.format).await
             }
+            Some(Commands::Project { command }) => {
+                commands::project::run(exec, command, cli.format).await
+            }
             Some(Commands::Security { command }) => {
-                commands::security::run(command, cli.format).await
+                commands::security::run(exec, command, cli.format).await
             }
             Some(Commands::Task { command }) => task_commands::run(exec, command, cli.format).await,
             Some(Commands::Team { command }) => {
@@ -228,6 +256,19 @@ async fn run() -> Result<()> {
             Some(Commands::Trigger { command }) => {
                 commands::trigger::run(exec, command, cli.format).await
             }
+            Some(Commands::BrowserPlan { command }) => {
+                commands::browser_plan::run(exec, command, cli.format).await
+            }
+            Some(Commands::Connector { command }) => {
+                commands::connector::run(exec, command, cli.format).await
+            }
+            Some(Commands::Usage(args)) => commands::usage::run(exec, args, cli.format).await,
+            Some(Commands::Audit { command }) => {
+                commands::audit::run(exec, command, cli.format).await
+            }
+            Some(Commands::Replay { command }) => {
+                commands::replay::run(exec, command, cli.format).await
+            }
             Some(Commands::Info) => commands::info::run(),
             Some(Commands::Completions { .. }) => Ok(()),
             Some(Commands::Stop) => Ok(()),
@@ -0,0 +1,104 @@
+//! Optional OpenTelemetry export of tracing spans.
+//!
+//! When `system.otlp_endpoint` is set, the `agent.run`, `llm.completion` and
+//! `tool.execute` spans are exported over OTLP/HTTP, so a slow run can be
+//! inspected end to end in Jaeger or any other OTLP collector. The endpoint is
+//! read once at startup.
+
+use anyhow::{Context, Result};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use restflow_storage::ConfigStorage;
+
+const SERVICE_NAME: &str = "restflow";
+const TRACES_PATH: &str = "/v1/traces";
+
+/// Span exporter kept alive for the process; flushes pending spans on drop.
+pub struct OtlpExport {
+    provider: SdkTracerProvider,
+}
+
+impl OtlpExport {
+    /// Start exporting to the configured endpoint, if any.
+    ///
+    /// Runs before logging is initialized, so problems are reported on stderr.
+    pub fn from_config() -> Option<Self> {
+        let endpoint = match ConfigStorage::default().get_effective_config() {
+            Ok(config) => config.otlp_endpoint?,
+            Err(error) => {
+                eprintln!("Failed to read OTLP endpoint from config: {error:#}");
+                return None;
+            }
+        };
+        let endpoint = endpoint.trim();
+        if endpoint.is_empty() {
+            return None;
+        }
+        match Self::new(endpoint) {
+            Ok(export) => Some(export),
+            Err(error) => {
+                eprintln!("Failed to start OTLP export to {endpoint}: {error:#}");
+                None
+            }
+        }
+    }
+
+    fn new(endpoint: &str) -> Result<Self> {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(traces_url(endpoint))
+            .build()
+            .context("build OTLP span exporter")?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+            .build();
+        Ok(Self { provider })
+    }
+
+    pub fn tracer(&self) -> SdkTracer {
+        self.provider.tracer(SERVICE_NAME)
+    }
+}
+
+impl Drop for OtlpExport {
+    fn drop(&mut self) {
+        if let Err(error) = self.provider.shutdown() {
+            eprintln!("Failed to flush OTLP spans: {error}");
+        }
+    }
+}
+
+/// OTLP/HTTP traces URL for a collector base endpoint such as
+/// `http://localhost:4318`.
+fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with(TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}{TRACES_PATH}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traces_url_appends_signal_path_once() {
+        assert_eq!(
+            traces_url("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://collector:4318/v1/traces"),
+            "http://collector:4318/v1/traces"
+        );
+    }
+}
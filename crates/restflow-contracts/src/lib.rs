@@ -9,11 +9,13 @@ mod tool;
 
 pub use error::{ErrorKind, ErrorPayload};
 pub use operation::{
-    AllowedPeerResponse, ApiKeyResponse, ApprovalHandledResponse, ArchiveResponse, CancelResponse,
-    CleanupReportResponse, ClearResponse, DeleteResponse, DeleteWithIdResponse, IdResponse,
-    IpcDaemonStatus, OkResponse, PairingApprovalResponse, PairingOwnerResponse,
+    AllowedPeerResponse, ApiKeyResponse, ApiTokenResponse, ApprovalHandledResponse,
+    ArchiveResponse, ArtifactPurgeResponse, ArtifactRemovalResponse, CancelResponse,
+    CleanupReportResponse, ClearResponse, DaemonComponentState, DaemonComponentStatus,
+    DatabaseBackupResponse, DeleteResponse, DeleteWithIdResponse, IdResponse, IpcDaemonStatus,
+    IssuedApiTokenResponse, OkResponse, PairingApprovalResponse, PairingOwnerResponse,
     PairingRequestResponse, PairingStateResponse, PromptResponse, RouteBindingResponse,
-    SecretResponse, SessionSourceMigrationResponse, SteerResponse,
+    SecretResponse, SessionSourceMigrationResponse, SteerResponse, UpdatedResponse, UserResponse,
 };
 pub use request::IpcRequest;
 pub use response::ResponseEnvelope;
@@ -39,6 +41,7 @@ mod tests {
             pid: 1,
             started_at_ms: 0,
             uptime_secs: 0,
+            components: Vec::new(),
         };
     }
 }
@@ -21,6 +21,11 @@ pub struct ClearResponse {
     pub deleted: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpdatedResponse {
+    pub updated: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CancelResponse {
     pub canceled: bool,
@@ -102,6 +107,33 @@ pub struct PairingOwnerResponse {
     pub source: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiTokenResponse {
+    pub id: String,
+    pub name: String,
+    pub token_prefix: String,
+    pub scope: crate::request::ApiTokenScope,
+    pub call_count: u32,
+    pub created_at: i64,
+    pub last_used_at: Option<i64>,
+    pub expires_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IssuedApiTokenResponse {
+    pub token: ApiTokenResponse,
+    /// Plaintext token secret. Only returned once, at creation time.
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserResponse {
+    pub id: String,
+    pub name: String,
+    pub created_at: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RouteBindingResponse {
     pub id: String,
@@ -121,6 +153,36 @@ pub struct CleanupReportResponse {
     pub memory_sessions: usize,
     pub vector_orphans: usize,
     pub daemon_log_files: usize,
+    #[serde(default)]
+    pub artifacts: usize,
+    #[serde(default)]
+    pub artifact_bytes_reclaimed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArtifactRemovalResponse {
+    pub kind: String,
+    pub owner_id: Option<String>,
+    pub path: String,
+    pub size_bytes: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArtifactPurgeResponse {
+    pub dry_run: bool,
+    pub scanned: usize,
+    pub preserved: usize,
+    pub removed: Vec<ArtifactRemovalResponse>,
+    pub bytes_reclaimed: u64,
+    pub bytes_remaining: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DatabaseBackupResponse {
+    pub path: String,
+    pub tables: usize,
+    pub entries: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -140,6 +202,26 @@ pub struct IpcDaemonStatus {
     pub pid: u32,
     pub started_at_ms: i64,
     pub uptime_secs: u64,
+    /// Subsystems started by the daemon, such as the task runner and channels
+    #[serde(default)]
+    pub components: Vec<DaemonComponentStatus>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DaemonComponentState {
+    Running,
+    /// Not configured, e.g. no channel credentials stored
+    Disabled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DaemonComponentStatus {
+    pub name: String,
+    pub state: DaemonComponentState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
 }
 
 #[cfg(test)]
@@ -248,6 +330,11 @@ mod tests {
             pid: 42,
             started_at_ms: 123,
             uptime_secs: 456,
+            components: vec![DaemonComponentStatus {
+                name: "channels".to_string(),
+                state: DaemonComponentState::Running,
+                detail: Some("telegram".to_string()),
+            }],
         };
         assert_roundtrip(&response);
     }
@@ -296,6 +383,37 @@ mod tests {
             memory_sessions: 5,
             vector_orphans: 6,
             daemon_log_files: 7,
+            artifacts: 8,
+            artifact_bytes_reclaimed: 9,
+        };
+        assert_roundtrip(&response);
+    }
+
+    #[test]
+    fn artifact_purge_response_round_trips() {
+        let response = ArtifactPurgeResponse {
+            dry_run: true,
+            scanned: 3,
+            preserved: 1,
+            removed: vec![ArtifactRemovalResponse {
+                kind: "tool_output".to_string(),
+                owner_id: Some("task-1".to_string()),
+                path: "/tmp/tool-output/task-1".to_string(),
+                size_bytes: 42,
+                reason: "orphaned".to_string(),
+            }],
+            bytes_reclaimed: 42,
+            bytes_remaining: 8,
+        };
+        assert_roundtrip(&response);
+    }
+
+    #[test]
+    fn database_backup_response_round_trips() {
+        let response = DatabaseBackupResponse {
+            path: "/tmp/restflow-backup.redb".to_string(),
+            tables: 12,
+            entries: 340,
         };
         assert_roundtrip(&response);
     }
@@ -54,6 +54,54 @@ pub fn default_inter_segment_pause_ms() -> u64 {
     1_000
 }
 
+pub fn default_failover_backoff_ms() -> u64 {
+    1_000
+}
+
 pub fn default_memory_limit() -> u32 {
     50
 }
+
+pub fn default_chat_search_limit() -> u32 {
+    20
+}
+
+pub fn default_memory_embedding_dimension() -> usize {
+    1536
+}
+
+pub fn default_memory_hybrid_vector_weight() -> f64 {
+    0.7
+}
+
+pub fn default_memory_hybrid_rrf_k() -> u32 {
+    60
+}
+
+pub fn default_memory_consolidation_interval_hours() -> u64 {
+    24
+}
+
+pub fn default_memory_consolidation_min_cluster_size() -> usize {
+    3
+}
+
+pub fn default_memory_consolidation_similarity() -> f64 {
+    0.9
+}
+
+pub fn default_memory_session_retention_days() -> u32 {
+    30
+}
+
+pub fn default_memory_max_chunks_per_agent() -> u32 {
+    10_000
+}
+
+pub fn default_retry_backoff_secs() -> u64 {
+    30
+}
+
+pub fn default_max_backoff_secs() -> u64 {
+    3_600
+}
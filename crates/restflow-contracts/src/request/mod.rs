@@ -6,6 +6,8 @@ use specta::Type;
 use std::collections::{BTreeMap, HashMap};
 use ts_rs::TS;
 
+// Requests are decoded once per message and moved, never stored in bulk.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", content = "data")]
 pub enum IpcRequest {
@@ -29,6 +31,12 @@ pub enum IpcRequest {
     DeleteAgent {
         id: String,
     },
+    ListAgentTemplates,
+    ProvisionAgentTemplate {
+        template_id: String,
+        #[serde(default)]
+        name: Option<String>,
+    },
 
     ListSkills,
     GetSkill {
@@ -103,6 +111,27 @@ pub enum IpcRequest {
     SetPairingOwner {
         chat_id: String,
     },
+    ListApiTokens {
+        #[serde(default)]
+        include_revoked: bool,
+    },
+    CreateApiToken {
+        name: String,
+        #[serde(default)]
+        scope: ApiTokenScope,
+        #[serde(default)]
+        expires_at: Option<i64>,
+    },
+    RevokeApiToken {
+        id: String,
+    },
+    ListUsers,
+    CreateUser {
+        name: String,
+    },
+    DeleteUser {
+        id: String,
+    },
     ListRouteBindings,
     BindRoute {
         binding_type: String,
@@ -116,6 +145,23 @@ pub enum IpcRequest {
     MigrateSessionSources {
         dry_run: bool,
     },
+    PurgeArtifacts {
+        dry_run: bool,
+    },
+    BackupDatabase {
+        path: String,
+    },
+    RestoreDatabase {
+        path: String,
+    },
+    ExportBundle {
+        secrets_password: Option<String>,
+    },
+    ImportBundle {
+        bundle: Value,
+        secrets_password: Option<String>,
+        overwrite: bool,
+    },
 
     ListSecrets,
     GetSecret {
@@ -139,12 +185,35 @@ pub enum IpcRequest {
     DeleteSecret {
         key: String,
     },
+    RotateSecret {
+        key: String,
+        value: String,
+        #[serde(default)]
+        expires_at: Option<i64>,
+    },
+    SetSecretExpiry {
+        key: String,
+        #[serde(default)]
+        expires_at: Option<i64>,
+    },
+    ListExpiringSecrets {
+        before: i64,
+    },
+    GetSecretUsage {
+        key: String,
+    },
 
     GetConfig,
     GetGlobalConfig,
     SetConfig {
         config: SystemConfig,
     },
+    /// Reconcile agents, skills and triggers with a parsed `restflow.yaml`.
+    ApplyProjectConfig {
+        config: Value,
+        #[serde(default)]
+        dry_run: bool,
+    },
 
     SearchMemory {
         query: String,
@@ -211,6 +280,20 @@ pub enum IpcRequest {
         session_id: String,
         delete_chunks: bool,
     },
+    TagMemory {
+        chunk_ids: Vec<String>,
+        #[serde(default)]
+        add_tags: Vec<String>,
+        #[serde(default)]
+        remove_tags: Vec<String>,
+    },
+    MergeMemoryTags {
+        sources: Vec<String>,
+        target: String,
+    },
+    ListMemoryTags {
+        agent_id: Option<String>,
+    },
 
     ListSessions,
     ListFullSessions,
@@ -253,6 +336,9 @@ pub enum IpcRequest {
     SearchSessions {
         query: String,
     },
+    SearchChatSessions {
+        query: ChatSearchQuery,
+    },
     AddMessage {
         session_id: String,
         role: String,
@@ -289,6 +375,9 @@ pub enum IpcRequest {
     GetExecutionRunThread {
         run_id: String,
     },
+    GetRelatedEntities {
+        entity_id: String,
+    },
     ListChildRuns {
         query: ChildRunListQuery,
     },
@@ -296,6 +385,10 @@ pub enum IpcRequest {
         #[serde(default)]
         query: ExecutionTraceQuery,
     },
+    QueryExecutionTraceStats {
+        #[serde(default)]
+        query: ExecutionTraceQuery,
+    },
     GetExecutionRunTimeline {
         run_id: String,
     },
@@ -319,6 +412,23 @@ pub enum IpcRequest {
         #[serde(alias = "event_id")]
         id: String,
     },
+    /// Recorded steps of an execution; `id` is an execution ID or a run ID.
+    GetExecutionReplay {
+        id: String,
+    },
+    /// Re-execute a recorded execution from one of its steps.
+    ReplayExecution {
+        execution_id: String,
+        step: u32,
+        #[serde(default)]
+        agent_id: Option<String>,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    GetUsageStats {
+        #[serde(default)]
+        query: UsageQuery,
+    },
 
     ListTerminalSessions,
     GetTerminalSession {
@@ -343,6 +453,51 @@ pub enum IpcRequest {
     },
     MarkAllTerminalSessionsStopped,
 
+    ListBrowserPlans,
+    GetBrowserPlan {
+        name: String,
+        #[serde(default)]
+        version: Option<u32>,
+    },
+    ListBrowserPlanVersions {
+        name: String,
+    },
+    SaveBrowserPlan {
+        name: String,
+        #[serde(default)]
+        description: Option<String>,
+        actions: Value,
+        #[serde(default)]
+        timeout_secs: Option<u64>,
+    },
+    DeleteBrowserPlan {
+        name: String,
+    },
+    RunBrowserPlan {
+        name: String,
+        #[serde(default)]
+        version: Option<u32>,
+        #[serde(default)]
+        payload: Option<Value>,
+    },
+    BrowserDoctor,
+
+    ListApiConnectors,
+    GetApiConnector {
+        name: String,
+    },
+    ImportApiConnector {
+        name: String,
+        spec: String,
+        #[serde(default)]
+        base_url: Option<String>,
+        #[serde(default)]
+        auth_secret: Option<String>,
+    },
+    DeleteApiConnector {
+        name: String,
+    },
+
     ListAuthProfiles,
     GetAuthProfile {
         id: String,
@@ -353,6 +508,14 @@ pub enum IpcRequest {
         source: String,
         provider: String,
     },
+    AddOAuthProfile {
+        name: String,
+        credential: Credential,
+        provider: String,
+        oauth: OAuthClientConfig,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client_secret: Option<String>,
+    },
     RemoveAuthProfile {
         id: String,
     },
@@ -405,6 +568,18 @@ pub enum IpcRequest {
         id: String,
         action: String,
     },
+    /// Run a task now with secrets scoped to that single execution.
+    RunTaskWithSecrets {
+        id: String,
+        secrets: HashMap<String, String>,
+    },
+    /// Register the condition that wakes a paused task; `None` clears it.
+    SetTaskWakeCondition {
+        id: String,
+        condition: Option<WakeCondition>,
+        #[serde(default)]
+        checkpoint_id: Option<String>,
+    },
     GetTaskProgress {
         id: String,
         event_limit: Option<usize>,
@@ -418,6 +593,22 @@ pub enum IpcRequest {
         id: String,
         approved: bool,
     },
+    /// Tool calls queued by the tool approval policy, newest first.
+    ListToolApprovals {
+        #[serde(default)]
+        pending_only: bool,
+    },
+    /// Approve or reject a queued tool call and resume the waiting run.
+    ResolveToolApproval {
+        id: String,
+        approved: bool,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    GetToolApprovalPolicy,
+    SetToolApprovalPolicy {
+        policy: Value,
+    },
     ListTaskMessages {
         id: String,
         limit: Option<usize>,
@@ -465,6 +656,14 @@ pub enum SkillPreflightPolicyMode {
     Enforce,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStrategy {
+    #[default]
+    React,
+    PlanAndExecute,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ModelRoutingConfig {
     pub enabled: bool,
@@ -475,6 +674,15 @@ pub struct ModelRoutingConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub complex_model: Option<String>,
     pub escalate_on_failure: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailoverPolicy {
+    pub fallback_models: Vec<String>,
+    #[serde(default = "defaults::default_failover_backoff_ms")]
+    pub backoff_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -518,6 +726,10 @@ pub struct AgentNode {
     pub skill_preflight_policy_mode: Option<SkillPreflightPolicyMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_routing: Option<ModelRoutingConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_strategy: Option<ExecutionStrategy>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -600,6 +812,19 @@ pub enum Credential {
     },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OAuthClientConfig {
+    pub client_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret_ref: Option<String>,
+    pub authorization_url: String,
+    pub token_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_authorization_url: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProfileUpdate {
     pub name: Option<String>,
@@ -663,6 +888,24 @@ impl Default for TaskSchedule {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WakeCondition {
+    WebhookReceived,
+    FileChanged { path: String },
+    WorkItemStatus { item_id: String, status: String },
+    TimeReached { at: i64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResultCallback {
+    pub url: String,
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub once: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NotificationConfig {
     #[serde(default)]
@@ -773,6 +1016,39 @@ impl Default for ContinuationConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskQueuePolicy {
+    #[serde(default)]
+    pub priority: SpawnPriority,
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    #[serde(default = "defaults::default_retry_backoff_secs")]
+    pub retry_backoff_secs: u64,
+    #[serde(default = "defaults::default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+}
+
+impl Default for TaskQueuePolicy {
+    fn default() -> Self {
+        Self {
+            priority: SpawnPriority::default(),
+            max_retries: None,
+            retry_backoff_secs: defaults::default_retry_backoff_secs(),
+            max_backoff_secs: defaults::default_max_backoff_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConcurrencyLimits {
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub max_group_runs: Option<u32>,
+    #[serde(default)]
+    pub max_agent_runs: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TaskSpec {
     pub name: String,
@@ -802,6 +1078,8 @@ pub struct TaskSpec {
     pub prerequisites: Vec<String>,
     #[serde(default)]
     pub continuation: Option<ContinuationConfig>,
+    #[serde(default)]
+    pub concurrency: Option<ConcurrencyLimits>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -836,6 +1114,12 @@ pub struct TaskPatch {
     pub prerequisites: Option<Vec<String>>,
     #[serde(default)]
     pub continuation: Option<ContinuationConfig>,
+    #[serde(default)]
+    pub callbacks: Option<Vec<ResultCallback>>,
+    #[serde(default)]
+    pub queue: Option<TaskQueuePolicy>,
+    #[serde(default)]
+    pub concurrency: Option<ConcurrencyLimits>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -938,6 +1222,33 @@ pub struct ChatSessionUpdate {
     pub name: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChatSearchQuery {
+    pub query: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub skill_id: Option<String>,
+    #[serde(default)]
+    pub include_archived: bool,
+    #[serde(default = "defaults::default_chat_search_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageQuery {
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub from_time: Option<i64>,
+    #[serde(default)]
+    pub to_time: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum HookEvent {
     #[serde(rename = "task_started")]
@@ -1003,6 +1314,52 @@ pub struct Hook {
     pub updated_at: i64,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenRole {
+    ReadOnly,
+    Execute,
+    Admin,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiTokenScope {
+    #[serde(default)]
+    pub allowed_agents: Option<Vec<String>>,
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    #[serde(default)]
+    pub max_calls: Option<u32>,
+    #[serde(default = "defaults::default_true")]
+    pub mcp: bool,
+    #[serde(default = "defaults::default_true")]
+    pub webhooks: bool,
+    #[serde(default = "defaults::default_true")]
+    pub openai_api: bool,
+    #[serde(default)]
+    pub role: Option<ApiTokenRole>,
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+impl Default for ApiTokenScope {
+    fn default() -> Self {
+        Self {
+            allowed_agents: None,
+            allowed_tools: None,
+            max_calls: None,
+            mcp: true,
+            webhooks: true,
+            openai_api: true,
+            role: None,
+            rate_limit_per_minute: None,
+            user_id: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum MemorySource {
@@ -1040,6 +1397,10 @@ pub struct MemoryChunk {
     pub embedding_model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub embedding_dim: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consolidated_from: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1066,6 +1427,8 @@ pub enum SearchMode {
     Keyword,
     Phrase,
     Regex,
+    Semantic,
+    Hybrid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1198,6 +1561,7 @@ pub enum ExecutionTraceCategory {
     MetricSample,
     ProviderHealth,
     LogRecord,
+    SecurityDecision,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
@@ -1241,6 +1605,12 @@ pub struct LlmCallTrace {
     pub duration_ms: Option<i64>,
     pub is_reasoning: Option<bool>,
     pub message_count: Option<u32>,
+    #[serde(default)]
+    pub cache_read_tokens: Option<u32>,
+    #[serde(default)]
+    pub cache_write_tokens: Option<u32>,
+    #[serde(default)]
+    pub cache_savings_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
@@ -1326,6 +1696,20 @@ pub struct LogRecordTrace {
     pub fields: Vec<ExecutionLogField>,
 }
 
+/// Outcome of a security gate check on a command or tool action.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct SecurityDecisionTrace {
+    pub tool_name: String,
+    pub operation: String,
+    pub target: String,
+    pub allowed: bool,
+    pub requires_approval: bool,
+    pub approval_id: Option<String>,
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
 #[specta(skip_attr = "ts")]
 #[ts(export)]
@@ -1364,6 +1748,8 @@ pub struct ExecutionTraceEvent {
     pub provider_health: Option<ProviderHealthTrace>,
     #[serde(default)]
     pub log_record: Option<LogRecordTrace>,
+    #[serde(default)]
+    pub security_decision: Option<SecurityDecisionTrace>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS, Type, PartialEq)]
@@ -1379,6 +1765,8 @@ pub struct ExecutionTraceStats {
     pub metric_sample_count: u64,
     pub provider_health_count: u64,
     pub log_record_count: u64,
+    #[serde(default)]
+    pub security_decision_count: u64,
     pub total_tokens: u64,
     pub total_cost_usd: f64,
     pub time_range: Option<ExecutionTraceTimeRange>,
@@ -1585,6 +1973,34 @@ pub struct AgentSettings {
     pub bash_timeout_secs: u64,
     pub python_timeout_secs: u64,
     pub browser_timeout_secs: u64,
+    #[serde(default)]
+    pub browser_allowed_domains: Vec<String>,
+    #[serde(default)]
+    pub browser_denied_domains: Vec<String>,
+    #[serde(default)]
+    pub browser_block_private_networks: bool,
+    #[serde(default)]
+    pub browser_artifact_max_session_bytes: u64,
+    #[serde(default)]
+    pub browser_artifact_max_total_bytes: u64,
+    #[serde(default)]
+    pub browser_artifact_max_age_secs: u64,
+    #[serde(default)]
+    pub subprocess_memory_limit_mb: u64,
+    #[serde(default)]
+    pub subprocess_cpu_weight: u32,
+    #[serde(default)]
+    pub subprocess_max_processes: u32,
+    #[serde(default)]
+    pub subprocess_cpu_time_secs: u64,
+    #[serde(default)]
+    pub container_runtime: Option<String>,
+    #[serde(default)]
+    pub container_image: String,
+    #[serde(default)]
+    pub container_network: bool,
+    #[serde(default)]
+    pub subprocess_network_allow: Option<Vec<String>>,
     pub process_session_ttl_secs: u64,
     pub approval_timeout_secs: u64,
     pub max_iterations: usize,
@@ -1619,6 +2035,8 @@ pub struct ApiSettings {
 pub struct RuntimeSettings {
     pub background_runner_poll_interval_ms: u64,
     pub background_runner_max_concurrent_tasks: usize,
+    #[serde(default)]
+    pub background_runner_auto_resume: bool,
     pub chat_max_session_history: usize,
 }
 
@@ -1626,12 +2044,68 @@ pub struct RuntimeSettings {
 pub struct ChannelSettings {
     pub telegram_api_timeout_secs: u64,
     pub telegram_polling_timeout_secs: u32,
+    pub voice_replies: bool,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookSinkConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookSinkConfig {
+    pub url: String,
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct RegistrySettings {
     pub github_cache_ttl_secs: u64,
     pub marketplace_cache_ttl_secs: u64,
+    #[serde(default)]
+    pub trusted_skill_keys: Vec<String>,
+    #[serde(default)]
+    pub require_signed_skills: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MemorySettings {
+    pub auto_tag: bool,
+    pub max_suggested_tags: usize,
+    #[serde(default)]
+    pub tag_retention: Vec<TagRetentionRule>,
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+    #[serde(default = "defaults::default_memory_embedding_dimension")]
+    pub embedding_dimension: usize,
+    #[serde(default)]
+    pub embedding_base_url: Option<String>,
+    #[serde(default)]
+    pub embedding_api_key_secret: Option<String>,
+    #[serde(default = "defaults::default_memory_hybrid_vector_weight")]
+    pub hybrid_vector_weight: f64,
+    #[serde(default = "defaults::default_memory_hybrid_rrf_k")]
+    pub hybrid_rrf_k: u32,
+    #[serde(default)]
+    pub consolidation_enabled: bool,
+    #[serde(default = "defaults::default_memory_consolidation_interval_hours")]
+    pub consolidation_interval_hours: u64,
+    #[serde(default)]
+    pub consolidation_model: Option<String>,
+    #[serde(default = "defaults::default_memory_consolidation_min_cluster_size")]
+    pub consolidation_min_cluster_size: usize,
+    #[serde(default = "defaults::default_memory_consolidation_similarity")]
+    pub consolidation_similarity: f64,
+    #[serde(default = "defaults::default_memory_session_retention_days")]
+    pub session_retention_days: u32,
+    #[serde(default = "defaults::default_memory_max_chunks_per_agent")]
+    pub max_chunks_per_agent: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TagRetentionRule {
+    pub tag: String,
+    pub retention_days: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -1649,6 +2123,16 @@ pub struct SystemConfig {
     pub checkpoint_retention_days: u32,
     pub memory_chunk_retention_days: u32,
     pub log_file_retention_days: u32,
+    #[serde(default)]
+    pub artifact_retention_days: u32,
+    #[serde(default)]
+    pub artifact_max_total_mb: u64,
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
     pub experimental_features: Vec<String>,
     #[serde(default)]
     pub agent: AgentSettings,
@@ -1660,6 +2144,8 @@ pub struct SystemConfig {
     pub channel_defaults: ChannelSettings,
     #[serde(default)]
     pub registry_defaults: RegistrySettings,
+    #[serde(default)]
+    pub memory_defaults: MemorySettings,
 }
 
 #[cfg(test)]
@@ -1700,7 +2186,13 @@ mod tests {
                 moderate_model: Some("gpt-5".to_string()),
                 complex_model: Some("gpt-5-pro".to_string()),
                 escalate_on_failure: true,
+                failover: Some(FailoverPolicy {
+                    fallback_models: vec!["claude-sonnet-4-5".to_string()],
+                    backoff_ms: 1_000,
+                }),
             }),
+            dry_run: Some(true),
+            execution_strategy: Some(ExecutionStrategy::PlanAndExecute),
         }
     }
 
@@ -1773,6 +2265,7 @@ mod tests {
                     max_total_cost_usd: Some(5.0),
                     inter_segment_pause_ms: 500,
                 }),
+                concurrency: None,
             },
         };
         assert_roundtrip(&request);
@@ -2063,6 +2556,58 @@ mod tests {
             },
         };
         assert_roundtrip(&request);
+
+        let request = IpcRequest::AddOAuthProfile {
+            name: "GitHub".to_string(),
+            credential: Credential::OAuth {
+                access_token: "access".to_string(),
+                refresh_token: Some("refresh".to_string()),
+                expires_at: None,
+                email: None,
+            },
+            provider: "other".to_string(),
+            oauth: OAuthClientConfig {
+                client_id: "client".to_string(),
+                client_secret_ref: None,
+                authorization_url: "https://github.com/login/oauth/authorize".to_string(),
+                token_url: "https://github.com/login/oauth/access_token".to_string(),
+                device_authorization_url: Some("https://github.com/login/device/code".to_string()),
+                scopes: vec!["read:user".to_string()],
+            },
+            client_secret: Some("secret".to_string()),
+        };
+        assert_roundtrip(&request);
+    }
+
+    #[test]
+    fn ipc_request_api_token_round_trips() {
+        let request = IpcRequest::CreateApiToken {
+            name: "ci".to_string(),
+            scope: ApiTokenScope {
+                allowed_agents: Some(vec!["agent-1".to_string()]),
+                allowed_tools: Some(vec!["list_skills".to_string()]),
+                max_calls: Some(100),
+                mcp: true,
+                webhooks: false,
+                openai_api: true,
+                role: Some(ApiTokenRole::ReadOnly),
+                rate_limit_per_minute: Some(60),
+                user_id: Some("user-1".to_string()),
+            },
+            expires_at: Some(1_700_000_000_000),
+        };
+        assert_roundtrip(&request);
+
+        let decoded: IpcRequest =
+            serde_json::from_str(r#"{"type":"CreateApiToken","data":{"name":"ci"}}"#).unwrap();
+        assert_eq!(
+            decoded,
+            IpcRequest::CreateApiToken {
+                name: "ci".to_string(),
+                scope: ApiTokenScope::default(),
+                expires_at: None,
+            }
+        );
     }
 
     #[test]
@@ -2127,6 +2672,7 @@ mod tests {
             metric_sample: None,
             provider_health: None,
             log_record: None,
+            security_decision: None,
         };
         assert_roundtrip(&event);
 
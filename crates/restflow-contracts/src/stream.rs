@@ -33,14 +33,36 @@ pub enum StreamEnvelope<TEvent> {
         name: String,
         arguments: Value,
     },
+    /// Partial output of a running tool call.
+    ToolOutput {
+        id: String,
+        content: String,
+    },
     ToolResult {
         id: String,
         result: String,
         success: bool,
     },
+    /// Live progress of a sub-agent spawned by this run.
+    SubagentProgress {
+        task_id: String,
+        agent_name: String,
+        iteration: u32,
+        /// `running`, `completed`, `failed`, `interrupted` or `timed_out`.
+        status: String,
+        /// Tool the sub-agent is currently running.
+        tool: Option<String>,
+    },
     Event {
         event: TEvent,
     },
+    /// Sent after events were dropped for a lagging consumer. `events` holds
+    /// the latest event for each affected subject so the client can resync.
+    Resync {
+        dropped: u64,
+        #[serde(default = "Vec::new")]
+        events: Vec<TEvent>,
+    },
     Done {
         total_tokens: Option<u32>,
     },
@@ -160,6 +182,17 @@ mod tests {
         assert_eq!(decoded, frame);
     }
 
+    #[test]
+    fn stream_tool_output_round_trips() {
+        let frame = StreamEnvelope::<TestEvent>::ToolOutput {
+            id: "call-1".to_string(),
+            content: "compiling...\n".to_string(),
+        };
+        let encoded = serde_json::to_string(&frame).unwrap();
+        let decoded: StreamEnvelope<TestEvent> = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, frame);
+    }
+
     #[test]
     fn stream_tool_result_round_trips() {
         let frame = StreamEnvelope::<TestEvent>::ToolResult {
@@ -172,6 +205,31 @@ mod tests {
         assert_eq!(decoded, frame);
     }
 
+    #[test]
+    fn stream_subagent_progress_round_trips() {
+        let frame = StreamEnvelope::<TestEvent>::SubagentProgress {
+            task_id: "task-1".to_string(),
+            agent_name: "researcher".to_string(),
+            iteration: 2,
+            status: "running".to_string(),
+            tool: Some("web_search".to_string()),
+        };
+        let encoded = serde_json::to_string(&frame).unwrap();
+        let decoded: StreamEnvelope<TestEvent> = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, frame);
+    }
+
+    #[test]
+    fn stream_resync_round_trips() {
+        let frame = StreamEnvelope::Resync {
+            dropped: 3,
+            events: vec![TestEvent::Session],
+        };
+        let encoded = serde_json::to_string(&frame).unwrap();
+        let decoded: StreamEnvelope<TestEvent> = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, frame);
+    }
+
     #[test]
     fn stream_done_round_trips() {
         let frame = StreamEnvelope::<TestEvent>::Done {
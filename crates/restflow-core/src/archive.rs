@@ -0,0 +1,260 @@
+//! Password-based encryption for exported archives.
+//!
+//! Encrypted archives start with a fixed magic, followed by a length-prefixed
+//! JSON header that records the cipher and key derivation parameters, and then
+//! the AES-256-GCM ciphertext. The header is authenticated as associated data,
+//! so tampering with the KDF parameters is detected on decryption.
+//!
+//! ```text
+//! RFARCH01 | u32 LE header length | header JSON | ciphertext + tag
+//! ```
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Magic prefix identifying an encrypted RestFlow archive.
+pub const ENCRYPTED_ARCHIVE_MAGIC: &[u8; 8] = b"RFARCH01";
+/// File extension appended to encrypted exports.
+pub const ENCRYPTED_ARCHIVE_EXTENSION: &str = "rfenc";
+
+const ARCHIVE_FORMAT_VERSION: u32 = 1;
+const CIPHER_NAME: &str = "aes-256-gcm";
+const KDF_NAME: &str = "pbkdf2-hmac-sha256";
+const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+const MIN_KDF_ITERATIONS: u32 = 1_000;
+/// Upper bound on the iteration count read from an archive header, so a
+/// crafted archive cannot pin a CPU deriving a key.
+const MAX_KDF_ITERATIONS: u32 = DEFAULT_KDF_ITERATIONS * 10;
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+const KEY_SIZE: usize = 32;
+const MAX_HEADER_LEN: usize = 4096;
+
+/// Header stored in front of the ciphertext.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchiveHeader {
+    pub version: u32,
+    pub cipher: String,
+    pub kdf: String,
+    pub iterations: u32,
+    /// Base64-encoded KDF salt.
+    pub salt: String,
+    /// Base64-encoded cipher nonce.
+    pub nonce: String,
+}
+
+/// Returns true when `data` starts with the encrypted archive magic.
+pub fn is_encrypted_archive(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_ARCHIVE_MAGIC)
+}
+
+/// Encrypt an export payload with a password.
+pub fn encrypt_archive(plaintext: &[u8], password: &str) -> Result<Vec<u8>> {
+    encrypt_archive_with_iterations(plaintext, password, DEFAULT_KDF_ITERATIONS)
+}
+
+fn encrypt_archive_with_iterations(
+    plaintext: &[u8],
+    password: &str,
+    iterations: u32,
+) -> Result<Vec<u8>> {
+    if password.is_empty() {
+        bail!("Archive password must not be empty");
+    }
+
+    let mut salt = [0u8; SALT_SIZE];
+    let mut nonce = [0u8; NONCE_SIZE];
+    rand::rng().fill_bytes(&mut salt);
+    rand::rng().fill_bytes(&mut nonce);
+
+    let header = ArchiveHeader {
+        version: ARCHIVE_FORMAT_VERSION,
+        cipher: CIPHER_NAME.to_string(),
+        kdf: KDF_NAME.to_string(),
+        iterations,
+        salt: BASE64_STANDARD.encode(salt),
+        nonce: BASE64_STANDARD.encode(nonce),
+    };
+    let header_bytes = serde_json::to_vec(&header)?;
+
+    let cipher = archive_cipher(password, &salt, iterations)?;
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &header_bytes,
+            },
+        )
+        .map_err(|_| anyhow!("Failed to encrypt archive"))?;
+
+    let mut output = Vec::with_capacity(
+        ENCRYPTED_ARCHIVE_MAGIC.len() + 4 + header_bytes.len() + ciphertext.len(),
+    );
+    output.extend_from_slice(ENCRYPTED_ARCHIVE_MAGIC);
+    output.extend_from_slice(&(header_bytes.len() as u32).to_le_bytes());
+    output.extend_from_slice(&header_bytes);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Read the header of an encrypted archive without decrypting it.
+pub fn read_archive_header(data: &[u8]) -> Result<ArchiveHeader> {
+    let (header, _, _) = split_archive(data)?;
+    Ok(header)
+}
+
+/// Decrypt an archive produced by [`encrypt_archive`].
+pub fn decrypt_archive(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    let (header, header_bytes, ciphertext) = split_archive(data)?;
+    if header.version != ARCHIVE_FORMAT_VERSION {
+        bail!("Unsupported archive format version {}", header.version);
+    }
+    if header.cipher != CIPHER_NAME || header.kdf != KDF_NAME {
+        bail!(
+            "Unsupported archive encryption ({} / {})",
+            header.cipher,
+            header.kdf
+        );
+    }
+    if header.iterations < MIN_KDF_ITERATIONS {
+        bail!("Archive key derivation parameters are too weak");
+    }
+    if header.iterations > MAX_KDF_ITERATIONS {
+        bail!(
+            "Archive key derivation uses {} iterations, more than the allowed {}",
+            header.iterations,
+            MAX_KDF_ITERATIONS
+        );
+    }
+
+    let salt = BASE64_STANDARD
+        .decode(&header.salt)
+        .context("Invalid archive salt")?;
+    let nonce = BASE64_STANDARD
+        .decode(&header.nonce)
+        .context("Invalid archive nonce")?;
+    if nonce.len() != NONCE_SIZE {
+        bail!("Invalid archive nonce length");
+    }
+
+    let cipher = archive_cipher(password, &salt, header.iterations)?;
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: ciphertext,
+                aad: header_bytes,
+            },
+        )
+        .map_err(|_| anyhow!("Failed to decrypt archive: wrong password or corrupted file"))
+}
+
+fn split_archive(data: &[u8]) -> Result<(ArchiveHeader, &[u8], &[u8])> {
+    let Some(rest) = data.strip_prefix(ENCRYPTED_ARCHIVE_MAGIC.as_slice()) else {
+        bail!("Not an encrypted RestFlow archive");
+    };
+    if rest.len() < 4 {
+        bail!("Encrypted archive is truncated");
+    }
+    let (len_bytes, rest) = rest.split_at(4);
+    let header_len = u32::from_le_bytes(len_bytes.try_into()?) as usize;
+    if header_len > MAX_HEADER_LEN || rest.len() < header_len {
+        bail!("Encrypted archive header is invalid");
+    }
+
+    let (header_bytes, ciphertext) = rest.split_at(header_len);
+    let header: ArchiveHeader =
+        serde_json::from_slice(header_bytes).context("Encrypted archive header is invalid")?;
+    Ok((header, header_bytes, ciphertext))
+}
+
+fn archive_cipher(password: &str, salt: &[u8], iterations: u32) -> Result<Aes256Gcm> {
+    let mut key = [0u8; KEY_SIZE];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+    Aes256Gcm::new_from_slice(&key).map_err(|_| anyhow!("Invalid archive key length"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_ITERATIONS: u32 = MIN_KDF_ITERATIONS;
+
+    #[test]
+    fn roundtrip_with_password() {
+        let encrypted =
+            encrypt_archive_with_iterations(b"# Memory export", "hunter2", TEST_ITERATIONS)
+                .unwrap();
+
+        assert!(is_encrypted_archive(&encrypted));
+        assert!(!is_encrypted_archive(b"# Memory export"));
+
+        let header = read_archive_header(&encrypted).unwrap();
+        assert_eq!(header.kdf, KDF_NAME);
+        assert_eq!(header.iterations, TEST_ITERATIONS);
+
+        let decrypted = decrypt_archive(&encrypted, "hunter2").unwrap();
+        assert_eq!(decrypted, b"# Memory export");
+    }
+
+    #[test]
+    fn rejects_wrong_password() {
+        let encrypted =
+            encrypt_archive_with_iterations(b"secret", "right", TEST_ITERATIONS).unwrap();
+        let error = decrypt_archive(&encrypted, "wrong").unwrap_err();
+        assert!(error.to_string().contains("wrong password"));
+    }
+
+    #[test]
+    fn detects_header_tampering() {
+        let encrypted = encrypt_archive_with_iterations(b"secret", "pw", TEST_ITERATIONS).unwrap();
+        // Rewrite the header in place with a same-length change so only the
+        // authenticated data differs from what was encrypted.
+        let mut bytes = encrypted.clone();
+        let header_start = ENCRYPTED_ARCHIVE_MAGIC.len() + 4;
+        let header_len = read_header_len(&encrypted);
+        let mut header = read_archive_header(&encrypted).unwrap();
+        header.iterations += 1;
+        let new_header = serde_json::to_vec(&header).unwrap();
+        assert_eq!(new_header.len(), header_len);
+        bytes[header_start..header_start + header_len].copy_from_slice(&new_header);
+
+        assert!(decrypt_archive(&bytes, "pw").is_err());
+    }
+
+    #[test]
+    fn rejects_excessive_kdf_iterations() {
+        let encrypted = encrypt_archive_with_iterations(b"secret", "pw", TEST_ITERATIONS).unwrap();
+        let header_len = read_header_len(&encrypted);
+        let body_start = ENCRYPTED_ARCHIVE_MAGIC.len() + 4 + header_len;
+        let mut header = read_archive_header(&encrypted).unwrap();
+        header.iterations = MAX_KDF_ITERATIONS + 1;
+        let new_header = serde_json::to_vec(&header).unwrap();
+
+        let mut bytes = ENCRYPTED_ARCHIVE_MAGIC.to_vec();
+        bytes.extend_from_slice(&(new_header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&new_header);
+        bytes.extend_from_slice(&encrypted[body_start..]);
+
+        let error = decrypt_archive(&bytes, "pw").unwrap_err();
+        assert!(error.to_string().contains("more than the allowed"));
+    }
+
+    #[test]
+    fn rejects_empty_password_and_plain_input() {
+        assert!(encrypt_archive(b"data", "").is_err());
+        assert!(decrypt_archive(b"plain markdown", "pw").is_err());
+    }
+
+    fn read_header_len(data: &[u8]) -> usize {
+        let start = ENCRYPTED_ARCHIVE_MAGIC.len();
+        u32::from_le_bytes(data[start..start + 4].try_into().unwrap()) as usize
+    }
+}
@@ -344,6 +344,10 @@ impl EnvVarDiscoverer {
                 ("OPENAI_API_KEY".to_string(), AuthProvider::OpenAI),
                 ("GOOGLE_API_KEY".to_string(), AuthProvider::Google),
                 ("GEMINI_API_KEY".to_string(), AuthProvider::Google),
+                (
+                    "AWS_BEARER_TOKEN_BEDROCK".to_string(),
+                    AuthProvider::Bedrock,
+                ),
                 ("DEEPSEEK_API_KEY".to_string(), AuthProvider::Other),
                 ("GROQ_API_KEY".to_string(), AuthProvider::Other),
                 ("OPENROUTER_API_KEY".to_string(), AuthProvider::Other),
@@ -3,11 +3,12 @@
 //! Manages credential profiles with selection, health tracking, and failover.
 
 use super::discoverer::{CompositeDiscoverer, DiscoveredProfile};
+use super::oauth::{OAuthClientConfig, OAuthFlow};
 use super::refresh::{AnthropicRefresher, OAuthRefresher};
 use super::resolver::CredentialResolver;
 use super::types::{
     AuthProfile, AuthProvider, Credential, CredentialSource, DiscoverySummary, ProfileHealth,
-    ProfileSelection, SecureCredential,
+    ProfileSelection, secret_key,
 };
 use super::writer::CredentialWriter;
 use anyhow::{Context, Result, anyhow};
@@ -326,22 +327,36 @@ impl AuthProfileManager {
     }
 
     async fn refresh_expired_profiles(&self, provider: AuthProvider) -> Result<usize> {
-        let refresher = match self.refreshers.get(&provider) {
-            Some(refresher) => refresher.clone(),
-            None => return Ok(0),
-        };
+        self.refresh_profiles(|profile| {
+            profile.provider == provider && profile.credential.is_expired()
+        })
+        .await
+    }
+
+    /// Refresh OAuth profiles of any provider whose token expires within `window`.
+    ///
+    /// Used by the daemon to keep tokens referenced by connectors valid.
+    pub async fn refresh_expiring_profiles(&self, window: chrono::Duration) -> Result<usize> {
+        self.refresh_profiles(|profile| profile.credential.expires_within(window))
+            .await
+    }
 
+    async fn refresh_profiles(
+        &self,
+        needs_refresh: impl Fn(&AuthProfile) -> bool,
+    ) -> Result<usize> {
         // Collect candidates that need refresh
-        let candidates: Vec<(String, SecureCredential)> = {
+        let candidates: Vec<AuthProfile> = {
             let profiles = self.profiles.read().await;
             profiles
                 .values()
                 .filter(|profile| {
-                    profile.provider == provider
-                        && profile.credential.is_expired()
+                    needs_refresh(profile)
                         && profile.credential.can_refresh()
+                        && (profile.oauth.is_some()
+                            || self.refreshers.contains_key(&profile.provider))
                 })
-                .map(|profile| (profile.id.clone(), profile.credential.clone()))
+                .cloned()
                 .collect()
         };
 
@@ -351,9 +366,12 @@ impl AuthProfileManager {
 
         let mut refreshed = 0;
 
-        for (profile_id, secure_credential) in candidates {
+        for candidate in candidates {
+            let profile_id = candidate.id.as_str();
+            let secure_credential = &candidate.credential;
+
             // Resolve current tokens
-            let access_token = match self.resolver.resolve_auth_value(&secure_credential) {
+            let access_token = match self.resolver.resolve_auth_value(secure_credential) {
                 Ok(token) => token,
                 Err(e) => {
                     warn!(%e, profile_id, "Failed to resolve access token for refresh");
@@ -361,7 +379,7 @@ impl AuthProfileManager {
                 }
             };
 
-            let refresh_token = match self.resolver.resolve_refresh_token(&secure_credential) {
+            let refresh_token = match self.resolver.resolve_refresh_token(secure_credential) {
                 Ok(Some(token)) => token,
                 Ok(None) => {
                     warn!(profile_id, "No refresh token available");
@@ -373,15 +391,28 @@ impl AuthProfileManager {
                 }
             };
 
-            // Create temporary Credential for refresher
-            let temp_credential = Credential::OAuth {
-                access_token,
-                refresh_token: Some(refresh_token),
-                expires_at: None,
-                email: secure_credential.get_email().map(|s| s.to_string()),
+            let result = match &candidate.oauth {
+                // Profiles created through an OAuth flow refresh against their own client.
+                Some(config) => match self.oauth_flow(config) {
+                    Ok(flow) => flow.refresh(&refresh_token).await,
+                    Err(e) => Err(e),
+                },
+                None => {
+                    let Some(refresher) = self.refreshers.get(&candidate.provider) else {
+                        continue;
+                    };
+                    // Create temporary Credential for refresher
+                    let temp_credential = Credential::OAuth {
+                        access_token,
+                        refresh_token: Some(refresh_token),
+                        expires_at: None,
+                        email: secure_credential.get_email().map(|s| s.to_string()),
+                    };
+                    refresher.refresh(&temp_credential).await
+                }
             };
 
-            match refresher.refresh(&temp_credential).await {
+            match result {
                 Ok(updated) => {
                     // Update secrets
                     if let Err(e) = self.writer.update_secret(
@@ -402,7 +433,7 @@ impl AuthProfileManager {
 
                     // Update profile metadata
                     let mut profiles = self.profiles.write().await;
-                    if let Some(profile) = profiles.get_mut(&profile_id) {
+                    if let Some(profile) = profiles.get_mut(profile_id) {
                         profile.credential.update_oauth_metadata(
                             updated.refresh_token.and_then(|_| {
                                 secure_credential.refresh_token_ref().map(|s| s.to_string())
@@ -410,10 +441,16 @@ impl AuthProfileManager {
                             updated.expires_at,
                         );
                         refreshed += 1;
+
+                        if profile.source == CredentialSource::Manual
+                            && let Err(e) = self.save_profile_to_storage(profile)
+                        {
+                            warn!(error = %e, profile_id, "Failed to persist refreshed profile");
+                        }
                     }
                 }
                 Err(error) => {
-                    warn!(%error, profile_id, provider = %provider, "Failed to refresh OAuth token");
+                    warn!(%error, profile_id, provider = %candidate.provider, "Failed to refresh OAuth token");
                 }
             }
         }
@@ -421,6 +458,18 @@ impl AuthProfileManager {
         Ok(refreshed)
     }
 
+    fn oauth_flow(&self, config: &OAuthClientConfig) -> Result<OAuthFlow> {
+        let client_secret = match &config.client_secret_ref {
+            Some(secret_ref) => Some(
+                self.resolver
+                    .resolve_secret(secret_ref)?
+                    .ok_or_else(|| anyhow!("Secret not found: {}", secret_ref))?,
+            ),
+            None => None,
+        };
+        Ok(OAuthFlow::new(config.clone(), client_secret))
+    }
+
     /// Get the best API key for a provider
     pub async fn get_api_key(&self, provider: AuthProvider) -> Option<String> {
         let selected = self.select_profile(provider).await?;
@@ -526,6 +575,52 @@ impl AuthProfileManager {
         Ok(profile_id)
     }
 
+    /// Add a profile obtained through an OAuth flow.
+    ///
+    /// The client configuration is kept on the profile so the token can be
+    /// refreshed later; the client secret is stored in SecretStorage.
+    pub async fn add_oauth_profile(
+        &self,
+        name: impl Into<String>,
+        credential: Credential,
+        provider: AuthProvider,
+        mut oauth: OAuthClientConfig,
+        client_secret: Option<String>,
+    ) -> Result<String> {
+        if !matches!(credential, Credential::OAuth { .. }) {
+            return Err(anyhow!("OAuth profiles require an OAuth credential"));
+        }
+
+        let profile_id = Uuid::new_v4().to_string();
+        oauth.client_secret_ref = None;
+        if let Some(secret) = client_secret {
+            let secret_ref = secret_key(&profile_id, "client_secret");
+            self.writer.update_secret(&secret_ref, &secret)?;
+            oauth.client_secret_ref = Some(secret_ref);
+        }
+
+        let secure_credential = self.writer.store_credential(&profile_id, &credential)?;
+        let mut profile = AuthProfile::new_with_id(
+            profile_id,
+            name,
+            secure_credential,
+            CredentialSource::Manual,
+            provider,
+        );
+        profile.oauth = Some(oauth);
+
+        match self.add_profile(profile.clone()).await {
+            Ok(id) => Ok(id),
+            Err(e) => {
+                let _ = self.writer.delete_credential(&profile.credential);
+                if let Some(secret_ref) = profile.oauth.and_then(|o| o.client_secret_ref) {
+                    let _ = self.writer.delete_secret(&secret_ref);
+                }
+                Err(e)
+            }
+        }
+    }
+
     /// Add a profile (for internal use with already-secure credentials)
     pub async fn add_profile(&self, profile: AuthProfile) -> Result<String> {
         let mut profiles = self.profiles.write().await;
@@ -570,6 +665,14 @@ impl AuthProfileManager {
         if let Err(e) = self.writer.delete_credential(&profile.credential) {
             warn!(error = %e, profile_id, "Failed to delete credential secrets");
         }
+        if let Some(secret_ref) = profile
+            .oauth
+            .as_ref()
+            .and_then(|oauth| oauth.client_secret_ref.as_deref())
+            && let Err(e) = self.writer.delete_secret(secret_ref)
+        {
+            warn!(error = %e, profile_id, "Failed to delete OAuth client secret");
+        }
 
         info!(profile_id, name = %profile.name, "Profile removed");
 
@@ -762,6 +865,47 @@ impl AuthProfileManager {
         Ok(())
     }
 
+    /// Re-read manual profiles from storage.
+    ///
+    /// Profiles added or removed by another manager instance (e.g. an IPC
+    /// request) become visible without a restart. Runtime health state of
+    /// profiles that are still present is kept.
+    pub async fn reload_from_storage(&self) -> Result<()> {
+        let Some(storage) = &self.storage else {
+            return Ok(());
+        };
+        let mut stored: HashMap<String, AuthProfile> = HashMap::new();
+        for (_, bytes) in storage.list_raw()? {
+            match serde_json::from_slice::<AuthProfile>(&bytes) {
+                Ok(profile) if profile.source == CredentialSource::Manual => {
+                    stored.insert(profile.id.clone(), profile);
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Skipping corrupt auth profile entry"),
+            }
+        }
+
+        let mut profiles = self.profiles.write().await;
+        profiles.retain(|id, profile| {
+            profile.source != CredentialSource::Manual || stored.contains_key(id)
+        });
+        for (id, stored_profile) in stored {
+            match profiles.get_mut(&id) {
+                Some(profile) => {
+                    profile.name = stored_profile.name;
+                    profile.credential = stored_profile.credential;
+                    profile.enabled = stored_profile.enabled;
+                    profile.priority = stored_profile.priority;
+                    profile.oauth = stored_profile.oauth;
+                }
+                None => {
+                    profiles.insert(id, stored_profile);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Migrate profiles from legacy JSON file into the database.
     pub async fn migrate_from_json(&self, json_path: &Path) -> Result<usize> {
         if !json_path.exists() {
@@ -970,6 +1114,68 @@ mod tests {
         assert!(profiles.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_manager_oauth_profile_stores_client_secret() {
+        let (secrets, _dir) = create_test_secrets();
+        let manager = AuthProfileManager::new(secrets.clone());
+        let config = OAuthClientConfig::preset("github", "client-1").unwrap();
+        let credential = Credential::OAuth {
+            access_token: "gho_access".to_string(),
+            refresh_token: Some("ghr_refresh".to_string()),
+            expires_at: None,
+            email: None,
+        };
+
+        let id = manager
+            .add_oauth_profile(
+                "GitHub",
+                credential,
+                AuthProvider::Other,
+                config,
+                Some("client-secret".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let profile = manager.get_profile(&id).await.unwrap();
+        let secret_ref = profile
+            .oauth
+            .as_ref()
+            .and_then(|oauth| oauth.client_secret_ref.clone())
+            .unwrap();
+        assert_eq!(secret_ref, secret_key(&id, "client_secret"));
+        assert_eq!(
+            secrets.get_secret(&secret_ref).unwrap().as_deref(),
+            Some("client-secret")
+        );
+        assert_eq!(
+            secrets
+                .get_secret(&secret_key(&id, "access_token"))
+                .unwrap()
+                .as_deref(),
+            Some("gho_access")
+        );
+
+        manager.remove_profile(&id).await.unwrap();
+        assert!(secrets.get_secret(&secret_ref).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_manager_oauth_profile_rejects_api_key() {
+        let (secrets, _dir) = create_test_secrets();
+        let manager = AuthProfileManager::new(secrets);
+        let config = OAuthClientConfig::preset("google", "client-1").unwrap();
+        let credential = Credential::ApiKey {
+            key: "key".to_string(),
+            email: None,
+        };
+
+        let result = manager
+            .add_oauth_profile("Google", credential, AuthProvider::Google, config, None)
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_manager_get_profile() {
         let (secrets, _dir) = create_test_secrets();
@@ -5,9 +5,11 @@
 //! - Profile storage and rotation
 //! - Health tracking and cooldown management
 //! - Secure storage for manual profiles
+//! - OAuth2 authorization-code and device-code flows
 
 pub mod discoverer;
 pub mod manager;
+pub mod oauth;
 pub(crate) mod provider_access;
 pub mod refresh;
 pub mod resolver;
@@ -21,6 +23,10 @@ pub use discoverer::{
     DiscoveredProfile, DiscoveryResult, EnvVarDiscoverer,
 };
 pub use manager::{AuthManagerConfig, AuthProfileManager, ManagerSummary, ProfileUpdate};
+pub use oauth::{
+    AuthorizationRequest, DeviceAuthorization, LoopbackRedirect, OAuthClientConfig, OAuthFlow,
+    TokenResponse,
+};
 pub(crate) use provider_access::{
     build_runtime_api_keys, provider_available, resolve_model_from_credentials, secret_exists,
     secret_or_env_exists,
@@ -0,0 +1,600 @@
+//! OAuth2 authorization flows for auth profiles.
+//!
+//! Two ways of obtaining tokens are supported:
+//! - Authorization code with PKCE and a loopback redirect listener, for the
+//!   desktop app where a browser is available on the same machine.
+//! - Device authorization grant, for the CLI and headless servers.
+//!
+//! Tokens end up in a [`Credential::OAuth`] that the profile manager stores in
+//! SecretStorage and refreshes through [`OAuthFlow::refresh`].
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{Duration, Utc};
+use rand::RngExt;
+use rand::distr::Alphanumeric;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use specta::Type;
+use std::time::Duration as StdDuration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use ts_rs::TS;
+use url::Url;
+
+use super::refresh::RefreshedCredential;
+use super::types::Credential;
+
+const TS_EXPORT_TO_WEB_TYPES: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../web/src/types/generated/"
+);
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+const REDIRECT_PATH: &str = "/callback";
+
+/// OAuth2 client registration used by an auth profile.
+///
+/// The client secret itself lives in SecretStorage; only its reference is kept
+/// here so the configuration can be stored alongside the profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export, export_to = TS_EXPORT_TO_WEB_TYPES)]
+pub struct OAuthClientConfig {
+    /// OAuth client identifier
+    pub client_id: String,
+    /// Reference to the client secret in SecretStorage (public clients have none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret_ref: Option<String>,
+    /// Authorization endpoint for the authorization-code flow
+    pub authorization_url: String,
+    /// Token endpoint used for code exchange, device polling and refresh
+    pub token_url: String,
+    /// Device authorization endpoint, when the provider supports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_authorization_url: Option<String>,
+    /// Requested scopes
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl OAuthClientConfig {
+    /// Endpoint configuration for a well-known provider (`google`, `microsoft`, `github`).
+    pub fn preset(provider: &str, client_id: impl Into<String>) -> Result<Self> {
+        let (authorization_url, token_url, device_authorization_url, scopes): (
+            &str,
+            &str,
+            &str,
+            &[&str],
+        ) = match provider.to_lowercase().as_str() {
+            "google" => (
+                "https://accounts.google.com/o/oauth2/v2/auth",
+                "https://oauth2.googleapis.com/token",
+                "https://oauth2.googleapis.com/device/code",
+                &["openid", "email"],
+            ),
+            "microsoft" => (
+                "https://login.microsoftonline.com/common/oauth2/v2.0/authorize",
+                "https://login.microsoftonline.com/common/oauth2/v2.0/token",
+                "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode",
+                &["offline_access", "User.Read"],
+            ),
+            "github" => (
+                "https://github.com/login/oauth/authorize",
+                "https://github.com/login/oauth/access_token",
+                "https://github.com/login/device/code",
+                &["read:user"],
+            ),
+            other => bail!(
+                "Unknown OAuth provider preset: {other}. Valid options: google, microsoft, github"
+            ),
+        };
+
+        Ok(Self {
+            client_id: client_id.into(),
+            client_secret_ref: None,
+            authorization_url: authorization_url.to_string(),
+            token_url: token_url.to_string(),
+            device_authorization_url: Some(device_authorization_url.to_string()),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+        })
+    }
+}
+
+/// Authorization request waiting for the user to approve it in a browser.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    /// URL to open in the browser
+    pub url: String,
+    /// Anti-CSRF state echoed back on the redirect
+    pub state: String,
+    /// PKCE code verifier sent with the code exchange
+    pub code_verifier: String,
+    /// Redirect URI registered for this request
+    pub redirect_uri: String,
+}
+
+/// Device authorization returned by the provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    #[serde(alias = "verification_url")]
+    pub verification_uri: String,
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+/// Successful token endpoint response.
+#[derive(Clone, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    #[serde(default)]
+    pub expires_in: Option<i64>,
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl std::fmt::Debug for TokenResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenResponse")
+            .field("access_token", &"[REDACTED]")
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("expires_in", &self.expires_in)
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+impl TokenResponse {
+    /// Convert into an OAuth credential ready to be stored on a profile.
+    pub fn into_credential(self, email: Option<String>) -> Credential {
+        Credential::OAuth {
+            expires_at: self
+                .expires_in
+                .map(|seconds| Utc::now() + Duration::seconds(seconds)),
+            access_token: self.access_token,
+            refresh_token: self.refresh_token,
+            email,
+        }
+    }
+
+    fn into_refreshed(self, previous_refresh_token: &str) -> RefreshedCredential {
+        RefreshedCredential {
+            expires_at: self
+                .expires_in
+                .map(|seconds| Utc::now() + Duration::seconds(seconds)),
+            access_token: self.access_token,
+            // Providers that do not rotate refresh tokens omit them from the response.
+            refresh_token: self
+                .refresh_token
+                .or_else(|| Some(previous_refresh_token.to_string())),
+        }
+    }
+}
+
+/// Runs OAuth2 flows against one client configuration.
+#[derive(Clone)]
+pub struct OAuthFlow {
+    config: OAuthClientConfig,
+    client_secret: Option<String>,
+    client: Client,
+}
+
+impl OAuthFlow {
+    pub fn new(config: OAuthClientConfig, client_secret: Option<String>) -> Self {
+        Self {
+            config,
+            client_secret,
+            client: Client::builder()
+                .timeout(StdDuration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn config(&self) -> &OAuthClientConfig {
+        &self.config
+    }
+
+    /// Build the browser URL for an authorization-code request with PKCE.
+    pub fn authorization_request(&self, redirect_uri: &str) -> Result<AuthorizationRequest> {
+        let state = random_token(32);
+        let code_verifier = random_token(64);
+        let challenge = pkce_challenge(&code_verifier);
+
+        let mut url = Url::parse(&self.config.authorization_url)
+            .context("Invalid OAuth authorization URL")?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &challenge)
+            .append_pair("code_challenge_method", "S256")
+            // Google only issues refresh tokens for offline access.
+            .append_pair("access_type", "offline");
+
+        Ok(AuthorizationRequest {
+            url: url.to_string(),
+            state,
+            code_verifier,
+            redirect_uri: redirect_uri.to_string(),
+        })
+    }
+
+    /// Exchange an authorization code for tokens.
+    pub async fn exchange_code(
+        &self,
+        request: &AuthorizationRequest,
+        code: &str,
+    ) -> Result<TokenResponse> {
+        let body = self
+            .token_request(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &request.redirect_uri),
+                ("code_verifier", &request.code_verifier),
+            ])
+            .await?;
+        parse_token_response(body)
+    }
+
+    /// Start a device authorization request.
+    pub async fn start_device_authorization(&self) -> Result<DeviceAuthorization> {
+        let url = self
+            .config
+            .device_authorization_url
+            .as_deref()
+            .ok_or_else(|| anyhow!("OAuth client has no device authorization endpoint"))?;
+        let scope = self.config.scopes.join(" ");
+        self.client
+            .post(url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("scope", scope.as_str()),
+            ])
+            .send()
+            .await
+            .context("Failed to send device authorization request")?
+            .error_for_status()
+            .context("Device authorization request failed")?
+            .json::<DeviceAuthorization>()
+            .await
+            .context("Failed to parse device authorization response")
+    }
+
+    /// Poll the token endpoint until the user approves or rejects the device.
+    pub async fn poll_device_token(&self, device: &DeviceAuthorization) -> Result<TokenResponse> {
+        let deadline = tokio::time::Instant::now() + StdDuration::from_secs(device.expires_in);
+        let mut interval = device.interval.max(1);
+
+        loop {
+            tokio::time::sleep(StdDuration::from_secs(interval)).await;
+            if tokio::time::Instant::now() >= deadline {
+                bail!("Device code expired before authorization completed");
+            }
+
+            let body = self
+                .token_request(&[
+                    ("grant_type", DEVICE_CODE_GRANT),
+                    ("device_code", &device.device_code),
+                ])
+                .await?;
+            match device_poll_outcome(body)? {
+                DevicePoll::Token(token) => return Ok(token),
+                DevicePoll::Pending => {}
+                DevicePoll::SlowDown => interval += 5,
+            }
+        }
+    }
+
+    /// Exchange a refresh token for a new access token.
+    pub async fn refresh(&self, refresh_token: &str) -> Result<RefreshedCredential> {
+        let body = self
+            .token_request(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+            ])
+            .await?;
+        Ok(parse_token_response(body)?.into_refreshed(refresh_token))
+    }
+
+    async fn token_request(&self, params: &[(&str, &str)]) -> Result<Value> {
+        let mut form: Vec<(&str, &str)> = params.to_vec();
+        form.push(("client_id", &self.config.client_id));
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret));
+        }
+
+        // Error bodies (e.g. authorization_pending) are returned with 4xx
+        // statuses by most providers, so the body is inspected either way.
+        let response = self
+            .client
+            .post(&self.config.token_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&form)
+            .send()
+            .await
+            .context("Failed to send OAuth token request")?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .context("Failed to read OAuth token response")?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Unexpected OAuth token response (HTTP {})", status))
+    }
+}
+
+enum DevicePoll {
+    Token(TokenResponse),
+    Pending,
+    SlowDown,
+}
+
+fn device_poll_outcome(body: Value) -> Result<DevicePoll> {
+    match body.get("error").and_then(Value::as_str) {
+        Some("authorization_pending") => Ok(DevicePoll::Pending),
+        Some("slow_down") => Ok(DevicePoll::SlowDown),
+        Some("expired_token") => bail!("Device code expired before authorization completed"),
+        Some("access_denied") => bail!("Authorization was denied"),
+        _ => parse_token_response(body).map(DevicePoll::Token),
+    }
+}
+
+fn parse_token_response(body: Value) -> Result<TokenResponse> {
+    if let Some(error) = body.get("error").and_then(Value::as_str) {
+        let description = body
+            .get("error_description")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        bail!("OAuth token request failed: {} {}", error, description);
+    }
+    serde_json::from_value(body).context("OAuth token response is missing access_token")
+}
+
+/// Local HTTP listener that receives the authorization-code redirect.
+pub struct LoopbackRedirect {
+    listener: TcpListener,
+    redirect_uri: String,
+}
+
+impl LoopbackRedirect {
+    /// Bind an ephemeral port on the loopback interface.
+    pub async fn bind() -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .context("Failed to bind OAuth redirect listener")?;
+        let port = listener.local_addr()?.port();
+        Ok(Self {
+            listener,
+            redirect_uri: format!("http://127.0.0.1:{}{}", port, REDIRECT_PATH),
+        })
+    }
+
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// Wait for the browser redirect carrying `expected_state` and return the code.
+    pub async fn wait_for_code(self, expected_state: &str, timeout: StdDuration) -> Result<String> {
+        tokio::time::timeout(timeout, self.accept_code(expected_state))
+            .await
+            .map_err(|_| anyhow!("Timed out waiting for OAuth authorization"))?
+    }
+
+    async fn accept_code(&self, expected_state: &str) -> Result<String> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+            let mut buffer = vec![0u8; 8192];
+            let read = stream.read(&mut buffer).await?;
+            let request = String::from_utf8_lossy(&buffer[..read]);
+
+            let outcome = parse_redirect(&request, expected_state);
+            let (status, message) = match &outcome {
+                Some(Ok(_)) => (
+                    "200 OK",
+                    "Authorization complete. You can close this window.",
+                ),
+                Some(Err(_)) => (
+                    "400 Bad Request",
+                    "Authorization failed. You can close this window.",
+                ),
+                None => ("404 Not Found", "Not found"),
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}",
+                message.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+
+            // Unrelated requests (e.g. favicon) keep the listener waiting.
+            if let Some(result) = outcome {
+                return result;
+            }
+        }
+    }
+}
+
+/// Parse the redirect request line. `None` means the request was not the callback.
+fn parse_redirect(request: &str, expected_state: &str) -> Option<Result<String>> {
+    let target = request.lines().next()?.split_whitespace().nth(1)?;
+    let url = Url::parse(&format!("http://127.0.0.1{}", target)).ok()?;
+    if url.path() != REDIRECT_PATH {
+        return None;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    if let Some(error) = param("error") {
+        return Some(Err(anyhow!("Authorization failed: {}", error)));
+    }
+    if param("state").as_deref() != Some(expected_state) {
+        return Some(Err(anyhow!("OAuth state mismatch")));
+    }
+    Some(param("code").ok_or_else(|| anyhow!("Authorization redirect is missing the code")))
+}
+
+fn random_token(len: usize) -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pkce_challenge_matches_rfc_example() {
+        // RFC 7636 appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_authorization_request_contains_pkce_and_state() {
+        let config = OAuthClientConfig::preset("google", "client-1").unwrap();
+        let flow = OAuthFlow::new(config, None);
+        let request = flow
+            .authorization_request("http://127.0.0.1:4000/callback")
+            .unwrap();
+
+        let url = Url::parse(&request.url).unwrap();
+        let pairs: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(pairs["client_id"], "client-1");
+        assert_eq!(pairs["state"], request.state);
+        assert_eq!(pairs["scope"], "openid email");
+        assert_eq!(
+            pairs["code_challenge"],
+            pkce_challenge(&request.code_verifier)
+        );
+        assert_eq!(pairs["redirect_uri"], "http://127.0.0.1:4000/callback");
+    }
+
+    #[test]
+    fn test_unknown_preset_is_rejected() {
+        assert!(OAuthClientConfig::preset("dropbox", "id").is_err());
+        let github = OAuthClientConfig::preset("GitHub", "id").unwrap();
+        assert_eq!(
+            github.token_url,
+            "https://github.com/login/oauth/access_token"
+        );
+    }
+
+    #[test]
+    fn test_parse_redirect() {
+        let ok = "GET /callback?code=abc&state=xyz HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n";
+        assert_eq!(parse_redirect(ok, "xyz").unwrap().unwrap(), "abc");
+        assert!(parse_redirect(ok, "other").unwrap().is_err());
+
+        let denied = "GET /callback?error=access_denied&state=xyz HTTP/1.1\r\n\r\n";
+        assert!(parse_redirect(denied, "xyz").unwrap().is_err());
+
+        let favicon = "GET /favicon.ico HTTP/1.1\r\n\r\n";
+        assert!(parse_redirect(favicon, "xyz").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_loopback_redirect_receives_code() {
+        let redirect = LoopbackRedirect::bind().await.unwrap();
+        let uri = Url::parse(redirect.redirect_uri()).unwrap();
+        let port = uri.port().unwrap();
+
+        let client = tokio::spawn(async move {
+            let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+                .await
+                .unwrap();
+            stream
+                .write_all(b"GET /callback?code=the-code&state=s1 HTTP/1.1\r\n\r\n")
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        });
+
+        let code = redirect
+            .wait_for_code("s1", StdDuration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(code, "the-code");
+        assert!(client.await.unwrap().starts_with("HTTP/1.1 200"));
+    }
+
+    #[test]
+    fn test_device_poll_outcomes() {
+        assert!(matches!(
+            device_poll_outcome(json!({ "error": "authorization_pending" })).unwrap(),
+            DevicePoll::Pending
+        ));
+        assert!(matches!(
+            device_poll_outcome(json!({ "error": "slow_down" })).unwrap(),
+            DevicePoll::SlowDown
+        ));
+        assert!(device_poll_outcome(json!({ "error": "access_denied" })).is_err());
+        assert!(device_poll_outcome(json!({ "error": "expired_token" })).is_err());
+
+        let DevicePoll::Token(token) = device_poll_outcome(json!({
+            "access_token": "at",
+            "refresh_token": "rt",
+            "expires_in": 3600
+        }))
+        .unwrap() else {
+            panic!("expected token");
+        };
+        match token.into_credential(None) {
+            Credential::OAuth {
+                access_token,
+                refresh_token,
+                expires_at,
+                ..
+            } => {
+                assert_eq!(access_token, "at");
+                assert_eq!(refresh_token.as_deref(), Some("rt"));
+                assert!(expires_at.is_some());
+            }
+            other => panic!("unexpected credential: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_refresh_keeps_previous_refresh_token() {
+        let token: TokenResponse =
+            serde_json::from_value(json!({ "access_token": "new" })).unwrap();
+        let refreshed = token.into_refreshed("old-refresh");
+        assert_eq!(refreshed.access_token, "new");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("old-refresh"));
+    }
+}
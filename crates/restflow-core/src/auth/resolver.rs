@@ -35,6 +35,11 @@ impl CredentialResolver {
         }
     }
 
+    /// Resolve an arbitrary secret reference (e.g. an OAuth client secret).
+    pub fn resolve_secret(&self, secret_ref: &str) -> Result<Option<String>> {
+        self.secrets.get_secret(secret_ref)
+    }
+
     /// Check if all required secrets exist for this credential.
     pub fn validate(&self, credential: &SecureCredential) -> Result<()> {
         for secret_ref in credential.secret_refs() {
@@ -8,6 +8,7 @@ use specta::Type;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::oauth::OAuthClientConfig;
 use super::resolver::CredentialResolver;
 use crate::Provider;
 use crate::models::provider_auth_providers;
@@ -265,6 +266,17 @@ impl SecureCredential {
         }
     }
 
+    /// Check if the credential expires within the given window.
+    pub fn expires_within(&self, window: chrono::Duration) -> bool {
+        match self {
+            SecureCredential::ApiKey { .. } => false,
+            SecureCredential::Token { expires_at, .. }
+            | SecureCredential::OAuth { expires_at, .. } => expires_at
+                .map(|exp| exp < Utc::now() + window)
+                .unwrap_or(false),
+        }
+    }
+
     /// Check if the credential can be refreshed.
     pub fn can_refresh(&self) -> bool {
         matches!(
@@ -356,6 +368,8 @@ pub enum AuthProvider {
     OpenAICodex,
     /// Google Gemini API
     Google,
+    /// AWS Bedrock API key (`AWS_BEARER_TOKEN_BEDROCK`)
+    Bedrock,
     /// Other/Custom provider
     Other,
 }
@@ -368,6 +382,7 @@ impl std::fmt::Display for AuthProvider {
             AuthProvider::OpenAI => write!(f, "OpenAI"),
             AuthProvider::OpenAICodex => write!(f, "OpenAICodex"),
             AuthProvider::Google => write!(f, "Google"),
+            AuthProvider::Bedrock => write!(f, "Bedrock"),
             AuthProvider::Other => write!(f, "Other"),
         }
     }
@@ -435,6 +450,9 @@ pub struct AuthProfile {
     /// Cooldown end time if in cooldown
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cooldown_until: Option<DateTime<Utc>>,
+    /// OAuth client used to obtain and refresh the credential
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<OAuthClientConfig>,
 }
 
 fn default_true() -> bool {
@@ -480,6 +498,7 @@ impl AuthProfile {
             last_failed_at: None,
             failure_count: 0,
             cooldown_until: None,
+            oauth: None,
         }
     }
 
@@ -78,6 +78,11 @@ impl CredentialWriter {
         Ok(())
     }
 
+    /// Delete a single secret by reference.
+    pub fn delete_secret(&self, secret_ref: &str) -> Result<()> {
+        self.secrets.delete_secret(secret_ref)
+    }
+
     /// Update a specific secret value.
     pub fn update_secret(&self, secret_ref: &str, value: &str) -> Result<()> {
         self.secrets.set_secret(secret_ref, value, None)
@@ -1,10 +1,11 @@
 use crate::models::{
-    AgentNode, ApiKeyConfig, CodexCliExecutionMode, ModelId, ModelRef, ModelRoutingConfig,
-    SkillPreflightPolicyMode, ValidationError,
+    AgentNode, ApiKeyConfig, CodexCliExecutionMode, ExecutionStrategy, FailoverPolicy, ModelId,
+    ModelRef, ModelRoutingConfig, SkillPreflightPolicyMode, ValidationError,
 };
 use restflow_contracts::request::{
     AgentNode as ContractAgentNode, ApiKeyConfig as ContractApiKeyConfig,
     CodexCliExecutionMode as ContractCodexCliExecutionMode,
+    ExecutionStrategy as ContractExecutionStrategy,
     SkillPreflightPolicyMode as ContractSkillPreflightPolicyMode,
 };
 
@@ -36,6 +37,8 @@ pub(crate) fn agent_to_contract(value: AgentNode) -> ContractAgentNode {
         skill_variables: value.skill_variables,
         skill_preflight_policy_mode: value.skill_preflight_policy_mode.map(Into::into),
         model_routing: value.model_routing.map(Into::into),
+        dry_run: value.dry_run,
+        execution_strategy: value.execution_strategy.map(Into::into),
     }
 }
 
@@ -95,6 +98,15 @@ pub(crate) fn agent_from_contract(
             moderate_model: routing.moderate_model,
             complex_model: routing.complex_model,
             escalate_on_failure: routing.escalate_on_failure,
+            failover: routing.failover.map(|policy| FailoverPolicy {
+                fallback_models: policy.fallback_models,
+                backoff_ms: policy.backoff_ms,
+            }),
+        }),
+        dry_run: value.dry_run,
+        execution_strategy: value.execution_strategy.map(|strategy| match strategy {
+            ContractExecutionStrategy::React => ExecutionStrategy::React,
+            ContractExecutionStrategy::PlanAndExecute => ExecutionStrategy::PlanAndExecute,
         }),
     };
 
@@ -145,12 +157,19 @@ mod tests {
             skill_variables: None,
             skill_preflight_policy_mode: Some(SkillPreflightPolicyMode::Warn),
             model_routing: Some(ModelRoutingConfig::default()),
+            dry_run: Some(true),
+            execution_strategy: Some(ExecutionStrategy::PlanAndExecute),
         };
 
         let contract: ContractAgentNode = agent.clone().into();
         let decoded = AgentNode::try_from(contract).expect("agent boundary should decode");
         assert_eq!(decoded.model_ref, agent.model_ref);
         assert_eq!(decoded.model, agent.model);
+        assert_eq!(decoded.dry_run, Some(true));
+        assert_eq!(
+            decoded.execution_strategy,
+            Some(ExecutionStrategy::PlanAndExecute)
+        );
     }
 
     #[test]
@@ -210,6 +210,7 @@ pub(crate) fn store_create_request_to_core_task_spec(
         )?,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     })
 }
 
@@ -259,6 +260,9 @@ pub(crate) fn update_request_to_patch(
         )?,
         prerequisites: None,
         continuation: None,
+        callbacks: None,
+        queue: None,
+        concurrency: None,
     })
 }
 
@@ -438,6 +442,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn contract_patch_to_core_decodes_queue_policy() {
+        let contract: ContractTaskPatch = serde_json::from_value(serde_json::json!({
+            "queue": {
+                "priority": "high",
+                "max_retries": 2
+            }
+        }))
+        .expect("contract background patch");
+
+        let queue = contract_patch_to_core(contract)
+            .expect("core background patch")
+            .queue
+            .expect("queue policy");
+
+        assert_eq!(queue.priority, crate::models::QueuePriority::High);
+        assert_eq!(queue.max_retries, Some(2));
+        assert_eq!(
+            queue.retry_backoff_secs,
+            crate::models::TaskQueuePolicy::default().retry_backoff_secs
+        );
+    }
+
     #[test]
     fn create_request_to_spec_merges_memory_scope() {
         let spec = create_request_to_spec(BackgroundAgentCreateRequest {
@@ -216,49 +216,22 @@ impl DiscordChannel {
                     Err(_) => continue,
                 };
 
+                // Reconnect (op 7) and Invalid Session (op 9) require a new
+                // connection; ending the stream lets the handler restart it.
+                if matches!(payload["op"].as_u64(), Some(7) | Some(9)) {
+                    info!("Discord gateway requested reconnect");
+                    break;
+                }
+
                 // Only handle MESSAGE_CREATE (type "t")
                 if payload["t"].as_str() != Some("MESSAGE_CREATE") {
                     continue;
                 }
 
-                let data = &payload["d"];
-
-                // Skip bot messages
-                if data["author"]["bot"].as_bool() == Some(true) {
+                let Some(inbound) = parse_message_create(&payload["d"]) else {
                     continue;
-                }
-
-                let message_id = match data["id"].as_str() {
-                    Some(id) => id,
-                    None => continue,
                 };
 
-                let content = data["content"].as_str().unwrap_or("");
-                if content.is_empty() {
-                    continue;
-                }
-
-                let channel_id = data["channel_id"].as_str().unwrap_or("");
-                let author_id = data["author"]["id"].as_str().unwrap_or("");
-                let author_name = data["author"]["username"].as_str().map(|s| s.to_string());
-
-                // Build conversation ID (channel_id or channel_id:thread_id)
-                let conversation_id =
-                    if let Some(thread_id) = data["message_reference"]["message_id"].as_str() {
-                        format!("{}:{}", channel_id, thread_id)
-                    } else {
-                        channel_id.to_string()
-                    };
-
-                let mut inbound = InboundMessage::new(
-                    format!("dc_{}", message_id),
-                    ChannelType::Discord,
-                    author_id,
-                    &conversation_id,
-                    content,
-                );
-                inbound.sender_name = author_name;
-
                 if tx.send(inbound).await.is_err() {
                     debug!("Discord message channel closed");
                     break;
@@ -287,6 +260,49 @@ impl DiscordChannel {
     }
 }
 
+/// Convert a gateway `MESSAGE_CREATE` payload into an inbound message.
+///
+/// The conversation is the channel the message was posted in (threads are
+/// channels of their own in Discord), so replies go back to the same place.
+/// Bot messages and messages without text are skipped.
+pub(crate) fn parse_message_create(data: &Value) -> Option<InboundMessage> {
+    if data["author"]["bot"].as_bool() == Some(true) {
+        return None;
+    }
+
+    let message_id = data["id"].as_str()?;
+    let content = data["content"].as_str().unwrap_or("");
+    if content.is_empty() {
+        return None;
+    }
+
+    let channel_id = data["channel_id"].as_str()?;
+    let author_id = data["author"]["id"].as_str().unwrap_or("");
+
+    let mut metadata = json!({
+        "chat_type": if data["guild_id"].is_string() { "guild" } else { "dm" },
+    });
+    if let Some(guild_id) = data["guild_id"].as_str() {
+        metadata["account_id"] = json!(guild_id);
+    }
+
+    let mut inbound = InboundMessage::new(
+        format!("dc_{}", message_id),
+        ChannelType::Discord,
+        author_id,
+        channel_id,
+        content,
+    )
+    .with_metadata(metadata);
+    if let Some(name) = data["author"]["username"].as_str() {
+        inbound = inbound.with_sender_name(name);
+    }
+    if let Some(reply_to) = data["message_reference"]["message_id"].as_str() {
+        inbound = inbound.with_reply_to(reply_to);
+    }
+    Some(inbound)
+}
+
 #[async_trait]
 impl Channel for DiscordChannel {
     fn channel_type(&self) -> ChannelType {
@@ -345,11 +361,53 @@ mod tests {
     }
 
     #[test]
-    fn test_conversation_id_with_thread() {
-        let channel_id = "123";
-        let thread_id = "456";
-        let conv_id = format!("{}:{}", channel_id, thread_id);
-        assert_eq!(conv_id, "123:456");
+    fn test_parse_guild_reply_keeps_channel_as_conversation() {
+        let data = json!({
+            "id": "900",
+            "channel_id": "123",
+            "guild_id": "777",
+            "content": "hello agent",
+            "author": { "id": "42", "username": "ann" },
+            "message_reference": { "message_id": "456" },
+        });
+        let inbound = parse_message_create(&data).unwrap();
+        assert_eq!(inbound.id, "dc_900");
+        assert_eq!(inbound.conversation_id, "123");
+        assert_eq!(inbound.reply_to.as_deref(), Some("456"));
+        assert_eq!(inbound.sender_id, "42");
+        assert_eq!(inbound.sender_name.as_deref(), Some("ann"));
+        let metadata = inbound.metadata.unwrap();
+        assert_eq!(metadata["chat_type"], "guild");
+        assert_eq!(metadata["account_id"], "777");
+    }
+
+    #[test]
+    fn test_parse_skips_bot_and_empty_messages() {
+        let bot = json!({
+            "id": "1",
+            "channel_id": "123",
+            "content": "beep",
+            "author": { "id": "9", "bot": true },
+        });
+        assert!(parse_message_create(&bot).is_none());
+
+        let empty = json!({
+            "id": "2",
+            "channel_id": "123",
+            "content": "",
+            "author": { "id": "42" },
+        });
+        assert!(parse_message_create(&empty).is_none());
+
+        let dm = json!({
+            "id": "3",
+            "channel_id": "55",
+            "content": "hi",
+            "author": { "id": "42" },
+        });
+        let metadata = parse_message_create(&dm).unwrap().metadata.unwrap();
+        assert_eq!(metadata["chat_type"], "dm");
+        assert!(metadata.get("account_id").is_none());
     }
 
     #[test]
@@ -0,0 +1,478 @@
+//! Matrix channel implementation.
+//!
+//! Uses the Matrix Client-Server API: `/sync` long-polling for receiving
+//! messages and the room `send` endpoint for replies. Rooms the bot is invited
+//! to are joined automatically. End-to-end encrypted rooms are not supported.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::{OnceCell, mpsc};
+use tracing::{debug, error, info, warn};
+
+use super::chunk::chunk_markdown;
+use super::traits::Channel;
+use super::types::{ChannelType, InboundMessage, OutboundMessage};
+
+const MATRIX_MAX_MESSAGE_LEN: usize = 32000;
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+const SYNC_RETRY_DELAY: Duration = Duration::from_secs(5);
+const TYPING_TIMEOUT_MS: u64 = 30_000;
+
+/// Only room timelines are needed; skip presence and account data.
+const SYNC_FILTER: &str = r#"{"presence":{"types":[]},"account_data":{"types":[]},"room":{"timeline":{"limit":50,"types":["m.room.message"]},"state":{"lazy_load_members":true},"ephemeral":{"types":[]},"account_data":{"types":[]}}}"#;
+
+/// Matrix channel configuration.
+#[derive(Debug, Clone)]
+pub struct MatrixConfig {
+    /// Homeserver base URL (e.g. `https://matrix.org`).
+    pub homeserver_url: String,
+    /// Access token of the bot account.
+    pub access_token: String,
+    /// Default room ID for notifications.
+    pub default_room_id: Option<String>,
+}
+
+/// Matrix channel using `/sync` for receiving and the room send API for sending.
+pub struct MatrixChannel {
+    config: MatrixConfig,
+    client: Client,
+    polling: Arc<AtomicBool>,
+    txn_counter: AtomicU64,
+    user_id: OnceCell<String>,
+}
+
+impl MatrixChannel {
+    pub fn new(config: MatrixConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            polling: Arc::new(AtomicBool::new(false)),
+            txn_counter: AtomicU64::new(0),
+            user_id: OnceCell::new(),
+        }
+    }
+
+    pub fn with_credentials(homeserver_url: &str, access_token: &str) -> Self {
+        Self::new(MatrixConfig {
+            homeserver_url: homeserver_url.trim_end_matches('/').to_string(),
+            access_token: access_token.to_string(),
+            default_room_id: None,
+        })
+    }
+
+    pub fn with_default_room(mut self, room_id: String) -> Self {
+        self.config.default_room_id = Some(room_id);
+        self
+    }
+
+    fn api_url(homeserver_url: &str, path: &str) -> String {
+        format!("{}/_matrix/client/v3{}", homeserver_url, path)
+    }
+
+    /// Unique transaction ID so retried sends are deduplicated by the server.
+    fn next_txn_id(&self) -> String {
+        format!(
+            "restflow-{}-{}",
+            chrono::Utc::now().timestamp_millis(),
+            self.txn_counter.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    /// Send a text message to a room.
+    async fn send_message(&self, room_id: &str, text: &str) -> Result<()> {
+        let room = urlencoding::encode(room_id);
+        for chunk in chunk_markdown(text, Some(MATRIX_MAX_MESSAGE_LEN)) {
+            let url = Self::api_url(
+                &self.config.homeserver_url,
+                &format!("/rooms/{}/send/m.room.message/{}", room, self.next_txn_id()),
+            );
+            let resp = self
+                .client
+                .put(url)
+                .bearer_auth(&self.config.access_token)
+                .json(&json!({ "msgtype": "m.text", "body": chunk }))
+                .send()
+                .await?;
+
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                warn!("Matrix send failed ({}): {}", status, body);
+            }
+        }
+        Ok(())
+    }
+
+    /// Start the `/sync` loop and return a message stream.
+    fn start_sync(
+        &self,
+    ) -> Option<Pin<Box<dyn tokio_stream::Stream<Item = InboundMessage> + Send>>> {
+        let homeserver = self.config.homeserver_url.clone();
+        let token = self.config.access_token.clone();
+        let client = self.client.clone();
+        let polling = self.polling.clone();
+
+        if polling.swap(true, Ordering::SeqCst) {
+            warn!("Matrix sync already running");
+            return None;
+        }
+
+        let (tx, rx) = mpsc::channel::<InboundMessage>(256);
+
+        tokio::spawn(async move {
+            let _guard = scopeguard::guard((), |_| {
+                polling.store(false, Ordering::SeqCst);
+            });
+
+            let own_user_id = match Self::whoami(&client, &homeserver, &token).await {
+                Ok(user_id) => user_id,
+                Err(e) => {
+                    error!("Failed to authenticate Matrix account: {}", e);
+                    return;
+                }
+            };
+            info!("Matrix sync started as {}", own_user_id);
+
+            // The first sync only establishes a position so old room history
+            // is not replayed as new messages.
+            let mut since: Option<String> = None;
+            let mut initial = true;
+
+            while polling.load(Ordering::SeqCst) && !tx.is_closed() {
+                let timeout = if initial { 0 } else { SYNC_TIMEOUT_MS };
+                let body = match Self::sync(&client, &homeserver, &token, since.as_deref(), timeout)
+                    .await
+                {
+                    Ok(body) => body,
+                    Err(e) => {
+                        warn!("Matrix sync failed: {}", e);
+                        if tx.is_closed() {
+                            break;
+                        }
+                        tokio::time::sleep(SYNC_RETRY_DELAY).await;
+                        continue;
+                    }
+                };
+
+                for room_id in invited_rooms(&body) {
+                    if let Err(e) = Self::join_room(&client, &homeserver, &token, &room_id).await {
+                        warn!("Failed to join Matrix room {}: {}", room_id, e);
+                    } else {
+                        info!("Joined Matrix room {}", room_id);
+                    }
+                }
+
+                if !initial {
+                    for inbound in parse_sync_messages(&body, &own_user_id) {
+                        if tx.send(inbound).await.is_err() {
+                            debug!("Matrix message channel closed");
+                            return;
+                        }
+                    }
+                }
+
+                since = body["next_batch"].as_str().map(str::to_string);
+                initial = false;
+            }
+
+            info!("Matrix sync ended");
+        });
+
+        Some(Box::pin(tokio_stream::wrappers::ReceiverStream::new(rx)))
+    }
+
+    async fn whoami(client: &Client, homeserver: &str, token: &str) -> Result<String> {
+        let body: Value = client
+            .get(Self::api_url(homeserver, "/account/whoami"))
+            .bearer_auth(token)
+            .send()
+            .await
+            .context("Failed to reach Matrix homeserver")?
+            .error_for_status()?
+            .json()
+            .await?;
+        body["user_id"]
+            .as_str()
+            .map(str::to_string)
+            .context("Missing 'user_id' in Matrix whoami response")
+    }
+
+    async fn sync(
+        client: &Client,
+        homeserver: &str,
+        token: &str,
+        since: Option<&str>,
+        timeout_ms: u64,
+    ) -> Result<Value> {
+        let mut query = vec![
+            ("timeout", timeout_ms.to_string()),
+            ("filter", SYNC_FILTER.to_string()),
+        ];
+        if let Some(since) = since {
+            query.push(("since", since.to_string()));
+        }
+        let body = client
+            .get(Self::api_url(homeserver, "/sync"))
+            .bearer_auth(token)
+            .query(&query)
+            .timeout(Duration::from_millis(timeout_ms) + Duration::from_secs(30))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(body)
+    }
+
+    async fn join_room(
+        client: &Client,
+        homeserver: &str,
+        token: &str,
+        room_id: &str,
+    ) -> Result<()> {
+        client
+            .post(Self::api_url(
+                homeserver,
+                &format!("/rooms/{}/join", urlencoding::encode(room_id)),
+            ))
+            .bearer_auth(token)
+            .json(&json!({}))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Room IDs the account has pending invites for.
+pub(crate) fn invited_rooms(sync: &Value) -> Vec<String> {
+    sync["rooms"]["invite"]
+        .as_object()
+        .map(|rooms| rooms.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Extract text messages from joined room timelines of a `/sync` response.
+///
+/// The room ID is the conversation. Messages sent by the bot itself and
+/// non-text message types (notices, images, etc.) are skipped.
+pub(crate) fn parse_sync_messages(sync: &Value, own_user_id: &str) -> Vec<InboundMessage> {
+    let Some(rooms) = sync["rooms"]["join"].as_object() else {
+        return Vec::new();
+    };
+
+    let mut messages = Vec::new();
+    for (room_id, room) in rooms {
+        let Some(events) = room["timeline"]["events"].as_array() else {
+            continue;
+        };
+        for event in events {
+            if event["type"].as_str() != Some("m.room.message") {
+                continue;
+            }
+            let sender = event["sender"].as_str().unwrap_or("");
+            if sender.is_empty() || sender == own_user_id {
+                continue;
+            }
+            let content = &event["content"];
+            if content["msgtype"].as_str() != Some("m.text") {
+                continue;
+            }
+            // Edits arrive as new events; only the original is handled.
+            if content["m.relates_to"]["rel_type"].as_str() == Some("m.replace") {
+                continue;
+            }
+            let body = content["body"].as_str().unwrap_or("");
+            let Some(event_id) = event["event_id"].as_str() else {
+                continue;
+            };
+            if body.is_empty() {
+                continue;
+            }
+
+            let mut inbound = InboundMessage::new(
+                format!("mx_{}", event_id),
+                ChannelType::Matrix,
+                sender,
+                room_id,
+                body,
+            )
+            .with_metadata(json!({
+                "chat_type": "room",
+                "account_id": own_user_id,
+            }));
+            if let Some(timestamp) = event["origin_server_ts"].as_i64() {
+                inbound.timestamp = timestamp;
+            }
+            if let Some(reply_to) = content["m.relates_to"]["m.in_reply_to"]["event_id"].as_str() {
+                inbound = inbound.with_reply_to(reply_to);
+            }
+            messages.push(inbound);
+        }
+    }
+    messages
+}
+
+#[async_trait]
+impl Channel for MatrixChannel {
+    fn channel_type(&self) -> ChannelType {
+        ChannelType::Matrix
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.config.homeserver_url.is_empty() && !self.config.access_token.is_empty()
+    }
+
+    async fn send(&self, message: OutboundMessage) -> Result<()> {
+        self.send_message(&message.conversation_id, &message.content)
+            .await
+    }
+
+    async fn send_typing(&self, conversation_id: &str) -> Result<()> {
+        // The typing endpoint needs the bot's own user ID.
+        let user_id = self
+            .user_id
+            .get_or_try_init(|| {
+                Self::whoami(
+                    &self.client,
+                    &self.config.homeserver_url,
+                    &self.config.access_token,
+                )
+            })
+            .await?;
+        self.client
+            .put(Self::api_url(
+                &self.config.homeserver_url,
+                &format!(
+                    "/rooms/{}/typing/{}",
+                    urlencoding::encode(conversation_id),
+                    urlencoding::encode(user_id)
+                ),
+            ))
+            .bearer_auth(&self.config.access_token)
+            .json(&json!({ "typing": true, "timeout": TYPING_TIMEOUT_MS }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    fn start_receiving(
+        &self,
+    ) -> Option<Pin<Box<dyn tokio_stream::Stream<Item = InboundMessage> + Send>>> {
+        self.start_sync()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_channel_is_configured() {
+        assert!(MatrixChannel::with_credentials("https://matrix.org", "token").is_configured());
+        assert!(!MatrixChannel::with_credentials("https://matrix.org", "").is_configured());
+        assert!(!MatrixChannel::with_credentials("", "token").is_configured());
+    }
+
+    #[test]
+    fn test_with_credentials_trims_homeserver() {
+        let ch = MatrixChannel::with_credentials("https://matrix.org/", "token")
+            .with_default_room("!room:matrix.org".into());
+        assert_eq!(ch.config.homeserver_url, "https://matrix.org");
+        assert_eq!(
+            ch.config.default_room_id.as_deref(),
+            Some("!room:matrix.org")
+        );
+        assert_eq!(ch.channel_type(), ChannelType::Matrix);
+    }
+
+    #[test]
+    fn test_txn_ids_are_unique() {
+        let ch = MatrixChannel::with_credentials("https://matrix.org", "token");
+        assert_ne!(ch.next_txn_id(), ch.next_txn_id());
+    }
+
+    #[test]
+    fn test_parse_sync_messages() {
+        let sync = json!({
+            "next_batch": "s2",
+            "rooms": {
+                "join": {
+                    "!ops:example.org": {
+                        "timeline": {
+                            "events": [
+                                {
+                                    "type": "m.room.message",
+                                    "event_id": "$e1",
+                                    "sender": "@ann:example.org",
+                                    "origin_server_ts": 1700000000000i64,
+                                    "content": {
+                                        "msgtype": "m.text",
+                                        "body": "deploy status?",
+                                        "m.relates_to": {
+                                            "m.in_reply_to": { "event_id": "$e0" }
+                                        }
+                                    }
+                                },
+                                {
+                                    "type": "m.room.message",
+                                    "event_id": "$e2",
+                                    "sender": "@bot:example.org",
+                                    "content": { "msgtype": "m.text", "body": "echo" }
+                                },
+                                {
+                                    "type": "m.room.message",
+                                    "event_id": "$e3",
+                                    "sender": "@ann:example.org",
+                                    "content": { "msgtype": "m.image", "body": "cat.png" }
+                                },
+                                {
+                                    "type": "m.room.message",
+                                    "event_id": "$e4",
+                                    "sender": "@ann:example.org",
+                                    "content": {
+                                        "msgtype": "m.text",
+                                        "body": "* deploy status??",
+                                        "m.relates_to": { "rel_type": "m.replace", "event_id": "$e1" }
+                                    }
+                                }
+                            ]
+                        }
+                    }
+                },
+                "invite": { "!new:example.org": {} }
+            }
+        });
+
+        let messages = parse_sync_messages(&sync, "@bot:example.org");
+        assert_eq!(messages.len(), 1);
+        let message = &messages[0];
+        assert_eq!(message.id, "mx_$e1");
+        assert_eq!(message.conversation_id, "!ops:example.org");
+        assert_eq!(message.sender_id, "@ann:example.org");
+        assert_eq!(message.content, "deploy status?");
+        assert_eq!(message.timestamp, 1700000000000);
+        assert_eq!(message.reply_to.as_deref(), Some("$e0"));
+        assert_eq!(
+            message.metadata.as_ref().unwrap()["account_id"],
+            "@bot:example.org"
+        );
+
+        assert_eq!(invited_rooms(&sync), vec!["!new:example.org".to_string()]);
+        assert!(parse_sync_messages(&json!({}), "@bot:example.org").is_empty());
+    }
+
+    #[test]
+    fn test_sync_prevents_double_start() {
+        let ch = MatrixChannel::with_credentials("https://matrix.org", "token");
+        ch.polling.store(true, Ordering::SeqCst);
+        assert!(ch.start_sync().is_none());
+    }
+}
@@ -90,6 +90,7 @@
 
 pub mod chunk;
 pub mod discord;
+pub mod matrix;
 pub mod pairing;
 pub mod plugin;
 mod reply_sender;
@@ -101,6 +102,7 @@ mod traits;
 mod types;
 
 pub use discord::{DiscordChannel, DiscordConfig};
+pub use matrix::{MatrixChannel, MatrixConfig};
 pub use pairing::{AllowedPeer, PairingManager, PairingRequest};
 pub use plugin::{ChannelPlugin, ChannelRegistry};
 pub use reply_sender::ChannelReplySender;
@@ -109,7 +111,9 @@ pub use router::ChannelRouter;
 pub use slack::{SlackChannel, SlackConfig};
 pub use telegram::{TelegramChannel, TelegramConfig};
 pub use traits::{Channel, StreamReceiver, WebhookReceiver};
-pub use types::{ChannelType, ConversationContext, InboundMessage, MessageLevel, OutboundMessage};
+pub use types::{
+    ChannelType, ConversationContext, InboundMessage, InlineButton, MessageLevel, OutboundMessage,
+};
 
 #[cfg(test)]
 pub use traits::mock;
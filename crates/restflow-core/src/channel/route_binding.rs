@@ -119,6 +119,7 @@ impl RouteResolver {
             "telegram" => "telegram".to_string(),
             "discord" => "discord".to_string(),
             "slack" => "slack".to_string(),
+            "matrix" => "matrix".to_string(),
             "email" => "email".to_string(),
             "webhook" => "webhook".to_string(),
             // If unknown, return as-is (let caller handle validation)
@@ -4,6 +4,7 @@
 
 use anyhow::{Result, anyhow};
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -132,6 +133,72 @@ impl ChannelRouter {
         channel.send_typing(conversation_id).await
     }
 
+    /// Send message to a specific channel and return the delivered message ID
+    pub async fn send_to_with_id(
+        &self,
+        channel_type: ChannelType,
+        message: OutboundMessage,
+    ) -> Result<Option<String>> {
+        let channel = self.configured_channel(channel_type)?;
+        channel.send_with_id(message).await
+    }
+
+    /// Check if a registered, configured channel supports message editing
+    pub fn supports_message_editing(&self, channel_type: ChannelType) -> bool {
+        self.registry
+            .get(channel_type)
+            .is_some_and(|channel| channel.is_configured() && channel.supports_message_editing())
+    }
+
+    /// Edit a previously sent message in a specific channel
+    pub async fn edit_message_in(
+        &self,
+        channel_type: ChannelType,
+        conversation_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> Result<()> {
+        let channel = self.configured_channel(channel_type)?;
+        channel
+            .edit_message(conversation_id, message_id, content)
+            .await
+    }
+
+    /// Send a local file to a specific channel
+    pub async fn send_file_to(
+        &self,
+        channel_type: ChannelType,
+        conversation_id: &str,
+        file_path: &Path,
+        caption: Option<&str>,
+    ) -> Result<()> {
+        let channel = self.configured_channel(channel_type)?;
+        channel.send_file(conversation_id, file_path, caption).await
+    }
+
+    fn configured_channel(&self, channel_type: ChannelType) -> Result<&Arc<dyn ChannelPlugin>> {
+        let channel = self
+            .registry
+            .get(channel_type)
+            .ok_or_else(|| anyhow!("Channel {:?} not registered", channel_type))?;
+
+        if !channel.is_configured() {
+            return Err(anyhow!("Channel {:?} not configured", channel_type));
+        }
+        Ok(channel)
+    }
+
+    /// Send a local audio file as a voice message to a specific channel
+    pub async fn send_voice_to(
+        &self,
+        channel_type: ChannelType,
+        conversation_id: &str,
+        file_path: &Path,
+    ) -> Result<()> {
+        let channel = self.configured_channel(channel_type)?;
+        channel.send_voice(conversation_id, file_path).await
+    }
+
     /// Reply to a conversation (auto-selects the channel based on context)
     ///
     /// This method looks up the conversation context to determine which channel
@@ -487,6 +554,27 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_rich_reply_capabilities_require_configured_channel() {
+        let mut router = ChannelRouter::new();
+        router.register(MockChannel::new(ChannelType::Telegram));
+        router.register(MockChannel::unconfigured(ChannelType::Discord));
+
+        assert!(!router.supports_message_editing(ChannelType::Telegram));
+        assert!(!router.supports_message_editing(ChannelType::Slack));
+
+        let id = router
+            .send_to_with_id(ChannelType::Telegram, OutboundMessage::new("chat-1", "Hi"))
+            .await
+            .unwrap();
+        assert_eq!(id, None);
+
+        let result = router
+            .edit_message_in(ChannelType::Discord, "chat-1", "1", "edited")
+            .await;
+        assert!(result.unwrap_err().to_string().contains("not configured"));
+    }
+
     #[tokio::test]
     async fn test_send_to_unconfigured_channel() {
         let mut router = ChannelRouter::new();
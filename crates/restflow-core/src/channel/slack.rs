@@ -177,47 +177,10 @@ impl SlackChannel {
                     continue;
                 }
 
-                let event = &payload["payload"]["event"];
-                let event_type = event["type"].as_str().unwrap_or("");
-
-                if event_type != "message" {
-                    continue;
-                }
-
-                // Skip bot messages
-                if event["bot_id"].as_str().is_some() {
-                    continue;
-                }
-
-                // Skip message subtypes (edits, joins, etc.)
-                if event["subtype"].as_str().is_some() {
-                    continue;
-                }
-
-                let msg_text = event["text"].as_str().unwrap_or("");
-                if msg_text.is_empty() {
+                let Some(inbound) = parse_events_api_payload(&payload["payload"]) else {
                     continue;
-                }
-
-                let ts = event["ts"].as_str().unwrap_or("");
-                let channel_id = event["channel"].as_str().unwrap_or("");
-                let user_id = event["user"].as_str().unwrap_or("");
-
-                // Build conversation ID (channel or channel:thread_ts)
-                let conversation_id = if let Some(thread_ts) = event["thread_ts"].as_str() {
-                    format!("{}:{}", channel_id, thread_ts)
-                } else {
-                    channel_id.to_string()
                 };
 
-                let inbound = InboundMessage::new(
-                    format!("sk_{}", ts),
-                    ChannelType::Slack,
-                    user_id,
-                    &conversation_id,
-                    msg_text,
-                );
-
                 if tx.send(inbound).await.is_err() {
                     debug!("Slack message channel closed");
                     break;
@@ -252,6 +215,55 @@ impl SlackChannel {
     }
 }
 
+/// Convert an Events API payload into an inbound message.
+///
+/// Only plain user `message` events are handled: bot messages and subtypes
+/// (edits, joins, etc.) are skipped. Thread replies use `channel:thread_ts`
+/// as the conversation so answers stay in the thread.
+pub(crate) fn parse_events_api_payload(payload: &Value) -> Option<InboundMessage> {
+    let event = &payload["event"];
+    if event["type"].as_str() != Some("message")
+        || event["bot_id"].is_string()
+        || event["subtype"].is_string()
+    {
+        return None;
+    }
+
+    let msg_text = event["text"].as_str().unwrap_or("");
+    if msg_text.is_empty() {
+        return None;
+    }
+
+    let ts = event["ts"].as_str().unwrap_or("");
+    let channel_id = event["channel"].as_str()?;
+    let user_id = event["user"].as_str().unwrap_or("");
+
+    // Build conversation ID (channel or channel:thread_ts)
+    let conversation_id = if let Some(thread_ts) = event["thread_ts"].as_str() {
+        format!("{}:{}", channel_id, thread_ts)
+    } else {
+        channel_id.to_string()
+    };
+
+    let mut metadata = json!({
+        "chat_type": event["channel_type"].as_str().unwrap_or("channel"),
+    });
+    if let Some(team_id) = payload["team_id"].as_str() {
+        metadata["account_id"] = json!(team_id);
+    }
+
+    Some(
+        InboundMessage::new(
+            format!("sk_{}", ts),
+            ChannelType::Slack,
+            user_id,
+            &conversation_id,
+            msg_text,
+        )
+        .with_metadata(metadata),
+    )
+}
+
 #[async_trait]
 impl Channel for SlackChannel {
     fn channel_type(&self) -> ChannelType {
@@ -318,6 +330,53 @@ mod tests {
         assert!(id.starts_with("sk_"));
     }
 
+    #[test]
+    fn test_parse_thread_reply() {
+        let payload = json!({
+            "team_id": "T1",
+            "event": {
+                "type": "message",
+                "channel": "C123",
+                "channel_type": "channel",
+                "user": "U42",
+                "text": "summarize this thread",
+                "ts": "1700000001.000200",
+                "thread_ts": "1700000000.000100",
+            },
+        });
+        let inbound = parse_events_api_payload(&payload).unwrap();
+        assert_eq!(inbound.id, "sk_1700000001.000200");
+        assert_eq!(inbound.conversation_id, "C123:1700000000.000100");
+        assert_eq!(inbound.sender_id, "U42");
+        let metadata = inbound.metadata.unwrap();
+        assert_eq!(metadata["chat_type"], "channel");
+        assert_eq!(metadata["account_id"], "T1");
+    }
+
+    #[test]
+    fn test_parse_skips_bot_and_subtype_events() {
+        let event = |extra: Value| {
+            let mut event = json!({
+                "type": "message",
+                "channel": "D1",
+                "channel_type": "im",
+                "user": "U42",
+                "text": "hi",
+                "ts": "1.0",
+            });
+            for (key, value) in extra.as_object().unwrap() {
+                event[key] = value.clone();
+            }
+            json!({ "event": event })
+        };
+        assert!(parse_events_api_payload(&event(json!({}))).is_some());
+        assert!(parse_events_api_payload(&event(json!({ "bot_id": "B1" }))).is_none());
+        assert!(
+            parse_events_api_payload(&event(json!({ "subtype": "message_changed" }))).is_none()
+        );
+        assert!(parse_events_api_payload(&event(json!({ "type": "app_mention" }))).is_none());
+    }
+
     #[test]
     fn test_conversation_id_parsing() {
         let conv = "C123:1234567890.123456";
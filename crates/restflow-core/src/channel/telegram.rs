@@ -20,9 +20,14 @@ use uuid::Uuid;
 
 use super::chunk::chunk_markdown;
 use super::traits::{Channel, StreamReceiver};
-use super::types::{ChannelType, InboundMessage, OutboundMessage};
+use super::types::{ChannelType, InboundMessage, InlineButton, OutboundMessage};
 
 const TELEGRAM_API_BASE: &str = "https://api.telegram.org/bot";
+/// Maximum text length accepted by `sendMessage` / `editMessageText`.
+const TELEGRAM_MAX_MESSAGE_CHARS: usize = 4096;
+/// Telegram rejects callback data longer than 64 bytes.
+const TELEGRAM_MAX_CALLBACK_DATA_BYTES: usize = 64;
+const PHOTO_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
 
 /// Telegram channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -184,6 +189,7 @@ impl TelegramChannel {
         parse_mode: Option<&str>,
         reply_to_message_id: Option<&str>,
         message_thread_id: Option<i64>,
+        reply_markup: Option<&serde_json::Value>,
     ) -> Result<TelegramMessageResponse> {
         let url = self.api_url("sendMessage");
 
@@ -209,6 +215,10 @@ impl TelegramChannel {
             params["message_thread_id"] = serde_json::Value::Number(thread_id.into());
         }
 
+        if let Some(markup) = reply_markup {
+            params["reply_markup"] = markup.clone();
+        }
+
         let response = self
             .client
             .post(&url)
@@ -243,7 +253,7 @@ impl TelegramChannel {
         let params = serde_json::json!({
             "offset": if offset > 0 { offset + 1 } else { 0 },
             "timeout": self.config.polling_timeout,
-            "allowed_updates": ["message"],
+            "allowed_updates": ["message", "callback_query"],
         });
 
         let response = self
@@ -352,6 +362,10 @@ impl TelegramChannel {
 
     /// Convert Telegram update to InboundMessage
     async fn convert_update(&self, update: TelegramUpdate) -> Option<InboundMessage> {
+        if let Some(callback) = update.callback_query {
+            return Self::convert_callback_query(update.update_id, callback);
+        }
+
         let message = update.message?;
         let from = message.from?;
         let conversation_id =
@@ -483,6 +497,203 @@ impl TelegramChannel {
         None
     }
 
+    /// Convert an inline keyboard press into an InboundMessage carrying the
+    /// button's callback data, so it is routed like a typed message.
+    fn convert_callback_query(
+        update_id: i64,
+        callback: TelegramCallbackQuery,
+    ) -> Option<InboundMessage> {
+        let data = callback.data.filter(|data| !data.trim().is_empty())?;
+        let message = callback.message?;
+        let conversation_id =
+            Self::build_conversation_id(message.chat.id, message.message_thread_id);
+
+        let mut metadata = serde_json::json!({
+            "chat_type": message.chat.r#type,
+            "chat_title": message.chat.title,
+            "update_id": update_id,
+            "callback_query_id": callback.id,
+            "callback_message_id": format!("tg_{}", message.message_id),
+        });
+        if let Some(thread_id) = message.message_thread_id {
+            metadata["message_thread_id"] = serde_json::Value::Number(thread_id.into());
+        }
+
+        let sender_name = callback
+            .from
+            .username
+            .clone()
+            .or_else(|| callback.from.first_name.clone())
+            .unwrap_or_default();
+
+        let inbound = InboundMessage::new(
+            format!("tg_cb_{}", callback.id),
+            ChannelType::Telegram,
+            callback.from.id.to_string(),
+            conversation_id,
+            data,
+        )
+        .with_sender_name(sender_name)
+        .with_metadata(metadata);
+        Some(inbound)
+    }
+
+    /// Stop the button spinner and remove the keyboard so a choice can only
+    /// be made once.
+    async fn acknowledge_callback_query(&self, callback: &TelegramCallbackQuery) -> Result<()> {
+        self.post_json(
+            "answerCallbackQuery",
+            &serde_json::json!({ "callback_query_id": callback.id }),
+        )
+        .await?;
+
+        if let Some(message) = &callback.message {
+            self.post_json(
+                "editMessageReplyMarkup",
+                &serde_json::json!({
+                    "chat_id": message.chat.id,
+                    "message_id": message.message_id,
+                    "reply_markup": { "inline_keyboard": [] },
+                }),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Call a Bot API method whose result is not needed
+    async fn post_json(&self, method: &str, params: &serde_json::Value) -> Result<()> {
+        let response = self
+            .client
+            .post(self.api_url(method))
+            .json(params)
+            .timeout(std::time::Duration::from_secs(self.config.api_timeout_secs))
+            .send()
+            .await?;
+
+        let api_response: TelegramResponse<serde_json::Value> = response.json().await?;
+        if api_response.ok {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Telegram API error: {}",
+                api_response.description.unwrap_or_default()
+            ))
+        }
+    }
+
+    /// Send all chunks of a message, attaching buttons to the last one.
+    ///
+    /// Returns the ID of the last delivered message.
+    async fn send_chunks(&self, message: &OutboundMessage) -> Result<Option<i64>> {
+        let formatted = self.format_message(message);
+        let parse_mode = message.parse_mode.as_deref();
+
+        // Parse conversation_id to extract chat_id and thread_id
+        let (chat_id, parsed_thread_id) = Self::parse_conversation_id(&message.conversation_id);
+
+        // Use explicit message_thread_id if provided, otherwise use parsed thread_id
+        let thread_id = message.message_thread_id.or(parsed_thread_id);
+
+        let reply_markup = if message.buttons.is_empty() {
+            None
+        } else {
+            Some(inline_keyboard_markup(&message.buttons)?)
+        };
+
+        let chunks = chunk_markdown(&formatted, None);
+        let mut last_message_id = None;
+        for (index, chunk) in chunks.iter().enumerate() {
+            let is_last = index + 1 == chunks.len();
+            let sent = self
+                .send_message(
+                    &chat_id,
+                    chunk,
+                    parse_mode,
+                    message.reply_to.as_deref(),
+                    thread_id,
+                    reply_markup.as_ref().filter(|_| is_last),
+                )
+                .await?;
+            last_message_id = Some(sent.message_id);
+        }
+
+        Ok(last_message_id)
+    }
+
+    /// Replace the text of a sent message via `editMessageText`
+    async fn edit_message_text(&self, chat_id: &str, message_id: i64, text: &str) -> Result<()> {
+        let text: String = text.chars().take(TELEGRAM_MAX_MESSAGE_CHARS).collect();
+        let result = self
+            .post_json(
+                "editMessageText",
+                &serde_json::json!({
+                    "chat_id": chat_id,
+                    "message_id": message_id,
+                    "text": text,
+                }),
+            )
+            .await;
+        match result {
+            // Editing to identical text is rejected; treat it as done.
+            Err(e) if e.to_string().contains("message is not modified") => Ok(()),
+            other => other,
+        }
+    }
+
+    /// Upload a local file as a photo (image extensions) or document
+    async fn send_file_upload(
+        &self,
+        chat_id: &str,
+        file_path: &Path,
+        caption: Option<&str>,
+        message_thread_id: Option<i64>,
+    ) -> Result<()> {
+        let (method, field) = if is_photo_path(file_path) {
+            ("sendPhoto", "photo")
+        } else {
+            ("sendDocument", "document")
+        };
+        let bytes = fs::read(file_path).await?;
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file")
+            .to_string();
+
+        let mut form = reqwest::multipart::Form::new()
+            .text("chat_id", chat_id.to_string())
+            .part(
+                field,
+                reqwest::multipart::Part::bytes(bytes).file_name(file_name),
+            );
+        if let Some(caption) = caption.filter(|caption| !caption.is_empty()) {
+            form = form.text("caption", caption.to_string());
+        }
+        if let Some(thread_id) = message_thread_id {
+            form = form.text("message_thread_id", thread_id.to_string());
+        }
+
+        let response = self
+            .client
+            .post(self.api_url(method))
+            .multipart(form)
+            .timeout(std::time::Duration::from_secs(self.config.api_timeout_secs))
+            .send()
+            .await?;
+
+        let api_response: TelegramResponse<TelegramMessageResponse> = response.json().await?;
+        if api_response.ok {
+            debug!("Sent file {} to {}", file_path.display(), chat_id);
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Telegram API error: {}",
+                api_response.description.unwrap_or_default()
+            ))
+        }
+    }
+
     /// Test the connection by calling getMe
     pub async fn test_connection(&self) -> Result<TelegramUser> {
         let url = self.api_url("getMe");
@@ -540,6 +751,58 @@ impl TelegramChannel {
             Err(anyhow!("Telegram HTTP error: {}", error))
         }
     }
+
+    /// Upload an Ogg/Opus file as a voice note via `sendVoice`
+    async fn send_voice_file(
+        &self,
+        chat_id: &str,
+        file_path: &Path,
+        message_thread_id: Option<i64>,
+    ) -> Result<()> {
+        let url = self.api_url("sendVoice");
+        let bytes = fs::read(file_path).await?;
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("voice.ogg")
+            .to_string();
+
+        let mut form = reqwest::multipart::Form::new()
+            .text("chat_id", chat_id.to_string())
+            .part(
+                "voice",
+                reqwest::multipart::Part::bytes(bytes)
+                    .file_name(file_name)
+                    .mime_str("audio/ogg")?,
+            );
+        if let Some(thread_id) = message_thread_id {
+            form = form.text("message_thread_id", thread_id.to_string());
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .multipart(form)
+            .timeout(std::time::Duration::from_secs(self.config.api_timeout_secs))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let api_response: TelegramResponse<TelegramMessageResponse> = response.json().await?;
+            if api_response.ok {
+                debug!("Sent voice message to {}", chat_id);
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "Telegram API error: {}",
+                    api_response.description.unwrap_or_default()
+                ))
+            }
+        } else {
+            let error = response.text().await.unwrap_or_default();
+            Err(anyhow!("Telegram HTTP error: {}", error))
+        }
+    }
 }
 
 #[async_trait]
@@ -553,34 +816,54 @@ impl Channel for TelegramChannel {
     }
 
     async fn send(&self, message: OutboundMessage) -> Result<()> {
-        let formatted = self.format_message(&message);
-        let parse_mode = message.parse_mode.as_deref();
+        self.send_chunks(&message).await?;
+        Ok(())
+    }
 
-        // Parse conversation_id to extract chat_id and thread_id
-        let (chat_id, parsed_thread_id) = Self::parse_conversation_id(&message.conversation_id);
+    async fn send_with_id(&self, message: OutboundMessage) -> Result<Option<String>> {
+        let message_id = self.send_chunks(&message).await?;
+        Ok(message_id.map(|id| format!("tg_{}", id)))
+    }
 
-        // Use explicit message_thread_id if provided, otherwise use parsed thread_id
-        let thread_id = message.message_thread_id.or(parsed_thread_id);
+    fn supports_message_editing(&self) -> bool {
+        true
+    }
 
-        let chunks = chunk_markdown(&formatted, None);
-        for chunk in &chunks {
-            self.send_message(
-                &chat_id,
-                chunk,
-                parse_mode,
-                message.reply_to.as_deref(),
-                thread_id,
-            )
-            .await?;
-        }
+    async fn edit_message(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> Result<()> {
+        let (chat_id, _) = Self::parse_conversation_id(conversation_id);
+        let numeric_id = message_id
+            .strip_prefix("tg_")
+            .unwrap_or(message_id)
+            .parse::<i64>()
+            .map_err(|_| anyhow!("Invalid Telegram message id: {}", message_id))?;
+        self.edit_message_text(&chat_id, numeric_id, content).await
+    }
 
-        Ok(())
+    async fn send_file(
+        &self,
+        conversation_id: &str,
+        file_path: &Path,
+        caption: Option<&str>,
+    ) -> Result<()> {
+        let (chat_id, thread_id) = Self::parse_conversation_id(conversation_id);
+        self.send_file_upload(&chat_id, file_path, caption, thread_id)
+            .await
     }
 
     async fn send_typing(&self, conversation_id: &str) -> Result<()> {
         self.send_typing_action(conversation_id).await
     }
 
+    async fn send_voice(&self, conversation_id: &str, file_path: &Path) -> Result<()> {
+        let (chat_id, thread_id) = Self::parse_conversation_id(conversation_id);
+        self.send_voice_file(&chat_id, file_path, thread_id).await
+    }
+
     fn start_receiving(&self) -> Option<Pin<Box<dyn Stream<Item = InboundMessage> + Send>>> {
         if !self.is_configured() {
             return None;
@@ -610,6 +893,11 @@ impl Channel for TelegramChannel {
                 match channel.poll_updates().await {
                     Ok(updates) => {
                         for update in updates {
+                            if let Some(callback) = &update.callback_query
+                                && let Err(e) = channel.acknowledge_callback_query(callback).await
+                            {
+                                warn!("Failed to acknowledge Telegram callback query: {}", e);
+                            }
                             if let Some(message) = channel.convert_update(update).await {
                                 debug!(
                                     "Received Telegram message: {} from {}",
@@ -675,6 +963,16 @@ struct TelegramResponse<T> {
 struct TelegramUpdate {
     update_id: i64,
     message: Option<TelegramMessage>,
+    #[serde(default)]
+    callback_query: Option<TelegramCallbackQuery>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TelegramCallbackQuery {
+    id: String,
+    from: TelegramUser,
+    message: Option<TelegramMessage>,
+    data: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -791,10 +1089,44 @@ struct TelegramChat {
 
 #[derive(Debug, Deserialize)]
 struct TelegramMessageResponse {
-    #[allow(dead_code)]
     message_id: i64,
 }
 
+/// Build an `inline_keyboard` reply markup from button rows
+fn inline_keyboard_markup(rows: &[Vec<InlineButton>]) -> Result<serde_json::Value> {
+    let keyboard = rows
+        .iter()
+        .filter(|row| !row.is_empty())
+        .map(|row| {
+            row.iter()
+                .map(|button| {
+                    if button.action.is_empty()
+                        || button.action.len() > TELEGRAM_MAX_CALLBACK_DATA_BYTES
+                    {
+                        return Err(anyhow!(
+                            "Button '{}' action must be 1-{} bytes",
+                            button.label,
+                            TELEGRAM_MAX_CALLBACK_DATA_BYTES
+                        ));
+                    }
+                    Ok(serde_json::json!({
+                        "text": button.label,
+                        "callback_data": button.action,
+                    }))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(serde_json::json!({ "inline_keyboard": keyboard }))
+}
+
+fn is_photo_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| PHOTO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -925,6 +1257,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         let inbound = channel.convert_update(update).await.unwrap();
@@ -936,6 +1269,86 @@ mod tests {
         assert_eq!(inbound.channel_type, ChannelType::Telegram);
     }
 
+    #[tokio::test]
+    async fn test_convert_update_callback_query() {
+        let channel = TelegramChannel::with_token("test");
+        let update: TelegramUpdate = serde_json::from_value(serde_json::json!({
+            "update_id": 7,
+            "callback_query": {
+                "id": "cb-1",
+                "from": { "id": 42, "is_bot": false, "first_name": "John", "username": "johndoe" },
+                "message": {
+                    "message_id": 55,
+                    "chat": { "id": -100, "type": "supergroup", "title": "Ops" },
+                    "date": 1234567890,
+                    "message_thread_id": 3,
+                    "text": "Approve deploy?"
+                },
+                "data": "/approve task-1"
+            }
+        }))
+        .unwrap();
+
+        let inbound = channel.convert_update(update).await.unwrap();
+        assert_eq!(inbound.id, "tg_cb_cb-1");
+        assert_eq!(inbound.sender_id, "42");
+        assert_eq!(inbound.conversation_id, "-100:3");
+        assert_eq!(inbound.content, "/approve task-1");
+        let metadata = inbound.metadata.unwrap();
+        assert_eq!(metadata["callback_query_id"], "cb-1");
+        assert_eq!(metadata["callback_message_id"], "tg_55");
+    }
+
+    #[tokio::test]
+    async fn test_convert_update_callback_query_without_data_is_ignored() {
+        let channel = TelegramChannel::with_token("test");
+        let update: TelegramUpdate = serde_json::from_value(serde_json::json!({
+            "update_id": 8,
+            "callback_query": {
+                "id": "cb-2",
+                "from": { "id": 42, "is_bot": false },
+                "message": {
+                    "message_id": 56,
+                    "chat": { "id": 999, "type": "private" },
+                    "date": 1234567890
+                }
+            }
+        }))
+        .unwrap();
+
+        assert!(channel.convert_update(update).await.is_none());
+    }
+
+    #[test]
+    fn test_inline_keyboard_markup() {
+        let rows = vec![
+            vec![
+                InlineButton::new("Approve", "/approve task-1"),
+                InlineButton::new("Deny", "/deny task-1"),
+            ],
+            vec![],
+        ];
+        let markup = inline_keyboard_markup(&rows).unwrap();
+        assert_eq!(
+            markup,
+            serde_json::json!({
+                "inline_keyboard": [[
+                    { "text": "Approve", "callback_data": "/approve task-1" },
+                    { "text": "Deny", "callback_data": "/deny task-1" }
+                ]]
+            })
+        );
+
+        let too_long = vec![vec![InlineButton::new("Long", "x".repeat(65))]];
+        assert!(inline_keyboard_markup(&too_long).is_err());
+    }
+
+    #[test]
+    fn test_is_photo_path() {
+        assert!(is_photo_path(Path::new("/tmp/chart.PNG")));
+        assert!(!is_photo_path(Path::new("/tmp/report.pdf")));
+    }
+
     #[tokio::test]
     async fn test_convert_update_no_username() {
         let channel = TelegramChannel::with_token("test");
@@ -968,6 +1381,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         let inbound = channel.convert_update(update).await.unwrap();
@@ -981,6 +1395,7 @@ mod tests {
         let update = TelegramUpdate {
             update_id: 12345,
             message: None,
+            callback_query: None,
         };
 
         assert!(channel.convert_update(update).await.is_none());
@@ -1018,6 +1433,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         assert!(channel.convert_update(update).await.is_none());
@@ -1055,6 +1471,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         let inbound = channel.convert_update(update).await.unwrap();
@@ -1107,6 +1524,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         let inbound = channel.convert_update(update).await.unwrap();
@@ -1165,6 +1583,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         let inbound = channel.convert_update(update).await.unwrap();
@@ -1219,6 +1638,7 @@ mod tests {
                 document: None,
                 reply_to_message: None,
             }),
+            callback_query: None,
         };
 
         let inbound = channel.convert_update(update).await.unwrap();
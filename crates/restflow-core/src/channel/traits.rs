@@ -5,6 +5,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use futures::Stream;
+use std::path::Path;
 use std::pin::Pin;
 
 use super::types::{ChannelType, InboundMessage, OutboundMessage};
@@ -66,6 +67,51 @@ pub trait Channel: Send + Sync {
         self.send(OutboundMessage::new(conversation_id, text)).await
     }
 
+    /// Send a message and return the ID of the delivered message, if known
+    ///
+    /// The ID can be passed to `edit_message`. Default implementation sends
+    /// normally and returns `None`.
+    async fn send_with_id(&self, message: OutboundMessage) -> Result<Option<String>> {
+        self.send(message).await?;
+        Ok(None)
+    }
+
+    /// Check if previously sent messages can be edited
+    fn supports_message_editing(&self) -> bool {
+        false
+    }
+
+    /// Replace the text of a previously sent message
+    ///
+    /// Default implementation returns an error. Override together with
+    /// `supports_message_editing` and `send_with_id`.
+    async fn edit_message(
+        &self,
+        _conversation_id: &str,
+        _message_id: &str,
+        _content: &str,
+    ) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "{} does not support editing messages",
+            self.name()
+        ))
+    }
+
+    /// Send a local file (photo or document) with an optional caption
+    ///
+    /// Default implementation returns an error. Override for channels that support uploads.
+    async fn send_file(
+        &self,
+        _conversation_id: &str,
+        _file_path: &Path,
+        _caption: Option<&str>,
+    ) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "{} does not support file uploads",
+            self.name()
+        ))
+    }
+
     /// Send typing indicator to show the bot is processing
     ///
     /// Default implementation does nothing. Override for channels that support this.
@@ -73,6 +119,16 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Send a local audio file as a voice message
+    ///
+    /// Default implementation returns an error. Override for channels that support voice notes.
+    async fn send_voice(&self, _conversation_id: &str, _file_path: &Path) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "{} does not support voice messages",
+            self.name()
+        ))
+    }
+
     /// Start receiving messages (returns None if channel doesn't support receiving)
     ///
     /// The returned stream should be spawned in a background task.
@@ -229,6 +285,38 @@ mod tests {
 
         assert_eq!(channel.name(), "Telegram");
         assert!(channel.supports_interaction());
+        assert!(
+            channel
+                .send_voice("chat-123", Path::new("/tmp/reply.ogg"))
+                .await
+                .is_err()
+        );
+        assert!(!channel.supports_message_editing());
+        assert!(
+            channel
+                .edit_message("chat-123", "1", "edited")
+                .await
+                .is_err()
+        );
+        assert!(
+            channel
+                .send_file("chat-123", Path::new("/tmp/chart.png"), None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_with_id_defaults_to_send() {
+        let channel = MockChannel::new(ChannelType::Telegram);
+
+        let id = channel
+            .send_with_id(OutboundMessage::new("chat-123", "Hello"))
+            .await
+            .unwrap();
+
+        assert_eq!(id, None);
+        assert_eq!(channel.get_sent_messages().await.len(), 1);
     }
 
     #[tokio::test]
@@ -15,6 +15,7 @@ pub enum ChannelType {
     Telegram,
     Discord,
     Slack,
+    Matrix,
     Email,
     Webhook,
 }
@@ -26,6 +27,7 @@ impl ChannelType {
             Self::Telegram => "telegram",
             Self::Discord => "discord",
             Self::Slack => "slack",
+            Self::Matrix => "matrix",
             Self::Email => "email",
             Self::Webhook => "webhook",
         }
@@ -33,7 +35,10 @@ impl ChannelType {
 
     /// Whether this channel type supports bidirectional interaction
     pub fn supports_interaction(&self) -> bool {
-        matches!(self, Self::Telegram | Self::Discord | Self::Slack)
+        matches!(
+            self,
+            Self::Telegram | Self::Discord | Self::Slack | Self::Matrix
+        )
     }
 
     /// Display name for UI
@@ -42,6 +47,7 @@ impl ChannelType {
             Self::Telegram => "Telegram",
             Self::Discord => "Discord",
             Self::Slack => "Slack",
+            Self::Matrix => "Matrix",
             Self::Email => "Email",
             Self::Webhook => "Webhook",
         }
@@ -146,6 +152,29 @@ impl InboundMessage {
     }
 }
 
+/// Inline button attached to an outbound message
+///
+/// Pressing the button delivers `action` back to the bot as an inbound message
+/// from the pressing user, so it goes through normal command/chat routing
+/// (e.g. `/approve <task_id>` or an option label).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InlineButton {
+    /// Button label shown to the user
+    pub label: String,
+    /// Text sent back when the button is pressed
+    pub action: String,
+}
+
+impl InlineButton {
+    /// Create a new inline button
+    pub fn new(label: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            action: action.into(),
+        }
+    }
+}
+
 /// Outbound message to a channel
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutboundMessage {
@@ -163,6 +192,9 @@ pub struct OutboundMessage {
     pub message_thread_id: Option<i64>,
     /// Parse mode (markdown, html, plain)
     pub parse_mode: Option<String>,
+    /// Inline button rows (channels without button support ignore them)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buttons: Vec<Vec<InlineButton>>,
 }
 
 impl OutboundMessage {
@@ -176,6 +208,7 @@ impl OutboundMessage {
             reply_to: None,
             message_thread_id: None,
             parse_mode: Some("Markdown".to_string()),
+            buttons: Vec::new(),
         }
     }
 
@@ -209,6 +242,12 @@ impl OutboundMessage {
         self
     }
 
+    /// Attach inline button rows
+    pub fn with_buttons(mut self, buttons: Vec<Vec<InlineButton>>) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
     /// Clear parse mode (disable markdown/html parsing)
     pub fn without_parse_mode(mut self) -> Self {
         self.parse_mode = None;
@@ -315,6 +354,7 @@ mod tests {
         assert!(ChannelType::Telegram.supports_interaction());
         assert!(ChannelType::Discord.supports_interaction());
         assert!(ChannelType::Slack.supports_interaction());
+        assert!(ChannelType::Matrix.supports_interaction());
         assert!(!ChannelType::Email.supports_interaction());
         assert!(!ChannelType::Webhook.supports_interaction());
     }
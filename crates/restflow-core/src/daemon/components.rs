@@ -0,0 +1,67 @@
+//! Process-wide status of daemon subsystems.
+//!
+//! The daemon entry point records each subsystem it starts (IPC server, HTTP
+//! server, task runner, channels, schedulers) here. The health endpoint and
+//! `GetStatus` report them so a headless daemon can be monitored without the
+//! GUI.
+
+use parking_lot::RwLock;
+use restflow_contracts::{DaemonComponentState, DaemonComponentStatus};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+fn registry() -> &'static RwLock<BTreeMap<String, DaemonComponentStatus>> {
+    static COMPONENTS: OnceLock<RwLock<BTreeMap<String, DaemonComponentStatus>>> = OnceLock::new();
+    COMPONENTS.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Record the current state of a daemon subsystem, replacing any earlier state
+pub fn set_daemon_component(name: &str, state: DaemonComponentState, detail: Option<String>) {
+    registry().write().insert(
+        name.to_string(),
+        DaemonComponentStatus {
+            name: name.to_string(),
+            state,
+            detail,
+        },
+    );
+}
+
+/// Recorded subsystems, ordered by name
+pub fn daemon_components() -> Vec<DaemonComponentStatus> {
+    registry().read().values().cloned().collect()
+}
+
+/// Overall status string: `degraded` once any subsystem has failed
+pub(crate) fn overall_status(components: &[DaemonComponentStatus]) -> &'static str {
+    if components
+        .iter()
+        .any(|component| component.state == DaemonComponentState::Failed)
+    {
+        "degraded"
+    } else {
+        "running"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overall_status_reports_failures() {
+        let mut components = vec![DaemonComponentStatus {
+            name: "channels".to_string(),
+            state: DaemonComponentState::Disabled,
+            detail: None,
+        }];
+        assert_eq!(overall_status(&components), "running");
+
+        components.push(DaemonComponentStatus {
+            name: "task_runner".to_string(),
+            state: DaemonComponentState::Failed,
+            detail: Some("boom".to_string()),
+        });
+        assert_eq!(overall_status(&components), "degraded");
+    }
+}
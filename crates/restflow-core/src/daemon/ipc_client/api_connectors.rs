@@ -0,0 +1,41 @@
+#[cfg(unix)]
+use super::*;
+#[cfg(unix)]
+use crate::models::ApiConnector;
+#[cfg(unix)]
+use restflow_contracts::DeleteResponse;
+
+#[cfg(unix)]
+impl IpcClient {
+    pub async fn list_api_connectors(&mut self) -> Result<Vec<ApiConnector>> {
+        self.request_typed(IpcRequest::ListApiConnectors).await
+    }
+
+    pub async fn get_api_connector(&mut self, name: String) -> Result<ApiConnector> {
+        self.request_typed(IpcRequest::GetApiConnector { name })
+            .await
+    }
+
+    pub async fn import_api_connector(
+        &mut self,
+        name: String,
+        spec: String,
+        base_url: Option<String>,
+        auth_secret: Option<String>,
+    ) -> Result<ApiConnector> {
+        self.request_typed(IpcRequest::ImportApiConnector {
+            name,
+            spec,
+            base_url,
+            auth_secret,
+        })
+        .await
+    }
+
+    pub async fn delete_api_connector(&mut self, name: String) -> Result<bool> {
+        let response: DeleteResponse = self
+            .request_typed(IpcRequest::DeleteApiConnector { name })
+            .await?;
+        Ok(response.deleted)
+    }
+}
@@ -34,6 +34,27 @@ impl IpcClient {
         .await
     }
 
+    pub async fn add_oauth_profile(
+        &mut self,
+        name: String,
+        credential: Credential,
+        provider: AuthProvider,
+        oauth: crate::auth::OAuthClientConfig,
+        client_secret: Option<String>,
+    ) -> Result<AuthProfile> {
+        let credential = to_contract(credential)?;
+        let provider = to_contract(provider)?;
+        let oauth = to_contract(oauth)?;
+        self.request_typed(IpcRequest::AddOAuthProfile {
+            name,
+            credential,
+            provider,
+            oauth,
+            client_secret,
+        })
+        .await
+    }
+
     pub async fn remove_auth_profile(&mut self, id: String) -> Result<AuthProfile> {
         self.request_typed(IpcRequest::RemoveAuthProfile { id })
             .await
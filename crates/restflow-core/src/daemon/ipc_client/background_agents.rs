@@ -7,6 +7,8 @@ use crate::daemon::request_mapper::to_contract;
 use restflow_contracts::DeleteWithIdResponse;
 #[cfg(unix)]
 use restflow_contracts::request::TaskFromSessionRequest;
+#[cfg(unix)]
+use std::collections::HashMap;
 
 #[cfg(unix)]
 impl IpcClient {
@@ -55,6 +57,30 @@ impl IpcClient {
             .await
     }
 
+    pub async fn run_task_with_secrets(
+        &mut self,
+        id: String,
+        secrets: HashMap<String, String>,
+    ) -> Result<BackgroundAgent> {
+        self.request_typed(IpcRequest::RunTaskWithSecrets { id, secrets })
+            .await
+    }
+
+    pub async fn set_task_wake_condition(
+        &mut self,
+        id: String,
+        condition: Option<crate::models::WakeCondition>,
+        checkpoint_id: Option<String>,
+    ) -> Result<BackgroundAgent> {
+        let condition = condition.map(to_contract).transpose()?;
+        self.request_typed(IpcRequest::SetTaskWakeCondition {
+            id,
+            condition,
+            checkpoint_id,
+        })
+        .await
+    }
+
     pub async fn get_task_history(&mut self, id: String) -> Result<Vec<BackgroundAgentEvent>> {
         self.request_typed(IpcRequest::GetTaskHistory { id }).await
     }
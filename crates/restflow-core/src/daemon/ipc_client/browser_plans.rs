@@ -0,0 +1,77 @@
+#[cfg(unix)]
+use super::*;
+#[cfg(unix)]
+use crate::models::SavedBrowserPlan;
+#[cfg(unix)]
+use crate::services::browser_webhook::BrowserWebhookOutcome;
+#[cfg(unix)]
+use restflow_browser::BrowserDoctorReport;
+#[cfg(unix)]
+use restflow_contracts::DeleteResponse;
+#[cfg(unix)]
+use serde_json::Value;
+
+#[cfg(unix)]
+impl IpcClient {
+    pub async fn list_browser_plans(&mut self) -> Result<Vec<SavedBrowserPlan>> {
+        self.request_typed(IpcRequest::ListBrowserPlans).await
+    }
+
+    pub async fn get_browser_plan(
+        &mut self,
+        name: String,
+        version: Option<u32>,
+    ) -> Result<SavedBrowserPlan> {
+        self.request_typed(IpcRequest::GetBrowserPlan { name, version })
+            .await
+    }
+
+    pub async fn list_browser_plan_versions(
+        &mut self,
+        name: String,
+    ) -> Result<Vec<SavedBrowserPlan>> {
+        self.request_typed(IpcRequest::ListBrowserPlanVersions { name })
+            .await
+    }
+
+    pub async fn save_browser_plan(
+        &mut self,
+        name: String,
+        description: Option<String>,
+        actions: Value,
+        timeout_secs: Option<u64>,
+    ) -> Result<SavedBrowserPlan> {
+        self.request_typed(IpcRequest::SaveBrowserPlan {
+            name,
+            description,
+            actions,
+            timeout_secs,
+        })
+        .await
+    }
+
+    pub async fn delete_browser_plan(&mut self, name: String) -> Result<bool> {
+        let response: DeleteResponse = self
+            .request_typed(IpcRequest::DeleteBrowserPlan { name })
+            .await?;
+        Ok(response.deleted)
+    }
+
+    pub async fn run_browser_plan(
+        &mut self,
+        name: String,
+        version: Option<u32>,
+        payload: Option<Value>,
+    ) -> Result<BrowserWebhookOutcome> {
+        self.request_typed(IpcRequest::RunBrowserPlan {
+            name,
+            version,
+            payload,
+        })
+        .await
+    }
+
+    pub async fn browser_doctor(&mut self) -> Result<BrowserDoctorReport> {
+        self.request_typed(IpcRequest::BrowserDoctor).await
+    }
+}
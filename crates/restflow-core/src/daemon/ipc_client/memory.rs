@@ -3,7 +3,7 @@ use super::*;
 #[cfg(unix)]
 use crate::daemon::request_mapper::to_contract;
 #[cfg(unix)]
-use restflow_contracts::{ClearResponse, DeleteResponse, IdResponse};
+use restflow_contracts::{ClearResponse, DeleteResponse, IdResponse, UpdatedResponse};
 
 #[cfg(unix)]
 impl IpcClient {
@@ -158,4 +158,32 @@ impl IpcClient {
             .await?;
         Ok(resp.deleted)
     }
+
+    pub async fn tag_memory(
+        &mut self,
+        chunk_ids: Vec<String>,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+    ) -> Result<u32> {
+        let resp: UpdatedResponse = self
+            .request_typed(IpcRequest::TagMemory {
+                chunk_ids,
+                add_tags,
+                remove_tags,
+            })
+            .await?;
+        Ok(resp.updated)
+    }
+
+    pub async fn merge_memory_tags(&mut self, sources: Vec<String>, target: String) -> Result<u32> {
+        let resp: UpdatedResponse = self
+            .request_typed(IpcRequest::MergeMemoryTags { sources, target })
+            .await?;
+        Ok(resp.updated)
+    }
+
+    pub async fn list_memory_tags(&mut self, agent_id: Option<String>) -> Result<Vec<TagCount>> {
+        self.request_typed(IpcRequest::ListMemoryTags { agent_id })
+            .await
+    }
 }
@@ -7,10 +7,10 @@ use crate::daemon::session_events::ChatSessionEvent;
 use crate::memory::ExportResult;
 use crate::models::{
     AgentNode, BackgroundAgent, BackgroundAgentControlAction, BackgroundAgentEvent,
-    BackgroundAgentPatch, BackgroundAgentSpec, ChatMessage, ChatRole, ChatSession,
-    ChatSessionSummary, ChatSessionUpdate, ExecutionTraceEvent, ExecutionTraceQuery,
-    ExecutionTraceStats, MemoryChunk, MemorySearchResult, MemorySession, MemoryStats, RunListQuery,
-    RunSummary, Skill, TerminalSession,
+    BackgroundAgentPatch, BackgroundAgentSpec, ChatMessage, ChatRole, ChatSearchQuery,
+    ChatSearchResult, ChatSession, ChatSessionSummary, ChatSessionUpdate, ExecutionTraceEvent,
+    ExecutionTraceQuery, ExecutionTraceStats, MemoryChunk, MemorySearchResult, MemorySession,
+    MemoryStats, RunListQuery, RunSummary, Skill, TagCount, TerminalSession,
 };
 use crate::runtime::TaskStreamEvent;
 use crate::storage::agent::StoredAgent;
@@ -23,8 +23,10 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 #[cfg(unix)]
 use tokio::net::UnixStream;
 
+mod api_connectors;
 mod auth;
 mod background_agents;
+mod browser_plans;
 mod memory;
 mod sessions;
 mod skills;
@@ -3,6 +3,8 @@ use super::*;
 #[cfg(unix)]
 use crate::daemon::request_mapper::to_contract;
 #[cfg(unix)]
+use crate::models::{ExecutionReplay, ReplayRunResult, UsageQuery, UsageStats};
+#[cfg(unix)]
 use crate::{
     ExecutionLogResponse, ExecutionMetricsResponse, ExecutionTimeline, ProviderHealthQuery,
     ProviderHealthResponse,
@@ -96,6 +98,15 @@ impl IpcClient {
             .await
     }
 
+    pub async fn search_chat_sessions(
+        &mut self,
+        query: ChatSearchQuery,
+    ) -> Result<ChatSearchResult> {
+        let query = to_contract(query)?;
+        self.request_typed(IpcRequest::SearchChatSessions { query })
+            .await
+    }
+
     pub async fn add_message(
         &mut self,
         session_id: String,
@@ -165,6 +176,15 @@ impl IpcClient {
             .await
     }
 
+    pub async fn query_execution_trace_stats(
+        &mut self,
+        query: ExecutionTraceQuery,
+    ) -> Result<ExecutionTraceStats> {
+        let query = to_contract(query)?;
+        self.request_typed(IpcRequest::QueryExecutionTraceStats { query })
+            .await
+    }
+
     pub async fn get_execution_run_timeline(
         &mut self,
         run_id: String,
@@ -216,4 +236,31 @@ impl IpcClient {
         self.request_optional(IpcRequest::GetExecutionTraceById { id })
             .await
     }
+
+    pub async fn get_execution_replay(&mut self, id: String) -> Result<Option<ExecutionReplay>> {
+        self.request_optional(IpcRequest::GetExecutionReplay { id })
+            .await
+    }
+
+    pub async fn replay_execution(
+        &mut self,
+        execution_id: String,
+        step: u32,
+        agent_id: Option<String>,
+        message: Option<String>,
+    ) -> Result<ReplayRunResult> {
+        self.request_typed(IpcRequest::ReplayExecution {
+            execution_id,
+            step,
+            agent_id,
+            message,
+        })
+        .await
+    }
+
+    pub async fn get_usage_stats(&mut self, query: UsageQuery) -> Result<UsageStats> {
+        let query = to_contract(query)?;
+        self.request_typed(IpcRequest::GetUsageStats { query })
+            .await
+    }
 }
@@ -101,6 +101,14 @@ impl IpcClient {
                 } => {
                     on_event(event)?;
                 }
+                StreamFrame::Resync { dropped, events } => {
+                    tracing::debug!(dropped, "Stream resynced after dropping events");
+                    for event in events {
+                        if let IpcStreamEvent::BackgroundAgent(event) = event {
+                            on_event(event)?;
+                        }
+                    }
+                }
                 StreamFrame::Error(error) => {
                     bail!(
                         "Task event stream error: {}",
@@ -146,6 +154,14 @@ impl IpcClient {
                 } => {
                     on_event(event)?;
                 }
+                StreamFrame::Resync { dropped, events } => {
+                    tracing::debug!(dropped, "Stream resynced after dropping events");
+                    for event in events {
+                        if let IpcStreamEvent::Session(event) = event {
+                            on_event(event)?;
+                        }
+                    }
+                }
                 StreamFrame::Error(error) => {
                     bail!(
                         "Session event stream error: {}",
@@ -1,7 +1,9 @@
 #[cfg(unix)]
 use super::*;
 #[cfg(unix)]
-use restflow_contracts::PromptResponse;
+use crate::models::{PendingApproval, ToolApprovalPolicy};
+#[cfg(unix)]
+use restflow_contracts::{OkResponse, PromptResponse};
 
 #[cfg(unix)]
 impl IpcClient {
@@ -26,4 +28,38 @@ impl IpcClient {
         self.request_typed(IpcRequest::ExecuteTool { name, input })
             .await
     }
+
+    pub async fn list_tool_approvals(
+        &mut self,
+        pending_only: bool,
+    ) -> Result<Vec<PendingApproval>> {
+        self.request_typed(IpcRequest::ListToolApprovals { pending_only })
+            .await
+    }
+
+    pub async fn resolve_tool_approval(
+        &mut self,
+        id: String,
+        approved: bool,
+        reason: Option<String>,
+    ) -> Result<PendingApproval> {
+        self.request_typed(IpcRequest::ResolveToolApproval {
+            id,
+            approved,
+            reason,
+        })
+        .await
+    }
+
+    pub async fn get_tool_approval_policy(&mut self) -> Result<ToolApprovalPolicy> {
+        self.request_typed(IpcRequest::GetToolApprovalPolicy).await
+    }
+
+    pub async fn set_tool_approval_policy(&mut self, policy: ToolApprovalPolicy) -> Result<()> {
+        let policy = serde_json::to_value(policy)?;
+        let _: OkResponse = self
+            .request_typed(IpcRequest::SetToolApprovalPolicy { policy })
+            .await?;
+        Ok(())
+    }
 }
@@ -76,7 +76,10 @@ impl IpcClient {
         Self::unsupported()
     }
 
-    pub async fn delete_task(&mut self, _id: String) -> Result<restflow_contracts::DeleteWithIdResponse> {
+    pub async fn delete_task(
+        &mut self,
+        _id: String,
+    ) -> Result<restflow_contracts::DeleteWithIdResponse> {
         Self::unsupported()
     }
 
@@ -118,6 +121,9 @@ impl IpcClient {
         fn list_memory_sessions(&mut self, _agent_id: String) -> Vec<MemorySession>;
         fn create_memory_session(&mut self, _session: MemorySession) -> MemorySession;
         fn delete_memory_session(&mut self, _session_id: String, _delete_chunks: bool) -> bool;
+        fn tag_memory(&mut self, _chunk_ids: Vec<String>, _add_tags: Vec<String>, _remove_tags: Vec<String>) -> u32;
+        fn merge_memory_tags(&mut self, _sources: Vec<String>, _target: String) -> u32;
+        fn list_memory_tags(&mut self, _agent_id: Option<String>) -> Vec<TagCount>;
         fn list_sessions(&mut self) -> Vec<ChatSessionSummary>;
         fn list_full_sessions(&mut self) -> Vec<ChatSession>;
         fn list_sessions_by_agent(&mut self, _agent_id: String) -> Vec<ChatSession>;
@@ -131,6 +137,7 @@ impl IpcClient {
         fn archive_session(&mut self, _id: String) -> bool;
         fn delete_session(&mut self, _id: String) -> bool;
         fn search_sessions(&mut self, _query: String) -> Vec<ChatSessionSummary>;
+        fn search_chat_sessions(&mut self, _query: ChatSearchQuery) -> ChatSearchResult;
         fn add_message(&mut self, _session_id: String, _role: ChatRole, _content: String) -> ChatSession;
         fn append_message(&mut self, _session_id: String, _message: ChatMessage) -> ChatSession;
         fn execute_chat_session(&mut self, _session_id: String, _user_input: Option<String>) -> ChatSession;
@@ -139,11 +146,15 @@ impl IpcClient {
         fn get_session_messages(&mut self, _session_id: String, _limit: Option<usize>) -> Vec<ChatMessage>;
         fn list_execution_sessions(&mut self, _query: RunListQuery) -> Vec<RunSummary>;
         fn query_execution_traces(&mut self, _query: ExecutionTraceQuery) -> Vec<ExecutionTraceEvent>;
+        fn query_execution_trace_stats(&mut self, _query: ExecutionTraceQuery) -> ExecutionTraceStats;
         fn get_execution_trace_stats(&mut self, _run_id: Option<String>) -> ExecutionTraceStats;
         fn get_execution_run_timeline(&mut self, _run_id: String) -> restflow_contracts::request::ExecutionTimeline;
         fn get_execution_run_metrics(&mut self, _run_id: String) -> restflow_contracts::request::ExecutionMetricsResponse;
         fn query_execution_run_logs(&mut self, _run_id: String) -> restflow_contracts::request::ExecutionLogResponse;
         fn get_execution_trace_by_id(&mut self, _id: String) -> Option<ExecutionTraceEvent>;
+        fn get_execution_replay(&mut self, _id: String) -> Option<crate::models::ExecutionReplay>;
+        fn replay_execution(&mut self, _execution_id: String, _step: u32, _agent_id: Option<String>, _message: Option<String>) -> crate::models::ReplayRunResult;
+        fn get_usage_stats(&mut self, _query: crate::models::UsageQuery) -> crate::models::UsageStats;
         fn list_terminal_sessions(&mut self) -> Vec<TerminalSession>;
         fn get_terminal_session(&mut self, _id: String) -> TerminalSession;
         fn create_terminal_session(&mut self) -> TerminalSession;
@@ -178,6 +189,10 @@ impl IpcClient {
         fn init_python(&mut self) -> bool;
         fn get_available_tool_definitions(&mut self) -> Vec<ToolDefinition>;
         fn execute_tool(&mut self, _name: String, _input: serde_json::Value) -> ToolExecutionResult;
+        fn list_tool_approvals(&mut self, _pending_only: bool) -> Vec<crate::models::PendingApproval>;
+        fn resolve_tool_approval(&mut self, _id: String, _approved: bool, _reason: Option<String>) -> crate::models::PendingApproval;
+        fn get_tool_approval_policy(&mut self) -> crate::models::ToolApprovalPolicy;
+        fn set_tool_approval_policy(&mut self, _policy: crate::models::ToolApprovalPolicy) -> ();
     }
 
     pub async fn execute_chat_session_stream<F>(
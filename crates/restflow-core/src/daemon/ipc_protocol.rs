@@ -80,6 +80,7 @@ mod tests {
             pid: 1234,
             started_at_ms: 1_700_000_000_000,
             uptime_secs: 42,
+            components: Vec::new(),
         };
 
         let value = serde_json::to_value(&status).unwrap();
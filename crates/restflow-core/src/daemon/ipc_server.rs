@@ -1,16 +1,20 @@
+use super::components::{daemon_components, overall_status};
 use super::ipc_protocol::{
     IPC_PROTOCOL_VERSION, IpcDaemonStatus, IpcRequest, IpcResponse, IpcStreamEvent,
     MAX_MESSAGE_SIZE, StreamFrame, ToolDefinition,
 };
 use super::session_events::subscribe_session_events;
+use super::stream_buffer::{
+    DEFAULT_STREAM_BUFFER_CAPACITY, StreamReceiver, StreamSender, stream_channel,
+};
 use super::subscribe_background_events;
 use crate::AppCore;
 use crate::auth::{AuthManagerConfig, AuthProfileManager};
 use crate::memory::{MemoryExporter, MemoryExporterBuilder, SearchEngineBuilder};
 use crate::models::{
-    AgentNode, BackgroundAgentStatus, ChatExecutionStatus, ChatMessage, ChatRole, ChatSession,
-    ChatSessionSummary, MemoryChunk, MemorySearchQuery, MessageExecution, ModelId, SteerMessage,
-    SteerSource, TerminalSession,
+    AgentNode, BackgroundAgentStatus, ChatExecutionStatus, ChatMessage, ChatRole, ChatSearchQuery,
+    ChatSession, ChatSessionSummary, MemoryChunk, MemorySearchQuery, MessageExecution, ModelId,
+    SteerMessage, SteerSource, TerminalSession,
 };
 use crate::process::ProcessRegistry;
 use crate::runtime::background_agent::{AgentRuntimeExecutor, SessionInputMode};
@@ -31,7 +35,9 @@ use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
 use restflow_ai::agent::StreamEmitter;
-use restflow_ai::agent::{SubagentConfig, SubagentTracker};
+use restflow_ai::agent::{
+    SubagentConfig, SubagentProgress, SubagentProgressEvent, SubagentStatus, SubagentTracker,
+};
 use restflow_storage::{AgentDefaults, AuthProfileStorage};
 use restflow_telemetry::RestflowTrace;
 use restflow_traits::DEFAULT_CHAT_MAX_SESSION_HISTORY;
@@ -130,24 +136,26 @@ pub(crate) fn build_daemon_status() -> IpcDaemonStatus {
     let started_at_ms = daemon_started_at_ms();
     let now_ms = Utc::now().timestamp_millis();
     let uptime_secs = ((now_ms - started_at_ms).max(0) / 1000) as u64;
+    let components = daemon_components();
 
     IpcDaemonStatus {
-        status: "running".to_string(),
+        status: overall_status(&components).to_string(),
         protocol_version: IPC_PROTOCOL_VERSION.to_string(),
         daemon_version: env!("CARGO_PKG_VERSION").to_string(),
         pid: std::process::id(),
         started_at_ms,
         uptime_secs,
+        components,
     }
 }
 
 struct IpcStreamEmitter {
-    tx: mpsc::UnboundedSender<StreamFrame>,
+    tx: StreamSender,
     has_text_streamed: Arc<AtomicBool>,
 }
 
 impl IpcStreamEmitter {
-    fn new(tx: mpsc::UnboundedSender<StreamFrame>, has_text_streamed: Arc<AtomicBool>) -> Self {
+    fn new(tx: StreamSender, has_text_streamed: Arc<AtomicBool>) -> Self {
         Self {
             tx,
             has_text_streamed,
@@ -157,13 +165,13 @@ impl IpcStreamEmitter {
 
 struct SessionReplySender {
     buffered_messages: Arc<Mutex<VecDeque<String>>>,
-    stream_tx: Option<mpsc::UnboundedSender<StreamFrame>>,
+    stream_tx: Option<StreamSender>,
 }
 
 impl SessionReplySender {
     fn new(
         buffered_messages: Arc<Mutex<VecDeque<String>>>,
-        stream_tx: Option<mpsc::UnboundedSender<StreamFrame>>,
+        stream_tx: Option<StreamSender>,
     ) -> Self {
         Self {
             buffered_messages,
@@ -205,6 +213,32 @@ fn parse_tool_arguments(arguments: &str) -> serde_json::Value {
     }
 }
 
+fn subagent_progress_frame(progress: &SubagentProgress) -> StreamFrame {
+    let (status, tool) = match &progress.event {
+        SubagentProgressEvent::IterationStarted | SubagentProgressEvent::ToolCompleted { .. } => {
+            ("running", None)
+        }
+        SubagentProgressEvent::ToolStarted { tool_name } => ("running", Some(tool_name.clone())),
+        SubagentProgressEvent::Finished { status } => (
+            match status {
+                SubagentStatus::Pending | SubagentStatus::Running => "running",
+                SubagentStatus::Completed => "completed",
+                SubagentStatus::Failed => "failed",
+                SubagentStatus::Interrupted => "interrupted",
+                SubagentStatus::TimedOut => "timed_out",
+            },
+            None,
+        ),
+    };
+    StreamFrame::SubagentProgress {
+        task_id: progress.id.clone(),
+        agent_name: progress.agent_name.clone(),
+        iteration: u32::try_from(progress.iteration).unwrap_or(u32::MAX),
+        status: status.to_string(),
+        tool,
+    }
+}
+
 fn normalize_model_input(model: &str) -> Result<String> {
     ModelId::normalize_model_id(model)
         .ok_or_else(|| anyhow::anyhow!("Unsupported model identifier: {}", model))
@@ -252,6 +286,13 @@ impl StreamEmitter for IpcStreamEmitter {
         });
     }
 
+    async fn emit_tool_call_output(&mut self, id: &str, _name: &str, output: &str) {
+        let _ = self.tx.send(StreamFrame::ToolOutput {
+            id: id.to_string(),
+            content: output.to_string(),
+        });
+    }
+
     async fn emit_tool_call_result(&mut self, id: &str, _name: &str, result: &str, success: bool) {
         let _ = self.tx.send(StreamFrame::ToolResult {
             id: id.to_string(),
@@ -260,6 +301,10 @@ impl StreamEmitter for IpcStreamEmitter {
         });
     }
 
+    async fn emit_subagent_progress(&mut self, progress: &SubagentProgress) {
+        let _ = self.tx.send(subagent_progress_frame(progress));
+    }
+
     async fn emit_complete(&mut self) {}
 }
 
@@ -392,7 +437,7 @@ impl IpcServer {
     pub(crate) async fn open_stream(
         core: Arc<AppCore>,
         request: IpcRequest,
-    ) -> Result<mpsc::UnboundedReceiver<StreamFrame>> {
+    ) -> Result<StreamReceiver> {
         match request {
             IpcRequest::ExecuteChatSessionStream {
                 session_id,
@@ -415,7 +460,7 @@ impl IpcServer {
         session_id: String,
         user_input: Option<String>,
         stream_id: String,
-    ) -> Result<mpsc::UnboundedReceiver<StreamFrame>> {
+    ) -> Result<StreamReceiver> {
         let stream_id = if stream_id.trim().is_empty() {
             Uuid::new_v4().to_string()
         } else {
@@ -466,7 +511,7 @@ impl IpcServer {
                 .remove(&previous_stream_id);
         }
 
-        let (tx, rx) = mpsc::unbounded_channel::<StreamFrame>();
+        let (tx, rx) = stream_channel(DEFAULT_STREAM_BUFFER_CAPACITY);
         tx.send(StreamFrame::Start {
             stream_id: stream_id.clone(),
         })?;
@@ -534,11 +579,9 @@ impl IpcServer {
         Ok(rx)
     }
 
-    async fn open_task_event_stream(
-        task_id: String,
-    ) -> Result<mpsc::UnboundedReceiver<StreamFrame>> {
+    async fn open_task_event_stream(task_id: String) -> Result<StreamReceiver> {
         let stream_id = format!("task-{}", Uuid::new_v4());
-        let (tx, rx) = mpsc::unbounded_channel::<StreamFrame>();
+        let (tx, rx) = stream_channel(DEFAULT_STREAM_BUFFER_CAPACITY);
         let mut receiver = subscribe_background_events();
         tx.send(StreamFrame::Start {
             stream_id: stream_id.clone(),
@@ -582,9 +625,9 @@ impl IpcServer {
         Ok(rx)
     }
 
-    async fn open_session_event_stream() -> Result<mpsc::UnboundedReceiver<StreamFrame>> {
+    async fn open_session_event_stream() -> Result<StreamReceiver> {
         let stream_id = format!("session-events-{}", Uuid::new_v4());
-        let (tx, rx) = mpsc::unbounded_channel::<StreamFrame>();
+        let (tx, rx) = stream_channel(DEFAULT_STREAM_BUFFER_CAPACITY);
         let mut receiver = subscribe_session_events();
         tx.send(StreamFrame::Start {
             stream_id: stream_id.clone(),
@@ -1,9 +1,15 @@
 #[path = "dispatch/agents.rs"]
 mod agents;
+#[path = "dispatch/api_connectors.rs"]
+mod api_connectors;
+#[path = "dispatch/api_tokens.rs"]
+mod api_tokens;
 #[path = "dispatch/auth.rs"]
 mod auth;
 #[path = "dispatch/background_agents.rs"]
 mod background_agents;
+#[path = "dispatch/browser_plans.rs"]
+mod browser_plans;
 #[path = "dispatch/config.rs"]
 mod config;
 #[path = "dispatch/hooks.rs"]
@@ -26,8 +32,14 @@ mod skills;
 mod system;
 #[path = "dispatch/terminals.rs"]
 mod terminals;
+#[path = "dispatch/tool_approvals.rs"]
+mod tool_approvals;
+#[path = "dispatch/users.rs"]
+mod users;
 #[path = "dispatch/work_items.rs"]
 mod work_items;
+#[path = "dispatch/workspace.rs"]
+mod workspace;
 
 use super::*;
 use crate::boundary::background_agent::{
@@ -62,6 +74,10 @@ impl IpcServer {
                 Self::handle_update_agent(core, id, name, agent).await
             }
             IpcRequest::DeleteAgent { id } => Self::handle_delete_agent(core, id).await,
+            IpcRequest::ListAgentTemplates => Self::handle_list_agent_templates(),
+            IpcRequest::ProvisionAgentTemplate { template_id, name } => {
+                Self::handle_provision_agent_template(core, template_id, name).await
+            }
             IpcRequest::ListSkills => Self::handle_list_skills(core).await,
             IpcRequest::GetSkill { id } => Self::handle_get_skill(core, id).await,
             IpcRequest::CreateSkill { skill } => match from_contract(skill) {
@@ -117,6 +133,21 @@ impl IpcServer {
             IpcRequest::SetPairingOwner { chat_id } => {
                 Self::handle_set_pairing_owner(core, chat_id).await
             }
+            IpcRequest::ListApiTokens { include_revoked } => {
+                Self::handle_list_api_tokens(core, include_revoked).await
+            }
+            IpcRequest::CreateApiToken {
+                name,
+                scope,
+                expires_at,
+            } => match from_contract(scope) {
+                Ok(scope) => Self::handle_create_api_token(core, name, scope, expires_at).await,
+                Err(err) => invalid_request_response(err),
+            },
+            IpcRequest::RevokeApiToken { id } => Self::handle_revoke_api_token(core, id).await,
+            IpcRequest::ListUsers => Self::handle_list_users(core).await,
+            IpcRequest::CreateUser { name } => Self::handle_create_user(core, name).await,
+            IpcRequest::DeleteUser { id } => Self::handle_delete_user(core, id).await,
             IpcRequest::ListRouteBindings => Self::handle_list_route_bindings(core).await,
             IpcRequest::BindRoute {
                 binding_type,
@@ -128,6 +159,19 @@ impl IpcServer {
             IpcRequest::MigrateSessionSources { dry_run } => {
                 Self::handle_migrate_session_sources(core, dry_run).await
             }
+            IpcRequest::PurgeArtifacts { dry_run } => {
+                Self::handle_purge_artifacts(core, dry_run).await
+            }
+            IpcRequest::BackupDatabase { path } => Self::handle_backup_database(core, path).await,
+            IpcRequest::RestoreDatabase { path } => Self::handle_restore_database(core, path).await,
+            IpcRequest::ExportBundle { secrets_password } => {
+                Self::handle_export_bundle(core, secrets_password).await
+            }
+            IpcRequest::ImportBundle {
+                bundle,
+                secrets_password,
+                overwrite,
+            } => Self::handle_import_bundle(core, bundle, secrets_password, overwrite).await,
             IpcRequest::ListSecrets => Self::handle_list_secrets(core).await,
             IpcRequest::GetSecret { key } => Self::handle_get_secret(core, key).await,
             IpcRequest::SetSecret {
@@ -146,12 +190,27 @@ impl IpcServer {
                 description,
             } => Self::handle_update_secret(core, key, value, description).await,
             IpcRequest::DeleteSecret { key } => Self::handle_delete_secret(core, key).await,
+            IpcRequest::RotateSecret {
+                key,
+                value,
+                expires_at,
+            } => Self::handle_rotate_secret(core, key, value, expires_at).await,
+            IpcRequest::SetSecretExpiry { key, expires_at } => {
+                Self::handle_set_secret_expiry(core, key, expires_at).await
+            }
+            IpcRequest::ListExpiringSecrets { before } => {
+                Self::handle_list_expiring_secrets(core, before).await
+            }
+            IpcRequest::GetSecretUsage { key } => Self::handle_get_secret_usage(core, key).await,
             IpcRequest::GetConfig => Self::handle_get_config(core).await,
             IpcRequest::GetGlobalConfig => Self::handle_get_global_config(core).await,
             IpcRequest::SetConfig { config } => match from_contract(config) {
                 Ok(config) => Self::handle_set_config(core, config).await,
                 Err(err) => invalid_request_response(err),
             },
+            IpcRequest::ApplyProjectConfig { config, dry_run } => {
+                Self::handle_apply_project_config(core, config, dry_run).await
+            }
             IpcRequest::SearchMemory {
                 query,
                 agent_id,
@@ -229,6 +288,17 @@ impl IpcServer {
                 session_id,
                 delete_chunks,
             } => Self::handle_delete_memory_session(core, session_id, delete_chunks).await,
+            IpcRequest::TagMemory {
+                chunk_ids,
+                add_tags,
+                remove_tags,
+            } => Self::handle_tag_memory(core, chunk_ids, add_tags, remove_tags).await,
+            IpcRequest::MergeMemoryTags { sources, target } => {
+                Self::handle_merge_memory_tags(core, sources, target).await
+            }
+            IpcRequest::ListMemoryTags { agent_id } => {
+                Self::handle_list_memory_tags(core, agent_id).await
+            }
             IpcRequest::ListSessions => Self::handle_list_sessions(core).await,
             IpcRequest::ListFullSessions => Self::handle_list_full_sessions(core).await,
             IpcRequest::ListSessionsByAgent { agent_id } => {
@@ -261,6 +331,10 @@ impl IpcServer {
                 Self::handle_rebuild_external_session(core, id).await
             }
             IpcRequest::SearchSessions { query } => Self::handle_search_sessions(core, query).await,
+            IpcRequest::SearchChatSessions { query } => match from_contract(query) {
+                Ok(query) => Self::handle_search_chat_sessions(core, query).await,
+                Err(err) => invalid_request_response(err),
+            },
             IpcRequest::AddMessage {
                 session_id,
                 role,
@@ -303,6 +377,9 @@ impl IpcServer {
             IpcRequest::GetExecutionRunThread { run_id } => {
                 Self::handle_get_execution_run_thread(core, run_id).await
             }
+            IpcRequest::GetRelatedEntities { entity_id } => {
+                Self::handle_get_related_entities(core, entity_id).await
+            }
             IpcRequest::ListChildRuns { query } => match from_contract(query) {
                 Ok(query) => Self::handle_list_child_runs(core, query).await,
                 Err(err) => invalid_request_response(err),
@@ -311,6 +388,10 @@ impl IpcServer {
                 Ok(query) => Self::handle_query_execution_traces(core, query).await,
                 Err(err) => invalid_request_response(err),
             },
+            IpcRequest::QueryExecutionTraceStats { query } => match from_contract(query) {
+                Ok(query) => Self::handle_query_execution_trace_stats(core, query).await,
+                Err(err) => invalid_request_response(err),
+            },
             IpcRequest::GetExecutionRunTimeline { run_id } => {
                 Self::handle_get_execution_run_timeline(core, run_id).await
             }
@@ -330,6 +411,19 @@ impl IpcServer {
             IpcRequest::GetExecutionTraceById { id } => {
                 Self::handle_get_execution_trace_by_id(core, id).await
             }
+            IpcRequest::GetExecutionReplay { id } => {
+                Self::handle_get_execution_replay(core, id).await
+            }
+            IpcRequest::ReplayExecution {
+                execution_id,
+                step,
+                agent_id,
+                message,
+            } => Self::handle_replay_execution(core, execution_id, step, agent_id, message).await,
+            IpcRequest::GetUsageStats { query } => match from_contract(query) {
+                Ok(query) => Self::handle_get_usage_stats(core, query).await,
+                Err(err) => invalid_request_response(err),
+            },
             IpcRequest::ListTerminalSessions => Self::handle_list_terminal_sessions(core).await,
             IpcRequest::GetTerminalSession { id } => {
                 Self::handle_get_terminal_session(core, id).await
@@ -363,6 +457,43 @@ impl IpcServer {
             IpcRequest::MarkAllTerminalSessionsStopped => {
                 Self::handle_mark_all_terminal_sessions_stopped(core).await
             }
+            IpcRequest::ListBrowserPlans => Self::handle_list_browser_plans(core).await,
+            IpcRequest::GetBrowserPlan { name, version } => {
+                Self::handle_get_browser_plan(core, name, version).await
+            }
+            IpcRequest::ListBrowserPlanVersions { name } => {
+                Self::handle_list_browser_plan_versions(core, name).await
+            }
+            IpcRequest::SaveBrowserPlan {
+                name,
+                description,
+                actions,
+                timeout_secs,
+            } => {
+                Self::handle_save_browser_plan(core, name, description, actions, timeout_secs).await
+            }
+            IpcRequest::DeleteBrowserPlan { name } => {
+                Self::handle_delete_browser_plan(core, name).await
+            }
+            IpcRequest::RunBrowserPlan {
+                name,
+                version,
+                payload,
+            } => Self::handle_run_browser_plan(core, name, version, payload).await,
+            IpcRequest::BrowserDoctor => Self::handle_browser_doctor(core).await,
+            IpcRequest::ListApiConnectors => Self::handle_list_api_connectors(core).await,
+            IpcRequest::GetApiConnector { name } => {
+                Self::handle_get_api_connector(core, name).await
+            }
+            IpcRequest::ImportApiConnector {
+                name,
+                spec,
+                base_url,
+                auth_secret,
+            } => Self::handle_import_api_connector(core, name, spec, base_url, auth_secret).await,
+            IpcRequest::DeleteApiConnector { name } => {
+                Self::handle_delete_api_connector(core, name).await
+            }
             IpcRequest::ListAuthProfiles => Self::handle_list_auth_profiles(core).await,
             IpcRequest::GetAuthProfile { id } => Self::handle_get_auth_profile(core, id).await,
             IpcRequest::AddAuthProfile {
@@ -385,6 +516,35 @@ impl IpcServer {
                 };
                 Self::handle_add_auth_profile(core, name, credential, source, provider).await
             }
+            IpcRequest::AddOAuthProfile {
+                name,
+                credential,
+                provider,
+                oauth,
+                client_secret,
+            } => {
+                let credential = match from_contract(credential) {
+                    Ok(credential) => credential,
+                    Err(err) => return invalid_request_response(err),
+                };
+                let provider = match from_contract(provider) {
+                    Ok(provider) => provider,
+                    Err(err) => return invalid_request_response(err),
+                };
+                let oauth = match from_contract(oauth) {
+                    Ok(oauth) => oauth,
+                    Err(err) => return invalid_request_response(err),
+                };
+                Self::handle_add_oauth_profile(
+                    core,
+                    name,
+                    credential,
+                    provider,
+                    oauth,
+                    client_secret,
+                )
+                .await
+            }
             IpcRequest::RemoveAuthProfile { id } => {
                 Self::handle_remove_auth_profile(core, id).await
             }
@@ -430,6 +590,25 @@ impl IpcServer {
                 Ok(action) => Self::handle_control_task(core, id, action).await,
                 Err(err) => invalid_request_response(err),
             },
+            IpcRequest::RunTaskWithSecrets { id, secrets } => {
+                Self::handle_run_background_agent_with_secrets(core, id, secrets).await
+            }
+            IpcRequest::SetTaskWakeCondition {
+                id,
+                condition,
+                checkpoint_id,
+            } => match condition.map(from_contract).transpose() {
+                Ok(condition) => {
+                    Self::handle_set_background_agent_wake_condition(
+                        core,
+                        id,
+                        condition,
+                        checkpoint_id,
+                    )
+                    .await
+                }
+                Err(err) => invalid_request_response(err),
+            },
             IpcRequest::GetTaskProgress { id, event_limit } => {
                 Self::handle_get_task_progress(core, id, event_limit).await
             }
@@ -447,6 +626,19 @@ impl IpcServer {
             IpcRequest::HandleTaskApproval { id, approved } => {
                 Self::handle_task_approval(core, id, approved).await
             }
+            IpcRequest::ListToolApprovals { pending_only } => {
+                Self::handle_list_tool_approvals(core, pending_only).await
+            }
+            IpcRequest::ResolveToolApproval {
+                id,
+                approved,
+                reason,
+            } => Self::handle_resolve_tool_approval(core, id, approved, reason).await,
+            IpcRequest::GetToolApprovalPolicy => Self::handle_get_tool_approval_policy(core).await,
+            IpcRequest::SetToolApprovalPolicy { policy } => match from_contract(policy) {
+                Ok(policy) => Self::handle_set_tool_approval_policy(core, policy).await,
+                Err(err) => invalid_request_response(err),
+            },
             IpcRequest::ListTaskMessages { id, limit } => {
                 Self::handle_list_task_messages(core, id, limit).await
             }
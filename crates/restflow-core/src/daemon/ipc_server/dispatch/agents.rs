@@ -1,4 +1,5 @@
 use super::super::*;
+use crate::services::agent_template as agent_template_service;
 use crate::services::operation_assessment::{
     assess_agent_create, assess_agent_update, assessment_summary,
 };
@@ -41,25 +42,38 @@ impl IpcServer {
         name: String,
         agent: crate::models::AgentNode,
     ) -> IpcResponse {
+        if let Some(blocked) = Self::agent_create_blockers(core, &name, &agent).await {
+            return blocked;
+        }
+
+        match agent_service::create_agent(core, name, agent).await {
+            Ok(agent) => IpcResponse::success(agent),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    /// Error response when the create assessment blocks `agent`.
+    pub(super) async fn agent_create_blockers(
+        core: &Arc<AppCore>,
+        name: &str,
+        agent: &crate::models::AgentNode,
+    ) -> Option<IpcResponse> {
         let assessment = match assess_agent_create(
             core,
             AgentCreateRequest {
-                name: name.clone(),
+                name: name.to_string(),
                 agent: ContractAgentNode::from(agent.clone()),
             },
         )
         .await
         {
             Ok(assessment) => assessment,
-            Err(err) => return IpcResponse::error(500, err.to_string()),
+            Err(err) => return Some(IpcResponse::error(500, err.to_string())),
         };
-        if !assessment.blockers.is_empty() {
-            return blocked_assessment_response(assessment);
-        }
-
-        match agent_service::create_agent(core, name, agent).await {
-            Ok(agent) => IpcResponse::success(agent),
-            Err(err) => IpcResponse::error(500, err.to_string()),
+        if assessment.blockers.is_empty() {
+            None
+        } else {
+            Some(blocked_assessment_response(assessment))
         }
     }
 
@@ -98,4 +112,29 @@ impl IpcServer {
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
     }
+
+    pub(super) fn handle_list_agent_templates() -> IpcResponse {
+        match agent_template_service::list_agent_templates() {
+            Ok(templates) => IpcResponse::success(templates),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_provision_agent_template(
+        core: &Arc<AppCore>,
+        template_id: String,
+        name: Option<String>,
+    ) -> IpcResponse {
+        match agent_template_service::get_agent_template(&template_id) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return IpcResponse::error(404, format!("Agent template not found: {template_id}"));
+            }
+            Err(err) => return IpcResponse::error(500, err.to_string()),
+        }
+        match agent_template_service::provision_agent_template(core, &template_id, name).await {
+            Ok(provisioned) => IpcResponse::success(provisioned),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
 }
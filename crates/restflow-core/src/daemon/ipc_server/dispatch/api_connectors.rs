@@ -0,0 +1,45 @@
+use super::super::*;
+use crate::services::api_connector as api_connector_service;
+use restflow_contracts::DeleteResponse;
+
+impl IpcServer {
+    pub(super) async fn handle_list_api_connectors(core: &Arc<AppCore>) -> IpcResponse {
+        match api_connector_service::list_api_connectors(core).await {
+            Ok(connectors) => IpcResponse::success(connectors),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_get_api_connector(core: &Arc<AppCore>, name: String) -> IpcResponse {
+        match api_connector_service::get_api_connector(core, &name).await {
+            Ok(Some(connector)) => IpcResponse::success(connector),
+            Ok(None) => IpcResponse::not_found("API connector"),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_import_api_connector(
+        core: &Arc<AppCore>,
+        name: String,
+        spec: String,
+        base_url: Option<String>,
+        auth_secret: Option<String>,
+    ) -> IpcResponse {
+        match api_connector_service::import_api_connector(core, &name, &spec, base_url, auth_secret)
+            .await
+        {
+            Ok(connector) => IpcResponse::success(connector),
+            Err(err) => IpcResponse::error(400, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_delete_api_connector(
+        core: &Arc<AppCore>,
+        name: String,
+    ) -> IpcResponse {
+        match api_connector_service::delete_api_connector(core, &name).await {
+            Ok(deleted) => IpcResponse::success(DeleteResponse { deleted }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::super::*;
+use crate::models::{ApiToken, ApiTokenRole, ApiTokenScope};
+use crate::services::api_tokens::ApiTokenService;
+use crate::services::workspace::WorkspaceService;
+use restflow_contracts::{ApiTokenResponse, IssuedApiTokenResponse, OkResponse};
+
+impl IpcServer {
+    pub(super) async fn handle_list_api_tokens(
+        core: &Arc<AppCore>,
+        include_revoked: bool,
+    ) -> IpcResponse {
+        let service = ApiTokenService::from_storage(core.storage.as_ref());
+        match service.list(include_revoked) {
+            Ok(tokens) => IpcResponse::success(
+                tokens
+                    .into_iter()
+                    .map(api_token_response)
+                    .collect::<Vec<_>>(),
+            ),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_create_api_token(
+        core: &Arc<AppCore>,
+        name: String,
+        scope: ApiTokenScope,
+        expires_at: Option<i64>,
+    ) -> IpcResponse {
+        if name.trim().is_empty() {
+            return IpcResponse::error(400, "API token name must not be empty");
+        }
+
+        if let Some(user_id) = &scope.user_id {
+            match WorkspaceService::from_storage(core.storage.as_ref()).get_user(user_id) {
+                Ok(Some(_)) => {}
+                Ok(None) => return IpcResponse::error(400, format!("User {user_id} not found")),
+                Err(err) => return IpcResponse::error(500, err.to_string()),
+            }
+        }
+
+        let service = ApiTokenService::from_storage(core.storage.as_ref());
+        match service.issue(name, scope, expires_at) {
+            Ok(issued) => IpcResponse::success(IssuedApiTokenResponse {
+                token: api_token_response(issued.token),
+                secret: issued.secret,
+            }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_revoke_api_token(core: &Arc<AppCore>, id: String) -> IpcResponse {
+        let service = ApiTokenService::from_storage(core.storage.as_ref());
+        match service.revoke(&id) {
+            Ok(true) => IpcResponse::success(OkResponse { ok: true }),
+            Ok(false) => IpcResponse::not_found("API token"),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+}
+
+fn api_token_response(token: ApiToken) -> ApiTokenResponse {
+    ApiTokenResponse {
+        id: token.id,
+        name: token.name,
+        token_prefix: token.token_prefix,
+        scope: restflow_contracts::request::ApiTokenScope {
+            allowed_agents: token.scope.allowed_agents,
+            allowed_tools: token.scope.allowed_tools,
+            max_calls: token.scope.max_calls,
+            mcp: token.scope.mcp,
+            webhooks: token.scope.webhooks,
+            openai_api: token.scope.openai_api,
+            role: token.scope.role.map(api_token_role),
+            rate_limit_per_minute: token.scope.rate_limit_per_minute,
+            user_id: token.scope.user_id,
+        },
+        call_count: token.call_count,
+        created_at: token.created_at,
+        last_used_at: token.last_used_at,
+        expires_at: token.expires_at,
+        revoked_at: token.revoked_at,
+    }
+}
+
+fn api_token_role(role: ApiTokenRole) -> restflow_contracts::request::ApiTokenRole {
+    match role {
+        ApiTokenRole::ReadOnly => restflow_contracts::request::ApiTokenRole::ReadOnly,
+        ApiTokenRole::Execute => restflow_contracts::request::ApiTokenRole::Execute,
+        ApiTokenRole::Admin => restflow_contracts::request::ApiTokenRole::Admin,
+    }
+}
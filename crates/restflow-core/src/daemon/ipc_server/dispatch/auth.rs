@@ -45,6 +45,30 @@ impl IpcServer {
         }
     }
 
+    pub(super) async fn handle_add_oauth_profile(
+        core: &Arc<AppCore>,
+        name: String,
+        credential: crate::auth::Credential,
+        provider: crate::auth::AuthProvider,
+        oauth: crate::auth::OAuthClientConfig,
+        client_secret: Option<String>,
+    ) -> IpcResponse {
+        let manager = match build_auth_manager(core).await {
+            Ok(manager) => manager,
+            Err(err) => return IpcResponse::error(500, err.to_string()),
+        };
+        match manager
+            .add_oauth_profile(name, credential, provider, oauth, client_secret)
+            .await
+        {
+            Ok(id) => match manager.get_profile(&id).await {
+                Some(profile) => IpcResponse::success(profile),
+                None => IpcResponse::error(500, "Profile created but not found"),
+            },
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
     pub(super) async fn handle_remove_auth_profile(core: &Arc<AppCore>, id: String) -> IpcResponse {
         let manager = match build_auth_manager(core).await {
             Ok(manager) => manager,
@@ -11,6 +11,7 @@ use crate::services::operation_assessment::OperationAssessorAdapter;
 use crate::storage::background_agent::ResolveTaskIdError;
 use restflow_contracts::ApprovalHandledResponse;
 use restflow_traits::store::{BackgroundAgentControlRequest, BackgroundAgentDeleteRequest};
+use std::collections::HashMap;
 
 fn resolve_background_agent_id(
     core: &Arc<AppCore>,
@@ -200,6 +201,56 @@ impl IpcServer {
         }
     }
 
+    pub(super) async fn handle_run_background_agent_with_secrets(
+        core: &Arc<AppCore>,
+        id: String,
+        secrets: HashMap<String, String>,
+    ) -> IpcResponse {
+        let resolved_id = match resolve_background_agent_id(core, &id) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let background_agents = &core.storage.background_agents;
+        if let Err(err) = background_agents.stage_temporary_secrets(&resolved_id, secrets) {
+            return IpcResponse::error(400, err.to_string());
+        }
+        let response = Self::handle_control_background_agent(
+            core,
+            resolved_id.clone(),
+            crate::models::BackgroundAgentControlAction::RunNow,
+        )
+        .await;
+        if matches!(response, IpcResponse::Error(_)) {
+            background_agents
+                .temporary_secrets()
+                .discard_pending(&resolved_id);
+        }
+        response
+    }
+
+    pub(super) async fn handle_set_background_agent_wake_condition(
+        core: &Arc<AppCore>,
+        id: String,
+        condition: Option<crate::models::WakeCondition>,
+        checkpoint_id: Option<String>,
+    ) -> IpcResponse {
+        let resolved_id = match resolve_background_agent_id(core, &id) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let background_agents = &core.storage.background_agents;
+        let result = match condition {
+            Some(condition) => {
+                background_agents.register_wake_condition(&resolved_id, condition, checkpoint_id)
+            }
+            None => background_agents.clear_wake_condition(&resolved_id),
+        };
+        match result {
+            Ok(task) => IpcResponse::success(task),
+            Err(err) => IpcResponse::error(400, err.to_string()),
+        }
+    }
+
     pub(super) async fn handle_get_background_agent_progress(
         core: &Arc<AppCore>,
         id: String,
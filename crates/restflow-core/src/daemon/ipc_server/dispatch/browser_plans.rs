@@ -0,0 +1,90 @@
+use super::super::*;
+use crate::models::BrowserPlan;
+use crate::services::browser_plan as browser_plan_service;
+use crate::services::browser_webhook::browser_doctor;
+use restflow_contracts::DeleteResponse;
+use serde_json::Value;
+
+impl IpcServer {
+    pub(super) async fn handle_list_browser_plans(core: &Arc<AppCore>) -> IpcResponse {
+        match browser_plan_service::list_browser_plans(core).await {
+            Ok(plans) => IpcResponse::success(plans),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_get_browser_plan(
+        core: &Arc<AppCore>,
+        name: String,
+        version: Option<u32>,
+    ) -> IpcResponse {
+        match browser_plan_service::get_browser_plan(core, &name, version).await {
+            Ok(Some(plan)) => IpcResponse::success(plan),
+            Ok(None) => IpcResponse::not_found("Browser plan"),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_list_browser_plan_versions(
+        core: &Arc<AppCore>,
+        name: String,
+    ) -> IpcResponse {
+        match browser_plan_service::list_browser_plan_versions(core, &name).await {
+            Ok(versions) if versions.is_empty() => IpcResponse::not_found("Browser plan"),
+            Ok(versions) => IpcResponse::success(versions),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_save_browser_plan(
+        core: &Arc<AppCore>,
+        name: String,
+        description: Option<String>,
+        actions: Value,
+        timeout_secs: Option<u64>,
+    ) -> IpcResponse {
+        let plan = BrowserPlan {
+            actions,
+            timeout_secs,
+        };
+        match browser_plan_service::save_browser_plan(core, &name, description, plan).await {
+            Ok(saved) => IpcResponse::success(saved),
+            Err(err) => IpcResponse::error(400, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_delete_browser_plan(
+        core: &Arc<AppCore>,
+        name: String,
+    ) -> IpcResponse {
+        match browser_plan_service::delete_browser_plan(core, &name).await {
+            Ok(deleted) => IpcResponse::success(DeleteResponse { deleted }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_run_browser_plan(
+        core: &Arc<AppCore>,
+        name: String,
+        version: Option<u32>,
+        payload: Option<Value>,
+    ) -> IpcResponse {
+        match browser_plan_service::run_saved_browser_plan(core, &name, version, payload).await {
+            Ok(outcome) => IpcResponse::success(outcome),
+            Err(err) => IpcResponse::error(400, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_browser_doctor(core: &Arc<AppCore>) -> IpcResponse {
+        let agent_settings = core
+            .storage
+            .config
+            .get_effective_config()
+            .map(|config| config.agent)
+            .unwrap_or_default();
+        match browser_doctor(&agent_settings).await {
+            Ok(report) => IpcResponse::success(report),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+}
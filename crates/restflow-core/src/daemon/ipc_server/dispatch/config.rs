@@ -1,4 +1,5 @@
 use super::super::*;
+use crate::services::project_config::{self, ProjectConfig};
 use restflow_contracts::OkResponse;
 
 impl IpcServer {
@@ -25,4 +26,22 @@ impl IpcServer {
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
     }
+
+    pub(super) async fn handle_apply_project_config(
+        core: &Arc<AppCore>,
+        config: serde_json::Value,
+        dry_run: bool,
+    ) -> IpcResponse {
+        let config: ProjectConfig = match serde_json::from_value(config) {
+            Ok(config) => config,
+            Err(err) => return IpcResponse::error(400, format!("Invalid project config: {err}")),
+        };
+        if let Err(err) = config.validate() {
+            return IpcResponse::error(400, format!("Invalid project config: {err}"));
+        }
+        match project_config::apply_project_config(core, &config, dry_run).await {
+            Ok(report) => IpcResponse::success(report),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
 }
@@ -1,5 +1,9 @@
 use super::super::*;
-use restflow_contracts::{CleanupReportResponse, SessionSourceMigrationResponse};
+use crate::services::data_bundle;
+use restflow_contracts::{
+    ArtifactPurgeResponse, ArtifactRemovalResponse, CleanupReportResponse, DatabaseBackupResponse,
+    SessionSourceMigrationResponse,
+};
 
 impl IpcServer {
     pub(super) async fn handle_run_cleanup(core: &Arc<AppCore>) -> IpcResponse {
@@ -12,6 +16,32 @@ impl IpcServer {
                 memory_sessions: report.memory_sessions,
                 vector_orphans: report.vector_orphans,
                 daemon_log_files: report.daemon_log_files,
+                artifacts: report.artifacts,
+                artifact_bytes_reclaimed: report.artifact_bytes_reclaimed,
+            }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_purge_artifacts(core: &Arc<AppCore>, dry_run: bool) -> IpcResponse {
+        match crate::services::artifacts::run_artifact_gc(core, dry_run).await {
+            Ok(report) => IpcResponse::success(ArtifactPurgeResponse {
+                dry_run: report.dry_run,
+                scanned: report.scanned,
+                preserved: report.preserved,
+                removed: report
+                    .removed
+                    .into_iter()
+                    .map(|removal| ArtifactRemovalResponse {
+                        kind: removal.kind.as_str().to_string(),
+                        owner_id: removal.owner_id,
+                        path: removal.path,
+                        size_bytes: removal.size_bytes,
+                        reason: removal.reason.as_str().to_string(),
+                    })
+                    .collect(),
+                bytes_reclaimed: report.bytes_reclaimed,
+                bytes_remaining: report.bytes_remaining,
             }),
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
@@ -36,4 +66,63 @@ impl IpcServer {
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
     }
+
+    pub(super) async fn handle_backup_database(core: &Arc<AppCore>, path: String) -> IpcResponse {
+        if path.trim().is_empty() {
+            return IpcResponse::error(400, "path is required");
+        }
+        match core.storage.backup_to(std::path::Path::new(&path)) {
+            Ok(summary) => IpcResponse::success(DatabaseBackupResponse {
+                path,
+                tables: summary.tables,
+                entries: summary.entries,
+            }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_restore_database(core: &Arc<AppCore>, path: String) -> IpcResponse {
+        if path.trim().is_empty() {
+            return IpcResponse::error(400, "path is required");
+        }
+        match core.storage.restore_from(std::path::Path::new(&path)) {
+            Ok(summary) => IpcResponse::success(DatabaseBackupResponse {
+                path,
+                tables: summary.tables,
+                entries: summary.entries,
+            }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_export_bundle(
+        core: &Arc<AppCore>,
+        secrets_password: Option<String>,
+    ) -> IpcResponse {
+        match data_bundle::export_bundle(&core.storage, secrets_password.as_deref()) {
+            Ok(bundle) => IpcResponse::success(bundle),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_import_bundle(
+        core: &Arc<AppCore>,
+        bundle: serde_json::Value,
+        secrets_password: Option<String>,
+        overwrite: bool,
+    ) -> IpcResponse {
+        let bundle: data_bundle::DataBundle = match serde_json::from_value(bundle) {
+            Ok(bundle) => bundle,
+            Err(err) => return IpcResponse::error(400, format!("Invalid bundle: {err}")),
+        };
+        match data_bundle::import_bundle(
+            &core.storage,
+            &bundle,
+            secrets_password.as_deref(),
+            overwrite,
+        ) {
+            Ok(report) => IpcResponse::success(report),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use super::super::runtime::resolve_agent_id;
 use super::super::*;
-use restflow_contracts::{ClearResponse, DeleteResponse, IdResponse};
+use restflow_contracts::{ClearResponse, DeleteResponse, IdResponse, UpdatedResponse};
 
 impl IpcServer {
     pub(super) async fn handle_search_memory(
@@ -106,6 +106,19 @@ impl IpcServer {
         if !tags.is_empty() {
             chunk = chunk.with_tags(tags);
         }
+        let memory_defaults = core
+            .storage
+            .config
+            .get_effective_config()
+            .map(|config| config.memory_defaults)
+            .unwrap_or_default();
+        if let Err(err) = crate::memory::suggest_tags_for_chunk(
+            &core.storage.memory,
+            &memory_defaults,
+            &mut chunk,
+        ) {
+            return IpcResponse::error(500, err.to_string());
+        }
         match core.storage.memory.store_chunk(&chunk) {
             Ok(id) => IpcResponse::success(IdResponse { id }),
             Err(err) => IpcResponse::error(500, err.to_string()),
@@ -283,4 +296,57 @@ impl IpcServer {
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
     }
+
+    pub(super) async fn handle_tag_memory(
+        core: &Arc<AppCore>,
+        chunk_ids: Vec<String>,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+    ) -> IpcResponse {
+        if chunk_ids.is_empty() {
+            return IpcResponse::error(400, "chunk_ids must not be empty");
+        }
+        if add_tags.is_empty() && remove_tags.is_empty() {
+            return IpcResponse::error(400, "add_tags or remove_tags is required");
+        }
+        match core
+            .storage
+            .memory
+            .update_tags(&chunk_ids, &add_tags, &remove_tags)
+        {
+            Ok(updated) => IpcResponse::success(UpdatedResponse {
+                updated: updated as u32,
+            }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_merge_memory_tags(
+        core: &Arc<AppCore>,
+        sources: Vec<String>,
+        target: String,
+    ) -> IpcResponse {
+        if sources.is_empty() {
+            return IpcResponse::error(400, "sources must not be empty");
+        }
+        if target.trim().is_empty() {
+            return IpcResponse::error(400, "target must not be empty");
+        }
+        match core.storage.memory.merge_tags(&sources, &target) {
+            Ok(updated) => IpcResponse::success(UpdatedResponse {
+                updated: updated as u32,
+            }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_list_memory_tags(
+        core: &Arc<AppCore>,
+        agent_id: Option<String>,
+    ) -> IpcResponse {
+        match core.storage.memory.list_tags(agent_id.as_deref()) {
+            Ok(tags) => IpcResponse::success(tags),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
 }
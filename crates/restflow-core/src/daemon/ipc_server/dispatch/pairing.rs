@@ -172,12 +172,16 @@ impl IpcServer {
 }
 
 fn pairing_manager(core: &Arc<AppCore>) -> anyhow::Result<crate::channel::PairingManager> {
-    let storage = Arc::new(crate::storage::PairingStorage::new(core.storage.get_db())?);
+    let storage = Arc::new(crate::storage::PairingStorage::with_backend(
+        core.storage.backend(),
+    )?);
     Ok(crate::channel::PairingManager::new(storage))
 }
 
 fn route_resolver(core: &Arc<AppCore>) -> anyhow::Result<crate::channel::RouteResolver> {
-    let storage = Arc::new(crate::storage::PairingStorage::new(core.storage.get_db())?);
+    let storage = Arc::new(crate::storage::PairingStorage::with_backend(
+        core.storage.backend(),
+    )?);
     Ok(crate::channel::RouteResolver::new(storage))
 }
 
@@ -59,4 +59,44 @@ impl IpcServer {
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
     }
+
+    pub(super) async fn handle_rotate_secret(
+        core: &Arc<AppCore>,
+        key: String,
+        value: String,
+        expires_at: Option<i64>,
+    ) -> IpcResponse {
+        match secrets_service::rotate_secret(core, &key, &value, expires_at).await {
+            Ok(()) => IpcResponse::success(OkResponse { ok: true }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_set_secret_expiry(
+        core: &Arc<AppCore>,
+        key: String,
+        expires_at: Option<i64>,
+    ) -> IpcResponse {
+        match secrets_service::set_secret_expiry(core, &key, expires_at).await {
+            Ok(()) => IpcResponse::success(OkResponse { ok: true }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_list_expiring_secrets(
+        core: &Arc<AppCore>,
+        before: i64,
+    ) -> IpcResponse {
+        match secrets_service::list_expiring_secrets(core, before).await {
+            Ok(secrets) => IpcResponse::success(secrets),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_get_secret_usage(core: &Arc<AppCore>, key: String) -> IpcResponse {
+        match secrets_service::get_secret_usage(core, &key).await {
+            Ok(usage) => IpcResponse::success(usage),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use super::super::runtime::{
-    cancel_chat_stream, execute_chat_session, resolve_agent_id, steer_chat_stream,
+    build_auth_manager, cancel_chat_stream, create_chat_executor, execute_chat_session,
+    resolve_agent_id, steer_chat_stream,
 };
 use super::super::*;
 use crate::services::execution_console::{ExecutionConsoleService, ExecutionThreadError};
+use crate::services::replay::{ReplayError, ReplayService};
 use crate::telemetry::{
     get_execution_metrics, get_execution_timeline, get_provider_health, query_execution_logs,
 };
@@ -42,6 +44,21 @@ impl IpcServer {
         map_execution_thread_response(service.get_execution_run_thread(&run_id))
     }
 
+    pub(super) async fn handle_get_related_entities(
+        core: &Arc<AppCore>,
+        entity_id: String,
+    ) -> IpcResponse {
+        let entity_id = entity_id.trim();
+        if entity_id.is_empty() {
+            return IpcResponse::error(400, "entity_id is required");
+        }
+
+        match core.storage.entity_relations.get_related(entity_id) {
+            Ok(related) => IpcResponse::success(related),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
     pub(super) async fn handle_list_child_runs(
         core: &Arc<AppCore>,
         query: crate::models::ChildRunListQuery,
@@ -245,6 +262,17 @@ impl IpcServer {
         }
     }
 
+    pub(super) async fn handle_search_chat_sessions(
+        core: &Arc<AppCore>,
+        query: ChatSearchQuery,
+    ) -> IpcResponse {
+        let session_service = SessionService::from_storage(&core.storage);
+        match session_service.search_messages(&query) {
+            Ok(result) => IpcResponse::success(result),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
     pub(super) async fn handle_add_message(
         core: &Arc<AppCore>,
         session_id: String,
@@ -341,6 +369,16 @@ impl IpcServer {
         }
     }
 
+    pub(super) async fn handle_query_execution_trace_stats(
+        core: &Arc<AppCore>,
+        query: crate::models::ExecutionTraceQuery,
+    ) -> IpcResponse {
+        match core.storage.execution_traces.stats_for_query(&query) {
+            Ok(stats) => IpcResponse::success(stats),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
     pub(super) async fn handle_get_execution_run_timeline(
         core: &Arc<AppCore>,
         run_id: String,
@@ -405,6 +443,16 @@ impl IpcServer {
         }
     }
 
+    pub(super) async fn handle_get_usage_stats(
+        core: &Arc<AppCore>,
+        query: crate::models::UsageQuery,
+    ) -> IpcResponse {
+        match core.storage.usage.stats(&query) {
+            Ok(stats) => IpcResponse::success(stats),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
     pub(super) async fn handle_query_execution_run_logs(
         core: &Arc<AppCore>,
         run_id: String,
@@ -475,6 +523,57 @@ impl IpcServer {
             Err(err) => IpcResponse::error(500, err.to_string()),
         }
     }
+
+    pub(super) async fn handle_get_execution_replay(
+        core: &Arc<AppCore>,
+        id: String,
+    ) -> IpcResponse {
+        let id = id.trim();
+        if id.is_empty() {
+            return IpcResponse::error(400, "id is required");
+        }
+        map_replay_response(ReplayService::from_storage(&core.storage).get_replay(id))
+    }
+
+    pub(super) async fn handle_replay_execution(
+        core: &Arc<AppCore>,
+        execution_id: String,
+        step: u32,
+        agent_id: Option<String>,
+        message: Option<String>,
+    ) -> IpcResponse {
+        let execution_id = execution_id.trim();
+        if execution_id.is_empty() {
+            return IpcResponse::error(400, "execution_id is required");
+        }
+        let auth_manager = match build_auth_manager(core).await {
+            Ok(manager) => Arc::new(manager),
+            Err(err) => return IpcResponse::error(500, err.to_string()),
+        };
+        let executor = create_chat_executor(core, auth_manager);
+        let result = ReplayService::from_storage(&core.storage)
+            .replay_from_step(
+                &executor,
+                execution_id,
+                step,
+                agent_id.as_deref(),
+                message.as_deref(),
+            )
+            .await;
+        map_replay_response(result)
+    }
+}
+
+fn map_replay_response<T: serde::Serialize>(
+    result: std::result::Result<T, ReplayError>,
+) -> IpcResponse {
+    match result {
+        Ok(value) => IpcResponse::success(value),
+        Err(ReplayError::NotFound(_)) => IpcResponse::not_found("Execution replay"),
+        Err(ReplayError::StepNotFound { .. }) => IpcResponse::not_found("Replay step"),
+        Err(err @ ReplayError::AgentRequired(_)) => IpcResponse::error(400, err.to_string()),
+        Err(ReplayError::Internal(err)) => IpcResponse::error(500, err.to_string()),
+    }
 }
 
 fn map_execution_thread_response(
@@ -0,0 +1,83 @@
+use super::super::runtime::steer_chat_stream;
+use super::super::*;
+use crate::models::{ApprovalStatus, TaskMessageSource, ToolApprovalPolicy};
+use crate::security::{approval_steer_message, resolve_tool_approval};
+use restflow_contracts::OkResponse;
+
+impl IpcServer {
+    pub(super) async fn handle_list_tool_approvals(
+        core: &Arc<AppCore>,
+        pending_only: bool,
+    ) -> IpcResponse {
+        let status = pending_only.then_some(ApprovalStatus::Pending);
+        match core.storage.tool_approvals.list(status) {
+            Ok(approvals) => IpcResponse::success(approvals),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_resolve_tool_approval(
+        core: &Arc<AppCore>,
+        id: String,
+        approved: bool,
+        reason: Option<String>,
+    ) -> IpcResponse {
+        let storage = &core.storage.tool_approvals;
+        match storage.get(&id) {
+            Ok(Some(_)) => {}
+            Ok(None) => return IpcResponse::not_found("Tool approval"),
+            Err(err) => return IpcResponse::error(500, err.to_string()),
+        }
+        let approval = match resolve_tool_approval(storage, &id, approved, reason) {
+            Ok(approval) => approval,
+            Err(err) => return IpcResponse::error(409, err.to_string()),
+        };
+
+        // Resume the run that deferred the call.
+        let message = approval_steer_message(&approval);
+        if !approval.task_id.is_empty() {
+            if let Err(err) = core
+                .storage
+                .background_agents
+                .send_background_agent_message(
+                    &approval.task_id,
+                    message,
+                    TaskMessageSource::System,
+                )
+            {
+                warn!(
+                    approval_id = %approval.id,
+                    task_id = %approval.task_id,
+                    error = %err,
+                    "Failed to deliver tool approval to background task"
+                );
+            }
+        } else if let Some(session_id) = approval.session_id.as_deref()
+            && !steer_chat_stream(session_id, &message).await
+        {
+            debug!(
+                approval_id = %approval.id,
+                session_id,
+                "No active chat stream to resume after tool approval"
+            );
+        }
+        IpcResponse::success(approval)
+    }
+
+    pub(super) async fn handle_get_tool_approval_policy(core: &Arc<AppCore>) -> IpcResponse {
+        match core.storage.tool_approvals.get_policy() {
+            Ok(policy) => IpcResponse::success(policy),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_set_tool_approval_policy(
+        core: &Arc<AppCore>,
+        policy: ToolApprovalPolicy,
+    ) -> IpcResponse {
+        match core.storage.tool_approvals.set_policy(&policy) {
+            Ok(()) => IpcResponse::success(OkResponse { ok: true }),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+}
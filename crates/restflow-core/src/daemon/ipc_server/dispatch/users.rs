@@ -0,0 +1,41 @@
+use super::super::*;
+use crate::models::User;
+use crate::services::workspace::WorkspaceService;
+use restflow_contracts::{DeleteResponse, UserResponse};
+
+impl IpcServer {
+    pub(super) async fn handle_list_users(core: &Arc<AppCore>) -> IpcResponse {
+        let service = WorkspaceService::from_storage(core.storage.as_ref());
+        match service.list_users() {
+            Ok(users) => {
+                IpcResponse::success(users.into_iter().map(user_response).collect::<Vec<_>>())
+            }
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_create_user(core: &Arc<AppCore>, name: String) -> IpcResponse {
+        let service = WorkspaceService::from_storage(core.storage.as_ref());
+        match service.create_user(&name) {
+            Ok(user) => IpcResponse::success(user_response(user)),
+            Err(err) => IpcResponse::error(400, err.to_string()),
+        }
+    }
+
+    pub(super) async fn handle_delete_user(core: &Arc<AppCore>, id: String) -> IpcResponse {
+        let service = WorkspaceService::from_storage(core.storage.as_ref());
+        match service.delete_user(&id) {
+            Ok(true) => IpcResponse::success(DeleteResponse { deleted: true }),
+            Ok(false) => IpcResponse::not_found("User"),
+            Err(err) => IpcResponse::error(500, err.to_string()),
+        }
+    }
+}
+
+fn user_response(user: User) -> UserResponse {
+    UserResponse {
+        id: user.id,
+        name: user.name,
+        created_at: user.created_at,
+    }
+}
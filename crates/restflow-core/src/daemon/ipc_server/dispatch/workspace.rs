@@ -0,0 +1,143 @@
+use super::super::*;
+use crate::daemon::request_mapper::invalid_validation_response;
+use crate::services::workspace::{WorkspaceAccess, WorkspaceAccessError};
+use restflow_contracts::{ErrorPayload, OkResponse};
+
+fn access_error_response(error: WorkspaceAccessError) -> IpcResponse {
+    IpcResponse::error_payload(access_error_payload(error))
+}
+
+fn access_error_payload(error: WorkspaceAccessError) -> ErrorPayload {
+    match error {
+        WorkspaceAccessError::NotFound(what) => ErrorPayload::not_found(what),
+        error => ErrorPayload::new(i32::from(error.status_code()), error.to_string(), None),
+    }
+}
+
+impl IpcServer {
+    /// Run `request` on behalf of a workspace user.
+    ///
+    /// Listings and creates go through [`WorkspaceAccess`]; requests that
+    /// address an existing resource run as usual once the workspace is known
+    /// to own it. Requests not handled here are refused, so new requests stay
+    /// closed to workspace callers until they are scoped explicitly.
+    pub(crate) async fn process_in_workspace(
+        core: &Arc<AppCore>,
+        runtime_tool_registry: &OnceLock<restflow_ai::tools::ToolRegistry>,
+        access: &mut WorkspaceAccess,
+        request: IpcRequest,
+    ) -> IpcResponse {
+        if let Err(error) = Self::check_workspace_request(access, &request) {
+            return IpcResponse::error_payload(error);
+        }
+        match request {
+            IpcRequest::ListAgents => match access.list_agents() {
+                Ok(agents) => IpcResponse::success(agents),
+                Err(error) => access_error_response(error),
+            },
+            IpcRequest::ListSkills => match access.list_skills() {
+                Ok(skills) => IpcResponse::success(skills),
+                Err(error) => access_error_response(error),
+            },
+            IpcRequest::ListSessions => match access.list_sessions() {
+                Ok(sessions) => IpcResponse::success(
+                    sessions
+                        .iter()
+                        .map(ChatSessionSummary::from)
+                        .collect::<Vec<_>>(),
+                ),
+                Err(error) => access_error_response(error),
+            },
+            IpcRequest::ListFullSessions => match access.list_sessions() {
+                Ok(sessions) => IpcResponse::success(sessions),
+                Err(error) => access_error_response(error),
+            },
+            IpcRequest::CreateAgent { name, agent } => {
+                let agent = match AgentNode::try_from(agent) {
+                    Ok(agent) => agent,
+                    Err(errors) => return invalid_validation_response(errors),
+                };
+                if let Some(blocked) = Self::agent_create_blockers(core, &name, &agent).await {
+                    return blocked;
+                }
+                match access.create_agent(name, agent).await {
+                    Ok(agent) => IpcResponse::success(agent),
+                    Err(error) => access_error_response(error),
+                }
+            }
+            IpcRequest::CreateSkill { skill } => match access.create_skill(skill).await {
+                Ok(()) => IpcResponse::success(OkResponse { ok: true }),
+                Err(error) => access_error_response(error),
+            },
+            IpcRequest::DeleteAgent { id } => match access.delete_agent(&id).await {
+                Ok(()) => IpcResponse::success(OkResponse { ok: true }),
+                Err(error) => access_error_response(error),
+            },
+            IpcRequest::DeleteSkill { id } => match access.delete_skill(&id).await {
+                Ok(()) => IpcResponse::success(OkResponse { ok: true }),
+                Err(error) => access_error_response(error),
+            },
+            request => Self::process(core, runtime_tool_registry, request).await,
+        }
+    }
+
+    /// Check that `request` is open to workspace callers and only touches
+    /// resources the workspace owns. Others' resources are reported as not
+    /// found.
+    pub(crate) fn check_workspace_request(
+        access: &WorkspaceAccess,
+        request: &IpcRequest,
+    ) -> Result<(), ErrorPayload> {
+        let checked = match request {
+            IpcRequest::Ping
+            | IpcRequest::GetStatus
+            | IpcRequest::ListAgents
+            | IpcRequest::ListAgentTemplates
+            | IpcRequest::ListSkills
+            | IpcRequest::ListSessions
+            | IpcRequest::ListFullSessions
+            | IpcRequest::CreateAgent { .. }
+            | IpcRequest::CreateSkill { .. } => Ok(()),
+            IpcRequest::GetAgent { id }
+            | IpcRequest::UpdateAgent { id, .. }
+            | IpcRequest::DeleteAgent { id }
+            | IpcRequest::ListSessionsByAgent { agent_id: id } => access.agent(id).map(drop),
+            IpcRequest::GetSkill { id }
+            | IpcRequest::UpdateSkill { id, .. }
+            | IpcRequest::DeleteSkill { id }
+            | IpcRequest::GetSkillReference { skill_id: id, .. } => access.skill(id),
+            IpcRequest::CreateSession {
+                agent_id, skill_id, ..
+            } => access
+                .required_agent(agent_id.as_deref())
+                .and_then(|_| skill_id.as_deref().map_or(Ok(()), |id| access.skill(id))),
+            IpcRequest::GetSession { id }
+            | IpcRequest::UpdateSession { id, .. }
+            | IpcRequest::RenameSession { id, .. }
+            | IpcRequest::ArchiveSession { id }
+            | IpcRequest::DeleteSession { id }
+            | IpcRequest::AddMessage { session_id: id, .. }
+            | IpcRequest::AppendMessage { session_id: id, .. }
+            | IpcRequest::ExecuteChatSession { session_id: id, .. }
+            | IpcRequest::ExecuteChatSessionStream { session_id: id, .. }
+            | IpcRequest::SteerChatSessionStream { session_id: id, .. }
+            | IpcRequest::GetSessionMessages { session_id: id, .. }
+            | IpcRequest::ListMemoryBySession { session_id: id } => access.session(id).map(drop),
+            IpcRequest::SearchMemory { agent_id, .. }
+            | IpcRequest::ListMemory { agent_id, .. }
+            | IpcRequest::AddMemory { agent_id, .. }
+            | IpcRequest::ClearMemory { agent_id }
+            | IpcRequest::GetMemoryStats { agent_id } => {
+                access.required_agent(agent_id.as_deref()).map(drop)
+            }
+            _ => {
+                return Err(ErrorPayload::new(
+                    403,
+                    "This request is not available to workspace-scoped tokens",
+                    None,
+                ));
+            }
+        };
+        checked.map_err(access_error_payload)
+    }
+}
@@ -246,7 +246,7 @@ pub(super) async fn execute_chat_session(
     session_id: String,
     user_input: Option<String>,
     turn_id: String,
-    ack_frame_tx: Option<mpsc::UnboundedSender<StreamFrame>>,
+    ack_frame_tx: Option<StreamSender>,
     emitter: Option<Box<dyn StreamEmitter>>,
     steer_rx: Option<mpsc::Receiver<SteerMessage>>,
 ) -> std::result::Result<ChatSession, ExecuteChatSessionError> {
@@ -489,9 +489,8 @@ pub(crate) async fn build_auth_manager(core: &Arc<AppCore>) -> Result<AuthProfil
         auto_discover: false,
         ..AuthManagerConfig::default()
     };
-    let db = core.storage.get_db();
     let secrets = Arc::new(core.storage.secrets.clone());
-    let profile_storage = AuthProfileStorage::new(db)?;
+    let profile_storage = AuthProfileStorage::with_backend(core.storage.backend())?;
     let manager = AuthProfileManager::with_storage(config, secrets, Some(profile_storage));
     manager.initialize().await?;
     Ok(manager)
@@ -505,6 +504,7 @@ pub(super) fn parse_background_agent_status(status: &str) -> Result<BackgroundAg
         "completed" => Ok(BackgroundAgentStatus::Completed),
         "failed" => Ok(BackgroundAgentStatus::Failed),
         "interrupted" => Ok(BackgroundAgentStatus::Interrupted),
+        "dead_letter" => Ok(BackgroundAgentStatus::DeadLetter),
         _ => Err(anyhow::anyhow!(
             "Unknown background agent status: {}",
             status
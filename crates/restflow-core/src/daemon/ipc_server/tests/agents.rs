@@ -3,13 +3,14 @@ use crate::daemon::request_mapper::to_contract;
 use crate::models::{ApiKeyConfig, ModelId};
 use restflow_contracts::request::{AgentNode as ContractAgentNode, WireModelRef};
 use restflow_contracts::{
-    ApprovalHandledResponse, CleanupReportResponse, DeleteWithIdResponse, PairingApprovalResponse,
-    PairingStateResponse, RouteBindingResponse, SessionSourceMigrationResponse,
+    ApiTokenResponse, ApprovalHandledResponse, ArtifactPurgeResponse, CleanupReportResponse,
+    DatabaseBackupResponse, DeleteWithIdResponse, IssuedApiTokenResponse, PairingApprovalResponse,
+    PairingStateResponse, RouteBindingResponse, SessionSourceMigrationResponse, UserResponse,
 };
 use restflow_storage::SimpleStorage;
 
 fn raw_agent_storage(core: &Arc<AppCore>) -> restflow_storage::AgentStorage {
-    restflow_storage::AgentStorage::new(core.storage.get_db()).unwrap()
+    restflow_storage::AgentStorage::with_backend(core.storage.backend()).unwrap()
 }
 
 fn ensure_test_agent_with_id(core: &Arc<AppCore>, id: &str) {
@@ -53,6 +54,7 @@ fn background_agent_spec(name: &str) -> crate::models::BackgroundAgentSpec {
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     }
 }
 
@@ -73,7 +75,7 @@ fn configure_default_agent(core: &Arc<AppCore>) -> String {
 }
 
 fn raw_background_agent_storage(core: &Arc<AppCore>) -> restflow_storage::BackgroundAgentStorage {
-    restflow_storage::BackgroundAgentStorage::new(core.storage.get_db()).unwrap()
+    restflow_storage::BackgroundAgentStorage::with_backend(core.storage.backend()).unwrap()
 }
 
 fn insert_background_agent_with_id(
@@ -236,6 +238,72 @@ async fn process_handle_background_agent_approval_returns_typed_response() {
     }
 }
 
+#[tokio::test]
+async fn process_resolve_tool_approval_resumes_background_task() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+    ensure_test_agent_with_id(&core, "agent-1");
+
+    let task = core
+        .storage
+        .background_agents
+        .create_background_agent(background_agent_spec("ipc-tool-approval"))
+        .unwrap();
+    let approval = crate::models::PendingApproval::new("bash", task.id.clone(), "agent-1", 300)
+        .with_tool_call("bash", serde_json::json!({ "command": "rm a.txt" }));
+    core.storage.tool_approvals.save(&approval).unwrap();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ResolveToolApproval {
+            id: approval.id.clone(),
+            approved: true,
+            reason: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            let resolved: crate::models::PendingApproval =
+                serde_json::from_value(value).expect("approval response");
+            assert_eq!(resolved.status, crate::models::ApprovalStatus::Approved);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let messages = core
+        .storage
+        .background_agents
+        .list_background_agent_messages(&task.id, 10)
+        .unwrap();
+    let expected = format!("approval {} approved", approval.id);
+    assert!(messages.iter().any(|message| message.message == expected));
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ListToolApprovals { pending_only: true },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => assert_eq!(value, serde_json::json!([])),
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ResolveToolApproval {
+            id: approval.id.clone(),
+            approved: false,
+            reason: None,
+        },
+    )
+    .await;
+    assert!(matches!(response, IpcResponse::Error(error) if error.code == 409));
+}
+
 #[tokio::test]
 async fn process_get_background_agent_returns_not_found_for_missing_task() {
     let (core, _temp) = create_test_core().await;
@@ -517,6 +585,126 @@ async fn process_control_background_agent_resolves_unique_prefix() {
         other => panic!("expected success response, got {other:?}"),
     }
 }
+
+#[tokio::test]
+async fn process_run_task_with_secrets_stages_secrets_for_next_run() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+    let task = insert_background_agent_with_id(&core, "secret-run-1");
+
+    let rejected = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::RunTaskWithSecrets {
+            id: task.id.clone(),
+            secrets: HashMap::from([("bad name".to_string(), "x".to_string())]),
+        },
+    )
+    .await;
+    match rejected {
+        IpcResponse::Error(error) => assert_eq!(error.code, 400),
+        other => panic!("expected error response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::RunTaskWithSecrets {
+            id: "secret-run".to_string(),
+            secrets: HashMap::from([("DEPLOY_TOKEN".to_string(), "abc".to_string())]),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            let updated: crate::models::BackgroundAgent =
+                serde_json::from_value(value).expect("background agent");
+            assert_eq!(updated.id, task.id);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let secrets = core.storage.background_agents.temporary_secrets();
+    assert_eq!(secrets.activate(&task.id, "run-1"), vec!["DEPLOY_TOKEN"]);
+    assert_eq!(
+        secrets.resolve(&task.id, "DEPLOY_TOKEN").as_deref(),
+        Some("abc")
+    );
+}
+
+#[tokio::test]
+async fn process_set_task_wake_condition_requires_paused_task() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+    let task = insert_background_agent_with_id(&core, "wake-task-1");
+    let condition = restflow_contracts::request::WakeCondition::WorkItemStatus {
+        item_id: "item-1".to_string(),
+        status: "done".to_string(),
+    };
+
+    let rejected = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::SetTaskWakeCondition {
+            id: task.id.clone(),
+            condition: Some(condition.clone()),
+            checkpoint_id: None,
+        },
+    )
+    .await;
+    match rejected {
+        IpcResponse::Error(error) => assert_eq!(error.code, 400),
+        other => panic!("expected error response, got {other:?}"),
+    }
+
+    core.storage.background_agents.pause_task(&task.id).unwrap();
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::SetTaskWakeCondition {
+            id: "wake-task".to_string(),
+            condition: Some(condition),
+            checkpoint_id: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            let updated: crate::models::BackgroundAgent =
+                serde_json::from_value(value).expect("background agent");
+            let wake = updated.wake.expect("wake condition");
+            assert_eq!(
+                wake.condition,
+                crate::models::WakeCondition::WorkItemStatus {
+                    item_id: "item-1".to_string(),
+                    status: crate::models::ItemStatus::Done,
+                }
+            );
+            assert!(wake.fired.is_none());
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let cleared = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::SetTaskWakeCondition {
+            id: task.id.clone(),
+            condition: None,
+            checkpoint_id: None,
+        },
+    )
+    .await;
+    assert!(matches!(cleared, IpcResponse::Success(_)));
+    let stored = core
+        .storage
+        .background_agents
+        .get_task(&task.id)
+        .unwrap()
+        .unwrap();
+    assert!(stored.wake.is_none());
+}
+
 #[tokio::test]
 async fn process_list_auth_profiles_returns_empty_by_default() {
     let (core, _temp) = create_test_core().await;
@@ -592,12 +780,125 @@ async fn process_list_pairing_state_returns_empty_by_default() {
     }
 }
 
+#[tokio::test]
+async fn process_api_token_create_list_and_revoke() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::CreateApiToken {
+            name: "ci".to_string(),
+            scope: restflow_contracts::request::ApiTokenScope {
+                allowed_tools: Some(vec!["list_skills".to_string()]),
+                ..Default::default()
+            },
+            expires_at: None,
+        },
+    )
+    .await;
+    let issued: IssuedApiTokenResponse = match response {
+        IpcResponse::Success(value) => serde_json::from_value(value).expect("issued token"),
+        other => panic!("expected success response, got {other:?}"),
+    };
+    assert!(issued.secret.starts_with(&issued.token.token_prefix));
+    assert_eq!(
+        issued.token.scope.allowed_tools,
+        Some(vec!["list_skills".to_string()])
+    );
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::RevokeApiToken {
+            id: issued.token.id.clone(),
+        },
+    )
+    .await;
+    assert!(matches!(response, IpcResponse::Success(_)));
+
+    for (include_revoked, expected) in [(false, 0), (true, 1)] {
+        let response = IpcServer::process(
+            &core,
+            &runtime_tool_registry,
+            IpcRequest::ListApiTokens { include_revoked },
+        )
+        .await;
+        match response {
+            IpcResponse::Success(value) => {
+                let tokens: Vec<ApiTokenResponse> =
+                    serde_json::from_value(value).expect("api tokens");
+                assert_eq!(tokens.len(), expected);
+            }
+            other => panic!("expected success response, got {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn process_user_create_list_and_delete() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::CreateUser {
+            name: "alice".to_string(),
+        },
+    )
+    .await;
+    let user: UserResponse = match response {
+        IpcResponse::Success(value) => serde_json::from_value(value).expect("user"),
+        other => panic!("expected success response, got {other:?}"),
+    };
+    assert_eq!(user.name, "alice");
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::CreateApiToken {
+            name: "alice".to_string(),
+            scope: restflow_contracts::request::ApiTokenScope {
+                user_id: Some("missing-user".to_string()),
+                ..Default::default()
+            },
+            expires_at: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Error(error) => assert_eq!(error.code, 400),
+        other => panic!("expected error response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::DeleteUser {
+            id: user.id.clone(),
+        },
+    )
+    .await;
+    assert!(matches!(response, IpcResponse::Success(_)));
+
+    let response = IpcServer::process(&core, &runtime_tool_registry, IpcRequest::ListUsers).await;
+    match response {
+        IpcResponse::Success(value) => {
+            let users: Vec<UserResponse> = serde_json::from_value(value).expect("users");
+            assert!(users.is_empty());
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn process_approve_pairing_auto_binds_owner_chat_id() {
     let (core, _temp) = create_test_core().await;
     let runtime_tool_registry = OnceLock::new();
     let pairing_storage =
-        Arc::new(crate::storage::PairingStorage::new(core.storage.get_db()).unwrap());
+        Arc::new(crate::storage::PairingStorage::with_backend(core.storage.backend()).unwrap());
     let manager = crate::channel::PairingManager::new(pairing_storage);
     let code = manager
         .create_request("peer-1", Some("Peer 1"), "chat-100")
@@ -659,7 +960,8 @@ async fn process_bind_route_preserves_legacy_group_binding() {
         other => panic!("expected success response, got {other:?}"),
     }
 
-    let storage = Arc::new(crate::storage::PairingStorage::new(core.storage.get_db()).unwrap());
+    let storage =
+        Arc::new(crate::storage::PairingStorage::with_backend(core.storage.backend()).unwrap());
     let resolver = crate::channel::RouteResolver::new(storage);
     let resolved = resolver.resolve_route(
         crate::channel::ChannelType::Telegram,
@@ -712,6 +1014,123 @@ async fn process_run_cleanup_returns_report() {
     }
 }
 
+#[tokio::test]
+async fn process_purge_artifacts_dry_run_returns_report() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::PurgeArtifacts { dry_run: true },
+    )
+    .await;
+
+    match response {
+        IpcResponse::Success(value) => {
+            let report: ArtifactPurgeResponse =
+                serde_json::from_value(value).expect("purge report");
+            assert!(report.dry_run);
+            assert!(report.removed.len() <= report.scanned);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn process_backup_and_restore_database_round_trips() {
+    let (core, temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+    let kept = crate::models::ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+    core.storage
+        .chat_sessions
+        .create(&kept)
+        .expect("create session");
+    let path = temp
+        .path()
+        .join("backup.redb")
+        .to_string_lossy()
+        .to_string();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::BackupDatabase { path: path.clone() },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            let backup: DatabaseBackupResponse =
+                serde_json::from_value(value).expect("backup response");
+            assert_eq!(backup.path, path);
+            assert!(backup.entries > 0);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let later = crate::models::ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+    core.storage
+        .chat_sessions
+        .create(&later)
+        .expect("create session");
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::RestoreDatabase { path },
+    )
+    .await;
+    assert!(matches!(response, IpcResponse::Success(_)));
+    assert!(core.storage.chat_sessions.exists(&kept.id).unwrap());
+    assert!(!core.storage.chat_sessions.exists(&later.id).unwrap());
+}
+
+#[tokio::test]
+async fn process_export_and_import_bundle_round_trips() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+    let session = crate::models::ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+    core.storage
+        .chat_sessions
+        .create(&session)
+        .expect("create session");
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ExportBundle {
+            secrets_password: None,
+        },
+    )
+    .await;
+    let bundle = match response {
+        IpcResponse::Success(value) => value,
+        other => panic!("expected success response, got {other:?}"),
+    };
+    assert_eq!(bundle["format"], "restflow-bundle");
+
+    let (target, _target_temp) = create_test_core().await;
+    let response = IpcServer::process(
+        &target,
+        &runtime_tool_registry,
+        IpcRequest::ImportBundle {
+            bundle,
+            secrets_password: None,
+            overwrite: false,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            let report: crate::services::data_bundle::BundleImportReport =
+                serde_json::from_value(value).expect("import report");
+            assert!(report.chat_sessions >= 1);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+    assert!(target.storage.chat_sessions.exists(&session.id).unwrap());
+}
+
 #[tokio::test]
 async fn process_migrate_session_sources_dry_run_reports_stats_without_writing() {
     let (core, _temp) = create_test_core().await;
@@ -845,6 +1264,8 @@ async fn process_create_agent_returns_stored_agent() {
                 skill_variables: None,
                 skill_preflight_policy_mode: None,
                 model_routing: None,
+                dry_run: None,
+                execution_strategy: None,
             })
             .expect("contract agent node"),
         },
@@ -860,6 +1281,51 @@ async fn process_create_agent_returns_stored_agent() {
     }
 }
 
+#[tokio::test]
+async fn process_list_agent_templates_returns_builtin_templates() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ListAgentTemplates,
+    )
+    .await;
+
+    match response {
+        IpcResponse::Success(value) => {
+            let ids: Vec<_> = value
+                .as_array()
+                .expect("template list")
+                .iter()
+                .filter_map(|template| template["id"].as_str())
+                .collect();
+            assert!(ids.contains(&"research-assistant"));
+            assert!(ids.contains(&"price-monitor"));
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn process_provision_unknown_agent_template_returns_not_found() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ProvisionAgentTemplate {
+            template_id: "missing".to_string(),
+            name: None,
+        },
+    )
+    .await;
+
+    assert!(matches!(response, IpcResponse::Error(error) if error.code == 404));
+}
+
 #[tokio::test]
 async fn process_create_agent_with_warning_persists_without_confirmation() {
     let (core, _temp) = create_test_core().await;
@@ -974,6 +1440,7 @@ async fn process_create_background_agent_persists_when_agent_provider_missing()
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("contract spec"),
         },
@@ -1050,3 +1517,88 @@ async fn process_get_config_returns_system_config() {
         other => panic!("expected success response, got {other:?}"),
     }
 }
+
+#[tokio::test]
+async fn process_in_workspace_records_and_enforces_ownership() {
+    use crate::models::WorkspaceResourceKind;
+    use crate::services::workspace::{WorkspaceAccess, WorkspaceService};
+
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+    let workspaces = WorkspaceService::from_storage(&core.storage);
+    let alice = workspaces.create_user("alice").unwrap();
+    let other = core
+        .storage
+        .agents
+        .create_agent("someone-else".to_string(), AgentNode::new())
+        .unwrap();
+    let mut access = WorkspaceAccess::for_user(core.clone(), &alice.id).unwrap();
+
+    let created = match IpcServer::process_in_workspace(
+        &core,
+        &runtime_tool_registry,
+        &mut access,
+        IpcRequest::CreateAgent {
+            name: "alice-agent".to_string(),
+            agent: to_contract(AgentNode::new()).expect("contract agent"),
+        },
+    )
+    .await
+    {
+        IpcResponse::Success(value) => value["id"].as_str().unwrap().to_string(),
+        other => panic!("expected success response, got {other:?}"),
+    };
+    assert_eq!(
+        workspaces
+            .owner_of(WorkspaceResourceKind::Agent, &created)
+            .unwrap(),
+        Some(alice.id.clone())
+    );
+
+    match IpcServer::process_in_workspace(
+        &core,
+        &runtime_tool_registry,
+        &mut access,
+        IpcRequest::ListAgents,
+    )
+    .await
+    {
+        IpcResponse::Success(serde_json::Value::Array(agents)) => {
+            let ids: Vec<&str> = agents
+                .iter()
+                .map(|agent| agent["id"].as_str().unwrap())
+                .collect();
+            assert_eq!(ids, vec![created.as_str()]);
+        }
+        other => panic!("expected agent list, got {other:?}"),
+    }
+
+    let response = IpcServer::process_in_workspace(
+        &core,
+        &runtime_tool_registry,
+        &mut access,
+        IpcRequest::DeleteAgent {
+            id: other.id.clone(),
+        },
+    )
+    .await;
+    assert!(matches!(response, IpcResponse::Error(error) if error.code == 404));
+    assert!(core.storage.agents.get_agent(other.id).unwrap().is_some());
+
+    let response = IpcServer::process_in_workspace(
+        &core,
+        &runtime_tool_registry,
+        &mut access,
+        IpcRequest::DeleteAgent {
+            id: created.clone(),
+        },
+    )
+    .await;
+    assert!(matches!(response, IpcResponse::Success(_)));
+    assert_eq!(
+        workspaces
+            .owner_of(WorkspaceResourceKind::Agent, &created)
+            .unwrap(),
+        None
+    );
+}
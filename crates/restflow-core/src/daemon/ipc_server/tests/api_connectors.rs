@@ -0,0 +1,93 @@
+use super::*;
+
+const PETSTORE_SPEC: &str = r#"
+openapi: 3.0.0
+info:
+  title: Petstore
+servers:
+  - url: https://petstore.example.com/v1
+paths:
+  /pets:
+    get:
+      operationId: listPets
+"#;
+
+#[tokio::test]
+async fn process_import_api_connector_round_trip() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ImportApiConnector {
+            name: "petstore".to_string(),
+            spec: PETSTORE_SPEC.to_string(),
+            base_url: None,
+            auth_secret: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            assert_eq!(value["base_url"], "https://petstore.example.com/v1");
+            assert_eq!(value["operations"][0]["id"], "list_pets");
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let response =
+        IpcServer::process(&core, &runtime_tool_registry, IpcRequest::ListApiConnectors).await;
+    match response {
+        IpcResponse::Success(value) => assert_eq!(value.as_array().map(Vec::len), Some(1)),
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::DeleteApiConnector {
+            name: "petstore".to_string(),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => assert_eq!(value["deleted"], true),
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn process_api_connector_requests_reject_invalid_and_missing_connectors() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ImportApiConnector {
+            name: "broken".to_string(),
+            spec: "{\"paths\": {}}".to_string(),
+            base_url: None,
+            auth_secret: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Error(error) => assert_eq!(error.code, 400),
+        other => panic!("expected error response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::GetApiConnector {
+            name: "missing".to_string(),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Error(error) => assert_eq!(error.code, 404),
+        other => panic!("expected error response, got {other:?}"),
+    }
+}
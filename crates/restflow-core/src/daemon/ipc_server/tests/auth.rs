@@ -0,0 +1,48 @@
+use super::*;
+use restflow_contracts::request::{Credential, OAuthClientConfig};
+
+#[tokio::test]
+async fn process_add_oauth_profile_keeps_client_config() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::AddOAuthProfile {
+            name: "GitHub".to_string(),
+            credential: Credential::OAuth {
+                access_token: "gho_access".to_string(),
+                refresh_token: None,
+                expires_at: None,
+                email: None,
+            },
+            provider: "other".to_string(),
+            oauth: OAuthClientConfig {
+                client_id: "client-1".to_string(),
+                client_secret_ref: None,
+                authorization_url: "https://github.com/login/oauth/authorize".to_string(),
+                token_url: "https://github.com/login/oauth/access_token".to_string(),
+                device_authorization_url: None,
+                scopes: vec!["repo".to_string()],
+            },
+            client_secret: Some("client-secret".to_string()),
+        },
+    )
+    .await;
+    let id = match response {
+        IpcResponse::Success(value) => {
+            assert_eq!(value["oauth"]["client_id"], "client-1");
+            assert_eq!(value["credential"]["type"], "o_auth");
+            value["id"].as_str().unwrap().to_string()
+        }
+        other => panic!("expected success response, got {other:?}"),
+    };
+
+    let secret = core
+        .storage
+        .secrets
+        .get_secret(&crate::auth::secret_key(&id, "client_secret"))
+        .unwrap();
+    assert_eq!(secret.as_deref(), Some("client-secret"));
+}
@@ -0,0 +1,122 @@
+use super::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn process_save_browser_plan_keeps_versions() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    for url in ["https://a.test", "https://b.test"] {
+        let response = IpcServer::process(
+            &core,
+            &runtime_tool_registry,
+            IpcRequest::SaveBrowserPlan {
+                name: "prices".to_string(),
+                description: None,
+                actions: json!([{ "type": "navigate", "url": url }]),
+                timeout_secs: None,
+            },
+        )
+        .await;
+        assert!(matches!(response, IpcResponse::Success(_)));
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::GetBrowserPlan {
+            name: "prices".to_string(),
+            version: Some(1),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            assert_eq!(value["version"], 1);
+            assert_eq!(value["plan"]["actions"][0]["url"], "https://a.test");
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ListBrowserPlanVersions {
+            name: "prices".to_string(),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => assert_eq!(value.as_array().map(Vec::len), Some(2)),
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::DeleteBrowserPlan {
+            name: "prices".to_string(),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => assert_eq!(value["deleted"], true),
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn process_browser_plan_requests_reject_invalid_and_missing_plans() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::SaveBrowserPlan {
+            name: "bad".to_string(),
+            description: None,
+            actions: json!({ "type": "navigate" }),
+            timeout_secs: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Error(error) => assert_eq!(error.code, 400),
+        other => panic!("expected error response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::RunBrowserPlan {
+            name: "missing".to_string(),
+            version: None,
+            payload: None,
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Error(error) => assert!(error.message.contains("not found")),
+        other => panic!("expected error response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn process_browser_doctor_returns_probe_with_remediation() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let response =
+        IpcServer::process(&core, &runtime_tool_registry, IpcRequest::BrowserDoctor).await;
+    match response {
+        IpcResponse::Success(value) => {
+            let ready = value["probe"]["ready"].as_bool().unwrap();
+            let remediation = value["remediation"].as_array().unwrap();
+            if !ready {
+                assert!(!remediation.is_empty());
+            }
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
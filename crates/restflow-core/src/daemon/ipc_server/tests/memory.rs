@@ -24,6 +24,8 @@ async fn process_search_memory_returns_matching_chunk() {
                 skill_variables: None,
                 skill_preflight_policy_mode: None,
                 model_routing: None,
+                dry_run: None,
+                execution_strategy: None,
             },
         )
         .unwrap();
@@ -19,6 +19,9 @@ pub(super) async fn create_test_core() -> (Arc<AppCore>, tempfile::TempDir) {
 }
 
 mod agents;
+mod api_connectors;
+mod auth;
+mod browser_plans;
 mod memory;
 mod runtime_tools;
 mod sessions;
@@ -202,6 +202,36 @@ async fn get_execution_run_thread_returns_existing_run_thread() {
     }
 }
 
+#[tokio::test]
+async fn get_related_entities_returns_session_neighborhood() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+    core.storage.chat_sessions.create(&session).unwrap();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::GetRelatedEntities {
+            entity_id: "agent-1".to_string(),
+        },
+    )
+    .await;
+
+    match response {
+        IpcResponse::Success(value) => {
+            let related: crate::RelatedEntities =
+                serde_json::from_value(value).expect("related entities");
+            assert_eq!(related.kind, Some(crate::EntityKind::Agent));
+            assert_eq!(related.incoming.len(), 1);
+            assert_eq!(related.incoming[0].source.id, session.id);
+            assert_eq!(related.incoming[0].relation, crate::RelationKind::SpawnedBy);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn list_child_execution_sessions_returns_bad_request_for_blank_parent_run_id() {
     let (core, _temp) = create_test_core().await;
@@ -323,6 +353,110 @@ async fn get_execution_trace_stats_filters_by_run_id() {
     }
 }
 
+#[tokio::test]
+async fn query_execution_trace_stats_filters_by_category_and_time() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+    let session_id = session.id.clone();
+    core.storage.chat_sessions.create(&session).unwrap();
+    store_run_events(&core.storage, "task-1", &session_id, "run-1", None);
+    let mut decision = crate::models::execution_trace_builders::security_decision(
+        "task-1",
+        "agent-1",
+        crate::models::SecurityDecisionTrace {
+            tool_name: "shell".to_string(),
+            operation: "execute".to_string(),
+            target: "rm -rf /".to_string(),
+            allowed: false,
+            requires_approval: false,
+            approval_id: None,
+            reason: Some("blocked".to_string()),
+        },
+    );
+    decision.timestamp = 1_000;
+    core.storage.execution_traces.store(&decision).unwrap();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::QueryExecutionTraceStats {
+            query: crate::ExecutionTraceQuery {
+                category: Some(ExecutionTraceCategory::SecurityDecision),
+                to_timestamp: Some(2_000),
+                ..crate::ExecutionTraceQuery::default()
+            },
+        },
+    )
+    .await;
+
+    match response {
+        IpcResponse::Success(value) => {
+            let stats: crate::ExecutionTraceStats =
+                serde_json::from_value(value).expect("execution trace stats");
+            assert_eq!(stats.total_events, 1);
+            assert_eq!(stats.security_decision_count, 1);
+            assert_eq!(stats.lifecycle_count, 0);
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn get_execution_replay_resolves_run_id_and_reports_missing_steps() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    let mut state = restflow_ai::AgentState::new("exec-1".to_string(), 10);
+    state.add_message(restflow_ai::Message::user("hello"));
+    state.complete("hi");
+    let snapshot = crate::models::ReplaySnapshot::new(
+        "exec-1".to_string(),
+        0,
+        state.iteration,
+        serde_json::to_vec(&state).unwrap(),
+    )
+    .with_agent_id(Some("agent-1".to_string()))
+    .with_run_id(Some("run-1".to_string()));
+    core.storage
+        .checkpoints
+        .save_replay_snapshot(&snapshot)
+        .unwrap();
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::GetExecutionReplay {
+            id: "run-1".to_string(),
+        },
+    )
+    .await;
+    match response {
+        IpcResponse::Success(value) => {
+            let replay: crate::models::ExecutionReplay =
+                serde_json::from_value(value).expect("execution replay");
+            assert_eq!(replay.execution_id, "exec-1");
+            assert_eq!(replay.steps.len(), 1);
+            assert_eq!(replay.steps[0].status, "completed");
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::ReplayExecution {
+            execution_id: "exec-1".to_string(),
+            step: 5,
+            agent_id: None,
+            message: None,
+        },
+    )
+    .await;
+    assert_execution_thread_error(response, 404, "Replay step not found");
+}
+
 #[tokio::test]
 async fn get_execution_trace_stats_rejects_legacy_task_id_filter() {
     let (core, _temp) = create_test_core().await;
@@ -677,6 +811,7 @@ async fn delete_session_rejects_background_bound_workspace_session() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -723,6 +858,7 @@ async fn archive_session_rejects_background_bound_workspace_session() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -919,7 +1055,7 @@ async fn steer_chat_stream_returns_false_when_no_active_session_stream() {
 #[tokio::test]
 async fn session_reply_sender_buffers_message_and_emits_ack_frame() {
     let buffer = Arc::new(Mutex::new(VecDeque::new()));
-    let (tx, mut rx) = mpsc::unbounded_channel::<StreamFrame>();
+    let (tx, mut rx) = stream_channel(DEFAULT_STREAM_BUFFER_CAPACITY);
     let sender = SessionReplySender::new(buffer.clone(), Some(tx));
     ReplySender::send(&sender, "Working on it".to_string())
         .await
@@ -939,7 +1075,7 @@ async fn session_reply_sender_buffers_message_and_emits_ack_frame() {
 #[tokio::test]
 async fn session_reply_sender_ignores_blank_messages() {
     let buffer = Arc::new(Mutex::new(VecDeque::new()));
-    let (tx, mut rx) = mpsc::unbounded_channel::<StreamFrame>();
+    let (tx, mut rx) = stream_channel(DEFAULT_STREAM_BUFFER_CAPACITY);
     let sender = SessionReplySender::new(buffer.clone(), Some(tx));
     ReplySender::send(&sender, "   ".to_string()).await.unwrap();
 
@@ -947,7 +1083,7 @@ async fn session_reply_sender_ignores_blank_messages() {
     assert!(guard.is_empty());
     drop(guard);
 
-    assert!(rx.try_recv().is_err());
+    assert!(rx.try_recv().is_none());
 }
 
 #[tokio::test]
@@ -1072,7 +1208,8 @@ async fn add_message_returns_bad_request_for_invalid_role_payload() {
 async fn execute_chat_session_returns_internal_error_for_malformed_session_payload() {
     let (core, _temp) = create_test_core().await;
     let runtime_tool_registry = OnceLock::new();
-    let raw_storage = restflow_storage::ChatSessionStorage::new(core.storage.get_db()).unwrap();
+    let raw_storage =
+        restflow_storage::ChatSessionStorage::with_backend(core.storage.backend()).unwrap();
 
     raw_storage.put_raw("bad-session", b"{bad-json").unwrap();
 
@@ -1095,3 +1232,60 @@ async fn execute_chat_session_returns_internal_error_for_malformed_session_paylo
         other => panic!("expected error response, got {other:?}"),
     }
 }
+
+#[tokio::test]
+async fn get_usage_stats_filters_by_agent() {
+    let (core, _temp) = create_test_core().await;
+    let runtime_tool_registry = OnceLock::new();
+
+    for (id, agent_id) in [
+        ("call-1", "agent-1"),
+        ("call-2", "agent-1"),
+        ("call-3", "agent-2"),
+    ] {
+        core.storage
+            .usage
+            .record(&crate::models::UsageRecord {
+                id: id.to_string(),
+                timestamp: 1_000,
+                agent_id: agent_id.to_string(),
+                session_id: Some(format!("{agent_id}-session")),
+                task_id: "task-1".to_string(),
+                run_id: None,
+                provider: None,
+                model: "gpt-5".to_string(),
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                cost_usd: Some(0.5),
+                cache_read_tokens: 0,
+                cache_write_tokens: 0,
+                cache_savings_usd: None,
+            })
+            .unwrap();
+    }
+
+    let response = IpcServer::process(
+        &core,
+        &runtime_tool_registry,
+        IpcRequest::GetUsageStats {
+            query: restflow_contracts::request::UsageQuery {
+                agent_id: Some("agent-1".to_string()),
+                ..Default::default()
+            },
+        },
+    )
+    .await;
+
+    match response {
+        IpcResponse::Success(value) => {
+            let stats: crate::models::UsageStats =
+                serde_json::from_value(value).expect("usage stats");
+            assert_eq!(stats.totals.call_count, 2);
+            assert_eq!(stats.totals.total_tokens, 30);
+            assert_eq!(stats.totals.cost_usd, 1.0);
+            assert_eq!(stats.by_agent.len(), 1);
+            assert_eq!(stats.by_session[0].key, "agent-1-session");
+        }
+        other => panic!("expected success response, got {other:?}"),
+    }
+}
@@ -1,25 +1,36 @@
 use crate::AppCore;
 use crate::daemon::{IpcRequest, IpcResponse, IpcServer, StreamFrame};
 use crate::mcp::RestFlowMcpServer;
-use crate::models::storage_mode::StorageMode;
 use crate::models::{
-    BackgroundAgentConversionResult, GatingCheckResult, Skill, SkillManifest, SkillVersion,
+    ApiTokenRole, AuthConfig, BackgroundAgentControlAction, BackgroundAgentConversionResult,
+    GatingCheckResult, ResultCallback, Skill, SkillManifest, SkillVersion, TaskMessageSource,
+    TriggerConfig, WakeCondition, WakeEvent, WebhookRateLimiter, WebhookRequest, WebhookResponse,
 };
 use crate::registry::{
     GatingChecker, GitHubProvider, MarketplaceProvider, SkillProvider as _, SkillSearchQuery,
     SkillSearchResult, SkillSortOrder,
 };
 use crate::runtime::channel::transcribe_media_file;
+use crate::services::adapters::MarketplaceStoreAdapter;
+use crate::services::api_tokens::{API_TOKEN_SECRET_PREFIX, ApiTokenError};
 use crate::services::background_agent_command::{TaskCommandService, TaskExecutionMode};
+use crate::services::browser_webhook::{
+    BrowserWebhookOutcome, browser_service_for_settings, render_plan_actions, run_browser_plan,
+};
 use crate::services::operation_assessment::OperationAssessorAdapter;
+use crate::services::workspace::WorkspaceAccess;
 use anyhow::Result;
 use axum::Json;
 use axum::Router;
 use axum::body::Body;
-use axum::extract::{OriginalUri, State};
-use axum::http::{HeaderValue, StatusCode, header::CONTENT_TYPE};
+use axum::extract::{ConnectInfo, Extension, OriginalUri, Path as UrlPath, Query, Request, State};
+use axum::http::{
+    HeaderMap, HeaderValue, Method, StatusCode,
+    header::{AUTHORIZATION, CONTENT_TYPE},
+};
+use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, post, post_service};
+use axum::routing::{any, get, post, post_service};
 use base64::Engine as _;
 use bytes::Bytes;
 use futures::StreamExt;
@@ -29,30 +40,46 @@ use http::{
 };
 use http_body_util::{BodyExt, Full, combinators::BoxBody};
 use restflow_contracts::ErrorPayload;
+use restflow_traits::network::resolve_and_validate_url;
+use restflow_traits::store::MarketplaceStore as _;
 use rmcp::transport::streamable_http_server::{
     StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::path::{Component, Path, PathBuf};
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
+use subtle::ConstantTimeEq;
 use tokio::net::TcpListener;
-use tokio::sync::{broadcast, mpsc};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
 use tower::util::MapResponseLayer;
 use tracing::{info, warn};
 
+mod api_auth;
+mod audit;
+mod openai_compat;
+mod sse;
+mod workspace_scope;
+
+use api_auth::ApiCaller;
+
 use super::ipc_protocol::IpcDaemonStatus;
+use super::stream_buffer::{
+    StreamBackpressureTotals, StreamReceiver, stream_backpressure_totals, stream_channel,
+};
 
 const ERROR_CONTENT_TYPE: &str = "application/json; charset=utf-8";
 const RECOVERY_HEADER: &str = "x-restflow-mcp-recover";
 const RECOVERY_REINITIALIZE: &str = "reinitialize";
 const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson; charset=utf-8";
+const SSE_CONTENT_TYPE: &str = "text/event-stream";
 const WEB_DIST_ENV: &str = "RESTFLOW_WEB_DIST_DIR";
+const WEBHOOK_TOKEN_HEADER: &str = "x-webhook-token";
 
 type McpHttpBody = BoxBody<Bytes, Infallible>;
 type McpHttpResponse = HttpResponse<McpHttpBody>;
@@ -64,6 +91,10 @@ struct DaemonHttpState {
     core: Arc<AppCore>,
     runtime_tool_registry: Arc<OnceLock<RuntimeToolRegistry>>,
     web_dist_dir: Option<PathBuf>,
+    webhook_rate_limiter: Arc<Mutex<WebhookRateLimiter>>,
+    /// Per-minute request counts keyed by API token ID.
+    api_token_rate_limiter: Arc<Mutex<WebhookRateLimiter>>,
+    mcp_sse_sessions: sse::SseSessions,
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,6 +119,13 @@ struct SaveVoiceMessageRequest {
     session_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct AgentStreamRequest {
+    input: String,
+    #[serde(default)]
+    session_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ReadMediaFileRequest {
     file_path: String,
@@ -157,12 +195,15 @@ pub async fn run_mcp_http_server(
     let listener = TcpListener::bind(addr).await?;
     info!(%addr, "Daemon HTTP server listening");
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async move {
-            let _ = shutdown.recv().await;
-            cancellation.cancel();
-        })
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        let _ = shutdown.recv().await;
+        cancellation.cancel();
+    })
+    .await?;
 
     Ok(())
 }
@@ -173,7 +214,8 @@ fn build_http_router(
     web_dist_dir: Option<PathBuf>,
 ) -> Router {
     let config = build_streamable_http_server_config(cancellation);
-    let server_factory = build_mcp_server_factory(RestFlowMcpServer::new(core.clone()));
+    let mcp_server = RestFlowMcpServer::new(core.clone());
+    let server_factory = build_mcp_server_factory(mcp_server.clone());
 
     let mcp_service = StreamableHttpService::new(
         server_factory,
@@ -188,17 +230,13 @@ fn build_http_router(
         core,
         runtime_tool_registry: Arc::new(OnceLock::new()),
         web_dist_dir,
+        webhook_rate_limiter: Arc::new(Mutex::new(WebhookRateLimiter::new())),
+        api_token_rate_limiter: Arc::new(Mutex::new(WebhookRateLimiter::new())),
+        mcp_sse_sessions: sse::SseSessions::new(mcp_server),
     };
 
-    Router::new()
-        .route("/api/health", get(api_health))
-        .route("/health", get(api_health))
-        .route("/api/request", post(api_request))
-        .route("/api/stream", post(api_stream))
-        .route(
-            "/api/background-agents/convert-session",
-            post(api_convert_session_to_background_agent),
-        )
+    let read_routes = Router::new()
+        .route("/api/stream/stats", get(api_stream_stats))
         .route("/api/marketplace/search", post(api_marketplace_search))
         .route("/api/marketplace/skill", post(api_marketplace_get_skill))
         .route(
@@ -213,6 +251,23 @@ fn build_http_router(
             "/api/marketplace/gating",
             post(api_marketplace_check_gating),
         )
+        .route(
+            "/api/marketplace/installed",
+            get(api_marketplace_list_installed),
+        )
+        .merge(audit::routes());
+    let voice_routes = Router::new()
+        .route("/api/voice/transcribe", post(api_transcribe_audio))
+        .route("/api/voice/save", post(api_save_voice_message))
+        .route("/api/voice/read", post(api_read_media_file));
+    let execute_routes = Router::new()
+        .route("/api/agents/{agent_id}/stream", post(api_agent_stream))
+        .merge(workspace_scope::without_workspace_tokens(voice_routes));
+    let admin_routes = Router::new()
+        .route(
+            "/api/background-agents/convert-session",
+            post(api_convert_session_to_background_agent),
+        )
         .route(
             "/api/marketplace/install",
             post(api_marketplace_install_skill),
@@ -220,15 +275,40 @@ fn build_http_router(
         .route(
             "/api/marketplace/uninstall",
             post(api_marketplace_uninstall_skill),
-        )
+        );
+    // `/api/request` and `/api/stream` check the role per IPC request.
+    let api_routes = Router::new()
+        .route("/api/request", post(api_request))
+        .route("/api/stream", post(api_stream))
+        .merge(api_auth::with_role(
+            ApiTokenRole::ReadOnly,
+            workspace_scope::without_workspace_tokens(read_routes),
+        ))
+        .merge(api_auth::with_role(ApiTokenRole::Execute, execute_routes))
+        .merge(api_auth::with_role(
+            ApiTokenRole::Admin,
+            workspace_scope::without_workspace_tokens(admin_routes),
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_auth::require_api_caller,
+        ));
+
+    Router::new()
+        .route("/api/health", get(api_health))
+        .route("/health", get(api_health))
+        .merge(api_routes)
+        .route("/hooks/trigger/{task_id}", post(webhook_trigger_task))
+        .route("/hooks/browser/{trigger_id}", any(webhook_run_browser_plan))
         .route(
-            "/api/marketplace/installed",
-            get(api_marketplace_list_installed),
+            "/mcp",
+            post_service(mcp_service).layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_mcp_api_token,
+            )),
         )
-        .route("/api/voice/transcribe", post(api_transcribe_audio))
-        .route("/api/voice/save", post(api_save_voice_message))
-        .route("/api/voice/read", post(api_read_media_file))
-        .route_service("/mcp", post_service(mcp_service))
+        .merge(sse::routes(state.clone()))
+        .merge(openai_compat::routes(state.clone()))
         .fallback(get(static_or_missing))
         .with_state(state)
 }
@@ -237,25 +317,497 @@ async fn api_health() -> Json<IpcDaemonStatus> {
     Json(super::ipc_server::build_daemon_status())
 }
 
+async fn api_stream_stats() -> Json<StreamBackpressureTotals> {
+    Json(stream_backpressure_totals())
+}
+
 async fn api_request(
     State(state): State<DaemonHttpState>,
+    Extension(caller): Extension<ApiCaller>,
     Json(request): Json<IpcRequest>,
-) -> Json<IpcResponse> {
-    let response =
-        IpcServer::process(&state.core, state.runtime_tool_registry.as_ref(), request).await;
-    Json(response)
+) -> Response {
+    let operation = api_auth::request_type(&request);
+    let mut response = match authorize_ipc_request(&state, &caller, &operation, &request) {
+        Ok(access) => {
+            let registry = state.runtime_tool_registry.as_ref();
+            let response = match access {
+                Some(mut access) => {
+                    IpcServer::process_in_workspace(&state.core, registry, &mut access, request)
+                        .await
+                }
+                None => IpcServer::process(&state.core, registry, request).await,
+            };
+            Json(response).into_response()
+        }
+        Err(IpcAuthError::Token(error)) => api_token_error_response(&error),
+        Err(IpcAuthError::Workspace(error)) => {
+            Json(IpcResponse::error_payload(error)).into_response()
+        }
+    };
+    response
+        .extensions_mut()
+        .insert(api_auth::AuditOperation(operation));
+    response
 }
 
 async fn api_stream(
     State(state): State<DaemonHttpState>,
+    Extension(caller): Extension<ApiCaller>,
     Json(request): Json<IpcRequest>,
 ) -> Response {
+    let operation = api_auth::request_type(&request);
+    let mut response = match authorize_ipc_request(&state, &caller, &operation, &request) {
+        Ok(_) => {
+            let receiver = match IpcServer::open_stream(state.core.clone(), request).await {
+                Ok(receiver) => receiver,
+                Err(error) => single_frame_channel(StreamFrame::error(400, error.to_string())),
+            };
+            stream_frames_response(receiver)
+        }
+        Err(IpcAuthError::Token(error)) => api_token_error_response(&error),
+        Err(IpcAuthError::Workspace(error)) => {
+            stream_frames_response(single_frame_channel(StreamFrame::Error(error)))
+        }
+    };
+    response
+        .extensions_mut()
+        .insert(api_auth::AuditOperation(operation));
+    response
+}
+
+enum IpcAuthError {
+    Token(ApiTokenError),
+    Workspace(ErrorPayload),
+}
+
+/// Check the caller's role and, for user-bound tokens, workspace ownership
+/// before an IPC request runs. Returns the caller's workspace access, if any.
+fn authorize_ipc_request(
+    state: &DaemonHttpState,
+    caller: &ApiCaller,
+    operation: &str,
+    request: &IpcRequest,
+) -> Result<Option<WorkspaceAccess>, IpcAuthError> {
+    caller
+        .authorize(api_auth::required_role(operation))
+        .map_err(IpcAuthError::Token)?;
+    let access = workspace_scope::caller_access(state, caller).map_err(IpcAuthError::Token)?;
+    if let Some(access) = &access {
+        IpcServer::check_workspace_request(access, request).map_err(IpcAuthError::Workspace)?;
+    }
+    Ok(access)
+}
+
+/// Run one agent turn and stream its frames as server-sent events.
+///
+/// Continues `session_id` when it belongs to the agent, otherwise starts a new
+/// workspace session. Each event's data is one JSON [`StreamFrame`], so web
+/// clients receive the same token, tool-call and tool-result updates as IPC
+/// stream consumers.
+async fn api_agent_stream(
+    State(state): State<DaemonHttpState>,
+    Extension(caller): Extension<ApiCaller>,
+    UrlPath(agent_id): UrlPath<String>,
+    Json(request): Json<AgentStreamRequest>,
+) -> Response {
+    let error_stream = |code: i32, message: String| {
+        sse_frames_response(single_frame_channel(StreamFrame::error(code, message)))
+    };
+    if request.input.trim().is_empty() {
+        return error_stream(400, "Input must not be empty".to_string());
+    }
+    let agent_id = match state
+        .core
+        .storage
+        .agents
+        .resolve_existing_agent_id(&agent_id)
+    {
+        Ok(agent_id) => agent_id,
+        Err(error) => return error_stream(404, error.to_string()),
+    };
+    match workspace_scope::caller_access(&state, &caller) {
+        Ok(Some(access)) if access.agent(&agent_id).is_err() => {
+            return error_stream(404, format!("Agent {agent_id} not found"));
+        }
+        Ok(_) => {}
+        Err(error) => return error_stream(i32::from(error.status_code()), error.to_string()),
+    }
+
+    let session_id = match request.session_id {
+        Some(session_id) => match state.core.storage.chat_sessions.get(&session_id) {
+            Ok(Some(session)) if session.agent_id == agent_id => session.id,
+            Ok(Some(_)) => {
+                return error_stream(
+                    400,
+                    format!("Session {session_id} does not belong to agent {agent_id}"),
+                );
+            }
+            Ok(None) => return error_stream(404, format!("Session {session_id} not found")),
+            Err(error) => return error_stream(500, error.to_string()),
+        },
+        None => {
+            let response = IpcServer::process(
+                &state.core,
+                state.runtime_tool_registry.as_ref(),
+                IpcRequest::CreateSession {
+                    agent_id: Some(agent_id),
+                    model: None,
+                    name: None,
+                    skill_id: None,
+                },
+            )
+            .await;
+            match response {
+                IpcResponse::Success(value) => match value["id"].as_str() {
+                    Some(id) => id.to_string(),
+                    None => return error_stream(500, "Created session has no ID".to_string()),
+                },
+                IpcResponse::Error(error) => return error_stream(error.code, error.message),
+                IpcResponse::Pong => {
+                    return error_stream(500, "Unexpected response".to_string());
+                }
+            }
+        }
+    };
+
+    let request = IpcRequest::ExecuteChatSessionStream {
+        session_id,
+        user_input: Some(request.input),
+        stream_id: String::new(),
+    };
     let receiver = match IpcServer::open_stream(state.core.clone(), request).await {
         Ok(receiver) => receiver,
         Err(error) => single_frame_channel(StreamFrame::error(400, error.to_string())),
     };
+    sse_frames_response(receiver)
+}
+
+/// Authenticate MCP callers with scoped API tokens.
+///
+/// Loopback callers without credentials keep full access. Remote callers must
+/// present `Authorization: Bearer rft_...`; the resolved grant is attached to
+/// the request so the MCP server can enforce tool, agent, and budget limits.
+async fn require_mcp_api_token(
+    State(state): State<DaemonHttpState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(secret) = bearer_token(request.headers()) else {
+        if is_loopback_request(&request) {
+            return next.run(request).await;
+        }
+        return api_token_error_response(&ApiTokenError::Invalid);
+    };
+
+    match api_auth::authenticate_api_token(&state, &secret) {
+        Ok(grant) if !grant.scope().mcp || grant.scope().user_id.is_some() => {
+            api_token_error_response(&ApiTokenError::Forbidden("access MCP".to_string()))
+        }
+        Ok(grant) => {
+            request.extensions_mut().insert(grant);
+            next.run(request).await
+        }
+        Err(error) => api_token_error_response(&error),
+    }
+}
+
+/// Trigger a background task from an external webhook call.
+///
+/// The task's webhook must be enabled. Accepts either the task's own webhook
+/// token or a scoped API token that is allowed to run the task's agent. An optional `callback_url` receives the
+/// result of the triggered run, and optional `secrets` are resolvable only by
+/// that run. A paused task waiting for a webhook wake condition is woken
+/// instead, with the request source, input and metadata as the wake event
+/// details.
+async fn webhook_trigger_task(
+    State(state): State<DaemonHttpState>,
+    UrlPath(task_id): UrlPath<String>,
+    headers: HeaderMap,
+    Json(request): Json<WebhookRequest>,
+) -> (StatusCode, Json<WebhookResponse>) {
+    let background_agents = &state.core.storage.background_agents;
+    let task = match background_agents.get_task(&task_id) {
+        Ok(Some(task)) => task,
+        Ok(None) => {
+            return webhook_error(StatusCode::NOT_FOUND, format!("Task {task_id} not found"));
+        }
+        Err(error) => return webhook_error(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+    };
+
+    let secret = bearer_token(&headers).or_else(|| {
+        headers
+            .get(WEBHOOK_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+    });
+    let Some(secret) = secret else {
+        return webhook_error(StatusCode::UNAUTHORIZED, "Missing webhook token");
+    };
+
+    let Some(webhook) = task.webhook.as_ref().filter(|webhook| webhook.enabled) else {
+        return webhook_error(
+            StatusCode::FORBIDDEN,
+            "Webhook trigger is disabled for this task",
+        );
+    };
+
+    if secret.starts_with(API_TOKEN_SECRET_PREFIX) {
+        let authorized = api_auth::authenticate_api_token(&state, &secret).and_then(|grant| {
+            if let Some(access) = workspace_scope::token_access(&state, &grant)?
+                && access.agent(&task.agent_id).is_err()
+            {
+                return Err(ApiTokenError::Forbidden(format!(
+                    "run agent '{}'",
+                    task.agent_id
+                )));
+            }
+            grant.authorize_webhook(&task.agent_id)
+        });
+        if let Err(error) = authorized {
+            return webhook_error(api_token_error_status(&error), error.to_string());
+        }
+    } else if !bool::from(webhook.token.as_bytes().ct_eq(secret.as_bytes())) {
+        return webhook_error(StatusCode::UNAUTHORIZED, "Invalid webhook token");
+    }
+
+    if let Some(limit) = webhook.rate_limit {
+        let allowed = state
+            .webhook_rate_limiter
+            .lock()
+            .map(|mut limiter| limiter.check_and_record(&task.id, limit))
+            .unwrap_or(false);
+        if !allowed {
+            return webhook_error(StatusCode::TOO_MANY_REQUESTS, "Webhook rate limit exceeded");
+        }
+    }
+
+    if let Some(url) = request.callback_url.filter(|url| !url.trim().is_empty()) {
+        if let Err(error) = resolve_and_validate_url(&url).await {
+            return webhook_error(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid callback URL: {error}"),
+            );
+        }
+        if let Err(error) = background_agents
+            .add_result_callback(&task.id, ResultCallback::once(url, request.callback_secret))
+        {
+            return webhook_error(StatusCode::BAD_REQUEST, error.to_string());
+        }
+    }
+    if let Some(secrets) = request.secrets.filter(|secrets| !secrets.is_empty())
+        && let Err(error) = background_agents.stage_temporary_secrets(&task.id, secrets)
+    {
+        return webhook_error(StatusCode::BAD_REQUEST, error.to_string());
+    }
+    if task
+        .wake
+        .as_ref()
+        .is_some_and(|wake| wake.condition == WakeCondition::WebhookReceived)
+    {
+        let event = WakeEvent::new(
+            &WakeCondition::WebhookReceived,
+            json!({
+                "source": request.source,
+                "input": request.input,
+                "metadata": request.metadata,
+            }),
+        );
+        if let Err(error) = background_agents.fire_wake_condition(&task.id, event) {
+            return webhook_error(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+        }
+        info!(task_id = %task.id, "Webhook fired wake condition");
+        return (StatusCode::ACCEPTED, Json(WebhookResponse::queued(task.id)));
+    }
+    if let Some(input) = request.input.filter(|input| !input.trim().is_empty())
+        && let Err(error) = background_agents.send_background_agent_message(
+            &task.id,
+            input,
+            TaskMessageSource::System,
+        )
+    {
+        return webhook_error(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+    }
+    if let Err(error) =
+        background_agents.control_background_agent(&task.id, BackgroundAgentControlAction::RunNow)
+    {
+        return webhook_error(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+    }
+
+    info!(
+        task_id = %task.id,
+        source = request.source.as_deref().unwrap_or("unknown"),
+        "Webhook triggered task"
+    );
+    (StatusCode::ACCEPTED, Json(WebhookResponse::queued(task.id)))
+}
+
+/// Run the browser plan of a webhook trigger and return its results.
+///
+/// The JSON request body (or the query string when the body is empty) is the
+/// `payload` used to render `${payload.<path>}` placeholders in the plan.
+async fn webhook_run_browser_plan(
+    State(state): State<DaemonHttpState>,
+    UrlPath(trigger_id): UrlPath<String>,
+    method: Method,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> (StatusCode, Json<BrowserWebhookOutcome>) {
+    let triggers = &state.core.storage.triggers;
+    let mut trigger = match triggers.get_active_trigger(&trigger_id) {
+        Ok(Some(trigger)) => trigger,
+        Ok(None) => {
+            return browser_webhook_error(
+                StatusCode::NOT_FOUND,
+                format!("Trigger {trigger_id} not found"),
+            );
+        }
+        Err(error) => {
+            return browser_webhook_error(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+        }
+    };
+    let TriggerConfig::Webhook {
+        method: expected_method,
+        auth,
+        browser_plan: Some(plan),
+        ..
+    } = trigger.trigger_config.clone()
+    else {
+        return browser_webhook_error(
+            StatusCode::NOT_FOUND,
+            format!("Trigger {trigger_id} has no browser plan"),
+        );
+    };
+
+    if !expected_method.eq_ignore_ascii_case(method.as_str()) {
+        return browser_webhook_error(
+            StatusCode::METHOD_NOT_ALLOWED,
+            format!("Trigger {trigger_id} expects {expected_method}"),
+        );
+    }
+    if !webhook_auth_matches(auth.as_ref(), &headers) {
+        return browser_webhook_error(StatusCode::UNAUTHORIZED, "Invalid webhook credentials");
+    }
+
+    let payload = if body.iter().all(u8::is_ascii_whitespace) {
+        json!(query)
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(payload) => payload,
+            Err(error) => {
+                return browser_webhook_error(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid JSON payload: {error}"),
+                );
+            }
+        }
+    };
+    let actions = match render_plan_actions(&plan, &payload) {
+        Ok(actions) => actions,
+        Err(error) => return browser_webhook_error(StatusCode::BAD_REQUEST, error.to_string()),
+    };
+
+    trigger.record_trigger();
+    if let Err(error) = triggers.update_trigger(&trigger) {
+        warn!(trigger_id = %trigger.id, error = %error, "Failed to record webhook trigger");
+    }
+
+    let agent_settings = state
+        .core
+        .storage
+        .config
+        .get_effective_config()
+        .map(|config| config.agent)
+        .unwrap_or_default();
+    let timeout_secs = plan
+        .timeout_secs
+        .unwrap_or(agent_settings.browser_timeout_secs);
+    let outcome = match browser_service_for_settings(&agent_settings) {
+        Ok(service) => run_browser_plan(&service, actions, timeout_secs).await,
+        Err(error) => Err(error),
+    };
+    match outcome {
+        Ok(outcome) => {
+            info!(
+                trigger_id = %trigger.id,
+                success = outcome.success,
+                "Webhook ran browser plan"
+            );
+            let status = if outcome.success {
+                StatusCode::OK
+            } else {
+                StatusCode::BAD_GATEWAY
+            };
+            (status, Json(outcome))
+        }
+        Err(error) => browser_webhook_error(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+    }
+}
+
+fn webhook_auth_matches(auth: Option<&AuthConfig>, headers: &HeaderMap) -> bool {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    match auth {
+        None | Some(AuthConfig::None) => true,
+        Some(AuthConfig::ApiKey { key, header_name }) => {
+            header(header_name.as_deref().unwrap_or("X-API-Key")) == Some(key.as_str())
+        }
+        Some(AuthConfig::Basic { username, password }) => header(AUTHORIZATION.as_str())
+            .and_then(|value| value.strip_prefix("Basic "))
+            .and_then(|encoded| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .ok()
+            })
+            .is_some_and(|decoded| decoded == format!("{username}:{password}").into_bytes()),
+    }
+}
+
+fn browser_webhook_error(
+    status: StatusCode,
+    message: impl Into<String>,
+) -> (StatusCode, Json<BrowserWebhookOutcome>) {
+    (status, Json(BrowserWebhookOutcome::error(message)))
+}
+
+fn webhook_error(
+    status: StatusCode,
+    message: impl Into<String>,
+) -> (StatusCode, Json<WebhookResponse>) {
+    (status, Json(WebhookResponse::error(message)))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn is_loopback_request(request: &Request) -> bool {
+    // Routers served without connect info (in-process tests) are treated as local.
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_none_or(|info| info.0.ip().is_loopback())
+}
+
+fn api_token_error_status(error: &ApiTokenError) -> StatusCode {
+    StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
 
-    stream_frames_response(receiver)
+fn api_token_error_response(error: &ApiTokenError) -> Response {
+    (
+        api_token_error_status(error),
+        Json(ErrorPayload::new(
+            i32::from(error.status_code()),
+            error.to_string(),
+            None,
+        )),
+    )
+        .into_response()
 }
 
 fn provider_name(source: Option<&str>) -> &str {
@@ -304,83 +856,6 @@ fn resolve_content_version(
     }
 }
 
-fn manifest_to_skill(manifest: SkillManifest, content: String) -> Skill {
-    let gating = if manifest.gating.binaries.is_empty()
-        && manifest.gating.env_vars.is_empty()
-        && manifest.gating.supported_os.is_empty()
-    {
-        None
-    } else {
-        Some(crate::models::SkillGating {
-            bins: if manifest.gating.binaries.is_empty() {
-                None
-            } else {
-                Some(
-                    manifest
-                        .gating
-                        .binaries
-                        .iter()
-                        .map(|binary| binary.name.clone())
-                        .collect(),
-                )
-            },
-            env: if manifest.gating.env_vars.is_empty() {
-                None
-            } else {
-                Some(
-                    manifest
-                        .gating
-                        .env_vars
-                        .iter()
-                        .map(|env_var| env_var.name.clone())
-                        .collect(),
-                )
-            },
-            os: if manifest.gating.supported_os.is_empty() {
-                None
-            } else {
-                Some(
-                    manifest
-                        .gating
-                        .supported_os
-                        .iter()
-                        .map(|os| match os {
-                            crate::models::OsType::Windows => "windows".to_string(),
-                            crate::models::OsType::MacOS => "macos".to_string(),
-                            crate::models::OsType::Linux => "linux".to_string(),
-                            crate::models::OsType::Any => "any".to_string(),
-                        })
-                        .collect(),
-                )
-            },
-        })
-    };
-
-    Skill {
-        id: manifest.id.clone(),
-        name: manifest.name.clone(),
-        description: manifest.description.clone(),
-        tags: Some(manifest.keywords.clone()),
-        triggers: Vec::new(),
-        content,
-        folder_path: None,
-        suggested_tools: Vec::new(),
-        scripts: Vec::new(),
-        references: Vec::new(),
-        gating,
-        version: Some(manifest.version.to_string()),
-        author: manifest.author.as_ref().map(|author| author.name.clone()),
-        license: manifest.license.clone(),
-        content_hash: None,
-        status: crate::models::SkillStatus::Active,
-        auto_complete: false,
-        storage_mode: StorageMode::DatabaseOnly,
-        is_synced: false,
-        created_at: chrono::Utc::now().timestamp_millis(),
-        updated_at: chrono::Utc::now().timestamp_millis(),
-    }
-}
-
 async fn api_convert_session_to_background_agent(
     State(state): State<DaemonHttpState>,
     Json(request): Json<restflow_contracts::request::TaskFromSessionRequest>,
@@ -551,62 +1026,27 @@ async fn api_marketplace_install_skill(
     State(state): State<DaemonHttpState>,
     Json(request): Json<MarketplaceInstallRequest>,
 ) -> std::result::Result<StatusCode, (StatusCode, String)> {
-    let manifest = match provider_name(request.source.as_deref()) {
-        "github" => GitHubProvider::new()
-            .get_manifest(&request.id)
-            .await
-            .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?,
-        _ => MarketplaceProvider::new()
-            .get_manifest(&request.id)
-            .await
-            .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?,
-    };
-
-    let gating_result = GatingChecker::default().check(&manifest.gating);
-    if !gating_result.passed {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            format!("Gating requirements not met: {}", gating_result.summary),
-        ));
-    }
-
-    let version = request
-        .version
-        .and_then(|value| SkillVersion::parse(&value));
-    let content_version = version.unwrap_or_else(|| manifest.version.clone());
-    let content = match provider_name(request.source.as_deref()) {
-        "github" => GitHubProvider::new()
-            .get_content(&request.id, &content_version)
-            .await
-            .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?,
-        _ => MarketplaceProvider::new()
-            .get_content(&request.id, &content_version)
-            .await
-            .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?,
-    };
-
-    let skill = manifest_to_skill(manifest, content);
-    if state
+    let registry_settings = state
         .core
         .storage
-        .skills
-        .exists(&skill.id)
-        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?
-    {
-        state
-            .core
-            .storage
-            .skills
-            .update(&skill.id, &skill)
-            .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
-    } else {
-        state
-            .core
-            .storage
-            .skills
-            .create(&skill)
-            .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
-    }
+        .config
+        .get_effective_config()
+        .map(|config| config.registry_defaults)
+        .unwrap_or_default();
+    let target = match request.version.as_deref() {
+        Some(version) => format!("{}@{}", request.id, version),
+        None => request.id.clone(),
+    };
+
+    // Dependencies are installed too; skills whose gating fails stay in draft.
+    MarketplaceStoreAdapter::new_with_defaults(
+        state.core.storage.skills.clone(),
+        registry_settings,
+    )
+    .with_secrets(state.core.storage.secrets.clone())
+    .install_skill(&target, request.source.as_deref(), true)
+    .await
+    .map_err(|error| (StatusCode::BAD_GATEWAY, error.to_string()))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -769,16 +1209,26 @@ fn save_audio_to_temp(audio_base64: &str) -> std::result::Result<String, (Status
     Ok(file_path.to_string_lossy().to_string())
 }
 
-fn stream_frames_response(receiver: mpsc::UnboundedReceiver<StreamFrame>) -> Response {
-    let stream = UnboundedReceiverStream::new(receiver).map(|frame| {
-        let mut bytes = match serde_json::to_vec(&frame) {
-            Ok(bytes) => bytes,
-            Err(error) => serde_json::to_vec(&StreamFrame::error(
-                500,
-                format!("Failed to encode stream frame: {error}"),
-            ))
-            .expect("stream error frame serialization"),
-        };
+fn encode_stream_frame(frame: &StreamFrame) -> Vec<u8> {
+    match serde_json::to_vec(frame) {
+        Ok(bytes) => bytes,
+        Err(error) => serde_json::to_vec(&StreamFrame::error(
+            500,
+            format!("Failed to encode stream frame: {error}"),
+        ))
+        .expect("stream error frame serialization"),
+    }
+}
+
+fn receiver_frames(receiver: StreamReceiver) -> impl futures::Stream<Item = StreamFrame> {
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|frame| (frame, receiver))
+    })
+}
+
+fn stream_frames_response(receiver: StreamReceiver) -> Response {
+    let stream = receiver_frames(receiver).map(|frame| {
+        let mut bytes = encode_stream_frame(&frame);
         bytes.push(b'\n');
         Ok::<Bytes, Infallible>(Bytes::from(bytes))
     });
@@ -790,9 +1240,24 @@ fn stream_frames_response(receiver: mpsc::UnboundedReceiver<StreamFrame>) -> Res
         .into_response()
 }
 
-fn single_frame_channel(frame: StreamFrame) -> mpsc::UnboundedReceiver<StreamFrame> {
-    let (tx, rx) = mpsc::unbounded_channel();
-    let _ = tx.send(frame);
+fn sse_frames_response(receiver: StreamReceiver) -> Response {
+    let stream = receiver_frames(receiver).map(|frame| {
+        let mut bytes = b"data: ".to_vec();
+        bytes.extend(encode_stream_frame(&frame));
+        bytes.extend_from_slice(b"\n\n");
+        Ok::<Bytes, Infallible>(Bytes::from(bytes))
+    });
+
+    (
+        [(CONTENT_TYPE, HeaderValue::from_static(SSE_CONTENT_TYPE))],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+fn single_frame_channel(frame: StreamFrame) -> StreamReceiver {
+    let (tx, rx) = stream_channel(1);
+    let _ = tx.send(frame);
     rx
 }
 
@@ -1020,7 +1485,7 @@ fn is_expected_connection_close(error: &str) -> bool {
 mod tests {
     use super::{
         ERROR_CONTENT_TYPE, NDJSON_CONTENT_TYPE, RECOVERY_HEADER, RECOVERY_REINITIALIZE,
-        WEB_DIST_ENV, build_http_router, build_mcp_server_factory,
+        SSE_CONTENT_TYPE, WEB_DIST_ENV, build_http_router, build_mcp_server_factory,
         build_streamable_http_server_config, is_expected_connection_close,
         normalize_mcp_error_response, resolve_web_dist_dir,
     };
@@ -1029,8 +1494,16 @@ mod tests {
     use crate::daemon::{
         IpcRequest, IpcResponse, IpcStreamEvent, StreamFrame, publish_session_event,
     };
-    use crate::models::{AgentNode, ChatMessage, ChatSession, ModelId};
+    use crate::models::{
+        ActiveTrigger, AgentNode, ApiTokenRole, ApiTokenScope, AuthConfig, BrowserPlan,
+        ChatMessage, ChatSession, ModelId, TaskSchedule, TriggerConfig, WakeCondition,
+        WebhookConfig, WebhookResponse, WorkspaceResourceKind,
+    };
+    use crate::services::api_tokens::ApiTokenService;
+    use crate::services::browser_webhook::BrowserWebhookOutcome;
+    use crate::services::workspace::WorkspaceService;
     use axum::body::{self, Body};
+    use axum::extract::ConnectInfo;
     use axum::http::{HeaderValue, Request, StatusCode, header::CONTENT_TYPE};
     use bytes::Bytes;
     use futures::StreamExt;
@@ -1038,6 +1511,7 @@ mod tests {
     use http_body_util::{BodyExt, Full};
     use serde_json::Value;
     use std::env;
+    use std::net::SocketAddr;
     use std::path::{Path, PathBuf};
     use std::sync::Arc;
     use std::sync::{Mutex, MutexGuard, OnceLock};
@@ -1421,4 +1895,962 @@ mod tests {
         let resolved = resolve_web_dist_dir();
         assert_eq!(resolved, Some(temp.path().to_path_buf()));
     }
+
+    fn webhook_request(task_id: &str, header: Option<(&str, &str)>) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(format!("/hooks/trigger/{task_id}"))
+            .header(CONTENT_TYPE, "application/json");
+        if let Some((name, value)) = header {
+            builder = builder.header(name, value);
+        }
+        builder
+            .body(Body::from(r#"{"input":"run from webhook"}"#))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn webhook_trigger_accepts_task_token_and_rejects_others() {
+        let core = test_core().await;
+        let mut task = core
+            .storage
+            .background_agents
+            .create_task(
+                "Webhook Task".to_string(),
+                "agent-hook".to_string(),
+                TaskSchedule::default(),
+            )
+            .expect("create task");
+        task.webhook = Some(WebhookConfig::with_token("task-secret".to_string()));
+        core.storage
+            .background_agents
+            .update_task(&task)
+            .expect("update task");
+
+        let app = build_http_router(core.clone(), CancellationToken::new(), None);
+        let missing = app
+            .clone()
+            .oneshot(webhook_request(&task.id, None))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+
+        let wrong = app
+            .clone()
+            .oneshot(webhook_request(&task.id, Some(("x-webhook-token", "nope"))))
+            .await
+            .unwrap();
+        assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+
+        let accepted = app
+            .clone()
+            .oneshot(webhook_request(
+                &task.id,
+                Some(("x-webhook-token", "task-secret")),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+        let body = body::to_bytes(accepted.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let payload: WebhookResponse = serde_json::from_slice(&body).unwrap();
+        assert!(payload.accepted);
+        assert_eq!(payload.task_id.as_deref(), Some(task.id.as_str()));
+
+        let pending = core
+            .storage
+            .background_agents
+            .list_background_agent_messages(&task.id, 10)
+            .expect("list messages");
+        assert_eq!(pending.len(), 1);
+
+        let missing_task = app
+            .oneshot(webhook_request(
+                "missing-task",
+                Some(("x-webhook-token", "task-secret")),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(missing_task.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn webhook_trigger_fires_wake_condition_of_paused_task() {
+        let core = test_core().await;
+        let background_agents = &core.storage.background_agents;
+        let mut task = background_agents
+            .create_task(
+                "Waiting Webhook Task".to_string(),
+                "agent-hook".to_string(),
+                TaskSchedule::default(),
+            )
+            .expect("create task");
+        task.webhook = Some(WebhookConfig::with_token("task-secret".to_string()));
+        background_agents.update_task(&task).expect("update task");
+        background_agents.pause_task(&task.id).expect("pause task");
+        background_agents
+            .register_wake_condition(&task.id, WakeCondition::WebhookReceived, None)
+            .expect("register wake condition");
+
+        let app = build_http_router(core.clone(), CancellationToken::new(), None);
+        let accepted = app
+            .oneshot(webhook_request(
+                &task.id,
+                Some(("x-webhook-token", "task-secret")),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+
+        let stored = background_agents
+            .get_task(&task.id)
+            .expect("get task")
+            .expect("task");
+        assert_eq!(stored.status, crate::models::TaskStatus::Paused);
+        let fired = stored.wake.and_then(|wake| wake.fired).expect("fired wake");
+        assert_eq!(fired.kind, "webhook_received");
+        assert_eq!(fired.details["input"], "run from webhook");
+        assert!(
+            background_agents
+                .list_background_agent_messages(&task.id, 10)
+                .expect("list messages")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn webhook_trigger_registers_one_shot_result_callback() {
+        let core = test_core().await;
+        let mut task = core
+            .storage
+            .background_agents
+            .create_task(
+                "Callback Task".to_string(),
+                "agent-hook".to_string(),
+                TaskSchedule::default(),
+            )
+            .expect("create task");
+        task.webhook = Some(WebhookConfig::with_token("task-secret".to_string()));
+        core.storage
+            .background_agents
+            .update_task(&task)
+            .expect("update task");
+
+        let app = build_http_router(core.clone(), CancellationToken::new(), None);
+        let request = |callback_url: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/hooks/trigger/{}", task.id))
+                .header(CONTENT_TYPE, "application/json")
+                .header("x-webhook-token", "task-secret")
+                .body(Body::from(
+                    serde_json::json!({
+                        "callback_url": callback_url,
+                        "callback_secret": "cb-secret"
+                    })
+                    .to_string(),
+                ))
+                .unwrap()
+        };
+
+        let invalid = app.clone().oneshot(request("not a url")).await.unwrap();
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+        let internal = app
+            .clone()
+            .oneshot(request("http://169.254.169.254/latest/meta-data"))
+            .await
+            .unwrap();
+        assert_eq!(internal.status(), StatusCode::BAD_REQUEST);
+
+        // A literal public address keeps the test independent of DNS.
+        let accepted = app
+            .oneshot(request("https://1.1.1.1/results"))
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+
+        let stored = core
+            .storage
+            .background_agents
+            .get_task(&task.id)
+            .expect("get task")
+            .expect("task exists");
+        assert_eq!(
+            stored.callbacks,
+            vec![ResultCallback::once(
+                "https://1.1.1.1/results",
+                Some("cb-secret".to_string())
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn webhook_trigger_stages_execution_scoped_secrets() {
+        let core = test_core().await;
+        let mut task = core
+            .storage
+            .background_agents
+            .create_task(
+                "Secret Task".to_string(),
+                "agent-hook".to_string(),
+                TaskSchedule::default(),
+            )
+            .expect("create task");
+        task.webhook = Some(WebhookConfig::with_token("task-secret".to_string()));
+        core.storage
+            .background_agents
+            .update_task(&task)
+            .expect("update task");
+
+        let app = build_http_router(core.clone(), CancellationToken::new(), None);
+        let request = |secrets: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/hooks/trigger/{}", task.id))
+                .header(CONTENT_TYPE, "application/json")
+                .header("x-webhook-token", "task-secret")
+                .body(Body::from(
+                    serde_json::json!({ "secrets": secrets }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let invalid = app
+            .clone()
+            .oneshot(request(serde_json::json!({ "bad name": "x" })))
+            .await
+            .unwrap();
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+
+        let accepted = app
+            .oneshot(request(serde_json::json!({ "DEPLOY_TOKEN": "abc" })))
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+
+        let secrets = core.storage.background_agents.temporary_secrets();
+        assert_eq!(secrets.resolve(&task.id, "DEPLOY_TOKEN"), None);
+        assert_eq!(secrets.activate(&task.id, "run-1"), vec!["DEPLOY_TOKEN"]);
+        assert_eq!(
+            secrets.resolve(&task.id, "DEPLOY_TOKEN").as_deref(),
+            Some("abc")
+        );
+    }
+
+    #[tokio::test]
+    async fn browser_plan_webhook_checks_trigger_auth_and_payload() {
+        let core = test_core().await;
+        let trigger = ActiveTrigger::new(
+            "price-watch".to_string(),
+            TriggerConfig::Webhook {
+                path: "/price-watch".to_string(),
+                method: "POST".to_string(),
+                auth: Some(AuthConfig::ApiKey {
+                    key: "plan-key".to_string(),
+                    header_name: None,
+                }),
+                browser_plan: Some(BrowserPlan {
+                    actions: serde_json::json!([
+                        { "type": "navigate", "url": "${payload.url}" },
+                        { "type": "extract_text", "selector": ".price" }
+                    ]),
+                    timeout_secs: Some(5),
+                }),
+            },
+        );
+        core.storage
+            .triggers
+            .activate_trigger(&trigger)
+            .expect("activate trigger");
+        let plain = ActiveTrigger::new("plain".to_string(), TriggerConfig::Manual);
+        core.storage
+            .triggers
+            .activate_trigger(&plain)
+            .expect("activate trigger");
+
+        let app = build_http_router(core.clone(), CancellationToken::new(), None);
+        let request = |trigger_id: &str, key: Option<&str>, body: &str| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri(format!("/hooks/browser/{trigger_id}"))
+                .header(CONTENT_TYPE, "application/json");
+            if let Some(key) = key {
+                builder = builder.header("x-api-key", key);
+            }
+            builder.body(Body::from(body.to_string())).unwrap()
+        };
+
+        let missing = app
+            .clone()
+            .oneshot(request("missing", Some("plan-key"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let no_plan = app
+            .clone()
+            .oneshot(request(&plain.id, Some("plan-key"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(no_plan.status(), StatusCode::NOT_FOUND);
+
+        let unauthorized = app
+            .clone()
+            .oneshot(request(&trigger.id, Some("wrong"), "{}"))
+            .await
+            .unwrap();
+        assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+
+        let unrendered = app
+            .oneshot(request(&trigger.id, Some("plan-key"), r#"{"sku":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(unrendered.status(), StatusCode::BAD_REQUEST);
+        let body = body::to_bytes(unrendered.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let outcome: BrowserWebhookOutcome = serde_json::from_slice(&body).unwrap();
+        assert!(!outcome.success);
+        assert!(outcome.error.unwrap().contains("payload.url"));
+
+        let stored = core
+            .storage
+            .triggers
+            .get_active_trigger(&trigger.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.trigger_count, 0);
+    }
+
+    #[tokio::test]
+    async fn webhook_trigger_enforces_api_token_agent_scope() {
+        let core = test_core().await;
+        let mut task = core
+            .storage
+            .background_agents
+            .create_task(
+                "Scoped Task".to_string(),
+                "agent-allowed".to_string(),
+                TaskSchedule::default(),
+            )
+            .expect("create task");
+        task.webhook = Some(WebhookConfig::with_token("task-secret".to_string()));
+        core.storage
+            .background_agents
+            .update_task(&task)
+            .expect("update task");
+        let service = ApiTokenService::from_storage(core.storage.as_ref());
+        let allowed = service
+            .issue(
+                "allowed".to_string(),
+                ApiTokenScope {
+                    allowed_agents: Some(vec!["agent-allowed".to_string()]),
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+        let denied = service
+            .issue(
+                "denied".to_string(),
+                ApiTokenScope {
+                    allowed_agents: Some(vec!["other-agent".to_string()]),
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+
+        let app = build_http_router(core.clone(), CancellationToken::new(), None);
+        let bearer = format!("Bearer {}", denied.secret);
+        let response = app
+            .clone()
+            .oneshot(webhook_request(&task.id, Some(("authorization", &bearer))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let bearer = format!("Bearer {}", allowed.secret);
+        let response = app
+            .clone()
+            .oneshot(webhook_request(&task.id, Some(("authorization", &bearer))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let token = core
+            .storage
+            .api_tokens
+            .get(&allowed.token.id)
+            .unwrap()
+            .expect("token exists");
+        assert_eq!(token.call_count, 1);
+
+        if let Some(webhook) = task.webhook.as_mut() {
+            webhook.enabled = false;
+        }
+        core.storage
+            .background_agents
+            .update_task(&task)
+            .expect("update task");
+        let response = app
+            .oneshot(webhook_request(&task.id, Some(("authorization", &bearer))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn mcp_endpoint_requires_token_for_remote_callers() {
+        let app = build_http_router(test_core().await, CancellationToken::new(), None);
+        let remote = SocketAddr::from(([10, 0, 0, 5], 40000));
+
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo(remote));
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header(CONTENT_TYPE, "application/json")
+            .header("authorization", "Bearer rft_invalid")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn mcp_sse_transport_round_trips_initialize() {
+        let app = build_http_router(test_core().await, CancellationToken::new(), None);
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/mcp/sse").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut events = response.into_body().into_data_stream();
+        let endpoint = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let endpoint = String::from_utf8(endpoint.to_vec()).unwrap();
+        let path = endpoint
+            .strip_prefix("event: endpoint\ndata: ")
+            .unwrap()
+            .trim()
+            .to_string();
+        assert!(path.starts_with("/mcp/messages?sessionId="));
+
+        let initialize = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0" }
+            }
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post(path.as_str())
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(initialize.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let message = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let message = String::from_utf8(message.to_vec()).unwrap();
+        let data = message.strip_prefix("event: message\ndata: ").unwrap();
+        let payload: Value = serde_json::from_str(data.trim()).unwrap();
+        assert_eq!(payload["id"], 1);
+        assert_eq!(payload["result"]["serverInfo"]["name"], "restflow");
+
+        let response = app
+            .oneshot(
+                Request::post("/mcp/messages?sessionId=missing")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(initialize.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    fn api_request_with_token(request: &IpcRequest, secret: &str) -> Request<Body> {
+        Request::post("/api/request")
+            .header(CONTENT_TYPE, "application/json")
+            .header("authorization", format!("Bearer {secret}"))
+            .body(Body::from(serde_json::to_vec(request).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn api_request_enforces_token_role() {
+        let core = test_core().await;
+        let service = ApiTokenService::from_storage(core.storage.as_ref());
+        let no_role = service
+            .issue("mcp-only".to_string(), ApiTokenScope::default(), None)
+            .expect("issue token");
+        let reader = service
+            .issue(
+                "reader".to_string(),
+                ApiTokenScope {
+                    role: Some(ApiTokenRole::ReadOnly),
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+        let app = build_http_router(core, CancellationToken::new(), None);
+
+        let mut request = Request::post("/api/request")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                serde_json::to_vec(&IpcRequest::GetStatus).unwrap(),
+            ))
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 5], 40000))));
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(api_request_with_token(
+                &IpcRequest::GetStatus,
+                &no_role.secret,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .clone()
+            .oneshot(api_request_with_token(
+                &IpcRequest::GetStatus,
+                &reader.secret,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let delete = IpcRequest::DeleteAgent {
+            id: "agent-1".to_string(),
+        };
+        let response = app
+            .clone()
+            .oneshot(api_request_with_token(&delete, &reader.secret))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .oneshot(
+                Request::post("/api/marketplace/uninstall")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header("authorization", format!("Bearer {}", reader.secret))
+                    .body(Body::from(r#"{"id":"skill-1"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn api_request_applies_token_rate_limit() {
+        let core = test_core().await;
+        let token = ApiTokenService::from_storage(core.storage.as_ref())
+            .issue(
+                "limited".to_string(),
+                ApiTokenScope {
+                    role: Some(ApiTokenRole::ReadOnly),
+                    rate_limit_per_minute: Some(1),
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+        let app = build_http_router(core, CancellationToken::new(), None);
+
+        let response = app
+            .clone()
+            .oneshot(api_request_with_token(
+                &IpcRequest::GetStatus,
+                &token.secret,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(api_request_with_token(
+                &IpcRequest::GetStatus,
+                &token.secret,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn api_request_scopes_user_bound_token_to_workspace() {
+        let core = test_core().await;
+        let mine = core
+            .storage
+            .agents
+            .create_agent("workspace-mine".to_string(), AgentNode::new())
+            .expect("create agent");
+        let theirs = core
+            .storage
+            .agents
+            .create_agent("workspace-theirs".to_string(), AgentNode::new())
+            .expect("create agent");
+        let workspaces = WorkspaceService::from_storage(core.storage.as_ref());
+        let alice = workspaces.create_user("alice").expect("create user");
+        workspaces
+            .assign(WorkspaceResourceKind::Agent, &mine.id, &alice.id)
+            .expect("assign agent");
+        let token = ApiTokenService::from_storage(core.storage.as_ref())
+            .issue(
+                "alice".to_string(),
+                ApiTokenScope {
+                    role: Some(ApiTokenRole::Admin),
+                    user_id: Some(alice.id.clone()),
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+        let app = build_http_router(core, CancellationToken::new(), None);
+        let send = |request: IpcRequest| {
+            let app = app.clone();
+            let secret = token.secret.clone();
+            async move {
+                let response = app
+                    .oneshot(api_request_with_token(&request, &secret))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<IpcResponse>(&body).unwrap()
+            }
+        };
+
+        match send(IpcRequest::ListAgents).await {
+            IpcResponse::Success(Value::Array(agents)) => {
+                let ids: Vec<&str> = agents
+                    .iter()
+                    .map(|agent| agent["id"].as_str().unwrap())
+                    .collect();
+                assert_eq!(ids, vec![mine.id.as_str()]);
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+        assert!(matches!(
+            send(IpcRequest::GetAgent {
+                id: mine.id.clone()
+            })
+            .await,
+            IpcResponse::Success(_)
+        ));
+        match send(IpcRequest::GetAgent { id: theirs.id }).await {
+            IpcResponse::Error(error) => assert_eq!(error.code, 404),
+            other => panic!("unexpected response: {other:?}"),
+        }
+        let create_session = IpcRequest::CreateSession {
+            agent_id: None,
+            model: None,
+            name: None,
+            skill_id: None,
+        };
+        match send(create_session).await {
+            IpcResponse::Error(error) => assert_eq!(error.code, 400),
+            other => panic!("unexpected response: {other:?}"),
+        }
+        match send(IpcRequest::ListSecrets).await {
+            IpcResponse::Error(error) => assert_eq!(error.code, 403),
+            other => panic!("unexpected response: {other:?}"),
+        }
+
+        let response = app
+            .oneshot(
+                Request::post("/api/marketplace/uninstall")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header("authorization", format!("Bearer {}", token.secret))
+                    .body(Body::from(r#"{"id":"skill-1"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn agent_stream_reports_unknown_agent_as_sse_error_frame() {
+        let app = build_http_router(test_core().await, CancellationToken::new(), None);
+        let response = app
+            .oneshot(
+                Request::post("/api/agents/missing-agent/stream")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"input":"hello"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE),
+            Some(&HeaderValue::from_static(SSE_CONTENT_TYPE))
+        );
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let data = text
+            .strip_prefix("data: ")
+            .and_then(|rest| rest.strip_suffix("\n\n"))
+            .expect("single SSE event");
+        match serde_json::from_str::<StreamFrame>(data).unwrap() {
+            StreamFrame::Error(error) => assert_eq!(error.code, 404),
+            other => panic!("unexpected frame: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn openai_models_lists_agents_within_token_scope() {
+        let core = test_core().await;
+        let allowed = core
+            .storage
+            .agents
+            .create_agent("openai-allowed".to_string(), AgentNode::new())
+            .expect("create agent");
+        let hidden = core
+            .storage
+            .agents
+            .create_agent("openai-hidden".to_string(), AgentNode::new())
+            .expect("create agent");
+        let token = ApiTokenService::from_storage(core.storage.as_ref())
+            .issue(
+                "openai".to_string(),
+                ApiTokenScope {
+                    allowed_agents: Some(vec![allowed.id.clone()]),
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+
+        let app = build_http_router(core, CancellationToken::new(), None);
+        let ids = |payload: Value| -> Vec<String> {
+            assert_eq!(payload["object"], "list");
+            payload["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|model| model["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/v1/models").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let local = ids(serde_json::from_slice(&body).unwrap());
+        assert!(local.contains(&allowed.id));
+        assert!(local.contains(&hidden.id));
+
+        let response = app
+            .oneshot(
+                Request::get("/v1/models")
+                    .header("authorization", format!("Bearer {}", token.secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(serde_json::from_slice(&body).unwrap()),
+            vec![allowed.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn openai_endpoints_require_token_with_openai_scope() {
+        let core = test_core().await;
+        let token = ApiTokenService::from_storage(core.storage.as_ref())
+            .issue(
+                "no-openai".to_string(),
+                ApiTokenScope {
+                    openai_api: false,
+                    ..ApiTokenScope::default()
+                },
+                None,
+            )
+            .expect("issue token");
+        let app = build_http_router(core, CancellationToken::new(), None);
+
+        let mut request = Request::get("/v1/models").body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 5], 40000))));
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .oneshot(
+                Request::get("/v1/models")
+                    .header("authorization", format!("Bearer {}", token.secret))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let payload: Value = serde_json::from_slice(&body).unwrap();
+        assert!(payload["error"]["message"].is_string());
+    }
+
+    #[tokio::test]
+    async fn openai_chat_completions_validates_request() {
+        let app = build_http_router(test_core().await, CancellationToken::new(), None);
+        let completion = |payload: Value| {
+            Request::post("/v1/chat/completions")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(payload.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(completion(
+                serde_json::json!({ "model": "default", "messages": [] }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(completion(serde_json::json!({
+                "model": "default",
+                "messages": [{ "role": "assistant", "content": "hi" }],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .oneshot(completion(serde_json::json!({
+                "model": "no-such-agent",
+                "messages": [{ "role": "user", "content": [{ "type": "text", "text": "hi" }] }],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn audit_routes_filter_by_category_and_time_range() {
+        let core = test_core().await;
+        let decision = |target: &str, timestamp: i64| {
+            let mut event = crate::models::execution_trace_builders::security_decision(
+                "task-1",
+                "agent-1",
+                crate::models::SecurityDecisionTrace {
+                    tool_name: "shell".to_string(),
+                    operation: "execute".to_string(),
+                    target: target.to_string(),
+                    allowed: false,
+                    requires_approval: false,
+                    approval_id: None,
+                    reason: None,
+                },
+            );
+            event.timestamp = timestamp;
+            event
+        };
+        let log = crate::models::execution_trace_builders::log_record(
+            "task-1",
+            "agent-1",
+            crate::models::LogRecordTrace {
+                level: "info".to_string(),
+                message: "started".to_string(),
+                fields: Vec::new(),
+            },
+        );
+        for event in [decision("rm -rf /", 1_000), decision("ls", 9_000), log] {
+            core.storage.execution_traces.store(&event).unwrap();
+        }
+        let app = build_http_router(core, CancellationToken::new(), None);
+        let get_json = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<Value>(&body).unwrap()
+            }
+        };
+
+        let events = get_json("/api/audit/events?category=security_decision&to=5000").await;
+        let events = events.as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["security_decision"]["target"], "rm -rf /");
+
+        let stats = get_json("/api/audit/stats?category=security_decision").await;
+        assert_eq!(stats["total_events"], 2);
+        assert_eq!(stats["security_decision_count"], 2);
+        assert_eq!(stats["log_record_count"], 0);
+
+        let response = app
+            .oneshot(
+                Request::get("/api/audit/stats?category=unknown")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
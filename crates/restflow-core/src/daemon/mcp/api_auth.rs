@@ -0,0 +1,259 @@
+//! Authentication, role checks, rate limits and audit logging for the daemon
+//! HTTP API.
+//!
+//! Loopback callers without credentials keep full access. Remote callers must
+//! present `Authorization: Bearer rft_...` for a token whose scope grants an
+//! HTTP API role; each route (or, for `/api/request` and `/api/stream`, each
+//! IPC request) requires a minimum role.
+
+use super::{DaemonHttpState, api_token_error_response, bearer_token, is_loopback_request};
+use crate::daemon::IpcRequest;
+use crate::models::ApiTokenRole;
+use crate::services::api_tokens::{ApiTokenError, ApiTokenGrant, ApiTokenService};
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use std::time::Instant;
+use tracing::info;
+
+const AUDIT_TARGET: &str = "restflow::audit";
+
+/// Caller of an authenticated HTTP route, attached as a request extension.
+#[derive(Debug, Clone)]
+pub(super) enum ApiCaller {
+    /// Loopback caller without credentials.
+    Local,
+    Token(ApiTokenGrant),
+}
+
+impl ApiCaller {
+    /// Check that the caller holds at least `required`.
+    pub(super) fn authorize(&self, required: ApiTokenRole) -> Result<(), ApiTokenError> {
+        match self {
+            Self::Local => Ok(()),
+            Self::Token(grant) if grant.scope().allows_role(required) => Ok(()),
+            Self::Token(_) => Err(ApiTokenError::Forbidden(format!(
+                "perform {} operations",
+                required.as_str()
+            ))),
+        }
+    }
+
+    fn audit_label(&self) -> String {
+        match self {
+            Self::Local => "local".to_string(),
+            Self::Token(grant) => format!("token:{}", grant.token().id),
+        }
+    }
+}
+
+/// Operation name recorded in the audit log, set by handlers on responses.
+#[derive(Debug, Clone)]
+pub(super) struct AuditOperation(pub(super) String);
+
+/// Authenticate `secret` and apply the token's per-minute rate limit.
+pub(super) fn authenticate_api_token(
+    state: &DaemonHttpState,
+    secret: &str,
+) -> Result<ApiTokenGrant, ApiTokenError> {
+    let grant = ApiTokenService::from_storage(state.core.storage.as_ref()).authenticate(secret)?;
+    if let Some(limit) = grant.scope().rate_limit_per_minute {
+        // A panic elsewhere must not switch rate limiting off, so keep using
+        // the limiter state if the lock was poisoned.
+        let allowed = state
+            .api_token_rate_limiter
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .check_and_record(&grant.token().id, limit);
+        if !allowed {
+            return Err(ApiTokenError::RateLimited);
+        }
+    }
+    Ok(grant)
+}
+
+/// Resolve the caller, attach it to the request and audit the outcome.
+pub(super) async fn require_api_caller(
+    State(state): State<DaemonHttpState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let caller = match bearer_token(request.headers()) {
+        Some(secret) => match authenticate_api_token(&state, &secret) {
+            Ok(grant) if grant.scope().role.is_none() => {
+                let error = ApiTokenError::Forbidden("access the HTTP API".to_string());
+                return audited(&request, "token", api_token_error_response(&error));
+            }
+            Ok(grant) => ApiCaller::Token(grant),
+            Err(error) => {
+                return audited(&request, "token", api_token_error_response(&error));
+            }
+        },
+        None if is_loopback_request(&request) => ApiCaller::Local,
+        None => {
+            let response = api_token_error_response(&ApiTokenError::Invalid);
+            return audited(&request, "anonymous", response);
+        }
+    };
+
+    let label = caller.audit_label();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    request.extensions_mut().insert(caller);
+    let started = Instant::now();
+    let response = next.run(request).await;
+    audit(&label, method.as_str(), &path, &response, started);
+    response
+}
+
+/// Require at least `role` for every route in `router`.
+pub(super) fn with_role(
+    role: ApiTokenRole,
+    router: Router<DaemonHttpState>,
+) -> Router<DaemonHttpState> {
+    router.route_layer(middleware::from_fn(
+        move |request: Request, next: Next| async move {
+            let authorized = request
+                .extensions()
+                .get::<ApiCaller>()
+                .map_or(Ok(()), |caller| caller.authorize(role));
+            match authorized {
+                Ok(()) => next.run(request).await,
+                Err(error) => api_token_error_response(&error),
+            }
+        },
+    ))
+}
+
+/// IPC request name, e.g. `GetSession`.
+pub(super) fn request_type(request: &IpcRequest) -> String {
+    serde_json::to_value(request)
+        .ok()
+        .and_then(|value| value.get("type")?.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Minimum role needed to send an IPC request over HTTP.
+///
+/// Reads of credentials, configuration, token state, users, replays,
+/// terminal sessions and MCP server definitions need `admin`; other reads
+/// need `read_only`; running agents, sessions, tasks and tools needs
+/// `execute`; anything else changes state and needs `admin`.
+pub(super) fn required_role(request_type: &str) -> ApiTokenRole {
+    const ADMIN_READS: &[&str] = &[
+        "GetSecret",
+        "ListSecrets",
+        "ListExpiringSecrets",
+        "GetSecretUsage",
+        "GetApiKey",
+        "GetApiKeyForProfile",
+        "GetAuthProfile",
+        "ListAuthProfiles",
+        "ListApiTokens",
+        "GetPairingOwner",
+        "ListPairingState",
+        "GetConfig",
+        "GetGlobalConfig",
+        "ListUsers",
+        "GetExecutionReplay",
+        "GetTerminalSession",
+        "ListMcpServers",
+    ];
+    const READ_PREFIXES: &[&str] = &["Get", "List", "Search", "Count", "Query", "Subscribe"];
+    const EXECUTE: &[&str] = &[
+        "CreateSession",
+        "AddMessage",
+        "AppendMessage",
+        "ExecuteChatSession",
+        "ExecuteChatSessionStream",
+        "SteerChatSessionStream",
+        "CancelChatSessionStream",
+        "ControlTask",
+        "RunTaskWithSecrets",
+        "SendTaskMessage",
+        "HandleTaskApproval",
+        "ResolveToolApproval",
+        "RunBrowserPlan",
+        "ExecuteTool",
+        "TestHook",
+    ];
+
+    if ADMIN_READS.contains(&request_type) || request_type.starts_with("Export") {
+        ApiTokenRole::Admin
+    } else if matches!(request_type, "Ping" | "BuildAgentSystemPrompt")
+        || READ_PREFIXES
+            .iter()
+            .any(|prefix| request_type.starts_with(prefix))
+    {
+        ApiTokenRole::ReadOnly
+    } else if EXECUTE.contains(&request_type) {
+        ApiTokenRole::Execute
+    } else {
+        ApiTokenRole::Admin
+    }
+}
+
+fn audited(request: &Request, caller: &str, response: Response) -> Response {
+    audit(
+        caller,
+        request.method().as_str(),
+        request.uri().path(),
+        &response,
+        Instant::now(),
+    );
+    response
+}
+
+fn audit(caller: &str, method: &str, path: &str, response: &Response, started: Instant) {
+    let operation = response
+        .extensions()
+        .get::<AuditOperation>()
+        .map(|operation| operation.0.as_str())
+        .unwrap_or("");
+    info!(
+        target: AUDIT_TARGET,
+        caller,
+        method,
+        path,
+        operation,
+        status = response.status().as_u16(),
+        duration_ms = started.elapsed().as_millis() as u64,
+        "HTTP API request"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_ipc_requests_by_role() {
+        assert_eq!(required_role("GetSession"), ApiTokenRole::ReadOnly);
+        assert_eq!(required_role("ListAgents"), ApiTokenRole::ReadOnly);
+        assert_eq!(required_role("SubscribeTaskEvents"), ApiTokenRole::ReadOnly);
+        assert_eq!(required_role("ExecuteChatSession"), ApiTokenRole::Execute);
+        assert_eq!(required_role("ExecuteTool"), ApiTokenRole::Execute);
+        assert_eq!(required_role("ResolveToolApproval"), ApiTokenRole::Execute);
+        assert_eq!(required_role("SetToolApprovalPolicy"), ApiTokenRole::Admin);
+        assert_eq!(required_role("GetSecret"), ApiTokenRole::Admin);
+        assert_eq!(required_role("GetSecretUsage"), ApiTokenRole::Admin);
+        assert_eq!(required_role("ExportMemory"), ApiTokenRole::Admin);
+        assert_eq!(required_role("SetConfig"), ApiTokenRole::Admin);
+        assert_eq!(required_role("DeleteAgent"), ApiTokenRole::Admin);
+        assert_eq!(required_role("SomethingNew"), ApiTokenRole::Admin);
+    }
+
+    #[test]
+    fn sensitive_reads_require_admin() {
+        assert_eq!(required_role("ListUsers"), ApiTokenRole::Admin);
+        assert_eq!(required_role("GetExecutionReplay"), ApiTokenRole::Admin);
+        assert_eq!(required_role("GetTerminalSession"), ApiTokenRole::Admin);
+        assert_eq!(required_role("ListMcpServers"), ApiTokenRole::Admin);
+    }
+
+    #[test]
+    fn reads_request_type_from_ipc_request() {
+        assert_eq!(request_type(&IpcRequest::GetStatus), "GetStatus");
+    }
+}
@@ -0,0 +1,78 @@
+//! Audit log API over execution trace events.
+//!
+//! `GET /api/audit/events` lists events newest first and
+//! `GET /api/audit/stats` counts them per category. Both accept `category`,
+//! `agent_id`, `run_id`, `session_id` and a `from`/`to` time range in Unix
+//! milliseconds; `events` also takes `limit` and `offset`.
+
+use super::DaemonHttpState;
+use crate::models::{
+    ExecutionTraceCategory, ExecutionTraceEvent, ExecutionTraceQuery, ExecutionTraceStats,
+};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+
+const DEFAULT_EVENT_LIMIT: usize = 100;
+
+#[derive(Debug, Default, Deserialize)]
+struct AuditQuery {
+    category: Option<ExecutionTraceCategory>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+    session_id: Option<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+impl AuditQuery {
+    fn into_trace_query(self) -> ExecutionTraceQuery {
+        ExecutionTraceQuery {
+            category: self.category,
+            agent_id: self.agent_id,
+            run_id: self.run_id,
+            session_id: self.session_id,
+            from_timestamp: self.from,
+            to_timestamp: self.to,
+            limit: Some(self.limit.unwrap_or(DEFAULT_EVENT_LIMIT)),
+            offset: self.offset,
+            ..ExecutionTraceQuery::default()
+        }
+    }
+}
+
+pub(super) fn routes() -> Router<DaemonHttpState> {
+    Router::new()
+        .route("/api/audit/events", get(list_events))
+        .route("/api/audit/stats", get(stats))
+}
+
+async fn list_events(
+    State(state): State<DaemonHttpState>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<Vec<ExecutionTraceEvent>>, (StatusCode, String)> {
+    state
+        .core
+        .storage
+        .execution_traces
+        .query(&query.into_trace_query())
+        .map(Json)
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))
+}
+
+async fn stats(
+    State(state): State<DaemonHttpState>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<ExecutionTraceStats>, (StatusCode, String)> {
+    state
+        .core
+        .storage
+        .execution_traces
+        .stats_for_query(&query.into_trace_query())
+        .map(Json)
+        .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))
+}
@@ -0,0 +1,423 @@
+//! OpenAI-compatible chat completions API.
+//!
+//! Exposes `/v1/models` and `/v1/chat/completions` so tools built against the
+//! OpenAI SDKs can talk to RestFlow agents. The request `model` selects the
+//! agent (by ID, ID prefix or name, or `default`); the agent runs with its own
+//! model, prompt and tools, and only its final reply is returned.
+
+use super::api_auth::authenticate_api_token;
+use super::workspace_scope::token_workspace;
+use super::{
+    DaemonHttpState, SSE_CONTENT_TYPE, api_token_error_status, bearer_token, is_loopback_request,
+    receiver_frames,
+};
+use crate::daemon::{IpcRequest, IpcResponse, IpcServer, StreamFrame};
+use crate::models::{ChatMessage, ChatRole, ChatSession, ModelId};
+use crate::services::api_tokens::{ApiTokenError, ApiTokenGrant};
+use crate::services::session::SessionService;
+use crate::services::workspace::WorkspaceScope;
+use axum::body::Body;
+use axum::extract::{Extension, Request, State};
+use axum::http::{HeaderValue, StatusCode, header::CONTENT_TYPE};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use bytes::Bytes;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::convert::Infallible;
+
+const SESSION_NAME: &str = "OpenAI API";
+const SESSION_SOURCE: &str = "openai_api";
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionRequest {
+    #[serde(default)]
+    model: String,
+    messages: Vec<ChatCompletionMessage>,
+    #[serde(default)]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionMessage {
+    role: String,
+    #[serde(default)]
+    content: Value,
+}
+
+impl ChatCompletionMessage {
+    /// Flatten string or content-part message bodies to plain text.
+    fn text(&self) -> String {
+        match &self.content {
+            Value::String(text) => text.clone(),
+            Value::Array(parts) => parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => String::new(),
+        }
+    }
+
+    fn to_chat_message(&self) -> Option<ChatMessage> {
+        match self.role.as_str() {
+            "system" | "developer" => Some(ChatMessage::system(self.text())),
+            "user" => Some(ChatMessage::user(self.text())),
+            "assistant" => Some(ChatMessage::assistant(self.text())),
+            _ => None,
+        }
+    }
+}
+
+pub(super) fn routes(state: DaemonHttpState) -> Router<DaemonHttpState> {
+    Router::new()
+        .route("/v1/models", get(list_models))
+        .route("/v1/chat/completions", post(chat_completions))
+        .layer(middleware::from_fn_with_state(
+            state,
+            require_openai_api_token,
+        ))
+}
+
+/// Authenticate callers of the `/v1` endpoints with scoped API tokens.
+///
+/// Mirrors the MCP endpoint: loopback callers without credentials keep full
+/// access, remote callers need an `rft_` bearer token allowed to use the
+/// OpenAI-compatible API.
+async fn require_openai_api_token(
+    State(state): State<DaemonHttpState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(secret) = bearer_token(request.headers()) else {
+        if is_loopback_request(&request) {
+            return next.run(request).await;
+        }
+        return token_error_response(&ApiTokenError::Invalid);
+    };
+
+    match authenticate_api_token(&state, &secret) {
+        Ok(grant) if !grant.scope().openai_api => token_error_response(&ApiTokenError::Forbidden(
+            "use the OpenAI-compatible API".to_string(),
+        )),
+        Ok(grant) => {
+            request.extensions_mut().insert(grant);
+            next.run(request).await
+        }
+        Err(error) => token_error_response(&error),
+    }
+}
+
+/// List agents as OpenAI models.
+async fn list_models(
+    State(state): State<DaemonHttpState>,
+    grant: Option<Extension<ApiTokenGrant>>,
+) -> Response {
+    let workspace = match grant_workspace(&state, grant.as_deref()) {
+        Ok(workspace) => workspace,
+        Err(response) => return response,
+    };
+    let agents = match state.core.storage.agents.list_agents() {
+        Ok(agents) => agents,
+        Err(error) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+    };
+    let data: Vec<Value> = agents
+        .into_iter()
+        .filter(|agent| {
+            grant
+                .as_ref()
+                .is_none_or(|grant| grant.scope().allows_agent(&agent.id))
+                && workspace
+                    .as_ref()
+                    .is_none_or(|workspace| workspace.owns_agent(&agent.id))
+        })
+        .map(|agent| {
+            json!({
+                "id": agent.id,
+                "object": "model",
+                "created": agent.created_at.unwrap_or_default() / 1000,
+                "owned_by": "restflow",
+                "name": agent.name,
+            })
+        })
+        .collect();
+    Json(json!({ "object": "list", "data": data })).into_response()
+}
+
+/// Run one chat turn against the agent named by `model`.
+///
+/// Earlier messages seed a fresh workspace session; the last message must
+/// come from the user and is executed as the new turn.
+async fn chat_completions(
+    State(state): State<DaemonHttpState>,
+    grant: Option<Extension<ApiTokenGrant>>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Response {
+    let Some((last, history)) = request.messages.split_last() else {
+        return error_response(StatusCode::BAD_REQUEST, "messages must not be empty");
+    };
+    if last.role != "user" {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "the last message must be from the user",
+        );
+    }
+    let input = last.text();
+    if input.trim().is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "the last user message is empty");
+    }
+
+    let workspace = match grant_workspace(&state, grant.as_deref()) {
+        Ok(workspace) => workspace,
+        Err(response) => return response,
+    };
+    let agent_id = match resolve_agent(&state, workspace.as_ref(), &request.model) {
+        Ok(agent_id) => agent_id,
+        Err(response) => return response,
+    };
+    if let Some(Extension(grant)) = &grant
+        && let Err(error) = grant.authorize_chat_completion(&agent_id)
+    {
+        return token_error_response(&error);
+    }
+
+    let session = match create_session(&state, agent_id, history) {
+        Ok(session) => session,
+        Err(response) => return response,
+    };
+
+    if request.stream {
+        return stream_completion(&state, session, input, request.model).await;
+    }
+
+    let response = IpcServer::process(
+        &state.core,
+        state.runtime_tool_registry.as_ref(),
+        IpcRequest::ExecuteChatSession {
+            session_id: session.id,
+            user_input: Some(input),
+        },
+    )
+    .await;
+    let session = match response {
+        IpcResponse::Success(value) => match serde_json::from_value::<ChatSession>(value) {
+            Ok(session) => session,
+            Err(error) => {
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+            }
+        },
+        IpcResponse::Error(error) => {
+            let status = StatusCode::from_u16(error.code as u16)
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            return error_response(status, error.message);
+        }
+        IpcResponse::Pong => {
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Unexpected response");
+        }
+    };
+
+    let reply = session
+        .messages
+        .iter()
+        .rev()
+        .find(|message| message.role == ChatRole::Assistant);
+    let content = reply
+        .map(|message| message.content.clone())
+        .unwrap_or_default();
+    let execution = reply.and_then(|message| message.execution.as_ref());
+    let prompt_tokens = execution
+        .and_then(|execution| execution.input_tokens)
+        .unwrap_or(0);
+    let completion_tokens = execution
+        .and_then(|execution| execution.output_tokens)
+        .unwrap_or(0);
+
+    Json(json!({
+        "id": completion_id(&session.id),
+        "object": "chat.completion",
+        "created": chrono::Utc::now().timestamp(),
+        "model": request.model,
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop",
+        }],
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "completion_tokens": completion_tokens,
+            "total_tokens": prompt_tokens + completion_tokens,
+        },
+    }))
+    .into_response()
+}
+
+/// Stream the turn as server-sent `chat.completion.chunk` events.
+async fn stream_completion(
+    state: &DaemonHttpState,
+    session: ChatSession,
+    input: String,
+    model: String,
+) -> Response {
+    let receiver = match IpcServer::open_stream(
+        state.core.clone(),
+        IpcRequest::ExecuteChatSessionStream {
+            session_id: session.id.clone(),
+            user_input: Some(input),
+            stream_id: String::new(),
+        },
+    )
+    .await
+    {
+        Ok(receiver) => receiver,
+        Err(error) => return error_response(StatusCode::BAD_REQUEST, error.to_string()),
+    };
+
+    let id = completion_id(&session.id);
+    let created = chrono::Utc::now().timestamp();
+    let chunk = move |delta: Value, finish_reason: Option<&str>| {
+        sse_event(&json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": model,
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+        }))
+    };
+
+    let first = futures::stream::iter([chunk(json!({ "role": "assistant" }), None)]);
+    let events = receiver_frames(receiver).filter_map(move |frame| {
+        let event = match frame {
+            StreamFrame::Data { content } => Some(chunk(json!({ "content": content }), None)),
+            StreamFrame::Done { .. } => Some(format!(
+                "{}data: [DONE]\n\n",
+                chunk(json!({}), Some("stop"))
+            )),
+            StreamFrame::Error(error) => Some(format!(
+                "{}data: [DONE]\n\n",
+                sse_event(&error_body(&error.message))
+            )),
+            _ => None,
+        };
+        futures::future::ready(event)
+    });
+    let body = first
+        .chain(events)
+        .map(|event| Ok::<Bytes, Infallible>(Bytes::from(event)));
+
+    (
+        [(CONTENT_TYPE, HeaderValue::from_static(SSE_CONTENT_TYPE))],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+/// Resolve the workspace of the calling token, if it is bound to a user.
+fn grant_workspace(
+    state: &DaemonHttpState,
+    grant: Option<&ApiTokenGrant>,
+) -> Result<Option<WorkspaceScope>, Response> {
+    match grant {
+        Some(grant) => token_workspace(state, grant).map_err(|error| token_error_response(&error)),
+        None => Ok(None),
+    }
+}
+
+/// Resolve the agent selected by an OpenAI `model` value.
+///
+/// Workspace-scoped callers only resolve agents their workspace owns.
+fn resolve_agent(
+    state: &DaemonHttpState,
+    workspace: Option<&WorkspaceScope>,
+    model: &str,
+) -> Result<String, Response> {
+    let agents = &state.core.storage.agents;
+    let model = model.trim();
+    let visible = |agent_id: &str| workspace.is_none_or(|workspace| workspace.owns_agent(agent_id));
+    let not_found = || {
+        error_response(
+            StatusCode::NOT_FOUND,
+            format!("The model '{model}' does not match any agent"),
+        )
+    };
+    if model.is_empty() || model == "default" {
+        return match agents.resolve_default_agent_id() {
+            Ok(agent_id) if visible(&agent_id) => Ok(agent_id),
+            Ok(_) => Err(not_found()),
+            Err(error) => Err(error_response(StatusCode::NOT_FOUND, error.to_string())),
+        };
+    }
+    if let Ok(agent_id) = agents.resolve_existing_agent_id(model) {
+        return if visible(&agent_id) {
+            Ok(agent_id)
+        } else {
+            Err(not_found())
+        };
+    }
+    agents
+        .list_agents()
+        .map_err(|error| error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?
+        .into_iter()
+        .find(|agent| agent.name.eq_ignore_ascii_case(model) && visible(&agent.id))
+        .map(|agent| agent.id)
+        .ok_or_else(not_found)
+}
+
+fn create_session(
+    state: &DaemonHttpState,
+    agent_id: String,
+    history: &[ChatCompletionMessage],
+) -> Result<ChatSession, Response> {
+    let internal =
+        |error: anyhow::Error| error_response(StatusCode::INTERNAL_SERVER_ERROR, error.to_string());
+    let model = state
+        .core
+        .storage
+        .agents
+        .get_agent(agent_id.clone())
+        .map_err(internal)?
+        .and_then(|agent| agent.agent.model)
+        .unwrap_or(ModelId::Gpt5)
+        .as_serialized_str()
+        .to_string();
+
+    let service = SessionService::from_storage(&state.core.storage);
+    let mut session = service
+        .create_workspace_session(agent_id, model, Some(SESSION_NAME.to_string()), None, None)
+        .map_err(internal)?;
+    if !history.is_empty() {
+        for message in history
+            .iter()
+            .filter_map(ChatCompletionMessage::to_chat_message)
+        {
+            session.add_message(message);
+        }
+        service
+            .save_existing_session(&session, SESSION_SOURCE)
+            .map_err(internal)?;
+    }
+    Ok(session)
+}
+
+fn completion_id(session_id: &str) -> String {
+    format!("chatcmpl-{session_id}")
+}
+
+fn sse_event(payload: &Value) -> String {
+    format!("data: {payload}\n\n")
+}
+
+fn error_body(message: &str) -> Value {
+    json!({ "error": { "message": message, "type": "restflow_error" } })
+}
+
+/// Errors use the OpenAI `{"error": {...}}` shape so SDK clients surface them.
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(error_body(&message.into()))).into_response()
+}
+
+fn token_error_response(error: &ApiTokenError) -> Response {
+    error_response(api_token_error_status(error), error.to_string())
+}
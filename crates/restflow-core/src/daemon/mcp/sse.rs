@@ -0,0 +1,245 @@
+//! MCP HTTP+SSE transport.
+//!
+//! Older MCP clients connect by opening `GET /mcp/sse`, which first sends an
+//! `endpoint` event naming `/mcp/messages?sessionId=...`. JSON-RPC messages
+//! are POSTed there and every server message is pushed back on the event
+//! stream. Each session runs its own MCP server over an in-memory pipe with
+//! the same newline-delimited framing as the stdio transport.
+
+use super::{DaemonHttpState, SSE_CONTENT_TYPE, require_mcp_api_token};
+use crate::mcp::RestFlowMcpServer;
+use crate::services::api_tokens::ApiTokenGrant;
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Extension, Query, State};
+use axum::http::{HeaderValue, StatusCode, header::CONTENT_TYPE};
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use bytes::Bytes;
+use futures::StreamExt;
+use rmcp::ServiceExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+const MESSAGES_PATH: &str = "/mcp/messages";
+const PIPE_CAPACITY: usize = 256 * 1024;
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Open SSE sessions keyed by session ID.
+#[derive(Clone)]
+pub(super) struct SseSessions {
+    server: RestFlowMcpServer,
+    sessions: Arc<Mutex<HashMap<String, SseSession>>>,
+}
+
+struct SseSession {
+    inbound: mpsc::Sender<Vec<u8>>,
+    /// ID of the API token that opened the session, if any.
+    token_id: Option<String>,
+}
+
+impl SseSessions {
+    pub(super) fn new(server: RestFlowMcpServer) -> Self {
+        Self {
+            server,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn insert(&self, id: String, session: SseSession) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(id, session);
+    }
+
+    fn remove(&self, id: &str) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(id);
+    }
+
+    fn inbound(&self, id: &str, token_id: Option<&str>) -> Option<mpsc::Sender<Vec<u8>>> {
+        let sessions = self
+            .sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        sessions
+            .get(id)
+            .filter(|session| session.token_id.as_deref() == token_id)
+            .map(|session| session.inbound.clone())
+    }
+}
+
+/// Removes the session and stops its server once the event stream is dropped.
+struct SessionGuard {
+    sessions: SseSessions,
+    id: String,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.sessions.remove(&self.id);
+        for task in &self.tasks {
+            task.abort();
+        }
+        debug!(session_id = %self.id, "MCP SSE session closed");
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+pub(super) fn routes(state: DaemonHttpState) -> Router<DaemonHttpState> {
+    Router::new()
+        .route("/mcp/sse", get(open_stream))
+        .route(MESSAGES_PATH, post(post_message))
+        .layer(middleware::from_fn_with_state(state, require_mcp_api_token))
+}
+
+async fn open_stream(
+    State(state): State<DaemonHttpState>,
+    grant: Option<Extension<ApiTokenGrant>>,
+) -> Response {
+    let grant = grant.map(|Extension(grant)| grant);
+    let sessions = state.mcp_sse_sessions.clone();
+    let session_id = Uuid::new_v4().to_string();
+
+    let (server_io, bridge_io) = tokio::io::duplex(PIPE_CAPACITY);
+    let (bridge_read, mut bridge_write) = tokio::io::split(bridge_io);
+    let (inbound_tx, mut inbound_rx) = mpsc::channel::<Vec<u8>>(CHANNEL_CAPACITY);
+    let (event_tx, event_rx) = mpsc::channel::<Bytes>(CHANNEL_CAPACITY);
+
+    let server = sessions.server.clone().with_session_grant(grant.clone());
+    let server_task = tokio::spawn(async move {
+        match server.serve(server_io).await {
+            Ok(running) => {
+                let _ = running.waiting().await;
+            }
+            Err(error) => warn!(%error, "MCP SSE session failed to initialize"),
+        }
+    });
+    let writer_task = tokio::spawn(async move {
+        while let Some(message) = inbound_rx.recv().await {
+            if bridge_write.write_all(&message).await.is_err() {
+                break;
+            }
+        }
+    });
+    let reader_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(bridge_read).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if event_tx.send(sse_event("message", &line)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    sessions.insert(
+        session_id.clone(),
+        SseSession {
+            inbound: inbound_tx,
+            token_id: grant.as_ref().map(|grant| grant.token().id.clone()),
+        },
+    );
+    let guard = SessionGuard {
+        sessions,
+        id: session_id.clone(),
+        tasks: vec![server_task, writer_task, reader_task],
+    };
+
+    let endpoint = sse_event(
+        "endpoint",
+        &format!("{}?sessionId={}", MESSAGES_PATH, session_id),
+    );
+    let stream = futures::stream::once(async move { endpoint })
+        .chain(ReceiverStream::new(event_rx))
+        .map(move |event| {
+            // The stream owns the guard, so a client disconnect ends the session.
+            let _ = &guard;
+            Ok::<Bytes, Infallible>(event)
+        });
+
+    (
+        [(CONTENT_TYPE, HeaderValue::from_static(SSE_CONTENT_TYPE))],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+async fn post_message(
+    State(state): State<DaemonHttpState>,
+    Query(query): Query<MessageQuery>,
+    grant: Option<Extension<ApiTokenGrant>>,
+    body: Bytes,
+) -> Response {
+    let token_id = grant
+        .as_ref()
+        .map(|Extension(grant)| grant.token().id.clone());
+    let Some(inbound) = state
+        .mcp_sse_sessions
+        .inbound(&query.session_id, token_id.as_deref())
+    else {
+        return (StatusCode::NOT_FOUND, "MCP session not found").into_response();
+    };
+
+    let Some(message) = frame_message(&body) else {
+        return (StatusCode::BAD_REQUEST, "Body must be a JSON-RPC message").into_response();
+    };
+    if inbound.send(message).await.is_err() {
+        return (StatusCode::GONE, "MCP session closed").into_response();
+    }
+    StatusCode::ACCEPTED.into_response()
+}
+
+/// Re-encode a JSON-RPC message on a single line for the newline-delimited pipe.
+fn frame_message(body: &[u8]) -> Option<Vec<u8>> {
+    let value: Value = serde_json::from_slice(body).ok()?;
+    if !value.is_object() && !value.is_array() {
+        return None;
+    }
+    let mut message = serde_json::to_vec(&value).ok()?;
+    message.push(b'\n');
+    Some(message)
+}
+
+fn sse_event(event: &str, data: &str) -> Bytes {
+    Bytes::from(format!("event: {}\ndata: {}\n\n", event, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_message_keeps_messages_on_one_line() {
+        let framed =
+            frame_message(b"{\n  \"jsonrpc\": \"2.0\",\n  \"method\": \"ping\"\n}").unwrap();
+        assert_eq!(framed, b"{\"jsonrpc\":\"2.0\",\"method\":\"ping\"}\n");
+        assert!(frame_message(b"not json").is_none());
+        assert!(frame_message(b"42").is_none());
+    }
+
+    #[test]
+    fn sse_event_formats_named_event() {
+        assert_eq!(
+            sse_event("endpoint", "/mcp/messages?sessionId=abc"),
+            Bytes::from_static(b"event: endpoint\ndata: /mcp/messages?sessionId=abc\n\n")
+        );
+    }
+}
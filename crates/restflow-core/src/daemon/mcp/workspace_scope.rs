@@ -0,0 +1,75 @@
+//! Workspace isolation for API tokens bound to a user.
+//!
+//! A token whose scope names a `user_id` acts through that user's
+//! [`WorkspaceAccess`]; IPC requests then run via
+//! [`crate::daemon::IpcServer::process_in_workspace`], which owns the
+//! ownership checks. This module only resolves the caller's workspace and
+//! closes routes that have no workspace-aware variant.
+
+use super::api_auth::ApiCaller;
+use super::{DaemonHttpState, api_token_error_response};
+use crate::services::api_tokens::{ApiTokenError, ApiTokenGrant};
+use crate::services::workspace::{WorkspaceAccess, WorkspaceScope, WorkspaceService};
+use axum::Router;
+use axum::extract::Request;
+use axum::middleware::{self, Next};
+
+/// Resolve the workspace access of a token. Unbound tokens return `None`.
+pub(super) fn token_access(
+    state: &DaemonHttpState,
+    grant: &ApiTokenGrant,
+) -> Result<Option<WorkspaceAccess>, ApiTokenError> {
+    let Some(user_id) = &grant.scope().user_id else {
+        return Ok(None);
+    };
+    let service = WorkspaceService::from_storage(state.core.storage.as_ref());
+    if service.get_user(user_id)?.is_none() {
+        return Err(ApiTokenError::Forbidden(format!(
+            "act as unknown user '{user_id}'"
+        )));
+    }
+    Ok(Some(WorkspaceAccess::for_user(
+        state.core.clone(),
+        user_id,
+    )?))
+}
+
+/// Resolve the workspace a token acts in. Unbound tokens return `None`.
+pub(super) fn token_workspace(
+    state: &DaemonHttpState,
+    grant: &ApiTokenGrant,
+) -> Result<Option<WorkspaceScope>, ApiTokenError> {
+    Ok(token_access(state, grant)?.map(|access| access.scope().clone()))
+}
+
+/// Resolve the workspace access of an HTTP API caller. Local callers see
+/// everything.
+pub(super) fn caller_access(
+    state: &DaemonHttpState,
+    caller: &ApiCaller,
+) -> Result<Option<WorkspaceAccess>, ApiTokenError> {
+    match caller {
+        ApiCaller::Local => Ok(None),
+        ApiCaller::Token(grant) => token_access(state, grant),
+    }
+}
+
+/// Refuse every route in `router` to tokens bound to a user.
+///
+/// For endpoints that reach data outside any workspace, such as the skill
+/// marketplace or session conversion.
+pub(super) fn without_workspace_tokens(router: Router<DaemonHttpState>) -> Router<DaemonHttpState> {
+    router.route_layer(middleware::from_fn(
+        |request: Request, next: Next| async move {
+            let bound = matches!(
+                request.extensions().get::<ApiCaller>(),
+                Some(ApiCaller::Token(grant)) if grant.scope().user_id.is_some()
+            );
+            if bound {
+                let error = ApiTokenError::Forbidden("use this endpoint".to_string());
+                return api_token_error_response(&error);
+            }
+            next.run(request).await
+        },
+    ))
+}
@@ -1,4 +1,5 @@
 mod background_events;
+mod components;
 mod core_access;
 mod health;
 mod ipc_client;
@@ -10,11 +11,14 @@ mod mcp;
 mod process;
 pub mod recovery;
 pub mod request_mapper;
+mod service;
 pub(crate) mod session_events;
+mod stream_buffer;
 mod supervisor;
 pub(crate) mod tool_result_mapper;
 
 pub use background_events::{publish_background_event, subscribe_background_events};
+pub use components::{daemon_components, set_daemon_component};
 pub use core_access::CoreAccess;
 pub use health::{HealthChecker, HealthStatus, check_health};
 pub use ipc_client::{IpcClient, is_daemon_available};
@@ -30,6 +34,15 @@ pub use launcher::{
 pub use logging::{LogPaths, open_daemon_log_append, resolve_log_paths};
 pub use mcp::run_mcp_http_server;
 pub use process::{DaemonConfig, ProcessManager};
-pub use restflow_contracts::{ToolDefinition, ToolExecutionResult};
+pub use restflow_contracts::{
+    DaemonComponentState, DaemonComponentStatus, ToolDefinition, ToolExecutionResult,
+};
+pub use service::{
+    ServiceInstallation, ServiceManager, ServiceSpec, install_service, uninstall_service,
+};
 pub use session_events::{ChatSessionEvent, publish_session_event, subscribe_session_events};
+pub use stream_buffer::{
+    DEFAULT_STREAM_BUFFER_CAPACITY, StreamBackpressureTotals, StreamBufferStats, StreamClosed,
+    StreamReceiver, StreamSender, stream_backpressure_totals, stream_channel,
+};
 pub use supervisor::{Supervisor, SupervisorConfig};
@@ -200,7 +200,7 @@ fn daemon_working_dir() -> Result<PathBuf> {
     paths::ensure_restflow_dir()
 }
 
-fn build_daemon_child_path() -> Option<OsString> {
+pub(super) fn build_daemon_child_path() -> Option<OsString> {
     let mut entries: Vec<PathBuf> = std::env::var_os("PATH")
         .map(|value| std::env::split_paths(&value).collect())
         .unwrap_or_default();
@@ -0,0 +1,477 @@
+//! OS service definitions for running the daemon headless.
+//!
+//! Installing registers `restflow daemon start --foreground` with the
+//! platform service manager so the daemon starts at login and is restarted
+//! after a crash: a launchd agent on macOS, a systemd user unit on Linux and a
+//! Task Scheduler logon task on Windows. A clean `restflow daemon stop` is not
+//! treated as a crash.
+
+use super::process::build_daemon_child_path;
+use crate::paths;
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::warn;
+
+/// launchd label and plist name
+pub const LAUNCHD_LABEL: &str = "com.restflow.daemon";
+/// systemd user unit name
+pub const SYSTEMD_UNIT: &str = "restflow.service";
+/// Task Scheduler task name
+pub const WINDOWS_TASK_NAME: &str = "RestFlow Daemon";
+
+const DESCRIPTION: &str = "RestFlow AI Agent Daemon";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceManager {
+    Launchd,
+    Systemd,
+    TaskScheduler,
+}
+
+impl ServiceManager {
+    /// Service manager of the running platform
+    pub fn current() -> Result<Self> {
+        if cfg!(target_os = "macos") {
+            Ok(Self::Launchd)
+        } else if cfg!(target_os = "windows") {
+            Ok(Self::TaskScheduler)
+        } else if cfg!(target_os = "linux") {
+            Ok(Self::Systemd)
+        } else {
+            Err(anyhow!(
+                "Service install is supported on macOS, Linux and Windows"
+            ))
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Launchd => "launchd",
+            Self::Systemd => "systemd",
+            Self::TaskScheduler => "Task Scheduler",
+        }
+    }
+
+    /// Where the service definition is written
+    pub fn definition_path(&self) -> Result<PathBuf> {
+        match self {
+            Self::Launchd => Ok(dirs::home_dir()
+                .ok_or_else(|| anyhow!("Cannot determine home directory"))?
+                .join("Library")
+                .join("LaunchAgents")
+                .join(format!("{LAUNCHD_LABEL}.plist"))),
+            Self::Systemd => Ok(dirs::config_dir()
+                .ok_or_else(|| anyhow!("Cannot determine config directory"))?
+                .join("systemd")
+                .join("user")
+                .join(SYSTEMD_UNIT)),
+            // schtasks keeps its own copy; the file is kept for inspection.
+            Self::TaskScheduler => Ok(paths::resolve_restflow_dir()?.join("daemon-task.xml")),
+        }
+    }
+
+    /// Render the service definition for `spec`
+    pub fn render(&self, spec: &ServiceSpec) -> String {
+        match self {
+            Self::Launchd => render_launchd(spec),
+            Self::Systemd => render_systemd(spec),
+            Self::TaskScheduler => render_task_scheduler(spec),
+        }
+    }
+
+    fn encode(&self, definition: &str) -> Vec<u8> {
+        match self {
+            // schtasks /XML expects UTF-16 with a byte order mark.
+            Self::TaskScheduler => std::iter::once(0xFEFF)
+                .chain(definition.encode_utf16())
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+            Self::Launchd | Self::Systemd => definition.as_bytes().to_vec(),
+        }
+    }
+
+    fn activate_commands(&self, path: &Path) -> Vec<Vec<String>> {
+        let path = path.to_string_lossy().into_owned();
+        match self {
+            Self::Launchd => vec![command(&["launchctl", "load", "-w", &path])],
+            Self::Systemd => vec![
+                command(&["systemctl", "--user", "daemon-reload"]),
+                command(&["systemctl", "--user", "enable", "--now", SYSTEMD_UNIT]),
+            ],
+            Self::TaskScheduler => vec![
+                command(&[
+                    "schtasks",
+                    "/Create",
+                    "/TN",
+                    WINDOWS_TASK_NAME,
+                    "/XML",
+                    &path,
+                    "/F",
+                ]),
+                command(&["schtasks", "/Run", "/TN", WINDOWS_TASK_NAME]),
+            ],
+        }
+    }
+
+    fn deactivate_commands(&self, path: &Path) -> Vec<Vec<String>> {
+        let path = path.to_string_lossy().into_owned();
+        match self {
+            Self::Launchd => vec![command(&["launchctl", "unload", "-w", &path])],
+            Self::Systemd => vec![command(&[
+                "systemctl",
+                "--user",
+                "disable",
+                "--now",
+                SYSTEMD_UNIT,
+            ])],
+            Self::TaskScheduler => vec![
+                command(&["schtasks", "/End", "/TN", WINDOWS_TASK_NAME]),
+                command(&["schtasks", "/Delete", "/TN", WINDOWS_TASK_NAME, "/F"]),
+            ],
+        }
+    }
+}
+
+/// How the installed service launches the daemon
+#[derive(Debug, Clone)]
+pub struct ServiceSpec {
+    pub executable: PathBuf,
+    pub mcp_port: Option<u16>,
+    /// Working directory and `RESTFLOW_DIR` of the service
+    pub restflow_dir: PathBuf,
+    pub log_file: PathBuf,
+    /// `PATH` for tools the agents run; service managers start with a minimal one
+    pub path_env: Option<String>,
+}
+
+impl ServiceSpec {
+    /// Spec for the running executable and the current RestFlow directory
+    pub fn current(mcp_port: Option<u16>) -> Result<Self> {
+        Ok(Self {
+            executable: std::env::current_exe()?,
+            mcp_port,
+            restflow_dir: paths::ensure_restflow_dir()?,
+            log_file: paths::daemon_log_path()?,
+            path_env: build_daemon_child_path().map(|path| path.to_string_lossy().into_owned()),
+        })
+    }
+
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "daemon".to_string(),
+            "start".to_string(),
+            "--foreground".to_string(),
+        ];
+        if let Some(port) = self.mcp_port {
+            args.push("--mcp-port".to_string());
+            args.push(port.to_string());
+        }
+        args
+    }
+
+    fn environment(&self) -> Vec<(&'static str, String)> {
+        let mut env = vec![("RESTFLOW_DIR", self.restflow_dir.display().to_string())];
+        if let Some(path) = &self.path_env {
+            env.push(("PATH", path.clone()));
+        }
+        env
+    }
+}
+
+/// Result of installing the service
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceInstallation {
+    pub manager: ServiceManager,
+    pub path: PathBuf,
+    /// Whether the service manager was asked to start the daemon now
+    pub started: bool,
+}
+
+/// Write the service definition and, when `start` is set, enable and start it
+pub fn install_service(spec: &ServiceSpec, start: bool) -> Result<ServiceInstallation> {
+    let manager = ServiceManager::current()?;
+    let path = manager.definition_path()?;
+    if path.exists() && start {
+        // Reinstall: drop the old registration so the new definition is loaded.
+        run_commands(&manager.deactivate_commands(&path), false)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, manager.encode(&manager.render(spec)))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    if start {
+        run_commands(&manager.activate_commands(&path), true)?;
+    }
+    Ok(ServiceInstallation {
+        manager,
+        path,
+        started: start,
+    })
+}
+
+/// Stop and unregister the service. Returns the removed definition, if any.
+pub fn uninstall_service() -> Result<Option<PathBuf>> {
+    let manager = ServiceManager::current()?;
+    let path = manager.definition_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    run_commands(&manager.deactivate_commands(&path), false)?;
+    std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    if manager == ServiceManager::Systemd {
+        run_commands(&[command(&["systemctl", "--user", "daemon-reload"])], false)?;
+    }
+    Ok(Some(path))
+}
+
+fn command(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Run service manager commands in order. With `required`, the first failure is
+/// returned; otherwise failures are logged and skipped.
+fn run_commands(commands: &[Vec<String>], required: bool) -> Result<()> {
+    for args in commands {
+        let line = args.join(" ");
+        let result = Command::new(&args[0])
+            .args(&args[1..])
+            .output()
+            .map_err(anyhow::Error::from)
+            .and_then(|output| {
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    Err(anyhow!(
+                        "exited with {}: {}",
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ))
+                }
+            });
+        match result {
+            Ok(()) => {}
+            Err(err) if required => return Err(err.context(format!("`{line}` failed"))),
+            Err(err) => warn!(command = %line, error = %err, "Service command failed"),
+        }
+    }
+    Ok(())
+}
+
+fn render_launchd(spec: &ServiceSpec) -> String {
+    let mut arguments = format!(
+        "      <string>{}</string>\n",
+        xml_escape(&spec.executable.display().to_string())
+    );
+    for arg in spec.args() {
+        arguments.push_str(&format!("      <string>{}</string>\n", xml_escape(&arg)));
+    }
+    let mut environment = String::new();
+    for (key, value) in spec.environment() {
+        environment.push_str(&format!(
+            "      <key>{key}</key>\n      <string>{}</string>\n",
+            xml_escape(&value)
+        ));
+    }
+    let log_file = xml_escape(&spec.log_file.display().to_string());
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+  <dict>
+    <key>Label</key>
+    <string>{LAUNCHD_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>WorkingDirectory</key>
+    <string>{working_dir}</string>
+    <key>EnvironmentVariables</key>
+    <dict>
+{environment}    </dict>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+      <key>SuccessfulExit</key>
+      <false/>
+    </dict>
+    <key>StandardOutPath</key>
+    <string>{log_file}</string>
+    <key>StandardErrorPath</key>
+    <string>{log_file}</string>
+  </dict>
+</plist>
+"#,
+        working_dir = xml_escape(&spec.restflow_dir.display().to_string()),
+    )
+}
+
+fn render_systemd(spec: &ServiceSpec) -> String {
+    let exec_start = std::iter::once(spec.executable.display().to_string())
+        .chain(spec.args())
+        .map(|arg| systemd_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let environment = spec
+        .environment()
+        .into_iter()
+        .map(|(key, value)| format!("Environment={}\n", systemd_quote(&format!("{key}={value}"))))
+        .collect::<String>();
+
+    format!(
+        "[Unit]
+Description={DESCRIPTION}
+After=network-online.target
+
+[Service]
+Type=simple
+WorkingDirectory={working_dir}
+{environment}ExecStart={exec_start}
+Restart=on-failure
+RestartSec=5
+
+[Install]
+WantedBy=default.target
+",
+        working_dir = systemd_quote(&spec.restflow_dir.display().to_string()),
+    )
+}
+
+/// Task Scheduler cannot set environment variables; the logon task runs as the
+/// user and inherits their environment, including `RESTFLOW_DIR` when set.
+fn render_task_scheduler(spec: &ServiceSpec) -> String {
+    let arguments = spec
+        .args()
+        .iter()
+        .map(|arg| xml_escape(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Description>{DESCRIPTION}</Description>
+  </RegistrationInfo>
+  <Triggers>
+    <LogonTrigger>
+      <Enabled>true</Enabled>
+    </LogonTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author">
+      <LogonType>InteractiveToken</LogonType>
+      <RunLevel>LeastPrivilege</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+    <RestartOnFailure>
+      <Interval>PT1M</Interval>
+      <Count>3</Count>
+    </RestartOnFailure>
+    <Hidden>true</Hidden>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>{command}</Command>
+      <Arguments>{arguments}</Arguments>
+      <WorkingDirectory>{working_dir}</WorkingDirectory>
+    </Exec>
+  </Actions>
+</Task>
+"#,
+        command = xml_escape(&spec.executable.display().to_string()),
+        working_dir = xml_escape(&spec.restflow_dir.display().to_string()),
+    )
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Quote a systemd unit value when it contains whitespace or quotes, and
+/// escape `%` so it is not read as a specifier
+fn systemd_quote(value: &str) -> String {
+    let value = value.replace('%', "%%");
+    if value
+        .chars()
+        .any(|c| c.is_whitespace() || c == '"' || c == '\\')
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> ServiceSpec {
+        ServiceSpec {
+            executable: PathBuf::from("/opt/rest flow/bin/restflow"),
+            mcp_port: Some(9000),
+            restflow_dir: PathBuf::from("/home/dev/.restflow"),
+            log_file: PathBuf::from("/home/dev/.restflow/logs/daemon.log"),
+            path_env: Some("/usr/local/bin:/usr/bin".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_render_systemd_unit() {
+        let unit = ServiceManager::Systemd.render(&spec());
+        assert!(unit.contains(
+            "ExecStart=\"/opt/rest flow/bin/restflow\" daemon start --foreground --mcp-port 9000\n"
+        ));
+        assert!(unit.contains("Environment=RESTFLOW_DIR=/home/dev/.restflow\n"));
+        assert!(unit.contains("Environment=PATH=/usr/local/bin:/usr/bin\n"));
+        assert!(unit.contains("Restart=on-failure"));
+        assert!(unit.contains("WantedBy=default.target"));
+    }
+
+    #[test]
+    fn test_render_launchd_plist() {
+        let mut spec = spec();
+        spec.mcp_port = None;
+        spec.restflow_dir = PathBuf::from("/Users/dev/R&D/.restflow");
+        let plist = ServiceManager::Launchd.render(&spec);
+        assert!(plist.contains("<string>com.restflow.daemon</string>"));
+        assert!(plist.contains("<string>--foreground</string>\n    </array>"));
+        assert!(!plist.contains("--mcp-port"));
+        assert!(plist.contains("<string>/Users/dev/R&amp;D/.restflow</string>"));
+        assert!(plist.contains("<key>SuccessfulExit</key>"));
+    }
+
+    #[test]
+    fn test_render_task_scheduler_xml() {
+        let manager = ServiceManager::TaskScheduler;
+        let xml = manager.render(&spec());
+        assert!(xml.contains("<Command>/opt/rest flow/bin/restflow</Command>"));
+        assert!(xml.contains("<Arguments>daemon start --foreground --mcp-port 9000</Arguments>"));
+
+        let encoded = manager.encode(&xml);
+        assert_eq!(&encoded[..4], &[0xFF, 0xFE, b'<', 0]);
+    }
+
+    #[test]
+    fn test_systemd_quote() {
+        assert_eq!(systemd_quote("plain"), "plain");
+        assert_eq!(systemd_quote("a b"), "\"a b\"");
+        assert_eq!(systemd_quote("50%"), "50%%");
+        assert_eq!(systemd_quote(r#"say "hi""#), r#""say \"hi\"""#);
+    }
+}
@@ -22,6 +22,18 @@ pub enum ChatSessionEvent {
     Deleted {
         session_id: String,
     },
+    /// A tool call of a run in this session is waiting for user approval.
+    ToolApprovalRequested {
+        session_id: String,
+        approval_id: String,
+        tool_name: String,
+    },
+    /// A pending tool call was approved or rejected.
+    ToolApprovalResolved {
+        session_id: String,
+        approval_id: String,
+        approved: bool,
+    },
 }
 
 fn stream_sender() -> &'static broadcast::Sender<ChatSessionEvent> {
@@ -0,0 +1,473 @@
+//! Bounded, priority-aware buffering for daemon stream frames.
+//!
+//! Streams opened by the daemon used to sit on unbounded channels, so a slow
+//! client let frames pile up without limit. A [`StreamSender`] never blocks
+//! the producer; once the queue reaches capacity it sheds load by priority:
+//!
+//! - text deltas coalesce into the pending `Data` frame,
+//! - bus events are dropped oldest-first and replayed as a `Resync` snapshot
+//!   once the client catches up,
+//! - tool calls, tool results, acks and terminal frames are never dropped.
+
+use super::ChatSessionEvent;
+use super::ipc_protocol::{IpcStreamEvent, StreamFrame};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+/// Frames a stream may queue before low-priority frames are shed.
+pub const DEFAULT_STREAM_BUFFER_CAPACITY: usize = 256;
+
+/// Backpressure counters for one stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StreamBufferStats {
+    /// Frames currently waiting for the client.
+    pub queued: usize,
+    /// Largest queue length observed.
+    pub high_watermark: usize,
+    /// Text deltas merged into a pending frame.
+    pub coalesced: u64,
+    /// Event frames dropped while the client lagged.
+    pub dropped: u64,
+    /// Must-deliver frames queued beyond capacity.
+    pub overflowed: u64,
+    /// Resync snapshots sent.
+    pub resyncs: u64,
+}
+
+/// Daemon-wide backpressure totals across all streams.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StreamBackpressureTotals {
+    pub coalesced: u64,
+    pub dropped: u64,
+    pub overflowed: u64,
+    pub resyncs: u64,
+}
+
+static TOTAL_COALESCED: AtomicU64 = AtomicU64::new(0);
+static TOTAL_DROPPED: AtomicU64 = AtomicU64::new(0);
+static TOTAL_OVERFLOWED: AtomicU64 = AtomicU64::new(0);
+static TOTAL_RESYNCS: AtomicU64 = AtomicU64::new(0);
+
+/// Snapshot of backpressure totals since the daemon started.
+pub fn stream_backpressure_totals() -> StreamBackpressureTotals {
+    StreamBackpressureTotals {
+        coalesced: TOTAL_COALESCED.load(Ordering::Relaxed),
+        dropped: TOTAL_DROPPED.load(Ordering::Relaxed),
+        overflowed: TOTAL_OVERFLOWED.load(Ordering::Relaxed),
+        resyncs: TOTAL_RESYNCS.load(Ordering::Relaxed),
+    }
+}
+
+/// Returned by [`StreamSender::send`] when the client went away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamClosed;
+
+impl std::fmt::Display for StreamClosed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("stream receiver closed")
+    }
+}
+
+impl std::error::Error for StreamClosed {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FramePriority {
+    /// Never dropped.
+    Critical,
+    /// Merged into the pending frame of the same kind.
+    Coalescible,
+    /// Dropped oldest-first under pressure, recovered by a resync snapshot.
+    Droppable,
+}
+
+fn frame_priority(frame: &StreamFrame) -> FramePriority {
+    match frame {
+        StreamFrame::Data { .. } => FramePriority::Coalescible,
+        StreamFrame::Event { .. } => FramePriority::Droppable,
+        _ => FramePriority::Critical,
+    }
+}
+
+/// Subject an event describes; newer events for a subject supersede older ones.
+fn event_subject(event: &IpcStreamEvent) -> String {
+    match event {
+        IpcStreamEvent::BackgroundAgent(event) => format!("task:{}", event.task_id),
+        IpcStreamEvent::Session(event) => match event {
+            ChatSessionEvent::Created { session_id }
+            | ChatSessionEvent::Updated { session_id }
+            | ChatSessionEvent::MessageAdded { session_id, .. }
+            | ChatSessionEvent::Deleted { session_id } => format!("session:{session_id}"),
+            ChatSessionEvent::ToolApprovalRequested { approval_id, .. }
+            | ChatSessionEvent::ToolApprovalResolved { approval_id, .. } => {
+                format!("approval:{approval_id}")
+            }
+        },
+    }
+}
+
+#[derive(Default)]
+struct PendingResync {
+    dropped: u64,
+    latest: BTreeMap<String, IpcStreamEvent>,
+}
+
+struct State {
+    queue: VecDeque<StreamFrame>,
+    senders: usize,
+    receiver_alive: bool,
+    stats: StreamBufferStats,
+    resync: Option<PendingResync>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    notify: Notify,
+    capacity: usize,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Create a bounded stream buffer holding up to `capacity` frames.
+pub fn stream_channel(capacity: usize) -> (StreamSender, StreamReceiver) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            senders: 1,
+            receiver_alive: true,
+            stats: StreamBufferStats::default(),
+            resync: None,
+        }),
+        notify: Notify::new(),
+        capacity: capacity.max(1),
+    });
+    (
+        StreamSender {
+            shared: shared.clone(),
+        },
+        StreamReceiver { shared },
+    )
+}
+
+/// Producer half of a stream buffer.
+pub struct StreamSender {
+    shared: Arc<Shared>,
+}
+
+impl StreamSender {
+    /// Queue a frame without blocking, shedding low-priority frames when the
+    /// buffer is full.
+    pub fn send(&self, frame: StreamFrame) -> Result<(), StreamClosed> {
+        let capacity = self.shared.capacity;
+        let mut state = self.shared.lock();
+        if !state.receiver_alive {
+            return Err(StreamClosed);
+        }
+
+        match (frame_priority(&frame), frame) {
+            (FramePriority::Coalescible, StreamFrame::Data { content }) => {
+                if let Some(StreamFrame::Data { content: pending }) = state.queue.back_mut() {
+                    pending.push_str(&content);
+                    state.stats.coalesced += 1;
+                    TOTAL_COALESCED.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    push_frame(&mut state, StreamFrame::Data { content }, capacity);
+                }
+            }
+            (FramePriority::Droppable, StreamFrame::Event { event }) => {
+                let subject = event_subject(&event);
+                if let Some(resync) = state.resync.as_mut()
+                    && resync.latest.contains_key(&subject)
+                {
+                    resync.latest.insert(subject.clone(), event.clone());
+                }
+                if state.queue.len() >= capacity {
+                    shed_event(&mut state, subject, event);
+                } else {
+                    push_frame(&mut state, StreamFrame::Event { event }, capacity);
+                }
+            }
+            (_, frame) => push_frame(&mut state, frame, capacity),
+        }
+        drop(state);
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        !self.shared.lock().receiver_alive
+    }
+
+    pub fn stats(&self) -> StreamBufferStats {
+        self.shared.lock().stats
+    }
+}
+
+impl Clone for StreamSender {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl Drop for StreamSender {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        let last = state.senders == 0;
+        drop(state);
+        if last {
+            self.shared.notify.notify_one();
+        }
+    }
+}
+
+fn push_frame(state: &mut State, frame: StreamFrame, capacity: usize) {
+    if state.queue.len() >= capacity {
+        state.stats.overflowed += 1;
+        TOTAL_OVERFLOWED.fetch_add(1, Ordering::Relaxed);
+    }
+    state.queue.push_back(frame);
+    state.stats.queued = state.queue.len();
+    state.stats.high_watermark = state.stats.high_watermark.max(state.queue.len());
+}
+
+/// Make room for `event` by dropping the oldest queued event, or drop
+/// `event` itself when only must-deliver frames are queued.
+fn shed_event(state: &mut State, subject: String, event: IpcStreamEvent) {
+    if state.resync.is_none() {
+        warn!(
+            queued = state.queue.len(),
+            "Stream client is lagging; dropping event frames until it catches up"
+        );
+    }
+    let oldest_event = state
+        .queue
+        .iter()
+        .position(|frame| matches!(frame, StreamFrame::Event { .. }));
+    let resync = state.resync.get_or_insert_with(PendingResync::default);
+    resync.dropped += 1;
+
+    match oldest_event.and_then(|index| state.queue.remove(index)) {
+        Some(StreamFrame::Event { event: removed }) => {
+            let removed_subject = event_subject(&removed);
+            // A newer queued event for the same subject supersedes the removed one.
+            let latest = state
+                .queue
+                .iter()
+                .rev()
+                .find_map(|frame| match frame {
+                    StreamFrame::Event { event } if event_subject(event) == removed_subject => {
+                        Some(event.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap_or(removed);
+            resync.latest.insert(removed_subject, latest);
+            state.queue.push_back(StreamFrame::Event { event });
+        }
+        _ => {
+            resync.latest.insert(subject, event);
+        }
+    }
+    state.stats.dropped += 1;
+    state.stats.queued = state.queue.len();
+    TOTAL_DROPPED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Consumer half of a stream buffer.
+pub struct StreamReceiver {
+    shared: Arc<Shared>,
+}
+
+impl StreamReceiver {
+    /// Receive the next frame, or `None` once every sender is gone and the
+    /// queue is drained.
+    pub async fn recv(&mut self) -> Option<StreamFrame> {
+        loop {
+            let notified = self.shared.notify.notified();
+            {
+                let mut state = self.shared.lock();
+                if let Some(frame) = state.queue.pop_front() {
+                    self.schedule_resync(&mut state);
+                    state.stats.queued = state.queue.len();
+                    return Some(frame);
+                }
+                if state.senders == 0 {
+                    return None;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Receive a frame if one is queued, without waiting.
+    pub fn try_recv(&mut self) -> Option<StreamFrame> {
+        let mut state = self.shared.lock();
+        let frame = state.queue.pop_front()?;
+        self.schedule_resync(&mut state);
+        state.stats.queued = state.queue.len();
+        Some(frame)
+    }
+
+    pub fn stats(&self) -> StreamBufferStats {
+        self.shared.lock().stats
+    }
+
+    /// Queue the resync snapshot once the client has drained half the buffer.
+    /// Every event it supersedes is already ahead of it in the queue.
+    fn schedule_resync(&self, state: &mut State) {
+        if state.queue.len() > self.shared.capacity / 2 {
+            return;
+        }
+        let Some(resync) = state.resync.take() else {
+            return;
+        };
+        state.queue.push_back(StreamFrame::Resync {
+            dropped: resync.dropped,
+            events: resync.latest.into_values().collect(),
+        });
+        state.stats.resyncs += 1;
+        TOTAL_RESYNCS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for StreamReceiver {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver_alive = false;
+        state.queue.clear();
+        let stats = state.stats;
+        if stats.coalesced > 0 || stats.dropped > 0 || stats.overflowed > 0 {
+            debug!(
+                high_watermark = stats.high_watermark,
+                coalesced = stats.coalesced,
+                dropped = stats.dropped,
+                overflowed = stats.overflowed,
+                resyncs = stats.resyncs,
+                "Stream closed after backpressure"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::TaskStreamEvent;
+    use crate::runtime::background_agent::StreamEventKind;
+
+    fn session_event(session_id: &str) -> StreamFrame {
+        StreamFrame::Event {
+            event: IpcStreamEvent::Session(ChatSessionEvent::Updated {
+                session_id: session_id.to_string(),
+            }),
+        }
+    }
+
+    fn task_progress(task_id: &str, percent: u8) -> StreamFrame {
+        StreamFrame::Event {
+            event: IpcStreamEvent::BackgroundAgent(TaskStreamEvent::progress(
+                task_id.to_string(),
+                "step",
+                Some(percent),
+                None,
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn text_deltas_coalesce_while_pending() {
+        let (tx, mut rx) = stream_channel(4);
+        for chunk in ["Hel", "lo", " world"] {
+            tx.send(StreamFrame::Data {
+                content: chunk.to_string(),
+            })
+            .unwrap();
+        }
+        tx.send(StreamFrame::Done { total_tokens: None }).unwrap();
+        drop(tx);
+
+        match rx.recv().await {
+            Some(StreamFrame::Data { content }) => assert_eq!(content, "Hello world"),
+            other => panic!("expected data frame, got {other:?}"),
+        }
+        assert!(matches!(rx.recv().await, Some(StreamFrame::Done { .. })));
+        assert!(rx.recv().await.is_none());
+        assert_eq!(rx.stats().coalesced, 2);
+    }
+
+    #[tokio::test]
+    async fn tool_results_are_never_dropped() {
+        let (tx, mut rx) = stream_channel(2);
+        for index in 0..5 {
+            tx.send(StreamFrame::ToolResult {
+                id: format!("call-{index}"),
+                result: "x".repeat(1024),
+                success: true,
+            })
+            .unwrap();
+        }
+        drop(tx);
+
+        let mut received = 0;
+        while let Some(frame) = rx.recv().await {
+            assert!(matches!(frame, StreamFrame::ToolResult { .. }));
+            received += 1;
+        }
+        assert_eq!(received, 5);
+        assert_eq!(rx.stats().overflowed, 3);
+    }
+
+    #[tokio::test]
+    async fn lagging_client_gets_resync_with_latest_events() {
+        let (tx, mut rx) = stream_channel(2);
+        tx.send(task_progress("task-a", 10)).unwrap();
+        tx.send(session_event("s1")).unwrap();
+        tx.send(task_progress("task-a", 50)).unwrap();
+        tx.send(task_progress("task-a", 90)).unwrap();
+        drop(tx);
+
+        let mut frames = Vec::new();
+        while let Some(frame) = rx.recv().await {
+            frames.push(frame);
+        }
+        assert_eq!(rx.stats().dropped, 2);
+
+        let Some(StreamFrame::Resync { dropped, events }) = frames.last() else {
+            panic!("expected trailing resync frame, got {frames:?}");
+        };
+        assert_eq!(*dropped, 2);
+        let subjects: Vec<String> = events.iter().map(event_subject).collect();
+        assert_eq!(subjects, vec!["session:s1", "task:task-a"]);
+        match &events[1] {
+            IpcStreamEvent::BackgroundAgent(TaskStreamEvent {
+                kind: StreamEventKind::Progress { percent, .. },
+                ..
+            }) => assert_eq!(*percent, Some(90)),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_fails_after_receiver_drops() {
+        let (tx, rx) = stream_channel(4);
+        drop(rx);
+        assert!(tx.is_closed());
+        assert_eq!(
+            tx.send(StreamFrame::Done { total_tokens: None }),
+            Err(StreamClosed)
+        );
+    }
+}
@@ -5,6 +5,8 @@ use crate::models::{Hook, HookAction, HookContext, HookFilter, TaskSchedule, Tas
 use crate::storage::{BackgroundAgentStorage, HookStorage};
 use anyhow::Result;
 use async_trait::async_trait;
+use restflow_traits::SecretResolver;
+use restflow_traits::secret_ref::resolve_secret_refs;
 use std::collections::HashMap;
 use std::process::Stdio;
 use std::sync::Arc;
@@ -71,6 +73,7 @@ impl HookTaskScheduler for TaskHookScheduler {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })?;
         Ok(())
     }
@@ -87,6 +90,7 @@ pub struct HookExecutor {
     task_scheduler: Option<Arc<dyn HookTaskScheduler>>,
     policy: HookExecutionPolicyConfig,
     http_client: reqwest::Client,
+    secret_resolver: Option<SecretResolver>,
 }
 
 impl HookExecutor {
@@ -99,6 +103,7 @@ impl HookExecutor {
             task_scheduler: None,
             policy: HookExecutionPolicyConfig::default(),
             http_client: reqwest::Client::new(),
+            secret_resolver: None,
         }
     }
 
@@ -111,6 +116,7 @@ impl HookExecutor {
             task_scheduler: None,
             policy: HookExecutionPolicyConfig::default(),
             http_client: reqwest::Client::new(),
+            secret_resolver: None,
         }
     }
 
@@ -124,6 +130,12 @@ impl HookExecutor {
         self
     }
 
+    /// Resolve `${secret:NAME}` references in webhook URLs and headers.
+    pub fn with_secret_resolver(mut self, resolver: SecretResolver) -> Self {
+        self.secret_resolver = Some(resolver);
+        self
+    }
+
     /// Enable or disable policy enforcement for hook actions.
     /// Default is disabled (open behavior).
     pub fn with_policy_enforcement(mut self, enabled: bool) -> Self {
@@ -211,10 +223,16 @@ impl HookExecutor {
                 let method =
                     reqwest::Method::from_bytes(method.as_deref().unwrap_or("POST").as_bytes())?;
 
+                let resolve = |name: &str| {
+                    self.secret_resolver
+                        .as_ref()
+                        .and_then(|resolver| resolver(name))
+                };
+                let url = resolve_secret_refs(url, resolve)?;
                 let mut request = self.http_client.request(method, url);
                 if let Some(headers) = headers {
                     for (key, value) in headers {
-                        request = request.header(key, value);
+                        request = request.header(key, resolve_secret_refs(value, resolve)?);
                     }
                 }
 
@@ -375,6 +393,7 @@ fn parse_channel_type(input: &str) -> Result<ChannelType> {
         "telegram" => Ok(ChannelType::Telegram),
         "discord" => Ok(ChannelType::Discord),
         "slack" => Ok(ChannelType::Slack),
+        "matrix" => Ok(ChannelType::Matrix),
         "email" => Ok(ChannelType::Email),
         "webhook" => Ok(ChannelType::Webhook),
         _ => anyhow::bail!("Unsupported channel type: {}", input),
@@ -386,6 +405,7 @@ mod tests {
     use super::*;
     use crate::channel::{Channel, OutboundMessage};
     use crate::models::HookEvent;
+    use std::collections::BTreeMap;
     use std::pin::Pin;
     use std::sync::atomic::{AtomicU32, Ordering};
     use tokio::sync::Mutex;
@@ -648,4 +668,25 @@ mod tests {
             .expect_err("webhook should be blocked");
         assert!(error.to_string().contains("blocked by policy"));
     }
+
+    #[tokio::test]
+    async fn test_webhook_fails_on_unresolved_secret_reference() {
+        let resolver: SecretResolver =
+            Arc::new(|name| (name == "HOOK_HOST").then(|| "localhost".to_string()));
+        let executor = HookExecutor::new(Vec::new()).with_secret_resolver(resolver);
+        let webhook = HookAction::Webhook {
+            url: "http://${secret:HOOK_HOST}/hook".to_string(),
+            method: None,
+            headers: Some(BTreeMap::from([(
+                "Authorization".to_string(),
+                "Bearer ${secret:HOOK_TOKEN}".to_string(),
+            )])),
+        };
+
+        let error = executor
+            .execute_action(&webhook, &sample_context())
+            .await
+            .expect_err("missing secret should fail");
+        assert!(error.to_string().contains("HOOK_TOKEN"));
+    }
 }
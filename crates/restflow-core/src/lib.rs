@@ -1,3 +1,4 @@
+pub mod archive;
 pub mod auth;
 pub(crate) mod boundary;
 pub mod channel;
@@ -58,6 +59,9 @@ pub use models::{
     Deliverable,
     DeliverableType,
     DurabilityMode,
+    EntityKind,
+    EntityRef,
+    EntityRelation,
     EnvVarRequirement,
     ExecutionContainerKind,
     ExecutionContainerRef,
@@ -71,6 +75,7 @@ pub use models::{
     ExecutionMode,
     ExecutionStep,
     ExecutionStepInfo,
+    ExecutionStrategy,
     ExecutionThread,
     ExecutionTimeline,
     ExecutionTraceCategory,
@@ -79,6 +84,7 @@ pub use models::{
     ExecutionTraceSource,
     ExecutionTraceStats,
     ExecutionTraceTimeRange,
+    FailoverPolicy,
     GatingCheckResult,
     GatingRequirements,
     Hook,
@@ -120,6 +126,8 @@ pub use models::{
     ProviderHealthQuery,
     ProviderHealthResponse,
     ProviderHealthTrace,
+    RelatedEntities,
+    RelationKind,
     ResourceLimits,
     ResumePayload,
     RunKind,
@@ -164,6 +172,7 @@ pub use models::{
     TaskSchedule,
     TaskSpec,
     TaskStatus,
+    TaskWake,
     TerminalSession,
     TerminalStatus,
     ToolAction,
@@ -177,6 +186,8 @@ pub use models::{
     ValidationErrorResponse,
     VersionRequirement,
     Visibility,
+    WakeCondition,
+    WakeEvent,
     WebhookConfig,
     WebhookRateLimiter,
     WebhookRequest,
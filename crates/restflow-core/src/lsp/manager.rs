@@ -12,6 +12,7 @@ use tokio::sync::Mutex;
 use tracing::warn;
 use url::Url;
 
+use restflow_browser::BrowserService;
 use restflow_traits::store::DiagnosticsProvider;
 
 use super::client::{LspClient, LspClientConfig};
@@ -61,6 +62,7 @@ pub struct LspManager {
     clients: Arc<DashMap<LanguageId, Arc<LspClient>>>,
     init_lock: Arc<Mutex<()>>,
     watcher: Arc<Mutex<Option<LspWatcher>>>,
+    browser: Option<Arc<BrowserService>>,
 }
 
 impl LspManager {
@@ -70,6 +72,7 @@ impl LspManager {
             clients: Arc::new(DashMap::new()),
             init_lock: Arc::new(Mutex::new(())),
             watcher: Arc::new(Mutex::new(None)),
+            browser: None,
         };
 
         if let Ok(handle) = tokio::runtime::Handle::try_current() {
@@ -86,6 +89,12 @@ impl LspManager {
         manager
     }
 
+    /// Probe `service` when runtime diagnostics are requested.
+    pub fn with_browser_service(mut self, service: Arc<BrowserService>) -> Self {
+        self.browser = Some(service);
+        self
+    }
+
     pub async fn get_client_for_file(&self, path: &Path) -> anyhow::Result<Option<Arc<LspClient>>> {
         let Some(language) = LanguageId::from_path(path) else {
             return Ok(None);
@@ -212,4 +221,17 @@ impl DiagnosticsProvider for LspManager {
             .await
             .map_err(|e| restflow_tools::ToolError::Tool(e.to_string()))
     }
+
+    async fn runtime_report(&self) -> restflow_tools::Result<serde_json::Value> {
+        let Some(browser) = &self.browser else {
+            return Err(restflow_tools::ToolError::Tool(
+                "Browser runtime diagnostics are not configured".to_string(),
+            ));
+        };
+        let report = browser
+            .doctor()
+            .await
+            .map_err(|e| restflow_tools::ToolError::Tool(e.to_string()))?;
+        Ok(serde_json::json!({ "browser": report }))
+    }
 }
@@ -7,14 +7,15 @@ use crate::AppCore;
 use crate::auth::build_runtime_api_keys;
 use crate::daemon::{IpcClient, IpcRequest};
 use crate::models::{
-    ChatSession, ChatSessionSummary, Deliverable, ExecutionContainerKind, ExecutionContainerRef,
-    ExecutionTraceCategory, ExecutionTraceEvent, ExecutionTraceQuery, ExecutionTraceSource, Hook,
-    HookAction, HookEvent, HookFilter, MemoryChunk, MemorySearchQuery, MemorySearchResult,
-    MemorySource, MemoryStats, ModelId, RunListQuery, RunSummary, SearchMode, Skill, SkillStatus,
-    Task, TaskControlAction, TaskMessage, TaskMessageSource, TaskPatch, TaskProgress, TaskSpec,
-    TaskStatus, ValidationError,
+    ApiTokenScope, ChatSession, ChatSessionSummary, Deliverable, ExecutionContainerKind,
+    ExecutionContainerRef, ExecutionTraceCategory, ExecutionTraceEvent, ExecutionTraceQuery,
+    ExecutionTraceSource, Hook, HookAction, HookEvent, HookFilter, ItemQuery, MemoryChunk,
+    MemorySearchQuery, MemorySearchResult, MemorySource, MemoryStats, ModelId, RunListQuery,
+    RunSummary, SearchMode, Skill, SkillStatus, Task, TaskControlAction, TaskMessage,
+    TaskMessageSource, TaskPatch, TaskProgress, TaskSpec, TaskStatus, ValidationError, WorkItem,
 };
 use crate::services::{
+    api_tokens::{ApiTokenError, ApiTokenGrant},
     operation_assessment::OperationAssessorAdapter,
     tool_registry::create_tool_registry_with_assessor,
 };
@@ -35,8 +36,10 @@ use rmcp::{
     ErrorData as McpError, ServerHandler, ServiceExt,
     handler::server::tool::schema_for_type,
     model::{
-        CallToolRequestParams, CallToolResult, Content, Implementation, ListToolsResult,
-        PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool,
+        CallToolRequestParams, CallToolResult, Content, GetPromptRequestParams, GetPromptResult,
+        Implementation, ListPromptsResult, ListResourcesResult, ListToolsResult,
+        PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult, ResourceContents,
+        ServerCapabilities, ServerInfo, Tool,
     },
     schemars::{self, JsonSchema},
     service::{RequestContext, RoleServer},
@@ -55,10 +58,14 @@ mod agents;
 mod backends;
 #[path = "server/background_agents.rs"]
 mod background_agents;
+#[path = "server/cache.rs"]
+mod cache;
 #[path = "server/hooks.rs"]
 mod hooks;
 #[path = "server/memory.rs"]
 mod memory;
+#[path = "server/resources.rs"]
+mod resources;
 #[path = "server/runtime_tools.rs"]
 mod runtime_tools;
 #[path = "server/sessions.rs"]
@@ -69,8 +76,27 @@ mod skills;
 mod types;
 
 use self::backends::{CoreBackend, IpcBackend};
+pub use self::cache::McpCacheStats;
+use self::cache::{CachingBackend, DEFAULT_CONTENT_CACHE_TTL, content_etag};
+use self::resources::ContextResource;
 use self::types::*;
 
+/// Tools that touch no agent-owned data. A token limited to certain agents
+/// may call these without naming an agent; every other tool, including
+/// runtime tools added later, must name an allowed agent. `list_agents` is
+/// filtered to the allowed agents instead.
+const UNSCOPED_TOOLS: &[&str] = &[
+    "list_skills",
+    "get_skill",
+    "get_skill_reference",
+    "get_skill_context",
+    "create_skill",
+    "update_skill",
+    "delete_skill",
+    "use_skill",
+    "list_agents",
+];
+
 fn schema_map_from_value(schema: Value) -> Map<String, Value> {
     schema
         .as_object()
@@ -84,7 +110,11 @@ fn schema_map_from_value(schema: Value) -> Map<String, Value> {
 #[derive(Clone)]
 pub struct RestFlowMcpServer {
     backend: Arc<dyn McpBackend>,
+    content_cache: Option<Arc<CachingBackend>>,
     switch_model_tool: SwitchModelTool,
+    /// Grant of the token that opened a session on a transport without
+    /// per-request HTTP parts (the SSE transport).
+    session_grant: Option<ApiTokenGrant>,
 }
 
 #[async_trait::async_trait]
@@ -103,10 +133,20 @@ pub trait McpBackend: Send + Sync {
     async fn list_agents(&self) -> Result<Vec<StoredAgent>, String>;
     async fn get_agent(&self, id: &str) -> Result<StoredAgent, String>;
 
+    /// Revision of skill and agent content that moves on every write.
+    /// Backends that cannot observe writes return `None`; cached content
+    /// then expires by TTL.
+    async fn content_revision(&self) -> Option<u64> {
+        None
+    }
+
     async fn search_memory(&self, query: MemorySearchQuery) -> Result<MemorySearchResult, String>;
     async fn store_memory(&self, chunk: MemoryChunk) -> Result<String, String>;
     async fn get_memory_stats(&self, agent_id: &str) -> Result<MemoryStats, String>;
 
+    async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>, String>;
+    async fn get_note(&self, id: &str) -> Result<Option<WorkItem>, String>;
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>, String>;
     async fn list_sessions_by_agent(
         &self,
@@ -277,23 +317,24 @@ fn build_switch_model_tool(secret_storage: Option<&SecretStorage>) -> SwitchMode
 impl RestFlowMcpServer {
     /// Create a new MCP server with the given AppCore
     pub fn new(core: Arc<AppCore>) -> Self {
-        Self {
-            switch_model_tool: build_switch_model_tool(Some(&core.storage.secrets)),
-            backend: Arc::new(CoreBackend {
+        let switch_model_tool = build_switch_model_tool(Some(&core.storage.secrets));
+        Self::with_content_cache(
+            Arc::new(CoreBackend {
                 core,
                 registry: std::sync::OnceLock::new(),
             }),
-        }
+            switch_model_tool,
+        )
     }
 
     /// Create a new MCP server using daemon IPC
     pub fn with_ipc(client: IpcClient) -> Self {
-        Self {
-            switch_model_tool: build_switch_model_tool(None),
-            backend: Arc::new(IpcBackend {
+        Self::with_content_cache(
+            Arc::new(IpcBackend {
                 client: Arc::new(Mutex::new(client)),
             }),
-        }
+            build_switch_model_tool(None),
+        )
     }
 
     /// Create a new MCP server with a custom backend
@@ -301,9 +342,35 @@ impl RestFlowMcpServer {
         Self {
             switch_model_tool: build_switch_model_tool(None),
             backend,
+            content_cache: None,
+            session_grant: None,
+        }
+    }
+
+    fn with_content_cache(
+        backend: Arc<dyn McpBackend>,
+        switch_model_tool: SwitchModelTool,
+    ) -> Self {
+        let cache = Arc::new(CachingBackend::new(backend, DEFAULT_CONTENT_CACHE_TTL));
+        Self {
+            switch_model_tool,
+            backend: cache.clone(),
+            content_cache: Some(cache),
+            session_grant: None,
         }
     }
 
+    /// Scope every request of this server instance to an API token grant.
+    pub fn with_session_grant(mut self, grant: Option<ApiTokenGrant>) -> Self {
+        self.session_grant = grant;
+        self
+    }
+
+    /// Hit/miss counters of the skill and agent content cache, if enabled.
+    pub fn cache_stats(&self) -> Option<McpCacheStats> {
+        self.content_cache.as_ref().map(|cache| cache.stats())
+    }
+
     /// Run the MCP server using stdio transport
     pub async fn run(self) -> anyhow::Result<()> {
         tracing::info!("Starting RestFlow MCP server...");
@@ -344,6 +411,7 @@ impl RestFlowMcpServer {
             ExecutionTraceCategory::MetricSample => "metric_sample",
             ExecutionTraceCategory::ProviderHealth => "provider_health",
             ExecutionTraceCategory::LogRecord => "log_record",
+            ExecutionTraceCategory::SecurityDecision => "security_decision",
         }
     }
 
@@ -357,6 +425,7 @@ impl RestFlowMcpServer {
             ExecutionTraceCategory::MetricSample => "metric",
             ExecutionTraceCategory::ProviderHealth => "provider_health",
             ExecutionTraceCategory::LogRecord => "log",
+            ExecutionTraceCategory::SecurityDecision => "security",
         }
     }
 
@@ -375,6 +444,7 @@ impl RestFlowMcpServer {
                         | "metric"
                         | "provider_health"
                         | "log"
+                        | "security"
                         | "turn_started"
                         | "tool_call_started"
                         | "tool_call_completed"
@@ -385,12 +455,13 @@ impl RestFlowMcpServer {
                         | "model_switch"
                         | "metric_sample"
                         | "log_record"
+                        | "security_decision"
                 ) =>
             {
                 Ok(Some(s))
             }
             Some(s) => Err(format!(
-                "Unknown trace category: {}. Supported: turn, tool, llm, model, message, metric, provider_health, log, turn_started, tool_call_started, tool_call_completed, turn_completed, turn_failed, turn_interrupted, llm_call, model_switch, metric_sample, log_record",
+                "Unknown trace category: {}. Supported: turn, tool, llm, model, message, metric, provider_health, log, security, turn_started, tool_call_started, tool_call_completed, turn_completed, turn_failed, turn_interrupted, llm_call, model_switch, metric_sample, log_record, security_decision",
                 s
             )),
         }
@@ -431,6 +502,7 @@ impl RestFlowMcpServer {
             "metric" => Self::execution_trace_category_name(trace) == "metric",
             "provider_health" => Self::execution_trace_category_name(trace) == "provider_health",
             "log" => Self::execution_trace_category_name(trace) == "log",
+            "security" => Self::execution_trace_category_name(trace) == "security",
             event_type => Self::execution_trace_event_name(trace) == event_type,
         }
     }
@@ -579,6 +651,28 @@ impl RestFlowMcpServer {
         })
     }
 
+    /// Serialize `value` with an `etag` field, or a bare `not_modified`
+    /// marker when it matches `if_none_match`.
+    fn with_etag<T: Serialize>(
+        id: &str,
+        value: &T,
+        if_none_match: Option<&str>,
+    ) -> Result<Value, serde_json::Error> {
+        let etag = content_etag(value).map_err(serde::ser::Error::custom)?;
+        if if_none_match.map(str::trim) == Some(etag.as_str()) {
+            return Ok(serde_json::json!({
+                "id": id,
+                "etag": etag,
+                "not_modified": true,
+            }));
+        }
+        let mut payload = serde_json::to_value(value)?;
+        if let Value::Object(map) = &mut payload {
+            map.insert("etag".to_string(), Value::String(etag));
+        }
+        Ok(payload)
+    }
+
     fn required_string(value: Option<String>, field: &str) -> Result<String, String> {
         value
             .map(|v| v.trim().to_string())
@@ -596,6 +690,7 @@ impl RestFlowMcpServer {
             Some(s) if s == "completed" => Ok(Some(TaskStatus::Completed)),
             Some(s) if s == "failed" => Ok(Some(TaskStatus::Failed)),
             Some(s) if s == "interrupted" => Ok(Some(TaskStatus::Interrupted)),
+            Some(s) if s == "dead_letter" => Ok(Some(TaskStatus::DeadLetter)),
             Some(s) => Err(format!("Unknown status: {}", s)),
         }
     }
@@ -804,6 +899,100 @@ impl RestFlowMcpServer {
         format!("{}: {}", context, error)
     }
 
+    /// Scoped API token grant attached by the HTTP transport, if any.
+    fn api_token_grant(&self, context: &RequestContext<RoleServer>) -> Option<ApiTokenGrant> {
+        context
+            .extensions
+            .get::<http::request::Parts>()
+            .and_then(|parts| parts.extensions.get::<ApiTokenGrant>())
+            .cloned()
+            .or_else(|| self.session_grant.clone())
+    }
+
+    /// Authorize a call to `tool_name` under `grant`.
+    ///
+    /// When the token is limited to certain agents, every agent the call
+    /// reads or acts on is resolved from its arguments, including agents
+    /// reached through a task or chat session. Tools outside
+    /// [`UNSCOPED_TOOLS`] that name no agent are refused rather than allowed
+    /// across all agents.
+    async fn authorize_api_token_call(
+        &self,
+        grant: &ApiTokenGrant,
+        tool_name: &str,
+        arguments: Option<&Map<String, Value>>,
+    ) -> Result<(), ApiTokenError> {
+        let mut agent_ids = Vec::new();
+        if grant.scope().allowed_agents.is_some() {
+            agent_ids = self
+                .scoped_agent_ids(tool_name, arguments)
+                .await
+                .map_err(|error| {
+                    ApiTokenError::Forbidden(format!("call '{tool_name}': {error}"))
+                })?;
+            if agent_ids.is_empty() && !UNSCOPED_TOOLS.contains(&tool_name) {
+                return Err(ApiTokenError::Forbidden(format!(
+                    "call '{tool_name}' without naming an agent"
+                )));
+            }
+        }
+        grant.authorize_tool_call(tool_name, &agent_ids).map(|_| ())
+    }
+
+    /// Agents targeted by a tool call's arguments.
+    async fn scoped_agent_ids(
+        &self,
+        tool_name: &str,
+        arguments: Option<&Map<String, Value>>,
+    ) -> Result<Vec<String>, String> {
+        let argument = |key: &str| {
+            arguments
+                .and_then(|arguments| arguments.get(key))
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut agent_ids: Vec<String> =
+            argument("agent_id").into_iter().map(String::from).collect();
+        if tool_name == "get_agent"
+            && let Some(id) = argument("id")
+        {
+            agent_ids.push(id.to_string());
+        }
+        if matches!(tool_name, "manage_tasks" | "manage_background_agents") {
+            for key in ["id", "task_id"] {
+                if let Some(task_id) = argument(key) {
+                    agent_ids.push(self.backend.get_task(task_id).await?.agent_id);
+                }
+            }
+            if let Some(workers) = arguments
+                .and_then(|arguments| arguments.get("workers"))
+                .and_then(Value::as_array)
+            {
+                agent_ids.extend(
+                    workers
+                        .iter()
+                        .filter_map(|worker| worker.get("agent_id").and_then(Value::as_str))
+                        .map(String::from),
+                );
+            }
+        }
+        if matches!(
+            tool_name,
+            "chat_session_get" | "manage_tasks" | "manage_background_agents"
+        ) {
+            for key in ["session_id", "chat_session_id"] {
+                if let Some(session_id) = argument(key) {
+                    agent_ids.push(self.backend.get_session(session_id).await?.agent_id);
+                }
+            }
+        }
+        agent_ids.sort();
+        agent_ids.dedup();
+        Ok(agent_ids)
+    }
+
     fn to_call_tool_result(result: Result<String, String>) -> CallToolResult {
         match result {
             Ok(text) => CallToolResult::success(vec![Content::text(text)]),
@@ -825,7 +1014,11 @@ impl ServerHandler for RestFlowMcpServer {
     fn get_info(&self) -> ServerInfo {
         let mut info = ServerInfo::default();
         info.protocol_version = Default::default();
-        info.capabilities = ServerCapabilities::builder().enable_tools().build();
+        info.capabilities = ServerCapabilities::builder()
+            .enable_tools()
+            .enable_resources()
+            .enable_prompts()
+            .build();
         info.server_info = Implementation::new("restflow", env!("CARGO_PKG_VERSION"))
             .with_title("RestFlow MCP Server");
         info.instructions = Some(
@@ -833,7 +1026,9 @@ impl ServerHandler for RestFlowMcpServer {
             Use list_skills/get_skill to access skills, list_agents/get_agent for agents, \
             memory_search/memory_store for memory, chat_session_list/chat_session_get for sessions, \
             manage_hooks for lifecycle hook automation, \
-            and manage_tasks for task lifecycle, session conversion, progress, and messaging operations."
+            and manage_tasks for task lifecycle, session conversion, progress, and messaging operations. \
+            Skills, workspace notes, agent memory, and task deliverables are also browsable as \
+            restflow:// resources, with apply_skill/recall_memory/review_deliverables prompts."
                 .to_string(),
         );
         info
@@ -842,7 +1037,7 @@ impl ServerHandler for RestFlowMcpServer {
     async fn list_tools(
         &self,
         _request: Option<PaginatedRequestParams>,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
         let mut tools = vec![
             Tool::new(
@@ -852,7 +1047,7 @@ impl ServerHandler for RestFlowMcpServer {
             ),
             Tool::new(
                 "get_skill",
-                "Get the full content of a skill by its ID. Returns the complete skill including its markdown content.",
+                "Get the full content of a skill by its ID. Returns the complete skill including its markdown content and an etag; pass it back as if_none_match to skip unchanged content.",
                 schema_for_type::<GetSkillParams>(),
             ),
             // No CLI needed: Deep reference lookup, use `skill show` for basic viewing
@@ -883,7 +1078,7 @@ impl ServerHandler for RestFlowMcpServer {
             ),
             Tool::new(
                 "get_agent",
-                "Get the full configuration of an agent by its ID. Returns the complete agent including model, prompt, temperature, and tools.",
+                "Get the full configuration of an agent by its ID. Returns the complete agent including model, prompt, temperature, tools, and an etag; pass it back as if_none_match to skip unchanged content.",
                 schema_for_type::<GetAgentParams>(),
             ),
             Tool::new(
@@ -995,6 +1190,10 @@ impl ServerHandler for RestFlowMcpServer {
             }
         }
 
+        if let Some(grant) = self.api_token_grant(&context) {
+            tools.retain(|tool| grant.scope().allows_tool(tool.name.as_ref()));
+        }
+
         Ok(ListToolsResult {
             meta: None,
             tools,
@@ -1005,8 +1204,17 @@ impl ServerHandler for RestFlowMcpServer {
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
+        let grant = self.api_token_grant(&context);
+        if let Some(grant) = &grant
+            && let Err(error) = self
+                .authorize_api_token_call(grant, request.name.as_ref(), request.arguments.as_ref())
+                .await
+        {
+            return Ok(Self::to_call_tool_result(Err(error.to_string())));
+        }
+
         let result = match request.name.as_ref() {
             "list_skills" => {
                 let params: ListSkillsParams =
@@ -1056,7 +1264,10 @@ impl ServerHandler for RestFlowMcpServer {
                         })?;
                 self.handle_delete_skill(params).await
             }
-            "list_agents" => self.handle_list_agents().await,
+            "list_agents" => {
+                self.handle_list_agents(grant.as_ref().map(ApiTokenGrant::scope))
+                    .await
+            }
             "get_agent" => {
                 let params: GetAgentParams =
                     serde_json::from_value(Value::Object(request.arguments.unwrap_or_default()))
@@ -1170,6 +1381,95 @@ impl ServerHandler for RestFlowMcpServer {
 
         Ok(Self::to_call_tool_result(result))
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let grant = self.api_token_grant(&context);
+        let resources = self
+            .list_context_resources(grant.as_ref())
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?;
+
+        Ok(ListResourcesResult {
+            meta: None,
+            resources,
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let not_found =
+            || McpError::resource_not_found(format!("Resource not found: {}", request.uri), None);
+        let resource = ContextResource::parse(&request.uri).ok_or_else(not_found)?;
+        if let Some(grant) = self.api_token_grant(&context)
+            && let Err(error) = self
+                .authorize_api_token_call(
+                    &grant,
+                    resource.tool_name(),
+                    Some(&resource.scope_arguments()),
+                )
+                .await
+        {
+            return Err(McpError::invalid_request(error.to_string(), None));
+        }
+
+        let (mime_type, text) = self
+            .read_context_resource(&resource)
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?
+            .ok_or_else(not_found)?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri.clone(),
+                mime_type: Some(mime_type),
+                text,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        let grant = self.api_token_grant(&context);
+        Ok(ListPromptsResult {
+            meta: None,
+            prompts: Self::context_prompts(grant.as_ref()),
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let Some(tool_name) = Self::prompt_tool_name(&request.name) else {
+            return Err(McpError::invalid_params(
+                format!("Unknown prompt: {}", request.name),
+                None,
+            ));
+        };
+        if let Some(grant) = self.api_token_grant(&context)
+            && let Err(error) = self
+                .authorize_api_token_call(&grant, tool_name, request.arguments.as_ref())
+                .await
+        {
+            return Err(McpError::invalid_request(error.to_string(), None));
+        }
+
+        self.render_context_prompt(&request.name, &request.arguments)
+            .await
+    }
 }
 
 #[cfg(test)]
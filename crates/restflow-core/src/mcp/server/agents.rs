@@ -1,7 +1,12 @@
 use super::*;
 
 impl RestFlowMcpServer {
-    pub(crate) async fn handle_list_agents(&self) -> Result<String, String> {
+    /// List agents, limited to those `scope` allows when the caller holds a
+    /// scoped API token.
+    pub(crate) async fn handle_list_agents(
+        &self,
+        scope: Option<&ApiTokenScope>,
+    ) -> Result<String, String> {
         let agents = self
             .backend
             .list_agents()
@@ -10,6 +15,7 @@ impl RestFlowMcpServer {
 
         let summaries: Vec<AgentSummary> = agents
             .into_iter()
+            .filter(|a| scope.is_none_or(|scope| scope.allows_agent(&a.id)))
             .map(|a| AgentSummary {
                 id: a.id,
                 name: a.name,
@@ -36,7 +42,8 @@ impl RestFlowMcpServer {
             .await
             .map_err(|e| format!("Failed to get agent: {}", e))?;
 
-        serde_json::to_string_pretty(&agent)
+        Self::with_etag(&agent.id, &agent, params.if_none_match.as_deref())
+            .and_then(|payload| serde_json::to_string_pretty(&payload))
             .map_err(|e| format!("Failed to serialize agent: {}", e))
     }
 }
@@ -91,6 +91,11 @@ impl McpBackend for CoreBackend {
             .map_err(|e| e.to_string())
     }
 
+    async fn content_revision(&self) -> Option<u64> {
+        let storage = &self.core.storage;
+        Some(storage.skills.revision() + storage.agents.revision())
+    }
+
     async fn search_memory(&self, query: MemorySearchQuery) -> Result<MemorySearchResult, String> {
         self.core
             .storage
@@ -99,7 +104,20 @@ impl McpBackend for CoreBackend {
             .map_err(|e| e.to_string())
     }
 
-    async fn store_memory(&self, chunk: MemoryChunk) -> Result<String, String> {
+    async fn store_memory(&self, mut chunk: MemoryChunk) -> Result<String, String> {
+        let memory_defaults = self
+            .core
+            .storage
+            .config
+            .get_effective_config()
+            .map(|config| config.memory_defaults)
+            .unwrap_or_default();
+        crate::memory::suggest_tags_for_chunk(
+            &self.core.storage.memory,
+            &memory_defaults,
+            &mut chunk,
+        )
+        .map_err(|e| e.to_string())?;
         self.core
             .storage
             .memory
@@ -115,6 +133,22 @@ impl McpBackend for CoreBackend {
             .map_err(|e| e.to_string())
     }
 
+    async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>, String> {
+        self.core
+            .storage
+            .work_items
+            .list_notes(query)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn get_note(&self, id: &str) -> Result<Option<WorkItem>, String> {
+        self.core
+            .storage
+            .work_items
+            .get_note(id)
+            .map_err(|e| e.to_string())
+    }
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>, String> {
         let mut sessions = self
             .core
@@ -551,6 +585,20 @@ impl McpBackend for IpcBackend {
             .map_err(|e| e.to_string())
     }
 
+    async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>, String> {
+        let query = to_contract(query).map_err(|e| e.to_string())?;
+        self.request_typed(IpcRequest::ListWorkItems { query })
+            .await
+    }
+
+    async fn get_note(&self, id: &str) -> Result<Option<WorkItem>, String> {
+        let mut client = self.client.lock().await;
+        client
+            .request_optional(IpcRequest::GetWorkItem { id: id.to_string() })
+            .await
+            .map_err(|e| e.to_string())
+    }
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>, String> {
         let mut client = self.client.lock().await;
         client.list_sessions().await.map_err(|e| e.to_string())
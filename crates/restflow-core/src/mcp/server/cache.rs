@@ -0,0 +1,406 @@
+use super::*;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How long cached content may be served without observing a write.
+///
+/// Only matters for backends without a content revision (IPC), where writes
+/// made by other clients cannot be observed directly.
+pub(crate) const DEFAULT_CONTENT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Weak validator for a serialized MCP payload.
+pub(crate) fn content_etag<T: Serialize>(value: &T) -> Result<String, String> {
+    let bytes = serde_json::to_vec(value).map_err(|e| format!("Failed to compute etag: {}", e))?;
+    let digest = Sha256::digest(&bytes);
+    Ok(format!("W/\"{}\"", hex::encode(&digest[..12])))
+}
+
+/// Cache hit/miss counters exposed for telemetry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct McpCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+}
+
+struct CacheEntry<T> {
+    value: T,
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct CacheState {
+    /// Backend content revision the entries were read at.
+    revision: Option<u64>,
+    skills: Option<CacheEntry<Vec<Skill>>>,
+    skill: HashMap<String, CacheEntry<Option<Skill>>>,
+    skill_references: HashMap<(String, String), CacheEntry<Option<String>>>,
+    agents: Option<CacheEntry<Vec<StoredAgent>>>,
+    agent: HashMap<String, CacheEntry<StoredAgent>>,
+    runtime_tools: Option<CacheEntry<Vec<RuntimeToolDefinition>>>,
+}
+
+impl CacheState {
+    fn clear_content(&mut self) {
+        self.skills = None;
+        self.skill.clear();
+        self.skill_references.clear();
+        self.agents = None;
+        self.agent.clear();
+    }
+}
+
+/// Read-through cache for skill, agent prompt and tool-list reads.
+///
+/// Entries are dropped when the backend content revision moves, when a write
+/// goes through this backend, or once they outlive the TTL.
+pub(crate) struct CachingBackend {
+    inner: Arc<dyn McpBackend>,
+    ttl: Duration,
+    state: std::sync::Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl CachingBackend {
+    pub(crate) fn new(inner: Arc<dyn McpBackend>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            state: std::sync::Mutex::new(CacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> McpCacheStats {
+        McpCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Drop content entries if the backend revision moved since they were
+    /// read. Returns the revision observed before the read.
+    async fn sync_revision(&self) -> Option<u64> {
+        let revision = self.inner.content_revision().await;
+        let mut state = self.lock_state();
+        if state.revision != revision {
+            if state.revision.is_some() {
+                self.record_invalidation("revision changed");
+            }
+            state.clear_content();
+            state.revision = revision;
+        }
+        revision
+    }
+
+    /// Store a freshly read value unless a write landed while it was read.
+    fn store(&self, observed: Option<u64>, insert: impl FnOnce(&mut CacheState)) {
+        let mut state = self.lock_state();
+        if state.revision == observed {
+            insert(&mut state);
+        }
+    }
+
+    fn invalidate_content(&self, reason: &str) {
+        self.lock_state().clear_content();
+        self.record_invalidation(reason);
+    }
+
+    fn record_invalidation(&self, reason: &str) {
+        let count = self.invalidations.fetch_add(1, Ordering::Relaxed) + 1;
+        tracing::debug!(
+            reason,
+            invalidations = count,
+            "MCP content cache invalidated"
+        );
+    }
+
+    fn lookup<T: Clone>(&self, entry: Option<&CacheEntry<T>>) -> Option<T> {
+        let value = entry
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .map(|entry| entry.value.clone());
+        if value.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        value
+    }
+
+    fn entry<T>(value: T) -> CacheEntry<T> {
+        CacheEntry {
+            value,
+            stored_at: Instant::now(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl McpBackend for CachingBackend {
+    async fn list_skills(&self) -> Result<Vec<Skill>, String> {
+        let revision = self.sync_revision().await;
+        let cached = self.lookup(self.lock_state().skills.as_ref());
+        if let Some(skills) = cached {
+            return Ok(skills);
+        }
+        let skills = self.inner.list_skills().await?;
+        self.store(revision, |state| {
+            state.skills = Some(Self::entry(skills.clone()));
+        });
+        Ok(skills)
+    }
+
+    async fn get_skill(&self, id: &str) -> Result<Option<Skill>, String> {
+        let revision = self.sync_revision().await;
+        let cached = self.lookup(self.lock_state().skill.get(id));
+        if let Some(skill) = cached {
+            return Ok(skill);
+        }
+        let skill = self.inner.get_skill(id).await?;
+        self.store(revision, |state| {
+            state
+                .skill
+                .insert(id.to_string(), Self::entry(skill.clone()));
+        });
+        Ok(skill)
+    }
+
+    async fn get_skill_reference(
+        &self,
+        skill_id: &str,
+        ref_id: &str,
+    ) -> Result<Option<String>, String> {
+        let revision = self.sync_revision().await;
+        let key = (skill_id.to_string(), ref_id.to_string());
+        let cached = self.lookup(self.lock_state().skill_references.get(&key));
+        if let Some(content) = cached {
+            return Ok(content);
+        }
+        let content = self.inner.get_skill_reference(skill_id, ref_id).await?;
+        self.store(revision, |state| {
+            state
+                .skill_references
+                .insert(key, Self::entry(content.clone()));
+        });
+        Ok(content)
+    }
+
+    async fn create_skill(&self, skill: Skill) -> Result<(), String> {
+        let result = self.inner.create_skill(skill).await;
+        self.invalidate_content("create_skill");
+        result
+    }
+
+    async fn update_skill(&self, skill: Skill) -> Result<(), String> {
+        let result = self.inner.update_skill(skill).await;
+        self.invalidate_content("update_skill");
+        result
+    }
+
+    async fn delete_skill(&self, id: &str) -> Result<(), String> {
+        let result = self.inner.delete_skill(id).await;
+        self.invalidate_content("delete_skill");
+        result
+    }
+
+    async fn list_agents(&self) -> Result<Vec<StoredAgent>, String> {
+        let revision = self.sync_revision().await;
+        let cached = self.lookup(self.lock_state().agents.as_ref());
+        if let Some(agents) = cached {
+            return Ok(agents);
+        }
+        let agents = self.inner.list_agents().await?;
+        self.store(revision, |state| {
+            state.agents = Some(Self::entry(agents.clone()));
+        });
+        Ok(agents)
+    }
+
+    async fn get_agent(&self, id: &str) -> Result<StoredAgent, String> {
+        let revision = self.sync_revision().await;
+        let cached = self.lookup(self.lock_state().agent.get(id));
+        if let Some(agent) = cached {
+            return Ok(agent);
+        }
+        let agent = self.inner.get_agent(id).await?;
+        self.store(revision, |state| {
+            state
+                .agent
+                .insert(id.to_string(), Self::entry(agent.clone()));
+        });
+        Ok(agent)
+    }
+
+    async fn content_revision(&self) -> Option<u64> {
+        self.inner.content_revision().await
+    }
+
+    async fn search_memory(&self, query: MemorySearchQuery) -> Result<MemorySearchResult, String> {
+        self.inner.search_memory(query).await
+    }
+
+    async fn store_memory(&self, chunk: MemoryChunk) -> Result<String, String> {
+        self.inner.store_memory(chunk).await
+    }
+
+    async fn get_memory_stats(&self, agent_id: &str) -> Result<MemoryStats, String> {
+        self.inner.get_memory_stats(agent_id).await
+    }
+
+    async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>, String> {
+        self.inner.list_notes(query).await
+    }
+
+    async fn get_note(&self, id: &str) -> Result<Option<WorkItem>, String> {
+        self.inner.get_note(id).await
+    }
+
+    async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>, String> {
+        self.inner.list_sessions().await
+    }
+
+    async fn list_sessions_by_agent(
+        &self,
+        agent_id: &str,
+    ) -> Result<Vec<ChatSessionSummary>, String> {
+        self.inner.list_sessions_by_agent(agent_id).await
+    }
+
+    async fn get_session(&self, id: &str) -> Result<ChatSession, String> {
+        self.inner.get_session(id).await
+    }
+
+    async fn list_tasks(&self, status: Option<TaskStatus>) -> Result<Vec<Task>, String> {
+        self.inner.list_tasks(status).await
+    }
+
+    async fn create_task(&self, spec: TaskSpec) -> Result<Task, String> {
+        self.inner.create_task(spec).await
+    }
+
+    async fn update_task(&self, id: &str, patch: TaskPatch) -> Result<Task, String> {
+        self.inner.update_task(id, patch).await
+    }
+
+    async fn delete_task(
+        &self,
+        request: TaskDeleteRequest,
+    ) -> Result<TaskCommandOutcome<DeleteWithIdResponse>, String> {
+        self.inner.delete_task(request).await
+    }
+
+    async fn control_task(&self, id: &str, action: TaskControlAction) -> Result<Task, String> {
+        self.inner.control_task(id, action).await
+    }
+
+    async fn get_task_progress(
+        &self,
+        id: &str,
+        event_limit: usize,
+    ) -> Result<TaskProgress, String> {
+        self.inner.get_task_progress(id, event_limit).await
+    }
+
+    async fn send_task_message(
+        &self,
+        id: &str,
+        message: String,
+        source: TaskMessageSource,
+    ) -> Result<TaskMessage, String> {
+        self.inner.send_task_message(id, message, source).await
+    }
+
+    async fn list_task_messages(&self, id: &str, limit: usize) -> Result<Vec<TaskMessage>, String> {
+        self.inner.list_task_messages(id, limit).await
+    }
+
+    async fn list_deliverables(&self, task_id: &str) -> Result<Vec<Deliverable>, String> {
+        self.inner.list_deliverables(task_id).await
+    }
+
+    async fn list_execution_sessions(
+        &self,
+        query: RunListQuery,
+    ) -> Result<Vec<RunSummary>, String> {
+        self.inner.list_execution_sessions(query).await
+    }
+
+    async fn query_execution_traces(
+        &self,
+        query: ExecutionTraceQuery,
+    ) -> Result<Vec<ExecutionTraceEvent>, String> {
+        self.inner.query_execution_traces(query).await
+    }
+
+    async fn query_execution_run_traces(
+        &self,
+        run_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ExecutionTraceEvent>, String> {
+        self.inner.query_execution_run_traces(run_id, limit).await
+    }
+
+    async fn get_task(&self, id: &str) -> Result<Task, String> {
+        self.inner.get_task(id).await
+    }
+
+    async fn list_hooks(&self) -> Result<Vec<Hook>, String> {
+        self.inner.list_hooks().await
+    }
+
+    async fn create_hook(&self, hook: Hook) -> Result<Hook, String> {
+        self.inner.create_hook(hook).await
+    }
+
+    async fn update_hook(&self, id: &str, hook: Hook) -> Result<Hook, String> {
+        self.inner.update_hook(id, hook).await
+    }
+
+    async fn delete_hook(&self, id: &str) -> Result<bool, String> {
+        self.inner.delete_hook(id).await
+    }
+
+    async fn test_hook(&self, id: &str) -> Result<(), String> {
+        self.inner.test_hook(id).await
+    }
+
+    async fn list_runtime_tools(&self) -> Result<Vec<RuntimeToolDefinition>, String> {
+        let cached = self.lookup(self.lock_state().runtime_tools.as_ref());
+        if let Some(tools) = cached {
+            return Ok(tools);
+        }
+        let tools = self.inner.list_runtime_tools().await?;
+        self.lock_state().runtime_tools = Some(Self::entry(tools.clone()));
+        Ok(tools)
+    }
+
+    async fn execute_runtime_tool(
+        &self,
+        name: &str,
+        input: Value,
+    ) -> Result<RuntimeToolResult, String> {
+        let result = self.inner.execute_runtime_tool(name, input).await;
+        // Runtime tools such as `skill` can write content; without a revision
+        // to compare against, assume they did.
+        if self.inner.content_revision().await.is_none() {
+            self.invalidate_content(name);
+        }
+        result
+    }
+
+    async fn get_api_defaults(&self) -> Result<ApiDefaults, String> {
+        self.inner.get_api_defaults().await
+    }
+}
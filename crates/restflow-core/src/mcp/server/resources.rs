@@ -0,0 +1,448 @@
+//! MCP resources and prompts.
+//!
+//! Skills, workspace notes, per-agent memory, and task deliverables are
+//! published as `restflow://` resources so clients can browse context before
+//! calling tools. A few prompt templates wrap the same context into ready-made
+//! messages.
+
+use super::*;
+use rmcp::model::{
+    Prompt, PromptArgument, PromptMessage, PromptMessageRole, RawResource, Resource,
+};
+
+const SKILLS_PREFIX: &str = "restflow://skills/";
+const NOTES_PREFIX: &str = "restflow://notes/";
+const MEMORY_PREFIX: &str = "restflow://memory/";
+const DELIVERABLES_PREFIX: &str = "restflow://deliverables/";
+const MARKDOWN_MIME: &str = "text/markdown";
+/// Most recent memory chunks rendered into one memory resource.
+const MEMORY_RESOURCE_LIMIT: u32 = 50;
+
+pub(crate) const APPLY_SKILL_PROMPT: &str = "apply_skill";
+pub(crate) const RECALL_MEMORY_PROMPT: &str = "recall_memory";
+pub(crate) const REVIEW_DELIVERABLES_PROMPT: &str = "review_deliverables";
+
+/// Context addressed by a `restflow://` resource URI.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ContextResource<'a> {
+    Skill(&'a str),
+    Note(&'a str),
+    /// Memory of one agent.
+    Memory(&'a str),
+    Deliverable {
+        task_id: &'a str,
+        deliverable_id: &'a str,
+    },
+}
+
+impl<'a> ContextResource<'a> {
+    pub(crate) fn parse(uri: &'a str) -> Option<Self> {
+        let non_empty = |id: &'a str| (!id.is_empty() && !id.contains('/')).then_some(id);
+        if let Some(id) = uri.strip_prefix(SKILLS_PREFIX) {
+            return non_empty(id).map(Self::Skill);
+        }
+        if let Some(id) = uri.strip_prefix(NOTES_PREFIX) {
+            return non_empty(id).map(Self::Note);
+        }
+        if let Some(id) = uri.strip_prefix(MEMORY_PREFIX) {
+            return non_empty(id).map(Self::Memory);
+        }
+        let (task_id, deliverable_id) = uri.strip_prefix(DELIVERABLES_PREFIX)?.split_once('/')?;
+        Some(Self::Deliverable {
+            task_id: non_empty(task_id)?,
+            deliverable_id: non_empty(deliverable_id)?,
+        })
+    }
+
+    /// Tool whose access in an API token scope also covers this resource.
+    pub(crate) fn tool_name(&self) -> &'static str {
+        match self {
+            Self::Skill(_) => "get_skill",
+            Self::Note(_) => "work_items",
+            Self::Memory(_) => "memory_search",
+            Self::Deliverable { .. } => "manage_tasks",
+        }
+    }
+
+    /// Tool arguments equivalent to reading this resource, used to resolve
+    /// the agent it belongs to.
+    pub(crate) fn scope_arguments(&self) -> Map<String, Value> {
+        let mut arguments = Map::new();
+        match self {
+            Self::Memory(agent_id) => {
+                arguments.insert("agent_id".to_string(), Value::from(*agent_id));
+            }
+            Self::Deliverable { task_id, .. } => {
+                arguments.insert("task_id".to_string(), Value::from(*task_id));
+            }
+            Self::Skill(_) | Self::Note(_) => {}
+        }
+        arguments
+    }
+}
+
+fn resource(uri: String, name: String, description: Option<String>, mime_type: &str) -> Resource {
+    let mut raw = RawResource::new(uri, name);
+    raw.description = description;
+    raw.mime_type = Some(mime_type.to_string());
+    raw.no_annotation()
+}
+
+fn prompt_argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: Some(description.to_string()),
+        required: Some(required),
+    }
+}
+
+fn prompt_text_argument(arguments: &Option<Map<String, Value>>, name: &str) -> Option<String> {
+    arguments
+        .as_ref()
+        .and_then(|arguments| arguments.get(name))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn required_prompt_argument(
+    arguments: &Option<Map<String, Value>>,
+    name: &str,
+) -> Result<String, McpError> {
+    prompt_text_argument(arguments, name).ok_or_else(|| {
+        McpError::invalid_params(format!("Missing required argument: {}", name), None)
+    })
+}
+
+fn render_memory(agent_id: &str, chunks: &[MemoryChunk]) -> String {
+    let mut text = format!("# Memory for agent {}\n", agent_id);
+    if chunks.is_empty() {
+        text.push_str("\nNo memory chunks stored.\n");
+    }
+    for chunk in chunks {
+        text.push_str(&format!("\n## {}\n", chunk.id));
+        if !chunk.tags.is_empty() {
+            text.push_str(&format!("Tags: {}\n", chunk.tags.join(", ")));
+        }
+        text.push('\n');
+        text.push_str(chunk.content.trim());
+        text.push('\n');
+    }
+    text
+}
+
+fn render_note(note: &WorkItem) -> String {
+    format!(
+        "# {}\n\nFolder: {}\n\n{}\n",
+        note.title, note.folder, note.content
+    )
+}
+
+impl RestFlowMcpServer {
+    fn grant_allows(grant: Option<&ApiTokenGrant>, tool_name: &str) -> bool {
+        grant.is_none_or(|grant| grant.scope().allows_tool(tool_name))
+    }
+
+    pub(crate) async fn list_context_resources(
+        &self,
+        grant: Option<&ApiTokenGrant>,
+    ) -> Result<Vec<Resource>, String> {
+        let mut resources = Vec::new();
+
+        if Self::grant_allows(grant, "get_skill") {
+            let skills = self
+                .backend
+                .list_skills()
+                .await
+                .map_err(|e| format!("Failed to list skills: {}", e))?;
+            resources.extend(skills.into_iter().map(|skill| {
+                resource(
+                    format!("{}{}", SKILLS_PREFIX, skill.id),
+                    skill.name,
+                    skill.description,
+                    MARKDOWN_MIME,
+                )
+            }));
+        }
+
+        if Self::grant_allows(grant, "work_items") {
+            let notes = self
+                .backend
+                .list_notes(ItemQuery::default())
+                .await
+                .map_err(|e| format!("Failed to list workspace notes: {}", e))?;
+            resources.extend(notes.into_iter().map(|note| {
+                resource(
+                    format!("{}{}", NOTES_PREFIX, note.id),
+                    note.title,
+                    Some(format!("Workspace note in {}", note.folder)),
+                    MARKDOWN_MIME,
+                )
+            }));
+        }
+
+        if Self::grant_allows(grant, "memory_search") {
+            let agents = self
+                .backend
+                .list_agents()
+                .await
+                .map_err(|e| format!("Failed to list agents: {}", e))?;
+            resources.extend(
+                agents
+                    .into_iter()
+                    .filter(|agent| grant.is_none_or(|grant| grant.scope().allows_agent(&agent.id)))
+                    .map(|agent| {
+                        resource(
+                            format!("{}{}", MEMORY_PREFIX, agent.id),
+                            format!("{} memory", agent.name),
+                            Some(format!(
+                                "Most recent {} memory chunks of agent {}",
+                                MEMORY_RESOURCE_LIMIT, agent.name
+                            )),
+                            MARKDOWN_MIME,
+                        )
+                    }),
+            );
+        }
+
+        if Self::grant_allows(grant, "manage_tasks") {
+            let tasks = self
+                .backend
+                .list_tasks(None)
+                .await
+                .map_err(|e| format!("Failed to list tasks: {}", e))?;
+            let tasks = tasks.into_iter().filter(|task| {
+                grant.is_none_or(|grant| grant.scope().allows_agent(&task.agent_id))
+            });
+            for task in tasks {
+                let deliverables = self
+                    .backend
+                    .list_deliverables(&task.id)
+                    .await
+                    .map_err(|e| format!("Failed to list deliverables: {}", e))?;
+                resources.extend(deliverables.into_iter().map(|deliverable| {
+                    resource(
+                        format!(
+                            "{}{}/{}",
+                            DELIVERABLES_PREFIX, deliverable.task_id, deliverable.id
+                        ),
+                        deliverable.title,
+                        Some(format!("Deliverable of task {}", task.name)),
+                        deliverable.content_type.as_deref().unwrap_or(MARKDOWN_MIME),
+                    )
+                }));
+            }
+        }
+
+        Ok(resources)
+    }
+
+    /// Read a resource, returning its MIME type and text.
+    pub(crate) async fn read_context_resource(
+        &self,
+        resource: &ContextResource<'_>,
+    ) -> Result<Option<(String, String)>, String> {
+        match resource {
+            ContextResource::Skill(id) => {
+                let skill = self
+                    .backend
+                    .get_skill(id)
+                    .await
+                    .map_err(|e| format!("Failed to get skill: {}", e))?;
+                Ok(skill.map(|skill| (MARKDOWN_MIME.to_string(), skill.content)))
+            }
+            ContextResource::Note(id) => {
+                let note = self
+                    .backend
+                    .get_note(id)
+                    .await
+                    .map_err(|e| format!("Failed to get workspace note: {}", e))?;
+                Ok(note.map(|note| (MARKDOWN_MIME.to_string(), render_note(&note))))
+            }
+            ContextResource::Memory(agent_id) => {
+                let query =
+                    MemorySearchQuery::new(agent_id.to_string()).paginate(MEMORY_RESOURCE_LIMIT, 0);
+                let result = self
+                    .backend
+                    .search_memory(query)
+                    .await
+                    .map_err(|e| format!("Failed to read memory: {}", e))?;
+                Ok(Some((
+                    MARKDOWN_MIME.to_string(),
+                    render_memory(agent_id, &result.chunks),
+                )))
+            }
+            ContextResource::Deliverable {
+                task_id,
+                deliverable_id,
+            } => {
+                let deliverables = self
+                    .backend
+                    .list_deliverables(task_id)
+                    .await
+                    .map_err(|e| format!("Failed to list deliverables: {}", e))?;
+                Ok(deliverables
+                    .into_iter()
+                    .find(|deliverable| deliverable.id == *deliverable_id)
+                    .map(|deliverable| {
+                        let mime_type = deliverable
+                            .content_type
+                            .unwrap_or_else(|| MARKDOWN_MIME.to_string());
+                        (mime_type, deliverable.content)
+                    }))
+            }
+        }
+    }
+
+    pub(crate) fn context_prompts(grant: Option<&ApiTokenGrant>) -> Vec<Prompt> {
+        let prompts = [
+            (
+                "get_skill",
+                Prompt::new(
+                    APPLY_SKILL_PROMPT,
+                    Some("Apply a RestFlow skill's instructions to the given input."),
+                    Some(vec![
+                        prompt_argument("skill_id", "ID of the skill to apply", true),
+                        prompt_argument("input", "Input to apply the skill to", false),
+                    ]),
+                ),
+            ),
+            (
+                "memory_search",
+                Prompt::new(
+                    RECALL_MEMORY_PROMPT,
+                    Some("Answer using what an agent has stored in RestFlow memory."),
+                    Some(vec![
+                        prompt_argument("agent_id", "Agent whose memory to search", true),
+                        prompt_argument("query", "Keywords to search memory for", true),
+                    ]),
+                ),
+            ),
+            (
+                "manage_tasks",
+                Prompt::new(
+                    REVIEW_DELIVERABLES_PROMPT,
+                    Some("Review the deliverables produced by a RestFlow task."),
+                    Some(vec![prompt_argument(
+                        "task_id",
+                        "ID of the task whose deliverables to review",
+                        true,
+                    )]),
+                ),
+            ),
+        ];
+        prompts
+            .into_iter()
+            .filter(|(tool_name, _)| Self::grant_allows(grant, tool_name))
+            .map(|(_, prompt)| prompt)
+            .collect()
+    }
+
+    /// Tool whose access covers a prompt, or `None` for an unknown prompt.
+    pub(crate) fn prompt_tool_name(name: &str) -> Option<&'static str> {
+        match name {
+            APPLY_SKILL_PROMPT => Some("get_skill"),
+            RECALL_MEMORY_PROMPT => Some("memory_search"),
+            REVIEW_DELIVERABLES_PROMPT => Some("manage_tasks"),
+            _ => None,
+        }
+    }
+
+    pub(crate) async fn render_context_prompt(
+        &self,
+        name: &str,
+        arguments: &Option<Map<String, Value>>,
+    ) -> Result<GetPromptResult, McpError> {
+        match name {
+            APPLY_SKILL_PROMPT => {
+                let skill_id = required_prompt_argument(arguments, "skill_id")?;
+                let skill = self
+                    .backend
+                    .get_skill(&skill_id)
+                    .await
+                    .map_err(|e| McpError::internal_error(e, None))?
+                    .ok_or_else(|| {
+                        McpError::invalid_params(format!("Skill not found: {}", skill_id), None)
+                    })?;
+                let mut text = format!(
+                    "Follow the instructions of the RestFlow skill \"{}\".\n\n{}",
+                    skill.name, skill.content
+                );
+                if let Some(input) = prompt_text_argument(arguments, "input") {
+                    text.push_str(&format!("\n\nInput:\n{}", input));
+                }
+                Ok(GetPromptResult {
+                    description: skill.description,
+                    messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+                })
+            }
+            RECALL_MEMORY_PROMPT => {
+                let agent_id = required_prompt_argument(arguments, "agent_id")?;
+                let query_text = required_prompt_argument(arguments, "query")?;
+                let limit = self
+                    .load_api_defaults()
+                    .await
+                    .map_err(|e| McpError::internal_error(e, None))?
+                    .memory_search_limit
+                    .max(1);
+                let query = MemorySearchQuery::new(agent_id.clone())
+                    .with_query(query_text.clone())
+                    .with_mode(SearchMode::Keyword)
+                    .paginate(limit, 0);
+                let result = self
+                    .backend
+                    .search_memory(query)
+                    .await
+                    .map_err(|e| McpError::internal_error(e, None))?;
+                let text = format!(
+                    "Answer the question below using the memory of agent {}. \
+                    Say so if the memory does not cover it.\n\nQuestion: {}\n\n{}",
+                    agent_id,
+                    query_text,
+                    render_memory(&agent_id, &result.chunks)
+                );
+                Ok(GetPromptResult {
+                    description: Some(format!("Memory of agent {} matching the query", agent_id)),
+                    messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+                })
+            }
+            REVIEW_DELIVERABLES_PROMPT => {
+                let task_id = required_prompt_argument(arguments, "task_id")?;
+                let deliverables = self
+                    .backend
+                    .list_deliverables(&task_id)
+                    .await
+                    .map_err(|e| McpError::internal_error(e, None))?;
+                let mut text = format!(
+                    "Review the deliverables of RestFlow task {}. Summarize what was \
+                    produced and point out gaps or errors.\n",
+                    task_id
+                );
+                if deliverables.is_empty() {
+                    text.push_str("\nThe task has not produced any deliverables yet.\n");
+                }
+                for deliverable in &deliverables {
+                    text.push_str(&format!(
+                        "\n## {}\n\n{}\n",
+                        deliverable.title,
+                        deliverable.content.trim()
+                    ));
+                }
+                Ok(GetPromptResult {
+                    description: Some(format!(
+                        "{} deliverables of task {}",
+                        deliverables.len(),
+                        task_id
+                    )),
+                    messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+                })
+            }
+            _ => Err(McpError::invalid_params(
+                format!("Unknown prompt: {}", name),
+                None,
+            )),
+        }
+    }
+}
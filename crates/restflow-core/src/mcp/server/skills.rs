@@ -39,7 +39,8 @@ impl RestFlowMcpServer {
             .map_err(|e| format!("Failed to get skill: {}", e))?
             .ok_or_else(|| format!("Skill not found: {}", params.id))?;
 
-        serde_json::to_string_pretty(&skill)
+        Self::with_etag(&skill.id, &skill, params.if_none_match.as_deref())
+            .and_then(|payload| serde_json::to_string_pretty(&payload))
             .map_err(|e| format!("Failed to serialize skill: {}", e))
     }
 
@@ -59,10 +60,12 @@ impl RestFlowMcpServer {
                 )
             })?;
 
+        let etag = content_etag(&content)?;
         let response = serde_json::json!({
             "skill_id": params.skill_id,
             "ref_id": params.ref_id,
             "content": content,
+            "etag": etag,
         });
 
         serde_json::to_string_pretty(&response)
@@ -174,8 +177,10 @@ impl RestFlowMcpServer {
             })
             .collect();
 
+        let etag = content_etag(&skill)?;
         let response = serde_json::json!({
             "skill_id": skill.id,
+            "etag": etag,
             "name": skill.name,
             "content": skill.content,
             "input": params.input,
@@ -101,6 +101,8 @@ fn create_test_agent_node(prompt: &str) -> AgentNode {
         skill_variables: None,
         skill_preflight_policy_mode: None,
         model_routing: None,
+        dry_run: None,
+        execution_strategy: None,
     }
 }
 
@@ -155,6 +157,196 @@ async fn test_core_backend_session_source_is_resolved_from_binding() {
     );
 }
 
+#[test]
+fn test_context_resource_parses_restflow_uris() {
+    assert_eq!(
+        ContextResource::parse("restflow://skills/skill-1"),
+        Some(ContextResource::Skill("skill-1"))
+    );
+    assert_eq!(
+        ContextResource::parse("restflow://memory/agent-1"),
+        Some(ContextResource::Memory("agent-1"))
+    );
+    assert_eq!(
+        ContextResource::parse("restflow://deliverables/task-1/d-1"),
+        Some(ContextResource::Deliverable {
+            task_id: "task-1",
+            deliverable_id: "d-1",
+        })
+    );
+    assert_eq!(ContextResource::parse("restflow://skills/"), None);
+    assert_eq!(ContextResource::parse("restflow://notes/a/b"), None);
+    assert_eq!(
+        ContextResource::parse("restflow://deliverables/task-1"),
+        None
+    );
+    assert_eq!(ContextResource::parse("file:///etc/passwd"), None);
+}
+
+#[tokio::test]
+async fn test_context_resources_list_and_read_skills_and_notes() {
+    let (server, core, _db, _agents, _guard) = create_test_server().await;
+    let skill = create_test_skill("resource-skill", "Resource Skill");
+    core.storage.skills.create(&skill).unwrap();
+    let note = core
+        .storage
+        .work_items
+        .create_note(crate::models::WorkItemSpec {
+            folder: "ideas".to_string(),
+            title: "Release checklist".to_string(),
+            content: "Tag the release.".to_string(),
+            priority: None,
+            tags: Vec::new(),
+        })
+        .unwrap();
+
+    let resources = server.list_context_resources(None).await.unwrap();
+    let uris: Vec<&str> = resources.iter().map(|r| r.uri.as_str()).collect();
+    assert!(uris.contains(&"restflow://skills/resource-skill"));
+    let note_uri = format!("restflow://notes/{}", note.id);
+    assert!(uris.contains(&note_uri.as_str()));
+    let default_agent = core.storage.agents.resolve_default_agent().unwrap();
+    let memory_uri = format!("restflow://memory/{}", default_agent.id);
+    assert!(uris.contains(&memory_uri.as_str()));
+
+    let (mime_type, text) = server
+        .read_context_resource(&ContextResource::parse(&note_uri).unwrap())
+        .await
+        .unwrap()
+        .expect("note resource should exist");
+    assert_eq!(mime_type, "text/markdown");
+    assert!(text.contains("# Release checklist"));
+    assert!(text.contains("Tag the release."));
+
+    let missing = server
+        .read_context_resource(&ContextResource::Skill("missing"))
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn test_apply_skill_prompt_renders_skill_content() {
+    let (server, core, _db, _agents, _guard) = create_test_server().await;
+    let skill = create_test_skill("prompt-skill", "Prompt Skill");
+    core.storage.skills.create(&skill).unwrap();
+
+    let prompts = RestFlowMcpServer::context_prompts(None);
+    assert!(
+        prompts
+            .iter()
+            .any(|prompt| prompt.name == resources::APPLY_SKILL_PROMPT)
+    );
+
+    let arguments = json!({ "skill_id": "prompt-skill", "input": "draft notes" })
+        .as_object()
+        .cloned();
+    let result = server
+        .render_context_prompt(resources::APPLY_SKILL_PROMPT, &arguments)
+        .await
+        .unwrap();
+    let message = serde_json::to_string(&result.messages).unwrap();
+    assert!(message.contains("Prompt Skill"));
+    assert!(message.contains("Content here."));
+    assert!(message.contains("draft notes"));
+
+    let missing = server
+        .render_context_prompt(resources::APPLY_SKILL_PROMPT, &None)
+        .await;
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_api_token_agent_scope_resolves_task_targets() {
+    let (server, core, _db, _agents, _guard) = create_test_server().await;
+    let task = core
+        .storage
+        .background_agents
+        .create_task(
+            "Scoped Task".to_string(),
+            "agent-hidden".to_string(),
+            TaskSchedule::default(),
+        )
+        .unwrap();
+    let service = crate::services::api_tokens::ApiTokenService::from_storage(&core.storage);
+    let issued = service
+        .issue(
+            "scoped".to_string(),
+            crate::models::ApiTokenScope {
+                allowed_agents: Some(vec!["agent-allowed".to_string()]),
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+    let grant = service.authenticate(&issued.secret).unwrap();
+    let authorize = |tool: &'static str, arguments: Value| {
+        let server = server.clone();
+        let grant = grant.clone();
+        async move {
+            server
+                .authorize_api_token_call(&grant, tool, arguments.as_object())
+                .await
+        }
+    };
+
+    let by_task = authorize(
+        "manage_tasks",
+        json!({ "operation": "progress", "id": task.id }),
+    )
+    .await;
+    assert!(matches!(by_task, Err(ApiTokenError::Forbidden(_))));
+
+    let untargeted = authorize("chat_session_list", json!({})).await;
+    assert!(matches!(untargeted, Err(ApiTokenError::Forbidden(_))));
+
+    let allowed = authorize("memory_stats", json!({ "agent_id": "agent-allowed" })).await;
+    assert!(allowed.is_ok());
+    assert!(authorize("list_skills", json!({})).await.is_ok());
+    assert!(authorize("list_agents", json!({})).await.is_ok());
+
+    // Tools outside the unscoped list fail closed when they name no agent.
+    for tool in ["manage_hooks", "bash", "some_future_tool"] {
+        let result = authorize(tool, json!({})).await;
+        assert!(matches!(result, Err(ApiTokenError::Forbidden(_))), "{tool}");
+    }
+}
+
+#[tokio::test]
+async fn test_list_agents_respects_token_scope() {
+    let (server, core, _temp_dir, _temp_agents, _guard) = create_test_server().await;
+    let visible = crate::services::agent::create_agent(
+        &core,
+        "Visible".to_string(),
+        create_test_agent_node("Prompt"),
+    )
+    .await
+    .unwrap();
+    crate::services::agent::create_agent(
+        &core,
+        "Hidden".to_string(),
+        create_test_agent_node("Prompt"),
+    )
+    .await
+    .unwrap();
+
+    let scope = crate::models::ApiTokenScope {
+        allowed_agents: Some(vec![visible.id.clone()]),
+        ..Default::default()
+    };
+    let json = server.handle_list_agents(Some(&scope)).await.unwrap();
+    let agents: Vec<AgentSummary> = serde_json::from_str(&json).unwrap();
+    assert_eq!(agents.len(), 1);
+    assert_eq!(agents[0].id, visible.id);
+
+    let json = server
+        .handle_list_agents(Some(&crate::models::ApiTokenScope::default()))
+        .await
+        .unwrap();
+    let agents: Vec<AgentSummary> = serde_json::from_str(&json).unwrap();
+    assert_eq!(agents.len(), 3);
+}
+
 #[tokio::test]
 async fn test_core_backend_backfills_legacy_external_route_to_binding() {
     let (server, core, _db, _agents, _guard) = create_test_server().await;
@@ -310,6 +502,7 @@ async fn test_get_skill_success() {
 
     let params = GetSkillParams {
         id: "test-skill".to_string(),
+        if_none_match: None,
     };
     let result = server.handle_get_skill(params).await;
 
@@ -327,6 +520,7 @@ async fn test_get_skill_not_found() {
 
     let params = GetSkillParams {
         id: "nonexistent".to_string(),
+        if_none_match: None,
     };
     let result = server.handle_get_skill(params).await;
 
@@ -491,6 +685,7 @@ async fn test_update_skill_success() {
     // Verify changes
     let get_params = GetSkillParams {
         id: "test-skill".to_string(),
+        if_none_match: None,
     };
     let json = server.handle_get_skill(get_params).await.unwrap();
     let updated: Skill = serde_json::from_str(&json).unwrap();
@@ -568,6 +763,7 @@ async fn test_update_skill_partial() {
 
     let get_params = GetSkillParams {
         id: "test-skill".to_string(),
+        if_none_match: None,
     };
     let json = server.handle_get_skill(get_params).await.unwrap();
     let updated: Skill = serde_json::from_str(&json).unwrap();
@@ -599,6 +795,7 @@ async fn test_delete_skill_success() {
     // Verify deletion
     let get_params = GetSkillParams {
         id: "test-skill".to_string(),
+        if_none_match: None,
     };
     let get_result = server.handle_get_skill(get_params).await;
     assert!(get_result.is_err());
@@ -613,7 +810,7 @@ async fn test_list_agents_default() {
     // AppCore creates a default agent on initialization
     let (server, _core, _temp_dir, _temp_agents, _guard) = create_test_server().await;
 
-    let result = server.handle_list_agents().await;
+    let result = server.handle_list_agents(None).await;
 
     assert!(result.is_ok());
     let json = result.unwrap();
@@ -638,7 +835,7 @@ async fn test_list_agents_multiple() {
         .await
         .unwrap();
 
-    let result = server.handle_list_agents().await;
+    let result = server.handle_list_agents(None).await;
 
     assert!(result.is_ok());
     let json = result.unwrap();
@@ -658,6 +855,7 @@ async fn test_get_agent_success() {
 
     let params = GetAgentParams {
         id: stored.id.clone(),
+        if_none_match: None,
     };
     let result = server.handle_get_agent(params).await;
 
@@ -675,12 +873,127 @@ async fn test_get_agent_not_found() {
 
     let params = GetAgentParams {
         id: "nonexistent".to_string(),
+        if_none_match: None,
     };
     let result = server.handle_get_agent(params).await;
 
     assert!(result.is_err());
 }
 
+// =========================================================================
+// Content Cache Tests
+// =========================================================================
+
+#[tokio::test]
+async fn test_get_skill_etag_tracks_storage_writes() {
+    let (server, core, _temp_dir, _temp_agents, _guard) = create_test_server().await;
+
+    let mut skill = create_test_skill("etag-skill", "Etag Skill");
+    core.storage.skills.create(&skill).unwrap();
+
+    let get = |if_none_match: Option<String>| {
+        server.handle_get_skill(GetSkillParams {
+            id: "etag-skill".to_string(),
+            if_none_match,
+        })
+    };
+
+    let first: Value = serde_json::from_str(&get(None).await.unwrap()).unwrap();
+    let etag = first["etag"].as_str().unwrap().to_string();
+    assert_eq!(first["content"], skill.content);
+
+    let unchanged: Value = serde_json::from_str(&get(Some(etag.clone())).await.unwrap()).unwrap();
+    assert_eq!(unchanged["not_modified"], true);
+    assert!(unchanged.get("content").is_none());
+
+    // Writes outside MCP bump the storage revision and drop cached content.
+    skill.content = "# Changed".to_string();
+    core.storage.skills.update("etag-skill", &skill).unwrap();
+
+    let changed: Value = serde_json::from_str(&get(Some(etag.clone())).await.unwrap()).unwrap();
+    assert_eq!(changed["content"], "# Changed");
+    assert_ne!(changed["etag"], etag);
+
+    let stats = server
+        .cache_stats()
+        .expect("core server should cache content");
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.invalidations, 1);
+}
+
+#[tokio::test]
+async fn test_get_agent_if_none_match_returns_not_modified() {
+    let (server, core, _temp_dir, _temp_agents, _guard) = create_test_server().await;
+    let stored = crate::services::agent::create_agent(
+        &core,
+        "Etag Agent".to_string(),
+        create_test_agent_node("Etag prompt"),
+    )
+    .await
+    .unwrap();
+
+    let first: Value = serde_json::from_str(
+        &server
+            .handle_get_agent(GetAgentParams {
+                id: stored.id.clone(),
+                if_none_match: None,
+            })
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let etag = first["etag"].as_str().unwrap().to_string();
+
+    let second: Value = serde_json::from_str(
+        &server
+            .handle_get_agent(GetAgentParams {
+                id: stored.id.clone(),
+                if_none_match: Some(etag.clone()),
+            })
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(second["id"], stored.id);
+    assert_eq!(second["etag"], etag);
+    assert_eq!(second["not_modified"], true);
+}
+
+#[tokio::test]
+async fn test_caching_backend_invalidates_on_writes_and_ttl() {
+    let cache = CachingBackend::new(Arc::new(MockBackend::new()), Duration::from_secs(60));
+
+    cache.list_skills().await.unwrap();
+    cache.list_skills().await.unwrap();
+    cache.list_runtime_tools().await.unwrap();
+    cache.list_runtime_tools().await.unwrap();
+    assert_eq!(
+        cache.stats(),
+        McpCacheStats {
+            hits: 2,
+            misses: 2,
+            invalidations: 0,
+        }
+    );
+
+    let skill = cache.get_skill("mock-skill").await.unwrap().unwrap();
+    cache.update_skill(skill).await.unwrap();
+    cache.list_skills().await.unwrap();
+    assert_eq!(cache.stats().misses, 4);
+    assert_eq!(cache.stats().invalidations, 1);
+
+    // Tool lists are not content and survive skill writes.
+    cache.list_runtime_tools().await.unwrap();
+    assert_eq!(cache.stats().hits, 3);
+
+    let expired = CachingBackend::new(Arc::new(MockBackend::new()), Duration::ZERO);
+    expired.list_agents().await.unwrap();
+    expired.list_agents().await.unwrap();
+    assert_eq!(expired.stats().hits, 0);
+    assert_eq!(expired.stats().misses, 2);
+}
+
 // =========================================================================
 // ServerHandler Trait Tests
 // =========================================================================
@@ -792,6 +1105,7 @@ async fn test_skill_crud_workflow() {
     // 3. Get
     let get_params = GetSkillParams {
         id: skill_id.clone(),
+        if_none_match: None,
     };
     let get_json = server.handle_get_skill(get_params).await.unwrap();
     let skill: Skill = serde_json::from_str(&get_json).unwrap();
@@ -810,6 +1124,7 @@ async fn test_skill_crud_workflow() {
     // 5. Verify update
     let get_params2 = GetSkillParams {
         id: skill_id.clone(),
+        if_none_match: None,
     };
     let get_json2 = server.handle_get_skill(get_params2).await.unwrap();
     let updated_skill: Skill = serde_json::from_str(&get_json2).unwrap();
@@ -982,6 +1297,8 @@ impl MockBackend {
                 skill_variables: None,
                 skill_preflight_policy_mode: None,
                 model_routing: None,
+                dry_run: None,
+                execution_strategy: None,
             },
             prompt_file: None,
             created_at: None,
@@ -1114,6 +1431,14 @@ impl McpBackend for MockBackend {
         })
     }
 
+    async fn list_notes(&self, _query: ItemQuery) -> Result<Vec<WorkItem>, String> {
+        Ok(Vec::new())
+    }
+
+    async fn get_note(&self, _id: &str) -> Result<Option<WorkItem>, String> {
+        Ok(None)
+    }
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>, String> {
         Ok(vec![ChatSessionSummary::from(&self.session)])
     }
@@ -1526,6 +1851,14 @@ impl McpBackend for LegacyBackgroundAgentBackend {
         self.inner.get_memory_stats(agent_id).await
     }
 
+    async fn list_notes(&self, query: ItemQuery) -> Result<Vec<WorkItem>, String> {
+        self.inner.list_notes(query).await
+    }
+
+    async fn get_note(&self, id: &str) -> Result<Option<WorkItem>, String> {
+        self.inner.get_note(id).await
+    }
+
     async fn list_sessions(&self) -> Result<Vec<ChatSessionSummary>, String> {
         self.inner.list_sessions().await
     }
@@ -1689,6 +2022,7 @@ async fn test_task_methods_remain_compatible_with_legacy_backend_implementations
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .await
         .unwrap();
@@ -1739,6 +2073,7 @@ async fn test_legacy_background_agent_aliases_remain_compatible_with_task_only_b
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .await
         .unwrap();
@@ -1773,6 +2108,7 @@ async fn test_canonical_task_methods_work_for_legacy_background_agent_backend()
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .await
         .unwrap();
@@ -5,6 +5,9 @@ use super::*;
 pub struct GetSkillParams {
     /// The ID of the skill to retrieve
     pub id: String,
+    /// ETag from a previous read; returns only `not_modified` when unchanged
+    #[serde(default)]
+    pub if_none_match: Option<String>,
 }
 
 /// Parameters for get_skill_reference tool
@@ -62,6 +65,9 @@ pub struct DeleteSkillParams {
 pub struct GetAgentParams {
     /// The ID of the agent to retrieve
     pub id: String,
+    /// ETag from a previous read; returns only `not_modified` when unchanged
+    #[serde(default)]
+    pub if_none_match: Option<String>,
 }
 
 /// Parameters for memory_search tool
@@ -0,0 +1,398 @@
+//! Memory consolidation.
+//!
+//! A consolidation pass groups related chunks of an agent's memory, asks a
+//! [`ChunkSummarizer`] to merge each group into one higher-level chunk, and
+//! archives the originals so search returns the summary instead. Chunks are
+//! grouped by embedding similarity when vector search is enabled, otherwise
+//! by identical tag sets.
+
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use restflow_ai::llm::{CachingLlmClient, CompletionRequest, Message};
+use restflow_ai::{DefaultLlmClientFactory, LlmClient, LlmClientFactory};
+use restflow_storage::MemorySettings;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::auth::build_runtime_api_keys;
+use crate::models::memory::{MemoryChunk, MemorySource};
+use crate::models::{ModelId, provider_default_model, secret_provider_resolution_order};
+use crate::storage::{MemoryStorage, SecretStorage};
+
+/// `tool_name` recorded on summary chunks written by consolidation.
+pub const CONSOLIDATION_SOURCE: &str = "memory_consolidation";
+/// Largest number of chunks merged into one summary.
+const MAX_CLUSTER_SIZE: usize = 12;
+const SUMMARY_MAX_TOKENS: u32 = 1024;
+
+const SUMMARY_PROMPT: &str = "You consolidate an AI agent's long-term memory. \
+Merge the numbered notes into one concise note. Keep every distinct fact, \
+decision, name, date, number and open question; drop repetition and filler. \
+Reply with the merged note only.";
+
+/// Merges a cluster of related chunks into one summary text.
+#[async_trait]
+pub trait ChunkSummarizer: Send + Sync {
+    async fn summarize(&self, chunks: &[MemoryChunk]) -> Result<String>;
+}
+
+/// [`ChunkSummarizer`] backed by an LLM.
+pub struct LlmChunkSummarizer {
+    client: Arc<dyn LlmClient>,
+    model: ModelId,
+}
+
+impl LlmChunkSummarizer {
+    pub fn new(client: Arc<dyn LlmClient>, model: ModelId) -> Self {
+        Self { client, model }
+    }
+
+    /// Build a summarizer for `memory.consolidation_model`, or for the
+    /// default model of the first provider with an API key. Summaries are
+    /// requested at temperature 0 and served from the shared response cache
+    /// when the same cluster is consolidated again.
+    pub fn from_settings(settings: &MemorySettings, secrets: &SecretStorage) -> Result<Self> {
+        let api_keys = build_runtime_api_keys(Some(secrets));
+        let model = match settings.consolidation_model.as_deref() {
+            Some(name) => restflow_models::parse_model_reference(name)
+                .ok_or_else(|| anyhow!("Unknown memory.consolidation_model '{name}'"))?,
+            None => secret_provider_resolution_order()
+                .iter()
+                .find(|provider| api_keys.contains_key(&provider.as_llm_provider()))
+                .map(|provider| provider_default_model(*provider))
+                .ok_or_else(|| anyhow!("No LLM API key configured for memory consolidation"))?,
+        };
+        let api_key = api_keys.get(&model.provider().as_llm_provider()).cloned();
+        let factory = DefaultLlmClientFactory::new(api_keys, ModelId::build_model_specs());
+        let client = factory
+            .create_client(model.as_serialized_str(), api_key.as_deref())
+            .with_context(|| format!("Failed to create client for {}", model.as_str()))?;
+        Ok(Self::new(Arc::new(CachingLlmClient::shared(client)), model))
+    }
+}
+
+#[async_trait]
+impl ChunkSummarizer for LlmChunkSummarizer {
+    async fn summarize(&self, chunks: &[MemoryChunk]) -> Result<String> {
+        let notes = chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| format!("{}. {}", i + 1, chunk.content.trim()))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let mut request =
+            CompletionRequest::new(vec![Message::system(SUMMARY_PROMPT), Message::user(notes)])
+                .with_max_tokens(SUMMARY_MAX_TOKENS);
+        if self.model.supports_temperature() {
+            request = request.with_temperature(0.0);
+        }
+        let response = self.client.complete(request).await?;
+        let summary = response.content.unwrap_or_default().trim().to_string();
+        if summary.is_empty() {
+            return Err(anyhow!("Summarizer returned an empty summary"));
+        }
+        Ok(summary)
+    }
+}
+
+/// Outcome of a consolidation pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConsolidationReport {
+    pub agents: usize,
+    pub clusters: usize,
+    pub chunks_archived: usize,
+    pub chunks_created: usize,
+}
+
+impl ConsolidationReport {
+    fn add(&mut self, other: &ConsolidationReport) {
+        self.agents += other.agents;
+        self.clusters += other.clusters;
+        self.chunks_archived += other.chunks_archived;
+        self.chunks_created += other.chunks_created;
+    }
+}
+
+/// Clusters related memory chunks and replaces them with summaries.
+pub struct MemoryConsolidator {
+    storage: MemoryStorage,
+    summarizer: Arc<dyn ChunkSummarizer>,
+    min_cluster_size: usize,
+    similarity: f32,
+}
+
+impl MemoryConsolidator {
+    pub fn new(storage: MemoryStorage, summarizer: Arc<dyn ChunkSummarizer>) -> Self {
+        Self::with_settings(storage, summarizer, &MemorySettings::default())
+    }
+
+    /// Use the cluster size and similarity from `[memory]`.
+    pub fn with_settings(
+        storage: MemoryStorage,
+        summarizer: Arc<dyn ChunkSummarizer>,
+        settings: &MemorySettings,
+    ) -> Self {
+        Self {
+            storage,
+            summarizer,
+            min_cluster_size: settings.consolidation_min_cluster_size.max(2),
+            similarity: settings.consolidation_similarity as f32,
+        }
+    }
+
+    /// Consolidate the memory of every agent that has chunks.
+    ///
+    /// A failure for one agent is logged and does not stop the others.
+    pub async fn consolidate_all(&self) -> Result<ConsolidationReport> {
+        let agent_ids: BTreeSet<String> = self
+            .storage
+            .list_all_chunks()?
+            .into_iter()
+            .map(|chunk| chunk.agent_id)
+            .collect();
+        let mut report = ConsolidationReport::default();
+        for agent_id in agent_ids {
+            match self.consolidate_agent(&agent_id).await {
+                Ok(agent_report) => report.add(&agent_report),
+                Err(error) => {
+                    warn!(agent_id = %agent_id, error = %error, "Memory consolidation failed");
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Consolidate one agent's memory.
+    pub async fn consolidate_agent(&self, agent_id: &str) -> Result<ConsolidationReport> {
+        let mut report = ConsolidationReport {
+            agents: 1,
+            ..Default::default()
+        };
+        for cluster in self.clusters(agent_id)? {
+            let summary = self.summarizer.summarize(&cluster).await?;
+            let source_ids: Vec<String> = cluster.iter().map(|chunk| chunk.id.clone()).collect();
+            self.storage
+                .store_chunk(&summary_chunk(agent_id, summary, &cluster))?;
+            let archived = self
+                .storage
+                .archive_chunks(&source_ids, chrono::Utc::now().timestamp_millis())?;
+            debug!(agent_id, archived, "Consolidated memory cluster");
+            report.clusters += 1;
+            report.chunks_archived += archived;
+            report.chunks_created += 1;
+        }
+        Ok(report)
+    }
+
+    /// Group an agent's active chunks, oldest first. Earlier summaries are
+    /// left alone so repeated passes do not re-summarize their own output.
+    fn clusters(&self, agent_id: &str) -> Result<Vec<Vec<MemoryChunk>>> {
+        let mut candidates: Vec<MemoryChunk> = self
+            .storage
+            .list_chunks(agent_id)?
+            .into_iter()
+            .filter(|chunk| !chunk.is_archived() && chunk.consolidated_from.is_empty())
+            .collect();
+        if candidates.len() < self.min_cluster_size {
+            return Ok(Vec::new());
+        }
+        candidates.sort_by_key(|chunk| chunk.created_at);
+
+        if self.storage.has_vector_search() {
+            self.clusters_by_similarity(candidates)
+        } else {
+            Ok(self.clusters_by_tags(candidates))
+        }
+    }
+
+    fn clusters_by_similarity(
+        &self,
+        mut remaining: Vec<MemoryChunk>,
+    ) -> Result<Vec<Vec<MemoryChunk>>> {
+        let mut clusters = Vec::new();
+        while !remaining.is_empty() {
+            let seed = remaining.remove(0);
+            let Some(embedding) = self.storage.chunk_embedding(&seed.id)? else {
+                continue;
+            };
+            let ids: Vec<String> = remaining.iter().map(|chunk| chunk.id.clone()).collect();
+            let neighbors: BTreeSet<String> = self
+                .storage
+                .semantic_search_among(&embedding, MAX_CLUSTER_SIZE - 1, &ids)?
+                .into_iter()
+                .filter(|m| m.similarity >= self.similarity)
+                .map(|m| m.chunk.id)
+                .collect();
+            if neighbors.len() + 1 < self.min_cluster_size {
+                continue;
+            }
+            let (members, rest): (Vec<_>, Vec<_>) = remaining
+                .into_iter()
+                .partition(|chunk| neighbors.contains(&chunk.id));
+            remaining = rest;
+            let mut cluster = vec![seed];
+            cluster.extend(members);
+            clusters.push(cluster);
+        }
+        Ok(clusters)
+    }
+
+    fn clusters_by_tags(&self, candidates: Vec<MemoryChunk>) -> Vec<Vec<MemoryChunk>> {
+        let mut groups: BTreeMap<Vec<String>, Vec<MemoryChunk>> = BTreeMap::new();
+        for chunk in candidates {
+            let mut tags = chunk.tags.clone();
+            tags.sort();
+            tags.dedup();
+            if !tags.is_empty() {
+                groups.entry(tags).or_default().push(chunk);
+            }
+        }
+        groups
+            .into_values()
+            .flat_map(|group| {
+                group
+                    .chunks(MAX_CLUSTER_SIZE)
+                    .map(<[MemoryChunk]>::to_vec)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cluster| cluster.len() >= self.min_cluster_size)
+            .collect()
+    }
+}
+
+fn summary_chunk(agent_id: &str, summary: String, cluster: &[MemoryChunk]) -> MemoryChunk {
+    let tags: BTreeSet<String> = cluster
+        .iter()
+        .flat_map(|chunk| chunk.tags.iter().cloned())
+        .collect();
+    let mut chunk = MemoryChunk::new(agent_id.to_string(), summary)
+        .with_source(MemorySource::AgentGenerated {
+            tool_name: CONSOLIDATION_SOURCE.to_string(),
+        })
+        .with_tags(tags.into_iter().collect())
+        .with_consolidated_from(cluster.iter().map(|chunk| chunk.id.clone()).collect());
+    let first_session = cluster[0].session_id.as_ref();
+    if let Some(session_id) = first_session
+        && cluster
+            .iter()
+            .all(|chunk| chunk.session_id.as_ref() == first_session)
+    {
+        chunk = chunk.with_session(session_id.clone());
+    }
+    chunk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryEmbedder;
+    use crate::memory::embedding::tests::{BagOfWordsProvider, TEST_DIMENSION};
+    use redb::Database;
+    use restflow_storage::VectorConfig;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    /// Joins chunk contents and records each cluster it was given.
+    #[derive(Default)]
+    struct JoiningSummarizer {
+        clusters: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl ChunkSummarizer for JoiningSummarizer {
+        async fn summarize(&self, chunks: &[MemoryChunk]) -> Result<String> {
+            self.clusters.lock().unwrap().push(chunks.len());
+            Ok(chunks
+                .iter()
+                .map(|chunk| chunk.content.as_str())
+                .collect::<Vec<_>>()
+                .join(" / "))
+        }
+    }
+
+    fn store(storage: &MemoryStorage, content: &str, tags: &[&str]) -> MemoryChunk {
+        let chunk = MemoryChunk::new("agent-1".to_string(), content.to_string())
+            .with_tags(tags.iter().map(|tag| tag.to_string()).collect());
+        storage.store_chunk(&chunk).unwrap();
+        chunk
+    }
+
+    #[tokio::test]
+    async fn consolidates_chunks_sharing_tags() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("consolidate.db")).unwrap());
+        let storage = MemoryStorage::new(db).unwrap();
+        let originals = [
+            store(&storage, "Deploys run on Fridays", &["deploy"]),
+            store(&storage, "Deploys need a green CI run", &["deploy"]),
+            store(
+                &storage,
+                "Deploy owner is the on-call engineer",
+                &["deploy"],
+            ),
+        ];
+        store(&storage, "The office closes at six", &["office"]);
+
+        let summarizer = Arc::new(JoiningSummarizer::default());
+        let consolidator = MemoryConsolidator::new(storage.clone(), summarizer.clone());
+        let report = consolidator.consolidate_all().await.unwrap();
+        assert_eq!(
+            report,
+            ConsolidationReport {
+                agents: 1,
+                clusters: 1,
+                chunks_archived: 3,
+                chunks_created: 1,
+            }
+        );
+        assert_eq!(*summarizer.clusters.lock().unwrap(), vec![3]);
+
+        let stats = storage.get_stats("agent-1").unwrap();
+        assert_eq!(stats.chunk_count, 5);
+        assert_eq!(stats.archived_count, 3);
+        assert_eq!(stats.consolidated_count, 1);
+        for chunk in &originals {
+            assert!(storage.get_chunk(&chunk.id).unwrap().unwrap().is_archived());
+        }
+
+        let query = crate::models::memory::MemorySearchQuery::new("agent-1".to_string())
+            .with_query("deploy".to_string());
+        let results = storage.search(&query).unwrap();
+        assert_eq!(results.chunks.len(), 1);
+        assert_eq!(results.chunks[0].consolidated_from.len(), 3);
+
+        // A second pass leaves the summary alone.
+        let report = consolidator.consolidate_all().await.unwrap();
+        assert_eq!(report.clusters, 0);
+    }
+
+    #[tokio::test]
+    async fn clusters_by_embedding_similarity() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("consolidate.db")).unwrap());
+        let config = VectorConfig {
+            dimension: TEST_DIMENSION,
+            ..VectorConfig::default()
+        };
+        let storage = MemoryStorage::with_vectors(db, config).unwrap();
+        store(&storage, "release checklist deploy", &[]);
+        store(&storage, "deploy release checklist", &[]);
+        store(&storage, "checklist deploy release", &[]);
+        store(&storage, "office plants watering", &[]);
+        MemoryEmbedder::new(Arc::new(BagOfWordsProvider), storage.clone())
+            .embed_pending("agent-1")
+            .await
+            .unwrap();
+
+        let summarizer = Arc::new(JoiningSummarizer::default());
+        let report = MemoryConsolidator::new(storage.clone(), summarizer.clone())
+            .consolidate_agent("agent-1")
+            .await
+            .unwrap();
+        assert_eq!(report.clusters, 1);
+        assert_eq!(report.chunks_archived, 3);
+        assert_eq!(*summarizer.clusters.lock().unwrap(), vec![3]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Embedding providers for semantic memory recall.
+//!
+//! An [`EmbeddingProvider`] turns text into vectors. [`MemoryEmbedder`]
+//! pairs a provider with [`MemoryStorage`] so chunks saved without an
+//! embedding are embedded lazily before a semantic search runs.
+//! [`CompactionEmbedder`] lends the same model to agent context compaction.
+
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use restflow_ai::AiError;
+use restflow_ai::agent::context_manager::MessageEmbedder;
+use restflow_storage::{MemorySettings, VectorConfig};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::storage::{MemoryStorage, SecretStorage};
+
+/// Default OpenAI-compatible endpoint.
+pub const OPENAI_EMBEDDINGS_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_API_KEY_SECRET: &str = "OPENAI_API_KEY";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Texts sent per embeddings request.
+const EMBED_BATCH_SIZE: usize = 64;
+
+/// Produces embedding vectors for text.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Model identifier.
+    fn model(&self) -> &str;
+
+    /// Width of returned vectors.
+    fn dimension(&self) -> usize;
+
+    /// Embed `texts`, returning one vector per input in the same order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+}
+
+/// Provider for the OpenAI `/embeddings` API and compatible local servers
+/// (llama.cpp, Ollama) serving gguf models.
+pub struct OpenAiEmbeddingProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    dimension: usize,
+}
+
+impl OpenAiEmbeddingProvider {
+    pub fn new(
+        base_url: impl Into<String>,
+        api_key: Option<String>,
+        model: impl Into<String>,
+        dimension: usize,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key,
+            model: model.into(),
+            dimension,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingItem>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingItem {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+fn parse_embeddings(
+    response: EmbeddingsResponse,
+    expected: usize,
+    dimension: usize,
+) -> Result<Vec<Vec<f32>>> {
+    let mut items = response.data;
+    if items.len() != expected {
+        bail!(
+            "Embeddings endpoint returned {} vectors for {} inputs",
+            items.len(),
+            expected
+        );
+    }
+    items.sort_by_key(|item| item.index);
+    items
+        .into_iter()
+        .map(|item| {
+            if item.embedding.len() != dimension {
+                bail!(
+                    "Embedding has {} dimensions, expected {} (check memory.embedding_dimension)",
+                    item.embedding.len(),
+                    dimension
+                );
+            }
+            Ok(item.embedding)
+        })
+        .collect()
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiEmbeddingProvider {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut request = self
+            .client
+            .post(format!("{}/embeddings", self.base_url))
+            .json(&json!({ "model": self.model, "input": texts }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await.context("Embeddings request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!("Embeddings endpoint returned {status}: {body}");
+        }
+        let response: EmbeddingsResponse = response
+            .json()
+            .await
+            .context("Invalid embeddings response")?;
+        parse_embeddings(response, texts.len(), self.dimension)
+    }
+}
+
+/// Vector storage settings matching the configured embedding model.
+pub fn vector_config(settings: &MemorySettings) -> Option<VectorConfig> {
+    settings.embedding_model.as_ref()?;
+    Some(VectorConfig {
+        dimension: settings.embedding_dimension,
+        ..VectorConfig::default()
+    })
+}
+
+/// Build the provider configured in `[memory]`, if any.
+///
+/// The API key is read from the configured secret, falling back to the
+/// environment variable of the same name.
+pub fn embedding_provider_from_settings(
+    settings: &MemorySettings,
+    secrets: &SecretStorage,
+) -> Result<Option<Arc<dyn EmbeddingProvider>>> {
+    let Some(model) = &settings.embedding_model else {
+        return Ok(None);
+    };
+    let base_url = settings
+        .embedding_base_url
+        .as_deref()
+        .unwrap_or(OPENAI_EMBEDDINGS_BASE_URL);
+    // Local servers usually need no key, so only the OpenAI default has one.
+    let key_name = match (
+        &settings.embedding_api_key_secret,
+        &settings.embedding_base_url,
+    ) {
+        (Some(name), _) => Some(name.as_str()),
+        (None, None) => Some(DEFAULT_API_KEY_SECRET),
+        (None, Some(_)) => None,
+    };
+    let api_key = match key_name {
+        Some(name) => {
+            let key = secrets
+                .get_non_empty(name)?
+                .or_else(|| std::env::var(name).ok().filter(|v| !v.trim().is_empty()));
+            Some(key.ok_or_else(|| anyhow!("Embedding API key secret '{name}' is not set"))?)
+        }
+        None => None,
+    };
+    Ok(Some(Arc::new(OpenAiEmbeddingProvider::new(
+        base_url,
+        api_key,
+        model.clone(),
+        settings.embedding_dimension,
+    ))))
+}
+
+/// Embeds memory chunks and queries for semantic search.
+pub struct MemoryEmbedder {
+    provider: Arc<dyn EmbeddingProvider>,
+    storage: MemoryStorage,
+}
+
+impl MemoryEmbedder {
+    pub fn new(provider: Arc<dyn EmbeddingProvider>, storage: MemoryStorage) -> Self {
+        Self { provider, storage }
+    }
+
+    /// Build an embedder from `[memory]` settings.
+    ///
+    /// Returns `None` when no embedding model is configured, vector storage
+    /// is off, or the provider cannot be set up (logged).
+    pub fn from_settings(
+        settings: &MemorySettings,
+        storage: MemoryStorage,
+        secrets: &SecretStorage,
+    ) -> Option<Arc<Self>> {
+        if !storage.has_vector_search() {
+            return None;
+        }
+        match embedding_provider_from_settings(settings, secrets) {
+            Ok(provider) => provider.map(|provider| Arc::new(Self::new(provider, storage))),
+            Err(error) => {
+                warn!(error = %error, "Semantic memory search disabled");
+                None
+            }
+        }
+    }
+
+    pub fn model(&self) -> &str {
+        self.provider.model()
+    }
+
+    /// Embed an agent's chunks that have no vector yet.
+    ///
+    /// Returns the number of chunks embedded.
+    pub async fn embed_pending(&self, agent_id: &str) -> Result<usize> {
+        let pending = self.storage.list_chunks_without_embedding(agent_id)?;
+        for batch in pending.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|chunk| chunk.content.clone()).collect();
+            let embeddings = self.provider.embed(&texts).await?;
+            for (chunk, embedding) in batch.iter().zip(embeddings) {
+                self.storage.set_chunk_embedding(&chunk.id, &embedding)?;
+            }
+        }
+        Ok(pending.len())
+    }
+
+    /// Embed `query` for searching `agent_id`'s memory, embedding any of
+    /// the agent's chunks that are still missing vectors first.
+    pub async fn embed_query(&self, agent_id: &str, query: &str) -> Result<Vec<f32>> {
+        self.embed_pending(agent_id).await?;
+        self.provider
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("Embeddings endpoint returned no vector"))
+    }
+}
+
+/// Scores message relevance for agent context compaction with the
+/// configured embedding model.
+pub struct CompactionEmbedder {
+    provider: Arc<dyn EmbeddingProvider>,
+}
+
+impl CompactionEmbedder {
+    pub fn new(provider: Arc<dyn EmbeddingProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl MessageEmbedder for CompactionEmbedder {
+    async fn embed(&self, texts: &[String]) -> restflow_ai::error::Result<Vec<Vec<f32>>> {
+        self.provider
+            .embed(texts)
+            .await
+            .map_err(|error| AiError::Agent(format!("Embedding failed: {error}")))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::models::memory::MemoryChunk;
+    use tempfile::tempdir;
+
+    /// Hashes words into a small vector so related texts land close together.
+    pub(crate) struct BagOfWordsProvider;
+
+    pub(crate) const TEST_DIMENSION: usize = 16;
+
+    #[async_trait]
+    impl EmbeddingProvider for BagOfWordsProvider {
+        fn model(&self) -> &str {
+            "bag-of-words"
+        }
+
+        fn dimension(&self) -> usize {
+            TEST_DIMENSION
+        }
+
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut vector = vec![0.0; TEST_DIMENSION];
+                    for word in text.split_whitespace() {
+                        let word = word.to_lowercase();
+                        let bucket = word.bytes().map(usize::from).sum::<usize>() % TEST_DIMENSION;
+                        vector[bucket] += 1.0;
+                    }
+                    vector
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn parse_embeddings_orders_by_index_and_checks_dimension() {
+        let response: EmbeddingsResponse = serde_json::from_value(json!({
+            "data": [
+                { "index": 1, "embedding": [0.0, 1.0] },
+                { "index": 0, "embedding": [1.0, 0.0] }
+            ]
+        }))
+        .unwrap();
+        let vectors = parse_embeddings(response, 2, 2).unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+        let response: EmbeddingsResponse =
+            serde_json::from_value(json!({ "data": [{ "index": 0, "embedding": [1.0] }] }))
+                .unwrap();
+        assert!(parse_embeddings(response, 1, 2).is_err());
+    }
+
+    #[test]
+    fn no_provider_without_model() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(redb::Database::create(dir.path().join("embed.db")).unwrap());
+        let secrets = SecretStorage::new(db).unwrap();
+        let settings = MemorySettings::default();
+        assert!(vector_config(&settings).is_none());
+        assert!(
+            embedding_provider_from_settings(&settings, &secrets)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn embed_pending_backfills_missing_vectors() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(redb::Database::create(dir.path().join("embed.db")).unwrap());
+        let config = VectorConfig {
+            dimension: TEST_DIMENSION,
+            ..VectorConfig::default()
+        };
+        let storage = MemoryStorage::with_vectors(db, config).unwrap();
+        storage
+            .store_chunk(&MemoryChunk::new(
+                "agent-1".to_string(),
+                "the deploy runbook lives in the ops wiki".to_string(),
+            ))
+            .unwrap();
+
+        let embedder = MemoryEmbedder::new(Arc::new(BagOfWordsProvider), storage.clone());
+        assert_eq!(embedder.embed_pending("agent-1").await.unwrap(), 1);
+        assert_eq!(embedder.embed_pending("agent-1").await.unwrap(), 0);
+
+        let query = embedder
+            .embed_query("agent-1", "where is the deploy runbook")
+            .await
+            .unwrap();
+        let matches = storage.semantic_search("agent-1", &query, 3).unwrap();
+        assert_eq!(matches.len(), 1);
+    }
+}
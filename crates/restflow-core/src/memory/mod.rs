@@ -3,6 +3,7 @@
 //! This module provides utilities for managing agent memory, including:
 //! - Text chunking for efficient storage
 //! - Search engine with relevance scoring for memory retrieval
+//! - Keyword tag suggestions applied when chunks are saved
 //! - Markdown export for human-readable output (TODO)
 //!
 //! # Architecture
@@ -56,18 +57,29 @@
 //! ```
 
 mod chunker;
+pub mod consolidation;
+pub mod embedding;
 mod export;
 mod mirror;
 mod search;
+mod tagging;
 mod unified_search;
 
 pub use chunker::{TextChunker, TextChunkerBuilder};
+pub use consolidation::{
+    ChunkSummarizer, ConsolidationReport, LlmChunkSummarizer, MemoryConsolidator,
+};
+pub use embedding::{
+    CompactionEmbedder, EmbeddingProvider, MemoryEmbedder, OpenAiEmbeddingProvider,
+    embedding_provider_from_settings,
+};
 pub use export::{ExportOptions, ExportResult, MemoryExporter, MemoryExporterBuilder};
 pub use mirror::{ChatSessionMirror, MessageMirror, NoopMirror};
 pub use search::{
     RankedSearchResult, ScoreBreakdown, ScoredChunk, SearchConfig, SearchEngine,
     SearchEngineBuilder,
 };
+pub use tagging::{TagSuggester, suggest_tags_for_chunk};
 pub use unified_search::{
     SearchResultSource, SourceCounts, UnifiedSearchConfig, UnifiedSearchEngine,
     UnifiedSearchResult, UnifiedSearchResults,
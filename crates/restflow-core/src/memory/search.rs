@@ -273,7 +273,7 @@ impl SearchEngine {
         let search_lower = search_text.to_lowercase();
 
         let match_count = match mode {
-            SearchMode::Keyword => {
+            SearchMode::Keyword | SearchMode::Semantic | SearchMode::Hybrid => {
                 // Count occurrences of each keyword
                 let keywords: Vec<&str> = search_lower.split_whitespace().collect();
                 let mut total_matches = 0u32;
@@ -0,0 +1,292 @@
+//! Tag suggestions for new memory chunks.
+//!
+//! [`TagSuggester`] is a lightweight keyword classifier that runs at save time
+//! without calling a model. It prefers tags the agent already uses, so the
+//! vocabulary converges instead of growing a new synonym for every chunk, and
+//! falls back to a small set of built-in categories.
+
+use crate::models::memory::MemoryChunk;
+use crate::storage::{MemorySettings, MemoryStorage};
+use anyhow::Result;
+
+/// Built-in categories with the keywords that vote for them.
+const CATEGORY_RULES: &[(&str, &[&str])] = &[
+    (
+        "bug",
+        &[
+            "bug",
+            "error",
+            "crash",
+            "crashes",
+            "exception",
+            "panic",
+            "failed",
+            "failing",
+            "broken",
+            "regression",
+            "stacktrace",
+        ],
+    ),
+    (
+        "todo",
+        &[
+            "todo",
+            "follow-up",
+            "followup",
+            "remind",
+            "reminder",
+            "deadline",
+            "pending",
+            "later",
+        ],
+    ),
+    (
+        "decision",
+        &[
+            "decided",
+            "decision",
+            "agreed",
+            "chose",
+            "approved",
+            "tradeoff",
+            "consensus",
+        ],
+    ),
+    (
+        "preference",
+        &[
+            "prefer",
+            "prefers",
+            "preference",
+            "likes",
+            "dislikes",
+            "favorite",
+            "favourite",
+            "always",
+            "never",
+        ],
+    ),
+    (
+        "meeting",
+        &[
+            "meeting",
+            "standup",
+            "agenda",
+            "attendees",
+            "minutes",
+            "call",
+            "sync",
+        ],
+    ),
+    (
+        "code",
+        &[
+            "function",
+            "struct",
+            "compile",
+            "compiler",
+            "cargo",
+            "commit",
+            "refactor",
+            "repository",
+            "branch",
+            "api",
+        ],
+    ),
+    (
+        "config",
+        &[
+            "config",
+            "configuration",
+            "setting",
+            "settings",
+            "env",
+            "environment",
+            "toml",
+            "yaml",
+        ],
+    ),
+    (
+        "research",
+        &[
+            "paper",
+            "study",
+            "article",
+            "benchmark",
+            "findings",
+            "investigate",
+            "investigation",
+        ],
+    ),
+];
+
+/// Distinct keyword hits a built-in category needs before it is suggested.
+const MIN_CATEGORY_HITS: usize = 2;
+
+/// Score given to a tag from the existing vocabulary found in the content.
+/// Higher than any single category hit so known tags win ties.
+const VOCABULARY_SCORE: usize = MIN_CATEGORY_HITS + 1;
+
+/// Keyword-based tag suggester.
+#[derive(Debug, Clone)]
+pub struct TagSuggester {
+    max_suggestions: usize,
+    vocabulary: Vec<String>,
+}
+
+impl TagSuggester {
+    pub fn new(max_suggestions: usize) -> Self {
+        Self {
+            max_suggestions,
+            vocabulary: Vec::new(),
+        }
+    }
+
+    /// Build a suggester from memory settings, or `None` when auto-tagging is
+    /// disabled.
+    pub fn from_settings(settings: &MemorySettings) -> Option<Self> {
+        (settings.auto_tag && settings.max_suggested_tags > 0)
+            .then(|| Self::new(settings.max_suggested_tags))
+    }
+
+    /// Tags already in use that should be suggested when they appear in the
+    /// content. Internal tags (prefixed with `__`) are ignored.
+    pub fn with_vocabulary(mut self, tags: impl IntoIterator<Item = String>) -> Self {
+        self.vocabulary = tags
+            .into_iter()
+            .filter(|tag| !tag.starts_with("__") && !tag.trim().is_empty())
+            .collect();
+        self
+    }
+
+    /// Suggest tags for `content`, skipping any already in `existing`.
+    pub fn suggest(&self, content: &str, existing: &[String]) -> Vec<String> {
+        if self.max_suggestions == 0 {
+            return Vec::new();
+        }
+
+        let tokens = tokenize(content);
+        let existing: Vec<String> = existing.iter().map(|tag| tag.to_lowercase()).collect();
+        let mut scored: Vec<(usize, String)> = Vec::new();
+        let mut push = |score: usize, tag: &str| {
+            let lowered = tag.to_lowercase();
+            if existing.contains(&lowered)
+                || scored
+                    .iter()
+                    .any(|(_, seen)| seen.to_lowercase() == lowered)
+            {
+                return;
+            }
+            scored.push((score, tag.to_string()));
+        };
+
+        for tag in &self.vocabulary {
+            let phrase = tokenize(tag);
+            if !phrase.is_empty() && contains_phrase(&tokens, &phrase) {
+                push(VOCABULARY_SCORE, tag);
+            }
+        }
+
+        for (tag, keywords) in CATEGORY_RULES {
+            let hits = keywords
+                .iter()
+                .filter(|keyword| tokens.iter().any(|token| token == *keyword))
+                .count();
+            if hits >= MIN_CATEGORY_HITS {
+                push(hits, tag);
+            }
+        }
+
+        // Stable sort keeps vocabulary order among equal scores.
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored
+            .into_iter()
+            .take(self.max_suggestions)
+            .map(|(_, tag)| tag)
+            .collect()
+    }
+
+    /// Append suggested tags to a chunk before it is stored.
+    pub fn apply(&self, chunk: &mut MemoryChunk) {
+        let suggestions = self.suggest(&chunk.content, &chunk.tags);
+        chunk.tags.extend(suggestions);
+    }
+}
+
+/// Add suggested tags to `chunk` according to `settings`, using the agent's
+/// existing tags as vocabulary. Does nothing when auto-tagging is disabled.
+pub fn suggest_tags_for_chunk(
+    storage: &MemoryStorage,
+    settings: &MemorySettings,
+    chunk: &mut MemoryChunk,
+) -> Result<()> {
+    let Some(suggester) = TagSuggester::from_settings(settings) else {
+        return Ok(());
+    };
+    let vocabulary = storage
+        .list_tags(Some(&chunk.agent_id))?
+        .into_iter()
+        .map(|tag| tag.tag);
+    suggester.with_vocabulary(vocabulary).apply(chunk);
+    Ok(())
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|ch: char| !(ch.is_alphanumeric() || ch == '-' || ch == '_'))
+        .map(|token| token.trim_matches('-').to_lowercase())
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+fn contains_phrase(tokens: &[String], phrase: &[String]) -> bool {
+    tokens.windows(phrase.len()).any(|window| window == phrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_categories_with_enough_evidence() {
+        let suggester = TagSuggester::new(3);
+        let tags = suggester.suggest(
+            "The deploy failed with a panic; the regression came from yesterday's commit.",
+            &[],
+        );
+        assert_eq!(tags, vec!["bug".to_string()]);
+
+        assert!(suggester.suggest("We had a call.", &[]).is_empty());
+    }
+
+    #[test]
+    fn prefers_existing_vocabulary_and_skips_present_tags() {
+        let suggester = TagSuggester::new(2).with_vocabulary(vec![
+            "__title:notes".to_string(),
+            "Project Atlas".to_string(),
+            "billing".to_string(),
+        ]);
+        let tags = suggester.suggest(
+            "Project atlas billing error: the crash is a regression.",
+            &["billing".to_string()],
+        );
+        assert_eq!(tags, vec!["Project Atlas".to_string(), "bug".to_string()]);
+    }
+
+    #[test]
+    fn respects_settings_and_limit() {
+        let disabled = MemorySettings {
+            auto_tag: false,
+            ..MemorySettings::default()
+        };
+        assert!(TagSuggester::from_settings(&disabled).is_none());
+
+        let suggester = TagSuggester::new(1);
+        let mut chunk = MemoryChunk::new(
+            "agent".to_string(),
+            "Decided and agreed: I prefer tabs and always use them; the build failed with an error."
+                .to_string(),
+        );
+        suggester.apply(&mut chunk);
+        assert_eq!(chunk.tags.len(), 1);
+    }
+}
@@ -2,10 +2,11 @@
 
 use crate::memory::{SearchConfig, SearchEngine};
 use crate::models::chat_session::{ChatMessage, ChatRole, ChatSession};
-use crate::models::memory::UnifiedSearchQuery;
+use crate::models::memory::{SearchMode, UnifiedSearchQuery};
 use crate::storage::{ChatSessionStorage, MemoryStorage};
 use anyhow::Result;
 use chrono::Utc;
+use restflow_storage::MemorySettings;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use ts_rs::TS;
@@ -71,6 +72,10 @@ pub struct UnifiedSearchConfig {
     pub memory_config: SearchConfig,
     pub memory_weight: f64,
     pub min_score: f64,
+    /// Share of hybrid memory ranking given to vector similarity.
+    pub hybrid_vector_weight: f32,
+    /// Reciprocal rank fusion constant for hybrid memory ranking.
+    pub rrf_k: f32,
 }
 
 impl Default for UnifiedSearchConfig {
@@ -79,10 +84,26 @@ impl Default for UnifiedSearchConfig {
             memory_config: SearchConfig::default(),
             memory_weight: 0.5,
             min_score: 10.0,
+            hybrid_vector_weight: 0.7,
+            rrf_k: 60.0,
         }
     }
 }
 
+impl UnifiedSearchConfig {
+    /// Default configuration with hybrid ranking taken from `[memory]`.
+    pub fn from_memory_settings(settings: &MemorySettings) -> Self {
+        Self {
+            hybrid_vector_weight: settings.hybrid_vector_weight as f32,
+            rrf_k: settings.hybrid_rrf_k as f32,
+            ..Self::default()
+        }
+    }
+}
+
+/// Minimum number of memory candidates ranked by semantic and hybrid search.
+const MIN_SEMANTIC_CANDIDATES: usize = 20;
+
 /// Unified search engine combining memory and session results.
 #[derive(Clone)]
 pub struct UnifiedSearchEngine {
@@ -116,25 +137,52 @@ impl UnifiedSearchEngine {
 
     /// Search both memory chunks and chat sessions.
     pub fn search(&self, query: &UnifiedSearchQuery) -> Result<UnifiedSearchResults> {
+        self.search_with_embedding(query, None)
+    }
+
+    /// Search with an embedding of the query text for semantic memory recall.
+    ///
+    /// For [`SearchMode::Semantic`] and [`SearchMode::Hybrid`] queries with
+    /// vector search enabled, memory results are ranked by vector similarity
+    /// or by fusing it with keyword matches. Other modes, or a missing
+    /// embedding, use keyword scoring. Session results are unaffected.
+    pub fn search_with_embedding(
+        &self,
+        query: &UnifiedSearchQuery,
+        query_embedding: Option<&[f32]>,
+    ) -> Result<UnifiedSearchResults> {
         let mut all_results = Vec::new();
         let mut source_counts = SourceCounts::default();
 
-        let mut memory_query = query.base.clone();
-        memory_query.limit = u32::MAX;
-        memory_query.offset = 0;
-
-        let memory_results = self.memory_engine.search_ranked(&memory_query)?;
-        for scored in memory_results.chunks {
-            all_results.push(UnifiedSearchResult {
-                id: scored.chunk.id.clone(),
-                content: scored.chunk.content.clone(),
-                source: SearchResultSource::Memory,
-                score: scored.score * self.config.memory_weight,
-                timestamp: scored.chunk.created_at,
-                context: None,
-            });
+        let query_embedding = query_embedding.filter(|_| {
+            query.base.search_mode.uses_embeddings()
+                && self.memory_engine.storage().has_vector_search()
+        });
+        match query_embedding {
+            Some(embedding) => {
+                let memory_results = self.search_memory_semantic(query, embedding)?;
+                source_counts.memory = memory_results.len() as u32;
+                all_results.extend(memory_results);
+            }
+            _ => {
+                let mut memory_query = query.base.clone();
+                memory_query.limit = u32::MAX;
+                memory_query.offset = 0;
+
+                let memory_results = self.memory_engine.search_ranked(&memory_query)?;
+                for scored in memory_results.chunks {
+                    all_results.push(UnifiedSearchResult {
+                        id: scored.chunk.id.clone(),
+                        content: scored.chunk.content.clone(),
+                        source: SearchResultSource::Memory,
+                        score: scored.score * self.config.memory_weight,
+                        timestamp: scored.chunk.created_at,
+                        context: None,
+                    });
+                }
+                source_counts.memory = memory_results.total_count;
+            }
         }
-        source_counts.memory = memory_results.total_count;
 
         if query.include_sessions {
             let session_results = self.search_sessions(query)?;
@@ -170,6 +218,54 @@ impl UnifiedSearchEngine {
         })
     }
 
+    /// Semantic or hybrid memory search, scored 0-100.
+    ///
+    /// Semantic scores are cosine similarity; hybrid RRF scores are relative
+    /// to the best match.
+    fn search_memory_semantic(
+        &self,
+        query: &UnifiedSearchQuery,
+        embedding: &[f32],
+    ) -> Result<Vec<UnifiedSearchResult>> {
+        let storage = self.memory_engine.storage();
+        let wanted = query.base.offset.saturating_add(query.base.limit) as usize;
+        let top_k = wanted.max(MIN_SEMANTIC_CANDIDATES);
+        let (matches, scale) = match query.base.search_mode {
+            SearchMode::Semantic => (
+                storage.semantic_search(&query.base.agent_id, embedding, top_k)?,
+                1.0,
+            ),
+            _ => {
+                let text = query.base.query.as_deref().unwrap_or_default();
+                let matches = storage.hybrid_search(
+                    &query.base.agent_id,
+                    embedding,
+                    text,
+                    top_k,
+                    self.config.hybrid_vector_weight,
+                    self.config.rrf_k,
+                )?;
+                let best = matches
+                    .first()
+                    .map(|m| m.similarity)
+                    .filter(|best| *best > 0.0)
+                    .unwrap_or(1.0);
+                (matches, best)
+            }
+        };
+        Ok(matches
+            .into_iter()
+            .map(|m| UnifiedSearchResult {
+                id: m.chunk.id.clone(),
+                content: m.chunk.content.clone(),
+                source: SearchResultSource::Memory,
+                score: f64::from(m.similarity / scale) * 100.0 * self.config.memory_weight,
+                timestamp: m.chunk.created_at,
+                context: None,
+            })
+            .collect())
+    }
+
     fn search_sessions(&self, query: &UnifiedSearchQuery) -> Result<Vec<UnifiedSearchResult>> {
         let Some(ref search_text) = query.base.query else {
             return Ok(Vec::new());
@@ -281,8 +377,9 @@ fn preview_content(content: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::MemoryEmbedder;
     use crate::models::chat_session::ChatMessage;
-    use crate::models::memory::{MemoryChunk, MemorySearchQuery, SearchMode};
+    use crate::models::memory::{MemoryChunk, MemorySearchQuery};
     use crate::storage::ChatSessionStorage;
     use redb::Database;
     use std::sync::Arc;
@@ -320,6 +417,83 @@ mod tests {
         assert!(!results.results.is_empty());
     }
 
+    fn create_vector_engine() -> (UnifiedSearchEngine, MemoryEmbedder, tempfile::TempDir) {
+        use crate::memory::embedding::tests::{BagOfWordsProvider, TEST_DIMENSION};
+
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let config = restflow_storage::VectorConfig {
+            dimension: TEST_DIMENSION,
+            ..Default::default()
+        };
+        let memory_storage = MemoryStorage::with_vectors(db.clone(), config).unwrap();
+        let engine =
+            UnifiedSearchEngine::new(memory_storage.clone(), ChatSessionStorage::new(db).unwrap());
+        let embedder = MemoryEmbedder::new(Arc::new(BagOfWordsProvider), memory_storage);
+        (engine, embedder, temp_dir)
+    }
+
+    fn embed(embedder: &MemoryEmbedder, text: &str) -> Vec<f32> {
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(embedder.embed_query("agent-1", text))
+            .unwrap()
+    }
+
+    fn memory_query(text: &str, mode: SearchMode) -> UnifiedSearchQuery {
+        let base = MemorySearchQuery::new("agent-1".to_string())
+            .with_query(text.to_string())
+            .with_mode(mode)
+            .paginate(10, 0);
+        UnifiedSearchQuery::new(base).with_sessions(false)
+    }
+
+    #[test]
+    fn test_search_with_embedding_ranks_memory_by_hybrid_score() {
+        let (engine, embedder, _temp) = create_vector_engine();
+        let chunk = MemoryChunk::new(
+            "agent-1".to_string(),
+            "Deploys happen every Friday afternoon".to_string(),
+        );
+        engine.memory_engine.storage().store_chunk(&chunk).unwrap();
+
+        let query = memory_query("friday deploys", SearchMode::Hybrid);
+        let embedding = embed(&embedder, "friday deploys");
+
+        let results = engine
+            .search_with_embedding(&query, Some(&embedding))
+            .unwrap();
+        assert_eq!(results.results[0].id, chunk.id);
+    }
+
+    #[test]
+    fn test_search_mode_selects_embedding_ranking() {
+        let (engine, embedder, _temp) = create_vector_engine();
+        let chunk = MemoryChunk::new(
+            "agent-1".to_string(),
+            "Deploys happen every Friday afternoon".to_string(),
+        );
+        engine.memory_engine.storage().store_chunk(&chunk).unwrap();
+        // "release" is not in the chunk, so keyword matching finds nothing.
+        let embedding = embed(&embedder, "friday deploys release");
+
+        let keyword = memory_query("friday deploys release", SearchMode::Keyword);
+        let results = engine
+            .search_with_embedding(&keyword, Some(&embedding))
+            .unwrap();
+        assert!(results.results.is_empty());
+
+        let semantic = memory_query("friday deploys release", SearchMode::Semantic);
+        let results = engine
+            .search_with_embedding(&semantic, Some(&embedding))
+            .unwrap();
+        assert_eq!(results.results[0].id, chunk.id);
+
+        let hybrid = memory_query("friday deploys release", SearchMode::Hybrid);
+        let results = engine.search_with_embedding(&hybrid, None).unwrap();
+        assert!(results.results.is_empty());
+    }
+
     #[test]
     fn test_search_sessions() {
         let (engine, _temp) = create_engine();
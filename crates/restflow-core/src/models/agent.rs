@@ -7,6 +7,7 @@ use crate::{AppCore, models::ValidationError};
 use restflow_contracts::request::{
     AgentNode as ContractAgentNode, ApiKeyConfig as ContractApiKeyConfig,
     CodexCliExecutionMode as ContractCodexCliExecutionMode,
+    ExecutionStrategy as ContractExecutionStrategy, FailoverPolicy as ContractFailoverPolicy,
     ModelRoutingConfig as ContractModelRoutingConfig,
     SkillPreflightPolicyMode as ContractSkillPreflightPolicyMode,
 };
@@ -53,6 +54,29 @@ pub enum SkillPreflightPolicyMode {
     Enforce,
 }
 
+/// How an agent works towards its goal.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq, Default)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStrategy {
+    /// Single ReAct loop over the whole goal.
+    #[default]
+    React,
+    /// Draft a plan (persisted as work items), then run and verify each
+    /// step, re-planning on failure. Suits complex long-horizon tasks.
+    PlanAndExecute,
+}
+
+impl From<ExecutionStrategy> for restflow_ai::agent::ExecutionStrategy {
+    fn from(value: ExecutionStrategy) -> Self {
+        match value {
+            ExecutionStrategy::React => Self::React,
+            ExecutionStrategy::PlanAndExecute => Self::PlanAndExecute,
+        }
+    }
+}
+
 /// Model routing configuration for automatic tier-based model selection.
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
 #[specta(skip_attr = "ts")]
@@ -74,6 +98,10 @@ pub struct ModelRoutingConfig {
     pub complex_model: Option<String>,
     /// Escalate to complex tier after a failed iteration.
     pub escalate_on_failure: bool,
+    /// Retry rate-limited or failed LLM requests on fallback models.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverPolicy>,
 }
 
 impl Default for ModelRoutingConfig {
@@ -84,10 +112,28 @@ impl Default for ModelRoutingConfig {
             moderate_model: None,
             complex_model: None,
             escalate_on_failure: true,
+            failover: None,
         }
     }
 }
 
+/// Fallback models tried in order when a provider is rate-limited or down.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct FailoverPolicy {
+    /// Fallback models, tried in order.
+    pub fallback_models: Vec<String>,
+    /// Delay before the first failover in milliseconds, doubled per attempt.
+    #[ts(type = "number")]
+    #[serde(default = "default_failover_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+fn default_failover_backoff_ms() -> u64 {
+    restflow_ai::agent::FailoverPolicy::new(Vec::new()).backoff_ms
+}
+
 impl From<&ModelRoutingConfig> for restflow_ai::agent::ModelRoutingConfig {
     fn from(config: &ModelRoutingConfig) -> Self {
         Self {
@@ -96,6 +142,13 @@ impl From<&ModelRoutingConfig> for restflow_ai::agent::ModelRoutingConfig {
             moderate_model: config.moderate_model.clone(),
             complex_model: config.complex_model.clone(),
             escalate_on_failure: config.escalate_on_failure,
+            failover: config
+                .failover
+                .as_ref()
+                .map(|policy| restflow_ai::agent::FailoverPolicy {
+                    fallback_models: policy.fallback_models.clone(),
+                    backoff_ms: policy.backoff_ms,
+                }),
         }
     }
 }
@@ -165,6 +218,15 @@ pub struct AgentNode {
     #[ts(optional)]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_routing: Option<ModelRoutingConfig>,
+    /// Simulate side-effecting tools (shell, file writes, outbound messages)
+    /// so a run previews the agent's plan without acting on it.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    /// Execution strategy (`react` | `plan_and_execute`), defaulting to `react`.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_strategy: Option<ExecutionStrategy>,
 }
 
 impl From<CodexCliExecutionMode> for ContractCodexCliExecutionMode {
@@ -186,6 +248,15 @@ impl From<SkillPreflightPolicyMode> for ContractSkillPreflightPolicyMode {
     }
 }
 
+impl From<ExecutionStrategy> for ContractExecutionStrategy {
+    fn from(value: ExecutionStrategy) -> Self {
+        match value {
+            ExecutionStrategy::React => Self::React,
+            ExecutionStrategy::PlanAndExecute => Self::PlanAndExecute,
+        }
+    }
+}
+
 impl From<ApiKeyConfig> for ContractApiKeyConfig {
     fn from(value: ApiKeyConfig) -> Self {
         match value {
@@ -203,6 +274,10 @@ impl From<ModelRoutingConfig> for ContractModelRoutingConfig {
             moderate_model: value.moderate_model,
             complex_model: value.complex_model,
             escalate_on_failure: value.escalate_on_failure,
+            failover: value.failover.map(|policy| ContractFailoverPolicy {
+                fallback_models: policy.fallback_models,
+                backoff_ms: policy.backoff_ms,
+            }),
         }
     }
 }
@@ -305,6 +380,28 @@ impl AgentNode {
         self
     }
 
+    /// Enable or disable dry-run mode.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = Some(dry_run);
+        self
+    }
+
+    /// Whether side-effecting tools are simulated, defaulting to `false`.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(false)
+    }
+
+    /// Set the execution strategy.
+    pub fn with_execution_strategy(mut self, strategy: ExecutionStrategy) -> Self {
+        self.execution_strategy = Some(strategy);
+        self
+    }
+
+    /// Get the effective execution strategy, defaulting to `react`.
+    pub fn effective_execution_strategy(&self) -> ExecutionStrategy {
+        self.execution_strategy.unwrap_or_default()
+    }
+
     /// Resolve effective provider + model, preferring `model_ref`.
     pub fn resolved_model_ref(&self) -> Option<ModelRef> {
         self.model_ref
@@ -448,12 +545,23 @@ impl AgentNode {
                     "model_routing.complex_model",
                     routing.complex_model.as_deref(),
                 ),
-            ] {
+            ]
+            .into_iter()
+            .chain(routing.failover.iter().flat_map(|policy| {
+                policy.fallback_models.iter().map(|model| {
+                    (
+                        "model_routing.failover.fallback_models",
+                        Some(model.as_str()),
+                    )
+                })
+            })) {
                 if let Some(model) = model {
                     let normalized = model.trim();
                     if normalized.is_empty() {
                         errors.push(ValidationError::new(field, "must not be empty"));
-                    } else if ModelId::from_api_name(normalized).is_none() {
+                    } else if ModelId::from_api_name(normalized).is_none()
+                        && restflow_models::parse_local_model(normalized).is_none()
+                    {
                         errors.push(ValidationError::new(
                             field,
                             format!("unsupported model '{}'", normalized),
@@ -659,6 +767,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn execution_strategy_defaults_to_react() {
+        let node: AgentNode = serde_json::from_str(r#"{"prompt":"hello"}"#).unwrap();
+        assert_eq!(
+            node.effective_execution_strategy(),
+            ExecutionStrategy::React
+        );
+
+        let node: AgentNode =
+            serde_json::from_str(r#"{"execution_strategy":"plan_and_execute"}"#).unwrap();
+        assert_eq!(
+            node.effective_execution_strategy(),
+            ExecutionStrategy::PlanAndExecute
+        );
+    }
+
+    #[test]
+    fn dry_run_defaults_to_disabled() {
+        let node: AgentNode = serde_json::from_str(r#"{"prompt":"hello"}"#).unwrap();
+        assert!(!node.is_dry_run());
+        assert!(AgentNode::new().with_dry_run(true).is_dry_run());
+    }
+
     #[test]
     fn validate_accepts_valid_codex_config() {
         let node = AgentNode {
@@ -788,6 +919,21 @@ mod tests {
             moderate_model: Some("claude-sonnet-4-5".to_string()),
             complex_model: Some("claude-opus-4-6".to_string()),
             escalate_on_failure: true,
+            failover: None,
+        });
+
+        assert!(node.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_accepts_model_routing_with_local_models() {
+        let node = AgentNode::new().with_model_routing(ModelRoutingConfig {
+            enabled: true,
+            routine_model: Some("ollama:llama3.1:8b".to_string()),
+            moderate_model: Some("local:qwen2.5-coder".to_string()),
+            complex_model: Some("gpt-5".to_string()),
+            escalate_on_failure: true,
+            failover: None,
         });
 
         assert!(node.validate().is_ok());
@@ -801,6 +947,7 @@ mod tests {
             moderate_model: None,
             complex_model: None,
             escalate_on_failure: true,
+            failover: None,
         });
 
         let errors = node.validate().expect_err("expected validation error");
@@ -811,6 +958,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_rejects_unknown_failover_model() {
+        let node = AgentNode::new().with_model_routing(ModelRoutingConfig {
+            failover: Some(FailoverPolicy {
+                fallback_models: vec!["claude-sonnet-4-5".to_string(), "nope".to_string()],
+                backoff_ms: 500,
+            }),
+            ..ModelRoutingConfig::default()
+        });
+
+        let errors = node.validate().expect_err("expected validation error");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "model_routing.failover.fallback_models");
+    }
+
     #[test]
     fn normalize_model_fields_backfills_model_ref_from_legacy_model() {
         let mut node = AgentNode {
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+use super::{AgentNode, TaskSchedule};
+
+/// A ready-made agent setup that can be provisioned in one step.
+///
+/// Provisioning creates the agent, then one background task per entry in
+/// `tasks`. Skills listed on the agent are attached when installed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct AgentTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub agent: AgentNode,
+    #[serde(default)]
+    pub tasks: Vec<AgentTemplateTask>,
+    /// Secrets the agent's tools read at run time.
+    #[serde(default)]
+    pub required_secrets: Vec<String>,
+}
+
+/// A scheduled background task created alongside a template agent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct AgentTemplateTask {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub input: String,
+    pub schedule: TaskSchedule,
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use specta::Type;
+use ts_rs::TS;
+
+/// A REST API integration generated from an OpenAPI document.
+///
+/// Each operation is exposed to agents as its own tool; authentication is
+/// applied from stored secrets when the operation is called, so the
+/// connector itself never holds credentials.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ApiConnector {
+    /// Unique connector name, used as the tool name prefix.
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Base URL that operation paths are appended to.
+    pub base_url: String,
+    #[serde(default)]
+    pub auth: ConnectorAuth,
+    pub operations: Vec<ConnectorOperation>,
+    /// Unix timestamp in milliseconds when the connector was first imported.
+    #[ts(type = "number")]
+    pub created_at: i64,
+    /// Unix timestamp in milliseconds when the connector was last imported.
+    #[ts(type = "number")]
+    pub updated_at: i64,
+}
+
+impl ApiConnector {
+    pub fn operation(&self, id: &str) -> Option<&ConnectorOperation> {
+        self.operations.iter().find(|operation| operation.id == id)
+    }
+}
+
+/// How requests made by a connector are authenticated.
+///
+/// `secret` is the name of a stored secret, resolved at call time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConnectorAuth {
+    #[default]
+    None,
+    /// `Authorization: Bearer <secret>`
+    Bearer { secret: String },
+    /// API key sent in the named header or query parameter.
+    ApiKey {
+        secret: String,
+        name: String,
+        location: ApiKeyLocation,
+    },
+    /// HTTP basic auth; the secret holds `username:password`.
+    Basic { secret: String },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+/// A single callable API operation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ConnectorOperation {
+    /// Operation id, unique within the connector.
+    pub id: String,
+    /// Upper-case HTTP method.
+    pub method: String,
+    /// Path template relative to the base URL, e.g. `/pets/{petId}`.
+    pub path: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<ConnectorParameter>,
+    /// JSON schema of the request body, if the operation takes one.
+    #[serde(default)]
+    #[ts(type = "any")]
+    pub request_body: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ConnectorParameter {
+    pub name: String,
+    pub location: ParameterLocation,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// JSON schema of the parameter value.
+    #[ts(type = "any")]
+    pub schema: Value,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+}
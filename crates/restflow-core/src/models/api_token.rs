@@ -0,0 +1,231 @@
+//! Scoped API tokens for external MCP and webhook callers.
+//!
+//! Tokens are issued per caller and only the SHA-256 hash of the secret is
+//! persisted. Each token carries a scope describing which agents it may run,
+//! which tools it may call, how many calls it may make in total and per
+//! minute, which role it holds on the daemon HTTP API, and which workspace
+//! user it acts as.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+/// Access level of a token on the daemon HTTP API (`/api/...`).
+///
+/// Roles are ordered: each role includes the permissions of the ones below it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq, PartialOrd, Ord)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenRole {
+    /// Read agents, sessions, runs and other non-sensitive state.
+    ReadOnly,
+    /// Also run agents, chat sessions, tasks and tools.
+    Execute,
+    /// Full access, including configuration, secrets and tokens.
+    Admin,
+}
+
+impl ApiTokenRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Execute => "execute",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+/// Capability restrictions attached to an API token.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ApiTokenScope {
+    /// Agent IDs the token may run. `None` allows every agent.
+    #[serde(default)]
+    pub allowed_agents: Option<Vec<String>>,
+    /// MCP tool names the token may call. `None` allows every tool.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Maximum number of calls over the token lifetime. `None` is unlimited.
+    #[serde(default)]
+    pub max_calls: Option<u32>,
+    /// Whether the token may access the MCP endpoint.
+    #[serde(default = "default_true")]
+    pub mcp: bool,
+    /// Whether the token may trigger tasks through webhook endpoints.
+    #[serde(default = "default_true")]
+    pub webhooks: bool,
+    /// Whether the token may call the OpenAI-compatible `/v1` endpoints.
+    #[serde(default = "default_true")]
+    pub openai_api: bool,
+    /// Role on the daemon HTTP API. `None` denies access to `/api/...`.
+    #[serde(default)]
+    pub role: Option<ApiTokenRole>,
+    /// Maximum number of requests per minute. `None` is unlimited.
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+    /// Workspace user the token acts as. `None` sees every workspace.
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+impl Default for ApiTokenScope {
+    fn default() -> Self {
+        Self {
+            allowed_agents: None,
+            allowed_tools: None,
+            max_calls: None,
+            mcp: true,
+            webhooks: true,
+            openai_api: true,
+            role: None,
+            rate_limit_per_minute: None,
+            user_id: None,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ApiTokenScope {
+    /// Check whether the scope allows running the given agent.
+    pub fn allows_agent(&self, agent_id: &str) -> bool {
+        self.allowed_agents
+            .as_ref()
+            .is_none_or(|agents| agents.iter().any(|allowed| allowed == agent_id))
+    }
+
+    /// Check whether the scope grants at least `required` on the HTTP API.
+    pub fn allows_role(&self, required: ApiTokenRole) -> bool {
+        self.role.is_some_and(|role| role >= required)
+    }
+
+    /// Check whether the scope allows calling the given tool.
+    pub fn allows_tool(&self, tool_name: &str) -> bool {
+        self.allowed_tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|allowed| allowed == tool_name))
+    }
+}
+
+/// Persisted API token record (the plaintext secret is never stored).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ApiToken {
+    pub id: String,
+    /// Human-readable caller name.
+    pub name: String,
+    /// Hex-encoded SHA-256 hash of the token secret.
+    pub token_hash: String,
+    /// Leading characters of the secret, for identification in listings.
+    pub token_prefix: String,
+    #[serde(default)]
+    pub scope: ApiTokenScope,
+    /// Number of authorized calls made with this token.
+    #[serde(default)]
+    pub call_count: u32,
+    #[ts(type = "number")]
+    pub created_at: i64,
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub last_used_at: Option<i64>,
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiToken {
+    /// Whether the token has been revoked or has expired at `now`.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    /// Whether the call budget has been used up.
+    pub fn budget_exhausted(&self) -> bool {
+        self.scope
+            .max_calls
+            .is_some_and(|max_calls| self.call_count >= max_calls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(scope: ApiTokenScope) -> ApiToken {
+        ApiToken {
+            id: "tok-1".to_string(),
+            name: "ci".to_string(),
+            token_hash: "hash".to_string(),
+            token_prefix: "rft_abcd".to_string(),
+            scope,
+            call_count: 0,
+            created_at: 0,
+            last_used_at: None,
+            expires_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_scope_defaults_allow_everything() {
+        let scope = ApiTokenScope::default();
+        assert!(scope.allows_agent("any-agent"));
+        assert!(scope.allows_tool("list_skills"));
+        assert!(scope.mcp);
+        assert!(scope.webhooks);
+        assert!(scope.openai_api);
+        assert!(!scope.allows_role(ApiTokenRole::ReadOnly));
+    }
+
+    #[test]
+    fn test_roles_include_lower_roles() {
+        let scope = ApiTokenScope {
+            role: Some(ApiTokenRole::Execute),
+            ..ApiTokenScope::default()
+        };
+        assert!(scope.allows_role(ApiTokenRole::ReadOnly));
+        assert!(scope.allows_role(ApiTokenRole::Execute));
+        assert!(!scope.allows_role(ApiTokenRole::Admin));
+    }
+
+    #[test]
+    fn test_scope_restricts_agents_and_tools() {
+        let scope = ApiTokenScope {
+            allowed_agents: Some(vec!["agent-a".to_string()]),
+            allowed_tools: Some(vec!["list_skills".to_string()]),
+            ..ApiTokenScope::default()
+        };
+        assert!(scope.allows_agent("agent-a"));
+        assert!(!scope.allows_agent("agent-b"));
+        assert!(scope.allows_tool("list_skills"));
+        assert!(!scope.allows_tool("delete_skill"));
+    }
+
+    #[test]
+    fn test_token_activity_and_budget() {
+        let mut token = token(ApiTokenScope {
+            max_calls: Some(2),
+            ..ApiTokenScope::default()
+        });
+        assert!(token.is_active(100));
+        assert!(!token.budget_exhausted());
+
+        token.call_count = 2;
+        assert!(token.budget_exhausted());
+
+        token.expires_at = Some(50);
+        assert!(!token.is_active(100));
+
+        token.expires_at = None;
+        token.revoked_at = Some(10);
+        assert!(!token.is_active(100));
+    }
+}
@@ -80,6 +80,9 @@ mod tests {
                 duration_ms: Some(1500),
                 is_reasoning: Some(false),
                 message_count: Some(10),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+                cache_savings_usd: None,
             },
         );
 
@@ -95,6 +95,9 @@ pub enum TaskStatus {
     Failed,
     /// Task execution interrupted by an explicit stop or checkpoint flow.
     Interrupted,
+    /// Task exhausted its queue retries and will not run until restarted
+    #[serde(rename = "dead_letter")]
+    DeadLetter,
 }
 
 impl TaskStatus {
@@ -106,6 +109,7 @@ impl TaskStatus {
             TaskStatus::Completed => "completed",
             TaskStatus::Failed => "failed",
             TaskStatus::Interrupted => "interrupted",
+            TaskStatus::DeadLetter => "dead_letter",
         }
     }
 }
@@ -214,6 +218,14 @@ fn default_inter_segment_pause_ms() -> u64 {
     1_000
 }
 
+fn default_retry_backoff_secs() -> u64 {
+    30
+}
+
+fn default_max_backoff_secs() -> u64 {
+    3_600
+}
+
 /// Scope for task memory persistence.
 ///
 /// Controls whether long-term memory is shared across all tasks of an
@@ -349,6 +361,23 @@ impl Default for ResourceLimits {
     }
 }
 
+/// Concurrency limits that keep bursts of triggers from starting too many runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ConcurrencyLimits {
+    /// Named group shared with other tasks, e.g. `"deploy"` or `"scraping"`.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Maximum runs of tasks in `group` at once; this task waits while the
+    /// group is full.
+    #[serde(default)]
+    pub max_group_runs: Option<u32>,
+    /// Maximum runs at once across all tasks bound to the same agent.
+    #[serde(default)]
+    pub max_agent_runs: Option<u32>,
+}
+
 /// Configuration for long-horizon execution continuation.
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
 #[specta(skip_attr = "ts")]
@@ -384,6 +413,64 @@ impl Default for ContinuationConfig {
     }
 }
 
+/// Queue priority for runnable tasks, using the same levels as sub-agent spawns.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, TS, Type, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuePriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// Queue policy controlling ordering and retries of task executions.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct TaskQueuePolicy {
+    /// Priority used when several tasks are runnable at once.
+    #[serde(default)]
+    pub priority: QueuePriority,
+    /// Retries after a failed run before the task is dead-lettered.
+    ///
+    /// `None` keeps rescheduling failed tasks on their regular schedule.
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    /// Delay before the first retry; doubles with each further attempt.
+    #[serde(default = "default_retry_backoff_secs")]
+    pub retry_backoff_secs: u64,
+    /// Upper bound for the retry delay.
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+}
+
+impl Default for TaskQueuePolicy {
+    fn default() -> Self {
+        Self {
+            priority: QueuePriority::default(),
+            max_retries: None,
+            retry_backoff_secs: default_retry_backoff_secs(),
+            max_backoff_secs: default_max_backoff_secs(),
+        }
+    }
+}
+
+impl TaskQueuePolicy {
+    /// Exponential backoff delay in milliseconds for the given retry attempt (1-based).
+    pub fn backoff_ms(&self, attempt: u32) -> i64 {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let secs = self
+            .retry_backoff_secs
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_backoff_secs);
+        i64::try_from(secs.saturating_mul(1_000)).unwrap_or(i64::MAX)
+    }
+}
+
 /// Creation payload for scheduled tasks.
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
 #[specta(skip_attr = "ts")]
@@ -433,6 +520,9 @@ pub struct TaskSpec {
     /// Optional continuation policy for long-horizon execution
     #[serde(default)]
     pub continuation: Option<ContinuationConfig>,
+    /// Optional concurrency limits shared with other tasks
+    #[serde(default)]
+    pub concurrency: Option<ConcurrencyLimits>,
 }
 
 /// Partial update payload for scheduled tasks.
@@ -485,6 +575,15 @@ pub struct TaskPatch {
     /// New continuation policy
     #[serde(default)]
     pub continuation: Option<ContinuationConfig>,
+    /// New result callbacks (replaces the existing list)
+    #[serde(default)]
+    pub callbacks: Option<Vec<super::webhook::ResultCallback>>,
+    /// New queue priority and retry policy
+    #[serde(default)]
+    pub queue: Option<TaskQueuePolicy>,
+    /// New concurrency limits
+    #[serde(default)]
+    pub concurrency: Option<ConcurrencyLimits>,
 }
 
 /// Control actions for a scheduled task.
@@ -1003,6 +1102,96 @@ impl SubflowPath {
     }
 }
 
+/// Condition that wakes a paused task.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WakeCondition {
+    /// The task's webhook endpoint receives a request
+    WebhookReceived,
+    /// A file's modification time changes
+    FileChanged {
+        path: String,
+        /// Modification time observed at registration (`None` when the file
+        /// did not exist yet, so its creation also wakes the task)
+        #[serde(default)]
+        #[ts(type = "number | null")]
+        baseline_modified_at: Option<i64>,
+    },
+    /// A work item reaches the given status
+    WorkItemStatus {
+        item_id: String,
+        status: super::work_item::ItemStatus,
+    },
+    /// The clock passes the given timestamp (milliseconds since epoch)
+    TimeReached {
+        #[ts(type = "number")]
+        at: i64,
+    },
+}
+
+impl WakeCondition {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            WakeCondition::WebhookReceived => "webhook_received",
+            WakeCondition::FileChanged { .. } => "file_changed",
+            WakeCondition::WorkItemStatus { .. } => "work_item_status",
+            WakeCondition::TimeReached { .. } => "time_reached",
+        }
+    }
+}
+
+/// Event that satisfied a task's wake condition.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct WakeEvent {
+    /// Kind of the condition that fired
+    pub kind: String,
+    /// Timestamp when the condition fired (milliseconds since epoch)
+    #[ts(type = "number")]
+    pub fired_at: i64,
+    /// Event details injected into the resumed run
+    #[serde(default)]
+    #[ts(type = "any")]
+    pub details: serde_json::Value,
+}
+
+impl WakeEvent {
+    pub fn new(condition: &WakeCondition, details: serde_json::Value) -> Self {
+        Self {
+            kind: condition.kind().to_string(),
+            fired_at: chrono::Utc::now().timestamp_millis(),
+            details,
+        }
+    }
+
+    /// Message queued for the resumed run so the agent sees why it woke up.
+    pub fn context_message(&self) -> String {
+        format!(
+            "[Wake event] Condition '{}' fired. Details: {}",
+            self.kind, self.details
+        )
+    }
+}
+
+/// Wake condition registered by a paused task.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct TaskWake {
+    pub condition: WakeCondition,
+    /// Checkpoint to resume from; defaults to the task's latest checkpoint
+    #[serde(default)]
+    pub checkpoint_id: Option<String>,
+    #[ts(type = "number")]
+    pub registered_at: i64,
+    /// Set once the condition fires, until the runner resumes the task
+    #[serde(default)]
+    pub fired: Option<WakeEvent>,
+}
+
 /// Record of a task execution event
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
 #[specta(skip_attr = "ts")]
@@ -1128,6 +1317,9 @@ pub struct Task {
     /// Number of continuation segments completed so far.
     #[serde(default)]
     pub continuation_segments_completed: u32,
+    /// Concurrency limits shared with other tasks
+    #[serde(default)]
+    pub concurrency: ConcurrencyLimits,
     /// Current status of the task
     #[serde(default)]
     pub status: TaskStatus,
@@ -1151,6 +1343,12 @@ pub struct Task {
     /// Count of failed executions
     #[serde(default)]
     pub failure_count: u32,
+    /// Queue priority and retry policy
+    #[serde(default)]
+    pub queue: TaskQueuePolicy,
+    /// Consecutive failed runs counted against the queue retry limit
+    #[serde(default)]
+    pub retry_attempts: u32,
     /// Cumulative tokens used across executions
     #[serde(default)]
     pub total_tokens_used: u32,
@@ -1163,9 +1361,18 @@ pub struct Task {
     /// Webhook configuration for external triggers
     #[serde(default)]
     pub webhook: Option<super::webhook::WebhookConfig>,
+    /// Callbacks that receive run results when the task finishes
+    #[serde(default)]
+    pub callbacks: Vec<super::webhook::ResultCallback>,
+    /// Recent result callback deliveries, newest last
+    #[serde(default)]
+    pub callback_deliveries: Vec<super::webhook::CallbackDelivery>,
     /// Summary message pointer for compacted task sessions
     #[serde(default)]
     pub summary_message_id: Option<String>,
+    /// Wake condition registered while the task is paused
+    #[serde(default)]
+    pub wake: Option<TaskWake>,
 }
 
 impl Task {
@@ -1194,6 +1401,7 @@ impl Task {
             continuation: ContinuationConfig::default(),
             continuation_total_iterations: 0,
             continuation_segments_completed: 0,
+            concurrency: ConcurrencyLimits::default(),
             status: TaskStatus::Active,
             created_at: now,
             updated_at: now,
@@ -1201,11 +1409,16 @@ impl Task {
             next_run_at: next_run,
             success_count: 0,
             failure_count: 0,
+            queue: TaskQueuePolicy::default(),
+            retry_attempts: 0,
             total_tokens_used: 0,
             total_cost_usd: 0.0,
             last_error: None,
             webhook: None,
+            callbacks: Vec::new(),
+            callback_deliveries: Vec::new(),
             summary_message_id: None,
+            wake: None,
         }
     }
 
@@ -1321,6 +1534,7 @@ impl Task {
     /// Mark the task as completed successfully
     pub fn set_completed(&mut self) {
         self.success_count += 1;
+        self.retry_attempts = 0;
         self.last_error = None;
         self.updated_at = chrono::Utc::now().timestamp_millis();
 
@@ -1338,12 +1552,32 @@ impl Task {
     }
 
     /// Mark the task as failed
+    ///
+    /// With a queue retry limit the task is retried after an exponential
+    /// backoff and dead-lettered once the limit is exceeded.
     pub fn set_failed(&mut self, error: String) {
         self.failure_count += 1;
         self.last_error = Some(error);
         self.status = TaskStatus::Failed;
         self.updated_at = chrono::Utc::now().timestamp_millis();
-        self.update_next_run(); // Still schedule next run
+
+        let Some(max_retries) = self.queue.max_retries else {
+            self.update_next_run(); // Still schedule next run
+            return;
+        };
+        self.retry_attempts = self.retry_attempts.saturating_add(1);
+        if self.retry_attempts > max_retries {
+            self.status = TaskStatus::DeadLetter;
+            self.next_run_at = None;
+        } else {
+            let delay = self.queue.backoff_ms(self.retry_attempts);
+            self.next_run_at = Some(self.updated_at.saturating_add(delay));
+        }
+    }
+
+    /// Check if the task exhausted its queue retries.
+    pub fn is_dead_lettered(&self) -> bool {
+        self.status == TaskStatus::DeadLetter
     }
 
     /// Mark the task as interrupted.
@@ -1366,6 +1600,7 @@ impl Task {
     /// Resume the task
     pub fn resume(&mut self) {
         self.status = TaskStatus::Active;
+        self.wake = None;
         self.updated_at = chrono::Utc::now().timestamp_millis();
         self.update_next_run();
     }
@@ -1718,6 +1953,77 @@ mod tests {
         assert!(!task.should_run(chrono::Utc::now().timestamp_millis()));
     }
 
+    #[test]
+    fn test_failed_task_retries_with_backoff_then_dead_letters() {
+        let run_at = chrono::Utc::now().timestamp_millis() - 1_000;
+        let mut task = BackgroundAgent::new(
+            "task-retry".to_string(),
+            "Retry Task".to_string(),
+            "agent-456".to_string(),
+            TaskSchedule::Once { run_at },
+        );
+        task.queue.max_retries = Some(2);
+        task.queue.retry_backoff_secs = 10;
+
+        task.set_running();
+        task.set_failed("first".to_string());
+        assert_eq!(task.status, BackgroundAgentStatus::Failed);
+        assert_eq!(task.retry_attempts, 1);
+        assert_eq!(task.next_run_at, Some(task.updated_at + 10_000));
+        assert!(task.is_active());
+
+        task.set_running();
+        task.set_failed("second".to_string());
+        assert_eq!(task.retry_attempts, 2);
+        assert_eq!(task.next_run_at, Some(task.updated_at + 20_000));
+
+        task.set_running();
+        task.set_failed("third".to_string());
+        assert!(task.is_dead_lettered());
+        assert!(task.next_run_at.is_none());
+        assert!(!task.should_run(i64::MAX));
+    }
+
+    #[test]
+    fn test_queue_backoff_is_capped_and_reset_on_success() {
+        let policy = TaskQueuePolicy {
+            retry_backoff_secs: 30,
+            max_backoff_secs: 100,
+            ..TaskQueuePolicy::default()
+        };
+        assert_eq!(policy.backoff_ms(1), 30_000);
+        assert_eq!(policy.backoff_ms(2), 60_000);
+        assert_eq!(policy.backoff_ms(3), 100_000);
+        assert_eq!(policy.backoff_ms(64), 100_000);
+
+        let mut task = BackgroundAgent::new(
+            "task-reset".to_string(),
+            "Reset Task".to_string(),
+            "agent-456".to_string(),
+            TaskSchedule::default(),
+        );
+        task.queue = policy;
+        task.queue.max_retries = Some(3);
+        task.set_failed("boom".to_string());
+        assert_eq!(task.retry_attempts, 1);
+        task.set_completed();
+        assert_eq!(task.retry_attempts, 0);
+        assert_eq!(task.status, BackgroundAgentStatus::Active);
+    }
+
+    #[test]
+    fn test_dead_letter_status_serializes_snake_case() {
+        assert_eq!(
+            serde_json::to_value(TaskStatus::DeadLetter).unwrap(),
+            serde_json::json!("dead_letter")
+        );
+        assert_eq!(TaskStatus::DeadLetter.as_str(), "dead_letter");
+        assert_eq!(
+            serde_json::to_value(QueuePriority::High).unwrap(),
+            serde_json::json!("high")
+        );
+    }
+
     #[test]
     fn test_once_task_completion() {
         let mut task = BackgroundAgent::new(
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+use super::BrowserPlan;
+
+/// A named browser action plan saved for re-use.
+///
+/// Saving a plan under an existing name stores a new version; older
+/// versions stay available until the plan is deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct SavedBrowserPlan {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Version number, starting at 1.
+    pub version: u32,
+    pub plan: BrowserPlan,
+    /// Unix timestamp in milliseconds when the first version was saved.
+    #[ts(type = "number")]
+    pub created_at: i64,
+    /// Unix timestamp in milliseconds when this version was saved.
+    #[ts(type = "number")]
+    pub updated_at: i64,
+}
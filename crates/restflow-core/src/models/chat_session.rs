@@ -373,6 +373,8 @@ pub enum ChatSessionSource {
     Discord,
     /// Created from Slack inbound messages.
     Slack,
+    /// Created from Matrix inbound messages.
+    Matrix,
     /// Migrated from legacy `channel:*` naming without precise channel type.
     ExternalLegacy,
 }
@@ -640,6 +642,113 @@ impl From<&ChatSession> for ChatSessionSummary {
     }
 }
 
+fn default_chat_search_limit() -> u32 {
+    20
+}
+
+/// Full-text search over chat message content.
+///
+/// Every term must appear in a message for it to match; `"quoted text"` is
+/// matched as a phrase.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ChatSearchQuery {
+    /// Search text
+    pub query: String,
+    /// Only search this agent's sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub agent_id: Option<String>,
+    /// Only search sessions for this skill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub skill_id: Option<String>,
+    /// Also search archived sessions
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Maximum hits to return
+    #[serde(default = "default_chat_search_limit")]
+    pub limit: u32,
+    /// Hits to skip, for pagination
+    #[serde(default)]
+    pub offset: u32,
+}
+
+impl ChatSearchQuery {
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            agent_id: None,
+            skill_id: None,
+            include_archived: false,
+            limit: default_chat_search_limit(),
+            offset: 0,
+        }
+    }
+
+    pub fn with_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn paginate(mut self, limit: u32, offset: u32) -> Self {
+        self.limit = limit;
+        self.offset = offset;
+        self
+    }
+}
+
+/// Character range of a matched term within a snippet.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ChatSearchHighlight {
+    /// Start offset in characters
+    pub start: u32,
+    /// End offset in characters (exclusive)
+    pub end: u32,
+}
+
+/// A chat message matching a [`ChatSearchQuery`].
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ChatSearchHit {
+    /// Session ID
+    pub session_id: String,
+    /// Session name
+    pub session_name: String,
+    /// Agent ID
+    pub agent_id: String,
+    /// Matching message ID
+    pub message_id: String,
+    /// Role of the message sender
+    pub role: ChatRole,
+    /// Message timestamp in milliseconds
+    #[ts(type = "number")]
+    pub timestamp: i64,
+    /// Relevance score (higher is better)
+    pub score: f32,
+    /// Excerpt of the message around the first match
+    pub snippet: String,
+    /// Matched terms within `snippet`
+    pub highlights: Vec<ChatSearchHighlight>,
+}
+
+/// One page of chat search hits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ChatSearchResult {
+    /// Hits on this page, best first
+    pub hits: Vec<ChatSearchHit>,
+    /// Total number of matching messages
+    pub total_count: u32,
+    /// Whether more hits follow this page
+    pub has_more: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+/// Kind of entity tracked in the relationship graph.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq, Hash)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Agent,
+    ChatSession,
+    BackgroundTask,
+    Deliverable,
+}
+
+/// Typed edge between two entities. Edges point from the entity that
+/// records the link to the one it references.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq, Hash)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    /// Session or task created to run an agent.
+    SpawnedBy,
+    /// Task started after a prerequisite task completed.
+    TriggeredBy,
+    /// Deliverable produced by a task execution.
+    ProducedBy,
+    /// Task that created and owns its chat session.
+    Owns,
+    /// Task reusing an existing chat session.
+    BoundTo,
+}
+
+impl RelationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SpawnedBy => "spawned_by",
+            Self::TriggeredBy => "triggered_by",
+            Self::ProducedBy => "produced_by",
+            Self::Owns => "owns",
+            Self::BoundTo => "bound_to",
+        }
+    }
+}
+
+/// Reference to a single entity in the relationship graph.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq, Hash)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct EntityRef {
+    pub kind: EntityKind,
+    pub id: String,
+}
+
+impl EntityRef {
+    pub fn new(kind: EntityKind, id: impl Into<String>) -> Self {
+        Self {
+            kind,
+            id: id.into(),
+        }
+    }
+}
+
+/// Directed edge stored in the relationship index.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct EntityRelation {
+    pub source: EntityRef,
+    pub relation: RelationKind,
+    pub target: EntityRef,
+    /// Unix timestamp in milliseconds when the edge was recorded.
+    pub created_at: i64,
+}
+
+/// Typed neighborhood of an entity returned by `get_related`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct RelatedEntities {
+    pub entity_id: String,
+    /// Kind of the entity, when any edge touches it.
+    pub kind: Option<EntityKind>,
+    /// Edges recorded by this entity (e.g. the agent a task was spawned by).
+    pub outgoing: Vec<EntityRelation>,
+    /// Edges pointing at this entity (e.g. the tasks an agent spawned).
+    pub incoming: Vec<EntityRelation>,
+}
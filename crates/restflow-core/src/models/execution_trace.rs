@@ -10,7 +10,7 @@ pub use restflow_contracts::request::{
     ExecutionTraceQuery, ExecutionTraceSource, ExecutionTraceStats, ExecutionTraceTimeRange,
     LifecycleTrace, LlmCallTrace, LogRecordTrace, MessageTrace, MetricDimension, MetricSampleTrace,
     ModelSwitchTrace, ProviderHealthQuery, ProviderHealthResponse, ProviderHealthTrace,
-    ToolCallPhase, ToolCallTrace,
+    SecurityDecisionTrace, ToolCallPhase, ToolCallTrace,
 };
 use serde::{Deserialize, Serialize};
 use specta::Type;
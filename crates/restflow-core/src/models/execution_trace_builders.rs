@@ -4,7 +4,7 @@ use restflow_telemetry::RestflowTrace;
 use super::{
     ExecutionTraceCategory, ExecutionTraceEvent, ExecutionTraceSource, LifecycleTrace,
     LlmCallTrace, LogRecordTrace, MessageTrace, MetricSampleTrace, ModelSwitchTrace,
-    ProviderHealthTrace, ToolCallTrace,
+    ProviderHealthTrace, SecurityDecisionTrace, ToolCallTrace,
 };
 
 pub(crate) fn new_event(
@@ -37,6 +37,7 @@ pub(crate) fn new_event(
         metric_sample: None,
         provider_health: None,
         log_record: None,
+        security_decision: None,
     }
 }
 
@@ -168,6 +169,22 @@ pub(crate) fn log_record(
     )
 }
 
+pub(crate) fn security_decision(
+    task_id: impl Into<String>,
+    agent_id: impl Into<String>,
+    trace: SecurityDecisionTrace,
+) -> ExecutionTraceEvent {
+    with_security_decision(
+        new_event(
+            task_id,
+            agent_id,
+            ExecutionTraceCategory::SecurityDecision,
+            ExecutionTraceSource::Runtime,
+        ),
+        trace,
+    )
+}
+
 pub(crate) fn with_llm_call(
     mut event: ExecutionTraceEvent,
     trace: LlmCallTrace,
@@ -232,6 +249,14 @@ pub(crate) fn with_log_record(
     event
 }
 
+pub(crate) fn with_security_decision(
+    mut event: ExecutionTraceEvent,
+    trace: SecurityDecisionTrace,
+) -> ExecutionTraceEvent {
+    event.security_decision = Some(trace);
+    event
+}
+
 #[allow(dead_code)]
 pub(crate) fn with_subflow_path(
     mut event: ExecutionTraceEvent,
@@ -309,6 +334,9 @@ mod tests {
                 duration_ms: Some(10),
                 is_reasoning: Some(false),
                 message_count: Some(1),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+                cache_savings_usd: None,
             },
         );
         assert_eq!(llm.category, ExecutionTraceCategory::LlmCall);
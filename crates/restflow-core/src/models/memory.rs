@@ -126,6 +126,17 @@ pub struct MemoryChunk {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub embedding_dim: Option<usize>,
+
+    /// Unix timestamp in milliseconds when consolidation archived this chunk.
+    /// Archived chunks are kept but excluded from search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "number")]
+    pub archived_at: Option<i64>,
+
+    /// IDs of the chunks this chunk summarizes, when written by consolidation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(optional)]
+    pub consolidated_from: Vec<String>,
 }
 
 impl MemoryChunk {
@@ -153,6 +164,8 @@ impl MemoryChunk {
             embedding: None,
             embedding_model: None,
             embedding_dim: None,
+            archived_at: None,
+            consolidated_from: Vec::new(),
         }
     }
 
@@ -219,6 +232,19 @@ impl MemoryChunk {
         self.created_at = timestamp;
         self
     }
+
+    /// Record the chunks this chunk summarizes
+    #[must_use]
+    pub fn with_consolidated_from(mut self, chunk_ids: Vec<String>) -> Self {
+        self.consolidated_from = chunk_ids;
+        self
+    }
+
+    /// Check if consolidation has archived this chunk
+    #[must_use]
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
 }
 
 /// A memory session representing a group of related memory chunks.
@@ -619,6 +645,19 @@ pub enum SearchMode {
     Phrase,
     /// Regular expression search
     Regex,
+    /// Vector similarity only (requires an embedding model)
+    Semantic,
+    /// Keyword and vector rankings fused with reciprocal rank fusion
+    Hybrid,
+}
+
+impl SearchMode {
+    /// Whether this mode ranks by embedding similarity.
+    ///
+    /// Without embeddings these modes fall back to keyword matching.
+    pub fn uses_embeddings(&self) -> bool {
+        matches!(self, SearchMode::Semantic | SearchMode::Hybrid)
+    }
 }
 
 /// Filter for memory source types.
@@ -676,6 +715,34 @@ pub struct MemoryStats {
     /// Newest memory timestamp
     #[ts(type = "number | null")]
     pub newest_memory: Option<i64>,
+
+    /// Chunks archived by consolidation (included in `chunk_count`)
+    #[serde(default)]
+    pub archived_count: u32,
+
+    /// Summary chunks written by consolidation (included in `chunk_count`)
+    #[serde(default)]
+    pub consolidated_count: u32,
+
+    /// Days before session chunks expire (0 keeps them)
+    #[serde(default)]
+    pub session_retention_days: u32,
+
+    /// Chunk limit before least recently used chunks are evicted (0 for none)
+    #[serde(default)]
+    pub max_chunks: u32,
+}
+
+/// Number of memory chunks carrying a tag.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct TagCount {
+    /// Tag name
+    pub tag: String,
+
+    /// Number of chunks with this tag
+    pub count: u32,
 }
 
 #[cfg(test)]
@@ -859,6 +926,14 @@ mod tests {
             serde_json::to_string(&SearchMode::Regex).unwrap(),
             "\"regex\""
         );
+        assert_eq!(
+            serde_json::to_string(&SearchMode::Hybrid).unwrap(),
+            "\"hybrid\""
+        );
+        assert_eq!(
+            serde_json::from_str::<SearchMode>("\"semantic\"").unwrap(),
+            SearchMode::Semantic
+        );
     }
 
     #[test]
@@ -1,12 +1,17 @@
 pub mod agent;
 pub mod agent_execution;
 pub mod agent_meta;
+pub mod agent_template;
+pub mod api_connector;
+pub mod api_token;
 pub mod audit;
 pub mod background_agent;
+pub mod browser_plan;
 pub mod channel_session_binding;
 pub mod chat_session;
 pub mod checkpoint;
 pub mod deliverable;
+pub mod entity_relation;
 pub mod execution_console;
 pub mod execution_trace;
 pub(crate) mod execution_trace_builders;
@@ -14,6 +19,7 @@ pub mod hook;
 pub mod memory;
 pub mod model_ref;
 mod provider_policy;
+pub mod replay;
 pub mod security;
 pub mod shared_space;
 pub mod skill;
@@ -24,18 +30,27 @@ pub mod steer;
 pub mod storage_mode;
 pub mod terminal_session;
 pub mod trigger;
+pub mod usage;
 pub mod validation;
 pub mod webhook;
 pub mod work_item;
+pub mod workspace;
 
 #[cfg(test)]
 mod model_tests;
 
 pub use agent::{
-    AgentNode, ApiKeyConfig, CodexCliExecutionMode, ModelRoutingConfig, SkillPreflightPolicyMode,
+    AgentNode, ApiKeyConfig, CodexCliExecutionMode, ExecutionStrategy, FailoverPolicy,
+    ModelRoutingConfig, SkillPreflightPolicyMode,
 };
 pub use agent_execution::{AgentExecuteResponse, ExecutionDetails, ExecutionStep, ToolCallInfo};
 pub use agent_meta::{AgentMeta, AgentType};
+pub use agent_template::{AgentTemplate, AgentTemplateTask};
+pub use api_connector::{
+    ApiConnector, ApiKeyLocation, ConnectorAuth, ConnectorOperation, ConnectorParameter,
+    ParameterLocation,
+};
+pub use api_token::{ApiToken, ApiTokenRole, ApiTokenScope};
 pub(crate) use background_agent::{
     BackgroundAgent, BackgroundAgentControlAction, BackgroundAgentConversionResult,
     BackgroundAgentEvent, BackgroundAgentEventType, BackgroundAgentPatch, BackgroundAgentRun,
@@ -43,14 +58,18 @@ pub(crate) use background_agent::{
     BackgroundAgentSpec, BackgroundAgentStatus, BackgroundMessage, BackgroundProgress,
 };
 pub use background_agent::{
-    CliExecutionConfig, ContinuationConfig, DurabilityMode, ExecutionMode, MemoryConfig,
-    MemoryScope, NotificationConfig, ResourceLimits, Task, TaskControlAction, TaskConversionResult,
-    TaskEvent, TaskEventType, TaskMessage, TaskMessageSource, TaskMessageStatus, TaskPatch,
-    TaskProgress, TaskRun, TaskRunMetrics, TaskRunStatus, TaskSchedule, TaskSpec, TaskStatus,
+    CliExecutionConfig, ConcurrencyLimits, ContinuationConfig, DurabilityMode, ExecutionMode,
+    MemoryConfig, MemoryScope, NotificationConfig, QueuePriority, ResourceLimits, Task,
+    TaskControlAction, TaskConversionResult, TaskEvent, TaskEventType, TaskMessage,
+    TaskMessageSource, TaskMessageStatus, TaskPatch, TaskProgress, TaskQueuePolicy, TaskRun,
+    TaskRunMetrics, TaskRunStatus, TaskSchedule, TaskSpec, TaskStatus, TaskWake, WakeCondition,
+    WakeEvent,
 };
+pub use browser_plan::SavedBrowserPlan;
 pub use channel_session_binding::ChannelSessionBinding;
 pub use checkpoint::{AgentCheckpoint, ResumePayload};
 pub use deliverable::{Deliverable, DeliverableType};
+pub use entity_relation::{EntityKind, EntityRef, EntityRelation, RelatedEntities, RelationKind};
 pub use execution_console::{
     ChildRunListQuery, ExecutionContainerKind, ExecutionContainerRef, ExecutionContainerSummary,
     ExecutionThread, RunKind, RunListQuery, RunSummary,
@@ -58,7 +77,7 @@ pub use execution_console::{
 pub use hook::{Hook, HookAction, HookContext, HookEvent, HookFilter};
 pub use memory::{
     MemoryChunk, MemorySearchQuery, MemorySearchResult, MemorySession, MemorySource, MemoryStats,
-    SearchMode, SourceTypeFilter, UnifiedSearchQuery,
+    SearchMode, SourceTypeFilter, TagCount, UnifiedSearchQuery,
 };
 pub use model_ref::ModelRef;
 pub(crate) use provider_policy::{
@@ -66,10 +85,15 @@ pub(crate) use provider_policy::{
     provider_auth_providers, provider_default_model, provider_display_order,
     secret_provider_resolution_order,
 };
+pub use replay::{ExecutionReplay, ReplayRunResult, ReplaySnapshot, ReplayStep};
 pub use restflow_models::{ModelId, ModelMetadata, ModelMetadataDTO, Provider};
 pub use steer::{SteerMessage, SteerSource};
-pub use webhook::{WebhookConfig, WebhookRateLimiter, WebhookRequest, WebhookResponse};
+pub use webhook::{
+    CallbackDelivery, CallbackDeliveryStatus, ResultCallback, WebhookConfig, WebhookRateLimiter,
+    WebhookRequest, WebhookResponse,
+};
 pub use work_item::{ItemQuery, ItemStatus, WorkItem, WorkItemPatch, WorkItemSpec};
+pub use workspace::{User, WorkspaceResource, WorkspaceResourceKind};
 
 // Export execution trace types (new naming)
 pub use execution_trace::{
@@ -78,7 +102,7 @@ pub use execution_trace::{
     ExecutionTraceQuery, ExecutionTraceSource, ExecutionTraceStats, ExecutionTraceTimeRange,
     LifecycleTrace, LlmCallTrace, LogRecordTrace, MessageTrace, MetricDimension, MetricSampleTrace,
     ModelSwitchTrace, ProviderHealthQuery, ProviderHealthResponse, ProviderHealthTrace,
-    ToolCallCompletion, ToolCallPhase, ToolCallTrace,
+    SecurityDecisionTrace, ToolCallCompletion, ToolCallPhase, ToolCallTrace,
 };
 
 // Re-export audit types for backward compatibility (aliases to execution_trace)
@@ -89,13 +113,14 @@ pub use audit::{
 
 pub use chat_session::{
     ChatExecutionStatus, ChatMediaType, ChatMessage, ChatMessageMedia, ChatMessageTranscript,
-    ChatRole, ChatSession, ChatSessionMetadata, ChatSessionSource, ChatSessionSummary,
-    ChatSessionUpdate, ExecutionStepInfo, MessageExecution,
+    ChatRole, ChatSearchHighlight, ChatSearchHit, ChatSearchQuery, ChatSearchResult, ChatSession,
+    ChatSessionMetadata, ChatSessionSource, ChatSessionSummary, ChatSessionUpdate,
+    ExecutionStepInfo, MessageExecution,
 };
-pub use restflow_storage::Secret;
+pub use restflow_storage::{Secret, SecretAccess};
 pub use security::{
     AgentSecurityConfig, ApprovalStatus, AskMode, CommandPattern, PendingApproval, SecurityAction,
-    SecurityCheckResult, SecurityMode, SecurityPolicy, ToolAction, ToolRule,
+    SecurityCheckResult, SecurityMode, SecurityPolicy, ToolAction, ToolApprovalPolicy, ToolRule,
 };
 pub use shared_space::{SharedEntry, Visibility};
 pub use skill::{Skill, SkillStatus};
@@ -103,10 +128,11 @@ pub use skill_folder::{SkillGating, SkillReference, SkillScript};
 pub use skill_marketplace::{
     BinaryRequirement, EnvVarRequirement, GatingCheckResult, GatingRequirements, InstallStatus,
     InstalledSkill, OsType, SkillAuthor, SkillDependency, SkillManifest, SkillPermission,
-    SkillPermissions, SkillSource, SkillVersion, VersionRequirement,
+    SkillPermissions, SkillSignature, SkillSource, SkillVersion, VersionRequirement,
 };
 pub use skill_meta::SkillMeta;
 pub use storage_mode::StorageMode;
 pub use terminal_session::{TerminalSession, TerminalStatus};
-pub use trigger::{ActiveTrigger, AuthConfig, TriggerConfig};
+pub use trigger::{ActiveTrigger, AuthConfig, BrowserPlan, DigestKind, TriggerConfig};
+pub use usage::{UsageBucket, UsageBudgetStatus, UsageQuery, UsageRecord, UsageStats, UsageTotals};
 pub use validation::{ValidationError, ValidationErrorResponse, encode_validation_error};
@@ -9,6 +9,7 @@ const AUTH_CLAUDE_CODE: &[AuthProvider] = &[AuthProvider::ClaudeCode];
 const AUTH_OPENAI: &[AuthProvider] = &[AuthProvider::OpenAI];
 const AUTH_OPENAI_CODEX: &[AuthProvider] = &[AuthProvider::OpenAICodex];
 const AUTH_GOOGLE: &[AuthProvider] = &[AuthProvider::Google];
+const AUTH_BEDROCK: &[AuthProvider] = &[AuthProvider::Bedrock];
 const AUTH_OTHER: &[AuthProvider] = &[AuthProvider::Other];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +37,7 @@ const ALL_PROVIDER_AUTH_POLICIES: &[(Provider, &[AuthProvider])] = &[
     (Provider::SiliconFlow, AUTH_OTHER),
     (Provider::MiniMax, AUTH_OTHER),
     (Provider::MiniMaxCodingPlan, AUTH_OTHER),
+    (Provider::Bedrock, AUTH_BEDROCK),
 ];
 
 const DISPLAY_PROVIDER_ORDER: &[Provider] = &[
@@ -46,6 +48,7 @@ const DISPLAY_PROVIDER_ORDER: &[Provider] = &[
     Provider::Codex,
     Provider::Anthropic,
     Provider::Google,
+    Provider::Bedrock,
     Provider::DeepSeek,
     Provider::Groq,
     Provider::OpenRouter,
@@ -65,6 +68,7 @@ const PROFILE_PROVIDER_RESOLUTION_ORDER: &[(AuthProvider, Provider)] = &[
     (AuthProvider::Anthropic, Provider::Anthropic),
     (AuthProvider::OpenAI, Provider::OpenAI),
     (AuthProvider::Google, Provider::Google),
+    (AuthProvider::Bedrock, Provider::Bedrock),
 ];
 
 const SECRET_PROVIDER_RESOLUTION_ORDER: &[Provider] = &[
@@ -75,6 +79,7 @@ const SECRET_PROVIDER_RESOLUTION_ORDER: &[Provider] = &[
     Provider::Anthropic,
     Provider::OpenAI,
     Provider::Google,
+    Provider::Bedrock,
     Provider::DeepSeek,
     Provider::Groq,
     Provider::OpenRouter,
@@ -102,6 +107,10 @@ const ACCESS_GOOGLE: ProviderAccessPolicy = ProviderAccessPolicy {
     auth_profiles: AUTH_GOOGLE,
     allow_secret_env: true,
 };
+const ACCESS_BEDROCK: ProviderAccessPolicy = ProviderAccessPolicy {
+    auth_profiles: AUTH_BEDROCK,
+    allow_secret_env: true,
+};
 const ACCESS_CLAUDE_CODE: ProviderAccessPolicy = ProviderAccessPolicy {
     auth_profiles: AUTH_CLAUDE_CODE,
     allow_secret_env: false,
@@ -130,6 +139,7 @@ const ALL_PROVIDER_ACCESS_POLICIES: &[(Provider, ProviderAccessPolicy)] = &[
     (Provider::SiliconFlow, ACCESS_SECRET_ONLY),
     (Provider::MiniMax, ACCESS_SECRET_ONLY),
     (Provider::MiniMaxCodingPlan, ACCESS_SECRET_ONLY),
+    (Provider::Bedrock, ACCESS_BEDROCK),
 ];
 
 fn provider_auth_policy(provider: Provider) -> &'static [AuthProvider] {
@@ -0,0 +1,159 @@
+//! Replay models for stepping through past agent runs.
+//!
+//! Every ReAct turn of an agent run is recorded as a `ReplaySnapshot` holding
+//! the full serialized `AgentState`. Snapshots are presented to the UI as an
+//! `ExecutionReplay`, and any step can be used as the starting point of a new
+//! run.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+use super::ExecutionStep;
+
+/// Default snapshot retention: 7 days.
+const DEFAULT_REPLAY_TTL_MS: i64 = 7 * 24 * 3600 * 1000;
+
+/// Persisted agent state after one step of an execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaySnapshot {
+    /// Links to `AgentState.execution_id`.
+    pub execution_id: String,
+    /// Zero-based position of the snapshot within the execution.
+    pub step: u32,
+    /// Links to `Task.id` (if running as a background task).
+    #[serde(default)]
+    pub task_id: Option<String>,
+    /// Agent that produced the run.
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Telemetry run the execution belongs to.
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// `AgentState.iteration` when the snapshot was taken.
+    pub iteration: usize,
+    /// Serialized `AgentState` (full JSON).
+    pub state_json: Vec<u8>,
+    /// Creation timestamp in milliseconds since epoch.
+    pub created_at: i64,
+    /// TTL: auto-cleanup after this timestamp (milliseconds since epoch).
+    #[serde(default)]
+    pub expired_at: Option<i64>,
+}
+
+impl ReplaySnapshot {
+    /// Create a new snapshot.
+    pub fn new(execution_id: String, step: u32, iteration: usize, state_json: Vec<u8>) -> Self {
+        let now = chrono::Utc::now().timestamp_millis();
+        Self {
+            execution_id,
+            step,
+            task_id: None,
+            agent_id: None,
+            run_id: None,
+            iteration,
+            state_json,
+            created_at: now,
+            expired_at: Some(now + DEFAULT_REPLAY_TTL_MS),
+        }
+    }
+
+    /// Set the background task the run belongs to.
+    pub fn with_task_id(mut self, task_id: Option<String>) -> Self {
+        self.task_id = task_id;
+        self
+    }
+
+    /// Set the agent that produced the run.
+    pub fn with_agent_id(mut self, agent_id: Option<String>) -> Self {
+        self.agent_id = agent_id;
+        self
+    }
+
+    /// Set the telemetry run the execution belongs to.
+    pub fn with_run_id(mut self, run_id: Option<String>) -> Self {
+        self.run_id = run_id;
+        self
+    }
+}
+
+/// Step-by-step view of a recorded execution.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ExecutionReplay {
+    pub execution_id: String,
+    pub task_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    /// Recorded steps in execution order.
+    pub steps: Vec<ReplayStep>,
+}
+
+/// One recorded step of an execution.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ReplayStep {
+    /// Position to pass when re-executing from this step.
+    pub step: u32,
+    /// ReAct iteration reached at this step.
+    pub iteration: usize,
+    /// Agent status: "running" | "completed" | "failed" | "max_iterations" |
+    /// "interrupted" | "resource_exhausted"
+    pub status: String,
+    /// Creation timestamp in milliseconds since epoch.
+    #[ts(type = "number")]
+    pub created_at: i64,
+    /// LLM messages, tool calls and tool outputs added since the previous step.
+    pub messages: Vec<ExecutionStep>,
+}
+
+/// Outcome of re-executing a recorded run from one of its steps.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ReplayRunResult {
+    /// Execution the replay started from.
+    pub source_execution_id: String,
+    /// Step of the source execution the replay started from.
+    pub from_step: u32,
+    /// Execution ID of the new run; can itself be replayed.
+    pub execution_id: String,
+    /// Agent that ran the replay.
+    pub agent_id: String,
+    /// Final answer of the new run.
+    pub output: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_defaults_to_week_ttl() {
+        let snapshot = ReplaySnapshot::new("exec-1".into(), 2, 3, b"{}".to_vec())
+            .with_agent_id(Some("agent-1".into()));
+        assert_eq!(snapshot.step, 2);
+        assert_eq!(snapshot.agent_id.as_deref(), Some("agent-1"));
+        assert_eq!(
+            snapshot.expired_at,
+            Some(snapshot.created_at + DEFAULT_REPLAY_TTL_MS)
+        );
+    }
+
+    #[test]
+    fn export_bindings_executionreplay() {
+        ExecutionReplay::export_to_string(&ts_rs::Config::default()).unwrap();
+    }
+
+    #[test]
+    fn export_bindings_replaystep() {
+        ReplayStep::export_to_string(&ts_rs::Config::default()).unwrap();
+    }
+
+    #[test]
+    fn export_bindings_replayrunresult() {
+        ReplayRunResult::export_to_string(&ts_rs::Config::default()).unwrap();
+    }
+}
@@ -89,6 +89,11 @@ pub struct ToolRule {
     pub tool_name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub operation: Option<String>,
+    /// Argument whose string value is matched against `target_pattern` by
+    /// the tool approval policy. When unset the pattern is matched against
+    /// the JSON-encoded arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub argument: Option<String>,
     pub target_pattern: String,
     pub action: SecurityAction,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -97,6 +102,87 @@ pub struct ToolRule {
     pub priority: i32,
 }
 
+impl ToolRule {
+    /// Check whether this rule applies to a call of `tool_name` with `args`.
+    ///
+    /// `operation` is compared with the `operation` (or `action`) argument.
+    pub fn matches_call(&self, tool_name: &str, args: &serde_json::Value) -> bool {
+        if self.tool_name != "*" && self.tool_name != tool_name {
+            return false;
+        }
+        if let Some(operation) = self.operation.as_deref() {
+            let called = args
+                .get("operation")
+                .or_else(|| args.get("action"))
+                .and_then(serde_json::Value::as_str);
+            if called != Some(operation) {
+                return false;
+            }
+        }
+        let target = match self.argument.as_deref() {
+            Some(argument) => match args.get(argument) {
+                Some(serde_json::Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => return false,
+            },
+            None => args.to_string(),
+        };
+        glob_match(&self.target_pattern, &target)
+    }
+}
+
+/// Approval policy applied to every tool call made by an agent.
+///
+/// Rules are evaluated by descending priority; the first matching rule
+/// decides whether the call runs, is blocked or waits for user approval.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ToolApprovalPolicy {
+    /// Action for tool calls not matching any rule (default: allow)
+    #[serde(default = "default_tool_approval_action")]
+    pub default_action: SecurityAction,
+
+    /// Per-tool and per-argument rules
+    #[serde(default)]
+    pub rules: Vec<ToolRule>,
+
+    /// Approval timeout in seconds (default: 300 = 5 minutes)
+    #[serde(default = "default_approval_timeout")]
+    pub approval_timeout_secs: u64,
+}
+
+impl ToolApprovalPolicy {
+    /// Decide what to do with a call, returning the matched rule if any.
+    pub fn evaluate(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+    ) -> (SecurityAction, Option<&ToolRule>) {
+        let mut rules: Vec<&ToolRule> = self.rules.iter().collect();
+        rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+        rules
+            .into_iter()
+            .find(|rule| rule.matches_call(tool_name, args))
+            .map(|rule| (rule.action, Some(rule)))
+            .unwrap_or((self.default_action, None))
+    }
+}
+
+impl Default for ToolApprovalPolicy {
+    fn default() -> Self {
+        Self {
+            default_action: default_tool_approval_action(),
+            rules: Vec::new(),
+            approval_timeout_secs: default_approval_timeout(),
+        }
+    }
+}
+
+fn default_tool_approval_action() -> SecurityAction {
+    SecurityAction::Allow
+}
+
 fn default_approval_timeout() -> u64 {
     300
 }
@@ -380,6 +466,19 @@ pub struct PendingApproval {
     /// Optional reason for rejection
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rejection_reason: Option<String>,
+
+    /// Tool awaiting approval (tool approval policy only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+
+    /// Arguments of the tool call awaiting approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(type = "any")]
+    pub arguments: Option<serde_json::Value>,
+
+    /// Chat session the requesting run belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 impl PendingApproval {
@@ -401,6 +500,9 @@ impl PendingApproval {
             expires_at: now + timeout_secs as i64,
             status: ApprovalStatus::Pending,
             rejection_reason: None,
+            tool_name: None,
+            arguments: None,
+            session_id: None,
         }
     }
 
@@ -410,6 +512,23 @@ impl PendingApproval {
         self
     }
 
+    /// Attach the tool call awaiting approval.
+    pub fn with_tool_call(
+        mut self,
+        tool_name: impl Into<String>,
+        arguments: serde_json::Value,
+    ) -> Self {
+        self.tool_name = Some(tool_name.into());
+        self.arguments = Some(arguments);
+        self
+    }
+
+    /// Set the chat session the requesting run belongs to.
+    pub fn with_session_id(mut self, session_id: Option<String>) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
     /// Check if the approval request has expired.
     pub fn is_expired(&self) -> bool {
         chrono::Utc::now().timestamp() > self.expires_at
@@ -758,6 +877,65 @@ mod tests {
         assert_eq!(action.as_pattern_string(), "email:send user@test.com");
     }
 
+    #[test]
+    fn test_tool_approval_policy_matches_argument_patterns() {
+        let rule = |id: &str, argument: Option<&str>, pattern: &str, action, priority| ToolRule {
+            id: id.to_string(),
+            tool_name: "bash".to_string(),
+            operation: None,
+            argument: argument.map(str::to_string),
+            target_pattern: pattern.to_string(),
+            action,
+            description: None,
+            priority,
+        };
+        let policy = ToolApprovalPolicy {
+            rules: vec![
+                rule("ask-bash", None, "*", SecurityAction::RequireApproval, 0),
+                rule(
+                    "allow-ls",
+                    Some("command"),
+                    "ls *",
+                    SecurityAction::Allow,
+                    10,
+                ),
+                rule(
+                    "deny-rm",
+                    Some("command"),
+                    "rm -rf *",
+                    SecurityAction::Block,
+                    20,
+                ),
+            ],
+            ..ToolApprovalPolicy::default()
+        };
+
+        let evaluate = |command: &str| {
+            let (action, rule) =
+                policy.evaluate("bash", &serde_json::json!({ "command": command }));
+            (action, rule.map(|rule| rule.id.clone()))
+        };
+        assert_eq!(
+            evaluate("ls -la"),
+            (SecurityAction::Allow, Some("allow-ls".to_string()))
+        );
+        assert_eq!(
+            evaluate("rm -rf build"),
+            (SecurityAction::Block, Some("deny-rm".to_string()))
+        );
+        assert_eq!(
+            evaluate("cargo build"),
+            (
+                SecurityAction::RequireApproval,
+                Some("ask-bash".to_string())
+            )
+        );
+        assert_eq!(
+            policy.evaluate("file", &serde_json::json!({ "path": "a.txt" })),
+            (SecurityAction::Allow, None)
+        );
+    }
+
     #[test]
     fn test_tool_rules_default_empty() {
         let policy = SecurityPolicy::default();
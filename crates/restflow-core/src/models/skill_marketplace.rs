@@ -11,6 +11,8 @@ use specta::Type;
 use std::collections::HashMap;
 use ts_rs::TS;
 
+use super::SkillGating;
+
 /// Semantic version for skills
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
 #[specta(skip_attr = "ts")]
@@ -179,6 +181,21 @@ impl VersionRequirement {
     }
 }
 
+impl std::fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact(v) => write!(f, "={}", v),
+            Self::Caret(v) => write!(f, "^{}", v),
+            Self::Tilde(v) => write!(f, "~{}", v),
+            Self::GreaterThan(v) => write!(f, ">{}", v),
+            Self::GreaterOrEqual(v) => write!(f, ">={}", v),
+            Self::LessThan(v) => write!(f, "<{}", v),
+            Self::LessOrEqual(v) => write!(f, "<={}", v),
+            Self::Any => write!(f, "*"),
+        }
+    }
+}
+
 /// Skill dependency
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
 #[specta(skip_attr = "ts")]
@@ -302,6 +319,77 @@ pub struct GatingRequirements {
     pub min_restflow_version: Option<SkillVersion>,
 }
 
+impl GatingRequirements {
+    /// Convert to the compact gating stored on installed skills.
+    pub fn to_skill_gating(&self) -> Option<SkillGating> {
+        if self.binaries.is_empty() && self.env_vars.is_empty() && self.supported_os.is_empty() {
+            return None;
+        }
+        let non_empty = |values: Vec<String>| (!values.is_empty()).then_some(values);
+        Some(SkillGating {
+            bins: non_empty(self.binaries.iter().map(|b| b.name.clone()).collect()),
+            env: non_empty(
+                self.env_vars
+                    .iter()
+                    .filter(|env_var| env_var.required)
+                    .map(|env_var| env_var.name.clone())
+                    .collect(),
+            ),
+            os: non_empty(
+                self.supported_os
+                    .iter()
+                    .map(|os| match os {
+                        OsType::Windows => "windows".to_string(),
+                        OsType::MacOS => "macos".to_string(),
+                        OsType::Linux => "linux".to_string(),
+                        OsType::Any => "any".to_string(),
+                    })
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Rebuild checkable requirements from the gating stored on a skill.
+    pub fn from_skill_gating(gating: &SkillGating) -> Self {
+        Self {
+            binaries: gating
+                .bins
+                .iter()
+                .flatten()
+                .map(|name| BinaryRequirement {
+                    name: name.clone(),
+                    version: None,
+                    version_command: None,
+                    version_pattern: None,
+                })
+                .collect(),
+            env_vars: gating
+                .env
+                .iter()
+                .flatten()
+                .map(|name| EnvVarRequirement {
+                    name: name.clone(),
+                    required: true,
+                    description: None,
+                })
+                .collect(),
+            supported_os: gating
+                .os
+                .iter()
+                .flatten()
+                .filter_map(|os| match os.to_lowercase().as_str() {
+                    "windows" => Some(OsType::Windows),
+                    "macos" | "darwin" => Some(OsType::MacOS),
+                    "linux" => Some(OsType::Linux),
+                    "any" => Some(OsType::Any),
+                    _ => None,
+                })
+                .collect(),
+            min_restflow_version: None,
+        }
+    }
+}
+
 /// Author information
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
 #[specta(skip_attr = "ts")]
@@ -396,6 +484,30 @@ pub struct SkillManifest {
     /// Additional metadata
     #[ts(type = "Record<string, unknown>")]
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Publisher signature over the skill id, version and content
+    #[serde(default)]
+    pub signature: Option<SkillSignature>,
+}
+
+/// Detached ed25519 signature published alongside a skill manifest
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct SkillSignature {
+    /// Base64-encoded ed25519 public key of the publisher
+    pub public_key: String,
+    /// Base64-encoded signature over [`SkillSignature::payload`]
+    pub signature: String,
+}
+
+impl SkillSignature {
+    /// Bytes covered by the signature: `"{id}@{version}\n{sha256(content) hex}"`.
+    pub fn payload(id: &str, version: &SkillVersion, content: &str) -> Vec<u8> {
+        use sha2::{Digest, Sha256};
+
+        let digest = hex::encode(Sha256::digest(content.as_bytes()));
+        format!("{id}@{version}\n{digest}").into_bytes()
+    }
 }
 
 impl Default for SkillManifest {
@@ -419,6 +531,7 @@ impl Default for SkillManifest {
             readme: None,
             changelog: None,
             metadata: HashMap::new(),
+            signature: None,
         }
     }
 }
@@ -13,7 +13,11 @@ pub enum TriggerConfig {
         path: String,
         method: String, // HTTP method as string (GET, POST, etc.)
         auth: Option<AuthConfig>,
-        // Webhooks use async mode only, returning execution_id
+        // Webhooks use async mode only, returning execution_id, unless they
+        // carry a browser plan whose results are returned inline.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        browser_plan: Option<BrowserPlan>,
     },
     Schedule {
         cron: String,
@@ -21,6 +25,47 @@ pub enum TriggerConfig {
         #[ts(type = "any")]
         payload: Option<Value>,
     },
+    /// Run a browser plan on a cron schedule and keep each result as a
+    /// deliverable of the trigger.
+    #[serde(rename = "browser_schedule")]
+    BrowserSchedule {
+        cron: String,
+        timezone: Option<String>,
+        plan: BrowserPlan,
+        /// Payload used to render `${payload.<path>}` placeholders.
+        #[serde(default)]
+        #[ts(type = "any")]
+        payload: Option<Value>,
+        /// Send a notification after each successful run.
+        #[serde(default)]
+        notify: bool,
+        /// Only notify when the result differs from the previous run.
+        #[serde(default)]
+        notify_only_on_change: bool,
+    },
+    /// Send a periodic summary through the notification channel.
+    ///
+    /// Each digest covers the time since the previous one was sent.
+    Digest {
+        cron: String,
+        timezone: Option<String>,
+        digest: DigestKind,
+        /// Limit the digest to tasks and memories of this agent.
+        #[serde(default)]
+        agent_id: Option<String>,
+    },
+}
+
+/// Content of a scheduled digest.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum DigestKind {
+    /// Background agent runs, failures and upcoming schedules.
+    TaskProgress,
+    /// Memories saved by agents.
+    MemoryHighlights,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
@@ -39,6 +84,24 @@ pub enum AuthConfig {
     },
 }
 
+/// Browser action plan run directly by a webhook or scheduled trigger.
+///
+/// String values in `actions` may reference the trigger payload with
+/// `${payload.<path>}`; a string that is only a placeholder is replaced by
+/// the referenced JSON value as-is.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, Type)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct BrowserPlan {
+    /// Browser actions in the `run_actions` format.
+    #[ts(type = "any")]
+    pub actions: Value,
+    /// Overall timeout for the plan, defaults to the agent browser timeout.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub timeout_secs: Option<u64>,
+}
+
 // ResponseMode removed - Webhooks use async mode only
 
 // Store active trigger information
@@ -0,0 +1,196 @@
+//! LLM usage and cost accounting models.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+/// Token usage and cost of a single LLM call.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct UsageRecord {
+    /// Record ID (the originating trace event ID)
+    pub id: String,
+    /// Call timestamp in milliseconds
+    #[ts(type = "number")]
+    pub timestamp: i64,
+    /// Agent that made the call
+    pub agent_id: String,
+    /// Chat session the call belongs to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub session_id: Option<String>,
+    /// Background task the call belongs to
+    pub task_id: String,
+    /// Run the call belongs to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub run_id: Option<String>,
+    /// Provider that served the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub provider: Option<String>,
+    /// Model that served the call
+    pub model: String,
+    /// Prompt (input) tokens
+    pub prompt_tokens: u32,
+    /// Completion (output) tokens
+    pub completion_tokens: u32,
+    /// Cost in USD, when the model's pricing is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cost_usd: Option<f64>,
+    /// Prompt tokens served from the provider's prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: u32,
+    /// Prompt tokens written to the provider's prompt cache
+    #[serde(default)]
+    pub cache_write_tokens: u32,
+    /// USD saved by prompt caching, when the model's pricing is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cache_savings_usd: Option<f64>,
+}
+
+/// Filter for usage aggregation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct UsageQuery {
+    /// Only count calls made by this agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub agent_id: Option<String>,
+    /// Only count calls made in this chat session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub session_id: Option<String>,
+    /// Inclusive lower bound in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "number")]
+    pub from_time: Option<i64>,
+    /// Exclusive upper bound in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "number")]
+    pub to_time: Option<i64>,
+}
+
+impl UsageQuery {
+    pub fn matches(&self, record: &UsageRecord) -> bool {
+        if let Some(agent_id) = &self.agent_id
+            && record.agent_id != *agent_id
+        {
+            return false;
+        }
+        if let Some(session_id) = &self.session_id
+            && record.session_id.as_ref() != Some(session_id)
+        {
+            return false;
+        }
+        if let Some(from_time) = self.from_time
+            && record.timestamp < from_time
+        {
+            return false;
+        }
+        if let Some(to_time) = self.to_time
+            && record.timestamp >= to_time
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Summed usage over a set of LLM calls.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct UsageTotals {
+    /// Number of LLM calls
+    pub call_count: u32,
+    /// Prompt (input) tokens
+    #[ts(type = "number")]
+    pub prompt_tokens: u64,
+    /// Completion (output) tokens
+    #[ts(type = "number")]
+    pub completion_tokens: u64,
+    /// Prompt plus completion tokens
+    #[ts(type = "number")]
+    pub total_tokens: u64,
+    /// Cost in USD of calls with known pricing
+    pub cost_usd: f64,
+    /// Prompt tokens served from the prompt cache
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub cache_read_tokens: u64,
+    /// Prompt tokens written to the prompt cache
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub cache_write_tokens: u64,
+    /// USD saved by prompt caching
+    #[serde(default)]
+    pub cache_savings_usd: f64,
+}
+
+impl UsageTotals {
+    pub fn add(&mut self, record: &UsageRecord) {
+        self.call_count += 1;
+        self.prompt_tokens += u64::from(record.prompt_tokens);
+        self.completion_tokens += u64::from(record.completion_tokens);
+        self.total_tokens += u64::from(record.prompt_tokens) + u64::from(record.completion_tokens);
+        self.cost_usd += record.cost_usd.unwrap_or(0.0);
+        self.cache_read_tokens += u64::from(record.cache_read_tokens);
+        self.cache_write_tokens += u64::from(record.cache_write_tokens);
+        self.cache_savings_usd += record.cache_savings_usd.unwrap_or(0.0);
+    }
+}
+
+/// Usage grouped under one key (session ID, agent ID, or UTC day).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct UsageBucket {
+    /// Group key
+    pub key: String,
+    /// Usage within the group
+    pub totals: UsageTotals,
+}
+
+/// Aggregated usage returned by `get_usage_stats`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct UsageStats {
+    /// Usage across all matching calls
+    pub totals: UsageTotals,
+    /// Usage per chat session, most expensive first
+    pub by_session: Vec<UsageBucket>,
+    /// Usage per agent, most expensive first
+    pub by_agent: Vec<UsageBucket>,
+    /// Usage per UTC day (`YYYY-MM-DD`), oldest first
+    pub by_day: Vec<UsageBucket>,
+}
+
+/// Spend of an agent against a budget.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct UsageBudgetStatus {
+    /// Agent ID
+    pub agent_id: String,
+    /// Start of the budget window in milliseconds
+    #[ts(type = "number")]
+    pub since: i64,
+    /// Usage within the window
+    pub spent: UsageTotals,
+    /// Budget in USD, if one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub budget_usd: Option<f64>,
+    /// Budget left in USD, if a budget was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub remaining_usd: Option<f64>,
+    /// Whether spend has reached the budget
+    pub exceeded: bool,
+}
@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 use ts_rs::TS;
 
 /// Webhook configuration for a task
@@ -83,6 +84,16 @@ pub struct WebhookRequest {
     /// Optional metadata for logging/debugging
     #[serde(default)]
     pub metadata: Option<serde_json::Value>,
+    /// Optional URL that receives the result of this triggered run
+    #[serde(default)]
+    pub callback_url: Option<String>,
+    /// Optional secret used to sign the result callback for this run
+    #[serde(default)]
+    pub callback_secret: Option<String>,
+    /// Execution-scoped secrets for this run only; kept in memory and wiped
+    /// when the run finishes
+    #[serde(default)]
+    pub secrets: Option<HashMap<String, String>>,
 }
 
 /// Webhook trigger response
@@ -119,6 +130,17 @@ impl WebhookResponse {
         }
     }
 
+    /// Create a response for a task run that was queued but has not started yet
+    pub fn queued(task_id: String) -> Self {
+        Self {
+            accepted: true,
+            run_id: None,
+            task_id: Some(task_id),
+            error: None,
+            timestamp: Some(chrono::Utc::now().timestamp_millis()),
+        }
+    }
+
     /// Create an error response
     pub fn error(message: impl Into<String>) -> Self {
         Self {
@@ -131,6 +153,76 @@ impl WebhookResponse {
     }
 }
 
+/// Outbound callback that receives run results when a task finishes
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct ResultCallback {
+    /// URL that receives a POST with the run result
+    pub url: String,
+    /// Optional HMAC-SHA256 signing secret
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Remove the callback after its first delivery (per-trigger callbacks)
+    #[serde(default)]
+    pub once: bool,
+}
+
+impl ResultCallback {
+    /// Create a persistent callback for every run of a task
+    pub fn new(url: impl Into<String>, secret: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret,
+            once: false,
+        }
+    }
+
+    /// Create a callback that is consumed by the next finished run
+    pub fn once(url: impl Into<String>, secret: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret,
+            once: true,
+        }
+    }
+}
+
+/// Final state of a result callback delivery
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackDeliveryStatus {
+    Delivered,
+    Failed,
+}
+
+/// Delivery record for one result callback, kept on the task
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct CallbackDelivery {
+    /// Callback URL that was called
+    pub url: String,
+    /// Run that produced the delivered result
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// Final delivery status
+    pub status: CallbackDeliveryStatus,
+    /// Number of attempts made, including retries
+    pub attempts: u32,
+    /// HTTP status code of the last attempt, if a response was received
+    #[serde(default)]
+    pub status_code: Option<u16>,
+    /// Error from the last failed attempt
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Timestamp of the last attempt (milliseconds since epoch)
+    #[ts(type = "number")]
+    pub attempted_at: i64,
+}
+
 /// Rate limiter state for webhook endpoints
 #[derive(Debug, Clone, Default)]
 pub struct WebhookRateLimiter {
@@ -267,5 +359,20 @@ mod tests {
         assert!(request.input.is_none());
         assert!(request.source.is_none());
         assert!(request.metadata.is_none());
+        assert!(request.callback_url.is_none());
+    }
+
+    #[test]
+    fn test_result_callback_constructors() {
+        let callback = ResultCallback::new("https://example.com/cb", None);
+        assert!(!callback.once);
+
+        let callback = ResultCallback::once("https://example.com/cb", Some("s".to_string()));
+        assert!(callback.once);
+        assert_eq!(callback.secret.as_deref(), Some("s"));
+
+        let parsed: ResultCallback =
+            serde_json::from_str(r#"{"url":"https://example.com/cb"}"#).unwrap();
+        assert_eq!(parsed, ResultCallback::new("https://example.com/cb", None));
     }
 }
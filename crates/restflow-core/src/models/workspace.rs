@@ -0,0 +1,60 @@
+//! Users and workspace ownership for multi-user server mode.
+//!
+//! A single daemon can host several people. Each user owns a workspace: the
+//! agents and skills created through tokens bound to that user. Sessions and
+//! memory belong to the workspace of the agent they were recorded for.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use ts_rs::TS;
+
+/// A person whose data is isolated from other users of the same server.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct User {
+    pub id: String,
+    /// Display name, unique across users.
+    pub name: String,
+    #[ts(type = "number")]
+    pub created_at: i64,
+}
+
+/// Kind of resource a workspace can own.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, Type, PartialEq, Eq, Hash)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceResourceKind {
+    Agent,
+    Skill,
+}
+
+impl WorkspaceResourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Agent => "agent",
+            Self::Skill => "skill",
+        }
+    }
+}
+
+/// Ownership record tying an agent or skill to a user's workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, PartialEq, Eq)]
+#[specta(skip_attr = "ts")]
+#[ts(export)]
+pub struct WorkspaceResource {
+    pub kind: WorkspaceResourceKind,
+    pub resource_id: String,
+    /// ID of the owning [`User`].
+    pub owner_id: String,
+    #[ts(type = "number")]
+    pub assigned_at: i64,
+}
+
+impl WorkspaceResource {
+    /// Storage key of the ownership record for `kind` and `resource_id`.
+    pub fn key(kind: WorkspaceResourceKind, resource_id: &str) -> String {
+        format!("{}:{resource_id}", kind.as_str())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::BackgroundAgent;
+use crate::models::{BackgroundAgent, QueuePriority};
 use anyhow::Result;
 use async_trait::async_trait;
 use crossbeam_queue::SegQueue;
@@ -17,6 +17,16 @@ pub enum TaskPriority {
     Critical = 3,
 }
 
+impl From<QueuePriority> for TaskPriority {
+    fn from(priority: QueuePriority) -> Self {
+        match priority {
+            QueuePriority::Low => TaskPriority::Low,
+            QueuePriority::Normal => TaskPriority::Normal,
+            QueuePriority::High => TaskPriority::High,
+        }
+    }
+}
+
 /// Task queued for execution.
 #[derive(Debug, Clone)]
 pub struct QueuedTask {
@@ -173,6 +173,18 @@ impl ProcessRegistry {
         }
     }
 
+    /// Shell started for interactive agent sessions.
+    fn interactive_shell() -> String {
+        #[cfg(target_os = "windows")]
+        {
+            std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string())
+        }
+    }
+
     fn append_output(output: &mut SessionOutput, data: &str, max_bytes: usize) {
         output.pending.push_str(data);
         output.aggregated.push_str(data);
@@ -680,6 +692,18 @@ impl ProcessManager for ProcessRegistry {
     fn log(&self, session_id: &str, offset: usize, limit: usize) -> Result<ProcessLog> {
         Self::get_log(self, session_id, offset, limit)
     }
+
+    fn spawn_shell(&self, cwd: Option<String>) -> Result<String> {
+        let options = ProcessShellOptions {
+            spawn: ProcessSpawnOptions {
+                cwd,
+                source: ProcessSessionSource::Agent,
+                ..Default::default()
+            },
+            startup_command: None,
+        };
+        Self::spawn_shell(self, &Self::interactive_shell(), options)
+    }
 }
 
 fn current_timestamp_ms() -> i64 {
@@ -2,10 +2,11 @@
 //!
 //! Checks whether system requirements are met for a skill:
 //! - Required binaries
-//! - Environment variables
+//! - Environment variables (or stored secrets of the same name)
 //! - OS compatibility
 //! - RestFlow version
 
+use std::collections::HashSet;
 use std::process::Command;
 
 use crate::models::{BinaryRequirement, GatingCheckResult, GatingRequirements, SkillVersion};
@@ -14,12 +15,27 @@ use crate::models::{BinaryRequirement, GatingCheckResult, GatingRequirements, Sk
 pub struct GatingChecker {
     /// Current RestFlow version
     restflow_version: SkillVersion,
+    /// Names of stored secrets that satisfy env var requirements
+    available_secrets: HashSet<String>,
 }
 
 impl GatingChecker {
     /// Create a new gating checker
     pub fn new(restflow_version: SkillVersion) -> Self {
-        Self { restflow_version }
+        Self {
+            restflow_version,
+            available_secrets: HashSet::new(),
+        }
+    }
+
+    /// Treat the given stored secrets as satisfying env var requirements
+    pub fn with_available_secrets<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.available_secrets = names.into_iter().map(Into::into).collect();
+        self
     }
 
     /// Create a gating checker with default RestFlow version
@@ -62,7 +78,7 @@ impl GatingChecker {
 
         // Check environment variables
         for env_var in &requirements.env_vars {
-            if env_var.required && std::env::var(&env_var.name).is_err() {
+            if env_var.required && !self.check_env_var(&env_var.name) {
                 result.missing_env_vars.push(env_var.name.clone());
                 result.passed = false;
             }
@@ -224,9 +240,9 @@ impl GatingChecker {
         self.check_binary(&req)
     }
 
-    /// Check a single environment variable
+    /// Check a single environment variable, accepting a stored secret of the same name
     pub fn check_env_var(&self, name: &str) -> bool {
-        std::env::var(name).is_ok()
+        std::env::var(name).is_ok() || self.available_secrets.contains(name)
     }
 }
 
@@ -288,6 +304,26 @@ mod tests {
         assert!(!checker.check_env_var("THIS_ENV_VAR_SHOULD_NOT_EXIST_12345"));
     }
 
+    #[test]
+    fn test_env_var_satisfied_by_stored_secret() {
+        let requirements = GatingRequirements {
+            env_vars: vec![EnvVarRequirement {
+                name: "RESTFLOW_GATING_TEST_TOKEN".to_string(),
+                required: true,
+                description: None,
+            }],
+            ..Default::default()
+        };
+
+        let result = GatingChecker::default_version().check(&requirements);
+        assert_eq!(result.missing_env_vars, vec!["RESTFLOW_GATING_TEST_TOKEN"]);
+
+        let result = GatingChecker::default_version()
+            .with_available_secrets(["RESTFLOW_GATING_TEST_TOKEN"])
+            .check(&requirements);
+        assert!(result.passed, "Check failed: {}", result.summary);
+    }
+
     #[test]
     fn test_full_check() {
         let checker = GatingChecker::default_version();
@@ -264,6 +264,7 @@ impl GitHubProvider {
             dependencies: vec![],
             permissions: SkillPermissions::default(),
             gating: GatingRequirements::default(),
+            signature: None,
         })
     }
 
@@ -0,0 +1,211 @@
+//! Dependency-aware install planning.
+//!
+//! Walks a skill's dependency tree against a provider, reusing installed
+//! dependencies that already satisfy their version requirements and picking
+//! the newest matching version for everything else.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::models::{SkillDependency, SkillManifest, SkillVersion};
+
+use super::provider::{SkillProvider, SkillProviderError};
+use super::resolver::{DependencyError, DependencyResolver, InstallAction, InstallPlan};
+
+/// An install plan together with the manifests it refers to
+#[derive(Debug, Clone)]
+pub struct ResolvedInstall {
+    /// Ordered plan (dependencies first)
+    pub plan: InstallPlan,
+    /// Manifests of every skill in the plan, keyed by skill ID
+    pub manifests: HashMap<String, SkillManifest>,
+}
+
+impl ResolvedInstall {
+    /// Manifests that need to be installed or updated, dependencies first
+    pub fn pending(&self) -> Vec<&SkillManifest> {
+        self.plan
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                InstallAction::Install { skill_id, .. }
+                | InstallAction::Update { skill_id, .. } => self.manifests.get(skill_id),
+                InstallAction::Skip { .. } => None,
+            })
+            .collect()
+    }
+}
+
+/// Resolve the dependencies of `root` using `provider`.
+///
+/// `installed` maps skill IDs to their installed versions. The root skill is
+/// always part of the plan; pass it in `installed` only to get an `Update`
+/// action instead of `Install`.
+pub async fn resolve_install(
+    provider: &dyn SkillProvider,
+    root: SkillManifest,
+    installed: &HashMap<String, SkillVersion>,
+) -> Result<ResolvedInstall, DependencyError> {
+    let root_id = root.id.clone();
+    let mut resolver = DependencyResolver::new();
+    resolver.set_installed(installed.clone());
+
+    let mut seen = HashSet::from([root_id.clone()]);
+    let mut queue = VecDeque::from([root]);
+    while let Some(manifest) = queue.pop_front() {
+        for dep in manifest.dependencies.iter().filter(|dep| !dep.optional) {
+            if !seen.insert(dep.skill_id.clone()) {
+                continue;
+            }
+            let dep_manifest = match installed.get(&dep.skill_id) {
+                Some(version) if version.satisfies(&dep.version) => SkillManifest {
+                    id: dep.skill_id.clone(),
+                    name: dep.skill_id.clone(),
+                    version: version.clone(),
+                    ..Default::default()
+                },
+                _ => fetch_dependency(provider, dep).await?,
+            };
+            queue.push_back(dep_manifest);
+        }
+        resolver.add_skill(manifest)?;
+    }
+
+    let plan = resolver.resolve(&[root_id])?;
+    Ok(ResolvedInstall {
+        plan,
+        manifests: resolver.manifests().clone(),
+    })
+}
+
+async fn fetch_dependency(
+    provider: &dyn SkillProvider,
+    dep: &SkillDependency,
+) -> Result<SkillManifest, DependencyError> {
+    let to_dependency_error = |e: SkillProviderError| match e {
+        SkillProviderError::NotFound(_) => DependencyError::SkillNotFound(dep.skill_id.clone()),
+        SkillProviderError::VersionNotFound(_) => DependencyError::VersionNotFound {
+            skill: dep.skill_id.clone(),
+            version: dep.version.to_string(),
+        },
+        other => DependencyError::Other(format!("{}: {}", dep.skill_id, other)),
+    };
+
+    let version = provider
+        .resolve_version(&dep.skill_id, &dep.version)
+        .await
+        .map_err(to_dependency_error)?;
+    provider
+        .get_manifest_version(&dep.skill_id, &version)
+        .await
+        .map_err(to_dependency_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::VersionRequirement;
+    use crate::registry::BuiltinSkillProvider;
+
+    fn manifest(
+        id: &str,
+        version: SkillVersion,
+        deps: Vec<(&str, VersionRequirement)>,
+    ) -> SkillManifest {
+        SkillManifest {
+            id: id.to_string(),
+            name: id.to_string(),
+            version,
+            dependencies: deps
+                .into_iter()
+                .map(|(skill_id, version)| SkillDependency {
+                    skill_id: skill_id.to_string(),
+                    version,
+                    optional: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn provider() -> BuiltinSkillProvider {
+        let mut provider = BuiltinSkillProvider::new();
+        provider.add_skill(
+            manifest(
+                "git-helper",
+                SkillVersion::new(1, 3, 0),
+                vec![("shell-basics", VersionRequirement::Any)],
+            ),
+            "# Git".to_string(),
+        );
+        provider.add_skill(
+            manifest("shell-basics", SkillVersion::new(2, 0, 0), vec![]),
+            "# Shell".to_string(),
+        );
+        provider
+    }
+
+    #[tokio::test]
+    async fn test_resolves_transitive_dependencies_in_order() {
+        let root = manifest(
+            "deploy",
+            SkillVersion::new(1, 0, 0),
+            vec![(
+                "git-helper",
+                VersionRequirement::Caret(SkillVersion::new(1, 0, 0)),
+            )],
+        );
+
+        let resolved = resolve_install(&provider(), root, &HashMap::new())
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = resolved
+            .pending()
+            .into_iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["shell-basics", "git-helper", "deploy"]);
+    }
+
+    #[tokio::test]
+    async fn test_reuses_installed_dependency() {
+        let root = manifest(
+            "deploy",
+            SkillVersion::new(1, 0, 0),
+            vec![(
+                "git-helper",
+                VersionRequirement::Caret(SkillVersion::new(1, 0, 0)),
+            )],
+        );
+        let installed = HashMap::from([("git-helper".to_string(), SkillVersion::new(1, 1, 0))]);
+
+        let resolved = resolve_install(&provider(), root, &installed)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = resolved
+            .pending()
+            .into_iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["deploy"]);
+        assert_eq!(resolved.plan.unchanged, vec!["git-helper"]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_dependency_version() {
+        let root = manifest(
+            "deploy",
+            SkillVersion::new(1, 0, 0),
+            vec![(
+                "shell-basics",
+                VersionRequirement::Caret(SkillVersion::new(3, 0, 0)),
+            )],
+        );
+
+        let err = resolve_install(&provider(), root, &HashMap::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DependencyError::VersionNotFound { .. }));
+    }
+}
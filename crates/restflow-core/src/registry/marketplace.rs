@@ -14,7 +14,7 @@ use super::{
 };
 use crate::models::{
     BinaryRequirement, EnvVarRequirement, GatingRequirements, OsType, SkillAuthor, SkillManifest,
-    SkillPermission, SkillPermissions, SkillSource, SkillVersion,
+    SkillPermission, SkillPermissions, SkillSignature, SkillSource, SkillVersion,
 };
 use restflow_traits::DEFAULT_MARKETPLACE_CACHE_TTL_SECS;
 
@@ -57,6 +57,8 @@ struct MarketplaceSkill {
     required_tools: Vec<String>,
     #[serde(default)]
     gating: MarketplaceGating,
+    #[serde(default)]
+    signature: Option<SkillSignature>,
 }
 
 /// Gating requirements from marketplace
@@ -239,6 +241,7 @@ impl MarketplaceProvider {
             dependencies,
             permissions,
             gating,
+            signature: skill.signature,
         })
     }
 
@@ -255,6 +258,42 @@ impl MarketplaceProvider {
             .await
             .map_err(|e| SkillProviderError::Network(e.to_string()))
     }
+
+    /// Fetch the manifest published for a specific version of a skill
+    pub async fn get_manifest_version(
+        &self,
+        id: &str,
+        version: &SkillVersion,
+    ) -> Result<SkillManifest, SkillProviderError> {
+        let url = format!(
+            "{}/skills/{}/versions/{}",
+            self.base_url,
+            urlencoding::encode(id),
+            version
+        );
+        let response = self.request(&url).await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(SkillProviderError::VersionNotFound(format!(
+                "{}@{}",
+                id, version
+            )));
+        }
+
+        if !response.status().is_success() {
+            return Err(SkillProviderError::Network(format!(
+                "Marketplace API returned status {}",
+                response.status()
+            )));
+        }
+
+        let skill: MarketplaceSkill = response
+            .json()
+            .await
+            .map_err(|e| SkillProviderError::Parse(e.to_string()))?;
+
+        Self::to_manifest(skill)
+    }
 }
 
 impl Default for MarketplaceProvider {
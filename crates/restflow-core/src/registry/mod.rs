@@ -4,18 +4,22 @@
 //! - Discovering skills from multiple sources (local, builtin, marketplace, GitHub)
 //! - Installing and updating skills with dependency resolution
 //! - Checking gating requirements before installation
+//! - Verifying publisher signatures of marketplace skills
 
 mod cache;
 mod gating;
 mod github;
+mod installer;
 mod marketplace;
 mod provider;
 #[allow(clippy::module_inception)]
 mod registry;
 mod resolver;
+mod signing;
 
 pub use gating::GatingChecker;
 pub use github::GitHubProvider;
+pub use installer::{ResolvedInstall, resolve_install};
 pub use marketplace::{DEFAULT_MARKETPLACE_URL, MarketplaceProvider};
 pub use provider::{
     BuiltinSkillProvider, LocalSkillProvider, SkillProvider, SkillProviderError, SkillSearchQuery,
@@ -23,3 +27,4 @@ pub use provider::{
 };
 pub use registry::{SkillRegistry, SkillRegistryConfig};
 pub use resolver::{DependencyError, DependencyResolver, InstallAction, InstallPlan};
+pub use signing::{SignatureStatus, check_skill_signature, verify_skill_signature};
@@ -31,6 +31,9 @@ pub enum SkillProviderError {
     #[error("Version not found: {0}")]
     VersionNotFound(String),
 
+    #[error("Signature verification failed: {0}")]
+    Signature(String),
+
     #[error("Provider error: {0}")]
     Other(String),
 }
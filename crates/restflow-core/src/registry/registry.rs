@@ -4,6 +4,7 @@
 //! - Unified search across all sources
 //! - Installation with dependency resolution
 //! - Gating requirement checks
+//! - Publisher signature checks
 //! - Update management
 
 use std::collections::HashMap;
@@ -21,6 +22,7 @@ use super::provider::{
     SkillSearchResult,
 };
 use super::resolver::{DependencyError, DependencyResolver, InstallPlan};
+use super::signing::check_skill_signature;
 
 /// Registry configuration
 #[derive(Debug, Clone)]
@@ -33,6 +35,10 @@ pub struct SkillRegistryConfig {
     pub enable_marketplace: bool,
     /// Marketplace URL
     pub marketplace_url: Option<String>,
+    /// Base64 ed25519 public keys trusted to sign skills
+    pub trusted_skill_keys: Vec<String>,
+    /// Refuse to install skills without a trusted signature
+    pub require_signed_skills: bool,
 }
 
 impl Default for SkillRegistryConfig {
@@ -45,6 +51,8 @@ impl Default for SkillRegistryConfig {
             cache_dir: data_dir.join("cache").join("skills"),
             enable_marketplace: true,
             marketplace_url: None,
+            trusted_skill_keys: Vec::new(),
+            require_signed_skills: false,
         }
     }
 }
@@ -227,6 +235,12 @@ impl SkillRegistry {
     pub async fn install(&self, skill_id: &str) -> Result<InstalledSkill, SkillProviderError> {
         let manifest = self.get_manifest(skill_id).await?;
         let content = self.get_content(skill_id, &manifest.version).await?;
+        check_skill_signature(
+            &manifest,
+            &content,
+            &self.config.trusted_skill_keys,
+            self.config.require_signed_skills,
+        )?;
 
         // Check gating requirements
         let gating_result = self.gating_checker.check(&manifest.gating);
@@ -459,6 +473,8 @@ mod tests {
             cache_dir: temp_dir.path().join("cache"),
             enable_marketplace: false,
             marketplace_url: None,
+            trusted_skill_keys: Vec::new(),
+            require_signed_skills: false,
         };
 
         let registry = SkillRegistry::new(config);
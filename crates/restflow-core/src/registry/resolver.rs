@@ -10,7 +10,7 @@ use thiserror::Error;
 
 #[cfg(test)]
 use crate::models::SkillDependency;
-use crate::models::{SkillManifest, SkillVersion};
+use crate::models::{SkillManifest, SkillVersion, VersionRequirement};
 
 /// Errors that can occur during dependency resolution
 #[derive(Debug, Error)]
@@ -93,6 +93,8 @@ pub struct DependencyResolver {
     manifests: HashMap<String, SkillManifest>,
     /// Currently installed skills
     installed: HashMap<String, SkillVersion>,
+    /// Version requirements declared by dependents, keyed by dependency ID
+    requirements: HashMap<String, Vec<VersionRequirement>>,
 }
 
 impl DependencyResolver {
@@ -103,6 +105,7 @@ impl DependencyResolver {
             node_map: HashMap::new(),
             manifests: HashMap::new(),
             installed: HashMap::new(),
+            requirements: HashMap::new(),
         }
     }
 
@@ -141,6 +144,10 @@ impl DependencyResolver {
 
             // Add edge from skill to dependency (skill depends on dep)
             self.graph.add_edge(node, dep_node, ());
+            self.requirements
+                .entry(dep.skill_id.clone())
+                .or_default()
+                .push(dep.version.clone());
         }
 
         self.manifests.insert(skill_id, manifest);
@@ -199,9 +206,29 @@ impl DependencyResolver {
                 }
             };
 
+            let requirements = self
+                .requirements
+                .get(&skill_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            if let Some(requirement) = requirements
+                .iter()
+                .find(|requirement| !manifest.version.satisfies(requirement))
+            {
+                return Err(DependencyError::VersionNotFound {
+                    skill: skill_id,
+                    version: requirement.to_string(),
+                });
+            }
+
             // Check if already installed
             if let Some(installed_version) = self.installed.get(&skill_id) {
-                if installed_version == &manifest.version {
+                // Dependencies that already satisfy every requirement are left alone
+                let satisfied = !root_skills.contains(&skill_id)
+                    && requirements
+                        .iter()
+                        .all(|requirement| installed_version.satisfies(requirement));
+                if satisfied || installed_version == &manifest.version {
                     // Already at correct version
                     plan.actions.push(InstallAction::Skip {
                         skill_id: skill_id.clone(),
@@ -279,6 +306,7 @@ impl DependencyResolver {
         self.graph.clear();
         self.node_map.clear();
         self.manifests.clear();
+        self.requirements.clear();
     }
 }
 
@@ -362,6 +390,46 @@ mod tests {
         assert_eq!(plan.unchanged[0], "skill-b");
     }
 
+    #[test]
+    fn test_installed_dependency_satisfying_requirement_is_kept() {
+        let mut resolver = DependencyResolver::new();
+
+        let mut installed = HashMap::new();
+        installed.insert("skill-b".to_string(), SkillVersion::new(1, 0, 0));
+        resolver.set_installed(installed);
+
+        let mut root = create_manifest("skill-a", vec!["skill-b"]);
+        root.dependencies[0].version = VersionRequirement::Caret(SkillVersion::new(1, 0, 0));
+        resolver.add_skill(root).unwrap();
+        let mut newer = create_manifest("skill-b", vec![]);
+        newer.version = SkillVersion::new(1, 4, 0);
+        resolver.add_skill(newer).unwrap();
+
+        let plan = resolver.resolve(&["skill-a".to_string()]).unwrap();
+        assert_eq!(plan.unchanged, vec!["skill-b"]);
+        assert_eq!(plan.to_install, vec!["skill-a"]);
+    }
+
+    #[test]
+    fn test_unsatisfied_version_requirement() {
+        let mut resolver = DependencyResolver::new();
+
+        let mut root = create_manifest("skill-a", vec!["skill-b"]);
+        root.dependencies[0].version =
+            VersionRequirement::GreaterOrEqual(SkillVersion::new(2, 0, 0));
+        resolver.add_skill(root).unwrap();
+        resolver
+            .add_skill(create_manifest("skill-b", vec![]))
+            .unwrap();
+
+        let result = resolver.resolve(&["skill-a".to_string()]);
+        assert!(matches!(
+            result,
+            Err(DependencyError::VersionNotFound { ref skill, ref version })
+                if skill == "skill-b" && version == ">=2.0.0"
+        ));
+    }
+
     #[test]
     fn test_circular_dependency() {
         let mut resolver = DependencyResolver::new();
@@ -0,0 +1,154 @@
+//! Ed25519 signature verification for marketplace skills.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use ring::signature::{ED25519, UnparsedPublicKey};
+use serde::Serialize;
+
+use super::SkillProviderError;
+use crate::models::{SkillManifest, SkillSignature};
+
+/// Outcome of checking a skill's publisher signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Valid signature from a trusted key
+    Verified,
+    /// Valid signature from a key that is not in the trusted set
+    Untrusted,
+    /// The manifest carries no signature
+    Unsigned,
+}
+
+/// Verify the manifest signature against the downloaded content.
+///
+/// A signature that does not match the content is always an error; whether
+/// untrusted or unsigned skills may be installed is up to the caller.
+pub fn verify_skill_signature(
+    manifest: &SkillManifest,
+    content: &str,
+    trusted_keys: &[String],
+) -> Result<SignatureStatus, SkillProviderError> {
+    let Some(signature) = manifest.signature.as_ref() else {
+        return Ok(SignatureStatus::Unsigned);
+    };
+
+    let public_key = decode(&signature.public_key, "public key")?;
+    let signature_bytes = decode(&signature.signature, "signature")?;
+    let payload = SkillSignature::payload(&manifest.id, &manifest.version, content);
+    UnparsedPublicKey::new(&ED25519, &public_key)
+        .verify(&payload, &signature_bytes)
+        .map_err(|_| {
+            SkillProviderError::Signature(format!(
+                "{}@{} does not match its signature",
+                manifest.id, manifest.version
+            ))
+        })?;
+
+    let trusted = trusted_keys
+        .iter()
+        .filter_map(|key| STANDARD.decode(key.trim()).ok())
+        .any(|key| key == public_key);
+    Ok(if trusted {
+        SignatureStatus::Verified
+    } else {
+        SignatureStatus::Untrusted
+    })
+}
+
+/// Verify the signature and, when `require_signed` is set, reject skills that
+/// are not signed by a trusted key.
+pub fn check_skill_signature(
+    manifest: &SkillManifest,
+    content: &str,
+    trusted_keys: &[String],
+    require_signed: bool,
+) -> Result<SignatureStatus, SkillProviderError> {
+    let status = verify_skill_signature(manifest, content, trusted_keys)?;
+    if require_signed && status != SignatureStatus::Verified {
+        return Err(SkillProviderError::Signature(format!(
+            "{}@{} is not signed by a trusted key",
+            manifest.id, manifest.version
+        )));
+    }
+    Ok(status)
+}
+
+fn decode(value: &str, what: &str) -> Result<Vec<u8>, SkillProviderError> {
+    STANDARD
+        .decode(value.trim())
+        .map_err(|e| SkillProviderError::Signature(format!("Invalid {what} encoding: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SkillVersion;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn signed_manifest(content: &str) -> (SkillManifest, String) {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let mut manifest = SkillManifest {
+            id: "deploy-helper".to_string(),
+            version: SkillVersion::new(1, 2, 0),
+            ..Default::default()
+        };
+        let payload = SkillSignature::payload(&manifest.id, &manifest.version, content);
+        let public_key = STANDARD.encode(key_pair.public_key().as_ref());
+        manifest.signature = Some(SkillSignature {
+            public_key: public_key.clone(),
+            signature: STANDARD.encode(key_pair.sign(&payload).as_ref()),
+        });
+        (manifest, public_key)
+    }
+
+    #[test]
+    fn test_verify_trusted_and_untrusted_signatures() {
+        let (manifest, public_key) = signed_manifest("# Deploy");
+
+        assert_eq!(
+            verify_skill_signature(&manifest, "# Deploy", &[public_key]).unwrap(),
+            SignatureStatus::Verified
+        );
+        assert_eq!(
+            verify_skill_signature(&manifest, "# Deploy", &[]).unwrap(),
+            SignatureStatus::Untrusted
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_content() {
+        let (manifest, public_key) = signed_manifest("# Deploy");
+
+        let err =
+            verify_skill_signature(&manifest, "# Deploy; rm -rf /", &[public_key]).unwrap_err();
+        assert!(matches!(err, SkillProviderError::Signature(_)));
+    }
+
+    #[test]
+    fn test_verify_unsigned_manifest() {
+        let manifest = SkillManifest::default();
+        assert_eq!(
+            verify_skill_signature(&manifest, "", &[]).unwrap(),
+            SignatureStatus::Unsigned
+        );
+    }
+
+    #[test]
+    fn test_check_requires_trusted_signature_when_enforced() {
+        let (manifest, public_key) = signed_manifest("# Deploy");
+
+        assert!(check_skill_signature(&manifest, "# Deploy", &[], true).is_err());
+        assert!(check_skill_signature(&SkillManifest::default(), "", &[], true).is_err());
+        assert_eq!(
+            check_skill_signature(&manifest, "# Deploy", &[], false).unwrap(),
+            SignatureStatus::Untrusted
+        );
+        assert_eq!(
+            check_skill_signature(&manifest, "# Deploy", &[public_key], true).unwrap(),
+            SignatureStatus::Verified
+        );
+    }
+}
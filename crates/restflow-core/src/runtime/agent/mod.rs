@@ -21,7 +21,8 @@ pub use tools::{
     BashConfig, BashTool, EmailTool, FileConfig, FileTool, HttpTool, ListSubagentsTool,
     SpawnSubagentTool, SpawnTool, TelegramTool, Tool, ToolRegistry, ToolRegistryBuilder,
     ToolResult, UseSkillTool, WaitSubagentsTool, default_registry, effective_main_agent_tool_names,
-    main_agent_default_tool_names, registry_from_allowlist, secret_resolver_from_storage,
+    main_agent_default_tool_names, registry_from_allowlist, secret_resolver_for_task,
+    secret_resolver_from_storage,
 };
 
 /// Build the agent system prompt from agent configuration.
@@ -1,7 +1,10 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
-use crate::services::adapters::{AgentStoreAdapter, KvStoreAdapter, TaskStoreAdapter};
+use crate::services::adapters::{
+    AgentStoreAdapter, ApiConnectorStoreAdapter, KvStoreAdapter, TaskStoreAdapter,
+};
 use crate::services::operation_assessment::OperationAssessorAdapter;
 use crate::services::session::SessionService;
 use crate::storage::Storage;
@@ -9,15 +12,21 @@ use crate::storage::{
     AgentStorage, BackgroundAgentStorage, DeliverableStorage, KvStoreStorage, SecretStorage,
     SkillStorage,
 };
+use restflow_storage::AgentSettings;
 use restflow_tools::{
-    BashConfig, EmailTool, FileConfig, HttpTool, ListSubagentsTool, PythonTool, RunPythonTool,
-    SpawnSubagentTool, ToolRegistryBuilder, WaitSubagentsTool,
+    ApiConnectorTool, ArtifactRetentionPolicy, BashConfig, CalendarTool, ContainerConfig,
+    ContainerEngine, EmailTool, FanoutTool, FileConfig, GitHubTool, GitTool, HttpTool,
+    ListSubagentsTool, NavigationPolicy, NetworkEndpoint, NetworkPolicy, ProcessPythonBackend,
+    PythonExecutionBackend, PythonTool, ResourceLimits, RunPythonTool, SandboxPolicy,
+    SpawnSubagentTool, SpreadsheetTool, SqlTool, ToolRegistryBuilder, WaitSubagentsTool,
 };
 use restflow_traits::AgentOperationAssessor;
 use restflow_traits::SubagentManager;
 use restflow_traits::registry::ToolRegistry;
 use restflow_traits::security::SecurityGate;
 use restflow_traits::store::{AgentStore, KvStore, TaskStore};
+use restflow_traits::tool::SecretResolver;
+use tracing::warn;
 
 pub(crate) const KNOWN_TOOL_ALIASES: [(&str, &str); 8] = [
     ("http", "http_request"),
@@ -78,6 +87,120 @@ pub(crate) fn register_file_execution_tool(
     builder
 }
 
+pub(crate) fn register_git_tool(
+    mut builder: ToolRegistryBuilder,
+    base_dir: Option<PathBuf>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: &str,
+    task_id: &str,
+) -> ToolRegistryBuilder {
+    if let Some(gate) = security_gate {
+        let mut tool = GitTool::new().require_base_dir();
+        if let Some(base_dir) = base_dir {
+            tool = tool.with_base_dir(base_dir);
+        }
+        builder
+            .registry
+            .register(tool.with_security(gate, agent_id, task_id));
+    } else {
+        builder = builder.with_git_and_base_dir(base_dir);
+    }
+    builder
+}
+
+pub(crate) fn register_spreadsheet_tool(
+    mut builder: ToolRegistryBuilder,
+    base_dir: Option<PathBuf>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: &str,
+    task_id: &str,
+) -> ToolRegistryBuilder {
+    if let Some(gate) = security_gate {
+        let mut tool = SpreadsheetTool::new().require_base_dir();
+        if let Some(base_dir) = base_dir {
+            tool = tool.with_base_dir(base_dir);
+        }
+        builder
+            .registry
+            .register(tool.with_security(gate, agent_id, task_id));
+    } else {
+        builder = builder.with_spreadsheet_and_base_dir(base_dir);
+    }
+    builder
+}
+
+pub(crate) fn register_github_tool(
+    mut builder: ToolRegistryBuilder,
+    secret_resolver: Option<SecretResolver>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: &str,
+    task_id: &str,
+) -> anyhow::Result<ToolRegistryBuilder> {
+    if let Some(gate) = security_gate {
+        let mut tool = GitHubTool::new()?;
+        if let Some(resolver) = secret_resolver {
+            tool = tool.with_secret_resolver(resolver);
+        }
+        builder
+            .registry
+            .register(tool.with_security(gate, agent_id, task_id));
+    } else if let Some(resolver) = secret_resolver {
+        builder = builder.with_github_with_resolver(resolver)?;
+    } else {
+        builder = builder.with_github()?;
+    }
+    Ok(builder)
+}
+
+pub(crate) fn register_calendar_tool(
+    mut builder: ToolRegistryBuilder,
+    secret_resolver: Option<SecretResolver>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: &str,
+    task_id: &str,
+) -> anyhow::Result<ToolRegistryBuilder> {
+    if let Some(gate) = security_gate {
+        let mut tool = CalendarTool::new()?;
+        if let Some(resolver) = secret_resolver {
+            tool = tool.with_secret_resolver(resolver);
+        }
+        builder
+            .registry
+            .register(tool.with_security(gate, agent_id, task_id));
+    } else if let Some(resolver) = secret_resolver {
+        builder = builder.with_calendar_with_resolver(resolver)?;
+    } else {
+        builder = builder.with_calendar()?;
+    }
+    Ok(builder)
+}
+
+pub(crate) fn register_sql_tool(
+    mut builder: ToolRegistryBuilder,
+    secret_resolver: Option<SecretResolver>,
+    allow_write: bool,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: &str,
+    task_id: &str,
+) -> ToolRegistryBuilder {
+    match (secret_resolver, security_gate) {
+        (Some(resolver), None) if !allow_write => {
+            builder = builder.with_sql_with_resolver(resolver);
+        }
+        (secret_resolver, security_gate) => {
+            let mut tool = SqlTool::new().with_write(allow_write);
+            if let Some(resolver) = secret_resolver {
+                tool = tool.with_secret_resolver(resolver);
+            }
+            if let Some(gate) = security_gate {
+                tool = tool.with_security(gate, agent_id, task_id);
+            }
+            builder.registry.register(tool);
+        }
+    }
+    builder
+}
+
 pub(crate) fn register_http_execution_tool(
     mut builder: ToolRegistryBuilder,
     security_gate: Option<Arc<dyn SecurityGate>>,
@@ -110,25 +233,136 @@ pub(crate) fn register_send_email_execution_tool(
     builder
 }
 
+/// Register one tool per operation of the stored API connector `connector`,
+/// or of every stored connector when `connector` is `None`.
+pub(crate) fn register_api_connector_tools(
+    mut builder: ToolRegistryBuilder,
+    storage: &Storage,
+    connector: Option<&str>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: &str,
+    task_id: &str,
+) -> ToolRegistryBuilder {
+    let store = Arc::new(ApiConnectorStoreAdapter::new(
+        storage.api_connectors.clone(),
+        storage.secrets.clone(),
+    ));
+    let tools = match ApiConnectorTool::from_store(store, connector) {
+        Ok(tools) => tools,
+        Err(error) => {
+            warn!(connector = ?connector, error = %error, "Failed to load API connector tools");
+            return builder;
+        }
+    };
+    for tool in tools {
+        match security_gate.clone() {
+            Some(gate) => builder
+                .registry
+                .register(tool.with_security(gate, agent_id, task_id)),
+            None => builder.registry.register(tool),
+        }
+    }
+    builder
+}
+
 pub(crate) fn register_python_execution_tools(
     mut builder: ToolRegistryBuilder,
+    backend: Arc<dyn PythonExecutionBackend>,
     security_gate: Option<Arc<dyn SecurityGate>>,
     agent_id: &str,
     task_id: &str,
 ) -> ToolRegistryBuilder {
     if let Some(gate) = security_gate {
-        builder
-            .registry
-            .register(RunPythonTool::new().with_security(gate.clone(), agent_id, task_id));
-        builder
-            .registry
-            .register(PythonTool::new().with_security(gate, agent_id, task_id));
+        builder.registry.register(
+            RunPythonTool::new()
+                .with_backend(backend.clone())
+                .with_security(gate.clone(), agent_id, task_id),
+        );
+        builder.registry.register(
+            PythonTool::new()
+                .with_backend(backend)
+                .with_security(gate, agent_id, task_id),
+        );
     } else {
-        builder = builder.with_python();
+        builder = builder.with_python_backend(backend);
     }
     builder
 }
 
+/// Python execution backend for the python tools from the `[agent]` settings.
+pub(crate) fn python_execution_backend(agent: &AgentSettings) -> Arc<dyn PythonExecutionBackend> {
+    let mut backend =
+        ProcessPythonBackend::monty().with_resource_limits(subprocess_resource_limits(agent));
+    if let Some(container) = subprocess_container(agent) {
+        backend = backend.with_container(container);
+    }
+    if let Some(network) = subprocess_network_policy(agent) {
+        backend = backend.with_sandbox_policy(SandboxPolicy::network_only(network));
+    }
+    Arc::new(backend)
+}
+
+/// Container that bash and python tool processes run in, when the `[agent]`
+/// settings select a container runtime.
+pub(crate) fn subprocess_container(agent: &AgentSettings) -> Option<ContainerConfig> {
+    let engine = ContainerEngine::from_name(agent.container_runtime.as_deref()?)?;
+    Some(
+        ContainerConfig::new(engine, agent.container_image.clone())
+            .with_network(agent.container_network),
+    )
+}
+
+/// Network policy for bash and python tool processes on the host from the
+/// `[agent]` settings; `None` leaves their network unrestricted.
+pub(crate) fn subprocess_network_policy(agent: &AgentSettings) -> Option<NetworkPolicy> {
+    let entries = agent.subprocess_network_allow.as_ref()?;
+    if entries.is_empty() {
+        return Some(NetworkPolicy::Deny);
+    }
+    match entries
+        .iter()
+        .map(|entry| entry.parse::<NetworkEndpoint>())
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(endpoints) => Some(NetworkPolicy::AllowList { endpoints }),
+        Err(err) => {
+            warn!(error = %err, "Invalid agent.subprocess_network_allow, denying network access");
+            Some(NetworkPolicy::Deny)
+        }
+    }
+}
+
+/// Process limits for bash and python tool processes from the `[agent]` settings.
+pub(crate) fn subprocess_resource_limits(agent: &AgentSettings) -> ResourceLimits {
+    ResourceLimits {
+        memory_bytes: (agent.subprocess_memory_limit_mb > 0)
+            .then(|| agent.subprocess_memory_limit_mb.saturating_mul(1024 * 1024)),
+        cpu_weight: (agent.subprocess_cpu_weight > 0).then_some(agent.subprocess_cpu_weight),
+        max_processes: (agent.subprocess_max_processes > 0)
+            .then_some(agent.subprocess_max_processes),
+        cpu_time_secs: (agent.subprocess_cpu_time_secs > 0)
+            .then_some(agent.subprocess_cpu_time_secs),
+    }
+}
+
+/// Navigation policy for the browser tool from the `[agent]` settings.
+pub(crate) fn browser_navigation_policy(agent: &AgentSettings) -> NavigationPolicy {
+    NavigationPolicy {
+        allowed_domains: agent.browser_allowed_domains.clone(),
+        denied_domains: agent.browser_denied_domains.clone(),
+        block_private_networks: agent.browser_block_private_networks,
+    }
+}
+
+/// Artifact retention quotas for the browser tool from the `[agent]` settings.
+pub(crate) fn browser_artifact_retention(agent: &AgentSettings) -> ArtifactRetentionPolicy {
+    ArtifactRetentionPolicy {
+        max_session_bytes: agent.browser_artifact_max_session_bytes,
+        max_total_bytes: agent.browser_artifact_max_total_bytes,
+        max_age_secs: agent.browser_artifact_max_age_secs,
+    }
+}
+
 pub(crate) fn populate_known_tools_from_registry(
     known_tools: &Arc<RwLock<HashSet<String>>>,
     registry: &ToolRegistry,
@@ -245,14 +479,17 @@ pub(crate) fn register_subagent_management_tools(
     assessor: Option<Arc<dyn AgentOperationAssessor>>,
 ) {
     let mut spawn_tool = SpawnSubagentTool::new(manager.clone());
+    let mut fanout_tool = FanoutTool::new(manager.clone());
     if let Some(kv_store) = kv_store {
         spawn_tool = spawn_tool.with_kv_store(kv_store);
     }
     if let Some(assessor) = assessor {
+        fanout_tool = fanout_tool.with_assessor(assessor.clone());
         spawn_tool = spawn_tool.with_assessor(assessor);
     }
 
     registry.register(spawn_tool);
+    registry.register(fanout_tool);
     registry.register(WaitSubagentsTool::new(manager.clone()));
     registry.register(ListSubagentsTool::new(manager));
 }
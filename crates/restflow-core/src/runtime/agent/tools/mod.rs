@@ -14,21 +14,27 @@ use tracing::{debug, warn};
 use self::assembly::{
     KNOWN_TOOL_ALIASES, build_agent_crud_components, build_kv_store, build_runtime_assessor,
     build_task_store_runtime_components, populate_known_tools_from_registry,
-    register_bash_execution_tool, register_file_execution_tool, register_http_execution_tool,
-    register_management_tools, register_python_execution_tools, register_send_email_execution_tool,
-    register_subagent_management_tools,
+    python_execution_backend, register_api_connector_tools, register_bash_execution_tool,
+    register_calendar_tool, register_file_execution_tool, register_git_tool, register_github_tool,
+    register_http_execution_tool, register_management_tools, register_python_execution_tools,
+    register_send_email_execution_tool, register_spreadsheet_tool, register_sql_tool,
+    register_subagent_management_tools, subprocess_container, subprocess_network_policy,
+    subprocess_resource_limits,
 };
 use crate::lsp::LspManager;
-use crate::memory::UnifiedSearchEngine;
+use crate::memory::{MemoryEmbedder, UnifiedSearchConfig, UnifiedSearchEngine};
+use crate::security::AuditedSecurityGate;
 use crate::services::adapters::*;
+use crate::services::browser_webhook::browser_service_for_settings;
 use crate::storage::Storage;
-use restflow_storage::{AgentSettings, ApiSettings};
+use restflow_storage::ApiSettings;
 use restflow_traits::SubagentManager;
 use restflow_traits::security::SecurityGate;
 use restflow_traits::skill::SkillProvider;
 use restflow_traits::store::{
-    DiagnosticsProvider, MANAGE_BACKGROUND_AGENTS_TOOL_NAME, MANAGE_TASKS_TOOL_NAME,
-    is_legacy_task_tool_name, is_task_management_tool_name,
+    API_CONNECTOR_TOOL_PREFIX, API_CONNECTORS_TOOL_GROUP, DiagnosticsProvider,
+    MANAGE_BACKGROUND_AGENTS_TOOL_NAME, MANAGE_TASKS_TOOL_NAME, is_legacy_task_tool_name,
+    is_task_management_tool_name,
 };
 
 // Re-export tool types from restflow-tools
@@ -50,6 +56,42 @@ pub fn secret_resolver_from_storage(storage: &Storage) -> SecretResolver {
     Arc::new(move |key| secrets.get_secret(key).ok().flatten())
 }
 
+/// Secret resolver for a background task run. Temporary secrets bound to the
+/// task's active run take precedence over stored secrets.
+pub fn secret_resolver_for_task(storage: &Storage, task_id: Option<&str>) -> SecretResolver {
+    let Some(task_id) = task_id else {
+        return secret_resolver_from_storage(storage);
+    };
+    let secrets = storage.secrets.clone();
+    let temporary = storage.background_agents.temporary_secrets().clone();
+    let task_id = task_id.to_string();
+    Arc::new(move |key| {
+        temporary
+            .resolve(&task_id, key)
+            .or_else(|| secrets.get_secret(key).ok().flatten())
+    })
+}
+
+/// Wrap `resolver` so every secret it hands to `tool` is recorded in the
+/// secret's usage history.
+pub fn tracked_secret_resolver(
+    resolver: SecretResolver,
+    storage: &Storage,
+    tool: &str,
+    agent_id: Option<&str>,
+) -> SecretResolver {
+    let secrets = storage.secrets.clone();
+    let tool = tool.to_string();
+    let agent_id = agent_id.map(str::to_string);
+    Arc::new(move |key| {
+        let value = resolver(key)?;
+        if let Err(error) = secrets.record_secret_usage(key, &tool, agent_id.as_deref()) {
+            warn!(secret = key, error = %error, "Failed to record secret usage");
+        }
+        Some(value)
+    })
+}
+
 fn wants_named_tool(tool_names: &[String], tool_name: &str) -> bool {
     tool_names.iter().any(|name| name == tool_name)
 }
@@ -66,11 +108,14 @@ pub fn main_agent_default_tool_names() -> Vec<String> {
         "slack",
         "run_python",
         "browser",
+        "use_plan",
         "transcribe",
+        "speak",
         "vision",
         "spawn_subagent",
         "wait_subagents",
         "list_subagents",
+        "fanout",
         "use_skill",
         "manage_tasks",
         "manage_agents",
@@ -89,6 +134,8 @@ pub fn main_agent_default_tool_names() -> Vec<String> {
         "manage_memory",
         "manage_auth_profiles",
         "save_deliverable",
+        "deliverable_builder",
+        "check_usage_budget",
         "edit",
         "multiedit",
         "patch",
@@ -98,6 +145,12 @@ pub fn main_agent_default_tool_names() -> Vec<String> {
         "jina_reader",
         "reply",
         "process",
+        "shell_session",
+        "git",
+        "github",
+        "calendar",
+        "sql",
+        "spreadsheet",
         "glob",
         "grep",
         "task_list",
@@ -170,6 +223,14 @@ pub fn registry_from_allowlist_with_security_gate(
         return Ok(ToolRegistry::new());
     }
 
+    let security_gate = match (security_gate, storage) {
+        (Some(gate), Some(storage)) => Some(AuditedSecurityGate::wrap(
+            gate,
+            storage.execution_traces.clone(),
+        )),
+        (gate, _) => gate,
+    };
+
     let wants_manage_agents = wants_named_tool(tool_names, "manage_agents");
     let wants_manage_tasks = wants_named_tool(tool_names, MANAGE_TASKS_TOOL_NAME);
     let wants_manage_tasks_alias = tool_names.iter().any(|name| is_legacy_task_tool_name(name));
@@ -177,8 +238,9 @@ pub fn registry_from_allowlist_with_security_gate(
     let wants_spawn_subagent = tool_names.iter().any(|name| name == "spawn_subagent");
     let wants_wait_subagents = tool_names.iter().any(|name| name == "wait_subagents");
     let wants_list_subagents = tool_names.iter().any(|name| name == "list_subagents");
+    let wants_fanout = tool_names.iter().any(|name| name == "fanout");
     let wants_guarded_assessor =
-        wants_manage_agents || wants_manage_task_tools || wants_spawn_subagent;
+        wants_manage_agents || wants_manage_task_tools || wants_spawn_subagent || wants_fanout;
     let wants_shared_kv_store =
         wants_manage_task_tools || wants_spawn_subagent || wants_named_tool(tool_names, "kv_store");
 
@@ -212,6 +274,8 @@ pub fn registry_from_allowlist_with_security_gate(
     let mut builder = ToolRegistryBuilder::new();
     let mut allow_file = false;
     let mut allow_file_write = false;
+    let mut allow_sql = false;
+    let mut allow_sql_write = false;
     let mut allowlisted_skill_ids: Vec<String> = Vec::new();
     let mut recorded_skill_ids: HashSet<String> = HashSet::new();
     let effective_config = storage.and_then(|value| {
@@ -221,6 +285,18 @@ pub fn registry_from_allowlist_with_security_gate(
             .ok()
     });
 
+    // `browser` and `use_plan` share one BrowserService so saved plans can run
+    // in sessions opened by the browser tool.
+    let shared_browser = if wants_named_tool(tool_names, "use_plan") {
+        let agent_settings = effective_config
+            .as_ref()
+            .map(|config| config.agent.clone())
+            .unwrap_or_default();
+        Some(Arc::new(browser_service_for_settings(&agent_settings)?))
+    } else {
+        None
+    };
+
     // Pre-create shared diagnostics provider when any of diagnostics/edit/multiedit
     // are in the allowlist, so they all share the same LspManager instance.
     let needs_diag = tool_names
@@ -228,7 +304,19 @@ pub fn registry_from_allowlist_with_security_gate(
         .any(|n| matches!(n.as_str(), "diagnostics" | "edit" | "multiedit"));
     let shared_diagnostics: Option<Arc<dyn DiagnosticsProvider>> =
         if needs_diag && let Some(root) = workspace_root {
-            Some(Arc::new(LspManager::new(root.to_path_buf())))
+            let browser = match shared_browser.clone() {
+                Some(service) => service,
+                None => {
+                    let agent_settings = effective_config
+                        .as_ref()
+                        .map(|config| config.agent.clone())
+                        .unwrap_or_default();
+                    Arc::new(browser_service_for_settings(&agent_settings)?)
+                }
+            };
+            Some(Arc::new(
+                LspManager::new(root.to_path_buf()).with_browser_service(browser),
+            ))
         } else {
             None
         };
@@ -244,11 +332,31 @@ pub fn registry_from_allowlist_with_security_gate(
         };
     }
 
+    // Each tool gets its own resolver so secret reads are attributed to it.
+    let tool_secret_resolver = |tool: &str| match (secret_resolver.clone(), storage) {
+        (Some(resolver), Some(storage)) => {
+            Some(tracked_secret_resolver(resolver, storage, tool, agent_id))
+        }
+        (resolver, _) => resolver,
+    };
+
     for raw_name in tool_names {
+        let secret_resolver = tool_secret_resolver(raw_name);
         match raw_name.as_str() {
             // --- Simple tools (no storage required) ---
             "bash" => {
-                let config = bash_config.clone().unwrap_or_default();
+                let config = bash_config.clone().unwrap_or_else(|| {
+                    let agent_settings = effective_config
+                        .as_ref()
+                        .map(|config| config.agent.clone())
+                        .unwrap_or_default();
+                    BashConfig {
+                        resource_limits: subprocess_resource_limits(&agent_settings),
+                        container: subprocess_container(&agent_settings),
+                        network: subprocess_network_policy(&agent_settings),
+                        ..BashConfig::default()
+                    }
+                });
                 builder = register_bash_execution_tool(
                     builder,
                     config,
@@ -290,19 +398,77 @@ pub fn registry_from_allowlist_with_security_gate(
                 builder = builder.with_slack()?;
             }
             "python" | "run_python" => {
+                let agent_settings = effective_config
+                    .as_ref()
+                    .map(|config| config.agent.clone())
+                    .unwrap_or_default();
                 builder = register_python_execution_tools(
                     builder,
+                    python_execution_backend(&agent_settings),
                     security_gate.clone(),
                     agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
                     DEFAULT_SECURITY_TASK_ID,
                 );
             }
             "browser" => {
-                let timeout_secs = effective_config
+                let agent_settings = effective_config
                     .as_ref()
-                    .map(|config| config.agent.browser_timeout_secs)
-                    .unwrap_or_else(|| AgentSettings::default().browser_timeout_secs);
-                builder = builder.with_browser_timeout(timeout_secs)?;
+                    .map(|config| config.agent.clone())
+                    .unwrap_or_default();
+                let service = match shared_browser.clone() {
+                    Some(service) => service,
+                    None => Arc::new(browser_service_for_settings(&agent_settings)?),
+                };
+                builder = match secret_resolver.clone() {
+                    Some(resolver) => builder.with_browser_vision_fallback(
+                        service,
+                        agent_settings.browser_timeout_secs,
+                        resolver,
+                    )?,
+                    None => {
+                        builder.with_browser_service(service, agent_settings.browser_timeout_secs)
+                    }
+                };
+            }
+            "use_plan" => {
+                with_storage!(storage, "use_plan", builder, |s| {
+                    let agent_settings = effective_config
+                        .as_ref()
+                        .map(|config| config.agent.clone())
+                        .unwrap_or_default();
+                    builder.with_browser_plans(
+                        Arc::new(BrowserPlanStoreAdapter::new(s.browser_plans.clone())),
+                        shared_browser
+                            .clone()
+                            .expect("shared browser service should exist for use_plan"),
+                        agent_settings.browser_timeout_secs,
+                    )
+                });
+            }
+            API_CONNECTORS_TOOL_GROUP => {
+                with_storage!(storage, API_CONNECTORS_TOOL_GROUP, builder, |s| {
+                    register_api_connector_tools(
+                        builder,
+                        s,
+                        None,
+                        security_gate.clone(),
+                        agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+                        DEFAULT_SECURITY_TASK_ID,
+                    )
+                });
+            }
+            name if name.starts_with(API_CONNECTOR_TOOL_PREFIX) => {
+                let connector = &name[API_CONNECTOR_TOOL_PREFIX.len()..];
+                with_storage!(storage, name, builder, |s| {
+                    register_api_connector_tools(
+                        builder,
+                        s,
+                        Some(connector),
+                        security_gate.clone(),
+                        agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+                        DEFAULT_SECURITY_TASK_ID,
+                    )
+                });
             }
             "transcribe" => {
                 if let Some(resolver) = secret_resolver.clone() {
@@ -317,6 +483,13 @@ pub fn registry_from_allowlist_with_security_gate(
                     );
                 }
             }
+            "speak" => {
+                if let Some(resolver) = secret_resolver.clone() {
+                    builder = builder.with_speak(resolver);
+                } else {
+                    warn!(tool_name = "speak", "Secret resolver missing, skipping");
+                }
+            }
             "vision" => {
                 if let Some(resolver) = secret_resolver.clone() {
                     builder = builder.with_vision(resolver)?;
@@ -338,6 +511,31 @@ pub fn registry_from_allowlist_with_security_gate(
                     builder = builder.with_web_search_with_defaults(default_num_results)?;
                 }
             }
+            "github" => {
+                builder = register_github_tool(
+                    builder,
+                    secret_resolver.clone(),
+                    security_gate.clone(),
+                    agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+                    DEFAULT_SECURITY_TASK_ID,
+                )?;
+            }
+            "calendar" => {
+                builder = register_calendar_tool(
+                    builder,
+                    secret_resolver.clone(),
+                    security_gate.clone(),
+                    agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+                    DEFAULT_SECURITY_TASK_ID,
+                )?;
+            }
+            "sql" => {
+                allow_sql = true;
+            }
+            "sql_write" => {
+                allow_sql = true;
+                allow_sql_write = true;
+            }
             "web_fetch" => {
                 builder = builder.with_web_fetch();
             }
@@ -380,7 +578,7 @@ pub fn registry_from_allowlist_with_security_gate(
             }
 
             // --- Subagent tools ---
-            "spawn_subagent" | "wait_subagents" | "list_subagents" => {}
+            "spawn_subagent" | "wait_subagents" | "list_subagents" | "fanout" => {}
             "use_skill" => {
                 if let Some(storage) = storage {
                     let provider: Arc<dyn SkillProvider> =
@@ -409,10 +607,13 @@ pub fn registry_from_allowlist_with_security_gate(
                         .as_ref()
                         .map(|config| config.registry_defaults.clone())
                         .unwrap_or_default();
-                    builder.with_marketplace(Arc::new(MarketplaceStoreAdapter::new_with_defaults(
-                        s.skills.clone(),
-                        registry_defaults,
-                    )))
+                    builder.with_marketplace(Arc::new(
+                        MarketplaceStoreAdapter::new_with_defaults(
+                            s.skills.clone(),
+                            registry_defaults,
+                        )
+                        .with_secrets(s.secrets.clone()),
+                    ))
                 });
             }
             "manage_triggers" => {
@@ -452,9 +653,23 @@ pub fn registry_from_allowlist_with_security_gate(
             }
             "memory_search" => {
                 with_storage!(storage, "memory_search", builder, |s| {
-                    let engine =
-                        UnifiedSearchEngine::new(s.memory.clone(), s.chat_sessions.clone());
-                    builder.with_unified_search(Arc::new(UnifiedMemorySearchAdapter::new(engine)))
+                    let memory_defaults = effective_config
+                        .as_ref()
+                        .map(|config| config.memory_defaults.clone())
+                        .unwrap_or_default();
+                    let embedder = MemoryEmbedder::from_settings(
+                        &memory_defaults,
+                        s.memory.clone(),
+                        &s.secrets,
+                    );
+                    let engine = UnifiedSearchEngine::with_config(
+                        s.memory.clone(),
+                        s.chat_sessions.clone(),
+                        UnifiedSearchConfig::from_memory_settings(&memory_defaults),
+                    );
+                    builder.with_unified_search(Arc::new(
+                        UnifiedMemorySearchAdapter::new(engine).with_embedder(embedder),
+                    ))
                 });
             }
             "kv_store" => {
@@ -510,9 +725,14 @@ pub fn registry_from_allowlist_with_security_gate(
                 // Register all 4 memory CRUD tools at once (idempotent via has() check)
                 if !builder.registry.has("save_to_memory") {
                     with_storage!(storage, raw_name.as_str(), builder, |s| {
-                        builder.with_memory_store(Arc::new(DbMemoryStoreAdapter::new(
-                            s.memory.clone(),
-                        )))
+                        let memory_defaults = effective_config
+                            .as_ref()
+                            .map(|config| config.memory_defaults.clone())
+                            .unwrap_or_default();
+                        builder.with_memory_store(Arc::new(
+                            DbMemoryStoreAdapter::new(s.memory.clone())
+                                .with_memory_settings(memory_defaults),
+                        ))
                     });
                 }
             }
@@ -523,8 +743,41 @@ pub fn registry_from_allowlist_with_security_gate(
                     )))
                 });
             }
+            "deliverable_builder" => {
+                with_storage!(storage, "deliverable_builder", builder, |s| {
+                    builder.with_deliverable_builder(Arc::new(DeliverableStoreAdapter::new(
+                        s.deliverables.clone(),
+                    )))
+                });
+            }
+            "check_usage_budget" => {
+                with_storage!(storage, "check_usage_budget", builder, |s| {
+                    builder.with_usage_budget(
+                        Arc::new(UsageStoreAdapter::new(s.usage.clone())),
+                        agent_id,
+                    )
+                });
+            }
 
             // --- Search tools ---
+            "git" => {
+                builder = register_git_tool(
+                    builder,
+                    workspace_root.map(Path::to_path_buf),
+                    security_gate.clone(),
+                    agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+                    DEFAULT_SECURITY_TASK_ID,
+                );
+            }
+            "spreadsheet" => {
+                builder = register_spreadsheet_tool(
+                    builder,
+                    workspace_root.map(Path::to_path_buf),
+                    security_gate.clone(),
+                    agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+                    DEFAULT_SECURITY_TASK_ID,
+                );
+            }
             "glob" => {
                 builder = builder.with_glob_and_base_dir(workspace_root.map(Path::to_path_buf));
             }
@@ -549,7 +802,7 @@ pub fn registry_from_allowlist_with_security_gate(
             "reply" => {
                 // Registered by callers that provide a ReplySender.
             }
-            "process" => {
+            "process" | "shell_session" => {
                 // Registered by callers that provide a ProcessRegistry.
             }
             unknown => {
@@ -590,6 +843,17 @@ pub fn registry_from_allowlist_with_security_gate(
         );
     }
 
+    if allow_sql {
+        builder = register_sql_tool(
+            builder,
+            tool_secret_resolver("sql"),
+            allow_sql_write,
+            security_gate.clone(),
+            agent_id.unwrap_or(DEFAULT_SECURITY_AGENT_ID),
+            DEFAULT_SECURITY_TASK_ID,
+        );
+    }
+
     // Register allowlisted skills as callable tools
     if let Some(storage) = storage {
         if !allowlisted_skill_ids.is_empty() {
@@ -648,7 +912,7 @@ pub fn registry_from_allowlist_with_security_gate(
 
     let mut registry = builder.build();
 
-    if wants_spawn_subagent || wants_wait_subagents || wants_list_subagents {
+    if wants_spawn_subagent || wants_wait_subagents || wants_list_subagents || wants_fanout {
         if let Some(manager) = &subagent_manager {
             register_subagent_management_tools(
                 &mut registry,
@@ -658,7 +922,7 @@ pub fn registry_from_allowlist_with_security_gate(
                 } else {
                     None
                 },
-                if wants_spawn_subagent {
+                if wants_spawn_subagent || wants_fanout {
                     shared_assessor.clone()
                 } else {
                     None
@@ -683,6 +947,9 @@ pub fn registry_from_allowlist_with_security_gate(
                     "Subagent manager missing, skipping"
                 );
             }
+            if wants_fanout {
+                debug!(tool_name = "fanout", "Subagent manager missing, skipping");
+            }
         }
     }
 
@@ -885,6 +1152,8 @@ mod tests {
             "manage_terminal".to_string(),
             "manage_ops".to_string(),
             "security_query".to_string(),
+            "use_plan".to_string(),
+            "check_usage_budget".to_string(),
         ];
 
         let registry =
@@ -895,6 +1164,37 @@ mod tests {
         assert!(registry.has("manage_terminal"));
         assert!(registry.has("manage_ops"));
         assert!(registry.has("security_query"));
+        assert!(registry.has("use_plan"));
+        assert!(registry.has("check_usage_budget"));
+    }
+
+    #[test]
+    fn test_api_connector_operations_registered_as_tools() {
+        let dir = tempdir().expect("temp dir should be created");
+        let db_path = dir.path().join("api-connectors.db");
+        let storage = Storage::new(db_path.to_str().expect("db path should be valid"))
+            .expect("storage should be created");
+        let spec = r#"{
+            "openapi": "3.0.0",
+            "servers": [{ "url": "https://shop.example.com" }],
+            "paths": {
+                "/orders": { "get": { "operationId": "listOrders" } },
+                "/orders/{id}": { "delete": { "operationId": "cancelOrder" } }
+            }
+        }"#;
+        let connector =
+            crate::services::api_connector::parse_openapi("shop", spec, None, None).unwrap();
+        storage.api_connectors.save(connector).unwrap();
+
+        let names = vec![
+            "connector:shop".to_string(),
+            "connector:missing".to_string(),
+        ];
+        let registry =
+            registry_from_allowlist(Some(&names), None, None, Some(&storage), None, None, None)
+                .unwrap();
+        assert!(registry.has("shop_list_orders"));
+        assert!(registry.has("shop_cancel_order"));
     }
 
     #[test]
@@ -903,12 +1203,14 @@ mod tests {
         assert!(tools.iter().any(|name| name == "run_python"));
         assert!(tools.iter().any(|name| name == "browser"));
         assert!(tools.iter().any(|name| name == "transcribe"));
+        assert!(tools.iter().any(|name| name == "speak"));
         assert!(tools.iter().any(|name| name == "vision"));
         assert!(tools.iter().any(|name| name == "switch_model"));
         assert!(tools.iter().any(|name| name == "manage_agents"));
         assert!(tools.iter().any(|name| name == "manage_marketplace"));
         assert!(tools.iter().any(|name| name == "manage_triggers"));
         assert!(tools.iter().any(|name| name == "manage_terminal"));
+        assert!(tools.iter().any(|name| name == "shell_session"));
         assert!(tools.iter().any(|name| name == "manage_ops"));
         assert!(tools.iter().any(|name| name == "security_query"));
     }
@@ -0,0 +1,533 @@
+//! Outbound run-result callbacks.
+//!
+//! When a background task finishes, every callback registered on the task
+//! receives a signed POST with the result summary and deliverable references.
+//! Delivery runs off the runner's critical path, retries with exponential
+//! backoff, and records the final status on the task record.
+
+use crate::models::{CallbackDelivery, CallbackDeliveryStatus, ResultCallback, Task};
+use crate::storage::{BackgroundAgentStorage, DeliverableStorage};
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use restflow_traits::http_client::build_ssrf_safe_client;
+use restflow_traits::network::resolve_and_validate_url;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+/// Header carrying `sha256=<hex>` HMAC of `"{timestamp}.{body}"`.
+pub const CALLBACK_SIGNATURE_HEADER: &str = "x-restflow-signature";
+/// Header carrying the millisecond timestamp included in the signature.
+pub const CALLBACK_TIMESTAMP_HEADER: &str = "x-restflow-timestamp";
+/// Header carrying the event name of a webhook sink delivery, e.g.
+/// `task.completed`.
+pub const WEBHOOK_EVENT_HEADER: &str = "x-restflow-event";
+
+const MAX_SUMMARY_CHARS: usize = 4000;
+
+/// Retry policy for callback delivery.
+#[derive(Debug, Clone)]
+pub struct CallbackRetryPolicy {
+    /// Total attempts per callback, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Upper bound for the exponential backoff delay.
+    pub max_delay: Duration,
+    /// Per-request timeout.
+    pub request_timeout: Duration,
+}
+
+impl Default for CallbackRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+            request_timeout: Duration::from_secs(15),
+        }
+    }
+}
+
+impl CallbackRetryPolicy {
+    pub(crate) fn delay_for_retry(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+}
+
+/// Reference to a deliverable produced by the run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeliverableLink {
+    pub id: String,
+    pub title: String,
+    pub deliverable_type: String,
+    #[serde(default)]
+    pub file_path: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+}
+
+/// JSON body posted to result callbacks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunResultPayload {
+    /// `task.completed` or `task.failed`
+    pub event: String,
+    pub task_id: String,
+    pub task_name: String,
+    pub agent_id: String,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    pub success: bool,
+    /// Truncated run output for successful runs.
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    #[serde(default)]
+    pub deliverables: Vec<DeliverableLink>,
+    pub timestamp: i64,
+}
+
+impl RunResultPayload {
+    pub fn completed(task: &Task, run_id: &str, output: &str, duration_ms: i64) -> Self {
+        Self {
+            event: "task.completed".to_string(),
+            task_id: task.id.clone(),
+            task_name: task.name.clone(),
+            agent_id: task.agent_id.clone(),
+            run_id: Some(run_id.to_string()),
+            success: true,
+            summary: Some(truncate_summary(output)),
+            error: None,
+            duration_ms,
+            deliverables: Vec::new(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    pub fn failed(task: &Task, run_id: &str, error: &str, duration_ms: i64) -> Self {
+        Self {
+            event: "task.failed".to_string(),
+            task_id: task.id.clone(),
+            task_name: task.name.clone(),
+            agent_id: task.agent_id.clone(),
+            run_id: Some(run_id.to_string()),
+            success: false,
+            summary: None,
+            error: Some(error.to_string()),
+            duration_ms,
+            deliverables: Vec::new(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+fn truncate_summary(output: &str) -> String {
+    match output.char_indices().nth(MAX_SUMMARY_CHARS) {
+        Some((end, _)) => format!("{}...", &output[..end]),
+        None => output.to_string(),
+    }
+}
+
+/// Compute the `sha256=<hex>` signature for a callback body.
+pub fn sign_callback_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delivers run results to the callbacks registered on a task.
+#[derive(Clone)]
+pub struct CallbackDispatcher {
+    storage: Arc<BackgroundAgentStorage>,
+    deliverables: Option<DeliverableStorage>,
+    retry: CallbackRetryPolicy,
+}
+
+impl CallbackDispatcher {
+    pub fn new(storage: Arc<BackgroundAgentStorage>) -> Self {
+        Self {
+            storage,
+            deliverables: None,
+            retry: CallbackRetryPolicy::default(),
+        }
+    }
+
+    /// Include deliverable references from this storage in payloads.
+    pub fn with_deliverables(mut self, deliverables: DeliverableStorage) -> Self {
+        self.deliverables = Some(deliverables);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: CallbackRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Deliver the payload to every callback on the task in the background.
+    pub fn dispatch(&self, task: &Task, payload: RunResultPayload) {
+        if task.callbacks.is_empty() {
+            return;
+        }
+
+        let dispatcher = self.clone();
+        let task_id = task.id.clone();
+        let callbacks = task.callbacks.clone();
+        tokio::spawn(async move {
+            dispatcher.deliver_all(&task_id, callbacks, payload).await;
+        });
+    }
+
+    async fn deliver_all(
+        &self,
+        task_id: &str,
+        callbacks: Vec<ResultCallback>,
+        mut payload: RunResultPayload,
+    ) {
+        let run_started_at = payload.timestamp - payload.duration_ms.max(0);
+        payload.deliverables =
+            self.deliverable_links(task_id, payload.run_id.as_deref(), run_started_at);
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(err) => {
+                warn!(task_id, error = %err, "Failed to serialize callback payload");
+                return;
+            }
+        };
+
+        for callback in callbacks {
+            let delivery = self.deliver(&callback, &body, payload.run_id.clone()).await;
+            if delivery.status == CallbackDeliveryStatus::Failed {
+                warn!(
+                    task_id,
+                    url = %delivery.url,
+                    attempts = delivery.attempts,
+                    error = delivery.error.as_deref().unwrap_or_default(),
+                    "Result callback delivery failed"
+                );
+            }
+            if let Err(err) = self.storage.record_callback_delivery(task_id, delivery) {
+                warn!(task_id, error = %err, "Failed to record callback delivery");
+            }
+        }
+    }
+
+    /// Deliverables tagged with the run id, or produced by the task during the run.
+    fn deliverable_links(
+        &self,
+        task_id: &str,
+        run_id: Option<&str>,
+        run_started_at: i64,
+    ) -> Vec<DeliverableLink> {
+        let Some(deliverables) = &self.deliverables else {
+            return Vec::new();
+        };
+        match deliverables.list_by_task(task_id) {
+            Ok(items) => items
+                .into_iter()
+                .filter(|item| {
+                    run_id == Some(item.execution_id.as_str()) || item.created_at >= run_started_at
+                })
+                .map(|item| DeliverableLink {
+                    id: item.id,
+                    title: item.title,
+                    deliverable_type: serde_json::to_value(&item.deliverable_type)
+                        .ok()
+                        .and_then(|value| value.as_str().map(str::to_string))
+                        .unwrap_or_default(),
+                    file_path: item.file_path,
+                    content_type: item.content_type,
+                })
+                .collect(),
+            Err(err) => {
+                warn!(task_id, error = %err, "Failed to load deliverables for callback");
+                Vec::new()
+            }
+        }
+    }
+
+    async fn deliver(
+        &self,
+        callback: &ResultCallback,
+        body: &[u8],
+        run_id: Option<String>,
+    ) -> CallbackDelivery {
+        let outcome = match pinned_client(&callback.url).await {
+            Ok(http_client) => {
+                deliver_signed(
+                    &http_client,
+                    &self.retry,
+                    &SignedRequest {
+                        url: &callback.url,
+                        secret: callback.secret.as_deref(),
+                        event: None,
+                        body,
+                    },
+                )
+                .await
+            }
+            Err(error) => SignedDeliveryOutcome {
+                delivered: false,
+                attempts: 0,
+                status_code: None,
+                error: Some(error),
+            },
+        };
+
+        CallbackDelivery {
+            url: callback.url.clone(),
+            run_id,
+            status: if outcome.delivered {
+                CallbackDeliveryStatus::Delivered
+            } else {
+                CallbackDeliveryStatus::Failed
+            },
+            attempts: outcome.attempts,
+            status_code: outcome.status_code,
+            error: outcome.error,
+            attempted_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+/// One signed JSON POST, shared by result callbacks and webhook sinks.
+pub(crate) struct SignedRequest<'a> {
+    pub url: &'a str,
+    /// Signing secret; the request is sent unsigned when `None`.
+    pub secret: Option<&'a str>,
+    /// Event name sent in `x-restflow-event`, if any.
+    pub event: Option<&'a str>,
+    pub body: &'a [u8],
+}
+
+/// Result of [`deliver_signed`].
+#[derive(Debug)]
+pub(crate) struct SignedDeliveryOutcome {
+    pub delivered: bool,
+    pub attempts: u32,
+    /// Status of the last response, if the last attempt got one.
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Resolve `url` and build a client pinned to the resolved address.
+///
+/// Fails when the URL is not http(s) or any resolved address is loopback,
+/// private, link-local or otherwise internal. The client does not follow
+/// redirects, so a receiver cannot bounce the POST to an internal host.
+pub(crate) async fn pinned_client(url: &str) -> std::result::Result<reqwest::Client, String> {
+    let (parsed, addr) = resolve_and_validate_url(url).await?;
+    let host = parsed.host_str().unwrap_or_default();
+    build_ssrf_safe_client(host, addr).map_err(|error| error.to_string())
+}
+
+/// POST `request`, retrying transport errors, rate limiting and server
+/// errors with backoff. Each attempt is signed with a fresh timestamp.
+pub(crate) async fn deliver_signed(
+    http_client: &reqwest::Client,
+    retry: &CallbackRetryPolicy,
+    request: &SignedRequest<'_>,
+) -> SignedDeliveryOutcome {
+    let max_attempts = retry.max_attempts.max(1);
+    let mut outcome = SignedDeliveryOutcome {
+        delivered: false,
+        attempts: 0,
+        status_code: None,
+        error: None,
+    };
+
+    while outcome.attempts < max_attempts {
+        if outcome.attempts > 0 {
+            tokio::time::sleep(retry.delay_for_retry(outcome.attempts)).await;
+        }
+        outcome.attempts += 1;
+
+        match post_signed(http_client, retry, request).await {
+            Ok(code) if (200..300).contains(&code) => {
+                outcome.delivered = true;
+                outcome.status_code = Some(code);
+                outcome.error = None;
+                return outcome;
+            }
+            Ok(code) => {
+                outcome.status_code = Some(code);
+                outcome.error = Some(format!("Receiver responded with HTTP {code}"));
+                // Client errors other than rate limiting will not succeed on retry.
+                if (400..500).contains(&code) && code != 429 {
+                    break;
+                }
+            }
+            Err(err) => {
+                outcome.status_code = None;
+                outcome.error = Some(err.to_string());
+            }
+        }
+    }
+
+    outcome
+}
+
+async fn post_signed(
+    http_client: &reqwest::Client,
+    retry: &CallbackRetryPolicy,
+    request: &SignedRequest<'_>,
+) -> Result<u16> {
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let mut builder = http_client
+        .post(request.url)
+        .timeout(retry.request_timeout)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(CALLBACK_TIMESTAMP_HEADER, timestamp.to_string());
+    if let Some(event) = request.event {
+        builder = builder.header(WEBHOOK_EVENT_HEADER, event);
+    }
+    if let Some(secret) = request.secret {
+        builder = builder.header(
+            CALLBACK_SIGNATURE_HEADER,
+            sign_callback_payload(secret, timestamp, request.body),
+        );
+    }
+
+    // URLs may embed secrets, so keep them out of transport errors.
+    let response = builder
+        .body(request.body.to_vec())
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)?;
+    Ok(response.status().as_u16())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TaskSchedule;
+
+    #[test]
+    fn test_signature_matches_known_vector() {
+        let signature = sign_callback_payload("key", 1, b"{}");
+        assert_eq!(
+            signature,
+            "sha256=1ba6b8171186efc613e8bcc0cbdab2748f24984d7c5a84faa2637afa0e40d224"
+        );
+        assert_ne!(signature, sign_callback_payload("other", 1, b"{}"));
+        assert_ne!(signature, sign_callback_payload("key", 2, b"{}"));
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_and_caps() {
+        let policy = CallbackRetryPolicy {
+            initial_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(5),
+            ..CallbackRetryPolicy::default()
+        };
+        assert_eq!(policy.delay_for_retry(1), Duration::from_secs(2));
+        assert_eq!(policy.delay_for_retry(2), Duration::from_secs(4));
+        assert_eq!(policy.delay_for_retry(3), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_deliver_signed_retries_server_errors() {
+        use axum::http::{HeaderMap, StatusCode};
+        use std::sync::Mutex;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let hits = Arc::new(AtomicU32::new(0));
+        let last_headers = Arc::new(Mutex::new(HeaderMap::new()));
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post({
+                let hits = hits.clone();
+                let last_headers = last_headers.clone();
+                move |headers: HeaderMap| async move {
+                    *last_headers.lock().unwrap() = headers;
+                    if hits.fetch_add(1, Ordering::SeqCst) == 0 {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let retry = CallbackRetryPolicy {
+            initial_delay: Duration::from_millis(1),
+            ..CallbackRetryPolicy::default()
+        };
+        let outcome = deliver_signed(
+            &reqwest::Client::new(),
+            &retry,
+            &SignedRequest {
+                url: &url,
+                secret: Some("key"),
+                event: Some("task.completed"),
+                body: b"{}",
+            },
+        )
+        .await;
+
+        assert!(outcome.delivered);
+        assert_eq!(outcome.attempts, 2);
+        assert_eq!(outcome.status_code, Some(200));
+        let headers = last_headers.lock().unwrap();
+        let timestamp: i64 = headers[CALLBACK_TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            headers[CALLBACK_SIGNATURE_HEADER].to_str().unwrap(),
+            sign_callback_payload("key", timestamp, b"{}")
+        );
+        assert_eq!(headers[WEBHOOK_EVENT_HEADER], "task.completed");
+    }
+
+    #[tokio::test]
+    async fn test_pinned_client_rejects_internal_hosts() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://localhost:8080/hook",
+            "file:///etc/passwd",
+        ] {
+            assert!(
+                pinned_client(url).await.is_err(),
+                "{url} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_payload_truncates_summary() {
+        let task = Task::new(
+            "task-1".to_string(),
+            "Task".to_string(),
+            "agent-1".to_string(),
+            TaskSchedule::default(),
+        );
+        let output = "x".repeat(MAX_SUMMARY_CHARS + 10);
+        let payload = RunResultPayload::completed(&task, "run-1", &output, 5);
+        assert_eq!(payload.event, "task.completed");
+        assert_eq!(
+            payload.summary.map(|summary| summary.len()),
+            Some(MAX_SUMMARY_CHARS + 3)
+        );
+
+        let payload = RunResultPayload::failed(&task, "run-1", "boom", 5);
+        assert!(!payload.success);
+        assert_eq!(payload.error.as_deref(), Some("boom"));
+    }
+}
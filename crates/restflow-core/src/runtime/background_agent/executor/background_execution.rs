@@ -101,9 +101,16 @@ impl AgentRuntimeExecutor {
                 .as_ref()
                 .map(|path| path.to_string_lossy().into_owned()),
             timeout_secs: agent_defaults.bash_timeout_secs,
+            resource_limits: subprocess_resource_limits(&agent_defaults),
+            container: subprocess_container(&agent_defaults),
+            network: subprocess_network_policy(&agent_defaults),
             ..BashConfig::default()
         };
         let reply_sender = self.resolve_reply_sender(background_task_id, agent_id);
+        let chat_session_id = background_task_snapshot
+            .as_ref()
+            .map(|task| task.chat_session_id.trim().to_string())
+            .filter(|session_id| !session_id.is_empty());
         let tools = self.build_tool_registry(
             Some(&effective_tools),
             swappable.clone(),
@@ -113,6 +120,8 @@ impl AgentRuntimeExecutor {
             Some(bash_config),
             reply_sender,
             workspace_root.as_deref(),
+            background_task_id,
+            chat_session_id.as_deref(),
         )?;
         let system_prompt =
             self.build_background_system_prompt(agent_node, agent_id, background_task_id, input)?;
@@ -132,14 +141,11 @@ impl AgentRuntimeExecutor {
             context_window,
         );
         let execution_context = background_task_id.map(|task_id| {
-            let chat_session_id = background_task_snapshot
-                .as_ref()
-                .map(|task| task.chat_session_id.trim().to_string())
-                .filter(|session_id| !session_id.is_empty())
-                .unwrap_or_else(|| "unknown".to_string());
             ExecutionContext::background(
                 agent_id.unwrap_or("unknown-agent"),
-                chat_session_id,
+                chat_session_id
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
                 task_id,
             )
         });
@@ -164,7 +170,8 @@ impl AgentRuntimeExecutor {
             .with_max_tool_concurrency(agent_defaults.max_tool_concurrency)
             .with_prune_tool_max_chars(agent_defaults.prune_tool_max_chars)
             .with_compact_preserve_tokens(agent_defaults.compact_preserve_tokens)
-            .with_yolo_mode(background_task_id.is_some());
+            .with_yolo_mode(background_task_id.is_some())
+            .with_dry_run(agent_node.is_dry_run());
         if let Some(entry) = model_entry
             && !model.is_cli_model()
         {
@@ -183,7 +190,7 @@ impl AgentRuntimeExecutor {
         config = Self::apply_llm_timeout(config, agent_defaults.llm_timeout_secs);
         if let Some(model_routing) = agent_node.model_routing.as_ref() {
             config = config.with_model_routing(AiModelRoutingConfig::from(model_routing));
-            if model_routing.enabled {
+            if model_routing.enabled || model_routing.failover.is_some() {
                 let switcher: Arc<dyn LlmSwitcher> = Arc::new(RuntimeModelSwitcher {
                     swappable: swappable.clone(),
                     factory: factory.clone(),
@@ -195,6 +202,8 @@ impl AgentRuntimeExecutor {
         if let Some(context) = execution_context.as_ref() {
             config = Self::apply_execution_context(config, context);
         }
+        config = self.apply_compaction_embedder(config, workspace_root.as_deref());
+        config = self.apply_execution_strategy(config, agent_node);
         config = config
             .with_telemetry_sink(crate::telemetry::build_core_telemetry_sink(
                 self.storage.as_ref(),
@@ -234,14 +243,16 @@ impl AgentRuntimeExecutor {
                         .map_err(|e| {
                             AiError::Agent(format!("Failed to save checkpoint with savepoint: {e}"))
                         })?;
-                    checkpoint.savepoint_id = Some(savepoint_id);
-                    checkpoints
-                        .save_checkpoint_with_savepoint_id(&checkpoint)
-                        .map_err(|e| {
-                            AiError::Agent(format!(
-                                "Failed to persist checkpoint with savepoint id: {e}"
-                            ))
-                        })?;
+                    if savepoint_id.is_some() {
+                        checkpoint.savepoint_id = savepoint_id;
+                        checkpoints
+                            .save_checkpoint_with_savepoint_id(&checkpoint)
+                            .map_err(|e| {
+                                AiError::Agent(format!(
+                                    "Failed to persist checkpoint with savepoint id: {e}"
+                                ))
+                            })?;
+                    }
                     checkpoints
                         .set_task_run_checkpoint(&run_id, Some(checkpoint.id.clone()))
                         .map_err(|e| {
@@ -254,6 +265,14 @@ impl AgentRuntimeExecutor {
             });
         }
 
+        config = self.apply_replay_recording(
+            config,
+            initial_state.as_ref(),
+            background_task_id,
+            agent_id,
+            &telemetry_context.trace.run_id,
+        );
+
         let mut agent = ReActAgentExecutor::new(swappable.clone(), tools)
             .with_subagent_tracker(self.subagent_tracker.clone());
         if let Some(workspace_root) = workspace_root {
@@ -911,6 +930,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("task");
         let stale_trace = restflow_telemetry::RestflowTrace::new(
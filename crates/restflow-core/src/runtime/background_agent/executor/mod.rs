@@ -17,7 +17,7 @@ use crate::{
     auth::{AuthProfileManager, resolve_model_from_credentials, secret_exists},
     models::{
         AgentCheckpoint, AgentNode, ApiKeyConfig, ChatMessage, ChatRole, ChatSession,
-        DurabilityMode, MemoryConfig, SharedEntry, Skill, SteerMessage, Visibility,
+        DurabilityMode, MemoryConfig, ReplaySnapshot, SharedEntry, Skill, SteerMessage, Visibility,
     },
     process::ProcessRegistry,
     prompt_files,
@@ -34,7 +34,7 @@ use restflow_ai::{
     DefaultLlmClientFactory, LlmClient, LlmClientFactory, ResourceLimits as AgentResourceLimits,
     SwappableLlm,
 };
-use restflow_tools::{ProcessTool, ReplyTool, SwitchModelTool};
+use restflow_tools::{ProcessTool, ReplyTool, ShellSessionTool, SwitchModelTool};
 use restflow_traits::llm::{LlmProvider, LlmSwitcher, SwapResult};
 use restflow_traits::{ExecutionOutcome, ExecutionPlan, ReplySender, ToolError};
 use tokio::sync::mpsc;
@@ -52,9 +52,12 @@ use super::skill_snapshot::{
     SkillSnapshotCache, SkillSnapshotKey, SkillSnapshotPayload, build_skill_filter_signature,
     build_skill_version_hash, build_trigger_context_signature,
 };
+use crate::runtime::agent::tools::assembly::{
+    subprocess_container, subprocess_network_policy, subprocess_resource_limits,
+};
 use crate::runtime::agent::{
     BashConfig, ToolRegistry, build_agent_system_prompt, effective_main_agent_tool_names,
-    main_agent_default_tool_names, registry_from_allowlist, secret_resolver_from_storage,
+    main_agent_default_tool_names, registry_from_allowlist, secret_resolver_for_task,
 };
 use restflow_ai::agent::SubagentDefLookup;
 use restflow_ai::agent::{
@@ -91,6 +94,7 @@ pub struct AgentRuntimeExecutor {
     skill_snapshot_cache: Arc<SkillSnapshotCache>,
     reply_sender: Option<Arc<dyn ReplySender>>,
     reply_sender_factory: Option<Arc<dyn ReplySenderFactory>>,
+    system_prompt_section: Option<String>,
 }
 
 /// Factory for constructing execution-scoped reply senders.
@@ -224,6 +228,9 @@ impl AgentRuntimeExecutor {
             .unwrap_or_default();
         let bash_config = BashConfig {
             timeout_secs: agent_defaults.bash_timeout_secs,
+            resource_limits: subprocess_resource_limits(&agent_defaults),
+            container: subprocess_container(&agent_defaults),
+            network: subprocess_network_policy(&agent_defaults),
             ..BashConfig::default()
         };
         let default_tools = main_agent_default_tool_names();
@@ -236,6 +243,8 @@ impl AgentRuntimeExecutor {
             Some(bash_config),
             None,
             None,
+            None,
+            None,
         )?;
         execute_subagent_plan(
             self.subagent_definitions.clone(),
@@ -359,6 +368,7 @@ impl AgentRuntimeExecutor {
             skill_snapshot_cache: Arc::new(SkillSnapshotCache::default()),
             reply_sender: None,
             reply_sender_factory: None,
+            system_prompt_section: None,
         }
     }
 
@@ -374,6 +384,20 @@ impl AgentRuntimeExecutor {
         self.reply_sender_factory = Some(factory);
         self
     }
+
+    /// Append an extra section to the system prompt of interactive session
+    /// turns, such as channel formatting rules.
+    pub fn with_system_prompt_section(mut self, section: Option<String>) -> Self {
+        self.system_prompt_section = section;
+        self
+    }
+
+    fn with_extra_prompt_section(&self, base_prompt: String) -> String {
+        match self.system_prompt_section.as_deref() {
+            Some(section) if !section.trim().is_empty() => format!("{base_prompt}\n\n{section}"),
+            _ => base_prompt,
+        }
+    }
 }
 
 fn is_credential_error(error: &anyhow::Error) -> bool {
@@ -74,7 +74,11 @@ impl AgentRuntimeExecutor {
         agent_node: &AgentNode,
         agent_id: Option<&str>,
     ) -> Result<String> {
-        let base_prompt = build_agent_system_prompt(self.storage.clone(), agent_node, agent_id)?;
+        let base_prompt = self.with_extra_prompt_section(build_agent_system_prompt(
+            self.storage.clone(),
+            agent_node,
+            agent_id,
+        )?);
         Ok(format!(
             "{base_prompt}\n\n## Temporary Acknowledgement Phase\n{ACK_PHASE_SYSTEM_DIRECTIVE}"
         ))
@@ -222,6 +226,9 @@ impl AgentRuntimeExecutor {
             .unwrap_or_default();
         let bash_config = BashConfig {
             timeout_secs: agent_defaults.bash_timeout_secs,
+            resource_limits: subprocess_resource_limits(&agent_defaults),
+            container: subprocess_container(&agent_defaults),
+            network: subprocess_network_policy(&agent_defaults),
             ..BashConfig::default()
         };
         let reply_sender = self.resolve_reply_sender(None, agent_id);
@@ -234,8 +241,14 @@ impl AgentRuntimeExecutor {
             Some(bash_config),
             reply_sender,
             None,
+            None,
+            Some(session.id.as_str()),
         )?;
-        let system_prompt = build_agent_system_prompt(self.storage.clone(), agent_node, agent_id)?;
+        let system_prompt = self.with_extra_prompt_section(build_agent_system_prompt(
+            self.storage.clone(),
+            agent_node,
+            agent_id,
+        )?);
 
         let catalog = ModelCatalog::global().await;
         let model_entry = catalog.resolve(model).await;
@@ -279,7 +292,8 @@ impl AgentRuntimeExecutor {
             .with_max_tool_concurrency(agent_defaults.max_tool_concurrency)
             .with_prune_tool_max_chars(agent_defaults.prune_tool_max_chars)
             .with_compact_preserve_tokens(agent_defaults.compact_preserve_tokens)
-            .with_stream_display_mode(stream_display_mode);
+            .with_stream_display_mode(stream_display_mode)
+            .with_dry_run(agent_node.is_dry_run());
         if let Some(entry) = model_entry
             && !model.is_cli_model()
         {
@@ -292,11 +306,20 @@ impl AgentRuntimeExecutor {
         }
         config = Self::apply_llm_timeout(config, agent_defaults.llm_timeout_secs);
         config = Self::apply_execution_context(config, &execution_context);
+        config = self.apply_compaction_embedder(config, None);
+        config = self.apply_execution_strategy(config, agent_node);
         config = config
             .with_telemetry_sink(crate::telemetry::build_core_telemetry_sink(
                 self.storage.as_ref(),
             ))
             .with_telemetry_context(final_telemetry_context.clone());
+        config = self.apply_replay_recording(
+            config,
+            None,
+            None,
+            Some(agent_id.unwrap_or(&session.agent_id)),
+            &final_telemetry_context.trace.run_id,
+        );
 
         let mut agent = ReActAgentExecutor::new(swappable.clone(), tools)
             .with_subagent_tracker(self.subagent_tracker.clone());
@@ -789,13 +812,13 @@ impl AgentRuntimeExecutor {
                         primary_provider,
                         max_history,
                         input_mode,
-                    emitter,
-                    Some(agent_id.as_str()),
-                    steer_rx,
-                    Some(telemetry_context),
-                    stream_display_mode,
-                )
-                .await
+                        emitter,
+                        Some(agent_id.as_str()),
+                        steer_rx,
+                        Some(telemetry_context),
+                        stream_display_mode,
+                    )
+                    .await
                 }
             })
             .await;
@@ -282,6 +282,22 @@ fn test_build_ack_system_prompt_appends_phase_directive() {
     assert!(prompt.contains("Reply with exactly one short assistant message"));
 }
 
+#[test]
+fn test_build_ack_system_prompt_includes_extra_section() {
+    let (storage, _temp_dir) = create_test_storage();
+    let executor = create_test_executor(storage)
+        .with_system_prompt_section(Some("## Reply Format (telegram)".to_string()));
+    let node = AgentNode {
+        prompt: Some("Base prompt".to_string()),
+        ..AgentNode::new()
+    };
+
+    let prompt = executor.build_ack_system_prompt(&node, None).unwrap();
+    let section_pos = prompt.find("## Reply Format (telegram)").unwrap();
+    assert!(prompt.find("Base prompt").unwrap() < section_pos);
+    assert!(section_pos < prompt.find("Temporary Acknowledgement Phase").unwrap());
+}
+
 /// Skills are now registered as callable tools, not injected into the prompt.
 /// Triggered skills are resolved but do not appear in the system prompt.
 #[test]
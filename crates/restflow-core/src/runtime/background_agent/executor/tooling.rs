@@ -1,6 +1,11 @@
 use super::*;
+use crate::memory::{CompactionEmbedder, embedding_provider_from_settings};
+use crate::models::ExecutionStrategy;
+use crate::security::{ToolApprovalScope, ToolApprovalWrapper};
+use crate::services::adapters::DbWorkItemAdapter;
 use restflow_ai::agent::SubagentManagerImpl;
 use restflow_traits::SubagentManager;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 impl AgentRuntimeExecutor {
     pub(super) fn to_agent_resource_limits(
@@ -70,6 +75,93 @@ impl AgentRuntimeExecutor {
         config
     }
 
+    /// Apply the agent's execution strategy. Plan-and-execute persists its
+    /// plan steps as work items.
+    pub(super) fn apply_execution_strategy(
+        &self,
+        config: ReActAgentConfig,
+        agent_node: &AgentNode,
+    ) -> ReActAgentConfig {
+        match agent_node.effective_execution_strategy() {
+            ExecutionStrategy::React => config,
+            strategy => config
+                .with_execution_strategy(strategy.into())
+                .with_plan_store(Arc::new(DbWorkItemAdapter::new(
+                    self.storage.work_items.clone(),
+                ))),
+        }
+    }
+
+    /// Score compaction relevance with the configured memory embedding
+    /// model, if any. Otherwise the agent falls back to lexical similarity.
+    pub(super) fn apply_compaction_embedder(
+        &self,
+        config: ReActAgentConfig,
+        workspace_root: Option<&std::path::Path>,
+    ) -> ReActAgentConfig {
+        let memory_defaults = self
+            .storage
+            .config
+            .get_effective_config_for_workspace(workspace_root)
+            .ok()
+            .map(|c| c.memory_defaults)
+            .unwrap_or_default();
+        match embedding_provider_from_settings(&memory_defaults, &self.storage.secrets) {
+            Ok(Some(provider)) => {
+                config.with_compaction_embedder(Arc::new(CompactionEmbedder::new(provider)))
+            }
+            Ok(None) => config,
+            Err(error) => {
+                debug!(error = %error, "Compaction embedder unavailable, using lexical similarity");
+                config
+            }
+        }
+    }
+
+    /// Record a replay snapshot after every turn of the run.
+    pub(super) fn apply_replay_recording(
+        &self,
+        config: ReActAgentConfig,
+        initial_state: Option<&restflow_ai::AgentState>,
+        task_id: Option<&str>,
+        agent_id: Option<&str>,
+        run_id: &str,
+    ) -> ReActAgentConfig {
+        let checkpoints = self.storage.checkpoints.clone();
+        // A resumed run keeps its execution ID, so continue after the steps
+        // recorded before it was interrupted.
+        let first_step = initial_state
+            .and_then(|state| checkpoints.list_replay_snapshots(&state.execution_id).ok())
+            .and_then(|snapshots| snapshots.last().map(|snapshot| snapshot.step + 1))
+            .unwrap_or(0);
+        let next_step = AtomicU32::new(first_step);
+        let task_id = task_id.map(str::to_string);
+        let agent_id = agent_id.map(str::to_string);
+        let run_id = run_id.to_string();
+        config.with_replay_callback(move |state| {
+            let step = next_step.fetch_add(1, Ordering::Relaxed);
+            let snapshot = serde_json::to_vec(state).map(|state_json| {
+                ReplaySnapshot::new(
+                    state.execution_id.clone(),
+                    step,
+                    state.iteration,
+                    state_json,
+                )
+                .with_task_id(task_id.clone())
+                .with_agent_id(agent_id.clone())
+                .with_run_id(Some(run_id.clone()))
+            });
+            let checkpoints = checkpoints.clone();
+            async move {
+                let snapshot =
+                    snapshot.map_err(|e| AiError::Agent(format!("Failed to encode state: {e}")))?;
+                checkpoints
+                    .save_replay_snapshot(&snapshot)
+                    .map_err(|e| AiError::Agent(format!("Failed to save replay snapshot: {e}")))
+            }
+        })
+    }
+
     pub(super) fn non_main_agent_prompt_flags() -> PromptFlags {
         PromptFlags::new().without_workspace_context()
     }
@@ -117,12 +209,40 @@ impl AgentRuntimeExecutor {
         bash_config: Option<BashConfig>,
         reply_sender: Option<Arc<dyn ReplySender>>,
         workspace_root: Option<&std::path::Path>,
+        background_task_id: Option<&str>,
+        chat_session_id: Option<&str>,
     ) -> anyhow::Result<Arc<ToolRegistry>> {
+        // PTY shells and spawned processes bypass the bash sandbox, so they
+        // are withheld whenever bash commands are confined.
+        let allow_unsandboxed_processes = !match &bash_config {
+            Some(config) => config.is_sandboxed(),
+            None => {
+                let agent_defaults = self
+                    .storage
+                    .config
+                    .get_effective_config()
+                    .ok()
+                    .map(|config| config.agent)
+                    .unwrap_or_default();
+                BashConfig {
+                    resource_limits: subprocess_resource_limits(&agent_defaults),
+                    container: subprocess_container(&agent_defaults),
+                    network: subprocess_network_policy(&agent_defaults),
+                    ..BashConfig::default()
+                }
+                .is_sandboxed()
+            }
+        };
         let has_reply_sender = reply_sender.is_some();
         let filtered_tool_names = self.filter_requested_tool_names(tool_names, has_reply_sender);
         let filtered_tool_names_ref = filtered_tool_names.as_deref();
-        let secret_resolver = Some(secret_resolver_from_storage(&self.storage));
-        let subagent_tool_registry = Arc::new(registry_from_allowlist(
+        let secret_resolver = Some(secret_resolver_for_task(&self.storage, background_task_id));
+        let approval_scope = ToolApprovalScope {
+            agent_id: agent_id.map(str::to_string),
+            task_id: background_task_id.map(str::to_string),
+            session_id: chat_session_id.map(str::to_string),
+        };
+        let mut subagent_tool_registry = registry_from_allowlist(
             filtered_tool_names_ref,
             None,
             secret_resolver.clone(),
@@ -130,7 +250,10 @@ impl AgentRuntimeExecutor {
             agent_id,
             bash_config.clone(),
             workspace_root,
-        )?);
+        )?;
+        ToolApprovalWrapper::new(self.storage.tool_approvals.clone(), approval_scope.clone())
+            .apply(&mut subagent_tool_registry);
+        let subagent_tool_registry = Arc::new(subagent_tool_registry);
         let subagent_manager: Arc<dyn SubagentManager> = Arc::new(self.build_subagent_manager(
             llm_client,
             subagent_tool_registry,
@@ -157,16 +280,23 @@ impl AgentRuntimeExecutor {
             registry.register(SwitchModelTool::new(switcher));
         }
 
-        if requested("process") {
+        if allow_unsandboxed_processes && requested("process") {
             registry.register(ProcessTool::new(self.process_registry.clone()));
         }
 
+        if allow_unsandboxed_processes && requested("shell_session") {
+            registry.register(ShellSessionTool::new(self.process_registry.clone()));
+        }
+
         if requested("reply")
             && let Some(sender) = reply_sender
         {
             registry.register(ReplyTool::new(sender));
         }
 
+        // Applied last so the policy also covers runtime-registered tools.
+        ToolApprovalWrapper::new(self.storage.tool_approvals.clone(), approval_scope)
+            .apply(&mut registry);
         Ok(Arc::new(registry))
     }
 
@@ -12,6 +12,7 @@
 //! - `executor`: Real agent executor that bridges to restflow_ai
 //! - `cli_executor`: CLI agent executor for external tools (Claude Code, Aider)
 //! - `notifier`: Telegram notification sender for task results
+//! - `callbacks`: Signed outbound run-result callbacks with retry
 //! - `events`: Real-time streaming events for frontend updates
 //! - `heartbeat`: Status types and emitters (integrated into runner)
 //! - `retry`: Retry mechanism for transient failures
@@ -140,6 +141,7 @@
 //! ```
 
 pub mod broadcast_emitter;
+pub mod callbacks;
 pub mod cli_executor;
 pub mod error_classification;
 pub mod events;
@@ -160,6 +162,7 @@ pub mod testkit;
 pub mod transactional_checkpoint;
 
 pub use crate::runtime::orchestrator::OrchestratingAgentExecutor;
+pub use callbacks::{CallbackDispatcher, CallbackRetryPolicy, RunResultPayload};
 pub use cli_executor::{CliAgentExecutor, create_cli_executor_with_events};
 pub use events::{
     ChannelEventEmitter, ExecutionStats, NoopEventEmitter, StreamEventKind, TASK_STREAM_EVENT,
@@ -17,8 +17,9 @@ use crate::performance::{
     TaskExecutor, TaskPriority, TaskQueue, TaskQueueConfig, WorkerPool, WorkerPoolConfig,
 };
 use crate::runtime::output::{ensure_success_output, format_error_output};
+use crate::services::budget::BudgetGuard;
 use crate::steer::SteerRegistry;
-use crate::storage::{BackgroundAgentStorage, MemoryStorage};
+use crate::storage::{BackgroundAgentStorage, MemoryStorage, WorkItemStorage};
 use anyhow::{Result, anyhow};
 use restflow_ai::agent::StreamEmitter;
 use restflow_telemetry::{RunDescriptor, RunKind, RunLifecycleService};
@@ -33,6 +34,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 use super::broadcast_emitter::BroadcastStreamEmitter;
+use super::callbacks::{CallbackDispatcher, RunResultPayload};
 use super::events::{NoopEventEmitter, TaskEventEmitter, TaskStreamEvent};
 use super::persist::MemoryPersister;
 use restflow_traits::{
@@ -45,9 +47,11 @@ use super::heartbeat::{
 };
 use super::outcome::ExecutionOutcome;
 use finalizer::BackgroundRunFinalizer;
+mod concurrency;
 mod finalizer;
 mod notification;
 mod persistence;
+mod wake;
 
 #[cfg(test)]
 mod tests;
@@ -111,6 +115,9 @@ pub struct TaskRunnerConfig {
     ///
     /// `None` disables periodic stalled-task recovery.
     pub stall_timeout_secs: Option<u64>,
+    /// Resume runs interrupted by a restart from their last checkpoint on
+    /// startup. When `false` the checkpoint is only offered for resume.
+    pub auto_resume: bool,
 }
 
 impl Default for TaskRunnerConfig {
@@ -121,6 +128,7 @@ impl Default for TaskRunnerConfig {
             worker_count: DEFAULT_BACKGROUND_RUNNER_MAX_CONCURRENT_TASKS,
             task_timeout_secs: None,
             stall_timeout_secs: None,
+            auto_resume: false,
         }
     }
 }
@@ -459,6 +467,12 @@ pub struct TaskRunner {
     memory_persister: Option<MemoryPersister>,
     /// Optional hook executor for lifecycle automation
     hook_executor: Option<Arc<HookExecutor>>,
+    /// Optional dispatcher for outbound run-result callbacks
+    callback_dispatcher: Option<Arc<CallbackDispatcher>>,
+    /// Optional work item storage for evaluating work item wake conditions
+    work_items: Option<WorkItemStorage>,
+    /// Optional global LLM budget checked before each run
+    budget_guard: Option<Arc<BudgetGuard>>,
     steer_registry: Arc<SteerRegistry>,
     /// Optional channel router for broadcasting notifications to all configured channels
     channel_router: Arc<RwLock<Option<Arc<ChannelRouter>>>>,
@@ -503,6 +517,9 @@ impl TaskRunner {
             start_time: Instant::now(),
             memory_persister: None,
             hook_executor: None,
+            callback_dispatcher: None,
+            work_items: None,
+            budget_guard: None,
             steer_registry,
             channel_router: Arc::new(RwLock::new(None)),
             #[cfg(test)]
@@ -542,6 +559,9 @@ impl TaskRunner {
             start_time: Instant::now(),
             memory_persister: None,
             hook_executor: None,
+            callback_dispatcher: None,
+            work_items: None,
+            budget_guard: None,
             steer_registry,
             channel_router: Arc::new(RwLock::new(None)),
             #[cfg(test)]
@@ -585,6 +605,9 @@ impl TaskRunner {
             start_time: Instant::now(),
             memory_persister: Some(MemoryPersister::new(memory_storage)),
             hook_executor: None,
+            callback_dispatcher: None,
+            work_items: None,
+            budget_guard: None,
             steer_registry,
             channel_router: Arc::new(RwLock::new(None)),
             #[cfg(test)]
@@ -604,6 +627,24 @@ impl TaskRunner {
         self
     }
 
+    /// Attach a dispatcher for outbound run-result callbacks.
+    pub fn with_callback_dispatcher(mut self, dispatcher: Arc<CallbackDispatcher>) -> Self {
+        self.callback_dispatcher = Some(dispatcher);
+        self
+    }
+
+    /// Attach work item storage so work item wake conditions can fire.
+    pub fn with_work_item_storage(mut self, work_items: WorkItemStorage) -> Self {
+        self.work_items = Some(work_items);
+        self
+    }
+
+    /// Refuse to start runs while a global LLM budget is exceeded.
+    pub fn with_budget_guard(mut self, budget_guard: Arc<BudgetGuard>) -> Self {
+        self.budget_guard = Some(budget_guard);
+        self
+    }
+
     /// Replace the internal channel-router handle with a shared pointer.
     ///
     /// This is useful when other runtime components (for example reply senders)
@@ -858,7 +899,7 @@ impl TaskRunner {
         // Recover tasks stuck in Running status from a previous daemon session.
         // When the daemon restarts, in-flight tasks lose their runtime context
         // but remain marked as Running in the database, preventing rescheduling.
-        self.recover_stale_running_tasks();
+        let resumable = self.recover_stale_running_tasks();
 
         let executor = Arc::new(RunnerTaskExecutor {
             runner: self.clone(),
@@ -873,11 +914,14 @@ impl TaskRunner {
         );
         worker_pool.start();
 
+        self.reconcile_interrupted_checkpoints(resumable).await;
+
         loop {
             tokio::select! {
                 _ = poll_interval.tick() => {
                     // Emit status pulse during each poll cycle
                     self.emit_heartbeat_pulse().await;
+                    self.process_wake_conditions().await;
                     self.check_and_run_tasks().await;
                 }
                 cmd = command_rx.recv() => {
@@ -969,9 +1013,13 @@ impl TaskRunner {
     ///
     /// This assumes a single active daemon per workspace/database. If multiple
     /// daemons operate on the same storage, this recovery strategy is unsafe.
-    fn recover_stale_running_tasks(&self) {
+    ///
+    /// Returns `(task_id, checkpoint_id)` pairs for interrupted runs that left
+    /// a checkpoint which can still be resumed.
+    fn recover_stale_running_tasks(&self) -> Vec<(String, String)> {
         let now = chrono::Utc::now().timestamp_millis();
         let mut recovered_task_ids = HashSet::new();
+        let mut resumable = Vec::new();
 
         match self.storage.list_active_task_runs() {
             Ok(runs) => {
@@ -994,6 +1042,9 @@ impl TaskRunner {
                         now,
                     ));
                     recovered_task_ids.insert(run.task_id.clone());
+                    if let Some(checkpoint_id) = self.resumable_checkpoint_id(&run, now) {
+                        resumable.push((run.task_id.clone(), checkpoint_id));
+                    }
                     if task.status == TaskStatus::Running
                         && let Err(err) = self.storage.resume_task(&task.id)
                     {
@@ -1016,7 +1067,7 @@ impl TaskRunner {
             Ok(t) => t,
             Err(e) => {
                 error!("Failed to list tasks for startup recovery: {}", e);
-                return;
+                return resumable;
             }
         };
 
@@ -1058,6 +1109,74 @@ impl TaskRunner {
                 e
             );
         }
+
+        resumable
+    }
+
+    /// Checkpoint left behind by an interrupted run, if it can still be resumed.
+    fn resumable_checkpoint_id(&self, run: &TaskRun, now: i64) -> Option<String> {
+        let checkpoint_id = run.checkpoint_id.as_deref()?;
+        match self.storage.load_checkpoint(checkpoint_id) {
+            Ok(Some(checkpoint))
+                if checkpoint.task_id.as_deref() == Some(run.task_id.as_str())
+                    && !checkpoint.is_resumed()
+                    && !checkpoint.is_expired(now) =>
+            {
+                Some(checkpoint.id)
+            }
+            Ok(_) => None,
+            Err(err) => {
+                warn!(
+                    "Failed to load checkpoint {} for interrupted run {}: {}",
+                    checkpoint_id, run.run_id, err
+                );
+                None
+            }
+        }
+    }
+
+    /// Resume or offer to resume runs interrupted by a restart.
+    ///
+    /// With `auto_resume` enabled the run restarts from its checkpoint right
+    /// away; otherwise a `resume_available` progress event tells clients which
+    /// checkpoint can be passed back through a resume request.
+    async fn reconcile_interrupted_checkpoints(&self, resumable: Vec<(String, String)>) {
+        for (task_id, checkpoint_id) in resumable {
+            if self.config.auto_resume {
+                info!(
+                    task_id = %task_id,
+                    checkpoint_id = %checkpoint_id,
+                    "Auto-resuming run interrupted by restart"
+                );
+                self.resume_from_checkpoint(
+                    &task_id,
+                    crate::models::ResumePayload {
+                        checkpoint_id,
+                        approved: true,
+                        user_message: None,
+                        metadata: serde_json::Value::Null,
+                    },
+                )
+                .await;
+            } else {
+                info!(
+                    task_id = %task_id,
+                    checkpoint_id = %checkpoint_id,
+                    "Run interrupted by restart can be resumed from checkpoint"
+                );
+                self.event_emitter
+                    .emit(TaskStreamEvent::progress(
+                        &task_id,
+                        "resume_available",
+                        None,
+                        Some(format!(
+                            "Interrupted by restart; resume from checkpoint {}",
+                            checkpoint_id
+                        )),
+                    ))
+                    .await;
+            }
+        }
     }
 
     /// Check for runnable tasks and execute them
@@ -1095,14 +1214,27 @@ impl TaskRunner {
             return;
         }
 
-        // Execute tasks up to available slots
-        for task in runnable_tasks.into_iter().take(available_slots) {
+        // Execute tasks up to available slots; tasks over their group or
+        // agent limits stay runnable and are retried on a later cycle.
+        let running = self.running_tasks.read().await.clone();
+        let mut usage = self.concurrency_usage(&running);
+        let mut started = 0;
+        for task in runnable_tasks {
+            if started >= available_slots {
+                break;
+            }
+            if !usage.admits(&task) {
+                debug!("Task {} deferred by concurrency limits", task.id);
+                continue;
+            }
             // Add to running set BEFORE enqueuing to prevent duplicates.
             let task_id = task.id.clone();
             let inserted = self.running_tasks.write().await.insert(task_id.clone());
             if !inserted {
                 continue;
             }
+            usage.record(&task);
+            started += 1;
             let (stop_tx, stop_rx) = oneshot::channel();
             self.stop_senders
                 .write()
@@ -1113,7 +1245,8 @@ impl TaskRunner {
                 .await
                 .insert(task.id.clone(), stop_rx);
 
-            if let Err(err) = self.task_queue.submit(task, TaskPriority::Normal).await {
+            let priority = TaskPriority::from(task.queue.priority);
+            if let Err(err) = self.task_queue.submit(task, priority).await {
                 warn!("Failed to enqueue task {}: {:?}", task_id, err);
                 self.cleanup_task_tracking(task_id.as_str()).await;
             }
@@ -1284,6 +1417,19 @@ impl TaskRunner {
             self.cleanup_runtime_tracking(&task_id_owned).await;
             return;
         }
+        // Resumed runs continue work that already held a slot.
+        if !resume_launch {
+            let mut running = self.running_tasks.read().await.clone();
+            running.remove(&task_id_owned);
+            if !self.concurrency_usage(&running).admits(&original_task) {
+                warn!(
+                    "Cannot run task {} - concurrency limits reached, deferring to scheduler",
+                    task_id
+                );
+                self.cleanup_runtime_tracking(&task_id_owned).await;
+                return;
+            }
+        }
 
         let resume_task_activated =
             match self.activate_resume_intent_for_launch(&task_id_owned).await {
@@ -1778,6 +1924,30 @@ impl TaskRunner {
             ));
         }
         run_handle.start().await;
+        let temporary_secret_names = self
+            .storage
+            .temporary_secrets()
+            .activate(&task.id, run_handle.run_id());
+        if !temporary_secret_names.is_empty() {
+            info!(
+                task_id = %task.id,
+                run_id = %run_handle.run_id(),
+                count = temporary_secret_names.len(),
+                "Bound temporary secrets to run"
+            );
+        }
+        // Wipe on every exit path, including panics, once the run is over.
+        let _temporary_secret_guard = scopeguard::guard(
+            (
+                self.storage.clone(),
+                task.id.clone(),
+                task.agent_id.clone(),
+                run_handle.run_id().to_string(),
+            ),
+            |(storage, task_id, agent_id, run_id)| {
+                storage.wipe_temporary_secrets(&task_id, &agent_id, &run_id);
+            },
+        );
         let finalizer = BackgroundRunFinalizer::new(
             self,
             task.clone(),
@@ -1835,6 +2005,25 @@ impl TaskRunner {
             return Ok(false);
         }
 
+        if let Some(budget_guard) = &self.budget_guard
+            && let Err(err) = budget_guard.check().await
+        {
+            let duration_ms = chrono::Utc::now().timestamp_millis() - start_time;
+            let error_msg = format!("Execution error: {}", err);
+
+            warn!("Task '{}' refused: {}", task.name, err);
+            pump_cancel.cancel();
+            if let Some(pump) = message_pump.take() {
+                let _ = pump.await;
+            }
+            finalizer
+                .finalize_failure(&error_msg, duration_ms, false)
+                .await;
+            self.clear_task_conversation_links(task_id).await;
+            self.cleanup_task_tracking(task_id).await;
+            return Ok(false);
+        }
+
         let broadcast_emitter = if matches!(task.execution_mode, ExecutionMode::Api)
             && task.notification.broadcast_steps
         {
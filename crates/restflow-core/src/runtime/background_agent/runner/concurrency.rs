@@ -0,0 +1,52 @@
+use super::*;
+
+/// Snapshot of in-flight runs per concurrency group and per agent.
+#[derive(Debug, Default)]
+pub(super) struct ConcurrencyUsage {
+    groups: HashMap<String, u32>,
+    agents: HashMap<String, u32>,
+}
+
+impl ConcurrencyUsage {
+    /// Whether starting `task` would stay within its group and agent limits.
+    pub(super) fn admits(&self, task: &Task) -> bool {
+        let limits = &task.concurrency;
+        if let (Some(group), Some(max)) = (limits.group.as_deref(), limits.max_group_runs)
+            && self.groups.get(group).copied().unwrap_or(0) >= max
+        {
+            return false;
+        }
+        if let Some(max) = limits.max_agent_runs
+            && self.agents.get(&task.agent_id).copied().unwrap_or(0) >= max
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Count `task` as running.
+    pub(super) fn record(&mut self, task: &Task) {
+        if let Some(group) = task.concurrency.group.as_ref() {
+            *self.groups.entry(group.clone()).or_default() += 1;
+        }
+        *self.agents.entry(task.agent_id.clone()).or_default() += 1;
+    }
+}
+
+impl TaskRunner {
+    /// Build concurrency usage from the tasks currently tracked as running.
+    pub(super) fn concurrency_usage(&self, running: &HashSet<String>) -> ConcurrencyUsage {
+        let mut usage = ConcurrencyUsage::default();
+        for task_id in running {
+            match self.storage.get_task(task_id) {
+                Ok(Some(task)) => usage.record(&task),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Failed to load running task {} for concurrency accounting: {}",
+                    task_id, e
+                ),
+            }
+        }
+        usage
+    }
+}
@@ -158,6 +158,15 @@ impl<'a> BackgroundRunFinalizer<'a> {
         self.runner
             .send_notification(&self.task, true, &exec_result.output)
             .await;
+        self.runner.dispatch_result_callbacks(
+            &self.task,
+            RunResultPayload::completed(
+                &self.task,
+                self.run_handle.run_id(),
+                &exec_result.output,
+                duration_ms,
+            ),
+        );
     }
 
     pub(super) async fn finalize_failure(
@@ -214,6 +223,10 @@ impl<'a> BackgroundRunFinalizer<'a> {
         self.runner
             .send_notification(&self.task, false, error_msg)
             .await;
+        self.runner.dispatch_result_callbacks(
+            &self.task,
+            RunResultPayload::failed(&self.task, self.run_handle.run_id(), error_msg, duration_ms),
+        );
     }
 
     pub(super) async fn finalize_timeout(
@@ -267,6 +280,10 @@ impl<'a> BackgroundRunFinalizer<'a> {
         self.runner
             .send_notification(&self.task, false, error_msg)
             .await;
+        self.runner.dispatch_result_callbacks(
+            &self.task,
+            RunResultPayload::failed(&self.task, self.run_handle.run_id(), error_msg, duration_ms),
+        );
     }
 
     pub(super) async fn finalize_interrupted(&self, reason: &str, duration_ms: i64) {
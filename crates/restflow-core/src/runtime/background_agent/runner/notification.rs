@@ -7,6 +7,21 @@ impl TaskRunner {
         }
     }
 
+    /// Push the run result to callbacks registered on the task.
+    ///
+    /// Reloads the task so callbacks registered while the run was in flight
+    /// (for example by a webhook trigger) are included.
+    pub(super) fn dispatch_result_callbacks(&self, task: &Task, payload: RunResultPayload) {
+        let Some(dispatcher) = &self.callback_dispatcher else {
+            return;
+        };
+        let latest = match self.storage.get_task(&task.id) {
+            Ok(Some(latest)) => latest,
+            _ => task.clone(),
+        };
+        dispatcher.dispatch(&latest, payload);
+    }
+
     /// Send notification for task completion/failure.
     ///
     /// Prefers broadcasting through ChannelRouter when available. Falls
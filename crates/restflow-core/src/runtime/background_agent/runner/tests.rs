@@ -2,8 +2,9 @@ use super::*;
 use crate::channel::{Channel, ChannelType, InboundMessage, OutboundMessage};
 use crate::hooks::{HookExecutor, HookTaskScheduler};
 use crate::models::{
-    AgentCheckpoint, BackgroundAgent, BackgroundAgentControlAction, BackgroundAgentStatus, Hook,
-    HookAction, HookEvent, MemoryScope, ResumePayload, TaskEventType, TaskSchedule,
+    AgentCheckpoint, BackgroundAgent, BackgroundAgentControlAction, BackgroundAgentStatus,
+    ConcurrencyLimits, Hook, HookAction, HookEvent, MemoryScope, ResumePayload, TaskEventType,
+    TaskSchedule, WakeCondition,
 };
 use crate::runtime::background_agent::{ChannelEventEmitter, StreamEventKind};
 use async_trait::async_trait;
@@ -827,6 +828,86 @@ async fn test_runner_respects_concurrency_limit() {
     );
 }
 
+#[tokio::test]
+async fn test_runner_respects_concurrency_group_limit() {
+    let (storage, _temp_dir) = create_test_storage();
+    let executor = Arc::new(MockExecutor::with_delay(200));
+    let notifier = Arc::new(NoopNotificationSender);
+
+    let past_time = chrono::Utc::now().timestamp_millis() - 1000;
+    for i in 0..3 {
+        let mut task = storage
+            .create_task(
+                format!("Deploy {}", i),
+                format!("agent-{}", i),
+                TaskSchedule::Once { run_at: past_time },
+            )
+            .unwrap();
+        task.input = Some(format!("Deploy input {}", i));
+        task.next_run_at = Some(past_time);
+        task.concurrency = ConcurrencyLimits {
+            group: Some("deploy".to_string()),
+            max_group_runs: Some(1),
+            max_agent_runs: None,
+        };
+        storage.update_task(&task).unwrap();
+    }
+
+    let config = RunnerConfig {
+        poll_interval_ms: 50,
+        max_concurrent_tasks: 4,
+        ..Default::default()
+    };
+
+    let steer_registry = Arc::new(SteerRegistry::new());
+    let runner = Arc::new(BackgroundAgentRunner::new(
+        storage.clone(),
+        executor.clone(),
+        notifier,
+        config,
+        steer_registry,
+    ));
+
+    let handle = runner.clone().start();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut max_running = 0;
+    while executor.call_count() < 2 {
+        max_running = max_running.max(runner.running_task_count().await);
+        assert!(
+            Instant::now() < deadline,
+            "group-limited tasks did not make progress"
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    handle.stop().await.unwrap();
+
+    assert_eq!(max_running, 1, "Only one deploy task may run at a time");
+}
+
+#[test]
+fn test_concurrency_usage_enforces_agent_limit() {
+    let mut task = Task::new(
+        "task-1".to_string(),
+        "Scrape".to_string(),
+        "agent-browser".to_string(),
+        TaskSchedule::default(),
+    );
+    task.concurrency.max_agent_runs = Some(2);
+
+    let mut usage = super::concurrency::ConcurrencyUsage::default();
+    assert!(usage.admits(&task));
+    usage.record(&task);
+    assert!(usage.admits(&task));
+    usage.record(&task);
+    assert!(!usage.admits(&task));
+
+    let mut other_agent = task.clone();
+    other_agent.agent_id = "agent-other".to_string();
+    assert!(usage.admits(&other_agent));
+}
+
 #[tokio::test]
 async fn test_runner_check_now() {
     let (storage, _temp_dir) = create_test_storage();
@@ -1444,6 +1525,124 @@ async fn test_resume_from_checkpoint_records_run_checkpoint_binding() {
     );
 }
 
+fn seed_interrupted_checkpoint_run(
+    storage: &Arc<BackgroundAgentStorage>,
+    name: &str,
+) -> (Task, String) {
+    let mut task = storage
+        .create_task(
+            name.to_string(),
+            "agent-001".to_string(),
+            TaskSchedule::default(),
+        )
+        .unwrap();
+    task.input = Some("Checkpoint task input".to_string());
+    task.status = BackgroundAgentStatus::Running;
+    storage.update_task(&task).unwrap();
+
+    let mut state = restflow_ai::AgentState::new("exec-before-restart".to_string(), 10);
+    state.iteration = 3;
+    state.add_message(restflow_ai::Message::user("resume me"));
+    let checkpoint = AgentCheckpoint::new(
+        state.execution_id.clone(),
+        Some(task.id.clone()),
+        state.version,
+        state.iteration,
+        serde_json::to_vec(&state).unwrap(),
+        "periodic_checkpoint".to_string(),
+    );
+    let checkpoint_id = checkpoint.id.clone();
+    storage.save_checkpoint(&checkpoint).unwrap();
+    storage
+        .start_task_run(
+            &task.id,
+            "run-before-restart",
+            "exec-before-restart",
+            chrono::Utc::now().timestamp_millis() - 60_000,
+            Some(checkpoint_id.clone()),
+        )
+        .unwrap();
+
+    (task, checkpoint_id)
+}
+
+#[tokio::test]
+async fn test_startup_auto_resumes_interrupted_run_from_checkpoint() {
+    let (storage, _temp_dir) = create_test_storage();
+    let executor = Arc::new(MockExecutor::new());
+    let (task, checkpoint_id) = seed_interrupted_checkpoint_run(&storage, "Auto Resume");
+
+    let runner = Arc::new(BackgroundAgentRunner::new(
+        storage.clone(),
+        executor.clone(),
+        Arc::new(NoopNotificationSender),
+        RunnerConfig {
+            auto_resume: true,
+            ..Default::default()
+        },
+        Arc::new(SteerRegistry::new()),
+    ));
+
+    let handle = runner.clone().start();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    handle.stop().await.unwrap();
+
+    assert_eq!(executor.resume_call_count(), 1);
+    let interrupted = storage.get_task_run("run-before-restart").unwrap().unwrap();
+    assert_eq!(
+        interrupted.status,
+        crate::models::BackgroundAgentRunStatus::Interrupted
+    );
+    let resumed = storage
+        .list_task_runs(&task.id)
+        .unwrap()
+        .into_iter()
+        .find(|run| run.run_id != "run-before-restart")
+        .expect("resumed run");
+    assert_eq!(
+        resumed.checkpoint_id.as_deref(),
+        Some(checkpoint_id.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_startup_offers_resume_when_auto_resume_disabled() {
+    let (storage, _temp_dir) = create_test_storage();
+    let executor = Arc::new(MockExecutor::new());
+    let (task, checkpoint_id) = seed_interrupted_checkpoint_run(&storage, "Offer Resume");
+    let (channel_emitter, mut event_rx) = ChannelEventEmitter::new();
+
+    let runner = Arc::new(
+        BackgroundAgentRunner::new(
+            storage.clone(),
+            executor.clone(),
+            Arc::new(NoopNotificationSender),
+            RunnerConfig::default(),
+            Arc::new(SteerRegistry::new()),
+        )
+        .with_event_emitter(Arc::new(channel_emitter)),
+    );
+
+    let handle = runner.clone().start();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    handle.stop().await.unwrap();
+
+    assert_eq!(executor.resume_call_count(), 0);
+    let checkpoint = storage.load_checkpoint(&checkpoint_id).unwrap().unwrap();
+    assert!(!checkpoint.is_resumed());
+
+    let mut offered = false;
+    while let Ok(event) = event_rx.try_recv() {
+        if event.task_id == task.id
+            && let StreamEventKind::Progress { phase, details, .. } = event.kind
+            && phase == "resume_available"
+        {
+            offered = details.is_some_and(|detail| detail.contains(&checkpoint_id));
+        }
+    }
+    assert!(offered);
+}
+
 #[tokio::test]
 async fn test_resume_from_checkpoint_start_task_run_failure_rolls_back_without_started_side_effects()
  {
@@ -1738,6 +1937,68 @@ async fn test_runner_skips_paused_tasks() {
     assert_eq!(executor.call_count(), 0);
 }
 
+#[tokio::test]
+async fn test_runner_wakes_paused_task_when_condition_fires() {
+    let (storage, _temp_dir) = create_test_storage();
+    let executor = Arc::new(MockExecutor::new());
+    let notifier = Arc::new(NoopNotificationSender);
+
+    let future_time = chrono::Utc::now().timestamp_millis() + 3600000;
+    let mut task = storage
+        .create_task(
+            "Waiting Task".to_string(),
+            "agent-001".to_string(),
+            TaskSchedule::Once {
+                run_at: future_time,
+            },
+        )
+        .unwrap();
+    task.input = Some("Waiting task input".to_string());
+    storage.update_task(&task).unwrap();
+    storage.pause_task(&task.id).unwrap();
+    storage
+        .register_wake_condition(
+            &task.id,
+            WakeCondition::TimeReached {
+                at: chrono::Utc::now().timestamp_millis() + 150,
+            },
+            None,
+        )
+        .unwrap();
+
+    let config = RunnerConfig {
+        poll_interval_ms: 100,
+        ..Default::default()
+    };
+
+    let steer_registry = Arc::new(SteerRegistry::new());
+    let runner = Arc::new(BackgroundAgentRunner::new(
+        storage.clone(),
+        executor.clone(),
+        notifier,
+        config,
+        steer_registry,
+    ));
+
+    let handle = runner.clone().start();
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+
+    handle.stop().await.unwrap();
+
+    assert_eq!(executor.call_count(), 1);
+    let stored = storage.get_task(&task.id).unwrap().unwrap();
+    assert!(stored.wake.is_none());
+    let messages = storage
+        .list_background_agent_messages(&task.id, 10)
+        .unwrap();
+    assert!(
+        messages
+            .iter()
+            .any(|message| message.message.contains("time_reached"))
+    );
+}
+
 #[test]
 fn test_render_input_template_replaces_known_placeholders() {
     let mut task = BackgroundAgent::new(
@@ -0,0 +1,151 @@
+use super::*;
+use crate::models::{ResumePayload, TaskWake, WakeCondition, WakeEvent};
+use crate::storage::background_agent::file_modified_at;
+use serde_json::json;
+use std::path::Path;
+
+impl TaskRunner {
+    /// Fire polled wake conditions of paused tasks and resume tasks whose
+    /// condition has fired (including ones fired externally, e.g. by a
+    /// webhook, or before a daemon restart).
+    pub(super) async fn process_wake_conditions(&self) {
+        let tasks = match self.storage.list_waiting_tasks() {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                error!("Failed to list tasks waiting for wake conditions: {}", e);
+                return;
+            }
+        };
+        let now = chrono::Utc::now().timestamp_millis();
+
+        for task in tasks {
+            let Some(wake) = task.wake.as_ref() else {
+                continue;
+            };
+            if wake.fired.is_none() {
+                let Some(event) = self.evaluate_wake_condition(&wake.condition, now) else {
+                    continue;
+                };
+                match self.storage.fire_wake_condition(&task.id, event) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(e) => {
+                        warn!("Failed to fire wake condition for task {}: {}", task.id, e);
+                        continue;
+                    }
+                }
+            }
+            self.wake_task(&task.id).await;
+        }
+    }
+
+    fn evaluate_wake_condition(&self, condition: &WakeCondition, now: i64) -> Option<WakeEvent> {
+        match condition {
+            // Fired by the webhook endpoint, never polled.
+            WakeCondition::WebhookReceived => None,
+            WakeCondition::TimeReached { at } => {
+                (now >= *at).then(|| WakeEvent::new(condition, json!({ "at": at })))
+            }
+            WakeCondition::FileChanged {
+                path,
+                baseline_modified_at,
+            } => {
+                let modified_at = file_modified_at(Path::new(path.trim()));
+                (modified_at != *baseline_modified_at).then(|| {
+                    WakeEvent::new(
+                        condition,
+                        json!({
+                            "path": path,
+                            "modified_at": modified_at,
+                            "previous_modified_at": baseline_modified_at,
+                        }),
+                    )
+                })
+            }
+            WakeCondition::WorkItemStatus { item_id, status } => {
+                let work_items = self.work_items.as_ref()?;
+                let item = match work_items.get_note(item_id) {
+                    Ok(item) => item?,
+                    Err(e) => {
+                        warn!("Failed to load work item {} for wake check: {}", item_id, e);
+                        return None;
+                    }
+                };
+                (item.status == *status).then(|| {
+                    WakeEvent::new(
+                        condition,
+                        json!({
+                            "item_id": item.id,
+                            "title": item.title,
+                            "status": item.status,
+                            "updated_at": item.updated_at,
+                        }),
+                    )
+                })
+            }
+        }
+    }
+
+    /// Resume a task whose wake condition fired, from its checkpoint when one
+    /// is available. The wake event reaches the run as a queued system message.
+    async fn wake_task(&self, task_id: &str) {
+        let wake = match self.storage.take_fired_wake(task_id) {
+            Ok(Some(wake)) => wake,
+            Ok(None) => return,
+            Err(e) => {
+                error!("Failed to consume wake event for task {}: {}", task_id, e);
+                return;
+            }
+        };
+        let metadata = wake
+            .fired
+            .as_ref()
+            .and_then(|event| serde_json::to_value(event).ok())
+            .unwrap_or_default();
+
+        match self.wake_checkpoint_id(task_id, &wake) {
+            Some(checkpoint_id) => {
+                info!(task_id = %task_id, checkpoint_id = %checkpoint_id, "Wake condition fired, resuming from checkpoint");
+                self.resume_from_checkpoint(
+                    task_id,
+                    ResumePayload {
+                        checkpoint_id,
+                        approved: true,
+                        user_message: None,
+                        metadata,
+                    },
+                )
+                .await;
+            }
+            None => {
+                info!(task_id = %task_id, "Wake condition fired, resuming task");
+                if let Err(e) = self.storage.resume_task(task_id) {
+                    error!("Failed to resume woken task {}: {}", task_id, e);
+                    return;
+                }
+                self.run_task_immediate(task_id).await;
+            }
+        }
+    }
+
+    /// Checkpoint a woken task resumes from: the registered one, otherwise the
+    /// task's latest checkpoint that is still resumable.
+    fn wake_checkpoint_id(&self, task_id: &str, wake: &TaskWake) -> Option<String> {
+        let checkpoint = match wake.checkpoint_id.as_deref() {
+            Some(checkpoint_id) => self.storage.load_checkpoint(checkpoint_id),
+            None => self.storage.load_checkpoint_by_task_id(task_id),
+        };
+        let checkpoint = match checkpoint {
+            Ok(checkpoint) => checkpoint?,
+            Err(e) => {
+                warn!("Failed to load wake checkpoint for task {}: {}", task_id, e);
+                return None;
+            }
+        };
+        let now = chrono::Utc::now().timestamp_millis();
+        (checkpoint.task_id.as_deref() == Some(task_id)
+            && !checkpoint.is_resumed()
+            && !checkpoint.is_expired(now))
+        .then_some(checkpoint.id)
+    }
+}
@@ -0,0 +1,424 @@
+//! Cron-scheduled browser plans.
+//!
+//! `BrowserScheduleRunner` polls `browser_schedule` triggers, runs the ones
+//! whose cron expression is due, and stores each result as a `Data`
+//! deliverable keyed by the trigger id. The previous deliverable is used to
+//! detect changes so monitoring jobs can notify only when the page changed.
+
+use crate::models::{
+    ActiveTrigger, BackgroundAgent, Deliverable, DeliverableType, TaskSchedule, TriggerConfig,
+};
+use crate::runtime::background_agent::NotificationSender;
+use crate::services::browser_webhook::{
+    BrowserWebhookOutcome, browser_service_for_settings, render_plan_actions, run_browser_plan,
+};
+use crate::storage::Storage;
+use anyhow::{Result, anyhow};
+use restflow_browser::BrowserService;
+use restflow_traits::floor_char_boundary;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const NOTIFICATION_PREVIEW_BYTES: usize = 3000;
+const RUN_METADATA_KEY: &str = "run";
+
+/// Result of one scheduled browser plan run.
+#[derive(Debug, Clone)]
+pub struct BrowserScheduleRun {
+    pub trigger_id: String,
+    pub outcome: BrowserWebhookOutcome,
+    /// Deliverable stored for a successful run.
+    pub deliverable: Option<Deliverable>,
+    /// Whether the result differs from the previous successful run.
+    pub changed: bool,
+}
+
+/// Polls browser schedule triggers and runs the due ones.
+pub struct BrowserScheduleRunner {
+    storage: Arc<Storage>,
+    notifier: Option<Arc<dyn NotificationSender>>,
+    browser: Option<Arc<BrowserService>>,
+    poll_interval: Duration,
+}
+
+impl BrowserScheduleRunner {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self {
+            storage,
+            notifier: None,
+            browser: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn with_notifier(mut self, notifier: Arc<dyn NotificationSender>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Run plans on `service` instead of a service built from the `[agent]`
+    /// browser settings for each run.
+    pub fn with_browser_service(mut self, service: Arc<BrowserService>) -> Self {
+        self.browser = Some(service);
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Spawn the polling loop. Abort the returned handle to stop it.
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let now = chrono::Utc::now().timestamp_millis();
+                if let Err(error) = self.run_due(now).await {
+                    warn!(error = %error, "Browser schedule poll failed");
+                }
+            }
+        })
+    }
+
+    /// Run every browser schedule trigger that is due at `now_ms`.
+    pub async fn run_due(&self, now_ms: i64) -> Result<Vec<BrowserScheduleRun>> {
+        let due: Vec<ActiveTrigger> = self
+            .storage
+            .triggers
+            .list_active_triggers()?
+            .into_iter()
+            .filter(|trigger| is_due(trigger, now_ms))
+            .collect();
+
+        let mut runs = Vec::with_capacity(due.len());
+        for mut trigger in due {
+            // Record the fire first so a slow or failing plan is not retried
+            // on every poll.
+            trigger.record_trigger();
+            self.storage.triggers.update_trigger(&trigger)?;
+            match self.run_trigger(&trigger).await {
+                Ok(run) => runs.push(run),
+                Err(error) => {
+                    warn!(trigger_id = %trigger.id, error = %error, "Scheduled browser plan failed");
+                }
+            }
+        }
+        Ok(runs)
+    }
+
+    async fn run_trigger(&self, trigger: &ActiveTrigger) -> Result<BrowserScheduleRun> {
+        let TriggerConfig::BrowserSchedule {
+            plan,
+            payload,
+            notify,
+            notify_only_on_change,
+            ..
+        } = &trigger.trigger_config
+        else {
+            return Err(anyhow!("Trigger {} is not a browser schedule", trigger.id));
+        };
+
+        let payload = payload
+            .clone()
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        let actions = render_plan_actions(plan, &payload)?;
+        let agent_settings = self
+            .storage
+            .config
+            .get_effective_config()
+            .map(|config| config.agent)
+            .unwrap_or_default();
+        let timeout_secs = plan
+            .timeout_secs
+            .unwrap_or(agent_settings.browser_timeout_secs);
+        let outcome = match &self.browser {
+            Some(service) => run_browser_plan(service, actions, timeout_secs).await?,
+            None => {
+                let service = browser_service_for_settings(&agent_settings)?;
+                run_browser_plan(&service, actions, timeout_secs).await?
+            }
+        };
+
+        if !outcome.success {
+            warn!(
+                trigger_id = %trigger.id,
+                error = outcome.error.as_deref().unwrap_or("unknown"),
+                "Scheduled browser plan did not complete"
+            );
+            return Ok(BrowserScheduleRun {
+                trigger_id: trigger.id.clone(),
+                outcome,
+                deliverable: None,
+                changed: false,
+            });
+        }
+
+        let content = serde_json::to_string_pretty(&outcome.results)?;
+        let previous = self
+            .storage
+            .deliverables
+            .list_by_task(&trigger.id)?
+            .into_iter()
+            .max_by_key(|deliverable| (deliverable.created_at, run_number(deliverable)));
+        let changed = previous.is_none_or(|previous| previous.content != content);
+        let deliverable = build_deliverable(trigger, content, changed);
+        self.storage.deliverables.save(&deliverable)?;
+        info!(trigger_id = %trigger.id, changed, "Scheduled browser plan stored result");
+
+        if *notify && (changed || !notify_only_on_change) {
+            self.notify(trigger, &deliverable, changed).await;
+        }
+
+        Ok(BrowserScheduleRun {
+            trigger_id: trigger.id.clone(),
+            outcome,
+            deliverable: Some(deliverable),
+            changed,
+        })
+    }
+
+    async fn notify(&self, trigger: &ActiveTrigger, deliverable: &Deliverable, changed: bool) {
+        let Some(notifier) = &self.notifier else {
+            return;
+        };
+        let status = if changed { "changed" } else { "unchanged" };
+        let preview = if deliverable.content.len() > NOTIFICATION_PREVIEW_BYTES {
+            let end = floor_char_boundary(&deliverable.content, NOTIFICATION_PREVIEW_BYTES);
+            format!("{}...", &deliverable.content[..end])
+        } else {
+            deliverable.content.clone()
+        };
+        let message = format!(
+            "Browser schedule '{}' result {status}:\n{preview}",
+            trigger.workflow_id
+        );
+        if let Err(error) = notifier.send_formatted(&message).await {
+            warn!(trigger_id = %trigger.id, error = %error, "Failed to send browser schedule notification");
+        }
+    }
+}
+
+fn is_due(trigger: &ActiveTrigger, now_ms: i64) -> bool {
+    let TriggerConfig::BrowserSchedule { cron, timezone, .. } = &trigger.trigger_config else {
+        return false;
+    };
+    let schedule = TaskSchedule::Cron {
+        expression: cron.clone(),
+        timezone: timezone.clone(),
+    };
+    let last_fire_ms = trigger
+        .last_triggered_at
+        .unwrap_or(trigger.activated_at)
+        .saturating_mul(1000);
+    BackgroundAgent::calculate_next_run(&schedule, last_fire_ms).is_some_and(|next| next <= now_ms)
+}
+
+/// Trigger fire count recorded on a deliverable, used to order results
+/// stored within the same millisecond.
+fn run_number(deliverable: &Deliverable) -> u64 {
+    deliverable
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(RUN_METADATA_KEY))
+        .and_then(|run| run.parse().ok())
+        .unwrap_or_default()
+}
+
+fn build_deliverable(trigger: &ActiveTrigger, content: String, changed: bool) -> Deliverable {
+    let metadata = BTreeMap::from([
+        ("source".to_string(), "browser_schedule".to_string()),
+        (
+            RUN_METADATA_KEY.to_string(),
+            trigger.trigger_count.to_string(),
+        ),
+        ("changed".to_string(), changed.to_string()),
+    ]);
+    Deliverable {
+        id: uuid::Uuid::new_v4().to_string(),
+        task_id: trigger.id.clone(),
+        execution_id: format!("{}-{}", trigger.id, trigger.trigger_count),
+        deliverable_type: DeliverableType::Data,
+        title: format!("{} result", trigger.workflow_id),
+        size_bytes: content.len(),
+        content,
+        file_path: None,
+        content_type: Some("application/json".to_string()),
+        created_at: chrono::Utc::now().timestamp_millis(),
+        metadata: Some(metadata),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BrowserPlan;
+    use async_trait::async_trait;
+    use restflow_browser::{
+        BrowserExecutionResult, BrowserExecutor, BrowserSession, RunActionsRequest,
+        RunScriptRequest, RuntimeProbe,
+    };
+    use serde_json::json;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    /// Returns the queued prices in order, repeating the last one.
+    struct PriceExecutor {
+        prices: Vec<&'static str>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl BrowserExecutor for PriceExecutor {
+        async fn probe_runtime(&self) -> Result<RuntimeProbe> {
+            Err(anyhow!("unexpected runtime probe"))
+        }
+
+        async fn run_script(
+            &self,
+            _session: &BrowserSession,
+            _request: &RunScriptRequest,
+        ) -> Result<BrowserExecutionResult> {
+            Err(anyhow!("unexpected script run"))
+        }
+
+        async fn run_actions(
+            &self,
+            _session: &BrowserSession,
+            _request: &RunActionsRequest,
+        ) -> Result<BrowserExecutionResult> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let price = self.prices[call.min(self.prices.len() - 1)];
+            Ok(BrowserExecutionResult {
+                runtime: "mock".to_string(),
+                exit_code: 0,
+                duration_ms: 1,
+                stdout: String::new(),
+                stderr: String::new(),
+                payload: Some(json!({
+                    "success": true,
+                    "result": [{ "type": "extract_text", "selector": ".price", "value": price }]
+                })),
+                trace_path: None,
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        messages: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl NotificationSender for RecordingNotifier {
+        async fn send(
+            &self,
+            _config: &crate::models::NotificationConfig,
+            _task: &BackgroundAgent,
+            _success: bool,
+            _message: &str,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send_formatted(&self, message: &str) -> Result<()> {
+            self.messages.lock().unwrap().push(message.to_string());
+            Ok(())
+        }
+    }
+
+    fn schedule_trigger(activated_at: i64) -> ActiveTrigger {
+        let mut trigger = ActiveTrigger::new(
+            "price-watch".to_string(),
+            TriggerConfig::BrowserSchedule {
+                cron: "0 * * * *".to_string(),
+                timezone: None,
+                plan: BrowserPlan {
+                    actions: json!([
+                        { "type": "navigate", "url": "${payload.url}" },
+                        { "type": "extract_text", "selector": ".price" }
+                    ]),
+                    timeout_secs: Some(5),
+                },
+                payload: Some(json!({ "url": "https://shop.example.com/item" })),
+                notify: true,
+                notify_only_on_change: true,
+            },
+        );
+        trigger.activated_at = activated_at;
+        trigger
+    }
+
+    #[test]
+    fn is_due_follows_cron_from_last_fire() {
+        let activated_at = 1_700_000_000; // 2023-11-14T22:13:20Z
+        let trigger = schedule_trigger(activated_at);
+        let next_hour_ms = 1_700_002_800_000; // 23:00:00Z
+        assert!(!is_due(&trigger, next_hour_ms - 1));
+        assert!(is_due(&trigger, next_hour_ms));
+
+        let mut fired = trigger.clone();
+        fired.last_triggered_at = Some(next_hour_ms / 1000);
+        assert!(!is_due(&fired, next_hour_ms + 60_000));
+
+        let manual = ActiveTrigger::new("manual".to_string(), TriggerConfig::Manual);
+        assert!(!is_due(&manual, i64::MAX));
+    }
+
+    #[tokio::test]
+    async fn run_due_stores_deliverables_and_notifies_on_change_only() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("test.db");
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let executor = Arc::new(PriceExecutor {
+            prices: vec!["$10", "$10", "$12"],
+            calls: AtomicUsize::new(0),
+        });
+        let browser = Arc::new(
+            BrowserService::new_with_executor(temp.path().join("browser"), executor).unwrap(),
+        );
+        let notifier = Arc::new(RecordingNotifier::default());
+        let runner = BrowserScheduleRunner::new(storage.clone())
+            .with_browser_service(browser)
+            .with_notifier(notifier.clone());
+
+        let trigger = schedule_trigger(0);
+        storage.triggers.activate_trigger(&trigger).unwrap();
+
+        let mut now = chrono::Utc::now().timestamp_millis();
+        let mut changes = Vec::new();
+        for _ in 0..3 {
+            let runs = runner.run_due(now).await.unwrap();
+            assert_eq!(runs.len(), 1);
+            changes.push(runs[0].changed);
+            // Move past the next hourly fire.
+            now += 3_600_000;
+        }
+        assert_eq!(changes, vec![true, false, true]);
+
+        let mut deliverables = storage.deliverables.list_by_task(&trigger.id).unwrap();
+        deliverables.sort_by_key(run_number);
+        assert_eq!(deliverables.len(), 3);
+        assert!(deliverables[2].content.contains("$12"));
+        assert_eq!(
+            deliverables[1]
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get("changed"))
+                .map(String::as_str),
+            Some("false")
+        );
+
+        let messages = notifier.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[1].contains("$12"));
+    }
+}
@@ -4,13 +4,14 @@
 //! ChatDispatcher processes it through an AI agent and returns the response.
 
 use anyhow::{Result, anyhow};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex as TokioMutex;
 use tracing::{debug, error, info, warn};
 
 use crate::auth::AuthProfileManager;
 use crate::channel::{
-    ChannelReplySender, ChannelRouter, ChannelType, InboundMessage, OutboundMessage,
+    ChannelReplySender, ChannelRouter, ChannelType, InboundMessage, OutboundMessage, RouteResolver,
 };
 use crate::models::{
     ChannelSessionBinding, ChatMessage, ChatSession, ChatSessionSource, MessageExecution, ModelId,
@@ -18,21 +19,23 @@ use crate::models::{
 use crate::process::ProcessRegistry;
 use crate::runtime::background_agent::{AgentRuntimeExecutor, SessionInputMode};
 use crate::runtime::channel::{
-    build_turn_persistence_payload, detect_voice_message, hydrate_voice_message_metadata,
-    preprocess_voice_message,
+    StreamPreview, build_turn_persistence_payload, detect_voice_message,
+    hydrate_voice_message_metadata, preprocess_voice_message, synthesize_voice_reply,
 };
 use crate::runtime::orchestrator::{
     AgentOrchestratorImpl, InteractiveExecutionError, InteractiveSessionRequest,
 };
-use restflow_ai::StreamDisplayMode;
 use crate::runtime::output::{ensure_success_output, format_error_output};
+use crate::services::budget::BudgetGuard;
 use crate::services::session::{PersistInteractiveTurnRequest, SessionService};
 use crate::storage::Storage;
 use crate::telemetry::{build_execution_trace_sink, emit_message};
+use restflow_ai::StreamDisplayMode;
 use restflow_storage::AgentDefaults;
 use restflow_traits::DEFAULT_CHAT_MAX_SESSION_HISTORY;
 
 use super::debounce::MessageDebouncer;
+use super::persona::{ChannelPersona, MarkdownDialect};
 use restflow_ai::agent::{SubagentConfig, SubagentDefLookup, SubagentTracker};
 
 /// Configuration for the ChatDispatcher.
@@ -48,6 +51,24 @@ pub struct ChatDispatcherConfig {
     pub send_typing_indicator: bool,
     /// Default agent name to use when none is specified.
     pub default_agent_name: String,
+    /// Reply formatting and tone profiles keyed by channel.
+    ///
+    /// Channels without an entry get unconstrained replies.
+    pub channel_personas: HashMap<ChannelType, ChannelPersona>,
+    /// Also answer voice messages with a synthesized voice note.
+    ///
+    /// The text reply is always sent; the voice note follows it on channels
+    /// that support voice messages.
+    pub voice_replies: bool,
+}
+
+impl ChatDispatcherConfig {
+    /// Persona for `channel_type`, if one is configured.
+    pub fn persona_for(&self, channel_type: ChannelType) -> Option<&ChannelPersona> {
+        self.channel_personas
+            .get(&channel_type)
+            .filter(|persona| !persona.is_empty())
+    }
 }
 
 impl Default for ChatDispatcherConfig {
@@ -57,6 +78,17 @@ impl Default for ChatDispatcherConfig {
             response_timeout_secs: None,
             send_typing_indicator: true,
             default_agent_name: "default".to_string(),
+            channel_personas: HashMap::from([
+                (ChannelType::Telegram, ChannelPersona::terse()),
+                (
+                    ChannelType::Slack,
+                    ChannelPersona {
+                        markdown: MarkdownDialect::SlackMrkdwn,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            voice_replies: false,
         }
     }
 }
@@ -78,6 +110,8 @@ pub enum ChatError {
     RateLimited,
     /// Timeout.
     Timeout,
+    /// A global LLM budget has been reached.
+    BudgetExceeded(String),
 }
 
 const MAX_USER_ERROR_DETAIL_CHARS: usize = 280;
@@ -116,6 +150,9 @@ impl ChatError {
             Self::NoApiKey { .. } => "API key not configured. Please add your API key in settings.",
             Self::RateLimited => "Too many requests. Please wait a moment and try again.",
             Self::Timeout => "AI response timed out. Please try again or simplify your question.",
+            Self::BudgetExceeded(_) => {
+                "LLM budget exceeded. Raise the budget in settings to continue."
+            }
             Self::ExecutionFailed(_) | Self::SessionError(_) | Self::VoicePreprocessFailed(_) => {
                 "An error occurred while processing your message. Please try again."
             }
@@ -137,6 +174,7 @@ impl ChatError {
                     summarize_error_detail(detail)
                 )
             }
+            Self::BudgetExceeded(detail) => detail.clone(),
             _ => self.user_message().to_string(),
         }
     }
@@ -154,6 +192,7 @@ impl std::fmt::Display for ChatError {
             Self::NoApiKey { provider } => write!(f, "No API key for provider: {}", provider),
             Self::RateLimited => write!(f, "Rate limited"),
             Self::Timeout => write!(f, "Response timeout"),
+            Self::BudgetExceeded(msg) => write!(f, "Budget exceeded: {}", msg),
         }
     }
 }
@@ -180,6 +219,7 @@ pub struct ChatSessionManager {
     /// from being created under concurrent requests for the same conversation.
     session_creation_mutex: TokioMutex<()>,
     default_agent_id: Option<String>,
+    route_resolver: Option<RouteResolver>,
     max_history: usize,
 }
 
@@ -191,6 +231,7 @@ impl ChatSessionManager {
             storage,
             session_creation_mutex: TokioMutex::new(()),
             default_agent_id: None,
+            route_resolver: None,
             max_history,
         }
     }
@@ -201,6 +242,14 @@ impl ChatSessionManager {
         self
     }
 
+    /// Route sessions to agents using peer/account/channel route bindings.
+    ///
+    /// A matching binding takes precedence over the default agent.
+    pub fn with_route_resolver(mut self, resolver: RouteResolver) -> Self {
+        self.route_resolver = Some(resolver);
+        self
+    }
+
     /// Get or create a session for a conversation.
     ///
     /// Sessions are keyed by conversation_id (e.g., Telegram chat ID).
@@ -210,6 +259,21 @@ impl ChatSessionManager {
         conversation_id: &str,
         user_id: &str,
     ) -> Result<ChatSession> {
+        self.get_or_create_routed_session(channel_type, "", conversation_id, user_id)
+            .await
+    }
+
+    /// Get or create a session for a conversation, resolving its agent from
+    /// the route bindings for `account_id` (workspace, guild or bot account).
+    pub async fn get_or_create_routed_session(
+        &self,
+        channel_type: ChannelType,
+        account_id: &str,
+        conversation_id: &str,
+        user_id: &str,
+    ) -> Result<ChatSession> {
+        let target_agent_id =
+            self.resolve_target_agent(channel_type, account_id, conversation_id, user_id);
         let source_channel = Self::source_from_channel(channel_type);
         let binding_channel = Self::binding_channel_key(channel_type);
 
@@ -222,7 +286,7 @@ impl ChatSessionManager {
             && let Some(mut session) =
                 self.lookup_session_from_binding(channel_key, conversation_id)?
         {
-            self.maybe_rebind_to_forced_default(&mut session)?;
+            self.maybe_rebind_agent(&mut session, target_agent_id.as_deref())?;
             debug!(
                 "Found session {} via channel binding for {:?} conversation {}",
                 session.id, channel_type, conversation_id
@@ -253,7 +317,7 @@ impl ChatSessionManager {
                     "Failed to backfill channel-session binding for existing source session"
                 );
             }
-            self.maybe_rebind_to_forced_default(&mut session)?;
+            self.maybe_rebind_agent(&mut session, target_agent_id.as_deref())?;
             debug!(
                 "Found existing session {} for {:?} conversation {}",
                 session.id, channel_type, conversation_id
@@ -295,7 +359,7 @@ impl ChatSessionManager {
                     "Failed to backfill channel-session binding for migrated legacy session"
                 );
             }
-            self.maybe_rebind_to_forced_default(&mut session)?;
+            self.maybe_rebind_agent(&mut session, target_agent_id.as_deref())?;
 
             debug!(
                 "Reused migrated legacy session {} for {:?} conversation {}",
@@ -305,7 +369,10 @@ impl ChatSessionManager {
         }
 
         // Create new session (we hold the mutex, so no race)
-        let agent_id = self.get_default_agent_id()?;
+        let agent_id = match target_agent_id {
+            Some(agent_id) => agent_id,
+            None => self.get_default_agent_id()?,
+        };
         let model = self.get_agent_model(&agent_id)?;
 
         let mut session = ChatSession::new(agent_id, model).with_name(conversation_id);
@@ -354,6 +421,7 @@ impl ChatSessionManager {
             ChannelType::Telegram => Some(ChatSessionSource::Telegram),
             ChannelType::Discord => Some(ChatSessionSource::Discord),
             ChannelType::Slack => Some(ChatSessionSource::Slack),
+            ChannelType::Matrix => Some(ChatSessionSource::Matrix),
             ChannelType::Email | ChannelType::Webhook => None,
         }
     }
@@ -363,6 +431,7 @@ impl ChatSessionManager {
             ChannelType::Telegram => Some("telegram"),
             ChannelType::Discord => Some("discord"),
             ChannelType::Slack => Some("slack"),
+            ChannelType::Matrix => Some("matrix"),
             ChannelType::Email | ChannelType::Webhook => None,
         }
     }
@@ -526,22 +595,64 @@ impl ChatSessionManager {
             .unwrap_or_else(|| ModelId::Gpt5.as_serialized_str().to_string()))
     }
 
-    fn maybe_rebind_to_forced_default(&self, session: &mut ChatSession) -> Result<()> {
-        let Some(default_agent_id) = self.default_agent_id.as_ref() else {
+    /// Agent a conversation should run on: the bound agent when a route
+    /// binding matches, otherwise the forced default agent (if any).
+    ///
+    /// Slack thread conversations (`channel:thread_ts`) resolve against their
+    /// parent channel so a whole channel can share one binding.
+    fn resolve_target_agent(
+        &self,
+        channel_type: ChannelType,
+        account_id: &str,
+        conversation_id: &str,
+        user_id: &str,
+    ) -> Option<String> {
+        let chat_id = match channel_type {
+            ChannelType::Slack => conversation_id
+                .split_once(':')
+                .map_or(conversation_id, |(channel, _)| channel),
+            _ => conversation_id,
+        };
+        let routed = self.route_resolver.as_ref().and_then(|resolver| {
+            resolver.resolve_route(channel_type, account_id, user_id, chat_id)
+        });
+        if let Some(route) = routed {
+            match self.storage.agents.get_agent(route.agent_id.clone()) {
+                Ok(Some(_)) => {
+                    debug!(
+                        agent_id = %route.agent_id,
+                        matched_by = ?route.matched_by,
+                        "Resolved channel route for {:?} conversation {}",
+                        channel_type,
+                        conversation_id
+                    );
+                    return Some(route.agent_id);
+                }
+                _ => warn!(
+                    agent_id = %route.agent_id,
+                    "Route binding points to a missing agent; using the default agent"
+                ),
+            }
+        }
+        self.default_agent_id.clone()
+    }
+
+    fn maybe_rebind_agent(&self, session: &mut ChatSession, agent_id: Option<&str>) -> Result<()> {
+        let Some(agent_id) = agent_id else {
             return Ok(());
         };
-        if session.agent_id == *default_agent_id {
+        if session.agent_id == agent_id {
             return Ok(());
         }
 
-        let model = self.get_agent_model(default_agent_id)?;
-        session.agent_id = default_agent_id.clone();
+        let model = self.get_agent_model(agent_id)?;
+        session.agent_id = agent_id.to_string();
         session.model = model.clone();
         session.metadata.last_model = Some(model);
 
         if let Err(err) = self.storage.chat_sessions.save(session) {
             warn!(
-                "Failed to persist forced default-agent rebind for session {}: {}",
+                "Failed to persist agent rebind for session {}: {}",
                 session.id, err
             );
         }
@@ -567,6 +678,7 @@ pub struct ChatDispatcher {
     subagent_tracker: Arc<SubagentTracker>,
     subagent_definitions: Arc<dyn SubagentDefLookup>,
     subagent_config: SubagentConfig,
+    budget_guard: Option<Arc<BudgetGuard>>,
 }
 
 impl ChatDispatcher {
@@ -593,9 +705,16 @@ impl ChatDispatcher {
             subagent_tracker,
             subagent_definitions,
             subagent_config,
+            budget_guard: None,
         }
     }
 
+    /// Refuse to reply while a global LLM budget is exceeded.
+    pub fn with_budget_guard(mut self, budget_guard: Arc<BudgetGuard>) -> Self {
+        self.budget_guard = Some(budget_guard);
+        self
+    }
+
     fn process_registry_for_executor(&self) -> Arc<ProcessRegistry> {
         let ttl_secs = match self.storage.config.get_effective_config() {
             Ok(config) => config.agent.process_session_ttl_secs,
@@ -755,11 +874,24 @@ impl ChatDispatcher {
             }
         };
 
+        if let Some(budget_guard) = &self.budget_guard
+            && let Err(error) = budget_guard.check().await
+        {
+            warn!(
+                "Refusing chat message for {}: {}",
+                message.conversation_id, error
+            );
+            self.send_error_response(message, ChatError::BudgetExceeded(error.to_string()))
+                .await?;
+            return Ok(());
+        }
+
         // 3. Get or create session
         let mut session = match self
             .sessions
-            .get_or_create_session(
+            .get_or_create_routed_session(
                 message.channel_type,
+                Self::account_id(message),
                 &message.conversation_id,
                 &message.sender_id,
             )
@@ -867,10 +999,26 @@ impl ChatDispatcher {
             &message.conversation_id,
             message.channel_type,
         ));
-        let executor = self.create_executor().with_reply_sender(reply_sender);
+        let persona = self.config.persona_for(message.channel_type);
+        let executor = self
+            .create_executor()
+            .with_reply_sender(reply_sender)
+            .with_system_prompt_section(persona.and_then(|persona| {
+                persona.system_prompt_section(message.channel_type.plugin_id())
+            }));
         self.maybe_send_acknowledgement(&executor, &mut session, &agent_input, input_mode, message)
             .await;
         let run_id = uuid::Uuid::new_v4().to_string();
+        let preview = self
+            .channel_router
+            .supports_message_editing(message.channel_type)
+            .then(|| {
+                StreamPreview::new(
+                    self.channel_router.clone(),
+                    message.channel_type,
+                    &message.conversation_id,
+                )
+            });
         let orchestrator = AgentOrchestratorImpl::from_runtime_executor(executor);
         let traced_execution = match orchestrator
             .run_traced_interactive_session_turn(InteractiveSessionRequest {
@@ -881,7 +1029,7 @@ impl ChatDispatcher {
                 run_id,
                 execution_trace_storage: self.storage.execution_traces.clone(),
                 timeout_secs: self.config.response_timeout_secs,
-                emitter: None,
+                emitter: preview.as_ref().map(StreamPreview::emitter),
                 steer_rx: None,
                 stream_display_mode: StreamDisplayMode::Buffered,
             })
@@ -929,6 +1077,10 @@ impl ChatDispatcher {
             "Processed the inbound message using the active agent, available tools, and session context.",
             &verification,
         );
+        let structured_output = match persona {
+            Some(persona) => persona.enforce(&structured_output),
+            None => structured_output,
+        };
 
         let (execution, final_persisted_input) = build_turn_persistence_payload(
             &self.storage.execution_traces,
@@ -981,11 +1133,22 @@ impl ChatDispatcher {
             .await;
         }
 
-        // 6. Send response (plain message without emoji prefix for AI chat)
-        let response = OutboundMessage::plain(&message.conversation_id, &structured_output);
-        self.channel_router
-            .send_to(message.channel_type, response)
-            .await?;
+        // 6. Send response (plain message without emoji prefix for AI chat),
+        // replacing the streamed preview in place when possible.
+        let delivered_by_preview = match &preview {
+            Some(preview) => preview.finish(&structured_output).await,
+            None => false,
+        };
+        if !delivered_by_preview {
+            let response = OutboundMessage::plain(&message.conversation_id, &structured_output);
+            self.channel_router
+                .send_to(message.channel_type, response)
+                .await?;
+        }
+
+        if self.config.voice_replies && voice_input.is_some() {
+            self.send_voice_reply(message, &structured_output).await;
+        }
 
         info!(
             "Chat response sent for session {} (output length: {} chars)",
@@ -996,6 +1159,36 @@ impl ChatDispatcher {
         Ok(())
     }
 
+    /// Follow a text reply with a synthesized voice note. Failures are only
+    /// logged since the text reply has already been delivered.
+    async fn send_voice_reply(&self, message: &InboundMessage, text: &str) {
+        let file_path = match synthesize_voice_reply(&self.storage, text).await {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("Failed to synthesize voice reply: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self
+            .channel_router
+            .send_voice_to(message.channel_type, &message.conversation_id, &file_path)
+            .await
+        {
+            warn!("Failed to send voice reply: {}", e);
+        }
+    }
+
+    /// Account identifier used for route bindings (Slack workspace, Discord
+    /// guild), taken from channel metadata.
+    fn account_id(message: &InboundMessage) -> &str {
+        message
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("account_id"))
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+    }
+
     fn build_ack_outbound_message(conversation_id: &str, content: &str) -> OutboundMessage {
         let mut response = OutboundMessage::new(conversation_id, content);
         // Ack text is generated dynamically and may include markdown-reserved
@@ -1161,6 +1354,21 @@ mod tests {
         assert!(config.send_typing_indicator);
     }
 
+    #[test]
+    fn test_config_default_personas() {
+        let config = ChatDispatcherConfig::default();
+        let telegram = config.persona_for(ChannelType::Telegram).unwrap();
+        assert!(telegram.max_length.is_some());
+        assert_eq!(
+            config
+                .persona_for(ChannelType::Slack)
+                .map(|persona| persona.markdown),
+            Some(MarkdownDialect::SlackMrkdwn)
+        );
+        assert!(config.persona_for(ChannelType::Discord).is_none());
+        assert!(!config.voice_replies);
+    }
+
     #[test]
     fn test_config_allows_explicit_timeout() {
         let config = ChatDispatcherConfig {
@@ -1182,6 +1390,11 @@ mod tests {
         );
         assert!(!ChatError::RateLimited.user_message().is_empty());
         assert!(!ChatError::Timeout.user_message().is_empty());
+        assert!(
+            !ChatError::BudgetExceeded("test".to_string())
+                .user_message()
+                .is_empty()
+        );
         assert!(
             !ChatError::ExecutionFailed("test".to_string())
                 .user_message()
@@ -1324,6 +1537,57 @@ mod tests {
         unsafe { std::env::remove_var("RESTFLOW_AGENTS_DIR") };
     }
 
+    #[tokio::test]
+    async fn test_session_manager_uses_route_bindings() {
+        let (storage, _temp_dir) = create_test_storage();
+
+        use crate::channel::RouteBindingType;
+        use crate::models::AgentNode;
+        let default_agent = storage
+            .agents
+            .create_agent("Default Agent".to_string(), AgentNode::new())
+            .unwrap();
+        let slack_agent = storage
+            .agents
+            .create_agent("Slack Agent".to_string(), AgentNode::new())
+            .unwrap();
+        let ops_agent = storage
+            .agents
+            .create_agent("Ops Agent".to_string(), AgentNode::new())
+            .unwrap();
+
+        let resolver = RouteResolver::new(Arc::new(storage.pairing.clone()));
+        resolver
+            .bind(RouteBindingType::Channel, "slack", &slack_agent.id)
+            .unwrap();
+        resolver
+            .bind(RouteBindingType::Group, "C-OPS", &ops_agent.id)
+            .unwrap();
+
+        let manager = ChatSessionManager::new(storage.clone(), 20)
+            .with_default_agent(default_agent.id.clone())
+            .with_route_resolver(RouteResolver::new(Arc::new(storage.pairing.clone())));
+
+        let slack = manager
+            .get_or_create_routed_session(ChannelType::Slack, "T1", "C-GENERAL", "U1")
+            .await
+            .unwrap();
+        assert_eq!(slack.agent_id, slack_agent.id);
+
+        // Threads inherit the binding of their parent channel.
+        let thread = manager
+            .get_or_create_routed_session(ChannelType::Slack, "T1", "C-OPS:1700.01", "U1")
+            .await
+            .unwrap();
+        assert_eq!(thread.agent_id, ops_agent.id);
+
+        let telegram = manager
+            .get_or_create_session(ChannelType::Telegram, "chat-1", "user-1")
+            .await
+            .unwrap();
+        assert_eq!(telegram.agent_id, default_agent.id);
+    }
+
     #[tokio::test]
     async fn test_session_manager_rebinds_external_legacy_source_channel() {
         let (storage, _temp_dir) = create_test_storage();
@@ -1,14 +1,18 @@
 //! Telegram/Channel Command Handler
 //!
-//! Handles command messages (/help, /tasks, /run, /status, /stop) from channels.
+//! Handles command messages (/help, /tasks, /run, /status, /stop, /approve,
+//! /deny) from channels.
 
-use crate::channel::{ChannelRouter, InboundMessage, MessageLevel, OutboundMessage};
+use crate::channel::{ChannelRouter, InboundMessage, InlineButton, MessageLevel, OutboundMessage};
 use crate::models::TaskStatus;
 use anyhow::Result;
 use tracing::debug;
 
 use super::trigger::TaskTrigger;
 
+/// Longest command that still fits in Telegram callback data (64 bytes).
+const MAX_BUTTON_ACTION_BYTES: usize = 64;
+
 /// Handle command messages
 ///
 /// Parses the command and executes the appropriate action.
@@ -35,6 +39,10 @@ pub async fn handle_command(
         }
         "/status" => cmd_status(router, trigger, message).await,
         "/stop" => cmd_stop(router, trigger, message).await,
+        "/approve" | "/deny" => {
+            let approved = command == "/approve";
+            cmd_approval(router, trigger, message, parts.get(1).copied(), approved).await
+        }
         _ => cmd_unknown(router, message, &command).await,
     }
 }
@@ -48,6 +56,8 @@ async fn cmd_help(router: &ChannelRouter, message: &InboundMessage) -> Result<()
 `/run <name>` - Run a task by name or ID
 `/status` - Show current status
 `/stop` - Stop active task
+`/approve [id]` - Approve the pending action of a task
+`/deny [id]` - Reject the pending action of a task
 `/help` - Show this help
 
 *During Task Execution:*
@@ -66,6 +76,7 @@ async fn cmd_list_tasks(
     let tasks = trigger.list_tasks().await?;
 
     let mut text = String::from("📋 *Tasks:*\n\n");
+    let mut buttons = Vec::new();
 
     if tasks.is_empty() {
         text.push_str("_No tasks configured._\n\nCreate one in the RestFlow app.");
@@ -78,15 +89,21 @@ async fn cmd_list_tasks(
                 TaskStatus::Failed => "❌",
                 TaskStatus::Paused => "⏸️",
                 TaskStatus::Interrupted => "⏸️",
+                TaskStatus::DeadLetter => "☠️",
             };
             text.push_str(&format!("{} `{}` - {}\n", status_emoji, task.id, task.name));
+            if task.status != TaskStatus::Running
+                && let Some(button) = command_button(format!("▶️ {}", task.name), "/run", &task.id)
+            {
+                buttons.push(vec![button]);
+            }
         }
         if tasks.len() > 10 {
             text.push_str(&format!("\n_...and {} more_", tasks.len() - 10));
         }
     }
 
-    let response = OutboundMessage::new(&message.conversation_id, text);
+    let response = OutboundMessage::new(&message.conversation_id, text).with_buttons(buttons);
     router.send_to(message.channel_type, response).await
 }
 
@@ -132,7 +149,8 @@ async fn cmd_run_task(
                     "🚀 Started: *{}*\n\nI'll send updates as the run progresses.",
                     task.name
                 ),
-            );
+            )
+            .with_buttons(vec![vec![InlineButton::new("⏹️ Stop", "/stop")]]);
             router.send_to(message.channel_type, response).await
         }
         Err(e) => {
@@ -199,6 +217,73 @@ async fn cmd_stop(
     router.send_to(message.channel_type, response).await
 }
 
+/// Approve or reject the pending action of a task
+///
+/// Uses the task linked to this conversation when no ID is given.
+async fn cmd_approval(
+    router: &ChannelRouter,
+    trigger: &dyn TaskTrigger,
+    message: &InboundMessage,
+    task_id: Option<&str>,
+    approved: bool,
+) -> Result<()> {
+    let task_id = match task_id {
+        Some(id) => Some(id.to_string()),
+        None => router
+            .get_conversation(&message.conversation_id)
+            .await
+            .and_then(|context| context.task_id),
+    };
+    let Some(task_id) = task_id else {
+        let response = OutboundMessage::new(
+            &message.conversation_id,
+            "⚠️ Usage: `/approve <id>` or `/deny <id>`\n\nNo active task in this conversation.",
+        )
+        .with_level(MessageLevel::Warning);
+        return router.send_to(message.channel_type, response).await;
+    };
+
+    let response = match trigger
+        .handle_background_agent_approval(&task_id, approved)
+        .await
+    {
+        Ok(true) if approved => {
+            OutboundMessage::success(&message.conversation_id, format!("Approved `{}`.", task_id))
+        }
+        Ok(true) => OutboundMessage::new(
+            &message.conversation_id,
+            format!("🚫 Rejected `{}`.", task_id),
+        ),
+        Ok(false) => OutboundMessage::warning(
+            &message.conversation_id,
+            format!("No pending approval for `{}`.", task_id),
+        ),
+        Err(e) => OutboundMessage::error(
+            &message.conversation_id,
+            format!("Failed to record approval: {}", e),
+        ),
+    };
+    router.send_to(message.channel_type, response).await
+}
+
+/// Approve/Deny buttons for a task's pending action.
+///
+/// Returns no rows when the task ID is too long to fit in a button.
+pub fn approval_buttons(task_id: &str) -> Vec<Vec<InlineButton>> {
+    match (
+        command_button("✅ Approve", "/approve", task_id),
+        command_button("🚫 Deny", "/deny", task_id),
+    ) {
+        (Some(approve), Some(deny)) => vec![vec![approve, deny]],
+        _ => Vec::new(),
+    }
+}
+
+fn command_button(label: impl Into<String>, command: &str, arg: &str) -> Option<InlineButton> {
+    let action = format!("{} {}", command, arg);
+    (action.len() <= MAX_BUTTON_ACTION_BYTES).then(|| InlineButton::new(label, action))
+}
+
 /// Handle unknown command
 async fn cmd_unknown(
     router: &ChannelRouter,
@@ -312,7 +397,16 @@ mod tests {
         let command = parts.first().map(|s| s.to_lowercase()).unwrap_or_default();
         assert!(!matches!(
             command.as_str(),
-            "/start" | "/help" | "/agents" | "/tasks" | "/list" | "/run" | "/status" | "/stop"
+            "/start"
+                | "/help"
+                | "/agents"
+                | "/tasks"
+                | "/list"
+                | "/run"
+                | "/status"
+                | "/stop"
+                | "/approve"
+                | "/deny"
         ));
     }
 
@@ -347,4 +441,70 @@ mod tests {
             .unwrap();
         assert_eq!(context.task_id, Some("task-1".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_approve_command_uses_linked_task() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut router = ChannelRouter::new();
+        router.register(CaptureChannel { sent: sent.clone() });
+
+        let trigger = MockTaskTrigger::new();
+        let message = create_message("/approve");
+        router
+            .record_conversation(&message, Some("task-9".to_string()))
+            .await;
+
+        handle_command(&router, &trigger, &message).await.unwrap();
+
+        assert_eq!(
+            *trigger.last_approval.lock().await,
+            Some(("task-9".to_string(), true))
+        );
+        let sent = sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].content.contains("Approved"));
+    }
+
+    #[tokio::test]
+    async fn test_deny_command_with_explicit_task_id() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut router = ChannelRouter::new();
+        router.register(CaptureChannel { sent: sent.clone() });
+
+        let trigger = MockTaskTrigger::new();
+        let message = create_message("/deny task-3");
+
+        handle_command(&router, &trigger, &message).await.unwrap();
+
+        assert_eq!(
+            *trigger.last_approval.lock().await,
+            Some(("task-3".to_string(), false))
+        );
+        assert!(sent.lock().await[0].content.contains("Rejected"));
+    }
+
+    #[tokio::test]
+    async fn test_approve_without_task_warns() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut router = ChannelRouter::new();
+        router.register(CaptureChannel { sent: sent.clone() });
+
+        let trigger = MockTaskTrigger::new();
+        let message = create_message("/approve");
+
+        handle_command(&router, &trigger, &message).await.unwrap();
+
+        assert!(trigger.last_approval.lock().await.is_none());
+        assert_eq!(sent.lock().await[0].level, MessageLevel::Warning);
+    }
+
+    #[test]
+    fn test_approval_buttons() {
+        let rows = approval_buttons("task-1");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][0].action, "/approve task-1");
+        assert_eq!(rows[0][1].action, "/deny task-1");
+
+        assert!(approval_buttons(&"x".repeat(MAX_BUTTON_ACTION_BYTES)).is_empty());
+    }
 }
@@ -4,7 +4,7 @@
 //! the task execution system. It handles:
 //!
 //! - Processing inbound messages from interactive channels (Telegram, etc.)
-//! - Routing commands (/help, /agents, /run, /status, /stop)
+//! - Routing commands (/help, /agents, /run, /status, /stop, /approve, /deny)
 //! - Dispatching natural language messages to AI chat
 //!
 //! # Architecture
@@ -64,7 +64,9 @@ mod chat_dispatcher;
 mod commands;
 mod debounce;
 mod handler;
+mod persona;
 mod router;
+mod stream_preview;
 mod trigger;
 mod turn_persistence;
 mod voice_preprocess;
@@ -77,11 +79,13 @@ pub use handler::{
     MessageHandlerConfig, MessageHandlerHandle, start_message_handler,
     start_message_handler_with_chat, start_message_handler_with_pairing,
 };
+pub use persona::{ChannelPersona, EmojiPolicy, MarkdownDialect};
 pub use router::{MessageRouter, RouteDecision};
+pub(crate) use stream_preview::StreamPreview;
 pub use trigger::{SystemStatus, TaskTrigger};
 pub(crate) use turn_persistence::build_turn_persistence_payload;
 pub(crate) use voice_preprocess::{
-    detect_voice_message, preprocess_voice_message, transcribe_media_file,
+    detect_voice_message, preprocess_voice_message, synthesize_voice_reply, transcribe_media_file,
 };
 pub(crate) use voice_transcript::{
     hydrate_voice_message_metadata, replace_latest_user_message_content,
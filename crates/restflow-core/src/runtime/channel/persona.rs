@@ -0,0 +1,276 @@
+//! Per-channel persona and formatting profiles.
+//!
+//! A [`ChannelPersona`] describes how replies should look on one channel. It
+//! is rendered into a system prompt section before execution and enforced on
+//! the final output before it is persisted and sent, so channel limits hold
+//! even when the model ignores the prompt.
+
+use serde::{Deserialize, Serialize};
+
+/// Markdown flavour understood by a channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkdownDialect {
+    /// CommonMark, left untouched.
+    #[default]
+    CommonMark,
+    /// Slack mrkdwn (`*bold*`, `<url|text>`).
+    SlackMrkdwn,
+    /// No markup; formatting markers are stripped.
+    Plain,
+}
+
+/// Whether replies may contain emoji.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmojiPolicy {
+    #[default]
+    Allow,
+    /// Prompt against emoji without removing them from the output.
+    Discourage,
+    /// Remove emoji from the output.
+    Strip,
+}
+
+/// Formatting and tone profile for replies on a channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelPersona {
+    /// Maximum reply length in characters.
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    #[serde(default)]
+    pub markdown: MarkdownDialect,
+    #[serde(default)]
+    pub emoji: EmojiPolicy,
+    /// Free-form tone guidance, one hint per entry.
+    #[serde(default)]
+    pub tone_hints: Vec<String>,
+}
+
+const TRUNCATION_MARKER: &str = "…";
+
+impl ChannelPersona {
+    /// Terse plain-text profile used for Telegram by default.
+    pub fn terse() -> Self {
+        Self {
+            max_length: Some(1500),
+            markdown: MarkdownDialect::Plain,
+            emoji: EmojiPolicy::Allow,
+            tone_hints: vec![
+                "Be brief: answer in a few short sentences or a short list.".to_string(),
+                "Skip preambles and recaps of the question.".to_string(),
+            ],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// System prompt section describing this profile, or `None` when the
+    /// profile imposes nothing.
+    pub fn system_prompt_section(&self, channel_name: &str) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut lines = vec![format!("## Reply Format ({channel_name})")];
+        if let Some(max_length) = self.max_length {
+            lines.push(format!(
+                "- Keep the reply under {max_length} characters; longer replies are cut off."
+            ));
+        }
+        match self.markdown {
+            MarkdownDialect::CommonMark => {}
+            MarkdownDialect::SlackMrkdwn => lines.push(
+                "- Use Slack mrkdwn: *bold*, _italic_, `code`, <url|text> links. No headings or tables."
+                    .to_string(),
+            ),
+            MarkdownDialect::Plain => lines.push(
+                "- Write plain text. Do not use Markdown headings, bold, tables or code fences."
+                    .to_string(),
+            ),
+        }
+        match self.emoji {
+            EmojiPolicy::Allow => {}
+            EmojiPolicy::Discourage | EmojiPolicy::Strip => {
+                lines.push("- Do not use emoji.".to_string())
+            }
+        }
+        lines.extend(self.tone_hints.iter().map(|hint| format!("- {hint}")));
+        Some(lines.join("\n"))
+    }
+
+    /// Apply the profile's output guardrails to a reply.
+    pub fn enforce(&self, output: &str) -> String {
+        let mut text = match self.markdown {
+            MarkdownDialect::CommonMark => output.to_string(),
+            MarkdownDialect::SlackMrkdwn => to_slack_mrkdwn(output),
+            MarkdownDialect::Plain => strip_markdown(output),
+        };
+        if self.emoji == EmojiPolicy::Strip {
+            text = strip_emoji(&text);
+        }
+        match self.max_length {
+            Some(max_length) => truncate_chars(&text, max_length),
+            None => text,
+        }
+    }
+}
+
+fn truncate_chars(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        return text.to_string();
+    }
+    let keep = max_length.saturating_sub(TRUNCATION_MARKER.chars().count());
+    let mut truncated: String = text.chars().take(keep).collect();
+    // Prefer cutting at a word boundary when one is reasonably close.
+    if let Some(boundary) = truncated.rfind(char::is_whitespace)
+        && truncated[..boundary].chars().count() * 5 >= keep * 4
+    {
+        truncated.truncate(boundary);
+    }
+    format!("{}{}", truncated.trim_end(), TRUNCATION_MARKER)
+}
+
+fn strip_markdown(text: &str) -> String {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            continue;
+        }
+        let line = heading_text(line).unwrap_or(line);
+        let line = if line.trim_start().starts_with("* ") {
+            line.replacen("* ", "- ", 1)
+        } else {
+            line.to_string()
+        };
+        lines.push(
+            rewrite_links(&line, |label, url| format!("{label} ({url})"))
+                .replace("**", "")
+                .replace("__", "")
+                .replace('`', ""),
+        );
+    }
+    lines.join("\n")
+}
+
+fn to_slack_mrkdwn(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = match heading_text(line) {
+                Some(heading) => format!("*{}*", heading.trim_end()),
+                None => line.to_string(),
+            };
+            rewrite_links(&line, |label, url| format!("<{url}|{label}>")).replace("**", "*")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Text of an ATX heading line (`## Title`), if `line` is one.
+fn heading_text(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start_matches('#');
+    rest.starts_with(' ').then(|| rest.trim_start())
+}
+
+/// Rewrite `[label](url)` links with `render`.
+fn rewrite_links(line: &str, render: impl Fn(&str, &str) -> String) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        let after = &rest[start + 1..];
+        let Some(label_end) = after.find("](") else {
+            break;
+        };
+        let url_part = &after[label_end + 2..];
+        let Some(url_end) = url_part.find(')') else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        output.push_str(&render(&after[..label_end], &url_part[..url_end]));
+        rest = &url_part[url_end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn is_emoji(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D | 0xE0020..=0xE007F
+    )
+}
+
+fn strip_emoji(text: &str) -> String {
+    let stripped: String = text.chars().filter(|ch| !is_emoji(*ch)).collect();
+    stripped
+        .lines()
+        .map(|line| {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let words: Vec<&str> = line.split_whitespace().collect();
+            format!("{indent}{}", words.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_persona_adds_no_prompt_and_keeps_output() {
+        let persona = ChannelPersona::default();
+        assert!(persona.system_prompt_section("discord").is_none());
+        assert_eq!(persona.enforce("**hi** 👋"), "**hi** 👋");
+    }
+
+    #[test]
+    fn prompt_section_lists_limits_and_hints() {
+        let section = ChannelPersona::terse()
+            .system_prompt_section("telegram")
+            .unwrap();
+        assert!(section.starts_with("## Reply Format (telegram)"));
+        assert!(section.contains("under 1500 characters"));
+        assert!(section.contains("plain text"));
+        assert!(section.contains("Be brief"));
+    }
+
+    #[test]
+    fn plain_dialect_strips_markup_and_inlines_links() {
+        let persona = ChannelPersona {
+            markdown: MarkdownDialect::Plain,
+            ..Default::default()
+        };
+        let output =
+            persona.enforce("## Result\n**Done**, see [docs](https://x.io).\n```\nls\n```");
+        assert_eq!(output, "Result\nDone, see docs (https://x.io).\nls");
+    }
+
+    #[test]
+    fn slack_dialect_rewrites_bold_and_links() {
+        let persona = ChannelPersona {
+            markdown: MarkdownDialect::SlackMrkdwn,
+            ..Default::default()
+        };
+        assert_eq!(
+            persona.enforce("# Title\n**bold** [site](https://x.io)"),
+            "*Title*\n*bold* <https://x.io|site>"
+        );
+    }
+
+    #[test]
+    fn strips_emoji_and_truncates_to_max_length() {
+        let persona = ChannelPersona {
+            max_length: Some(20),
+            emoji: EmojiPolicy::Strip,
+            ..Default::default()
+        };
+        assert_eq!(persona.enforce("Done ✅ 🎉"), "Done");
+        let truncated = persona.enforce("alpha beta gamma delta epsilon");
+        assert!(truncated.chars().count() <= 20);
+        assert_eq!(truncated, "alpha beta gamma…");
+    }
+}
@@ -0,0 +1,314 @@
+//! Live reply previews for channels that can edit sent messages.
+//!
+//! While the agent runs, streamed text is posted as a single message and
+//! edited in place at a throttled rate. When the turn finishes, the preview
+//! is replaced with the final reply so the chat shows one message instead
+//! of a trail of partial updates.
+
+use async_trait::async_trait;
+use restflow_ai::agent::StreamEmitter;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex as TokioMutex;
+use tracing::warn;
+
+use crate::channel::{ChannelRouter, ChannelType, OutboundMessage};
+
+/// Minimum time between two preview edits.
+const PREVIEW_EDIT_INTERVAL: Duration = Duration::from_millis(1500);
+/// Buffered characters required before the first preview is posted.
+const PREVIEW_MIN_CHARS: usize = 40;
+/// Final replies longer than this are sent as new messages instead of edits.
+const PREVIEW_MAX_CHARS: usize = 4096;
+const PREVIEW_CURSOR: &str = " ▌";
+
+struct PreviewState {
+    router: Arc<ChannelRouter>,
+    channel_type: ChannelType,
+    conversation_id: String,
+    text: String,
+    message_id: Option<String>,
+    last_update: Option<Instant>,
+    failed: bool,
+}
+
+impl PreviewState {
+    fn should_update(&self, now: Instant) -> bool {
+        if self.failed || self.text.trim().chars().count() < PREVIEW_MIN_CHARS {
+            return false;
+        }
+        self.last_update
+            .is_none_or(|last| now.duration_since(last) >= PREVIEW_EDIT_INTERVAL)
+    }
+
+    fn preview_text(&self) -> String {
+        let budget = PREVIEW_MAX_CHARS - PREVIEW_CURSOR.chars().count();
+        let count = self.text.chars().count();
+        let visible: String = if count > budget {
+            self.text.chars().skip(count - budget).collect()
+        } else {
+            self.text.clone()
+        };
+        format!("{}{}", visible.trim_end(), PREVIEW_CURSOR)
+    }
+
+    async fn update(&mut self) {
+        let content = self.preview_text();
+        let result = match self.message_id.clone() {
+            Some(message_id) => self
+                .router
+                .edit_message_in(
+                    self.channel_type,
+                    &self.conversation_id,
+                    &message_id,
+                    &content,
+                )
+                .await
+                .map(|()| Some(message_id)),
+            None => {
+                self.router
+                    .send_to_with_id(
+                        self.channel_type,
+                        OutboundMessage::plain(&self.conversation_id, content),
+                    )
+                    .await
+            }
+        };
+        self.last_update = Some(Instant::now());
+        match result {
+            Ok(Some(message_id)) => self.message_id = Some(message_id),
+            // The channel accepted the message but did not report an ID, so
+            // there is nothing to edit later.
+            Ok(None) => self.failed = true,
+            Err(error) => {
+                warn!(
+                    conversation_id = %self.conversation_id,
+                    error = %error,
+                    "Failed to update streaming preview; disabling previews for this turn"
+                );
+                self.failed = true;
+            }
+        }
+    }
+}
+
+/// Handle to a reply preview shared with its [`StreamPreviewEmitter`].
+#[derive(Clone)]
+pub(crate) struct StreamPreview {
+    state: Arc<TokioMutex<PreviewState>>,
+}
+
+impl StreamPreview {
+    pub(crate) fn new(
+        router: Arc<ChannelRouter>,
+        channel_type: ChannelType,
+        conversation_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            state: Arc::new(TokioMutex::new(PreviewState {
+                router,
+                channel_type,
+                conversation_id: conversation_id.into(),
+                text: String::new(),
+                message_id: None,
+                last_update: None,
+                failed: false,
+            })),
+        }
+    }
+
+    pub(crate) fn emitter(&self) -> Box<dyn StreamEmitter> {
+        Box::new(StreamPreviewEmitter {
+            state: self.state.clone(),
+        })
+    }
+
+    /// Replace the preview with the final reply.
+    ///
+    /// Returns `true` when the reply was delivered by editing the preview.
+    /// Returns `false` when no preview was posted, the reply is too long, or
+    /// the edit failed; the caller should then send the reply normally.
+    pub(crate) async fn finish(&self, final_text: &str) -> bool {
+        let mut state = self.state.lock().await;
+        state.failed = true;
+        let Some(message_id) = state.message_id.clone() else {
+            return false;
+        };
+        if final_text.chars().count() > PREVIEW_MAX_CHARS {
+            return false;
+        }
+        match state
+            .router
+            .edit_message_in(
+                state.channel_type,
+                &state.conversation_id,
+                &message_id,
+                final_text,
+            )
+            .await
+        {
+            Ok(()) => true,
+            Err(error) => {
+                warn!(
+                    conversation_id = %state.conversation_id,
+                    error = %error,
+                    "Failed to finalize streaming preview"
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Stream emitter that feeds text deltas into a [`StreamPreview`].
+pub(crate) struct StreamPreviewEmitter {
+    state: Arc<TokioMutex<PreviewState>>,
+}
+
+#[async_trait]
+impl StreamEmitter for StreamPreviewEmitter {
+    async fn emit_text_delta(&mut self, text: &str) {
+        let mut state = self.state.lock().await;
+        state.text.push_str(text);
+        if state.should_update(Instant::now()) {
+            state.update().await;
+        }
+    }
+
+    async fn emit_thinking_delta(&mut self, _text: &str) {}
+
+    async fn emit_tool_call_start(&mut self, _id: &str, _name: &str, _arguments: &str) {}
+
+    async fn emit_tool_call_result(
+        &mut self,
+        _id: &str,
+        _name: &str,
+        _result: &str,
+        _success: bool,
+    ) {
+    }
+
+    async fn emit_complete(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::{Channel, InboundMessage};
+    use anyhow::Result;
+    use futures::Stream;
+    use std::pin::Pin;
+
+    #[derive(Default)]
+    struct EditableChannel {
+        sent: TokioMutex<Vec<String>>,
+        edits: TokioMutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl Channel for EditableChannel {
+        fn channel_type(&self) -> ChannelType {
+            ChannelType::Telegram
+        }
+
+        fn is_configured(&self) -> bool {
+            true
+        }
+
+        async fn send(&self, message: OutboundMessage) -> Result<()> {
+            self.sent.lock().await.push(message.content);
+            Ok(())
+        }
+
+        async fn send_with_id(&self, message: OutboundMessage) -> Result<Option<String>> {
+            self.send(message).await?;
+            Ok(Some("tg_1".to_string()))
+        }
+
+        fn supports_message_editing(&self) -> bool {
+            true
+        }
+
+        async fn edit_message(
+            &self,
+            _conversation_id: &str,
+            message_id: &str,
+            content: &str,
+        ) -> Result<()> {
+            self.edits
+                .lock()
+                .await
+                .push((message_id.to_string(), content.to_string()));
+            Ok(())
+        }
+
+        async fn send_typing(&self, _conversation_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn start_receiving(&self) -> Option<Pin<Box<dyn Stream<Item = InboundMessage> + Send>>> {
+            None
+        }
+    }
+
+    fn preview_with_channel() -> (StreamPreview, Arc<ChannelRouter>) {
+        let mut router = ChannelRouter::new();
+        router.register(EditableChannel::default());
+        let router = Arc::new(router);
+        (
+            StreamPreview::new(router.clone(), ChannelType::Telegram, "chat-1"),
+            router,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_short_deltas_do_not_post_preview() {
+        let (preview, _router) = preview_with_channel();
+        let mut emitter = preview.emitter();
+        emitter.emit_text_delta("Hi").await;
+
+        assert!(preview.state.lock().await.message_id.is_none());
+        assert!(!preview.finish("Hi there").await);
+    }
+
+    #[tokio::test]
+    async fn test_preview_posted_then_replaced_by_final_reply() {
+        let (preview, _router) = preview_with_channel();
+        let mut emitter = preview.emitter();
+        emitter.emit_text_delta(&"word ".repeat(10)).await;
+        // Throttled: the second delta only extends the buffer.
+        emitter.emit_text_delta("more").await;
+
+        {
+            let state = preview.state.lock().await;
+            assert_eq!(state.message_id.as_deref(), Some("tg_1"));
+            assert!(state.text.ends_with("more"));
+        }
+        assert!(preview.finish("Final answer").await);
+    }
+
+    #[tokio::test]
+    async fn test_long_final_reply_is_not_edited() {
+        let (preview, _router) = preview_with_channel();
+        let mut emitter = preview.emitter();
+        emitter.emit_text_delta(&"word ".repeat(10)).await;
+
+        assert!(!preview.finish(&"a".repeat(PREVIEW_MAX_CHARS + 1)).await);
+    }
+
+    #[test]
+    fn test_preview_text_keeps_tail_within_limit() {
+        let state = PreviewState {
+            router: Arc::new(ChannelRouter::new()),
+            channel_type: ChannelType::Telegram,
+            conversation_id: "chat-1".to_string(),
+            text: format!("{}END", "a".repeat(PREVIEW_MAX_CHARS)),
+            message_id: None,
+            last_update: None,
+            failed: false,
+        };
+        let text = state.preview_text();
+        assert_eq!(text.chars().count(), PREVIEW_MAX_CHARS);
+        assert!(text.ends_with(&format!("END{}", PREVIEW_CURSOR)));
+    }
+}
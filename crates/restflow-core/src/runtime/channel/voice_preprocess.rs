@@ -2,8 +2,11 @@ use crate::runtime::agent::tools::secret_resolver_from_storage;
 use crate::storage::Storage;
 use anyhow::{Result, anyhow, bail};
 use restflow_tools::Tool;
+use restflow_tools::audio::speech::MAX_SPEECH_INPUT_CHARS;
+use restflow_tools::impls::speak::SpeakTool;
 use restflow_tools::impls::transcribe::{TranscribeConfig, TranscribeTool};
 use serde_json::{Value, json};
+use std::path::PathBuf;
 
 const VOICE_MEDIA_TYPE_LINE: &str = "media_type: voice";
 const FILE_PATH_PREFIX: &str = "local_file_path: ";
@@ -169,6 +172,36 @@ pub(crate) async fn transcribe_media_file(
     Ok(VoiceTranscriptionResult { text, model })
 }
 
+/// Synthesize an agent reply into an Ogg/Opus voice note in the media
+/// directory. Replies longer than the speech input limit are truncated.
+pub(crate) async fn synthesize_voice_reply(storage: &Storage, text: &str) -> Result<PathBuf> {
+    let resolver = secret_resolver_from_storage(storage);
+    let tool = SpeakTool::new(resolver);
+    let text = voice_reply_text(text);
+    let output = tool
+        .execute(json!({ "text": text, "format": "opus" }))
+        .await?;
+    if !output.success {
+        bail!(
+            "{}",
+            output
+                .error
+                .unwrap_or_else(|| "Speech synthesis failed".to_string())
+        );
+    }
+
+    output
+        .result
+        .get("file_path")
+        .and_then(|value| value.as_str())
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Speech synthesis returned no file path"))
+}
+
+fn voice_reply_text(text: &str) -> String {
+    text.trim().chars().take(MAX_SPEECH_INPUT_CHARS).collect()
+}
+
 fn parse_voice_message_content(content: &str) -> Option<ParsedVoiceMessage> {
     let header = content.lines().next()?.trim();
     if !header.starts_with(VOICE_HEADER_PREFIX) {
@@ -214,6 +247,16 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn voice_reply_text_is_trimmed_and_capped() {
+        assert_eq!(voice_reply_text("  hello \n"), "hello");
+        let long = "é".repeat(MAX_SPEECH_INPUT_CHARS + 10);
+        assert_eq!(
+            voice_reply_text(&long).chars().count(),
+            MAX_SPEECH_INPUT_CHARS
+        );
+    }
+
     #[test]
     fn detects_voice_message_from_metadata_without_media_context() {
         let descriptor = detect_voice_message(
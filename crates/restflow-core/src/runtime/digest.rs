@@ -0,0 +1,443 @@
+//! Scheduled digests and proactive notifications.
+//!
+//! `DigestRunner` polls `digest` triggers and, when their cron expression is
+//! due, sends a summary through the notification channel: a task progress
+//! standup or the memories agents saved since the previous digest.
+
+use crate::models::{
+    ActiveTrigger, BackgroundAgent, DigestKind, MemoryChunk, TaskSchedule, TaskStatus,
+    TriggerConfig,
+};
+use crate::runtime::background_agent::NotificationSender;
+use crate::storage::Storage;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use restflow_traits::floor_char_boundary;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_DIGEST_ITEMS: usize = 10;
+const ITEM_PREVIEW_BYTES: usize = 200;
+
+/// A digest that was built and handed to the notifier.
+#[derive(Debug, Clone)]
+pub struct DigestRun {
+    pub trigger_id: String,
+    pub message: String,
+    /// Whether the notifier accepted the message.
+    pub delivered: bool,
+}
+
+/// Polls digest triggers and sends the due ones.
+pub struct DigestRunner {
+    storage: Arc<Storage>,
+    notifier: Arc<dyn NotificationSender>,
+    poll_interval: Duration,
+}
+
+impl DigestRunner {
+    pub fn new(storage: Arc<Storage>, notifier: Arc<dyn NotificationSender>) -> Self {
+        Self {
+            storage,
+            notifier,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Spawn the polling loop. Abort the returned handle to stop it.
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let now = Utc::now().timestamp_millis();
+                if let Err(error) = self.run_due(now).await {
+                    warn!(error = %error, "Digest poll failed");
+                }
+            }
+        })
+    }
+
+    /// Send every digest trigger that is due at `now_ms`.
+    pub async fn run_due(&self, now_ms: i64) -> Result<Vec<DigestRun>> {
+        let due: Vec<ActiveTrigger> = self
+            .storage
+            .triggers
+            .list_active_triggers()?
+            .into_iter()
+            .filter(|trigger| is_due(trigger, now_ms))
+            .collect();
+
+        let mut runs = Vec::with_capacity(due.len());
+        for mut trigger in due {
+            let since_ms = last_fire_ms(&trigger);
+            // Record the fire first so a failing digest is not retried on
+            // every poll.
+            trigger.record_trigger();
+            self.storage.triggers.update_trigger(&trigger)?;
+
+            let message = match self.build_digest(&trigger, since_ms, now_ms) {
+                Ok(message) => message,
+                Err(error) => {
+                    warn!(trigger_id = %trigger.id, error = %error, "Failed to build digest");
+                    continue;
+                }
+            };
+            let delivered = match self.notifier.send_formatted(&message).await {
+                Ok(()) => true,
+                Err(error) => {
+                    warn!(trigger_id = %trigger.id, error = %error, "Failed to send digest");
+                    false
+                }
+            };
+            info!(trigger_id = %trigger.id, delivered, "Digest processed");
+            runs.push(DigestRun {
+                trigger_id: trigger.id.clone(),
+                message,
+                delivered,
+            });
+        }
+        Ok(runs)
+    }
+
+    fn build_digest(&self, trigger: &ActiveTrigger, since_ms: i64, now_ms: i64) -> Result<String> {
+        let TriggerConfig::Digest {
+            digest, agent_id, ..
+        } = &trigger.trigger_config
+        else {
+            return Err(anyhow!("Trigger {} is not a digest", trigger.id));
+        };
+
+        match digest {
+            DigestKind::TaskProgress => {
+                let tasks = match agent_id {
+                    Some(agent_id) => self
+                        .storage
+                        .background_agents
+                        .list_tasks_by_agent_id(agent_id)?,
+                    None => self.storage.background_agents.list_tasks()?,
+                };
+                Ok(task_progress_digest(
+                    &trigger.workflow_id,
+                    &tasks,
+                    since_ms,
+                    now_ms,
+                ))
+            }
+            DigestKind::MemoryHighlights => {
+                let chunks = match agent_id {
+                    Some(agent_id) => self.storage.memory.list_chunks(agent_id)?,
+                    None => self.storage.memory.list_all_chunks()?,
+                };
+                Ok(memory_highlights_digest(
+                    &trigger.workflow_id,
+                    chunks,
+                    since_ms,
+                ))
+            }
+        }
+    }
+}
+
+fn last_fire_ms(trigger: &ActiveTrigger) -> i64 {
+    trigger
+        .last_triggered_at
+        .unwrap_or(trigger.activated_at)
+        .saturating_mul(1000)
+}
+
+fn is_due(trigger: &ActiveTrigger, now_ms: i64) -> bool {
+    let TriggerConfig::Digest { cron, timezone, .. } = &trigger.trigger_config else {
+        return false;
+    };
+    let schedule = TaskSchedule::Cron {
+        expression: cron.clone(),
+        timezone: timezone.clone(),
+    };
+    BackgroundAgent::calculate_next_run(&schedule, last_fire_ms(trigger))
+        .is_some_and(|next| next <= now_ms)
+}
+
+/// Standup-style summary of background agent activity since `since_ms`.
+fn task_progress_digest(
+    name: &str,
+    tasks: &[BackgroundAgent],
+    since_ms: i64,
+    now_ms: i64,
+) -> String {
+    let count = |status: TaskStatus| tasks.iter().filter(|task| task.status == status).count();
+    let mut message = format!(
+        "📋 {name}: task progress since {}\n",
+        format_timestamp(since_ms)
+    );
+    message.push_str(&format!(
+        "Active {} · Running {} · Paused {} · Failed {}\n",
+        count(TaskStatus::Active),
+        count(TaskStatus::Running),
+        count(TaskStatus::Paused),
+        count(TaskStatus::Failed)
+    ));
+
+    let mut recent: Vec<&BackgroundAgent> = tasks
+        .iter()
+        .filter(|task| task.last_run_at.is_some_and(|run_at| run_at >= since_ms))
+        .collect();
+    recent.sort_by_key(|task| std::cmp::Reverse(task.last_run_at));
+    if recent.is_empty() {
+        message.push_str("\nNo runs since the last digest.\n");
+    } else {
+        message.push_str("\nRecent runs:\n");
+        for task in recent.iter().take(MAX_DIGEST_ITEMS) {
+            let marker = if task.status == TaskStatus::Failed {
+                "❌"
+            } else {
+                "✅"
+            };
+            message.push_str(&format!(
+                "{marker} {} ({} ok, {} failed)\n",
+                task.name, task.success_count, task.failure_count
+            ));
+            if task.status == TaskStatus::Failed
+                && let Some(error) = &task.last_error
+            {
+                message.push_str(&format!("   {}\n", preview(error)));
+            }
+        }
+        if recent.len() > MAX_DIGEST_ITEMS {
+            message.push_str(&format!(
+                "...and {} more\n",
+                recent.len() - MAX_DIGEST_ITEMS
+            ));
+        }
+    }
+
+    let mut upcoming: Vec<(&BackgroundAgent, i64)> = tasks
+        .iter()
+        .filter(|task| task.status == TaskStatus::Active)
+        .filter_map(|task| {
+            task.next_run_at
+                .filter(|next| *next >= now_ms)
+                .map(|next| (task, next))
+        })
+        .collect();
+    upcoming.sort_by_key(|(_, next)| *next);
+    if !upcoming.is_empty() {
+        message.push_str("\nUpcoming:\n");
+        for (task, next) in upcoming.iter().take(MAX_DIGEST_ITEMS) {
+            message.push_str(&format!(
+                "⏰ {} at {}\n",
+                task.name,
+                format_timestamp(*next)
+            ));
+        }
+    }
+    message.trim_end().to_string()
+}
+
+/// Newest memories saved since `since_ms`, excluding archived chunks.
+fn memory_highlights_digest(name: &str, chunks: Vec<MemoryChunk>, since_ms: i64) -> String {
+    let mut recent: Vec<MemoryChunk> = chunks
+        .into_iter()
+        .filter(|chunk| !chunk.is_archived() && chunk.created_at >= since_ms)
+        .collect();
+    recent.sort_by_key(|chunk| std::cmp::Reverse(chunk.created_at));
+
+    let mut message = format!(
+        "🧠 {name}: memory highlights since {}\n",
+        format_timestamp(since_ms)
+    );
+    if recent.is_empty() {
+        message.push_str("\nNo new memories.");
+        return message;
+    }
+    message.push_str(&format!("{} new memories\n\n", recent.len()));
+    for chunk in recent.iter().take(MAX_DIGEST_ITEMS) {
+        message.push_str(&format!("• {}", preview(&chunk.content)));
+        if !chunk.tags.is_empty() {
+            message.push_str(&format!(" [{}]", chunk.tags.join(", ")));
+        }
+        message.push('\n');
+    }
+    if recent.len() > MAX_DIGEST_ITEMS {
+        message.push_str(&format!(
+            "...and {} more\n",
+            recent.len() - MAX_DIGEST_ITEMS
+        ));
+    }
+    message.trim_end().to_string()
+}
+
+fn preview(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.len() > ITEM_PREVIEW_BYTES {
+        let end = floor_char_boundary(&text, ITEM_PREVIEW_BYTES);
+        format!("{}...", &text[..end])
+    } else {
+        text
+    }
+}
+
+fn format_timestamp(ms: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(ms)
+        .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| ms.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        messages: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl NotificationSender for RecordingNotifier {
+        async fn send(
+            &self,
+            _config: &crate::models::NotificationConfig,
+            _task: &BackgroundAgent,
+            _success: bool,
+            _message: &str,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send_formatted(&self, message: &str) -> Result<()> {
+            self.messages.lock().unwrap().push(message.to_string());
+            Ok(())
+        }
+    }
+
+    fn digest_trigger(digest: DigestKind, agent_id: Option<&str>) -> ActiveTrigger {
+        let mut trigger = ActiveTrigger::new(
+            "daily-standup".to_string(),
+            TriggerConfig::Digest {
+                cron: "0 9 * * *".to_string(),
+                timezone: None,
+                digest,
+                agent_id: agent_id.map(str::to_string),
+            },
+        );
+        trigger.activated_at = 0;
+        trigger
+    }
+
+    fn task(name: &str, status: TaskStatus, last_run_at: Option<i64>) -> BackgroundAgent {
+        let mut task = BackgroundAgent::new(
+            format!("task-{name}"),
+            name.to_string(),
+            "agent-1".to_string(),
+            TaskSchedule::default(),
+        );
+        task.status = status;
+        task.last_run_at = last_run_at;
+        task.next_run_at = None;
+        task
+    }
+
+    #[test]
+    fn is_due_follows_cron_from_last_fire() {
+        let mut trigger = digest_trigger(DigestKind::TaskProgress, None);
+        trigger.activated_at = 1_700_000_000; // 2023-11-14T22:13:20Z
+        let next_morning_ms = 1_700_038_800_000; // 2023-11-15T09:00:00Z
+        assert!(!is_due(&trigger, next_morning_ms - 1));
+        assert!(is_due(&trigger, next_morning_ms));
+
+        trigger.last_triggered_at = Some(next_morning_ms / 1000);
+        assert!(!is_due(&trigger, next_morning_ms + 3_600_000));
+
+        let manual = ActiveTrigger::new("manual".to_string(), TriggerConfig::Manual);
+        assert!(!is_due(&manual, i64::MAX));
+    }
+
+    #[test]
+    fn task_progress_digest_lists_recent_runs_and_failures() {
+        let since = 1_000;
+        let mut failed = task("Sync inbox", TaskStatus::Failed, Some(5_000));
+        failed.failure_count = 2;
+        failed.last_error = Some("IMAP login rejected".to_string());
+        let mut upcoming = task("Nightly report", TaskStatus::Active, Some(500));
+        upcoming.next_run_at = Some(10_000);
+        let tasks = vec![
+            task("Build docs", TaskStatus::Active, Some(2_000)),
+            failed,
+            upcoming,
+        ];
+
+        let message = task_progress_digest("standup", &tasks, since, 3_000);
+        assert!(message.contains("Active 2 · Running 0 · Paused 0 · Failed 1"));
+        assert!(message.contains("✅ Build docs"));
+        assert!(message.contains("❌ Sync inbox (0 ok, 2 failed)"));
+        assert!(message.contains("IMAP login rejected"));
+        assert!(!message.contains("✅ Nightly report"));
+        assert!(message.contains("⏰ Nightly report"));
+        // Newest run first.
+        assert!(message.find("Sync inbox") < message.find("Build docs"));
+    }
+
+    #[test]
+    fn memory_highlights_digest_skips_old_and_archived_chunks() {
+        let mut fresh = MemoryChunk::new("agent-1".to_string(), "Prefers short replies".into());
+        fresh.created_at = 2_000;
+        fresh.tags = vec!["preference".to_string()];
+        let mut old = MemoryChunk::new("agent-1".to_string(), "Old fact".into());
+        old.created_at = 10;
+        let mut archived = MemoryChunk::new("agent-1".to_string(), "Archived fact".into());
+        archived.created_at = 3_000;
+        archived.archived_at = Some(4_000);
+
+        let message = memory_highlights_digest("weekly", vec![old, fresh, archived], 1_000);
+        assert!(message.contains("1 new memories"));
+        assert!(message.contains("• Prefers short replies [preference]"));
+        assert!(!message.contains("Old fact"));
+        assert!(!message.contains("Archived fact"));
+
+        let empty = memory_highlights_digest("weekly", Vec::new(), 1_000);
+        assert!(empty.ends_with("No new memories."));
+    }
+
+    #[tokio::test]
+    async fn run_due_sends_each_digest_once_per_fire() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("test.db");
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let notifier = Arc::new(RecordingNotifier::default());
+        let runner = DigestRunner::new(storage.clone(), notifier.clone());
+
+        let trigger = digest_trigger(DigestKind::MemoryHighlights, Some("agent-1"));
+        storage.triggers.activate_trigger(&trigger).unwrap();
+        let chunk = MemoryChunk::new("agent-1".to_string(), "Deploys happen on Fridays".into());
+        storage.memory.store_chunk(&chunk).unwrap();
+
+        let now = Utc::now().timestamp_millis();
+        let runs = runner.run_due(now).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].delivered);
+        assert!(runs[0].message.contains("Deploys happen on Fridays"));
+
+        // Already fired for this period.
+        assert!(runner.run_due(now).await.unwrap().is_empty());
+
+        let stored = storage
+            .triggers
+            .get_active_trigger(&trigger.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.trigger_count, 1);
+        assert_eq!(notifier.messages.lock().unwrap().len(), 1);
+    }
+}
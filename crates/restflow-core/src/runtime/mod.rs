@@ -1,10 +1,13 @@
 pub mod agent;
 pub mod background_agent;
+pub mod browser_schedule;
 pub mod channel;
+pub mod digest;
 pub mod execution_context;
 pub mod orchestrator;
 mod output;
 pub mod subagent;
+pub mod webhook_sink;
 
 // Public surface rule:
 // - `restflow-core::runtime` re-exports durable runtime and core-owned adapters.
@@ -15,7 +18,7 @@ pub use agent::{
     SpawnSubagentTool, SpawnTool, TelegramTool, Tool, ToolRegistry, ToolRegistryBuilder,
     ToolResult, UseSkillTool, WaitSubagentsTool, build_agent_system_prompt, default_registry,
     effective_main_agent_tool_names, main_agent_default_tool_names, registry_from_allowlist,
-    secret_resolver_from_storage,
+    secret_resolver_for_task, secret_resolver_from_storage,
 };
 pub use background_agent::{
     AgentExecutor, AgentRuntimeExecutor, ExecutionResult, NoopHeartbeatEmitter,
@@ -23,14 +26,17 @@ pub use background_agent::{
     TaskEventEmitter, TaskRunner, TaskRunnerConfig, TaskRunnerHandle, TaskStreamEvent,
     TelegramNotifier,
 };
+pub use browser_schedule::BrowserScheduleRunner;
 pub use channel::{
-    ChatDispatcher, ChatDispatcherConfig, ChatError, ChatSessionManager, MessageDebouncer,
-    MessageHandlerConfig, MessageHandlerHandle, MessageRouter, RouteDecision, SystemStatus,
-    TaskTrigger, start_message_handler, start_message_handler_with_chat,
+    ChannelPersona, ChatDispatcher, ChatDispatcherConfig, ChatError, ChatSessionManager,
+    MessageDebouncer, MessageHandlerConfig, MessageHandlerHandle, MessageRouter, RouteDecision,
+    SystemStatus, TaskTrigger, start_message_handler, start_message_handler_with_chat,
 };
+pub use digest::DigestRunner;
 pub use execution_context::{ExecutionContext, ExecutionRole};
 pub use orchestrator::{AgentOrchestratorImpl, OrchestratingAgentExecutor};
 pub use restflow_telemetry::RestflowTrace;
 pub use subagent::{
     AgentDefinition, AgentDefinitionRegistry, StorageBackedSubagentLookup, builtin_agents,
 };
+pub use webhook_sink::WebhookSinkRunner;
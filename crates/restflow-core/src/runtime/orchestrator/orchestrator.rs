@@ -715,6 +715,9 @@ mod tests {
                                         duration_ms: Some(120),
                                         is_reasoning: None,
                                         message_count: Some(2),
+                                        cache_read_tokens: None,
+                                        cache_write_tokens: None,
+                                        cache_savings_usd: None,
                                     },
                                 ),
                                 &trace,
@@ -0,0 +1,565 @@
+//! Outbound webhook sinks for agent events.
+//!
+//! `WebhookSinkRunner` watches persisted task events and newly created
+//! deliverables, and POSTs each one as signed JSON to the webhook URLs
+//! configured under `[[channel.webhooks]]`. Delivery goes through the same
+//! signed POST as result callbacks, so receivers can verify both with the
+//! same code. Sinks pointing at loopback, private or metadata addresses are
+//! refused, both when configured and after DNS resolution at delivery time.
+
+use crate::models::{BackgroundAgentEvent, Deliverable};
+use crate::runtime::background_agent::CallbackRetryPolicy;
+pub use crate::runtime::background_agent::callbacks::WEBHOOK_EVENT_HEADER;
+use crate::runtime::background_agent::callbacks::{
+    SignedDeliveryOutcome, SignedRequest, deliver_signed, pinned_client,
+};
+use crate::storage::{Storage, WebhookSinkConfig};
+use anyhow::Result;
+use chrono::Utc;
+use restflow_traits::floor_char_boundary;
+use restflow_traits::network::validate_url;
+use restflow_traits::secret_ref::resolve_secret_refs;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex as TokioMutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_TEXT_BYTES: usize = 4000;
+const DELIVERABLE_CREATED_EVENT: &str = "deliverable.created";
+
+/// JSON body posted to webhook sinks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookEnvelope {
+    /// ID of the task event or deliverable; stable across retries.
+    pub id: String,
+    /// `task.<event_type>` or `deliverable.created`
+    pub event: String,
+    /// Milliseconds since epoch at which the event happened.
+    pub timestamp: i64,
+    pub task_id: String,
+    #[serde(default)]
+    pub task_name: Option<String>,
+    /// The task event or deliverable, with long text truncated.
+    pub data: serde_json::Value,
+}
+
+impl WebhookEnvelope {
+    fn from_task_event(event: &BackgroundAgentEvent, task_name: Option<String>) -> Self {
+        let mut event = event.clone();
+        event.output = event.output.as_deref().map(truncate_text);
+        let event_type = serde_json::to_value(&event.event_type)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        Self {
+            id: event.id.clone(),
+            event: format!("task.{event_type}"),
+            timestamp: event.timestamp,
+            task_id: event.task_id.clone(),
+            task_name,
+            data: serde_json::to_value(&event).unwrap_or_default(),
+        }
+    }
+
+    fn from_deliverable(deliverable: &Deliverable, task_name: Option<String>) -> Self {
+        let mut deliverable = deliverable.clone();
+        deliverable.content = truncate_text(&deliverable.content);
+        Self {
+            id: deliverable.id.clone(),
+            event: DELIVERABLE_CREATED_EVENT.to_string(),
+            timestamp: deliverable.created_at,
+            task_id: deliverable.task_id.clone(),
+            task_name,
+            data: serde_json::to_value(&deliverable).unwrap_or_default(),
+        }
+    }
+}
+
+fn truncate_text(text: &str) -> String {
+    if text.len() > MAX_TEXT_BYTES {
+        let end = floor_char_boundary(text, MAX_TEXT_BYTES);
+        format!("{}...", &text[..end])
+    } else {
+        text.to_string()
+    }
+}
+
+/// Whether `event` matches a sink's filter.
+///
+/// Patterns are exact names, `prefix.*`, or `*`. An empty filter matches
+/// every event.
+fn matches_filter(patterns: &[String], event: &str) -> bool {
+    patterns.is_empty()
+        || patterns
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => event.starts_with(prefix),
+                None => pattern == event,
+            })
+}
+
+/// A configured sink with its signing secret and URL references resolved.
+#[derive(Debug, Clone)]
+struct ResolvedSink {
+    url: String,
+    /// URL as configured, with `${secret:NAME}` references left in place;
+    /// used for logging.
+    label: String,
+    secret: Option<String>,
+    events: Vec<String>,
+}
+
+/// Events at or after `timestamp_ms` that have not been delivered yet.
+#[derive(Debug, Default)]
+struct PollCursor {
+    timestamp_ms: i64,
+    /// IDs already delivered with `timestamp == timestamp_ms`.
+    seen: HashSet<String>,
+}
+
+impl PollCursor {
+    fn is_new(&self, id: &str, timestamp: i64) -> bool {
+        timestamp > self.timestamp_ms || (timestamp == self.timestamp_ms && !self.seen.contains(id))
+    }
+
+    fn advance(&mut self, envelopes: &[WebhookEnvelope]) {
+        for envelope in envelopes {
+            if envelope.timestamp > self.timestamp_ms {
+                self.timestamp_ms = envelope.timestamp;
+                self.seen.clear();
+            }
+            if envelope.timestamp == self.timestamp_ms {
+                self.seen.insert(envelope.id.clone());
+            }
+        }
+    }
+}
+
+/// Polls task events and deliverables and forwards them to webhook sinks.
+pub struct WebhookSinkRunner {
+    storage: Arc<Storage>,
+    sinks: Vec<ResolvedSink>,
+    retry: CallbackRetryPolicy,
+    poll_interval: Duration,
+    cursor: TokioMutex<PollCursor>,
+}
+
+impl WebhookSinkRunner {
+    /// Build a runner for `sinks`, delivering events that happen from now on.
+    ///
+    /// Sinks whose signing secret cannot be found are skipped with a warning
+    /// rather than sent unsigned.
+    pub fn new(storage: Arc<Storage>, sinks: &[WebhookSinkConfig]) -> Self {
+        let sinks = sinks
+            .iter()
+            .filter_map(|sink| resolve_sink(&storage, sink))
+            .collect();
+
+        Self {
+            storage,
+            sinks,
+            retry: CallbackRetryPolicy::default(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            cursor: TokioMutex::new(PollCursor {
+                timestamp_ms: Utc::now().timestamp_millis(),
+                seen: HashSet::new(),
+            }),
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: CallbackRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Number of sinks that will receive events.
+    pub fn sink_count(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Spawn the polling loop. Abort the returned handle to stop it.
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                match self.poll().await {
+                    Ok(envelopes) => self.dispatch(envelopes),
+                    Err(error) => warn!(error = %error, "Webhook sink poll failed"),
+                }
+            }
+        })
+    }
+
+    /// Collect events persisted since the previous poll, oldest first.
+    pub async fn poll(&self) -> Result<Vec<WebhookEnvelope>> {
+        let mut cursor = self.cursor.lock().await;
+        let tasks = self.storage.background_agents.list_tasks()?;
+        let task_names: HashMap<String, String> = tasks
+            .iter()
+            .map(|task| (task.id.clone(), task.name.clone()))
+            .collect();
+
+        let mut envelopes = Vec::new();
+        for task in &tasks {
+            for event in self
+                .storage
+                .background_agents
+                .list_events_for_task(&task.id)?
+            {
+                if cursor.is_new(&event.id, event.timestamp) {
+                    envelopes.push(WebhookEnvelope::from_task_event(
+                        &event,
+                        Some(task.name.clone()),
+                    ));
+                }
+            }
+        }
+        for deliverable in self.storage.deliverables.list()? {
+            if cursor.is_new(&deliverable.id, deliverable.created_at) {
+                let task_name = task_names.get(&deliverable.task_id).cloned();
+                envelopes.push(WebhookEnvelope::from_deliverable(&deliverable, task_name));
+            }
+        }
+
+        envelopes.sort_by_key(|envelope| envelope.timestamp);
+        cursor.advance(&envelopes);
+        Ok(envelopes)
+    }
+
+    /// Deliver `envelopes` to every matching sink in the background.
+    ///
+    /// Each sink receives its events in order; a slow sink does not hold up
+    /// the others.
+    pub fn dispatch(&self, envelopes: Vec<WebhookEnvelope>) {
+        if envelopes.is_empty() {
+            return;
+        }
+        for sink in &self.sinks {
+            let matching: Vec<WebhookEnvelope> = envelopes
+                .iter()
+                .filter(|envelope| matches_filter(&sink.events, &envelope.event))
+                .cloned()
+                .collect();
+            if matching.is_empty() {
+                continue;
+            }
+            let sink = sink.clone();
+            let retry = self.retry.clone();
+            tokio::spawn(async move {
+                for envelope in matching {
+                    deliver(&retry, &sink, &envelope).await;
+                }
+            });
+        }
+    }
+}
+
+fn resolve_sink(storage: &Storage, sink: &WebhookSinkConfig) -> Option<ResolvedSink> {
+    let secret = match sink.signing_secret.as_deref() {
+        None => None,
+        Some(name) => match storage.secrets.get_secret(name) {
+            Ok(Some(secret)) => Some(secret),
+            Ok(None) => {
+                warn!(
+                    url = %sink.url,
+                    secret = name,
+                    "Webhook signing secret not found; sink disabled"
+                );
+                return None;
+            }
+            Err(error) => {
+                warn!(
+                    url = %sink.url,
+                    secret = name,
+                    error = %error,
+                    "Failed to read webhook signing secret; sink disabled"
+                );
+                return None;
+            }
+        },
+    };
+    let url = match resolve_secret_refs(&sink.url, |name| {
+        storage.secrets.get_secret(name).ok().flatten()
+    }) {
+        Ok(url) => url,
+        Err(error) => {
+            warn!(url = %sink.url, error = %error, "Failed to resolve webhook URL; sink disabled");
+            return None;
+        }
+    };
+    if let Err(error) = validate_url(&url) {
+        warn!(url = %sink.url, error = %error, "Webhook URL not allowed; sink disabled");
+        return None;
+    }
+    Some(ResolvedSink {
+        url,
+        label: sink.url.clone(),
+        secret,
+        events: sink.events.clone(),
+    })
+}
+
+async fn deliver(retry: &CallbackRetryPolicy, sink: &ResolvedSink, envelope: &WebhookEnvelope) {
+    let body = match serde_json::to_vec(envelope) {
+        Ok(body) => body,
+        Err(error) => {
+            warn!(event_id = %envelope.id, error = %error, "Failed to serialize webhook payload");
+            return;
+        }
+    };
+
+    let outcome = deliver_to_sink(retry, sink, envelope, &body).await;
+    if outcome.delivered {
+        debug!(url = %sink.label, event = %envelope.event, "Webhook delivered");
+    } else {
+        warn!(
+            url = %sink.label,
+            event = %envelope.event,
+            event_id = %envelope.id,
+            attempts = outcome.attempts,
+            error = outcome.error.as_deref().unwrap_or_default(),
+            "Webhook delivery failed"
+        );
+    }
+}
+
+/// Resolve the sink host, refuse internal addresses and POST through a
+/// client pinned to the validated address.
+async fn deliver_to_sink(
+    retry: &CallbackRetryPolicy,
+    sink: &ResolvedSink,
+    envelope: &WebhookEnvelope,
+    body: &[u8],
+) -> SignedDeliveryOutcome {
+    let http_client = match pinned_client(&sink.url).await {
+        Ok(client) => client,
+        Err(error) => {
+            return SignedDeliveryOutcome {
+                delivered: false,
+                attempts: 0,
+                status_code: None,
+                error: Some(error),
+            };
+        }
+    };
+    deliver_signed(
+        &http_client,
+        retry,
+        &SignedRequest {
+            url: &sink.url,
+            secret: sink.secret.as_deref(),
+            event: Some(envelope.event.as_str()),
+            body,
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DeliverableType, TaskEventType};
+    use tempfile::tempdir;
+
+    fn filters(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    #[test]
+    fn test_filter_patterns() {
+        assert!(matches_filter(&[], "task.completed"));
+        assert!(matches_filter(&filters(&["*"]), "deliverable.created"));
+        assert!(matches_filter(&filters(&["task.*"]), "task.failed"));
+        assert!(!matches_filter(
+            &filters(&["task.*"]),
+            "deliverable.created"
+        ));
+        assert!(matches_filter(
+            &filters(&["task.completed", "deliverable.created"]),
+            "deliverable.created"
+        ));
+        assert!(!matches_filter(
+            &filters(&["task.completed"]),
+            "task.completed_late"
+        ));
+    }
+
+    #[test]
+    fn test_envelope_names_and_truncates() {
+        let event = BackgroundAgentEvent::new("task-1".to_string(), TaskEventType::Completed)
+            .with_output("x".repeat(MAX_TEXT_BYTES + 10));
+        let envelope = WebhookEnvelope::from_task_event(&event, Some("Report".to_string()));
+        assert_eq!(envelope.event, "task.completed");
+        assert_eq!(envelope.id, event.id);
+        assert_eq!(envelope.task_name.as_deref(), Some("Report"));
+        assert_eq!(
+            envelope.data["output"].as_str().map(str::len),
+            Some(MAX_TEXT_BYTES + 3)
+        );
+
+        let event =
+            BackgroundAgentEvent::new("task-1".to_string(), TaskEventType::NotificationSent);
+        let envelope = WebhookEnvelope::from_task_event(&event, None);
+        assert_eq!(envelope.event, "task.notification_sent");
+    }
+
+    #[tokio::test]
+    async fn test_poll_returns_each_event_once() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("test.db");
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let task = storage
+            .background_agents
+            .create_task(
+                "Weekly report".to_string(),
+                "agent-1".to_string(),
+                crate::models::TaskSchedule::default(),
+            )
+            .unwrap();
+        let runner = WebhookSinkRunner::new(storage.clone(), &[]);
+        runner.cursor.lock().await.timestamp_ms = 0;
+
+        let mut started = BackgroundAgentEvent::new(task.id.clone(), TaskEventType::Started);
+        started.timestamp = 1_000;
+        let mut completed = BackgroundAgentEvent::new(task.id.clone(), TaskEventType::Completed);
+        completed.timestamp = 2_000;
+        storage.background_agents.add_event(&started).unwrap();
+        storage.background_agents.add_event(&completed).unwrap();
+        storage
+            .deliverables
+            .save(&Deliverable {
+                id: "deliverable-1".to_string(),
+                task_id: task.id.clone(),
+                execution_id: "run-1".to_string(),
+                deliverable_type: DeliverableType::Report,
+                title: "Summary".to_string(),
+                content: "All green".to_string(),
+                file_path: None,
+                content_type: Some("text/markdown".to_string()),
+                size_bytes: 9,
+                created_at: 2_000,
+                metadata: None,
+            })
+            .unwrap();
+
+        let envelopes = runner.poll().await.unwrap();
+        let events: Vec<&str> = envelopes.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], "task.started");
+        assert!(events.contains(&"deliverable.created"));
+        assert!(
+            envelopes
+                .iter()
+                .all(|envelope| envelope.task_name.as_deref() == Some("Weekly report"))
+        );
+
+        // Nothing new since the previous poll.
+        assert!(runner.poll().await.unwrap().is_empty());
+
+        let mut failed = BackgroundAgentEvent::new(task.id.clone(), TaskEventType::Failed);
+        failed.timestamp = 2_000;
+        storage.background_agents.add_event(&failed).unwrap();
+        let envelopes = runner.poll().await.unwrap();
+        assert_eq!(envelopes.len(), 1);
+        assert_eq!(envelopes[0].event, "task.failed");
+    }
+
+    #[test]
+    fn test_missing_signing_secret_disables_sink() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("test.db");
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let sinks = vec![
+            WebhookSinkConfig {
+                url: "https://example.com/hook".to_string(),
+                signing_secret: Some("MISSING_SECRET".to_string()),
+                events: Vec::new(),
+            },
+            WebhookSinkConfig {
+                url: "https://example.com/unsigned".to_string(),
+                signing_secret: None,
+                events: filters(&["task.*"]),
+            },
+        ];
+        let runner = WebhookSinkRunner::new(storage, &sinks);
+        assert_eq!(runner.sink_count(), 1);
+        assert_eq!(runner.sinks[0].url, "https://example.com/unsigned");
+    }
+
+    #[test]
+    fn test_sink_url_secret_references_are_resolved() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("test.db");
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        storage
+            .secrets
+            .set_secret("SLACK_HOOK_PATH", "T000/B000/XXXX", None)
+            .unwrap();
+        let sinks = vec![
+            WebhookSinkConfig {
+                url: "https://hooks.slack.com/services/${secret:SLACK_HOOK_PATH}".to_string(),
+                signing_secret: None,
+                events: Vec::new(),
+            },
+            WebhookSinkConfig {
+                url: "https://example.com/${secret:MISSING_PATH}".to_string(),
+                signing_secret: None,
+                events: Vec::new(),
+            },
+        ];
+        let runner = WebhookSinkRunner::new(storage, &sinks);
+        assert_eq!(runner.sink_count(), 1);
+        assert_eq!(
+            runner.sinks[0].url,
+            "https://hooks.slack.com/services/T000/B000/XXXX"
+        );
+        assert_eq!(runner.sinks[0].label, sinks[0].url);
+    }
+
+    #[tokio::test]
+    async fn test_internal_sink_urls_are_rejected() {
+        let temp = tempdir().unwrap();
+        let db_path = temp.path().join("test.db");
+        let storage = Arc::new(Storage::new(db_path.to_str().unwrap()).unwrap());
+        let internal = ["http://127.0.0.1/", "http://169.254.169.254/"];
+        let sinks: Vec<WebhookSinkConfig> = internal
+            .iter()
+            .map(|url| WebhookSinkConfig {
+                url: url.to_string(),
+                signing_secret: None,
+                events: Vec::new(),
+            })
+            .collect();
+        let runner = WebhookSinkRunner::new(storage, &sinks);
+        assert_eq!(runner.sink_count(), 0);
+
+        // Delivery re-checks the URL, so a sink that slipped past
+        // configuration is still never contacted.
+        let event = BackgroundAgentEvent::new("task-1".to_string(), TaskEventType::Completed);
+        let envelope = WebhookEnvelope::from_task_event(&event, None);
+        for url in internal {
+            let sink = ResolvedSink {
+                url: url.to_string(),
+                label: url.to_string(),
+                secret: None,
+                events: Vec::new(),
+            };
+            let outcome =
+                deliver_to_sink(&CallbackRetryPolicy::default(), &sink, &envelope, b"{}").await;
+            assert!(!outcome.delivered);
+            assert_eq!(outcome.attempts, 0);
+            assert!(outcome.error.is_some());
+        }
+    }
+}
@@ -29,7 +29,7 @@ use std::time::{Duration, Instant};
 use anyhow::{Result, anyhow, bail};
 use redb::Database;
 use regex::Regex;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 const MAX_REGEX_PATTERN_LEN: usize = 512;
@@ -250,8 +250,13 @@ pub struct SecurityAmendmentStore {
 
 impl SecurityAmendmentStore {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::SecurityAmendmentStorage::new(db)?,
+            inner: restflow_storage::SecurityAmendmentStorage::with_backend(backend)?,
         })
     }
 
@@ -0,0 +1,197 @@
+//! Audit trail for security gate decisions.
+//!
+//! `AuditedSecurityGate` wraps another gate and records every decision as a
+//! `security_decision` execution trace event, so allowed, blocked and
+//! approval-pending actions can be queried alongside tool calls and model
+//! switches.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use restflow_traits::security::{SecurityDecision, SecurityGate, ToolAction};
+use tracing::warn;
+
+use crate::models::SecurityDecisionTrace;
+use crate::models::execution_trace_builders;
+use crate::storage::ExecutionTraceStorage;
+
+/// Tool name recorded for shell command checks.
+const COMMAND_TOOL_NAME: &str = "shell";
+
+/// Security gate that records each decision of the wrapped gate.
+pub struct AuditedSecurityGate {
+    inner: Arc<dyn SecurityGate>,
+    traces: ExecutionTraceStorage,
+}
+
+impl AuditedSecurityGate {
+    pub fn new(inner: Arc<dyn SecurityGate>, traces: ExecutionTraceStorage) -> Self {
+        Self { inner, traces }
+    }
+
+    /// Wrap `inner` so its decisions are written to `traces`.
+    pub fn wrap(
+        inner: Arc<dyn SecurityGate>,
+        traces: ExecutionTraceStorage,
+    ) -> Arc<dyn SecurityGate> {
+        Arc::new(Self::new(inner, traces))
+    }
+
+    fn record(
+        &self,
+        task_id: &str,
+        agent_id: &str,
+        tool_name: &str,
+        operation: &str,
+        target: &str,
+        decision: &SecurityDecision,
+    ) {
+        let event = execution_trace_builders::security_decision(
+            task_id,
+            agent_id,
+            SecurityDecisionTrace {
+                tool_name: tool_name.to_string(),
+                operation: operation.to_string(),
+                target: target.to_string(),
+                allowed: decision.allowed,
+                requires_approval: decision.requires_approval,
+                approval_id: decision.approval_id.clone(),
+                reason: decision.reason.clone(),
+            },
+        );
+        if let Err(error) = self.traces.store(&event) {
+            warn!(error = %error, tool = tool_name, "Failed to record security decision");
+        }
+    }
+}
+
+#[async_trait]
+impl SecurityGate for AuditedSecurityGate {
+    async fn check_command(
+        &self,
+        command: &str,
+        task_id: &str,
+        agent_id: &str,
+        workdir: Option<&str>,
+    ) -> restflow_traits::error::Result<SecurityDecision> {
+        let decision = self
+            .inner
+            .check_command(command, task_id, agent_id, workdir)
+            .await?;
+        self.record(
+            task_id,
+            agent_id,
+            COMMAND_TOOL_NAME,
+            "execute",
+            command,
+            &decision,
+        );
+        Ok(decision)
+    }
+
+    async fn check_tool_action(
+        &self,
+        action: &ToolAction,
+        agent_id: Option<&str>,
+        task_id: Option<&str>,
+    ) -> restflow_traits::error::Result<SecurityDecision> {
+        let decision = self
+            .inner
+            .check_tool_action(action, agent_id, task_id)
+            .await?;
+        self.record(
+            task_id.unwrap_or_default(),
+            agent_id.unwrap_or_default(),
+            &action.tool_name,
+            &action.operation,
+            &action.target,
+            &decision,
+        );
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExecutionTraceCategory, ExecutionTraceQuery};
+
+    struct BlockDeletes;
+
+    #[async_trait]
+    impl SecurityGate for BlockDeletes {
+        async fn check_command(
+            &self,
+            command: &str,
+            _task_id: &str,
+            _agent_id: &str,
+            _workdir: Option<&str>,
+        ) -> restflow_traits::error::Result<SecurityDecision> {
+            if command.starts_with("rm ") {
+                return Ok(SecurityDecision::blocked(Some("rm is blocked".to_string())));
+            }
+            Ok(SecurityDecision::allowed(None))
+        }
+
+        async fn check_tool_action(
+            &self,
+            _action: &ToolAction,
+            _agent_id: Option<&str>,
+            _task_id: Option<&str>,
+        ) -> restflow_traits::error::Result<SecurityDecision> {
+            Ok(SecurityDecision::requires_approval(
+                "approval-1".to_string(),
+                None,
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn records_command_and_tool_action_decisions() {
+        let traces = ExecutionTraceStorage::in_memory().unwrap();
+        let gate = AuditedSecurityGate::wrap(Arc::new(BlockDeletes), traces.clone());
+
+        let decision = gate
+            .check_command("rm -rf build", "task-1", "agent-1", None)
+            .await
+            .unwrap();
+        assert!(!decision.allowed);
+        gate.check_tool_action(
+            &ToolAction {
+                tool_name: "http".to_string(),
+                operation: "get".to_string(),
+                target: "https://example.com".to_string(),
+                summary: "fetch".to_string(),
+            },
+            Some("agent-1"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let events = traces
+            .query(&ExecutionTraceQuery {
+                category: Some(ExecutionTraceCategory::SecurityDecision),
+                ..ExecutionTraceQuery::default()
+            })
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let command = events
+            .iter()
+            .filter_map(|event| event.security_decision.as_ref())
+            .find(|trace| trace.tool_name == COMMAND_TOOL_NAME)
+            .unwrap();
+        assert_eq!(command.target, "rm -rf build");
+        assert!(!command.allowed);
+        assert_eq!(command.reason.as_deref(), Some("rm is blocked"));
+
+        let action = events
+            .iter()
+            .filter_map(|event| event.security_decision.as_ref())
+            .find(|trace| trace.tool_name == "http")
+            .unwrap();
+        assert!(action.requires_approval);
+        assert_eq!(action.approval_id.as_deref(), Some("approval-1"));
+    }
+}
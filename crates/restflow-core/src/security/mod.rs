@@ -60,16 +60,22 @@
 
 mod amendments;
 mod approval;
+mod audit;
 mod cache;
 mod checker;
 mod config_store;
 mod path_resolver;
 mod shell_parser;
+mod tool_approval;
 
 pub use amendments::{
     AmendmentMatchType, AmendmentScope, SecurityAmendment, SecurityAmendmentStore,
 };
 pub use approval::{ApprovalCallback, ApprovalManager};
+pub use audit::AuditedSecurityGate;
 pub use cache::{ApprovalCache, ApprovalGrant, ApprovalKey, ApprovalScope};
 pub use checker::SecurityChecker;
 pub use config_store::SecurityConfigStore;
+pub use tool_approval::{
+    ToolApprovalScope, ToolApprovalWrapper, approval_steer_message, resolve_tool_approval,
+};
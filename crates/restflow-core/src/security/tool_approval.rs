@@ -0,0 +1,316 @@
+//! Per-tool approval policy enforcement.
+//!
+//! `ToolApprovalWrapper` decorates every tool of an agent run and checks each
+//! call against the stored `ToolApprovalPolicy`. Allowed calls run, blocked
+//! calls fail, and calls that need approval are queued as `PendingApproval`
+//! and returned as `pending_approval` results. The executor defers those
+//! calls until the user resolves them, then re-runs them with the approval
+//! ID, which lets the approved call through.
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use restflow_traits::{Tool, ToolErrorCategory, ToolOutput, ToolRegistry, ToolResult, ToolWrapper};
+use serde_json::{Value, json};
+use tracing::warn;
+
+use crate::daemon::session_events::{ChatSessionEvent, publish_session_event};
+use crate::models::{ApprovalStatus, PendingApproval, SecurityAction};
+use crate::storage::ToolApprovalStorage;
+
+/// Argument carrying the approval ID when a deferred call is re-run.
+const APPROVAL_ID_ARG: &str = "approval_id";
+
+/// Run a tool approval is requested from.
+#[derive(Debug, Clone, Default)]
+pub struct ToolApprovalScope {
+    pub agent_id: Option<String>,
+    pub task_id: Option<String>,
+    pub session_id: Option<String>,
+}
+
+/// Tool wrapper enforcing the stored tool approval policy.
+pub struct ToolApprovalWrapper {
+    storage: ToolApprovalStorage,
+    scope: ToolApprovalScope,
+}
+
+impl ToolApprovalWrapper {
+    pub fn new(storage: ToolApprovalStorage, scope: ToolApprovalScope) -> Self {
+        Self { storage, scope }
+    }
+
+    /// Wrap every tool in `registry` with the approval policy.
+    pub fn apply(self, registry: &mut ToolRegistry) {
+        let wrapper: Arc<dyn ToolWrapper> = Arc::new(self);
+        let names: Vec<String> = registry.list().into_iter().map(str::to_string).collect();
+        for name in names {
+            if let Some(tool) = registry.get(&name) {
+                registry.register_wrapped_arc(tool, vec![wrapper.clone()]);
+            }
+        }
+    }
+
+    /// Look up the approval a re-run call refers to, if it is one of ours.
+    fn approval_for_call(&self, tool_name: &str, input: &Value) -> Option<PendingApproval> {
+        let approval_id = input.get(APPROVAL_ID_ARG)?.as_str()?;
+        match self.storage.get(approval_id) {
+            Ok(approval) => {
+                approval.filter(|approval| approval.tool_name.as_deref() == Some(tool_name))
+            }
+            Err(error) => {
+                warn!(approval_id, error = %error, "Failed to load tool approval");
+                None
+            }
+        }
+    }
+
+    fn request_approval(&self, tool_name: &str, input: Value, timeout_secs: u64) -> ToolOutput {
+        let approval = PendingApproval::new(
+            format!("{} {}", tool_name, input),
+            self.scope.task_id.clone().unwrap_or_default(),
+            self.scope.agent_id.clone().unwrap_or_default(),
+            timeout_secs,
+        )
+        .with_tool_call(tool_name, input)
+        .with_session_id(self.scope.session_id.clone());
+        if let Err(error) = self.storage.save(&approval) {
+            return ToolOutput::non_retryable_error(
+                format!(
+                    "Failed to queue approval for tool '{}': {}",
+                    tool_name, error
+                ),
+                ToolErrorCategory::Config,
+            );
+        }
+        if let Some(session_id) = approval.session_id.clone() {
+            publish_session_event(ChatSessionEvent::ToolApprovalRequested {
+                session_id,
+                approval_id: approval.id.clone(),
+                tool_name: tool_name.to_string(),
+            });
+        }
+
+        ToolOutput {
+            success: false,
+            result: json!({
+                "pending_approval": true,
+                "approval_id": approval.id,
+                "tool": tool_name,
+            }),
+            error: Some(format!(
+                "Tool '{}' requires user approval (approval_id: {})",
+                tool_name, approval.id
+            )),
+            error_category: Some(ToolErrorCategory::Auth),
+            retryable: Some(false),
+            retry_after_ms: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolWrapper for ToolApprovalWrapper {
+    fn wrapper_name(&self) -> &str {
+        "tool_approval"
+    }
+
+    async fn wrap_execute(
+        &self,
+        tool_name: &str,
+        mut input: Value,
+        next: &dyn Tool,
+    ) -> ToolResult<ToolOutput> {
+        if let Some(approval) = self.approval_for_call(tool_name, &input) {
+            if approval.status != ApprovalStatus::Approved {
+                return Ok(ToolOutput::non_retryable_error(
+                    format!(
+                        "Tool '{}' was not approved (approval_id: {})",
+                        tool_name, approval.id
+                    ),
+                    ToolErrorCategory::Auth,
+                ));
+            }
+            if let Some(map) = input.as_object_mut() {
+                map.remove(APPROVAL_ID_ARG);
+            }
+            return next.execute(input).await;
+        }
+
+        let policy = self.storage.get_policy()?;
+        let (action, rule) = policy.evaluate(tool_name, &input);
+        match action {
+            SecurityAction::Allow => next.execute(input).await,
+            SecurityAction::Block => {
+                let reason = rule
+                    .and_then(|rule| rule.description.clone())
+                    .or_else(|| rule.map(|rule| format!("Blocked by rule: {}", rule.id)))
+                    .unwrap_or_else(|| "Blocked by the default tool policy".to_string());
+                Ok(ToolOutput::non_retryable_error(
+                    format!("Tool '{}' is blocked: {}", tool_name, reason),
+                    ToolErrorCategory::Auth,
+                ))
+            }
+            SecurityAction::RequireApproval => {
+                Ok(self.request_approval(tool_name, input, policy.approval_timeout_secs))
+            }
+        }
+    }
+}
+
+/// Approve or reject a pending tool call and notify session subscribers.
+///
+/// The caller is responsible for steering the waiting run with
+/// [`approval_steer_message`].
+pub fn resolve_tool_approval(
+    storage: &ToolApprovalStorage,
+    id: &str,
+    approved: bool,
+    reason: Option<String>,
+) -> Result<PendingApproval> {
+    let mut approval = storage
+        .get(id)?
+        .ok_or_else(|| anyhow!("Tool approval {} not found", id))?;
+    if approval.status == ApprovalStatus::Pending && approval.is_expired() {
+        approval.expire();
+        storage.save(&approval)?;
+    }
+    if approval.status != ApprovalStatus::Pending {
+        anyhow::bail!("Tool approval {} is no longer pending", id);
+    }
+
+    if approved {
+        approval.approve();
+    } else {
+        approval.reject(reason);
+    }
+    storage.save(&approval)?;
+    if let Some(session_id) = approval.session_id.clone() {
+        publish_session_event(ChatSessionEvent::ToolApprovalResolved {
+            session_id,
+            approval_id: approval.id.clone(),
+            approved,
+        });
+    }
+    Ok(approval)
+}
+
+/// Steer instruction that resumes the run waiting on `approval`.
+pub fn approval_steer_message(approval: &PendingApproval) -> String {
+    match approval.status {
+        ApprovalStatus::Approved => format!("approval {} approved", approval.id),
+        _ => format!(
+            "approval {} denied {}",
+            approval.id,
+            approval.rejection_reason.as_deref().unwrap_or_default()
+        )
+        .trim_end()
+        .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ToolApprovalPolicy, ToolRule};
+    use redb::Database;
+    use restflow_traits::WrappedTool;
+    use tempfile::tempdir;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "bash"
+        }
+
+        fn description(&self) -> &str {
+            "Echo input"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, input: Value) -> ToolResult<ToolOutput> {
+            Ok(ToolOutput::success(input))
+        }
+    }
+
+    fn setup(action: SecurityAction) -> (ToolApprovalStorage, WrappedTool, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("create temp dir");
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).expect("create db"));
+        let storage = ToolApprovalStorage::new(db).expect("create storage");
+        let policy = ToolApprovalPolicy {
+            rules: vec![ToolRule {
+                id: "bash-rm".to_string(),
+                tool_name: "bash".to_string(),
+                operation: None,
+                argument: Some("command".to_string()),
+                target_pattern: "rm *".to_string(),
+                action,
+                description: None,
+                priority: 0,
+            }],
+            ..ToolApprovalPolicy::default()
+        };
+        storage.set_policy(&policy).expect("set policy");
+        let scope = ToolApprovalScope {
+            agent_id: Some("agent-1".to_string()),
+            task_id: Some("task-1".to_string()),
+            session_id: None,
+        };
+        let wrapper: Arc<dyn ToolWrapper> =
+            Arc::new(ToolApprovalWrapper::new(storage.clone(), scope));
+        let tool = WrappedTool::new(Arc::new(EchoTool), vec![wrapper]);
+        (storage, tool, temp_dir)
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_calls_run_and_blocked_calls_fail() {
+        let (_storage, tool, _temp_dir) = setup(SecurityAction::Block);
+        let output = tool.execute(json!({ "command": "ls" })).await.unwrap();
+        assert!(output.success);
+
+        let output = tool
+            .execute(json!({ "command": "rm -rf build" }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert_eq!(output.error_category, Some(ToolErrorCategory::Auth));
+    }
+
+    #[tokio::test]
+    async fn test_approval_required_calls_run_once_approved() {
+        let (storage, tool, _temp_dir) = setup(SecurityAction::RequireApproval);
+        let output = tool
+            .execute(json!({ "command": "rm a.txt" }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert_eq!(output.result["pending_approval"], true);
+        let approval_id = output.result["approval_id"].as_str().unwrap().to_string();
+
+        let queued = storage.list(Some(ApprovalStatus::Pending)).unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].tool_name.as_deref(), Some("bash"));
+        assert_eq!(queued[0].task_id, "task-1");
+
+        let rerun = json!({ "command": "rm a.txt", "approval_id": approval_id });
+        let output = tool.execute(rerun.clone()).await.unwrap();
+        assert!(!output.success, "pending approvals must not run");
+
+        let approval = resolve_tool_approval(&storage, &approval_id, true, None).unwrap();
+        assert_eq!(
+            approval_steer_message(&approval),
+            format!("approval {approval_id} approved")
+        );
+        let output = tool.execute(rerun).await.unwrap();
+        assert!(output.success);
+        assert!(output.result.get("approval_id").is_none());
+
+        assert!(resolve_tool_approval(&storage, &approval_id, false, None).is_err());
+    }
+}
@@ -4,7 +4,9 @@ use crate::storage::skill::SkillStorage;
 use crate::storage::{AgentStorage, BackgroundAgentStorage, SecretStorage};
 use restflow_contracts::request::AgentNode as ContractAgentNode;
 use restflow_tools::ToolError;
-use restflow_traits::store::{AgentCreateRequest, AgentStore, AgentUpdateRequest};
+use restflow_traits::store::{
+    AgentCreateRequest, AgentStore, AgentUpdateRequest, is_api_connector_tool_group,
+};
 use serde_json::{Value, json};
 use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
@@ -63,7 +65,7 @@ impl AgentStoreAdapter {
                     .read()
                     .map(|set| set.contains(normalized))
                     .unwrap_or(false);
-                if !is_known {
+                if !is_known && !is_api_connector_tool_group(normalized) {
                     errors.push(crate::models::ValidationError::new(
                         "tools",
                         format!("unknown tool: {}", normalized),
@@ -220,6 +222,7 @@ mod tests {
             db.clone(),
             restflow_storage::SecretStorageConfig {
                 allow_insecure_file_permissions: true,
+                ..Default::default()
             },
         )
         .unwrap();
@@ -0,0 +1,107 @@
+//! ApiConnectorStore adapter backed by ApiConnectorStorage.
+
+use crate::services::api_connector::{
+    operation_parameters_schema, operation_tool_name, prepare_request,
+};
+use crate::storage::{ApiConnectorStorage, SecretStorage};
+use restflow_tools::ToolError;
+use restflow_traits::store::ApiConnectorStore;
+use serde_json::{Value, json};
+
+pub struct ApiConnectorStoreAdapter {
+    storage: ApiConnectorStorage,
+    secrets: SecretStorage,
+}
+
+impl ApiConnectorStoreAdapter {
+    pub fn new(storage: ApiConnectorStorage, secrets: SecretStorage) -> Self {
+        Self { storage, secrets }
+    }
+}
+
+impl ApiConnectorStore for ApiConnectorStoreAdapter {
+    fn list_operations(&self, connector: Option<&str>) -> restflow_tools::Result<Value> {
+        let connectors = match connector {
+            Some(name) => vec![
+                self.storage
+                    .get(name)?
+                    .ok_or_else(|| ToolError::Tool(format!("API connector '{name}' not found")))?,
+            ],
+            None => self.storage.list()?,
+        };
+        let operations = connectors
+            .iter()
+            .flat_map(|connector| {
+                connector.operations.iter().map(move |operation| {
+                    json!({
+                        "connector": connector.name,
+                        "operation": operation.id,
+                        "tool_name": operation_tool_name(&connector.name, &operation.id),
+                        "description": operation.summary,
+                        "parameters": operation_parameters_schema(operation),
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(Value::Array(operations))
+    }
+
+    fn prepare_request(
+        &self,
+        connector: &str,
+        operation: &str,
+        args: &Value,
+    ) -> restflow_tools::Result<Value> {
+        let saved = self
+            .storage
+            .get(connector)?
+            .ok_or_else(|| ToolError::Tool(format!("API connector '{connector}' not found")))?;
+        Ok(prepare_request(&saved, operation, args, |secret| {
+            self.secrets.get_secret(secret)
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_connector::parse_openapi;
+    use redb::Database;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_operations_and_requests_use_stored_secret() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let storage = ApiConnectorStorage::new(db.clone()).unwrap();
+        let secrets = SecretStorage::new(db).unwrap();
+        secrets.set_secret("SHOP_TOKEN", "token-1", None).unwrap();
+
+        let spec = r#"{
+            "openapi": "3.0.0",
+            "servers": [{ "url": "https://shop.example.com/v1" }],
+            "components": { "securitySchemes": { "token": { "type": "http", "scheme": "bearer" } } },
+            "security": [{ "token": [] }],
+            "paths": { "/orders/{id}": { "get": {
+                "operationId": "getOrder",
+                "summary": "Fetch an order",
+                "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }]
+            } } }
+        }"#;
+        let connector = parse_openapi("shop", spec, None, Some("SHOP_TOKEN")).unwrap();
+        storage.save(connector).unwrap();
+
+        let adapter = ApiConnectorStoreAdapter::new(storage, secrets);
+        let operations = adapter.list_operations(Some("shop")).unwrap();
+        assert_eq!(operations[0]["tool_name"], "shop_get_order");
+        assert_eq!(operations[0]["parameters"]["required"], json!(["id"]));
+        assert!(adapter.list_operations(Some("missing")).is_err());
+
+        let request = adapter
+            .prepare_request("shop", "get_order", &json!({ "id": "42" }))
+            .unwrap();
+        assert_eq!(request["url"], "https://shop.example.com/v1/orders/42");
+        assert_eq!(request["headers"]["Authorization"], "Bearer token-1");
+    }
+}
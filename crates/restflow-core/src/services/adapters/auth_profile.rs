@@ -165,6 +165,7 @@ mod tests {
             db,
             restflow_storage::SecretStorageConfig {
                 allow_insecure_file_permissions: true,
+                ..Default::default()
             },
         )
         .unwrap();
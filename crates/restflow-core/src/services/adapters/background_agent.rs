@@ -71,6 +71,7 @@ impl TaskStoreAdapter {
             "completed" => Ok(TaskStatus::Completed),
             "failed" => Ok(TaskStatus::Failed),
             "interrupted" => Ok(TaskStatus::Interrupted),
+            "dead_letter" => Ok(TaskStatus::DeadLetter),
             _ => Err(ToolError::Tool(format!("Unknown status: {}", status))),
         }
     }
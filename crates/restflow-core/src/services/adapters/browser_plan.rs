@@ -0,0 +1,108 @@
+//! BrowserPlanStore adapter backed by BrowserPlanStorage.
+
+use crate::services::browser_webhook::render_plan_actions;
+use crate::storage::BrowserPlanStorage;
+use restflow_tools::ToolError;
+use restflow_traits::store::BrowserPlanStore;
+use serde_json::{Value, json};
+
+pub struct BrowserPlanStoreAdapter {
+    storage: BrowserPlanStorage,
+}
+
+impl BrowserPlanStoreAdapter {
+    pub fn new(storage: BrowserPlanStorage) -> Self {
+        Self { storage }
+    }
+}
+
+impl BrowserPlanStore for BrowserPlanStoreAdapter {
+    fn list_plans(&self) -> restflow_tools::Result<Value> {
+        let plans = self
+            .storage
+            .list()?
+            .into_iter()
+            .map(|plan| {
+                json!({
+                    "name": plan.name,
+                    "description": plan.description,
+                    "version": plan.version,
+                    "updated_at": plan.updated_at,
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(Value::Array(plans))
+    }
+
+    fn resolve_plan(
+        &self,
+        name: &str,
+        version: Option<u32>,
+        payload: &Value,
+    ) -> restflow_tools::Result<Value> {
+        let saved = self
+            .storage
+            .get_version(name, version)?
+            .ok_or_else(|| match version {
+                Some(version) => {
+                    ToolError::Tool(format!("Browser plan '{name}' has no version {version}"))
+                }
+                None => ToolError::Tool(format!("Browser plan '{name}' not found")),
+            })?;
+        let actions = render_plan_actions(&saved.plan, payload)?;
+        Ok(json!({
+            "name": saved.name,
+            "version": saved.version,
+            "actions": actions,
+            "timeout_secs": saved.plan.timeout_secs,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BrowserPlan;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resolve_plan_renders_requested_version() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(redb::Database::create(temp_dir.path().join("test.db")).unwrap());
+        let storage = BrowserPlanStorage::new(db).unwrap();
+        storage
+            .save(
+                "open",
+                None,
+                BrowserPlan {
+                    actions: json!([{ "type": "navigate", "url": "${payload.url}" }]),
+                    timeout_secs: Some(9),
+                },
+            )
+            .unwrap();
+        storage
+            .save(
+                "open",
+                None,
+                BrowserPlan {
+                    actions: json!([{ "type": "navigate", "url": "https://fixed.test" }]),
+                    timeout_secs: None,
+                },
+            )
+            .unwrap();
+        let adapter = BrowserPlanStoreAdapter::new(storage);
+
+        let resolved = adapter
+            .resolve_plan("open", Some(1), &json!({ "url": "https://a.test" }))
+            .unwrap();
+        assert_eq!(resolved["version"], 1);
+        assert_eq!(resolved["actions"][0]["url"], "https://a.test");
+        assert_eq!(resolved["timeout_secs"], 9);
+
+        let latest = adapter.resolve_plan("open", None, &json!({})).unwrap();
+        assert_eq!(latest["version"], 2);
+        assert!(adapter.resolve_plan("missing", None, &json!({})).is_err());
+        assert_eq!(adapter.list_plans().unwrap().as_array().unwrap().len(), 1);
+    }
+}
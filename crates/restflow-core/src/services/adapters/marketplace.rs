@@ -1,7 +1,13 @@
 //! MarketplaceStore adapter backed by SkillStorage.
 
-use crate::models::Skill;
-use crate::registry::{GitHubProvider, MarketplaceProvider, SkillProvider as _, SkillSearchQuery};
+use std::collections::HashMap;
+
+use crate::models::{GatingRequirements, Skill, SkillStatus, SkillVersion};
+use crate::registry::{
+    GatingChecker, GitHubProvider, MarketplaceProvider, SkillProvider, SkillProviderError,
+    SkillSearchQuery, check_skill_signature, resolve_install,
+};
+use crate::storage::SecretStorage;
 use crate::storage::skill::SkillStorage;
 use chrono::Utc;
 use restflow_storage::{RegistryDefaults, RegistrySettings};
@@ -13,6 +19,9 @@ pub struct MarketplaceStoreAdapter {
     storage: SkillStorage,
     github_provider: GitHubProvider,
     marketplace_provider: MarketplaceProvider,
+    trusted_skill_keys: Vec<String>,
+    require_signed_skills: bool,
+    secrets: Option<SecretStorage>,
 }
 
 impl MarketplaceStoreAdapter {
@@ -29,6 +38,9 @@ impl MarketplaceStoreAdapter {
             storage,
             github_provider,
             marketplace_provider,
+            trusted_skill_keys: registry.trusted_skill_keys,
+            require_signed_skills: registry.require_signed_skills,
+            secrets: None,
         }
     }
 
@@ -36,6 +48,12 @@ impl MarketplaceStoreAdapter {
         Self::new_with_settings(storage, registry_defaults)
     }
 
+    /// Let stored secrets satisfy the env var requirements of installed skills.
+    pub fn with_secrets(mut self, secrets: SecretStorage) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
     fn provider_name(source: Option<&str>) -> &str {
         match source {
             Some("github") => "github",
@@ -43,6 +61,55 @@ impl MarketplaceStoreAdapter {
         }
     }
 
+    fn provider(&self, source: &str) -> &dyn SkillProvider {
+        match source {
+            "github" => &self.github_provider,
+            _ => &self.marketplace_provider,
+        }
+    }
+
+    /// Gating checker that counts stored secrets as satisfying env vars.
+    fn gating_checker(&self, requirements: &GatingRequirements) -> GatingChecker {
+        let stored = requirements
+            .env_vars
+            .iter()
+            .filter(|env_var| {
+                self.secrets.as_ref().is_some_and(|secrets| {
+                    secrets.has_available_secret(&env_var.name).unwrap_or(false)
+                })
+            })
+            .map(|env_var| env_var.name.clone());
+        GatingChecker::default().with_available_secrets(stored)
+    }
+
+    /// Check that a skill's gating requirements pass so it can be activated.
+    pub(crate) fn check_activation(&self, skill: &Skill) -> restflow_tools::Result<()> {
+        if let Some(gating) = skill.gating.as_ref() {
+            let requirements = GatingRequirements::from_skill_gating(gating);
+            let result = self.gating_checker(&requirements).check(&requirements);
+            if !result.passed {
+                return Err(ToolError::Tool(format!(
+                    "Cannot activate '{}': {}",
+                    skill.id, result.summary
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Versions of installed skills, for dependency resolution.
+    fn installed_versions(&self) -> Result<HashMap<String, SkillVersion>, ToolError> {
+        Ok(self
+            .storage
+            .list()?
+            .into_iter()
+            .filter_map(|skill| {
+                let version = skill.version.as_deref().and_then(SkillVersion::parse)?;
+                Some((skill.id, version))
+            })
+            .collect())
+    }
+
     async fn search_source(
         &self,
         source: &str,
@@ -81,6 +148,31 @@ impl MarketplaceStoreAdapter {
         }
     }
 
+    async fn get_manifest_version(
+        &self,
+        source: &str,
+        id: &str,
+        version: &SkillVersion,
+    ) -> Result<crate::models::SkillManifest, ToolError> {
+        match source {
+            "github" => {
+                let manifest = self.get_manifest(source, id).await?;
+                if manifest.version != *version {
+                    return Err(ToolError::Tool(
+                        SkillProviderError::VersionNotFound(format!("{}@{}", id, version))
+                            .to_string(),
+                    ));
+                }
+                Ok(manifest)
+            }
+            _ => self
+                .marketplace_provider
+                .get_manifest_version(id, version)
+                .await
+                .map_err(|e| ToolError::Tool(e.to_string())),
+        }
+    }
+
     async fn get_content(
         &self,
         source: &str,
@@ -101,6 +193,19 @@ impl MarketplaceStoreAdapter {
         }
     }
 
+    /// Split `name@version` into the skill id and an optional pinned version.
+    fn parse_install_target(target: &str) -> Result<(&str, Option<SkillVersion>), ToolError> {
+        match target.rsplit_once('@') {
+            Some((id, version)) => {
+                let version = SkillVersion::parse(version).ok_or_else(|| {
+                    ToolError::Tool(format!("Invalid skill version in '{}'", target))
+                })?;
+                Ok((id, Some(version)))
+            }
+            None => Ok((target, None)),
+        }
+    }
+
     fn manifest_to_skill(manifest: crate::models::SkillManifest, content: String) -> Skill {
         let now = Utc::now().timestamp_millis();
         Skill {
@@ -114,7 +219,7 @@ impl MarketplaceStoreAdapter {
             suggested_tools: Vec::new(),
             scripts: Vec::new(),
             references: Vec::new(),
-            gating: None,
+            gating: manifest.gating.to_skill_gating(),
             version: Some(manifest.version.to_string()),
             author: manifest.author.map(|a| a.name),
             license: manifest.license,
@@ -168,9 +273,11 @@ impl MarketplaceStore for MarketplaceStoreAdapter {
         overwrite: bool,
     ) -> restflow_tools::Result<Value> {
         let source_name = Self::provider_name(source);
-        let manifest = self.get_manifest(source_name, id).await?;
-        let content = self.get_content(source_name, id, &manifest.version).await?;
-        let skill = Self::manifest_to_skill(manifest, content);
+        let (id, version) = Self::parse_install_target(id)?;
+        let manifest = match version {
+            Some(version) => self.get_manifest_version(source_name, id, &version).await?,
+            None => self.get_manifest(source_name, id).await?,
+        };
 
         let exists = self.storage.exists(id)?;
         if exists && !overwrite {
@@ -179,19 +286,124 @@ impl MarketplaceStore for MarketplaceStoreAdapter {
             ));
         }
 
-        if exists {
-            self.storage.update(id, &skill)?;
-        } else {
-            self.storage.create(&skill)?;
+        let mut installed = self.installed_versions()?;
+        installed.remove(id);
+        let resolved = resolve_install(self.provider(source_name), manifest, &installed)
+            .await
+            .map_err(|e| ToolError::Tool(e.to_string()))?;
+
+        // Download and verify the whole plan before writing anything.
+        let mut staged = Vec::new();
+        for manifest in resolved.pending() {
+            if manifest.id != id
+                && !installed.contains_key(&manifest.id)
+                && !overwrite
+                && self.storage.exists(&manifest.id)?
+            {
+                return Err(ToolError::Tool(format!(
+                    "Dependency '{}' is already installed without a version. Set overwrite=true to replace.",
+                    manifest.id
+                )));
+            }
+            let content = self
+                .get_content(source_name, &manifest.id, &manifest.version)
+                .await?;
+            let signature = check_skill_signature(
+                manifest,
+                &content,
+                &self.trusted_skill_keys,
+                self.require_signed_skills,
+            )
+            .map_err(|e| ToolError::Tool(e.to_string()))?;
+            let gating = self
+                .gating_checker(&manifest.gating)
+                .check(&manifest.gating);
+            staged.push((manifest.clone(), content, signature, gating));
         }
 
-        Ok(json!({
+        let mut dependencies = Vec::new();
+        let mut blocked = Vec::new();
+        let mut missing_binaries = Vec::new();
+        let mut missing_secrets = Vec::new();
+        let mut root = None;
+        for (manifest, content, signature, gating) in staged {
+            let mut skill = Self::manifest_to_skill(manifest, content);
+            // Skills stay in draft until their requirements are met.
+            if !gating.passed {
+                skill.status = SkillStatus::Draft;
+                blocked.push(skill.id.clone());
+            }
+            let replace = if skill.id == id {
+                exists
+            } else {
+                self.storage.exists(&skill.id)?
+            };
+            if replace {
+                self.storage.update(&skill.id, &skill)?;
+            } else {
+                self.storage.create(&skill)?;
+            }
+
+            for binary in &gating.missing_binaries {
+                if !missing_binaries.contains(binary) {
+                    missing_binaries.push(binary.clone());
+                }
+            }
+            for secret in &gating.missing_env_vars {
+                if !missing_secrets.contains(secret) {
+                    missing_secrets.push(secret.clone());
+                }
+            }
+
+            if skill.id == id {
+                root = Some((skill, signature, gating));
+            } else {
+                dependencies.push(json!({
+                    "id": skill.id,
+                    "version": skill.version,
+                    "active": gating.passed,
+                    "signature": signature
+                }));
+            }
+        }
+
+        let (skill, signature, gating) = root.ok_or_else(|| {
+            ToolError::Tool(format!("Install plan for '{}' did not include it", id))
+        })?;
+        let mut result = json!({
             "id": id,
             "name": skill.name,
             "version": skill.version,
             "installed": true,
-            "updated": exists
-        }))
+            "updated": exists,
+            "signature": signature,
+            "active": gating.passed,
+            "dependencies": dependencies,
+            "unchanged_dependencies": resolved.plan.unchanged,
+        });
+        if !blocked.is_empty() {
+            let mut next_steps = format!(
+                "Installed but inactive until requirements are met: {}.",
+                blocked.join(", ")
+            );
+            if !missing_binaries.is_empty() {
+                next_steps.push_str(&format!(
+                    " Install binaries: {}.",
+                    missing_binaries.join(", ")
+                ));
+            }
+            if !missing_secrets.is_empty() {
+                next_steps.push_str(&format!(
+                    " Store secrets with manage_secrets: {}.",
+                    missing_secrets.join(", ")
+                ));
+            }
+            next_steps.push_str(" Then run operation=activate for each skill.");
+            result["missing_binaries"] = json!(missing_binaries);
+            result["missing_secrets"] = json!(missing_secrets);
+            result["next_steps"] = json!(next_steps);
+        }
+        Ok(result)
     }
 
     fn uninstall_skill(&self, id: &str) -> restflow_tools::Result<Value> {
@@ -209,6 +421,50 @@ impl MarketplaceStore for MarketplaceStoreAdapter {
         let skills = self.storage.list()?;
         Ok(serde_json::to_value(skills)?)
     }
+
+    async fn check_updates(&self, source: Option<&str>) -> restflow_tools::Result<Value> {
+        let source_name = Self::provider_name(source);
+        let mut updates = Vec::new();
+        for skill in self.storage.list()? {
+            let Some(installed) = skill.version.as_deref().and_then(SkillVersion::parse) else {
+                continue;
+            };
+            let latest = match self.get_manifest(source_name, &skill.id).await {
+                Ok(manifest) => manifest.version,
+                // Skills that are not published to this source have no updates.
+                Err(_) => continue,
+            };
+            if latest.compare(&installed) > 0 {
+                updates.push(json!({
+                    "id": skill.id,
+                    "installed": installed.to_string(),
+                    "latest": latest.to_string(),
+                    "install": format!("{}@{}", skill.id, latest)
+                }));
+            }
+        }
+        Ok(Value::Array(updates))
+    }
+
+    fn activate_skill(&self, id: &str) -> restflow_tools::Result<Value> {
+        let mut skill = self
+            .storage
+            .get(id)?
+            .ok_or_else(|| ToolError::Tool(format!("Skill '{}' is not installed", id)))?;
+        self.check_activation(&skill)?;
+
+        let activated = skill.status != SkillStatus::Active;
+        if activated {
+            skill.status = SkillStatus::Active;
+            skill.updated_at = Utc::now().timestamp_millis();
+            self.storage.update(id, &skill)?;
+        }
+        Ok(json!({
+            "id": id,
+            "active": true,
+            "activated": activated
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -258,6 +514,72 @@ mod tests {
         assert_eq!(result["deleted"], true);
     }
 
+    #[test]
+    fn test_parse_install_target() {
+        let (id, version) = MarketplaceStoreAdapter::parse_install_target("deploy@1.2.3").unwrap();
+        assert_eq!(id, "deploy");
+        assert_eq!(version, Some(SkillVersion::new(1, 2, 3)));
+
+        let (id, version) = MarketplaceStoreAdapter::parse_install_target("deploy").unwrap();
+        assert_eq!(id, "deploy");
+        assert!(version.is_none());
+
+        assert!(MarketplaceStoreAdapter::parse_install_target("deploy@latest").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_updates_skips_unversioned_skills() {
+        let (adapter, _dir) = setup();
+        let skill = crate::models::Skill::new(
+            "local-only".to_string(),
+            "Local".to_string(),
+            None,
+            None,
+            "# Local".to_string(),
+        );
+        adapter.storage.create(&skill).unwrap();
+
+        let result = adapter.check_updates(None).await.unwrap();
+        assert_eq!(result, json!([]));
+    }
+
+    #[test]
+    fn test_activate_skill_requires_gating_secrets() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(redb::Database::create(temp_dir.path().join("test.db")).unwrap());
+        let secrets = SecretStorage::new(db.clone()).unwrap();
+        let adapter = MarketplaceStoreAdapter::new(SkillStorage::new(db).unwrap())
+            .with_secrets(secrets.clone());
+
+        let mut skill = crate::models::Skill::new(
+            "deploy".to_string(),
+            "Deploy".to_string(),
+            None,
+            None,
+            "# Deploy".to_string(),
+        );
+        skill.status = SkillStatus::Draft;
+        skill.gating = Some(crate::models::SkillGating {
+            bins: None,
+            env: Some(vec!["RESTFLOW_TEST_DEPLOY_TOKEN".to_string()]),
+            os: None,
+        });
+        adapter.storage.create(&skill).unwrap();
+
+        let err = adapter.activate_skill("deploy").unwrap_err();
+        assert!(err.to_string().contains("RESTFLOW_TEST_DEPLOY_TOKEN"));
+
+        secrets
+            .set_secret("RESTFLOW_TEST_DEPLOY_TOKEN", "token", None)
+            .unwrap();
+        let result = adapter.activate_skill("deploy").unwrap();
+        assert_eq!(result["activated"], true);
+        assert_eq!(
+            adapter.storage.get("deploy").unwrap().unwrap().status,
+            SkillStatus::Active
+        );
+    }
+
     #[test]
     fn test_provider_name() {
         assert_eq!(
@@ -1,7 +1,7 @@
 //! Memory adapters: MemoryManager and MemoryStore backed by MemoryStorage.
 
-use crate::memory::MemoryExporter;
-use crate::storage::MemoryStorage;
+use crate::memory::{MemoryExporter, suggest_tags_for_chunk};
+use crate::storage::{MemorySettings, MemoryStorage};
 use restflow_traits::store::{
     MemoryClearRequest, MemoryCompactRequest, MemoryExportRequest, MemoryManager, MemoryStore,
 };
@@ -101,11 +101,21 @@ impl MemoryManager for MemoryManagerAdapter {
 #[derive(Clone)]
 pub struct DbMemoryStoreAdapter {
     storage: MemoryStorage,
+    memory_settings: Option<MemorySettings>,
 }
 
 impl DbMemoryStoreAdapter {
     pub fn new(storage: MemoryStorage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            memory_settings: None,
+        }
+    }
+
+    /// Suggest tags for saved memories according to `settings`.
+    pub fn with_memory_settings(mut self, settings: MemorySettings) -> Self {
+        self.memory_settings = Some(settings);
+        self
     }
 
     /// Extract title from tags (stored as `__title:{value}`)
@@ -180,12 +190,15 @@ impl MemoryStore for DbMemoryStoreAdapter {
         use crate::models::memory::MemorySource;
 
         let db_tags = Self::build_tags(title, tags);
-        let chunk =
+        let mut chunk =
             crate::models::memory::MemoryChunk::new(agent_id.to_string(), content.to_string())
                 .with_tags(db_tags)
                 .with_source(MemorySource::AgentGenerated {
                     tool_name: "save_to_memory".to_string(),
                 });
+        if let Some(settings) = &self.memory_settings {
+            suggest_tags_for_chunk(&self.storage, settings, &mut chunk)?;
+        }
 
         let stored_id = self.storage.store_chunk(&chunk)?;
 
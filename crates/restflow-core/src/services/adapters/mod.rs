@@ -5,8 +5,10 @@
 //! to tool implementations in restflow-tools.
 
 pub mod agent;
+pub mod api_connector;
 pub mod auth_profile;
 pub mod background_agent;
+pub mod browser_plan;
 pub mod config;
 pub mod deliverable;
 pub mod kv_store;
@@ -20,11 +22,14 @@ pub mod skill_provider;
 pub mod terminal;
 pub mod trigger;
 pub mod unified_search;
+pub mod usage;
 pub mod work_item;
 
 pub use agent::AgentStoreAdapter;
+pub use api_connector::ApiConnectorStoreAdapter;
 pub use auth_profile::AuthProfileStorageAdapter;
 pub use background_agent::{BackgroundAgentStoreAdapter, TaskStoreAdapter};
+pub use browser_plan::BrowserPlanStoreAdapter;
 pub use config::ConfigStoreAdapter;
 pub use deliverable::DeliverableStoreAdapter;
 pub use kv_store::KvStoreAdapter;
@@ -38,4 +43,5 @@ pub use skill_provider::SkillStorageProvider;
 pub use terminal::TerminalStoreAdapter;
 pub use trigger::TriggerStoreAdapter;
 pub use unified_search::UnifiedMemorySearchAdapter;
+pub use usage::UsageStoreAdapter;
 pub use work_item::DbWorkItemAdapter;
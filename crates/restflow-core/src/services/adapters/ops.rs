@@ -46,9 +46,10 @@ impl OpsProviderAdapter {
             "completed" => TaskStatus::Completed,
             "failed" => TaskStatus::Failed,
             "interrupted" => TaskStatus::Interrupted,
+            "dead_letter" => TaskStatus::DeadLetter,
             value => {
                 return Err(ToolError::Tool(format!(
-                    "Unknown status: {}. Supported: active, paused, running, completed, failed, interrupted",
+                    "Unknown status: {}. Supported: active, paused, running, completed, failed, interrupted, dead_letter",
                     value
                 )));
             }
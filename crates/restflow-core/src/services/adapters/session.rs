@@ -1,5 +1,6 @@
 //! SessionStore adapter backed by ChatSessionStorage.
 
+use crate::models::ChatSearchQuery;
 use crate::services::session::SessionService;
 use crate::storage::{AgentStorage, BackgroundAgentStorage, SessionStorage};
 use restflow_tools::ToolError;
@@ -100,15 +101,17 @@ impl SessionStore for SessionStorageAdapter {
     }
 
     fn search_sessions(&self, query: SessionSearchQuery) -> restflow_tools::Result<Value> {
-        let matched = self.session_service().search_session_views(
-            &query.query,
-            query.agent_id.as_deref(),
-            query.skill_id.as_deref(),
-            query.include_archived.unwrap_or(false),
-            query.limit.unwrap_or(20) as usize,
-        )?;
+        let mut search = ChatSearchQuery::new(query.query);
+        search.agent_id = query.agent_id;
+        search.skill_id = query.skill_id;
+        search.include_archived = query.include_archived.unwrap_or(false);
+        if let Some(limit) = query.limit {
+            search.limit = limit;
+        }
+        search.offset = query.offset.unwrap_or(0);
 
-        Ok(serde_json::to_value(matched)?)
+        let result = self.session_service().search_messages(&search)?;
+        Ok(serde_json::to_value(result)?)
     }
 
     fn cleanup_sessions(&self) -> restflow_tools::Result<Value> {
@@ -237,6 +240,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .unwrap();
 
@@ -315,7 +319,18 @@ mod tests {
             skill_id: None,
             retention: None,
         };
-        adapter.create_session(request).unwrap();
+        let created = adapter.create_session(request).unwrap();
+        let session_id = created["id"].as_str().unwrap();
+        let mut stored = adapter
+            .sessions
+            .chat_sessions
+            .get(session_id)
+            .unwrap()
+            .unwrap();
+        stored.add_message(crate::models::ChatMessage::user(
+            "Summarize the meeting agenda".to_string(),
+        ));
+        adapter.sessions.chat_sessions.update(&stored).unwrap();
 
         let query = SessionSearchQuery {
             query: "meeting".to_string(),
@@ -323,10 +338,14 @@ mod tests {
             skill_id: None,
             include_archived: None,
             limit: None,
+            offset: None,
         };
         let result = adapter.search_sessions(query).unwrap();
-        let sessions = result.as_array().unwrap();
-        assert_eq!(sessions.len(), 1);
+        let hits = result["hits"].as_array().unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["session_id"], session_id);
+        assert_eq!(hits[0]["session_name"], "Meeting Notes");
+        assert_eq!(hits[0]["highlights"].as_array().unwrap().len(), 1);
     }
 
     #[test]
@@ -368,7 +387,7 @@ mod tests {
     }
 
     #[test]
-    fn test_search_sessions_applies_effective_source_normalization() {
+    fn test_search_sessions_paginates_hits() {
         let (adapter, _dir) = setup();
         let agent_id = create_default_agent(&adapter);
         let created = adapter
@@ -387,11 +406,9 @@ mod tests {
             .get(session_id)
             .unwrap()
             .unwrap();
-        stored.source_channel = Some(crate::models::ChatSessionSource::Telegram);
-        stored.source_conversation_id = Some("chat-search".to_string());
-        stored.add_message(crate::models::ChatMessage::user(
-            "find this remote chat".to_string(),
-        ));
+        for content in ["remote one", "remote two", "remote three"] {
+            stored.add_message(crate::models::ChatMessage::user(content.to_string()));
+        }
         adapter.sessions.chat_sessions.update(&stored).unwrap();
 
         let result = adapter
@@ -400,13 +417,13 @@ mod tests {
                 agent_id: None,
                 skill_id: None,
                 include_archived: None,
-                limit: None,
+                limit: Some(2),
+                offset: Some(2),
             })
             .unwrap();
-        let sessions = result.as_array().unwrap();
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0]["source_channel"], "telegram");
-        assert_eq!(sessions[0]["source_conversation_id"], "chat-search");
+        assert_eq!(result["total_count"], 3);
+        assert_eq!(result["hits"].as_array().unwrap().len(), 1);
+        assert_eq!(result["has_more"], false);
     }
 
     #[test]
@@ -1,17 +1,63 @@
 //! UnifiedMemorySearch adapter backed by UnifiedSearchEngine.
 
-use crate::memory::UnifiedSearchEngine;
+use crate::memory::{MemoryEmbedder, UnifiedSearchEngine};
 use crate::models::{MemorySearchQuery, SearchMode, UnifiedSearchQuery};
 use restflow_traits::store::UnifiedMemorySearch;
 use serde_json::Value;
+use std::sync::Arc;
+use tracing::warn;
 
 pub struct UnifiedMemorySearchAdapter {
     engine: UnifiedSearchEngine,
+    embedder: Option<Arc<MemoryEmbedder>>,
 }
 
 impl UnifiedMemorySearchAdapter {
     pub fn new(engine: UnifiedSearchEngine) -> Self {
-        Self { engine }
+        Self {
+            engine,
+            embedder: None,
+        }
+    }
+
+    /// Use `embedder` for semantic recall; keyword search is used without one.
+    pub fn with_embedder(mut self, embedder: Option<Arc<MemoryEmbedder>>) -> Self {
+        self.embedder = embedder;
+        self
+    }
+
+    /// Embed the query, falling back to keyword search on failure.
+    fn embed_query(&self, agent_id: &str, query: &str) -> Option<Vec<f32>> {
+        let embedder = self.embedder.clone()?;
+        let agent_id = agent_id.to_string();
+        let query = query.to_string();
+        let future = async move { embedder.embed_query(&agent_id, &query).await };
+
+        let result = match tokio::runtime::Handle::try_current() {
+            Ok(handle)
+                if matches!(
+                    handle.runtime_flavor(),
+                    tokio::runtime::RuntimeFlavor::MultiThread
+                ) =>
+            {
+                tokio::task::block_in_place(|| handle.block_on(future))
+            }
+            _ => std::thread::spawn(move || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?
+                    .block_on(future)
+            })
+            .join()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("embedding thread panicked"))),
+        };
+        match result {
+            Ok(embedding) => Some(embedding),
+            Err(error) => {
+                warn!(error = %error, "Query embedding failed; using keyword search");
+                None
+            }
+        }
     }
 }
 
@@ -20,17 +66,30 @@ impl UnifiedMemorySearch for UnifiedMemorySearchAdapter {
         &self,
         agent_id: &str,
         query: &str,
+        mode: Option<&str>,
         include_sessions: bool,
         limit: u32,
         offset: u32,
     ) -> restflow_tools::Result<Value> {
+        let mode = match mode {
+            Some(mode) => serde_json::from_value(Value::String(mode.to_string()))?,
+            None if self.embedder.is_some() => SearchMode::Hybrid,
+            None => SearchMode::Keyword,
+        };
+        let embedding = if mode.uses_embeddings() {
+            self.embed_query(agent_id, query)
+        } else {
+            None
+        };
         let base = MemorySearchQuery::new(agent_id.to_string())
             .with_query(query.to_string())
-            .with_mode(SearchMode::Keyword)
+            .with_mode(mode)
             .paginate(limit, offset);
         let unified_query = UnifiedSearchQuery::new(base).with_sessions(include_sessions);
 
-        let results = self.engine.search(&unified_query)?;
+        let results = self
+            .engine
+            .search_with_embedding(&unified_query, embedding.as_deref())?;
 
         Ok(serde_json::to_value(results)?)
     }
@@ -61,7 +120,9 @@ mod tests {
     #[test]
     fn test_search_empty_returns_valid_json() {
         let (adapter, _storage, _dir) = setup();
-        let result = adapter.search("agent-1", "anything", false, 10, 0).unwrap();
+        let result = adapter
+            .search("agent-1", "anything", None, false, 10, 0)
+            .unwrap();
         assert!(result.is_object());
     }
 
@@ -74,14 +135,18 @@ mod tests {
         );
         storage.store_chunk(&chunk).unwrap();
 
-        let result = adapter.search("agent-1", "rust", false, 10, 0).unwrap();
+        let result = adapter
+            .search("agent-1", "rust", None, false, 10, 0)
+            .unwrap();
         assert!(result.is_object());
     }
 
     #[test]
     fn test_search_with_sessions() {
         let (adapter, _storage, _dir) = setup();
-        let result = adapter.search("agent-1", "test", true, 10, 0).unwrap();
+        let result = adapter
+            .search("agent-1", "test", None, true, 10, 0)
+            .unwrap();
         assert!(result.is_object());
     }
 }
@@ -0,0 +1,89 @@
+//! UsageStore adapter backed by UsageStorage.
+
+use crate::models::{UsageBudgetStatus, UsageQuery};
+use crate::storage::UsageStorage;
+use restflow_tools::ToolError;
+use restflow_traits::store::UsageStore;
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct UsageStoreAdapter {
+    storage: UsageStorage,
+}
+
+impl UsageStoreAdapter {
+    pub fn new(storage: UsageStorage) -> Self {
+        Self { storage }
+    }
+}
+
+impl UsageStore for UsageStoreAdapter {
+    fn budget_status(
+        &self,
+        agent_id: &str,
+        since_ms: i64,
+        budget_usd: Option<f64>,
+    ) -> restflow_tools::Result<Value> {
+        let spent = self.storage.totals(&UsageQuery {
+            agent_id: Some(agent_id.to_string()),
+            from_time: Some(since_ms),
+            ..UsageQuery::default()
+        })?;
+        let status = UsageBudgetStatus {
+            agent_id: agent_id.to_string(),
+            since: since_ms,
+            remaining_usd: budget_usd.map(|budget| (budget - spent.cost_usd).max(0.0)),
+            exceeded: budget_usd.is_some_and(|budget| spent.cost_usd >= budget),
+            budget_usd,
+            spent,
+        };
+        serde_json::to_value(status).map_err(ToolError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UsageRecord;
+    use redb::Database;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_budget_status_reports_exceeded_spend() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("usage.db")).unwrap());
+        let storage = UsageStorage::new(db).unwrap();
+        for (id, timestamp) in [("old", 100), ("new", 2_000)] {
+            storage
+                .record(&UsageRecord {
+                    id: id.to_string(),
+                    timestamp,
+                    agent_id: "agent-1".to_string(),
+                    session_id: None,
+                    task_id: "task-1".to_string(),
+                    run_id: None,
+                    provider: None,
+                    model: "gpt-5".to_string(),
+                    prompt_tokens: 10,
+                    completion_tokens: 10,
+                    cost_usd: Some(1.0),
+                    cache_read_tokens: 0,
+                    cache_write_tokens: 0,
+                    cache_savings_usd: None,
+                })
+                .unwrap();
+        }
+
+        let adapter = UsageStoreAdapter::new(storage);
+        let status = adapter.budget_status("agent-1", 1_000, Some(1.0)).unwrap();
+        assert_eq!(status["spent"]["call_count"], 1);
+        assert_eq!(status["remaining_usd"], 0.0);
+        assert_eq!(status["exceeded"], true);
+
+        let status = adapter.budget_status("agent-1", 0, None).unwrap();
+        assert_eq!(status["spent"]["call_count"], 2);
+        assert_eq!(status["exceeded"], false);
+        assert!(status.get("remaining_usd").is_none());
+    }
+}
@@ -159,6 +159,18 @@ pub(crate) fn check_agent_has_external_channel_sessions(
                 }
                 sources.insert("slack".to_string());
             }
+            Some(ChatSessionSource::Matrix) => {
+                if let Some(conversation_id) = legacy_conversation_id.clone() {
+                    let binding = crate::models::ChannelSessionBinding::new(
+                        "matrix",
+                        None,
+                        conversation_id,
+                        &session.id,
+                    );
+                    let _ = channel_session_bindings.upsert(&binding);
+                }
+                sources.insert("matrix".to_string());
+            }
             Some(ChatSessionSource::ExternalLegacy) => {
                 sources.insert("external_legacy".to_string());
             }
@@ -234,7 +246,7 @@ pub async fn delete_agent(core: &Arc<AppCore>, id: &str) -> Result<()> {
         .with_context(|| format!("Failed to delete agent {}", id))
 }
 
-fn normalize_model_fields(agent: &mut AgentNode) -> Result<()> {
+pub(crate) fn normalize_model_fields(agent: &mut AgentNode) -> Result<()> {
     if let Err(error) = agent.normalize_model_fields() {
         anyhow::bail!(encode_validation_error(vec![error]));
     }
@@ -251,7 +263,7 @@ fn archive_agent_workspace_sessions(
     Ok(())
 }
 
-async fn validate_agent_node(core: &Arc<AppCore>, agent: &AgentNode) -> Result<()> {
+pub(crate) async fn validate_agent_node(core: &Arc<AppCore>, agent: &AgentNode) -> Result<()> {
     if let Err(errors) = agent.validate() {
         anyhow::bail!(encode_validation_error(errors));
     }
@@ -335,6 +347,8 @@ mod tests {
             skill_variables: None,
             skill_preflight_policy_mode: None,
             model_routing: None,
+            dry_run: None,
+            execution_strategy: None,
         }
     }
 
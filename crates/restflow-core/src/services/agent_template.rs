@@ -0,0 +1,248 @@
+//! Built-in agent templates and one-step provisioning.
+//!
+//! Templates are bundled as JSON under `assets/agent_templates`. Provisioning
+//! creates the agent with the template's prompt, tools and installed skills,
+//! then creates its scheduled background tasks.
+
+use crate::{
+    AppCore,
+    models::{AgentTemplate, BackgroundAgentSpec, Task},
+    services::agent as agent_service,
+    storage::agent::StoredAgent,
+};
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "research-assistant",
+        include_str!("../../assets/agent_templates/research-assistant.json"),
+    ),
+    (
+        "inbox-triager",
+        include_str!("../../assets/agent_templates/inbox-triager.json"),
+    ),
+    (
+        "repo-reviewer",
+        include_str!("../../assets/agent_templates/repo-reviewer.json"),
+    ),
+    (
+        "price-monitor",
+        include_str!("../../assets/agent_templates/price-monitor.json"),
+    ),
+];
+
+/// Everything created by provisioning a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvisionedAgentTemplate {
+    pub template_id: String,
+    pub agent: StoredAgent,
+    pub tasks: Vec<Task>,
+    /// Template skills that are not installed and were left off the agent
+    pub missing_skills: Vec<String>,
+    /// Required secrets that are not yet stored
+    pub missing_secrets: Vec<String>,
+}
+
+/// List the bundled agent templates
+pub fn list_agent_templates() -> Result<Vec<AgentTemplate>> {
+    BUILTIN_TEMPLATES
+        .iter()
+        .map(|(id, json)| {
+            serde_json::from_str(json)
+                .with_context(|| format!("Failed to parse agent template {}", id))
+        })
+        .collect()
+}
+
+/// Get a bundled agent template by ID
+pub fn get_agent_template(id: &str) -> Result<Option<AgentTemplate>> {
+    Ok(list_agent_templates()?
+        .into_iter()
+        .find(|template| template.id == id))
+}
+
+/// Create the agent and background tasks described by a template
+pub async fn provision_agent_template(
+    core: &Arc<AppCore>,
+    template_id: &str,
+    name: Option<String>,
+) -> Result<ProvisionedAgentTemplate> {
+    let template = get_agent_template(template_id)?.ok_or_else(|| {
+        let available = BUILTIN_TEMPLATES
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow!(
+            "Unknown agent template '{}'. Available: {}",
+            template_id,
+            available
+        )
+    })?;
+
+    let mut agent = template.agent;
+    let mut missing_skills = Vec::new();
+    if let Some(skills) = agent.skills.take() {
+        let mut installed = Vec::new();
+        for skill_id in skills {
+            if core.storage.skills.exists(&skill_id)? {
+                installed.push(skill_id);
+            } else {
+                missing_skills.push(skill_id);
+            }
+        }
+        agent.skills = (!installed.is_empty()).then_some(installed);
+    }
+
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(template.name);
+    let agent = agent_service::create_agent(core, name, agent).await?;
+
+    let mut tasks = Vec::new();
+    for task in template.tasks {
+        let created = core
+            .storage
+            .background_agents
+            .create_background_agent(BackgroundAgentSpec {
+                name: task.name.clone(),
+                agent_id: agent.id.clone(),
+                chat_session_id: None,
+                description: task.description,
+                input: Some(task.input),
+                input_template: None,
+                schedule: task.schedule,
+                notification: None,
+                execution_mode: None,
+                timeout_secs: None,
+                memory: None,
+                durability_mode: None,
+                resource_limits: None,
+                prerequisites: Vec::new(),
+                continuation: None,
+                concurrency: None,
+            })
+            .with_context(|| format!("Failed to create task '{}'", task.name));
+        match created {
+            Ok(created) => tasks.push(created),
+            Err(err) => {
+                rollback(core, &agent, &tasks);
+                return Err(err);
+            }
+        }
+    }
+
+    let mut missing_secrets = Vec::new();
+    for secret in template.required_secrets {
+        if !core.storage.secrets.has_available_secret(&secret)? {
+            missing_secrets.push(secret);
+        }
+    }
+
+    Ok(ProvisionedAgentTemplate {
+        template_id: template.id,
+        agent,
+        tasks,
+        missing_skills,
+        missing_secrets,
+    })
+}
+
+/// Remove what a failed provisioning run already created
+fn rollback(core: &Arc<AppCore>, agent: &StoredAgent, tasks: &[Task]) {
+    for task in tasks {
+        if let Err(err) = core.storage.background_agents.delete_task(&task.id) {
+            tracing::warn!(task_id = %task.id, error = %err, "Failed to roll back template task");
+        }
+    }
+    if let Err(err) = core.storage.agents.delete_agent(agent.id.clone()) {
+        tracing::warn!(agent_id = %agent.id, error = %err, "Failed to roll back template agent");
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::await_holding_lock)]
+mod tests {
+    use super::*;
+    use crate::models::TaskSchedule;
+    use crate::prompt_files;
+    use tempfile::{TempDir, tempdir};
+
+    struct AgentsDirEnvGuard {
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl Drop for AgentsDirEnvGuard {
+        fn drop(&mut self) {
+            unsafe { std::env::remove_var(prompt_files::AGENTS_DIR_ENV) };
+        }
+    }
+
+    /// Create a test AppCore with an isolated agents directory.
+    /// All returned values must be held alive for the test duration.
+    async fn create_test_core() -> (Arc<AppCore>, TempDir, TempDir, AgentsDirEnvGuard) {
+        let env_guard = AgentsDirEnvGuard {
+            _lock: prompt_files::agents_dir_env_lock(),
+        };
+        let temp_db = tempdir().unwrap();
+        let temp_agents = tempdir().unwrap();
+        unsafe { std::env::set_var(prompt_files::AGENTS_DIR_ENV, temp_agents.path()) };
+        let db_path = temp_db.path().join("templates.db");
+        let core = Arc::new(AppCore::new(db_path.to_str().unwrap()).await.unwrap());
+        (core, temp_db, temp_agents, env_guard)
+    }
+
+    #[test]
+    fn test_builtin_templates_parse() {
+        let templates = list_agent_templates().unwrap();
+        let ids: Vec<_> = templates.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "research-assistant",
+                "inbox-triager",
+                "repo-reviewer",
+                "price-monitor"
+            ]
+        );
+        for (template, (id, _)) in templates.iter().zip(BUILTIN_TEMPLATES) {
+            assert_eq!(template.id, *id);
+            assert!(template.agent.prompt.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provision_creates_agent_and_tasks() {
+        let (core, _db, _agents, _guard) = create_test_core().await;
+
+        let provisioned =
+            provision_agent_template(&core, "price-monitor", Some("Shop prices".to_string()))
+                .await
+                .unwrap();
+
+        assert_eq!(provisioned.agent.name, "Shop prices");
+        assert_eq!(provisioned.tasks.len(), 1);
+        let task = &provisioned.tasks[0];
+        assert_eq!(task.agent_id, provisioned.agent.id);
+        assert!(matches!(
+            task.schedule,
+            TaskSchedule::Interval {
+                interval_ms: 3_600_000,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_provision_unknown_template() {
+        let (core, _db, _agents, _guard) = create_test_core().await;
+
+        let err = provision_agent_template(&core, "missing", None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("research-assistant"));
+    }
+}
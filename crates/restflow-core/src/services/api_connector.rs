@@ -0,0 +1,775 @@
+//! REST API connector service layer.
+//!
+//! Connectors are generated from OpenAPI 3 or Swagger 2 documents and stored
+//! by name. Every operation becomes an agent tool; calling one renders the
+//! HTTP request from the tool arguments and applies the connector's auth
+//! from stored secrets.
+
+use crate::{
+    AppCore,
+    models::{
+        ApiConnector, ApiKeyLocation, ConnectorAuth, ConnectorOperation, ConnectorParameter,
+        ParameterLocation,
+    },
+};
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use restflow_traits::secret_ref::resolve_secret_refs;
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "patch", "head", "options"];
+/// Nested `$ref`s deeper than this are replaced by an empty schema, which
+/// also bounds recursive schemas.
+const MAX_REF_DEPTH: usize = 8;
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// List every stored connector
+pub async fn list_api_connectors(core: &Arc<AppCore>) -> Result<Vec<ApiConnector>> {
+    core.storage
+        .api_connectors
+        .list()
+        .context("Failed to list API connectors")
+}
+
+/// Get a connector by name
+pub async fn get_api_connector(core: &Arc<AppCore>, name: &str) -> Result<Option<ApiConnector>> {
+    core.storage
+        .api_connectors
+        .get(name)
+        .with_context(|| format!("Failed to get API connector {}", name))
+}
+
+/// Generate a connector from an OpenAPI document and store it, replacing
+/// any connector with the same name
+pub async fn import_api_connector(
+    core: &Arc<AppCore>,
+    name: &str,
+    spec: &str,
+    base_url: Option<String>,
+    auth_secret: Option<String>,
+) -> Result<ApiConnector> {
+    let connector = parse_openapi(name, spec, base_url.as_deref(), auth_secret.as_deref())?;
+    core.storage.api_connectors.save(connector)
+}
+
+/// Delete a connector
+pub async fn delete_api_connector(core: &Arc<AppCore>, name: &str) -> Result<bool> {
+    core.storage
+        .api_connectors
+        .delete(name)
+        .with_context(|| format!("Failed to delete API connector {}", name))
+}
+
+/// Build a connector from an OpenAPI 3 or Swagger 2 document in JSON or YAML.
+///
+/// `base_url` overrides the server declared by the document. When
+/// `auth_secret` is set, it is applied with the document's first security
+/// scheme (bearer when the document declares none).
+pub fn parse_openapi(
+    name: &str,
+    spec: &str,
+    base_url: Option<&str>,
+    auth_secret: Option<&str>,
+) -> Result<ApiConnector> {
+    let document: Value = match serde_json::from_str(spec) {
+        Ok(document) => document,
+        Err(_) => serde_yaml::from_str(spec)
+            .context("OpenAPI document is neither valid JSON nor valid YAML")?,
+    };
+    let swagger2 = document
+        .get("swagger")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with('2'));
+    if !swagger2 && document.get("openapi").and_then(Value::as_str).is_none() {
+        bail!("Document is not an OpenAPI 3 or Swagger 2 specification");
+    }
+
+    let base_url = match base_url {
+        Some(url) => url.trim().trim_end_matches('/').to_string(),
+        None => document_base_url(&document, swagger2)?,
+    };
+    let parsed = url::Url::parse(&base_url)
+        .with_context(|| format!("Invalid connector base URL '{}'", base_url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        bail!("Connector base URL must use http or https");
+    }
+
+    let auth = match auth_secret {
+        Some(secret) => document_auth(&document, swagger2, secret)?,
+        None => ConnectorAuth::None,
+    };
+    let operations = document_operations(&document, swagger2)?;
+    if operations.is_empty() {
+        bail!("OpenAPI document defines no operations");
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    Ok(ApiConnector {
+        name: name.trim().to_string(),
+        description: document
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        base_url,
+        auth,
+        operations,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+fn document_base_url(document: &Value, swagger2: bool) -> Result<String> {
+    let url = if swagger2 {
+        let host = document
+            .get("host")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Swagger document has no host; pass a base URL"))?;
+        let scheme = document
+            .pointer("/schemes/0")
+            .and_then(Value::as_str)
+            .unwrap_or("https");
+        let base_path = document
+            .get("basePath")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        format!("{scheme}://{host}{base_path}")
+    } else {
+        let server = document
+            .pointer("/servers/0")
+            .ok_or_else(|| anyhow!("OpenAPI document declares no servers; pass a base URL"))?;
+        let mut url = server
+            .get("url")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+            for (variable, definition) in variables {
+                if let Some(default) = definition.get("default").and_then(Value::as_str) {
+                    url = url.replace(&format!("{{{variable}}}"), default);
+                }
+            }
+        }
+        if !url.contains("://") {
+            bail!("Server URL '{}' is relative; pass a base URL", url);
+        }
+        url
+    };
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+fn document_auth(document: &Value, swagger2: bool, secret: &str) -> Result<ConnectorAuth> {
+    let schemes_pointer = if swagger2 {
+        "/securityDefinitions"
+    } else {
+        "/components/securitySchemes"
+    };
+    let Some(schemes) = document.pointer(schemes_pointer).and_then(Value::as_object) else {
+        return Ok(ConnectorAuth::Bearer {
+            secret: secret.to_string(),
+        });
+    };
+    let preferred = document
+        .pointer("/security/0")
+        .and_then(Value::as_object)
+        .and_then(|requirement| requirement.keys().next())
+        .and_then(|name| schemes.get(name));
+    let Some(scheme) = preferred.or_else(|| schemes.values().next()) else {
+        return Ok(ConnectorAuth::Bearer {
+            secret: secret.to_string(),
+        });
+    };
+    let scheme = resolve_ref(document, scheme);
+
+    let secret = secret.to_string();
+    let scheme_type = scheme.get("type").and_then(Value::as_str).unwrap_or("");
+    match scheme_type {
+        "basic" => Ok(ConnectorAuth::Basic { secret }),
+        "http" => match scheme.get("scheme").and_then(Value::as_str) {
+            Some(http_scheme) if http_scheme.eq_ignore_ascii_case("basic") => {
+                Ok(ConnectorAuth::Basic { secret })
+            }
+            _ => Ok(ConnectorAuth::Bearer { secret }),
+        },
+        "apiKey" => {
+            let name = scheme
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("apiKey security scheme has no name"))?
+                .to_string();
+            let location = match scheme.get("in").and_then(Value::as_str) {
+                Some("header") => ApiKeyLocation::Header,
+                Some("query") => ApiKeyLocation::Query,
+                other => bail!(
+                    "Unsupported apiKey location '{}'",
+                    other.unwrap_or_default()
+                ),
+            };
+            Ok(ConnectorAuth::ApiKey {
+                secret,
+                name,
+                location,
+            })
+        }
+        "oauth2" | "openIdConnect" => Ok(ConnectorAuth::Bearer { secret }),
+        other => bail!("Unsupported security scheme type '{}'", other),
+    }
+}
+
+fn document_operations(document: &Value, swagger2: bool) -> Result<Vec<ConnectorOperation>> {
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| anyhow!("OpenAPI document has no paths"))?;
+
+    let mut operations = Vec::new();
+    let mut used_ids = HashSet::new();
+    for (path, item) in paths {
+        let item = resolve_ref(document, item);
+        let shared_parameters = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method) else {
+                continue;
+            };
+            let base_id = operation
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map(to_identifier)
+                .filter(|id| !id.is_empty())
+                .unwrap_or_else(|| to_identifier(&format!("{method}_{path}")));
+            let mut id = base_id.clone();
+            let mut suffix = 2;
+            while !used_ids.insert(id.clone()) {
+                id = format!("{base_id}_{suffix}");
+                suffix += 1;
+            }
+
+            let mut raw_parameters: Vec<&Value> = Vec::new();
+            let operation_parameters = operation
+                .get("parameters")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for parameter in shared_parameters.iter().chain(operation_parameters) {
+                let parameter = resolve_ref(document, parameter);
+                // Operation-level parameters override path-level ones.
+                raw_parameters.retain(|existing| {
+                    existing.get("name") != parameter.get("name")
+                        || existing.get("in") != parameter.get("in")
+                });
+                raw_parameters.push(parameter);
+            }
+
+            let mut parameters = Vec::new();
+            let mut request_body = None;
+            for parameter in raw_parameters {
+                let Some(name) = parameter.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                let location = match parameter.get("in").and_then(Value::as_str) {
+                    Some("path") => ParameterLocation::Path,
+                    Some("query") => ParameterLocation::Query,
+                    Some("header") => ParameterLocation::Header,
+                    Some("body") if swagger2 => {
+                        request_body = parameter
+                            .get("schema")
+                            .map(|schema| inline_refs(document, schema, 0));
+                        continue;
+                    }
+                    // Cookie and form parameters are not supported.
+                    _ => continue,
+                };
+                let schema = match parameter.get("schema") {
+                    Some(schema) => inline_refs(document, schema, 0),
+                    None => swagger2_parameter_schema(parameter),
+                };
+                parameters.push(ConnectorParameter {
+                    name: name.to_string(),
+                    location,
+                    required: location == ParameterLocation::Path
+                        || parameter
+                            .get("required")
+                            .and_then(Value::as_bool)
+                            .unwrap_or(false),
+                    description: parameter
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    schema,
+                });
+            }
+
+            if !swagger2 && let Some(body) = operation.get("requestBody") {
+                request_body = json_body_schema(resolve_ref(document, body))
+                    .map(|schema| inline_refs(document, schema, 0));
+            }
+
+            operations.push(ConnectorOperation {
+                id,
+                method: method.to_ascii_uppercase(),
+                path: path.clone(),
+                summary: operation
+                    .get("summary")
+                    .or_else(|| operation.get("description"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                parameters,
+                request_body,
+            });
+        }
+    }
+    Ok(operations)
+}
+
+/// Follow a local `$ref` (`#/...`); anything else is returned unchanged.
+fn resolve_ref<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..MAX_REF_DEPTH {
+        match current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        {
+            Some(target) => current = target,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Copy `schema` with every local `$ref` replaced by its target.
+fn inline_refs(document: &Value, schema: &Value, depth: usize) -> Value {
+    match schema {
+        Value::Object(object) => {
+            if object.contains_key("$ref") {
+                if depth >= MAX_REF_DEPTH {
+                    return json!({});
+                }
+                let target = resolve_ref(document, schema);
+                if std::ptr::eq(target, schema) {
+                    return json!({});
+                }
+                return inline_refs(document, target, depth + 1);
+            }
+            Value::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), inline_refs(document, value, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| inline_refs(document, item, depth))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Swagger 2 declares non-body parameter types inline.
+fn swagger2_parameter_schema(parameter: &Value) -> Value {
+    let mut schema = Map::new();
+    for key in ["type", "format", "items", "enum", "default"] {
+        if let Some(value) = parameter.get(key) {
+            schema.insert(key.to_string(), value.clone());
+        }
+    }
+    if schema.is_empty() {
+        schema.insert("type".to_string(), json!("string"));
+    }
+    Value::Object(schema)
+}
+
+fn json_body_schema(body: &Value) -> Option<&Value> {
+    let content = body.get("content")?.as_object()?;
+    content
+        .get("application/json")
+        .or_else(|| {
+            content
+                .iter()
+                .find(|(media_type, _)| media_type.contains("json"))
+                .map(|(_, media)| media)
+        })
+        .or_else(|| content.values().next())?
+        .get("schema")
+}
+
+/// Convert an operation id or path to a snake_case identifier.
+fn to_identifier(raw: &str) -> String {
+    let mut identifier = String::new();
+    let mut previous: Option<char> = None;
+    for ch in raw.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase()
+                && previous.is_some_and(|prev| prev.is_ascii_lowercase() || prev.is_ascii_digit())
+            {
+                identifier.push('_');
+            }
+            identifier.push(ch.to_ascii_lowercase());
+        } else if !identifier.is_empty() && !identifier.ends_with('_') {
+            identifier.push('_');
+        }
+        previous = Some(ch);
+    }
+    identifier.trim_end_matches('_').to_string()
+}
+
+/// Tool name under which an operation is exposed to agents.
+pub fn operation_tool_name(connector: &str, operation_id: &str) -> String {
+    let mut name = format!("{}_{}", to_identifier(connector), operation_id);
+    name.truncate(MAX_TOOL_NAME_LEN);
+    name
+}
+
+/// JSON schema of the tool arguments for an operation: one property per
+/// parameter plus `body` when the operation takes a request body.
+pub fn operation_parameters_schema(operation: &ConnectorOperation) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for parameter in &operation.parameters {
+        let mut schema = parameter.schema.clone();
+        if let (Some(description), Some(object)) =
+            (parameter.description.as_ref(), schema.as_object_mut())
+        {
+            object
+                .entry("description")
+                .or_insert_with(|| json!(description));
+        }
+        properties.insert(parameter.name.clone(), schema);
+        if parameter.required {
+            required.push(parameter.name.clone());
+        }
+    }
+    if let Some(body) = &operation.request_body {
+        properties.insert("body".to_string(), body.clone());
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// Render the HTTP request for an operation call.
+///
+/// Returns `{ method, url, headers, body }` with auth applied. Secrets are
+/// looked up by name through `resolve_secret`, including `${secret:NAME}`
+/// references in the connector's base URL.
+pub fn prepare_request(
+    connector: &ApiConnector,
+    operation_id: &str,
+    args: &Value,
+    resolve_secret: impl Fn(&str) -> Result<Option<String>>,
+) -> Result<Value> {
+    let operation = connector.operation(operation_id).ok_or_else(|| {
+        anyhow!(
+            "Connector '{}' has no operation '{}'",
+            connector.name,
+            operation_id
+        )
+    })?;
+
+    let mut path = operation.path.clone();
+    let mut query: Vec<(String, String)> = Vec::new();
+    let mut headers = Map::new();
+    for parameter in &operation.parameters {
+        let Some(value) = args.get(&parameter.name).filter(|value| !value.is_null()) else {
+            if parameter.required {
+                bail!("Missing required parameter '{}'", parameter.name);
+            }
+            continue;
+        };
+        match parameter.location {
+            ParameterLocation::Path => {
+                path = path.replace(
+                    &format!("{{{}}}", parameter.name),
+                    &urlencoding::encode(&scalar_to_string(value)),
+                );
+            }
+            ParameterLocation::Query => match value {
+                Value::Array(items) => query.extend(
+                    items
+                        .iter()
+                        .map(|item| (parameter.name.clone(), scalar_to_string(item))),
+                ),
+                _ => query.push((parameter.name.clone(), scalar_to_string(value))),
+            },
+            ParameterLocation::Header => {
+                headers.insert(parameter.name.clone(), json!(scalar_to_string(value)));
+            }
+        }
+    }
+
+    let lookup = |secret: &str| -> Result<String> {
+        resolve_secret(secret)?.ok_or_else(|| {
+            anyhow!(
+                "Secret '{}' for connector '{}' not found",
+                secret,
+                connector.name
+            )
+        })
+    };
+    match &connector.auth {
+        ConnectorAuth::None => {}
+        ConnectorAuth::Bearer { secret } => {
+            headers.insert(
+                "Authorization".to_string(),
+                json!(format!("Bearer {}", lookup(secret)?)),
+            );
+        }
+        ConnectorAuth::ApiKey {
+            secret,
+            name,
+            location,
+        } => match location {
+            ApiKeyLocation::Header => {
+                headers.insert(name.clone(), json!(lookup(secret)?));
+            }
+            ApiKeyLocation::Query => query.push((name.clone(), lookup(secret)?)),
+        },
+        ConnectorAuth::Basic { secret } => {
+            headers.insert(
+                "Authorization".to_string(),
+                json!(format!("Basic {}", BASE64.encode(lookup(secret)?))),
+            );
+        }
+    }
+
+    let base_url = resolve_secret_refs(&connector.base_url, |secret| {
+        resolve_secret(secret).ok().flatten()
+    })
+    .with_context(|| format!("Invalid base URL for connector '{}'", connector.name))?;
+    let mut url = url::Url::parse(&format!("{}{}", base_url.trim_end_matches('/'), path))
+        .with_context(|| format!("Invalid URL for operation '{}'", operation.id))?;
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+
+    Ok(json!({
+        "method": operation.method,
+        "url": url.to_string(),
+        "headers": headers,
+        "body": args.get("body").cloned().unwrap_or(Value::Null),
+    }))
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE_YAML: &str = r##"
+openapi: 3.0.0
+info:
+  title: Petstore
+servers:
+  - url: https://{region}.petstore.test/v1
+    variables:
+      region:
+        default: eu
+security:
+  - api_key: []
+components:
+  securitySchemes:
+    api_key:
+      type: apiKey
+      in: header
+      name: X-Api-Key
+  parameters:
+    Limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+        tag:
+          $ref: '#/components/schemas/Tag'
+    Tag:
+      type: string
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: List pets
+      parameters:
+        - $ref: '#/components/parameters/Limit'
+    post:
+      operationId: createPet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        schema:
+          type: string
+    get:
+      summary: Get a pet
+"##;
+
+    #[test]
+    fn test_parse_openapi3_yaml() {
+        let connector = parse_openapi("petstore", PETSTORE_YAML, None, Some("PET_KEY")).unwrap();
+        assert_eq!(connector.base_url, "https://eu.petstore.test/v1");
+        assert_eq!(connector.description.as_deref(), Some("Petstore"));
+        assert_eq!(
+            connector.auth,
+            ConnectorAuth::ApiKey {
+                secret: "PET_KEY".to_string(),
+                name: "X-Api-Key".to_string(),
+                location: ApiKeyLocation::Header,
+            }
+        );
+
+        let ids: Vec<&str> = connector
+            .operations
+            .iter()
+            .map(|operation| operation.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["list_pets", "create_pet", "get_pets_pet_id"]);
+
+        let list = connector.operation("list_pets").unwrap();
+        assert_eq!(list.parameters[0].name, "limit");
+        assert_eq!(list.parameters[0].location, ParameterLocation::Query);
+
+        let create = connector.operation("create_pet").unwrap();
+        let body = create.request_body.as_ref().unwrap();
+        assert_eq!(body["properties"]["tag"]["type"], "string");
+
+        let get = connector.operation("get_pets_pet_id").unwrap();
+        assert!(get.parameters[0].required);
+        assert_eq!(get.summary.as_deref(), Some("Get a pet"));
+    }
+
+    #[test]
+    fn test_parse_swagger2_json_with_body_parameter() {
+        let spec = json!({
+            "swagger": "2.0",
+            "info": { "title": "Notes" },
+            "host": "notes.test",
+            "basePath": "/api",
+            "schemes": ["https"],
+            "securityDefinitions": { "basic": { "type": "basic" } },
+            "paths": {
+                "/notes": {
+                    "post": {
+                        "parameters": [
+                            { "name": "note", "in": "body", "schema": { "type": "object" } },
+                            { "name": "draft", "in": "query", "type": "boolean" }
+                        ]
+                    }
+                }
+            }
+        });
+        let connector =
+            parse_openapi("notes", &spec.to_string(), None, Some("NOTES_LOGIN")).unwrap();
+        assert_eq!(connector.base_url, "https://notes.test/api");
+        assert!(matches!(connector.auth, ConnectorAuth::Basic { .. }));
+        let operation = &connector.operations[0];
+        assert_eq!(operation.id, "post_notes");
+        assert_eq!(operation.request_body, Some(json!({ "type": "object" })));
+        assert_eq!(operation.parameters[0].schema, json!({ "type": "boolean" }));
+    }
+
+    #[test]
+    fn test_parse_rejects_relative_server_without_base_url() {
+        let spec = r#"{"openapi":"3.0.0","servers":[{"url":"/v1"}],"paths":{"/a":{"get":{}}}}"#;
+        assert!(parse_openapi("rel", spec, None, None).is_err());
+        let connector = parse_openapi("rel", spec, Some("https://rel.test/v1/"), None).unwrap();
+        assert_eq!(connector.base_url, "https://rel.test/v1");
+        assert!(parse_openapi("bad", "{\"info\":{}}", None, None).is_err());
+    }
+
+    #[test]
+    fn test_prepare_request_applies_parameters_and_auth() {
+        let connector = parse_openapi("petstore", PETSTORE_YAML, None, Some("PET_KEY")).unwrap();
+        let resolve = |name: &str| Ok((name == "PET_KEY").then(|| "secret-value".to_string()));
+
+        let request = prepare_request(
+            &connector,
+            "get_pets_pet_id",
+            &json!({ "petId": "a b" }),
+            resolve,
+        )
+        .unwrap();
+        assert_eq!(request["method"], "GET");
+        assert_eq!(request["url"], "https://eu.petstore.test/v1/pets/a%20b");
+        assert_eq!(request["headers"]["X-Api-Key"], "secret-value");
+
+        let request =
+            prepare_request(&connector, "list_pets", &json!({ "limit": 5 }), resolve).unwrap();
+        assert_eq!(request["url"], "https://eu.petstore.test/v1/pets?limit=5");
+
+        let error = prepare_request(&connector, "get_pets_pet_id", &json!({}), resolve)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("petId"));
+        assert!(prepare_request(&connector, "missing", &json!({}), resolve).is_err());
+
+        let missing_secret = |_: &str| Ok(None);
+        assert!(prepare_request(&connector, "list_pets", &json!({}), missing_secret).is_err());
+    }
+
+    #[test]
+    fn test_prepare_request_resolves_base_url_secret_refs() {
+        let mut connector =
+            parse_openapi("petstore", PETSTORE_YAML, None, Some("PET_KEY")).unwrap();
+        connector.base_url = "https://${secret:PET_TENANT}.petstore.test/v1".to_string();
+        let resolve = |name: &str| {
+            Ok(match name {
+                "PET_KEY" => Some("secret-value".to_string()),
+                "PET_TENANT" => Some("acme".to_string()),
+                _ => None,
+            })
+        };
+
+        let request = prepare_request(&connector, "list_pets", &json!({}), resolve).unwrap();
+        assert_eq!(request["url"], "https://acme.petstore.test/v1/pets");
+
+        connector.base_url = "https://${secret:UNKNOWN}.petstore.test".to_string();
+        assert!(prepare_request(&connector, "list_pets", &json!({}), resolve).is_err());
+    }
+
+    #[test]
+    fn test_tool_names_and_schema() {
+        assert_eq!(
+            operation_tool_name("Pet Store", "list_pets"),
+            "pet_store_list_pets"
+        );
+        assert_eq!(
+            operation_tool_name("x", &"a".repeat(100)).len(),
+            MAX_TOOL_NAME_LEN
+        );
+
+        let connector = parse_openapi("petstore", PETSTORE_YAML, None, None).unwrap();
+        assert_eq!(connector.auth, ConnectorAuth::None);
+        let schema = operation_parameters_schema(connector.operation("get_pets_pet_id").unwrap());
+        assert_eq!(schema["required"], json!(["petId"]));
+        let schema = operation_parameters_schema(connector.operation("create_pet").unwrap());
+        assert_eq!(schema["properties"]["body"]["type"], "object");
+    }
+}
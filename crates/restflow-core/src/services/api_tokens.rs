@@ -0,0 +1,381 @@
+//! Issuance and enforcement of scoped API tokens for external callers.
+
+use crate::models::{ApiToken, ApiTokenScope};
+use crate::storage::{ApiTokenStorage, Storage};
+use anyhow::Result;
+use rand::RngExt;
+use rand::distr::Alphanumeric;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Prefix applied to every issued token secret.
+pub const API_TOKEN_SECRET_PREFIX: &str = "rft_";
+const API_TOKEN_SECRET_LEN: usize = 40;
+const API_TOKEN_DISPLAY_PREFIX_LEN: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum ApiTokenError {
+    #[error("invalid API token")]
+    Invalid,
+    #[error("API token has been revoked or has expired")]
+    Inactive,
+    #[error("API token call budget exhausted")]
+    BudgetExhausted,
+    #[error("API token rate limit exceeded")]
+    RateLimited,
+    #[error("API token is not allowed to {0}")]
+    Forbidden(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl ApiTokenError {
+    /// HTTP-style status code for this error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::Invalid | Self::Inactive => 401,
+            Self::Forbidden(_) => 403,
+            Self::BudgetExhausted | Self::RateLimited => 429,
+            Self::Internal(_) => 500,
+        }
+    }
+}
+
+/// A freshly issued token. The plaintext secret is only available here.
+#[derive(Debug, Clone)]
+pub struct IssuedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+/// An authenticated token, used to authorize individual calls.
+#[derive(Debug, Clone)]
+pub struct ApiTokenGrant {
+    token: ApiToken,
+    tokens: ApiTokenStorage,
+}
+
+impl ApiTokenGrant {
+    pub fn token(&self) -> &ApiToken {
+        &self.token
+    }
+
+    pub fn scope(&self) -> &ApiTokenScope {
+        &self.token.scope
+    }
+
+    /// Authorize one MCP tool call targeting `agent_ids` and count it against
+    /// the token budget.
+    pub fn authorize_tool_call(
+        &self,
+        tool_name: &str,
+        agent_ids: &[String],
+    ) -> Result<ApiToken, ApiTokenError> {
+        self.record_use(|scope| {
+            if !scope.mcp {
+                return Err(ApiTokenError::Forbidden("access MCP".to_string()));
+            }
+            if !scope.allows_tool(tool_name) {
+                return Err(ApiTokenError::Forbidden(format!("call tool '{tool_name}'")));
+            }
+            agent_ids
+                .iter()
+                .try_for_each(|agent_id| check_agent(scope, Some(agent_id)))
+        })
+    }
+
+    /// Authorize one webhook trigger for a task owned by `agent_id` and count
+    /// it against the token budget.
+    pub fn authorize_webhook(&self, agent_id: &str) -> Result<ApiToken, ApiTokenError> {
+        self.record_use(|scope| {
+            if !scope.webhooks {
+                return Err(ApiTokenError::Forbidden("trigger webhooks".to_string()));
+            }
+            check_agent(scope, Some(agent_id))
+        })
+    }
+
+    /// Authorize one OpenAI-compatible chat completion against `agent_id` and
+    /// count it against the token budget.
+    pub fn authorize_chat_completion(&self, agent_id: &str) -> Result<ApiToken, ApiTokenError> {
+        self.record_use(|scope| {
+            if !scope.openai_api {
+                return Err(ApiTokenError::Forbidden(
+                    "use the OpenAI-compatible API".to_string(),
+                ));
+            }
+            check_agent(scope, Some(agent_id))
+        })
+    }
+
+    fn record_use(
+        &self,
+        check: impl FnOnce(&ApiTokenScope) -> Result<(), ApiTokenError>,
+    ) -> Result<ApiToken, ApiTokenError> {
+        // Check and count in one write transaction so concurrent callers
+        // cannot both pass the budget check.
+        let now = chrono::Utc::now().timestamp_millis();
+        self.tokens
+            .modify(&self.token.id, |token| {
+                if !token.is_active(now) {
+                    return Err(ApiTokenError::Inactive);
+                }
+                check(&token.scope)?;
+                if token.budget_exhausted() {
+                    return Err(ApiTokenError::BudgetExhausted);
+                }
+                token.call_count = token.call_count.saturating_add(1);
+                token.last_used_at = Some(now);
+                Ok(token.clone())
+            })?
+            .ok_or(ApiTokenError::Invalid)?
+    }
+}
+
+fn check_agent(scope: &ApiTokenScope, agent_id: Option<&str>) -> Result<(), ApiTokenError> {
+    match agent_id {
+        Some(agent_id) if !scope.allows_agent(agent_id) => {
+            Err(ApiTokenError::Forbidden(format!("run agent '{agent_id}'")))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiTokenService {
+    tokens: ApiTokenStorage,
+}
+
+impl ApiTokenService {
+    pub fn new(tokens: ApiTokenStorage) -> Self {
+        Self { tokens }
+    }
+
+    pub fn from_storage(storage: &Storage) -> Self {
+        Self::new(storage.api_tokens.clone())
+    }
+
+    /// Issue a new token for an external caller.
+    pub fn issue(
+        &self,
+        name: String,
+        scope: ApiTokenScope,
+        expires_at: Option<i64>,
+    ) -> Result<IssuedApiToken> {
+        let random: String = rand::rng()
+            .sample_iter(&Alphanumeric)
+            .take(API_TOKEN_SECRET_LEN)
+            .map(char::from)
+            .collect();
+        let secret = format!("{API_TOKEN_SECRET_PREFIX}{random}");
+
+        let token = ApiToken {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            token_hash: hash_secret(&secret),
+            token_prefix: secret[..API_TOKEN_DISPLAY_PREFIX_LEN].to_string(),
+            scope,
+            call_count: 0,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            last_used_at: None,
+            expires_at,
+            revoked_at: None,
+        };
+        self.tokens.create(&token)?;
+
+        Ok(IssuedApiToken { token, secret })
+    }
+
+    /// List tokens, optionally including revoked and expired ones.
+    pub fn list(&self, include_inactive: bool) -> Result<Vec<ApiToken>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        Ok(self
+            .tokens
+            .list()?
+            .into_iter()
+            .filter(|token| include_inactive || token.is_active(now))
+            .collect())
+    }
+
+    /// Revoke a token. Returns `false` when the token does not exist.
+    pub fn revoke(&self, id: &str) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let revoked = self.tokens.modify(id, |token| {
+            if token.revoked_at.is_some() {
+                // Already revoked; skip the write.
+                return Err(());
+            }
+            token.revoked_at = Some(now);
+            Ok(())
+        })?;
+        Ok(revoked.is_some())
+    }
+
+    /// Resolve a presented secret into a grant, recording last use.
+    pub fn authenticate(&self, secret: &str) -> Result<ApiTokenGrant, ApiTokenError> {
+        if !secret.starts_with(API_TOKEN_SECRET_PREFIX) {
+            return Err(ApiTokenError::Invalid);
+        }
+        let found = self
+            .tokens
+            .find_by_hash(&hash_secret(secret))?
+            .ok_or(ApiTokenError::Invalid)?;
+        let now = chrono::Utc::now().timestamp_millis();
+        // Update in place so a concurrent call count is not overwritten.
+        let token = self
+            .tokens
+            .modify(&found.id, |token| {
+                if !token.is_active(now) {
+                    return Err(ApiTokenError::Inactive);
+                }
+                token.last_used_at = Some(now);
+                Ok(token.clone())
+            })?
+            .ok_or(ApiTokenError::Invalid)??;
+
+        Ok(ApiTokenGrant {
+            token,
+            tokens: self.tokens.clone(),
+        })
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redb::Database;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn setup() -> (ApiTokenService, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("create temp dir");
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).expect("create db"));
+        let storage = ApiTokenStorage::new(db).expect("create storage");
+        (ApiTokenService::new(storage), temp_dir)
+    }
+
+    #[test]
+    fn test_issue_and_authenticate() {
+        let (service, _temp_dir) = setup();
+        let issued = service
+            .issue("ci".to_string(), ApiTokenScope::default(), None)
+            .expect("issue token");
+
+        assert!(issued.secret.starts_with(API_TOKEN_SECRET_PREFIX));
+        assert_ne!(issued.token.token_hash, issued.secret);
+        assert!(issued.secret.starts_with(&issued.token.token_prefix));
+
+        let grant = service.authenticate(&issued.secret).expect("authenticate");
+        assert_eq!(grant.token().id, issued.token.id);
+        assert!(grant.token().last_used_at.is_some());
+
+        assert!(matches!(
+            service.authenticate("rft_wrong"),
+            Err(ApiTokenError::Invalid)
+        ));
+    }
+
+    #[test]
+    fn test_revoked_token_is_rejected() {
+        let (service, _temp_dir) = setup();
+        let issued = service
+            .issue("ci".to_string(), ApiTokenScope::default(), None)
+            .expect("issue token");
+        let grant = service.authenticate(&issued.secret).expect("authenticate");
+
+        assert!(service.revoke(&issued.token.id).expect("revoke"));
+        assert!(!service.revoke("missing").expect("revoke missing"));
+
+        assert!(matches!(
+            service.authenticate(&issued.secret),
+            Err(ApiTokenError::Inactive)
+        ));
+        assert!(matches!(
+            grant.authorize_tool_call("list_skills", &[]),
+            Err(ApiTokenError::Inactive)
+        ));
+        assert!(service.list(false).expect("list").is_empty());
+        assert_eq!(service.list(true).expect("list").len(), 1);
+    }
+
+    #[test]
+    fn test_scope_and_budget_enforcement() {
+        let (service, _temp_dir) = setup();
+        let scope = ApiTokenScope {
+            allowed_agents: Some(vec!["agent-a".to_string()]),
+            allowed_tools: Some(vec!["get_agent".to_string()]),
+            max_calls: Some(1),
+            mcp: true,
+            webhooks: false,
+            openai_api: false,
+            role: None,
+            rate_limit_per_minute: None,
+            user_id: None,
+        };
+        let issued = service
+            .issue("limited".to_string(), scope, None)
+            .expect("issue token");
+        let grant = service.authenticate(&issued.secret).expect("authenticate");
+
+        assert!(matches!(
+            grant.authorize_tool_call("delete_skill", &[]),
+            Err(ApiTokenError::Forbidden(_))
+        ));
+        assert!(matches!(
+            grant.authorize_tool_call("get_agent", &["agent-b".to_string()]),
+            Err(ApiTokenError::Forbidden(_))
+        ));
+        assert!(matches!(
+            grant.authorize_webhook("agent-a"),
+            Err(ApiTokenError::Forbidden(_))
+        ));
+        assert!(matches!(
+            grant.authorize_chat_completion("agent-a"),
+            Err(ApiTokenError::Forbidden(_))
+        ));
+
+        let token = grant
+            .authorize_tool_call("get_agent", &["agent-a".to_string()])
+            .expect("allowed call");
+        assert_eq!(token.call_count, 1);
+
+        let err = grant
+            .authorize_tool_call("get_agent", &["agent-a".to_string()])
+            .expect_err("budget exhausted");
+        assert!(matches!(err, ApiTokenError::BudgetExhausted));
+        assert_eq!(err.status_code(), 429);
+    }
+
+    #[test]
+    fn test_concurrent_calls_do_not_exceed_budget() {
+        let (service, _temp_dir) = setup();
+        let scope = ApiTokenScope {
+            max_calls: Some(5),
+            ..ApiTokenScope::default()
+        };
+        let issued = service
+            .issue("burst".to_string(), scope, None)
+            .expect("issue token");
+        let grant = service.authenticate(&issued.secret).expect("authenticate");
+
+        let handles: Vec<_> = (0..20)
+            .map(|_| {
+                let grant = grant.clone();
+                std::thread::spawn(move || grant.authorize_tool_call("get_agent", &[]).is_ok())
+            })
+            .collect();
+        let allowed = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("join"))
+            .filter(|ok| *ok)
+            .count();
+
+        assert_eq!(allowed, 5);
+        let stored = service.list(true).expect("list");
+        assert_eq!(stored[0].call_count, 5);
+    }
+}
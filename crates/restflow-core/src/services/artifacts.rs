@@ -0,0 +1,639 @@
+//! Lifecycle management for on-disk execution artifacts.
+//!
+//! Tool output scratchpads, chat media and browser session directories are
+//! written under several roots. [`ArtifactRegistry`] lists those roots and
+//! turns each top-level entry into an [`ArtifactEntry`] with its owner and
+//! size. [`collect_garbage`] removes entries whose owner is gone, that are
+//! past retention, or that push the total over the size cap, while keeping
+//! anything a stored deliverable still points at.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{debug, warn};
+
+use crate::AppCore;
+use crate::storage::Storage;
+
+const TOOL_OUTPUT_DIR: &str = "tool-output";
+const MEDIA_DIR: &str = "media";
+/// Same resolution as the browser session manager in `restflow-browser`.
+const BROWSER_DIR_ENV: &str = "RESTFLOW_BROWSER_DIR";
+const BROWSER_DIR: &str = "restflow-browser";
+const DAY_SECS: u64 = 24 * 60 * 60;
+const MB: u64 = 1024 * 1024;
+/// Entries touched more recently than this are assumed to be in use.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// `~/.restflow/tool-output/{task_id}`: spilled tool output of background tasks.
+    ToolOutput,
+    /// `~/.restflow/media/{session_id}`: channel media attached to chat sessions.
+    Media,
+    /// Browser session directories (profile, screenshots, fixtures).
+    BrowserSession,
+}
+
+impl ArtifactKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ToolOutput => "tool_output",
+            Self::Media => "media",
+            Self::BrowserSession => "browser_session",
+        }
+    }
+}
+
+/// A directory whose immediate children are artifacts of one kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactLocation {
+    pub kind: ArtifactKind,
+    pub root: PathBuf,
+}
+
+/// One top-level file or directory below an artifact location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactEntry {
+    pub kind: ArtifactKind,
+    /// Background task or chat session the entry belongs to. `None` for
+    /// loose files that were never assigned to an owner.
+    pub owner_id: Option<String>,
+    pub path: PathBuf,
+    /// Total size of all files below the entry. Symlinks are not followed.
+    pub size_bytes: u64,
+    /// Newest modification time of anything below the entry.
+    pub modified: SystemTime,
+}
+
+/// Whether an entry's owner still exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerStatus {
+    Live,
+    Missing,
+    /// Ownership cannot be checked; only age and size rules apply.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactRegistry {
+    locations: Vec<ArtifactLocation>,
+}
+
+impl ArtifactRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the standard tool output, media and browser roots.
+    pub fn with_defaults() -> Result<Self> {
+        let restflow_dir = crate::paths::resolve_restflow_dir()?;
+        let browser_root = std::env::var_os(BROWSER_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join(BROWSER_DIR));
+        Ok(Self::new()
+            .with_location(ArtifactKind::ToolOutput, restflow_dir.join(TOOL_OUTPUT_DIR))
+            .with_location(ArtifactKind::Media, restflow_dir.join(MEDIA_DIR))
+            .with_location(ArtifactKind::BrowserSession, browser_root))
+    }
+
+    pub fn with_location(mut self, kind: ArtifactKind, root: impl Into<PathBuf>) -> Self {
+        self.locations.push(ArtifactLocation {
+            kind,
+            root: root.into(),
+        });
+        self
+    }
+
+    pub fn locations(&self) -> &[ArtifactLocation] {
+        &self.locations
+    }
+
+    /// List every artifact below the registered roots. Missing roots are
+    /// skipped and unreadable entries are logged and ignored.
+    pub fn scan(&self) -> Vec<ArtifactEntry> {
+        let mut entries = Vec::new();
+        for location in &self.locations {
+            let dir = match std::fs::read_dir(&location.root) {
+                Ok(dir) => dir,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => {
+                    warn!(
+                        root = %location.root.display(),
+                        error = %error,
+                        "Failed to scan artifact root"
+                    );
+                    continue;
+                }
+            };
+
+            for item in dir.flatten() {
+                let path = item.path();
+                let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+                    continue;
+                };
+                let (size_bytes, modified) = measure(&path, &metadata);
+                entries.push(ArtifactEntry {
+                    kind: location.kind,
+                    owner_id: owner_id(location.kind, &path, &metadata),
+                    path,
+                    size_bytes,
+                    modified,
+                });
+            }
+        }
+        entries
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        path.parent().is_some_and(|parent| {
+            self.locations
+                .iter()
+                .any(|location| location.root == parent)
+        })
+    }
+}
+
+/// Rules applied by [`collect_garbage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactGcPolicy {
+    /// Remove entries not modified for this long. `None` keeps them until
+    /// their owner disappears or the size cap is hit.
+    pub retention: Option<Duration>,
+    /// Cap on the combined size of all entries; the oldest go first.
+    pub max_total_bytes: Option<u64>,
+    /// Entries modified within this window are never removed.
+    pub grace_period: Duration,
+    /// Report what would be removed without deleting anything.
+    pub dry_run: bool,
+}
+
+impl ArtifactGcPolicy {
+    /// Build a policy from `system.artifact_retention_days` and
+    /// `system.artifact_max_total_mb`, where 0 disables the rule.
+    pub fn from_settings(retention_days: u32, max_total_mb: u64) -> Self {
+        Self {
+            retention: (retention_days > 0)
+                .then(|| Duration::from_secs(retention_days as u64 * DAY_SECS)),
+            max_total_bytes: (max_total_mb > 0).then(|| max_total_mb.saturating_mul(MB)),
+            grace_period: DEFAULT_GRACE_PERIOD,
+            dry_run: false,
+        }
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalReason {
+    /// The owning task or session no longer exists.
+    Orphaned,
+    /// Older than the retention period.
+    Expired,
+    /// Removed to get back under the total size cap.
+    OverQuota,
+}
+
+impl RemovalReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Orphaned => "orphaned",
+            Self::Expired => "expired",
+            Self::OverQuota => "over_quota",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArtifactRemoval {
+    pub kind: ArtifactKind,
+    pub owner_id: Option<String>,
+    pub path: String,
+    pub size_bytes: u64,
+    pub reason: RemovalReason,
+}
+
+/// Remaining artifacts of one kind after a GC pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArtifactUsage {
+    pub entries: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArtifactGcReport {
+    pub dry_run: bool,
+    pub scanned: usize,
+    /// Entries kept because a deliverable references a file inside them.
+    pub preserved: usize,
+    pub removed: Vec<ArtifactRemoval>,
+    pub bytes_reclaimed: u64,
+    pub bytes_remaining: u64,
+    pub usage: BTreeMap<ArtifactKind, ArtifactUsage>,
+}
+
+/// Apply `policy` to everything below the registry's roots.
+///
+/// `owner_status` decides whether an entry is orphaned. Entries containing
+/// any of `preserved` (typically deliverable file paths) are always kept.
+pub fn collect_garbage(
+    registry: &ArtifactRegistry,
+    policy: &ArtifactGcPolicy,
+    preserved: &HashSet<PathBuf>,
+    owner_status: impl Fn(&ArtifactEntry) -> OwnerStatus,
+    now: SystemTime,
+) -> ArtifactGcReport {
+    let entries = registry.scan();
+    let mut report = ArtifactGcReport {
+        dry_run: policy.dry_run,
+        scanned: entries.len(),
+        ..Default::default()
+    };
+
+    let mut kept = Vec::new();
+    let mut candidates = Vec::new();
+    for entry in entries {
+        if is_preserved(&entry.path, preserved) {
+            report.preserved += 1;
+            kept.push(entry);
+            continue;
+        }
+        let age = now.duration_since(entry.modified).unwrap_or_default();
+        if age < policy.grace_period {
+            kept.push(entry);
+            continue;
+        }
+
+        let reason = if owner_status(&entry) == OwnerStatus::Missing {
+            Some(RemovalReason::Orphaned)
+        } else if policy.retention.is_some_and(|retention| age > retention) {
+            Some(RemovalReason::Expired)
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => remove_entry(registry, policy, entry, reason, &mut report, &mut kept),
+            None => candidates.push(entry),
+        }
+    }
+
+    if let Some(max_total_bytes) = policy.max_total_bytes {
+        let mut total: u64 = kept
+            .iter()
+            .chain(candidates.iter())
+            .map(|entry| entry.size_bytes)
+            .sum();
+        candidates.sort_by_key(|entry| entry.modified);
+        let mut remaining = Vec::new();
+        for entry in candidates {
+            if total > max_total_bytes {
+                total = total.saturating_sub(entry.size_bytes);
+                remove_entry(
+                    registry,
+                    policy,
+                    entry,
+                    RemovalReason::OverQuota,
+                    &mut report,
+                    &mut kept,
+                );
+            } else {
+                remaining.push(entry);
+            }
+        }
+        candidates = remaining;
+    }
+
+    for entry in kept.iter().chain(candidates.iter()) {
+        let usage = report.usage.entry(entry.kind).or_default();
+        usage.entries += 1;
+        usage.size_bytes += entry.size_bytes;
+        report.bytes_remaining += entry.size_bytes;
+    }
+    report
+}
+
+/// Run a GC pass over the default artifact roots using the effective config.
+pub async fn run_artifact_gc(core: &Arc<AppCore>, dry_run: bool) -> Result<ArtifactGcReport> {
+    let config = core.storage.config.get_effective_config()?;
+    let policy = ArtifactGcPolicy::from_settings(
+        config.artifact_retention_days,
+        config.artifact_max_total_mb,
+    )
+    .dry_run(dry_run);
+    let registry = ArtifactRegistry::with_defaults()?;
+    let storage = core.storage.clone();
+
+    tokio::task::spawn_blocking(move || -> Result<ArtifactGcReport> {
+        let preserved = deliverable_paths(&storage)?;
+        Ok(collect_garbage(
+            &registry,
+            &policy,
+            &preserved,
+            |entry| storage_owner_status(&storage, entry),
+            SystemTime::now(),
+        ))
+    })
+    .await?
+}
+
+/// Files referenced by stored deliverables.
+fn deliverable_paths(storage: &Storage) -> Result<HashSet<PathBuf>> {
+    Ok(storage
+        .deliverables
+        .list()?
+        .into_iter()
+        .filter_map(|deliverable| deliverable.file_path)
+        .map(|path| normalize(Path::new(&path)))
+        .collect())
+}
+
+fn storage_owner_status(storage: &Storage, entry: &ArtifactEntry) -> OwnerStatus {
+    let Some(owner_id) = entry.owner_id.as_deref() else {
+        return OwnerStatus::Unknown;
+    };
+    let exists = match entry.kind {
+        ArtifactKind::ToolOutput => storage
+            .background_agents
+            .get_task(owner_id)
+            .map(|task| task.is_some()),
+        ArtifactKind::Media => storage.chat_sessions.exists(owner_id),
+        // Browser sessions only live in the daemon's memory.
+        ArtifactKind::BrowserSession => return OwnerStatus::Unknown,
+    };
+    match exists {
+        Ok(true) => OwnerStatus::Live,
+        Ok(false) => OwnerStatus::Missing,
+        Err(error) => {
+            warn!(owner_id, error = %error, "Failed to resolve artifact owner");
+            OwnerStatus::Unknown
+        }
+    }
+}
+
+fn remove_entry(
+    registry: &ArtifactRegistry,
+    policy: &ArtifactGcPolicy,
+    entry: ArtifactEntry,
+    reason: RemovalReason,
+    report: &mut ArtifactGcReport,
+    kept: &mut Vec<ArtifactEntry>,
+) {
+    // Only ever delete direct children of a registered root.
+    if !registry.contains(&entry.path) {
+        kept.push(entry);
+        return;
+    }
+    if !policy.dry_run
+        && let Err(error) = delete_path(&entry.path)
+    {
+        warn!(path = %entry.path.display(), error = %error, "Failed to remove artifact");
+        kept.push(entry);
+        return;
+    }
+
+    debug!(
+        path = %entry.path.display(),
+        kind = entry.kind.as_str(),
+        reason = ?reason,
+        dry_run = policy.dry_run,
+        "Removed artifact"
+    );
+    report.bytes_reclaimed += entry.size_bytes;
+    report.removed.push(ArtifactRemoval {
+        kind: entry.kind,
+        owner_id: entry.owner_id,
+        path: entry.path.to_string_lossy().into_owned(),
+        size_bytes: entry.size_bytes,
+        reason,
+    });
+}
+
+fn delete_path(path: &Path) -> std::io::Result<()> {
+    // `remove_dir_all` does not follow symlinks, and a symlinked entry is
+    // removed as a link rather than through its target.
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+fn owner_id(kind: ArtifactKind, path: &Path, metadata: &std::fs::Metadata) -> Option<String> {
+    // Loose files directly in the media root have not been moved into a
+    // session directory yet.
+    if kind == ArtifactKind::Media && !metadata.is_dir() {
+        return None;
+    }
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+}
+
+fn measure(path: &Path, metadata: &std::fs::Metadata) -> (u64, SystemTime) {
+    let mut modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    if !metadata.is_dir() {
+        return (metadata.len(), modified);
+    }
+
+    let mut size = 0;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(items) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for item in items.flatten() {
+            let Ok(metadata) = std::fs::symlink_metadata(item.path()) else {
+                continue;
+            };
+            if let Ok(time) = metadata.modified() {
+                modified = modified.max(time);
+            }
+            if metadata.is_dir() {
+                stack.push(item.path());
+            } else {
+                size += metadata.len();
+            }
+        }
+    }
+    (size, modified)
+}
+
+fn is_preserved(entry_path: &Path, preserved: &HashSet<PathBuf>) -> bool {
+    let entry_path = normalize(entry_path);
+    preserved.iter().any(|path| path.starts_with(&entry_path))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn age(path: &Path, days: u64) {
+        let time = SystemTime::now() - Duration::from_secs(days * DAY_SECS);
+        let time = filetime::FileTime::from_system_time(time);
+        if path.is_dir() {
+            for item in fs::read_dir(path).unwrap().flatten() {
+                filetime::set_file_mtime(item.path(), time).unwrap();
+            }
+        }
+        filetime::set_file_mtime(path, time).unwrap();
+    }
+
+    fn write(path: &Path, bytes: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![b'x'; bytes]).unwrap();
+    }
+
+    fn setup() -> (TempDir, ArtifactRegistry) {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let registry = ArtifactRegistry::new()
+            .with_location(ArtifactKind::ToolOutput, root.join("tool-output"))
+            .with_location(ArtifactKind::Media, root.join("media"));
+        (temp, registry)
+    }
+
+    fn root(temp: &TempDir) -> PathBuf {
+        temp.path().canonicalize().unwrap()
+    }
+
+    #[test]
+    fn scan_reports_owner_and_size() {
+        let (temp, registry) = setup();
+        let root = root(&temp);
+        write(&root.join("tool-output/task-1/a.txt"), 10);
+        write(&root.join("tool-output/task-1/nested/b.txt"), 5);
+        write(&root.join("media/loose.jpg"), 3);
+
+        let mut entries = registry.scan();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, ArtifactKind::Media);
+        assert_eq!(entries[0].owner_id, None);
+        assert_eq!(entries[0].size_bytes, 3);
+        assert_eq!(entries[1].owner_id.as_deref(), Some("task-1"));
+        assert_eq!(entries[1].size_bytes, 15);
+    }
+
+    #[test]
+    fn removes_orphaned_and_expired_entries() {
+        let (temp, registry) = setup();
+        let root = root(&temp);
+        let orphan = root.join("tool-output/gone");
+        let live = root.join("tool-output/live");
+        let expired = root.join("media/old-session");
+        write(&orphan.join("out.txt"), 4);
+        write(&live.join("out.txt"), 4);
+        write(&expired.join("photo.jpg"), 4);
+        age(&orphan, 2);
+        age(&live, 2);
+        age(&expired, 40);
+
+        let policy = ArtifactGcPolicy::from_settings(30, 0);
+        let report = collect_garbage(
+            &registry,
+            &policy,
+            &HashSet::new(),
+            |entry| match entry.owner_id.as_deref() {
+                Some("gone") => OwnerStatus::Missing,
+                _ => OwnerStatus::Live,
+            },
+            SystemTime::now(),
+        );
+
+        assert_eq!(report.scanned, 3);
+        assert_eq!(report.removed.len(), 2);
+        assert_eq!(report.bytes_reclaimed, 8);
+        assert_eq!(report.bytes_remaining, 4);
+        assert!(!orphan.exists());
+        assert!(!expired.exists());
+        assert!(live.exists());
+    }
+
+    #[test]
+    fn keeps_entries_referenced_by_deliverables() {
+        let (temp, registry) = setup();
+        let root = root(&temp);
+        let dir = root.join("tool-output/gone");
+        write(&dir.join("report.pdf"), 8);
+        age(&dir, 2);
+
+        let preserved = HashSet::from([dir.join("report.pdf")]);
+        let report = collect_garbage(
+            &registry,
+            &ArtifactGcPolicy::from_settings(1, 0),
+            &preserved,
+            |_| OwnerStatus::Missing,
+            SystemTime::now(),
+        );
+
+        assert_eq!(report.preserved, 1);
+        assert!(report.removed.is_empty());
+        assert!(dir.exists());
+    }
+
+    #[test]
+    fn enforces_size_cap_oldest_first() {
+        let (temp, registry) = setup();
+        let root = root(&temp);
+        let oldest = root.join("media/s1");
+        let newer = root.join("media/s2");
+        write(&oldest.join("a"), (MB as usize) * 2);
+        write(&newer.join("a"), (MB as usize) * 2);
+        age(&oldest, 5);
+        age(&newer, 2);
+
+        let report = collect_garbage(
+            &registry,
+            &ArtifactGcPolicy::from_settings(0, 3),
+            &HashSet::new(),
+            |_| OwnerStatus::Live,
+            SystemTime::now(),
+        );
+
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].reason, RemovalReason::OverQuota);
+        assert!(!oldest.exists());
+        assert!(newer.exists());
+    }
+
+    #[test]
+    fn dry_run_and_grace_period_leave_files() {
+        let (temp, registry) = setup();
+        let root = root(&temp);
+        let fresh = root.join("tool-output/fresh");
+        let stale = root.join("tool-output/stale");
+        write(&fresh.join("out.txt"), 1);
+        write(&stale.join("out.txt"), 1);
+        age(&stale, 2);
+
+        let report = collect_garbage(
+            &registry,
+            &ArtifactGcPolicy::from_settings(1, 0).dry_run(true),
+            &HashSet::new(),
+            |_| OwnerStatus::Missing,
+            SystemTime::now(),
+        );
+
+        assert!(report.dry_run);
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].reason, RemovalReason::Orphaned);
+        assert!(fresh.exists());
+        assert!(stale.exists());
+    }
+}
@@ -17,6 +17,7 @@ use crate::services::session::SessionService;
 use crate::storage::{AgentStorage, BackgroundAgentStorage, Storage};
 use restflow_contracts::{DeleteWithIdResponse, ErrorKind, ErrorPayload};
 use restflow_tools::ToolError;
+use restflow_traits::network::resolve_and_validate_url;
 use restflow_traits::store::{
     TaskControlRequest, TaskConvertSessionRequest, TaskCreateRequest, TaskDeleteRequest,
     TaskUpdateRequest,
@@ -522,6 +523,18 @@ impl TaskCommandService {
             .await
             .map_err(TaskCommandError::from_tool_error)?;
         let patch = update_request_to_patch(request).map_err(TaskCommandError::from_tool_error)?;
+        // Storage only sees literal hosts; resolve here so a name pointing at
+        // an internal address is refused when the callback is saved.
+        for callback in patch.callbacks.iter().flatten() {
+            resolve_and_validate_url(&callback.url)
+                .await
+                .map_err(|error| {
+                    TaskCommandError::validation(format!(
+                        "Invalid callback URL '{}': {}",
+                        callback.url, error
+                    ))
+                })?;
+        }
         Ok((guard, assessment, resolved_id, patch))
     }
 
@@ -1323,6 +1336,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1389,6 +1403,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1478,6 +1493,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1536,6 +1552,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1593,6 +1610,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1665,6 +1683,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1743,6 +1762,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1798,6 +1818,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -1871,6 +1892,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -2026,6 +2048,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("create task");
 
@@ -97,6 +97,7 @@ pub fn build_convert_session_spec(
         resource_limits: options.resource_limits,
         prerequisites: options.prerequisites,
         continuation: options.continuation,
+        concurrency: None,
     })
 }
 
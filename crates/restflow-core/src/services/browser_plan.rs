@@ -0,0 +1,91 @@
+//! Saved browser plan service layer.
+//!
+//! Plans are stored by name with every saved version kept, so they can be
+//! re-run without regenerating the action JSON.
+
+use crate::{
+    AppCore,
+    models::{BrowserPlan, SavedBrowserPlan},
+    services::browser_webhook::{
+        BrowserWebhookOutcome, browser_service_for_settings, render_plan_actions, run_browser_plan,
+    },
+};
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// List the latest version of every saved plan
+pub async fn list_browser_plans(core: &Arc<AppCore>) -> Result<Vec<SavedBrowserPlan>> {
+    core.storage
+        .browser_plans
+        .list()
+        .context("Failed to list browser plans")
+}
+
+/// Get a saved plan, optionally at a specific version
+pub async fn get_browser_plan(
+    core: &Arc<AppCore>,
+    name: &str,
+    version: Option<u32>,
+) -> Result<Option<SavedBrowserPlan>> {
+    core.storage
+        .browser_plans
+        .get_version(name, version)
+        .with_context(|| format!("Failed to get browser plan {}", name))
+}
+
+/// List every saved version of a plan
+pub async fn list_browser_plan_versions(
+    core: &Arc<AppCore>,
+    name: &str,
+) -> Result<Vec<SavedBrowserPlan>> {
+    core.storage
+        .browser_plans
+        .list_versions(name)
+        .with_context(|| format!("Failed to list versions of browser plan {}", name))
+}
+
+/// Save a plan, creating a new version when the name already exists
+pub async fn save_browser_plan(
+    core: &Arc<AppCore>,
+    name: &str,
+    description: Option<String>,
+    plan: BrowserPlan,
+) -> Result<SavedBrowserPlan> {
+    core.storage.browser_plans.save(name, description, plan)
+}
+
+/// Delete a plan with all of its versions
+pub async fn delete_browser_plan(core: &Arc<AppCore>, name: &str) -> Result<bool> {
+    core.storage
+        .browser_plans
+        .delete(name)
+        .with_context(|| format!("Failed to delete browser plan {}", name))
+}
+
+/// Render a saved plan against `payload` and run it in a throwaway session.
+pub async fn run_saved_browser_plan(
+    core: &Arc<AppCore>,
+    name: &str,
+    version: Option<u32>,
+    payload: Option<Value>,
+) -> Result<BrowserWebhookOutcome> {
+    let saved = get_browser_plan(core, name, version)
+        .await?
+        .ok_or_else(|| anyhow!("Browser plan '{}' not found", name))?;
+    let payload = payload.unwrap_or_else(|| Value::Object(Default::default()));
+    let actions = render_plan_actions(&saved.plan, &payload)?;
+
+    let agent_settings = core
+        .storage
+        .config
+        .get_effective_config()
+        .map(|config| config.agent)
+        .unwrap_or_default();
+    let timeout_secs = saved
+        .plan
+        .timeout_secs
+        .unwrap_or(agent_settings.browser_timeout_secs);
+    let service = browser_service_for_settings(&agent_settings)?;
+    run_browser_plan(&service, actions, timeout_secs).await
+}
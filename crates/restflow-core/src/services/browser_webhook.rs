@@ -0,0 +1,328 @@
+//! Run browser action plans attached to webhook triggers.
+//!
+//! A webhook trigger may carry a [`BrowserPlan`]. Each request renders
+//! the plan against its JSON payload, runs it in a fresh headless session and
+//! returns the per-action results (extracted text, evaluated values, ...) to
+//! the caller instead of queueing an asynchronous run.
+
+use crate::models::BrowserPlan;
+use crate::runtime::agent::tools::assembly::{
+    browser_artifact_retention, browser_navigation_policy,
+};
+use anyhow::{Result, anyhow, bail};
+use restflow_browser::{
+    BrowserAction, BrowserDoctorReport, BrowserService, NewSessionRequest, RunActionsRequest,
+    ScriptRuntime,
+};
+use restflow_storage::AgentSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::warn;
+
+const PLACEHOLDER_OPEN: &str = "${";
+const PAYLOAD_ROOT: &str = "payload";
+/// Fields left verbatim so request data can never be spliced into page
+/// scripts; evaluate actions should read payload values through `args`.
+const UNTEMPLATED_FIELDS: &[&str] = &["expression"];
+
+/// Response body of a browser plan webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BrowserWebhookOutcome {
+    pub success: bool,
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Result of each action in plan order.
+    #[serde(default)]
+    pub results: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BrowserWebhookOutcome {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            duration_ms: 0,
+            results: Vec::new(),
+            error: Some(message.into()),
+        }
+    }
+}
+
+/// Browser service restricted by the `[agent]` browser settings.
+pub fn browser_service_for_settings(agent: &AgentSettings) -> Result<BrowserService> {
+    Ok(BrowserService::new()?
+        .with_navigation_policy(browser_navigation_policy(agent))
+        .with_artifact_retention(browser_artifact_retention(agent)))
+}
+
+/// Probe the browser runtime agents would use and list remediation steps.
+pub async fn browser_doctor(agent: &AgentSettings) -> Result<BrowserDoctorReport> {
+    browser_service_for_settings(agent)?.doctor().await
+}
+
+/// Substitute `${payload.<path>}` placeholders in `plan` and parse the
+/// resulting actions.
+pub fn render_plan_actions(plan: &BrowserPlan, payload: &Value) -> Result<Vec<BrowserAction>> {
+    let rendered = render_value(&plan.actions, payload)?;
+    if !rendered.is_array() {
+        bail!("Browser plan actions must be a JSON array");
+    }
+    let actions: Vec<BrowserAction> = serde_json::from_value(rendered)
+        .map_err(|error| anyhow!("Invalid browser plan actions: {error}"))?;
+    if actions.is_empty() {
+        bail!("Browser plan has no actions");
+    }
+    Ok(actions)
+}
+
+/// Run already rendered `actions` in a throwaway session on `service`.
+pub async fn run_browser_plan(
+    service: &BrowserService,
+    actions: Vec<BrowserAction>,
+    timeout_secs: u64,
+) -> Result<BrowserWebhookOutcome> {
+    let session = service.new_session(NewSessionRequest::default()).await?;
+    let execution = service
+        .run_actions(&RunActionsRequest {
+            session_id: session.id.clone(),
+            actions,
+            runtime: ScriptRuntime::default(),
+            timeout_secs,
+            cwd: None,
+        })
+        .await;
+    if let Err(error) = service.close_session(&session.id).await {
+        warn!(session_id = %session.id, error = %error, "Failed to close webhook browser session");
+    }
+    let execution = execution?;
+
+    let results = execution
+        .payload
+        .as_ref()
+        .and_then(|payload| payload.get("result"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let success = execution.exit_code == 0;
+    Ok(BrowserWebhookOutcome {
+        success,
+        duration_ms: execution.duration_ms,
+        results,
+        error: (!success).then(|| execution.failed_message()),
+    })
+}
+
+fn render_value(value: &Value, payload: &Value) -> Result<Value> {
+    match value {
+        Value::String(text) => render_string(text, payload),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render_value(item, payload))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, field)| {
+                let rendered = if UNTEMPLATED_FIELDS.contains(&key.as_str()) {
+                    field.clone()
+                } else {
+                    render_value(field, payload)?
+                };
+                Ok((key.clone(), rendered))
+            })
+            .collect::<Result<Map<_, _>>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Placeholder path when `inner` refers to the payload, e.g. `payload.url`.
+fn payload_path(inner: &str) -> Option<&str> {
+    let inner = inner.trim();
+    if inner == PAYLOAD_ROOT || inner.starts_with("payload.") {
+        Some(inner)
+    } else {
+        None
+    }
+}
+
+fn render_string(text: &str, payload: &Value) -> Result<Value> {
+    // A lone placeholder keeps the JSON type of the referenced value.
+    if let Some(inner) = text
+        .strip_prefix(PLACEHOLDER_OPEN)
+        .and_then(|rest| rest.strip_suffix('}'))
+        && !inner.contains('}')
+        && let Some(path) = payload_path(inner)
+    {
+        return lookup(path, payload).cloned();
+    }
+
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(PLACEHOLDER_OPEN) {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + PLACEHOLDER_OPEN.len()..];
+        let Some((path, end)) = after
+            .find('}')
+            .and_then(|end| payload_path(&after[..end]).map(|path| (path, end)))
+        else {
+            // Not a payload placeholder, e.g. a JS template literal.
+            rendered.push_str(PLACEHOLDER_OPEN);
+            rest = after;
+            continue;
+        };
+        match lookup(path, payload)? {
+            Value::String(value) => rendered.push_str(value),
+            other => rendered.push_str(&other.to_string()),
+        }
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(Value::String(rendered))
+}
+
+fn lookup<'a>(path: &str, payload: &'a Value) -> Result<&'a Value> {
+    path.split('.')
+        .skip(1)
+        .try_fold(payload, |current, segment| match current {
+            Value::Object(fields) => fields.get(segment),
+            Value::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get(index)),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("Webhook payload has no value at '{path}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use restflow_browser::{
+        BrowserExecutionResult, BrowserExecutor, BrowserSession, RunScriptRequest, RuntimeProbe,
+    };
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tempfile::tempdir;
+
+    fn plan(actions: Value) -> BrowserPlan {
+        BrowserPlan {
+            actions,
+            timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn render_substitutes_payload_placeholders() {
+        let payload = json!({ "url": "https://example.com/item", "sku": 42, "tags": ["a", "b"] });
+        let actions = render_plan_actions(
+            &plan(json!([
+                { "type": "navigate", "url": "${payload.url}" },
+                { "type": "fill", "selector": "#q", "text": "sku-${payload.sku}/${payload.tags.1}" },
+                {
+                    "type": "evaluate",
+                    "expression": "`${payload.url}` + args.sku",
+                    "args": { "sku": "${payload.sku}" }
+                }
+            ])),
+            &payload,
+        )
+        .unwrap();
+
+        let rendered = serde_json::to_value(&actions).unwrap();
+        assert_eq!(rendered[0]["url"], "https://example.com/item");
+        assert_eq!(rendered[1]["text"], "sku-42/b");
+        assert_eq!(rendered[2]["expression"], "`${payload.url}` + args.sku");
+        assert_eq!(rendered[2]["args"]["sku"], 42);
+    }
+
+    #[test]
+    fn render_keeps_non_payload_templates_and_rejects_missing_values() {
+        let payload = json!({ "url": "https://example.com" });
+        let actions = render_plan_actions(
+            &plan(json!([{ "type": "fill", "selector": "#q", "text": "${other} ${payload.url}" }])),
+            &payload,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&actions).unwrap()[0]["text"],
+            "${other} https://example.com"
+        );
+
+        let error = render_plan_actions(
+            &plan(json!([{ "type": "navigate", "url": "${payload.missing}" }])),
+            &payload,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("payload.missing"));
+        assert!(render_plan_actions(&plan(json!({ "type": "navigate" })), &payload).is_err());
+    }
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        actions: Mutex<Vec<Vec<BrowserAction>>>,
+    }
+
+    #[async_trait]
+    impl BrowserExecutor for RecordingExecutor {
+        async fn probe_runtime(&self) -> Result<RuntimeProbe> {
+            bail!("unexpected runtime probe")
+        }
+
+        async fn run_script(
+            &self,
+            _session: &BrowserSession,
+            _request: &RunScriptRequest,
+        ) -> Result<BrowserExecutionResult> {
+            bail!("unexpected script run")
+        }
+
+        async fn run_actions(
+            &self,
+            _session: &BrowserSession,
+            request: &RunActionsRequest,
+        ) -> Result<BrowserExecutionResult> {
+            self.actions.lock().unwrap().push(request.actions.clone());
+            Ok(BrowserExecutionResult {
+                runtime: "mock".to_string(),
+                exit_code: 0,
+                duration_ms: 7,
+                stdout: String::new(),
+                stderr: String::new(),
+                payload: Some(json!({
+                    "success": true,
+                    "result": [
+                        { "type": "navigate" },
+                        { "type": "extract_text", "selector": "h1", "value": "Price: 10" }
+                    ]
+                })),
+                trace_path: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn run_browser_plan_returns_results_and_closes_session() {
+        let temp = tempdir().unwrap();
+        let executor = Arc::new(RecordingExecutor::default());
+        let service =
+            BrowserService::new_with_executor(temp.path().to_path_buf(), executor.clone()).unwrap();
+        let actions = render_plan_actions(
+            &plan(json!([
+                { "type": "navigate", "url": "${payload.url}" },
+                { "type": "extract_text", "selector": "h1" }
+            ])),
+            &json!({ "url": "https://example.com" }),
+        )
+        .unwrap();
+
+        let outcome = run_browser_plan(&service, actions, 30).await.unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.duration_ms, 7);
+        assert_eq!(outcome.results[1]["value"], "Price: 10");
+        assert_eq!(executor.actions.lock().unwrap().len(), 1);
+        assert!(service.list_sessions().await.is_empty());
+    }
+}
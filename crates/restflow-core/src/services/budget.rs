@@ -0,0 +1,314 @@
+//! Global LLM spend guardrails.
+//!
+//! `system.daily_budget_usd` and `system.monthly_budget_usd` cap the total
+//! LLM spend recorded in usage storage across all agents. Once a cap is
+//! reached, the task runner and chat dispatcher refuse to start new LLM work
+//! until the user raises the limit or the UTC period rolls over.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use tracing::warn;
+
+use crate::models::UsageQuery;
+use crate::runtime::background_agent::NotificationSender;
+use crate::storage::{ConfigStorage, Storage, UsageStorage};
+
+/// Window a global budget applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetPeriod {
+    /// Since 00:00 UTC today.
+    Daily,
+    /// Since 00:00 UTC on the first of this month.
+    Monthly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    /// Config key that sets this budget.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Self::Daily => "system.daily_budget_usd",
+            Self::Monthly => "system.monthly_budget_usd",
+        }
+    }
+
+    /// Start of the period containing `now`.
+    pub fn start(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let date = match self {
+            Self::Daily => now.date_naive(),
+            Self::Monthly => {
+                NaiveDate::from_ymd_opt(now.year(), now.month(), 1).unwrap_or(now.date_naive())
+            }
+        };
+        date.and_time(chrono::NaiveTime::MIN).and_utc()
+    }
+}
+
+/// A global budget that has been reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetExceeded {
+    pub period: BudgetPeriod,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    /// Start of the budget period in milliseconds.
+    pub since: i64,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since = DateTime::<Utc>::from_timestamp_millis(self.since)
+            .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| self.since.to_string());
+        write!(
+            f,
+            "LLM {} budget exceeded: spent ${:.2} of ${:.2} since {}. New LLM calls are paused until {} is raised.",
+            self.period.as_str(),
+            self.spent_usd,
+            self.limit_usd,
+            since,
+            self.period.config_key()
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+/// Checks global spend against the configured budgets before LLM work starts.
+pub struct BudgetGuard {
+    config: ConfigStorage,
+    usage: UsageStorage,
+    notifier: Option<Arc<dyn NotificationSender>>,
+    /// Budgets already reported, so each is announced once per period and limit.
+    notified: Mutex<HashSet<String>>,
+}
+
+impl BudgetGuard {
+    pub fn new(config: ConfigStorage, usage: UsageStorage) -> Self {
+        Self {
+            config,
+            usage,
+            notifier: None,
+            notified: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn from_storage(storage: &Storage) -> Self {
+        Self::new(storage.config.clone(), storage.usage.clone())
+    }
+
+    /// Notify through `notifier` the first time a budget is exceeded.
+    pub fn with_notifier(mut self, notifier: Arc<dyn NotificationSender>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Return the first configured budget reached as of `now`, if any.
+    pub fn exceeded_at(&self, now: DateTime<Utc>) -> Result<Option<BudgetExceeded>> {
+        let config = self.config.get_effective_config()?;
+        self.exceeded_with(config.daily_budget_usd, config.monthly_budget_usd, now)
+    }
+
+    fn exceeded_with(
+        &self,
+        daily_budget_usd: Option<f64>,
+        monthly_budget_usd: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<BudgetExceeded>> {
+        for (period, limit) in [
+            (BudgetPeriod::Daily, daily_budget_usd),
+            (BudgetPeriod::Monthly, monthly_budget_usd),
+        ] {
+            let Some(limit_usd) = limit else {
+                continue;
+            };
+            let since = period.start(now).timestamp_millis();
+            let spent_usd = self
+                .usage
+                .totals(&UsageQuery {
+                    from_time: Some(since),
+                    ..UsageQuery::default()
+                })?
+                .cost_usd;
+            if spent_usd >= limit_usd {
+                return Ok(Some(BudgetExceeded {
+                    period,
+                    limit_usd,
+                    spent_usd,
+                    since,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fail with [`BudgetExceeded`] when a global budget has been reached.
+    ///
+    /// Errors reading config or usage are logged and do not block execution.
+    pub async fn check(&self) -> Result<()> {
+        let exceeded = match self.exceeded_at(Utc::now()) {
+            Ok(Some(exceeded)) => exceeded,
+            Ok(None) => return Ok(()),
+            Err(error) => {
+                warn!(error = %error, "Failed to evaluate LLM budget; allowing execution");
+                return Ok(());
+            }
+        };
+        self.notify_once(&exceeded).await;
+        Err(exceeded.into())
+    }
+
+    async fn notify_once(&self, exceeded: &BudgetExceeded) {
+        let Some(notifier) = &self.notifier else {
+            return;
+        };
+        let key = format!(
+            "{}:{}:{}",
+            exceeded.period.as_str(),
+            exceeded.since,
+            exceeded.limit_usd
+        );
+        let first = self
+            .notified
+            .lock()
+            .map(|mut notified| notified.insert(key))
+            .unwrap_or(false);
+        if !first {
+            return;
+        }
+        if let Err(error) = notifier.send_formatted(&exceeded.to_string()).await {
+            warn!(error = %error, "Failed to send LLM budget notification");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BackgroundAgent, NotificationConfig, UsageRecord};
+    use chrono::TimeZone;
+    use redb::Database;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tempfile::tempdir;
+
+    fn record(id: &str, timestamp: i64, cost_usd: f64) -> UsageRecord {
+        UsageRecord {
+            id: id.to_string(),
+            timestamp,
+            agent_id: "agent".to_string(),
+            session_id: None,
+            task_id: "task".to_string(),
+            run_id: None,
+            provider: None,
+            model: "gpt-5".to_string(),
+            prompt_tokens: 10,
+            completion_tokens: 10,
+            cost_usd: Some(cost_usd),
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
+            cache_savings_usd: None,
+        }
+    }
+
+    #[test]
+    fn test_period_start() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 17, 15, 30, 0).unwrap();
+        assert_eq!(
+            BudgetPeriod::Daily.start(now),
+            Utc.with_ymd_and_hms(2026, 3, 17, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BudgetPeriod::Monthly.start(now),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    struct CountingNotifier(AtomicU32);
+
+    #[async_trait::async_trait]
+    impl NotificationSender for CountingNotifier {
+        async fn send(
+            &self,
+            _config: &NotificationConfig,
+            _task: &BackgroundAgent,
+            _success: bool,
+            _message: &str,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn send_formatted(&self, _message: &str) -> Result<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn guard() -> (tempfile::TempDir, BudgetGuard) {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("budget.db")).unwrap());
+        let usage = UsageStorage::new(db).unwrap();
+        (dir, BudgetGuard::new(ConfigStorage::default(), usage))
+    }
+
+    #[test]
+    fn test_exceeded_counts_spend_within_period() {
+        let (_dir, guard) = guard();
+        let now = Utc.with_ymd_and_hms(2026, 3, 17, 15, 30, 0).unwrap();
+        let yesterday = now.timestamp_millis() - 24 * 60 * 60 * 1000;
+        guard.usage.record(&record("a", yesterday, 3.0)).unwrap();
+        guard
+            .usage
+            .record(&record("b", now.timestamp_millis(), 0.6))
+            .unwrap();
+
+        assert_eq!(guard.exceeded_with(None, None, now).unwrap(), None);
+        assert_eq!(guard.exceeded_with(Some(1.0), None, now).unwrap(), None);
+
+        let exceeded = guard
+            .exceeded_with(Some(1.0), Some(3.5), now)
+            .unwrap()
+            .expect("monthly budget should be exceeded");
+        assert_eq!(exceeded.period, BudgetPeriod::Monthly);
+        assert!((exceeded.spent_usd - 3.6).abs() < 1e-9);
+        assert!(exceeded.to_string().contains("system.monthly_budget_usd"));
+
+        let exceeded = guard
+            .exceeded_with(Some(0.5), Some(3.5), now)
+            .unwrap()
+            .expect("daily budget should be exceeded");
+        assert_eq!(exceeded.period, BudgetPeriod::Daily);
+    }
+
+    #[tokio::test]
+    async fn test_notifies_once_per_period_and_limit() {
+        let (_dir, guard) = guard();
+        let notifier = Arc::new(CountingNotifier(AtomicU32::new(0)));
+        let guard = guard.with_notifier(notifier.clone());
+        let exceeded = BudgetExceeded {
+            period: BudgetPeriod::Daily,
+            limit_usd: 1.0,
+            spent_usd: 1.2,
+            since: 0,
+        };
+
+        guard.notify_once(&exceeded).await;
+        guard.notify_once(&exceeded).await;
+        assert_eq!(notifier.0.load(Ordering::SeqCst), 1);
+
+        let raised = BudgetExceeded {
+            limit_usd: 2.0,
+            ..exceeded
+        };
+        guard.notify_once(&raised).await;
+        assert_eq!(notifier.0.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::AppCore;
 use crate::services::session::SessionService;
@@ -18,6 +19,8 @@ pub struct CleanupReport {
     pub memory_sessions: usize,
     pub vector_orphans: usize,
     pub daemon_log_files: usize,
+    pub artifacts: usize,
+    pub artifact_bytes_reclaimed: u64,
 }
 
 pub async fn run_cleanup(core: &Arc<AppCore>) -> Result<CleanupReport> {
@@ -47,12 +50,23 @@ pub async fn run_cleanup(core: &Arc<AppCore>) -> Result<CleanupReport> {
         .background_agents
         .cleanup_expired_checkpoints()?;
 
-    let memory_chunks =
-        if let Some(cutoff) = retention_cutoff(now_ms, config.memory_chunk_retention_days) {
-            core.storage.memory.cleanup_old_chunks(cutoff)?
-        } else {
-            0
-        };
+    let tag_cutoffs: HashMap<String, Option<i64>> = config
+        .memory_defaults
+        .tag_retention
+        .iter()
+        .map(|rule| {
+            (
+                rule.tag.trim().to_ascii_lowercase(),
+                retention_cutoff(now_ms, rule.retention_days),
+            )
+        })
+        .collect();
+    let mut memory_chunks = core.storage.memory.cleanup_chunks_by_retention(
+        retention_cutoff(now_ms, config.memory_chunk_retention_days),
+        &tag_cutoffs,
+    )?;
+    // Session expiry and per-agent chunk limits
+    memory_chunks += core.storage.memory.enforce_retention(now_ms)?;
 
     // M2: Clean up empty memory sessions
     let memory_sessions = cleanup_empty_memory_sessions(core)?;
@@ -66,6 +80,15 @@ pub async fn run_cleanup(core: &Arc<AppCore>) -> Result<CleanupReport> {
         tokio::task::spawn_blocking(move || cleanup_daemon_log_files(retention_days).unwrap_or(0))
             .await
             .unwrap_or(0);
+
+    // Runs after task/session cleanup so their artifacts show up as orphaned.
+    let artifact_report = match crate::services::artifacts::run_artifact_gc(core, false).await {
+        Ok(report) => report,
+        Err(error) => {
+            warn!(error = %error, "Artifact garbage collection failed");
+            Default::default()
+        }
+    };
     Ok(CleanupReport {
         chat_sessions,
         background_tasks,
@@ -74,6 +97,8 @@ pub async fn run_cleanup(core: &Arc<AppCore>) -> Result<CleanupReport> {
         memory_sessions,
         vector_orphans,
         daemon_log_files,
+        artifacts: artifact_report.removed.len(),
+        artifact_bytes_reclaimed: artifact_report.bytes_reclaimed,
     })
 }
 
@@ -230,6 +255,8 @@ mod tests {
         assert_eq!(report.memory_sessions, 0);
         assert_eq!(report.vector_orphans, 0);
         assert_eq!(report.daemon_log_files, 0);
+        assert_eq!(report.artifacts, 0);
+        assert_eq!(report.artifact_bytes_reclaimed, 0);
     }
 
     #[test]
@@ -0,0 +1,379 @@
+//! Portable export/import of user data.
+//!
+//! A [`DataBundle`] is a single versioned JSON document holding agents,
+//! skills, triggers, chat sessions and memory, so an install can be moved
+//! between machines (e.g. from the desktop app to a server daemon). Secrets
+//! and auth profiles are only included when a password is supplied, and are
+//! then stored as encrypted archives (see [`crate::archive`]) inside the
+//! bundle. The CLI can additionally encrypt the whole bundle file.
+
+use anyhow::{Context, Result, bail};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use restflow_storage::{AuthProfileStorage, Secret, SimpleStorage};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::warn;
+
+use crate::archive::{decrypt_archive, encrypt_archive};
+use crate::auth::AuthProfile;
+use crate::models::memory::{MemoryChunk, MemorySession};
+use crate::models::{ActiveTrigger, ChatSession, Skill};
+use crate::storage::Storage;
+use crate::storage::agent::StoredAgent;
+
+/// Value of [`DataBundle::format`].
+pub const BUNDLE_FORMAT: &str = "restflow-bundle";
+/// Current bundle schema version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Everything needed to recreate user data on another install.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: i64,
+    #[serde(default)]
+    pub agents: Vec<StoredAgent>,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub triggers: Vec<ActiveTrigger>,
+    #[serde(default)]
+    pub chat_sessions: Vec<ChatSession>,
+    #[serde(default)]
+    pub memory_sessions: Vec<MemorySession>,
+    #[serde(default)]
+    pub memory_chunks: Vec<MemoryChunk>,
+    /// Base64 of a password-encrypted archive holding the secrets, if exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<String>,
+    /// Base64 of a password-encrypted archive holding the auth profiles, if
+    /// exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_profiles: Option<String>,
+}
+
+impl DataBundle {
+    pub fn has_secrets(&self) -> bool {
+        self.secrets.is_some()
+    }
+
+    /// Whether importing this bundle needs a password.
+    pub fn needs_password(&self) -> bool {
+        self.secrets.is_some() || self.auth_profiles.is_some()
+    }
+}
+
+/// Counts of records written by [`import_bundle`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleImportReport {
+    pub agents: usize,
+    pub skills: usize,
+    pub triggers: usize,
+    pub chat_sessions: usize,
+    pub memory_sessions: usize,
+    pub memory_chunks: usize,
+    pub secrets: usize,
+    #[serde(default)]
+    pub auth_profiles: usize,
+    /// Records left alone because they already exist and `overwrite` was off.
+    pub skipped: usize,
+}
+
+/// Collect all user data from `storage`.
+///
+/// Secrets and auth profiles are included only when `secrets_password` is set.
+pub fn export_bundle(storage: &Storage, secrets_password: Option<&str>) -> Result<DataBundle> {
+    let agents = storage.agents.list_agents()?;
+    let memory_chunks = storage.memory.list_all_chunks()?;
+
+    let agent_ids: BTreeSet<&str> = agents
+        .iter()
+        .map(|agent| agent.id.as_str())
+        .chain(memory_chunks.iter().map(|chunk| chunk.agent_id.as_str()))
+        .collect();
+    let mut memory_sessions = Vec::new();
+    for agent_id in agent_ids {
+        memory_sessions.extend(storage.memory.list_sessions(agent_id)?);
+    }
+
+    let (secrets, auth_profiles) = match secrets_password {
+        Some(password) => (
+            Some(seal(&collect_secrets(storage)?, password)?),
+            Some(seal(&collect_auth_profiles(storage)?, password)?),
+        ),
+        None => (None, None),
+    };
+
+    Ok(DataBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        skills: storage.skills.list()?,
+        triggers: storage.triggers.list_active_triggers()?,
+        chat_sessions: storage.chat_sessions.list_all()?,
+        agents,
+        memory_sessions,
+        memory_chunks,
+        secrets,
+        auth_profiles,
+    })
+}
+
+/// Write the contents of `bundle` into `storage`.
+///
+/// Records keep their IDs. Existing records are skipped unless `overwrite`
+/// is set. A bundle with secrets or auth profiles needs `secrets_password`.
+pub fn import_bundle(
+    storage: &Storage,
+    bundle: &DataBundle,
+    secrets_password: Option<&str>,
+    overwrite: bool,
+) -> Result<BundleImportReport> {
+    if bundle.format != BUNDLE_FORMAT {
+        bail!("Not a RestFlow data bundle (format '{}')", bundle.format);
+    }
+    if bundle.version > BUNDLE_VERSION {
+        bail!(
+            "Bundle version {} is newer than supported version {}",
+            bundle.version,
+            BUNDLE_VERSION
+        );
+    }
+    // Decrypt first so a wrong password fails before anything is written.
+    if bundle.needs_password() && secrets_password.is_none() {
+        bail!("Bundle contains secrets; a password is required to import it");
+    }
+    let secrets: Vec<Secret> = match (&bundle.secrets, secrets_password) {
+        (Some(encoded), Some(password)) => open(encoded, password, "secrets")?,
+        _ => Vec::new(),
+    };
+    let auth_profiles: Vec<AuthProfile> = match (&bundle.auth_profiles, secrets_password) {
+        (Some(encoded), Some(password)) => open(encoded, password, "auth profiles")?,
+        _ => Vec::new(),
+    };
+
+    let mut report = BundleImportReport::default();
+
+    for agent in &bundle.agents {
+        if !overwrite && storage.agents.get_agent(agent.id.clone())?.is_some() {
+            report.skipped += 1;
+            continue;
+        }
+        storage.agents.import_agent(agent.clone())?;
+        report.agents += 1;
+    }
+
+    for skill in &bundle.skills {
+        if !overwrite && storage.skills.exists(&skill.id)? {
+            report.skipped += 1;
+            continue;
+        }
+        storage.skills.upsert(skill)?;
+        report.skills += 1;
+    }
+
+    for trigger in &bundle.triggers {
+        if !overwrite && storage.triggers.get_active_trigger(&trigger.id)?.is_some() {
+            report.skipped += 1;
+            continue;
+        }
+        storage.triggers.activate_trigger(trigger)?;
+        report.triggers += 1;
+    }
+
+    for session in &bundle.chat_sessions {
+        if !overwrite && storage.chat_sessions.exists(&session.id)? {
+            report.skipped += 1;
+            continue;
+        }
+        storage.chat_sessions.save(session)?;
+        report.chat_sessions += 1;
+    }
+
+    for session in &bundle.memory_sessions {
+        if !overwrite && storage.memory.get_session(&session.id)?.is_some() {
+            report.skipped += 1;
+            continue;
+        }
+        storage.memory.create_session(session)?;
+        report.memory_sessions += 1;
+    }
+
+    // Chunks are immutable and deduplicated by content hash, so they are
+    // never overwritten.
+    for chunk in &bundle.memory_chunks {
+        if storage.memory.get_chunk(&chunk.id)?.is_some()
+            || storage.memory.store_chunk(chunk)? != chunk.id
+        {
+            report.skipped += 1;
+            continue;
+        }
+        report.memory_chunks += 1;
+    }
+
+    for secret in &secrets {
+        if !overwrite && storage.secrets.has_secret(&secret.key)? {
+            report.skipped += 1;
+            continue;
+        }
+        storage
+            .secrets
+            .set_secret(&secret.key, &secret.value, secret.description.clone())?;
+        report.secrets += 1;
+    }
+
+    let profile_storage = AuthProfileStorage::with_backend(storage.backend())?;
+    for profile in &auth_profiles {
+        if !overwrite && profile_storage.exists(&profile.id)? {
+            report.skipped += 1;
+            continue;
+        }
+        profile_storage.put_raw(&profile.id, &serde_json::to_vec(profile)?)?;
+        report.auth_profiles += 1;
+    }
+
+    Ok(report)
+}
+
+fn collect_secrets(storage: &Storage) -> Result<Vec<Secret>> {
+    let mut secrets = Vec::new();
+    for mut secret in storage.secrets.list_secrets()? {
+        let Some(value) = storage.secrets.get_secret(&secret.key)? else {
+            continue;
+        };
+        secret.value = value;
+        secrets.push(secret);
+    }
+    Ok(secrets)
+}
+
+fn collect_auth_profiles(storage: &Storage) -> Result<Vec<AuthProfile>> {
+    let profile_storage = AuthProfileStorage::with_backend(storage.backend())?;
+    let mut profiles = Vec::new();
+    for (id, bytes) in profile_storage.list_raw()? {
+        match serde_json::from_slice::<AuthProfile>(&bytes) {
+            Ok(profile) => profiles.push(profile),
+            Err(e) => warn!(profile_id = %id, error = %e, "Skipping corrupt auth profile entry"),
+        }
+    }
+    Ok(profiles)
+}
+
+fn seal<T: Serialize>(value: &T, password: &str) -> Result<String> {
+    let plaintext = serde_json::to_vec(value)?;
+    Ok(BASE64_STANDARD.encode(encrypt_archive(&plaintext, password)?))
+}
+
+fn open<T: DeserializeOwned>(encoded: &str, password: &str, section: &str) -> Result<T> {
+    let archive = BASE64_STANDARD
+        .decode(encoded)
+        .with_context(|| format!("Bundle {section} are not valid base64"))?;
+    let plaintext = decrypt_archive(&archive, password)?;
+    serde_json::from_slice(&plaintext).with_context(|| format!("Bundle {section} are invalid"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthProvider, CredentialSource, SecureCredential};
+    use tempfile::tempdir;
+
+    fn storage_in(dir: &tempfile::TempDir, name: &str) -> Storage {
+        Storage::new(dir.path().join(name).to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_sessions_memory_and_secrets() {
+        let dir = tempdir().unwrap();
+        let source = storage_in(&dir, "source.db");
+        let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        source.chat_sessions.create(&session).unwrap();
+        let chunk = MemoryChunk::new("agent-1".to_string(), "remember this".to_string());
+        source.memory.store_chunk(&chunk).unwrap();
+        source
+            .secrets
+            .set_secret("API_KEY", "sk-test", Some("test".to_string()))
+            .unwrap();
+        let profile = AuthProfile::new(
+            "Work account",
+            SecureCredential::ApiKey {
+                secret_ref: "API_KEY".to_string(),
+                email: Some("me@example.com".to_string()),
+            },
+            CredentialSource::Manual,
+            AuthProvider::Anthropic,
+        );
+        AuthProfileStorage::with_backend(source.backend())
+            .unwrap()
+            .put_raw(&profile.id, &serde_json::to_vec(&profile).unwrap())
+            .unwrap();
+
+        let bundle = export_bundle(&source, Some("pw")).unwrap();
+        assert_eq!(bundle.format, BUNDLE_FORMAT);
+        assert!(bundle.has_secrets());
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("sk-test"));
+        assert!(!json.contains("me@example.com"));
+        let bundle: DataBundle = serde_json::from_str(&json).unwrap();
+
+        let target = storage_in(&dir, "target.db");
+        let report = import_bundle(&target, &bundle, Some("pw"), false).unwrap();
+        assert_eq!(report.chat_sessions, 1);
+        assert_eq!(report.memory_chunks, 1);
+        assert_eq!(report.secrets, 1);
+        assert_eq!(report.auth_profiles, 1);
+        assert!(
+            AuthProfileStorage::with_backend(target.backend())
+                .unwrap()
+                .exists(&profile.id)
+                .unwrap()
+        );
+        assert!(target.chat_sessions.exists(&session.id).unwrap());
+        assert!(target.memory.get_chunk(&chunk.id).unwrap().is_some());
+        assert_eq!(
+            target.secrets.get_secret("API_KEY").unwrap().as_deref(),
+            Some("sk-test")
+        );
+
+        let again = import_bundle(&target, &bundle, Some("pw"), false).unwrap();
+        assert_eq!(again.chat_sessions, 0);
+        assert_eq!(again.skipped, 4);
+    }
+
+    #[test]
+    fn rejects_secrets_without_password_before_writing() {
+        let dir = tempdir().unwrap();
+        let source = storage_in(&dir, "source.db");
+        source
+            .secrets
+            .set_secret("API_KEY", "sk-test", None)
+            .unwrap();
+        let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        source.chat_sessions.create(&session).unwrap();
+        let bundle = export_bundle(&source, Some("pw")).unwrap();
+
+        let target = storage_in(&dir, "target.db");
+        assert!(import_bundle(&target, &bundle, None, false).is_err());
+        assert!(import_bundle(&target, &bundle, Some("wrong"), false).is_err());
+        assert!(!target.chat_sessions.exists(&session.id).unwrap());
+    }
+
+    #[test]
+    fn export_without_password_omits_secrets() {
+        let dir = tempdir().unwrap();
+        let source = storage_in(&dir, "source.db");
+        source
+            .secrets
+            .set_secret("API_KEY", "sk-test", None)
+            .unwrap();
+
+        let bundle = export_bundle(&source, None).unwrap();
+        assert!(!bundle.needs_password());
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("secrets"));
+        assert!(!json.contains("auth_profiles"));
+    }
+}
@@ -863,6 +863,7 @@ fn external_channel_key(source: ChatSessionSource) -> &'static str {
         ChatSessionSource::Telegram => "telegram",
         ChatSessionSource::Discord => "discord",
         ChatSessionSource::Slack => "slack",
+        ChatSessionSource::Matrix => "matrix",
         ChatSessionSource::ExternalLegacy => "external",
         ChatSessionSource::Workspace => "workspace",
     }
@@ -1076,6 +1077,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("task");
 
@@ -1116,6 +1118,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("task");
 
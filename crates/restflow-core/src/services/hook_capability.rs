@@ -1,5 +1,6 @@
 use crate::hooks::{HookExecutor, TaskHookScheduler};
 use crate::models::{Hook, HookContext, HookEvent};
+use crate::runtime::agent::tools::{SecretResolver, secret_resolver_from_storage};
 use crate::storage::{BackgroundAgentStorage, HookStorage, Storage};
 use anyhow::{Result, anyhow};
 use std::sync::Arc;
@@ -8,6 +9,7 @@ use std::sync::Arc;
 pub struct HookCapabilityService {
     hooks: HookStorage,
     background_agents: BackgroundAgentStorage,
+    secret_resolver: Option<SecretResolver>,
 }
 
 impl HookCapabilityService {
@@ -15,11 +17,18 @@ impl HookCapabilityService {
         Self {
             hooks,
             background_agents,
+            secret_resolver: None,
         }
     }
 
     pub fn from_storage(storage: &Storage) -> Self {
         Self::new(storage.hooks.clone(), storage.background_agents.clone())
+            .with_secret_resolver(secret_resolver_from_storage(storage))
+    }
+
+    pub fn with_secret_resolver(mut self, resolver: SecretResolver) -> Self {
+        self.secret_resolver = Some(resolver);
+        self
     }
 
     pub fn list(&self) -> Result<Vec<Hook>> {
@@ -46,8 +55,11 @@ impl HookCapabilityService {
             .get(id)?
             .ok_or_else(|| anyhow!("Hook not found: {id}"))?;
         let scheduler = Arc::new(TaskHookScheduler::new(self.background_agents.clone()));
-        let executor =
+        let mut executor =
             HookExecutor::with_storage(self.hooks.clone()).with_task_scheduler(scheduler);
+        if let Some(resolver) = self.secret_resolver.clone() {
+            executor = executor.with_secret_resolver(resolver);
+        }
         executor
             .execute_hook(&hook, &sample_hook_context(&hook.event))
             .await
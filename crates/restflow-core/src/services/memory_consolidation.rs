@@ -0,0 +1,19 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::AppCore;
+use crate::memory::{ConsolidationReport, LlmChunkSummarizer, MemoryConsolidator};
+
+/// Run one consolidation pass over all agents' memory.
+///
+/// Does nothing when `memory.consolidation_enabled` is off.
+pub async fn run_memory_consolidation(core: &Arc<AppCore>) -> Result<ConsolidationReport> {
+    let settings = core.storage.config.get_effective_config()?.memory_defaults;
+    if !settings.consolidation_enabled {
+        return Ok(ConsolidationReport::default());
+    }
+    let summarizer = LlmChunkSummarizer::from_settings(&settings, &core.storage.secrets)?;
+    MemoryConsolidator::with_settings(core.storage.memory.clone(), Arc::new(summarizer), &settings)
+        .consolidate_all()
+        .await
+}
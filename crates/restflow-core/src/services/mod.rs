@@ -1,12 +1,23 @@
 pub mod adapters;
 pub mod agent;
+pub mod agent_template;
+pub mod api_connector;
+pub mod api_tokens;
+pub mod artifacts;
 pub mod background_agent_command;
 pub mod background_agent_conversion;
+pub mod browser_plan;
+pub mod browser_webhook;
+pub mod budget;
 pub mod cleanup;
 pub mod config;
+pub mod data_bundle;
 pub mod execution_console;
 pub mod hook_capability;
+pub mod memory_consolidation;
 pub mod operation_assessment;
+pub mod project_config;
+pub mod replay;
 pub mod secrets;
 pub mod session;
 pub mod session_policy;
@@ -15,3 +26,4 @@ pub mod skill_triggers;
 pub mod skills;
 pub mod team_runtime;
 pub mod tool_registry;
+pub mod workspace;
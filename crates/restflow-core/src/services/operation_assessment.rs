@@ -4,7 +4,7 @@ use anyhow::{Result, anyhow};
 use restflow_contracts::request::{
     AgentNode as ContractAgentNode, RunSpawnRequest as ContractRunSpawnRequest,
 };
-use restflow_storage::AuthProfileStorage;
+use restflow_storage::{AuthProfileStorage, StorageBackend};
 use restflow_traits::ModelProvider as SharedModelProvider;
 use sha2::{Digest, Sha256};
 
@@ -30,7 +30,7 @@ use restflow_traits::assessment::{
 use restflow_traits::boundary::subagent::spawn_request_from_contract as run_spawn_request_from_contract;
 use restflow_traits::store::{
     AgentCreateRequest, AgentUpdateRequest, TaskControlRequest, TaskConvertSessionRequest,
-    TaskCreateRequest, TaskDeleteRequest, TaskUpdateRequest,
+    TaskCreateRequest, TaskDeleteRequest, TaskUpdateRequest, is_api_connector_tool_group,
 };
 use restflow_traits::subagent::{
     SpawnRequest as RunSpawnRequest, SubagentDefLookup as RunDefinitionLookup,
@@ -43,7 +43,7 @@ pub struct OperationAssessorAdapter {
 
 #[derive(Clone)]
 struct AssessmentContext {
-    db: Arc<redb::Database>,
+    storage_backend: Arc<dyn StorageBackend>,
     secrets: SecretStorage,
     skills: SkillStorage,
     memory: MemoryStorage,
@@ -67,7 +67,7 @@ impl AssessmentContext {
 
     fn from_storage(storage: &Storage) -> Self {
         Self {
-            db: storage.get_db(),
+            storage_backend: storage.backend(),
             secrets: storage.secrets.clone(),
             skills: storage.skills.clone(),
             memory: storage.memory.clone(),
@@ -345,7 +345,7 @@ async fn build_auth(context: &AssessmentContext) -> Result<AuthProfileManager> {
         ..AuthManagerConfig::default()
     };
     let secrets = Arc::new(context.secrets.clone());
-    let profile_storage = AuthProfileStorage::new(context.db.clone())?;
+    let profile_storage = AuthProfileStorage::with_backend(context.storage_backend.clone())?;
     let manager = AuthProfileManager::with_storage(config, secrets, Some(profile_storage));
     manager.initialize().await?;
     let _ = manager.discover().await;
@@ -506,7 +506,7 @@ async fn validate_agent_async(
                 errors.push(ValidationError::new("tools", "tool name must not be empty"));
                 continue;
             }
-            if !tool_registry.has(normalized) {
+            if !tool_registry.has(normalized) && !is_api_connector_tool_group(normalized) {
                 errors.push(ValidationError::new(
                     "tools",
                     format!("unknown tool: {}", normalized),
@@ -1206,6 +1206,8 @@ mod tests {
             skill_variables: None,
             skill_preflight_policy_mode: None,
             model_routing: None,
+            dry_run: None,
+            execution_strategy: None,
         }
     }
 
@@ -1623,6 +1625,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .expect("task");
 
@@ -0,0 +1,506 @@
+//! Declarative project configuration (`restflow.yaml`).
+//!
+//! A workspace can describe its agents (including their tool allowlists),
+//! the skills to enable and its triggers in a `restflow.yaml` kept under
+//! version control. Applying the file reconciles storage with it: agents are
+//! matched by name and triggers by ID, missing ones are created and differing
+//! ones updated. Records the file does not mention are left alone.
+
+use anyhow::{Context, Result, anyhow, bail};
+use restflow_traits::store::MarketplaceStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::AppCore;
+use crate::models::{ActiveTrigger, AgentNode, SkillStatus, TriggerConfig};
+use crate::services::adapters::MarketplaceStoreAdapter;
+use crate::services::agent as agent_service;
+
+/// File name looked up in a workspace directory.
+pub const PROJECT_CONFIG_FILE: &str = "restflow.yaml";
+
+/// Contents of `restflow.yaml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub agents: Vec<ProjectAgent>,
+    /// IDs of installed skills to activate.
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub triggers: Vec<ProjectTrigger>,
+}
+
+/// An agent, matched to a stored agent by name.
+///
+/// The remaining keys are the agent settings; `tools` is its tool allowlist.
+/// Leaving out `prompt` keeps the agent's current prompt file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectAgent {
+    pub name: String,
+    #[serde(flatten)]
+    pub agent: AgentNode,
+}
+
+/// A trigger, matched to an active trigger by ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTrigger {
+    pub id: String,
+    /// Label shown in digests and deliverables; defaults to the ID.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub config: TriggerConfig,
+}
+
+/// Kind of record a project change applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectResource {
+    Agent,
+    Skill,
+    Trigger,
+}
+
+/// What applying the project config does to one record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectAction {
+    Create,
+    /// Update the record; for skills, activate it.
+    Update,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectChange {
+    pub resource: ProjectResource,
+    /// Agent name, skill ID or trigger ID.
+    pub name: String,
+    pub action: ProjectAction,
+}
+
+/// Result of [`apply_project_config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectApplyReport {
+    /// Changes were only planned, not written.
+    pub dry_run: bool,
+    pub changes: Vec<ProjectChange>,
+}
+
+impl ProjectApplyReport {
+    fn push(&mut self, resource: ProjectResource, name: &str, action: ProjectAction) {
+        self.changes.push(ProjectChange {
+            resource,
+            name: name.to_string(),
+            action,
+        });
+    }
+
+    /// Number of records created or updated.
+    pub fn changed(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.action != ProjectAction::Unchanged)
+            .count()
+    }
+}
+
+/// Read `restflow.yaml` from `dir`, or `None` when the directory has none.
+pub fn load_project_config(dir: &Path) -> Result<Option<ProjectConfig>> {
+    let path = dir.join(PROJECT_CONFIG_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let yaml = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_project_config(&yaml)
+        .with_context(|| format!("Invalid project config {}", path.display()))
+        .map(Some)
+}
+
+/// Parse and validate a project config document.
+pub fn parse_project_config(yaml: &str) -> Result<ProjectConfig> {
+    let config: ProjectConfig = serde_yaml::from_str(yaml)?;
+    config.validate()?;
+    Ok(config)
+}
+
+impl ProjectConfig {
+    /// Check names are set and unique so every entry matches one record.
+    pub fn validate(&self) -> Result<()> {
+        let mut agents = HashSet::new();
+        for agent in &self.agents {
+            let name = agent.name.trim();
+            if name.is_empty() {
+                bail!("Agent name must not be empty");
+            }
+            if !agents.insert(name) {
+                bail!("Agent '{}' is defined more than once", name);
+            }
+        }
+        let mut skills = HashSet::new();
+        for skill in &self.skills {
+            if skill.trim().is_empty() {
+                bail!("Skill ID must not be empty");
+            }
+            if !skills.insert(skill.trim()) {
+                bail!("Skill '{}' is listed more than once", skill.trim());
+            }
+        }
+        let mut triggers = HashSet::new();
+        for trigger in &self.triggers {
+            let id = trigger.id.trim();
+            if id.is_empty() {
+                bail!("Trigger ID must not be empty");
+            }
+            if !triggers.insert(id) {
+                bail!("Trigger '{}' is defined more than once", id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A write planned by `apply_project_config`.
+enum PlannedWrite {
+    ActivateSkill(String),
+    CreateAgent { name: String, agent: AgentNode },
+    UpdateAgent { id: String, agent: AgentNode },
+    CreateTrigger(ActiveTrigger),
+    UpdateTrigger(ActiveTrigger),
+}
+
+/// Reconcile storage with `config`.
+///
+/// Every entry is resolved and validated before anything is written, so an
+/// invalid agent or a missing skill leaves storage untouched. Skills are
+/// activated first so agents can reference them. With `dry_run` the report
+/// lists the planned changes and nothing is written.
+pub async fn apply_project_config(
+    core: &Arc<AppCore>,
+    config: &ProjectConfig,
+    dry_run: bool,
+) -> Result<ProjectApplyReport> {
+    config.validate()?;
+    let mut report = ProjectApplyReport {
+        dry_run,
+        ..Default::default()
+    };
+    let mut writes = Vec::new();
+
+    let marketplace = MarketplaceStoreAdapter::new(core.storage.skills.clone())
+        .with_secrets(core.storage.secrets.clone());
+    for skill_id in &config.skills {
+        let skill_id = skill_id.trim();
+        let skill = core
+            .storage
+            .skills
+            .get(skill_id)?
+            .ok_or_else(|| anyhow!("Skill '{}' is not installed", skill_id))?;
+        if skill.status == SkillStatus::Active {
+            report.push(ProjectResource::Skill, skill_id, ProjectAction::Unchanged);
+            continue;
+        }
+        marketplace
+            .check_activation(&skill)
+            .map_err(|err| anyhow!(err.to_string()))?;
+        writes.push(PlannedWrite::ActivateSkill(skill_id.to_string()));
+        report.push(ProjectResource::Skill, skill_id, ProjectAction::Update);
+    }
+
+    let stored_agents = core.storage.agents.list_agents()?;
+    for project_agent in &config.agents {
+        let name = project_agent.name.trim();
+        let mut desired = project_agent.agent.clone();
+        agent_service::normalize_model_fields(&mut desired)
+            .with_context(|| format!("Invalid agent '{}'", name))?;
+        agent_service::validate_agent_node(core, &desired)
+            .await
+            .with_context(|| format!("Invalid agent '{}'", name))?;
+
+        let mut matching = stored_agents.iter().filter(|agent| agent.name == name);
+        let action = match (matching.next(), matching.next()) {
+            (None, _) => {
+                writes.push(PlannedWrite::CreateAgent {
+                    name: name.to_string(),
+                    agent: desired,
+                });
+                ProjectAction::Create
+            }
+            (Some(stored), None) if agent_matches(&stored.agent, &desired)? => {
+                ProjectAction::Unchanged
+            }
+            (Some(stored), None) => {
+                writes.push(PlannedWrite::UpdateAgent {
+                    id: stored.id.clone(),
+                    agent: desired,
+                });
+                ProjectAction::Update
+            }
+            (Some(_), Some(_)) => bail!(
+                "Several agents are named '{}'; rename them so restflow.yaml matches one",
+                name
+            ),
+        };
+        report.push(ProjectResource::Agent, name, action);
+    }
+
+    for trigger in &config.triggers {
+        let id = trigger.id.trim();
+        validate_trigger_config(&trigger.config)
+            .with_context(|| format!("Invalid trigger '{}'", id))?;
+        let workflow_id = trigger.name.clone().unwrap_or_else(|| id.to_string());
+        let action = match core.storage.triggers.get_active_trigger(id)? {
+            None => {
+                let mut created = ActiveTrigger::new(workflow_id, trigger.config.clone());
+                created.id = id.to_string();
+                writes.push(PlannedWrite::CreateTrigger(created));
+                ProjectAction::Create
+            }
+            Some(existing)
+                if existing.workflow_id == workflow_id
+                    && existing.trigger_config == trigger.config =>
+            {
+                ProjectAction::Unchanged
+            }
+            Some(mut existing) => {
+                existing.workflow_id = workflow_id;
+                existing.trigger_config = trigger.config.clone();
+                writes.push(PlannedWrite::UpdateTrigger(existing));
+                ProjectAction::Update
+            }
+        };
+        report.push(ProjectResource::Trigger, id, action);
+    }
+
+    if dry_run {
+        return Ok(report);
+    }
+    for write in writes {
+        match write {
+            PlannedWrite::ActivateSkill(skill_id) => {
+                marketplace
+                    .activate_skill(&skill_id)
+                    .map_err(|err| anyhow!(err.to_string()))?;
+            }
+            PlannedWrite::CreateAgent { name, agent } => {
+                agent_service::create_agent(core, name, agent).await?;
+            }
+            PlannedWrite::UpdateAgent { id, agent } => {
+                agent_service::update_agent(core, &id, None, Some(agent)).await?;
+            }
+            PlannedWrite::CreateTrigger(trigger) => {
+                core.storage.triggers.activate_trigger(&trigger)?;
+            }
+            PlannedWrite::UpdateTrigger(trigger) => {
+                core.storage.triggers.update_trigger(&trigger)?;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Reject cron expressions and timezones the schedulers could not use.
+fn validate_trigger_config(config: &TriggerConfig) -> Result<()> {
+    let (expression, timezone) = match config {
+        TriggerConfig::Schedule { cron, timezone, .. }
+        | TriggerConfig::BrowserSchedule { cron, timezone, .. }
+        | TriggerConfig::Digest { cron, timezone, .. } => (cron, timezone),
+        _ => return Ok(()),
+    };
+    let expression = expression.trim();
+    // Standard 5-field expressions get a seconds field, as the schedulers do.
+    let parsed = if expression.split_whitespace().count() == 5 {
+        cron::Schedule::from_str(&format!("0 {}", expression))
+            .or_else(|_| cron::Schedule::from_str(expression))
+    } else {
+        cron::Schedule::from_str(expression)
+    };
+    parsed.map_err(|err| anyhow!("Invalid cron expression '{}': {}", expression, err))?;
+    if let Some(timezone) = timezone {
+        timezone
+            .parse::<chrono_tz::Tz>()
+            .map_err(|_| anyhow!("Unknown timezone '{}'", timezone))?;
+    }
+    Ok(())
+}
+
+/// Whether the stored agent already has the desired settings.
+fn agent_matches(current: &AgentNode, desired: &AgentNode) -> Result<bool> {
+    let prompt_matches = match desired.prompt.as_deref() {
+        Some(prompt) => current.prompt.as_deref().map(str::trim) == Some(prompt.trim()),
+        None => true,
+    };
+    let without_prompt = |agent: &AgentNode| {
+        let mut agent = agent.clone();
+        agent.prompt = None;
+        serde_json::to_value(agent)
+    };
+    Ok(prompt_matches && without_prompt(current)? == without_prompt(desired)?)
+}
+
+#[cfg(test)]
+#[allow(clippy::await_holding_lock)]
+mod tests {
+    use super::*;
+    use crate::prompt_files;
+    use tempfile::{TempDir, tempdir};
+
+    const PROJECT_YAML: &str = r#"
+agents:
+  - name: Repo reviewer
+    prompt: |
+      Review open pull requests.
+    tools: [web_fetch, http_request]
+triggers:
+  - id: nightly-review
+    name: Nightly review
+    type: schedule
+    cron: "0 2 * * *"
+    timezone: UTC
+"#;
+
+    struct AgentsDirEnvGuard {
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl Drop for AgentsDirEnvGuard {
+        fn drop(&mut self) {
+            unsafe { std::env::remove_var(prompt_files::AGENTS_DIR_ENV) };
+        }
+    }
+
+    async fn create_test_core() -> (Arc<AppCore>, TempDir, TempDir, AgentsDirEnvGuard) {
+        let env_guard = AgentsDirEnvGuard {
+            _lock: prompt_files::agents_dir_env_lock(),
+        };
+        let temp_db = tempdir().unwrap();
+        let temp_agents = tempdir().unwrap();
+        unsafe { std::env::set_var(prompt_files::AGENTS_DIR_ENV, temp_agents.path()) };
+        let db_path = temp_db.path().join("project.db");
+        let core = Arc::new(AppCore::new(db_path.to_str().unwrap()).await.unwrap());
+        (core, temp_db, temp_agents, env_guard)
+    }
+
+    fn actions(report: &ProjectApplyReport) -> Vec<(ProjectResource, ProjectAction)> {
+        report
+            .changes
+            .iter()
+            .map(|change| (change.resource, change.action))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicates_and_unknown_keys() {
+        let config = parse_project_config(PROJECT_YAML).unwrap();
+        assert_eq!(config.agents[0].name, "Repo reviewer");
+        assert_eq!(
+            config.agents[0].agent.tools,
+            Some(vec!["web_fetch".to_string(), "http_request".to_string()])
+        );
+
+        let duplicate = "agents:\n  - name: a\n  - name: a\n";
+        assert!(parse_project_config(duplicate).is_err());
+        assert!(parse_project_config("workflows: []\n").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let dir = tempdir().unwrap();
+        assert!(load_project_config(dir.path()).unwrap().is_none());
+
+        std::fs::write(dir.path().join(PROJECT_CONFIG_FILE), PROJECT_YAML).unwrap();
+        let config = load_project_config(dir.path()).unwrap().unwrap();
+        assert_eq!(config.triggers[0].id, "nightly-review");
+    }
+
+    #[tokio::test]
+    async fn test_apply_creates_then_reconciles() {
+        let (core, _db, _agents, _guard) = create_test_core().await;
+        let mut config = parse_project_config(PROJECT_YAML).unwrap();
+
+        let planned = apply_project_config(&core, &config, true).await.unwrap();
+        assert_eq!(planned.changed(), 2);
+        assert!(
+            core.storage
+                .triggers
+                .list_active_triggers()
+                .unwrap()
+                .is_empty()
+        );
+
+        let report = apply_project_config(&core, &config, false).await.unwrap();
+        assert_eq!(
+            actions(&report),
+            vec![
+                (ProjectResource::Agent, ProjectAction::Create),
+                (ProjectResource::Trigger, ProjectAction::Create)
+            ]
+        );
+        let trigger = core
+            .storage
+            .triggers
+            .get_active_trigger("nightly-review")
+            .unwrap()
+            .unwrap();
+        assert_eq!(trigger.workflow_id, "Nightly review");
+
+        let again = apply_project_config(&core, &config, false).await.unwrap();
+        assert_eq!(again.changed(), 0);
+
+        config.agents[0].agent.tools = Some(vec!["http_request".to_string()]);
+        let updated = apply_project_config(&core, &config, false).await.unwrap();
+        assert_eq!(
+            actions(&updated)[0],
+            (ProjectResource::Agent, ProjectAction::Update)
+        );
+        let agents = core.storage.agents.list_agents().unwrap();
+        let agent = agents
+            .iter()
+            .find(|agent| agent.name == "Repo reviewer")
+            .unwrap();
+        assert_eq!(agent.agent.tools, Some(vec!["http_request".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_apply_requires_installed_skill() {
+        let (core, _db, _agents, _guard) = create_test_core().await;
+        let config = parse_project_config("skills: [missing-skill]\n").unwrap();
+
+        let err = apply_project_config(&core, &config, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("missing-skill"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_writes_nothing_when_a_later_entry_is_invalid() {
+        let (core, _db, _agents, _guard) = create_test_core().await;
+        let mut config = parse_project_config(PROJECT_YAML).unwrap();
+        config.triggers[0].config = TriggerConfig::Schedule {
+            cron: "not a cron".to_string(),
+            timezone: None,
+            payload: None,
+        };
+
+        let err = apply_project_config(&core, &config, false)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("nightly-review"));
+        assert!(
+            !core
+                .storage
+                .agents
+                .list_agents()
+                .unwrap()
+                .iter()
+                .any(|agent| agent.name == "Repo reviewer")
+        );
+    }
+}
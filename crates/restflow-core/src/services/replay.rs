@@ -0,0 +1,281 @@
+//! Step-by-step replay of recorded agent runs.
+//!
+//! The agent executor stores a `ReplaySnapshot` after every ReAct turn. This
+//! service turns those snapshots into an `ExecutionReplay` for the UI, and
+//! re-executes a run from any recorded step, optionally with a different agent
+//! or an extra user instruction.
+
+use std::sync::Arc;
+
+use restflow_ai::llm::{Message, Role};
+use restflow_ai::{AgentState, AgentStatus};
+use thiserror::Error;
+
+use crate::models::{
+    ExecutionReplay, ExecutionStep, MemoryConfig, ReplayRunResult, ReplaySnapshot, ReplayStep,
+    ToolCallInfo,
+};
+use crate::runtime::AgentExecutor;
+use crate::storage::Storage;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("no replay recorded for '{0}'")]
+    NotFound(String),
+    #[error("execution '{execution_id}' has no step {step}")]
+    StepNotFound { execution_id: String, step: u32 },
+    #[error("execution '{0}' has no recorded agent; pass an agent ID to replay it")]
+    AgentRequired(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+#[derive(Clone)]
+pub struct ReplayService {
+    storage: Arc<Storage>,
+}
+
+impl ReplayService {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { storage }
+    }
+
+    pub fn from_storage(storage: &Arc<Storage>) -> Self {
+        Self::new(storage.clone())
+    }
+
+    /// Load the recorded steps of an execution.
+    ///
+    /// `id` may also be a run ID, in which case the most recently recorded
+    /// execution of that run is returned.
+    pub fn get_replay(&self, id: &str) -> Result<ExecutionReplay, ReplayError> {
+        let snapshots = self.load_snapshots(id)?;
+        let first = snapshots
+            .first()
+            .ok_or_else(|| ReplayError::NotFound(id.to_string()))?;
+        let mut replay = ExecutionReplay {
+            execution_id: first.execution_id.clone(),
+            task_id: first.task_id.clone(),
+            agent_id: first.agent_id.clone(),
+            run_id: first.run_id.clone(),
+            steps: Vec::with_capacity(snapshots.len()),
+        };
+
+        let mut seen_messages = 0;
+        for snapshot in &snapshots {
+            let state = decode_state(snapshot)?;
+            // Context compaction can shrink the history; show what remains.
+            let start = seen_messages.min(state.messages.len());
+            replay.steps.push(ReplayStep {
+                step: snapshot.step,
+                iteration: snapshot.iteration,
+                status: status_name(&state.status).to_string(),
+                created_at: snapshot.created_at,
+                messages: state.messages[start..]
+                    .iter()
+                    .map(to_execution_step)
+                    .collect(),
+            });
+            seen_messages = state.messages.len();
+        }
+        Ok(replay)
+    }
+
+    /// Re-execute a recorded execution from the state saved at `step`.
+    ///
+    /// The new run gets its own execution ID and is recorded like any other
+    /// run. `agent_id` overrides the recorded agent, so the step can be rerun
+    /// with a different model, prompt or tool set; `message` is appended as a
+    /// user message before execution continues.
+    pub async fn replay_from_step(
+        &self,
+        executor: &dyn AgentExecutor,
+        execution_id: &str,
+        step: u32,
+        agent_id: Option<&str>,
+        message: Option<&str>,
+    ) -> Result<ReplayRunResult, ReplayError> {
+        let snapshot = self
+            .storage
+            .checkpoints
+            .load_replay_snapshot(execution_id, step)?
+            .ok_or_else(|| ReplayError::StepNotFound {
+                execution_id: execution_id.to_string(),
+                step,
+            })?;
+        let agent_id = agent_id
+            .map(str::to_string)
+            .or_else(|| snapshot.agent_id.clone())
+            .ok_or_else(|| ReplayError::AgentRequired(execution_id.to_string()))?;
+
+        let mut state = decode_state(&snapshot)?;
+        state.execution_id = uuid::Uuid::new_v4().to_string();
+        state.final_answer = None;
+        state.ended_at = None;
+        if let Some(message) = message.map(str::trim).filter(|m| !m.is_empty()) {
+            state.add_message(Message::user(message));
+        }
+        let new_execution_id = state.execution_id.clone();
+
+        let result = executor
+            .execute_from_state(&agent_id, None, state, &MemoryConfig::default(), None, None)
+            .await?;
+        Ok(ReplayRunResult {
+            source_execution_id: snapshot.execution_id,
+            from_step: step,
+            execution_id: new_execution_id,
+            agent_id,
+            output: result.output,
+        })
+    }
+
+    fn load_snapshots(&self, id: &str) -> Result<Vec<ReplaySnapshot>, ReplayError> {
+        let checkpoints = &self.storage.checkpoints;
+        let snapshots = checkpoints.list_replay_snapshots(id)?;
+        if !snapshots.is_empty() {
+            return Ok(snapshots);
+        }
+
+        let mut latest: Option<Vec<ReplaySnapshot>> = None;
+        for execution_id in checkpoints.list_replay_executions_by_run(id)? {
+            let candidate = checkpoints.list_replay_snapshots(&execution_id)?;
+            let is_newer = match (
+                candidate.last(),
+                latest.as_ref().and_then(|steps| steps.last()),
+            ) {
+                (Some(candidate), Some(current)) => candidate.created_at > current.created_at,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if is_newer {
+                latest = Some(candidate);
+            }
+        }
+        latest.ok_or_else(|| ReplayError::NotFound(id.to_string()))
+    }
+}
+
+fn decode_state(snapshot: &ReplaySnapshot) -> Result<AgentState, ReplayError> {
+    serde_json::from_slice(&snapshot.state_json).map_err(|error| {
+        ReplayError::Internal(anyhow::anyhow!(
+            "Failed to decode state of step {} in execution '{}': {}",
+            snapshot.step,
+            snapshot.execution_id,
+            error
+        ))
+    })
+}
+
+fn status_name(status: &AgentStatus) -> &'static str {
+    match status {
+        AgentStatus::Running => "running",
+        AgentStatus::Completed => "completed",
+        AgentStatus::Failed { .. } => "failed",
+        AgentStatus::MaxIterations => "max_iterations",
+        AgentStatus::Interrupted { .. } => "interrupted",
+        AgentStatus::ResourceExhausted { .. } => "resource_exhausted",
+    }
+}
+
+fn to_execution_step(message: &Message) -> ExecutionStep {
+    let step_type = match message.role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::Tool => "tool_result",
+    };
+    ExecutionStep {
+        step_type: step_type.to_string(),
+        content: message.content.clone(),
+        tool_calls: message.tool_calls.as_ref().map(|calls| {
+            calls
+                .iter()
+                .map(|call| ToolCallInfo {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    arguments: call.arguments.clone(),
+                })
+                .collect()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use restflow_ai::llm::ToolCall;
+    use tempfile::TempDir;
+
+    fn create_storage() -> (Arc<Storage>, TempDir) {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let db_path = temp_dir.path().join("replay-tests.redb");
+        let storage = Arc::new(Storage::new(db_path.to_str().expect("db path")).expect("storage"));
+        (storage, temp_dir)
+    }
+
+    fn save_snapshot(storage: &Storage, step: u32, state: &AgentState) {
+        let snapshot = ReplaySnapshot::new(
+            state.execution_id.clone(),
+            step,
+            state.iteration,
+            serde_json::to_vec(state).unwrap(),
+        )
+        .with_agent_id(Some("agent-1".to_string()))
+        .with_run_id(Some("run-1".to_string()));
+        storage.checkpoints.save_replay_snapshot(&snapshot).unwrap();
+    }
+
+    #[test]
+    fn get_replay_splits_messages_by_step() {
+        let (storage, _temp_dir) = create_storage();
+        let mut state = AgentState::new("exec-1".to_string(), 10);
+        state.add_message(Message::system("system prompt"));
+        state.add_message(Message::user("list files"));
+        state.add_message(Message::assistant_with_tool_calls(
+            None,
+            vec![ToolCall {
+                id: "call-1".to_string(),
+                name: "bash".to_string(),
+                arguments: serde_json::json!({ "command": "ls" }),
+            }],
+        ));
+        state.add_tool_result("call-1".to_string(), "README.md".to_string());
+        state.increment_iteration();
+        save_snapshot(&storage, 0, &state);
+
+        state.add_message(Message::assistant("One file: README.md"));
+        state.complete("One file: README.md");
+        save_snapshot(&storage, 1, &state);
+
+        let service = ReplayService::from_storage(&storage);
+        let replay = service.get_replay("exec-1").unwrap();
+        assert_eq!(replay.agent_id.as_deref(), Some("agent-1"));
+        assert_eq!(replay.steps.len(), 2);
+
+        let first = &replay.steps[0];
+        assert_eq!(first.status, "running");
+        assert_eq!(first.messages.len(), 4);
+        assert_eq!(first.messages[2].step_type, "assistant");
+        let tool_calls = first.messages[2].tool_calls.as_ref().unwrap();
+        assert_eq!(tool_calls[0].name, "bash");
+        assert_eq!(first.messages[3].step_type, "tool_result");
+
+        let second = &replay.steps[1];
+        assert_eq!(second.status, "completed");
+        assert_eq!(second.messages.len(), 1);
+        assert_eq!(second.messages[0].content, "One file: README.md");
+
+        let by_run = service.get_replay("run-1").unwrap();
+        assert_eq!(by_run.execution_id, "exec-1");
+    }
+
+    #[test]
+    fn get_replay_reports_unknown_execution() {
+        let (storage, _temp_dir) = create_storage();
+        let service = ReplayService::from_storage(&storage);
+        assert!(matches!(
+            service.get_replay("missing"),
+            Err(ReplayError::NotFound(_))
+        ));
+    }
+}
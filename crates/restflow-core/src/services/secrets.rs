@@ -1,4 +1,7 @@
-use crate::{AppCore, models::Secret};
+use crate::{
+    AppCore,
+    models::{Secret, SecretAccess},
+};
 use anyhow::{Context, Result};
 use std::sync::Arc;
 
@@ -69,6 +72,47 @@ pub async fn delete_secret(core: &Arc<AppCore>, key: &str) -> Result<()> {
         .with_context(|| format!("Failed to delete secret {}", key))
 }
 
+/// Replace a secret's value and reset its expiry
+pub async fn rotate_secret(
+    core: &Arc<AppCore>,
+    key: &str,
+    value: &str,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    core.storage
+        .secrets
+        .rotate_secret(key, value, expires_at)
+        .with_context(|| format!("Failed to rotate secret {}", key))
+}
+
+/// Set or clear a secret's expiry
+pub async fn set_secret_expiry(
+    core: &Arc<AppCore>,
+    key: &str,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    core.storage
+        .secrets
+        .set_secret_expiry(key, expires_at)
+        .with_context(|| format!("Failed to set expiry for secret {}", key))
+}
+
+/// List secrets that expire at or before `before` (without values)
+pub async fn list_expiring_secrets(core: &Arc<AppCore>, before: i64) -> Result<Vec<Secret>> {
+    core.storage
+        .secrets
+        .list_expiring_secrets(before)
+        .context("Failed to list expiring secrets")
+}
+
+/// Tools and agents that have read a secret
+pub async fn get_secret_usage(core: &Arc<AppCore>, key: &str) -> Result<Vec<SecretAccess>> {
+    core.storage
+        .secrets
+        .get_secret_usage(key)
+        .with_context(|| format!("Failed to get usage for secret {}", key))
+}
+
 /// Check whether a managed secret exists in storage.
 pub async fn has_secret(core: &Arc<AppCore>, key: &str) -> Result<bool> {
     core.storage
@@ -1,7 +1,7 @@
 use crate::daemon::session_events::{ChatSessionEvent, publish_session_event};
 use crate::models::{
-    ChatMessage, ChatRole, ChatSession, ChatSessionSource, ChatSessionUpdate, MessageExecution,
-    ModelId,
+    ChatMessage, ChatRole, ChatSearchQuery, ChatSearchResult, ChatSession, ChatSessionSource,
+    ChatSessionUpdate, MessageExecution, ModelId,
 };
 use crate::runtime::background_agent::persist::persist_chat_session_memory;
 use crate::runtime::channel::hydrate_voice_message_metadata;
@@ -143,6 +143,11 @@ impl SessionService {
             .collect())
     }
 
+    /// Full-text search over chat message content.
+    pub fn search_messages(&self, query: &ChatSearchQuery) -> Result<ChatSearchResult> {
+        self.sessions.chat_sessions.search_messages(query)
+    }
+
     pub fn create_workspace_session(
         &self,
         agent_id: String,
@@ -858,6 +863,10 @@ mod tests {
             )
             .expect_err("empty assistant output should be rejected");
 
-        assert!(error.to_string().contains("assistant_output must not be empty"));
+        assert!(
+            error
+                .to_string()
+                .contains("assistant_output must not be empty")
+        );
     }
 }
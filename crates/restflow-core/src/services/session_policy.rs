@@ -121,6 +121,7 @@ impl SessionPolicy {
             "telegram" => Some(ChatSessionSource::Telegram),
             "discord" => Some(ChatSessionSource::Discord),
             "slack" => Some(ChatSessionSource::Slack),
+            "matrix" => Some(ChatSessionSource::Matrix),
             _ => None,
         }
     }
@@ -428,6 +429,7 @@ mod tests {
                 resource_limits: None,
                 prerequisites: Vec::new(),
                 continuation: None,
+                concurrency: None,
             })
             .unwrap();
     }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::security::AuditedSecurityGate;
 use crate::services::session::SessionService;
 use crate::services::team_runtime::TeamRuntimeService;
 use restflow_tools::FileConfig;
@@ -75,10 +76,13 @@ pub fn create_tool_registry_with_assessor(
     security_gate: Option<Arc<dyn SecurityGate>>,
     assessor: Option<Arc<dyn AgentOperationAssessor>>,
 ) -> anyhow::Result<ToolRegistry> {
+    let security_gate =
+        security_gate.map(|gate| AuditedSecurityGate::wrap(gate, execution_trace_storage.clone()));
     let config_storage = Arc::new(config_storage);
     let agent_defaults = load_agent_defaults(&config_storage);
     let api_defaults = load_api_defaults(&config_storage);
     let registry_defaults = load_registry_defaults(&config_storage);
+    let memory_defaults = load_memory_defaults(&config_storage);
 
     let secret_resolver: SecretResolver = {
         let secrets = Arc::new(secret_storage.clone());
@@ -97,10 +101,20 @@ pub fn create_tool_registry_with_assessor(
         background_agent_storage.clone(),
     ));
     let memory_manager = Arc::new(MemoryManagerAdapter::new(memory_storage.clone()));
-    let mem_store = Arc::new(DbMemoryStoreAdapter::new(memory_storage.clone()));
+    let memory_embedder =
+        MemoryEmbedder::from_settings(&memory_defaults, memory_storage.clone(), &secret_storage);
+    let search_config = UnifiedSearchConfig::from_memory_settings(&memory_defaults);
+    let mem_store = Arc::new(
+        DbMemoryStoreAdapter::new(memory_storage.clone()).with_memory_settings(memory_defaults),
+    );
     let deliverable_store = Arc::new(DeliverableStoreAdapter::new(deliverable_storage.clone()));
-    let search_engine = UnifiedSearchEngine::new(memory_storage.clone(), chat_storage.clone());
-    let unified_search = Arc::new(UnifiedMemorySearchAdapter::new(search_engine));
+    let search_engine = UnifiedSearchEngine::with_config(
+        memory_storage.clone(),
+        chat_storage.clone(),
+        search_config,
+    );
+    let unified_search =
+        Arc::new(UnifiedMemorySearchAdapter::new(search_engine).with_embedder(memory_embedder));
     let ops_provider = Arc::new(OpsProviderAdapter::new(
         background_agent_storage.clone(),
         chat_storage.clone(),
@@ -131,10 +145,10 @@ pub fn create_tool_registry_with_assessor(
         kv_store.clone(),
         assessor.clone(),
     );
-    let marketplace_store = Arc::new(MarketplaceStoreAdapter::new_with_defaults(
-        skill_storage,
-        registry_defaults,
-    ));
+    let marketplace_store = Arc::new(
+        MarketplaceStoreAdapter::new_with_defaults(skill_storage, registry_defaults)
+            .with_secrets(secret_storage.clone()),
+    );
     let trigger_store = Arc::new(TriggerStoreAdapter::new(trigger_storage));
     let terminal_store = Arc::new(TerminalStoreAdapter::new(terminal_storage));
     let secret_store_adapter = Arc::new(SecretStoreAdapter::new(Arc::new(secret_storage.clone())));
@@ -150,12 +164,18 @@ pub fn create_tool_registry_with_assessor(
 
     let mut builder = ToolRegistryBuilder::new();
     let security_agent_id = agent_id.as_deref().unwrap_or(DEFAULT_SECURITY_AGENT_ID);
+    let bash_config = restflow_tools::BashConfig {
+        timeout_secs: agent_defaults.bash_timeout_secs,
+        resource_limits: subprocess_resource_limits(&agent_defaults),
+        container: subprocess_container(&agent_defaults),
+        network: subprocess_network_policy(&agent_defaults),
+        ..Default::default()
+    };
+    // PTY shells and spawned processes bypass the bash sandbox.
+    let allow_unsandboxed_processes = !bash_config.is_sandboxed();
     builder = register_bash_execution_tool(
         builder,
-        restflow_tools::BashConfig {
-            timeout_secs: agent_defaults.bash_timeout_secs,
-            ..Default::default()
-        },
+        bash_config,
         security_gate.clone(),
         security_agent_id,
         DEFAULT_SECURITY_TASK_ID,
@@ -184,6 +204,43 @@ pub fn create_tool_registry_with_assessor(
     );
     builder = register_python_execution_tools(
         builder,
+        python_execution_backend(&agent_defaults),
+        security_gate.clone(),
+        security_agent_id,
+        DEFAULT_SECURITY_TASK_ID,
+    );
+    builder = register_git_tool(
+        builder,
+        None,
+        security_gate.clone(),
+        security_agent_id,
+        DEFAULT_SECURITY_TASK_ID,
+    );
+    builder = register_github_tool(
+        builder,
+        Some(secret_resolver.clone()),
+        security_gate.clone(),
+        security_agent_id,
+        DEFAULT_SECURITY_TASK_ID,
+    )?;
+    builder = register_calendar_tool(
+        builder,
+        Some(secret_resolver.clone()),
+        security_gate.clone(),
+        security_agent_id,
+        DEFAULT_SECURITY_TASK_ID,
+    )?;
+    builder = register_spreadsheet_tool(
+        builder,
+        None,
+        security_gate.clone(),
+        security_agent_id,
+        DEFAULT_SECURITY_TASK_ID,
+    );
+    builder = register_sql_tool(
+        builder,
+        Some(secret_resolver.clone()),
+        false,
         security_gate.clone(),
         security_agent_id,
         DEFAULT_SECURITY_TASK_ID,
@@ -213,7 +270,11 @@ pub fn create_tool_registry_with_assessor(
         .with_telegram()?
         .with_discord()?
         .with_slack()?
-        .with_browser_timeout(agent_defaults.browser_timeout_secs)?
+        .with_browser_vision_fallback(
+            Arc::new(browser_service_for_settings(&agent_defaults)?),
+            agent_defaults.browser_timeout_secs,
+            secret_resolver.clone(),
+        )?
         .with_patch_and_base_dir(None)
         .with_edit_and_diagnostics_and_base_dir(None, None)
         .with_multiedit_and_diagnostics_and_base_dir(None, None)
@@ -229,11 +290,13 @@ pub fn create_tool_registry_with_assessor(
             secret_resolver.clone(),
             restflow_tools::TranscribeConfig::default(),
         )?
+        .with_speak(secret_resolver.clone())
         .with_vision(secret_resolver)?
         .with_session(session_store)
         .with_memory_management(memory_manager)
         .with_memory_store(mem_store)
-        .with_deliverable(deliverable_store)
+        .with_deliverable(deliverable_store.clone())
+        .with_deliverable_builder(deliverable_store)
         .with_unified_search(unified_search)
         .with_ops(ops_provider)
         .with_kv_store(kv_store.clone())
@@ -248,16 +311,29 @@ pub fn create_tool_registry_with_assessor(
         .with_security_query(security_provider)
         .build();
 
-    let process_tool = if let Some(gate) = security_gate {
-        ProcessTool::new(process_manager).with_security(
-            gate,
-            security_agent_id,
-            DEFAULT_SECURITY_TASK_ID,
+    let (process_tool, shell_session_tool) = if let Some(gate) = security_gate {
+        (
+            ProcessTool::new(process_manager.clone()).with_security(
+                gate.clone(),
+                security_agent_id,
+                DEFAULT_SECURITY_TASK_ID,
+            ),
+            ShellSessionTool::new(process_manager).with_security(
+                gate,
+                security_agent_id,
+                DEFAULT_SECURITY_TASK_ID,
+            ),
         )
     } else {
-        ProcessTool::new(process_manager)
+        (
+            ProcessTool::new(process_manager.clone()),
+            ShellSessionTool::new(process_manager),
+        )
     };
-    registry.register(process_tool);
+    if allow_unsandboxed_processes {
+        registry.register(process_tool);
+        registry.register(shell_session_tool);
+    }
     registry.register(ReplyTool::new(reply_sender));
     registry.register(switch_model_tool);
     let subagent_runtime_bundle = build_service_subagent_runtime_bundle(
@@ -60,6 +60,12 @@ pub(super) fn load_registry_defaults(
     load_system_config(config_storage).registry_defaults
 }
 
+pub(super) fn load_memory_defaults(
+    config_storage: &ConfigStorage,
+) -> restflow_storage::MemoryDefaults {
+    load_system_config(config_storage).memory_defaults
+}
+
 pub(super) fn load_subagent_config(config_storage: &ConfigStorage) -> SubagentConfig {
     let defaults = load_agent_defaults(config_storage);
     build_subagent_config(&defaults)
@@ -3,19 +3,23 @@
 //! Adapter implementations live in [`super::adapters`]. This module provides
 //! the [`create_tool_registry`] function that wires adapters into tools.
 
-use crate::memory::UnifiedSearchEngine;
+use crate::memory::{MemoryEmbedder, UnifiedSearchConfig, UnifiedSearchEngine};
 use crate::models::ModelId;
 use crate::process::ProcessRegistry;
 use crate::runtime::agent::main_agent_default_tool_names;
 use crate::runtime::agent::tools::assembly::{
     KNOWN_TOOL_ALIASES, build_agent_crud_components, build_kv_store, build_task_store_components,
-    populate_known_tools_from_registry, register_bash_execution_tool, register_file_execution_tool,
+    populate_known_tools_from_registry, python_execution_backend, register_bash_execution_tool,
+    register_calendar_tool, register_file_execution_tool, register_git_tool, register_github_tool,
     register_http_execution_tool, register_management_tools, register_python_execution_tools,
-    register_send_email_execution_tool, register_subagent_management_tools,
+    register_send_email_execution_tool, register_spreadsheet_tool, register_sql_tool,
+    register_subagent_management_tools, subprocess_container, subprocess_network_policy,
+    subprocess_resource_limits,
 };
 use crate::runtime::orchestrator::{AgentOrchestratorImpl, ExecutionBackend};
 use crate::runtime::subagent::StorageBackedSubagentLookup;
 use crate::services::adapters::*;
+use crate::services::browser_webhook::browser_service_for_settings;
 use crate::storage::skill::SkillStorage;
 use crate::storage::{
     AgentStorage, BackgroundAgentStorage, ChannelSessionBindingStorage, ChatSessionStorage,
@@ -33,7 +37,9 @@ use restflow_ai::llm::{
 };
 use restflow_models::LlmProvider;
 use restflow_storage::{AgentDefaults, ApiDefaults, SystemConfig};
-use restflow_tools::{ProcessTool, ReplyTool, SwitchModelTool, ToolRegistryBuilder};
+use restflow_tools::{
+    ProcessTool, ReplyTool, ShellSessionTool, SwitchModelTool, ToolRegistryBuilder,
+};
 use restflow_traits::registry::ToolRegistry;
 use restflow_traits::security::SecurityGate;
 use restflow_traits::store::{ProcessManager, ReplySender};
@@ -67,7 +73,7 @@ mod subagent_backend;
 
 use self::config::{
     build_llm_factory, build_switch_model_tool, load_agent_defaults, load_api_defaults,
-    load_registry_defaults, load_subagent_config,
+    load_memory_defaults, load_registry_defaults, load_subagent_config,
 };
 use self::subagent_backend::{
     build_direct_service_subagent_manager, build_service_subagent_manager,
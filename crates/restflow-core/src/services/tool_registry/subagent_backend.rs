@@ -100,13 +100,14 @@ pub(super) fn build_service_subagent_runtime_bundle(
 ) -> ServiceSubagentRuntimeBundle {
     let (completion_tx, completion_rx) = mpsc::channel(128);
     let tracker = Arc::new(SubagentTracker::new(completion_tx, completion_rx));
-    let db = execution_trace_storage.db();
+    let backend = execution_trace_storage.backend();
     let telemetry_sink = match (
         execution_trace_storage.clone(),
-        crate::storage::ChatSessionStorage::new(db.clone()),
-        crate::storage::TelemetryMetricSampleStorage::new(db.clone()),
-        crate::storage::ProviderHealthSnapshotStorage::new(db.clone()),
-        crate::storage::StructuredExecutionLogStorage::new(db),
+        crate::storage::ChatSessionStorage::with_backend(backend.clone()),
+        crate::storage::TelemetryMetricSampleStorage::with_backend(backend.clone()),
+        crate::storage::ProviderHealthSnapshotStorage::with_backend(backend.clone()),
+        crate::storage::StructuredExecutionLogStorage::with_backend(backend.clone()),
+        crate::storage::UsageStorage::with_backend(backend),
     ) {
         (
             execution_traces,
@@ -114,12 +115,14 @@ pub(super) fn build_service_subagent_runtime_bundle(
             Ok(telemetry_metric_samples),
             Ok(provider_health_snapshots),
             Ok(structured_execution_logs),
+            Ok(usage),
         ) => Some(Arc::new(crate::telemetry::CoreTelemetrySink::new(
             execution_traces,
             chat_sessions,
             telemetry_metric_samples,
             provider_health_snapshots,
             structured_execution_logs,
+            usage,
         )) as Arc<dyn restflow_telemetry::TelemetrySink>),
         _ => {
             warn!("Failed to initialize core telemetry sink for service subagents");
@@ -232,6 +232,7 @@ fn setup_storage() -> (
         db.clone(),
         restflow_storage::SecretStorageConfig {
             allow_insecure_file_permissions: true,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -437,16 +438,24 @@ fn test_create_tool_registry() {
     assert!(registry.has("multiedit"));
     assert!(registry.has("glob"));
     assert!(registry.has("grep"));
+    assert!(registry.has("git"));
+    assert!(registry.has("github"));
+    assert!(registry.has("calendar"));
+    assert!(registry.has("sql"));
+    assert!(registry.has("spreadsheet"));
+    assert!(registry.has("speak"));
     assert!(registry.has("task_list"));
     assert!(registry.has("skill"));
     assert!(registry.has("memory_search"));
     assert!(registry.has("kv_store"));
     assert!(registry.has("process"));
+    assert!(registry.has("shell_session"));
     assert!(registry.has("reply"));
     assert!(registry.has("switch_model"));
     assert!(registry.has("spawn_subagent"));
     assert!(registry.has("wait_subagents"));
     assert!(registry.has("list_subagents"));
+    assert!(registry.has("fanout"));
     // New system management tools
     assert!(registry.has("manage_secrets"));
     assert!(registry.has("manage_config"));
@@ -463,6 +472,7 @@ fn test_create_tool_registry() {
     assert!(registry.has("manage_memory"));
     assert!(registry.has("manage_auth_profiles"));
     assert!(registry.has("save_deliverable"));
+    assert!(registry.has("deliverable_builder"));
 }
 
 #[tokio::test(flavor = "current_thread")]
@@ -918,6 +928,8 @@ fn test_agent_store_adapter_crud_flow() {
         skill_variables: None,
         skill_preflight_policy_mode: None,
         model_routing: None,
+        dry_run: None,
+        execution_strategy: None,
     };
 
     let created = AgentStore::create_agent(
@@ -1866,6 +1878,7 @@ fn test_runtime_allowlist_assembly_matches_service_registry_for_core_tools() {
         "spawn_subagent".to_string(),
         "wait_subagents".to_string(),
         "list_subagents".to_string(),
+        "fanout".to_string(),
     ];
     let runtime_registry = crate::runtime::agent::tools::registry_from_allowlist(
         Some(&allowlist),
@@ -1891,6 +1904,7 @@ fn test_runtime_allowlist_assembly_matches_service_registry_for_core_tools() {
         "spawn_subagent",
         "wait_subagents",
         "list_subagents",
+        "fanout",
     ] {
         assert_eq!(
             runtime_registry.has(tool_name),
@@ -2041,7 +2055,8 @@ async fn test_create_subagent_manager_persists_execution_traces() {
     ) = setup_storage();
 
     let execution_trace_storage =
-        ExecutionTraceStorage::new(execution_trace_storage.db()).expect("execution trace storage");
+        ExecutionTraceStorage::with_backend(execution_trace_storage.backend())
+            .expect("execution trace storage");
 
     let service_registry = create_tool_registry(
         skill_storage,
@@ -0,0 +1,449 @@
+//! Users and per-user workspaces for multi-user server mode.
+//!
+//! Ownership is recorded beside the resources rather than on them: agents and
+//! skills get a [`WorkspaceResource`] record naming their owner, and chat
+//! sessions and memory inherit the workspace of the agent they belong to.
+//! Resources without an ownership record are only visible to unbound callers.
+//!
+//! Callers acting for a user go through [`WorkspaceAccess`], which checks
+//! ownership on every lookup, records it on every create and builds listings
+//! from the owned IDs only.
+
+use crate::AppCore;
+use crate::models::{ChatSession, Skill, User, WorkspaceResource, WorkspaceResourceKind};
+use crate::services::agent as agent_service;
+use crate::services::session::SessionService;
+use crate::services::skills as skills_service;
+use crate::storage::agent::StoredAgent;
+use crate::storage::{Storage, WorkspaceStorage};
+use anyhow::{Result, bail};
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::warn;
+
+/// The agents and skills owned by one user, resolved for access checks.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceScope {
+    pub user_id: String,
+    agents: HashSet<String>,
+    skills: HashSet<String>,
+}
+
+impl WorkspaceScope {
+    pub fn owns(&self, kind: WorkspaceResourceKind, resource_id: &str) -> bool {
+        match kind {
+            WorkspaceResourceKind::Agent => self.agents.contains(resource_id),
+            WorkspaceResourceKind::Skill => self.skills.contains(resource_id),
+        }
+    }
+
+    pub fn owns_agent(&self, agent_id: &str) -> bool {
+        self.owns(WorkspaceResourceKind::Agent, agent_id)
+    }
+
+    pub fn owns_skill(&self, skill_id: &str) -> bool {
+        self.owns(WorkspaceResourceKind::Skill, skill_id)
+    }
+}
+
+#[derive(Clone)]
+pub struct WorkspaceService {
+    workspaces: WorkspaceStorage,
+}
+
+impl WorkspaceService {
+    pub fn new(workspaces: WorkspaceStorage) -> Self {
+        Self { workspaces }
+    }
+
+    pub fn from_storage(storage: &Storage) -> Self {
+        Self::new(storage.workspaces.clone())
+    }
+
+    /// Create a user with a unique, non-empty name.
+    pub fn create_user(&self, name: &str) -> Result<User> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("User name must not be empty");
+        }
+        if self
+            .workspaces
+            .list_users()?
+            .iter()
+            .any(|user| user.name.eq_ignore_ascii_case(name))
+        {
+            bail!("User '{name}' already exists");
+        }
+
+        let user = User {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+        self.workspaces.create_user(&user)?;
+        Ok(user)
+    }
+
+    pub fn get_user(&self, id: &str) -> Result<Option<User>> {
+        self.workspaces.get_user(id)
+    }
+
+    pub fn list_users(&self) -> Result<Vec<User>> {
+        self.workspaces.list_users()
+    }
+
+    /// Delete a user and release everything their workspace owned.
+    ///
+    /// Released agents and skills are kept and become visible to unbound
+    /// callers only. Returns `false` when the user does not exist.
+    pub fn delete_user(&self, id: &str) -> Result<bool> {
+        if self.workspaces.get_user(id)?.is_none() {
+            return Ok(false);
+        }
+        for resource in self.workspaces.list_resources()? {
+            if resource.owner_id == id {
+                self.workspaces
+                    .delete_resource(resource.kind, &resource.resource_id)?;
+            }
+        }
+        self.workspaces.delete_user(id)
+    }
+
+    /// Place a resource in `owner_id`'s workspace.
+    pub fn assign(
+        &self,
+        kind: WorkspaceResourceKind,
+        resource_id: &str,
+        owner_id: &str,
+    ) -> Result<()> {
+        if self.workspaces.get_user(owner_id)?.is_none() {
+            bail!("User {owner_id} not found");
+        }
+        self.workspaces.put_resource(&WorkspaceResource {
+            kind,
+            resource_id: resource_id.to_string(),
+            owner_id: owner_id.to_string(),
+            assigned_at: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+
+    /// Drop the ownership record of a deleted resource.
+    pub fn release(&self, kind: WorkspaceResourceKind, resource_id: &str) -> Result<bool> {
+        self.workspaces.delete_resource(kind, resource_id)
+    }
+
+    /// ID of the user owning a resource, if any.
+    pub fn owner_of(
+        &self,
+        kind: WorkspaceResourceKind,
+        resource_id: &str,
+    ) -> Result<Option<String>> {
+        Ok(self
+            .workspaces
+            .get_resource(kind, resource_id)?
+            .map(|resource| resource.owner_id))
+    }
+
+    /// Resolve everything `user_id` owns.
+    pub fn scope(&self, user_id: &str) -> Result<WorkspaceScope> {
+        if self.workspaces.get_user(user_id)?.is_none() {
+            bail!("User {user_id} not found");
+        }
+        let mut scope = WorkspaceScope {
+            user_id: user_id.to_string(),
+            ..WorkspaceScope::default()
+        };
+        for resource in self.workspaces.list_resources()? {
+            if resource.owner_id != user_id {
+                continue;
+            }
+            match resource.kind {
+                WorkspaceResourceKind::Agent => scope.agents.insert(resource.resource_id),
+                WorkspaceResourceKind::Skill => scope.skills.insert(resource.resource_id),
+            };
+        }
+        Ok(scope)
+    }
+}
+
+/// Why a [`WorkspaceAccess`] call was refused.
+#[derive(Debug, Error)]
+pub enum WorkspaceAccessError {
+    /// The resource does not exist or belongs to another workspace.
+    #[error("{0} not found")]
+    NotFound(&'static str),
+    #[error("{0}")]
+    Invalid(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl WorkspaceAccessError {
+    /// HTTP-style status code for this error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::Invalid(_) => 400,
+            Self::Conflict(_) => 409,
+            Self::Internal(_) => 500,
+        }
+    }
+}
+
+type AccessResult<T> = std::result::Result<T, WorkspaceAccessError>;
+
+/// Agent, skill, session and memory operations on behalf of one user.
+///
+/// Resources of other workspaces behave as if they did not exist.
+pub struct WorkspaceAccess {
+    core: Arc<AppCore>,
+    workspaces: WorkspaceService,
+    scope: WorkspaceScope,
+}
+
+impl WorkspaceAccess {
+    /// Act as `user_id`, who must exist.
+    pub fn for_user(core: Arc<AppCore>, user_id: &str) -> Result<Self> {
+        let workspaces = WorkspaceService::from_storage(&core.storage);
+        let scope = workspaces.scope(user_id)?;
+        Ok(Self {
+            core,
+            workspaces,
+            scope,
+        })
+    }
+
+    pub fn scope(&self) -> &WorkspaceScope {
+        &self.scope
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.scope.user_id
+    }
+
+    /// Resolve an agent ID or name to an agent this workspace owns.
+    pub fn agent(&self, id: &str) -> AccessResult<String> {
+        match self.core.storage.agents.resolve_existing_agent_id(id) {
+            Ok(agent_id) if self.scope.owns_agent(&agent_id) => Ok(agent_id),
+            _ => Err(WorkspaceAccessError::NotFound("Agent")),
+        }
+    }
+
+    /// Like [`Self::agent`], for requests where the agent is optional
+    /// elsewhere but required inside a workspace.
+    pub fn required_agent(&self, id: Option<&str>) -> AccessResult<String> {
+        match id {
+            Some(id) => self.agent(id),
+            None => Err(WorkspaceAccessError::Invalid(
+                "agent_id is required for workspace-scoped tokens".to_string(),
+            )),
+        }
+    }
+
+    pub fn skill(&self, id: &str) -> AccessResult<()> {
+        if self.scope.owns_skill(id) {
+            Ok(())
+        } else {
+            Err(WorkspaceAccessError::NotFound("Skill"))
+        }
+    }
+
+    /// Load a session whose agent this workspace owns.
+    pub fn session(&self, id: &str) -> AccessResult<ChatSession> {
+        match self.core.storage.chat_sessions.get(id)? {
+            Some(session) if self.scope.owns_agent(&session.agent_id) => Ok(session),
+            _ => Err(WorkspaceAccessError::NotFound("Session")),
+        }
+    }
+
+    pub fn list_agents(&self) -> AccessResult<Vec<StoredAgent>> {
+        let mut agents = Vec::new();
+        for agent_id in &self.scope.agents {
+            if let Some(agent) = self.core.storage.agents.get_agent(agent_id.clone())? {
+                agents.push(agent);
+            }
+        }
+        agents.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(agents)
+    }
+
+    pub fn list_skills(&self) -> AccessResult<Vec<Skill>> {
+        let mut skills = Vec::new();
+        for skill_id in &self.scope.skills {
+            if let Some(skill) = self.core.storage.skills.get(skill_id)? {
+                skills.push(skill);
+            }
+        }
+        skills.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(skills)
+    }
+
+    /// Active sessions of the owned agents, most recently updated first.
+    pub fn list_sessions(&self) -> AccessResult<Vec<ChatSession>> {
+        let session_service = SessionService::from_storage(&self.core.storage);
+        let mut sessions = self
+            .core
+            .storage
+            .chat_sessions
+            .list_for_agents(&self.scope.agents)?;
+        for session in &mut sessions {
+            session_service.apply_effective_source(session)?;
+        }
+        Ok(sessions)
+    }
+
+    /// Create an agent owned by this workspace.
+    pub async fn create_agent(
+        &mut self,
+        name: String,
+        agent: crate::models::AgentNode,
+    ) -> AccessResult<StoredAgent> {
+        let agent = agent_service::create_agent(&self.core, name, agent).await?;
+        self.claim(WorkspaceResourceKind::Agent, &agent.id)?;
+        Ok(agent)
+    }
+
+    /// Create a skill owned by this workspace. Existing IDs are refused so a
+    /// workspace cannot take over another workspace's skill.
+    pub async fn create_skill(&mut self, skill: Skill) -> AccessResult<()> {
+        if self.core.storage.skills.exists(&skill.id)? {
+            return Err(WorkspaceAccessError::Conflict(format!(
+                "Skill {} already exists",
+                skill.id
+            )));
+        }
+        let skill_id = skill.id.clone();
+        skills_service::create_skill(&self.core, skill).await?;
+        self.claim(WorkspaceResourceKind::Skill, &skill_id)
+    }
+
+    pub async fn delete_agent(&mut self, id: &str) -> AccessResult<()> {
+        let agent_id = self.agent(id)?;
+        agent_service::delete_agent(&self.core, &agent_id).await?;
+        self.release(WorkspaceResourceKind::Agent, &agent_id);
+        Ok(())
+    }
+
+    pub async fn delete_skill(&mut self, id: &str) -> AccessResult<()> {
+        self.skill(id)?;
+        skills_service::delete_skill(&self.core, id).await?;
+        self.release(WorkspaceResourceKind::Skill, id);
+        Ok(())
+    }
+
+    /// Record ownership of a resource just created, removing it again if
+    /// that fails so it cannot end up unowned.
+    fn claim(&mut self, kind: WorkspaceResourceKind, resource_id: &str) -> AccessResult<()> {
+        if let Err(error) = self
+            .workspaces
+            .assign(kind, resource_id, &self.scope.user_id)
+        {
+            let removed = match kind {
+                WorkspaceResourceKind::Agent => self
+                    .core
+                    .storage
+                    .agents
+                    .delete_agent(resource_id.to_string()),
+                WorkspaceResourceKind::Skill => self.core.storage.skills.delete(resource_id),
+            };
+            if let Err(remove_error) = removed {
+                warn!(error = %remove_error, kind = kind.as_str(), id = %resource_id, "Failed to remove unowned workspace resource");
+            }
+            return Err(error.into());
+        }
+        match kind {
+            WorkspaceResourceKind::Agent => self.scope.agents.insert(resource_id.to_string()),
+            WorkspaceResourceKind::Skill => self.scope.skills.insert(resource_id.to_string()),
+        };
+        Ok(())
+    }
+
+    fn release(&mut self, kind: WorkspaceResourceKind, resource_id: &str) {
+        if let Err(error) = self.workspaces.release(kind, resource_id) {
+            warn!(error = %error, kind = kind.as_str(), id = %resource_id, "Failed to release workspace resource");
+        }
+        match kind {
+            WorkspaceResourceKind::Agent => self.scope.agents.remove(resource_id),
+            WorkspaceResourceKind::Skill => self.scope.skills.remove(resource_id),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redb::Database;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn setup() -> (WorkspaceService, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("create temp dir");
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).expect("create db"));
+        let storage = WorkspaceStorage::new(db).expect("create storage");
+        (WorkspaceService::new(storage), temp_dir)
+    }
+
+    #[test]
+    fn test_create_user_rejects_empty_and_duplicate_names() {
+        let (service, _temp_dir) = setup();
+        service.create_user("alice").expect("create user");
+        assert!(service.create_user("  ").is_err());
+        assert!(service.create_user("Alice").is_err());
+        assert_eq!(service.list_users().expect("list users").len(), 1);
+    }
+
+    #[test]
+    fn test_scope_only_includes_owned_resources() {
+        let (service, _temp_dir) = setup();
+        let alice = service.create_user("alice").expect("create user");
+        let bob = service.create_user("bob").expect("create user");
+        service
+            .assign(WorkspaceResourceKind::Agent, "agent-a", &alice.id)
+            .expect("assign agent");
+        service
+            .assign(WorkspaceResourceKind::Skill, "skill-a", &alice.id)
+            .expect("assign skill");
+        service
+            .assign(WorkspaceResourceKind::Agent, "agent-b", &bob.id)
+            .expect("assign agent");
+        assert!(
+            service
+                .assign(WorkspaceResourceKind::Agent, "agent-c", "missing")
+                .is_err()
+        );
+
+        let scope = service.scope(&alice.id).expect("alice scope");
+        assert!(scope.owns_agent("agent-a"));
+        assert!(scope.owns_skill("skill-a"));
+        assert!(!scope.owns_agent("agent-b"));
+        assert!(!scope.owns_skill("agent-a"));
+        assert_eq!(
+            service
+                .owner_of(WorkspaceResourceKind::Agent, "agent-b")
+                .expect("owner"),
+            Some(bob.id.clone())
+        );
+    }
+
+    #[test]
+    fn test_delete_user_releases_resources() {
+        let (service, _temp_dir) = setup();
+        let alice = service.create_user("alice").expect("create user");
+        service
+            .assign(WorkspaceResourceKind::Agent, "agent-a", &alice.id)
+            .expect("assign agent");
+
+        assert!(service.delete_user(&alice.id).expect("delete user"));
+        assert!(!service.delete_user(&alice.id).expect("delete user again"));
+        assert_eq!(
+            service
+                .owner_of(WorkspaceResourceKind::Agent, "agent-a")
+                .expect("owner"),
+            None
+        );
+        assert!(service.scope(&alice.id).is_err());
+    }
+}
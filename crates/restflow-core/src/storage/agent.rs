@@ -4,14 +4,17 @@ use crate::models::AgentNode;
 use crate::prompt_files;
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
 use restflow_storage::time_utils;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::EntityRelationStorage;
+
 /// Canonical default assistant name created during app initialization.
 pub const DEFAULT_ASSISTANT_NAME: &str = "Default Assistant";
 /// Legacy default assistant name for backward compatibility.
@@ -38,15 +41,36 @@ pub struct StoredAgent {
 #[derive(Clone)]
 pub struct AgentStorage {
     inner: restflow_storage::AgentStorage,
+    relations: EntityRelationStorage,
+    /// Bumped on every write so read caches can detect stale entries.
+    revision: Arc<AtomicU64>,
 }
 
 impl AgentStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage with agent records and relation edges on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::AgentStorage::new(db)?,
+            inner: restflow_storage::AgentStorage::with_backend(backend.clone())?,
+            relations: EntityRelationStorage::with_backend(backend)?,
+            revision: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Write revision shared by all clones of this storage.
+    ///
+    /// Prompt files edited outside RestFlow do not bump the revision.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn bump_revision(&self) {
+        self.revision.fetch_add(1, Ordering::AcqRel);
+    }
+
     pub fn create_agent(&self, name: String, mut agent: AgentNode) -> Result<StoredAgent> {
         normalize_model_fields(&mut agent)?;
         let now = time_utils::now_ms();
@@ -191,6 +215,32 @@ impl AgentStorage {
         self.hydrate_prompt_from_file(existing_agent)
     }
 
+    /// Store an agent under its existing ID, e.g. when importing a bundle.
+    ///
+    /// `agent.agent.prompt` is written to the prompt file; an existing agent
+    /// with the same ID is replaced.
+    pub fn import_agent(&self, mut agent: StoredAgent) -> Result<StoredAgent> {
+        normalize_model_fields(&mut agent.agent)?;
+        let prompt_override = agent.agent.prompt.take();
+        let existing_prompt_file = self
+            .inner
+            .get_raw(&agent.id)?
+            .map(|bytes| serde_json::from_slice::<StoredAgent>(&bytes))
+            .transpose()?
+            .and_then(|existing| existing.prompt_file);
+        let prompt_path = prompt_files::ensure_agent_prompt_file(
+            &agent.id,
+            &agent.name,
+            existing_prompt_file.as_deref(),
+            prompt_override.as_deref(),
+        )?;
+        agent.prompt_file = Some(path_file_name(&prompt_path)?);
+        agent.updated_at = Some(time_utils::now_ms());
+
+        self.persist_without_prompt(&agent)?;
+        self.hydrate_prompt_from_file(agent)
+    }
+
     /// Delete an agent atomically to prevent TOCTOU race conditions.
     ///
     /// This operation resolves the agent ID and deletes it within a single
@@ -212,6 +262,8 @@ impl AgentStorage {
         if !existed {
             return Err(anyhow::anyhow!("Agent {} not found", id));
         }
+        self.revision.fetch_add(1, Ordering::AcqRel);
+        self.relations.remove_entity(&resolved_id)?;
         let _ = prompt_files::delete_agent_prompt_file_for_agent(
             &existing.id,
             &existing.name,
@@ -282,6 +334,7 @@ impl AgentStorage {
         scrubbed.agent.prompt = None;
         let json_bytes = serde_json::to_vec(&scrubbed)?;
         self.inner.put_raw(&scrubbed.id, &json_bytes)?;
+        self.revision.fetch_add(1, Ordering::AcqRel);
         Ok(())
     }
 
@@ -365,6 +418,8 @@ mod tests {
             skill_variables: None,
             skill_preflight_policy_mode: None,
             model_routing: None,
+            dry_run: None,
+            execution_strategy: None,
         }
     }
 
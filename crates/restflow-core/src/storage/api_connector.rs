@@ -0,0 +1,122 @@
+//! Typed REST API connector storage wrapper.
+
+use crate::models::ApiConnector;
+use anyhow::{Result, bail};
+use redb::Database;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
+use std::sync::Arc;
+
+/// Typed connector storage wrapper around restflow-storage::ApiConnectorStorage.
+#[derive(Debug, Clone)]
+pub struct ApiConnectorStorage {
+    inner: restflow_storage::ApiConnectorStorage,
+}
+
+impl ApiConnectorStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            inner: restflow_storage::ApiConnectorStorage::with_backend(backend)?,
+        })
+    }
+
+    /// Save a connector, replacing any connector with the same name.
+    ///
+    /// The original `created_at` is kept when a connector is re-imported.
+    pub fn save(&self, mut connector: ApiConnector) -> Result<ApiConnector> {
+        connector.name = connector.name.trim().to_string();
+        if connector.name.is_empty() {
+            bail!("Connector name must not be empty");
+        }
+        if connector.operations.is_empty() {
+            bail!("Connector {} has no operations", connector.name);
+        }
+        if let Some(previous) = self.get(&connector.name)? {
+            connector.created_at = previous.created_at;
+        }
+
+        let json_bytes = serde_json::to_vec(&connector)?;
+        self.inner.put_raw(&connector.name, &json_bytes)?;
+        Ok(connector)
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<ApiConnector>> {
+        if let Some(bytes) = self.inner.get_raw(name)? {
+            Ok(Some(serde_json::from_slice(&bytes)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List all connectors sorted by name.
+    pub fn list(&self) -> Result<Vec<ApiConnector>> {
+        let mut connectors = Vec::new();
+        for (_, bytes) in self.inner.list_raw()? {
+            connectors.push(serde_json::from_slice::<ApiConnector>(&bytes)?);
+        }
+        connectors.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(connectors)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        self.inner.delete(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConnectorAuth, ConnectorOperation};
+    use tempfile::tempdir;
+
+    fn connector(name: &str, created_at: i64) -> ApiConnector {
+        ApiConnector {
+            name: name.to_string(),
+            description: None,
+            base_url: "https://api.example.com".to_string(),
+            auth: ConnectorAuth::None,
+            operations: vec![ConnectorOperation {
+                id: "list_items".to_string(),
+                method: "GET".to_string(),
+                path: "/items".to_string(),
+                summary: None,
+                parameters: Vec::new(),
+                request_body: None,
+            }],
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_save_replaces_and_keeps_created_at() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let storage = ApiConnectorStorage::new(db).unwrap();
+
+        storage.save(connector("shop", 1)).unwrap();
+        let saved = storage.save(connector("shop", 5)).unwrap();
+        assert_eq!(saved.created_at, 1);
+        assert_eq!(saved.updated_at, 5);
+        storage.save(connector("crm", 2)).unwrap();
+
+        let names: Vec<String> = storage
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|connector| connector.name)
+            .collect();
+        assert_eq!(names, vec!["crm", "shop"]);
+
+        let mut empty = connector("empty", 3);
+        empty.operations.clear();
+        assert!(storage.save(empty).is_err());
+
+        assert!(storage.delete("shop").unwrap());
+        assert!(storage.get("shop").unwrap().is_none());
+    }
+}
@@ -0,0 +1,173 @@
+//! Typed API token storage wrapper.
+
+use crate::models::ApiToken;
+use anyhow::Result;
+use redb::Database;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
+use std::sync::Arc;
+
+restflow_storage::define_simple_storage! {
+    /// Raw API token storage table.
+    pub struct RawApiTokenStorage { table: "api_tokens" }
+}
+
+/// Typed API token storage wrapper around raw key-value storage.
+#[derive(Debug, Clone)]
+pub struct ApiTokenStorage {
+    inner: RawApiTokenStorage,
+}
+
+impl ApiTokenStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            inner: RawApiTokenStorage::with_backend(backend)?,
+        })
+    }
+
+    /// Create a new token record (fails if the id already exists).
+    pub fn create(&self, token: &ApiToken) -> Result<()> {
+        if self.inner.exists(&token.id)? {
+            anyhow::bail!("API token {} already exists", token.id);
+        }
+        let json = serde_json::to_vec(token)?;
+        self.inner.put_raw(&token.id, &json)
+    }
+
+    /// Get a token by id.
+    pub fn get(&self, id: &str) -> Result<Option<ApiToken>> {
+        let Some(bytes) = self.inner.get_raw(id)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    /// Find a token by the hash of its secret.
+    pub fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .find(|token| token.token_hash == token_hash))
+    }
+
+    /// List all tokens sorted by creation time descending.
+    pub fn list(&self) -> Result<Vec<ApiToken>> {
+        let mut tokens = Vec::new();
+        for (_, bytes) in self.inner.list_raw()? {
+            tokens.push(serde_json::from_slice::<ApiToken>(&bytes)?);
+        }
+
+        tokens.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(tokens)
+    }
+
+    /// Update an existing token.
+    pub fn update(&self, token: &ApiToken) -> Result<()> {
+        if !self.inner.exists(&token.id)? {
+            anyhow::bail!("API token {} not found", token.id);
+        }
+
+        let json = serde_json::to_vec(token)?;
+        self.inner.put_raw(&token.id, &json)
+    }
+
+    /// Run `f` on the stored token inside one write transaction.
+    ///
+    /// The token is written back only when `f` returns `Ok`, so checks and
+    /// updates cannot interleave with concurrent callers. Returns `None` when
+    /// the token does not exist.
+    pub fn modify<R, E>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&mut ApiToken) -> std::result::Result<R, E>,
+    ) -> Result<Option<std::result::Result<R, E>>> {
+        self.inner.update_raw(id, |bytes| {
+            let Some(bytes) = bytes else {
+                return Ok((None, None));
+            };
+            let mut token: ApiToken = serde_json::from_slice(&bytes)?;
+            let outcome = f(&mut token);
+            let next = match outcome {
+                Ok(_) => Some(serde_json::to_vec(&token)?),
+                Err(_) => None,
+            };
+            Ok((next, Some(outcome)))
+        })
+    }
+
+    /// Delete a token by id.
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        self.inner.delete(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApiTokenScope;
+    use tempfile::tempdir;
+
+    fn setup() -> (ApiTokenStorage, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("create temp dir");
+        let db_path = temp_dir.path().join("test.db");
+        let db = Arc::new(Database::create(db_path).expect("create db"));
+        let storage = ApiTokenStorage::new(db).expect("create storage");
+        (storage, temp_dir)
+    }
+
+    fn build_token(id: &str, hash: &str, created_at: i64) -> ApiToken {
+        ApiToken {
+            id: id.to_string(),
+            name: format!("caller-{id}"),
+            token_hash: hash.to_string(),
+            token_prefix: "rft_test".to_string(),
+            scope: ApiTokenScope::default(),
+            call_count: 0,
+            created_at,
+            last_used_at: None,
+            expires_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_create_get_update_delete() {
+        let (storage, _temp_dir) = setup();
+        let mut token = build_token("tok-1", "hash-1", 1);
+
+        storage.create(&token).expect("create token");
+        assert!(storage.create(&token).is_err());
+
+        token.revoked_at = Some(10);
+        storage.update(&token).expect("update token");
+        let stored = storage.get("tok-1").expect("get token").expect("exists");
+        assert_eq!(stored.revoked_at, Some(10));
+
+        assert!(storage.delete("tok-1").expect("delete token"));
+        assert!(storage.get("tok-1").expect("get token").is_none());
+    }
+
+    #[test]
+    fn test_list_and_find_by_hash() {
+        let (storage, _temp_dir) = setup();
+        storage
+            .create(&build_token("tok-a", "hash-a", 1))
+            .expect("create token a");
+        storage
+            .create(&build_token("tok-b", "hash-b", 2))
+            .expect("create token b");
+
+        let tokens = storage.list().expect("list tokens");
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].id, "tok-b");
+
+        let found = storage.find_by_hash("hash-a").expect("find token");
+        assert_eq!(found.map(|token| token.id), Some("tok-a".to_string()));
+        assert!(storage.find_by_hash("missing").expect("find").is_none());
+    }
+}
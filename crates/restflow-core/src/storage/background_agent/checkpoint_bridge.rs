@@ -8,8 +8,12 @@ impl BackgroundAgentStorage {
         self.checkpoints.save(checkpoint)
     }
 
-    /// Save an agent checkpoint and return a persistent savepoint ID.
-    pub fn save_checkpoint_with_savepoint(&self, checkpoint: &AgentCheckpoint) -> Result<u64> {
+    /// Save an agent checkpoint and return a persistent savepoint ID, if the
+    /// storage backend supports savepoints.
+    pub fn save_checkpoint_with_savepoint(
+        &self,
+        checkpoint: &AgentCheckpoint,
+    ) -> Result<Option<u64>> {
         self.checkpoints.save_with_savepoint(checkpoint)
     }
 
@@ -6,17 +6,21 @@
 use crate::models::{
     AgentCheckpoint, BackgroundAgent, BackgroundAgentControlAction, BackgroundAgentEvent,
     BackgroundAgentEventType, BackgroundAgentPatch, BackgroundAgentSchedule, BackgroundAgentSpec,
-    BackgroundAgentStatus, BackgroundMessage, BackgroundProgress, ChatSession, ModelId,
-    TaskMessageSource, TaskMessageStatus,
+    BackgroundAgentStatus, BackgroundMessage, BackgroundProgress, CallbackDelivery, ChatSession,
+    ConcurrencyLimits, ModelId, ResultCallback, TaskMessageSource, TaskMessageStatus,
 };
 use anyhow::Result;
 use redb::Database;
+use restflow_storage::{RedbBackend, StorageBackend};
 use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::warn;
 use uuid::Uuid;
 
-use super::{AgentStorage, ChatSessionStorage, CheckpointStorage, ExecutionTraceStorage};
+use super::{
+    AgentStorage, ChatSessionStorage, CheckpointStorage, EntityRelationStorage,
+    ExecutionTraceStorage,
+};
 
 type BackgroundMessageSource = TaskMessageSource;
 type BackgroundMessageStatus = TaskMessageStatus;
@@ -29,6 +33,8 @@ pub struct BackgroundAgentStorage {
     agents: AgentStorage,
     chat_sessions: ChatSessionStorage,
     execution_traces: ExecutionTraceStorage,
+    relations: EntityRelationStorage,
+    temporary_secrets: TemporarySecretStore,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +45,7 @@ struct SessionBindingResolution {
 
 impl BackgroundAgentStorage {
     const MIN_TASK_TIMEOUT_SECS: u64 = 10;
+    const MAX_CALLBACK_DELIVERIES: usize = 20;
 
     fn has_non_empty_text(value: Option<&str>) -> bool {
         value.is_some_and(|text| !text.trim().is_empty())
@@ -67,6 +74,27 @@ impl BackgroundAgentStorage {
         Ok(())
     }
 
+    fn validate_result_callbacks(callbacks: &[ResultCallback]) -> Result<()> {
+        for callback in callbacks {
+            restflow_traits::network::validate_url(&callback.url)
+                .map_err(|e| anyhow::anyhow!("Invalid callback URL '{}': {}", callback.url, e))?;
+        }
+        Ok(())
+    }
+
+    fn normalize_concurrency_limits(mut limits: ConcurrencyLimits) -> Result<ConcurrencyLimits> {
+        limits.group = Self::normalize_optional_id(limits.group);
+        if limits.max_group_runs == Some(0) || limits.max_agent_runs == Some(0) {
+            return Err(anyhow::anyhow!("concurrency limits must be at least 1"));
+        }
+        if limits.max_group_runs.is_some() && limits.group.is_none() {
+            return Err(anyhow::anyhow!(
+                "concurrency max_group_runs requires a non-empty group"
+            ));
+        }
+        Ok(limits)
+    }
+
     fn validate_task_input(input: Option<&str>, input_template: Option<&str>) -> Result<()> {
         if Self::resolve_effective_input_for_validation(input, input_template).is_some() {
             return Ok(());
@@ -227,14 +255,29 @@ impl BackgroundAgentStorage {
 
     /// Create a new BackgroundAgentStorage instance
     pub fn new(db: Arc<Database>) -> Result<Self> {
-        let checkpoints = CheckpointStorage::new(db.clone())?;
-        let execution_traces = ExecutionTraceStorage::new(db.clone())?;
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage with every record on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        let chat_sessions = ChatSessionStorage::with_backend(backend.clone())?;
+        Self::with_chat_sessions(backend, chat_sessions)
+    }
+
+    /// Create the storage writing chat sessions through `chat_sessions`, so
+    /// run transcripts reach the search index it maintains.
+    pub fn with_chat_sessions(
+        backend: Arc<dyn StorageBackend>,
+        chat_sessions: ChatSessionStorage,
+    ) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::BackgroundAgentStorage::new(db.clone())?,
-            checkpoints,
-            agents: AgentStorage::new(db.clone())?,
-            chat_sessions: ChatSessionStorage::new(db)?,
-            execution_traces,
+            inner: restflow_storage::BackgroundAgentStorage::with_backend(backend.clone())?,
+            checkpoints: CheckpointStorage::with_backend(backend.clone())?,
+            agents: AgentStorage::with_backend(backend.clone())?,
+            chat_sessions,
+            execution_traces: ExecutionTraceStorage::with_backend(backend.clone())?,
+            relations: EntityRelationStorage::with_backend(backend)?,
+            temporary_secrets: TemporarySecretStore::default(),
         })
     }
 
@@ -272,8 +315,12 @@ mod message_queue;
 mod run_records;
 mod session_binding;
 mod task_lifecycle;
+mod temporary_secrets;
+mod wake;
 
 pub use task_lifecycle::ResolveTaskIdError;
+pub use temporary_secrets::TemporarySecretStore;
+pub(crate) use wake::file_modified_at;
 
 #[cfg(test)]
 mod tests;
@@ -21,10 +21,14 @@ impl BackgroundAgentStorage {
             resource_limits,
             prerequisites,
             continuation,
+            concurrency,
         } = spec;
 
         Self::validate_timeout_secs(timeout_secs)?;
         Self::validate_task_input(input.as_deref(), input_template.as_deref())?;
+        let concurrency = concurrency
+            .map(Self::normalize_concurrency_limits)
+            .transpose()?;
         let session_binding =
             self.resolve_chat_session_id_for_create(chat_session_id, &agent_id, &name)?;
         let mut task = BackgroundAgent::new(Uuid::new_v4().to_string(), name, agent_id, schedule);
@@ -54,6 +58,9 @@ impl BackgroundAgentStorage {
         if let Some(continuation) = continuation {
             task.continuation = continuation;
         }
+        if let Some(concurrency) = concurrency {
+            task.concurrency = concurrency;
+        }
         task.updated_at = chrono::Utc::now().timestamp_millis();
 
         self.save_task(&task)?;
@@ -85,6 +92,9 @@ impl BackgroundAgentStorage {
             resource_limits,
             prerequisites,
             continuation,
+            callbacks,
+            queue,
+            concurrency,
         } = patch;
         Self::validate_timeout_secs(timeout_secs)?;
         let mut task = self
@@ -142,6 +152,16 @@ impl BackgroundAgentStorage {
             task.continuation_total_iterations = 0;
             task.continuation_segments_completed = 0;
         }
+        if let Some(callbacks) = callbacks {
+            Self::validate_result_callbacks(&callbacks)?;
+            task.callbacks = callbacks;
+        }
+        if let Some(queue) = queue {
+            task.queue = queue;
+        }
+        if let Some(concurrency) = concurrency {
+            task.concurrency = Self::normalize_concurrency_limits(concurrency)?;
+        }
         Self::validate_task_input(task.input.as_deref(), task.input_template.as_deref())?;
 
         task.updated_at = chrono::Utc::now().timestamp_millis();
@@ -163,6 +183,7 @@ impl BackgroundAgentStorage {
         let event = match action {
             BackgroundAgentControlAction::Start => {
                 task.status = BackgroundAgentStatus::Active;
+                task.retry_attempts = 0;
                 task.next_run_at = Some(now);
                 task.updated_at = now;
                 BackgroundAgentEvent::new(task.id.clone(), BackgroundAgentEventType::Resumed)
@@ -185,6 +206,7 @@ impl BackgroundAgentStorage {
             }
             BackgroundAgentControlAction::RunNow => {
                 task.status = BackgroundAgentStatus::Active;
+                task.retry_attempts = 0;
                 task.next_run_at = Some(now);
                 task.updated_at = now;
                 BackgroundAgentEvent::new(task.id.clone(), BackgroundAgentEventType::Resumed)
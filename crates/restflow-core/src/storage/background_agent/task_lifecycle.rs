@@ -26,6 +26,7 @@ impl BackgroundAgentStorage {
         let json_bytes = serde_json::to_vec(&task)?;
         self.inner
             .put_task_raw_with_status(&task.id, task.status.as_str(), &json_bytes)?;
+        self.relations.index_task(&task)?;
 
         // Create a "created" event
         let event = BackgroundAgentEvent::new(task.id.clone(), BackgroundAgentEventType::Created)
@@ -205,6 +206,8 @@ impl BackgroundAgentStorage {
             }
         }
 
+        // Serve higher queue priorities first, then the longest-waiting tasks.
+        runnable.sort_by_key(|task| (std::cmp::Reverse(task.queue.priority), task.next_run_at));
         Ok(runnable)
     }
 
@@ -290,7 +293,7 @@ impl BackgroundAgentStorage {
             self.inner
                 .put_task_raw_with_status(&task.id, task.status.as_str(), &json_bytes)?;
         }
-        Ok(())
+        self.relations.index_task(task)
     }
 
     /// Update an existing agent task.
@@ -307,7 +310,7 @@ impl BackgroundAgentStorage {
             task.status.as_str(),
             &json_bytes,
         )?;
-        Ok(())
+        self.relations.index_task(task)
     }
 
     fn update_task_if_status_matches(
@@ -332,6 +335,7 @@ impl BackgroundAgentStorage {
         if !deleted {
             return Ok(false);
         }
+        self.relations.remove_entity(id)?;
 
         let Some(task) = task else {
             return Ok(true);
@@ -471,11 +475,56 @@ impl BackgroundAgentStorage {
         self.update_task(&task)?;
 
         // Record the failure event
+        let message = if task.is_dead_lettered() {
+            format!(
+                "{} (dead-lettered after {} attempts)",
+                error, task.retry_attempts
+            )
+        } else {
+            error
+        };
         let event = BackgroundAgentEvent::new(task.id.clone(), BackgroundAgentEventType::Failed)
-            .with_message(error)
+            .with_message(message)
             .with_duration(duration_ms);
         self.add_event(&event)?;
 
         Ok(task)
     }
+
+    /// Register a result callback on a task.
+    pub fn add_result_callback(&self, id: &str, callback: ResultCallback) -> Result<()> {
+        Self::validate_result_callbacks(std::slice::from_ref(&callback))?;
+        let mut task = self
+            .get_task(id)?
+            .ok_or_else(|| anyhow::anyhow!("Task {} not found", id))?;
+
+        task.callbacks.push(callback);
+        self.update_task(&task)
+    }
+
+    /// Record the outcome of a result callback delivery on the task.
+    ///
+    /// One-shot callbacks matching the delivered URL are removed, and only the
+    /// most recent deliveries are kept.
+    pub fn record_callback_delivery(&self, id: &str, delivery: CallbackDelivery) -> Result<()> {
+        let mut task = self
+            .get_task(id)?
+            .ok_or_else(|| anyhow::anyhow!("Task {} not found", id))?;
+
+        if let Some(index) = task
+            .callbacks
+            .iter()
+            .position(|callback| callback.once && callback.url == delivery.url)
+        {
+            task.callbacks.remove(index);
+        }
+        task.callback_deliveries.push(delivery);
+        let overflow = task
+            .callback_deliveries
+            .len()
+            .saturating_sub(Self::MAX_CALLBACK_DELIVERIES);
+        task.callback_deliveries.drain(..overflow);
+
+        self.update_task(&task)
+    }
 }
@@ -0,0 +1,255 @@
+//! Execution-scoped temporary secrets.
+//!
+//! Secrets supplied when a run is requested (webhook or `RunTaskWithSecrets`)
+//! are held in memory only. They are staged against a task, bound to the next
+//! run when it starts, resolvable by that run's tools, and wiped with an
+//! execution trace record once the run ends. Nothing here touches the database
+//! except the audit record, which lists secret names but never values.
+
+use super::*;
+use crate::models::{
+    ExecutionLogField, ExecutionTraceCategory, ExecutionTraceSource, LogRecordTrace,
+    execution_trace_builders,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Staged secrets that no run picked up are dropped after this long.
+const PENDING_SECRET_TTL: Duration = Duration::from_secs(15 * 60);
+const MAX_TEMPORARY_SECRETS: usize = 32;
+const MAX_TEMPORARY_SECRET_BYTES: usize = 16 * 1024;
+
+struct TaskSecrets {
+    values: HashMap<String, String>,
+    staged_at: Instant,
+    /// Run the secrets are bound to; `None` while waiting for the next run.
+    run_id: Option<String>,
+}
+
+/// In-memory holder for execution-scoped secrets, shared by every clone of
+/// the background agent storage.
+#[derive(Clone, Default)]
+pub struct TemporarySecretStore {
+    tasks: Arc<Mutex<HashMap<String, TaskSecrets>>>,
+}
+
+impl TemporarySecretStore {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, TaskSecrets>> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn validate(secrets: &HashMap<String, String>) -> Result<()> {
+        if secrets.len() > MAX_TEMPORARY_SECRETS {
+            anyhow::bail!(
+                "At most {} temporary secrets can be provided per run",
+                MAX_TEMPORARY_SECRETS
+            );
+        }
+        for (name, value) in secrets {
+            let valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+            if !valid_name {
+                anyhow::bail!(
+                    "Invalid temporary secret name '{}': use letters, digits, '_', '-' or '.'",
+                    name
+                );
+            }
+            if value.is_empty() || value.len() > MAX_TEMPORARY_SECRET_BYTES {
+                anyhow::bail!(
+                    "Temporary secret '{}' must be between 1 and {} bytes",
+                    name,
+                    MAX_TEMPORARY_SECRET_BYTES
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Stage secrets for the next run of `task_id`, merging with any already
+    /// staged. Fails while a run already holds secrets for the task.
+    pub fn stage(&self, task_id: &str, secrets: HashMap<String, String>) -> Result<()> {
+        Self::validate(&secrets)?;
+        let mut tasks = self.lock();
+        tasks.retain(|_, entry| {
+            entry.run_id.is_some() || entry.staged_at.elapsed() < PENDING_SECRET_TTL
+        });
+        let entry = tasks
+            .entry(task_id.to_string())
+            .or_insert_with(|| TaskSecrets {
+                values: HashMap::new(),
+                staged_at: Instant::now(),
+                run_id: None,
+            });
+        if entry.run_id.is_some() {
+            anyhow::bail!(
+                "Task {} is running with temporary secrets; wait for the run to finish",
+                task_id
+            );
+        }
+        let mut merged = entry.values.clone();
+        merged.extend(secrets);
+        Self::validate(&merged)?;
+        entry.values = merged;
+        entry.staged_at = Instant::now();
+        Ok(())
+    }
+
+    /// Bind staged secrets to `run_id`. Returns the bound secret names.
+    pub fn activate(&self, task_id: &str, run_id: &str) -> Vec<String> {
+        let mut tasks = self.lock();
+        let Some(entry) = tasks.get_mut(task_id) else {
+            return Vec::new();
+        };
+        if entry.run_id.is_none() && entry.staged_at.elapsed() >= PENDING_SECRET_TTL {
+            tasks.remove(task_id);
+            return Vec::new();
+        }
+        entry.run_id = Some(run_id.to_string());
+        let mut names: Vec<String> = entry.values.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Drop secrets staged for `task_id` that no run has picked up yet.
+    pub fn discard_pending(&self, task_id: &str) {
+        let mut tasks = self.lock();
+        if tasks
+            .get(task_id)
+            .is_some_and(|entry| entry.run_id.is_none())
+        {
+            tasks.remove(task_id);
+        }
+    }
+
+    /// Value of `name` for the active run of `task_id`, if any.
+    pub fn resolve(&self, task_id: &str, name: &str) -> Option<String> {
+        let tasks = self.lock();
+        let entry = tasks.get(task_id)?;
+        entry.run_id.as_ref()?;
+        entry.values.get(name).cloned()
+    }
+
+    /// Remove the secrets bound to `run_id`. Returns the wiped names, or
+    /// `None` when the run held no secrets.
+    pub fn wipe(&self, task_id: &str, run_id: &str) -> Option<Vec<String>> {
+        let mut tasks = self.lock();
+        if tasks.get(task_id)?.run_id.as_deref() != Some(run_id) {
+            return None;
+        }
+        let entry = tasks.remove(task_id)?;
+        let mut names: Vec<String> = entry.values.into_keys().collect();
+        names.sort();
+        Some(names)
+    }
+}
+
+impl BackgroundAgentStorage {
+    /// In-memory store for execution-scoped secrets.
+    pub fn temporary_secrets(&self) -> &TemporarySecretStore {
+        &self.temporary_secrets
+    }
+
+    /// Stage temporary secrets for the next run of an existing task.
+    pub fn stage_temporary_secrets(
+        &self,
+        task_id: &str,
+        secrets: HashMap<String, String>,
+    ) -> Result<()> {
+        if self.get_task(task_id)?.is_none() {
+            return Err(anyhow::anyhow!("Task {} not found", task_id));
+        }
+        self.temporary_secrets.stage(task_id, secrets)
+    }
+
+    /// Wipe the temporary secrets of a finished run and record which names
+    /// were discarded.
+    pub fn wipe_temporary_secrets(&self, task_id: &str, agent_id: &str, run_id: &str) {
+        let Some(names) = self.temporary_secrets.wipe(task_id, run_id) else {
+            return;
+        };
+
+        let mut event = execution_trace_builders::with_log_record(
+            execution_trace_builders::new_event(
+                task_id,
+                agent_id,
+                ExecutionTraceCategory::LogRecord,
+                ExecutionTraceSource::Runtime,
+            ),
+            LogRecordTrace {
+                level: "info".to_string(),
+                message: "Temporary secrets wiped".to_string(),
+                fields: vec![
+                    ExecutionLogField {
+                        key: "count".to_string(),
+                        value: names.len().to_string(),
+                    },
+                    ExecutionLogField {
+                        key: "names".to_string(),
+                        value: names.join(","),
+                    },
+                ],
+            },
+        );
+        event.run_id = Some(run_id.to_string());
+        if let Err(error) = self.execution_traces.store(&event) {
+            warn!(
+                task_id,
+                run_id,
+                error = %error,
+                "Failed to record temporary secret wipe"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secrets(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_secrets_resolve_only_while_bound_to_a_run() {
+        let store = TemporarySecretStore::default();
+        store
+            .stage("task-1", secrets(&[("DEPLOY_TOKEN", "abc")]))
+            .unwrap();
+        assert_eq!(store.resolve("task-1", "DEPLOY_TOKEN"), None);
+
+        assert_eq!(store.activate("task-1", "run-1"), vec!["DEPLOY_TOKEN"]);
+        assert_eq!(
+            store.resolve("task-1", "DEPLOY_TOKEN").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(store.resolve("task-2", "DEPLOY_TOKEN"), None);
+        assert!(store.stage("task-1", secrets(&[("OTHER", "x")])).is_err());
+
+        assert_eq!(store.wipe("task-1", "run-other"), None);
+        assert_eq!(
+            store.wipe("task-1", "run-1"),
+            Some(vec!["DEPLOY_TOKEN".to_string()])
+        );
+        assert_eq!(store.resolve("task-1", "DEPLOY_TOKEN"), None);
+    }
+
+    #[test]
+    fn test_stage_rejects_invalid_names_and_values() {
+        let store = TemporarySecretStore::default();
+        assert!(
+            store
+                .stage("task-1", secrets(&[("bad name", "v")]))
+                .is_err()
+        );
+        assert!(store.stage("task-1", secrets(&[("EMPTY", "")])).is_err());
+    }
+}
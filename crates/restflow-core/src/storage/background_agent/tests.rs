@@ -1,5 +1,7 @@
 use super::*;
-use crate::models::{BackgroundAgentRun, BackgroundAgentRunStatus};
+use crate::models::{
+    BackgroundAgentRun, BackgroundAgentRunStatus, ConcurrencyLimits, QueuePriority, TaskQueuePolicy,
+};
 use tempfile::tempdir;
 
 fn create_test_storage() -> BackgroundAgentStorage {
@@ -26,7 +28,7 @@ fn persist_checkpoint(
 
     if with_savepoint {
         let savepoint_id = storage.save_checkpoint_with_savepoint(&checkpoint).unwrap();
-        checkpoint.savepoint_id = Some(savepoint_id);
+        checkpoint.savepoint_id = savepoint_id;
         storage
             .save_checkpoint_with_savepoint_id(&checkpoint)
             .unwrap();
@@ -60,6 +62,7 @@ fn test_resolve_existing_task_id_exact_match() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -105,6 +108,7 @@ fn test_resolve_existing_task_id_unique_prefix() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -173,6 +177,7 @@ fn test_resolve_existing_task_id_ambiguous_prefix() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -193,6 +198,7 @@ fn test_resolve_existing_task_id_ambiguous_prefix() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -461,6 +467,7 @@ fn test_resolve_existing_task_id_exact_priority_over_prefix() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1016,6 +1023,7 @@ fn test_delete_task_archives_owned_chat_session() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1061,6 +1069,7 @@ fn test_delete_task_does_not_archive_non_owned_chat_session() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1095,6 +1104,7 @@ fn test_create_background_agent_rejects_reused_chat_session_binding() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1114,6 +1124,7 @@ fn test_create_background_agent_rejects_reused_chat_session_binding() {
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_err());
@@ -1148,6 +1159,70 @@ fn test_pause_and_resume_task() {
     assert!(event_types.contains(&&BackgroundAgentEventType::Resumed));
 }
 
+#[test]
+fn test_wake_condition_fires_once_and_queues_event_message() {
+    use crate::models::{WakeCondition, WakeEvent};
+
+    let storage = create_test_storage();
+    let temp_dir = tempdir().unwrap();
+    let watched = temp_dir.path().join("watched.txt");
+    std::fs::write(&watched, "v1").unwrap();
+
+    let task = storage
+        .create_task(
+            "Wake Task".to_string(),
+            "agent-001".to_string(),
+            BackgroundAgentSchedule::default(),
+        )
+        .unwrap();
+    let condition = WakeCondition::FileChanged {
+        path: watched.to_string_lossy().to_string(),
+        baseline_modified_at: None,
+    };
+    assert!(
+        storage
+            .register_wake_condition(&task.id, condition.clone(), None)
+            .is_err()
+    );
+
+    storage.pause_task(&task.id).unwrap();
+    let waiting = storage
+        .register_wake_condition(&task.id, condition.clone(), None)
+        .unwrap();
+    match &waiting.wake.as_ref().unwrap().condition {
+        WakeCondition::FileChanged {
+            baseline_modified_at,
+            ..
+        } => assert!(baseline_modified_at.is_some()),
+        other => panic!("unexpected condition {other:?}"),
+    }
+    assert_eq!(storage.list_waiting_tasks().unwrap().len(), 1);
+    assert!(storage.take_fired_wake(&task.id).unwrap().is_none());
+
+    let webhook = WakeEvent::new(&WakeCondition::WebhookReceived, serde_json::json!({}));
+    assert!(!storage.fire_wake_condition(&task.id, webhook).unwrap());
+    let event = WakeEvent::new(&condition, serde_json::json!({ "path": "watched.txt" }));
+    assert!(
+        storage
+            .fire_wake_condition(&task.id, event.clone())
+            .unwrap()
+    );
+    assert!(!storage.fire_wake_condition(&task.id, event).unwrap());
+
+    let wake = storage.take_fired_wake(&task.id).unwrap().unwrap();
+    assert_eq!(wake.fired.unwrap().kind, "file_changed");
+    let stored = storage.get_task(&task.id).unwrap().unwrap();
+    assert!(stored.wake.is_none());
+    assert_eq!(stored.status, BackgroundAgentStatus::Paused);
+
+    let pending = storage
+        .list_pending_background_messages(&task.id, 10)
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].source, TaskMessageSource::System);
+    assert!(pending[0].message.contains("watched.txt"));
+}
+
 #[test]
 fn test_task_execution_lifecycle() {
     let storage = create_test_storage();
@@ -1361,6 +1436,94 @@ fn test_list_runnable_tasks() {
     assert_eq!(runnable[0].name, "Ready Task");
 }
 
+#[test]
+fn test_list_runnable_tasks_orders_by_queue_priority_then_due_time() {
+    let storage = create_test_storage();
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let seed = |name: &str, priority: QueuePriority, due: i64| {
+        let mut task = storage
+            .create_task(
+                name.to_string(),
+                "agent-001".to_string(),
+                BackgroundAgentSchedule::Once { run_at: due },
+            )
+            .unwrap();
+        task.next_run_at = Some(due);
+        task.queue.priority = priority;
+        storage.update_task(&task).unwrap();
+    };
+    seed("low-oldest", QueuePriority::Low, now - 30_000);
+    seed("normal-newer", QueuePriority::Normal, now - 1_000);
+    seed("normal-older", QueuePriority::Normal, now - 20_000);
+    seed("high", QueuePriority::High, now - 500);
+
+    let names: Vec<String> = storage
+        .list_runnable_tasks(now)
+        .unwrap()
+        .into_iter()
+        .map(|task| task.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec!["high", "normal-older", "normal-newer", "low-oldest"]
+    );
+}
+
+#[test]
+fn test_fail_task_execution_dead_letters_after_retry_limit() {
+    let storage = create_test_storage();
+    let task = storage
+        .create_task(
+            "Flaky Task".to_string(),
+            "agent-001".to_string(),
+            BackgroundAgentSchedule::default(),
+        )
+        .unwrap();
+
+    let patch = BackgroundAgentPatch {
+        input: Some("do the thing".to_string()),
+        queue: Some(TaskQueuePolicy {
+            max_retries: Some(1),
+            retry_backoff_secs: 5,
+            ..TaskQueuePolicy::default()
+        }),
+        ..Default::default()
+    };
+    storage.update_background_agent(&task.id, patch).unwrap();
+
+    let retried = storage
+        .fail_task_execution(&task.id, "boom".to_string(), 10)
+        .unwrap();
+    assert_eq!(retried.status, BackgroundAgentStatus::Failed);
+    assert_eq!(retried.next_run_at, Some(retried.updated_at + 5_000));
+
+    let dead = storage
+        .fail_task_execution(&task.id, "boom again".to_string(), 10)
+        .unwrap();
+    assert_eq!(dead.status, BackgroundAgentStatus::DeadLetter);
+    assert!(
+        storage
+            .list_runnable_tasks(i64::MAX)
+            .unwrap()
+            .iter()
+            .all(|runnable| runnable.id != task.id)
+    );
+    let events = storage.list_events_for_task(&task.id).unwrap();
+    assert!(events.iter().any(|event| {
+        event
+            .message
+            .as_deref()
+            .is_some_and(|message| message.contains("dead-lettered after 2 attempts"))
+    }));
+
+    let restarted = storage
+        .control_background_agent(&task.id, BackgroundAgentControlAction::Start)
+        .unwrap();
+    assert_eq!(restarted.status, BackgroundAgentStatus::Active);
+    assert_eq!(restarted.retry_attempts, 0);
+}
+
 #[test]
 fn test_list_runnable_tasks_repairs_missing_next_run_for_cron() {
     let storage = create_test_storage();
@@ -1385,6 +1548,7 @@ fn test_list_runnable_tasks_repairs_missing_next_run_for_cron() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1424,6 +1588,7 @@ fn test_list_runnable_tasks_repairs_stale_next_run() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1474,6 +1639,7 @@ fn test_repair_runnable_task_does_not_overwrite_paused_status_from_stale_snapsho
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1523,6 +1689,7 @@ fn test_list_runnable_tasks_skips_task_when_repair_persist_fails() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1628,6 +1795,7 @@ fn test_background_agent_lifecycle() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
     assert_eq!(created.name, "BG Agent");
@@ -1671,6 +1839,61 @@ fn test_background_agent_lifecycle() {
     assert_eq!(stopped.status, BackgroundAgentStatus::Interrupted);
 }
 
+#[test]
+fn test_update_background_agent_validates_concurrency_limits() {
+    let storage = create_test_storage();
+    let task = storage
+        .create_task(
+            "Deploy".to_string(),
+            "agent-001".to_string(),
+            BackgroundAgentSchedule::default(),
+        )
+        .unwrap();
+
+    let patch = |concurrency| BackgroundAgentPatch {
+        input: Some("ship it".to_string()),
+        concurrency: Some(concurrency),
+        ..Default::default()
+    };
+
+    let err = storage
+        .update_background_agent(
+            &task.id,
+            patch(ConcurrencyLimits {
+                group: Some("   ".to_string()),
+                max_group_runs: Some(1),
+                max_agent_runs: None,
+            }),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("requires a non-empty group"));
+
+    let err = storage
+        .update_background_agent(
+            &task.id,
+            patch(ConcurrencyLimits {
+                max_agent_runs: Some(0),
+                ..ConcurrencyLimits::default()
+            }),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("at least 1"));
+
+    let updated = storage
+        .update_background_agent(
+            &task.id,
+            patch(ConcurrencyLimits {
+                group: Some(" deploy ".to_string()),
+                max_group_runs: Some(1),
+                max_agent_runs: Some(2),
+            }),
+        )
+        .unwrap();
+    assert_eq!(updated.concurrency.group.as_deref(), Some("deploy"));
+    assert_eq!(updated.concurrency.max_group_runs, Some(1));
+    assert_eq!(updated.concurrency.max_agent_runs, Some(2));
+}
+
 #[test]
 fn test_background_message_queue_and_progress() {
     let storage = create_test_storage();
@@ -1768,6 +1991,7 @@ fn test_create_background_agent_with_template_and_memory_scope() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1798,6 +2022,7 @@ fn test_create_background_agent_auto_creates_bound_chat_session() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1836,6 +2061,7 @@ fn test_create_background_agent_rejects_chat_session_bound_to_other_agent() {
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_err());
@@ -1864,6 +2090,7 @@ fn test_update_background_agent_agent_change_rebinds_chat_session() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
     let original_session_id = created.chat_session_id.clone();
@@ -1909,6 +2136,7 @@ fn test_update_background_agent_rejects_reused_chat_session_binding() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1929,6 +2157,7 @@ fn test_update_background_agent_rejects_reused_chat_session_binding() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -1966,6 +2195,7 @@ fn test_update_background_agent_updates_template_and_memory_scope() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -2014,6 +2244,7 @@ fn test_create_background_agent_rejects_timeout_below_minimum() {
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_err());
@@ -2045,6 +2276,7 @@ fn test_update_background_agent_updates_timeout_secs() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -2088,6 +2320,7 @@ fn test_background_agent_resource_limits_roundtrip() {
             }),
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -2145,6 +2378,7 @@ fn test_background_agent_continuation_roundtrip() {
                 max_total_cost_usd: Some(4.5),
                 inter_segment_pause_ms: 250,
             }),
+            concurrency: None,
         })
         .unwrap();
 
@@ -2204,6 +2438,7 @@ fn test_create_background_agent_rejects_missing_input_and_template() {
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_err());
@@ -2235,6 +2470,7 @@ fn test_update_background_agent_rejects_empty_input_and_template() {
             resource_limits: None,
             prerequisites: Vec::new(),
             continuation: None,
+            concurrency: None,
         })
         .unwrap();
 
@@ -2275,6 +2511,7 @@ fn test_create_background_agent_allows_empty_template_render_when_fallback_input
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_ok());
@@ -2299,6 +2536,7 @@ fn test_create_background_agent_rejects_template_that_renders_empty_without_fall
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_err());
@@ -2329,7 +2567,79 @@ fn test_create_background_agent_keeps_non_empty_template_compatibility() {
         resource_limits: None,
         prerequisites: Vec::new(),
         continuation: None,
+        concurrency: None,
     });
 
     assert!(result.is_ok());
 }
+
+// ============== Result Callback Tests ==============
+
+#[test]
+fn test_record_callback_delivery_consumes_one_shot_callbacks() {
+    let storage = create_test_storage();
+    let task = storage
+        .create_task(
+            "Callback Task".to_string(),
+            "agent-001".to_string(),
+            BackgroundAgentSchedule::default(),
+        )
+        .unwrap();
+
+    storage
+        .add_result_callback(
+            &task.id,
+            ResultCallback::new("https://example.com/always", None),
+        )
+        .unwrap();
+    storage
+        .add_result_callback(
+            &task.id,
+            ResultCallback::once("https://example.com/once", None),
+        )
+        .unwrap();
+    assert!(
+        storage
+            .add_result_callback(&task.id, ResultCallback::once("ftp://example.com", None))
+            .is_err()
+    );
+    for internal in ["http://127.0.0.1/hook", "http://169.254.169.254/latest"] {
+        assert!(
+            storage
+                .add_result_callback(&task.id, ResultCallback::once(internal, None))
+                .is_err()
+        );
+    }
+
+    let delivery = |url: &str| CallbackDelivery {
+        url: url.to_string(),
+        run_id: Some("run-1".to_string()),
+        status: crate::models::CallbackDeliveryStatus::Delivered,
+        attempts: 1,
+        status_code: Some(200),
+        error: None,
+        attempted_at: 1,
+    };
+    storage
+        .record_callback_delivery(&task.id, delivery("https://example.com/always"))
+        .unwrap();
+    storage
+        .record_callback_delivery(&task.id, delivery("https://example.com/once"))
+        .unwrap();
+
+    let stored = storage.get_task(&task.id).unwrap().unwrap();
+    assert_eq!(stored.callbacks.len(), 1);
+    assert_eq!(stored.callbacks[0].url, "https://example.com/always");
+    assert_eq!(stored.callback_deliveries.len(), 2);
+
+    for _ in 0..BackgroundAgentStorage::MAX_CALLBACK_DELIVERIES {
+        storage
+            .record_callback_delivery(&task.id, delivery("https://example.com/always"))
+            .unwrap();
+    }
+    let stored = storage.get_task(&task.id).unwrap().unwrap();
+    assert_eq!(
+        stored.callback_deliveries.len(),
+        BackgroundAgentStorage::MAX_CALLBACK_DELIVERIES
+    );
+}
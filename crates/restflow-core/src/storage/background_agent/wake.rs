@@ -0,0 +1,168 @@
+//! Wake conditions for paused tasks.
+//!
+//! A paused task can register one [`WakeCondition`]. The registration is
+//! stored on the task record so it survives daemon restarts. When the
+//! condition fires, the event is recorded on the registration until the
+//! runner consumes it, queues the event details as a system message and
+//! resumes the task.
+
+use super::*;
+use crate::models::{TaskWake, WakeCondition, WakeEvent};
+use std::path::Path;
+
+/// Modification time of `path` in milliseconds, `None` when it is missing.
+pub(crate) fn file_modified_at(path: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let elapsed = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    i64::try_from(elapsed.as_millis()).ok()
+}
+
+impl BackgroundAgentStorage {
+    // ============== Wake Condition Operations ==============
+
+    /// Register the condition that resumes a paused task.
+    ///
+    /// Replaces any previous registration. `checkpoint_id` selects the
+    /// checkpoint to resume from; without it the task's latest checkpoint is
+    /// used, or the task simply runs again when it has none.
+    pub fn register_wake_condition(
+        &self,
+        task_id: &str,
+        mut condition: WakeCondition,
+        checkpoint_id: Option<String>,
+    ) -> Result<BackgroundAgent> {
+        let mut task = self
+            .get_task(task_id)?
+            .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+        if task.status != BackgroundAgentStatus::Paused {
+            return Err(anyhow::anyhow!(
+                "Task {} must be paused to register a wake condition (status: {})",
+                task_id,
+                task.status.as_str()
+            ));
+        }
+
+        match &mut condition {
+            WakeCondition::WebhookReceived | WakeCondition::TimeReached { .. } => {}
+            WakeCondition::FileChanged {
+                path,
+                baseline_modified_at,
+            } => {
+                if path.trim().is_empty() {
+                    return Err(anyhow::anyhow!("Wake condition path must not be empty"));
+                }
+                *baseline_modified_at = file_modified_at(Path::new(path.trim()));
+            }
+            WakeCondition::WorkItemStatus { item_id, .. } => {
+                if item_id.trim().is_empty() {
+                    return Err(anyhow::anyhow!("Wake condition item_id must not be empty"));
+                }
+            }
+        }
+
+        let checkpoint_id = Self::normalize_optional_id(checkpoint_id);
+        if let Some(checkpoint_id) = checkpoint_id.as_deref() {
+            let checkpoint = self
+                .load_checkpoint(checkpoint_id)?
+                .ok_or_else(|| anyhow::anyhow!("checkpoint '{}' not found", checkpoint_id))?;
+            if checkpoint.task_id.as_deref() != Some(task_id) {
+                anyhow::bail!(
+                    "checkpoint '{}' belongs to task {:?}, expected '{}'",
+                    checkpoint_id,
+                    checkpoint.task_id,
+                    task_id
+                );
+            }
+        }
+
+        let kind = condition.kind();
+        let now = chrono::Utc::now().timestamp_millis();
+        task.wake = Some(TaskWake {
+            condition,
+            checkpoint_id,
+            registered_at: now,
+            fired: None,
+        });
+        task.updated_at = now;
+        self.update_task(&task)?;
+
+        let event = BackgroundAgentEvent::new(task.id.clone(), BackgroundAgentEventType::Paused)
+            .with_message(format!("Waiting for wake condition '{kind}'"));
+        self.add_event(&event)?;
+
+        Ok(task)
+    }
+
+    /// Remove the wake condition of a task, if any.
+    pub fn clear_wake_condition(&self, task_id: &str) -> Result<BackgroundAgent> {
+        let mut task = self
+            .get_task(task_id)?
+            .ok_or_else(|| anyhow::anyhow!("Task {} not found", task_id))?;
+        if task.wake.take().is_some() {
+            task.updated_at = chrono::Utc::now().timestamp_millis();
+            self.update_task(&task)?;
+        }
+        Ok(task)
+    }
+
+    /// Record that the wake condition of a paused task fired.
+    ///
+    /// Returns `false` when the task is not waiting for a condition of the
+    /// event's kind, or when it already fired and awaits resumption.
+    pub fn fire_wake_condition(&self, task_id: &str, event: WakeEvent) -> Result<bool> {
+        let Some(mut task) = self.get_task(task_id)? else {
+            return Ok(false);
+        };
+        if task.status != BackgroundAgentStatus::Paused {
+            return Ok(false);
+        }
+        let Some(wake) = task.wake.as_mut() else {
+            return Ok(false);
+        };
+        if wake.fired.is_some() || wake.condition.kind() != event.kind {
+            return Ok(false);
+        }
+
+        wake.fired = Some(event);
+        task.updated_at = chrono::Utc::now().timestamp_millis();
+        self.update_task(&task)?;
+        Ok(true)
+    }
+
+    /// List paused tasks with a registered wake condition.
+    pub fn list_waiting_tasks(&self) -> Result<Vec<BackgroundAgent>> {
+        Ok(self
+            .list_tasks_by_status(BackgroundAgentStatus::Paused)?
+            .into_iter()
+            .filter(|task| task.wake.is_some())
+            .collect())
+    }
+
+    /// Take the fired wake registration of a task and queue its event details
+    /// as a system message for the resumed run.
+    pub fn take_fired_wake(&self, task_id: &str) -> Result<Option<TaskWake>> {
+        let Some(mut task) = self.get_task(task_id)? else {
+            return Ok(None);
+        };
+        let Some(wake) = task.wake.take_if(|wake| wake.fired.is_some()) else {
+            return Ok(None);
+        };
+        let Some(event) = wake.fired.as_ref() else {
+            return Ok(None);
+        };
+
+        task.updated_at = chrono::Utc::now().timestamp_millis();
+        self.update_task(&task)?;
+        self.send_background_agent_message(
+            task_id,
+            event.context_message(),
+            BackgroundMessageSource::System,
+        )?;
+
+        let record = BackgroundAgentEvent::new(task.id.clone(), BackgroundAgentEventType::Resumed)
+            .with_message(format!("Woken by '{}'", event.kind));
+        self.add_event(&record)?;
+
+        Ok(Some(wake))
+    }
+}
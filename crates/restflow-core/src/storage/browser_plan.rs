@@ -0,0 +1,181 @@
+//! Typed browser plan storage wrapper.
+
+use crate::models::{BrowserPlan, SavedBrowserPlan};
+use anyhow::{Result, bail};
+use redb::Database;
+use restflow_storage::{RedbBackend, StorageBackend};
+use std::sync::Arc;
+
+/// Typed browser plan storage wrapper around restflow-storage::BrowserPlanStorage.
+#[derive(Clone)]
+pub struct BrowserPlanStorage {
+    inner: restflow_storage::BrowserPlanStorage,
+}
+
+impl BrowserPlanStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            inner: restflow_storage::BrowserPlanStorage::with_backend(backend)?,
+        })
+    }
+
+    /// Save a plan under `name`, creating the next version when the name is
+    /// already taken. A `None` description keeps the previous one.
+    pub fn save(
+        &self,
+        name: &str,
+        description: Option<String>,
+        plan: BrowserPlan,
+    ) -> Result<SavedBrowserPlan> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Browser plan name must not be empty");
+        }
+        if name.contains(':') {
+            bail!("Browser plan name must not contain ':'");
+        }
+        if !plan.actions.is_array() {
+            bail!("Browser plan actions must be a JSON array");
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let previous = self.get(name)?;
+        let saved = SavedBrowserPlan {
+            name: name.to_string(),
+            description: description.or_else(|| previous.as_ref()?.description.clone()),
+            version: previous.as_ref().map_or(1, |p| p.version + 1),
+            plan,
+            created_at: previous.as_ref().map_or(now, |p| p.created_at),
+            updated_at: now,
+        };
+
+        let json_bytes = serde_json::to_vec(&saved)?;
+        self.inner
+            .put_version_raw(&saved.name, saved.version, &json_bytes)?;
+        Ok(saved)
+    }
+
+    /// Get the latest version of a plan.
+    pub fn get(&self, name: &str) -> Result<Option<SavedBrowserPlan>> {
+        if let Some(bytes) = self.inner.get_raw(name)? {
+            Ok(Some(serde_json::from_slice(&bytes)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get a specific version of a plan, or the latest when `version` is `None`.
+    pub fn get_version(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<SavedBrowserPlan>> {
+        let Some(version) = version else {
+            return self.get(name);
+        };
+        if let Some(bytes) = self.inner.get_version_raw(name, version)? {
+            Ok(Some(serde_json::from_slice(&bytes)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List the latest version of every plan, sorted by name.
+    pub fn list(&self) -> Result<Vec<SavedBrowserPlan>> {
+        let mut plans = self
+            .inner
+            .list_raw()?
+            .into_iter()
+            .map(|(_, bytes)| serde_json::from_slice::<SavedBrowserPlan>(&bytes))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        plans.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(plans)
+    }
+
+    /// List every version of a plan, oldest first.
+    pub fn list_versions(&self, name: &str) -> Result<Vec<SavedBrowserPlan>> {
+        let versions = self
+            .inner
+            .list_versions_raw(name)?
+            .into_iter()
+            .map(|bytes| serde_json::from_slice::<SavedBrowserPlan>(&bytes))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(versions)
+    }
+
+    /// Delete a plan with all of its versions.
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        self.inner.delete(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn create_storage() -> (BrowserPlanStorage, tempfile::TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("browser_plans.db");
+        let db = Arc::new(Database::create(db_path).unwrap());
+        (BrowserPlanStorage::new(db).unwrap(), temp_dir)
+    }
+
+    fn plan(url: &str) -> BrowserPlan {
+        BrowserPlan {
+            actions: json!([{ "type": "navigate", "url": url }]),
+            timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_save_increments_version_and_keeps_history() {
+        let (storage, _dir) = create_storage();
+
+        let first = storage
+            .save(
+                "prices",
+                Some("Check prices".to_string()),
+                plan("https://a.test"),
+            )
+            .unwrap();
+        assert_eq!(first.version, 1);
+
+        let second = storage
+            .save("prices", None, plan("https://b.test"))
+            .unwrap();
+        assert_eq!(second.version, 2);
+        assert_eq!(second.created_at, first.created_at);
+        assert_eq!(second.description.as_deref(), Some("Check prices"));
+
+        assert_eq!(storage.get("prices").unwrap().unwrap(), second);
+        assert_eq!(
+            storage.get_version("prices", Some(1)).unwrap().unwrap(),
+            first
+        );
+        assert_eq!(storage.list_versions("prices").unwrap().len(), 2);
+        assert_eq!(storage.list().unwrap().len(), 1);
+
+        assert!(storage.delete("prices").unwrap());
+        assert!(storage.get_version("prices", Some(1)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_save_rejects_invalid_plans() {
+        let (storage, _dir) = create_storage();
+
+        assert!(storage.save(" ", None, plan("https://a.test")).is_err());
+        assert!(storage.save("a:b", None, plan("https://a.test")).is_err());
+        let not_array = BrowserPlan {
+            actions: json!({ "type": "navigate" }),
+            timeout_secs: None,
+        };
+        assert!(storage.save("plan", None, not_array).is_err());
+    }
+}
@@ -5,7 +5,7 @@
 use crate::models::ChannelSessionBinding;
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
@@ -15,8 +15,13 @@ pub struct ChannelSessionBindingStorage {
 
 impl ChannelSessionBindingStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::ChannelSessionBindingStorage::new(db)?,
+            inner: restflow_storage::ChannelSessionBindingStorage::with_backend(backend)?,
         })
     }
 
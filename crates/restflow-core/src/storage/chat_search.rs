@@ -0,0 +1,252 @@
+//! Full-text matching for chat message search.
+//!
+//! Messages are tokenized into lowercase alphanumeric words. A query is a
+//! list of terms and `"quoted phrases"`; a message matches when every term and
+//! phrase occurs in it. Terms, and the last word of a phrase, of three or more
+//! characters also match as word prefixes, so `config` finds `configured`.
+//! Matching and BM25 ranking run in the tantivy index; this module supplies
+//! the words it indexes and the highlights shown for each hit.
+
+use crate::models::ChatSearchHighlight;
+use restflow_storage::ChatQueryUnit;
+
+/// Characters of context kept before the first match in a snippet.
+const SNIPPET_LEAD: usize = 60;
+/// Maximum snippet length in characters, excluding ellipses.
+const SNIPPET_LEN: usize = 200;
+const MIN_PREFIX_LEN: usize = 3;
+
+/// A word in a message, with its character span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Token {
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+/// Split `text` into lowercase words with character offsets.
+pub(super) fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    for (index, ch) in text.chars().enumerate() {
+        if ch.is_alphanumeric() || ch == '_' {
+            let token = current.get_or_insert_with(|| Token {
+                text: String::new(),
+                start: index,
+                end: index,
+            });
+            token.text.extend(ch.to_lowercase());
+            token.end = index + 1;
+        } else if let Some(token) = current.take() {
+            tokens.push(token);
+        }
+    }
+    tokens.extend(current);
+    tokens
+}
+
+/// Words of `text` as stored in the search index.
+pub(super) fn index_words(text: &str) -> String {
+    tokenize(text)
+        .into_iter()
+        .map(|token| token.text)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parsed search query: each unit is a single term or a phrase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct QueryMatcher {
+    units: Vec<Vec<String>>,
+}
+
+/// Character spans of each query unit's occurrences in one message.
+pub(super) type UnitSpans = Vec<Vec<(usize, usize)>>;
+
+impl QueryMatcher {
+    /// Parse `query`, returning `None` when it has no searchable words.
+    pub(super) fn parse(query: &str) -> Option<Self> {
+        let mut units = Vec::new();
+        for (index, part) in query.split('"').enumerate() {
+            let words: Vec<String> = tokenize(part).into_iter().map(|t| t.text).collect();
+            // Odd parts sit between quotes.
+            if index % 2 == 1 {
+                if !words.is_empty() {
+                    units.push(words);
+                }
+            } else {
+                units.extend(words.into_iter().map(|word| vec![word]));
+            }
+        }
+        let mut unique: Vec<Vec<String>> = Vec::with_capacity(units.len());
+        for unit in units {
+            if !unique.contains(&unit) {
+                unique.push(unit);
+            }
+        }
+        (!unique.is_empty()).then_some(Self { units: unique })
+    }
+
+    /// The query as search index units.
+    pub(super) fn index_units(&self) -> Vec<ChatQueryUnit> {
+        self.units
+            .iter()
+            .map(|words| ChatQueryUnit {
+                words: words.clone(),
+                prefix: words
+                    .last()
+                    .is_some_and(|word| word.chars().count() >= MIN_PREFIX_LEN),
+            })
+            .collect()
+    }
+
+    /// Find every unit in `tokens`, or `None` if any unit is missing.
+    pub(super) fn find(&self, tokens: &[Token]) -> Option<UnitSpans> {
+        let mut spans = Vec::with_capacity(self.units.len());
+        for unit in &self.units {
+            let occurrences: Vec<(usize, usize)> = tokens
+                .windows(unit.len())
+                .filter(|window| {
+                    window
+                        .iter()
+                        .zip(unit)
+                        .enumerate()
+                        .all(|(index, (token, word))| {
+                            word_matches(&token.text, word, index + 1 == unit.len())
+                        })
+                })
+                .map(|window| (window[0].start, window[window.len() - 1].end))
+                .collect();
+            if occurrences.is_empty() {
+                return None;
+            }
+            spans.push(occurrences);
+        }
+        Some(spans)
+    }
+}
+
+fn word_matches(token: &str, word: &str, last: bool) -> bool {
+    token == word || (last && word.chars().count() >= MIN_PREFIX_LEN && token.starts_with(word))
+}
+
+/// Excerpt of `content` around the first match, with highlight ranges
+/// relative to the excerpt.
+pub(super) fn snippet(content: &str, spans: &UnitSpans) -> (String, Vec<ChatSearchHighlight>) {
+    let chars: Vec<char> = content.chars().collect();
+    let mut matches: Vec<(usize, usize)> = spans.iter().flatten().copied().collect();
+    matches.sort_unstable();
+    let first = matches.first().map_or(0, |(start, _)| *start);
+    let start = first.saturating_sub(SNIPPET_LEAD);
+    let end = (start + SNIPPET_LEN).min(chars.len());
+
+    let mut text = String::new();
+    let offset = if start > 0 {
+        text.push('…');
+        start - 1
+    } else {
+        0
+    };
+    // Whitespace becomes plain spaces so one-line output stays aligned.
+    text.extend(
+        chars[start..end]
+            .iter()
+            .map(|ch| if ch.is_whitespace() { ' ' } else { *ch }),
+    );
+    if end < chars.len() {
+        text.push('…');
+    }
+
+    let mut highlights: Vec<ChatSearchHighlight> = Vec::new();
+    for (match_start, match_end) in matches {
+        if match_start < start || match_end > end {
+            continue;
+        }
+        if let Some(last) = highlights.last_mut()
+            && match_start - offset <= last.end as usize
+        {
+            last.end = last.end.max((match_end - offset) as u32);
+            continue;
+        }
+        highlights.push(ChatSearchHighlight {
+            start: (match_start - offset) as u32,
+            end: (match_end - offset) as u32,
+        });
+    }
+    (text, highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlighted(text: &str, highlights: &[ChatSearchHighlight]) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        highlights
+            .iter()
+            .map(|h| chars[h.start as usize..h.end as usize].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_terms_and_phrases() {
+        let matcher = QueryMatcher::parse(r#"Webhook "retry policy" webhook"#).unwrap();
+        assert_eq!(
+            matcher.units,
+            vec![
+                vec!["webhook".to_string()],
+                vec!["retry".to_string(), "policy".to_string()],
+            ]
+        );
+        assert_eq!(matcher.index_units()[1].words.len(), 2);
+        assert!(matcher.index_units().iter().all(|unit| unit.prefix));
+        assert!(!QueryMatcher::parse("th").unwrap().index_units()[0].prefix);
+        assert!(QueryMatcher::parse("  \"\" ?! ").is_none());
+    }
+
+    #[test]
+    fn requires_every_unit_and_matches_prefixes() {
+        let tokens = tokenize("The agent configured the GitHub webhook.");
+        let matcher = QueryMatcher::parse("config webhook").unwrap();
+        let spans = matcher.find(&tokens).unwrap();
+        assert_eq!(spans, vec![vec![(10, 20)], vec![(32, 39)]]);
+
+        assert!(
+            QueryMatcher::parse("webhook slack")
+                .unwrap()
+                .find(&tokens)
+                .is_none()
+        );
+        // Short terms must match whole words.
+        assert!(QueryMatcher::parse("th").unwrap().find(&tokens).is_none());
+        assert!(
+            QueryMatcher::parse(r#""github webhook""#)
+                .unwrap()
+                .find(&tokens)
+                .is_some()
+        );
+        assert!(
+            QueryMatcher::parse(r#""webhook github""#)
+                .unwrap()
+                .find(&tokens)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn snippet_highlights_matches_near_first_hit() {
+        let content = format!("{} the webhook\nwas configured", "x ".repeat(100));
+        let tokens = tokenize(&content);
+        let spans = QueryMatcher::parse("webhook configured")
+            .unwrap()
+            .find(&tokens)
+            .unwrap();
+        let (text, highlights) = snippet(&content, &spans);
+        assert!(text.starts_with('…'));
+        assert!(!text.contains('\n'));
+        assert_eq!(
+            highlighted(&text, &highlights),
+            vec!["webhook".to_string(), "configured".to_string()]
+        );
+    }
+}
@@ -3,18 +3,31 @@
 //! Provides type-safe access to chat session storage, wrapping the byte-level
 //! API from restflow-storage with our Rust models.
 
-use crate::models::{ChatSession, ChatSessionSource, ChatSessionSummary, ModelId};
+use crate::models::{
+    ChatMessage, ChatSearchHit, ChatSearchQuery, ChatSearchResult, ChatSession, ChatSessionSource,
+    ChatSessionSummary, ModelId,
+};
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{
+    ChatIndexFilter, ChatMessageIndex, IndexableMessage, RedbBackend, SimpleStorage, StorageBackend,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use super::EntityRelationStorage;
+use super::chat_search::{QueryMatcher, index_words, snippet, tokenize};
+
 /// Typed chat session storage wrapper around restflow-storage::ChatSessionStorage.
 ///
 /// Provides CRUD operations for chat sessions with automatic JSON serialization.
+/// With a message index attached, writes keep it current; every instance that
+/// writes sessions must then share the same index.
 #[derive(Debug, Clone)]
 pub struct ChatSessionStorage {
     inner: restflow_storage::ChatSessionStorage,
+    relations: EntityRelationStorage,
+    index: Option<Arc<ChatMessageIndex>>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
@@ -60,6 +73,39 @@ fn normalize_session_model(session: &mut ChatSession) -> bool {
     true
 }
 
+fn indexable_messages(
+    session: &ChatSession,
+    messages: &[ChatMessage],
+    first_position: usize,
+) -> Vec<IndexableMessage> {
+    messages
+        .iter()
+        .enumerate()
+        .map(|(offset, message)| IndexableMessage {
+            session_id: session.id.clone(),
+            position: first_position + offset,
+            agent_id: session.agent_id.clone(),
+            skill_id: session.skill_id.clone(),
+            archived: session.is_archived(),
+            words: index_words(&message.content),
+        })
+        .collect()
+}
+
+/// Whether `session` only appends messages to `previous` and keeps the
+/// fields the index filters on.
+fn only_appends(previous: &ChatSession, session: &ChatSession) -> bool {
+    previous.agent_id == session.agent_id
+        && previous.skill_id == session.skill_id
+        && previous.is_archived() == session.is_archived()
+        && previous.messages.len() <= session.messages.len()
+        && previous
+            .messages
+            .iter()
+            .zip(&session.messages)
+            .all(|(before, after)| before.content == after.content)
+}
+
 fn extract_legacy_conversation_id(session_name: &str) -> Option<&str> {
     let conversation_id = session_name
         .strip_prefix(LEGACY_CHANNEL_SESSION_PREFIX)?
@@ -103,11 +149,90 @@ fn migrate_legacy_channel_source_fields(session: &mut ChatSession) -> bool {
 impl ChatSessionStorage {
     /// Create a new chat session storage instance.
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage with session records and relation edges on
+    /// `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Self::with_index(backend, None)
+    }
+
+    /// Create the storage with a message search index.
+    ///
+    /// Without an index, each search indexes all sessions in memory first.
+    pub fn with_index(
+        backend: Arc<dyn StorageBackend>,
+        index: Option<Arc<ChatMessageIndex>>,
+    ) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::ChatSessionStorage::new(db)?,
+            inner: restflow_storage::ChatSessionStorage::with_backend(backend.clone())?,
+            relations: EntityRelationStorage::with_backend(backend)?,
+            index,
         })
     }
 
+    /// Rebuild the message search index from persisted sessions.
+    pub fn rebuild_search_index(&self) -> Result<usize> {
+        let Some(index) = &self.index else {
+            return Ok(0);
+        };
+        self.rebuild_into(index)
+    }
+
+    /// Rebuild the message search index only when it is empty.
+    pub fn rebuild_search_index_if_empty(&self) -> Result<usize> {
+        let Some(index) = &self.index else {
+            return Ok(0);
+        };
+        if index.doc_count()? > 0 {
+            return Ok(0);
+        }
+        self.rebuild_into(index)
+    }
+
+    fn rebuild_into(&self, index: &ChatMessageIndex) -> Result<usize> {
+        let sessions = self.list_all()?;
+        index.rebuild(
+            sessions
+                .iter()
+                .flat_map(|session| indexable_messages(session, &session.messages, 0)),
+        )
+    }
+
+    /// Bring the index in line with `session`, replacing what was indexed
+    /// for `previous` unless messages were only appended.
+    fn reindex(&self, previous: Option<&ChatSession>, session: &ChatSession) -> Result<()> {
+        let Some(index) = &self.index else {
+            return Ok(());
+        };
+        match previous {
+            Some(previous) if only_appends(previous, session) => {
+                let start = previous.messages.len();
+                index.add_messages(&indexable_messages(
+                    session,
+                    &session.messages[start..],
+                    start,
+                ))
+            }
+            _ => index.replace_session(
+                &session.id,
+                &indexable_messages(session, &session.messages, 0),
+            ),
+        }
+    }
+
+    /// The stored session as last indexed, when an index needs it.
+    fn indexed_previous(&self, id: &str) -> Result<Option<ChatSession>> {
+        if self.index.is_none() {
+            return Ok(None);
+        }
+        match self.inner.get_raw(id)? {
+            Some(bytes) => Ok(serde_json::from_slice(&bytes).ok()),
+            None => Ok(None),
+        }
+    }
+
     /// Create a new chat session (fails if already exists).
     pub fn create(&self, session: &ChatSession) -> Result<()> {
         let mut normalized = session.clone();
@@ -120,7 +245,9 @@ impl ChatSessionStorage {
             ));
         }
         let json = serde_json::to_string(&normalized)?;
-        self.inner.put_raw(&normalized.id, json.as_bytes())
+        self.inner.put_raw(&normalized.id, json.as_bytes())?;
+        self.reindex(None, &normalized)?;
+        self.relations.index_chat_session(&normalized)
     }
 
     /// Get a chat session by ID.
@@ -198,6 +325,18 @@ impl ChatSessionStorage {
             .collect())
     }
 
+    /// List active chat sessions belonging to any of `agent_ids`.
+    pub fn list_for_agents(
+        &self,
+        agent_ids: &std::collections::HashSet<String>,
+    ) -> Result<Vec<ChatSession>> {
+        let sessions = self.list()?;
+        Ok(sessions
+            .into_iter()
+            .filter(|s| agent_ids.contains(&s.agent_id))
+            .collect())
+    }
+
     /// List chat sessions for a specific agent, including archived sessions.
     pub fn list_by_agent_all(&self, agent_id: &str) -> Result<Vec<ChatSession>> {
         let sessions = self.list_all()?;
@@ -225,6 +364,72 @@ impl ChatSessionStorage {
             .collect())
     }
 
+    /// Full-text search over message content.
+    ///
+    /// Hits are ranked by BM25 relevance.
+    pub fn search_messages(&self, query: &ChatSearchQuery) -> Result<ChatSearchResult> {
+        let Some(matcher) = QueryMatcher::parse(&query.query) else {
+            return Ok(ChatSearchResult::default());
+        };
+        let temporary;
+        let index = match &self.index {
+            Some(index) => index.as_ref(),
+            None => {
+                temporary = ChatMessageIndex::in_memory()?;
+                self.rebuild_into(&temporary)?;
+                &temporary
+            }
+        };
+        let filter = ChatIndexFilter {
+            agent_id: query.agent_id.clone(),
+            skill_id: query.skill_id.clone(),
+            include_archived: query.include_archived,
+        };
+        let page = index.search(
+            &matcher.index_units(),
+            &filter,
+            query.offset as usize,
+            query.limit as usize,
+        )?;
+
+        let mut sessions: HashMap<String, Option<ChatSession>> = HashMap::new();
+        let mut hits = Vec::with_capacity(page.hits.len());
+        for hit in page.hits {
+            if !sessions.contains_key(&hit.session_id) {
+                let session = self.get(&hit.session_id)?;
+                sessions.insert(hit.session_id.clone(), session);
+            }
+            let Some(session) = sessions.get(&hit.session_id).and_then(Option::as_ref) else {
+                continue;
+            };
+            let Some(message) = session.messages.get(hit.position) else {
+                continue;
+            };
+            let spans = matcher
+                .find(&tokenize(&message.content))
+                .unwrap_or_default();
+            let (text, highlights) = snippet(&message.content, &spans);
+            hits.push(ChatSearchHit {
+                session_id: session.id.clone(),
+                session_name: session.name.clone(),
+                agent_id: session.agent_id.clone(),
+                message_id: message.id.clone(),
+                role: message.role.clone(),
+                timestamp: message.timestamp,
+                score: hit.score,
+                snippet: text,
+                highlights,
+            });
+        }
+
+        let total_count = page.total_count as u32;
+        Ok(ChatSearchResult {
+            hits,
+            total_count,
+            has_more: total_count > query.offset.saturating_add(query.limit),
+        })
+    }
+
     /// Update an existing chat session.
     pub fn update(&self, session: &ChatSession) -> Result<()> {
         let mut normalized = session.clone();
@@ -233,21 +438,34 @@ impl ChatSessionStorage {
         if !self.inner.exists(&normalized.id)? {
             return Err(anyhow::anyhow!("Chat session {} not found", normalized.id));
         }
+        let previous = self.indexed_previous(&normalized.id)?;
         let json = serde_json::to_string(&normalized)?;
-        self.inner.put_raw(&normalized.id, json.as_bytes())
+        self.inner.put_raw(&normalized.id, json.as_bytes())?;
+        self.reindex(previous.as_ref(), &normalized)?;
+        self.relations.index_chat_session(&normalized)
     }
 
     /// Save a chat session (create or update).
     pub fn save(&self, session: &ChatSession) -> Result<()> {
         let mut normalized = session.clone();
         normalize_session_model(&mut normalized);
+        let previous = self.indexed_previous(&normalized.id)?;
         let json = serde_json::to_string(&normalized)?;
-        self.inner.put_raw(&normalized.id, json.as_bytes())
+        self.inner.put_raw(&normalized.id, json.as_bytes())?;
+        self.reindex(previous.as_ref(), &normalized)?;
+        self.relations.index_chat_session(&normalized)
     }
 
     /// Delete a chat session.
     pub fn delete(&self, id: &str) -> Result<bool> {
-        self.inner.delete(id)
+        let deleted = self.inner.delete(id)?;
+        if deleted {
+            if let Some(index) = &self.index {
+                index.remove_session(id)?;
+            }
+            self.relations.remove_entity(id)?;
+        }
+        Ok(deleted)
     }
 
     /// Archive a chat session.
@@ -810,6 +1028,95 @@ mod tests {
         assert_eq!(retrieved.messages[1].role, ChatRole::Assistant);
     }
 
+    #[test]
+    fn test_search_messages_ranks_and_paginates() {
+        let (storage, _temp_dir) = setup();
+
+        let mut webhook = ChatSession::new("agent-1".to_string(), "claude-sonnet-4".to_string())
+            .with_name("Integrations");
+        webhook.add_message(ChatMessage::user("Can you set up the GitHub webhook?"));
+        webhook.add_message(ChatMessage::assistant(
+            "I configured the webhook; webhook deliveries now go to /hooks/github.",
+        ));
+        storage.create(&webhook).unwrap();
+
+        let mut other = ChatSession::new("agent-2".to_string(), "claude-sonnet-4".to_string());
+        other.add_message(ChatMessage::user("What does a webhook do?"));
+        other.add_message(ChatMessage::user("Unrelated question about lunch"));
+        storage.create(&other).unwrap();
+
+        let result = storage
+            .search_messages(&ChatSearchQuery::new("webhook"))
+            .unwrap();
+        assert_eq!(result.total_count, 3);
+        assert!(!result.has_more);
+        assert_eq!(result.hits[0].session_id, webhook.id);
+        assert_eq!(result.hits[0].role, ChatRole::Assistant);
+        assert_eq!(result.hits[0].highlights.len(), 2);
+
+        let result = storage
+            .search_messages(&ChatSearchQuery::new("configure webhook"))
+            .unwrap();
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.hits[0].session_name, "Integrations");
+
+        let page = storage
+            .search_messages(&ChatSearchQuery::new("webhook").paginate(1, 1))
+            .unwrap();
+        assert_eq!(page.hits.len(), 1);
+        assert!(page.has_more);
+
+        let scoped = storage
+            .search_messages(&ChatSearchQuery::new("webhook").with_agent("agent-2"))
+            .unwrap();
+        assert_eq!(scoped.total_count, 1);
+
+        storage.archive(&other.id).unwrap();
+        let mut query = ChatSearchQuery::new("webhook");
+        assert_eq!(storage.search_messages(&query).unwrap().total_count, 2);
+        query.include_archived = true;
+        assert_eq!(storage.search_messages(&query).unwrap().total_count, 3);
+    }
+
+    #[test]
+    fn test_search_index_follows_session_writes() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let backend = Arc::new(RedbBackend::new(db));
+        let index = Arc::new(ChatMessageIndex::in_memory().unwrap());
+        let storage = ChatSessionStorage::with_index(backend, Some(index.clone())).unwrap();
+
+        let mut session = ChatSession::new("agent-1".to_string(), "claude-sonnet-4".to_string());
+        session.add_message(ChatMessage::user("Deploy the billing service"));
+        storage.create(&session).unwrap();
+        session.add_message(ChatMessage::assistant("Deployment finished"));
+        storage.save(&session).unwrap();
+        assert_eq!(index.doc_count().unwrap(), 2);
+        let result = storage
+            .search_messages(&ChatSearchQuery::new("deploy"))
+            .unwrap();
+        assert_eq!(result.total_count, 2);
+
+        // Editing an earlier message replaces the session's documents.
+        session.messages[0].content = "Restart the billing service".to_string();
+        storage.update(&session).unwrap();
+        assert_eq!(index.doc_count().unwrap(), 2);
+        let result = storage
+            .search_messages(&ChatSearchQuery::new("deploy"))
+            .unwrap();
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.hits[0].message_id, session.messages[1].id);
+
+        storage.archive(&session.id).unwrap();
+        let result = storage
+            .search_messages(&ChatSearchQuery::new("billing"))
+            .unwrap();
+        assert_eq!(result.total_count, 0);
+
+        storage.delete(&session.id).unwrap();
+        assert_eq!(index.doc_count().unwrap(), 0);
+    }
+
     #[test]
     fn test_cleanup_expired_deletes_old_only() {
         let (storage, _temp_dir) = setup();
@@ -5,9 +5,10 @@
 
 use anyhow::Result;
 use redb::Database;
+use restflow_storage::{RedbBackend, StorageBackend};
 use std::sync::Arc;
 
-use crate::models::AgentCheckpoint;
+use crate::models::{AgentCheckpoint, ReplaySnapshot};
 
 /// Typed checkpoint storage wrapper.
 #[derive(Clone)]
@@ -18,8 +19,13 @@ pub struct CheckpointStorage {
 impl CheckpointStorage {
     /// Create a new typed checkpoint storage.
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create a typed checkpoint storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::CheckpointStorage::new(db)?,
+            inner: restflow_storage::CheckpointStorage::with_backend(backend)?,
         })
     }
 
@@ -35,7 +41,9 @@ impl CheckpointStorage {
     }
 
     /// Save a checkpoint and attach a persistent redb savepoint ID.
-    pub fn save_with_savepoint(&self, checkpoint: &AgentCheckpoint) -> Result<u64> {
+    ///
+    /// Returns `None` when the storage backend does not support savepoints.
+    pub fn save_with_savepoint(&self, checkpoint: &AgentCheckpoint) -> Result<Option<u64>> {
         let data = serde_json::to_vec(checkpoint)?;
         self.inner.save_with_savepoint(
             &checkpoint.id,
@@ -90,7 +98,44 @@ impl CheckpointStorage {
         )
     }
 
-    /// Delete expired checkpoints. Returns the number deleted.
+    /// Save a replay snapshot, replacing any snapshot for the same step.
+    pub fn save_replay_snapshot(&self, snapshot: &ReplaySnapshot) -> Result<()> {
+        let data = serde_json::to_vec(snapshot)?;
+        self.inner.save_replay_step(
+            &snapshot.execution_id,
+            snapshot.run_id.as_deref(),
+            snapshot.step,
+            &data,
+        )
+    }
+
+    /// Load the replay snapshot taken at `step` of an execution.
+    pub fn load_replay_snapshot(
+        &self,
+        execution_id: &str,
+        step: u32,
+    ) -> Result<Option<ReplaySnapshot>> {
+        match self.inner.load_replay_step(execution_id, step)? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the replay snapshots of an execution ordered by step.
+    pub fn list_replay_snapshots(&self, execution_id: &str) -> Result<Vec<ReplaySnapshot>> {
+        self.inner
+            .list_replay_steps(execution_id)?
+            .iter()
+            .map(|data| Ok(serde_json::from_slice(data)?))
+            .collect()
+    }
+
+    /// List the execution IDs with replay snapshots recorded for a run.
+    pub fn list_replay_executions_by_run(&self, run_id: &str) -> Result<Vec<String>> {
+        self.inner.list_replay_executions_by_run(run_id)
+    }
+
+    /// Delete expired checkpoints and replay snapshots. Returns the number deleted.
     pub fn cleanup_expired(&self) -> Result<usize> {
         let now = chrono::Utc::now().timestamp_millis();
         self.inner.cleanup_expired(now)
@@ -0,0 +1,74 @@
+//! Key for `storage.encrypt_at_rest`.
+//!
+//! The key is read from `RESTFLOW_DB_KEY` (64 hex characters) when set, which
+//! suits headless servers. Otherwise it lives in the OS keychain under
+//! `restflow/database-key` and is generated on first use. Losing the key
+//! makes encrypted chat history and memory unreadable.
+
+use anyhow::{Context, Result, bail};
+use restflow_storage::ValueCipher;
+use std::env;
+
+pub const DB_KEY_ENV: &str = "RESTFLOW_DB_KEY";
+#[cfg(feature = "keychain")]
+const KEYCHAIN_SERVICE: &str = "restflow";
+#[cfg(feature = "keychain")]
+const KEYCHAIN_ACCOUNT: &str = "database-key";
+
+/// Load the database key, creating it in the keychain if none exists yet.
+pub fn load_database_cipher() -> Result<ValueCipher> {
+    let key = match env::var(DB_KEY_ENV) {
+        Ok(value) => decode_key(&value).with_context(|| format!("Invalid {DB_KEY_ENV}"))?,
+        Err(env::VarError::NotPresent) => load_keychain_key()?,
+        Err(err) => bail!("Failed to read {DB_KEY_ENV}: {err}"),
+    };
+    ValueCipher::new(&key)
+}
+
+fn decode_key(value: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(value.trim()).context("Key is not valid hex")?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow::anyhow!("Key must be 32 bytes, got {}", bytes.len()))
+}
+
+#[cfg(feature = "keychain")]
+fn load_keychain_key() -> Result<[u8; 32]> {
+    use rand::Rng;
+
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .context("Failed to access the OS keychain")?;
+    match entry.get_password() {
+        Ok(value) => decode_key(&value).context("Invalid database key in the OS keychain"),
+        Err(keyring::Error::NoEntry) => {
+            let mut key = [0u8; 32];
+            rand::rng().fill_bytes(&mut key);
+            entry
+                .set_password(&hex::encode(key))
+                .context("Failed to store the database key in the OS keychain")?;
+            tracing::info!("Generated database encryption key in the OS keychain");
+            Ok(key)
+        }
+        Err(err) => Err(err).context("Failed to read the database key from the OS keychain"),
+    }
+}
+
+#[cfg(not(feature = "keychain"))]
+fn load_keychain_key() -> Result<[u8; 32]> {
+    bail!(
+        "storage.encrypt_at_rest needs the OS keychain (build with the `keychain` feature) \
+         or a key in {DB_KEY_ENV}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_key_requires_32_hex_bytes() {
+        assert_eq!(decode_key(&"ab".repeat(32)).unwrap(), [0xab; 32]);
+        assert!(decode_key("abcd").is_err());
+        assert!(decode_key(&"zz".repeat(32)).is_err());
+    }
+}
@@ -3,18 +3,28 @@
 use crate::models::Deliverable;
 use anyhow::Result;
 use redb::Database;
+use restflow_storage::{RedbBackend, StorageBackend};
 use std::sync::Arc;
 
+use super::EntityRelationStorage;
+
 /// Typed deliverable storage wrapper around restflow-storage::DeliverableStorage.
 #[derive(Clone)]
 pub struct DeliverableStorage {
     inner: restflow_storage::DeliverableStorage,
+    relations: EntityRelationStorage,
 }
 
 impl DeliverableStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage with deliverables and relation edges on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::DeliverableStorage::new(db)?,
+            inner: restflow_storage::DeliverableStorage::with_backend(backend.clone())?,
+            relations: EntityRelationStorage::with_backend(backend)?,
         })
     }
 
@@ -25,7 +35,8 @@ impl DeliverableStorage {
             &deliverable.task_id,
             &deliverable.execution_id,
             &json_bytes,
-        )
+        )?;
+        self.relations.index_deliverable(deliverable)
     }
 
     pub fn get(&self, id: &str) -> Result<Option<Deliverable>> {
@@ -58,12 +69,28 @@ impl DeliverableStorage {
         Ok(items)
     }
 
+    pub fn list(&self) -> Result<Vec<Deliverable>> {
+        let mut items = self
+            .inner
+            .list_raw()?
+            .into_iter()
+            .map(|(_, bytes)| serde_json::from_slice::<Deliverable>(&bytes))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(items)
+    }
+
     pub fn delete(&self, id: &str) -> Result<bool> {
         let Some(existing) = self.get(id)? else {
             return Ok(false);
         };
-        self.inner
-            .delete(id, &existing.task_id, &existing.execution_id)
+        let deleted = self
+            .inner
+            .delete(id, &existing.task_id, &existing.execution_id)?;
+        if deleted {
+            self.relations.remove_entity(id)?;
+        }
+        Ok(deleted)
     }
 }
 
@@ -0,0 +1,252 @@
+//! Typed entity relation storage wrapper.
+//!
+//! Maintains the relationship graph between agents, chat sessions, background
+//! tasks and deliverables. Storage wrappers index an entity whenever it is
+//! written, so each entity owns the edges it records.
+
+use crate::models::{
+    BackgroundAgent, ChatSession, Deliverable, EntityKind, EntityRef, EntityRelation,
+    RelatedEntities, RelationKind,
+};
+use anyhow::Result;
+use redb::Database;
+use restflow_storage::{RedbBackend, RelationEdgeRaw, StorageBackend, time_utils};
+use std::sync::Arc;
+
+/// Typed entity relation storage wrapper around restflow-storage::EntityRelationStorage.
+#[derive(Debug, Clone)]
+pub struct EntityRelationStorage {
+    inner: restflow_storage::EntityRelationStorage,
+}
+
+impl EntityRelationStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            inner: restflow_storage::EntityRelationStorage::with_backend(backend)?,
+        })
+    }
+
+    /// Replace the edges recorded by `source`. Edges that already existed
+    /// keep their original `created_at`.
+    pub fn set_relations(
+        &self,
+        source: &EntityRef,
+        targets: &[(RelationKind, EntityRef)],
+    ) -> Result<()> {
+        let existing = self.list_outgoing(&source.id)?;
+        let now = time_utils::now_ms();
+
+        let mut edges = Vec::with_capacity(targets.len());
+        for (relation, target) in targets {
+            if target.id.trim().is_empty() {
+                continue;
+            }
+            let created_at = existing
+                .iter()
+                .find(|edge| edge.relation == *relation && edge.target == *target)
+                .map(|edge| edge.created_at)
+                .unwrap_or(now);
+            let edge = EntityRelation {
+                source: source.clone(),
+                relation: *relation,
+                target: target.clone(),
+                created_at,
+            };
+            edges.push(RelationEdgeRaw {
+                relation: relation.as_str(),
+                target: &target.id,
+                data: serde_json::to_vec(&edge)?,
+            });
+        }
+
+        self.inner.replace_outgoing_raw(&source.id, &edges)
+    }
+
+    pub fn index_chat_session(&self, session: &ChatSession) -> Result<()> {
+        self.set_relations(
+            &EntityRef::new(EntityKind::ChatSession, &session.id),
+            &[(
+                RelationKind::SpawnedBy,
+                EntityRef::new(EntityKind::Agent, &session.agent_id),
+            )],
+        )
+    }
+
+    pub fn index_task(&self, task: &BackgroundAgent) -> Result<()> {
+        let session_relation = if task.owns_chat_session {
+            RelationKind::Owns
+        } else {
+            RelationKind::BoundTo
+        };
+        let mut targets = vec![
+            (
+                RelationKind::SpawnedBy,
+                EntityRef::new(EntityKind::Agent, &task.agent_id),
+            ),
+            (
+                session_relation,
+                EntityRef::new(EntityKind::ChatSession, &task.chat_session_id),
+            ),
+        ];
+        targets.extend(task.prerequisites.iter().map(|prerequisite| {
+            (
+                RelationKind::TriggeredBy,
+                EntityRef::new(EntityKind::BackgroundTask, prerequisite),
+            )
+        }));
+
+        self.set_relations(
+            &EntityRef::new(EntityKind::BackgroundTask, &task.id),
+            &targets,
+        )
+    }
+
+    pub fn index_deliverable(&self, deliverable: &Deliverable) -> Result<()> {
+        self.set_relations(
+            &EntityRef::new(EntityKind::Deliverable, &deliverable.id),
+            &[(
+                RelationKind::ProducedBy,
+                EntityRef::new(EntityKind::BackgroundTask, &deliverable.task_id),
+            )],
+        )
+    }
+
+    /// Drop every edge touching `entity_id`. Returns the number removed.
+    pub fn remove_entity(&self, entity_id: &str) -> Result<usize> {
+        self.inner.remove_node(entity_id)
+    }
+
+    /// Typed neighborhood of `entity_id`, oldest edges first.
+    pub fn get_related(&self, entity_id: &str) -> Result<RelatedEntities> {
+        let outgoing = self.list_outgoing(entity_id)?;
+        let mut incoming = self
+            .inner
+            .list_incoming_raw(entity_id)?
+            .into_iter()
+            .map(|bytes| serde_json::from_slice::<EntityRelation>(&bytes))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        incoming.sort_by_key(|edge| edge.created_at);
+
+        let kind = outgoing
+            .first()
+            .map(|edge| edge.source.kind)
+            .or_else(|| incoming.first().map(|edge| edge.target.kind));
+
+        Ok(RelatedEntities {
+            entity_id: entity_id.to_string(),
+            kind,
+            outgoing,
+            incoming,
+        })
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        self.inner.is_empty()
+    }
+
+    fn list_outgoing(&self, entity_id: &str) -> Result<Vec<EntityRelation>> {
+        let mut edges = self
+            .inner
+            .list_outgoing_raw(entity_id)?
+            .into_iter()
+            .map(|bytes| serde_json::from_slice::<EntityRelation>(&bytes))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        edges.sort_by_key(|edge| edge.created_at);
+        Ok(edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BackgroundAgentSchedule, DeliverableType};
+    use tempfile::tempdir;
+
+    fn setup() -> (EntityRelationStorage, tempfile::TempDir) {
+        let dir = tempdir().expect("temp dir should be created");
+        let db = Arc::new(Database::create(dir.path().join("relations-core.db")).unwrap());
+        (EntityRelationStorage::new(db).unwrap(), dir)
+    }
+
+    fn deliverable(id: &str, task_id: &str) -> Deliverable {
+        Deliverable {
+            id: id.to_string(),
+            task_id: task_id.to_string(),
+            execution_id: "exec-1".to_string(),
+            deliverable_type: DeliverableType::Report,
+            title: "Report".to_string(),
+            content: String::new(),
+            file_path: None,
+            content_type: None,
+            size_bytes: 0,
+            created_at: 0,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_get_related_returns_typed_neighborhood() {
+        let (storage, _dir) = setup();
+
+        let mut task = BackgroundAgent::new(
+            "task-1".to_string(),
+            "Nightly".to_string(),
+            "agent-1".to_string(),
+            BackgroundAgentSchedule::default(),
+        );
+        task.chat_session_id = "session-1".to_string();
+        task.owns_chat_session = true;
+        task.prerequisites = vec!["task-0".to_string()];
+        storage.index_task(&task).unwrap();
+        storage
+            .index_deliverable(&deliverable("deliverable-1", "task-1"))
+            .unwrap();
+
+        let related = storage.get_related("task-1").unwrap();
+        assert_eq!(related.kind, Some(EntityKind::BackgroundTask));
+        assert_eq!(related.outgoing.len(), 3);
+        assert!(related.outgoing.iter().any(|edge| {
+            edge.relation == RelationKind::Owns && edge.target.kind == EntityKind::ChatSession
+        }));
+        assert_eq!(related.incoming.len(), 1);
+        assert_eq!(related.incoming[0].relation, RelationKind::ProducedBy);
+        assert_eq!(related.incoming[0].source.id, "deliverable-1");
+
+        let agent = storage.get_related("agent-1").unwrap();
+        assert_eq!(agent.kind, Some(EntityKind::Agent));
+        assert_eq!(agent.incoming[0].source.id, "task-1");
+    }
+
+    #[test]
+    fn test_reindex_replaces_edges_and_keeps_created_at() {
+        let (storage, _dir) = setup();
+
+        let mut task = BackgroundAgent::new(
+            "task-1".to_string(),
+            "Nightly".to_string(),
+            "agent-1".to_string(),
+            BackgroundAgentSchedule::default(),
+        );
+        storage.index_task(&task).unwrap();
+        let first = storage.get_related("task-1").unwrap().outgoing[0].clone();
+
+        storage.index_task(&task).unwrap();
+        assert_eq!(
+            storage.get_related("task-1").unwrap().outgoing[0].created_at,
+            first.created_at
+        );
+
+        task.agent_id = "agent-2".to_string();
+        storage.index_task(&task).unwrap();
+        assert!(storage.get_related("agent-1").unwrap().incoming.is_empty());
+        assert_eq!(storage.get_related("agent-2").unwrap().incoming.len(), 1);
+
+        assert_eq!(storage.remove_entity("task-1").unwrap(), 1);
+        assert!(storage.is_empty().unwrap());
+    }
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 
 use crate::models::execution_trace::{
     ExecutionTraceCategory, ExecutionTraceEvent, ExecutionTraceQuery, ExecutionTraceStats,
@@ -20,8 +20,13 @@ pub struct ExecutionTraceStorage {
 impl ExecutionTraceStorage {
     /// Create an execution trace storage with an existing database.
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage with trace events on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::ExecutionTraceStorageBackend::new(db)?,
+            inner: restflow_storage::ExecutionTraceStorageBackend::with_backend(backend)?,
         })
     }
 
@@ -33,9 +38,7 @@ impl ExecutionTraceStorage {
                 .create_with_backend(redb::backends::InMemoryBackend::new())
                 .context("Failed to create in-memory database")?,
         );
-        Ok(Self {
-            inner: restflow_storage::ExecutionTraceStorageBackend::new(db)?,
-        })
+        Self::new(db)
     }
 
     /// Store an execution trace event.
@@ -49,9 +52,10 @@ impl ExecutionTraceStorage {
         Ok(())
     }
 
-    /// Access the underlying database for related projection stores.
-    pub fn db(&self) -> Arc<Database> {
-        self.inner.db().clone()
+    /// Access the storage backend holding trace events, for related
+    /// projection stores.
+    pub fn backend(&self) -> Arc<dyn StorageBackend> {
+        self.inner.backend().clone()
     }
 
     /// Query execution trace events with filters.
@@ -155,10 +159,21 @@ impl ExecutionTraceStorage {
 
     /// Get statistics about execution trace events for a run or the entire store.
     pub fn stats(&self, run_id: Option<&str>) -> Result<ExecutionTraceStats> {
-        let raw_entries = self
-            .inner
-            .list_raw()
-            .context("Failed to list execution trace events")?;
+        self.stats_for_query(&ExecutionTraceQuery {
+            run_id: run_id.map(str::to_string),
+            ..ExecutionTraceQuery::default()
+        })
+    }
+
+    /// Get statistics about the events matching `query`.
+    ///
+    /// Pagination is ignored: every matching event is counted.
+    pub fn stats_for_query(&self, query: &ExecutionTraceQuery) -> Result<ExecutionTraceStats> {
+        let events = self.query(&ExecutionTraceQuery {
+            limit: Some(usize::MAX),
+            offset: None,
+            ..query.clone()
+        })?;
 
         let mut total_events = 0u64;
         let mut llm_call_count = 0u64;
@@ -169,55 +184,51 @@ impl ExecutionTraceStorage {
         let mut metric_sample_count = 0u64;
         let mut provider_health_count = 0u64;
         let mut log_record_count = 0u64;
+        let mut security_decision_count = 0u64;
         let mut total_tokens = 0u64;
         let mut total_cost_usd = 0.0f64;
         let mut earliest: Option<i64> = None;
         let mut latest: Option<i64> = None;
 
-        for (_, bytes) in raw_entries {
-            if let Ok(event) = serde_json::from_slice::<ExecutionTraceEvent>(&bytes) {
-                if let Some(rid) = run_id
-                    && event.run_id.as_deref() != Some(rid)
-                {
-                    continue;
-                }
+        for event in events {
+            total_events += 1;
 
-                total_events += 1;
-
-                match event.category {
-                    ExecutionTraceCategory::LlmCall => {
-                        llm_call_count += 1;
-                        if let Some(ref llm) = event.llm_call {
-                            total_tokens += llm.total_tokens.unwrap_or(0) as u64;
-                            total_cost_usd += llm.cost_usd.unwrap_or(0.0);
-                        }
-                    }
-                    ExecutionTraceCategory::ToolCall => {
-                        tool_call_count += 1;
-                    }
-                    ExecutionTraceCategory::ModelSwitch => {
-                        model_switch_count += 1;
-                    }
-                    ExecutionTraceCategory::Lifecycle => {
-                        lifecycle_count += 1;
-                    }
-                    ExecutionTraceCategory::Message => {
-                        message_count += 1;
-                    }
-                    ExecutionTraceCategory::MetricSample => {
-                        metric_sample_count += 1;
-                    }
-                    ExecutionTraceCategory::ProviderHealth => {
-                        provider_health_count += 1;
-                    }
-                    ExecutionTraceCategory::LogRecord => {
-                        log_record_count += 1;
+            match event.category {
+                ExecutionTraceCategory::LlmCall => {
+                    llm_call_count += 1;
+                    if let Some(ref llm) = event.llm_call {
+                        total_tokens += llm.total_tokens.unwrap_or(0) as u64;
+                        total_cost_usd += llm.cost_usd.unwrap_or(0.0);
                     }
                 }
-
-                earliest = Some(earliest.map_or(event.timestamp, |e| e.min(event.timestamp)));
-                latest = Some(latest.map_or(event.timestamp, |l| l.max(event.timestamp)));
+                ExecutionTraceCategory::ToolCall => {
+                    tool_call_count += 1;
+                }
+                ExecutionTraceCategory::ModelSwitch => {
+                    model_switch_count += 1;
+                }
+                ExecutionTraceCategory::Lifecycle => {
+                    lifecycle_count += 1;
+                }
+                ExecutionTraceCategory::Message => {
+                    message_count += 1;
+                }
+                ExecutionTraceCategory::MetricSample => {
+                    metric_sample_count += 1;
+                }
+                ExecutionTraceCategory::ProviderHealth => {
+                    provider_health_count += 1;
+                }
+                ExecutionTraceCategory::LogRecord => {
+                    log_record_count += 1;
+                }
+                ExecutionTraceCategory::SecurityDecision => {
+                    security_decision_count += 1;
+                }
             }
+
+            earliest = Some(earliest.map_or(event.timestamp, |e| e.min(event.timestamp)));
+            latest = Some(latest.map_or(event.timestamp, |l| l.max(event.timestamp)));
         }
 
         let time_range = match (earliest, latest) {
@@ -238,6 +249,7 @@ impl ExecutionTraceStorage {
             metric_sample_count,
             provider_health_count,
             log_record_count,
+            security_decision_count,
             total_tokens,
             total_cost_usd,
             time_range,
@@ -298,6 +310,9 @@ mod tests {
                 duration_ms: Some(1500),
                 is_reasoning: Some(false),
                 message_count: Some(10),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+                cache_savings_usd: None,
             },
         );
 
@@ -342,6 +357,9 @@ mod tests {
                     duration_ms: Some(100),
                     is_reasoning: Some(false),
                     message_count: Some(2),
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                    cache_savings_usd: None,
                 },
             ),
             &base_trace,
@@ -420,4 +438,61 @@ mod tests {
         assert_eq!(stats.llm_call_count, 1);
         assert_eq!(stats.total_events, 4);
     }
+
+    #[test]
+    fn test_stats_for_query_filters_category_and_time_range() {
+        let storage = ExecutionTraceStorage::in_memory().unwrap();
+        let decision = |target: &str, allowed: bool| {
+            execution_trace_builders::security_decision(
+                "task-1",
+                "agent-1",
+                crate::models::SecurityDecisionTrace {
+                    tool_name: "bash".to_string(),
+                    operation: "execute".to_string(),
+                    target: target.to_string(),
+                    allowed,
+                    requires_approval: false,
+                    approval_id: None,
+                    reason: None,
+                },
+            )
+        };
+
+        let mut old = decision("ls", true);
+        old.timestamp = 1_000;
+        let mut denied = decision("rm -rf /", false);
+        denied.timestamp = 2_000;
+        let mut log = execution_trace_builders::log_record(
+            "task-1",
+            "agent-1",
+            crate::models::LogRecordTrace {
+                level: "info".to_string(),
+                message: "started".to_string(),
+                fields: Vec::new(),
+            },
+        );
+        log.timestamp = 2_000;
+        for event in [&old, &denied, &log] {
+            storage.store(event).unwrap();
+        }
+
+        let stats = storage
+            .stats_for_query(&ExecutionTraceQuery {
+                category: Some(ExecutionTraceCategory::SecurityDecision),
+                from_timestamp: Some(1_500),
+                limit: Some(1),
+                offset: Some(5),
+                ..ExecutionTraceQuery::default()
+            })
+            .unwrap();
+        assert_eq!(stats.total_events, 1);
+        assert_eq!(stats.security_decision_count, 1);
+        assert_eq!(stats.log_record_count, 0);
+        let range = stats.time_range.unwrap();
+        assert_eq!((range.earliest, range.latest), (2_000, 2_000));
+
+        let all = storage.stats(None).unwrap();
+        assert_eq!(all.total_events, 3);
+        assert_eq!(all.security_decision_count, 2);
+    }
 }
@@ -3,7 +3,7 @@
 use crate::models::Hook;
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use std::sync::Arc;
 
 restflow_storage::define_simple_storage! {
@@ -19,8 +19,13 @@ pub struct HookStorage {
 
 impl HookStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: RawHookStorage::new(db)?,
+            inner: RawHookStorage::with_backend(backend)?,
         })
     }
 
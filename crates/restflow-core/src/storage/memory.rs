@@ -14,23 +14,61 @@
 
 use crate::models::memory::{
     MemoryChunk, MemorySearchQuery, MemorySearchResult, MemorySession, MemorySource, MemoryStats,
-    SearchMode, SemanticMatch, SourceTypeFilter,
+    SearchMode, SemanticMatch, SourceTypeFilter, TagCount,
 };
 use anyhow::{Result, anyhow};
 use redb::Database;
 use regex::Regex;
 use restflow_storage::{
-    IndexableChunk, MemoryIndex, PutChunkResult, VectorConfig, VectorStats, VectorStorage,
+    ChunkTagUpdate, IndexableChunk, MemoryIndex, MemorySettings, PutChunkResult, RedbBackend,
+    StorageBackend, ValueCipher, VectorConfig, VectorStats, VectorStorage,
 };
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::sync::Arc;
 
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
 /// Typed memory storage wrapper around restflow-storage::MemoryStorage.
 #[derive(Clone)]
 pub struct MemoryStorage {
     inner: restflow_storage::MemoryStorage,
     vectors: Option<Arc<VectorStorage>>,
     index: Option<Arc<MemoryIndex>>,
+    retention: MemoryRetention,
+}
+
+/// Per-scope retention limits enforced by [`MemoryStorage`].
+///
+/// A value of 0 disables the limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryRetention {
+    /// Days before chunks saved within a session expire.
+    pub session_retention_days: u32,
+    /// Chunks kept per agent; the least recently used are evicted first.
+    pub max_chunks_per_agent: u32,
+}
+
+impl MemoryRetention {
+    pub fn from_settings(settings: &MemorySettings) -> Self {
+        Self {
+            session_retention_days: settings.session_retention_days,
+            max_chunks_per_agent: settings.max_chunks_per_agent,
+        }
+    }
+
+    /// Cutoff before which session chunks are expired, if any.
+    fn session_cutoff(&self, now_ms: i64) -> Option<i64> {
+        (self.session_retention_days > 0)
+            .then(|| now_ms - i64::from(self.session_retention_days) * DAY_MS)
+    }
+
+    fn is_expired(&self, chunk: &MemoryChunk, now_ms: i64) -> bool {
+        chunk.session_id.is_some()
+            && self
+                .session_cutoff(now_ms)
+                .is_some_and(|cutoff| chunk.created_at < cutoff)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -87,21 +125,53 @@ impl MemoryStorage {
 
     /// Create a MemoryStorage instance with a custom text index
     pub fn with_index(db: Arc<Database>, index: Option<Arc<MemoryIndex>>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)), index, None)
+    }
+
+    /// Create a MemoryStorage instance on `backend`, encrypting chunks and
+    /// sessions when `cipher` is set.
+    pub fn with_backend(
+        backend: Arc<dyn StorageBackend>,
+        index: Option<Arc<MemoryIndex>>,
+        cipher: Option<ValueCipher>,
+    ) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::MemoryStorage::new(db)?,
+            inner: restflow_storage::MemoryStorage::with_backend(backend, cipher)?,
             vectors: None,
             index,
+            retention: MemoryRetention::default(),
         })
     }
 
+    /// Encrypt chunks and sessions stored before encryption was enabled.
+    pub fn seal_existing(&self) -> Result<usize> {
+        self.inner.seal_existing()
+    }
+
     /// Create a MemoryStorage instance with vector search enabled
     pub fn with_vectors(db: Arc<Database>, config: VectorConfig) -> Result<Self> {
-        let index = Some(Arc::new(MemoryIndex::in_memory()?));
-        Ok(Self {
-            inner: restflow_storage::MemoryStorage::new(db.clone())?,
-            vectors: Some(Arc::new(VectorStorage::new(db, config)?)),
-            index,
-        })
+        Self::new(db.clone())?.enable_vectors(Arc::new(RedbBackend::new(db)), config)
+    }
+
+    /// Attach vector storage on `backend` to an existing instance
+    pub fn enable_vectors(
+        mut self,
+        backend: Arc<dyn StorageBackend>,
+        config: VectorConfig,
+    ) -> Result<Self> {
+        self.vectors = Some(Arc::new(VectorStorage::with_backend(backend, config)?));
+        Ok(self)
+    }
+
+    /// Enforce per-scope retention limits
+    pub fn with_retention(mut self, retention: MemoryRetention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Retention limits in effect
+    pub fn retention(&self) -> MemoryRetention {
+        self.retention
     }
 
     /// Check if vector search is enabled
@@ -109,6 +179,33 @@ impl MemoryStorage {
         self.vectors.is_some()
     }
 
+    /// Store the embedding for an existing chunk
+    pub fn set_chunk_embedding(&self, chunk_id: &str, embedding: &[f32]) -> Result<()> {
+        let vectors = self
+            .vectors
+            .as_ref()
+            .ok_or_else(|| anyhow!("Vector search not enabled"))?;
+        vectors.add(chunk_id, embedding)
+    }
+
+    /// List an agent's active chunks that have no stored embedding yet
+    pub fn list_chunks_without_embedding(&self, agent_id: &str) -> Result<Vec<MemoryChunk>> {
+        let Some(vectors) = &self.vectors else {
+            return Ok(Vec::new());
+        };
+        let mut chunks = self.list_chunks(agent_id)?;
+        chunks.retain(|chunk| !chunk.is_archived() && !vectors.has_vector(&chunk.id));
+        Ok(chunks)
+    }
+
+    /// Get the stored embedding for a chunk
+    pub fn chunk_embedding(&self, chunk_id: &str) -> Result<Option<Vec<f32>>> {
+        match &self.vectors {
+            Some(vectors) => vectors.get(chunk_id),
+            None => Ok(None),
+        }
+    }
+
     /// Check if text index is enabled
     pub fn has_text_index(&self) -> bool {
         self.index.is_some()
@@ -141,6 +238,8 @@ impl MemoryStorage {
                     index.index_chunk(&Self::to_indexable_chunk(chunk))?;
                 }
 
+                self.evict_over_limit(&chunk.agent_id, Some(&chunk.id))?;
+
                 Ok(id)
             }
             PutChunkResult::Existing(id) => Ok(id),
@@ -175,6 +274,15 @@ impl MemoryStorage {
         Ok(result)
     }
 
+    /// List chunks across all agents, in key order.
+    pub fn list_all_chunks(&self) -> Result<Vec<MemoryChunk>> {
+        self.inner
+            .list_all_chunks_raw()?
+            .into_iter()
+            .map(|(_, bytes)| Ok(serde_json::from_slice(&bytes)?))
+            .collect()
+    }
+
     /// List all chunks for a session
     pub fn list_chunks_for_session(&self, session_id: &str) -> Result<Vec<MemoryChunk>> {
         let chunks = self.inner.list_chunks_by_session_raw(session_id)?;
@@ -188,6 +296,40 @@ impl MemoryStorage {
         Ok(result)
     }
 
+    /// Mark chunks as archived and drop them from the search indexes.
+    ///
+    /// Archived chunks stay readable by ID and in listings. Returns the number
+    /// of chunks archived; chunks that are already archived are skipped.
+    pub fn archive_chunks(&self, chunk_ids: &[String], archived_at: i64) -> Result<usize> {
+        let mut updates = Vec::new();
+        for mut chunk in self.get_chunks(chunk_ids)? {
+            if chunk.is_archived() {
+                continue;
+            }
+            chunk.archived_at = Some(archived_at);
+            updates.push(ChunkTagUpdate {
+                chunk_id: chunk.id.clone(),
+                old_tags: chunk.tags.clone(),
+                new_tags: chunk.tags.clone(),
+                data: serde_json::to_vec(&chunk)?,
+            });
+        }
+        if updates.is_empty() {
+            return Ok(0);
+        }
+
+        let archived = self.inner.update_chunk_tags_batch(&updates)?;
+        for update in &updates {
+            if let Some(vectors) = &self.vectors {
+                vectors.delete(&update.chunk_id)?;
+            }
+            if let Some(index) = &self.index {
+                index.remove_chunk(&update.chunk_id)?;
+            }
+        }
+        Ok(archived)
+    }
+
     /// Check if any chunks exist for a session without loading them all.
     pub fn has_chunks_for_session(&self, session_id: &str) -> Result<bool> {
         self.inner.has_chunks_for_session(session_id)
@@ -257,12 +399,31 @@ impl MemoryStorage {
     ///
     /// Returns the number of deleted chunks.
     pub fn cleanup_old_chunks(&self, older_than_ms: i64) -> Result<usize> {
+        self.cleanup_chunks_by_retention(Some(older_than_ms), &HashMap::new())
+    }
+
+    /// Delete chunks past their retention cutoff across all agents.
+    ///
+    /// `tag_cutoffs` maps lowercase tags to a cutoff timestamp, or `None` to
+    /// keep tagged chunks forever. A chunk matching several tags uses the
+    /// longest retention; chunks without a matching tag use `default_cutoff`.
+    ///
+    /// Returns the number of deleted chunks.
+    pub fn cleanup_chunks_by_retention(
+        &self,
+        default_cutoff: Option<i64>,
+        tag_cutoffs: &HashMap<String, Option<i64>>,
+    ) -> Result<usize> {
         let raw_chunks = self.inner.list_all_chunks_raw()?;
         let mut deleted = 0usize;
 
         for (chunk_id, bytes) in raw_chunks {
             let chunk: MemoryChunk = serde_json::from_slice(&bytes)?;
-            if chunk.created_at < older_than_ms && self.delete_chunk(&chunk_id)? {
+            let cutoff = Self::retention_cutoff_for(&chunk, default_cutoff, tag_cutoffs);
+            if let Some(cutoff) = cutoff
+                && chunk.created_at < cutoff
+                && self.delete_chunk(&chunk_id)?
+            {
                 deleted += 1;
             }
         }
@@ -270,6 +431,242 @@ impl MemoryStorage {
         Ok(deleted)
     }
 
+    fn retention_cutoff_for(
+        chunk: &MemoryChunk,
+        default_cutoff: Option<i64>,
+        tag_cutoffs: &HashMap<String, Option<i64>>,
+    ) -> Option<i64> {
+        let mut matched = chunk
+            .tags
+            .iter()
+            .filter_map(|tag| tag_cutoffs.get(&tag.to_ascii_lowercase()).copied())
+            .peekable();
+        if matched.peek().is_none() {
+            return default_cutoff;
+        }
+        // The earliest cutoff is the longest retention; `None` keeps the chunk.
+        matched.try_fold(i64::MAX, |earliest, cutoff| cutoff.map(|c| earliest.min(c)))
+    }
+
+    // ============== Retention ==============
+
+    /// Delete expired session chunks and evict chunks over the per-agent
+    /// limit, across all agents.
+    ///
+    /// Returns the number of deleted chunks.
+    pub fn enforce_retention(&self, now_ms: i64) -> Result<usize> {
+        let mut deleted = 0usize;
+        let mut agent_ids = std::collections::BTreeSet::new();
+        for chunk in self.list_all_chunks()? {
+            if self.retention.is_expired(&chunk, now_ms) && self.delete_chunk(&chunk.id)? {
+                deleted += 1;
+            } else {
+                agent_ids.insert(chunk.agent_id);
+            }
+        }
+        for agent_id in agent_ids {
+            deleted += self.evict_over_limit(&agent_id, None)?;
+        }
+        Ok(deleted)
+    }
+
+    /// Evict an agent's least recently used chunks until it is within
+    /// `max_chunks_per_agent`. `keep` is never evicted.
+    fn evict_over_limit(&self, agent_id: &str, keep: Option<&str>) -> Result<usize> {
+        let limit = self.retention.max_chunks_per_agent as usize;
+        if limit == 0 || self.inner.count_chunks_by_agent(agent_id)? as usize <= limit {
+            return Ok(0);
+        }
+
+        let chunks = self.list_chunks(agent_id)?;
+        let ids: Vec<String> = chunks.iter().map(|c| c.id.clone()).collect();
+        let accessed = self.inner.last_accessed(&ids)?;
+        let mut candidates: Vec<(i64, String)> = chunks
+            .into_iter()
+            .filter(|c| keep != Some(c.id.as_str()))
+            .map(|c| {
+                let last_used = accessed.get(&c.id).copied().unwrap_or(0).max(c.created_at);
+                (last_used, c.id)
+            })
+            .collect();
+        candidates.sort();
+
+        let excess = ids.len() - limit;
+        let mut evicted = 0usize;
+        for (_, chunk_id) in candidates.into_iter().take(excess) {
+            if self.delete_chunk(&chunk_id)? {
+                evicted += 1;
+            }
+        }
+        Ok(evicted)
+    }
+
+    /// Record that chunks were returned by a search
+    fn record_access(&self, chunk_ids: Vec<String>) -> Result<()> {
+        if self.retention.max_chunks_per_agent == 0 {
+            return Ok(());
+        }
+        self.inner
+            .touch_chunks(&chunk_ids, chrono::Utc::now().timestamp_millis())
+    }
+
+    // ============== Tag Operations ==============
+
+    /// Add tags to chunks. All changed chunks are written in one transaction.
+    ///
+    /// Returns the number of chunks whose tags changed.
+    pub fn add_tags(&self, chunk_ids: &[String], tags: &[String]) -> Result<usize> {
+        self.update_tags(chunk_ids, tags, &[])
+    }
+
+    /// Remove tags from chunks. All changed chunks are written in one
+    /// transaction.
+    ///
+    /// Returns the number of chunks whose tags changed.
+    pub fn remove_tags(&self, chunk_ids: &[String], tags: &[String]) -> Result<usize> {
+        self.update_tags(chunk_ids, &[], tags)
+    }
+
+    /// Remove `remove` and then add `add` on each chunk, writing every changed
+    /// chunk in one transaction. Fails without changes if any chunk is missing.
+    ///
+    /// Returns the number of chunks whose tags changed.
+    pub fn update_tags(
+        &self,
+        chunk_ids: &[String],
+        add: &[String],
+        remove: &[String],
+    ) -> Result<usize> {
+        let add = normalize_tags(add);
+        let remove = normalize_tags(remove);
+        let chunks = self.get_chunks(chunk_ids)?;
+        self.retag_chunks(chunks, |existing| {
+            let mut next: Vec<String> = existing
+                .iter()
+                .filter(|tag| !remove.contains(tag))
+                .cloned()
+                .collect();
+            next.extend(add.iter().cloned());
+            normalize_tags(&next)
+        })
+    }
+
+    /// Rename a tag on every chunk that carries it.
+    ///
+    /// Renaming onto an existing tag merges the two.
+    pub fn rename_tag(&self, from: &str, to: &str) -> Result<usize> {
+        self.merge_tags(&[from.to_string()], to)
+    }
+
+    /// Replace every source tag with `target` across all chunks in one
+    /// transaction, de-duplicating chunks that end up with `target` twice.
+    ///
+    /// Returns the number of chunks whose tags changed.
+    pub fn merge_tags(&self, sources: &[String], target: &str) -> Result<usize> {
+        let target = target.trim();
+        if target.is_empty() {
+            return Err(anyhow!("Target tag must not be empty"));
+        }
+        let sources: Vec<String> = normalize_tags(sources)
+            .into_iter()
+            .filter(|tag| tag != target)
+            .collect();
+
+        let mut chunks: BTreeMap<String, MemoryChunk> = BTreeMap::new();
+        for source in &sources {
+            for chunk in self.list_chunks_by_tag(source)? {
+                chunks.entry(chunk.id.clone()).or_insert(chunk);
+            }
+        }
+
+        self.retag_chunks(chunks.into_values().collect(), |existing| {
+            let replaced: Vec<String> = existing
+                .iter()
+                .map(|tag| {
+                    if sources.contains(tag) {
+                        target.to_string()
+                    } else {
+                        tag.clone()
+                    }
+                })
+                .collect();
+            normalize_tags(&replaced)
+        })
+    }
+
+    /// List tags with chunk counts, most used first.
+    ///
+    /// When `agent_id` is given only that agent's chunks are counted.
+    pub fn list_tags(&self, agent_id: Option<&str>) -> Result<Vec<TagCount>> {
+        let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+        match agent_id {
+            Some(agent_id) => {
+                for chunk in self.list_chunks(agent_id)? {
+                    for tag in chunk.tags {
+                        *counts.entry(tag).or_default() += 1;
+                    }
+                }
+            }
+            None => {
+                for (tag, count) in self.inner.list_tag_counts_raw()? {
+                    counts.insert(tag, count as u32);
+                }
+            }
+        }
+
+        let mut tags: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag, count })
+            .collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        Ok(tags)
+    }
+
+    fn get_chunks(&self, chunk_ids: &[String]) -> Result<Vec<MemoryChunk>> {
+        let mut chunks = Vec::with_capacity(chunk_ids.len());
+        for chunk_id in chunk_ids {
+            match self.get_chunk(chunk_id)? {
+                Some(chunk) => chunks.push(chunk),
+                None => return Err(anyhow!("Memory chunk not found: {}", chunk_id)),
+            }
+        }
+        Ok(chunks)
+    }
+
+    fn retag_chunks(
+        &self,
+        chunks: Vec<MemoryChunk>,
+        retag: impl Fn(&[String]) -> Vec<String>,
+    ) -> Result<usize> {
+        let mut updates = Vec::new();
+        let mut changed = Vec::new();
+        for mut chunk in chunks {
+            let new_tags = retag(&chunk.tags);
+            if new_tags == chunk.tags {
+                continue;
+            }
+            let old_tags = std::mem::replace(&mut chunk.tags, new_tags);
+            updates.push(ChunkTagUpdate {
+                chunk_id: chunk.id.clone(),
+                old_tags,
+                new_tags: chunk.tags.clone(),
+                data: serde_json::to_vec(&chunk)?,
+            });
+            changed.push(chunk);
+        }
+        if updates.is_empty() {
+            return Ok(0);
+        }
+
+        let updated = self.inner.update_chunk_tags_batch(&updates)?;
+        if let Some(index) = &self.index {
+            for chunk in &changed {
+                index.index_chunk(&Self::to_indexable_chunk(chunk))?;
+            }
+        }
+        Ok(updated)
+    }
+
     // ============== Session Operations ==============
 
     fn put_session_with_index_cleanup(&self, session: &MemorySession) -> Result<()> {
@@ -360,8 +757,14 @@ impl MemoryStorage {
 
     /// Search memory chunks based on a query
     pub fn search(&self, query: &MemorySearchQuery) -> Result<MemorySearchResult> {
+        let result = self.search_chunks(query)?;
+        self.record_access(result.chunks.iter().map(|c| c.id.clone()).collect())?;
+        Ok(result)
+    }
+
+    fn search_chunks(&self, query: &MemorySearchQuery) -> Result<MemorySearchResult> {
         if let (Some(index), Some(search_text)) = (&self.index, &query.query)
-            && query.search_mode == SearchMode::Keyword
+            && (query.search_mode == SearchMode::Keyword || query.search_mode.uses_embeddings())
         {
             return self.search_indexed(index, query, search_text);
         }
@@ -403,7 +806,9 @@ impl MemoryStorage {
         let mut docs = Vec::with_capacity(chunks.len());
         for (_, bytes) in chunks {
             let chunk: MemoryChunk = serde_json::from_slice(&bytes)?;
-            docs.push(Self::to_indexable_chunk(&chunk));
+            if !chunk.is_archived() {
+                docs.push(Self::to_indexable_chunk(&chunk));
+            }
         }
 
         index.rebuild(docs)
@@ -422,21 +827,38 @@ impl MemoryStorage {
         self.rebuild_text_index()
     }
 
-    /// Semantic vector search
+    /// Semantic vector search over an agent's active chunks
     pub fn semantic_search(
         &self,
         agent_id: &str,
         query_embedding: &[f32],
         top_k: usize,
+    ) -> Result<Vec<SemanticMatch>> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let chunk_ids: Vec<_> = self
+            .list_chunks(agent_id)?
+            .into_iter()
+            .filter(|c| !c.is_archived() && !self.retention.is_expired(c, now_ms))
+            .map(|c| c.id)
+            .collect();
+        let matches = self.semantic_search_among(query_embedding, top_k, &chunk_ids)?;
+        self.record_access(matches.iter().map(|m| m.chunk.id.clone()).collect())?;
+        Ok(matches)
+    }
+
+    /// Semantic vector search restricted to `chunk_ids`
+    pub fn semantic_search_among(
+        &self,
+        query_embedding: &[f32],
+        top_k: usize,
+        chunk_ids: &[String],
     ) -> Result<Vec<SemanticMatch>> {
         let vectors = self
             .vectors
             .as_ref()
             .ok_or_else(|| anyhow!("Vector search not enabled"))?;
 
-        let agent_chunks = self.list_chunks(agent_id)?;
-        let chunk_ids: Vec<_> = agent_chunks.iter().map(|c| c.id.clone()).collect();
-        let results = vectors.search_filtered(query_embedding, top_k, 100, &chunk_ids)?;
+        let results = vectors.search_filtered(query_embedding, top_k, 100, chunk_ids)?;
 
         let mut matches = Vec::new();
         for (chunk_id, distance) in results {
@@ -452,7 +874,11 @@ impl MemoryStorage {
         Ok(matches)
     }
 
-    /// Hybrid semantic + keyword search using reciprocal rank fusion
+    /// Hybrid semantic + keyword search using reciprocal rank fusion.
+    ///
+    /// Each result scores `weight / (rrf_k + rank)` per ranking it appears in,
+    /// with `semantic_weight` going to the vector ranking and the remainder to
+    /// the keyword ranking.
     pub fn hybrid_search(
         &self,
         agent_id: &str,
@@ -460,6 +886,7 @@ impl MemoryStorage {
         query_text: &str,
         top_k: usize,
         semantic_weight: f32,
+        rrf_k: f32,
     ) -> Result<Vec<SemanticMatch>> {
         use std::collections::HashMap;
 
@@ -470,7 +897,7 @@ impl MemoryStorage {
         let text_results = self.search(&text_query)?;
 
         let mut scores: HashMap<String, f32> = HashMap::new();
-        let k = 60.0;
+        let k = rrf_k;
 
         for (i, m) in semantic.iter().enumerate() {
             let rrf = semantic_weight / (k + i as f32 + 1.0);
@@ -507,6 +934,13 @@ impl MemoryStorage {
     ) -> Result<Vec<MemoryChunk>> {
         let mut filtered = chunks;
 
+        // Archived chunks are superseded by their consolidated summary
+        filtered.retain(|c| !c.is_archived());
+
+        // Expired session chunks are hidden until cleanup deletes them
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        filtered.retain(|c| !self.retention.is_expired(c, now_ms));
+
         // Filter by session
         if let Some(ref session_id) = query.session_id {
             filtered.retain(|c| c.session_id.as_ref() == Some(session_id));
@@ -541,7 +975,7 @@ impl MemoryStorage {
         mode: &SearchMode,
     ) -> Result<Vec<MemoryChunk>> {
         match mode {
-            SearchMode::Keyword => {
+            SearchMode::Keyword | SearchMode::Semantic | SearchMode::Hybrid => {
                 // Case-insensitive keyword search (all keywords must be present)
                 let search_lower = search_text.to_lowercase();
                 let keywords: Vec<&str> = search_lower.split_whitespace().collect();
@@ -719,6 +1153,11 @@ impl MemoryStorage {
 
         let oldest_memory = chunks.iter().map(|c| c.created_at).min();
         let newest_memory = chunks.iter().map(|c| c.created_at).max();
+        let archived_count = chunks.iter().filter(|c| c.is_archived()).count() as u32;
+        let consolidated_count = chunks
+            .iter()
+            .filter(|c| !c.consolidated_from.is_empty())
+            .count() as u32;
 
         Ok(MemoryStats {
             agent_id: agent_id.to_string(),
@@ -727,6 +1166,10 @@ impl MemoryStorage {
             total_tokens,
             oldest_memory,
             newest_memory,
+            archived_count,
+            consolidated_count,
+            session_retention_days: self.retention.session_retention_days,
+            max_chunks: self.retention.max_chunks_per_agent,
         })
     }
 
@@ -780,6 +1223,18 @@ impl MemoryStorage {
     }
 }
 
+/// Trim tags, drop empty ones and remove duplicates while keeping order.
+pub(crate) fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
 /// Check if a MemorySource matches a SourceTypeFilter
 fn matches_source_type(source: &MemorySource, filter: &SourceTypeFilter) -> bool {
     matches!(
@@ -1424,6 +1879,30 @@ mod tests {
         assert_eq!(other_results.total_count, 1);
     }
 
+    #[test]
+    fn test_hybrid_search_weights_rankings() {
+        let storage = create_test_storage_with_vectors(3);
+        let keyword_match =
+            MemoryChunk::new("agent-001".to_string(), "Alpha release notes".to_string())
+                .with_embedding(vec![1.0, 0.0, 0.0], "test-model".to_string());
+        let vector_match =
+            MemoryChunk::new("agent-001".to_string(), "Beta rollout plan".to_string())
+                .with_embedding(vec![0.0, 1.0, 0.0], "test-model".to_string());
+        storage.store_chunk(&keyword_match).unwrap();
+        storage.store_chunk(&vector_match).unwrap();
+
+        let query_embedding = [0.0, 1.0, 0.0];
+        let vector_first = storage
+            .hybrid_search("agent-001", &query_embedding, "alpha", 5, 1.0, 60.0)
+            .unwrap();
+        assert_eq!(vector_first[0].chunk.id, vector_match.id);
+
+        let keyword_first = storage
+            .hybrid_search("agent-001", &query_embedding, "alpha", 5, 0.0, 60.0)
+            .unwrap();
+        assert_eq!(keyword_first[0].chunk.id, keyword_match.id);
+    }
+
     #[test]
     fn test_delete_session_with_chunks_cleans_searchable_indexes() {
         let storage = create_test_storage_with_vectors(3);
@@ -1631,6 +2110,192 @@ mod tests {
         assert!(storage.get_chunk(&recent_chunk.id).unwrap().is_some());
     }
 
+    #[test]
+    fn test_session_chunks_expire_by_retention() {
+        let storage = create_test_storage().with_retention(MemoryRetention {
+            session_retention_days: 7,
+            max_chunks_per_agent: 0,
+        });
+        let now = chrono::Utc::now().timestamp_millis();
+        let day = 24 * 60 * 60 * 1000;
+
+        let old_session = MemoryChunk::new("agent-1".to_string(), "old session".to_string())
+            .with_session("session-1".to_string())
+            .with_created_at(now - 10 * day);
+        let recent_session = MemoryChunk::new("agent-1".to_string(), "recent session".to_string())
+            .with_session("session-1".to_string())
+            .with_created_at(now - day);
+        let old_agent = MemoryChunk::new("agent-1".to_string(), "old agent note".to_string())
+            .with_created_at(now - 10 * day);
+        for chunk in [&old_session, &recent_session, &old_agent] {
+            storage.store_chunk(chunk).unwrap();
+        }
+
+        let query = MemorySearchQuery::new("agent-1".to_string());
+        let visible: Vec<_> = storage
+            .search(&query)
+            .unwrap()
+            .chunks
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(visible.len(), 2);
+        assert!(!visible.contains(&old_session.id));
+
+        assert_eq!(storage.enforce_retention(now).unwrap(), 1);
+        assert!(storage.get_chunk(&old_session.id).unwrap().is_none());
+        assert!(storage.get_chunk(&old_agent.id).unwrap().is_some());
+
+        let stats = storage.get_stats("agent-1").unwrap();
+        assert_eq!(stats.chunk_count, 2);
+        assert_eq!(stats.session_retention_days, 7);
+        assert_eq!(stats.max_chunks, 0);
+    }
+
+    #[test]
+    fn test_agent_chunk_limit_evicts_least_recently_used() {
+        let storage = create_test_storage().with_retention(MemoryRetention {
+            session_retention_days: 0,
+            max_chunks_per_agent: 2,
+        });
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let first = MemoryChunk::new("agent-1".to_string(), "alpha fact".to_string())
+            .with_created_at(now - 3000);
+        let second = MemoryChunk::new("agent-1".to_string(), "beta fact".to_string())
+            .with_created_at(now - 2000);
+        storage.store_chunk(&first).unwrap();
+        storage.store_chunk(&second).unwrap();
+
+        // Reading the older chunk makes the newer one least recently used.
+        let query = MemorySearchQuery::new("agent-1".to_string()).with_query("alpha".to_string());
+        assert_eq!(storage.search(&query).unwrap().chunks.len(), 1);
+
+        let third = MemoryChunk::new("agent-1".to_string(), "gamma fact".to_string());
+        storage.store_chunk(&third).unwrap();
+
+        assert!(storage.get_chunk(&first.id).unwrap().is_some());
+        assert!(storage.get_chunk(&second.id).unwrap().is_none());
+        assert!(storage.get_chunk(&third.id).unwrap().is_some());
+        assert_eq!(storage.get_stats("agent-1").unwrap().max_chunks, 2);
+    }
+
+    #[test]
+    fn test_cleanup_chunks_by_retention_applies_tag_rules() {
+        let storage = create_test_storage();
+        let now = chrono::Utc::now().timestamp_millis();
+        let day = 24 * 60 * 60 * 1000;
+
+        let scratch = MemoryChunk::new("agent-1".to_string(), "scratch".to_string())
+            .with_created_at(now - 10 * day)
+            .with_tags(vec!["Scratch".to_string()]);
+        let pinned = MemoryChunk::new("agent-1".to_string(), "pinned".to_string())
+            .with_created_at(now - 400 * day)
+            .with_tags(vec!["scratch".to_string(), "pinned".to_string()]);
+        let untagged = MemoryChunk::new("agent-1".to_string(), "untagged".to_string())
+            .with_created_at(now - 120 * day);
+        let recent = MemoryChunk::new("agent-1".to_string(), "recent".to_string())
+            .with_created_at(now - 2 * day);
+
+        for chunk in [&scratch, &pinned, &untagged, &recent] {
+            storage.store_chunk(chunk).unwrap();
+        }
+
+        let tag_cutoffs = HashMap::from([
+            ("scratch".to_string(), Some(now - 7 * day)),
+            ("pinned".to_string(), None),
+        ]);
+        let deleted = storage
+            .cleanup_chunks_by_retention(Some(now - 90 * day), &tag_cutoffs)
+            .unwrap();
+
+        assert_eq!(deleted, 2);
+        assert!(storage.get_chunk(&scratch.id).unwrap().is_none());
+        assert!(storage.get_chunk(&untagged.id).unwrap().is_none());
+        assert!(storage.get_chunk(&pinned.id).unwrap().is_some());
+        assert!(storage.get_chunk(&recent.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_add_and_remove_tags_in_bulk() {
+        let storage = create_test_storage();
+
+        let first = MemoryChunk::new("agent-1".to_string(), "first".to_string())
+            .with_tags(vec!["rust".to_string()]);
+        let second = MemoryChunk::new("agent-1".to_string(), "second".to_string());
+        storage.store_chunk(&first).unwrap();
+        storage.store_chunk(&second).unwrap();
+
+        let ids = vec![first.id.clone(), second.id.clone()];
+        let changed = storage
+            .add_tags(&ids, &["rust".to_string(), " review ".to_string()])
+            .unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            storage.get_chunk(&first.id).unwrap().unwrap().tags,
+            vec!["rust".to_string(), "review".to_string()]
+        );
+        assert_eq!(storage.list_chunks_by_tag("review").unwrap().len(), 2);
+
+        let changed = storage.remove_tags(&ids, &["rust".to_string()]).unwrap();
+        assert_eq!(changed, 2);
+        assert!(storage.list_chunks_by_tag("rust").unwrap().is_empty());
+
+        assert!(
+            storage
+                .add_tags(&["chunk-missing".to_string()], &["x".to_string()])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rename_and_merge_tags() {
+        let storage = create_test_storage();
+
+        let a = MemoryChunk::new("agent-1".to_string(), "a".to_string())
+            .with_tags(vec!["todo".to_string(), "task".to_string()]);
+        let b = MemoryChunk::new("agent-1".to_string(), "b".to_string())
+            .with_tags(vec!["todos".to_string()]);
+        let c = MemoryChunk::new("agent-2".to_string(), "c".to_string())
+            .with_tags(vec!["bug".to_string()]);
+        for chunk in [&a, &b, &c] {
+            storage.store_chunk(chunk).unwrap();
+        }
+
+        let changed = storage
+            .merge_tags(&["todo".to_string(), "todos".to_string()], "task")
+            .unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            storage.get_chunk(&a.id).unwrap().unwrap().tags,
+            vec!["task".to_string()]
+        );
+        assert!(storage.list_chunks_by_tag("todo").unwrap().is_empty());
+        assert!(storage.list_chunks_by_tag("todos").unwrap().is_empty());
+
+        assert_eq!(storage.rename_tag("bug", "defect").unwrap(), 1);
+        assert_eq!(
+            storage.list_tags(None).unwrap(),
+            vec![
+                TagCount {
+                    tag: "task".to_string(),
+                    count: 2,
+                },
+                TagCount {
+                    tag: "defect".to_string(),
+                    count: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            storage.list_tags(Some("agent-2")).unwrap(),
+            vec![TagCount {
+                tag: "defect".to_string(),
+                count: 1,
+            }]
+        );
+    }
+
     #[test]
     fn test_search_time_range() {
         let storage = create_test_storage();
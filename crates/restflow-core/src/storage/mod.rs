@@ -4,12 +4,18 @@
 //! the byte-level APIs from restflow-storage with Rust types from our models.
 
 pub mod agent;
+pub mod api_connector;
+pub mod api_token;
 pub mod audit;
 pub mod background_agent;
+pub mod browser_plan;
 pub mod channel_session_binding;
+mod chat_search;
 pub mod chat_session;
 pub mod checkpoint;
+pub mod db_key;
 pub mod deliverable;
+pub mod entity_relation;
 pub mod execution_trace;
 pub mod hook;
 pub mod kv_store;
@@ -20,12 +26,17 @@ pub mod skill;
 pub mod structured_execution_log;
 pub mod telemetry_metric_sample;
 pub mod terminal_session;
+pub mod tool_approval;
 pub mod trigger;
+pub mod usage;
 pub mod work_item;
+pub mod workspace;
 
 use anyhow::Result;
-use redb::Database;
-use restflow_storage::MemoryIndex;
+use restflow_storage::{
+    AT_REST_SEALED_STATE_KEY, BackupSummary, ChatMessageIndex, EncryptedBackend, MemoryIndex,
+    StorageBackend, StorageSettings,
+};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -34,30 +45,37 @@ use crate::models::{ChannelSessionBinding, ChatSessionSource};
 // Re-export types that are self-contained in restflow-storage
 pub use restflow_storage::{
     AgentDefaults, AgentSettings, ApiDefaults, ApiSettings, ChannelDefaults, ChannelSettings,
-    CliConfig, ConfigStorage, DaemonStateStorage, PairingStorage, RegistryDefaults,
-    RegistrySettings, RuntimeDefaults, RuntimeSettings, Secret, SecretStorage, SecretStorageConfig,
-    SystemConfig,
+    CliConfig, ConfigStorage, DaemonStateStorage, MemoryDefaults, MemorySettings, PairingStorage,
+    RegistryDefaults, RegistrySettings, RuntimeDefaults, RuntimeSettings, Secret, SecretAccess,
+    SecretStorage, SecretStorageConfig, SystemConfig, TagRetentionRule, WebhookSinkConfig,
 };
 
 pub use agent::AgentStorage;
+pub use api_connector::ApiConnectorStorage;
+pub use api_token::ApiTokenStorage;
 pub use audit::AuditStorage;
 pub use background_agent::BackgroundAgentStorage;
+pub use browser_plan::BrowserPlanStorage;
 pub use channel_session_binding::ChannelSessionBindingStorage;
 pub use chat_session::ChatSessionStorage;
 pub use checkpoint::CheckpointStorage;
 pub use deliverable::DeliverableStorage;
+pub use entity_relation::EntityRelationStorage;
 pub use execution_trace::ExecutionTraceStorage;
 pub use hook::HookStorage;
 pub use kv_store::KvStoreStorage;
-pub use memory::MemoryStorage;
+pub use memory::{MemoryRetention, MemoryStorage};
 pub use provider_health_snapshot::ProviderHealthSnapshotStorage;
 pub use session::SessionStorage;
 pub use skill::SkillStorage;
 pub use structured_execution_log::StructuredExecutionLogStorage;
 pub use telemetry_metric_sample::TelemetryMetricSampleStorage;
 pub use terminal_session::TerminalSessionStorage;
+pub use tool_approval::ToolApprovalStorage;
 pub use trigger::TriggerStorage;
+pub use usage::UsageStorage;
 pub use work_item::WorkItemStorage;
+pub use workspace::WorkspaceStorage;
 
 /// Central storage manager that initializes all storage subsystems.
 ///
@@ -65,6 +83,9 @@ pub use work_item::WorkItemStorage;
 /// that convert between Rust models and byte-level storage.
 pub struct Storage {
     db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
+    /// Backend as stored on disk; differs from `backend` when encrypted.
+    raw_backend: Arc<dyn StorageBackend>,
     pub config: ConfigStorage,
     pub triggers: TriggerStorage,
     pub agents: AgentStorage,
@@ -79,10 +100,19 @@ pub struct Storage {
     pub channel_session_bindings: ChannelSessionBindingStorage,
     pub sessions: SessionStorage,
     pub deliverables: DeliverableStorage,
+    /// Saved, versioned browser action plans.
+    pub browser_plans: BrowserPlanStorage,
+    /// REST API connectors imported from OpenAPI documents.
+    pub api_connectors: ApiConnectorStorage,
+    /// Relationship graph between agents, sessions, tasks and deliverables.
+    pub entity_relations: EntityRelationStorage,
     pub hooks: HookStorage,
     pub work_items: WorkItemStorage,
     pub checkpoints: CheckpointStorage,
     pub pairing: PairingStorage,
+    pub api_tokens: ApiTokenStorage,
+    /// Tool approval policy and the queue of tool calls awaiting approval.
+    pub tool_approvals: ToolApprovalStorage,
     /// Primary execution trace storage.
     pub execution_traces: ExecutionTraceStorage,
     /// Telemetry metric sample projection storage.
@@ -91,6 +121,10 @@ pub struct Storage {
     pub provider_health_snapshots: ProviderHealthSnapshotStorage,
     /// Structured execution log projection storage.
     pub structured_execution_logs: StructuredExecutionLogStorage,
+    /// Per-call LLM token usage and cost projection storage.
+    pub usage: UsageStorage,
+    /// Users and the agents and skills their workspaces own.
+    pub workspaces: WorkspaceStorage,
     /// Backward-compatible alias storage.
     pub audit: AuditStorage,
 }
@@ -103,35 +137,113 @@ impl Storage {
     }
 
     /// Create a new storage instance with custom secret storage configuration.
+    ///
+    /// The storage backend comes from the `[storage]` section of config.toml.
     pub fn with_secret_config(path: &str, secret_config: SecretStorageConfig) -> Result<Self> {
-        let db = Arc::new(Database::create(path)?);
-
-        let config = ConfigStorage::new(db.clone())?;
-        let triggers = TriggerStorage::new(db.clone())?;
-        let agents = AgentStorage::new(db.clone())?;
-        let background_agents = BackgroundAgentStorage::new(db.clone())?;
-        let secrets = SecretStorage::with_config(db.clone(), secret_config)?;
-        let daemon_state = DaemonStateStorage::new(db.clone())?;
-        let skills = SkillStorage::new(db.clone())?;
-        let kv_store_raw = restflow_storage::KvStoreStorage::new(db.clone())?;
-        let kv_store = KvStoreStorage::new(kv_store_raw);
-        let terminal_sessions = TerminalSessionStorage::new(db.clone())?;
-        let index = if path == ":memory:" {
-            Some(Arc::new(MemoryIndex::in_memory()?))
+        let settings = restflow_storage::load_storage_settings()?;
+        Self::with_settings(path, secret_config, &settings)
+    }
+
+    /// Create a new storage instance on the backend selected by `settings`.
+    ///
+    /// Every store lives in the selected backend. With SQLite, the redb
+    /// database at `path` is only read once, to copy its tables over. Secret
+    /// values follow `settings.secrets`.
+    pub fn with_settings(
+        path: &str,
+        secret_config: SecretStorageConfig,
+        settings: &StorageSettings,
+    ) -> Result<Self> {
+        let secret_config = SecretStorageConfig {
+            mode: settings.secrets,
+            ..secret_config
+        };
+        let raw_backend = restflow_storage::open_backend(settings, Path::new(path))?;
+        let cipher = if settings.encrypt_at_rest {
+            Some(db_key::load_database_cipher()?)
         } else {
+            None
+        };
+        // Sealing existing plaintext scans every value, so it runs once per
+        // switch to encryption. Turning encryption off resets the marker so
+        // plaintext written meanwhile is sealed when it is turned back on.
+        let daemon_state = DaemonStateStorage::with_backend(raw_backend.clone())?;
+        let sealed_at = daemon_state.get_i64(AT_REST_SEALED_STATE_KEY)?;
+        let seal_pending = cipher.is_some() && sealed_at == 0;
+        if cipher.is_none() && sealed_at != 0 {
+            daemon_state.set_i64(AT_REST_SEALED_STATE_KEY, 0)?;
+        }
+        let backend: Arc<dyn StorageBackend> = match &cipher {
+            Some(cipher) => {
+                let encrypted = EncryptedBackend::new(raw_backend.clone(), cipher.clone());
+                if seal_pending {
+                    encrypted.seal_existing()?;
+                }
+                Arc::new(encrypted)
+            }
+            None => raw_backend.clone(),
+        };
+
+        let config = ConfigStorage::default();
+        let triggers = TriggerStorage::with_backend(backend.clone())?;
+        let agents = AgentStorage::with_backend(backend.clone())?;
+        let secrets = SecretStorage::with_backend(backend.clone(), secret_config)?;
+        let skills = SkillStorage::with_backend(backend.clone())?;
+        let kv_store_raw = restflow_storage::KvStoreStorage::with_backend(backend.clone())?;
+        let kv_store = KvStoreStorage::new(kv_store_raw);
+        let terminal_sessions = TerminalSessionStorage::with_backend(backend.clone())?;
+        let index_dir = |suffix: &str| -> Result<Option<std::path::PathBuf>> {
+            if path == ":memory:" {
+                return Ok(None);
+            }
             let db_path = Path::new(path);
             let parent = db_path.parent().unwrap_or_else(|| Path::new("."));
             let stem = db_path
                 .file_stem()
                 .and_then(|v| v.to_str())
                 .unwrap_or("restflow");
-            let index_path = parent.join(format!("{stem}.memory-index"));
-            Some(Arc::new(MemoryIndex::open(&index_path)?))
+            let index_path = parent.join(format!("{stem}.{suffix}"));
+            if cipher.is_some() {
+                // On-disk text indexes hold plaintext, so drop any left from
+                // before encryption and keep the index in memory.
+                if index_path.exists() {
+                    std::fs::remove_dir_all(&index_path)?;
+                }
+                return Ok(None);
+            }
+            Ok(Some(index_path))
+        };
+        let index = match index_dir("memory-index")? {
+            Some(index_path) => Some(Arc::new(MemoryIndex::open(&index_path)?)),
+            None => Some(Arc::new(MemoryIndex::in_memory()?)),
+        };
+        let chat_index = match index_dir("chat-index")? {
+            Some(index_path) => Arc::new(ChatMessageIndex::open(&index_path)?),
+            None => Arc::new(ChatMessageIndex::in_memory()?),
         };
-        let memory = MemoryStorage::with_index(db.clone(), index)?;
+        let memory = MemoryStorage::with_backend(backend.clone(), index, cipher)?;
+        if seal_pending {
+            memory.seal_existing()?;
+            daemon_state.set_i64(
+                AT_REST_SEALED_STATE_KEY,
+                chrono::Utc::now().timestamp_millis(),
+            )?;
+        }
+        let memory_settings = config
+            .get_effective_config()
+            .map(|config| config.memory_defaults)
+            .unwrap_or_default();
+        let memory = match crate::memory::embedding::vector_config(&memory_settings) {
+            Some(vector_config) => memory.enable_vectors(backend.clone(), vector_config)?,
+            None => memory,
+        }
+        .with_retention(MemoryRetention::from_settings(&memory_settings));
         memory.rebuild_text_index_if_empty()?;
-        let chat_sessions = ChatSessionStorage::new(db.clone())?;
-        let channel_session_bindings = ChannelSessionBindingStorage::new(db.clone())?;
+        let chat_sessions = ChatSessionStorage::with_index(backend.clone(), Some(chat_index))?;
+        chat_sessions.rebuild_search_index_if_empty()?;
+        let background_agents =
+            BackgroundAgentStorage::with_chat_sessions(backend.clone(), chat_sessions.clone())?;
+        let channel_session_bindings = ChannelSessionBindingStorage::with_backend(backend.clone())?;
         backfill_channel_session_bindings_from_legacy_sources(
             &chat_sessions,
             &channel_session_bindings,
@@ -139,21 +251,37 @@ impl Storage {
         let sessions = SessionStorage::new(
             chat_sessions.clone(),
             channel_session_bindings.clone(),
-            ExecutionTraceStorage::new(db.clone())?,
+            ExecutionTraceStorage::with_backend(backend.clone())?,
         );
-        let deliverables = DeliverableStorage::new(db.clone())?;
-        let hooks = HookStorage::new(db.clone())?;
-        let work_items = WorkItemStorage::new(db.clone())?;
-        let checkpoints = CheckpointStorage::new(db.clone())?;
-        let pairing = PairingStorage::new(db.clone())?;
-        let execution_traces = ExecutionTraceStorage::new(db.clone())?;
-        let telemetry_metric_samples = TelemetryMetricSampleStorage::new(db.clone())?;
-        let provider_health_snapshots = ProviderHealthSnapshotStorage::new(db.clone())?;
-        let structured_execution_logs = StructuredExecutionLogStorage::new(db.clone())?;
-        let audit = AuditStorage::new(db.clone())?;
+        let deliverables = DeliverableStorage::with_backend(backend.clone())?;
+        let entity_relations = EntityRelationStorage::with_backend(backend.clone())?;
+        backfill_entity_relations(
+            &entity_relations,
+            &chat_sessions,
+            &background_agents,
+            &deliverables,
+        )?;
+        let browser_plans = BrowserPlanStorage::with_backend(backend.clone())?;
+        let api_connectors = ApiConnectorStorage::with_backend(backend.clone())?;
+        let hooks = HookStorage::with_backend(backend.clone())?;
+        let work_items = WorkItemStorage::with_backend(backend.clone())?;
+        let checkpoints = CheckpointStorage::with_backend(backend.clone())?;
+        let pairing = PairingStorage::with_backend(backend.clone())?;
+        let api_tokens = ApiTokenStorage::with_backend(backend.clone())?;
+        let tool_approvals = ToolApprovalStorage::with_backend(backend.clone())?;
+        let execution_traces = ExecutionTraceStorage::with_backend(backend.clone())?;
+        let telemetry_metric_samples = TelemetryMetricSampleStorage::with_backend(backend.clone())?;
+        let provider_health_snapshots =
+            ProviderHealthSnapshotStorage::with_backend(backend.clone())?;
+        let structured_execution_logs =
+            StructuredExecutionLogStorage::with_backend(backend.clone())?;
+        let usage = UsageStorage::with_backend(backend.clone())?;
+        let workspaces = WorkspaceStorage::with_backend(backend.clone())?;
+        let audit = AuditStorage::with_backend(backend.clone())?;
 
         Ok(Self {
-            db,
+            backend,
+            raw_backend,
             config,
             triggers,
             agents,
@@ -168,21 +296,53 @@ impl Storage {
             channel_session_bindings,
             sessions,
             deliverables,
+            entity_relations,
+            browser_plans,
+            api_connectors,
             hooks,
             work_items,
             checkpoints,
             pairing,
+            api_tokens,
+            tool_approvals,
             execution_traces,
             telemetry_metric_samples,
             provider_health_snapshots,
             structured_execution_logs,
+            usage,
+            workspaces,
             audit,
         })
     }
 
-    /// Get a reference to the underlying database.
-    pub fn get_db(&self) -> Arc<Database> {
-        self.db.clone()
+    /// Get the backend holding every table.
+    ///
+    /// Stores opened outside `Storage` must use this, so they see the same
+    /// data when SQLite is selected.
+    pub fn backend(&self) -> Arc<dyn StorageBackend> {
+        self.backend.clone()
+    }
+
+    /// Write a consistent snapshot of all tables to a new file at `dest`.
+    ///
+    /// Safe to call while the daemon is serving requests. Values encrypted at
+    /// rest stay encrypted in the backup.
+    pub fn backup_to(&self, dest: &Path) -> Result<BackupSummary> {
+        restflow_storage::backup_to(self.raw_backend.as_ref(), dest)
+    }
+
+    /// Replace all tables with the contents of the backup at `src`.
+    ///
+    /// In-memory caches derived from the tables are refreshed afterwards. The
+    /// memory vector index is only rebuilt on startup, so semantic search
+    /// reflects the restored data after a daemon restart.
+    pub fn restore_from(&self, src: &Path) -> Result<BackupSummary> {
+        let summary = restflow_storage::restore_from(self.raw_backend.as_ref(), src)?;
+        self.agents.bump_revision();
+        self.skills.bump_revision();
+        self.memory.rebuild_text_index()?;
+        self.chat_sessions.rebuild_search_index()?;
+        Ok(summary)
     }
 }
 
@@ -198,6 +358,7 @@ fn backfill_channel_session_bindings_from_legacy_sources(
             Some(ChatSessionSource::Telegram) => Some("telegram"),
             Some(ChatSessionSource::Discord) => Some("discord"),
             Some(ChatSessionSource::Slack) => Some("slack"),
+            Some(ChatSessionSource::Matrix) => Some("matrix"),
             Some(ChatSessionSource::Workspace) | Some(ChatSessionSource::ExternalLegacy) | None => {
                 None
             }
@@ -231,10 +392,41 @@ fn backfill_channel_session_bindings_from_legacy_sources(
     Ok(created)
 }
 
+/// Index entities written before the relationship graph existed.
+///
+/// Runs only while the graph is empty, so later startups skip the scan.
+fn backfill_entity_relations(
+    entity_relations: &EntityRelationStorage,
+    chat_sessions: &ChatSessionStorage,
+    background_agents: &BackgroundAgentStorage,
+    deliverables: &DeliverableStorage,
+) -> Result<usize> {
+    if !entity_relations.is_empty()? {
+        return Ok(0);
+    }
+
+    let mut indexed = 0usize;
+    for session in chat_sessions.list_all()? {
+        entity_relations.index_chat_session(&session)?;
+        indexed += 1;
+    }
+    for task in background_agents.list_tasks()? {
+        entity_relations.index_task(&task)?;
+        indexed += 1;
+    }
+    for deliverable in deliverables.list()? {
+        entity_relations.index_deliverable(&deliverable)?;
+        indexed += 1;
+    }
+
+    Ok(indexed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::ChatSession;
+    use restflow_storage::SimpleStorage;
     use tempfile::tempdir;
 
     #[test]
@@ -268,4 +460,147 @@ mod tests {
         .unwrap();
         assert_eq!(created_again, 0);
     }
+
+    #[test]
+    fn backfill_entity_relations_runs_only_on_empty_graph() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("storage-relations.db");
+        let storage = Storage::new(db_path.to_str().unwrap()).unwrap();
+
+        let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        storage.chat_sessions.create(&session).unwrap();
+        storage.entity_relations.remove_entity(&session.id).unwrap();
+
+        let backfill = || {
+            backfill_entity_relations(
+                &storage.entity_relations,
+                &storage.chat_sessions,
+                &storage.background_agents,
+                &storage.deliverables,
+            )
+            .unwrap()
+        };
+        assert_eq!(backfill(), 1);
+        let related = storage.entity_relations.get_related("agent-1").unwrap();
+        assert_eq!(related.incoming[0].source.id, session.id);
+
+        assert_eq!(backfill(), 0);
+    }
+
+    #[test]
+    fn sqlite_backend_migrates_redb_once() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("storage-sqlite.db");
+        let sqlite_path = dir.path().join("shared.sqlite");
+        let migrated = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        {
+            let storage = Storage::new(db_path.to_str().unwrap()).unwrap();
+            storage.chat_sessions.create(&migrated).unwrap();
+        }
+
+        let settings = StorageSettings {
+            backend: restflow_storage::StorageBackendKind::Sqlite,
+            sqlite_path: Some(sqlite_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let storage = Storage::with_settings(
+            db_path.to_str().unwrap(),
+            SecretStorageConfig::default(),
+            &settings,
+        )
+        .unwrap();
+        assert!(storage.chat_sessions.exists(&migrated.id).unwrap());
+
+        let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        storage.chat_sessions.create(&session).unwrap();
+
+        let shared = restflow_storage::SqliteBackend::open(&sqlite_path).unwrap();
+        let raw = restflow_storage::ChatSessionStorage::with_backend(Arc::new(shared)).unwrap();
+        assert!(raw.exists(&session.id).unwrap());
+        // The redb file is closed again after the copy and left untouched.
+        let db = redb::Database::open(&db_path).unwrap();
+        let redb_only = restflow_storage::ChatSessionStorage::new(Arc::new(db)).unwrap();
+        assert!(redb_only.exists(&migrated.id).unwrap());
+        assert!(!redb_only.exists(&session.id).unwrap());
+    }
+
+    #[test]
+    fn restore_from_backup_rolls_back_later_writes() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("storage-backup.db");
+        let storage = Storage::new(db_path.to_str().unwrap()).unwrap();
+
+        let kept = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        storage.chat_sessions.create(&kept).unwrap();
+        let backup = dir.path().join("snapshot.redb");
+        storage.backup_to(&backup).unwrap();
+
+        let later = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        storage.chat_sessions.create(&later).unwrap();
+        let revision = storage.agents.revision();
+
+        storage.restore_from(&backup).unwrap();
+        assert!(storage.chat_sessions.exists(&kept.id).unwrap());
+        assert!(!storage.chat_sessions.exists(&later.id).unwrap());
+        assert!(storage.agents.revision() > revision);
+    }
+
+    #[test]
+    fn encrypt_at_rest_seals_existing_plaintext() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("storage-encrypted.db");
+        let path = db_path.to_str().unwrap();
+        let session = ChatSession::new("agent-1".to_string(), "gpt-5".to_string());
+        let chunk = crate::models::memory::MemoryChunk::new(
+            "agent-1".to_string(),
+            "plaintext memory".to_string(),
+        );
+        {
+            let storage = Storage::new(path).unwrap();
+            storage.chat_sessions.create(&session).unwrap();
+            storage.memory.store_chunk(&chunk).unwrap();
+        }
+
+        // SAFETY: no other test reads this variable.
+        unsafe { std::env::set_var(db_key::DB_KEY_ENV, "42".repeat(32)) };
+        let settings = StorageSettings {
+            encrypt_at_rest: true,
+            ..Default::default()
+        };
+        let storage = Storage::with_settings(path, SecretStorageConfig::default(), &settings);
+        unsafe { std::env::remove_var(db_key::DB_KEY_ENV) };
+        let storage = storage.unwrap();
+
+        assert!(storage.chat_sessions.exists(&session.id).unwrap());
+        assert!(storage.memory.get_chunk(&chunk.id).unwrap().is_some());
+        let raw = restflow_storage::ChatSessionStorage::with_backend(storage.raw_backend.clone())
+            .unwrap();
+        let stored = raw.get_raw(&session.id).unwrap().unwrap();
+        assert!(restflow_storage::ValueCipher::is_sealed(&stored));
+        let plain_memory =
+            restflow_storage::MemoryStorage::with_backend(storage.raw_backend.clone(), None)
+                .unwrap();
+        assert!(plain_memory.get_chunk_raw(&chunk.id).is_err());
+        assert!(!dir.path().join("storage-encrypted.memory-index").exists());
+        assert!(!dir.path().join("storage-encrypted.chat-index").exists());
+        assert!(
+            storage
+                .daemon_state
+                .get_i64(AT_REST_SEALED_STATE_KEY)
+                .unwrap()
+                > 0
+        );
+
+        // Opening without encryption clears the marker so a later switch
+        // seals again.
+        drop((raw, plain_memory, storage));
+        let storage = Storage::new(path).unwrap();
+        assert_eq!(
+            storage
+                .daemon_state
+                .get_i64(AT_REST_SEALED_STATE_KEY)
+                .unwrap(),
+            0
+        );
+    }
 }
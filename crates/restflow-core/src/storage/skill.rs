@@ -3,13 +3,16 @@
 use crate::models::Skill;
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Typed skill storage wrapper around restflow-storage::SkillStorage.
 #[derive(Debug, Clone)]
 pub struct SkillStorage {
     inner: restflow_storage::SkillStorage,
+    /// Bumped on every write so read caches can detect stale entries.
+    revision: Arc<AtomicU64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,11 +23,26 @@ pub enum SkillUpsertOutcome {
 
 impl SkillStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::SkillStorage::new(db)?,
+            inner: restflow_storage::SkillStorage::with_backend(backend)?,
+            revision: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Write revision shared by all clones of this storage.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn bump_revision(&self) {
+        self.revision.fetch_add(1, Ordering::AcqRel);
+    }
+
     /// Create a new skill (fails if already exists).
     ///
     /// This method uses atomic insert-if-absent to prevent TOCTOU race conditions
@@ -35,6 +53,7 @@ impl SkillStorage {
         if !inserted {
             return Err(anyhow::anyhow!("Skill {} already exists", skill.id));
         }
+        self.bump_revision();
         Ok(())
     }
 
@@ -70,12 +89,15 @@ impl SkillStorage {
             return Err(anyhow::anyhow!("Skill {} not found", id));
         }
         let json = serde_json::to_string(skill)?;
-        self.inner.put_raw(id, json.as_bytes())
+        self.inner.put_raw(id, json.as_bytes())?;
+        self.bump_revision();
+        Ok(())
     }
 
     /// Delete a skill
     pub fn delete(&self, id: &str) -> Result<()> {
         self.inner.delete(id)?;
+        self.bump_revision();
         Ok(())
     }
 
@@ -134,6 +156,31 @@ mod tests {
         assert_eq!(retrieved.description, Some("A test skill".to_string()));
     }
 
+    #[test]
+    fn test_writes_bump_shared_revision() {
+        let (storage, _temp_dir) = setup();
+        let clone = storage.clone();
+        assert_eq!(storage.revision(), 0);
+
+        let skill = Skill::new(
+            "rev-skill".to_string(),
+            "Rev Skill".to_string(),
+            None,
+            None,
+            "# Content".to_string(),
+        );
+        storage.create(&skill).unwrap();
+        assert_eq!(clone.revision(), 1);
+
+        storage.get("rev-skill").unwrap();
+        storage.list().unwrap();
+        assert_eq!(clone.revision(), 1);
+
+        storage.update("rev-skill", &skill).unwrap();
+        storage.delete("rev-skill").unwrap();
+        assert_eq!(clone.revision(), 3);
+    }
+
     #[test]
     fn test_create_duplicate_fails() {
         let (storage, _temp_dir) = setup();
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 
 use crate::models::ExecutionTraceEvent;
 
@@ -16,8 +16,13 @@ pub struct TelemetryMetricSampleStorage {
 
 impl TelemetryMetricSampleStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::TelemetryMetricSampleStorage::new(db)?,
+            inner: restflow_storage::TelemetryMetricSampleStorage::with_backend(backend)?,
         })
     }
 
@@ -3,7 +3,7 @@
 use crate::models::TerminalSession;
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use std::sync::Arc;
 
 /// Typed terminal session storage wrapper around restflow-storage::TerminalSessionStorage.
@@ -14,8 +14,13 @@ pub struct TerminalSessionStorage {
 
 impl TerminalSessionStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::TerminalSessionStorage::new(db)?,
+            inner: restflow_storage::TerminalSessionStorage::with_backend(backend)?,
         })
     }
 
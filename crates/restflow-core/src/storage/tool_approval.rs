@@ -0,0 +1,148 @@
+//! Typed storage for the tool approval policy and its approval queue.
+
+use crate::models::{ApprovalStatus, PendingApproval, ToolApprovalPolicy};
+use anyhow::Result;
+use redb::Database;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
+use std::sync::Arc;
+
+restflow_storage::define_simple_storage! {
+    /// Raw table of tool call approval requests.
+    pub struct RawToolApprovalStorage { table: "tool_approvals" }
+}
+
+restflow_storage::define_simple_storage! {
+    /// Raw table holding the tool approval policy.
+    pub struct RawToolApprovalPolicyStorage { table: "tool_approval_policy" }
+}
+
+const POLICY_KEY: &str = "default";
+
+/// Typed storage wrapper for the tool approval policy and pending approvals.
+#[derive(Debug, Clone)]
+pub struct ToolApprovalStorage {
+    approvals: RawToolApprovalStorage,
+    policy: RawToolApprovalPolicyStorage,
+}
+
+impl ToolApprovalStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            approvals: RawToolApprovalStorage::with_backend(backend.clone())?,
+            policy: RawToolApprovalPolicyStorage::with_backend(backend)?,
+        })
+    }
+
+    /// Get the configured policy, or the allow-all default when unset.
+    pub fn get_policy(&self) -> Result<ToolApprovalPolicy> {
+        let Some(bytes) = self.policy.get_raw(POLICY_KEY)? else {
+            return Ok(ToolApprovalPolicy::default());
+        };
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Replace the policy.
+    pub fn set_policy(&self, policy: &ToolApprovalPolicy) -> Result<()> {
+        let json = serde_json::to_vec(policy)?;
+        self.policy.put_raw(POLICY_KEY, &json)
+    }
+
+    /// Store an approval request (insert or replace).
+    pub fn save(&self, approval: &PendingApproval) -> Result<()> {
+        let json = serde_json::to_vec(approval)?;
+        self.approvals.put_raw(&approval.id, &json)
+    }
+
+    /// Get an approval request by id.
+    pub fn get(&self, id: &str) -> Result<Option<PendingApproval>> {
+        let Some(bytes) = self.approvals.get_raw(id)? else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    /// List approval requests, newest first.
+    ///
+    /// Pending requests past their deadline are marked expired on read.
+    pub fn list(&self, status: Option<ApprovalStatus>) -> Result<Vec<PendingApproval>> {
+        let mut approvals = Vec::new();
+        for (_, bytes) in self.approvals.list_raw()? {
+            let mut approval = serde_json::from_slice::<PendingApproval>(&bytes)?;
+            if approval.status == ApprovalStatus::Pending && approval.is_expired() {
+                approval.expire();
+                self.save(&approval)?;
+            }
+            if status.is_none_or(|status| approval.status == status) {
+                approvals.push(approval);
+            }
+        }
+        approvals.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(approvals)
+    }
+
+    /// Delete an approval request by id.
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        self.approvals.delete(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SecurityAction;
+    use tempfile::tempdir;
+
+    fn setup() -> (ToolApprovalStorage, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("create temp dir");
+        let db_path = temp_dir.path().join("test.db");
+        let db = Arc::new(Database::create(db_path).expect("create db"));
+        let storage = ToolApprovalStorage::new(db).expect("create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_policy_defaults_to_allow_and_round_trips() {
+        let (storage, _temp_dir) = setup();
+        let policy = storage.get_policy().expect("default policy");
+        assert_eq!(policy.default_action, SecurityAction::Allow);
+        assert!(policy.rules.is_empty());
+
+        let policy = ToolApprovalPolicy {
+            default_action: SecurityAction::RequireApproval,
+            ..ToolApprovalPolicy::default()
+        };
+        storage.set_policy(&policy).expect("set policy");
+        assert_eq!(
+            storage.get_policy().expect("get policy").default_action,
+            SecurityAction::RequireApproval
+        );
+    }
+
+    #[test]
+    fn test_list_filters_by_status_and_expires_stale_requests() {
+        let (storage, _temp_dir) = setup();
+        let pending = PendingApproval::new("bash", "task-1", "agent-1", 300);
+        let mut stale = PendingApproval::new("bash", "task-1", "agent-1", 300);
+        stale.expires_at = stale.created_at - 1;
+        let mut approved = PendingApproval::new("bash", "task-1", "agent-1", 300);
+        approved.approve();
+        for approval in [&pending, &stale, &approved] {
+            storage.save(approval).expect("save approval");
+        }
+
+        let listed = storage
+            .list(Some(ApprovalStatus::Pending))
+            .expect("list pending");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, pending.id);
+
+        let stored = storage.get(&stale.id).expect("get").expect("exists");
+        assert_eq!(stored.status, ApprovalStatus::Expired);
+        assert_eq!(storage.list(None).expect("list all").len(), 3);
+    }
+}
@@ -3,7 +3,7 @@
 use crate::models::{ActiveTrigger, TriggerConfig};
 use anyhow::Result;
 use redb::Database;
-use restflow_storage::SimpleStorage;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
 use std::sync::Arc;
 
 /// Typed trigger storage wrapper around restflow-storage::TriggerStorage.
@@ -14,8 +14,13 @@ pub struct TriggerStorage {
 
 impl TriggerStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::TriggerStorage::new(db)?,
+            inner: restflow_storage::TriggerStorage::with_backend(backend)?,
         })
     }
 
@@ -109,6 +114,7 @@ mod tests {
                     key: "test-key".to_string(),
                     header_name: Some("X-API-Key".to_string()),
                 }),
+                browser_plan: None,
             },
             trigger_count: 0,
             activated_at: chrono::Utc::now().timestamp(),
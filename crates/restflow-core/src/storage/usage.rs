@@ -0,0 +1,229 @@
+//! Typed LLM usage storage wrapper.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+use redb::Database;
+use restflow_storage::{RedbBackend, SimpleStorage, StorageBackend};
+
+use crate::models::{UsageBucket, UsageQuery, UsageRecord, UsageStats, UsageTotals};
+
+/// Typed storage wrapper for per-call LLM usage records.
+#[derive(Clone)]
+pub struct UsageStorage {
+    inner: restflow_storage::UsageRecordStorage,
+}
+
+impl UsageStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            inner: restflow_storage::UsageRecordStorage::with_backend(backend)?,
+        })
+    }
+
+    pub fn record(&self, record: &UsageRecord) -> Result<()> {
+        let key = format!("{:020}:{}", record.timestamp, record.id);
+        let bytes = serde_json::to_vec(record).context("Failed to serialize usage record")?;
+        self.inner.put_raw(&key, &bytes)?;
+        Ok(())
+    }
+
+    /// List records matching `query`, oldest first.
+    pub fn list(&self, query: &UsageQuery) -> Result<Vec<UsageRecord>> {
+        // Keys start with the zero-padded timestamp, so raw order is time order.
+        Ok(self
+            .inner
+            .list_raw()?
+            .into_iter()
+            .filter_map(|(_, bytes)| serde_json::from_slice::<UsageRecord>(&bytes).ok())
+            .filter(|record| query.matches(record))
+            .collect())
+    }
+
+    /// Sum usage matching `query`.
+    pub fn totals(&self, query: &UsageQuery) -> Result<UsageTotals> {
+        let mut totals = UsageTotals::default();
+        for record in self.list(query)? {
+            totals.add(&record);
+        }
+        Ok(totals)
+    }
+
+    /// Aggregate usage matching `query` per session, per agent and per day.
+    pub fn stats(&self, query: &UsageQuery) -> Result<UsageStats> {
+        let mut totals = UsageTotals::default();
+        let mut by_session: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        let mut by_agent: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        let mut by_day: BTreeMap<String, UsageTotals> = BTreeMap::new();
+
+        for record in self.list(query)? {
+            totals.add(&record);
+            if let Some(session_id) = &record.session_id {
+                by_session
+                    .entry(session_id.clone())
+                    .or_default()
+                    .add(&record);
+            }
+            by_agent
+                .entry(record.agent_id.clone())
+                .or_default()
+                .add(&record);
+            by_day
+                .entry(day_key(record.timestamp))
+                .or_default()
+                .add(&record);
+        }
+
+        Ok(UsageStats {
+            totals,
+            by_session: by_cost(by_session),
+            by_agent: by_cost(by_agent),
+            by_day: into_buckets(by_day),
+        })
+    }
+}
+
+fn day_key(timestamp_ms: i64) -> String {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn into_buckets(groups: BTreeMap<String, UsageTotals>) -> Vec<UsageBucket> {
+    groups
+        .into_iter()
+        .map(|(key, totals)| UsageBucket { key, totals })
+        .collect()
+}
+
+fn by_cost(groups: BTreeMap<String, UsageTotals>) -> Vec<UsageBucket> {
+    let mut buckets = into_buckets(groups);
+    buckets.sort_by(|a, b| {
+        b.totals
+            .cost_usd
+            .total_cmp(&a.totals.cost_usd)
+            .then_with(|| b.totals.total_tokens.cmp(&a.totals.total_tokens))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redb::Database;
+    use tempfile::tempdir;
+
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    fn record(id: &str, agent: &str, session: Option<&str>, timestamp: i64) -> UsageRecord {
+        UsageRecord {
+            id: id.to_string(),
+            timestamp,
+            agent_id: agent.to_string(),
+            session_id: session.map(str::to_string),
+            task_id: "task".to_string(),
+            run_id: None,
+            provider: Some("openai".to_string()),
+            model: "gpt-5".to_string(),
+            prompt_tokens: 100,
+            completion_tokens: 20,
+            cost_usd: Some(0.01),
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
+            cache_savings_usd: None,
+        }
+    }
+
+    fn storage() -> (tempfile::TempDir, UsageStorage) {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("usage.db")).unwrap());
+        (dir, UsageStorage::new(db).unwrap())
+    }
+
+    #[test]
+    fn test_stats_group_by_session_agent_and_day() {
+        let (_dir, storage) = storage();
+        storage
+            .record(&record("a", "agent-1", Some("s1"), 1_000))
+            .unwrap();
+        storage
+            .record(&record("b", "agent-1", Some("s1"), 2_000))
+            .unwrap();
+        storage
+            .record(&record("c", "agent-2", None, DAY_MS + 1_000))
+            .unwrap();
+
+        let stats = storage.stats(&UsageQuery::default()).unwrap();
+        assert_eq!(stats.totals.call_count, 3);
+        assert_eq!(stats.totals.prompt_tokens, 300);
+        assert_eq!(stats.totals.total_tokens, 360);
+        assert!((stats.totals.cost_usd - 0.03).abs() < 1e-9);
+
+        assert_eq!(stats.by_session.len(), 1);
+        assert_eq!(stats.by_session[0].key, "s1");
+        assert_eq!(stats.by_session[0].totals.call_count, 2);
+
+        assert_eq!(stats.by_agent[0].key, "agent-1");
+        assert_eq!(stats.by_agent[1].key, "agent-2");
+
+        let days: Vec<_> = stats.by_day.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(days, vec!["1970-01-01", "1970-01-02"]);
+    }
+
+    #[test]
+    fn test_totals_respects_agent_and_time_filters() {
+        let (_dir, storage) = storage();
+        storage
+            .record(&record("a", "agent-1", None, 1_000))
+            .unwrap();
+        storage
+            .record(&record("b", "agent-1", None, 5_000))
+            .unwrap();
+        storage
+            .record(&record("c", "agent-2", None, 5_000))
+            .unwrap();
+
+        let totals = storage
+            .totals(&UsageQuery {
+                agent_id: Some("agent-1".to_string()),
+                from_time: Some(2_000),
+                ..UsageQuery::default()
+            })
+            .unwrap();
+        assert_eq!(totals.call_count, 1);
+        assert_eq!(totals.completion_tokens, 20);
+    }
+
+    #[test]
+    fn test_totals_sum_prompt_cache_usage() {
+        let (_dir, storage) = storage();
+        storage
+            .record(&UsageRecord {
+                cache_read_tokens: 80,
+                cache_savings_usd: Some(0.002),
+                ..record("a", "agent-1", None, 1_000)
+            })
+            .unwrap();
+        storage
+            .record(&UsageRecord {
+                cache_write_tokens: 90,
+                cache_savings_usd: Some(-0.001),
+                ..record("b", "agent-1", None, 2_000)
+            })
+            .unwrap();
+
+        let totals = storage.totals(&UsageQuery::default()).unwrap();
+        assert_eq!(totals.cache_read_tokens, 80);
+        assert_eq!(totals.cache_write_tokens, 90);
+        assert!((totals.cache_savings_usd - 0.001).abs() < 1e-9);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::models::{ItemQuery, ItemStatus, WorkItem, WorkItemPatch, WorkItemSpec};
 use anyhow::{Result, anyhow};
 use redb::Database;
+use restflow_storage::{RedbBackend, StorageBackend};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -13,8 +14,13 @@ pub struct WorkItemStorage {
 
 impl WorkItemStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         Ok(Self {
-            inner: restflow_storage::WorkItemStorage::new(db)?,
+            inner: restflow_storage::WorkItemStorage::with_backend(backend)?,
         })
     }
 
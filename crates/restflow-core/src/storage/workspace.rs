@@ -0,0 +1,156 @@
+//! Typed storage for workspace users and resource ownership.
+
+use crate::models::{User, WorkspaceResource, WorkspaceResourceKind};
+use anyhow::Result;
+use redb::Database;
+use restflow_storage::{
+    RedbBackend, SimpleStorage, StorageBackend, UserStorage, WorkspaceResourceStorage,
+};
+use std::sync::Arc;
+
+/// Typed storage wrapper for users and the agents and skills they own.
+#[derive(Debug, Clone)]
+pub struct WorkspaceStorage {
+    users: UserStorage,
+    resources: WorkspaceResourceStorage,
+}
+
+impl WorkspaceStorage {
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on an explicit storage backend.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        Ok(Self {
+            users: UserStorage::with_backend(backend.clone())?,
+            resources: WorkspaceResourceStorage::with_backend(backend)?,
+        })
+    }
+
+    /// Create a new user (fails if the id already exists).
+    pub fn create_user(&self, user: &User) -> Result<()> {
+        if self.users.exists(&user.id)? {
+            anyhow::bail!("User {} already exists", user.id);
+        }
+        let json = serde_json::to_vec(user)?;
+        self.users.put_raw(&user.id, &json)
+    }
+
+    /// Get a user by id.
+    pub fn get_user(&self, id: &str) -> Result<Option<User>> {
+        let Some(bytes) = self.users.get_raw(id)? else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    /// List all users sorted by creation time ascending.
+    pub fn list_users(&self) -> Result<Vec<User>> {
+        let mut users = Vec::new();
+        for (_, bytes) in self.users.list_raw()? {
+            users.push(serde_json::from_slice::<User>(&bytes)?);
+        }
+        users.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(users)
+    }
+
+    /// Delete a user by id. Ownership records are left to the caller.
+    pub fn delete_user(&self, id: &str) -> Result<bool> {
+        self.users.delete(id)
+    }
+
+    /// Record (or replace) the owner of a resource.
+    pub fn put_resource(&self, resource: &WorkspaceResource) -> Result<()> {
+        let json = serde_json::to_vec(resource)?;
+        self.resources.put_raw(
+            &WorkspaceResource::key(resource.kind, &resource.resource_id),
+            &json,
+        )
+    }
+
+    /// Get the ownership record of a resource.
+    pub fn get_resource(
+        &self,
+        kind: WorkspaceResourceKind,
+        resource_id: &str,
+    ) -> Result<Option<WorkspaceResource>> {
+        let Some(bytes) = self
+            .resources
+            .get_raw(&WorkspaceResource::key(kind, resource_id))?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    /// List all ownership records.
+    pub fn list_resources(&self) -> Result<Vec<WorkspaceResource>> {
+        let mut resources = Vec::new();
+        for (_, bytes) in self.resources.list_raw()? {
+            resources.push(serde_json::from_slice::<WorkspaceResource>(&bytes)?);
+        }
+        Ok(resources)
+    }
+
+    /// Delete the ownership record of a resource.
+    pub fn delete_resource(&self, kind: WorkspaceResourceKind, resource_id: &str) -> Result<bool> {
+        self.resources
+            .delete(&WorkspaceResource::key(kind, resource_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup() -> (WorkspaceStorage, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("create temp dir");
+        let db_path = temp_dir.path().join("test.db");
+        let db = Arc::new(Database::create(db_path).expect("create db"));
+        let storage = WorkspaceStorage::new(db).expect("create storage");
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_users_and_resources_round_trip() {
+        let (storage, _temp_dir) = setup();
+        let user = User {
+            id: "user-1".to_string(),
+            name: "alice".to_string(),
+            created_at: 1,
+        };
+        storage.create_user(&user).expect("create user");
+        assert!(storage.create_user(&user).is_err());
+        assert_eq!(storage.list_users().expect("list users"), vec![user]);
+
+        let resource = WorkspaceResource {
+            kind: WorkspaceResourceKind::Agent,
+            resource_id: "agent-1".to_string(),
+            owner_id: "user-1".to_string(),
+            assigned_at: 2,
+        };
+        storage.put_resource(&resource).expect("put resource");
+        assert_eq!(
+            storage
+                .get_resource(WorkspaceResourceKind::Agent, "agent-1")
+                .expect("get resource"),
+            Some(resource)
+        );
+        assert!(
+            storage
+                .get_resource(WorkspaceResourceKind::Skill, "agent-1")
+                .expect("get resource")
+                .is_none()
+        );
+
+        assert!(
+            storage
+                .delete_resource(WorkspaceResourceKind::Agent, "agent-1")
+                .expect("delete resource")
+        );
+        assert!(storage.delete_user("user-1").expect("delete user"));
+        assert!(storage.list_users().expect("list users").is_empty());
+    }
+}
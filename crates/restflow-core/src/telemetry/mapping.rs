@@ -235,6 +235,9 @@ pub fn execution_event_to_trace_event(event: &ExecutionEventEnvelope) -> Executi
                 duration_ms: trace.duration_ms.map(|value| value as i64),
                 is_reasoning: trace.is_reasoning,
                 message_count: trace.message_count,
+                cache_read_tokens: trace.cache_read_tokens,
+                cache_write_tokens: trace.cache_write_tokens,
+                cache_savings_usd: trace.cache_savings_usd,
             },
         ),
         ExecutionEvent::ToolCallStarted(trace) => execution_trace_builders::tool_call(
@@ -445,6 +448,9 @@ mod tests {
                 duration_ms: Some(450),
                 is_reasoning: Some(false),
                 message_count: Some(2),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+                cache_savings_usd: None,
             },
         );
         llm_call.id = "evt-2".to_string();
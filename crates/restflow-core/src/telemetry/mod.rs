@@ -63,6 +63,7 @@ mod tests {
             storage.telemetry_metric_samples.clone(),
             storage.provider_health_snapshots.clone(),
             storage.structured_execution_logs.clone(),
+            storage.usage.clone(),
         );
         let trace =
             restflow_telemetry::RestflowTrace::new("run-1", "session-1", "session-1", "agent-1");
@@ -96,6 +97,9 @@ mod tests {
                     duration_ms: Some(900),
                     is_reasoning: Some(false),
                     message_count: Some(4),
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                    cache_savings_usd: None,
                 }),
             )
             .with_requested_model("minimax-coding-plan-m2-5-highspeed")
@@ -132,6 +136,16 @@ mod tests {
         assert_eq!(persisted.completion_tokens, 30);
         assert_eq!(persisted.cost, 0.42);
 
+        let usage = storage
+            .usage
+            .stats(&crate::models::UsageQuery::default())
+            .expect("usage stats");
+        assert_eq!(usage.totals.call_count, 1);
+        assert_eq!(usage.totals.prompt_tokens, 120);
+        assert_eq!(usage.totals.completion_tokens, 30);
+        assert_eq!(usage.by_session[0].key, "session-1");
+        assert_eq!(usage.by_agent[0].key, "agent-1");
+
         let metrics = get_execution_metrics(
             &storage.telemetry_metric_samples,
             &ExecutionMetricQuery {
@@ -1,9 +1,9 @@
 use anyhow::Result;
 
-use crate::models::{ExecutionTraceCategory, ExecutionTraceEvent};
+use crate::models::{ExecutionTraceCategory, ExecutionTraceEvent, UsageRecord};
 use crate::storage::{
     ChatSessionStorage, ExecutionTraceStorage, ProviderHealthSnapshotStorage,
-    StructuredExecutionLogStorage, TelemetryMetricSampleStorage,
+    StructuredExecutionLogStorage, TelemetryMetricSampleStorage, UsageStorage,
 };
 
 pub trait TelemetryProjector: Send + Sync {
@@ -138,3 +138,41 @@ impl TelemetryProjector for StructuredLogProjector {
         Ok(())
     }
 }
+
+#[derive(Clone)]
+pub struct UsageProjector {
+    storage: UsageStorage,
+}
+
+impl UsageProjector {
+    pub fn new(storage: UsageStorage) -> Self {
+        Self { storage }
+    }
+}
+
+impl TelemetryProjector for UsageProjector {
+    fn project(&self, event: &ExecutionTraceEvent) -> Result<()> {
+        let Some(llm_call) = event.llm_call.as_ref() else {
+            return Ok(());
+        };
+        self.storage.record(&UsageRecord {
+            id: event.id.clone(),
+            timestamp: event.timestamp,
+            agent_id: event.agent_id.clone(),
+            session_id: event.session_id.clone(),
+            task_id: event.task_id.clone(),
+            run_id: event.run_id.clone(),
+            provider: event.provider.clone(),
+            model: event
+                .effective_model
+                .clone()
+                .unwrap_or_else(|| llm_call.model.clone()),
+            prompt_tokens: llm_call.input_tokens.unwrap_or(0),
+            completion_tokens: llm_call.output_tokens.unwrap_or(0),
+            cost_usd: llm_call.cost_usd,
+            cache_read_tokens: llm_call.cache_read_tokens.unwrap_or(0),
+            cache_write_tokens: llm_call.cache_write_tokens.unwrap_or(0),
+            cache_savings_usd: llm_call.cache_savings_usd,
+        })
+    }
+}
@@ -139,6 +139,7 @@ pub fn execution_trace_stats_for_events(events: &[ExecutionTraceEvent]) -> Execu
             ExecutionTraceCategory::MetricSample => stats.metric_sample_count += 1,
             ExecutionTraceCategory::ProviderHealth => stats.provider_health_count += 1,
             ExecutionTraceCategory::LogRecord => stats.log_record_count += 1,
+            ExecutionTraceCategory::SecurityDecision => stats.security_decision_count += 1,
         }
     }
     stats
@@ -10,14 +10,14 @@ use restflow_telemetry::{
 use crate::models::ExecutionTraceCategory;
 use crate::storage::{
     ExecutionTraceStorage, ProviderHealthSnapshotStorage, StructuredExecutionLogStorage,
-    TelemetryMetricSampleStorage,
+    TelemetryMetricSampleStorage, UsageStorage,
 };
 
 use super::derive::derive_projection_events;
 use super::mapping::execution_event_to_trace_event;
 use super::projector::{
     ExecutionTraceProjector, MetricsProjector, ProviderHealthProjector, SessionProjectionProjector,
-    StructuredLogProjector, TelemetryProjector,
+    StructuredLogProjector, TelemetryProjector, UsageProjector,
 };
 
 #[derive(Clone)]
@@ -27,6 +27,7 @@ pub struct CoreTelemetrySink {
     metrics_projector: MetricsProjector,
     provider_health_projector: ProviderHealthProjector,
     structured_log_projector: StructuredLogProjector,
+    usage_projector: UsageProjector,
 }
 
 impl CoreTelemetrySink {
@@ -36,6 +37,7 @@ impl CoreTelemetrySink {
         telemetry_metric_samples: TelemetryMetricSampleStorage,
         provider_health_snapshots: ProviderHealthSnapshotStorage,
         structured_execution_logs: StructuredExecutionLogStorage,
+        usage: UsageStorage,
     ) -> Self {
         Self {
             trace_projector: ExecutionTraceProjector::new(execution_traces),
@@ -43,6 +45,7 @@ impl CoreTelemetrySink {
             metrics_projector: MetricsProjector::new(telemetry_metric_samples),
             provider_health_projector: ProviderHealthProjector::new(provider_health_snapshots),
             structured_log_projector: StructuredLogProjector::new(structured_execution_logs),
+            usage_projector: UsageProjector::new(usage),
         }
     }
 
@@ -52,6 +55,7 @@ impl CoreTelemetrySink {
         self.metrics_projector.project(event)?;
         self.provider_health_projector.project(event)?;
         self.structured_log_projector.project(event)?;
+        self.usage_projector.project(event)?;
         Ok(())
     }
 
@@ -143,6 +147,7 @@ pub fn build_core_telemetry_sink(storage: &crate::storage::Storage) -> Arc<dyn T
         storage.telemetry_metric_samples.clone(),
         storage.provider_health_snapshots.clone(),
         storage.structured_execution_logs.clone(),
+        storage.usage.clone(),
     ))
 }
 
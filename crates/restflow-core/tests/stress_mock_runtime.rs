@@ -44,6 +44,7 @@ async fn stress_runner_handles_mock_throughput_without_leaks() {
             worker_count: 8,
             task_timeout_secs: Some(30),
             stall_timeout_secs: None,
+            auto_resume: false,
         },
         Arc::new(SteerRegistry::new()),
     ));
@@ -123,6 +124,7 @@ async fn stress_runner_recovers_after_restart_without_orphan_running_tasks() {
             worker_count: 3,
             task_timeout_secs: Some(60),
             stall_timeout_secs: None,
+            auto_resume: false,
         },
         Arc::new(SteerRegistry::new()),
     ));
@@ -165,6 +167,7 @@ async fn stress_runner_recovers_after_restart_without_orphan_running_tasks() {
             worker_count: 6,
             task_timeout_secs: Some(60),
             stall_timeout_secs: None,
+            auto_resume: false,
         },
         Arc::new(SteerRegistry::new()),
     ));
@@ -189,6 +192,7 @@ async fn stress_runner_recovers_after_restart_without_orphan_running_tasks() {
             worker_count: 8,
             task_timeout_secs: Some(60),
             stall_timeout_secs: None,
+            auto_resume: false,
         },
         Arc::new(SteerRegistry::new()),
     ));
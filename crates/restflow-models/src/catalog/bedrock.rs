@@ -0,0 +1,45 @@
+use super::{ModelDescriptor, ProviderCatalog};
+use crate::{ModelId, Provider};
+
+const SONNET_ALIASES: &[&str] = &[
+    "bedrock-claude-sonnet",
+    "anthropic.claude-sonnet-4-5-20250929-v1:0",
+];
+const HAIKU_ALIASES: &[&str] = &[
+    "bedrock-claude-haiku",
+    "anthropic.claude-haiku-4-5-20251001-v1:0",
+];
+const NOVA_PRO_ALIASES: &[&str] = &["nova-pro", "amazon.nova-pro-v1:0"];
+
+// API names are US cross-region inference profiles, which on-demand
+// throughput requires for these models.
+pub const MODELS: &[ModelDescriptor] = &[
+    ModelDescriptor::new(
+        ModelId::BedrockClaudeSonnet4_5,
+        Provider::Bedrock,
+        "us.anthropic.claude-sonnet-4-5-20250929-v1:0",
+        "Claude Sonnet 4.5 (Bedrock)",
+        true,
+    )
+    .with_aliases(SONNET_ALIASES)
+    .with_same_provider_fallback(ModelId::BedrockClaudeHaiku4_5),
+    ModelDescriptor::new(
+        ModelId::BedrockClaudeHaiku4_5,
+        Provider::Bedrock,
+        "us.anthropic.claude-haiku-4-5-20251001-v1:0",
+        "Claude Haiku 4.5 (Bedrock)",
+        true,
+    )
+    .with_aliases(HAIKU_ALIASES),
+    ModelDescriptor::new(
+        ModelId::BedrockNovaPro,
+        Provider::Bedrock,
+        "us.amazon.nova-pro-v1:0",
+        "Amazon Nova Pro (Bedrock)",
+        true,
+    )
+    .with_aliases(NOVA_PRO_ALIASES),
+];
+
+pub const CATALOG: ProviderCatalog =
+    ProviderCatalog::new(Provider::Bedrock, ModelId::BedrockClaudeSonnet4_5, MODELS);
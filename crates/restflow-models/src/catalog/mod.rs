@@ -1,4 +1,5 @@
 mod anthropic;
+mod bedrock;
 mod claude_code;
 mod codex;
 mod deepseek;
@@ -155,6 +156,7 @@ pub const PROVIDER_CATALOGS: &[ProviderCatalog] = &[
     siliconflow::CATALOG,
     minimax::CATALOG,
     minimax_coding_plan::CATALOG,
+    bedrock::CATALOG,
 ];
 
 static DESCRIPTOR_BY_ID: OnceLock<HashMap<&'static str, &'static ModelDescriptor>> =
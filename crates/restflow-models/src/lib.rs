@@ -1,12 +1,17 @@
 //! Shared model/provider primitives used by runtime, core, and tools.
 
 pub mod catalog;
+mod local;
 mod model_id;
 mod model_metadata;
 mod provider;
 mod provider_meta;
 mod selector;
 
+pub use local::{
+    DEFAULT_OLLAMA_HOST, LOCAL_LLM_URL_ENV, LocalBackend, OLLAMA_HOST_ENV, local_llm_base_url,
+    ollama_host, parse_local_model,
+};
 pub use model_id::ModelId;
 pub use model_metadata::{ModelMetadata, ModelMetadataDTO};
 pub use provider::Provider;
@@ -205,7 +210,7 @@ mod tests {
 
     #[test]
     fn provider_meta_catalog_stays_in_sync_with_model_provider() {
-        assert_eq!(ALL_PROVIDER_META.len(), 18);
+        assert_eq!(ALL_PROVIDER_META.len(), 19);
         assert_eq!(
             provider_meta(ModelProvider::MiniMaxCodingPlan).canonical_name(),
             "minimax-coding-plan"
@@ -0,0 +1,148 @@
+//! Locally hosted models (Ollama, llama.cpp and other OpenAI-compatible servers).
+//!
+//! Local models are not part of the static catalog. They are referenced as
+//! `ollama:<model>` or `local:<model>` and resolved to a [`ModelSpec`] on demand.
+
+use crate::{LlmProvider, ModelSpec};
+
+/// Overrides the Ollama host, matching the variable read by the Ollama CLI.
+pub const OLLAMA_HOST_ENV: &str = "OLLAMA_HOST";
+/// Overrides the base URL of the OpenAI-compatible local server.
+pub const LOCAL_LLM_URL_ENV: &str = "RESTFLOW_LOCAL_LLM_URL";
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// Server type behind a local model reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalBackend {
+    /// Ollama, addressed by its host (`/api/*` and `/v1/*` live under it).
+    Ollama,
+    /// Any server exposing `/v1/chat/completions` (llama.cpp, LM Studio, vLLM).
+    OpenAICompatible,
+}
+
+impl LocalBackend {
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Ollama => "ollama",
+            Self::OpenAICompatible => "local",
+        }
+    }
+
+    pub fn from_prefix(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ollama" => Some(Self::Ollama),
+            "local" | "llama.cpp" | "llamacpp" | "llama-cpp" => Some(Self::OpenAICompatible),
+            _ => None,
+        }
+    }
+
+    pub fn runtime_provider(self) -> LlmProvider {
+        match self {
+            Self::Ollama => LlmProvider::Ollama,
+            Self::OpenAICompatible => LlmProvider::Local,
+        }
+    }
+
+    /// Model reference understood by the LLM factory, e.g. `ollama:llama3.1`.
+    pub fn reference(self, model: &str) -> String {
+        format!("{}:{}", self.prefix(), model.trim())
+    }
+
+    /// Base URL the client connects to, honouring the environment overrides.
+    pub fn base_url(self) -> String {
+        match self {
+            Self::Ollama => ollama_host(),
+            Self::OpenAICompatible => local_llm_base_url(),
+        }
+    }
+}
+
+/// Split a local model reference into its backend and model name.
+pub fn parse_local_model(value: &str) -> Option<(LocalBackend, &str)> {
+    let (prefix, model) = value.trim().split_once(':')?;
+    let backend = LocalBackend::from_prefix(prefix)?;
+    let model = model.trim();
+    (!model.is_empty()).then_some((backend, model))
+}
+
+/// Ollama host from `OLLAMA_HOST`, defaulting to `http://localhost:11434`.
+pub fn ollama_host() -> String {
+    std::env::var(OLLAMA_HOST_ENV)
+        .ok()
+        .map(|value| normalize_url(&value))
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_OLLAMA_HOST.to_string())
+}
+
+/// Base URL of the OpenAI-compatible local server, defaulting to llama.cpp's.
+pub fn local_llm_base_url() -> String {
+    std::env::var(LOCAL_LLM_URL_ENV)
+        .ok()
+        .map(|value| normalize_url(&value))
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| LlmProvider::Local.base_url().to_string())
+}
+
+/// Trim trailing slashes and add a scheme to bare `host:port` values.
+fn normalize_url(value: &str) -> String {
+    let trimmed = value.trim().trim_end_matches('/');
+    if trimmed.is_empty() || trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("http://{trimmed}")
+    }
+}
+
+impl ModelSpec {
+    /// Build a spec for a local model reference such as `ollama:qwen2.5-coder`.
+    pub fn local(reference: &str) -> Option<Self> {
+        let (backend, model) = parse_local_model(reference)?;
+        Some(
+            Self::new(backend.reference(model), backend.runtime_provider(), model)
+                .with_base_url(backend.base_url()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalBackend, normalize_url, parse_local_model};
+    use crate::{ClientKind, LlmProvider, ModelSpec};
+
+    #[test]
+    fn parses_local_model_references() {
+        assert_eq!(
+            parse_local_model("ollama:llama3.1:8b"),
+            Some((LocalBackend::Ollama, "llama3.1:8b"))
+        );
+        assert_eq!(
+            parse_local_model(" llama.cpp:qwen2.5 "),
+            Some((LocalBackend::OpenAICompatible, "qwen2.5"))
+        );
+        assert_eq!(parse_local_model("ollama:"), None);
+        assert_eq!(parse_local_model("openai:gpt-5"), None);
+        assert_eq!(parse_local_model("gpt-5"), None);
+    }
+
+    #[test]
+    fn local_model_spec_uses_local_provider_over_http() {
+        let spec = ModelSpec::local("Ollama:llama3.1").unwrap();
+        assert_eq!(spec.name, "ollama:llama3.1");
+        assert_eq!(spec.client_model, "llama3.1");
+        assert_eq!(spec.provider, LlmProvider::Ollama);
+        assert_eq!(spec.client_kind, ClientKind::Http);
+        assert!(spec.base_url.is_some());
+
+        let spec = ModelSpec::local("local:mistral").unwrap();
+        assert_eq!(spec.provider, LlmProvider::Local);
+    }
+
+    #[test]
+    fn normalize_url_adds_scheme_and_trims_slashes() {
+        assert_eq!(normalize_url("0.0.0.0:11434/"), "http://0.0.0.0:11434");
+        assert_eq!(
+            normalize_url("https://gpu-box:8080/v1/"),
+            "https://gpu-box:8080/v1"
+        );
+    }
+}
@@ -69,6 +69,9 @@ impl ModelId {
     pub const CodexCli: Self = Self("gpt-5.3-codex");
     pub const OpenCodeCli: Self = Self("opencode-cli");
     pub const GeminiCli: Self = Self("gemini-cli");
+    pub const BedrockClaudeSonnet4_5: Self = Self("bedrock-claude-sonnet-4-5");
+    pub const BedrockClaudeHaiku4_5: Self = Self("bedrock-claude-haiku-4-5");
+    pub const BedrockNovaPro: Self = Self("bedrock-nova-pro");
 
     pub const fn as_serialized_str(&self) -> &'static str {
         self.0
@@ -32,6 +32,7 @@ impl Provider {
     pub const SiliconFlow: Self = Self(ModelProvider::SiliconFlow);
     pub const MiniMax: Self = Self(ModelProvider::MiniMax);
     pub const MiniMaxCodingPlan: Self = Self(ModelProvider::MiniMaxCodingPlan);
+    pub const Bedrock: Self = Self(ModelProvider::Bedrock);
 
     pub fn all() -> &'static [Provider] {
         &ALL_PROVIDERS
@@ -115,7 +116,7 @@ impl<'de> Deserialize<'de> for Provider {
     }
 }
 
-const ALL_PROVIDERS: [Provider; 18] = [
+const ALL_PROVIDERS: [Provider; 19] = [
     Provider::OpenAI,
     Provider::Anthropic,
     Provider::ClaudeCode,
@@ -134,4 +135,5 @@ const ALL_PROVIDERS: [Provider; 18] = [
     Provider::SiliconFlow,
     Provider::MiniMax,
     Provider::MiniMaxCodingPlan,
+    Provider::Bedrock,
 ];
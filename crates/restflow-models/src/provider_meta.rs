@@ -164,6 +164,14 @@ pub const ALL_PROVIDER_META: &[ProviderMeta] = &[
         default_model_id: ModelId::MiniMaxM25CodingPlan,
         models_dev_provider_ids: &["minimax-coding-plan", "minimax-cn-coding-plan"],
     },
+    ProviderMeta {
+        provider: ModelProvider::Bedrock,
+        runtime_provider: LlmProvider::Bedrock,
+        api_key_env: Some("AWS_BEARER_TOKEN_BEDROCK"),
+        api_key_env_aliases: &[],
+        default_model_id: ModelId::BedrockClaudeSonnet4_5,
+        models_dev_provider_ids: &["amazon-bedrock"],
+    },
 ];
 
 pub fn provider_meta(provider: ModelProvider) -> &'static ProviderMeta {
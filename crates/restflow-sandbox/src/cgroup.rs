@@ -0,0 +1,199 @@
+//! cgroup v2 groups for spawned tool processes.
+//!
+//! Each child gets its own group below the daemon's cgroup (or below
+//! `RESTFLOW_CGROUP_ROOT` when set), so limits cover every process it forks
+//! and the whole tree can be killed at once.
+
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::SandboxError;
+use crate::limits::ResourceLimits;
+
+const CGROUP_MOUNT: &str = "/sys/fs/cgroup";
+const CGROUP_ROOT_ENV: &str = "RESTFLOW_CGROUP_ROOT";
+
+static NEXT_GROUP_ID: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) struct Cgroup {
+    path: PathBuf,
+    procs: CString,
+}
+
+impl Cgroup {
+    pub(crate) fn create(limits: &ResourceLimits) -> Result<Self, SandboxError> {
+        let parent = parent_cgroup()?;
+        enable_controllers(&parent, &required_controllers(limits))?;
+
+        let path = parent.join(format!(
+            "restflow-{}-{}",
+            std::process::id(),
+            NEXT_GROUP_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir(&path)?;
+        let procs = CString::new(path.join("cgroup.procs").as_os_str().as_bytes())
+            .map_err(|error| SandboxError::Unavailable(error.to_string()))?;
+        // From here on, dropping `cgroup` removes the directory again.
+        let cgroup = Self { path, procs };
+
+        if let Some(bytes) = limits.memory_bytes {
+            cgroup.write("memory.max", &bytes.to_string())?;
+            // Keep the ceiling from spilling into swap; missing without swap accounting.
+            let _ = cgroup.write("memory.swap.max", "0");
+        }
+        if let Some(weight) = limits.cpu_weight {
+            cgroup.write("cpu.weight", &weight.clamp(1, 10_000).to_string())?;
+        }
+        if let Some(max) = limits.max_processes {
+            cgroup.write("pids.max", &max.max(1).to_string())?;
+        }
+        Ok(cgroup)
+    }
+
+    /// Move the calling process into the group. Async-signal-safe, so it can
+    /// run inside `pre_exec`; writing `0` to `cgroup.procs` moves the writer.
+    pub(crate) fn join(&self) -> std::io::Result<()> {
+        let fd = unsafe { libc::open(self.procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let written = unsafe { libc::write(fd, b"0".as_ptr().cast(), 1) };
+        let result = if written == 1 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        };
+        unsafe {
+            libc::close(fd);
+        }
+        result
+    }
+
+    /// Counter from a flat-keyed events file such as `memory.events`.
+    pub(crate) fn event_count(&self, file: &str, key: &str) -> u64 {
+        std::fs::read_to_string(self.path.join(file))
+            .map(|contents| parse_event_count(&contents, key))
+            .unwrap_or(0)
+    }
+
+    fn write(&self, file: &str, value: &str) -> Result<(), SandboxError> {
+        std::fs::write(self.path.join(file), value)?;
+        Ok(())
+    }
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        // `cgroup.kill` (Linux 5.14+) reaps background processes the child left behind.
+        let _ = std::fs::write(self.path.join("cgroup.kill"), "1");
+        for _ in 0..5 {
+            if std::fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        tracing::debug!(path = %self.path.display(), "Failed to remove tool cgroup");
+    }
+}
+
+fn parent_cgroup() -> Result<PathBuf, SandboxError> {
+    if let Some(root) = std::env::var_os(CGROUP_ROOT_ENV).filter(|root| !root.is_empty()) {
+        return Ok(PathBuf::from(root));
+    }
+
+    if !Path::new(CGROUP_MOUNT).join("cgroup.controllers").exists() {
+        return Err(SandboxError::Unavailable(
+            "cgroup v2 is not mounted at /sys/fs/cgroup".into(),
+        ));
+    }
+    let membership = std::fs::read_to_string("/proc/self/cgroup")?;
+    let relative = membership
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .ok_or_else(|| SandboxError::Unavailable("process is not in a cgroup v2 group".into()))?;
+    Ok(Path::new(CGROUP_MOUNT).join(relative.trim_start_matches('/')))
+}
+
+fn required_controllers(limits: &ResourceLimits) -> Vec<&'static str> {
+    let mut controllers = Vec::new();
+    if limits.memory_bytes.is_some() {
+        controllers.push("memory");
+    }
+    if limits.cpu_weight.is_some() {
+        controllers.push("cpu");
+    }
+    if limits.max_processes.is_some() {
+        controllers.push("pids");
+    }
+    controllers
+}
+
+/// Make sure `parent` hands the controllers down to its children. Enabling
+/// fails when `parent` still holds processes itself, which is why a
+/// delegated `RESTFLOW_CGROUP_ROOT` is preferred in service setups.
+fn enable_controllers(parent: &Path, controllers: &[&str]) -> Result<(), SandboxError> {
+    let subtree_control = parent.join("cgroup.subtree_control");
+    let enabled = std::fs::read_to_string(&subtree_control)?;
+    for controller in controllers {
+        if !has_controller(&enabled, controller) {
+            let _ = std::fs::write(&subtree_control, format!("+{controller}"));
+        }
+    }
+
+    let enabled = std::fs::read_to_string(&subtree_control)?;
+    let missing: Vec<&str> = controllers
+        .iter()
+        .copied()
+        .filter(|controller| !has_controller(&enabled, controller))
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(SandboxError::Unavailable(format!(
+            "cgroup controllers {} are not delegated to {}",
+            missing.join(", "),
+            parent.display()
+        )))
+    }
+}
+
+fn has_controller(list: &str, controller: &str) -> bool {
+    list.split_whitespace().any(|entry| entry == controller)
+}
+
+fn parse_event_count(contents: &str, key: &str) -> u64 {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(name, _)| *name == key)
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_flat_keyed_event_files() {
+        let events = "low 0\nhigh 0\nmax 3\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(parse_event_count(events, "oom_kill"), 1);
+        assert_eq!(parse_event_count(events, "max"), 3);
+        assert_eq!(parse_event_count(events, "missing"), 0);
+    }
+
+    #[test]
+    fn controllers_follow_configured_limits() {
+        let limits = ResourceLimits {
+            memory_bytes: Some(64 * 1024 * 1024),
+            max_processes: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(required_controllers(&limits), vec!["memory", "pids"]);
+        assert!(has_controller("cpu io memory pids", "memory"));
+        assert!(!has_controller("cpuset io", "cpu"));
+    }
+}
@@ -13,4 +13,7 @@ pub enum SandboxError {
 
     #[error("sandbox unavailable: {0}")]
     Unavailable(String),
+
+    #[error("invalid sandbox policy: {0}")]
+    InvalidPolicy(String),
 }
@@ -6,12 +6,27 @@
 //!
 //! The sandbox works in two phases:
 //! 1. **Command wrapping** (`wrap_command`): On macOS, replaces the program
-//!    with `sandbox-exec`. On other platforms, returns the original program/args.
+//!    with `sandbox-exec`. On Windows, re-executes the current binary as a
+//!    launcher that starts the program in an AppContainer. On Linux, returns
+//!    the original program/args.
 //! 2. **Pre-exec hooks** (`pre_exec_hook`): On Linux, sets up Landlock and
 //!    seccomp in the child process. No-op on other platforms.
+//!
+//! Binaries that run sandboxed commands on Windows must call
+//! [`run_launcher_if_requested`] at the start of `main`.
+//!
+//! Outbound network access is part of the policy ([`NetworkPolicy`]); see
+//! the [`network`] module for per-platform enforcement.
+//!
+//! Resource ceilings (memory, CPU share, process count) are configured
+//! separately through [`ResourceLimits`]; see the [`limits`] module.
 
 pub mod error;
+pub mod limits;
+pub mod network;
 
+#[cfg(target_os = "linux")]
+mod cgroup;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
@@ -22,6 +37,8 @@ mod windows;
 use std::path::PathBuf;
 
 pub use error::SandboxError;
+pub use limits::{LimitEnforcement, LimitViolation, ResourceGuard, ResourceLimits};
+pub use network::{NetworkEndpoint, NetworkPolicy};
 
 /// Policy controlling what the sandboxed process may access.
 #[derive(Debug, Clone)]
@@ -29,34 +46,102 @@ pub enum SandboxPolicy {
     /// No restrictions (backward-compatible default).
     None,
 
-    /// File system is read-only everywhere; network is denied.
-    ReadOnly,
+    /// File system is read-only everywhere.
+    ReadOnly {
+        /// Outbound network access.
+        network: NetworkPolicy,
+    },
 
-    /// Specified directories are writable, everything else is read-only;
-    /// network is denied.
+    /// Specified directories are writable, everything else is read-only.
     WriteDir {
         /// Directories the child process may write to.
         writable_dirs: Vec<PathBuf>,
+        /// Outbound network access.
+        network: NetworkPolicy,
+    },
+
+    /// File system unrestricted; only the network policy applies.
+    NetworkOnly {
+        /// Outbound network access.
+        network: NetworkPolicy,
     },
 }
 
+impl SandboxPolicy {
+    /// Read-only file system with network access denied.
+    pub fn read_only() -> Self {
+        Self::ReadOnly {
+            network: NetworkPolicy::Deny,
+        }
+    }
+
+    /// Writable `writable_dirs` with network access denied.
+    pub fn write_dirs(writable_dirs: Vec<PathBuf>) -> Self {
+        Self::WriteDir {
+            writable_dirs,
+            network: NetworkPolicy::Deny,
+        }
+    }
+
+    /// Unrestricted file system with outbound access limited by `network`.
+    pub fn network_only(network: NetworkPolicy) -> Self {
+        Self::NetworkOnly { network }
+    }
+
+    /// Replace the network policy. No effect on [`SandboxPolicy::None`].
+    pub fn with_network(mut self, policy: NetworkPolicy) -> Self {
+        match &mut self {
+            Self::None => {}
+            Self::ReadOnly { network }
+            | Self::WriteDir { network, .. }
+            | Self::NetworkOnly { network } => *network = policy,
+        }
+        self
+    }
+
+    /// Network policy in effect; unrestricted for [`SandboxPolicy::None`].
+    pub fn network(&self) -> &NetworkPolicy {
+        match self {
+            Self::None => &NetworkPolicy::AllowAll,
+            Self::ReadOnly { network }
+            | Self::WriteDir { network, .. }
+            | Self::NetworkOnly { network } => network,
+        }
+    }
+
+    /// Whether the policy restricts nothing.
+    pub fn is_unrestricted(&self) -> bool {
+        match self {
+            Self::None => true,
+            Self::NetworkOnly { network } => *network == NetworkPolicy::AllowAll,
+            Self::ReadOnly { .. } | Self::WriteDir { .. } => false,
+        }
+    }
+}
+
 /// Wrap a command's program and arguments for sandbox enforcement.
 ///
 /// - **macOS**: Returns `("/usr/bin/sandbox-exec", ["-p", profile, "--", program, args...])`.
+/// - **Windows**: Returns `(current_exe, ["--restflow-sandbox-launch", "--write", dir, ..., "--", program, args...])`.
 /// - **Other platforms**: Returns the original program and args unchanged.
 ///
 /// After calling this, also call [`pre_exec_hook`] inside a `pre_exec` closure
 /// on Linux for full enforcement.
+///
+/// Fails with [`SandboxError::InvalidPolicy`] when the network allow list
+/// names hosts the platform cannot restrict.
 pub fn wrap_command(
     policy: &SandboxPolicy,
     program: &str,
     args: &[&str],
 ) -> Result<(String, Vec<String>), SandboxError> {
-    if matches!(policy, SandboxPolicy::None) {
+    if policy.is_unrestricted() {
         let args_owned: Vec<String> = args.iter().map(|s| s.to_string()).collect();
         return Ok((program.to_string(), args_owned));
     }
 
+    policy.network().validate()?;
+    network::warn_unenforced(policy.network());
     platform_wrap_command(policy, program, args)
 }
 
@@ -68,13 +153,24 @@ pub fn wrap_command(
 /// # Safety
 /// This must only be called inside a `pre_exec` closure (async-signal-safe context).
 pub fn pre_exec_hook(policy: &SandboxPolicy) -> Result<(), SandboxError> {
-    if matches!(policy, SandboxPolicy::None) {
+    if policy.is_unrestricted() {
         return Ok(());
     }
 
+    policy.network().validate()?;
     platform_pre_exec_hook(policy)
 }
 
+/// Act as the sandbox launcher if this process was started as one by
+/// [`wrap_command`], exiting with the sandboxed program's exit code.
+///
+/// Returns immediately for any other invocation and on platforms other than
+/// Windows.
+pub fn run_launcher_if_requested() {
+    #[cfg(target_os = "windows")]
+    windows::run_launcher_if_requested();
+}
+
 // ─── Platform dispatch ──────────────────────────────────────────────────
 
 #[cfg(target_os = "macos")]
@@ -86,7 +182,16 @@ fn platform_wrap_command(
     macos::wrap_command_macos(policy, program, args)
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(target_os = "windows")]
+fn platform_wrap_command(
+    policy: &SandboxPolicy,
+    program: &str,
+    args: &[&str],
+) -> Result<(String, Vec<String>), SandboxError> {
+    windows::wrap_command_windows(policy, program, args)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
 fn platform_wrap_command(
     _policy: &SandboxPolicy,
     program: &str,
@@ -0,0 +1,316 @@
+//! Resource limits for spawned tool processes.
+//!
+//! [`ResourceLimits`] caps the memory, CPU share, CPU time and process count
+//! of a child process and everything it forks:
+//!
+//! - **Linux**: a dedicated cgroup v2 group (`memory.max`, `cpu.weight`,
+//!   `pids.max`). When no delegated cgroup is writable, memory falls back to
+//!   `RLIMIT_AS` and CPU share to the nice value.
+//! - **macOS**: `RLIMIT_AS` and the nice value.
+//! - **Windows**: a job object with a job memory limit, active process limit,
+//!   job user time limit and weighted CPU rate control.
+//!
+//! On Unix CPU time is capped with `RLIMIT_CPU`, which applies to each process
+//! on its own rather than to the whole tree. `RLIMIT_NPROC` counts every
+//! process of the user rather than the child's tree, so the process limit is
+//! only enforced by cgroups and job objects.
+
+use std::fmt;
+use std::process::ExitStatus;
+
+use crate::SandboxError;
+
+/// Kernel default for cgroup v2 `cpu.weight`.
+pub const DEFAULT_CPU_WEIGHT: u32 = 100;
+
+/// Limits applied to a child process and its descendants. `None` leaves
+/// that resource unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Memory ceiling in bytes.
+    pub memory_bytes: Option<u64>,
+    /// Relative CPU share in the cgroup v2 `cpu.weight` range (1-10000,
+    /// default 100).
+    pub cpu_weight: Option<u32>,
+    /// Maximum number of live processes, including the child itself.
+    pub max_processes: Option<u32>,
+    /// CPU time ceiling in seconds. Per process on Unix, for the whole job on
+    /// Windows.
+    pub cpu_time_secs: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.memory_bytes.is_none()
+            && self.cpu_weight.is_none()
+            && self.max_processes.is_none()
+            && self.cpu_time_secs.is_none()
+    }
+
+    /// Prepare enforcement for one child process.
+    ///
+    /// Call [`ResourceGuard::pre_exec`] inside the child's `pre_exec` closure
+    /// on Unix, or [`ResourceGuard::attach`] right after spawning on Windows,
+    /// and keep the guard alive until the child has exited.
+    pub fn prepare(&self) -> Result<ResourceGuard, SandboxError> {
+        ResourceGuard::new(*self)
+    }
+}
+
+/// A limit that a child process ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitViolation {
+    Memory { limit_bytes: u64 },
+    Processes { limit: u32 },
+    CpuTime { limit_secs: u64 },
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Memory { limit_bytes } => write!(
+                f,
+                "memory limit of {} MiB exceeded",
+                limit_bytes.div_ceil(1024 * 1024)
+            ),
+            Self::Processes { limit } => write!(f, "process limit of {limit} reached"),
+            Self::CpuTime { limit_secs } => write!(f, "CPU time limit of {limit_secs}s exceeded"),
+        }
+    }
+}
+
+/// How a [`ResourceGuard`] enforces its limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitEnforcement {
+    None,
+    Cgroup,
+    Rlimit,
+    JobObject,
+}
+
+/// Per-child enforcement state. Dropping the guard kills whatever is left
+/// of the child's process tree where the platform allows it and releases
+/// the cgroup or job object.
+pub struct ResourceGuard {
+    limits: ResourceLimits,
+    #[cfg(target_os = "linux")]
+    cgroup: Option<crate::cgroup::Cgroup>,
+    #[cfg(target_os = "windows")]
+    job: Option<crate::windows::JobObject>,
+}
+
+impl ResourceGuard {
+    fn new(limits: ResourceLimits) -> Result<Self, SandboxError> {
+        #[cfg(target_os = "linux")]
+        let cgroup = if limits.is_unlimited() {
+            None
+        } else {
+            match crate::cgroup::Cgroup::create(&limits) {
+                Ok(cgroup) => Some(cgroup),
+                Err(error) => {
+                    tracing::debug!(
+                        error = %error,
+                        "cgroup v2 limits unavailable, falling back to rlimits"
+                    );
+                    None
+                }
+            }
+        };
+
+        #[cfg(target_os = "windows")]
+        let job = if limits.is_unlimited() {
+            None
+        } else {
+            Some(crate::windows::JobObject::create(&limits)?)
+        };
+
+        let guard = Self {
+            limits,
+            #[cfg(target_os = "linux")]
+            cgroup,
+            #[cfg(target_os = "windows")]
+            job,
+        };
+        if guard.enforcement() == LimitEnforcement::Rlimit && limits.max_processes.is_some() {
+            tracing::warn!("max_processes requires cgroup v2 delegation and is not enforced");
+        }
+        Ok(guard)
+    }
+
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    pub fn enforcement(&self) -> LimitEnforcement {
+        if self.limits.is_unlimited() {
+            return LimitEnforcement::None;
+        }
+        #[cfg(target_os = "linux")]
+        if self.cgroup.is_some() {
+            return LimitEnforcement::Cgroup;
+        }
+        #[cfg(target_os = "windows")]
+        if self.job.is_some() {
+            return LimitEnforcement::JobObject;
+        }
+        if cfg!(unix) {
+            LimitEnforcement::Rlimit
+        } else {
+            LimitEnforcement::None
+        }
+    }
+
+    /// Apply the limits to the calling process. Run inside a `pre_exec`
+    /// closure, before any sandbox hook that restricts file system access.
+    ///
+    /// Only async-signal-safe calls are made here.
+    #[cfg(unix)]
+    pub fn pre_exec(&self) -> std::io::Result<()> {
+        if let Some(secs) = self.limits.cpu_time_secs {
+            // SIGXCPU at the soft limit, SIGKILL a second later for children
+            // that ignore it.
+            let limit = rlimit(secs, secs.saturating_add(1));
+            if unsafe { libc::setrlimit(libc::RLIMIT_CPU, &limit) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &self.cgroup {
+            return cgroup.join();
+        }
+
+        if let Some(bytes) = self.limits.memory_bytes {
+            let limit = rlimit(bytes, bytes);
+            if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        if let Some(nice) = self.limits.cpu_weight.and_then(nice_for_cpu_weight) {
+            // Lowering priority never needs privileges; failures are harmless.
+            unsafe {
+                libc::setpriority(libc::PRIO_PROCESS, 0, nice);
+            }
+        }
+        Ok(())
+    }
+
+    /// Place a freshly spawned child in the job object.
+    #[cfg(target_os = "windows")]
+    pub fn attach(&self, process: std::os::windows::io::RawHandle) -> Result<(), SandboxError> {
+        match &self.job {
+            Some(job) => job.assign(process),
+            None => Ok(()),
+        }
+    }
+
+    /// The limit a finished child ran into, if it failed because of one.
+    pub fn violation(&self, status: &ExitStatus) -> Option<LimitViolation> {
+        if status.success() {
+            return None;
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &self.cgroup {
+            if let Some(limit_bytes) = self.limits.memory_bytes
+                && cgroup.event_count("memory.events", "oom_kill") > 0
+            {
+                return Some(LimitViolation::Memory { limit_bytes });
+            }
+            if let Some(limit) = self.limits.max_processes
+                && cgroup.event_count("pids.events", "max") > 0
+            {
+                return Some(LimitViolation::Processes { limit });
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        if let Some(job) = &self.job {
+            if let Some(limit_bytes) = self.limits.memory_bytes
+                && job.peak_memory().is_some_and(|peak| peak >= limit_bytes)
+            {
+                return Some(LimitViolation::Memory { limit_bytes });
+            }
+            if let Some(limit_secs) = self.limits.cpu_time_secs
+                && status.code() == Some(crate::windows::JOB_TIME_LIMIT_EXIT_CODE)
+            {
+                return Some(LimitViolation::CpuTime { limit_secs });
+            }
+        }
+
+        #[cfg(unix)]
+        if let Some(limit_secs) = self.limits.cpu_time_secs
+            && killed_by_cpu_limit(status)
+        {
+            return Some(LimitViolation::CpuTime { limit_secs });
+        }
+
+        None
+    }
+}
+
+#[cfg(unix)]
+fn rlimit(soft: u64, hard: u64) -> libc::rlimit {
+    libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    }
+}
+
+/// Whether the child, or the command a shell ran for it, died of `SIGXCPU`.
+/// Shells report a signalled command as exit code 128 + signal.
+#[cfg(unix)]
+fn killed_by_cpu_limit(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+
+    status.signal() == Some(libc::SIGXCPU) || status.code() == Some(128 + libc::SIGXCPU)
+}
+
+/// Map a CPU weight below the default onto a nice value (0-19). Weights at
+/// or above the default would need privileges to raise priority.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn nice_for_cpu_weight(weight: u32) -> Option<i32> {
+    if weight >= DEFAULT_CPU_WEIGHT {
+        return None;
+    }
+    let weight = weight.max(1);
+    Some(((DEFAULT_CPU_WEIGHT - weight) * 19 / (DEFAULT_CPU_WEIGHT - 1)) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_limits_are_unlimited() {
+        assert!(ResourceLimits::default().is_unlimited());
+        assert!(
+            !ResourceLimits {
+                max_processes: Some(16),
+                ..Default::default()
+            }
+            .is_unlimited()
+        );
+    }
+
+    #[test]
+    fn violation_messages_name_the_limit() {
+        let memory = LimitViolation::Memory {
+            limit_bytes: 256 * 1024 * 1024,
+        };
+        assert_eq!(memory.to_string(), "memory limit of 256 MiB exceeded");
+        let processes = LimitViolation::Processes { limit: 32 };
+        assert_eq!(processes.to_string(), "process limit of 32 reached");
+        let cpu_time = LimitViolation::CpuTime { limit_secs: 30 };
+        assert_eq!(cpu_time.to_string(), "CPU time limit of 30s exceeded");
+    }
+
+    #[test]
+    fn cpu_weight_maps_to_nice_value() {
+        assert_eq!(nice_for_cpu_weight(DEFAULT_CPU_WEIGHT), None);
+        assert_eq!(nice_for_cpu_weight(10_000), None);
+        assert_eq!(nice_for_cpu_weight(1), Some(19));
+        assert_eq!(nice_for_cpu_weight(0), Some(19));
+        assert_eq!(nice_for_cpu_weight(50), Some(9));
+    }
+}
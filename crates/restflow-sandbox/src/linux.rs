@@ -1,13 +1,14 @@
-//! Linux sandbox using Landlock (filesystem) and seccomp (network).
+//! Linux sandbox using Landlock (filesystem, TCP ports) and seccomp (network).
 //!
 //! Called from a `pre_exec` hook so only the child process is affected.
 
 use crate::SandboxError;
 use crate::SandboxPolicy;
+use crate::network::NetworkPolicy;
 
 use landlock::{
-    ABI, AccessFs, CompatLevel, Compatible, Ruleset, RulesetAttr, RulesetCreatedAttr,
-    RulesetStatus, path_beneath_rules,
+    ABI, AccessFs, AccessNet, CompatLevel, Compatible, NetPort, Ruleset, RulesetAttr,
+    RulesetCreatedAttr, RulesetStatus, path_beneath_rules,
 };
 
 /// Apply Landlock + seccomp restrictions inside the child process.
@@ -15,8 +16,10 @@ use landlock::{
 /// Must be called inside a `pre_exec` closure.
 pub(crate) fn pre_exec_hook_linux(policy: &SandboxPolicy) -> Result<(), SandboxError> {
     set_no_new_privs()?;
-    apply_landlock(policy)?;
-    apply_network_seccomp(policy)?;
+    if !matches!(policy, SandboxPolicy::NetworkOnly { .. }) {
+        apply_landlock(policy)?;
+    }
+    apply_network_policy(policy.network())?;
     Ok(())
 }
 
@@ -46,7 +49,7 @@ fn apply_landlock(policy: &SandboxPolicy) -> Result<(), SandboxError> {
         .add_rules(path_beneath_rules(&["/dev/null"], access_rw))
         .map_err(ll_err)?;
 
-    if let SandboxPolicy::WriteDir { writable_dirs } = policy {
+    if let SandboxPolicy::WriteDir { writable_dirs, .. } = policy {
         for dir in writable_dirs {
             ruleset = ruleset
                 .add_rules(path_beneath_rules(&[dir.as_path()], access_rw))
@@ -64,119 +67,231 @@ fn apply_landlock(policy: &SandboxPolicy) -> Result<(), SandboxError> {
     Ok(())
 }
 
-/// Install a seccomp BPF filter to block non-AF_UNIX socket creation.
-fn apply_network_seccomp(policy: &SandboxPolicy) -> Result<(), SandboxError> {
-    if matches!(policy, SandboxPolicy::None) {
-        return Ok(());
+/// Enforce the network policy.
+///
+/// Allow lists restrict outbound TCP ports with Landlock and reject every
+/// other socket family and protocol with seccomp, since Landlock only covers
+/// TCP. Kernels without Landlock network support (before 6.7) get the
+/// deny-all filter instead.
+fn apply_network_policy(network: &NetworkPolicy) -> Result<(), SandboxError> {
+    match network {
+        NetworkPolicy::AllowAll => Ok(()),
+        NetworkPolicy::Deny => apply_socket_filter(SocketFilter::UnixOnly),
+        NetworkPolicy::AllowList { .. } => match apply_landlock_net(&network.allowed_ports()) {
+            Ok(()) => apply_socket_filter(SocketFilter::UnixAndTcp),
+            Err(_) => apply_socket_filter(SocketFilter::UnixOnly),
+        },
     }
+}
 
-    let arch: u32 = if cfg!(target_arch = "x86_64") {
-        libc::AUDIT_ARCH_X86_64
-    } else if cfg!(target_arch = "aarch64") {
-        libc::AUDIT_ARCH_AARCH64
-    } else {
-        tracing::warn!("Seccomp network filter not supported on this architecture");
-        return Ok(());
-    };
+/// Allow outbound TCP connections to `ports` only.
+fn apply_landlock_net(ports: &[u16]) -> Result<(), SandboxError> {
+    let mut ruleset = Ruleset::default()
+        .set_compatibility(CompatLevel::HardRequirement)
+        .handle_access(AccessNet::ConnectTcp)
+        .map_err(ll_err)?
+        .create()
+        .map_err(ll_err)?;
+    for port in ports {
+        ruleset = ruleset
+            .add_rule(NetPort::new(*port, AccessNet::ConnectTcp))
+            .map_err(ll_err)?;
+    }
 
-    let sys_socket: u32 = if cfg!(target_arch = "x86_64") {
-        41
-    } else if cfg!(target_arch = "aarch64") {
-        198
-    } else {
-        return Ok(());
-    };
+    let status = ruleset.restrict_self().map_err(ll_err)?;
+    if status.ruleset != RulesetStatus::FullyEnforced {
+        return Err(SandboxError::Unavailable(
+            "Landlock network rules were not enforced".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Sockets that [`apply_socket_filter`] lets the child create.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SocketFilter {
+    /// `AF_UNIX` only.
+    UnixOnly,
+    /// `AF_UNIX`, plus `AF_INET`/`AF_INET6` stream sockets speaking TCP.
+    UnixAndTcp,
+}
+
+/// Target of a filter jump, resolved when the program is assembled.
+#[derive(Clone, Copy)]
+enum Jump {
+    Next,
+    Skip(u8),
+    Allow,
+    Deny,
+}
+
+struct Insn {
+    code: u16,
+    jt: Jump,
+    jf: Jump,
+    k: u32,
+}
+
+#[repr(C)]
+struct SockFilter {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32,
+}
 
-    let af_unix = libc::AF_UNIX as u32;
-
-    // BPF constants
-    const BPF_LD: u16 = 0x00;
-    const BPF_W: u16 = 0x00;
-    const BPF_ABS: u16 = 0x20;
-    const BPF_JMP: u16 = 0x05;
-    const BPF_JEQ: u16 = 0x10;
-    const BPF_RET: u16 = 0x06;
-    const BPF_K: u16 = 0x00;
-    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
-    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
-
-    // seccomp_data offsets
-    const OFFSET_ARCH: u32 = 4;
-    const OFFSET_NR: u32 = 0;
-    const OFFSET_ARGS_0: u32 = 16;
-
-    #[repr(C)]
-    struct SockFilter {
-        code: u16,
-        jt: u8,
-        jf: u8,
-        k: u32,
+#[repr(C)]
+struct SockFprog {
+    len: u16,
+    filter: *const SockFilter,
+}
+
+// BPF constants
+const BPF_LD: u16 = 0x00;
+const BPF_ALU: u16 = 0x04;
+const BPF_W: u16 = 0x00;
+const BPF_ABS: u16 = 0x20;
+const BPF_AND: u16 = 0x50;
+const BPF_JMP: u16 = 0x05;
+const BPF_JEQ: u16 = 0x10;
+const BPF_JGE: u16 = 0x30;
+const BPF_RET: u16 = 0x06;
+const BPF_K: u16 = 0x00;
+const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+
+// seccomp_data offsets
+const OFFSET_NR: u32 = 0;
+const OFFSET_ARCH: u32 = 4;
+const OFFSET_ARGS_0: u32 = 16;
+const OFFSET_ARGS_1: u32 = 24;
+const OFFSET_ARGS_2: u32 = 32;
+
+// Audit architectures from <linux/audit.h>
+const AUDIT_ARCH_X86_64: u32 = 0xC000_003E;
+const AUDIT_ARCH_AARCH64: u32 = 0xC000_00B7;
+/// Set in syscall numbers of the x32 ABI on x86_64.
+const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+/// `io_uring_setup`, the same on x86_64 and aarch64. io_uring can create
+/// sockets without going through `socket(2)`.
+const SYS_IO_URING_SETUP: u32 = 425;
+/// Mask of the socket type in `socket(2)`'s second argument.
+const SOCK_TYPE_MASK: u32 = 0xf;
+
+fn load(offset: u32) -> Insn {
+    Insn {
+        code: BPF_LD | BPF_W | BPF_ABS,
+        jt: Jump::Next,
+        jf: Jump::Next,
+        k: offset,
     }
+}
 
-    #[repr(C)]
-    struct SockFprog {
-        len: u16,
-        filter: *const SockFilter,
+fn jump(code: u16, k: u32, jt: Jump, jf: Jump) -> Insn {
+    Insn {
+        code: BPF_JMP | code | BPF_K,
+        jt,
+        jf,
+        k,
     }
+}
 
-    let filter: Vec<SockFilter> = vec![
-        // [0] Load arch
-        SockFilter {
-            code: BPF_LD | BPF_W | BPF_ABS,
-            jt: 0,
-            jf: 0,
-            k: OFFSET_ARCH,
-        },
-        // [1] If arch != expected -> allow (skip filter)
-        SockFilter {
-            code: BPF_JMP | BPF_JEQ | BPF_K,
-            jt: 0,
-            jf: 5,
-            k: arch,
-        },
-        // [2] Load syscall number
-        SockFilter {
-            code: BPF_LD | BPF_W | BPF_ABS,
-            jt: 0,
-            jf: 0,
-            k: OFFSET_NR,
-        },
-        // [3] If syscall != socket -> allow
-        SockFilter {
-            code: BPF_JMP | BPF_JEQ | BPF_K,
-            jt: 0,
-            jf: 3,
-            k: sys_socket,
-        },
-        // [4] Load first argument (domain)
-        SockFilter {
-            code: BPF_LD | BPF_W | BPF_ABS,
-            jt: 0,
-            jf: 0,
-            k: OFFSET_ARGS_0,
-        },
-        // [5] If domain == AF_UNIX -> allow
-        SockFilter {
-            code: BPF_JMP | BPF_JEQ | BPF_K,
-            jt: 1,
-            jf: 0,
-            k: af_unix,
-        },
-        // [6] Return EPERM
-        SockFilter {
-            code: BPF_RET | BPF_K,
-            jt: 0,
-            jf: 0,
-            k: SECCOMP_RET_ERRNO | (libc::EPERM as u32),
-        },
-        // [7] Allow
-        SockFilter {
-            code: BPF_RET | BPF_K,
-            jt: 0,
-            jf: 0,
-            k: SECCOMP_RET_ALLOW,
-        },
+/// Build the seccomp program for `mode` on a machine of audit arch `arch`.
+///
+/// Syscalls from any other arch (32-bit compat or x32) are denied, as is
+/// io_uring; `socket(2)` is allowed for the families `mode` permits.
+fn socket_filter_program(
+    mode: SocketFilter,
+    arch: u32,
+    sys_socket: u32,
+    deny_x32: bool,
+) -> Vec<SockFilter> {
+    let mut insns = vec![
+        load(OFFSET_ARCH),
+        jump(BPF_JEQ, arch, Jump::Next, Jump::Deny),
+        load(OFFSET_NR),
     ];
+    if deny_x32 {
+        insns.push(jump(BPF_JGE, X32_SYSCALL_BIT, Jump::Deny, Jump::Next));
+    }
+    insns.extend([
+        jump(BPF_JEQ, SYS_IO_URING_SETUP, Jump::Deny, Jump::Next),
+        jump(BPF_JEQ, sys_socket, Jump::Next, Jump::Allow),
+        load(OFFSET_ARGS_0),
+    ]);
+    match mode {
+        SocketFilter::UnixOnly => {
+            insns.push(jump(BPF_JEQ, libc::AF_UNIX as u32, Jump::Allow, Jump::Deny));
+        }
+        SocketFilter::UnixAndTcp => insns.extend([
+            jump(BPF_JEQ, libc::AF_UNIX as u32, Jump::Allow, Jump::Next),
+            jump(BPF_JEQ, libc::AF_INET as u32, Jump::Skip(1), Jump::Next),
+            jump(BPF_JEQ, libc::AF_INET6 as u32, Jump::Next, Jump::Deny),
+            load(OFFSET_ARGS_1),
+            Insn {
+                code: BPF_ALU | BPF_AND | BPF_K,
+                jt: Jump::Next,
+                jf: Jump::Next,
+                k: SOCK_TYPE_MASK,
+            },
+            jump(BPF_JEQ, libc::SOCK_STREAM as u32, Jump::Next, Jump::Deny),
+            // Protocol 0 picks TCP for stream sockets; SCTP and MPTCP are
+            // not covered by Landlock.
+            load(OFFSET_ARGS_2),
+            jump(BPF_JEQ, 0, Jump::Allow, Jump::Next),
+            jump(BPF_JEQ, libc::IPPROTO_TCP as u32, Jump::Allow, Jump::Deny),
+        ]),
+    }
+
+    let deny = insns.len();
+    let allow = deny + 1;
+    let mut filter: Vec<SockFilter> = insns
+        .iter()
+        .enumerate()
+        .map(|(index, insn)| {
+            let resolve = |target: Jump| match target {
+                Jump::Next => 0,
+                Jump::Skip(count) => count,
+                Jump::Deny => (deny - index - 1) as u8,
+                Jump::Allow => (allow - index - 1) as u8,
+            };
+            SockFilter {
+                code: insn.code,
+                jt: resolve(insn.jt),
+                jf: resolve(insn.jf),
+                k: insn.k,
+            }
+        })
+        .collect();
+    filter.push(SockFilter {
+        code: BPF_RET | BPF_K,
+        jt: 0,
+        jf: 0,
+        k: SECCOMP_RET_ERRNO | (libc::EPERM as u32),
+    });
+    filter.push(SockFilter {
+        code: BPF_RET | BPF_K,
+        jt: 0,
+        jf: 0,
+        k: SECCOMP_RET_ALLOW,
+    });
+    filter
+}
+
+/// Install a seccomp BPF filter restricting socket creation to `mode`.
+///
+/// Fails on architectures the filter does not know, rather than leaving
+/// the network open.
+fn apply_socket_filter(mode: SocketFilter) -> Result<(), SandboxError> {
+    let filter = if cfg!(target_arch = "x86_64") {
+        socket_filter_program(mode, AUDIT_ARCH_X86_64, 41, true)
+    } else if cfg!(target_arch = "aarch64") {
+        socket_filter_program(mode, AUDIT_ARCH_AARCH64, 198, false)
+    } else {
+        return Err(SandboxError::Unavailable(
+            "seccomp network filter not supported on this architecture".into(),
+        ));
+    };
 
     let prog = SockFprog {
         len: filter.len() as u16,
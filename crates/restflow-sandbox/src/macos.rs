@@ -5,6 +5,7 @@
 
 use crate::SandboxError;
 use crate::SandboxPolicy;
+use crate::network::NetworkPolicy;
 
 const SANDBOX_EXEC: &str = "/usr/bin/sandbox-exec";
 
@@ -37,11 +38,10 @@ fn generate_seatbelt_profile(policy: &SandboxPolicy) -> Result<String, SandboxEr
 
     match policy {
         SandboxPolicy::None => unreachable!("None policy filtered before reaching here"),
-        SandboxPolicy::ReadOnly => {
+        SandboxPolicy::ReadOnly { .. } => {
             profile.push_str("(allow file-read*)\n");
-            profile.push_str("(deny network*)\n");
         }
-        SandboxPolicy::WriteDir { writable_dirs } => {
+        SandboxPolicy::WriteDir { writable_dirs, .. } => {
             profile.push_str("(allow file-read*)\n");
             for dir in writable_dirs {
                 // Canonicalize to resolve symlinks like /var -> /private/var on macOS.
@@ -49,20 +49,50 @@ fn generate_seatbelt_profile(policy: &SandboxPolicy) -> Result<String, SandboxEr
                 let dir_str = canonical.to_string_lossy();
                 profile.push_str(&format!("(allow file-write* (subpath \"{dir_str}\"))\n"));
             }
-            profile.push_str("(deny network*)\n");
+        }
+        SandboxPolicy::NetworkOnly { .. } => {
+            profile.push_str("(allow file-read*)\n");
+            profile.push_str("(allow file-write*)\n");
         }
     }
+    push_network_rules(&mut profile, policy.network());
 
     Ok(profile)
 }
 
+fn push_network_rules(profile: &mut String, network: &NetworkPolicy) {
+    match network {
+        NetworkPolicy::Deny => profile.push_str("(deny network*)\n"),
+        NetworkPolicy::AllowAll => profile.push_str("(allow network*)\n"),
+        NetworkPolicy::AllowList { endpoints } => {
+            profile.push_str("(allow system-socket)\n");
+            // Name resolution goes through mDNSResponder.
+            profile.push_str(
+                "(allow network-outbound (remote unix-socket (path-literal \"/private/var/run/mDNSResponder\")))\n",
+            );
+            // Seatbelt only filters remote hosts by `localhost` or `*`.
+            for endpoint in endpoints {
+                let host = if endpoint.host == "localhost" {
+                    "localhost"
+                } else {
+                    "*"
+                };
+                profile.push_str(&format!(
+                    "(allow network-outbound (remote ip \"{host}:{}\"))\n",
+                    endpoint.port
+                ));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_generate_readonly_profile() {
-        let profile = generate_seatbelt_profile(&SandboxPolicy::ReadOnly).unwrap();
+        let profile = generate_seatbelt_profile(&SandboxPolicy::read_only()).unwrap();
         assert!(profile.contains("(deny default)"));
         assert!(profile.contains("(allow file-read*)"));
         assert!(profile.contains("(deny network*)"));
@@ -71,19 +101,40 @@ mod tests {
 
     #[test]
     fn test_generate_writedir_profile() {
-        let policy = SandboxPolicy::WriteDir {
-            writable_dirs: vec!["/tmp/sandbox-test".into()],
-        };
+        let policy = SandboxPolicy::write_dirs(vec!["/tmp/sandbox-test".into()]);
         let profile = generate_seatbelt_profile(&policy).unwrap();
         assert!(profile.contains("(allow file-read*)"));
         assert!(profile.contains("(allow file-write* (subpath \"/tmp/sandbox-test\"))"));
         assert!(profile.contains("(deny network*)"));
     }
 
+    #[test]
+    fn test_generate_network_allowlist_profile() {
+        let policy = SandboxPolicy::read_only().with_network(NetworkPolicy::AllowList {
+            endpoints: vec![
+                crate::NetworkEndpoint::new("localhost", 8080),
+                crate::NetworkEndpoint::any_host(443),
+            ],
+        });
+        let profile = generate_seatbelt_profile(&policy).unwrap();
+        assert!(profile.contains("(allow network-outbound (remote ip \"localhost:8080\"))"));
+        assert!(profile.contains("(allow network-outbound (remote ip \"*:443\"))"));
+        assert!(!profile.contains("(deny network*)"));
+        assert!(!profile.contains("(allow network*)"));
+    }
+
+    #[test]
+    fn test_generate_network_only_profile() {
+        let policy = SandboxPolicy::network_only(NetworkPolicy::Deny);
+        let profile = generate_seatbelt_profile(&policy).unwrap();
+        assert!(profile.contains("(allow file-write*)"));
+        assert!(profile.contains("(deny network*)"));
+    }
+
     #[test]
     fn test_wrap_command_readonly() {
         let (prog, args) =
-            wrap_command_macos(&SandboxPolicy::ReadOnly, "sh", &["-c", "echo hello"]).unwrap();
+            wrap_command_macos(&SandboxPolicy::read_only(), "sh", &["-c", "echo hello"]).unwrap();
 
         assert_eq!(prog, SANDBOX_EXEC);
         assert_eq!(args[0], "-p");
@@ -0,0 +1,187 @@
+//! Network egress policy for sandboxed processes.
+//!
+//! [`NetworkPolicy`] decides which outbound connections a sandboxed child
+//! may open:
+//!
+//! - **Linux**: `Deny` installs a seccomp filter rejecting every non-`AF_UNIX`
+//!   socket. `AllowList` restricts outbound TCP to the listed ports with
+//!   Landlock (ABI v4, Linux 6.7+) and rejects every socket other than
+//!   `AF_UNIX` and TCP with seccomp, so UDP (including DNS) stays closed.
+//!   Older kernels get the deny-all filter instead.
+//! - **macOS**: Seatbelt `network-outbound` rules per port.
+//! - **Windows**: AppContainer capabilities only grant all-or-nothing access,
+//!   so `AllowList` is enforced as `Deny`.
+//!
+//! No platform can restrict connections by host name, except `localhost` on
+//! macOS. Allow lists naming any other host are rejected by
+//! [`NetworkPolicy::validate`] rather than silently widened to every host on
+//! that port; use `*` to allow a port for any host.
+
+use crate::SandboxError;
+
+/// Outbound network access granted to a sandboxed process.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum NetworkPolicy {
+    /// No network access; Unix domain sockets stay available.
+    #[default]
+    Deny,
+
+    /// Unrestricted network access.
+    AllowAll,
+
+    /// Outbound connections only to the listed endpoints.
+    AllowList {
+        /// Destinations the child process may connect to.
+        endpoints: Vec<NetworkEndpoint>,
+    },
+}
+
+/// A destination a sandboxed process may connect to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkEndpoint {
+    /// Host name, or `*` for any host.
+    pub host: String,
+    /// TCP port.
+    pub port: u16,
+}
+
+impl NetworkEndpoint {
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Any host on `port`.
+    pub fn any_host(port: u16) -> Self {
+        Self::new("*", port)
+    }
+
+    pub fn is_any_host(&self) -> bool {
+        self.host == "*"
+    }
+}
+
+impl std::str::FromStr for NetworkEndpoint {
+    type Err = SandboxError;
+
+    /// Parse `host:port`, e.g. `*:443` or `localhost:8080`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || SandboxError::InvalidPolicy(format!("invalid network endpoint '{value}'"));
+        let (host, port) = value.trim().rsplit_once(':').ok_or_else(invalid)?;
+        let port: u16 = port.parse().map_err(|_| invalid())?;
+        if host.is_empty() || port == 0 {
+            return Err(invalid());
+        }
+        Ok(Self::new(host, port))
+    }
+}
+
+impl NetworkPolicy {
+    /// Distinct allowed ports, in policy order.
+    pub fn allowed_ports(&self) -> Vec<u16> {
+        let Self::AllowList { endpoints } = self else {
+            return Vec::new();
+        };
+        let mut ports = Vec::new();
+        for endpoint in endpoints {
+            if !ports.contains(&endpoint.port) {
+                ports.push(endpoint.port);
+            }
+        }
+        ports
+    }
+
+    /// Hosts of the allow list that this platform cannot restrict.
+    pub fn unenforced_hosts(&self) -> Vec<&str> {
+        let Self::AllowList { endpoints } = self else {
+            return Vec::new();
+        };
+        endpoints
+            .iter()
+            .filter(|endpoint| !endpoint.is_any_host())
+            .filter(|endpoint| !(cfg!(target_os = "macos") && endpoint.host == "localhost"))
+            .map(|endpoint| endpoint.host.as_str())
+            .collect()
+    }
+
+    /// Reject allow lists naming hosts this platform cannot restrict.
+    pub fn validate(&self) -> Result<(), SandboxError> {
+        let hosts = self.unenforced_hosts();
+        if hosts.is_empty() {
+            return Ok(());
+        }
+        Err(SandboxError::InvalidPolicy(format!(
+            "network allow list can only restrict ports on this platform, not hosts ({}); use '*:<port>'",
+            hosts.join(", ")
+        )))
+    }
+}
+
+/// Log the parts of `policy` the platform enforces more strictly than
+/// written.
+pub(crate) fn warn_unenforced(policy: &NetworkPolicy) {
+    if cfg!(target_os = "windows") && matches!(policy, NetworkPolicy::AllowList { .. }) {
+        tracing::warn!("Network allow lists are not supported on Windows, denying network access");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_network_policy_denies() {
+        assert_eq!(NetworkPolicy::default(), NetworkPolicy::Deny);
+        assert!(NetworkPolicy::Deny.allowed_ports().is_empty());
+    }
+
+    #[test]
+    fn allowed_ports_are_deduplicated() {
+        let policy = NetworkPolicy::AllowList {
+            endpoints: vec![
+                NetworkEndpoint::new("api.example.com", 443),
+                NetworkEndpoint::any_host(443),
+                NetworkEndpoint::any_host(80),
+            ],
+        };
+        assert_eq!(policy.allowed_ports(), vec![443, 80]);
+        assert_eq!(policy.unenforced_hosts(), vec!["api.example.com"]);
+    }
+
+    #[test]
+    fn host_specific_allow_lists_are_rejected() {
+        let policy = NetworkPolicy::AllowList {
+            endpoints: vec![
+                "*:443".parse().unwrap(),
+                "api.example.com:443".parse().unwrap(),
+            ],
+        };
+        assert!(matches!(
+            policy.validate(),
+            Err(SandboxError::InvalidPolicy(message)) if message.contains("api.example.com")
+        ));
+
+        let ports_only = NetworkPolicy::AllowList {
+            endpoints: vec![NetworkEndpoint::any_host(443)],
+        };
+        assert!(ports_only.validate().is_ok());
+        assert!(NetworkPolicy::Deny.validate().is_ok());
+    }
+
+    #[test]
+    fn endpoints_parse_from_host_and_port() {
+        assert_eq!(
+            "*:443".parse::<NetworkEndpoint>().unwrap(),
+            NetworkEndpoint::any_host(443)
+        );
+        assert_eq!(
+            "localhost:8080".parse::<NetworkEndpoint>().unwrap(),
+            NetworkEndpoint::new("localhost", 8080)
+        );
+        for invalid in ["443", ":443", "*:http", "*:0"] {
+            assert!(invalid.parse::<NetworkEndpoint>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -1,14 +1,682 @@
-//! Windows sandbox (no-op placeholder).
+//! Windows sandbox using AppContainer isolation, and job-object resource
+//! limits.
+//!
+//! `std::process::Command` cannot start a process inside an AppContainer, so
+//! the command is wrapped to re-execute the current binary as a launcher:
+//! `<current_exe> --restflow-sandbox-launch [--network] [--write <dir>]... -- <program> <args...>`.
+//! The launcher ([`run_launcher_if_requested`]) starts the program in the
+//! RestFlow AppContainer with the launcher's stdio, waits for it and exits
+//! with its exit code.
+//!
+//! An AppContainer without capabilities has no network access and may only
+//! open files whose ACLs grant its SID (or all application packages)
+//! access. `NetworkPolicy::AllowAll` adds the internet and private network
+//! client capabilities; allow lists cannot be expressed and get none. Network-only
+//! policies are refused, since the container cannot leave the file system
+//! open. The launcher grants the container read access to its working
+//! directory and full access to each writable directory; system
+//! directories stay readable through their default ACLs.
 
-use std::process::Command;
+use std::ffi::{OsStr, OsString};
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use windows_sys::Win32::Foundation::{
+    CloseHandle, ERROR_ALREADY_EXISTS, ERROR_NOT_ENOUGH_QUOTA, ERROR_SUCCESS, HANDLE, LocalFree,
+    WAIT_FAILED, WIN32_ERROR,
+};
+use windows_sys::Win32::Security::Authorization::{
+    ConvertStringSidToSidW, EXPLICIT_ACCESS_W, GRANT_ACCESS, GetEffectiveRightsFromAclW,
+    GetNamedSecurityInfoW, NO_MULTIPLE_TRUSTEE, SE_FILE_OBJECT, SetEntriesInAclW,
+    SetNamedSecurityInfoW, TRUSTEE_IS_SID, TRUSTEE_IS_UNKNOWN, TRUSTEE_W,
+};
+use windows_sys::Win32::Security::Isolation::{
+    CreateAppContainerProfile, DeriveAppContainerSidFromAppContainerName,
+};
+use windows_sys::Win32::Security::{
+    ACL, DACL_SECURITY_INFORMATION, FreeSid, PSECURITY_DESCRIPTOR, PSID, SECURITY_CAPABILITIES,
+    SID_AND_ATTRIBUTES, SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    FILE_ALL_ACCESS, FILE_GENERIC_EXECUTE, FILE_GENERIC_READ,
+};
+use windows_sys::Win32::System::Console::{
+    GetStdHandle, STD_ERROR_HANDLE, STD_INPUT_HANDLE, STD_OUTPUT_HANDLE,
+};
+use windows_sys::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_CPU_RATE_CONTROL_ENABLE,
+    JOB_OBJECT_CPU_RATE_CONTROL_WEIGHT_BASED, JOB_OBJECT_LIMIT_ACTIVE_PROCESS,
+    JOB_OBJECT_LIMIT_JOB_MEMORY, JOB_OBJECT_LIMIT_JOB_TIME, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    JOBOBJECT_CPU_RATE_CONTROL_INFORMATION, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+    JobObjectCpuRateControlInformation, JobObjectExtendedLimitInformation,
+    QueryInformationJobObject, SetInformationJobObject,
+};
+use windows_sys::Win32::System::Threading::{
+    CREATE_SUSPENDED, CreateProcessW, DeleteProcThreadAttributeList, EXTENDED_STARTUPINFO_PRESENT,
+    GetExitCodeProcess, INFINITE, InitializeProcThreadAttributeList,
+    PROC_THREAD_ATTRIBUTE_SECURITY_CAPABILITIES, PROCESS_INFORMATION, ResumeThread,
+    STARTF_USESTDHANDLES, STARTUPINFOEXW, UpdateProcThreadAttribute, WaitForSingleObject,
+};
 
 use crate::SandboxError;
 use crate::SandboxPolicy;
+use crate::limits::{DEFAULT_CPU_WEIGHT, ResourceLimits};
+use crate::network::NetworkPolicy;
+
+/// First argument of a re-executed launcher process.
+const LAUNCHER_ARG: &str = "--restflow-sandbox-launch";
+/// Launcher option naming one writable directory.
+const WRITE_ARG: &str = "--write";
+/// Launcher flag granting network access.
+const NETWORK_ARG: &str = "--network";
+/// Exit code of a launcher that failed to start the sandboxed program.
+const LAUNCH_FAILED_EXIT_CODE: i32 = 126;
+
+const APP_CONTAINER_NAME: &str = "restflow.sandbox";
+const APP_CONTAINER_DISPLAY_NAME: &str = "RestFlow Sandbox";
+
+/// `internetClient` and `privateNetworkClientServer` capability SIDs.
+const NETWORK_CAPABILITIES: [&str; 2] = ["S-1-15-3-1", "S-1-15-3-3"];
+const SE_GROUP_ENABLED: u32 = 0x4;
+
+/// `HRESULT_FROM_WIN32(ERROR_ALREADY_EXISTS)`.
+const HRESULT_ALREADY_EXISTS: i32 = (0x8007_0000 | ERROR_ALREADY_EXISTS) as i32;
+
+pub(crate) fn wrap_command_windows(
+    policy: &SandboxPolicy,
+    program: &str,
+    args: &[&str],
+) -> Result<(String, Vec<String>), SandboxError> {
+    if matches!(policy, SandboxPolicy::NetworkOnly { .. }) {
+        return Err(SandboxError::Unavailable(
+            "network-only sandboxing is not supported on Windows".into(),
+        ));
+    }
+    let launcher = std::env::current_exe()?;
+
+    let mut new_args = vec![LAUNCHER_ARG.to_string()];
+    if matches!(policy.network(), NetworkPolicy::AllowAll) {
+        new_args.push(NETWORK_ARG.to_string());
+    }
+    if let SandboxPolicy::WriteDir { writable_dirs, .. } = policy {
+        for dir in writable_dirs {
+            new_args.push(WRITE_ARG.to_string());
+            new_args.push(dir.to_string_lossy().into_owned());
+        }
+    }
+    new_args.push("--".to_string());
+    new_args.push(program.to_string());
+    new_args.extend(args.iter().map(|s| s.to_string()));
+
+    Ok((launcher.to_string_lossy().into_owned(), new_args))
+}
+
+pub(crate) fn run_launcher_if_requested() {
+    let mut args = std::env::args_os().skip(1);
+    if args.next().as_deref() != Some(OsStr::new(LAUNCHER_ARG)) {
+        return;
+    }
+
+    let code = match parse_launcher_args(args).and_then(|launch| launch.run()) {
+        Ok(code) => code as i32,
+        Err(error) => {
+            eprintln!("restflow sandbox: {error}");
+            LAUNCH_FAILED_EXIT_CODE
+        }
+    };
+    std::process::exit(code);
+}
+
+/// Program and policy passed to a launcher process.
+#[derive(Debug, PartialEq, Eq)]
+struct Launch {
+    allow_network: bool,
+    writable_dirs: Vec<PathBuf>,
+    command: Vec<OsString>,
+}
+
+fn parse_launcher_args(mut args: impl Iterator<Item = OsString>) -> Result<Launch, SandboxError> {
+    let mut allow_network = false;
+    let mut writable_dirs = Vec::new();
+    loop {
+        match args.next() {
+            Some(arg) if arg == NETWORK_ARG => allow_network = true,
+            Some(arg) if arg == WRITE_ARG => {
+                let dir = args.next().ok_or_else(|| {
+                    SandboxError::Unavailable(format!("missing directory after {WRITE_ARG}"))
+                })?;
+                writable_dirs.push(PathBuf::from(dir));
+            }
+            Some(arg) if arg == "--" => break,
+            Some(arg) => {
+                return Err(SandboxError::Unavailable(format!(
+                    "unexpected launcher argument: {}",
+                    arg.to_string_lossy()
+                )));
+            }
+            None => break,
+        }
+    }
+
+    let command: Vec<OsString> = args.collect();
+    if command.is_empty() {
+        return Err(SandboxError::Unavailable("no program to launch".into()));
+    }
+    Ok(Launch {
+        allow_network,
+        writable_dirs,
+        command,
+    })
+}
+
+impl Launch {
+    /// Start the program in the AppContainer and wait for its exit code.
+    fn run(&self) -> Result<u32, SandboxError> {
+        let container = AppContainer::open()?;
+
+        let workdir = std::env::current_dir()?;
+        grant_access(
+            &workdir,
+            container.sid,
+            FILE_GENERIC_READ | FILE_GENERIC_EXECUTE,
+        )?;
+        for dir in &self.writable_dirs {
+            grant_access(dir, container.sid, FILE_ALL_ACCESS)?;
+        }
+
+        // Killing the launcher closes this job and takes the program with it.
+        let job = JobObject::create(&ResourceLimits::default())?;
+
+        let mut attributes = ProcThreadAttributeList::new()?;
+        let capability_sids = if self.allow_network {
+            NETWORK_CAPABILITIES
+                .iter()
+                .map(|sid| CapabilitySid::parse(sid))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            Vec::new()
+        };
+        let mut capability_list: Vec<SID_AND_ATTRIBUTES> = capability_sids
+            .iter()
+            .map(|capability| SID_AND_ATTRIBUTES {
+                Sid: capability.0,
+                Attributes: SE_GROUP_ENABLED,
+            })
+            .collect();
+        let capabilities = SECURITY_CAPABILITIES {
+            AppContainerSid: container.sid,
+            Capabilities: capability_list.as_mut_ptr(),
+            CapabilityCount: capability_list.len() as u32,
+            Reserved: 0,
+        };
+        attributes.set_security_capabilities(&capabilities)?;
 
-pub(crate) fn apply_windows_sandbox(
-    _cmd: &mut Command,
-    _policy: &SandboxPolicy,
-) -> Result<(), SandboxError> {
-    tracing::warn!("OS-level sandbox not implemented on Windows, running without isolation");
+        let mut startup: STARTUPINFOEXW = unsafe { std::mem::zeroed() };
+        startup.StartupInfo.cb = std::mem::size_of::<STARTUPINFOEXW>() as u32;
+        startup.StartupInfo.dwFlags = STARTF_USESTDHANDLES;
+        startup.StartupInfo.hStdInput = unsafe { GetStdHandle(STD_INPUT_HANDLE) };
+        startup.StartupInfo.hStdOutput = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
+        startup.StartupInfo.hStdError = unsafe { GetStdHandle(STD_ERROR_HANDLE) };
+        startup.lpAttributeList = attributes.as_ptr();
+
+        let mut cmdline = wide(&command_line(&self.command));
+        let mut process: PROCESS_INFORMATION = unsafe { std::mem::zeroed() };
+        let created = unsafe {
+            CreateProcessW(
+                std::ptr::null(),
+                cmdline.as_mut_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                1,
+                EXTENDED_STARTUPINFO_PRESENT | CREATE_SUSPENDED,
+                std::ptr::null(),
+                std::ptr::null(),
+                &startup.StartupInfo,
+                &mut process,
+            )
+        };
+        if created == 0 {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        let child = OwnedHandle(process.hProcess);
+        let thread = OwnedHandle(process.hThread);
+
+        job.assign(child.0)?;
+        if unsafe { ResumeThread(thread.0) } == u32::MAX {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        if unsafe { WaitForSingleObject(child.0, INFINITE) } == WAIT_FAILED {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        let mut exit_code = 0u32;
+        if unsafe { GetExitCodeProcess(child.0, &mut exit_code) } == 0 {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        Ok(exit_code)
+    }
+}
+
+/// SID of the RestFlow AppContainer profile, created on first use.
+struct AppContainer {
+    sid: PSID,
+}
+
+impl AppContainer {
+    fn open() -> Result<Self, SandboxError> {
+        let name = wide(OsStr::new(APP_CONTAINER_NAME));
+        let display_name = wide(OsStr::new(APP_CONTAINER_DISPLAY_NAME));
+        let mut sid: PSID = std::ptr::null_mut();
+        let mut hr = unsafe {
+            CreateAppContainerProfile(
+                name.as_ptr(),
+                display_name.as_ptr(),
+                display_name.as_ptr(),
+                std::ptr::null(),
+                0,
+                &mut sid,
+            )
+        };
+        if hr == HRESULT_ALREADY_EXISTS {
+            hr = unsafe { DeriveAppContainerSidFromAppContainerName(name.as_ptr(), &mut sid) };
+        }
+        if hr < 0 {
+            return Err(SandboxError::Setup(std::io::Error::from_raw_os_error(hr)));
+        }
+        Ok(Self { sid })
+    }
+}
+
+impl Drop for AppContainer {
+    fn drop(&mut self) {
+        unsafe {
+            FreeSid(self.sid);
+        }
+    }
+}
+
+/// Capability SID parsed from its string form.
+struct CapabilitySid(PSID);
+
+impl CapabilitySid {
+    fn parse(sid: &str) -> Result<Self, SandboxError> {
+        let text = wide(OsStr::new(sid));
+        let mut psid: PSID = std::ptr::null_mut();
+        if unsafe { ConvertStringSidToSidW(text.as_ptr(), &mut psid) } == 0 {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        Ok(Self(psid))
+    }
+}
+
+impl Drop for CapabilitySid {
+    fn drop(&mut self) {
+        unsafe {
+            LocalFree(self.0);
+        }
+    }
+}
+
+/// Grant `sid` the `access` rights on `path` and everything below it.
+///
+/// Skipped when the DACL already grants them, since rewriting the DACL
+/// re-propagates inherited entries through the whole tree.
+fn grant_access(path: &Path, sid: PSID, access: u32) -> Result<(), SandboxError> {
+    let name = wide(path.as_os_str());
+    let mut dacl: *mut ACL = std::ptr::null_mut();
+    let mut descriptor: PSECURITY_DESCRIPTOR = std::ptr::null_mut();
+    let status = unsafe {
+        GetNamedSecurityInfoW(
+            name.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut dacl,
+            std::ptr::null_mut(),
+            &mut descriptor,
+        )
+    };
+    check_win32(status)?;
+
+    let trustee = TRUSTEE_W {
+        pMultipleTrustee: std::ptr::null_mut(),
+        MultipleTrusteeOperation: NO_MULTIPLE_TRUSTEE,
+        TrusteeForm: TRUSTEE_IS_SID,
+        TrusteeType: TRUSTEE_IS_UNKNOWN,
+        ptstrName: sid.cast(),
+    };
+    let mut granted = 0u32;
+    let status = unsafe { GetEffectiveRightsFromAclW(dacl, &trustee, &mut granted) };
+    let result = if status == ERROR_SUCCESS && granted & access == access {
+        Ok(())
+    } else {
+        let entry = EXPLICIT_ACCESS_W {
+            grfAccessPermissions: access,
+            grfAccessMode: GRANT_ACCESS,
+            grfInheritance: SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+            Trustee: trustee,
+        };
+        let mut new_dacl: *mut ACL = std::ptr::null_mut();
+        let mut status = unsafe { SetEntriesInAclW(1, &entry, dacl, &mut new_dacl) };
+        if status == ERROR_SUCCESS {
+            status = unsafe {
+                SetNamedSecurityInfoW(
+                    name.as_ptr(),
+                    SE_FILE_OBJECT,
+                    DACL_SECURITY_INFORMATION,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    new_dacl,
+                    std::ptr::null(),
+                )
+            };
+            unsafe {
+                LocalFree(new_dacl.cast());
+            }
+        }
+        check_win32(status)
+    };
+
+    unsafe {
+        LocalFree(descriptor);
+    }
+    result
+}
+
+/// Attribute list carrying the AppContainer security capabilities.
+struct ProcThreadAttributeList {
+    // usize elements keep the opaque list pointer-aligned.
+    buffer: Vec<usize>,
+}
+
+impl ProcThreadAttributeList {
+    fn new() -> Result<Self, SandboxError> {
+        let mut size = 0usize;
+        // The sizing call fails with ERROR_INSUFFICIENT_BUFFER by design.
+        unsafe {
+            InitializeProcThreadAttributeList(std::ptr::null_mut(), 1, 0, &mut size);
+        }
+        let mut buffer = vec![0usize; size.div_ceil(std::mem::size_of::<usize>())];
+        if unsafe { InitializeProcThreadAttributeList(buffer.as_mut_ptr().cast(), 1, 0, &mut size) }
+            == 0
+        {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        Ok(Self { buffer })
+    }
+
+    fn as_ptr(&mut self) -> *mut std::ffi::c_void {
+        self.buffer.as_mut_ptr().cast()
+    }
+
+    /// `capabilities` must outlive the process creation using this list.
+    fn set_security_capabilities(
+        &mut self,
+        capabilities: &SECURITY_CAPABILITIES,
+    ) -> Result<(), SandboxError> {
+        let ok = unsafe {
+            UpdateProcThreadAttribute(
+                self.as_ptr(),
+                0,
+                PROC_THREAD_ATTRIBUTE_SECURITY_CAPABILITIES as usize,
+                (capabilities as *const SECURITY_CAPABILITIES).cast(),
+                std::mem::size_of::<SECURITY_CAPABILITIES>(),
+                std::ptr::null_mut(),
+                std::ptr::null(),
+            )
+        };
+        if ok == 0 {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ProcThreadAttributeList {
+    fn drop(&mut self) {
+        unsafe {
+            DeleteProcThreadAttributeList(self.as_ptr());
+        }
+    }
+}
+
+struct OwnedHandle(HANDLE);
+
+impl Drop for OwnedHandle {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.0);
+        }
+    }
+}
+
+fn check_win32(status: WIN32_ERROR) -> Result<(), SandboxError> {
+    if status != ERROR_SUCCESS {
+        return Err(SandboxError::Setup(std::io::Error::from_raw_os_error(
+            status as i32,
+        )));
+    }
     Ok(())
 }
+
+/// Null-terminated UTF-16 copy of `value`.
+fn wide(value: &OsStr) -> Vec<u16> {
+    value.encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// Join arguments into a command line that `CommandLineToArgvW` and the MSVC
+/// runtime split back into the same arguments.
+fn command_line(args: &[OsString]) -> OsString {
+    let mut line = String::new();
+    for (index, arg) in args.iter().enumerate() {
+        if index > 0 {
+            line.push(' ');
+        }
+        push_quoted(&mut line, &arg.to_string_lossy());
+    }
+    line.into()
+}
+
+fn push_quoted(line: &mut String, arg: &str) {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '\u{b}', '"']) {
+        line.push_str(arg);
+        return;
+    }
+
+    line.push('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                line.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                line.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                line.extend(std::iter::repeat_n('\\', backslashes));
+                line.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    line.extend(std::iter::repeat_n('\\', backslashes * 2));
+    line.push('"');
+}
+
+/// Job object holding one tool process tree. Closing the handle kills every
+/// process still assigned to it.
+pub(crate) struct JobObject {
+    handle: HANDLE,
+}
+
+// Job object handles may be used from any thread.
+unsafe impl Send for JobObject {}
+unsafe impl Sync for JobObject {}
+
+impl JobObject {
+    pub(crate) fn create(limits: &ResourceLimits) -> Result<Self, SandboxError> {
+        let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+        if handle.is_null() {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        let job = Self { handle };
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        if let Some(bytes) = limits.memory_bytes {
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+            info.JobMemoryLimit = bytes as usize;
+        }
+        if let Some(max) = limits.max_processes {
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_ACTIVE_PROCESS;
+            info.BasicLimitInformation.ActiveProcessLimit = max.max(1);
+        }
+        if let Some(secs) = limits.cpu_time_secs {
+            // User-mode time of the whole job, in 100-nanosecond ticks.
+            info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_TIME;
+            info.BasicLimitInformation.PerJobUserTimeLimit =
+                secs.saturating_mul(10_000_000).min(i64::MAX as u64) as i64;
+        }
+        job.set_information(JobObjectExtendedLimitInformation, &info)?;
+
+        if let Some(weight) = limits.cpu_weight {
+            let mut rate: JOBOBJECT_CPU_RATE_CONTROL_INFORMATION = unsafe { std::mem::zeroed() };
+            rate.ControlFlags =
+                JOB_OBJECT_CPU_RATE_CONTROL_ENABLE | JOB_OBJECT_CPU_RATE_CONTROL_WEIGHT_BASED;
+            rate.Anonymous.Weight = job_cpu_weight(weight);
+            job.set_information(JobObjectCpuRateControlInformation, &rate)?;
+        }
+        Ok(job)
+    }
+
+    pub(crate) fn assign(
+        &self,
+        process: std::os::windows::io::RawHandle,
+    ) -> Result<(), SandboxError> {
+        if unsafe { AssignProcessToJobObject(self.handle, process as HANDLE) } == 0 {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    /// Highest memory use of the whole job, in bytes.
+    pub(crate) fn peak_memory(&self) -> Option<u64> {
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+        let ok = unsafe {
+            QueryInformationJobObject(
+                self.handle,
+                JobObjectExtendedLimitInformation,
+                (&mut info as *mut JOBOBJECT_EXTENDED_LIMIT_INFORMATION).cast(),
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                std::ptr::null_mut(),
+            )
+        };
+        (ok != 0).then_some(info.PeakJobMemoryUsed as u64)
+    }
+
+    fn set_information<T>(&self, class: i32, info: &T) -> Result<(), SandboxError> {
+        let ok = unsafe {
+            SetInformationJobObject(
+                self.handle,
+                class,
+                (info as *const T).cast(),
+                std::mem::size_of::<T>() as u32,
+            )
+        };
+        if ok == 0 {
+            return Err(SandboxError::Setup(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for JobObject {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.handle);
+        }
+    }
+}
+
+/// Exit code of every process in a job that exceeded its CPU time limit.
+pub(crate) const JOB_TIME_LIMIT_EXIT_CODE: i32 = ERROR_NOT_ENOUGH_QUOTA as i32;
+
+/// Map a cgroup-style CPU weight (1-10000, default 100) onto the job object
+/// weight range (1-9, default 5).
+fn job_cpu_weight(weight: u32) -> u32 {
+    let weight = weight.clamp(1, 10_000);
+    if weight < DEFAULT_CPU_WEIGHT {
+        1 + (weight - 1) * 4 / (DEFAULT_CPU_WEIGHT - 1)
+    } else {
+        5 + (weight - DEFAULT_CPU_WEIGHT) * 4 / (10_000 - DEFAULT_CPU_WEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn os_args<S: AsRef<str>>(args: &[S]) -> Vec<OsString> {
+        args.iter()
+            .map(|arg| OsString::from(arg.as_ref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_command_reexecutes_launcher() {
+        let policy = SandboxPolicy::write_dirs(vec![PathBuf::from(r"C:\work")]);
+        let (program, args) = wrap_command_windows(&policy, "sh", &["-c", "echo hi"]).unwrap();
+        assert_eq!(PathBuf::from(program), std::env::current_exe().unwrap());
+        assert_eq!(
+            args,
+            vec![
+                LAUNCHER_ARG,
+                WRITE_ARG,
+                r"C:\work",
+                "--",
+                "sh",
+                "-c",
+                "echo hi"
+            ]
+        );
+
+        let launch = parse_launcher_args(os_args(&args[1..]).into_iter()).unwrap();
+        assert!(!launch.allow_network);
+        assert_eq!(launch.writable_dirs, vec![PathBuf::from(r"C:\work")]);
+        assert_eq!(launch.command, os_args(&["sh", "-c", "echo hi"]));
+    }
+
+    #[test]
+    fn test_wrap_command_passes_network_access() {
+        let policy = SandboxPolicy::read_only().with_network(NetworkPolicy::AllowAll);
+        let (_, args) = wrap_command_windows(&policy, "sh", &[]).unwrap();
+        assert_eq!(args[1], NETWORK_ARG);
+        assert!(
+            parse_launcher_args(os_args(&args[1..]).into_iter())
+                .unwrap()
+                .allow_network
+        );
+    }
+
+    #[test]
+    fn test_parse_launcher_args_rejects_missing_program() {
+        assert!(parse_launcher_args(os_args(&["--"]).into_iter()).is_err());
+        assert!(parse_launcher_args(os_args(&[WRITE_ARG]).into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_command_line_quoting() {
+        let line = command_line(&os_args(&[
+            "sh",
+            "-c",
+            r#"echo "a b" \ c\"#,
+            "",
+            r"C:\dir with space\",
+        ]));
+        assert_eq!(
+            line,
+            OsString::from(r#"sh -c "echo \"a b\" \ c\\" "" "C:\dir with space\\""#)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 //! Integration tests for restflow-sandbox.
 
-use restflow_sandbox::{SandboxPolicy, pre_exec_hook, wrap_command};
+use restflow_sandbox::{
+    LimitEnforcement, LimitViolation, ResourceLimits, SandboxPolicy, pre_exec_hook, wrap_command,
+};
 use std::process::Command;
 
 // ─── Cross-platform tests ───────────────────────────────────────────────
@@ -20,10 +22,8 @@ fn test_sandbox_none_allows_everything() {
 fn test_sandbox_api_compiles() {
     let policies = vec![
         SandboxPolicy::None,
-        SandboxPolicy::ReadOnly,
-        SandboxPolicy::WriteDir {
-            writable_dirs: vec!["/tmp".into()],
-        },
+        SandboxPolicy::read_only(),
+        SandboxPolicy::write_dirs(vec!["/tmp".into()]),
     ];
 
     for policy in &policies {
@@ -37,18 +37,94 @@ fn test_sandbox_api_compiles() {
 
 #[test]
 fn test_policy_display() {
-    let policy = SandboxPolicy::ReadOnly;
+    let policy = SandboxPolicy::read_only();
     let debug_str = format!("{policy:?}");
     assert!(debug_str.contains("ReadOnly"));
 
-    let policy = SandboxPolicy::WriteDir {
-        writable_dirs: vec!["/tmp/test".into()],
-    };
+    let policy = SandboxPolicy::write_dirs(vec!["/tmp/test".into()]);
     let debug_str = format!("{policy:?}");
     assert!(debug_str.contains("WriteDir"));
     assert!(debug_str.contains("/tmp/test"));
 }
 
+#[test]
+fn test_host_specific_allow_list_is_rejected() {
+    use restflow_sandbox::{NetworkEndpoint, NetworkPolicy, SandboxError};
+
+    let policy = SandboxPolicy::network_only(NetworkPolicy::AllowList {
+        endpoints: vec![NetworkEndpoint::new("api.example.com", 443)],
+    });
+    assert!(matches!(
+        wrap_command(&policy, "true", &[]),
+        Err(SandboxError::InvalidPolicy(_))
+    ));
+}
+
+#[test]
+fn test_unlimited_resource_guard_is_noop() {
+    let guard = ResourceLimits::default().prepare().unwrap();
+    assert_eq!(guard.enforcement(), LimitEnforcement::None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_resource_limits_allow_small_commands() {
+    use std::os::unix::process::CommandExt;
+    use std::sync::Arc;
+
+    let limits = ResourceLimits {
+        memory_bytes: Some(512 * 1024 * 1024),
+        cpu_weight: Some(50),
+        max_processes: Some(64),
+        cpu_time_secs: Some(60),
+    };
+    let guard = Arc::new(limits.prepare().unwrap());
+    assert_ne!(guard.enforcement(), LimitEnforcement::None);
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg("echo limited");
+    let hook_guard = guard.clone();
+    unsafe {
+        cmd.pre_exec(move || hook_guard.pre_exec());
+    }
+
+    let output = cmd.output().expect("failed to run limited command");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("limited"));
+    assert_eq!(guard.violation(&output.status), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cpu_time_limit_stops_busy_loop() {
+    use std::os::unix::process::CommandExt;
+    use std::sync::Arc;
+
+    let limits = ResourceLimits {
+        cpu_time_secs: Some(1),
+        ..Default::default()
+    };
+    let guard = Arc::new(limits.prepare().unwrap());
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg("while :; do :; done");
+    let hook_guard = guard.clone();
+    unsafe {
+        cmd.pre_exec(move || hook_guard.pre_exec());
+    }
+
+    let status = cmd.status().expect("failed to run limited command");
+    assert!(!status.success());
+    assert_eq!(
+        guard.violation(&status),
+        Some(LimitViolation::CpuTime { limit_secs: 1 })
+    );
+}
+
 // ─── macOS-specific tests ───────────────────────────────────────────────
 
 #[cfg(target_os = "macos")]
@@ -62,7 +138,7 @@ mod macos_tests {
         let test_file = tmp.path().join("should_not_exist.txt");
 
         let (prog, args) = wrap_command(
-            &SandboxPolicy::ReadOnly,
+            &SandboxPolicy::read_only(),
             "sh",
             &["-c", &format!("touch {}", test_file.display())],
         )
@@ -82,7 +158,7 @@ mod macos_tests {
     #[test]
     fn test_readonly_allows_read() {
         let (prog, args) =
-            wrap_command(&SandboxPolicy::ReadOnly, "sh", &["-c", "cat /etc/hosts"]).unwrap();
+            wrap_command(&SandboxPolicy::read_only(), "sh", &["-c", "cat /etc/hosts"]).unwrap();
 
         let output = Command::new(&prog).args(&args).output().unwrap();
         assert!(
@@ -105,9 +181,7 @@ mod macos_tests {
         let blocked_file = blocked_tmp.path().join("blocked.txt");
 
         // Test: writing to the allowed directory should succeed.
-        let policy = SandboxPolicy::WriteDir {
-            writable_dirs: vec![writable_tmp.path().to_path_buf()],
-        };
+        let policy = SandboxPolicy::write_dirs(vec![writable_tmp.path().to_path_buf()]);
         let (prog, args) = wrap_command(
             &policy,
             "sh",
@@ -124,9 +198,7 @@ mod macos_tests {
         assert!(allowed_file.exists(), "allowed file should be created");
 
         // Test: writing outside the allowed directory should fail.
-        let policy2 = SandboxPolicy::WriteDir {
-            writable_dirs: vec![writable_tmp.path().to_path_buf()],
-        };
+        let policy2 = SandboxPolicy::write_dirs(vec![writable_tmp.path().to_path_buf()]);
         let (prog2, args2) = wrap_command(
             &policy2,
             "sh",
@@ -148,6 +220,7 @@ mod macos_tests {
 #[cfg(target_os = "linux")]
 mod linux_tests {
     use super::*;
+    use restflow_sandbox::NetworkPolicy;
     use std::os::unix::process::CommandExt;
     use tempfile::TempDir;
 
@@ -161,7 +234,7 @@ mod linux_tests {
         cmd.arg("-c").arg(format!("touch {test_file_str}"));
         unsafe {
             cmd.pre_exec(|| {
-                pre_exec_hook(&SandboxPolicy::ReadOnly)
+                pre_exec_hook(&SandboxPolicy::read_only())
                     .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
             });
         }
@@ -181,7 +254,7 @@ mod linux_tests {
         );
         unsafe {
             cmd.pre_exec(|| {
-                pre_exec_hook(&SandboxPolicy::ReadOnly)
+                pre_exec_hook(&SandboxPolicy::read_only())
                     .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
             });
         }
@@ -200,6 +273,77 @@ mod linux_tests {
         );
     }
 
+    #[test]
+    fn test_network_allow_all_permits_sockets() {
+        let mut cmd = Command::new("python3");
+        cmd.arg("-c")
+            .arg("import socket; socket.socket(socket.AF_INET, socket.SOCK_STREAM)");
+        unsafe {
+            cmd.pre_exec(|| {
+                let policy = SandboxPolicy::read_only().with_network(NetworkPolicy::AllowAll);
+                pre_exec_hook(&policy)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+            });
+        }
+
+        let Ok(output) = cmd.output() else {
+            return; // python3 unavailable
+        };
+        assert!(
+            output.status.success(),
+            "socket creation should be allowed, stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn run_python_sandboxed(policy: SandboxPolicy, script: &str) -> Option<std::process::Output> {
+        let mut cmd = Command::new("python3");
+        cmd.arg("-c").arg(script);
+        unsafe {
+            cmd.pre_exec(move || {
+                pre_exec_hook(&policy).map_err(|e| std::io::Error::other(e.to_string()))
+            });
+        }
+        cmd.output().ok()
+    }
+
+    #[test]
+    fn test_allow_list_blocks_udp_sockets() {
+        let policy = SandboxPolicy::read_only().with_network(NetworkPolicy::AllowList {
+            endpoints: vec![restflow_sandbox::NetworkEndpoint::any_host(443)],
+        });
+        let Some(output) = run_python_sandboxed(
+            policy,
+            "import socket; socket.socket(socket.AF_INET, socket.SOCK_DGRAM)",
+        ) else {
+            return; // python3 unavailable
+        };
+        assert!(
+            !output.status.success(),
+            "UDP socket creation should be blocked under an allow list"
+        );
+    }
+
+    #[test]
+    fn test_network_only_denies_network_but_allows_writes() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("written.txt");
+        let script = format!(
+            "open({:?}, 'w').write('ok')\nimport socket\nsocket.socket(socket.AF_INET, socket.SOCK_STREAM)",
+            file.display().to_string()
+        );
+        let Some(output) =
+            run_python_sandboxed(SandboxPolicy::network_only(NetworkPolicy::Deny), &script)
+        else {
+            return; // python3 unavailable
+        };
+        assert!(file.exists(), "writes should not be restricted");
+        assert!(
+            !output.status.success(),
+            "socket creation should be blocked"
+        );
+    }
+
     #[test]
     fn test_writedir_allows_specified_dir() {
         let writable_tmp = TempDir::new().unwrap();
@@ -211,9 +355,7 @@ mod linux_tests {
         cmd.arg("-c").arg(format!("echo ok > {allowed_file_str}"));
         unsafe {
             cmd.pre_exec(move || {
-                let policy = SandboxPolicy::WriteDir {
-                    writable_dirs: vec![writable_dir.clone()],
-                };
+                let policy = SandboxPolicy::write_dirs(vec![writable_dir.clone()]);
                 pre_exec_hook(&policy)
                     .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
             });
@@ -14,64 +14,42 @@ impl AgentStorage {
     /// This operation is atomic - the ID resolution and delete happen
     /// within the same write transaction to prevent TOCTOU race conditions.
     pub fn delete_atomically(&self, id_or_prefix: &str) -> anyhow::Result<(bool, Option<String>)> {
-        use redb::ReadableTable;
-
         let id = id_or_prefix.trim();
         if id.is_empty() {
             anyhow::bail!("Agent ID is empty");
         }
 
-        let write_txn = self.db.begin_write()?;
-        let (existed, resolved_id) = {
-            let mut table = write_txn.open_table(Self::TABLE)?;
-
+        self.backend.write_table(Self::TABLE, |table| {
             // First try exact match within the write transaction
-            if table.get(id)?.is_some() {
-                table.remove(id)?;
-                (true, Some(id.to_string()))
-            } else {
-                // Try prefix resolution within the same transaction
-                let matches: Vec<String> = table
-                    .iter()?
-                    .filter_map(|item| {
-                        item.ok().and_then(|(key, _)| {
-                            let key_str = key.value().to_string();
-                            if key_str.starts_with(id) {
-                                Some(key_str)
-                            } else {
-                                None
-                            }
-                        })
-                    })
-                    .collect();
-
-                match matches.len() {
-                    0 => (false, None),
-                    1 => {
-                        let resolved = matches.into_iter().next().unwrap();
-                        table.remove(resolved.as_str())?;
-                        (true, Some(resolved))
-                    }
-                    _ => {
-                        let preview = matches
-                            .iter()
-                            .take(5)
-                            .cloned()
-                            .collect::<Vec<_>>()
-                            .join(", ");
-                        anyhow::bail!(
-                            "Agent ID prefix '{}' is ambiguous ({} matches: {})",
-                            id,
-                            matches.len(),
-                            preview
-                        )
-                    }
-                }
+            if table.remove(id)? {
+                return Ok((true, Some(id.to_string())));
             }
-        };
-        write_txn.commit()?;
 
-        Ok((existed, resolved_id))
+            // Try prefix resolution within the same transaction
+            let matches = table.keys_with_prefix(id)?;
+            match matches.len() {
+                0 => Ok((false, None)),
+                1 => {
+                    let resolved = matches.into_iter().next().unwrap();
+                    table.remove(resolved.as_str())?;
+                    Ok((true, Some(resolved)))
+                }
+                _ => {
+                    let preview = matches
+                        .iter()
+                        .take(5)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    anyhow::bail!(
+                        "Agent ID prefix '{}' is ambiguous ({} matches: {})",
+                        id,
+                        matches.len(),
+                        preview
+                    )
+                }
+            }
+        })
     }
 }
 
@@ -0,0 +1,6 @@
+use crate::define_simple_storage;
+
+define_simple_storage! {
+    /// Byte-level storage for REST API connectors, keyed by connector name.
+    pub struct ApiConnectorStorage { table: "api_connectors" }
+}
@@ -0,0 +1,370 @@
+//! Encryption at rest for stored values.
+//!
+//! When `storage.encrypt_at_rest` is enabled, chat sessions and memory are
+//! sealed with AES-256-GCM before they reach the database. Sealed values
+//! carry a magic prefix, so plaintext written before encryption was enabled
+//! stays readable and can be sealed in place (see
+//! [`EncryptedBackend::seal_existing`] and
+//! [`MemoryStorage::seal_existing`](crate::MemoryStorage::seal_existing)).
+//! Sealing runs once after encryption is turned on; completion is recorded
+//! under [`AT_REST_SEALED_STATE_KEY`] and cleared when it is turned off.
+//!
+//! Only chat sessions and memory are covered. Other tables that can hold
+//! user content (task inputs and messages, deliverables, execution traces,
+//! checkpoints, workspace notes, terminal sessions) stay plaintext, as do
+//! keys, index tables and memory vectors.
+
+use anyhow::{Result, bail};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::SimpleStorage;
+use crate::backend::{
+    ReadTransaction, StorageBackend, StorageBackendKind, TableRead, TableWrite, WriteTransaction,
+};
+use crate::encryption::SecretEncryptor;
+
+/// Prefix marking a sealed value.
+pub const SEALED_VALUE_MAGIC: &[u8; 6] = b"RFENC1";
+
+/// Daemon state key holding the time (ms) existing values were last sealed;
+/// zero or absent while sealing is still pending.
+pub const AT_REST_SEALED_STATE_KEY: &str = "at_rest_sealed_at";
+
+/// Backend tables sealed by [`EncryptedBackend`].
+pub const ENCRYPTED_TABLES: &[&str] = &[<crate::ChatSessionStorage as SimpleStorage>::TABLE];
+
+/// AES-256-GCM cipher for table values.
+#[derive(Clone)]
+pub struct ValueCipher {
+    encryptor: Arc<SecretEncryptor>,
+}
+
+impl fmt::Debug for ValueCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueCipher").finish_non_exhaustive()
+    }
+}
+
+impl ValueCipher {
+    pub fn new(key: &[u8; 32]) -> Result<Self> {
+        Ok(Self {
+            encryptor: Arc::new(SecretEncryptor::new(key)?),
+        })
+    }
+
+    /// Whether `stored` was produced by [`seal`](Self::seal).
+    pub fn is_sealed(stored: &[u8]) -> bool {
+        stored.starts_with(SEALED_VALUE_MAGIC)
+    }
+
+    /// Encrypt `plaintext` for storage.
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let ciphertext = self.encryptor.encrypt(plaintext)?;
+        let mut sealed = Vec::with_capacity(SEALED_VALUE_MAGIC.len() + ciphertext.len());
+        sealed.extend_from_slice(SEALED_VALUE_MAGIC);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt a stored value. Plaintext values are returned unchanged.
+    pub fn open(&self, stored: &[u8]) -> Result<Vec<u8>> {
+        match stored.strip_prefix(SEALED_VALUE_MAGIC.as_slice()) {
+            Some(ciphertext) => self.encryptor.decrypt(ciphertext),
+            None => Ok(stored.to_vec()),
+        }
+    }
+}
+
+/// Open a stored value with an optional cipher.
+///
+/// Fails with a clear message when the value is sealed but no key is loaded.
+pub fn open_value(cipher: Option<&ValueCipher>, stored: &[u8]) -> Result<Vec<u8>> {
+    match cipher {
+        Some(cipher) => cipher.open(stored),
+        None if ValueCipher::is_sealed(stored) => {
+            bail!("Value is encrypted; enable storage.encrypt_at_rest to read it")
+        }
+        None => Ok(stored.to_vec()),
+    }
+}
+
+/// [`StorageBackend`] wrapper that seals values of [`ENCRYPTED_TABLES`].
+#[derive(Debug)]
+pub struct EncryptedBackend {
+    inner: Arc<dyn StorageBackend>,
+    cipher: ValueCipher,
+}
+
+impl EncryptedBackend {
+    pub fn new(inner: Arc<dyn StorageBackend>, cipher: ValueCipher) -> Self {
+        Self { inner, cipher }
+    }
+
+    /// The wrapped backend, which sees sealed values as stored.
+    pub fn inner(&self) -> &Arc<dyn StorageBackend> {
+        &self.inner
+    }
+
+    fn encrypts(table: &str) -> bool {
+        ENCRYPTED_TABLES.contains(&table)
+    }
+
+    /// Seal plaintext values left from before encryption was enabled.
+    ///
+    /// Scans every value, so callers run it once per switch to encryption.
+    /// Returns the number of values rewritten.
+    pub fn seal_existing(&self) -> Result<usize> {
+        let mut sealed = 0;
+        for table in ENCRYPTED_TABLES {
+            self.inner.ensure_table(table)?;
+            sealed += self.inner.write_table(table, |writer| {
+                let mut count = 0;
+                for (key, value) in writer.scan_prefix("")? {
+                    if !ValueCipher::is_sealed(&value) {
+                        writer.put(&key, &self.cipher.seal(&value)?)?;
+                        count += 1;
+                    }
+                }
+                Ok(count)
+            })?;
+        }
+        Ok(sealed)
+    }
+}
+
+struct SealedReader<'a> {
+    inner: Box<dyn TableRead + 'a>,
+    cipher: &'a ValueCipher,
+}
+
+impl TableRead for SealedReader<'_> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.inner
+            .get(key)?
+            .map(|value| self.cipher.open(&value))
+            .transpose()
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        self.inner
+            .scan_prefix(prefix)?
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.cipher.open(&value)?)))
+            .collect()
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inner.keys_with_prefix(prefix)
+    }
+
+    fn count(&self) -> Result<usize> {
+        self.inner.count()
+    }
+}
+
+struct SealedWriter<'a> {
+    inner: Box<dyn TableWrite + 'a>,
+    cipher: &'a ValueCipher,
+}
+
+impl TableRead for SealedWriter<'_> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.inner
+            .get(key)?
+            .map(|value| self.cipher.open(&value))
+            .transpose()
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        self.inner
+            .scan_prefix(prefix)?
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.cipher.open(&value)?)))
+            .collect()
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inner.keys_with_prefix(prefix)
+    }
+
+    fn count(&self) -> Result<usize> {
+        self.inner.count()
+    }
+}
+
+impl TableWrite for SealedWriter<'_> {
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<()> {
+        let sealed = self.cipher.seal(value)?;
+        self.inner.put(key, &sealed)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<bool> {
+        self.inner.remove(key)
+    }
+}
+
+struct SealedReadTransaction<'a> {
+    inner: &'a dyn ReadTransaction,
+    cipher: &'a ValueCipher,
+}
+
+impl ReadTransaction for SealedReadTransaction<'_> {
+    fn table(&self, table: &str) -> Result<Box<dyn TableRead + '_>> {
+        let inner = self.inner.table(table)?;
+        if !EncryptedBackend::encrypts(table) {
+            return Ok(inner);
+        }
+        Ok(Box::new(SealedReader {
+            inner,
+            cipher: self.cipher,
+        }))
+    }
+}
+
+struct SealedWriteTransaction<'a> {
+    inner: &'a dyn WriteTransaction,
+    cipher: &'a ValueCipher,
+}
+
+impl WriteTransaction for SealedWriteTransaction<'_> {
+    fn table(&self, table: &str) -> Result<Box<dyn TableWrite + '_>> {
+        let inner = self.inner.table(table)?;
+        if !EncryptedBackend::encrypts(table) {
+            return Ok(inner);
+        }
+        Ok(Box::new(SealedWriter {
+            inner,
+            cipher: self.cipher,
+        }))
+    }
+
+    fn clear(&self, table: &str) -> Result<()> {
+        self.inner.clear(table)
+    }
+}
+
+impl StorageBackend for EncryptedBackend {
+    fn kind(&self) -> StorageBackendKind {
+        self.inner.kind()
+    }
+
+    fn ensure_table(&self, table: &str) -> Result<()> {
+        self.inner.ensure_table(table)
+    }
+
+    fn table_names(&self) -> Result<Vec<String>> {
+        self.inner.table_names()
+    }
+
+    fn read_txn(&self, f: &mut dyn FnMut(&dyn ReadTransaction) -> Result<()>) -> Result<()> {
+        self.inner.read_txn(&mut |txn| {
+            f(&SealedReadTransaction {
+                inner: txn,
+                cipher: &self.cipher,
+            })
+        })
+    }
+
+    fn write_txn(&self, f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>) -> Result<()> {
+        self.inner.write_txn(&mut |txn| {
+            f(&SealedWriteTransaction {
+                inner: txn,
+                cipher: &self.cipher,
+            })
+        })
+    }
+
+    fn write_txn_with_savepoint(
+        &self,
+        f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>,
+    ) -> Result<Option<u64>> {
+        self.inner.write_txn_with_savepoint(&mut |txn| {
+            f(&SealedWriteTransaction {
+                inner: txn,
+                cipher: &self.cipher,
+            })
+        })
+    }
+
+    fn delete_savepoint(&self, savepoint_id: u64) -> Result<bool> {
+        self.inner.delete_savepoint(savepoint_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChatSessionStorage, RedbBackend, SkillStorage};
+    use redb::Database;
+    use tempfile::tempdir;
+
+    fn cipher() -> ValueCipher {
+        ValueCipher::new(&[7u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let cipher = cipher();
+        let sealed = cipher.seal(b"{\"id\":\"s1\"}").unwrap();
+        assert!(ValueCipher::is_sealed(&sealed));
+        assert_eq!(cipher.open(&sealed).unwrap(), b"{\"id\":\"s1\"}");
+        assert_eq!(
+            cipher.open(b"{\"plain\":true}").unwrap(),
+            b"{\"plain\":true}"
+        );
+        assert!(open_value(None, &sealed).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_backend_seals_only_listed_tables() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("at-rest.db")).unwrap());
+        let raw: Arc<dyn StorageBackend> = Arc::new(RedbBackend::new(db));
+        let encrypted: Arc<dyn StorageBackend> =
+            Arc::new(EncryptedBackend::new(raw.clone(), cipher()));
+
+        let sessions = ChatSessionStorage::with_backend(encrypted.clone()).unwrap();
+        sessions.put_raw("s1", b"{\"title\":\"hello\"}").unwrap();
+        let skills = SkillStorage::with_backend(encrypted).unwrap();
+        skills.put_raw("k1", b"skill").unwrap();
+
+        assert_eq!(
+            sessions.get_raw("s1").unwrap(),
+            Some(b"{\"title\":\"hello\"}".to_vec())
+        );
+        let stored = ChatSessionStorage::with_backend(raw.clone())
+            .unwrap()
+            .get_raw("s1")
+            .unwrap()
+            .unwrap();
+        assert!(ValueCipher::is_sealed(&stored));
+        let stored_skill = SkillStorage::with_backend(raw)
+            .unwrap()
+            .get_raw("k1")
+            .unwrap();
+        assert_eq!(stored_skill, Some(b"skill".to_vec()));
+    }
+
+    #[test]
+    fn test_seal_existing_migrates_plaintext() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(dir.path().join("migrate.db")).unwrap());
+        let raw: Arc<dyn StorageBackend> = Arc::new(RedbBackend::new(db));
+        let plain = ChatSessionStorage::with_backend(raw.clone()).unwrap();
+        plain.put_raw("legacy", b"{\"title\":\"old\"}").unwrap();
+
+        let backend = EncryptedBackend::new(raw, cipher());
+        assert_eq!(backend.seal_existing().unwrap(), 1);
+        assert_eq!(backend.seal_existing().unwrap(), 0);
+        assert!(ValueCipher::is_sealed(
+            &plain.get_raw("legacy").unwrap().unwrap()
+        ));
+
+        let sessions = ChatSessionStorage::with_backend(Arc::new(backend)).unwrap();
+        assert_eq!(
+            sessions.get_raw("legacy").unwrap(),
+            Some(b"{\"title\":\"old\"}".to_vec())
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! One-shot copy of a redb database into another backend.
+
+use anyhow::{Context, Result};
+use redb::Database;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{ReadTransaction, RedbBackend, StorageBackend, WriteTransaction};
+use crate::backup::BackupSummary;
+use crate::{DaemonStateStorage, SimpleStorage};
+
+/// Daemon state key holding the time (ms) the redb tables were copied in;
+/// zero or absent while the copy is still pending.
+pub const REDB_MIGRATED_STATE_KEY: &str = "redb_migrated_at";
+
+/// Copy every table of the redb database at `db_path` into `backend`.
+///
+/// Runs once: completion is recorded under [`REDB_MIGRATED_STATE_KEY`] in the
+/// same transaction as the copy, and later calls return `None` without
+/// opening the redb file. Rows are merged per key: a key that already exists
+/// in `backend` keeps its value, and every other redb row is copied, so a
+/// table that received writes before the migration ran still gets the rest
+/// of its redb rows. The redb file itself is not modified.
+pub fn migrate_from_redb(
+    db_path: &Path,
+    backend: &Arc<dyn StorageBackend>,
+) -> Result<Option<BackupSummary>> {
+    let state = DaemonStateStorage::with_backend(backend.clone())?;
+    if state.get_i64(REDB_MIGRATED_STATE_KEY)? != 0 {
+        return Ok(None);
+    }
+    let now = chrono::Utc::now().timestamp_millis();
+    if !db_path.is_file() {
+        state.set_i64(REDB_MIGRATED_STATE_KEY, now)?;
+        return Ok(None);
+    }
+
+    let db = Database::open(db_path)
+        .with_context(|| format!("Failed to open {} for migration", db_path.display()))?;
+    let source = RedbBackend::new(Arc::new(db));
+    let tables = source.table_names()?;
+
+    let source: &dyn StorageBackend = &source;
+    let summary = source.read_tables(|reader| {
+        backend.write_tables(|writer| {
+            let summary = merge_tables(reader, writer, &tables)?;
+            writer
+                .table(<DaemonStateStorage as SimpleStorage>::TABLE)?
+                .put(REDB_MIGRATED_STATE_KEY, &now.to_le_bytes())?;
+            Ok(summary)
+        })
+    })?;
+    Ok(Some(summary))
+}
+
+/// Copy the rows of `tables` whose keys are not in `writer` yet.
+fn merge_tables(
+    reader: &dyn ReadTransaction,
+    writer: &dyn WriteTransaction,
+    tables: &[String],
+) -> Result<BackupSummary> {
+    let mut summary = BackupSummary::default();
+    for table in tables {
+        let rows = reader.table(table)?.scan_prefix("")?;
+        let mut dest = writer.table(table)?;
+        let mut kept = 0usize;
+        for (key, value) in &rows {
+            if dest.get(key)?.is_some() {
+                kept += 1;
+                continue;
+            }
+            dest.put(key, value)?;
+            summary.entries += 1;
+        }
+        if kept > 0 {
+            tracing::warn!(
+                table = table.as_str(),
+                kept,
+                "Kept existing SQLite rows over their redb copies"
+            );
+        }
+        summary.tables += 1;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KvStoreStorage, SqliteBackend};
+    use redb::TableDefinition;
+    use tempfile::tempdir;
+
+    const LEGACY_INDEX: TableDefinition<&str, &str> = TableDefinition::new("legacy_index");
+
+    #[test]
+    fn test_migrates_once_and_merges_existing_tables() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("restflow.db");
+        {
+            let redb: Arc<dyn StorageBackend> = Arc::new(RedbBackend::open(&db_path).unwrap());
+            let kv = KvStoreStorage::with_backend(redb.clone()).unwrap();
+            kv.put_raw("from-redb", b"redb").unwrap();
+            kv.put_raw("shared", b"stale").unwrap();
+            redb.write_table("notes", |table| table.put("n1", b"note"))
+                .unwrap();
+        }
+        {
+            let db = Database::create(&db_path).unwrap();
+            let txn = db.begin_write().unwrap();
+            txn.open_table(LEGACY_INDEX)
+                .unwrap()
+                .insert("k", "v")
+                .unwrap();
+            txn.commit().unwrap();
+        }
+
+        let backend: Arc<dyn StorageBackend> =
+            Arc::new(SqliteBackend::open(&dir.path().join("restflow.sqlite")).unwrap());
+        let kv = KvStoreStorage::with_backend(backend.clone()).unwrap();
+        kv.put_raw("from-sqlite", b"live").unwrap();
+        kv.put_raw("shared", b"live").unwrap();
+
+        let summary = migrate_from_redb(&db_path, &backend).unwrap().unwrap();
+        assert_eq!(summary.tables, 3);
+        assert_eq!(summary.entries, 3);
+        // A table already written in SQLite still receives the redb-only rows.
+        assert_eq!(
+            kv.get_raw("from-redb").unwrap().as_deref(),
+            Some(&b"redb"[..])
+        );
+        assert_eq!(
+            kv.get_raw("from-sqlite").unwrap().as_deref(),
+            Some(&b"live"[..])
+        );
+        assert_eq!(kv.get_raw("shared").unwrap().as_deref(), Some(&b"live"[..]));
+        let note = backend
+            .read_table("notes", |table| table.get("n1"))
+            .unwrap();
+        assert_eq!(note.as_deref(), Some(&b"note"[..]));
+        let index = backend
+            .read_table("legacy_index", |table| table.get("k"))
+            .unwrap();
+        assert_eq!(index.as_deref(), Some(&b"v"[..]));
+
+        std::fs::remove_file(&db_path).unwrap();
+        assert!(migrate_from_redb(&db_path, &backend).unwrap().is_none());
+    }
+}
@@ -0,0 +1,438 @@
+//! Pluggable key-value backends for table-scoped storage.
+//!
+//! Every RestFlow table maps string keys to byte values, so a backend only
+//! needs to expose table-scoped reads and writes inside a transaction.
+//! [`RedbBackend`] wraps the embedded redb database and is the default;
+//! [`SqliteBackend`] keeps the same tables in one SQLite file so several
+//! processes can share state without fighting over redb's exclusive lock.
+//!
+//! The backend is chosen by the `[storage]` section of `config.toml` (see
+//! [`StorageSettings`](crate::config::StorageSettings)) and opened with
+//! [`open_backend`]. The first time SQLite is opened next to an existing redb
+//! database, every table is copied over once (see [`migrate_from_redb`]).
+
+mod migrate;
+mod redb;
+mod sqlite;
+
+pub use self::migrate::{REDB_MIGRATED_STATE_KEY, migrate_from_redb};
+pub use self::redb::RedbBackend;
+pub use self::sqlite::SqliteBackend;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::config::StorageSettings;
+
+/// SQLite file name used when `storage.sqlite_path` is not configured.
+pub const DEFAULT_SQLITE_FILE: &str = "restflow.sqlite";
+
+/// Which backend stores table data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackendKind {
+    /// Embedded redb database (single process).
+    #[default]
+    Redb,
+    /// SQLite database in WAL mode (safe for concurrent processes).
+    Sqlite,
+}
+
+impl StorageBackendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Redb => "redb",
+            Self::Sqlite => "sqlite",
+        }
+    }
+}
+
+impl fmt::Display for StorageBackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Read access to one table inside a backend transaction.
+pub trait TableRead {
+    /// Get the value stored under `key`.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// List entries whose key starts with `prefix`, in key order.
+    ///
+    /// An empty prefix lists the whole table.
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>>;
+
+    /// List keys starting with `prefix`, in key order.
+    fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>>;
+
+    /// Number of entries in the table.
+    fn count(&self) -> Result<usize>;
+}
+
+/// Write access to one table inside a backend transaction.
+pub trait TableWrite: TableRead {
+    /// Insert or replace the value stored under `key`.
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<()>;
+
+    /// Remove `key`, returning whether it existed.
+    fn remove(&mut self, key: &str) -> Result<bool>;
+}
+
+/// Tables opened inside one read transaction.
+pub trait ReadTransaction {
+    /// Open `table`. A table that was never written reads as empty.
+    fn table(&self, table: &str) -> Result<Box<dyn TableRead + '_>>;
+}
+
+/// Tables opened inside one write transaction.
+///
+/// Several tables may be open at once, but each at most once.
+pub trait WriteTransaction {
+    /// Open `table`, creating it if needed.
+    fn table(&self, table: &str) -> Result<Box<dyn TableWrite + '_>>;
+
+    /// Remove every entry of `table`. It must not be open.
+    fn clear(&self, table: &str) -> Result<()>;
+}
+
+/// Transactional key-value store addressed by table name.
+///
+/// Callbacks passed to [`read_txn`](Self::read_txn) and
+/// [`write_txn`](Self::write_txn) run inside a single transaction; a write is
+/// committed only when its callback returns `Ok`.
+pub trait StorageBackend: Send + Sync + fmt::Debug {
+    fn kind(&self) -> StorageBackendKind;
+
+    /// Create `table` if the backend needs it to exist before reads.
+    fn ensure_table(&self, table: &str) -> Result<()>;
+
+    /// Names of tables holding data, in no particular order.
+    fn table_names(&self) -> Result<Vec<String>>;
+
+    /// Run `f` in a read transaction.
+    fn read_txn(&self, f: &mut dyn FnMut(&dyn ReadTransaction) -> Result<()>) -> Result<()>;
+
+    /// Run `f` in a write transaction.
+    fn write_txn(&self, f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>) -> Result<()>;
+
+    /// Run `f` in a write transaction that first takes a persistent
+    /// savepoint, returning its id, or `None` if the backend has none.
+    fn write_txn_with_savepoint(
+        &self,
+        f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>,
+    ) -> Result<Option<u64>> {
+        self.write_txn(f)?;
+        Ok(None)
+    }
+
+    /// Delete a savepoint taken by
+    /// [`write_txn_with_savepoint`](Self::write_txn_with_savepoint).
+    fn delete_savepoint(&self, _savepoint_id: u64) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Run `f` against `table` in a read transaction.
+    fn read(&self, table: &str, f: &mut dyn FnMut(&dyn TableRead) -> Result<()>) -> Result<()> {
+        self.read_txn(&mut |txn| f(txn.table(table)?.as_ref()))
+    }
+
+    /// Run `f` against `table` in a write transaction.
+    fn write(
+        &self,
+        table: &str,
+        f: &mut dyn FnMut(&mut dyn TableWrite) -> Result<()>,
+    ) -> Result<()> {
+        self.write_txn(&mut |txn| f(txn.table(table)?.as_mut()))
+    }
+}
+
+impl dyn StorageBackend + '_ {
+    /// [`read`](StorageBackend::read) returning the callback's value.
+    pub fn read_table<R>(
+        &self,
+        table: &str,
+        f: impl FnOnce(&dyn TableRead) -> Result<R>,
+    ) -> Result<R> {
+        let mut f = Some(f);
+        let mut output = None;
+        self.read(table, &mut |reader| {
+            if let Some(f) = f.take() {
+                output = Some(f(reader)?);
+            }
+            Ok(())
+        })?;
+        output.ok_or_else(|| anyhow!("Storage backend did not run read for table '{table}'"))
+    }
+
+    /// [`write`](StorageBackend::write) returning the callback's value.
+    pub fn write_table<R>(
+        &self,
+        table: &str,
+        f: impl FnOnce(&mut dyn TableWrite) -> Result<R>,
+    ) -> Result<R> {
+        let mut f = Some(f);
+        let mut output = None;
+        self.write(table, &mut |writer| {
+            if let Some(f) = f.take() {
+                output = Some(f(writer)?);
+            }
+            Ok(())
+        })?;
+        output.ok_or_else(|| anyhow!("Storage backend did not run write for table '{table}'"))
+    }
+
+    /// [`read_txn`](StorageBackend::read_txn) returning the callback's value.
+    pub fn read_tables<R>(&self, f: impl FnOnce(&dyn ReadTransaction) -> Result<R>) -> Result<R> {
+        let mut f = Some(f);
+        let mut output = None;
+        self.read_txn(&mut |txn| {
+            if let Some(f) = f.take() {
+                output = Some(f(txn)?);
+            }
+            Ok(())
+        })?;
+        output.ok_or_else(|| anyhow!("Storage backend did not run read transaction"))
+    }
+
+    /// [`write_txn`](StorageBackend::write_txn) returning the callback's value.
+    pub fn write_tables<R>(&self, f: impl FnOnce(&dyn WriteTransaction) -> Result<R>) -> Result<R> {
+        let mut f = Some(f);
+        let mut output = None;
+        self.write_txn(&mut |txn| {
+            if let Some(f) = f.take() {
+                output = Some(f(txn)?);
+            }
+            Ok(())
+        })?;
+        output.ok_or_else(|| anyhow!("Storage backend did not run write transaction"))
+    }
+}
+
+/// Decode a value stored as text, such as an index entry's target key.
+pub(crate) fn text_value(value: Vec<u8>) -> Result<String> {
+    String::from_utf8(value).map_err(|err| anyhow!("Stored value is not valid UTF-8: {err}"))
+}
+
+/// Open the backend selected by `settings` for the database at `db_path`.
+///
+/// With redb, `db_path` is the database file. With SQLite the file defaults
+/// to [`DEFAULT_SQLITE_FILE`] next to it, and the redb file is only opened
+/// once, to copy its tables over (see [`migrate_from_redb`]).
+pub fn open_backend(settings: &StorageSettings, db_path: &Path) -> Result<Arc<dyn StorageBackend>> {
+    match settings.backend {
+        StorageBackendKind::Redb => Ok(Arc::new(RedbBackend::open(db_path)?)),
+        StorageBackendKind::Sqlite => {
+            let path = match settings.sqlite_path.as_deref().map(str::trim) {
+                Some(path) if !path.is_empty() => Path::new(path).to_path_buf(),
+                _ => db_path
+                    .parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .join(DEFAULT_SQLITE_FILE),
+            };
+            let backend: Arc<dyn StorageBackend> = Arc::new(SqliteBackend::open(&path)?);
+            if let Some(summary) = migrate_from_redb(db_path, &backend)? {
+                tracing::info!(
+                    tables = summary.tables,
+                    entries = summary.entries,
+                    "Copied redb tables into SQLite"
+                );
+            }
+            Ok(backend)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn backends(dir: &Path) -> Vec<Arc<dyn StorageBackend>> {
+        let db = Arc::new(::redb::Database::create(dir.join("test.db")).unwrap());
+        vec![
+            Arc::new(RedbBackend::new(db)),
+            Arc::new(SqliteBackend::open(&dir.join("test.sqlite")).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn backends_share_table_semantics() {
+        let dir = tempdir().unwrap();
+        for backend in backends(dir.path()) {
+            backend.ensure_table("items").unwrap();
+            backend.ensure_table("other").unwrap();
+
+            backend
+                .write_table("items", |table| {
+                    table.put("b:2", b"two")?;
+                    table.put("a:1", b"one")?;
+                    table.put("b:1", b"first")?;
+                    table.put("b:1", b"one")?;
+                    Ok(())
+                })
+                .unwrap();
+            backend
+                .write_table("other", |table| table.put("b:9", b"other"))
+                .unwrap();
+
+            let (value, entries, keys, count) = backend
+                .read_table("items", |table| {
+                    Ok((
+                        table.get("b:1")?,
+                        table.scan_prefix("b:")?,
+                        table.keys_with_prefix("")?,
+                        table.count()?,
+                    ))
+                })
+                .unwrap();
+            assert_eq!(value.as_deref(), Some(&b"one"[..]), "{}", backend.kind());
+            assert_eq!(
+                entries,
+                vec![
+                    ("b:1".to_string(), b"one".to_vec()),
+                    ("b:2".to_string(), b"two".to_vec()),
+                ]
+            );
+            assert_eq!(keys, vec!["a:1", "b:1", "b:2"]);
+            assert_eq!(count, 3);
+
+            let removed = backend
+                .write_table("items", |table| {
+                    Ok((table.remove("a:1")?, table.remove("a:1")?))
+                })
+                .unwrap();
+            assert_eq!(removed, (true, false));
+        }
+    }
+
+    #[test]
+    fn failed_write_is_rolled_back() {
+        let dir = tempdir().unwrap();
+        for backend in backends(dir.path()) {
+            backend.ensure_table("items").unwrap();
+            let result: Result<()> = backend.write_table("items", |table| {
+                table.put("key", b"value")?;
+                anyhow::bail!("abort")
+            });
+            assert!(result.is_err());
+            let value = backend
+                .read_table("items", |table| table.get("key"))
+                .unwrap();
+            assert!(
+                value.is_none(),
+                "{} kept a rolled back write",
+                backend.kind()
+            );
+        }
+    }
+
+    #[test]
+    fn transactions_span_tables() {
+        let dir = tempdir().unwrap();
+        for backend in backends(dir.path()) {
+            backend
+                .write_tables(|txn| {
+                    txn.table("items")?.put("a", b"1")?;
+                    txn.table("index")?.put("1", b"a")?;
+                    Ok(())
+                })
+                .unwrap();
+            let result: Result<()> = backend.write_tables(|txn| {
+                txn.table("items")?.put("b", b"2")?;
+                txn.table("index")?.put("2", b"b")?;
+                anyhow::bail!("abort")
+            });
+            assert!(result.is_err());
+
+            let (items, index) = backend
+                .read_tables(|txn| Ok((txn.table("items")?.count()?, txn.table("index")?.count()?)))
+                .unwrap();
+            assert_eq!((items, index), (1, 1), "{}", backend.kind());
+            let mut names = backend.table_names().unwrap();
+            names.sort();
+            assert_eq!(names, vec!["index", "items"]);
+
+            backend.write_tables(|txn| txn.clear("items")).unwrap();
+            let missing = backend
+                .read_tables(|txn| txn.table("never_written")?.count())
+                .unwrap();
+            assert_eq!(missing, 0);
+            assert_eq!(backend.table_names().unwrap(), vec!["index"]);
+        }
+    }
+
+    #[test]
+    fn redb_reads_and_upgrades_legacy_tables() {
+        let dir = tempdir().unwrap();
+        let db = Arc::new(::redb::Database::create(dir.path().join("legacy.db")).unwrap());
+        let txn = db.begin_write().unwrap();
+        txn.open_table(::redb::TableDefinition::<&str, &str>::new("text"))
+            .unwrap()
+            .insert("k", "v")
+            .unwrap();
+        txn.open_table(::redb::TableDefinition::<&str, i64>::new("ints"))
+            .unwrap()
+            .insert("k", 7)
+            .unwrap();
+        txn.commit().unwrap();
+
+        let backend: Arc<dyn StorageBackend> = Arc::new(RedbBackend::new(db));
+        let text = backend.read_table("text", |table| table.get("k")).unwrap();
+        assert_eq!(text.as_deref(), Some(&b"v"[..]));
+        let int = backend.read_table("ints", |table| table.get("k")).unwrap();
+        assert_eq!(int, Some(7i64.to_le_bytes().to_vec()));
+
+        backend
+            .write_table("text", |table| table.put("k2", b"v2"))
+            .unwrap();
+        let entries = backend
+            .read_table("text", |table| table.scan_prefix(""))
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ("k".to_string(), b"v".to_vec()),
+                ("k2".to_string(), b"v2".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn open_backend_defaults_sqlite_file_next_to_database() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("restflow.db");
+        let settings = StorageSettings {
+            backend: StorageBackendKind::Sqlite,
+            sqlite_path: None,
+            ..Default::default()
+        };
+
+        let backend = open_backend(&settings, &db_path).unwrap();
+
+        assert_eq!(backend.kind(), StorageBackendKind::Sqlite);
+        assert!(dir.path().join(DEFAULT_SQLITE_FILE).exists());
+        assert!(!db_path.exists());
+    }
+
+    #[test]
+    fn sqlite_backend_is_shared_between_handles() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("shared.sqlite");
+        let first: Arc<dyn StorageBackend> = Arc::new(SqliteBackend::open(&path).unwrap());
+        let second: Arc<dyn StorageBackend> = Arc::new(SqliteBackend::open(&path).unwrap());
+
+        first
+            .write_table("items", |table| table.put("key", b"value"))
+            .unwrap();
+        let value = second
+            .read_table("items", |table| table.get("key"))
+            .unwrap();
+
+        assert_eq!(value.as_deref(), Some(&b"value"[..]));
+    }
+}
@@ -0,0 +1,326 @@
+//! redb-backed [`StorageBackend`].
+//!
+//! Tables are typed `&str -> &[u8]`. Databases written before every store
+//! went through the backend also hold index tables typed `&str -> &str` and
+//! one `&str -> i64` table; reads decode those to bytes (UTF-8 text and
+//! little-endian integers), and the first write to such a table rewrites it
+//! as a byte table.
+
+use anyhow::{Context, Result};
+use redb::{
+    Database, ReadOnlyTable, ReadableDatabase, ReadableTable, ReadableTableMetadata,
+    TableDefinition, TableError, TableHandle,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{
+    ReadTransaction, StorageBackend, StorageBackendKind, TableRead, TableWrite, WriteTransaction,
+};
+
+type Definition<'a> = TableDefinition<'a, &'static str, &'static [u8]>;
+type TextDefinition<'a> = TableDefinition<'a, &'static str, &'static str>;
+type IntDefinition<'a> = TableDefinition<'a, &'static str, i64>;
+
+/// [`StorageBackend`] over an embedded redb database.
+#[derive(Debug, Clone)]
+pub struct RedbBackend {
+    db: Arc<Database>,
+}
+
+impl RedbBackend {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Open (or create) the database file at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = Database::create(path)
+            .with_context(|| format!("Failed to open database {}", path.display()))?;
+        Ok(Self::new(Arc::new(db)))
+    }
+
+    /// Access the underlying database.
+    pub fn db(&self) -> &Arc<Database> {
+        &self.db
+    }
+}
+
+struct RedbTable<T>(T);
+
+impl<T> TableRead for RedbTable<T>
+where
+    T: ReadableTable<&'static str, &'static [u8]>,
+{
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.0.get(key)?.map(|value| value.value().to_vec()))
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut entries = Vec::new();
+        for item in self.0.range(prefix..)? {
+            let (key, value) = item?;
+            let key = key.value();
+            if !key.starts_with(prefix) {
+                break;
+            }
+            entries.push((key.to_string(), value.value().to_vec()));
+        }
+        Ok(entries)
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        for item in self.0.range(prefix..)? {
+            let (key, _) = item?;
+            let key = key.value();
+            if !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key.to_string());
+        }
+        Ok(keys)
+    }
+
+    fn count(&self) -> Result<usize> {
+        Ok(self.0.len()? as usize)
+    }
+}
+
+impl TableWrite for RedbTable<redb::Table<'_, &'static str, &'static [u8]>> {
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<()> {
+        self.0.insert(key, value)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<bool> {
+        Ok(self.0.remove(key)?.is_some())
+    }
+}
+
+/// A table from before values were stored as bytes, read as bytes.
+enum LegacyTable {
+    Text(ReadOnlyTable<&'static str, &'static str>),
+    Int(ReadOnlyTable<&'static str, i64>),
+}
+
+impl LegacyTable {
+    fn open(txn: &redb::ReadTransaction, name: &str) -> Result<Self> {
+        match txn.open_table(TextDefinition::new(name)) {
+            Ok(table) => Ok(Self::Text(table)),
+            Err(TableError::TableTypeMismatch { .. }) => {
+                Ok(Self::Int(txn.open_table(IntDefinition::new(name))?))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn entries(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut entries = Vec::new();
+        match self {
+            Self::Text(table) => {
+                for item in table.range(prefix..)? {
+                    let (key, value) = item?;
+                    if !key.value().starts_with(prefix) {
+                        break;
+                    }
+                    entries.push((key.value().to_string(), value.value().as_bytes().to_vec()));
+                }
+            }
+            Self::Int(table) => {
+                for item in table.range(prefix..)? {
+                    let (key, value) = item?;
+                    if !key.value().starts_with(prefix) {
+                        break;
+                    }
+                    entries.push((
+                        key.value().to_string(),
+                        value.value().to_le_bytes().to_vec(),
+                    ));
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl TableRead for LegacyTable {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(match self {
+            Self::Text(table) => table
+                .get(key)?
+                .map(|value| value.value().as_bytes().to_vec()),
+            Self::Int(table) => table
+                .get(key)?
+                .map(|value| value.value().to_le_bytes().to_vec()),
+        })
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        self.entries(prefix)
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .entries(prefix)?
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect())
+    }
+
+    fn count(&self) -> Result<usize> {
+        Ok(match self {
+            Self::Text(table) => table.len()?,
+            Self::Int(table) => table.len()?,
+        } as usize)
+    }
+}
+
+/// Reads of a table that was never created.
+struct EmptyTable;
+
+impl TableRead for EmptyTable {
+    fn get(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn scan_prefix(&self, _prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(Vec::new())
+    }
+
+    fn keys_with_prefix(&self, _prefix: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn count(&self) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+impl ReadTransaction for redb::ReadTransaction {
+    fn table(&self, table: &str) -> Result<Box<dyn TableRead + '_>> {
+        match self.open_table(Definition::new(table)) {
+            Ok(opened) => Ok(Box::new(RedbTable(opened))),
+            Err(TableError::TableDoesNotExist(_)) => Ok(Box::new(EmptyTable)),
+            Err(TableError::TableTypeMismatch { .. }) => {
+                Ok(Box::new(LegacyTable::open(self, table)?))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Rewrite the legacy table `name` as a byte table inside `txn`.
+fn convert_legacy(txn: &redb::WriteTransaction, name: &str) -> Result<()> {
+    let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+    match txn.open_table(TextDefinition::new(name)) {
+        Ok(table) => {
+            for item in table.iter()? {
+                let (key, value) = item?;
+                entries.push((key.value().to_string(), value.value().as_bytes().to_vec()));
+            }
+            drop(table);
+            txn.delete_table(TextDefinition::new(name))?;
+        }
+        Err(TableError::TableTypeMismatch { .. }) => {
+            let table = txn.open_table(IntDefinition::new(name))?;
+            for item in table.iter()? {
+                let (key, value) = item?;
+                entries.push((
+                    key.value().to_string(),
+                    value.value().to_le_bytes().to_vec(),
+                ));
+            }
+            drop(table);
+            txn.delete_table(IntDefinition::new(name))?;
+        }
+        Err(err) => return Err(err.into()),
+    }
+    let mut table = txn.open_table(Definition::new(name))?;
+    for (key, value) in &entries {
+        table.insert(key.as_str(), value.as_slice())?;
+    }
+    Ok(())
+}
+
+impl WriteTransaction for redb::WriteTransaction {
+    fn table(&self, table: &str) -> Result<Box<dyn TableWrite + '_>> {
+        match self.open_table(Definition::new(table)) {
+            Ok(opened) => Ok(Box::new(RedbTable(opened))),
+            Err(TableError::TableTypeMismatch { .. }) => {
+                convert_legacy(self, table)?;
+                Ok(Box::new(RedbTable(
+                    self.open_table(Definition::new(table))?,
+                )))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn clear(&self, table: &str) -> Result<()> {
+        let handle = self.list_tables()?.find(|handle| handle.name() == table);
+        if let Some(handle) = handle {
+            self.delete_table(handle)?;
+        }
+        Ok(())
+    }
+}
+
+impl StorageBackend for RedbBackend {
+    fn kind(&self) -> StorageBackendKind {
+        StorageBackendKind::Redb
+    }
+
+    fn ensure_table(&self, table: &str) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        WriteTransaction::table(&write_txn, table)?;
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    fn table_names(&self) -> Result<Vec<String>> {
+        let read_txn = self.db.begin_read()?;
+        let mut names = Vec::new();
+        for handle in read_txn.list_tables()? {
+            if ReadTransaction::table(&read_txn, handle.name())?.count()? > 0 {
+                names.push(handle.name().to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    fn read_txn(&self, f: &mut dyn FnMut(&dyn ReadTransaction) -> Result<()>) -> Result<()> {
+        let read_txn = self.db.begin_read()?;
+        f(&read_txn)
+    }
+
+    fn write_txn(&self, f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>) -> Result<()> {
+        let write_txn = self.db.begin_write()?;
+        f(&write_txn)?;
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    fn write_txn_with_savepoint(
+        &self,
+        f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>,
+    ) -> Result<Option<u64>> {
+        // redb only allows savepoints before any table is opened.
+        let write_txn = self.db.begin_write()?;
+        let savepoint_id = write_txn
+            .persistent_savepoint()
+            .context("Failed to create persistent savepoint")?;
+        f(&write_txn)?;
+        write_txn.commit()?;
+        Ok(Some(savepoint_id))
+    }
+
+    fn delete_savepoint(&self, savepoint_id: u64) -> Result<bool> {
+        let write_txn = self.db.begin_write()?;
+        let deleted = write_txn
+            .delete_persistent_savepoint(savepoint_id)
+            .context("Failed to delete persistent savepoint")?;
+        write_txn.commit()?;
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,205 @@
+//! SQLite-backed [`StorageBackend`].
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, params};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::{
+    ReadTransaction, StorageBackend, StorageBackendKind, TableRead, TableWrite, WriteTransaction,
+};
+
+/// How long a writer waits for another process to release the database lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS restflow_kv (
+    tbl TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value BLOB NOT NULL,
+    PRIMARY KEY (tbl, key)
+) WITHOUT ROWID;
+";
+
+/// [`StorageBackend`] storing every table in one SQLite file.
+///
+/// Rows live in a single `restflow_kv(tbl, key, value)` table. The database
+/// runs in WAL mode with a busy timeout, so readers never block and writers
+/// from other processes wait for the lock instead of failing.
+#[derive(Debug)]
+pub struct SqliteBackend {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+impl SqliteBackend {
+    /// Open (or create) the SQLite database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create SQLite directory {}", parent.display())
+            })?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database {}", path.display()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        let _: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.execute_batch(SCHEMA)
+            .context("Failed to initialize SQLite storage schema")?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+struct SqliteTable<'a> {
+    conn: &'a Connection,
+    table: String,
+}
+
+impl TableRead for SqliteTable<'_> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT value FROM restflow_kv WHERE tbl = ?1 AND key = ?2")?;
+        Ok(stmt
+            .query_row(params![self.table, key], |row| row.get(0))
+            .optional()?)
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT key, value FROM restflow_kv WHERE tbl = ?1 AND key >= ?2 ORDER BY key",
+        )?;
+        let mut rows = stmt.query(params![self.table, prefix])?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next()? {
+            let key: String = row.get(0)?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            entries.push((key, row.get(1)?));
+        }
+        Ok(entries)
+    }
+
+    fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT key FROM restflow_kv WHERE tbl = ?1 AND key >= ?2 ORDER BY key",
+        )?;
+        let mut rows = stmt.query(params![self.table, prefix])?;
+        let mut keys = Vec::new();
+        while let Some(row) = rows.next()? {
+            let key: String = row.get(0)?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    fn count(&self) -> Result<usize> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT COUNT(*) FROM restflow_kv WHERE tbl = ?1")?;
+        let count: i64 = stmt.query_row(params![self.table], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+}
+
+impl TableWrite for SqliteTable<'_> {
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<()> {
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT INTO restflow_kv (tbl, key, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT (tbl, key) DO UPDATE SET value = excluded.value",
+        )?;
+        stmt.execute(params![self.table, key, value])?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<bool> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("DELETE FROM restflow_kv WHERE tbl = ?1 AND key = ?2")?;
+        Ok(stmt.execute(params![self.table, key])? > 0)
+    }
+}
+
+/// An open SQLite transaction; every table shares it.
+struct SqliteTransaction<'a>(Transaction<'a>);
+
+impl SqliteTransaction<'_> {
+    fn open(&self, table: &str) -> SqliteTable<'_> {
+        SqliteTable {
+            conn: &self.0,
+            table: table.to_string(),
+        }
+    }
+}
+
+impl ReadTransaction for SqliteTransaction<'_> {
+    fn table(&self, table: &str) -> Result<Box<dyn TableRead + '_>> {
+        Ok(Box::new(self.open(table)))
+    }
+}
+
+impl WriteTransaction for SqliteTransaction<'_> {
+    fn table(&self, table: &str) -> Result<Box<dyn TableWrite + '_>> {
+        Ok(Box::new(self.open(table)))
+    }
+
+    fn clear(&self, table: &str) -> Result<()> {
+        self.0
+            .execute("DELETE FROM restflow_kv WHERE tbl = ?1", params![table])?;
+        Ok(())
+    }
+}
+
+impl StorageBackend for SqliteBackend {
+    fn kind(&self) -> StorageBackendKind {
+        StorageBackendKind::Sqlite
+    }
+
+    fn ensure_table(&self, _table: &str) -> Result<()> {
+        // All tables share `restflow_kv`, created when the backend opens.
+        Ok(())
+    }
+
+    fn table_names(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT DISTINCT tbl FROM restflow_kv")?;
+        let names = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(names)
+    }
+
+    fn read_txn(&self, f: &mut dyn FnMut(&dyn ReadTransaction) -> Result<()>) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let txn = SqliteTransaction(conn.transaction_with_behavior(TransactionBehavior::Deferred)?);
+        f(&txn)?;
+        txn.0.commit()?;
+        Ok(())
+    }
+
+    fn write_txn(&self, f: &mut dyn FnMut(&dyn WriteTransaction) -> Result<()>) -> Result<()> {
+        let mut conn = self.conn.lock();
+        // Take the write lock up front so concurrent writers queue on the busy
+        // timeout instead of failing when a read lock is upgraded.
+        let txn =
+            SqliteTransaction(conn.transaction_with_behavior(TransactionBehavior::Immediate)?);
+        f(&txn)?;
+        txn.0.commit()?;
+        Ok(())
+    }
+}
@@ -1,53 +1,56 @@
 //! Agent Task storage - byte-level API for agent task persistence.
 //!
 //! Provides low-level storage operations for scheduled agent tasks and their
-//! execution events using the redb embedded database.
+//! execution events on a [`StorageBackend`].
 
 use anyhow::Result;
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use redb::Database;
 use std::sync::Arc;
 
-use crate::range_utils::prefix_range;
+use crate::backend::{RedbBackend, StorageBackend, TableRead, TableWrite, text_value};
 
-const BACKGROUND_AGENT_TABLE: TableDefinition<&str, &[u8]> =
-    TableDefinition::new("background_agents");
-const BACKGROUND_AGENT_EVENT_TABLE: TableDefinition<&str, &[u8]> =
-    TableDefinition::new("background_agent_events");
+const BACKGROUND_AGENT_TABLE: &str = "background_agents";
+const BACKGROUND_AGENT_EVENT_TABLE: &str = "background_agent_events";
 /// Index table: task_id -> event_id (for listing events by task)
-const BACKGROUND_AGENT_EVENT_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_agent_event_index");
+const BACKGROUND_AGENT_EVENT_INDEX_TABLE: &str = "background_agent_event_index";
 /// Index table: status:task_id -> task_id (for listing tasks by status)
-const BACKGROUND_AGENT_STATUS_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_agent_status_index");
+const BACKGROUND_AGENT_STATUS_INDEX_TABLE: &str = "background_agent_status_index";
 /// Reverse index: task_id -> status:task_id (for direct status cleanup)
-const BACKGROUND_AGENT_STATUS_LOOKUP_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_agent_status_lookup");
+const BACKGROUND_AGENT_STATUS_LOOKUP_TABLE: &str = "background_agent_status_lookup";
 /// Background execution attempt payload table.
-const BACKGROUND_AGENT_RUN_TABLE: TableDefinition<&str, &[u8]> =
-    TableDefinition::new("background_agent_runs");
+const BACKGROUND_AGENT_RUN_TABLE: &str = "background_agent_runs";
 /// Index table: task_id:run_id -> run_id
-const BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_agent_run_task_index");
+const BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE: &str = "background_agent_run_task_index";
 /// Index table: task_id -> run_id for the single active run of one task.
-const BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_agent_active_run_index");
+const BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE: &str = "background_agent_active_run_index";
 /// Background message payload table
-const BACKGROUND_MESSAGE_TABLE: TableDefinition<&str, &[u8]> =
-    TableDefinition::new("background_messages");
+const BACKGROUND_MESSAGE_TABLE: &str = "background_messages";
 /// Index table: task_id:message_id -> message_id
-const BACKGROUND_MESSAGE_TASK_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_message_task_index");
+const BACKGROUND_MESSAGE_TASK_INDEX_TABLE: &str = "background_message_task_index";
 /// Index table: status:task_id:message_id -> message_id
-const BACKGROUND_MESSAGE_STATUS_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_message_status_index");
+const BACKGROUND_MESSAGE_STATUS_INDEX_TABLE: &str = "background_message_status_index";
 /// Reverse index: message_id -> status:task_id:message_id
-const BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("background_message_status_lookup");
+const BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE: &str = "background_message_status_lookup";
+
+const TABLES: [&str; 12] = [
+    BACKGROUND_AGENT_TABLE,
+    BACKGROUND_AGENT_EVENT_TABLE,
+    BACKGROUND_AGENT_EVENT_INDEX_TABLE,
+    BACKGROUND_AGENT_STATUS_INDEX_TABLE,
+    BACKGROUND_AGENT_STATUS_LOOKUP_TABLE,
+    BACKGROUND_AGENT_RUN_TABLE,
+    BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE,
+    BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE,
+    BACKGROUND_MESSAGE_TABLE,
+    BACKGROUND_MESSAGE_TASK_INDEX_TABLE,
+    BACKGROUND_MESSAGE_STATUS_INDEX_TABLE,
+    BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE,
+];
 
 /// Low-level agent task storage with byte-level API
 #[derive(Clone)]
 pub struct BackgroundAgentStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
 }
 
 impl BackgroundAgentStorage {
@@ -134,11 +137,7 @@ impl BackgroundAgentStorage {
         Ok(())
     }
 
-    fn ensure_task_exists(
-        table: &redb::Table<&str, &[u8]>,
-        task_id: &str,
-        run_id: &str,
-    ) -> Result<()> {
+    fn ensure_task_exists(table: &dyn TableRead, task_id: &str, run_id: &str) -> Result<()> {
         if table.get(task_id)?.is_none() {
             anyhow::bail!(
                 "background run '{}' references missing background task '{}'",
@@ -150,22 +149,20 @@ impl BackgroundAgentStorage {
     }
 
     fn reconcile_active_run_slot(
-        active_index: &mut redb::Table<&str, &str>,
-        run_table: &redb::Table<&str, &[u8]>,
+        active_index: &mut dyn TableWrite,
+        run_table: &dyn TableRead,
         task_id: &str,
         run_id: &str,
         status: &str,
     ) -> Result<()> {
         let wants_active = status == "running";
-        let active_entry = active_index
-            .get(task_id)?
-            .map(|value| value.value().to_string());
+        let active_entry = active_index.get(task_id)?.map(text_value).transpose()?;
 
         if let Some(existing_run_id) = active_entry {
             if existing_run_id != run_id {
-                if let Some(existing_raw) = run_table.get(existing_run_id.as_str())? {
-                    let existing_task_id = Self::parse_run_task_id(existing_raw.value())?;
-                    let existing_status = Self::parse_run_status(existing_raw.value())?;
+                if let Some(existing_raw) = run_table.get(&existing_run_id)? {
+                    let existing_task_id = Self::parse_run_task_id(&existing_raw)?;
+                    let existing_status = Self::parse_run_status(&existing_raw)?;
                     if existing_task_id == task_id && existing_status == "running" {
                         if wants_active {
                             anyhow::bail!(
@@ -187,14 +184,14 @@ impl BackgroundAgentStorage {
         }
 
         if wants_active {
-            active_index.insert(task_id, run_id)?;
+            active_index.put(task_id, run_id.as_bytes())?;
         }
 
         Ok(())
     }
 
     fn ensure_unique_chat_session_binding(
-        table: &redb::Table<&str, &[u8]>,
+        table: &dyn TableRead,
         task_id: &str,
         target_chat_session_id: Option<&str>,
     ) -> Result<()> {
@@ -202,14 +199,12 @@ impl BackgroundAgentStorage {
             return Ok(());
         };
 
-        for item in table.iter()? {
-            let (key, value) = item?;
-            let existing_task_id = key.value();
+        for (existing_task_id, value) in table.scan_prefix("")? {
             if existing_task_id == task_id {
                 continue;
             }
 
-            let existing_chat_session_id = Self::parse_chat_session_id(value.value()).map_err(
+            let existing_chat_session_id = Self::parse_chat_session_id(&value).map_err(
                 |error| {
                     anyhow::anyhow!(
                         "failed to parse existing background task '{}' while validating chat_session_id uniqueness: {}",
@@ -223,7 +218,7 @@ impl BackgroundAgentStorage {
             }
 
             let existing_task_name =
-                Self::extract_task_name(value.value()).unwrap_or_else(|| "unknown".to_string());
+                Self::extract_task_name(&value).unwrap_or_else(|| "unknown".to_string());
             return Err(anyhow::anyhow!(
                 "chat_session_id '{}' is already bound to background task '{}' ({})",
                 target_chat_session_id,
@@ -235,61 +230,100 @@ impl BackgroundAgentStorage {
         Ok(())
     }
 
+    /// Point the status index of `id` at `new_key`, removing its previous
+    /// entry (or `fallback_key` when no lookup entry was recorded).
+    fn move_status_key(
+        status_index: &mut dyn TableWrite,
+        status_lookup: &mut dyn TableWrite,
+        id: &str,
+        fallback_key: Option<&str>,
+        new_key: Option<&str>,
+    ) -> Result<()> {
+        if let Some(previous_key) = status_lookup.get(id)? {
+            status_index.remove(&text_value(previous_key)?)?;
+        } else if let Some(fallback_key) = fallback_key {
+            status_index.remove(fallback_key)?;
+        }
+
+        match new_key {
+            Some(new_key) => {
+                status_index.put(new_key, id.as_bytes())?;
+                status_lookup.put(id, new_key.as_bytes())?;
+            }
+            None => {
+                status_lookup.remove(id)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Create a new BackgroundAgentStorage instance
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
         // Initialize all tables
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
-        write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-        write_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-        write_txn.open_table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
-        write_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-        write_txn.open_table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
-        write_txn.commit()?;
-
-        Ok(Self { db })
+        for table in TABLES {
+            backend.ensure_table(table)?;
+        }
+
+        Ok(Self { backend })
+    }
+
+    fn list_indexed(
+        &self,
+        index_table: &str,
+        data_table: &str,
+        prefix: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        self.backend.read_tables(|txn| {
+            let index = txn.table(index_table)?;
+            let table = txn.table(data_table)?;
+
+            let mut entries = Vec::new();
+            for (_, value) in index.scan_prefix(prefix)? {
+                let id = text_value(value)?;
+                if let Some(data) = table.get(&id)? {
+                    entries.push((id, data));
+                }
+            }
+            Ok(entries)
+        })
     }
 
     // ============== Agent Task Operations ==============
 
     /// Store raw agent task data
     pub fn put_task_raw(&self, id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            table.insert(id, data)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.backend
+            .write_table(BACKGROUND_AGENT_TABLE, |table| table.put(id, data))
     }
 
     /// Store raw agent task data with status index
     pub fn put_task_raw_with_status(&self, id: &str, status: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            let chat_session_id = Self::extract_chat_session_id(data);
-            Self::ensure_unique_chat_session_binding(&table, id, chat_session_id.as_deref())?;
-            table.insert(id, data)?;
-
-            let mut status_index = write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(id)? {
-                status_index.remove(previous_key.value())?;
+        self.backend.write_tables(|txn| {
+            {
+                let mut table = txn.table(BACKGROUND_AGENT_TABLE)?;
+                let chat_session_id = Self::extract_chat_session_id(data);
+                Self::ensure_unique_chat_session_binding(
+                    table.as_ref(),
+                    id,
+                    chat_session_id.as_deref(),
+                )?;
+                table.put(id, data)?;
             }
 
             let status_key = format!("{}:{}", status, id);
-            status_index.insert(status_key.as_str(), id)?;
-            status_lookup.insert(id, status_key.as_str())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            Self::move_status_key(
+                txn.table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?.as_mut(),
+                id,
+                None,
+                Some(&status_key),
+            )
+        })
     }
 
     /// Update raw agent task data while keeping the status index consistent
@@ -300,28 +334,28 @@ impl BackgroundAgentStorage {
         new_status: &str,
         data: &[u8],
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            let chat_session_id = Self::extract_chat_session_id(data);
-            Self::ensure_unique_chat_session_binding(&table, id, chat_session_id.as_deref())?;
-            table.insert(id, data)?;
-
-            let mut status_index = write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(id)? {
-                status_index.remove(previous_key.value())?;
-            } else if old_status != new_status {
-                let old_key = format!("{}:{}", old_status, id);
-                status_index.remove(old_key.as_str())?;
+        self.backend.write_tables(|txn| {
+            {
+                let mut table = txn.table(BACKGROUND_AGENT_TABLE)?;
+                let chat_session_id = Self::extract_chat_session_id(data);
+                Self::ensure_unique_chat_session_binding(
+                    table.as_ref(),
+                    id,
+                    chat_session_id.as_deref(),
+                )?;
+                table.put(id, data)?;
             }
 
+            let old_key = (old_status != new_status).then(|| format!("{}:{}", old_status, id));
             let new_key = format!("{}:{}", new_status, id);
-            status_index.insert(new_key.as_str(), id)?;
-            status_lookup.insert(id, new_key.as_str())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            Self::move_status_key(
+                txn.table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?.as_mut(),
+                id,
+                old_key.as_deref(),
+                Some(&new_key),
+            )
+        })
     }
 
     /// Compare-and-set update for task payload and status index.
@@ -338,231 +372,158 @@ impl BackgroundAgentStorage {
         new_status: &str,
         data: &[u8],
     ) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let mut table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-        let Some(existing) = table.get(id)? else {
-            drop(table);
-            write_txn.abort()?;
-            return Ok(false);
-        };
-
-        let current_status = Self::parse_task_status(existing.value())?;
-        drop(existing);
-        if current_status != expected_status {
-            drop(table);
-            write_txn.abort()?;
-            return Ok(false);
-        }
-
-        let chat_session_id = Self::extract_chat_session_id(data);
-        Self::ensure_unique_chat_session_binding(&table, id, chat_session_id.as_deref())?;
-        table.insert(id, data)?;
-        drop(table);
+        self.backend.write_tables(|txn| {
+            let current_status = {
+                let mut table = txn.table(BACKGROUND_AGENT_TABLE)?;
+                let Some(existing) = table.get(id)? else {
+                    return Ok(false);
+                };
+
+                let current_status = Self::parse_task_status(&existing)?;
+                if current_status != expected_status {
+                    return Ok(false);
+                }
 
-        let mut status_index = write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-        let mut status_lookup = write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-        if let Some(previous_key) = status_lookup.get(id)? {
-            status_index.remove(previous_key.value())?;
-        } else if current_status != new_status {
-            let old_key = format!("{}:{}", current_status, id);
-            status_index.remove(old_key.as_str())?;
-        }
+                let chat_session_id = Self::extract_chat_session_id(data);
+                Self::ensure_unique_chat_session_binding(
+                    table.as_ref(),
+                    id,
+                    chat_session_id.as_deref(),
+                )?;
+                table.put(id, data)?;
+                current_status
+            };
 
-        let new_key = format!("{}:{}", new_status, id);
-        status_index.insert(new_key.as_str(), id)?;
-        status_lookup.insert(id, new_key.as_str())?;
-        drop(status_lookup);
-        drop(status_index);
-        write_txn.commit()?;
-        Ok(true)
+            let old_key =
+                (current_status != new_status).then(|| format!("{}:{}", current_status, id));
+            let new_key = format!("{}:{}", new_status, id);
+            Self::move_status_key(
+                txn.table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?.as_mut(),
+                id,
+                old_key.as_deref(),
+                Some(&new_key),
+            )?;
+            Ok(true)
+        })
     }
 
     /// Get raw agent task data by ID
     pub fn get_task_raw(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-
-        if let Some(value) = table.get(id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(BACKGROUND_AGENT_TABLE, |table| table.get(id))
     }
 
     /// List all raw agent task data
     pub fn list_tasks_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-
-        let mut tasks = Vec::new();
-        for item in table.iter()? {
-            let (key, value) = item?;
-            tasks.push((key.value().to_string(), value.value().to_vec()));
-        }
-
-        Ok(tasks)
+        self.backend
+            .read_table(BACKGROUND_AGENT_TABLE, |table| table.scan_prefix(""))
     }
 
     /// List tasks by status using the status index
     pub fn list_tasks_by_status_indexed(&self, status: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let status_index = read_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-        let task_table = read_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-
-        let prefix = format!("{}:", status);
-        let (start, end) = prefix_range(&prefix);
-        let mut tasks = Vec::new();
-
-        for item in status_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let task_id = value.value();
-            if let Some(data) = task_table.get(task_id)? {
-                tasks.push((task_id.to_string(), data.value().to_vec()));
-            }
-        }
-
-        Ok(tasks)
+        self.list_indexed(
+            BACKGROUND_AGENT_STATUS_INDEX_TABLE,
+            BACKGROUND_AGENT_TABLE,
+            &format!("{}:", status),
+        )
     }
 
     /// Delete agent task by ID
     pub fn delete_task(&self, id: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            let existed = table.remove(id)?.is_some();
-
-            let mut status_index = write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-            let mut active_run_index =
-                write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(id)? {
-                status_index.remove(previous_key.value())?;
-            }
-            status_lookup.remove(id)?;
-            active_run_index.remove(id)?;
-
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+        self.delete_task_indexed(id, None)
     }
 
     /// Delete agent task by ID with status index cleanup
     pub fn delete_task_with_status(&self, id: &str, status: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            let existed = table.remove(id)?.is_some();
-
-            let mut status_index = write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-            let mut active_run_index =
-                write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(id)? {
-                status_index.remove(previous_key.value())?;
-            } else {
-                let status_key = format!("{}:{}", status, id);
-                status_index.remove(status_key.as_str())?;
-            }
-            status_lookup.remove(id)?;
-            active_run_index.remove(id)?;
+        self.delete_task_indexed(id, Some(&format!("{}:{}", status, id)))
+    }
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+    fn delete_task_indexed(&self, id: &str, fallback_status_key: Option<&str>) -> Result<bool> {
+        self.backend.write_tables(|txn| {
+            let existed = txn.table(BACKGROUND_AGENT_TABLE)?.remove(id)?;
+
+            Self::move_status_key(
+                txn.table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?.as_mut(),
+                id,
+                fallback_status_key,
+                None,
+            )?;
+            txn.table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?
+                .remove(id)?;
+
+            Ok(existed)
+        })
     }
 
     /// Delete a task and all related task/message/event records atomically.
     pub fn delete_task_cascade(&self, id: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut task_table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut task_table = txn.table(BACKGROUND_AGENT_TABLE)?;
             let existed = task_table.get(id)?.is_some();
 
             let prefix = format!("{}:", id);
-            let (start, end) = prefix_range(&prefix);
-
-            let mut event_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-            let mut event_index = write_txn.open_table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
-            let mut event_keys = Vec::new();
-            for item in event_index.range(start.as_str()..end.as_str())? {
-                let (key, value) = item?;
-                event_keys.push((key.value().to_string(), value.value().to_string()));
-            }
-            for (event_key, event_id) in event_keys {
-                event_index.remove(event_key.as_str())?;
-                event_table.remove(event_id.as_str())?;
-            }
 
-            let mut run_table = write_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-            let mut run_task_index = write_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
-            let mut active_run_index =
-                write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-            let mut run_keys = Vec::new();
-            for item in run_task_index.range(start.as_str()..end.as_str())? {
-                let (key, value) = item?;
-                run_keys.push((key.value().to_string(), value.value().to_string()));
-            }
-            for (run_key, run_id) in run_keys {
-                run_task_index.remove(run_key.as_str())?;
-                run_table.remove(run_id.as_str())?;
-            }
-            active_run_index.remove(id)?;
-
-            let mut message_table = write_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-            let mut message_task_index =
-                write_txn.open_table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
-            let mut message_status_index =
-                write_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-            let mut message_status_lookup =
-                write_txn.open_table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
-            let mut message_keys = Vec::new();
-            for item in message_task_index.range(start.as_str()..end.as_str())? {
-                let (key, value) = item?;
-                message_keys.push((key.value().to_string(), value.value().to_string()));
+            {
+                let mut event_table = txn.table(BACKGROUND_AGENT_EVENT_TABLE)?;
+                let mut event_index = txn.table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
+                for (event_key, event_id) in event_index.scan_prefix(&prefix)? {
+                    event_index.remove(&event_key)?;
+                    event_table.remove(&text_value(event_id)?)?;
+                }
             }
-            for (message_key, message_id) in message_keys {
-                message_task_index.remove(message_key.as_str())?;
-                if let Some(status_key) = message_status_lookup.get(message_id.as_str())? {
-                    message_status_index.remove(status_key.value())?;
+
+            {
+                let mut run_table = txn.table(BACKGROUND_AGENT_RUN_TABLE)?;
+                let mut run_task_index = txn.table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
+                for (run_key, run_id) in run_task_index.scan_prefix(&prefix)? {
+                    run_task_index.remove(&run_key)?;
+                    run_table.remove(&text_value(run_id)?)?;
                 }
-                message_status_lookup.remove(message_id.as_str())?;
-                message_table.remove(message_id.as_str())?;
+                txn.table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?
+                    .remove(id)?;
             }
 
-            let mut task_status_index =
-                write_txn.open_table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?;
-            let mut task_status_lookup =
-                write_txn.open_table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?;
-            if let Some(status_key) = task_status_lookup.get(id)? {
-                task_status_index.remove(status_key.value())?;
+            {
+                let mut message_table = txn.table(BACKGROUND_MESSAGE_TABLE)?;
+                let mut message_task_index = txn.table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
+                let mut message_status_index = txn.table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
+                let mut message_status_lookup =
+                    txn.table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
+                for (message_key, message_id) in message_task_index.scan_prefix(&prefix)? {
+                    let message_id = text_value(message_id)?;
+                    message_task_index.remove(&message_key)?;
+                    Self::move_status_key(
+                        message_status_index.as_mut(),
+                        message_status_lookup.as_mut(),
+                        &message_id,
+                        None,
+                        None,
+                    )?;
+                    message_table.remove(&message_id)?;
+                }
             }
-            task_status_lookup.remove(id)?;
+
+            Self::move_status_key(
+                txn.table(BACKGROUND_AGENT_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_AGENT_STATUS_LOOKUP_TABLE)?.as_mut(),
+                id,
+                None,
+                None,
+            )?;
 
             if existed {
                 task_table.remove(id)?;
             }
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+            Ok(existed)
+        })
     }
 
     /// Store raw background run data with a task index entry.
     pub fn put_run_raw(&self, run_id: &str, task_id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut run_table = write_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-            run_table.insert(run_id, data)?;
-
-            let mut task_index = write_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
-            let task_key = format!("{}:{}", task_id, run_id);
-            task_index.insert(task_key.as_str(), run_id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.update_run_raw(run_id, task_id, data)
     }
 
     /// Store raw background run data and keep the active-run index consistent.
@@ -575,18 +536,14 @@ impl BackgroundAgentStorage {
     ) -> Result<()> {
         Self::validate_run_payload(run_id, task_id, status, data)?;
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let task_table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            Self::ensure_task_exists(&task_table, task_id, run_id)?;
-            drop(task_table);
+        self.backend.write_tables(|txn| {
+            Self::ensure_task_exists(txn.table(BACKGROUND_AGENT_TABLE)?.as_ref(), task_id, run_id)?;
 
-            let mut run_table = write_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-            let mut task_index = write_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
-            let mut active_index = write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
+            let mut run_table = txn.table(BACKGROUND_AGENT_RUN_TABLE)?;
+            let mut active_index = txn.table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
 
             if let Some(existing) = run_table.get(run_id)? {
-                let existing_task_id = Self::parse_run_task_id(existing.value())?;
+                let existing_task_id = Self::parse_run_task_id(&existing)?;
                 if existing_task_id != task_id {
                     anyhow::bail!(
                         "background run '{}' is indexed under task '{}', not '{}'",
@@ -598,34 +555,29 @@ impl BackgroundAgentStorage {
             }
 
             Self::reconcile_active_run_slot(
-                &mut active_index,
-                &run_table,
+                active_index.as_mut(),
+                run_table.as_ref(),
                 task_id,
                 run_id,
                 status,
             )?;
-            run_table.insert(run_id, data)?;
+            run_table.put(run_id, data)?;
 
             let task_key = format!("{}:{}", task_id, run_id);
-            task_index.insert(task_key.as_str(), run_id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            txn.table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?
+                .put(&task_key, run_id.as_bytes())
+        })
     }
 
     /// Update raw background run data while preserving the task index.
     pub fn update_run_raw(&self, run_id: &str, task_id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut run_table = write_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-            run_table.insert(run_id, data)?;
+        self.backend.write_tables(|txn| {
+            txn.table(BACKGROUND_AGENT_RUN_TABLE)?.put(run_id, data)?;
 
-            let mut task_index = write_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
             let task_key = format!("{}:{}", task_id, run_id);
-            task_index.insert(task_key.as_str(), run_id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            txn.table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?
+                .put(&task_key, run_id.as_bytes())
+        })
     }
 
     /// Update raw background run data while preserving task index and active-run consistency.
@@ -639,16 +591,13 @@ impl BackgroundAgentStorage {
     ) -> Result<()> {
         Self::validate_run_payload(run_id, task_id, new_status, data)?;
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let task_table = write_txn.open_table(BACKGROUND_AGENT_TABLE)?;
-            Self::ensure_task_exists(&task_table, task_id, run_id)?;
-            drop(task_table);
+        self.backend.write_tables(|txn| {
+            Self::ensure_task_exists(txn.table(BACKGROUND_AGENT_TABLE)?.as_ref(), task_id, run_id)?;
 
-            let mut run_table = write_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
+            let mut run_table = txn.table(BACKGROUND_AGENT_RUN_TABLE)?;
             let current_status = match run_table.get(run_id)? {
                 Some(existing) => {
-                    let existing_task_id = Self::parse_run_task_id(existing.value())?;
+                    let existing_task_id = Self::parse_run_task_id(&existing)?;
                     if existing_task_id != task_id {
                         anyhow::bail!(
                             "background run '{}' is indexed under task '{}', not '{}'",
@@ -657,120 +606,96 @@ impl BackgroundAgentStorage {
                             task_id
                         );
                     }
-                    Self::parse_run_status(existing.value())?
+                    Self::parse_run_status(&existing)?
                 }
                 None => old_status.to_string(),
             };
 
-            let mut task_index = write_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
-            let mut active_index = write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
+            let mut active_index = txn.table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
 
             Self::reconcile_active_run_slot(
-                &mut active_index,
-                &run_table,
+                active_index.as_mut(),
+                run_table.as_ref(),
                 task_id,
                 run_id,
                 new_status,
             )?;
-            run_table.insert(run_id, data)?;
+            run_table.put(run_id, data)?;
 
             let task_key = format!("{}:{}", task_id, run_id);
-            task_index.insert(task_key.as_str(), run_id)?;
+            txn.table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?
+                .put(&task_key, run_id.as_bytes())?;
 
             if current_status == "running"
                 && new_status != "running"
                 && active_index
                     .get(task_id)?
-                    .is_some_and(|value| value.value() == run_id)
+                    .is_some_and(|value| value == run_id.as_bytes())
             {
                 active_index.remove(task_id)?;
             }
-        }
-        write_txn.commit()?;
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Get raw background run data by ID.
     pub fn get_run_raw(&self, run_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let run_table = read_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-        Ok(run_table.get(run_id)?.map(|value| value.value().to_vec()))
+        self.backend
+            .read_table(BACKGROUND_AGENT_RUN_TABLE, |run_table| {
+                run_table.get(run_id)
+            })
     }
 
     /// List all raw background run payloads.
     pub fn list_runs_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let run_table = read_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-        let mut runs = Vec::new();
-        for item in run_table.iter()? {
-            let (key, value) = item?;
-            runs.push((key.value().to_string(), value.value().to_vec()));
-        }
-        Ok(runs)
+        self.backend
+            .read_table(BACKGROUND_AGENT_RUN_TABLE, |run_table| {
+                run_table.scan_prefix("")
+            })
     }
 
     /// List raw background run payloads for one task.
     pub fn list_runs_by_task_raw(&self, task_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let task_index = read_txn.open_table(BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE)?;
-        let run_table = read_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-
-        let prefix = format!("{}:", task_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut runs = Vec::new();
-        for item in task_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let run_id = value.value();
-            if let Some(data) = run_table.get(run_id)? {
-                runs.push((run_id.to_string(), data.value().to_vec()));
-            }
-        }
-        Ok(runs)
+        self.list_indexed(
+            BACKGROUND_AGENT_RUN_TASK_INDEX_TABLE,
+            BACKGROUND_AGENT_RUN_TABLE,
+            &format!("{}:", task_id),
+        )
     }
 
     /// Return the active run payload referenced by the task-level active-run index.
     pub fn get_active_run_raw(&self, task_id: &str) -> Result<Option<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let active_index = read_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-        let run_table = read_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-
-        let Some(run_id) = active_index.get(task_id)? else {
-            return Ok(None);
-        };
-        let run_id = run_id.value().to_string();
-        let Some(data) = run_table.get(run_id.as_str())? else {
-            return Ok(None);
-        };
-        Ok(Some((run_id, data.value().to_vec())))
+        self.backend.read_tables(|txn| {
+            let Some(run_id) = txn
+                .table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?
+                .get(task_id)?
+            else {
+                return Ok(None);
+            };
+            let run_id = text_value(run_id)?;
+            let Some(data) = txn.table(BACKGROUND_AGENT_RUN_TABLE)?.get(&run_id)? else {
+                return Ok(None);
+            };
+            Ok(Some((run_id, data)))
+        })
     }
 
     /// Remove one task-level active-run index entry.
     pub fn clear_active_run_raw(&self, task_id: &str) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut active_index = write_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-            active_index.remove(task_id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.backend
+            .write_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE, |active_index| {
+                active_index.remove(task_id)?;
+                Ok(())
+            })
     }
 
     /// List all active run payloads referenced by the task-level active-run index.
     pub fn list_active_runs_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let active_index = read_txn.open_table(BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE)?;
-        let run_table = read_txn.open_table(BACKGROUND_AGENT_RUN_TABLE)?;
-        let mut runs = Vec::new();
-
-        for item in active_index.iter()? {
-            let (_, run_id) = item?;
-            let run_id = run_id.value();
-            if let Some(data) = run_table.get(run_id)? {
-                runs.push((run_id.to_string(), data.value().to_vec()));
-            }
-        }
-
-        Ok(runs)
+        self.list_indexed(
+            BACKGROUND_AGENT_ACTIVE_RUN_INDEX_TABLE,
+            BACKGROUND_AGENT_RUN_TABLE,
+            "",
+        )
     }
 
     // ============== Background Message Operations ==============
@@ -783,26 +708,22 @@ impl BackgroundAgentStorage {
         status: &str,
         data: &[u8],
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut message_table = write_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-            message_table.insert(message_id, data)?;
+        self.backend.write_tables(|txn| {
+            txn.table(BACKGROUND_MESSAGE_TABLE)?.put(message_id, data)?;
 
-            let mut task_index = write_txn.open_table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
             let task_key = format!("{}:{}", task_id, message_id);
-            task_index.insert(task_key.as_str(), message_id)?;
+            txn.table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?
+                .put(&task_key, message_id.as_bytes())?;
 
-            let mut status_index = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(message_id)? {
-                status_index.remove(previous_key.value())?;
-            }
             let status_key = format!("{}:{}:{}", status, task_id, message_id);
-            status_index.insert(status_key.as_str(), message_id)?;
-            status_lookup.insert(message_id, status_key.as_str())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            Self::move_status_key(
+                txn.table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?.as_mut(),
+                message_id,
+                None,
+                Some(&status_key),
+            )
+        })
     }
 
     /// Update raw background message data and keep status index consistent.
@@ -814,38 +735,26 @@ impl BackgroundAgentStorage {
         new_status: &str,
         data: &[u8],
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut message_table = write_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-            message_table.insert(message_id, data)?;
-
-            let mut status_index = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(message_id)? {
-                status_index.remove(previous_key.value())?;
-            } else if old_status != new_status {
-                let old_key = format!("{}:{}:{}", old_status, task_id, message_id);
-                status_index.remove(old_key.as_str())?;
-            }
+        self.backend.write_tables(|txn| {
+            txn.table(BACKGROUND_MESSAGE_TABLE)?.put(message_id, data)?;
 
+            let old_key = (old_status != new_status)
+                .then(|| format!("{}:{}:{}", old_status, task_id, message_id));
             let new_key = format!("{}:{}:{}", new_status, task_id, message_id);
-            status_index.insert(new_key.as_str(), message_id)?;
-            status_lookup.insert(message_id, new_key.as_str())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            Self::move_status_key(
+                txn.table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?.as_mut(),
+                message_id,
+                old_key.as_deref(),
+                Some(&new_key),
+            )
+        })
     }
 
     /// Get raw background message data by ID.
     pub fn get_background_message_raw(&self, message_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-
-        if let Some(value) = table.get(message_id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(BACKGROUND_MESSAGE_TABLE, |table| table.get(message_id))
     }
 
     /// List raw background messages for a task.
@@ -853,23 +762,11 @@ impl BackgroundAgentStorage {
         &self,
         task_id: &str,
     ) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let task_index = read_txn.open_table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
-        let message_table = read_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-
-        let prefix = format!("{}:", task_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut messages = Vec::new();
-
-        for item in task_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let message_id = value.value();
-            if let Some(data) = message_table.get(message_id)? {
-                messages.push((message_id.to_string(), data.value().to_vec()));
-            }
-        }
-
-        Ok(messages)
+        self.list_indexed(
+            BACKGROUND_MESSAGE_TASK_INDEX_TABLE,
+            BACKGROUND_MESSAGE_TABLE,
+            &format!("{}:", task_id),
+        )
     }
 
     /// List raw background messages for a task by status.
@@ -878,23 +775,11 @@ impl BackgroundAgentStorage {
         task_id: &str,
         status: &str,
     ) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let status_index = read_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-        let message_table = read_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-
-        let prefix = format!("{}:{}:", status, task_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut messages = Vec::new();
-
-        for item in status_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let message_id = value.value();
-            if let Some(data) = message_table.get(message_id)? {
-                messages.push((message_id.to_string(), data.value().to_vec()));
-            }
-        }
-
-        Ok(messages)
+        self.list_indexed(
+            BACKGROUND_MESSAGE_STATUS_INDEX_TABLE,
+            BACKGROUND_MESSAGE_TABLE,
+            &format!("{}:{}:", status, task_id),
+        )
     }
 
     /// Delete one background message and related indices.
@@ -904,29 +789,24 @@ impl BackgroundAgentStorage {
         task_id: &str,
         status: &str,
     ) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut message_table = write_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-            let existed = message_table.remove(message_id)?.is_some();
+        self.backend.write_tables(|txn| {
+            let existed = txn.table(BACKGROUND_MESSAGE_TABLE)?.remove(message_id)?;
 
-            let mut task_index = write_txn.open_table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
             let task_key = format!("{}:{}", task_id, message_id);
-            task_index.remove(task_key.as_str())?;
-
-            let mut status_index = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
-            if let Some(previous_key) = status_lookup.get(message_id)? {
-                status_index.remove(previous_key.value())?;
-            } else {
-                let status_key = format!("{}:{}:{}", status, task_id, message_id);
-                status_index.remove(status_key.as_str())?;
-            }
-            status_lookup.remove(message_id)?;
+            txn.table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?
+                .remove(&task_key)?;
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+            let status_key = format!("{}:{}:{}", status, task_id, message_id);
+            Self::move_status_key(
+                txn.table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?.as_mut(),
+                txn.table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?.as_mut(),
+                message_id,
+                Some(&status_key),
+                None,
+            )?;
+
+            Ok(existed)
+        })
     }
 
     /// Delete all background messages for a task.
@@ -938,31 +818,34 @@ impl BackgroundAgentStorage {
             return Ok(0);
         }
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut message_table = write_txn.open_table(BACKGROUND_MESSAGE_TABLE)?;
-            let mut task_index = write_txn.open_table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
-            let mut status_index = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
-            let mut status_lookup = write_txn.open_table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut message_table = txn.table(BACKGROUND_MESSAGE_TABLE)?;
+            let mut task_index = txn.table(BACKGROUND_MESSAGE_TASK_INDEX_TABLE)?;
+            let mut status_index = txn.table(BACKGROUND_MESSAGE_STATUS_INDEX_TABLE)?;
+            let mut status_lookup = txn.table(BACKGROUND_MESSAGE_STATUS_LOOKUP_TABLE)?;
 
             for (message_id, data) in &messages {
-                message_table.remove(message_id.as_str())?;
+                message_table.remove(message_id)?;
 
                 let task_key = format!("{}:{}", task_id, message_id);
-                task_index.remove(task_key.as_str())?;
-
-                if let Some(previous_key) = status_lookup.get(message_id.as_str())? {
-                    status_index.remove(previous_key.value())?;
-                } else if let Ok(value) = serde_json::from_slice::<serde_json::Value>(data)
-                    && let Some(status) = value.get("status").and_then(|s| s.as_str())
-                {
-                    let status_key = format!("{}:{}:{}", status, task_id, message_id);
-                    status_index.remove(status_key.as_str())?;
-                }
-                status_lookup.remove(message_id.as_str())?;
+                task_index.remove(&task_key)?;
+
+                let status_key = serde_json::from_slice::<serde_json::Value>(data)
+                    .ok()
+                    .and_then(|value| {
+                        let status = value.get("status")?.as_str()?.to_string();
+                        Some(format!("{}:{}:{}", status, task_id, message_id))
+                    });
+                Self::move_status_key(
+                    status_index.as_mut(),
+                    status_lookup.as_mut(),
+                    message_id,
+                    status_key.as_deref(),
+                    None,
+                )?;
             }
-        }
-        write_txn.commit()?;
+            Ok(())
+        })?;
         Ok(count)
     }
 
@@ -970,69 +853,44 @@ impl BackgroundAgentStorage {
 
     /// Store raw task event data with index
     pub fn put_event_raw(&self, event_id: &str, task_id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut event_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-            event_table.insert(event_id, data)?;
+        self.backend.write_tables(|txn| {
+            txn.table(BACKGROUND_AGENT_EVENT_TABLE)?
+                .put(event_id, data)?;
 
             // Create composite index key: task_id:timestamp:event_id for ordered retrieval
-            let mut index_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
             let index_key = format!("{}:{}", task_id, event_id);
-            index_table.insert(index_key.as_str(), event_id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            txn.table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?
+                .put(&index_key, event_id.as_bytes())
+        })
     }
 
     /// Get raw task event data by ID
     pub fn get_event_raw(&self, event_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-
-        if let Some(value) = table.get(event_id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(BACKGROUND_AGENT_EVENT_TABLE, |table| table.get(event_id))
     }
 
     /// List all events for a specific task
     pub fn list_events_for_task_raw(&self, task_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let index_table = read_txn.open_table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
-        let event_table = read_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-
-        let prefix = format!("{}:", task_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut events = Vec::new();
-
-        for item in index_table.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let event_id = value.value();
-            if let Some(event_data) = event_table.get(event_id)? {
-                events.push((event_id.to_string(), event_data.value().to_vec()));
-            }
-        }
-
-        Ok(events)
+        self.list_indexed(
+            BACKGROUND_AGENT_EVENT_INDEX_TABLE,
+            BACKGROUND_AGENT_EVENT_TABLE,
+            &format!("{}:", task_id),
+        )
     }
 
     /// Delete a task event by ID
     pub fn delete_event(&self, event_id: &str, task_id: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut event_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-            let existed = event_table.remove(event_id)?.is_some();
+        self.backend.write_tables(|txn| {
+            let existed = txn.table(BACKGROUND_AGENT_EVENT_TABLE)?.remove(event_id)?;
 
             // Remove from index
-            let mut index_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
             let index_key = format!("{}:{}", task_id, event_id);
-            index_table.remove(index_key.as_str())?;
+            txn.table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?
+                .remove(&index_key)?;
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+            Ok(existed)
+        })
     }
 
     /// Delete all events for a specific task
@@ -1045,18 +903,17 @@ impl BackgroundAgentStorage {
             return Ok(0);
         }
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut event_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_TABLE)?;
-            let mut index_table = write_txn.open_table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut event_table = txn.table(BACKGROUND_AGENT_EVENT_TABLE)?;
+            let mut index_table = txn.table(BACKGROUND_AGENT_EVENT_INDEX_TABLE)?;
 
             for (event_id, _) in &events {
-                event_table.remove(event_id.as_str())?;
+                event_table.remove(event_id)?;
                 let index_key = format!("{}:{}", task_id, event_id);
-                index_table.remove(index_key.as_str())?;
+                index_table.remove(&index_key)?;
             }
-        }
-        write_txn.commit()?;
+            Ok(())
+        })?;
 
         Ok(count)
     }
@@ -0,0 +1,173 @@
+//! Online backup and restore of the RestFlow database.
+//!
+//! A backup is a standalone redb file holding every table of the configured
+//! [`StorageBackend`] from one read snapshot, so it can be taken while the
+//! daemon keeps writing and restored into either backend.
+//!
+//! Restore replaces the live tables inside a single write transaction: either
+//! every table is swapped or none is.
+
+use anyhow::{Context, Result, bail};
+use redb::Database;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::backend::{ReadTransaction, RedbBackend, StorageBackend, WriteTransaction};
+
+/// What a backup or restore copied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub tables: usize,
+    pub entries: usize,
+}
+
+fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_os_string();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+/// Write a consistent copy of every `backend` table to `dest`.
+///
+/// `dest` must not exist yet. The file is written next to it first and only
+/// renamed into place once complete, so a failed backup leaves nothing behind.
+pub fn backup_to(backend: &dyn StorageBackend, dest: &Path) -> Result<BackupSummary> {
+    if dest.exists() {
+        bail!("Backup destination already exists: {}", dest.display());
+    }
+    let partial = partial_path(dest);
+    let _ = std::fs::remove_file(&partial);
+
+    let result = write_backup(backend, &partial).and_then(|summary| {
+        std::fs::rename(&partial, dest)
+            .with_context(|| format!("Failed to move backup to {}", dest.display()))?;
+        Ok(summary)
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+fn write_backup(backend: &dyn StorageBackend, path: &Path) -> Result<BackupSummary> {
+    let db = Database::create(path)
+        .with_context(|| format!("Failed to create backup file {}", path.display()))?;
+    let target = RedbBackend::new(Arc::new(db));
+    let target: &dyn StorageBackend = &target;
+
+    let tables = backend.table_names()?;
+    backend.read_tables(|reader| target.write_tables(|writer| copy_tables(reader, writer, &tables)))
+}
+
+/// Replace every `backend` table with the contents of the backup at `src`.
+///
+/// Live tables missing from the backup are emptied, so the result matches
+/// the backup exactly.
+pub fn restore_from(backend: &dyn StorageBackend, src: &Path) -> Result<BackupSummary> {
+    if !src.is_file() {
+        bail!("Backup file not found: {}", src.display());
+    }
+    let db = Database::open(src)
+        .with_context(|| format!("Failed to open backup file {}", src.display()))?;
+    let source = RedbBackend::new(Arc::new(db));
+    let tables = source.table_names()?;
+    let live_tables = backend.table_names()?;
+
+    let source: &dyn StorageBackend = &source;
+    source.read_tables(|reader| {
+        backend.write_tables(|writer| {
+            for table in &live_tables {
+                writer.clear(table)?;
+            }
+            copy_tables(reader, writer, &tables)
+        })
+    })
+}
+
+/// Copy every row of `tables` from `reader` into `writer`.
+pub(crate) fn copy_tables(
+    reader: &dyn ReadTransaction,
+    writer: &dyn WriteTransaction,
+    tables: &[String],
+) -> Result<BackupSummary> {
+    let mut summary = BackupSummary::default();
+    for table in tables {
+        let rows = reader.table(table)?.scan_prefix("")?;
+        let mut dest = writer.table(table)?;
+        for (key, value) in &rows {
+            dest.put(key, value)?;
+        }
+        summary.tables += 1;
+        summary.entries += rows.len();
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KvStoreStorage, SimpleStorage, SqliteBackend};
+    use tempfile::tempdir;
+
+    const INDEX: &str = "backup_test_index";
+
+    fn round_trip(backend_for: impl Fn(&Path) -> Arc<dyn StorageBackend>) {
+        let dir = tempdir().unwrap();
+        let backend = backend_for(dir.path());
+        let kv = KvStoreStorage::with_backend(backend.clone()).unwrap();
+        let put_index = |value: &[u8]| {
+            backend
+                .write_table(INDEX, |table| table.put("index", value))
+                .unwrap()
+        };
+
+        kv.put_raw("kept", b"before").unwrap();
+        put_index(b"before");
+
+        let backup = dir.path().join("backup.redb");
+        let summary = backup_to(backend.as_ref(), &backup).unwrap();
+        assert_eq!(summary.entries, 2);
+        assert!(!partial_path(&backup).exists());
+
+        kv.put_raw("kept", b"after").unwrap();
+        kv.put_raw("added", b"after").unwrap();
+        put_index(b"after");
+        backend
+            .write_table("created_after_backup", |table| table.put("k", b"v"))
+            .unwrap();
+
+        restore_from(backend.as_ref(), &backup).unwrap();
+        assert_eq!(kv.get_raw("kept").unwrap(), Some(b"before".to_vec()));
+        assert_eq!(kv.get_raw("added").unwrap(), None);
+        let index = backend
+            .read_table(INDEX, |table| table.get("index"))
+            .unwrap();
+        assert_eq!(index, Some(b"before".to_vec()));
+        let extra = backend
+            .read_table("created_after_backup", |table| table.count())
+            .unwrap();
+        assert_eq!(extra, 0);
+    }
+
+    #[test]
+    fn test_round_trip_redb() {
+        round_trip(|dir| Arc::new(RedbBackend::open(&dir.join("live.db")).unwrap()));
+    }
+
+    #[test]
+    fn test_round_trip_sqlite() {
+        round_trip(|dir| Arc::new(SqliteBackend::open(&dir.join("live.sqlite")).unwrap()));
+    }
+
+    #[test]
+    fn test_backup_refuses_existing_destination() {
+        let dir = tempdir().unwrap();
+        let backend = RedbBackend::open(&dir.path().join("live.db")).unwrap();
+        let dest = dir.path().join("backup.redb");
+        std::fs::write(&dest, b"existing").unwrap();
+
+        assert!(backup_to(&backend, &dest).is_err());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"existing");
+    }
+}
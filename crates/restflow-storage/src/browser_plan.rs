@@ -0,0 +1,135 @@
+//! Browser plan storage - byte-level API for saved, versioned browser action plans.
+
+use anyhow::Result;
+use redb::Database;
+use std::sync::Arc;
+
+use crate::backend::{RedbBackend, StorageBackend};
+
+/// Latest version of each plan: name -> plan
+const BROWSER_PLAN_TABLE: &str = "browser_plans";
+/// Every saved version: name:version -> plan
+const BROWSER_PLAN_VERSION_TABLE: &str = "browser_plan_versions";
+
+fn version_key(name: &str, version: u32) -> String {
+    format!("{}:{:010}", name, version)
+}
+
+/// Low-level browser plan storage with byte-level API.
+#[derive(Clone)]
+pub struct BrowserPlanStorage {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl BrowserPlanStorage {
+    /// Create a new BrowserPlanStorage instance.
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        backend.ensure_table(BROWSER_PLAN_TABLE)?;
+        backend.ensure_table(BROWSER_PLAN_VERSION_TABLE)?;
+        Ok(Self { backend })
+    }
+
+    /// Store a plan version and make it the latest version of the plan.
+    pub fn put_version_raw(&self, name: &str, version: u32, data: &[u8]) -> Result<()> {
+        self.backend.write_tables(|txn| {
+            txn.table(BROWSER_PLAN_TABLE)?.put(name, data)?;
+            txn.table(BROWSER_PLAN_VERSION_TABLE)?
+                .put(&version_key(name, version), data)
+        })
+    }
+
+    /// Get the latest raw version of a plan.
+    pub fn get_raw(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        self.backend
+            .read_table(BROWSER_PLAN_TABLE, |table| table.get(name))
+    }
+
+    /// Get a specific raw version of a plan.
+    pub fn get_version_raw(&self, name: &str, version: u32) -> Result<Option<Vec<u8>>> {
+        self.backend
+            .read_table(BROWSER_PLAN_VERSION_TABLE, |table| {
+                table.get(&version_key(name, version))
+            })
+    }
+
+    /// List the latest raw version of every plan.
+    pub fn list_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        self.backend
+            .read_table(BROWSER_PLAN_TABLE, |table| table.scan_prefix(""))
+    }
+
+    /// List every raw version of a plan, oldest first.
+    pub fn list_versions_raw(&self, name: &str) -> Result<Vec<Vec<u8>>> {
+        let prefix = format!("{}:", name);
+        let entries = self
+            .backend
+            .read_table(BROWSER_PLAN_VERSION_TABLE, |table| {
+                table.scan_prefix(&prefix)
+            })?;
+        Ok(entries.into_iter().map(|(_, value)| value).collect())
+    }
+
+    /// Delete a plan together with all of its versions.
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        self.backend.write_tables(|txn| {
+            let existed = txn.table(BROWSER_PLAN_TABLE)?.remove(name)?;
+
+            let mut versions = txn.table(BROWSER_PLAN_VERSION_TABLE)?;
+            for key in versions.keys_with_prefix(&format!("{}:", name))? {
+                versions.remove(&key)?;
+            }
+
+            Ok(existed)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_browser_plan_storage_versions() {
+        let dir = tempdir().expect("temp dir should be created");
+        let db_path = dir.path().join("browser-plan-storage.db");
+        let db = Arc::new(Database::create(db_path).expect("db should be created"));
+        let storage = BrowserPlanStorage::new(db).expect("storage should be created");
+
+        storage
+            .put_version_raw("login", 1, br#"{"version":1}"#)
+            .expect("first put should succeed");
+        storage
+            .put_version_raw("login", 2, br#"{"version":2}"#)
+            .expect("second put should succeed");
+        storage
+            .put_version_raw("login-extra", 1, br#"{"version":1}"#)
+            .expect("other plan put should succeed");
+
+        let latest = storage.get_raw("login").expect("get should succeed");
+        assert_eq!(latest.as_deref(), Some(br#"{"version":2}"#.as_slice()));
+
+        let first = storage
+            .get_version_raw("login", 1)
+            .expect("get version should succeed");
+        assert_eq!(first.as_deref(), Some(br#"{"version":1}"#.as_slice()));
+
+        let versions = storage
+            .list_versions_raw("login")
+            .expect("list versions should succeed");
+        assert_eq!(versions.len(), 2);
+
+        let all = storage.list_raw().expect("list should succeed");
+        assert_eq!(all.len(), 2);
+
+        assert!(storage.delete("login").expect("delete should succeed"));
+        assert!(storage.get_raw("login").unwrap().is_none());
+        assert!(storage.list_versions_raw("login").unwrap().is_empty());
+        assert_eq!(storage.list_versions_raw("login-extra").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,457 @@
+//! Full-text index over chat messages.
+//!
+//! Each document is one message. Callers hand in the message already split
+//! into lowercase words (joined by spaces), so matching follows the caller's
+//! tokenizer rather than tantivy's. Sessions are indexed incrementally:
+//! appended messages are added on their own and a session is only re-indexed
+//! when earlier messages or its agent, skill or archive state change.
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::Arc;
+use tantivy::collector::{Count, TopDocs};
+use tantivy::doc;
+use tantivy::query::{BooleanQuery, Occur, PhrasePrefixQuery, PhraseQuery, Query, TermQuery};
+use tantivy::schema::{
+    Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing, TextOptions,
+    Value,
+};
+use tantivy::tokenizer::{RemoveLongFilter, TextAnalyzer, WhitespaceTokenizer};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+
+const WORDS_TOKENIZER: &str = "chat_words";
+/// Words longer than this (bytes) are not indexed.
+const MAX_WORD_LEN: usize = 100;
+/// Upper bound on the distinct words a prefix may expand to.
+const MAX_PREFIX_EXPANSIONS: u32 = 1_000;
+
+#[derive(Debug, Clone)]
+pub struct IndexableMessage {
+    pub session_id: String,
+    /// Position of the message within its session.
+    pub position: usize,
+    pub agent_id: String,
+    pub skill_id: Option<String>,
+    pub archived: bool,
+    /// Lowercase words of the message content separated by single spaces.
+    pub words: String,
+}
+
+/// One term or phrase of a query. With `prefix`, the last word also
+/// matches longer words starting with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatQueryUnit {
+    pub words: Vec<String>,
+    pub prefix: bool,
+}
+
+/// Restricts a search to part of the index.
+#[derive(Debug, Clone, Default)]
+pub struct ChatIndexFilter {
+    pub agent_id: Option<String>,
+    pub skill_id: Option<String>,
+    pub include_archived: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessageHit {
+    pub session_id: String,
+    pub position: usize,
+    pub score: f32,
+}
+
+/// One page of hits plus the number of matches across all pages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatIndexPage {
+    pub hits: Vec<ChatMessageHit>,
+    pub total_count: usize,
+}
+
+pub struct ChatMessageIndex {
+    reader: IndexReader,
+    writer: Arc<Mutex<IndexWriter>>,
+    session_id_field: Field,
+    position_field: Field,
+    agent_id_field: Field,
+    skill_id_field: Field,
+    archived_field: Field,
+    words_field: Field,
+}
+
+impl std::fmt::Debug for ChatMessageIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatMessageIndex").finish_non_exhaustive()
+    }
+}
+
+impl ChatMessageIndex {
+    pub fn open(path: &Path) -> Result<Self> {
+        std::fs::create_dir_all(path)
+            .with_context(|| format!("failed to create index dir: {}", path.display()))?;
+
+        let schema = build_schema();
+        let index = Index::open_in_dir(path).or_else(|_| Index::create_in_dir(path, schema))?;
+        Self::from_index(index)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::from_index(Index::create_in_ram(build_schema()))
+    }
+
+    pub fn doc_count(&self) -> Result<u64> {
+        Ok(self.reader.searcher().num_docs())
+    }
+
+    /// Add messages appended to sessions that are already indexed.
+    pub fn add_messages(&self, messages: &[IndexableMessage]) -> Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let mut writer = self.writer.lock();
+        for message in messages {
+            writer.add_document(self.document(message))?;
+        }
+        writer.commit()?;
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    /// Replace everything indexed for `session_id` with `messages`.
+    pub fn replace_session(&self, session_id: &str, messages: &[IndexableMessage]) -> Result<()> {
+        let mut writer = self.writer.lock();
+        writer.delete_term(Term::from_field_text(self.session_id_field, session_id));
+        for message in messages {
+            writer.add_document(self.document(message))?;
+        }
+        writer.commit()?;
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    pub fn remove_session(&self, session_id: &str) -> Result<()> {
+        self.replace_session(session_id, &[])
+    }
+
+    pub fn rebuild<I>(&self, messages: I) -> Result<usize>
+    where
+        I: IntoIterator<Item = IndexableMessage>,
+    {
+        let mut writer = self.writer.lock();
+        writer.delete_all_documents()?;
+
+        let mut count = 0usize;
+        for message in messages {
+            writer.add_document(self.document(&message))?;
+            count += 1;
+        }
+
+        writer.commit()?;
+        self.reader.reload()?;
+        Ok(count)
+    }
+
+    /// Messages matching every unit, best BM25 score first.
+    pub fn search(
+        &self,
+        units: &[ChatQueryUnit],
+        filter: &ChatIndexFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ChatIndexPage> {
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for unit in units {
+            if let Some(query) = self.unit_query(unit) {
+                clauses.push((Occur::Must, query));
+            }
+        }
+        if clauses.is_empty() {
+            return Ok(ChatIndexPage::default());
+        }
+        if let Some(agent_id) = &filter.agent_id {
+            clauses.push((Occur::Must, self.exact(self.agent_id_field, agent_id)));
+        }
+        if let Some(skill_id) = &filter.skill_id {
+            clauses.push((Occur::Must, self.exact(self.skill_id_field, skill_id)));
+        }
+        if !filter.include_archived {
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(
+                    Term::from_field_bool(self.archived_field, false),
+                    IndexRecordOption::Basic,
+                )),
+            ));
+        }
+        let query = BooleanQuery::new(clauses);
+
+        let searcher = self.reader.searcher();
+        let total_count = searcher.search(&query, &Count)?;
+        if limit == 0 || offset >= total_count {
+            return Ok(ChatIndexPage {
+                hits: Vec::new(),
+                total_count,
+            });
+        }
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(limit).and_offset(offset))?;
+
+        let mut hits = Vec::with_capacity(top_docs.len());
+        for (score, address) in top_docs {
+            let document: TantivyDocument = searcher.doc(address)?;
+            let (Some(session_id), Some(position)) = (
+                document
+                    .get_first(self.session_id_field)
+                    .and_then(|value| value.as_str()),
+                document
+                    .get_first(self.position_field)
+                    .and_then(|value| value.as_u64()),
+            ) else {
+                continue;
+            };
+            hits.push(ChatMessageHit {
+                session_id: session_id.to_string(),
+                position: position as usize,
+                score,
+            });
+        }
+        Ok(ChatIndexPage { hits, total_count })
+    }
+
+    fn unit_query(&self, unit: &ChatQueryUnit) -> Option<Box<dyn Query>> {
+        let terms: Vec<Term> = unit
+            .words
+            .iter()
+            .map(|word| Term::from_field_text(self.words_field, word))
+            .collect();
+        match (terms.len(), unit.prefix) {
+            (0, _) => None,
+            (1, false) => Some(Box::new(TermQuery::new(
+                terms[0].clone(),
+                IndexRecordOption::WithFreqs,
+            ))),
+            // A lone prefix only matches by range, so the exact word is
+            // added to keep BM25 scoring for it.
+            (1, true) => {
+                let exact = TermQuery::new(terms[0].clone(), IndexRecordOption::WithFreqs);
+                let mut prefix = PhrasePrefixQuery::new(terms);
+                prefix.set_max_expansions(MAX_PREFIX_EXPANSIONS);
+                Some(Box::new(BooleanQuery::new(vec![
+                    (Occur::Should, Box::new(exact) as Box<dyn Query>),
+                    (Occur::Should, Box::new(prefix)),
+                ])))
+            }
+            (_, false) => Some(Box::new(PhraseQuery::new(terms))),
+            (_, true) => {
+                let mut prefix = PhrasePrefixQuery::new(terms);
+                prefix.set_max_expansions(MAX_PREFIX_EXPANSIONS);
+                Some(Box::new(prefix))
+            }
+        }
+    }
+
+    fn exact(&self, field: Field, value: &str) -> Box<dyn Query> {
+        Box::new(TermQuery::new(
+            Term::from_field_text(field, value),
+            IndexRecordOption::Basic,
+        ))
+    }
+
+    fn document(&self, message: &IndexableMessage) -> TantivyDocument {
+        let mut document = doc!(
+            self.session_id_field => message.session_id.clone(),
+            self.position_field => message.position as u64,
+            self.agent_id_field => message.agent_id.clone(),
+            self.archived_field => message.archived,
+            self.words_field => message.words.clone(),
+        );
+        if let Some(skill_id) = &message.skill_id {
+            document.add_text(self.skill_id_field, skill_id);
+        }
+        document
+    }
+
+    fn from_index(index: Index) -> Result<Self> {
+        index.tokenizers().register(
+            WORDS_TOKENIZER,
+            TextAnalyzer::builder(WhitespaceTokenizer::default())
+                .filter(RemoveLongFilter::limit(MAX_WORD_LEN))
+                .build(),
+        );
+        let schema = index.schema();
+        let field = |name: &str| {
+            schema
+                .get_field(name)
+                .with_context(|| format!("missing {name} field in index schema"))
+        };
+        let session_id_field = field("session_id")?;
+        let position_field = field("position")?;
+        let agent_id_field = field("agent_id")?;
+        let skill_id_field = field("skill_id")?;
+        let archived_field = field("archived")?;
+        let words_field = field("words")?;
+
+        let writer = index.writer(50_000_000)?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()?;
+
+        Ok(Self {
+            reader,
+            writer: Arc::new(Mutex::new(writer)),
+            session_id_field,
+            position_field,
+            agent_id_field,
+            skill_id_field,
+            archived_field,
+            words_field,
+        })
+    }
+}
+
+fn build_schema() -> Schema {
+    let mut schema_builder = Schema::builder();
+    schema_builder.add_text_field("session_id", STRING | STORED);
+    schema_builder.add_u64_field("position", STORED);
+    schema_builder.add_text_field("agent_id", STRING);
+    schema_builder.add_text_field("skill_id", STRING);
+    schema_builder.add_bool_field("archived", INDEXED);
+    schema_builder.add_text_field(
+        "words",
+        TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(WORDS_TOKENIZER)
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        ),
+    );
+    schema_builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn message(session_id: &str, position: usize, words: &str) -> IndexableMessage {
+        IndexableMessage {
+            session_id: session_id.to_string(),
+            position,
+            agent_id: "agent-a".to_string(),
+            skill_id: None,
+            archived: false,
+            words: words.to_string(),
+        }
+    }
+
+    fn unit(words: &[&str], prefix: bool) -> ChatQueryUnit {
+        ChatQueryUnit {
+            words: words.iter().map(|word| word.to_string()).collect(),
+            prefix,
+        }
+    }
+
+    fn hits(page: &ChatIndexPage) -> Vec<(&str, usize)> {
+        page.hits
+            .iter()
+            .map(|hit| (hit.session_id.as_str(), hit.position))
+            .collect()
+    }
+
+    #[test]
+    fn test_terms_prefixes_and_phrases() {
+        let index = ChatMessageIndex::in_memory().unwrap();
+        index
+            .add_messages(&[
+                message("s1", 0, "the agent configured the github webhook"),
+                message("s1", 1, "webhook github retries"),
+            ])
+            .unwrap();
+        let filter = ChatIndexFilter::default();
+
+        let page = index
+            .search(&[unit(&["config"], true)], &filter, 0, 10)
+            .unwrap();
+        assert_eq!(hits(&page), vec![("s1", 0)]);
+
+        let page = index
+            .search(&[unit(&["github", "webhook"], true)], &filter, 0, 10)
+            .unwrap();
+        assert_eq!(hits(&page), vec![("s1", 0)]);
+
+        let page = index
+            .search(
+                &[unit(&["webhook"], true), unit(&["github"], true)],
+                &filter,
+                0,
+                10,
+            )
+            .unwrap();
+        assert_eq!(page.total_count, 2);
+
+        let page = index
+            .search(&[unit(&["gith"], false)], &filter, 0, 10)
+            .unwrap();
+        assert_eq!(page.total_count, 0);
+    }
+
+    #[test]
+    fn test_append_replace_and_filters() {
+        let index = ChatMessageIndex::in_memory().unwrap();
+        index
+            .add_messages(&[message("s1", 0, "deploy the service")])
+            .unwrap();
+        index
+            .add_messages(&[IndexableMessage {
+                agent_id: "agent-b".to_string(),
+                archived: true,
+                ..message("s2", 0, "deploy again")
+            }])
+            .unwrap();
+        let deploy = [unit(&["deploy"], true)];
+
+        let page = index
+            .search(&deploy, &ChatIndexFilter::default(), 0, 10)
+            .unwrap();
+        assert_eq!(hits(&page), vec![("s1", 0)]);
+
+        let archived = ChatIndexFilter {
+            agent_id: Some("agent-b".to_string()),
+            include_archived: true,
+            ..ChatIndexFilter::default()
+        };
+        let page = index.search(&deploy, &archived, 0, 10).unwrap();
+        assert_eq!(hits(&page), vec![("s2", 0)]);
+
+        index
+            .replace_session("s1", &[message("s1", 0, "nothing relevant")])
+            .unwrap();
+        let page = index
+            .search(&deploy, &ChatIndexFilter::default(), 0, 10)
+            .unwrap();
+        assert_eq!(page.total_count, 0);
+
+        index.remove_session("s1").unwrap();
+        assert_eq!(index.doc_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_pagination_and_rebuild() {
+        let tmp = tempdir().unwrap();
+        let index = ChatMessageIndex::open(tmp.path()).unwrap();
+        let rebuilt = index
+            .rebuild((0..5).map(|i| message("s1", i, "status update")))
+            .unwrap();
+        assert_eq!(rebuilt, 5);
+
+        let page = index
+            .search(
+                &[unit(&["status"], true)],
+                &ChatIndexFilter::default(),
+                3,
+                10,
+            )
+            .unwrap();
+        assert_eq!(page.total_count, 5);
+        assert_eq!(page.hits.len(), 2);
+    }
+}
@@ -1,202 +1,244 @@
 //! Checkpoint storage - byte-level API for agent checkpoint persistence.
 //!
-//! Provides low-level storage operations for agent checkpoints using the
-//! redb embedded database.
+//! Provides low-level storage operations for agent checkpoints on a
+//! [`StorageBackend`].
 
-use anyhow::{Context, Result};
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use anyhow::Result;
+use redb::Database;
 use std::sync::Arc;
 
-use crate::range_utils::prefix_range;
+use crate::backend::{RedbBackend, StorageBackend, WriteTransaction, text_value};
 
 /// Primary table: checkpoint_id -> serialized AgentCheckpoint JSON
-const CHECKPOINT_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("agent_checkpoints");
+const CHECKPOINT_TABLE: &str = "agent_checkpoints";
 
 /// Index: execution_id:checkpoint_id -> checkpoint_id
-const CHECKPOINT_EXECUTION_INDEX: TableDefinition<&str, &str> =
-    TableDefinition::new("agent_checkpoint_execution_idx");
+const CHECKPOINT_EXECUTION_INDEX: &str = "agent_checkpoint_execution_idx";
 
 /// Index: task_id:checkpoint_id -> checkpoint_id
-const CHECKPOINT_TASK_INDEX: TableDefinition<&str, &str> =
-    TableDefinition::new("agent_checkpoint_task_idx");
+const CHECKPOINT_TASK_INDEX: &str = "agent_checkpoint_task_idx";
+
+/// Replay table: execution_id:step -> serialized ReplayStep JSON
+///
+/// Steps are zero-padded in the key so a prefix scan returns them in order.
+const REPLAY_STEP_TABLE: &str = "agent_replay_steps";
+
+/// Index: run_id:execution_id -> execution_id
+const REPLAY_RUN_INDEX: &str = "agent_replay_run_idx";
 
 /// Low-level checkpoint storage with byte-level API.
 #[derive(Clone)]
 pub struct CheckpointStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
 }
 
 impl CheckpointStorage {
     /// Create a new CheckpointStorage instance and initialize tables.
     pub fn new(db: Arc<Database>) -> Result<Self> {
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(CHECKPOINT_TABLE)?;
-        write_txn.open_table(CHECKPOINT_EXECUTION_INDEX)?;
-        write_txn.open_table(CHECKPOINT_TASK_INDEX)?;
-        write_txn.commit()?;
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
 
-        Ok(Self { db })
+    /// Create the storage on `backend` and initialize tables.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        for table in [
+            CHECKPOINT_TABLE,
+            CHECKPOINT_EXECUTION_INDEX,
+            CHECKPOINT_TASK_INDEX,
+            REPLAY_STEP_TABLE,
+            REPLAY_RUN_INDEX,
+        ] {
+            backend.ensure_table(table)?;
+        }
+
+        Ok(Self { backend })
     }
 
-    /// Store a checkpoint with its index entries.
-    pub fn save(
-        &self,
+    fn put_checkpoint(
+        txn: &dyn WriteTransaction,
         id: &str,
         execution_id: &str,
         task_id: Option<&str>,
         data: &[u8],
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(CHECKPOINT_TABLE)?;
-            table.insert(id, data)?;
+        txn.table(CHECKPOINT_TABLE)?.put(id, data)?;
 
-            let mut exec_idx = write_txn.open_table(CHECKPOINT_EXECUTION_INDEX)?;
-            let exec_key = format!("{}:{}", execution_id, id);
-            exec_idx.insert(exec_key.as_str(), id)?;
+        let exec_key = format!("{}:{}", execution_id, id);
+        txn.table(CHECKPOINT_EXECUTION_INDEX)?
+            .put(&exec_key, id.as_bytes())?;
 
-            if let Some(tid) = task_id {
-                let mut task_idx = write_txn.open_table(CHECKPOINT_TASK_INDEX)?;
-                let task_key = format!("{}:{}", tid, id);
-                task_idx.insert(task_key.as_str(), id)?;
-            }
+        if let Some(tid) = task_id {
+            let task_key = format!("{}:{}", tid, id);
+            txn.table(CHECKPOINT_TASK_INDEX)?
+                .put(&task_key, id.as_bytes())?;
         }
-        write_txn.commit()?;
         Ok(())
     }
 
+    /// Store a checkpoint with its index entries.
+    pub fn save(
+        &self,
+        id: &str,
+        execution_id: &str,
+        task_id: Option<&str>,
+        data: &[u8],
+    ) -> Result<()> {
+        self.backend
+            .write_tables(|txn| Self::put_checkpoint(txn, id, execution_id, task_id, data))
+    }
+
     /// Store a checkpoint and create a persistent savepoint in the same transaction.
     ///
-    /// The savepoint is created before opening any tables to satisfy redb constraints.
+    /// Returns `None` when the backend does not support savepoints.
     pub fn save_with_savepoint(
         &self,
         id: &str,
         execution_id: &str,
         task_id: Option<&str>,
         data: &[u8],
-    ) -> Result<u64> {
-        let write_txn = self.db.begin_write()?;
-        let savepoint_id = write_txn
-            .persistent_savepoint()
-            .context("Failed to create persistent savepoint")?;
-        {
-            let mut table = write_txn.open_table(CHECKPOINT_TABLE)?;
-            table.insert(id, data)?;
-
-            let mut exec_idx = write_txn.open_table(CHECKPOINT_EXECUTION_INDEX)?;
-            let exec_key = format!("{}:{}", execution_id, id);
-            exec_idx.insert(exec_key.as_str(), id)?;
-
-            if let Some(tid) = task_id {
-                let mut task_idx = write_txn.open_table(CHECKPOINT_TASK_INDEX)?;
-                let task_key = format!("{}:{}", tid, id);
-                task_idx.insert(task_key.as_str(), id)?;
-            }
-        }
-        write_txn.commit()?;
-        Ok(savepoint_id)
+    ) -> Result<Option<u64>> {
+        self.backend.write_txn_with_savepoint(&mut |txn| {
+            Self::put_checkpoint(txn, id, execution_id, task_id, data)
+        })
     }
 
     /// Delete a previously created persistent savepoint.
     pub fn delete_savepoint(&self, savepoint_id: u64) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let deleted = write_txn
-            .delete_persistent_savepoint(savepoint_id)
-            .context("Failed to delete persistent savepoint")?;
-        write_txn.commit()?;
-        Ok(deleted)
+        self.backend.delete_savepoint(savepoint_id)
     }
 
     /// Load a checkpoint by ID.
     pub fn load(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(CHECKPOINT_TABLE)?;
-        Ok(table.get(id)?.map(|v| v.value().to_vec()))
+        self.backend
+            .read_table(CHECKPOINT_TABLE, |table| table.get(id))
     }
 
     /// Load the most recent checkpoint for an execution_id.
     pub fn load_by_execution_id(&self, execution_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let exec_idx = read_txn.open_table(CHECKPOINT_EXECUTION_INDEX)?;
-
-        let prefix = format!("{}:", execution_id);
-        let (start, end) = prefix_range(&prefix);
-
-        // Get the last entry (most recently inserted) by iterating to the end
-        let mut last_cp_id: Option<String> = None;
-        for entry in exec_idx.range(start.as_str()..end.as_str())? {
-            let entry = entry?;
-            last_cp_id = Some(entry.1.value().to_string());
-        }
-
-        if let Some(cp_id) = last_cp_id {
-            let table = read_txn.open_table(CHECKPOINT_TABLE)?;
-            Ok(table.get(cp_id.as_str())?.map(|v| v.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.load_latest(CHECKPOINT_EXECUTION_INDEX, execution_id)
     }
 
     /// Load the most recent checkpoint for a task_id.
     pub fn load_by_task_id(&self, task_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let task_idx = read_txn.open_table(CHECKPOINT_TASK_INDEX)?;
-
-        let prefix = format!("{}:", task_id);
-        let (start, end) = prefix_range(&prefix);
+        self.load_latest(CHECKPOINT_TASK_INDEX, task_id)
+    }
 
-        let mut last_cp_id: Option<String> = None;
-        for entry in task_idx.range(start.as_str()..end.as_str())? {
-            let entry = entry?;
-            last_cp_id = Some(entry.1.value().to_string());
-        }
+    fn load_latest(&self, index_table: &str, owner_id: &str) -> Result<Option<Vec<u8>>> {
+        self.backend.read_tables(|txn| {
+            let prefix = format!("{}:", owner_id);
+            // The last index entry is the most recently inserted checkpoint.
+            let last_cp_id = txn.table(index_table)?.scan_prefix(&prefix)?.pop();
 
-        if let Some(cp_id) = last_cp_id {
-            let table = read_txn.open_table(CHECKPOINT_TABLE)?;
-            Ok(table.get(cp_id.as_str())?.map(|v| v.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+            match last_cp_id {
+                Some((_, cp_id)) => txn.table(CHECKPOINT_TABLE)?.get(&text_value(cp_id)?),
+                None => Ok(None),
+            }
+        })
     }
 
     /// Delete a checkpoint and its index entries.
     pub fn delete(&self, id: &str, execution_id: &str, task_id: Option<&str>) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(CHECKPOINT_TABLE)?;
-            table.remove(id)?;
+        self.backend.write_tables(|txn| {
+            txn.table(CHECKPOINT_TABLE)?.remove(id)?;
 
-            let mut exec_idx = write_txn.open_table(CHECKPOINT_EXECUTION_INDEX)?;
             let exec_key = format!("{}:{}", execution_id, id);
-            exec_idx.remove(exec_key.as_str())?;
+            txn.table(CHECKPOINT_EXECUTION_INDEX)?.remove(&exec_key)?;
 
             if let Some(tid) = task_id {
-                let mut task_idx = write_txn.open_table(CHECKPOINT_TASK_INDEX)?;
                 let task_key = format!("{}:{}", tid, id);
-                task_idx.remove(task_key.as_str())?;
+                txn.table(CHECKPOINT_TASK_INDEX)?.remove(&task_key)?;
             }
-        }
-        write_txn.commit()?;
-        Ok(())
+            Ok(())
+        })
+    }
+
+    /// Store the snapshot taken at `step` of an execution, replacing any
+    /// previous snapshot for the same step.
+    pub fn save_replay_step(
+        &self,
+        execution_id: &str,
+        run_id: Option<&str>,
+        step: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        self.backend.write_tables(|txn| {
+            txn.table(REPLAY_STEP_TABLE)?
+                .put(&replay_key(execution_id, step), data)?;
+
+            if let Some(rid) = run_id {
+                let run_key = format!("{}:{}", rid, execution_id);
+                txn.table(REPLAY_RUN_INDEX)?
+                    .put(&run_key, execution_id.as_bytes())?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Load the snapshot taken at `step` of an execution.
+    pub fn load_replay_step(&self, execution_id: &str, step: u32) -> Result<Option<Vec<u8>>> {
+        self.backend.read_table(REPLAY_STEP_TABLE, |table| {
+            table.get(&replay_key(execution_id, step))
+        })
     }
 
-    /// Delete all checkpoints with expired_at <= now_ms.
-    /// Returns the number of deleted checkpoints.
+    /// List all snapshots of an execution ordered by step.
+    pub fn list_replay_steps(&self, execution_id: &str) -> Result<Vec<Vec<u8>>> {
+        let prefix = format!("{}:", execution_id);
+        let entries = self
+            .backend
+            .read_table(REPLAY_STEP_TABLE, |table| table.scan_prefix(&prefix))?;
+        Ok(entries.into_iter().map(|(_, value)| value).collect())
+    }
+
+    /// List the execution IDs with replay steps recorded for a run.
+    pub fn list_replay_executions_by_run(&self, run_id: &str) -> Result<Vec<String>> {
+        let prefix = format!("{}:", run_id);
+        let entries = self
+            .backend
+            .read_table(REPLAY_RUN_INDEX, |run_idx| run_idx.scan_prefix(&prefix))?;
+        entries
+            .into_iter()
+            .map(|(_, execution_id)| text_value(execution_id))
+            .collect()
+    }
+
+    /// Delete all snapshots of an execution and its run index entry.
+    /// Returns the number of deleted steps.
+    pub fn delete_replay_steps(&self, execution_id: &str, run_id: Option<&str>) -> Result<usize> {
+        let prefix = format!("{}:", execution_id);
+
+        self.backend.write_tables(|txn| {
+            let count = {
+                let mut table = txn.table(REPLAY_STEP_TABLE)?;
+                let keys = table.keys_with_prefix(&prefix)?;
+                for key in &keys {
+                    table.remove(key)?;
+                }
+                keys.len()
+            };
+
+            if let Some(rid) = run_id {
+                let run_key = format!("{}:{}", rid, execution_id);
+                txn.table(REPLAY_RUN_INDEX)?.remove(&run_key)?;
+            }
+            Ok(count)
+        })
+    }
+
+    /// Delete all checkpoints and replay steps with expired_at <= now_ms.
+    /// Returns the number of deleted records.
     pub fn cleanup_expired(&self, now_ms: i64) -> Result<usize> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(CHECKPOINT_TABLE)?;
+        let entries = self
+            .backend
+            .read_table(CHECKPOINT_TABLE, |table| table.scan_prefix(""))?;
 
         // Collect IDs of expired checkpoints
         let mut expired: Vec<(String, String, Option<String>)> = Vec::new();
-        for entry in table.iter()? {
-            let entry = entry?;
-            let data = entry.1.value();
+        for (id, data) in entries {
             // Parse just enough to check expired_at, execution_id, task_id
-            if let Ok(val) = serde_json::from_slice::<serde_json::Value>(data)
+            if let Ok(val) = serde_json::from_slice::<serde_json::Value>(&data)
                 && let Some(exp) = val.get("expired_at").and_then(|v| v.as_i64())
                 && exp <= now_ms
             {
-                let id = entry.0.value().to_string();
                 let exec_id = val
                     .get("execution_id")
                     .and_then(|v| v.as_str())
@@ -209,17 +251,64 @@ impl CheckpointStorage {
                 expired.push((id, exec_id, task_id));
             }
         }
-        drop(table);
-        drop(read_txn);
 
         let count = expired.len();
         for (id, exec_id, task_id) in expired {
             self.delete(&id, &exec_id, task_id.as_deref())?;
         }
-        Ok(count)
+        Ok(count + self.cleanup_expired_replay_steps(now_ms)?)
+    }
+
+    fn cleanup_expired_replay_steps(&self, now_ms: i64) -> Result<usize> {
+        self.backend.write_tables(|txn| {
+            let mut table = txn.table(REPLAY_STEP_TABLE)?;
+            let mut expired = Vec::new();
+            let mut executions: Vec<(String, Option<String>)> = Vec::new();
+            for (key, data) in table.scan_prefix("")? {
+                if let Ok(val) = serde_json::from_slice::<serde_json::Value>(&data)
+                    && let Some(exp) = val.get("expired_at").and_then(|v| v.as_i64())
+                    && exp <= now_ms
+                {
+                    expired.push(key);
+                    let exec_id = val
+                        .get("execution_id")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string();
+                    let run_id = val
+                        .get("run_id")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+                    if !executions.contains(&(exec_id.clone(), run_id.clone())) {
+                        executions.push((exec_id, run_id));
+                    }
+                }
+            }
+            for key in &expired {
+                table.remove(key)?;
+            }
+
+            // Drop run index entries once no steps of the execution remain.
+            let mut run_idx = txn.table(REPLAY_RUN_INDEX)?;
+            for (exec_id, run_id) in executions {
+                let Some(rid) = run_id else {
+                    continue;
+                };
+                let prefix = format!("{}:", exec_id);
+                if table.keys_with_prefix(&prefix)?.is_empty() {
+                    let run_key = format!("{}:{}", rid, exec_id);
+                    run_idx.remove(&run_key)?;
+                }
+            }
+            Ok(expired.len())
+        })
     }
 }
 
+fn replay_key(execution_id: &str, step: u32) -> String {
+    format!("{}:{:010}", execution_id, step)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -363,6 +452,7 @@ mod tests {
 
         let savepoint_id = storage
             .save_with_savepoint("cp-1", "exec-1", Some("task-1"), b"data")
+            .unwrap()
             .unwrap();
         assert!(savepoint_id > 0);
         assert_eq!(storage.load("cp-1").unwrap().unwrap(), b"data".to_vec());
@@ -370,4 +460,85 @@ mod tests {
         let deleted = storage.delete_savepoint(savepoint_id).unwrap();
         assert!(deleted);
     }
+
+    #[test]
+    fn test_replay_steps_are_listed_in_order() {
+        let db = setup_db();
+        let storage = CheckpointStorage::new(db).unwrap();
+
+        storage
+            .save_replay_step("exec-1", Some("run-1"), 10, b"s10")
+            .unwrap();
+        storage
+            .save_replay_step("exec-1", Some("run-1"), 2, b"s2")
+            .unwrap();
+        storage
+            .save_replay_step("exec-10", None, 1, b"other")
+            .unwrap();
+        storage
+            .save_replay_step("exec-1", Some("run-1"), 2, b"s2-updated")
+            .unwrap();
+
+        let steps = storage.list_replay_steps("exec-1").unwrap();
+        assert_eq!(steps, vec![b"s2-updated".to_vec(), b"s10".to_vec()]);
+        assert_eq!(
+            storage.load_replay_step("exec-1", 10).unwrap().unwrap(),
+            b"s10".to_vec()
+        );
+        assert!(storage.load_replay_step("exec-1", 3).unwrap().is_none());
+        assert_eq!(
+            storage.list_replay_executions_by_run("run-1").unwrap(),
+            vec!["exec-1".to_string()]
+        );
+
+        assert_eq!(
+            storage
+                .delete_replay_steps("exec-1", Some("run-1"))
+                .unwrap(),
+            2
+        );
+        assert!(storage.list_replay_steps("exec-1").unwrap().is_empty());
+        assert!(
+            storage
+                .list_replay_executions_by_run("run-1")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(storage.list_replay_steps("exec-10").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_cleanup_expired_replay_steps() {
+        let db = setup_db();
+        let storage = CheckpointStorage::new(db).unwrap();
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let expired = serde_json::json!({
+            "execution_id": "exec-1",
+            "run_id": "run-1",
+            "expired_at": now - 1
+        });
+        let valid = serde_json::json!({ "execution_id": "exec-2", "expired_at": now + 1000 });
+        storage
+            .save_replay_step(
+                "exec-1",
+                Some("run-1"),
+                0,
+                &serde_json::to_vec(&expired).unwrap(),
+            )
+            .unwrap();
+        storage
+            .save_replay_step("exec-2", None, 0, &serde_json::to_vec(&valid).unwrap())
+            .unwrap();
+
+        assert_eq!(storage.cleanup_expired(now).unwrap(), 1);
+        assert!(storage.list_replay_steps("exec-1").unwrap().is_empty());
+        assert!(
+            storage
+                .list_replay_executions_by_run("run-1")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(storage.list_replay_steps("exec-2").unwrap().len(), 1);
+    }
 }
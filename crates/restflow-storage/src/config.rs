@@ -1,12 +1,14 @@
 //! System configuration storage.
 
+use crate::backend::StorageBackendKind;
+use crate::secrets::SecretStorageMode;
 use anyhow::{Context, Result};
 use redb::Database;
 use restflow_traits::{
     DEFAULT_AGENT_APPROVAL_TIMEOUT_SECS, DEFAULT_AGENT_BASH_TIMEOUT_SECS,
     DEFAULT_AGENT_BROWSER_TIMEOUT_SECS, DEFAULT_AGENT_COMPACT_PRESERVE_TOKENS,
-    DEFAULT_AGENT_LLM_TIMEOUT_SECS, DEFAULT_AGENT_MAX_DURATION_SECS, DEFAULT_AGENT_MAX_ITERATIONS,
-    DEFAULT_AGENT_MAX_TOOL_CALLS, DEFAULT_AGENT_MAX_TOOL_CONCURRENCY,
+    DEFAULT_AGENT_CONTAINER_IMAGE, DEFAULT_AGENT_LLM_TIMEOUT_SECS, DEFAULT_AGENT_MAX_DURATION_SECS,
+    DEFAULT_AGENT_MAX_ITERATIONS, DEFAULT_AGENT_MAX_TOOL_CALLS, DEFAULT_AGENT_MAX_TOOL_CONCURRENCY,
     DEFAULT_AGENT_MAX_TOOL_RESULT_LENGTH, DEFAULT_AGENT_PRUNE_TOOL_MAX_CHARS,
     DEFAULT_AGENT_PYTHON_TIMEOUT_SECS, DEFAULT_AGENT_TASK_TIMEOUT_SECS,
     DEFAULT_AGENT_TOOL_TIMEOUT_SECS, DEFAULT_API_DIAGNOSTICS_TIMEOUT_MS,
@@ -15,9 +17,13 @@ use restflow_traits::{
     DEFAULT_BG_PROGRESS_EVENT_LIMIT, DEFAULT_BG_TRACE_LINE_LIMIT, DEFAULT_BG_TRACE_LIST_LIMIT,
     DEFAULT_CHAT_MAX_SESSION_HISTORY, DEFAULT_GITHUB_CACHE_TTL_SECS,
     DEFAULT_MARKETPLACE_CACHE_TTL_SECS, DEFAULT_MAX_PARALLEL_SUBAGENTS,
-    DEFAULT_PROCESS_SESSION_TTL_SECS, DEFAULT_SUBAGENT_MAX_DEPTH, DEFAULT_SUBAGENT_TIMEOUT_SECS,
-    DEFAULT_TELEGRAM_API_TIMEOUT_SECS, DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS,
-    MAX_API_WEB_SEARCH_RESULTS,
+    DEFAULT_MEMORY_CONSOLIDATION_INTERVAL_HOURS, DEFAULT_MEMORY_CONSOLIDATION_MIN_CLUSTER_SIZE,
+    DEFAULT_MEMORY_CONSOLIDATION_SIMILARITY, DEFAULT_MEMORY_EMBEDDING_DIMENSION,
+    DEFAULT_MEMORY_HYBRID_RRF_K, DEFAULT_MEMORY_HYBRID_VECTOR_WEIGHT,
+    DEFAULT_MEMORY_MAX_CHUNKS_PER_AGENT, DEFAULT_MEMORY_MAX_SUGGESTED_TAGS,
+    DEFAULT_MEMORY_SESSION_RETENTION_DAYS, DEFAULT_PROCESS_SESSION_TTL_SECS,
+    DEFAULT_SUBAGENT_MAX_DEPTH, DEFAULT_SUBAGENT_TIMEOUT_SECS, DEFAULT_TELEGRAM_API_TIMEOUT_SECS,
+    DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS, MAX_API_WEB_SEARCH_RESULTS,
 };
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value as JsonValue;
@@ -45,6 +51,8 @@ const DEFAULT_BACKGROUND_TASK_RETENTION_DAYS: u32 = 7;
 const DEFAULT_CHECKPOINT_RETENTION_DAYS: u32 = 3;
 const DEFAULT_MEMORY_CHUNK_RETENTION_DAYS: u32 = 90;
 const DEFAULT_LOG_FILE_RETENTION_DAYS: u32 = 30;
+const DEFAULT_ARTIFACT_RETENTION_DAYS: u32 = 14;
+const DEFAULT_ARTIFACT_MAX_TOTAL_MB: u64 = 2048;
 const DEFAULT_MEMORY_SEARCH_LIMIT: u32 = 10;
 const DEFAULT_SESSION_LIST_LIMIT: u32 = 20;
 const MIN_RETENTION_DAYS: u32 = 1;
@@ -138,6 +146,14 @@ pub struct SystemSection {
     pub checkpoint_retention_days: u32,
     pub memory_chunk_retention_days: u32,
     pub log_file_retention_days: u32,
+    pub artifact_retention_days: u32,
+    pub artifact_max_total_mb: u64,
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
     pub experimental_features: Vec<String>,
 }
 
@@ -155,6 +171,11 @@ impl Default for SystemSection {
             checkpoint_retention_days: DEFAULT_CHECKPOINT_RETENTION_DAYS,
             memory_chunk_retention_days: DEFAULT_MEMORY_CHUNK_RETENTION_DAYS,
             log_file_retention_days: DEFAULT_LOG_FILE_RETENTION_DAYS,
+            artifact_retention_days: DEFAULT_ARTIFACT_RETENTION_DAYS,
+            artifact_max_total_mb: DEFAULT_ARTIFACT_MAX_TOTAL_MB,
+            daily_budget_usd: None,
+            monthly_budget_usd: None,
+            otlp_endpoint: None,
             experimental_features: Vec::new(),
         }
     }
@@ -174,11 +195,35 @@ impl From<&SystemConfig> for SystemSection {
             checkpoint_retention_days: config.checkpoint_retention_days,
             memory_chunk_retention_days: config.memory_chunk_retention_days,
             log_file_retention_days: config.log_file_retention_days,
+            artifact_retention_days: config.artifact_retention_days,
+            artifact_max_total_mb: config.artifact_max_total_mb,
+            daily_budget_usd: config.daily_budget_usd,
+            monthly_budget_usd: config.monthly_budget_usd,
+            otlp_endpoint: config.otlp_endpoint.clone(),
             experimental_features: config.experimental_features.clone(),
         }
     }
 }
 
+/// Storage backend selection.
+///
+/// Read before the database is opened, so changes apply on the next start.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Type)]
+#[serde(default)]
+pub struct StorageSettings {
+    pub backend: StorageBackendKind,
+    /// SQLite database file. Defaults to `restflow.sqlite` next to the redb
+    /// database.
+    pub sqlite_path: Option<String>,
+    /// Encrypt chat sessions and memory with a key held in the OS keychain.
+    /// Existing plaintext values are sealed once, on the next start. Other
+    /// tables are not encrypted.
+    pub encrypt_at_rest: bool,
+    /// Where secret values are kept (`database` or `keychain`). Existing
+    /// secrets are moved on the next start.
+    pub secrets: SecretStorageMode,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct ConfigDocument {
@@ -188,8 +233,11 @@ pub struct ConfigDocument {
     pub runtime: RuntimeSettings,
     pub channel: ChannelSettings,
     pub registry: RegistrySettings,
+    pub memory: MemorySettings,
     #[serde(default)]
     pub cli: CliConfig,
+    #[serde(default)]
+    pub storage: StorageSettings,
 }
 
 impl ConfigDocument {
@@ -201,7 +249,9 @@ impl ConfigDocument {
             runtime: system.runtime_defaults,
             channel: system.channel_defaults,
             registry: system.registry_defaults,
+            memory: system.memory_defaults,
             cli,
+            storage: StorageSettings::default(),
         }
     }
 
@@ -218,12 +268,18 @@ impl ConfigDocument {
             checkpoint_retention_days: self.system.checkpoint_retention_days,
             memory_chunk_retention_days: self.system.memory_chunk_retention_days,
             log_file_retention_days: self.system.log_file_retention_days,
+            artifact_retention_days: self.system.artifact_retention_days,
+            artifact_max_total_mb: self.system.artifact_max_total_mb,
+            daily_budget_usd: self.system.daily_budget_usd,
+            monthly_budget_usd: self.system.monthly_budget_usd,
+            otlp_endpoint: self.system.otlp_endpoint.clone(),
             experimental_features: self.system.experimental_features.clone(),
             agent: self.agent.clone(),
             api_defaults: self.api.clone(),
             runtime_defaults: self.runtime.clone(),
             channel_defaults: self.channel.clone(),
             registry_defaults: self.registry.clone(),
+            memory_defaults: self.memory.clone(),
         }
     }
 
@@ -238,6 +294,7 @@ impl ConfigDocument {
         self.runtime = system.runtime_defaults;
         self.channel = system.channel_defaults;
         self.registry = system.registry_defaults;
+        self.memory = system.memory_defaults;
     }
 }
 
@@ -259,6 +316,37 @@ pub struct AgentDefaults {
     pub python_timeout_secs: u64,
     /// Default timeout for browser tool execution in seconds.
     pub browser_timeout_secs: u64,
+    /// Domains the browser tool may navigate to. Empty allows any domain.
+    pub browser_allowed_domains: Vec<String>,
+    /// Domains the browser tool may never navigate to.
+    pub browser_denied_domains: Vec<String>,
+    /// Block browser navigation to loopback, private and metadata addresses.
+    pub browser_block_private_networks: bool,
+    /// Maximum artifact bytes kept per browser session. 0 disables the quota.
+    pub browser_artifact_max_session_bytes: u64,
+    /// Maximum artifact bytes kept across browser sessions. 0 disables the quota.
+    pub browser_artifact_max_total_bytes: u64,
+    /// Browser artifacts older than this are removed. 0 keeps them forever.
+    pub browser_artifact_max_age_secs: u64,
+    /// Memory ceiling in MiB for bash and python tool processes. 0 disables it.
+    pub subprocess_memory_limit_mb: u64,
+    /// CPU weight (1-10000, kernel default 100) for tool processes. 0 leaves it unset.
+    pub subprocess_cpu_weight: u32,
+    /// Maximum live processes per bash or python tool call. 0 disables it.
+    pub subprocess_max_processes: u32,
+    /// CPU time limit in seconds for bash and python tool processes. 0 disables it.
+    pub subprocess_cpu_time_secs: u64,
+    /// Container engine (`docker` or `podman`) that runs bash and python tool
+    /// processes in ephemeral containers. `None` runs them on the host.
+    pub container_runtime: Option<String>,
+    /// Image for containerized tool processes; must provide `sh` and `python3`.
+    pub container_image: String,
+    /// Give containerized tool processes network access.
+    pub container_network: bool,
+    /// Outbound connections bash and python tool processes on the host may
+    /// open, as `host:port` entries (`*:443` allows port 443 on any host).
+    /// `None` leaves the network open; an empty list denies it.
+    pub subprocess_network_allow: Option<Vec<String>>,
     /// TTL for finished process sessions in seconds.
     pub process_session_ttl_secs: u64,
     /// Default approval timeout for security checks in seconds.
@@ -307,6 +395,20 @@ impl Default for AgentDefaults {
             bash_timeout_secs: DEFAULT_AGENT_BASH_TIMEOUT_SECS,
             python_timeout_secs: DEFAULT_AGENT_PYTHON_TIMEOUT_SECS,
             browser_timeout_secs: DEFAULT_AGENT_BROWSER_TIMEOUT_SECS,
+            browser_allowed_domains: Vec::new(),
+            browser_denied_domains: Vec::new(),
+            browser_block_private_networks: false,
+            browser_artifact_max_session_bytes: 0,
+            browser_artifact_max_total_bytes: 0,
+            browser_artifact_max_age_secs: 0,
+            subprocess_memory_limit_mb: 0,
+            subprocess_cpu_weight: 0,
+            subprocess_max_processes: 0,
+            subprocess_cpu_time_secs: 0,
+            container_runtime: None,
+            container_image: DEFAULT_AGENT_CONTAINER_IMAGE.to_string(),
+            container_network: false,
+            subprocess_network_allow: None,
             process_session_ttl_secs: DEFAULT_PROCESS_SESSION_TTL_SECS,
             approval_timeout_secs: DEFAULT_AGENT_APPROVAL_TIMEOUT_SECS,
             max_iterations: DEFAULT_AGENT_MAX_ITERATIONS,
@@ -360,6 +462,51 @@ impl AgentDefaults {
                 MIN_TIMEOUT_SECONDS
             ));
         }
+        for (key, domains) in [
+            (
+                "agent.browser_allowed_domains",
+                &self.browser_allowed_domains,
+            ),
+            ("agent.browser_denied_domains", &self.browser_denied_domains),
+        ] {
+            if domains.iter().any(|domain| domain.trim().is_empty()) {
+                return Err(anyhow::anyhow!("{} must not contain empty entries", key));
+            }
+            if let Some(domain) = domains.iter().find(|domain| domain.contains("://")) {
+                return Err(anyhow::anyhow!(
+                    "{} entries must be domains, not URLs: {}",
+                    key,
+                    domain
+                ));
+            }
+        }
+        if self.subprocess_cpu_weight > 10_000 {
+            return Err(anyhow::anyhow!(
+                "agent.subprocess_cpu_weight must be between 1 and 10000"
+            ));
+        }
+        if let Some(runtime) = self.container_runtime.as_deref()
+            && !matches!(runtime, "docker" | "podman")
+        {
+            return Err(anyhow::anyhow!(
+                "agent.container_runtime must be 'docker' or 'podman', got '{}'",
+                runtime
+            ));
+        }
+        if self.container_image.trim().is_empty() {
+            return Err(anyhow::anyhow!("agent.container_image must not be empty"));
+        }
+        if let Some(entry) = self
+            .subprocess_network_allow
+            .iter()
+            .flatten()
+            .find(|entry| !is_network_endpoint(entry))
+        {
+            return Err(anyhow::anyhow!(
+                "agent.subprocess_network_allow entries must be 'host:port', got '{}'",
+                entry
+            ));
+        }
         if self.process_session_ttl_secs < MIN_TIMEOUT_SECONDS {
             return Err(anyhow::anyhow!(
                 "agent.process_session_ttl_secs must be at least {} seconds",
@@ -436,6 +583,14 @@ impl AgentDefaults {
     }
 }
 
+/// Whether `entry` has the `host:port` form of a sandbox network endpoint.
+fn is_network_endpoint(entry: &str) -> bool {
+    entry
+        .trim()
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok_and(|p| p > 0))
+}
+
 /// API-facing default limits used by MCP and adapter query operations.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(default)]
@@ -534,6 +689,9 @@ pub struct RuntimeDefaults {
     pub background_runner_poll_interval_ms: u64,
     /// Maximum concurrent tasks for the background runner.
     pub background_runner_max_concurrent_tasks: usize,
+    /// Resume runs interrupted by a restart from their last checkpoint on
+    /// startup, instead of only offering the resume.
+    pub background_runner_auto_resume: bool,
     /// Maximum session history kept for channel chat sessions.
     pub chat_max_session_history: usize,
 }
@@ -546,6 +704,7 @@ impl Default for RuntimeDefaults {
         Self {
             background_runner_poll_interval_ms: DEFAULT_BACKGROUND_RUNNER_POLL_INTERVAL_MS,
             background_runner_max_concurrent_tasks: DEFAULT_BACKGROUND_RUNNER_MAX_CONCURRENT_TASKS,
+            background_runner_auto_resume: false,
             chat_max_session_history: DEFAULT_CHAT_MAX_SESSION_HISTORY,
         }
     }
@@ -580,6 +739,10 @@ pub struct ChannelDefaults {
     pub telegram_api_timeout_secs: u64,
     /// Telegram long-poll timeout in seconds.
     pub telegram_polling_timeout_secs: u32,
+    /// Reply to inbound voice messages with a synthesized voice note.
+    pub voice_replies: bool,
+    /// Outbound webhooks that receive agent events as signed JSON.
+    pub webhooks: Vec<WebhookSinkConfig>,
 }
 
 /// Aligned alias that matches the on-disk `[channel]` section naming.
@@ -590,6 +753,8 @@ impl Default for ChannelDefaults {
         Self {
             telegram_api_timeout_secs: DEFAULT_TELEGRAM_API_TIMEOUT_SECS,
             telegram_polling_timeout_secs: DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS,
+            voice_replies: false,
+            webhooks: Vec::new(),
         }
     }
 }
@@ -607,10 +772,40 @@ impl ChannelDefaults {
                 "channel.telegram_polling_timeout_secs must be at least 1"
             ));
         }
+        for webhook in &self.webhooks {
+            let url = webhook.url.trim();
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err(anyhow::anyhow!(
+                    "channel.webhooks url must be an http(s) URL: {}",
+                    webhook.url
+                ));
+            }
+            if webhook.events.iter().any(|event| event.trim().is_empty()) {
+                return Err(anyhow::anyhow!(
+                    "channel.webhooks events must be non-empty strings"
+                ));
+            }
+        }
         Ok(())
     }
 }
 
+/// Outbound webhook destination for agent events.
+///
+/// Events are named `task.<event_type>` (e.g. `task.completed`) and
+/// `deliverable.created`. `events` accepts exact names, `prefix.*` patterns
+/// or `*`; an empty list subscribes to every event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct WebhookSinkConfig {
+    /// Destination URL; may contain `${secret:NAME}` references.
+    pub url: String,
+    /// Name of the stored secret used to sign deliveries with HMAC-SHA256.
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
 /// Registry and marketplace integration defaults.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(default)]
@@ -619,6 +814,10 @@ pub struct RegistryDefaults {
     pub github_cache_ttl_secs: u64,
     /// Marketplace provider cache TTL in seconds.
     pub marketplace_cache_ttl_secs: u64,
+    /// Base64 ed25519 public keys trusted to sign marketplace skills.
+    pub trusted_skill_keys: Vec<String>,
+    /// Refuse to install marketplace skills without a trusted signature.
+    pub require_signed_skills: bool,
 }
 
 /// Aligned alias that matches the on-disk `[registry]` section naming.
@@ -629,6 +828,8 @@ impl Default for RegistryDefaults {
         Self {
             github_cache_ttl_secs: DEFAULT_GITHUB_CACHE_TTL_SECS,
             marketplace_cache_ttl_secs: DEFAULT_MARKETPLACE_CACHE_TTL_SECS,
+            trusted_skill_keys: Vec::new(),
+            require_signed_skills: false,
         }
     }
 }
@@ -645,10 +846,161 @@ impl RegistryDefaults {
                 "registry.marketplace_cache_ttl_secs must be at least 1"
             ));
         }
+        if self
+            .trusted_skill_keys
+            .iter()
+            .any(|key| key.trim().is_empty())
+        {
+            return Err(anyhow::anyhow!(
+                "registry.trusted_skill_keys must not contain empty keys"
+            ));
+        }
+        if self.require_signed_skills && self.trusted_skill_keys.is_empty() {
+            return Err(anyhow::anyhow!(
+                "registry.require_signed_skills needs at least one trusted_skill_keys entry"
+            ));
+        }
         Ok(())
     }
 }
 
+/// Memory tagging and tag retention defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct MemoryDefaults {
+    /// Suggest tags for new memory chunks when they are saved.
+    pub auto_tag: bool,
+    /// Maximum number of suggested tags added to a single chunk.
+    pub max_suggested_tags: usize,
+    /// Per-tag retention overrides applied by the cleanup job.
+    pub tag_retention: Vec<TagRetentionRule>,
+    /// Embedding model for semantic recall. Vector search is off when unset.
+    pub embedding_model: Option<String>,
+    /// Width of the vectors returned by `embedding_model`.
+    pub embedding_dimension: usize,
+    /// OpenAI-compatible embeddings endpoint. Defaults to the OpenAI API;
+    /// point it at a local server (llama.cpp, Ollama) to use a gguf model.
+    pub embedding_base_url: Option<String>,
+    /// Secret holding the API key for the embeddings endpoint. Defaults to
+    /// `OPENAI_API_KEY` when `embedding_base_url` is unset.
+    pub embedding_api_key_secret: Option<String>,
+    /// Share of hybrid search ranking given to vector similarity (0.0-1.0);
+    /// the rest goes to keyword matches.
+    pub hybrid_vector_weight: f64,
+    /// Reciprocal rank fusion constant. Larger values flatten the advantage
+    /// of top-ranked results.
+    pub hybrid_rrf_k: u32,
+    /// Periodically merge clusters of related chunks into LLM-written
+    /// summaries and archive the originals.
+    pub consolidation_enabled: bool,
+    /// Hours between consolidation passes.
+    pub consolidation_interval_hours: u64,
+    /// Model used to write summaries. Defaults to the first provider with
+    /// a configured API key.
+    pub consolidation_model: Option<String>,
+    /// Minimum number of related chunks merged into one summary.
+    pub consolidation_min_cluster_size: usize,
+    /// Semantic similarity (0.0-1.0) a chunk needs to join a cluster.
+    pub consolidation_similarity: f64,
+    /// Days before chunks saved within a session expire (0 keeps them).
+    pub session_retention_days: u32,
+    /// Chunks kept per agent before the least recently used are evicted
+    /// (0 for no limit).
+    pub max_chunks_per_agent: u32,
+}
+
+/// Aligned alias that matches the on-disk `[memory]` section naming.
+pub type MemorySettings = MemoryDefaults;
+
+impl Default for MemoryDefaults {
+    fn default() -> Self {
+        Self {
+            auto_tag: true,
+            max_suggested_tags: DEFAULT_MEMORY_MAX_SUGGESTED_TAGS,
+            tag_retention: Vec::new(),
+            embedding_model: None,
+            embedding_dimension: DEFAULT_MEMORY_EMBEDDING_DIMENSION,
+            embedding_base_url: None,
+            embedding_api_key_secret: None,
+            hybrid_vector_weight: DEFAULT_MEMORY_HYBRID_VECTOR_WEIGHT,
+            hybrid_rrf_k: DEFAULT_MEMORY_HYBRID_RRF_K,
+            consolidation_enabled: false,
+            consolidation_interval_hours: DEFAULT_MEMORY_CONSOLIDATION_INTERVAL_HOURS,
+            consolidation_model: None,
+            consolidation_min_cluster_size: DEFAULT_MEMORY_CONSOLIDATION_MIN_CLUSTER_SIZE,
+            consolidation_similarity: DEFAULT_MEMORY_CONSOLIDATION_SIMILARITY,
+            session_retention_days: DEFAULT_MEMORY_SESSION_RETENTION_DAYS,
+            max_chunks_per_agent: DEFAULT_MEMORY_MAX_CHUNKS_PER_AGENT,
+        }
+    }
+}
+
+impl MemoryDefaults {
+    fn validate(&self) -> Result<()> {
+        if self.embedding_dimension == 0 {
+            return Err(anyhow::anyhow!(
+                "memory.embedding_dimension must be at least 1"
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.hybrid_vector_weight) {
+            return Err(anyhow::anyhow!(
+                "memory.hybrid_vector_weight must be between 0.0 and 1.0"
+            ));
+        }
+        if self.hybrid_rrf_k == 0 {
+            return Err(anyhow::anyhow!("memory.hybrid_rrf_k must be at least 1"));
+        }
+        if self.consolidation_interval_hours == 0 {
+            return Err(anyhow::anyhow!(
+                "memory.consolidation_interval_hours must be at least 1"
+            ));
+        }
+        if self.consolidation_min_cluster_size < 2 {
+            return Err(anyhow::anyhow!(
+                "memory.consolidation_min_cluster_size must be at least 2"
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.consolidation_similarity) {
+            return Err(anyhow::anyhow!(
+                "memory.consolidation_similarity must be between 0.0 and 1.0"
+            ));
+        }
+        let mut seen = HashSet::new();
+        for rule in &self.tag_retention {
+            let tag = rule.tag.trim().to_ascii_lowercase();
+            if tag.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "memory.tag_retention tags must be non-empty strings"
+                ));
+            }
+            if !seen.insert(tag.clone()) {
+                return Err(anyhow::anyhow!(
+                    "Duplicate memory.tag_retention rule for tag: {}",
+                    tag
+                ));
+            }
+            if rule.retention_days != 0 && rule.retention_days < MIN_RETENTION_DAYS {
+                return Err(anyhow::anyhow!(
+                    "Tag retention for '{}' must be 0 (forever) or at least {} day",
+                    tag,
+                    MIN_RETENTION_DAYS
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Retention override for memory chunks carrying a tag.
+///
+/// When a chunk matches several rules the longest retention wins, so a
+/// `retention_days = 0` (keep forever) rule protects the chunk entirely.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct TagRetentionRule {
+    pub tag: String,
+    pub retention_days: u32,
+}
+
 /// System configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(default)]
@@ -675,6 +1027,24 @@ pub struct SystemConfig {
     /// Retention period for daemon and event log files on disk.
     /// 0 = keep forever, otherwise delete files older than N days.
     pub log_file_retention_days: u32,
+    /// Retention period for tool output, media and browser session
+    /// artifacts on disk. 0 = keep until their owner is deleted.
+    pub artifact_retention_days: u32,
+    /// Total size cap for those artifacts in megabytes; the oldest are
+    /// removed first once exceeded. 0 = unlimited.
+    pub artifact_max_total_mb: u64,
+    /// LLM spend cap in USD per UTC day. Once reached, new agent runs and
+    /// chat replies are refused until the limit is raised. `None` = unlimited.
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    /// LLM spend cap in USD per UTC calendar month. `None` = unlimited.
+    #[serde(default)]
+    pub monthly_budget_usd: Option<f64>,
+    /// OTLP/HTTP collector endpoint (e.g. `http://localhost:4318`) that
+    /// receives agent run, tool and LLM spans. Read at process start;
+    /// `None` disables export.
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
     pub experimental_features: Vec<String>,
     /// Agent execution defaults.
     #[serde(default)]
@@ -691,6 +1061,9 @@ pub struct SystemConfig {
     /// Registry provider settings.
     #[serde(default)]
     pub registry_defaults: RegistrySettings,
+    /// Memory tagging settings.
+    #[serde(default)]
+    pub memory_defaults: MemorySettings,
 }
 
 impl Default for SystemConfig {
@@ -707,12 +1080,18 @@ impl Default for SystemConfig {
             checkpoint_retention_days: DEFAULT_CHECKPOINT_RETENTION_DAYS,
             memory_chunk_retention_days: DEFAULT_MEMORY_CHUNK_RETENTION_DAYS,
             log_file_retention_days: DEFAULT_LOG_FILE_RETENTION_DAYS,
+            artifact_retention_days: DEFAULT_ARTIFACT_RETENTION_DAYS,
+            artifact_max_total_mb: DEFAULT_ARTIFACT_MAX_TOTAL_MB,
+            daily_budget_usd: None,
+            monthly_budget_usd: None,
+            otlp_endpoint: None,
             experimental_features: Vec::new(),
             agent: AgentSettings::default(),
             api_defaults: ApiSettings::default(),
             runtime_defaults: RuntimeSettings::default(),
             channel_defaults: ChannelSettings::default(),
             registry_defaults: RegistrySettings::default(),
+            memory_defaults: MemorySettings::default(),
         }
     }
 }
@@ -763,6 +1142,20 @@ impl SystemConfig {
             return Err(anyhow::anyhow!("Max retries must be at least 1"));
         }
 
+        for (name, budget) in [
+            ("Daily budget", self.daily_budget_usd),
+            ("Monthly budget", self.monthly_budget_usd),
+        ] {
+            if let Some(budget) = budget
+                && (!budget.is_finite() || budget < 0.0)
+            {
+                return Err(anyhow::anyhow!(
+                    "{} must be a non-negative number of USD",
+                    name
+                ));
+            }
+        }
+
         if self.chat_session_retention_days != 0
             && self.chat_session_retention_days < MIN_RETENTION_DAYS
         {
@@ -802,6 +1195,13 @@ impl SystemConfig {
             ));
         }
 
+        if self.artifact_retention_days != 0 && self.artifact_retention_days < MIN_RETENTION_DAYS {
+            return Err(anyhow::anyhow!(
+                "Artifact retention must be 0 (forever) or at least {} day",
+                MIN_RETENTION_DAYS
+            ));
+        }
+
         let mut seen = HashSet::new();
         for feature in &self.experimental_features {
             let normalized = feature.trim().to_ascii_lowercase();
@@ -823,6 +1223,7 @@ impl SystemConfig {
         self.runtime_defaults.validate()?;
         self.channel_defaults.validate()?;
         self.registry_defaults.validate()?;
+        self.memory_defaults.validate()?;
 
         Ok(())
     }
@@ -947,6 +1348,37 @@ where
     })
 }
 
+fn deserialize_optional_f64_override<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Option<f64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ValueOrClear {
+        Value(f64),
+        Clear(String),
+    }
+
+    let parsed = Option::<ValueOrClear>::deserialize(deserializer)?;
+    Ok(match parsed {
+        None => None,
+        Some(ValueOrClear::Value(value)) => Some(Some(value)),
+        Some(ValueOrClear::Clear(value)) => {
+            let normalized = value.trim().to_ascii_lowercase();
+            match normalized.as_str() {
+                "none" | "null" | "unset" => Some(None),
+                _ => {
+                    return Err(serde::de::Error::custom(
+                        "expected a number or one of: \"none\", \"null\", \"unset\"",
+                    ));
+                }
+            }
+        }
+    })
+}
+
 fn deserialize_optional_string_list_override<'de, D>(
     deserializer: D,
 ) -> std::result::Result<Option<Option<Vec<String>>>, D::Error>
@@ -987,6 +1419,24 @@ struct AgentDefaultsOverride {
     pub bash_timeout_secs: Option<u64>,
     pub python_timeout_secs: Option<u64>,
     pub browser_timeout_secs: Option<u64>,
+    pub browser_allowed_domains: Option<Vec<String>>,
+    pub browser_denied_domains: Option<Vec<String>>,
+    pub browser_block_private_networks: Option<bool>,
+    pub browser_artifact_max_session_bytes: Option<u64>,
+    pub browser_artifact_max_total_bytes: Option<u64>,
+    pub browser_artifact_max_age_secs: Option<u64>,
+    pub subprocess_memory_limit_mb: Option<u64>,
+    pub subprocess_cpu_weight: Option<u32>,
+    pub subprocess_max_processes: Option<u32>,
+    pub subprocess_cpu_time_secs: Option<u64>,
+    pub container_runtime: Option<String>,
+    pub container_image: Option<String>,
+    pub container_network: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_string_list_override"
+    )]
+    pub subprocess_network_allow: Option<Option<Vec<String>>>,
     pub process_session_ttl_secs: Option<u64>,
     pub approval_timeout_secs: Option<u64>,
     pub max_iterations: Option<usize>,
@@ -1026,6 +1476,48 @@ impl AgentDefaultsOverride {
         if let Some(value) = self.browser_timeout_secs {
             agent.browser_timeout_secs = value;
         }
+        if let Some(value) = self.browser_allowed_domains.clone() {
+            agent.browser_allowed_domains = value;
+        }
+        if let Some(value) = self.browser_denied_domains.clone() {
+            agent.browser_denied_domains = value;
+        }
+        if let Some(value) = self.browser_block_private_networks {
+            agent.browser_block_private_networks = value;
+        }
+        if let Some(value) = self.browser_artifact_max_session_bytes {
+            agent.browser_artifact_max_session_bytes = value;
+        }
+        if let Some(value) = self.browser_artifact_max_total_bytes {
+            agent.browser_artifact_max_total_bytes = value;
+        }
+        if let Some(value) = self.browser_artifact_max_age_secs {
+            agent.browser_artifact_max_age_secs = value;
+        }
+        if let Some(value) = self.subprocess_memory_limit_mb {
+            agent.subprocess_memory_limit_mb = value;
+        }
+        if let Some(value) = self.subprocess_cpu_weight {
+            agent.subprocess_cpu_weight = value;
+        }
+        if let Some(value) = self.subprocess_max_processes {
+            agent.subprocess_max_processes = value;
+        }
+        if let Some(value) = self.subprocess_cpu_time_secs {
+            agent.subprocess_cpu_time_secs = value;
+        }
+        if let Some(value) = self.container_runtime.clone() {
+            agent.container_runtime = Some(value);
+        }
+        if let Some(value) = self.container_image.clone() {
+            agent.container_image = value;
+        }
+        if let Some(value) = self.container_network {
+            agent.container_network = value;
+        }
+        if let Some(value) = self.subprocess_network_allow.clone() {
+            agent.subprocess_network_allow = value;
+        }
         if let Some(value) = self.process_session_ttl_secs {
             agent.process_session_ttl_secs = value;
         }
@@ -1121,6 +1613,7 @@ impl ApiDefaultsOverride {
 struct RuntimeDefaultsOverride {
     pub background_runner_poll_interval_ms: Option<u64>,
     pub background_runner_max_concurrent_tasks: Option<usize>,
+    pub background_runner_auto_resume: Option<bool>,
     pub chat_max_session_history: Option<usize>,
 }
 
@@ -1132,6 +1625,9 @@ impl RuntimeDefaultsOverride {
         if let Some(value) = self.background_runner_max_concurrent_tasks {
             runtime_defaults.background_runner_max_concurrent_tasks = value;
         }
+        if let Some(value) = self.background_runner_auto_resume {
+            runtime_defaults.background_runner_auto_resume = value;
+        }
         if let Some(value) = self.chat_max_session_history {
             runtime_defaults.chat_max_session_history = value;
         }
@@ -1143,6 +1639,8 @@ impl RuntimeDefaultsOverride {
 struct ChannelDefaultsOverride {
     pub telegram_api_timeout_secs: Option<u64>,
     pub telegram_polling_timeout_secs: Option<u32>,
+    pub voice_replies: Option<bool>,
+    pub webhooks: Option<Vec<WebhookSinkConfig>>,
 }
 
 impl ChannelDefaultsOverride {
@@ -1153,6 +1651,12 @@ impl ChannelDefaultsOverride {
         if let Some(value) = self.telegram_polling_timeout_secs {
             channel_defaults.telegram_polling_timeout_secs = value;
         }
+        if let Some(value) = self.voice_replies {
+            channel_defaults.voice_replies = value;
+        }
+        if let Some(value) = &self.webhooks {
+            channel_defaults.webhooks = value.clone();
+        }
     }
 }
 
@@ -1161,6 +1665,8 @@ impl ChannelDefaultsOverride {
 struct RegistryDefaultsOverride {
     pub github_cache_ttl_secs: Option<u64>,
     pub marketplace_cache_ttl_secs: Option<u64>,
+    pub trusted_skill_keys: Option<Vec<String>>,
+    pub require_signed_skills: Option<bool>,
 }
 
 impl RegistryDefaultsOverride {
@@ -1171,6 +1677,86 @@ impl RegistryDefaultsOverride {
         if let Some(value) = self.marketplace_cache_ttl_secs {
             registry_defaults.marketplace_cache_ttl_secs = value;
         }
+        if let Some(value) = &self.trusted_skill_keys {
+            registry_defaults.trusted_skill_keys = value.clone();
+        }
+        if let Some(value) = self.require_signed_skills {
+            registry_defaults.require_signed_skills = value;
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MemoryDefaultsOverride {
+    pub auto_tag: Option<bool>,
+    pub max_suggested_tags: Option<usize>,
+    pub tag_retention: Option<Vec<TagRetentionRule>>,
+    pub embedding_model: Option<String>,
+    pub embedding_dimension: Option<usize>,
+    pub embedding_base_url: Option<String>,
+    pub embedding_api_key_secret: Option<String>,
+    pub hybrid_vector_weight: Option<f64>,
+    pub hybrid_rrf_k: Option<u32>,
+    pub consolidation_enabled: Option<bool>,
+    pub consolidation_interval_hours: Option<u64>,
+    pub consolidation_model: Option<String>,
+    pub consolidation_min_cluster_size: Option<usize>,
+    pub consolidation_similarity: Option<f64>,
+    pub session_retention_days: Option<u32>,
+    pub max_chunks_per_agent: Option<u32>,
+}
+
+impl MemoryDefaultsOverride {
+    fn apply_to(&self, memory_defaults: &mut MemoryDefaults) {
+        if let Some(value) = self.auto_tag {
+            memory_defaults.auto_tag = value;
+        }
+        if let Some(value) = self.max_suggested_tags {
+            memory_defaults.max_suggested_tags = value;
+        }
+        if let Some(value) = &self.tag_retention {
+            memory_defaults.tag_retention = value.clone();
+        }
+        if let Some(value) = self.embedding_model.clone() {
+            memory_defaults.embedding_model = Some(value);
+        }
+        if let Some(value) = self.embedding_dimension {
+            memory_defaults.embedding_dimension = value;
+        }
+        if let Some(value) = self.embedding_base_url.clone() {
+            memory_defaults.embedding_base_url = Some(value);
+        }
+        if let Some(value) = self.embedding_api_key_secret.clone() {
+            memory_defaults.embedding_api_key_secret = Some(value);
+        }
+        if let Some(value) = self.hybrid_vector_weight {
+            memory_defaults.hybrid_vector_weight = value;
+        }
+        if let Some(value) = self.hybrid_rrf_k {
+            memory_defaults.hybrid_rrf_k = value;
+        }
+        if let Some(value) = self.consolidation_enabled {
+            memory_defaults.consolidation_enabled = value;
+        }
+        if let Some(value) = self.consolidation_interval_hours {
+            memory_defaults.consolidation_interval_hours = value;
+        }
+        if let Some(value) = self.consolidation_model.clone() {
+            memory_defaults.consolidation_model = Some(value);
+        }
+        if let Some(value) = self.consolidation_min_cluster_size {
+            memory_defaults.consolidation_min_cluster_size = value;
+        }
+        if let Some(value) = self.consolidation_similarity {
+            memory_defaults.consolidation_similarity = value;
+        }
+        if let Some(value) = self.session_retention_days {
+            memory_defaults.session_retention_days = value;
+        }
+        if let Some(value) = self.max_chunks_per_agent {
+            memory_defaults.max_chunks_per_agent = value;
+        }
     }
 }
 
@@ -1190,6 +1776,13 @@ struct SystemSectionOverride {
     pub checkpoint_retention_days: Option<u32>,
     pub memory_chunk_retention_days: Option<u32>,
     pub log_file_retention_days: Option<u32>,
+    pub artifact_retention_days: Option<u32>,
+    pub artifact_max_total_mb: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_optional_f64_override")]
+    pub daily_budget_usd: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "deserialize_optional_f64_override")]
+    pub monthly_budget_usd: Option<Option<f64>>,
+    pub otlp_endpoint: Option<String>,
     pub experimental_features: Option<Vec<String>>,
 }
 
@@ -1228,12 +1821,53 @@ impl SystemSectionOverride {
         if let Some(value) = self.log_file_retention_days {
             config.log_file_retention_days = value;
         }
+        if let Some(value) = self.artifact_retention_days {
+            config.artifact_retention_days = value;
+        }
+        if let Some(value) = self.artifact_max_total_mb {
+            config.artifact_max_total_mb = value;
+        }
+        if let Some(value) = self.daily_budget_usd {
+            config.daily_budget_usd = value;
+        }
+        if let Some(value) = self.monthly_budget_usd {
+            config.monthly_budget_usd = value;
+        }
+        if let Some(value) = self.otlp_endpoint.clone() {
+            config.otlp_endpoint = Some(value);
+        }
         if let Some(values) = self.experimental_features.clone() {
             config.experimental_features = values;
         }
     }
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StorageSettingsOverride {
+    pub backend: Option<StorageBackendKind>,
+    pub sqlite_path: Option<String>,
+    pub encrypt_at_rest: Option<bool>,
+    pub secrets: Option<SecretStorageMode>,
+}
+
+impl StorageSettingsOverride {
+    fn apply_to(&self, config: &mut StorageSettings) {
+        if let Some(value) = self.backend {
+            config.backend = value;
+        }
+        if let Some(value) = self.sqlite_path.clone() {
+            config.sqlite_path = Some(value);
+        }
+        if let Some(value) = self.encrypt_at_rest {
+            config.encrypt_at_rest = value;
+        }
+        if let Some(value) = self.secrets {
+            config.secrets = value;
+        }
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(default, deny_unknown_fields)]
 struct UnifiedConfigOverride {
@@ -1243,7 +1877,9 @@ struct UnifiedConfigOverride {
     pub runtime: Option<RuntimeDefaultsOverride>,
     pub channel: Option<ChannelDefaultsOverride>,
     pub registry: Option<RegistryDefaultsOverride>,
+    pub memory: Option<MemoryDefaultsOverride>,
     pub cli: Option<CliConfigOverride>,
+    pub storage: Option<StorageSettingsOverride>,
 }
 
 impl UnifiedConfigOverride {
@@ -1266,9 +1902,15 @@ impl UnifiedConfigOverride {
         if let Some(registry_override) = &self.registry {
             registry_override.apply_to(&mut config.registry);
         }
+        if let Some(memory_override) = &self.memory {
+            memory_override.apply_to(&mut config.memory);
+        }
         if let Some(cli_override) = &self.cli {
             cli_override.apply_to(&mut config.cli);
         }
+        if let Some(storage_override) = &self.storage {
+            storage_override.apply_to(&mut config.storage);
+        }
     }
 }
 
@@ -1651,6 +2293,11 @@ pub fn load_global_cli_config() -> Result<CliConfig> {
     Ok(load_config_layers()?.global.cli.clone())
 }
 
+/// Load the effective storage backend settings from config.toml.
+pub fn load_storage_settings() -> Result<StorageSettings> {
+    Ok(load_config_layers()?.effective.storage.clone())
+}
+
 pub fn write_cli_config(config: &CliConfig) -> Result<()> {
     let mut current = load_config_layers()
         .map(|layers| layers.global)
@@ -1899,6 +2546,7 @@ mod tests {
                 .background_runner_max_concurrent_tasks,
             DEFAULT_BACKGROUND_RUNNER_MAX_CONCURRENT_TASKS
         );
+        assert!(!config.runtime_defaults.background_runner_auto_resume);
         assert_eq!(
             config.runtime_defaults.chat_max_session_history,
             DEFAULT_CHAT_MAX_SESSION_HISTORY
@@ -1911,6 +2559,8 @@ mod tests {
             config.channel_defaults.telegram_polling_timeout_secs,
             DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS
         );
+        assert!(!config.channel_defaults.voice_replies);
+        assert!(config.channel_defaults.webhooks.is_empty());
         assert_eq!(
             config.registry_defaults.github_cache_ttl_secs,
             DEFAULT_GITHUB_CACHE_TTL_SECS
@@ -2112,6 +2762,47 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_artifact_retention_defaults() {
+        let config = SystemConfig::default();
+        assert_eq!(config.artifact_retention_days, 14);
+        assert_eq!(config.artifact_max_total_mb, 2048);
+        assert!(config.validate().is_ok());
+
+        let config = SystemConfig {
+            artifact_retention_days: 0,
+            artifact_max_total_mb: 0,
+            ..SystemConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_budget_validation() {
+        let config = SystemConfig::default();
+        assert_eq!(config.daily_budget_usd, None);
+        assert_eq!(config.monthly_budget_usd, None);
+
+        let config = SystemConfig {
+            daily_budget_usd: Some(5.0),
+            monthly_budget_usd: Some(0.0),
+            ..SystemConfig::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let config = SystemConfig {
+            daily_budget_usd: Some(-1.0),
+            ..SystemConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = SystemConfig {
+            monthly_budget_usd: Some(f64::NAN),
+            ..SystemConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_agent_defaults_validation() {
         let mut config = SystemConfig::default();
@@ -2275,6 +2966,13 @@ task_timeout_seconds = 9999
 python_timeout_secs = 45
 llm_timeout_secs = 660
 browser_timeout_secs = 240
+browser_allowed_domains = ["example.com"]
+browser_block_private_networks = true
+browser_artifact_max_total_bytes = 1048576
+subprocess_memory_limit_mb = 512
+subprocess_cpu_time_secs = 300
+container_runtime = "podman"
+subprocess_network_allow = ["*:443"]
 process_session_ttl_secs = 5400
 approval_timeout_secs = 420
 max_wall_clock_secs = 7200
@@ -2288,6 +2986,26 @@ fallback_models = ["alpha", "beta"]
         assert_eq!(effective.agent.python_timeout_secs, 45);
         assert_eq!(effective.agent.llm_timeout_secs, Some(660));
         assert_eq!(effective.agent.browser_timeout_secs, 240);
+        assert_eq!(
+            effective.agent.browser_allowed_domains,
+            vec!["example.com".to_string()]
+        );
+        assert!(effective.agent.browser_denied_domains.is_empty());
+        assert!(effective.agent.browser_block_private_networks);
+        assert_eq!(effective.agent.browser_artifact_max_total_bytes, 1_048_576);
+        assert_eq!(effective.agent.browser_artifact_max_session_bytes, 0);
+        assert_eq!(effective.agent.subprocess_memory_limit_mb, 512);
+        assert_eq!(effective.agent.subprocess_max_processes, 0);
+        assert_eq!(effective.agent.subprocess_cpu_time_secs, 300);
+        assert_eq!(effective.agent.container_runtime.as_deref(), Some("podman"));
+        assert_eq!(
+            effective.agent.subprocess_network_allow,
+            Some(vec!["*:443".to_string()])
+        );
+        assert_eq!(
+            effective.agent.container_image,
+            DEFAULT_AGENT_CONTAINER_IMAGE
+        );
         assert_eq!(effective.agent.process_session_ttl_secs, 5400);
         assert_eq!(effective.agent.approval_timeout_secs, 420);
         assert_eq!(effective.agent.max_wall_clock_secs, Some(7200));
@@ -2320,15 +3038,24 @@ diagnostics_timeout_ms = 9000
             r#"[runtime]
 background_runner_poll_interval_ms = 15000
 background_runner_max_concurrent_tasks = 8
+background_runner_auto_resume = true
 chat_max_session_history = 42
 
 [channel]
 telegram_api_timeout_secs = 45
 telegram_polling_timeout_secs = 55
+voice_replies = true
+
+[[channel.webhooks]]
+url = "https://hooks.example.com/restflow"
+signing_secret = "WEBHOOK_SECRET"
+events = ["task.*", "deliverable.created"]
 
 [registry]
 github_cache_ttl_secs = 900
 marketplace_cache_ttl_secs = 450
+trusted_skill_keys = ["c2lnbmluZy1rZXk="]
+require_signed_skills = true
 "#,
         );
         let _guard = EnvGuard::set_path(WORKSPACE_CONFIG_ENV, file.path());
@@ -2346,11 +3073,154 @@ marketplace_cache_ttl_secs = 450
                 .background_runner_max_concurrent_tasks,
             8
         );
+        assert!(effective.runtime_defaults.background_runner_auto_resume);
         assert_eq!(effective.runtime_defaults.chat_max_session_history, 42);
         assert_eq!(effective.channel_defaults.telegram_api_timeout_secs, 45);
         assert_eq!(effective.channel_defaults.telegram_polling_timeout_secs, 55);
+        assert!(effective.channel_defaults.voice_replies);
+        assert_eq!(
+            effective.channel_defaults.webhooks,
+            vec![WebhookSinkConfig {
+                url: "https://hooks.example.com/restflow".to_string(),
+                signing_secret: Some("WEBHOOK_SECRET".to_string()),
+                events: vec!["task.*".to_string(), "deliverable.created".to_string()],
+            }]
+        );
         assert_eq!(effective.registry_defaults.github_cache_ttl_secs, 900);
         assert_eq!(effective.registry_defaults.marketplace_cache_ttl_secs, 450);
+        assert_eq!(
+            effective.registry_defaults.trusted_skill_keys,
+            vec!["c2lnbmluZy1rZXk=".to_string()]
+        );
+        assert!(effective.registry_defaults.require_signed_skills);
+    }
+
+    #[test]
+    fn test_partial_memory_override() {
+        let ctx = setup_test_storage();
+        let file = write_override_file(
+            r#"[memory]
+auto_tag = false
+
+[[memory.tag_retention]]
+tag = "scratch"
+retention_days = 7
+
+[[memory.tag_retention]]
+tag = "pinned"
+retention_days = 0
+"#,
+        );
+        let _guard = EnvGuard::set_path(WORKSPACE_CONFIG_ENV, file.path());
+
+        let effective = ctx.storage.get_effective_config().unwrap();
+        assert!(!effective.memory_defaults.auto_tag);
+        assert_eq!(
+            effective.memory_defaults.max_suggested_tags,
+            DEFAULT_MEMORY_MAX_SUGGESTED_TAGS
+        );
+        assert_eq!(
+            effective.memory_defaults.tag_retention,
+            vec![
+                TagRetentionRule {
+                    tag: "scratch".to_string(),
+                    retention_days: 7,
+                },
+                TagRetentionRule {
+                    tag: "pinned".to_string(),
+                    retention_days: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_storage_override() {
+        let _ctx = setup_test_storage();
+        assert_eq!(
+            load_storage_settings().unwrap().backend,
+            StorageBackendKind::Redb
+        );
+
+        let file = write_override_file(
+            r#"[storage]
+backend = "sqlite"
+sqlite_path = "/tmp/restflow-shared.sqlite"
+encrypt_at_rest = true
+secrets = "keychain"
+"#,
+        );
+        let _guard = EnvGuard::set_path(WORKSPACE_CONFIG_ENV, file.path());
+
+        let settings = load_storage_settings().unwrap();
+        assert_eq!(settings.backend, StorageBackendKind::Sqlite);
+        assert_eq!(
+            settings.sqlite_path.as_deref(),
+            Some("/tmp/restflow-shared.sqlite")
+        );
+        assert!(settings.encrypt_at_rest);
+        assert_eq!(settings.secrets, SecretStorageMode::Keychain);
+    }
+
+    #[test]
+    fn test_duplicate_tag_retention_rule_rejected() {
+        let mut config = SystemConfig::default();
+        config.memory_defaults.tag_retention = vec![
+            TagRetentionRule {
+                tag: "Scratch".to_string(),
+                retention_days: 7,
+            },
+            TagRetentionRule {
+                tag: "scratch".to_string(),
+                retention_days: 30,
+            },
+        ];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_webhook_sink_rejected() {
+        let mut config = SystemConfig::default();
+        config.channel_defaults.webhooks = vec![WebhookSinkConfig {
+            url: "ftp://example.com".to_string(),
+            signing_secret: None,
+            events: Vec::new(),
+        }];
+        assert!(config.validate().is_err());
+
+        config.channel_defaults.webhooks[0].url = "https://example.com/hook".to_string();
+        assert!(config.validate().is_ok());
+        config.channel_defaults.webhooks[0].events = vec![" ".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_browser_domain_rules_rejected() {
+        let mut config = SystemConfig::default();
+        config.agent.browser_allowed_domains = vec![" ".to_string()];
+        assert!(config.validate().is_err());
+
+        let mut config = SystemConfig::default();
+        config.agent.browser_denied_domains = vec!["https://example.com".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_container_settings_rejected() {
+        let mut config = SystemConfig::default();
+        config.agent.container_runtime = Some("lxc".to_string());
+        assert!(config.validate().is_err());
+
+        let mut config = SystemConfig::default();
+        config.agent.container_runtime = Some("docker".to_string());
+        config.agent.container_image = " ".to_string();
+        assert!(config.validate().is_err());
+
+        let mut config = SystemConfig::default();
+        config.agent.subprocess_network_allow = Some(vec!["example.com".to_string()]);
+        assert!(config.validate().is_err());
+        config.agent.subprocess_network_allow = Some(vec!["*:443".to_string()]);
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -2367,6 +3237,50 @@ llm_timeout_secs = "none"
         assert_eq!(effective.agent.llm_timeout_secs, None);
     }
 
+    #[test]
+    fn test_partial_system_override_sets_and_clears_budgets() {
+        let ctx = setup_test_storage();
+        let config = SystemConfig {
+            monthly_budget_usd: Some(100.0),
+            ..SystemConfig::default()
+        };
+        ctx.storage.update_config(config).unwrap();
+
+        let file = write_override_file(
+            r#"[system]
+daily_budget_usd = 5
+monthly_budget_usd = "none"
+"#,
+        );
+        let _guard = EnvGuard::set_path(WORKSPACE_CONFIG_ENV, file.path());
+
+        let effective = ctx.storage.get_effective_config().unwrap();
+        assert_eq!(effective.daily_budget_usd, Some(5.0));
+        assert_eq!(effective.monthly_budget_usd, None);
+    }
+
+    #[test]
+    fn test_partial_system_override_sets_otlp_endpoint() {
+        let ctx = setup_test_storage();
+        assert_eq!(
+            ctx.storage.get_effective_config().unwrap().otlp_endpoint,
+            None
+        );
+
+        let file = write_override_file(
+            r#"[system]
+otlp_endpoint = "http://localhost:4318"
+"#,
+        );
+        let _guard = EnvGuard::set_path(WORKSPACE_CONFIG_ENV, file.path());
+
+        let effective = ctx.storage.get_effective_config().unwrap();
+        assert_eq!(
+            effective.otlp_endpoint.as_deref(),
+            Some("http://localhost:4318")
+        );
+    }
+
     #[test]
     fn test_config_storage_ignores_legacy_db_config() {
         let _env_guard = env_lock();
@@ -2519,6 +3433,10 @@ memory_search_limit = 33
         let mut config = SystemConfig::default();
         config.registry_defaults.marketplace_cache_ttl_secs = 0;
         assert!(config.validate().is_err());
+
+        let mut config = SystemConfig::default();
+        config.registry_defaults.require_signed_skills = true;
+        assert!(config.validate().is_err());
     }
 
     #[test]
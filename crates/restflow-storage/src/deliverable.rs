@@ -1,35 +1,35 @@
 //! Deliverable storage - byte-level API for typed agent outputs.
 
 use anyhow::Result;
-use redb::{Database, ReadableDatabase, TableDefinition};
+use redb::Database;
 use std::sync::Arc;
 
-use crate::range_utils::prefix_range;
+use crate::backend::{ReadTransaction, RedbBackend, StorageBackend, text_value};
 
-const DELIVERABLE_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("deliverables");
+const DELIVERABLE_TABLE: &str = "deliverables";
 /// Index table: task_id:deliverable_id -> deliverable_id
-const DELIVERABLE_TASK_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("deliverable_task_index");
+const DELIVERABLE_TASK_INDEX_TABLE: &str = "deliverable_task_index";
 /// Index table: execution_id:deliverable_id -> deliverable_id
-const DELIVERABLE_EXECUTION_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("deliverable_execution_index");
+const DELIVERABLE_EXECUTION_INDEX_TABLE: &str = "deliverable_execution_index";
 
 /// Low-level deliverable storage with byte-level API.
 #[derive(Clone)]
 pub struct DeliverableStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
 }
 
 impl DeliverableStorage {
     /// Create a new DeliverableStorage instance.
     pub fn new(db: Arc<Database>) -> Result<Self> {
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(DELIVERABLE_TABLE)?;
-        write_txn.open_table(DELIVERABLE_TASK_INDEX_TABLE)?;
-        write_txn.open_table(DELIVERABLE_EXECUTION_INDEX_TABLE)?;
-        write_txn.commit()?;
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
 
-        Ok(Self { db })
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        backend.ensure_table(DELIVERABLE_TABLE)?;
+        backend.ensure_table(DELIVERABLE_TASK_INDEX_TABLE)?;
+        backend.ensure_table(DELIVERABLE_EXECUTION_INDEX_TABLE)?;
+        Ok(Self { backend })
     }
 
     /// Store raw deliverable data with task/execution indexes.
@@ -40,96 +40,78 @@ impl DeliverableStorage {
         execution_id: &str,
         data: &[u8],
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(DELIVERABLE_TABLE)?;
-            table.insert(id, data)?;
+        self.backend.write_tables(|txn| {
+            txn.table(DELIVERABLE_TABLE)?.put(id, data)?;
 
-            let mut task_index = write_txn.open_table(DELIVERABLE_TASK_INDEX_TABLE)?;
             let task_key = format!("{}:{}", task_id, id);
-            task_index.insert(task_key.as_str(), id)?;
+            txn.table(DELIVERABLE_TASK_INDEX_TABLE)?
+                .put(&task_key, id.as_bytes())?;
 
-            let mut execution_index = write_txn.open_table(DELIVERABLE_EXECUTION_INDEX_TABLE)?;
             let execution_key = format!("{}:{}", execution_id, id);
-            execution_index.insert(execution_key.as_str(), id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            txn.table(DELIVERABLE_EXECUTION_INDEX_TABLE)?
+                .put(&execution_key, id.as_bytes())
+        })
     }
 
     /// Get raw deliverable by ID.
     pub fn get_raw(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(DELIVERABLE_TABLE)?;
-
-        if let Some(value) = table.get(id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(DELIVERABLE_TABLE, |table| table.get(id))
     }
 
     /// List raw deliverables for task.
     pub fn list_by_task_raw(&self, task_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let task_index = read_txn.open_table(DELIVERABLE_TASK_INDEX_TABLE)?;
-        let deliverable_table = read_txn.open_table(DELIVERABLE_TABLE)?;
-
-        let prefix = format!("{}:", task_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut deliverables = Vec::new();
-
-        for item in task_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let deliverable_id = value.value();
-            if let Some(data) = deliverable_table.get(deliverable_id)? {
-                deliverables.push((deliverable_id.to_string(), data.value().to_vec()));
-            }
-        }
-
-        Ok(deliverables)
+        self.backend
+            .read_tables(|txn| Self::list_indexed(txn, DELIVERABLE_TASK_INDEX_TABLE, task_id))
     }
 
     /// List raw deliverables for execution.
     pub fn list_by_execution_raw(&self, execution_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let execution_index = read_txn.open_table(DELIVERABLE_EXECUTION_INDEX_TABLE)?;
-        let deliverable_table = read_txn.open_table(DELIVERABLE_TABLE)?;
+        self.backend.read_tables(|txn| {
+            Self::list_indexed(txn, DELIVERABLE_EXECUTION_INDEX_TABLE, execution_id)
+        })
+    }
 
-        let prefix = format!("{}:", execution_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut deliverables = Vec::new();
+    fn list_indexed(
+        txn: &dyn ReadTransaction,
+        index_table: &str,
+        owner_id: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let index = txn.table(index_table)?;
+        let deliverable_table = txn.table(DELIVERABLE_TABLE)?;
 
-        for item in execution_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let deliverable_id = value.value();
-            if let Some(data) = deliverable_table.get(deliverable_id)? {
-                deliverables.push((deliverable_id.to_string(), data.value().to_vec()));
+        let prefix = format!("{}:", owner_id);
+        let mut deliverables = Vec::new();
+        for (_, value) in index.scan_prefix(&prefix)? {
+            let deliverable_id = text_value(value)?;
+            if let Some(data) = deliverable_table.get(&deliverable_id)? {
+                deliverables.push((deliverable_id, data));
             }
         }
 
         Ok(deliverables)
     }
 
+    /// List all raw deliverables.
+    pub fn list_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        self.backend
+            .read_table(DELIVERABLE_TABLE, |table| table.scan_prefix(""))
+    }
+
     /// Delete deliverable by ID with index cleanup.
     pub fn delete(&self, id: &str, task_id: &str, execution_id: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut table = write_txn.open_table(DELIVERABLE_TABLE)?;
-            let existed = table.remove(id)?.is_some();
+        self.backend.write_tables(|txn| {
+            let existed = txn.table(DELIVERABLE_TABLE)?.remove(id)?;
 
-            let mut task_index = write_txn.open_table(DELIVERABLE_TASK_INDEX_TABLE)?;
             let task_key = format!("{}:{}", task_id, id);
-            task_index.remove(task_key.as_str())?;
+            txn.table(DELIVERABLE_TASK_INDEX_TABLE)?.remove(&task_key)?;
 
-            let mut execution_index = write_txn.open_table(DELIVERABLE_EXECUTION_INDEX_TABLE)?;
             let execution_key = format!("{}:{}", execution_id, id);
-            execution_index.remove(execution_key.as_str())?;
+            txn.table(DELIVERABLE_EXECUTION_INDEX_TABLE)?
+                .remove(&execution_key)?;
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+            Ok(existed)
+        })
     }
 }
 
@@ -165,6 +147,9 @@ mod tests {
             .expect("list by execution should succeed");
         assert_eq!(by_execution.len(), 1);
 
+        let all = storage.list_raw().expect("list should succeed");
+        assert_eq!(all.len(), 2);
+
         let deleted = storage
             .delete("d1", "t1", "e1")
             .expect("delete should succeed");
@@ -0,0 +1,225 @@
+//! Entity relation storage - byte-level API for the relationship graph.
+//!
+//! Edges are stored once under `source␟relation␟target` and mirrored in a
+//! reverse index so both directions can be scanned by prefix.
+
+use anyhow::Result;
+use redb::Database;
+use std::sync::Arc;
+
+use crate::backend::{RedbBackend, StorageBackend, text_value};
+
+const ENTITY_RELATION_TABLE: &str = "entity_relations";
+/// Index table: target␟relation␟source -> edge key
+const ENTITY_RELATION_REVERSE_INDEX_TABLE: &str = "entity_relation_reverse_index";
+
+/// Separator between key segments. Entity IDs may contain `:`.
+const KEY_SEPARATOR: char = '\u{1f}';
+
+fn edge_key(source: &str, relation: &str, target: &str) -> String {
+    format!("{source}{KEY_SEPARATOR}{relation}{KEY_SEPARATOR}{target}")
+}
+
+fn node_prefix(node: &str) -> String {
+    format!("{node}{KEY_SEPARATOR}")
+}
+
+/// Reverse index key for an edge key, or `None` if the key is malformed.
+fn reverse_edge_key(edge_key: &str) -> Option<String> {
+    let mut parts = edge_key.splitn(3, KEY_SEPARATOR);
+    let source = parts.next()?;
+    let relation = parts.next()?;
+    let target = parts.next()?;
+    Some(format!(
+        "{target}{KEY_SEPARATOR}{relation}{KEY_SEPARATOR}{source}"
+    ))
+}
+
+/// A single edge to write: relation name, target node and serialized payload.
+pub struct RelationEdgeRaw<'a> {
+    pub relation: &'a str,
+    pub target: &'a str,
+    pub data: Vec<u8>,
+}
+
+/// Low-level entity relation storage with byte-level API.
+#[derive(Debug, Clone)]
+pub struct EntityRelationStorage {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl EntityRelationStorage {
+    /// Create a new EntityRelationStorage instance.
+    pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        backend.ensure_table(ENTITY_RELATION_TABLE)?;
+        backend.ensure_table(ENTITY_RELATION_REVERSE_INDEX_TABLE)?;
+        Ok(Self { backend })
+    }
+
+    /// Replace every outgoing edge of `source` with `edges` atomically.
+    pub fn replace_outgoing_raw(&self, source: &str, edges: &[RelationEdgeRaw<'_>]) -> Result<()> {
+        self.backend.write_tables(|txn| {
+            let mut table = txn.table(ENTITY_RELATION_TABLE)?;
+            let mut reverse = txn.table(ENTITY_RELATION_REVERSE_INDEX_TABLE)?;
+
+            for key in table.keys_with_prefix(&node_prefix(source))? {
+                table.remove(&key)?;
+                if let Some(reverse_key) = reverse_edge_key(&key) {
+                    reverse.remove(&reverse_key)?;
+                }
+            }
+
+            for edge in edges {
+                let key = edge_key(source, edge.relation, edge.target);
+                table.put(&key, &edge.data)?;
+                let reverse_key = edge_key(edge.target, edge.relation, source);
+                reverse.put(&reverse_key, key.as_bytes())?;
+            }
+            Ok(())
+        })
+    }
+
+    /// List raw edges that start at `source`.
+    pub fn list_outgoing_raw(&self, source: &str) -> Result<Vec<Vec<u8>>> {
+        let entries = self.backend.read_table(ENTITY_RELATION_TABLE, |table| {
+            table.scan_prefix(&node_prefix(source))
+        })?;
+        Ok(entries.into_iter().map(|(_, value)| value).collect())
+    }
+
+    /// List raw edges that end at `target`.
+    pub fn list_incoming_raw(&self, target: &str) -> Result<Vec<Vec<u8>>> {
+        self.backend.read_tables(|txn| {
+            let reverse = txn.table(ENTITY_RELATION_REVERSE_INDEX_TABLE)?;
+            let table = txn.table(ENTITY_RELATION_TABLE)?;
+
+            let mut edges = Vec::new();
+            for (_, value) in reverse.scan_prefix(&node_prefix(target))? {
+                if let Some(data) = table.get(&text_value(value)?)? {
+                    edges.push(data);
+                }
+            }
+            Ok(edges)
+        })
+    }
+
+    /// Remove every edge that starts or ends at `node`.
+    ///
+    /// Returns the number of edges removed.
+    pub fn remove_node(&self, node: &str) -> Result<usize> {
+        self.backend.write_tables(|txn| {
+            let mut table = txn.table(ENTITY_RELATION_TABLE)?;
+            let mut reverse = txn.table(ENTITY_RELATION_REVERSE_INDEX_TABLE)?;
+
+            let prefix = node_prefix(node);
+            let outgoing = table.keys_with_prefix(&prefix)?;
+            let incoming = reverse.scan_prefix(&prefix)?;
+
+            let mut removed = 0;
+            for key in outgoing {
+                if table.remove(&key)? {
+                    removed += 1;
+                }
+                if let Some(reverse_key) = reverse_edge_key(&key) {
+                    reverse.remove(&reverse_key)?;
+                }
+            }
+            for (reverse_key, key) in incoming {
+                reverse.remove(&reverse_key)?;
+                if table.remove(&text_value(key)?)? {
+                    removed += 1;
+                }
+            }
+            Ok(removed)
+        })
+    }
+
+    /// Whether no edges have been stored yet.
+    pub fn is_empty(&self) -> Result<bool> {
+        let count = self
+            .backend
+            .read_table(ENTITY_RELATION_TABLE, |table| table.count())?;
+        Ok(count == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup() -> (EntityRelationStorage, tempfile::TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(temp_dir.path().join("relations.db")).unwrap());
+        (EntityRelationStorage::new(db).unwrap(), temp_dir)
+    }
+
+    fn edge<'a>(relation: &'a str, target: &'a str) -> RelationEdgeRaw<'a> {
+        RelationEdgeRaw {
+            relation,
+            target,
+            data: format!("{relation}->{target}").into_bytes(),
+        }
+    }
+
+    #[test]
+    fn test_replace_outgoing_updates_both_directions() {
+        let (storage, _dir) = setup();
+        assert!(storage.is_empty().unwrap());
+
+        storage
+            .replace_outgoing_raw("task-1", &[edge("spawned_by", "agent-1")])
+            .unwrap();
+        assert_eq!(storage.list_incoming_raw("agent-1").unwrap().len(), 1);
+
+        storage
+            .replace_outgoing_raw("task-1", &[edge("spawned_by", "agent-2")])
+            .unwrap();
+        assert!(storage.list_incoming_raw("agent-1").unwrap().is_empty());
+        assert_eq!(
+            storage.list_outgoing_raw("task-1").unwrap(),
+            vec![b"spawned_by->agent-2".to_vec()]
+        );
+        assert!(!storage.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_prefix_scans_do_not_match_longer_ids() {
+        let (storage, _dir) = setup();
+        storage
+            .replace_outgoing_raw("channel:abc", &[edge("spawned_by", "agent-1")])
+            .unwrap();
+        storage
+            .replace_outgoing_raw("channel:abc:def", &[edge("spawned_by", "agent-1")])
+            .unwrap();
+
+        assert_eq!(storage.list_outgoing_raw("channel:abc").unwrap().len(), 1);
+        assert_eq!(storage.list_incoming_raw("agent-1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_remove_node_drops_incoming_and_outgoing_edges() {
+        let (storage, _dir) = setup();
+        storage
+            .replace_outgoing_raw("task-1", &[edge("spawned_by", "agent-1")])
+            .unwrap();
+        storage
+            .replace_outgoing_raw("deliverable-1", &[edge("produced_by", "task-1")])
+            .unwrap();
+
+        assert_eq!(storage.remove_node("task-1").unwrap(), 2);
+        assert!(storage.list_incoming_raw("agent-1").unwrap().is_empty());
+        assert!(
+            storage
+                .list_outgoing_raw("deliverable-1")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(storage.is_empty().unwrap());
+    }
+}
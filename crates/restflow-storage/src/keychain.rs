@@ -0,0 +1,119 @@
+//! OS keychain storage for secret values.
+//!
+//! In `SecretStorageMode::Keychain` secret values are kept in the platform
+//! credential store (macOS Keychain, Windows Credential Manager, or the
+//! Secret Service/libsecret on Linux) under the `restflow` service. The redb
+//! record keeps only the encrypted metadata and the keychain account name.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Keychain service name shared with the database key entry
+pub const KEYCHAIN_SERVICE: &str = "restflow";
+
+/// Backing store for secret values kept outside the database
+pub trait SecretValueStore: Send + Sync {
+    fn get(&self, account: &str) -> Result<Option<String>>;
+    fn set(&self, account: &str, value: &str) -> Result<()>;
+    /// Remove an entry; removing a missing entry is not an error
+    fn delete(&self, account: &str) -> Result<()>;
+}
+
+/// Keychain account used for a secret key
+pub fn keychain_account(key: &str) -> String {
+    format!("secret:{key}")
+}
+
+/// The platform keychain
+#[cfg(feature = "keychain")]
+#[derive(Debug, Default)]
+pub struct OsKeychain;
+
+#[cfg(feature = "keychain")]
+impl OsKeychain {
+    fn entry(account: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, account).map_err(|err| {
+            anyhow::anyhow!("Failed to access the OS keychain entry {account}: {err}")
+        })
+    }
+}
+
+#[cfg(feature = "keychain")]
+impl SecretValueStore for OsKeychain {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        match Self::entry(account)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(anyhow::anyhow!(
+                "Failed to read {account} from the OS keychain: {err}"
+            )),
+        }
+    }
+
+    fn set(&self, account: &str, value: &str) -> Result<()> {
+        Self::entry(account)?
+            .set_password(value)
+            .map_err(|err| anyhow::anyhow!("Failed to store {account} in the OS keychain: {err}"))
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        match Self::entry(account)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(anyhow::anyhow!(
+                "Failed to remove {account} from the OS keychain: {err}"
+            )),
+        }
+    }
+}
+
+/// Open the platform keychain.
+#[cfg(feature = "keychain")]
+pub fn os_keychain() -> Result<Arc<dyn SecretValueStore>> {
+    Ok(Arc::new(OsKeychain))
+}
+
+/// Open the platform keychain.
+#[cfg(not(feature = "keychain"))]
+pub fn os_keychain() -> Result<Arc<dyn SecretValueStore>> {
+    anyhow::bail!("Keychain secret storage needs a build with the `keychain` feature")
+}
+
+/// In-process value store for tests and embedders without a keychain
+#[derive(Debug, Default)]
+pub struct MemoryValueStore {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryValueStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+impl SecretValueStore for MemoryValueStore {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        Ok(self.entries.lock().get(account).cloned())
+    }
+
+    fn set(&self, account: &str, value: &str) -> Result<()> {
+        self.entries
+            .lock()
+            .insert(account.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        self.entries.lock().remove(account);
+        Ok(())
+    }
+}
@@ -2,7 +2,6 @@
 
 use crate::{SimpleStorage, define_simple_storage};
 use anyhow::Result;
-use redb::{ReadableDatabase, ReadableTable};
 
 define_simple_storage! {
     /// KV store storage with byte-level API.
@@ -12,35 +11,17 @@ define_simple_storage! {
 impl KvStoreStorage {
     /// List all keys with optional prefix filter.
     pub fn list_keys(&self, prefix: Option<&str>) -> Result<Vec<String>> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(<Self as SimpleStorage>::TABLE)?;
-        let mut keys = Vec::new();
-
-        for entry in table.iter()? {
-            let (key, _) = entry?;
-            let key_str = key.value();
-            if prefix.is_none() || key_str.starts_with(prefix.unwrap()) {
-                keys.push(key_str.to_string());
-            }
-        }
-
-        Ok(keys)
+        self.backend()
+            .read_table(<Self as SimpleStorage>::TABLE, |table| {
+                table.keys_with_prefix(prefix.unwrap_or(""))
+            })
     }
 
     /// List all entries (key + raw data) with optional prefix filter.
     pub fn list_raw(&self, prefix: Option<&str>) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(<Self as SimpleStorage>::TABLE)?;
-        let mut entries = Vec::new();
-
-        for entry in table.iter()? {
-            let (key, value) = entry?;
-            let key_str = key.value();
-            if prefix.is_none() || key_str.starts_with(prefix.unwrap()) {
-                entries.push((key_str.to_string(), value.value().to_vec()));
-            }
-        }
-
-        Ok(entries)
+        self.backend()
+            .read_table(<Self as SimpleStorage>::TABLE, |table| {
+                table.scan_prefix(prefix.unwrap_or(""))
+            })
     }
 }
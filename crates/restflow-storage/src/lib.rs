@@ -8,18 +8,30 @@
 //! The storage layer uses a simple key-value design with separate tables for
 //! different entity types. Higher-level type wrappers are provided by
 //! restflow-core.
+//!
+//! Storages built on [`SimpleStorage`] go through a [`StorageBackend`], so
+//! their tables can live in redb or in a shared SQLite file depending on the
+//! `[storage]` section of `config.toml`.
 
 pub mod agent;
+pub mod api_connector;
+pub mod at_rest;
 pub mod audit;
 pub mod auth_profiles;
+pub mod backend;
 pub mod background_agent;
+pub mod backup;
+pub mod browser_plan;
 pub mod channel_session_binding;
+pub mod chat_index;
 pub mod chat_session;
 pub mod checkpoint;
 pub mod config;
 pub mod daemon_state;
 pub mod deliverable;
+pub mod entity_relation;
 pub mod execution_trace;
+pub mod keychain;
 pub mod kv_store;
 pub mod memory;
 pub mod memory_index;
@@ -34,36 +46,53 @@ pub mod structured_execution_log;
 pub mod telemetry_metric_sample;
 pub mod terminal_session;
 pub mod trigger;
+pub mod usage_record;
 pub mod vector;
 pub mod work_item;
+pub mod workspace;
 
 mod encryption;
 mod simple_storage;
 pub mod time_utils;
 
 pub use agent::AgentStorage;
+pub use api_connector::ApiConnectorStorage;
+pub use at_rest::{AT_REST_SEALED_STATE_KEY, EncryptedBackend, ValueCipher};
 pub use auth_profiles::AuthProfileStorage;
+pub use backend::{
+    REDB_MIGRATED_STATE_KEY, ReadTransaction, RedbBackend, SqliteBackend, StorageBackend,
+    StorageBackendKind, TableRead, TableWrite, WriteTransaction, migrate_from_redb, open_backend,
+};
 pub use background_agent::BackgroundAgentStorage;
+pub use backup::{BackupSummary, backup_to, restore_from};
+pub use browser_plan::BrowserPlanStorage;
 pub use channel_session_binding::ChannelSessionBindingStorage;
+pub use chat_index::{
+    ChatIndexFilter, ChatIndexPage, ChatMessageHit, ChatMessageIndex, ChatQueryUnit,
+    IndexableMessage,
+};
 pub use chat_session::ChatSessionStorage;
 pub use checkpoint::CheckpointStorage;
 pub use config::{
     AgentDefaults, AgentSettings, ApiDefaults, ApiSettings, ChannelDefaults, ChannelSettings,
     CliConfig, ConfigDocument, ConfigSourcePathInfo, ConfigStorage, ConfigValueSourceInfo,
-    ConfigValueSourceKind, EffectiveConfigSources, RegistryDefaults, RegistrySettings,
-    RuntimeDefaults, RuntimeSettings, SystemConfig, SystemSection, effective_config_sources,
-    load_cli_config, load_global_cli_config, write_cli_config,
+    ConfigValueSourceKind, EffectiveConfigSources, MemoryDefaults, MemorySettings,
+    RegistryDefaults, RegistrySettings, RuntimeDefaults, RuntimeSettings, StorageSettings,
+    SystemConfig, SystemSection, TagRetentionRule, WebhookSinkConfig, effective_config_sources,
+    load_cli_config, load_global_cli_config, load_storage_settings, write_cli_config,
 };
 pub use daemon_state::DaemonStateStorage;
 pub use deliverable::DeliverableStorage;
+pub use entity_relation::{EntityRelationStorage, RelationEdgeRaw};
 pub use execution_trace::ExecutionTraceStorage as AuditStorageBackend;
 pub use execution_trace::ExecutionTraceStorage as ExecutionTraceStorageBackend;
+pub use keychain::{MemoryValueStore, SecretValueStore};
 pub use kv_store::KvStoreStorage;
-pub use memory::{MemoryStorage, PutChunkResult};
+pub use memory::{ChunkTagUpdate, MemoryStorage, PutChunkResult};
 pub use memory_index::{IndexableChunk, MemoryIndex, SearchHit};
 pub use pairing::PairingStorage;
 pub use provider_health_snapshot::ProviderHealthSnapshotStorage;
-pub use secrets::{Secret, SecretStorage, SecretStorageConfig};
+pub use secrets::{Secret, SecretAccess, SecretStorage, SecretStorageConfig, SecretStorageMode};
 pub use security_amendment::SecurityAmendmentStorage;
 pub use simple_storage::SimpleStorage;
 pub use skill::SkillStorage;
@@ -71,5 +100,7 @@ pub use structured_execution_log::StructuredExecutionLogStorage;
 pub use telemetry_metric_sample::TelemetryMetricSampleStorage;
 pub use terminal_session::TerminalSessionStorage;
 pub use trigger::TriggerStorage;
+pub use usage_record::UsageRecordStorage;
 pub use vector::{VectorConfig, VectorStats, VectorStorage};
 pub use work_item::WorkItemStorage;
+pub use workspace::{UserStorage, WorkspaceResourceStorage};
@@ -1,7 +1,7 @@
 //! Memory storage - byte-level API for long-term memory persistence.
 //!
 //! Provides low-level storage operations for memory chunks and sessions
-//! using the redb embedded database. Supports indexing by agent_id, session_id,
+//! on a [`StorageBackend`]. Supports indexing by agent_id, session_id,
 //! content hash (for deduplication), and tags.
 //!
 //! # Tables
@@ -12,33 +12,40 @@
 //! - `memory_session_index`: session_id:chunk_id -> chunk_id (for listing by session)
 //! - `memory_hash_index`: agent_id:content_hash -> chunk_id (for deduplication)
 //! - `memory_tag_index`: tag:chunk_id -> chunk_id (for tag filtering)
+//! - `memory_chunk_access`: chunk_id -> last access time (for LRU eviction)
 
-use anyhow::Result;
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use anyhow::{Result, anyhow};
+use redb::Database;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::range_utils::prefix_range;
+use crate::at_rest::{ValueCipher, open_value};
+use crate::backend::{
+    RedbBackend, StorageBackend, TableRead, TableWrite, WriteTransaction, text_value,
+};
 
-const MEMORY_CHUNK_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("memory_chunks");
-const MEMORY_SESSION_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("memory_sessions");
+const MEMORY_CHUNK_TABLE: &str = "memory_chunks";
+const MEMORY_SESSION_TABLE: &str = "memory_sessions";
 
 /// Index: agent_id:chunk_id -> chunk_id
-const AGENT_INDEX_TABLE: TableDefinition<&str, &str> = TableDefinition::new("memory_agent_index");
+const AGENT_INDEX_TABLE: &str = "memory_agent_index";
 /// Index: session_id:chunk_id -> chunk_id
-const SESSION_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("memory_session_index");
+const SESSION_INDEX_TABLE: &str = "memory_session_index";
 /// Index: agent_id:content_hash -> chunk_id (for deduplication)
-const HASH_INDEX_TABLE: TableDefinition<&str, &str> = TableDefinition::new("memory_hash_index");
+const HASH_INDEX_TABLE: &str = "memory_hash_index";
 /// Index: tag:chunk_id -> chunk_id (for tag filtering)
-const TAG_INDEX_TABLE: TableDefinition<&str, &str> = TableDefinition::new("memory_tag_index");
+const TAG_INDEX_TABLE: &str = "memory_tag_index";
 /// Index: agent_id:session_id -> session_id (for listing sessions by agent)
-const AGENT_SESSION_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("memory_agent_session_index");
+const AGENT_SESSION_INDEX_TABLE: &str = "memory_agent_session_index";
+/// chunk_id -> last access time in ms, little-endian (for LRU eviction)
+const ACCESS_TABLE: &str = "memory_chunk_access";
 
 /// Low-level memory storage with byte-level API
 #[derive(Clone)]
 pub struct MemoryStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
+    /// Seals chunk and session values when encryption at rest is enabled.
+    cipher: Option<ValueCipher>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -47,6 +54,18 @@ pub enum PutChunkResult {
     Existing(String),
 }
 
+/// Tag change for one chunk, applied by [`MemoryStorage::update_chunk_tags_batch`].
+#[derive(Debug, Clone)]
+pub struct ChunkTagUpdate {
+    pub chunk_id: String,
+    /// Tags currently indexed for the chunk.
+    pub old_tags: Vec<String>,
+    /// Tags to index after the update.
+    pub new_tags: Vec<String>,
+    /// Re-serialized chunk data carrying `new_tags`.
+    pub data: Vec<u8>,
+}
+
 impl MemoryStorage {
     fn scoped_hash_key(agent_id: &str, content_hash: &str) -> String {
         format!("{}:{}", agent_id, content_hash)
@@ -54,18 +73,85 @@ impl MemoryStorage {
 
     /// Create a new MemoryStorage instance
     pub fn new(db: Arc<Database>) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)), None)
+    }
+
+    /// Create a MemoryStorage that encrypts chunk and session values.
+    pub fn with_cipher(db: Arc<Database>, cipher: ValueCipher) -> Result<Self> {
+        Self::with_backend(Arc::new(RedbBackend::new(db)), Some(cipher))
+    }
+
+    /// Create a MemoryStorage on `backend`, sealing values with `cipher`.
+    pub fn with_backend(
+        backend: Arc<dyn StorageBackend>,
+        cipher: Option<ValueCipher>,
+    ) -> Result<Self> {
         // Initialize all tables
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(MEMORY_CHUNK_TABLE)?;
-        write_txn.open_table(MEMORY_SESSION_TABLE)?;
-        write_txn.open_table(AGENT_INDEX_TABLE)?;
-        write_txn.open_table(SESSION_INDEX_TABLE)?;
-        write_txn.open_table(HASH_INDEX_TABLE)?;
-        write_txn.open_table(TAG_INDEX_TABLE)?;
-        write_txn.open_table(AGENT_SESSION_INDEX_TABLE)?;
-        write_txn.commit()?;
+        for table in [
+            MEMORY_CHUNK_TABLE,
+            MEMORY_SESSION_TABLE,
+            AGENT_INDEX_TABLE,
+            SESSION_INDEX_TABLE,
+            HASH_INDEX_TABLE,
+            TAG_INDEX_TABLE,
+            AGENT_SESSION_INDEX_TABLE,
+            ACCESS_TABLE,
+        ] {
+            backend.ensure_table(table)?;
+        }
 
-        Ok(Self { db })
+        Ok(Self { backend, cipher })
+    }
+
+    fn seal(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => cipher.seal(data),
+            None => Ok(data.to_vec()),
+        }
+    }
+
+    fn open(&self, stored: &[u8]) -> Result<Vec<u8>> {
+        open_value(self.cipher.as_ref(), stored)
+    }
+
+    /// Look up the values an index scan points at in `table`, opened.
+    fn resolve_index(
+        &self,
+        index: &dyn TableRead,
+        table: &dyn TableRead,
+        prefix: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let mut values = Vec::new();
+        for (_, value) in index.scan_prefix(prefix)? {
+            let id = text_value(value)?;
+            if let Some(data) = table.get(&id)? {
+                let data = self.open(&data)?;
+                values.push((id, data));
+            }
+        }
+        Ok(values)
+    }
+
+    /// Seal chunk and session values written before encryption was enabled.
+    ///
+    /// Returns the number of values rewritten. Does nothing without a cipher.
+    pub fn seal_existing(&self) -> Result<usize> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(0);
+        };
+        self.backend.write_tables(|txn| {
+            let mut sealed = 0;
+            for name in [MEMORY_CHUNK_TABLE, MEMORY_SESSION_TABLE] {
+                let mut table = txn.table(name)?;
+                for (key, value) in table.scan_prefix("")? {
+                    if !ValueCipher::is_sealed(&value) {
+                        table.put(&key, &cipher.seal(&value)?)?;
+                        sealed += 1;
+                    }
+                }
+            }
+            Ok(sealed)
+        })
     }
 
     // ============== Memory Chunk Operations ==============
@@ -82,41 +168,17 @@ impl MemoryStorage {
         tags: &[String],
         data: &[u8],
     ) -> Result<PutChunkResult> {
-        let write_txn = self.db.begin_write()?;
-        let result = {
-            // Open hash_index once as mutable for both read and write
-            let mut hash_index = write_txn.open_table(HASH_INDEX_TABLE)?;
+        let sealed = self.seal(data)?;
+        self.backend.write_tables(|txn| {
             let hash_key = Self::scoped_hash_key(agent_id, content_hash);
-            if let Some(existing) = hash_index.get(hash_key.as_str())? {
-                PutChunkResult::Existing(existing.value().to_string())
-            } else {
-                let mut chunk_table = write_txn.open_table(MEMORY_CHUNK_TABLE)?;
-                chunk_table.insert(chunk_id, data)?;
-
-                let mut agent_index = write_txn.open_table(AGENT_INDEX_TABLE)?;
-                let agent_key = format!("{}:{}", agent_id, chunk_id);
-                agent_index.insert(agent_key.as_str(), chunk_id)?;
-
-                if let Some(sid) = session_id {
-                    let mut session_index = write_txn.open_table(SESSION_INDEX_TABLE)?;
-                    let session_key = format!("{}:{}", sid, chunk_id);
-                    session_index.insert(session_key.as_str(), chunk_id)?;
-                }
-
-                // Use the already-opened hash_index for insert
-                hash_index.insert(hash_key.as_str(), chunk_id)?;
-
-                let mut tag_index = write_txn.open_table(TAG_INDEX_TABLE)?;
-                for tag in tags {
-                    let tag_key = format!("{}:{}", tag, chunk_id);
-                    tag_index.insert(tag_key.as_str(), chunk_id)?;
-                }
-
-                PutChunkResult::Created(chunk_id.to_string())
+            if let Some(existing) = txn.table(HASH_INDEX_TABLE)?.get(&hash_key)? {
+                return Ok(PutChunkResult::Existing(text_value(existing)?));
             }
-        };
-        write_txn.commit()?;
-        Ok(result)
+            Self::put_chunk_indexed(
+                txn, chunk_id, agent_id, session_id, &hash_key, tags, &sealed,
+            )?;
+            Ok(PutChunkResult::Created(chunk_id.to_string()))
+        })
     }
 
     /// Store a raw memory chunk with all necessary indexes.
@@ -137,153 +199,177 @@ impl MemoryStorage {
         tags: &[String],
         data: &[u8],
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            // Store the chunk data
-            let mut chunk_table = write_txn.open_table(MEMORY_CHUNK_TABLE)?;
-            chunk_table.insert(chunk_id, data)?;
-
-            // Index by agent_id
-            let mut agent_index = write_txn.open_table(AGENT_INDEX_TABLE)?;
-            let agent_key = format!("{}:{}", agent_id, chunk_id);
-            agent_index.insert(agent_key.as_str(), chunk_id)?;
+        let sealed = self.seal(data)?;
+        let hash_key = Self::scoped_hash_key(agent_id, content_hash);
+        self.backend.write_tables(|txn| {
+            Self::put_chunk_indexed(
+                txn, chunk_id, agent_id, session_id, &hash_key, tags, &sealed,
+            )
+        })
+    }
 
-            // Index by session_id if provided
-            if let Some(sid) = session_id {
-                let mut session_index = write_txn.open_table(SESSION_INDEX_TABLE)?;
-                let session_key = format!("{}:{}", sid, chunk_id);
-                session_index.insert(session_key.as_str(), chunk_id)?;
-            }
+    fn put_chunk_indexed(
+        txn: &dyn WriteTransaction,
+        chunk_id: &str,
+        agent_id: &str,
+        session_id: Option<&str>,
+        hash_key: &str,
+        tags: &[String],
+        sealed: &[u8],
+    ) -> Result<()> {
+        // Store the chunk data
+        txn.table(MEMORY_CHUNK_TABLE)?.put(chunk_id, sealed)?;
+
+        // Index by agent_id
+        let agent_key = format!("{}:{}", agent_id, chunk_id);
+        txn.table(AGENT_INDEX_TABLE)?
+            .put(&agent_key, chunk_id.as_bytes())?;
+
+        // Index by session_id if provided
+        if let Some(sid) = session_id {
+            let session_key = format!("{}:{}", sid, chunk_id);
+            txn.table(SESSION_INDEX_TABLE)?
+                .put(&session_key, chunk_id.as_bytes())?;
+        }
 
-            // Index by content hash for deduplication checking
-            let mut hash_index = write_txn.open_table(HASH_INDEX_TABLE)?;
-            let hash_key = Self::scoped_hash_key(agent_id, content_hash);
-            hash_index.insert(hash_key.as_str(), chunk_id)?;
+        // Index by content hash for deduplication checking
+        txn.table(HASH_INDEX_TABLE)?
+            .put(hash_key, chunk_id.as_bytes())?;
 
-            // Index by tags
-            let mut tag_index = write_txn.open_table(TAG_INDEX_TABLE)?;
-            for tag in tags {
-                let tag_key = format!("{}:{}", tag, chunk_id);
-                tag_index.insert(tag_key.as_str(), chunk_id)?;
-            }
+        // Index by tags
+        let mut tag_index = txn.table(TAG_INDEX_TABLE)?;
+        for tag in tags {
+            let tag_key = format!("{}:{}", tag, chunk_id);
+            tag_index.put(&tag_key, chunk_id.as_bytes())?;
         }
-        write_txn.commit()?;
         Ok(())
     }
 
     /// Get raw chunk data by ID
     pub fn get_chunk_raw(&self, chunk_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(MEMORY_CHUNK_TABLE)?;
-
-        if let Some(value) = table.get(chunk_id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(MEMORY_CHUNK_TABLE, |table| table.get(chunk_id))?
+            .map(|value| self.open(&value))
+            .transpose()
     }
 
     /// List all chunks for an agent
     pub fn list_chunks_by_agent_raw(&self, agent_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let agent_index = read_txn.open_table(AGENT_INDEX_TABLE)?;
-        let chunk_table = read_txn.open_table(MEMORY_CHUNK_TABLE)?;
-
-        let prefix = format!("{}:", agent_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut chunks = Vec::new();
-
-        for item in agent_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let chunk_id = value.value();
-            if let Some(chunk_data) = chunk_table.get(chunk_id)? {
-                chunks.push((chunk_id.to_string(), chunk_data.value().to_vec()));
-            }
-        }
-
-        Ok(chunks)
+        self.list_chunks_by_index(AGENT_INDEX_TABLE, agent_id)
     }
 
     /// List all chunks across agents.
     pub fn list_all_chunks_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let chunk_table = read_txn.open_table(MEMORY_CHUNK_TABLE)?;
-        let mut chunks = Vec::new();
-
-        for item in chunk_table.iter()? {
-            let (key, value) = item?;
-            chunks.push((key.value().to_string(), value.value().to_vec()));
-        }
-
-        Ok(chunks)
+        self.backend
+            .read_table(MEMORY_CHUNK_TABLE, |table| table.scan_prefix(""))?
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.open(&value)?)))
+            .collect()
     }
 
     /// List all chunks for a session
     pub fn list_chunks_by_session_raw(&self, session_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let session_index = read_txn.open_table(SESSION_INDEX_TABLE)?;
-        let chunk_table = read_txn.open_table(MEMORY_CHUNK_TABLE)?;
-
-        let prefix = format!("{}:", session_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut chunks = Vec::new();
-
-        for item in session_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let chunk_id = value.value();
-            if let Some(chunk_data) = chunk_table.get(chunk_id)? {
-                chunks.push((chunk_id.to_string(), chunk_data.value().to_vec()));
-            }
-        }
+        self.list_chunks_by_index(SESSION_INDEX_TABLE, session_id)
+    }
 
-        Ok(chunks)
+    fn list_chunks_by_index(
+        &self,
+        index_table: &str,
+        owner: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        self.backend.read_tables(|txn| {
+            let index = txn.table(index_table)?;
+            let chunk_table = txn.table(MEMORY_CHUNK_TABLE)?;
+            self.resolve_index(index.as_ref(), chunk_table.as_ref(), &format!("{}:", owner))
+        })
     }
 
     /// Check if any chunks exist for a session without loading them all.
     pub fn has_chunks_for_session(&self, session_id: &str) -> Result<bool> {
-        let read_txn = self.db.begin_read()?;
-        let session_index = read_txn.open_table(SESSION_INDEX_TABLE)?;
-
         let prefix = format!("{}:", session_id);
-        let (start, end) = prefix_range(&prefix);
-
-        let mut iter = session_index.range(start.as_str()..end.as_str())?;
-        Ok(iter.next().is_some())
+        let keys = self
+            .backend
+            .read_table(SESSION_INDEX_TABLE, |index| index.keys_with_prefix(&prefix))?;
+        Ok(!keys.is_empty())
     }
 
     /// List all chunks with a specific tag
     pub fn list_chunks_by_tag_raw(&self, tag: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let tag_index = read_txn.open_table(TAG_INDEX_TABLE)?;
-        let chunk_table = read_txn.open_table(MEMORY_CHUNK_TABLE)?;
-
-        let prefix = format!("{}:", tag);
-        let (start, end) = prefix_range(&prefix);
-        let mut chunks = Vec::new();
-
-        for item in tag_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let chunk_id = value.value();
-            if let Some(chunk_data) = chunk_table.get(chunk_id)? {
-                chunks.push((chunk_id.to_string(), chunk_data.value().to_vec()));
+        self.list_chunks_by_index(TAG_INDEX_TABLE, tag)
+    }
+
+    /// Count indexed chunks per tag, sorted by tag.
+    pub fn list_tag_counts_raw(&self) -> Result<Vec<(String, usize)>> {
+        let entries = self
+            .backend
+            .read_table(TAG_INDEX_TABLE, |tag_index| tag_index.scan_prefix(""))?;
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+
+        for (key, value) in entries {
+            let suffix = format!(":{}", text_value(value)?);
+            if let Some(tag) = key.strip_suffix(suffix.as_str()) {
+                *counts.entry(tag.to_string()).or_default() += 1;
             }
         }
 
-        Ok(chunks)
+        Ok(counts.into_iter().collect())
+    }
+
+    /// Rewrite chunk data and tag index entries for many chunks in one
+    /// transaction, so a bulk retag either lands completely or not at all.
+    ///
+    /// Returns the number of chunks updated. Chunks that no longer exist are
+    /// skipped.
+    pub fn update_chunk_tags_batch(&self, updates: &[ChunkTagUpdate]) -> Result<usize> {
+        self.backend.write_tables(|txn| {
+            let mut chunk_table = txn.table(MEMORY_CHUNK_TABLE)?;
+            let mut tag_index = txn.table(TAG_INDEX_TABLE)?;
+            let mut updated = 0usize;
+
+            for update in updates {
+                if chunk_table.get(&update.chunk_id)?.is_none() {
+                    continue;
+                }
+                chunk_table.put(&update.chunk_id, &self.seal(&update.data)?)?;
+                for tag in &update.old_tags {
+                    let tag_key = format!("{}:{}", tag, update.chunk_id);
+                    tag_index.remove(&tag_key)?;
+                }
+                for tag in &update.new_tags {
+                    let tag_key = format!("{}:{}", tag, update.chunk_id);
+                    tag_index.put(&tag_key, update.chunk_id.as_bytes())?;
+                }
+                updated += 1;
+            }
+
+            Ok(updated)
+        })
     }
 
     /// Check if a chunk with the given content hash already exists.
     /// Returns the existing chunk ID if found.
     pub fn find_by_hash(&self, agent_id: &str, content_hash: &str) -> Result<Option<String>> {
-        let read_txn = self.db.begin_read()?;
-        let hash_index = read_txn.open_table(HASH_INDEX_TABLE)?;
         let hash_key = Self::scoped_hash_key(agent_id, content_hash);
+        self.backend
+            .read_table(HASH_INDEX_TABLE, |hash_index| hash_index.get(&hash_key))?
+            .map(text_value)
+            .transpose()
+    }
 
-        if let Some(value) = hash_index.get(hash_key.as_str())? {
-            Ok(Some(value.value().to_string()))
-        } else {
-            Ok(None)
+    /// Remove the legacy global hash key for `content_hash` if it points at
+    /// `chunk_id`.
+    fn remove_legacy_hash_key(
+        hash_index: &mut dyn TableWrite,
+        content_hash: &str,
+        chunk_id: &str,
+    ) -> Result<()> {
+        let remove_legacy_key = hash_index
+            .get(content_hash)?
+            .is_some_and(|existing| existing == chunk_id.as_bytes());
+        if remove_legacy_key {
+            hash_index.remove(content_hash)?;
         }
+        Ok(())
     }
 
     /// Delete a chunk and all its index entries.
@@ -297,64 +383,85 @@ impl MemoryStorage {
         content_hash: &str,
         tags: &[String],
     ) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
+        self.backend.write_tables(|txn| {
             // Delete the chunk
-            let mut chunk_table = write_txn.open_table(MEMORY_CHUNK_TABLE)?;
-            let existed = chunk_table.remove(chunk_id)?.is_some();
+            let existed = txn.table(MEMORY_CHUNK_TABLE)?.remove(chunk_id)?;
 
             // Remove from agent index
-            let mut agent_index = write_txn.open_table(AGENT_INDEX_TABLE)?;
             let agent_key = format!("{}:{}", agent_id, chunk_id);
-            agent_index.remove(agent_key.as_str())?;
+            txn.table(AGENT_INDEX_TABLE)?.remove(&agent_key)?;
 
             // Remove from session index if applicable
             if let Some(sid) = session_id {
-                let mut session_index = write_txn.open_table(SESSION_INDEX_TABLE)?;
                 let session_key = format!("{}:{}", sid, chunk_id);
-                session_index.remove(session_key.as_str())?;
+                txn.table(SESSION_INDEX_TABLE)?.remove(&session_key)?;
             }
 
             // Remove from hash index
-            let mut hash_index = write_txn.open_table(HASH_INDEX_TABLE)?;
-            let hash_key = Self::scoped_hash_key(agent_id, content_hash);
-            hash_index.remove(hash_key.as_str())?;
-            // Best-effort cleanup for legacy global hash keys.
-            let remove_legacy_key = hash_index
-                .get(content_hash)?
-                .map(|existing| existing.value() == chunk_id)
-                .unwrap_or(false);
-            if remove_legacy_key {
-                hash_index.remove(content_hash)?;
+            {
+                let mut hash_index = txn.table(HASH_INDEX_TABLE)?;
+                let hash_key = Self::scoped_hash_key(agent_id, content_hash);
+                hash_index.remove(&hash_key)?;
+                // Best-effort cleanup for legacy global hash keys.
+                Self::remove_legacy_hash_key(hash_index.as_mut(), content_hash, chunk_id)?;
             }
 
             // Remove from tag indexes
-            let mut tag_index = write_txn.open_table(TAG_INDEX_TABLE)?;
-            for tag in tags {
-                let tag_key = format!("{}:{}", tag, chunk_id);
-                tag_index.remove(tag_key.as_str())?;
+            {
+                let mut tag_index = txn.table(TAG_INDEX_TABLE)?;
+                for tag in tags {
+                    let tag_key = format!("{}:{}", tag, chunk_id);
+                    tag_index.remove(&tag_key)?;
+                }
             }
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+            txn.table(ACCESS_TABLE)?.remove(chunk_id)?;
+
+            Ok(existed)
+        })
     }
 
-    /// Count chunks for an agent
-    pub fn count_chunks_by_agent(&self, agent_id: &str) -> Result<u32> {
-        let read_txn = self.db.begin_read()?;
-        let agent_index = read_txn.open_table(AGENT_INDEX_TABLE)?;
+    /// Record that chunks were read, for least-recently-used eviction.
+    pub fn touch_chunks(&self, chunk_ids: &[String], accessed_at: i64) -> Result<()> {
+        if chunk_ids.is_empty() {
+            return Ok(());
+        }
+        let accessed_at = accessed_at.to_le_bytes();
+        self.backend.write_table(ACCESS_TABLE, |table| {
+            for chunk_id in chunk_ids {
+                table.put(chunk_id, &accessed_at)?;
+            }
+            Ok(())
+        })
+    }
 
-        let prefix = format!("{}:", agent_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut count = 0u32;
+    /// Last recorded access time of each chunk that has one.
+    pub fn last_accessed(&self, chunk_ids: &[String]) -> Result<HashMap<String, i64>> {
+        self.backend.read_table(ACCESS_TABLE, |table| {
+            let mut times = HashMap::new();
+            for chunk_id in chunk_ids {
+                if let Some(value) = table.get(chunk_id)? {
+                    let bytes: [u8; 8] = value.as_slice().try_into().map_err(|_| {
+                        anyhow!("Invalid access time stored for chunk {}", chunk_id)
+                    })?;
+                    times.insert(chunk_id.clone(), i64::from_le_bytes(bytes));
+                }
+            }
+            Ok(times)
+        })
+    }
 
-        for _ in agent_index.range(start.as_str()..end.as_str())? {
-            count += 1;
-        }
+    /// Count chunks for an agent
+    pub fn count_chunks_by_agent(&self, agent_id: &str) -> Result<u32> {
+        self.count_index_entries(AGENT_INDEX_TABLE, agent_id)
+    }
 
-        Ok(count)
+    fn count_index_entries(&self, index_table: &str, owner: &str) -> Result<u32> {
+        let prefix = format!("{}:", owner);
+        let keys = self
+            .backend
+            .read_table(index_table, |index| index.keys_with_prefix(&prefix))?;
+        Ok(keys.len() as u32)
     }
 
     /// List chunks with database-level pagination
@@ -365,32 +472,38 @@ impl MemoryStorage {
         limit: usize,
         cursor: Option<&str>,
     ) -> Result<(Vec<(String, Vec<u8>)>, Option<String>)> {
-        let read_txn = self.db.begin_read()?;
-        let agent_index = read_txn.open_table(AGENT_INDEX_TABLE)?;
-        let chunk_table = read_txn.open_table(MEMORY_CHUNK_TABLE)?;
-
         let prefix = format!("{}:", agent_id);
-        let (_, end) = prefix_range(&prefix);
         let start = match cursor {
             Some(last_id) => format!("{}:{}\x00", agent_id, last_id),
             None => prefix.clone(),
         };
 
-        let mut chunks = Vec::new();
-        let mut last_id = None;
-        for item in agent_index.range(start.as_str()..end.as_str())? {
-            if chunks.len() >= limit {
-                break;
-            }
-            let (_, value) = item?;
-            let chunk_id = value.value();
-            if let Some(chunk_data) = chunk_table.get(chunk_id)? {
-                last_id = Some(chunk_id.to_string());
-                chunks.push((chunk_id.to_string(), chunk_data.value().to_vec()));
+        let chunks = self.backend.read_tables(|txn| {
+            let agent_index = txn.table(AGENT_INDEX_TABLE)?;
+            let chunk_table = txn.table(MEMORY_CHUNK_TABLE)?;
+
+            let mut chunks = Vec::new();
+            for (key, value) in agent_index.scan_prefix(&prefix)? {
+                if chunks.len() >= limit {
+                    break;
+                }
+                if key < start {
+                    continue;
+                }
+                let chunk_id = text_value(value)?;
+                if let Some(chunk_data) = chunk_table.get(&chunk_id)? {
+                    let chunk_data = self.open(&chunk_data)?;
+                    chunks.push((chunk_id, chunk_data));
+                }
             }
-        }
+            Ok(chunks)
+        })?;
 
-        let next_cursor = if chunks.len() >= limit { last_id } else { None };
+        let next_cursor = if chunks.len() >= limit {
+            chunks.last().map(|(chunk_id, _)| chunk_id.clone())
+        } else {
+            None
+        };
         Ok((chunks, next_cursor))
     }
 
@@ -398,85 +511,54 @@ impl MemoryStorage {
 
     /// Store a raw memory session
     pub fn put_session_raw(&self, session_id: &str, agent_id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut session_table = write_txn.open_table(MEMORY_SESSION_TABLE)?;
-            session_table.insert(session_id, data)?;
+        let sealed = self.seal(data)?;
+        self.backend.write_tables(|txn| {
+            txn.table(MEMORY_SESSION_TABLE)?.put(session_id, &sealed)?;
 
             // Index by agent_id
-            let mut agent_session_index = write_txn.open_table(AGENT_SESSION_INDEX_TABLE)?;
             let index_key = format!("{}:{}", agent_id, session_id);
-            agent_session_index.insert(index_key.as_str(), session_id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            txn.table(AGENT_SESSION_INDEX_TABLE)?
+                .put(&index_key, session_id.as_bytes())
+        })
     }
 
     /// Get raw session data by ID
     pub fn get_session_raw(&self, session_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(MEMORY_SESSION_TABLE)?;
-
-        if let Some(value) = table.get(session_id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(MEMORY_SESSION_TABLE, |table| table.get(session_id))?
+            .map(|value| self.open(&value))
+            .transpose()
     }
 
     /// List all sessions for an agent
     pub fn list_sessions_by_agent_raw(&self, agent_id: &str) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let agent_session_index = read_txn.open_table(AGENT_SESSION_INDEX_TABLE)?;
-        let session_table = read_txn.open_table(MEMORY_SESSION_TABLE)?;
-
-        let prefix = format!("{}:", agent_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut sessions = Vec::new();
-
-        for item in agent_session_index.range(start.as_str()..end.as_str())? {
-            let (_, value) = item?;
-            let session_id = value.value();
-            if let Some(session_data) = session_table.get(session_id)? {
-                sessions.push((session_id.to_string(), session_data.value().to_vec()));
-            }
-        }
-
-        Ok(sessions)
+        self.backend.read_tables(|txn| {
+            let agent_session_index = txn.table(AGENT_SESSION_INDEX_TABLE)?;
+            let session_table = txn.table(MEMORY_SESSION_TABLE)?;
+            self.resolve_index(
+                agent_session_index.as_ref(),
+                session_table.as_ref(),
+                &format!("{}:", agent_id),
+            )
+        })
     }
 
     /// Delete a session (does not delete associated chunks)
     pub fn delete_session(&self, session_id: &str, agent_id: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let existed = {
-            let mut session_table = write_txn.open_table(MEMORY_SESSION_TABLE)?;
-            let existed = session_table.remove(session_id)?.is_some();
+        self.backend.write_tables(|txn| {
+            let existed = txn.table(MEMORY_SESSION_TABLE)?.remove(session_id)?;
 
             // Remove from agent session index
-            let mut agent_session_index = write_txn.open_table(AGENT_SESSION_INDEX_TABLE)?;
             let index_key = format!("{}:{}", agent_id, session_id);
-            agent_session_index.remove(index_key.as_str())?;
+            txn.table(AGENT_SESSION_INDEX_TABLE)?.remove(&index_key)?;
 
-            existed
-        };
-        write_txn.commit()?;
-        Ok(existed)
+            Ok(existed)
+        })
     }
 
     /// Count sessions for an agent
     pub fn count_sessions_by_agent(&self, agent_id: &str) -> Result<u32> {
-        let read_txn = self.db.begin_read()?;
-        let agent_session_index = read_txn.open_table(AGENT_SESSION_INDEX_TABLE)?;
-
-        let prefix = format!("{}:", agent_id);
-        let (start, end) = prefix_range(&prefix);
-        let mut count = 0u32;
-
-        for _ in agent_session_index.range(start.as_str()..end.as_str())? {
-            count += 1;
-        }
-
-        Ok(count)
+        self.count_index_entries(AGENT_SESSION_INDEX_TABLE, agent_id)
     }
 
     // ============== Bulk Operations ==============
@@ -491,163 +573,123 @@ impl MemoryStorage {
             return Ok(0);
         }
 
-        let write_txn = self.db.begin_write()?;
-        let count = {
-            let mut chunk_table = write_txn.open_table(MEMORY_CHUNK_TABLE)?;
-            let mut agent_index = write_txn.open_table(AGENT_INDEX_TABLE)?;
-            let mut session_index = write_txn.open_table(SESSION_INDEX_TABLE)?;
-            let mut hash_index = write_txn.open_table(HASH_INDEX_TABLE)?;
-            let mut tag_index = write_txn.open_table(TAG_INDEX_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut chunk_table = txn.table(MEMORY_CHUNK_TABLE)?;
+            let mut agent_index = txn.table(AGENT_INDEX_TABLE)?;
+            let mut session_index = txn.table(SESSION_INDEX_TABLE)?;
+            let mut hash_index = txn.table(HASH_INDEX_TABLE)?;
+            let mut tag_index = txn.table(TAG_INDEX_TABLE)?;
+            let mut access_table = txn.table(ACCESS_TABLE)?;
             let mut deleted = 0u32;
 
             for (chunk_id, session_id, content_hash, tags) in chunk_metadata {
-                chunk_table.remove(chunk_id.as_str())?;
+                chunk_table.remove(chunk_id)?;
 
                 let agent_key = format!("{}:{}", agent_id, chunk_id);
-                agent_index.remove(agent_key.as_str())?;
+                agent_index.remove(&agent_key)?;
 
                 if let Some(sid) = session_id {
                     let session_key = format!("{}:{}", sid, chunk_id);
-                    session_index.remove(session_key.as_str())?;
+                    session_index.remove(&session_key)?;
                 }
 
-                let hash_key = Self::scoped_hash_key(agent_id, content_hash.as_str());
-                hash_index.remove(hash_key.as_str())?;
-                let remove_legacy_key = hash_index
-                    .get(content_hash.as_str())?
-                    .map(|existing| existing.value() == chunk_id.as_str())
-                    .unwrap_or(false);
-                if remove_legacy_key {
-                    hash_index.remove(content_hash.as_str())?;
-                }
+                let hash_key = Self::scoped_hash_key(agent_id, content_hash);
+                hash_index.remove(&hash_key)?;
+                Self::remove_legacy_hash_key(hash_index.as_mut(), content_hash, chunk_id)?;
 
                 for tag in tags {
                     let tag_key = format!("{}:{}", tag, chunk_id);
-                    tag_index.remove(tag_key.as_str())?;
+                    tag_index.remove(&tag_key)?;
                 }
 
+                access_table.remove(chunk_id)?;
+
                 deleted += 1;
             }
 
-            deleted
-        };
-        write_txn.commit()?;
-        Ok(count)
+            Ok(deleted)
+        })
     }
 
     /// Delete all chunks for an agent with full index cleanup.
     pub fn delete_all_chunks_for_agent(&self, agent_id: &str) -> Result<u32> {
-        let write_txn = self.db.begin_write()?;
-        let count = {
-            use std::collections::HashSet;
-
-            let mut chunk_table = write_txn.open_table(MEMORY_CHUNK_TABLE)?;
-            let mut agent_index = write_txn.open_table(AGENT_INDEX_TABLE)?;
-            let mut session_index = write_txn.open_table(SESSION_INDEX_TABLE)?;
-            let mut hash_index = write_txn.open_table(HASH_INDEX_TABLE)?;
-            let mut tag_index = write_txn.open_table(TAG_INDEX_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut chunk_table = txn.table(MEMORY_CHUNK_TABLE)?;
+            let mut agent_index = txn.table(AGENT_INDEX_TABLE)?;
+            let mut session_index = txn.table(SESSION_INDEX_TABLE)?;
+            let mut hash_index = txn.table(HASH_INDEX_TABLE)?;
+            let mut tag_index = txn.table(TAG_INDEX_TABLE)?;
+            let mut access_table = txn.table(ACCESS_TABLE)?;
 
             let prefix = format!("{}:", agent_id);
-            let (start, end) = prefix_range(&prefix);
 
             let mut chunk_ids: HashSet<String> = HashSet::new();
             let mut agent_keys: Vec<String> = Vec::new();
-            for item in agent_index.range(start.as_str()..end.as_str())? {
-                let (key, value) = item?;
-                chunk_ids.insert(value.value().to_string());
-                agent_keys.push(key.value().to_string());
+            for (key, value) in agent_index.scan_prefix(&prefix)? {
+                chunk_ids.insert(text_value(value)?);
+                agent_keys.push(key);
             }
 
             if chunk_ids.is_empty() {
-                0
-            } else {
-                let mut hash_keys: Vec<String> = Vec::new();
-                for item in hash_index.iter()? {
-                    let (key, value) = item?;
-                    if chunk_ids.contains(value.value()) {
-                        hash_keys.push(key.value().to_string());
-                    }
-                }
-
-                let mut session_keys: Vec<String> = Vec::new();
-                for item in session_index.iter()? {
-                    let (key, value) = item?;
-                    if chunk_ids.contains(value.value()) {
-                        session_keys.push(key.value().to_string());
-                    }
-                }
-
-                let mut tag_keys: Vec<String> = Vec::new();
-                for item in tag_index.iter()? {
-                    let (key, value) = item?;
-                    if chunk_ids.contains(value.value()) {
-                        tag_keys.push(key.value().to_string());
-                    }
-                }
-
-                for chunk_id in &chunk_ids {
-                    chunk_table.remove(chunk_id.as_str())?;
-                }
+                return Ok(0);
+            }
 
-                for key in &agent_keys {
-                    agent_index.remove(key.as_str())?;
-                }
+            // Keys of index entries pointing at one of the agent's chunks.
+            let keys_pointing_at_chunks = |index: &dyn TableRead| -> Result<Vec<String>> {
+                Ok(index
+                    .scan_prefix("")?
+                    .into_iter()
+                    .filter(|(_, value)| {
+                        std::str::from_utf8(value).is_ok_and(|id| chunk_ids.contains(id))
+                    })
+                    .map(|(key, _)| key)
+                    .collect())
+            };
+            let hash_keys = keys_pointing_at_chunks(hash_index.as_ref())?;
+            let session_keys = keys_pointing_at_chunks(session_index.as_ref())?;
+            let tag_keys = keys_pointing_at_chunks(tag_index.as_ref())?;
+
+            for chunk_id in &chunk_ids {
+                chunk_table.remove(chunk_id)?;
+                access_table.remove(chunk_id)?;
+            }
 
-                for key in &hash_keys {
-                    hash_index.remove(key.as_str())?;
-                }
+            for key in &agent_keys {
+                agent_index.remove(key)?;
+            }
 
-                for key in &session_keys {
-                    session_index.remove(key.as_str())?;
-                }
+            for key in &hash_keys {
+                hash_index.remove(key)?;
+            }
 
-                for key in &tag_keys {
-                    tag_index.remove(key.as_str())?;
-                }
+            for key in &session_keys {
+                session_index.remove(key)?;
+            }
 
-                chunk_ids.len() as u32
+            for key in &tag_keys {
+                tag_index.remove(key)?;
             }
-        };
-        write_txn.commit()?;
 
-        Ok(count)
+            Ok(chunk_ids.len() as u32)
+        })
     }
 
     /// Delete all sessions for an agent
     pub fn delete_all_sessions_for_agent(&self, agent_id: &str) -> Result<u32> {
-        let write_txn = self.db.begin_write()?;
-        let count = {
-            let mut session_table = write_txn.open_table(MEMORY_SESSION_TABLE)?;
-            let mut agent_session_index = write_txn.open_table(AGENT_SESSION_INDEX_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut session_table = txn.table(MEMORY_SESSION_TABLE)?;
+            let mut agent_session_index = txn.table(AGENT_SESSION_INDEX_TABLE)?;
 
             let prefix = format!("{}:", agent_id);
-            let (start, end) = prefix_range(&prefix);
-            let mut session_ids: Vec<String> = Vec::new();
-            let mut index_keys: Vec<String> = Vec::new();
-
-            for item in agent_session_index.range(start.as_str()..end.as_str())? {
-                let (key, value) = item?;
-                session_ids.push(value.value().to_string());
-                index_keys.push(key.value().to_string());
-            }
-
-            if session_ids.is_empty() {
-                0
-            } else {
-                for session_id in &session_ids {
-                    session_table.remove(session_id.as_str())?;
-                }
+            let entries = agent_session_index.scan_prefix(&prefix)?;
 
-                for key in &index_keys {
-                    agent_session_index.remove(key.as_str())?;
-                }
-
-                session_ids.len() as u32
+            for (key, session_id) in &entries {
+                session_table.remove(&text_value(session_id.clone())?)?;
+                agent_session_index.remove(key)?;
             }
-        };
-        write_txn.commit()?;
 
-        Ok(count)
+            Ok(entries.len() as u32)
+        })
     }
 }
 
@@ -695,6 +737,49 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_cipher_seals_values_and_migrates_plaintext() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let plain = MemoryStorage::new(db.clone()).unwrap();
+        plain
+            .put_chunk_raw("legacy", "agent-001", None, "hash0", &[], b"old secret")
+            .unwrap();
+        plain
+            .put_session_raw("session-001", "agent-001", b"session data")
+            .unwrap();
+
+        let cipher = ValueCipher::new(&[3u8; 32]).unwrap();
+        let sealed = MemoryStorage::with_cipher(db, cipher).unwrap();
+        assert_eq!(sealed.seal_existing().unwrap(), 2);
+        sealed
+            .put_chunk_raw("chunk-001", "agent-001", None, "hash1", &[], b"new secret")
+            .unwrap();
+
+        for id in ["legacy", "chunk-001"] {
+            let stored = plain
+                .backend
+                .read_table(MEMORY_CHUNK_TABLE, |chunks| chunks.get(id))
+                .unwrap()
+                .unwrap();
+            assert!(ValueCipher::is_sealed(&stored));
+        }
+        assert!(plain.get_chunk_raw("legacy").is_err());
+
+        assert_eq!(
+            sealed.get_chunk_raw("legacy").unwrap(),
+            Some(b"old secret".to_vec())
+        );
+        assert_eq!(
+            sealed.list_chunks_by_agent_raw("agent-001").unwrap().len(),
+            2
+        );
+        assert_eq!(
+            sealed.get_session_raw("session-001").unwrap(),
+            Some(b"session data".to_vec())
+        );
+    }
+
     #[test]
     fn test_list_chunks_by_agent() {
         let storage = create_test_storage();
@@ -868,6 +953,68 @@ mod tests {
         assert_eq!(python_chunks.len(), 1);
     }
 
+    #[test]
+    fn test_update_chunk_tags_batch_reindexes_tags() {
+        let storage = create_test_storage();
+
+        storage
+            .put_chunk_raw(
+                "chunk-001",
+                "agent-001",
+                None,
+                "hash1",
+                &["rust".to_string(), "todo".to_string()],
+                b"data1",
+            )
+            .unwrap();
+        storage
+            .put_chunk_raw(
+                "chunk-002",
+                "agent-001",
+                None,
+                "hash2",
+                &["todo".to_string()],
+                b"data2",
+            )
+            .unwrap();
+
+        let updated = storage
+            .update_chunk_tags_batch(&[
+                ChunkTagUpdate {
+                    chunk_id: "chunk-001".to_string(),
+                    old_tags: vec!["rust".to_string(), "todo".to_string()],
+                    new_tags: vec!["rust".to_string(), "task".to_string()],
+                    data: b"data1-v2".to_vec(),
+                },
+                ChunkTagUpdate {
+                    chunk_id: "chunk-002".to_string(),
+                    old_tags: vec!["todo".to_string()],
+                    new_tags: vec!["task".to_string()],
+                    data: b"data2-v2".to_vec(),
+                },
+                ChunkTagUpdate {
+                    chunk_id: "missing".to_string(),
+                    old_tags: Vec::new(),
+                    new_tags: vec!["task".to_string()],
+                    data: b"ghost".to_vec(),
+                },
+            ])
+            .unwrap();
+        assert_eq!(updated, 2);
+
+        assert!(storage.list_chunks_by_tag_raw("todo").unwrap().is_empty());
+        assert_eq!(storage.list_chunks_by_tag_raw("task").unwrap().len(), 2);
+        assert_eq!(
+            storage.get_chunk_raw("chunk-001").unwrap().unwrap(),
+            b"data1-v2"
+        );
+        assert!(storage.get_chunk_raw("missing").unwrap().is_none());
+        assert_eq!(
+            storage.list_tag_counts_raw().unwrap(),
+            vec![("rust".to_string(), 1), ("task".to_string(), 2)]
+        );
+    }
+
     #[test]
     fn test_find_by_hash_deduplication() {
         let storage = create_test_storage();
@@ -934,6 +1081,27 @@ mod tests {
         assert!(tag_chunks.is_empty());
     }
 
+    #[test]
+    fn test_touch_chunks_records_last_access() {
+        let storage = create_test_storage();
+        storage
+            .put_chunk_raw("chunk-001", "agent-001", None, "hash1", &[], b"data1")
+            .unwrap();
+        let ids = vec!["chunk-001".to_string(), "chunk-002".to_string()];
+        assert!(storage.last_accessed(&ids).unwrap().is_empty());
+
+        storage.touch_chunks(&ids[..1], 100).unwrap();
+        storage.touch_chunks(&ids[..1], 250).unwrap();
+        let times = storage.last_accessed(&ids).unwrap();
+        assert_eq!(times.len(), 1);
+        assert_eq!(times["chunk-001"], 250);
+
+        storage
+            .delete_chunk("chunk-001", "agent-001", None, "hash1", &[])
+            .unwrap();
+        assert!(storage.last_accessed(&ids).unwrap().is_empty());
+    }
+
     #[test]
     fn test_count_chunks_by_agent() {
         let storage = create_test_storage();
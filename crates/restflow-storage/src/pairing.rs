@@ -2,301 +2,227 @@
 //! and multi-dimension agent routing persistence.
 
 use anyhow::Result;
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use redb::Database;
 use std::sync::Arc;
 
+use crate::backend::{RedbBackend, StorageBackend, text_value};
+
 /// Allowed peers table: peer_id -> JSON AllowedPeer
-const ALLOWED_PEERS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("allowed_peers");
+const ALLOWED_PEERS_TABLE: &str = "allowed_peers";
 /// Pairing requests table: code -> JSON PairingRequest
-const PAIRING_REQUESTS_TABLE: TableDefinition<&str, &[u8]> =
-    TableDefinition::new("pairing_requests");
+const PAIRING_REQUESTS_TABLE: &str = "pairing_requests";
 /// Index: peer_id -> code (for lookup by peer)
-const PAIRING_PEER_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("pairing_peer_index");
+const PAIRING_PEER_INDEX_TABLE: &str = "pairing_peer_index";
 /// Route bindings table: id -> JSON RouteBinding
-const ROUTE_BINDINGS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("route_bindings");
+const ROUTE_BINDINGS_TABLE: &str = "route_bindings";
 /// Index: "{type}:{target_id}" -> id
-const ROUTE_BINDING_TARGET_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("route_binding_target_index");
+const ROUTE_BINDING_TARGET_INDEX_TABLE: &str = "route_binding_target_index";
 
 /// Low-level pairing and route binding storage
 #[derive(Clone)]
 pub struct PairingStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
 }
 
 impl PairingStorage {
     /// Create a new PairingStorage, initializing all tables.
     pub fn new(db: Arc<Database>) -> Result<Self> {
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(ALLOWED_PEERS_TABLE)?;
-        write_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-        write_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
-        write_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-        write_txn.open_table(ROUTE_BINDING_TARGET_INDEX_TABLE)?;
-        write_txn.commit()?;
-        Ok(Self { db })
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
+
+    /// Create the storage on `backend`, initializing all tables.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        for table in [
+            ALLOWED_PEERS_TABLE,
+            PAIRING_REQUESTS_TABLE,
+            PAIRING_PEER_INDEX_TABLE,
+            ROUTE_BINDINGS_TABLE,
+            ROUTE_BINDING_TARGET_INDEX_TABLE,
+        ] {
+            backend.ensure_table(table)?;
+        }
+        Ok(Self { backend })
     }
 
     // ============== Allowed Peer Operations ==============
 
     /// Add an allowed peer
     pub fn add_peer(&self, peer_id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(ALLOWED_PEERS_TABLE)?;
-            table.insert(peer_id, data)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.backend
+            .write_table(ALLOWED_PEERS_TABLE, |table| table.put(peer_id, data))
     }
 
     /// Remove an allowed peer
     pub fn remove_peer(&self, peer_id: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-        let removed = {
-            let mut table = write_txn.open_table(ALLOWED_PEERS_TABLE)?;
-            table.remove(peer_id)?.is_some()
-        };
-        write_txn.commit()?;
-        Ok(removed)
+        self.backend
+            .write_table(ALLOWED_PEERS_TABLE, |table| table.remove(peer_id))
     }
 
     /// Check if a peer is allowed
     pub fn is_peer_allowed(&self, peer_id: &str) -> Result<bool> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(ALLOWED_PEERS_TABLE)?;
-        Ok(table.get(peer_id)?.is_some())
+        Ok(self.get_peer(peer_id)?.is_some())
     }
 
     /// Get a peer's data
     pub fn get_peer(&self, peer_id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(ALLOWED_PEERS_TABLE)?;
-        Ok(table.get(peer_id)?.map(|v| v.value().to_vec()))
+        self.backend
+            .read_table(ALLOWED_PEERS_TABLE, |table| table.get(peer_id))
     }
 
     /// List all allowed peers
     pub fn list_peers(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(ALLOWED_PEERS_TABLE)?;
-        let mut result = Vec::new();
-        for entry in table.iter()? {
-            let (key, value) = entry?;
-            result.push((key.value().to_string(), value.value().to_vec()));
-        }
-        Ok(result)
+        self.backend
+            .read_table(ALLOWED_PEERS_TABLE, |table| table.scan_prefix(""))
     }
 
     // ============== Pairing Request Operations ==============
 
     /// Create a pairing request
     pub fn create_pairing_request(&self, code: &str, peer_id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-            let mut index = write_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
+        self.backend.write_tables(|txn| {
+            let mut table = txn.table(PAIRING_REQUESTS_TABLE)?;
+            let mut index = txn.table(PAIRING_PEER_INDEX_TABLE)?;
             if let Some(existing_code) = index.get(peer_id)? {
-                let existing_code = existing_code.value().to_string();
+                let existing_code = text_value(existing_code)?;
                 if existing_code != code {
-                    let _ = table.remove(existing_code.as_str())?;
+                    table.remove(&existing_code)?;
                 }
             }
-            table.insert(code, data)?;
-            index.insert(peer_id, code)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            table.put(code, data)?;
+            index.put(peer_id, code.as_bytes())
+        })
     }
 
     /// Get a pairing request by code
     pub fn get_pairing_request(&self, code: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-        Ok(table.get(code)?.map(|v| v.value().to_vec()))
+        self.backend
+            .read_table(PAIRING_REQUESTS_TABLE, |table| table.get(code))
     }
 
     /// Get a pairing request code by peer_id
     pub fn get_pairing_request_by_peer(&self, peer_id: &str) -> Result<Option<String>> {
-        let read_txn = self.db.begin_read()?;
-        let index = read_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
-        Ok(index.get(peer_id)?.map(|v| v.value().to_string()))
+        self.backend
+            .read_table(PAIRING_PEER_INDEX_TABLE, |index| index.get(peer_id))?
+            .map(text_value)
+            .transpose()
     }
 
     /// Delete a pairing request by code
     pub fn delete_pairing_request(&self, code: &str) -> Result<()> {
-        // First read peer_id from request for index cleanup
-        let peer_id = {
-            let read_txn = self.db.begin_read()?;
+        self.backend.write_tables(|txn| {
+            let mut index = txn.table(PAIRING_PEER_INDEX_TABLE)?;
             // Iterate the index to find the peer_id for this code
-            let index = read_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
-            let mut found_peer = None;
-            for entry in index.iter()? {
-                let (key, value) = entry?;
-                if value.value() == code {
-                    found_peer = Some(key.value().to_string());
-                    break;
-                }
-            }
-            found_peer
-        };
+            let peer_id = index
+                .scan_prefix("")?
+                .into_iter()
+                .find(|(_, value)| value == code.as_bytes())
+                .map(|(key, _)| key);
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-            table.remove(code)?;
+            txn.table(PAIRING_REQUESTS_TABLE)?.remove(code)?;
             if let Some(peer_id) = &peer_id {
-                let mut index = write_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
-                index.remove(peer_id.as_str())?;
+                index.remove(peer_id)?;
             }
-        }
-        write_txn.commit()?;
-        Ok(())
+            Ok(())
+        })
     }
 
     /// List all pairing requests
     pub fn list_pairing_requests(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-        let mut result = Vec::new();
-        for entry in table.iter()? {
-            let (key, value) = entry?;
-            result.push((key.value().to_string(), value.value().to_vec()));
-        }
-        Ok(result)
+        self.backend
+            .read_table(PAIRING_REQUESTS_TABLE, |table| table.scan_prefix(""))
     }
 
     /// Cleanup expired pairing requests (older than given timestamp in ms)
     pub fn cleanup_expired_requests(&self, now_ms: i64) -> Result<u32> {
-        // Collect expired codes first
-        let expired: Vec<(String, Option<String>)> = {
-            let read_txn = self.db.begin_read()?;
-            let table = read_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-            let index = read_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
-            let mut expired = Vec::new();
-
-            for entry in table.iter()? {
-                let (key, value) = entry?;
-                let data: serde_json::Value = serde_json::from_slice(value.value())?;
+        self.backend.write_tables(|txn| {
+            let mut table = txn.table(PAIRING_REQUESTS_TABLE)?;
+            let mut index = txn.table(PAIRING_PEER_INDEX_TABLE)?;
+            let peers = index.scan_prefix("")?;
+
+            let mut count = 0;
+            for (code, value) in table.scan_prefix("")? {
+                let data: serde_json::Value = serde_json::from_slice(&value)?;
                 if let Some(expires_at) = data.get("expires_at").and_then(|v| v.as_i64())
                     && expires_at <= now_ms
                 {
-                    let code = key.value().to_string();
+                    table.remove(&code)?;
                     // Find peer_id from index
-                    let mut found_peer = None;
-                    for idx_entry in index.iter()? {
-                        let (idx_key, idx_value) = idx_entry?;
-                        if idx_value.value() == code.as_str() {
-                            found_peer = Some(idx_key.value().to_string());
-                            break;
-                        }
-                    }
-                    expired.push((code, found_peer));
-                }
-            }
-            expired
-        };
-
-        let count = expired.len() as u32;
-        if count > 0 {
-            let write_txn = self.db.begin_write()?;
-            {
-                let mut table = write_txn.open_table(PAIRING_REQUESTS_TABLE)?;
-                let mut index = write_txn.open_table(PAIRING_PEER_INDEX_TABLE)?;
-                for (code, peer_id) in &expired {
-                    table.remove(code.as_str())?;
-                    if let Some(pid) = peer_id {
-                        index.remove(pid.as_str())?;
+                    if let Some((peer_id, _)) =
+                        peers.iter().find(|(_, value)| value == code.as_bytes())
+                    {
+                        index.remove(peer_id)?;
                     }
+                    count += 1;
                 }
             }
-            write_txn.commit()?;
-        }
-        Ok(count)
+            Ok(count)
+        })
     }
 
     // ============== Route Binding Operations ==============
 
     /// Add a route binding
     pub fn add_route_binding(&self, id: &str, index_key: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-            table.insert(id, data)?;
-            let mut index = write_txn.open_table(ROUTE_BINDING_TARGET_INDEX_TABLE)?;
-            index.insert(index_key, id)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.backend.write_tables(|txn| {
+            txn.table(ROUTE_BINDINGS_TABLE)?.put(id, data)?;
+            txn.table(ROUTE_BINDING_TARGET_INDEX_TABLE)?
+                .put(index_key, id.as_bytes())
+        })
     }
 
     /// Remove a route binding
     pub fn remove_route_binding(&self, id: &str) -> Result<bool> {
-        // First get the index key from the binding data
-        let index_key = {
-            let read_txn = self.db.begin_read()?;
-            let table = read_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-            if let Some(data) = table.get(id)? {
-                let binding: serde_json::Value = serde_json::from_slice(data.value())?;
-                let binding_type = binding
-                    .get("binding_type")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("default");
-                let target_id = binding
-                    .get("target_id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("*");
-                Some(format!("{}:{}", binding_type, target_id))
-            } else {
-                None
-            }
-        };
+        self.backend.write_tables(|txn| {
+            let mut table = txn.table(ROUTE_BINDINGS_TABLE)?;
+            // First get the index key from the binding data
+            let index_key = match table.get(id)? {
+                Some(data) => {
+                    let binding: serde_json::Value = serde_json::from_slice(&data)?;
+                    let binding_type = binding
+                        .get("binding_type")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("default");
+                    let target_id = binding
+                        .get("target_id")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("*");
+                    Some(format!("{}:{}", binding_type, target_id))
+                }
+                None => None,
+            };
 
-        let write_txn = self.db.begin_write()?;
-        let removed = {
-            let mut table = write_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-            let was_present = table.remove(id)?.is_some();
+            let was_present = table.remove(id)?;
             if let Some(index_key) = &index_key {
-                let mut index = write_txn.open_table(ROUTE_BINDING_TARGET_INDEX_TABLE)?;
-                index.remove(index_key.as_str())?;
+                txn.table(ROUTE_BINDING_TARGET_INDEX_TABLE)?
+                    .remove(index_key)?;
             }
-            was_present
-        };
-        write_txn.commit()?;
-        Ok(removed)
+            Ok(was_present)
+        })
     }
 
     /// Get a route binding by id
     pub fn get_route_binding(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-        Ok(table.get(id)?.map(|v| v.value().to_vec()))
+        self.backend
+            .read_table(ROUTE_BINDINGS_TABLE, |table| table.get(id))
     }
 
     /// Resolve route binding by target index key (e.g., "peer:12345")
     pub fn resolve_route_by_key(&self, index_key: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let index = read_txn.open_table(ROUTE_BINDING_TARGET_INDEX_TABLE)?;
-        if let Some(id_guard) = index.get(index_key)? {
-            let id = id_guard.value().to_string();
-            drop(id_guard);
-            let table = read_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-            Ok(table.get(id.as_str())?.map(|v| v.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend.read_tables(|txn| {
+            match txn
+                .table(ROUTE_BINDING_TARGET_INDEX_TABLE)?
+                .get(index_key)?
+            {
+                Some(id) => txn.table(ROUTE_BINDINGS_TABLE)?.get(&text_value(id)?),
+                None => Ok(None),
+            }
+        })
     }
 
     /// List all route bindings
     pub fn list_route_bindings(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(ROUTE_BINDINGS_TABLE)?;
-        let mut result = Vec::new();
-        for entry in table.iter()? {
-            let (key, value) = entry?;
-            result.push((key.value().to_string(), value.value().to_vec()));
-        }
-        Ok(result)
+        self.backend
+            .read_table(ROUTE_BINDINGS_TABLE, |table| table.scan_prefix(""))
     }
 }
 
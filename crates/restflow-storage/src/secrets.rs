@@ -1,27 +1,61 @@
 //! Secrets storage - encrypted storage for API keys and credentials.
 
+use crate::backend::{RedbBackend, StorageBackend};
 use crate::encryption::SecretEncryptor;
+use crate::keychain::{self, SecretValueStore, keychain_account};
 use crate::paths;
 use anyhow::{Context, Result};
 use base64::{Engine as _, engine::general_purpose::STANDARD};
+use parking_lot::Mutex;
 use rand::Rng;
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use redb::Database;
 use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
 use std::env;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{info, warn};
 use ts_rs::TS;
 
-const SECRETS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("secrets");
+const SECRETS_TABLE: &str = "secrets";
+const SECRET_USAGE_TABLE: &str = "secret_usage";
+/// Most recently used tool/agent pairs kept in one secret's usage history.
+const MAX_SECRET_ACCESSES: usize = 100;
+/// How long secret reads are counted in memory before they are written.
+const USAGE_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
 const MASTER_KEY_ENV: &str = "RESTFLOW_MASTER_KEY";
 const MASTER_KEY_FILE: &str = "master.key";
 
 #[derive(Debug, Clone, Default)]
 pub struct SecretStorageConfig {
     pub allow_insecure_file_permissions: bool,
+    /// Where secret values are kept. Secrets stored under the other mode are
+    /// moved when the storage is opened.
+    pub mode: SecretStorageMode,
+}
+
+/// Where secret values are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretStorageMode {
+    /// Encrypted in the database
+    #[default]
+    Database,
+    /// In the OS keychain; the database holds metadata and a reference
+    Keychain,
+}
+
+impl SecretStorageMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::Keychain => "keychain",
+        }
+    }
 }
 
 /// A stored secret with metadata
@@ -35,6 +69,29 @@ pub struct Secret {
     pub created_at: i64,
     #[ts(type = "number")]
     pub updated_at: i64,
+    /// When the value last changed after creation
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub rotated_at: Option<i64>,
+    /// When the value should be considered expired
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub expires_at: Option<i64>,
+}
+
+/// Aggregated reads of one secret by one tool (and agent)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SecretAccess {
+    pub tool: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[ts(type = "number")]
+    pub count: u64,
+    #[ts(type = "number")]
+    pub first_used_at: i64,
+    #[ts(type = "number")]
+    pub last_used_at: i64,
 }
 
 impl Secret {
@@ -47,31 +104,130 @@ impl Secret {
             description,
             created_at: now,
             updated_at: now,
+            rotated_at: None,
+            expires_at: None,
         }
     }
 
+    /// Whether the secret has expired at `now` (milliseconds)
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
     /// Update the secret value and description
     ///
     /// Pass `None` for description to clear it, or `Some(...)` to set a new one.
     pub fn update(&mut self, value: String, description: Option<String>) {
+        let now = chrono::Utc::now().timestamp_millis();
+        if value != self.value {
+            self.rotated_at = Some(now);
+        }
         self.value = value;
         self.description = description; // Always set, allowing None to clear
-        self.updated_at = chrono::Utc::now().timestamp_millis();
+        self.updated_at = now;
+    }
+}
+
+/// Database record of a secret.
+///
+/// When `keychain_ref` is set the value lives in the keychain under that
+/// account and `secret.value` is empty.
+#[derive(Serialize, Deserialize)]
+struct StoredSecret {
+    #[serde(flatten)]
+    secret: Secret,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    keychain_ref: Option<String>,
+}
+
+impl SecretAccess {
+    fn merge(accesses: &mut Vec<SecretAccess>, access: SecretAccess) {
+        match accesses
+            .iter_mut()
+            .find(|existing| existing.tool == access.tool && existing.agent_id == access.agent_id)
+        {
+            Some(existing) => {
+                existing.count += access.count;
+                existing.first_used_at = existing.first_used_at.min(access.first_used_at);
+                existing.last_used_at = existing.last_used_at.max(access.last_used_at);
+            }
+            None => accesses.push(access),
+        }
+    }
+}
+
+/// Secret reads counted in memory since the last flush, by secret key.
+#[derive(Default)]
+struct PendingUsage {
+    accesses: HashMap<String, Vec<SecretAccess>>,
+    since: Option<Instant>,
+}
+
+/// Aggregates secret reads in memory and writes them in one transaction
+/// per flush, so resolving a secret does not write to the database.
+/// Pending counts are flushed when the last storage handle is dropped.
+struct UsageRecorder {
+    backend: Arc<dyn StorageBackend>,
+    pending: Mutex<PendingUsage>,
+}
+
+impl UsageRecorder {
+    /// Write pending counts, skipping keys that are no longer stored.
+    fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.pending.lock());
+        if pending.accesses.is_empty() {
+            return Ok(());
+        }
+
+        self.backend.write_tables(|txn| {
+            let secrets = txn.table(SECRETS_TABLE)?;
+            let mut table = txn.table(SECRET_USAGE_TABLE)?;
+            for (key, new_accesses) in pending.accesses {
+                if secrets.get(&key)?.is_none() {
+                    continue;
+                }
+                let mut accesses: Vec<SecretAccess> = match table.get(&key)? {
+                    Some(data) => serde_json::from_slice(&data)?,
+                    None => Vec::new(),
+                };
+                for access in new_accesses {
+                    SecretAccess::merge(&mut accesses, access);
+                }
+                accesses.sort_by_key(|access| std::cmp::Reverse(access.last_used_at));
+                accesses.truncate(MAX_SECRET_ACCESSES);
+
+                let encoded = serde_json::to_vec(&accesses)?;
+                table.put(&key, &encoded)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Drop for UsageRecorder {
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            warn!(error = %error, "Failed to flush secret usage");
+        }
     }
 }
 
 /// Secret storage with AES-256-GCM encryption
 #[derive(Clone)]
 pub struct SecretStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
     encryptor: Arc<SecretEncryptor>,
+    mode: SecretStorageMode,
+    values: Option<Arc<dyn SecretValueStore>>,
+    usage: Arc<UsageRecorder>,
 }
 
 impl std::fmt::Debug for SecretStorage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SecretStorage")
-            .field("db", &"<redb::Database>")
+            .field("backend", &self.backend.kind())
             .field("encryptor", &"<SecretEncryptor>")
+            .field("mode", &self.mode)
             .finish()
     }
 }
@@ -82,14 +238,68 @@ impl SecretStorage {
     }
 
     pub fn with_config(db: Arc<Database>, config: SecretStorageConfig) -> Result<Self> {
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(SECRETS_TABLE)?;
-        write_txn.commit()?;
+        Self::with_backend(Arc::new(RedbBackend::new(db)), config)
+    }
+
+    /// Open on `backend`, keeping keychain-held values in the OS keychain.
+    pub fn with_backend(
+        backend: Arc<dyn StorageBackend>,
+        config: SecretStorageConfig,
+    ) -> Result<Self> {
+        // The keychain is also needed in database mode to move values back.
+        let values = match keychain::os_keychain() {
+            Ok(values) => Some(values),
+            Err(err) if config.mode == SecretStorageMode::Keychain => return Err(err),
+            Err(_) => None,
+        };
+        Self::open(backend, config, values)
+    }
+
+    /// Open with an explicit store for keychain-held values.
+    pub fn with_value_store(
+        db: Arc<Database>,
+        config: SecretStorageConfig,
+        values: Arc<dyn SecretValueStore>,
+    ) -> Result<Self> {
+        Self::open(Arc::new(RedbBackend::new(db)), config, Some(values))
+    }
+
+    fn open(
+        backend: Arc<dyn StorageBackend>,
+        config: SecretStorageConfig,
+        values: Option<Arc<dyn SecretValueStore>>,
+    ) -> Result<Self> {
+        backend.ensure_table(SECRETS_TABLE)?;
+        backend.ensure_table(SECRET_USAGE_TABLE)?;
 
         let master_key = load_master_key(&config)?;
         let encryptor = Arc::new(SecretEncryptor::new(&master_key)?);
 
-        Ok(Self { db, encryptor })
+        let usage = Arc::new(UsageRecorder {
+            backend: backend.clone(),
+            pending: Mutex::new(PendingUsage::default()),
+        });
+        let storage = Self {
+            backend,
+            encryptor,
+            mode: config.mode,
+            values,
+            usage,
+        };
+        let migrated = storage.migrate_to_mode()?;
+        if migrated > 0 {
+            info!(
+                count = migrated,
+                mode = storage.mode.as_str(),
+                "Migrated secret values"
+            );
+        }
+        Ok(storage)
+    }
+
+    /// Where this storage keeps secret values
+    pub fn mode(&self) -> SecretStorageMode {
+        self.mode
     }
 
     /// Create for testing with relaxed file permission checks.
@@ -99,19 +309,17 @@ impl SecretStorage {
             db,
             SecretStorageConfig {
                 allow_insecure_file_permissions: true,
+                ..Default::default()
             },
         )
     }
 
     /// Set or update a secret
     pub fn set_secret(&self, key: &str, value: &str, description: Option<String>) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(SECRETS_TABLE)?;
-
+        self.backend.write_table(SECRETS_TABLE, |table| {
             let existing = table
                 .get(key)?
-                .map(|data| self.decode_secret_bytes(data.value()))
+                .map(|data| self.decode_secret_bytes(&data))
                 .transpose()?;
 
             let secret = if let Some(mut existing_secret) = existing {
@@ -122,10 +330,8 @@ impl SecretStorage {
             };
 
             let encrypted = self.encode_secret(&secret)?;
-            table.insert(key, encrypted.as_slice())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            table.put(key, &encrypted)
+        })
     }
 
     /// Create a new secret (fails if already exists)
@@ -133,10 +339,7 @@ impl SecretStorage {
     /// This operation is atomic - the existence check and insert happen
     /// within the same write transaction to prevent race conditions.
     pub fn create_secret(&self, key: &str, value: &str, description: Option<String>) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(SECRETS_TABLE)?;
-
+        self.backend.write_table(SECRETS_TABLE, |table| {
             // Check existence within write transaction to prevent TOCTOU race
             if table.get(key)?.is_some() {
                 return Err(anyhow::anyhow!("Secret {} already exists", key));
@@ -144,10 +347,8 @@ impl SecretStorage {
 
             let secret = Secret::new(key.to_string(), value.to_string(), description);
             let encrypted = self.encode_secret(&secret)?;
-            table.insert(key, encrypted.as_slice())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            table.put(key, &encrypted)
+        })
     }
 
     /// Update an existing secret (fails if not exists)
@@ -155,14 +356,11 @@ impl SecretStorage {
     /// This operation is atomic - the existence check and update happen
     /// within the same write transaction to prevent race conditions.
     pub fn update_secret(&self, key: &str, value: &str, description: Option<String>) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(SECRETS_TABLE)?;
-
+        self.backend.write_table(SECRETS_TABLE, |table| {
             // Check existence and get current data within write transaction
             let existing = table
                 .get(key)?
-                .map(|data| self.decode_secret_bytes(data.value()))
+                .map(|data| self.decode_secret_bytes(&data))
                 .transpose()?;
 
             let mut existing_secret =
@@ -170,24 +368,52 @@ impl SecretStorage {
 
             existing_secret.update(value.to_string(), description);
             let encrypted = self.encode_secret(&existing_secret)?;
-            table.insert(key, encrypted.as_slice())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+            table.put(key, &encrypted)
+        })
+    }
+
+    /// Replace the value of an existing secret and reset its expiry.
+    ///
+    /// The description is kept. `expires_at` replaces the previous expiry;
+    /// pass `None` when the new value does not expire.
+    pub fn rotate_secret(&self, key: &str, value: &str, expires_at: Option<i64>) -> Result<()> {
+        self.modify_secret(key, |secret| {
+            let now = chrono::Utc::now().timestamp_millis();
+            secret.value = value.to_string();
+            secret.rotated_at = Some(now);
+            secret.updated_at = now;
+            secret.expires_at = expires_at;
+        })
+    }
+
+    /// Set or clear (`None`) the expiry of an existing secret
+    pub fn set_secret_expiry(&self, key: &str, expires_at: Option<i64>) -> Result<()> {
+        self.modify_secret(key, |secret| {
+            secret.expires_at = expires_at;
+            secret.updated_at = chrono::Utc::now().timestamp_millis();
+        })
+    }
+
+    fn modify_secret(&self, key: &str, apply: impl FnOnce(&mut Secret)) -> Result<()> {
+        self.backend.write_table(SECRETS_TABLE, |table| {
+            let mut secret = table
+                .get(key)?
+                .map(|data| self.decode_secret_bytes(&data))
+                .transpose()?
+                .ok_or_else(|| anyhow::anyhow!("Secret {} not found", key))?;
+
+            apply(&mut secret);
+            let encrypted = self.encode_secret(&secret)?;
+            table.put(key, &encrypted)
+        })
     }
 
     /// Get secret model (internal)
     fn get_secret_model(&self, key: &str) -> Result<Option<Secret>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(SECRETS_TABLE)?;
-
-        if let Some(data) = table.get(key)? {
-            let raw = data.value();
-            let secret = self.decode_secret_bytes(raw)?;
-            Ok(Some(secret))
-        } else {
-            Ok(None)
-        }
+        self.backend
+            .read_table(SECRETS_TABLE, |table| table.get(key))?
+            .map(|data| self.decode_secret_bytes(&data))
+            .transpose()
     }
 
     /// Get secret value, falling back to environment variable
@@ -211,28 +437,35 @@ impl SecretStorage {
             .filter(|value| !value.is_empty()))
     }
 
-    /// Delete a secret
+    /// Delete a secret and its usage history
     pub fn delete_secret(&self, key: &str) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(SECRETS_TABLE)?;
+        self.usage.pending.lock().accesses.remove(key);
+        let keychain_ref = self.backend.write_tables(|txn| {
+            let mut table = txn.table(SECRETS_TABLE)?;
+            let removed = table
+                .get(key)?
+                .map(|data| self.decode_record(&data))
+                .transpose()?;
             table.remove(key)?;
+            txn.table(SECRET_USAGE_TABLE)?.remove(key)?;
+            Ok(removed.and_then(|record| record.keychain_ref))
+        })?;
+        if let Some(account) = keychain_ref {
+            self.value_store(key)?.delete(&account)?;
         }
-        write_txn.commit()?;
         Ok(())
     }
 
     /// List all secrets (values are cleared for security)
     pub fn list_secrets(&self) -> Result<Vec<Secret>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(SECRETS_TABLE)?;
+        let entries = self
+            .backend
+            .read_table(SECRETS_TABLE, |table| table.scan_prefix(""))?;
 
         let mut secrets = Vec::new();
-        for item in table.iter()? {
-            let (_, value) = item?;
-            let secret = self.decode_secret_bytes(value.value())?;
-            let mut secret = secret;
-            // Clear the value for security
+        for (_, value) in entries {
+            // Keychain values are not read; the value is cleared anyway
+            let mut secret = self.decode_record(&value)?.secret;
             secret.value = String::new();
             secrets.push(secret);
         }
@@ -240,14 +473,84 @@ impl SecretStorage {
         Ok(secrets)
     }
 
+    /// List secrets that expire at or before `before` (milliseconds),
+    /// soonest first. Values are cleared.
+    pub fn list_expiring_secrets(&self, before: i64) -> Result<Vec<Secret>> {
+        let mut secrets: Vec<Secret> = self
+            .list_secrets()?
+            .into_iter()
+            .filter(|secret| secret.expires_at.is_some_and(|at| at <= before))
+            .collect();
+        secrets.sort_by_key(|secret| secret.expires_at);
+        Ok(secrets)
+    }
+
+    /// Record that `tool` (run by `agent_id`, if any) read a managed secret.
+    ///
+    /// Reads are counted in memory and written at most every
+    /// `USAGE_FLUSH_INTERVAL`, or by `flush_secret_usage`. Reads of keys that
+    /// are not stored (environment fallback) are ignored.
+    pub fn record_secret_usage(&self, key: &str, tool: &str, agent_id: Option<&str>) -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let due = {
+            let mut pending = self.usage.pending.lock();
+            SecretAccess::merge(
+                pending.accesses.entry(key.to_string()).or_default(),
+                SecretAccess {
+                    tool: tool.to_string(),
+                    agent_id: agent_id.map(str::to_string),
+                    count: 1,
+                    first_used_at: now,
+                    last_used_at: now,
+                },
+            );
+            pending.since.get_or_insert_with(Instant::now).elapsed() >= USAGE_FLUSH_INTERVAL
+        };
+        if due {
+            self.flush_secret_usage()?;
+        }
+        Ok(())
+    }
+
+    /// Write secret reads counted in memory to the database.
+    pub fn flush_secret_usage(&self) -> Result<()> {
+        self.usage.flush()
+    }
+
+    /// Tools and agents that have read a secret, most recent first.
+    ///
+    /// Includes reads that have not been flushed yet.
+    pub fn get_secret_usage(&self, key: &str) -> Result<Vec<SecretAccess>> {
+        let stored = self.backend.read_tables(|txn| {
+            if txn.table(SECRETS_TABLE)?.get(key)?.is_none() {
+                return Ok(None);
+            }
+            Ok(Some(txn.table(SECRET_USAGE_TABLE)?.get(key)?))
+        })?;
+        let mut accesses: Vec<SecretAccess> = match stored {
+            None => return Ok(Vec::new()),
+            Some(Some(data)) => serde_json::from_slice(&data)?,
+            Some(None) => Vec::new(),
+        };
+        if let Some(pending) = self.usage.pending.lock().accesses.get(key) {
+            for access in pending {
+                SecretAccess::merge(&mut accesses, access.clone());
+            }
+        }
+        accesses.sort_by_key(|access| std::cmp::Reverse(access.last_used_at));
+        accesses.truncate(MAX_SECRET_ACCESSES);
+        Ok(accesses)
+    }
+
     /// Check whether the secret is managed in storage only.
     ///
     /// This does not check environment-variable fallback. Use
     /// `has_available_secret` when callers need runtime availability semantics.
     pub fn has_secret(&self, key: &str) -> Result<bool> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(SECRETS_TABLE)?;
-        Ok(table.get(key)?.is_some())
+        let value = self
+            .backend
+            .read_table(SECRETS_TABLE, |table| table.get(key))?;
+        Ok(value.is_some())
     }
 
     /// Check whether the secret is available from managed storage or env fallback.
@@ -262,15 +565,105 @@ impl SecretStorage {
         key.to_uppercase().replace('-', "_")
     }
 
+    /// Encode a secret for the database, moving its value to the keychain
+    /// in keychain mode.
     fn encode_secret(&self, secret: &Secret) -> Result<Vec<u8>> {
-        let json = serde_json::to_vec(secret)?;
+        let record = match self.mode {
+            SecretStorageMode::Database => StoredSecret {
+                secret: secret.clone(),
+                keychain_ref: None,
+            },
+            SecretStorageMode::Keychain => {
+                let account = keychain_account(&secret.key);
+                self.value_store(&secret.key)?
+                    .set(&account, &secret.value)?;
+                StoredSecret {
+                    secret: Secret {
+                        value: String::new(),
+                        ..secret.clone()
+                    },
+                    keychain_ref: Some(account),
+                }
+            }
+        };
+        let json = serde_json::to_vec(&record)?;
         self.encryptor.encrypt(&json)
     }
 
-    fn decode_secret_bytes(&self, payload: &[u8]) -> Result<Secret> {
+    fn decode_record(&self, payload: &[u8]) -> Result<StoredSecret> {
         let plaintext = self.encryptor.decrypt(payload)?;
         Ok(serde_json::from_slice(&plaintext)?)
     }
+
+    fn decode_secret_bytes(&self, payload: &[u8]) -> Result<Secret> {
+        self.resolve_record(self.decode_record(payload)?)
+    }
+
+    /// Read the value of a keychain-held record from the keychain
+    fn resolve_record(&self, record: StoredSecret) -> Result<Secret> {
+        let StoredSecret {
+            mut secret,
+            keychain_ref,
+        } = record;
+        if let Some(account) = keychain_ref {
+            secret.value = self
+                .value_store(&secret.key)?
+                .get(&account)?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Secret {} is missing from the OS keychain", secret.key)
+                })?;
+        }
+        Ok(secret)
+    }
+
+    fn value_store(&self, key: &str) -> Result<&Arc<dyn SecretValueStore>> {
+        self.values.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Secret {} is kept in the OS keychain, which is not available in this build",
+                key
+            )
+        })
+    }
+
+    /// Move values stored under the other mode into this storage's mode.
+    ///
+    /// Keychain entries are removed only after the database no longer
+    /// references them. Returns the number of secrets moved.
+    fn migrate_to_mode(&self) -> Result<usize> {
+        let (migrated, released) = self.backend.write_table(SECRETS_TABLE, |table| {
+            let mut pending = Vec::new();
+            for (key, data) in table.scan_prefix("")? {
+                let record = self.decode_record(&data)?;
+                let in_keychain = record.keychain_ref.is_some();
+                if in_keychain != (self.mode == SecretStorageMode::Keychain) {
+                    pending.push((key, record));
+                }
+            }
+
+            let mut released = Vec::new();
+            let mut migrated = 0;
+            for (key, record) in pending {
+                let old_ref = record.keychain_ref.clone();
+                let encoded = self
+                    .resolve_record(record)
+                    .and_then(|secret| self.encode_secret(&secret))
+                    .with_context(|| format!("Failed to migrate secret {key}"))?;
+                table.put(&key, &encoded)?;
+                if let Some(account) = old_ref {
+                    released.push((key, account));
+                }
+                migrated += 1;
+            }
+            Ok((migrated, released))
+        })?;
+
+        for (key, account) in released {
+            if let Err(err) = self.value_store(&key)?.delete(&account) {
+                warn!(key = %key, error = %err, "Failed to remove migrated secret from the OS keychain");
+            }
+        }
+        Ok(migrated)
+    }
 }
 
 fn load_master_key(config: &SecretStorageConfig) -> Result<[u8; 32]> {
@@ -463,6 +856,7 @@ mod tests {
 
         let config = SecretStorageConfig {
             allow_insecure_file_permissions: true,
+            ..Default::default()
         };
 
         let key = load_master_key(&config).unwrap();
@@ -489,6 +883,7 @@ mod tests {
 
         let config = SecretStorageConfig {
             allow_insecure_file_permissions: true,
+            ..Default::default()
         };
 
         let key = load_master_key(&config).unwrap();
@@ -518,6 +913,7 @@ mod tests {
 
         let config = SecretStorageConfig {
             allow_insecure_file_permissions: true,
+            ..Default::default()
         };
         let existing = load_master_key_from_file(&config).unwrap().unwrap();
         assert_eq!(existing, first_key);
@@ -639,6 +1035,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotation_and_expiry_metadata() {
+        let (storage, _temp_dir) = setup();
+
+        storage.set_secret("ROTATE_ME", "v1", None).unwrap();
+        storage.set_secret("ROTATE_ME", "v1", None).unwrap();
+        let secret = storage.get_secret_model("ROTATE_ME").unwrap().unwrap();
+        assert_eq!(secret.rotated_at, None);
+
+        storage.set_secret_expiry("ROTATE_ME", Some(1_000)).unwrap();
+        storage.set_secret("LONG_LIVED", "v", None).unwrap();
+        storage
+            .set_secret_expiry("LONG_LIVED", Some(i64::MAX))
+            .unwrap();
+        let expiring = storage.list_expiring_secrets(5_000).unwrap();
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring[0].key, "ROTATE_ME");
+        assert!(expiring[0].is_expired(5_000));
+        assert_eq!(expiring[0].value, "");
+
+        storage.rotate_secret("ROTATE_ME", "v2", None).unwrap();
+        let secret = storage.get_secret_model("ROTATE_ME").unwrap().unwrap();
+        assert_eq!(secret.value, "v2");
+        assert!(secret.rotated_at.is_some());
+        assert_eq!(secret.expires_at, None);
+        assert!(storage.list_expiring_secrets(5_000).unwrap().is_empty());
+
+        assert!(storage.rotate_secret("MISSING", "v", None).is_err());
+        assert!(storage.set_secret_expiry("MISSING", None).is_err());
+    }
+
+    #[test]
+    fn test_secret_usage_tracking() {
+        let (storage, _temp_dir) = setup();
+
+        storage.set_secret("GITHUB_TOKEN", "ghp", None).unwrap();
+        storage
+            .record_secret_usage("GITHUB_TOKEN", "github", Some("agent-1"))
+            .unwrap();
+        storage
+            .record_secret_usage("GITHUB_TOKEN", "github", Some("agent-1"))
+            .unwrap();
+        storage
+            .record_secret_usage("GITHUB_TOKEN", "http_request", None)
+            .unwrap();
+        storage
+            .record_secret_usage("NOT_STORED", "github", None)
+            .unwrap();
+
+        let usage = storage.get_secret_usage("GITHUB_TOKEN").unwrap();
+        assert_eq!(usage.len(), 2);
+        let github = usage.iter().find(|access| access.tool == "github").unwrap();
+        assert_eq!(github.count, 2);
+        assert_eq!(github.agent_id.as_deref(), Some("agent-1"));
+        assert!(storage.get_secret_usage("NOT_STORED").unwrap().is_empty());
+
+        // Flushed counts merge with reads recorded afterwards.
+        storage.flush_secret_usage().unwrap();
+        storage
+            .record_secret_usage("GITHUB_TOKEN", "github", Some("agent-1"))
+            .unwrap();
+        let usage = storage.get_secret_usage("GITHUB_TOKEN").unwrap();
+        let github = usage.iter().find(|access| access.tool == "github").unwrap();
+        assert_eq!(github.count, 3);
+        storage.flush_secret_usage().unwrap();
+        let usage = storage.get_secret_usage("GITHUB_TOKEN").unwrap();
+        let github = usage.iter().find(|access| access.tool == "github").unwrap();
+        assert_eq!(github.count, 3);
+
+        storage.delete_secret("GITHUB_TOKEN").unwrap();
+        assert!(storage.get_secret_usage("GITHUB_TOKEN").unwrap().is_empty());
+    }
+
     #[test]
     fn test_create_secret_atomic() {
         let (storage, _temp_dir) = setup();
@@ -773,4 +1242,65 @@ mod tests {
         // SAFETY: This is a single-threaded test, no other threads access this env var
         unsafe { std::env::remove_var(RESTFLOW_DIR_ENV) };
     }
+
+    #[test]
+    fn test_keychain_mode_and_migration() {
+        let _env_lock = env_lock();
+        let temp_dir = tempdir().unwrap();
+        let state_dir = temp_dir.path().join("state");
+        std::fs::create_dir_all(&state_dir).unwrap();
+
+        // SAFETY: This is a single-threaded test, no other threads access this env var
+        unsafe { std::env::set_var(RESTFLOW_DIR_ENV, &state_dir) };
+
+        let db = Arc::new(Database::create(temp_dir.path().join("test.db")).unwrap());
+        let values = Arc::new(crate::keychain::MemoryValueStore::new());
+        let open = |mode| {
+            let config = SecretStorageConfig {
+                allow_insecure_file_permissions: true,
+                mode,
+            };
+            SecretStorage::with_value_store(db.clone(), config, values.clone()).unwrap()
+        };
+
+        let storage = open(SecretStorageMode::Database);
+        storage.set_secret("LEGACY", "old-value", None).unwrap();
+        assert!(values.is_empty());
+
+        // Switching to keychain mode moves the existing value out of the database
+        let storage = open(SecretStorageMode::Keychain);
+        assert_eq!(storage.mode(), SecretStorageMode::Keychain);
+        assert_eq!(
+            storage.get_secret("LEGACY").unwrap().as_deref(),
+            Some("old-value")
+        );
+        storage.set_secret("NEW", "new-value", None).unwrap();
+        assert_eq!(
+            values.get(&keychain_account("NEW")).unwrap().as_deref(),
+            Some("new-value")
+        );
+
+        let stored = storage
+            .backend
+            .read_table(SECRETS_TABLE, |table| table.get("NEW"))
+            .unwrap()
+            .unwrap();
+        let record = storage.decode_record(&stored).unwrap();
+        assert!(record.secret.value.is_empty());
+        assert_eq!(record.keychain_ref, Some(keychain_account("NEW")));
+
+        storage.delete_secret("NEW").unwrap();
+        assert_eq!(values.len(), 1);
+
+        // Switching back restores the value to the database and clears the keychain
+        let storage = open(SecretStorageMode::Database);
+        assert_eq!(
+            storage.get_secret("LEGACY").unwrap().as_deref(),
+            Some("old-value")
+        );
+        assert!(values.is_empty());
+
+        // SAFETY: This is a single-threaded test, no other threads access this env var
+        unsafe { std::env::remove_var(RESTFLOW_DIR_ENV) };
+    }
 }
@@ -1,17 +1,18 @@
 use anyhow::Result;
-use redb::{Database, ReadableDatabase, ReadableTable, ReadableTableMetadata, TableDefinition};
 use std::sync::Arc;
 
+use crate::backend::StorageBackend;
+
 /// Trait for simple key-value storage modules.
 ///
 /// Provides default implementations for common CRUD operations.
-/// Implementors only need to specify the table definition and database reference.
+/// Implementors only need to specify the table name and backend reference.
 pub trait SimpleStorage: Send + Sync {
-    /// The table definition for this storage type.
-    const TABLE: TableDefinition<'static, &'static str, &'static [u8]>;
+    /// The table name for this storage type.
+    const TABLE: &'static str;
 
-    /// Get reference to the database.
-    fn db(&self) -> &Arc<Database>;
+    /// Get reference to the storage backend.
+    fn backend(&self) -> &Arc<dyn StorageBackend>;
 
     /// Insert only if key doesn't exist (atomic check-and-insert).
     ///
@@ -21,117 +22,115 @@ pub trait SimpleStorage: Send + Sync {
     /// This operation is atomic - the existence check and insert happen
     /// in a single write transaction, preventing TOCTOU race conditions.
     fn insert_if_absent(&self, id: &str, data: &[u8]) -> Result<bool> {
-        let write_txn = self.db().begin_write()?;
-        let inserted = {
-            let mut table = write_txn.open_table(Self::TABLE)?;
+        self.backend().write_table(Self::TABLE, |table| {
             let existed = table.get(id)?.is_some();
             if !existed {
-                table.insert(id, data)?;
+                table.put(id, data)?;
             }
-            !existed
-        };
-        write_txn.commit()?;
-        Ok(inserted)
+            Ok(!existed)
+        })
     }
 
     /// Store raw bytes by ID.
     fn put_raw(&self, id: &str, data: &[u8]) -> Result<()> {
-        let write_txn = self.db().begin_write()?;
-        {
-            let mut table = write_txn.open_table(Self::TABLE)?;
-            table.insert(id, data)?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.backend()
+            .write_table(Self::TABLE, |table| table.put(id, data))
+    }
+
+    /// Read and replace the bytes stored under `id` in a single write
+    /// transaction.
+    ///
+    /// `f` receives the current bytes and returns the bytes to store (`None`
+    /// leaves the entry as it is) together with a value for the caller.
+    fn update_raw<R>(
+        &self,
+        id: &str,
+        f: impl FnOnce(Option<Vec<u8>>) -> Result<(Option<Vec<u8>>, R)>,
+    ) -> Result<R> {
+        self.backend().write_table(Self::TABLE, |table| {
+            let (next, output) = f(table.get(id)?)?;
+            if let Some(next) = next {
+                table.put(id, &next)?;
+            }
+            Ok(output)
+        })
     }
 
     /// Get raw bytes by ID.
     fn get_raw(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(Self::TABLE)?;
-        if let Some(value) = table.get(id)? {
-            Ok(Some(value.value().to_vec()))
-        } else {
-            Ok(None)
-        }
+        self.backend()
+            .read_table(Self::TABLE, |table| table.get(id))
     }
 
     /// List all entries as (id, data) pairs.
     fn list_raw(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(Self::TABLE)?;
-        let mut items = Vec::new();
-        for item in table.iter()? {
-            let (key, value) = item?;
-            items.push((key.value().to_string(), value.value().to_vec()));
-        }
-        Ok(items)
+        self.backend()
+            .read_table(Self::TABLE, |table| table.scan_prefix(""))
     }
 
     /// Delete by ID, returns true if existed.
     fn delete(&self, id: &str) -> Result<bool> {
-        let write_txn = self.db().begin_write()?;
-        let existed = {
-            let mut table = write_txn.open_table(Self::TABLE)?;
-            table.remove(id)?.is_some()
-        };
-        write_txn.commit()?;
-        Ok(existed)
+        self.backend()
+            .write_table(Self::TABLE, |table| table.remove(id))
     }
 
     /// Check if ID exists.
     fn exists(&self, id: &str) -> Result<bool> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(Self::TABLE)?;
-        Ok(table.get(id)?.is_some())
+        self.backend()
+            .read_table(Self::TABLE, |table| Ok(table.get(id)?.is_some()))
     }
 
     /// Check which IDs exist in a single read transaction.
     fn exists_many(&self, ids: &[&str]) -> Result<std::collections::HashSet<String>> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(Self::TABLE)?;
-        let mut found = std::collections::HashSet::new();
-        for &id in ids {
-            if table.get(id)?.is_some() {
-                found.insert(id.to_string());
+        self.backend().read_table(Self::TABLE, |table| {
+            let mut found = std::collections::HashSet::new();
+            for &id in ids {
+                if table.get(id)?.is_some() {
+                    found.insert(id.to_string());
+                }
             }
-        }
-        Ok(found)
+            Ok(found)
+        })
     }
 
     /// Count all entries.
     fn count(&self) -> Result<usize> {
-        let read_txn = self.db().begin_read()?;
-        let table = read_txn.open_table(Self::TABLE)?;
-        Ok(table.len()? as usize)
+        self.backend()
+            .read_table(Self::TABLE, |table| table.count())
     }
 }
 
 /// Macro to generate a simple storage struct with common implementations.
+///
+/// The generated type can be opened on a redb database with `new` or on any
+/// [`StorageBackend`] with `with_backend`.
 #[macro_export]
 macro_rules! define_simple_storage {
     ( $(#[$meta:meta])* $vis:vis struct $name:ident { table: $table_name:literal } ) => {
         $(#[$meta])*
         #[derive(Debug, Clone)]
         $vis struct $name {
-            db: std::sync::Arc<redb::Database>,
+            backend: std::sync::Arc<dyn $crate::StorageBackend>,
         }
 
         impl $name {
             pub fn new(db: std::sync::Arc<redb::Database>) -> anyhow::Result<Self> {
-                let write_txn = db.begin_write()?;
-                write_txn.open_table(<Self as $crate::SimpleStorage>::TABLE)?;
-                write_txn.commit()?;
-                Ok(Self { db })
+                Self::with_backend(std::sync::Arc::new($crate::RedbBackend::new(db)))
+            }
+
+            pub fn with_backend(
+                backend: std::sync::Arc<dyn $crate::StorageBackend>,
+            ) -> anyhow::Result<Self> {
+                backend.ensure_table(<Self as $crate::SimpleStorage>::TABLE)?;
+                Ok(Self { backend })
             }
         }
 
         impl $crate::SimpleStorage for $name {
-            const TABLE: redb::TableDefinition<'static, &'static str, &'static [u8]> =
-                redb::TableDefinition::new($table_name);
+            const TABLE: &'static str = $table_name;
 
-            fn db(&self) -> &std::sync::Arc<redb::Database> {
-                &self.db
+            fn backend(&self) -> &std::sync::Arc<dyn $crate::StorageBackend> {
+                &self.backend
             }
         }
     };
@@ -0,0 +1,6 @@
+use crate::define_simple_storage;
+
+define_simple_storage! {
+    /// Byte-level LLM usage record storage.
+    pub struct UsageRecordStorage { table: "usage_records_v1" }
+}
@@ -1,6 +1,6 @@
 //! Vector storage using HNSW for approximate nearest neighbor search.
 //!
-//! Provides low-level vector storage with persistence to ReDB.
+//! Provides low-level vector storage persisted through a [`StorageBackend`].
 //! The HNSW index is kept in memory for fast search, with vectors
 //! persisted to the database for durability.
 //!
@@ -17,14 +17,20 @@
 use anyhow::Result;
 use hnsw_rs::prelude::*;
 use parking_lot::RwLock;
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use redb::Database;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::backend::{RedbBackend, StorageBackend};
+
 type VectorIndex = Hnsw<'static, f32, DistCosine>;
 
-const VECTOR_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("memory_vectors");
-const VECTOR_META_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("memory_vector_meta");
+const VECTOR_TABLE: &str = "memory_vectors";
+const VECTOR_META_TABLE: &str = "memory_vector_meta";
+
+/// Filtered searches over at most this many candidates compare every vector
+/// exactly; larger candidate sets go through the HNSW graph.
+const EXACT_SEARCH_MAX_CANDIDATES: usize = 512;
 
 /// Configuration for vector storage.
 #[derive(Debug, Clone)]
@@ -63,7 +69,7 @@ pub struct VectorStats {
 
 /// Low-level vector storage with HNSW index.
 pub struct VectorStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
     config: VectorConfig,
     /// HNSW index (in-memory, rebuilt on load)
     index: RwLock<VectorIndex>,
@@ -80,10 +86,13 @@ pub struct VectorStorage {
 impl VectorStorage {
     /// Create new vector storage, loading existing vectors from DB.
     pub fn new(db: Arc<Database>, config: VectorConfig) -> Result<Self> {
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(VECTOR_TABLE)?;
-        write_txn.open_table(VECTOR_META_TABLE)?;
-        write_txn.commit()?;
+        Self::with_backend(Arc::new(RedbBackend::new(db)), config)
+    }
+
+    /// Create vector storage on `backend`, loading existing vectors.
+    pub fn with_backend(backend: Arc<dyn StorageBackend>, config: VectorConfig) -> Result<Self> {
+        backend.ensure_table(VECTOR_TABLE)?;
+        backend.ensure_table(VECTOR_META_TABLE)?;
 
         let hnsw: VectorIndex = Hnsw::new(
             config.max_connections,
@@ -94,7 +103,7 @@ impl VectorStorage {
         );
 
         let storage = Self {
-            db,
+            backend,
             config,
             index: RwLock::new(hnsw),
             id_map: RwLock::new(HashMap::new()),
@@ -178,12 +187,8 @@ impl VectorStorage {
             *orphan += 1;
         }
 
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(VECTOR_TABLE)?;
-            table.remove(chunk_id)?;
-        }
-        write_txn.commit()?;
+        self.backend
+            .write_table(VECTOR_TABLE, |table| table.remove(chunk_id))?;
 
         Ok(true)
     }
@@ -216,6 +221,9 @@ impl VectorStorage {
     }
 
     /// Search with filtering (only return IDs in allowed set).
+    ///
+    /// Small candidate sets are scanned exactly; larger ones use the HNSW
+    /// index restricted to the allowed IDs, so recall is approximate.
     pub fn search_filtered(
         &self,
         query: &[f32],
@@ -235,8 +243,43 @@ impl VectorStorage {
             return Ok(Vec::new());
         }
 
-        let _ = ef_search;
-        self.search_filtered_exact(query, top_k, allowed_ids)
+        if allowed_ids.len() <= EXACT_SEARCH_MAX_CANDIDATES {
+            return self.search_filtered_exact(query, top_k, allowed_ids);
+        }
+
+        let mut allowed: Vec<usize> = {
+            let id_map = self.id_map.read();
+            allowed_ids
+                .iter()
+                .filter_map(|chunk_id| id_map.get(chunk_id).copied())
+                .collect()
+        };
+        // The Vec<usize> filter binary-searches, so it must be sorted.
+        allowed.sort_unstable();
+
+        let index = self.index.read();
+        let reverse = self.reverse_map.read();
+        let results = index.search_filter(query, top_k, ef_search.max(top_k), Some(&allowed));
+        Ok(results
+            .into_iter()
+            .filter_map(|item| {
+                let chunk_id = reverse.get(&item.d_id)?;
+                Some((chunk_id.clone(), item.distance))
+            })
+            .collect())
+    }
+
+    /// Get the stored vector for a chunk.
+    pub fn get(&self, chunk_id: &str) -> Result<Option<Vec<f32>>> {
+        let Some(value) = self
+            .backend
+            .read_table(VECTOR_TABLE, |table| table.get(chunk_id))?
+        else {
+            return Ok(None);
+        };
+        let (vector, _): (Vec<f32>, usize) =
+            bincode::serde::decode_from_slice(&value, bincode::config::standard())?;
+        Ok(Some(vector))
     }
 
     /// Check if a chunk has a vector.
@@ -288,13 +331,8 @@ impl VectorStorage {
 
     fn persist_vector(&self, chunk_id: &str, vector: &[f32]) -> Result<()> {
         let bytes = bincode::serde::encode_to_vec(vector, bincode::config::standard())?;
-        let write_txn = self.db.begin_write()?;
-        {
-            let mut table = write_txn.open_table(VECTOR_TABLE)?;
-            table.insert(chunk_id, bytes.as_slice())?;
-        }
-        write_txn.commit()?;
-        Ok(())
+        self.backend
+            .write_table(VECTOR_TABLE, |table| table.put(chunk_id, &bytes))
     }
 
     fn search_filtered_exact(
@@ -303,19 +341,19 @@ impl VectorStorage {
         top_k: usize,
         allowed_ids: &[String],
     ) -> Result<Vec<(String, f32)>> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(VECTOR_TABLE)?;
-        let mut matches = Vec::new();
-
-        for chunk_id in allowed_ids {
-            let Some(value) = table.get(chunk_id.as_str())? else {
-                continue;
-            };
-            let (vector, _): (Vec<f32>, usize) =
-                bincode::serde::decode_from_slice(value.value(), bincode::config::standard())?;
-            let distance = cosine_distance(query, &vector);
-            matches.push((chunk_id.clone(), distance));
-        }
+        let mut matches = self.backend.read_table(VECTOR_TABLE, |table| {
+            let mut matches = Vec::new();
+            for chunk_id in allowed_ids {
+                let Some(value) = table.get(chunk_id)? else {
+                    continue;
+                };
+                let (vector, _): (Vec<f32>, usize) =
+                    bincode::serde::decode_from_slice(&value, bincode::config::standard())?;
+                let distance = cosine_distance(query, &vector);
+                matches.push((chunk_id.clone(), distance));
+            }
+            Ok(matches)
+        })?;
 
         matches.sort_by(|left, right| {
             left.1
@@ -327,17 +365,15 @@ impl VectorStorage {
     }
 
     fn rebuild_index(&self) -> Result<()> {
-        let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(VECTOR_TABLE)?;
-        let mut vectors: Vec<(String, Vec<f32>)> = Vec::new();
-        for item in table.iter()? {
-            let (key, value) = item?;
-            let chunk_id = key.value().to_string();
+        let entries = self
+            .backend
+            .read_table(VECTOR_TABLE, |table| table.scan_prefix(""))?;
+        let mut vectors: Vec<(String, Vec<f32>)> = Vec::with_capacity(entries.len());
+        for (chunk_id, value) in entries {
             let (vector, _): (Vec<f32>, usize) =
-                bincode::serde::decode_from_slice(value.value(), bincode::config::standard())?;
+                bincode::serde::decode_from_slice(&value, bincode::config::standard())?;
             vectors.push((chunk_id, vector));
         }
-        drop(read_txn);
 
         let mut index = self.index.write();
         let mut id_map = self.id_map.write();
@@ -600,4 +636,27 @@ mod tests {
         assert!(!ids_chunk_1.contains(&"chunk-3"));
         assert!(!ids_chunk_2.contains(&"chunk-3"));
     }
+
+    #[test]
+    fn test_filtered_search_over_large_candidate_set_uses_allowed_ids() {
+        let storage = create_test_storage(4);
+        let mut allowed = Vec::new();
+        for i in 0..800 {
+            let angle = i as f32 * 0.01;
+            let id = format!("chunk-{i}");
+            storage
+                .add(&id, &[angle.cos(), angle.sin(), 1.0, (i % 7) as f32])
+                .unwrap();
+            if i % 4 != 0 {
+                allowed.push(id);
+            }
+        }
+        assert!(allowed.len() > EXACT_SEARCH_MAX_CANDIDATES);
+
+        let angle = 201.0_f32 * 0.01;
+        let query = [angle.cos(), angle.sin(), 1.0, (201 % 7) as f32];
+        let results = storage.search_filtered(&query, 5, 100, &allowed).unwrap();
+        assert_eq!(results[0].0, "chunk-201");
+        assert!(results.iter().all(|(id, _)| allowed.contains(id)));
+    }
 }
@@ -1,64 +1,54 @@
 //! Work item storage with folder/status indexes.
 
 use anyhow::{Result, anyhow};
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use redb::Database;
 use std::sync::Arc;
 
-use crate::range_utils::prefix_range;
+use crate::backend::{RedbBackend, StorageBackend, text_value};
 
-const ITEMS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("work_items");
-const FOLDER_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("work_item_folder_index");
-const STATUS_INDEX_TABLE: TableDefinition<&str, &str> =
-    TableDefinition::new("work_item_status_index");
+const ITEMS_TABLE: &str = "work_items";
+const FOLDER_INDEX_TABLE: &str = "work_item_folder_index";
+const STATUS_INDEX_TABLE: &str = "work_item_status_index";
 
 #[derive(Debug, Clone)]
 pub struct WorkItemStorage {
-    db: Arc<Database>,
+    backend: Arc<dyn StorageBackend>,
 }
 
 impl WorkItemStorage {
     pub fn new(db: Arc<Database>) -> Result<Self> {
-        let write_txn = db.begin_write()?;
-        write_txn.open_table(ITEMS_TABLE)?;
-        write_txn.open_table(FOLDER_INDEX_TABLE)?;
-        write_txn.open_table(STATUS_INDEX_TABLE)?;
-        write_txn.commit()?;
+        Self::with_backend(Arc::new(RedbBackend::new(db)))
+    }
 
-        Ok(Self { db })
+    pub fn with_backend(backend: Arc<dyn StorageBackend>) -> Result<Self> {
+        backend.ensure_table(ITEMS_TABLE)?;
+        backend.ensure_table(FOLDER_INDEX_TABLE)?;
+        backend.ensure_table(STATUS_INDEX_TABLE)?;
+        Ok(Self { backend })
     }
 
     pub fn create(&self, id: &str, data: &[u8], folder: &str, status: &str) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
-
-        {
-            let mut notes = write_txn.open_table(ITEMS_TABLE)?;
-            if notes.get(id)?.is_some() {
-                return Err(anyhow!("Work item {} already exists", id));
+        self.backend.write_tables(|txn| {
+            {
+                let mut notes = txn.table(ITEMS_TABLE)?;
+                if notes.get(id)?.is_some() {
+                    return Err(anyhow!("Work item {} already exists", id));
+                }
+                notes.put(id, data)?;
             }
-            notes.insert(id, data)?;
-        }
 
-        {
-            let mut folder_index = write_txn.open_table(FOLDER_INDEX_TABLE)?;
             let folder_key = Self::folder_key(folder, id);
-            folder_index.insert(folder_key.as_str(), id)?;
-        }
+            txn.table(FOLDER_INDEX_TABLE)?
+                .put(&folder_key, id.as_bytes())?;
 
-        {
-            let mut status_index = write_txn.open_table(STATUS_INDEX_TABLE)?;
             let status_key = Self::status_key(status, id);
-            status_index.insert(status_key.as_str(), id)?;
-        }
-
-        write_txn.commit()?;
-        Ok(())
+            txn.table(STATUS_INDEX_TABLE)?
+                .put(&status_key, id.as_bytes())
+        })
     }
 
     pub fn get(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        let read_txn = self.db.begin_read()?;
-        let notes = read_txn.open_table(ITEMS_TABLE)?;
-        Ok(notes.get(id)?.map(|value| value.value().to_vec()))
+        self.backend.read_table(ITEMS_TABLE, |notes| notes.get(id))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -71,68 +61,45 @@ impl WorkItemStorage {
         new_folder: &str,
         new_status: &str,
     ) -> Result<()> {
-        let write_txn = self.db.begin_write()?;
+        self.backend.write_tables(|txn| {
+            {
+                let mut notes = txn.table(ITEMS_TABLE)?;
+                if notes.get(id)?.is_none() {
+                    return Err(anyhow!("Work item {} not found", id));
+                }
+                notes.put(id, data)?;
+            }
 
-        {
-            let mut notes = write_txn.open_table(ITEMS_TABLE)?;
-            if notes.get(id)?.is_none() {
-                return Err(anyhow!("Work item {} not found", id));
+            {
+                let mut folder_index = txn.table(FOLDER_INDEX_TABLE)?;
+                folder_index.remove(&Self::folder_key(old_folder, id))?;
+                folder_index.put(&Self::folder_key(new_folder, id), id.as_bytes())?;
             }
-            notes.insert(id, data)?;
-        }
-
-        {
-            let mut folder_index = write_txn.open_table(FOLDER_INDEX_TABLE)?;
-            let old_key = Self::folder_key(old_folder, id);
-            let new_key = Self::folder_key(new_folder, id);
-            folder_index.remove(old_key.as_str())?;
-            folder_index.insert(new_key.as_str(), id)?;
-        }
-
-        {
-            let mut status_index = write_txn.open_table(STATUS_INDEX_TABLE)?;
-            let old_key = Self::status_key(old_status, id);
-            let new_key = Self::status_key(new_status, id);
-            status_index.remove(old_key.as_str())?;
-            status_index.insert(new_key.as_str(), id)?;
-        }
-
-        write_txn.commit()?;
-        Ok(())
+
+            let mut status_index = txn.table(STATUS_INDEX_TABLE)?;
+            status_index.remove(&Self::status_key(old_status, id))?;
+            status_index.put(&Self::status_key(new_status, id), id.as_bytes())
+        })
     }
 
     pub fn delete(&self, id: &str, folder: &str, status: &str) -> Result<bool> {
-        let write_txn = self.db.begin_write()?;
-
-        let removed = {
-            let mut notes = write_txn.open_table(ITEMS_TABLE)?;
-            notes.remove(id)?.is_some()
-        };
-
-        if removed {
-            let mut folder_index = write_txn.open_table(FOLDER_INDEX_TABLE)?;
-            let folder_key = Self::folder_key(folder, id);
-            folder_index.remove(folder_key.as_str())?;
-
-            let mut status_index = write_txn.open_table(STATUS_INDEX_TABLE)?;
-            let status_key = Self::status_key(status, id);
-            status_index.remove(status_key.as_str())?;
-        }
+        self.backend.write_tables(|txn| {
+            let removed = txn.table(ITEMS_TABLE)?.remove(id)?;
+
+            if removed {
+                txn.table(FOLDER_INDEX_TABLE)?
+                    .remove(&Self::folder_key(folder, id))?;
+                txn.table(STATUS_INDEX_TABLE)?
+                    .remove(&Self::status_key(status, id))?;
+            }
 
-        write_txn.commit()?;
-        Ok(removed)
+            Ok(removed)
+        })
     }
 
     pub fn list(&self) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let notes = read_txn.open_table(ITEMS_TABLE)?;
-
-        let mut items = Vec::new();
-        for row in notes.iter()? {
-            let (key, value) = row?;
-            items.push((key.value().to_string(), value.value().to_vec()));
-        }
-        Ok(items)
+        self.backend
+            .read_table(ITEMS_TABLE, |notes| notes.scan_prefix(""))
     }
 
     pub fn list_by_folder(&self, folder: &str) -> Result<Vec<(String, Vec<u8>)>> {
@@ -146,23 +113,23 @@ impl WorkItemStorage {
     fn list_by_index(
         &self,
         key: &str,
-        index_table: TableDefinition<&str, &str>,
+        index_table: &str,
         prefix_fn: fn(&str) -> String,
     ) -> Result<Vec<(String, Vec<u8>)>> {
-        let read_txn = self.db.begin_read()?;
-        let index = read_txn.open_table(index_table)?;
-        let notes = read_txn.open_table(ITEMS_TABLE)?;
-        let prefix = prefix_fn(key);
-        let (start, end) = prefix_range(&prefix);
-
-        let mut items = Vec::new();
-        for row in index.range(start.as_str()..end.as_str())? {
-            let (_index_key, note_id) = row?;
-            if let Some(value) = notes.get(note_id.value())? {
-                items.push((note_id.value().to_string(), value.value().to_vec()));
+        self.backend.read_tables(|txn| {
+            let index = txn.table(index_table)?;
+            let notes = txn.table(ITEMS_TABLE)?;
+            let prefix = prefix_fn(key);
+
+            let mut items = Vec::new();
+            for (_index_key, note_id) in index.scan_prefix(&prefix)? {
+                let note_id = text_value(note_id)?;
+                if let Some(value) = notes.get(&note_id)? {
+                    items.push((note_id, value));
+                }
             }
-        }
-        Ok(items)
+            Ok(items)
+        })
     }
 
     fn folder_key(folder: &str, id: &str) -> String {
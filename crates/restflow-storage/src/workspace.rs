@@ -0,0 +1,11 @@
+use crate::define_simple_storage;
+
+define_simple_storage! {
+    /// Byte-level storage for workspace users.
+    pub struct UserStorage { table: "users" }
+}
+
+define_simple_storage! {
+    /// Byte-level storage for workspace ownership of agents and skills.
+    pub struct WorkspaceResourceStorage { table: "workspace_resources" }
+}
@@ -185,6 +185,12 @@ pub struct LlmCallPayload {
     pub duration_ms: Option<u64>,
     pub is_reasoning: Option<bool>,
     pub message_count: Option<u32>,
+    #[serde(default)]
+    pub cache_read_tokens: Option<u32>,
+    #[serde(default)]
+    pub cache_write_tokens: Option<u32>,
+    #[serde(default)]
+    pub cache_savings_usd: Option<f64>,
 }
 
 /// Message payload carried by the canonical telemetry schema.
@@ -714,6 +720,9 @@ mod tests {
                 duration_ms: Some(321),
                 is_reasoning: Some(false),
                 message_count: Some(4),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+                cache_savings_usd: None,
             }),
         );
 
@@ -753,6 +762,9 @@ mod tests {
                         duration_ms: Some(123),
                         is_reasoning: None,
                         message_count: Some(4),
+                        cache_read_tokens: None,
+                        cache_write_tokens: None,
+                        cache_savings_usd: None,
                     }),
                 ),
                 artifact_preview: Some(TraceArtifactPreview {
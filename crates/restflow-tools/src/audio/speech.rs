@@ -0,0 +1,171 @@
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::time::Duration;
+
+const SPEECH_ENDPOINT: &str = "https://api.openai.com/v1/audio/speech";
+const DEFAULT_SPEECH_MODEL: &str = "gpt-4o-mini-tts";
+const DEFAULT_SPEECH_VOICE: &str = "alloy";
+/// Longest input the speech endpoint accepts, in characters.
+pub const MAX_SPEECH_INPUT_CHARS: usize = 4096;
+
+/// Audio container returned by the speech endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechFormat {
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    Wav,
+}
+
+impl SpeechFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mp3" => Some(Self::Mp3),
+            "opus" | "ogg" => Some(Self::Opus),
+            "aac" => Some(Self::Aac),
+            "flac" => Some(Self::Flac),
+            "wav" => Some(Self::Wav),
+            _ => None,
+        }
+    }
+
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Opus => "opus",
+            Self::Aac => "aac",
+            Self::Flac => "flac",
+            Self::Wav => "wav",
+        }
+    }
+
+    /// File extension for saved audio. Opus is delivered in an Ogg container,
+    /// which is what voice-note APIs (e.g. Telegram `sendVoice`) expect.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Opus => "ogg",
+            other => other.api_name(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpeechOptions {
+    pub model: String,
+    pub voice: String,
+    pub format: SpeechFormat,
+    /// Optional delivery instructions (tone, pacing). Ignored by `tts-1` models.
+    pub instructions: Option<String>,
+    pub request_timeout: Duration,
+}
+
+impl Default for SpeechOptions {
+    fn default() -> Self {
+        Self {
+            model: DEFAULT_SPEECH_MODEL.to_string(),
+            voice: DEFAULT_SPEECH_VOICE.to_string(),
+            format: SpeechFormat::Opus,
+            instructions: None,
+            request_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// Synthesize `text` with the OpenAI speech endpoint and return the encoded audio.
+pub async fn synthesize_speech(
+    api_key: &str,
+    text: &str,
+    options: &SpeechOptions,
+) -> Result<Vec<u8>> {
+    if api_key.trim().is_empty() {
+        return Err(anyhow!("API key is required."));
+    }
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(anyhow!("Text to synthesize is empty."));
+    }
+    let char_count = text.chars().count();
+    if char_count > MAX_SPEECH_INPUT_CHARS {
+        return Err(anyhow!(
+            "Text is {} characters; speech synthesis accepts at most {}.",
+            char_count,
+            MAX_SPEECH_INPUT_CHARS
+        ));
+    }
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(12))
+        .timeout(options.request_timeout)
+        .user_agent("RestFlow/0.1")
+        .build()
+        .context("Failed to build speech HTTP client")?;
+
+    let mut body = json!({
+        "model": options.model,
+        "input": text,
+        "voice": options.voice,
+        "response_format": options.format.api_name(),
+    });
+    if let Some(instructions) = options
+        .instructions
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        body["instructions"] = json!(instructions);
+    }
+
+    let response = client
+        .post(SPEECH_ENDPOINT)
+        .header("Authorization", format!("Bearer {api_key}"))
+        .json(&body)
+        .send()
+        .await
+        .context("Failed to reach OpenAI speech API")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!(
+            "OpenAI speech request failed with HTTP {}: {}",
+            status,
+            body
+        ));
+    }
+
+    let audio = response
+        .bytes()
+        .await
+        .context("Failed to read OpenAI speech response")?;
+    Ok(audio.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_format_parse_and_extension() {
+        assert_eq!(SpeechFormat::parse("OPUS"), Some(SpeechFormat::Opus));
+        assert_eq!(SpeechFormat::parse("ogg"), Some(SpeechFormat::Opus));
+        assert_eq!(SpeechFormat::parse("mp3"), Some(SpeechFormat::Mp3));
+        assert_eq!(SpeechFormat::parse("pcm"), None);
+        assert_eq!(SpeechFormat::Opus.extension(), "ogg");
+        assert_eq!(SpeechFormat::Wav.extension(), "wav");
+    }
+
+    #[tokio::test]
+    async fn test_synthesize_speech_validates_input() {
+        let options = SpeechOptions::default();
+        let err = synthesize_speech("", "hello", &options).await.unwrap_err();
+        assert!(err.to_string().contains("API key"));
+
+        let err = synthesize_speech("key", "   ", &options).await.unwrap_err();
+        assert!(err.to_string().contains("empty"));
+
+        let long = "a".repeat(MAX_SPEECH_INPUT_CHARS + 1);
+        let err = synthesize_speech("key", &long, &options).await.unwrap_err();
+        assert!(err.to_string().contains("at most"));
+    }
+}
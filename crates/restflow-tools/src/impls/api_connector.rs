@@ -0,0 +1,287 @@
+//! Tools generated from stored REST API connectors.
+//!
+//! Every connector operation is registered as its own tool. The store renders
+//! the request (URL, auth headers, body) from the tool arguments; the tool
+//! validates the target and sends it.
+
+use async_trait::async_trait;
+use restflow_traits::store::ApiConnectorStore;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::Result;
+use crate::http_client::build_ssrf_safe_client;
+use crate::impls::HttpTool;
+use crate::security::{SecurityGate, ToolAction, resolve_and_validate_url};
+use crate::{Tool, ToolErrorCategory, ToolOutput, check_security};
+
+#[derive(Debug, Deserialize)]
+struct OperationInfo {
+    connector: String,
+    operation: String,
+    tool_name: String,
+    #[serde(default)]
+    description: Option<String>,
+    parameters: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct PreparedRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: Value,
+}
+
+/// Calls one operation of a stored API connector.
+pub struct ApiConnectorTool {
+    store: Arc<dyn ApiConnectorStore>,
+    connector: String,
+    operation: String,
+    name: String,
+    description: String,
+    parameters: Value,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl ApiConnectorTool {
+    /// Build one tool per operation of `connector`, or of every connector.
+    pub fn from_store(
+        store: Arc<dyn ApiConnectorStore>,
+        connector: Option<&str>,
+    ) -> Result<Vec<Self>> {
+        let operations: Vec<OperationInfo> =
+            serde_json::from_value(store.list_operations(connector)?)?;
+        Ok(operations
+            .into_iter()
+            .map(|info| {
+                let summary = info
+                    .description
+                    .unwrap_or_else(|| format!("Call the {} operation", info.operation));
+                Self {
+                    store: store.clone(),
+                    description: format!(
+                        "{} (API connector '{}', operation '{}'). Returns status and body.",
+                        summary, info.connector, info.operation
+                    ),
+                    connector: info.connector,
+                    operation: info.operation,
+                    name: info.tool_name,
+                    parameters: info.parameters,
+                    security_gate: None,
+                    agent_id: None,
+                    task_id: None,
+                }
+            })
+            .collect())
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for ApiConnectorTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.parameters.clone()
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let request = match self
+            .store
+            .prepare_request(&self.connector, &self.operation, &input)
+        {
+            Ok(request) => request,
+            Err(error) => {
+                return Ok(ToolOutput::non_retryable_error(
+                    error.to_string(),
+                    ToolErrorCategory::Config,
+                ));
+            }
+        };
+        let request: PreparedRequest = serde_json::from_value(request)?;
+
+        let (parsed_url, pinned_addr) = match resolve_and_validate_url(&request.url).await {
+            Ok(value) => value,
+            Err(error) => {
+                return Ok(ToolOutput::non_retryable_error(
+                    format!("URL validation failed: {}", error),
+                    ToolErrorCategory::Config,
+                ));
+            }
+        };
+
+        // The query string may carry an API key, so keep it out of the audit trail.
+        let mut target = parsed_url.clone();
+        target.set_query(None);
+        let action = ToolAction {
+            tool_name: self.name.clone(),
+            operation: request.method.to_lowercase(),
+            target: target.to_string(),
+            summary: format!(
+                "API {}.{}: {} {}",
+                self.connector,
+                self.operation,
+                request.method.to_uppercase(),
+                target
+            ),
+        };
+        if let Some(message) = check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await?
+        {
+            return Ok(ToolOutput::non_retryable_error(
+                message,
+                ToolErrorCategory::Auth,
+            ));
+        }
+
+        let Ok(method) = reqwest::Method::from_bytes(request.method.to_uppercase().as_bytes())
+        else {
+            return Ok(ToolOutput::non_retryable_error(
+                format!("Unknown method: {}", request.method),
+                ToolErrorCategory::Config,
+            ));
+        };
+        let host = parsed_url.host_str().unwrap_or_default();
+        let client = build_ssrf_safe_client(host, pinned_addr).map_err(anyhow::Error::from)?;
+        let mut builder = client.request(method, parsed_url);
+        for (key, value) in &request.headers {
+            builder = builder.header(key, value);
+        }
+        if !request.body.is_null() {
+            builder = builder.json(&request.body);
+        }
+
+        let response = match builder.send().await {
+            Ok(response) => response,
+            Err(error) => {
+                return Ok(ToolOutput::retryable_error(
+                    format!("API request failed: {}", error),
+                    ToolErrorCategory::Network,
+                ));
+            }
+        };
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        let body = serde_json::from_str::<Value>(&body).unwrap_or_else(|_| json!({ "text": body }));
+
+        if status >= 400 {
+            let (category, retryable) = HttpTool::classify_status(status);
+            return Ok(ToolOutput {
+                success: false,
+                result: json!({ "status": status, "body": body }),
+                error: Some(format!("API request failed with status {}", status)),
+                error_category: Some(category),
+                retryable: Some(retryable),
+                retry_after_ms: None,
+            });
+        }
+        Ok(ToolOutput::success(
+            json!({ "status": status, "body": body }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToolError;
+
+    struct StaticStore;
+
+    impl ApiConnectorStore for StaticStore {
+        fn list_operations(&self, connector: Option<&str>) -> Result<Value> {
+            let operations = json!([
+                {
+                    "connector": "petstore",
+                    "operation": "list_pets",
+                    "tool_name": "petstore_list_pets",
+                    "description": "List pets",
+                    "parameters": { "type": "object", "properties": {} }
+                },
+                {
+                    "connector": "crm",
+                    "operation": "get_contact",
+                    "tool_name": "crm_get_contact",
+                    "parameters": { "type": "object", "properties": {} }
+                }
+            ]);
+            Ok(Value::Array(
+                operations
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter(|op| connector.is_none_or(|name| op["connector"] == name))
+                    .cloned()
+                    .collect(),
+            ))
+        }
+
+        fn prepare_request(
+            &self,
+            _connector: &str,
+            operation: &str,
+            _args: &Value,
+        ) -> Result<Value> {
+            match operation {
+                "list_pets" => Ok(json!({ "method": "GET", "url": "http://127.0.0.1/pets" })),
+                _ => Err(ToolError::Tool(
+                    "Missing required parameter 'id'".to_string(),
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn test_one_tool_per_operation() {
+        let tools = ApiConnectorTool::from_store(Arc::new(StaticStore), None).unwrap();
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name()).collect();
+        assert_eq!(names, vec!["petstore_list_pets", "crm_get_contact"]);
+        assert!(tools[0].description().starts_with("List pets"));
+
+        let tools = ApiConnectorTool::from_store(Arc::new(StaticStore), Some("crm")).unwrap();
+        assert_eq!(tools.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_prepare_and_validation_errors() {
+        let tools = ApiConnectorTool::from_store(Arc::new(StaticStore), None).unwrap();
+
+        let output = tools[1].execute(json!({})).await.unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("'id'"));
+
+        // Loopback targets are rejected before any request is sent.
+        let output = tools[0].execute(json!({})).await.unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("URL validation failed"));
+    }
+}
@@ -1,23 +1,29 @@
 //! Bash command execution tool for AI agents.
 
 use async_trait::async_trait;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Instant;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
-use tokio::time::{Duration, sleep, timeout};
+use tokio::sync::mpsc;
+use tokio::time::{Duration, sleep};
 
 #[cfg(unix)]
 use nix::sys::signal::{Signal, killpg};
 #[cfg(unix)]
 use nix::unistd::Pid;
 
+use restflow_sandbox::{LimitViolation, ResourceLimits};
+
+use super::container::{self, ContainerConfig, ContainerInvocation};
 use crate::Result;
 use crate::security::SecurityGate;
-use crate::{Tool, ToolErrorCategory, ToolOutput};
+use crate::{Tool, ToolErrorCategory, ToolOutput, emit_tool_output};
 
 /// Default timeout for command execution in seconds.
 const DEFAULT_TIMEOUT_SECS: u64 = 300;
@@ -36,6 +42,103 @@ pub struct BashTool {
     task_id: Option<String>,
     #[cfg(feature = "sandbox")]
     sandbox_policy: Option<restflow_sandbox::SandboxPolicy>,
+    resource_limits: ResourceLimits,
+    container: Option<ContainerConfig>,
+}
+
+/// Result of one finished command.
+struct CommandRun {
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+    truncated: bool,
+    limit_violation: Option<LimitViolation>,
+    /// Output line that matched `abort_on` and stopped the command.
+    aborted_on: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum OutputPipe {
+    Stdout,
+    Stderr,
+}
+
+/// Forward everything read from `pipe` to `tx` until it closes.
+async fn forward_pipe(
+    mut pipe: impl AsyncRead + Unpin,
+    kind: OutputPipe,
+    tx: mpsc::Sender<(OutputPipe, Vec<u8>)>,
+) {
+    let mut buf = vec![0u8; 8192];
+    loop {
+        match pipe.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                if tx.send((kind, buf[..n].to_vec())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Output captured from one pipe of a running command.
+#[derive(Default)]
+struct CapturedOutput {
+    bytes: Vec<u8>,
+    /// Bytes already decoded and streamed.
+    streamed: usize,
+    /// Text after the last newline, not yet matched against `abort_on`.
+    partial_line: String,
+}
+
+impl CapturedOutput {
+    /// Append `chunk` and return the newly decodable text. A multi-byte
+    /// character split across chunks is held back until it is complete.
+    fn push(&mut self, chunk: &[u8]) -> String {
+        self.bytes.extend_from_slice(chunk);
+        let mut text = String::new();
+        loop {
+            let rest = &self.bytes[self.streamed..];
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    self.streamed = self.bytes.len();
+                    break;
+                }
+                Err(error) => {
+                    let valid_len = error.valid_up_to();
+                    text.push_str(&String::from_utf8_lossy(&rest[..valid_len]));
+                    self.streamed += valid_len;
+                    match error.error_len() {
+                        Some(invalid_len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            self.streamed += invalid_len;
+                        }
+                        None => break,
+                    }
+                }
+            }
+        }
+        text
+    }
+
+    /// First complete line of `text` matching `pattern`.
+    fn match_lines(&mut self, text: &str, pattern: &Regex) -> Option<String> {
+        self.partial_line.push_str(text);
+        let end = self.partial_line.rfind('\n')?;
+        let complete: String = self.partial_line.drain(..=end).collect();
+        complete
+            .lines()
+            .find(|line| pattern.is_match(line))
+            .map(str::to_string)
+    }
+
+    /// Match the unterminated last line once the stream has closed.
+    fn match_rest(&mut self, pattern: &Regex) -> Option<String> {
+        let rest = std::mem::take(&mut self.partial_line);
+        pattern.is_match(&rest).then_some(rest)
+    }
 }
 
 impl Default for BashTool {
@@ -55,6 +158,8 @@ impl BashTool {
             task_id: None,
             #[cfg(feature = "sandbox")]
             sandbox_policy: None,
+            resource_limits: ResourceLimits::default(),
+            container: None,
         }
     }
 
@@ -79,6 +184,18 @@ impl BashTool {
         self
     }
 
+    /// Cap memory, CPU share and process count of every command's process tree.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = limits;
+        self
+    }
+
+    /// Run every command in an ephemeral container instead of on the host.
+    pub fn with_container(mut self, container: ContainerConfig) -> Self {
+        self.container = Some(container);
+        self
+    }
+
     pub fn with_security(
         mut self,
         security_gate: Arc<dyn SecurityGate>,
@@ -91,27 +208,38 @@ impl BashTool {
         self
     }
 
+    /// Host command line for `command`, wrapped by the sandbox if configured.
+    fn host_command(
+        &self,
+        command: &str,
+    ) -> std::result::Result<(String, Vec<String>), std::io::Error> {
+        #[cfg(feature = "sandbox")]
+        if let Some(ref policy) = self.sandbox_policy {
+            return restflow_sandbox::wrap_command(policy, "sh", &["-c", command])
+                .map_err(|e| std::io::Error::other(e.to_string()));
+        }
+        Ok((
+            "sh".to_string(),
+            vec!["-c".to_string(), command.to_string()],
+        ))
+    }
+
     async fn run_command(
         &self,
         command: &str,
         workdir: &str,
         timeout_secs: u64,
-    ) -> std::result::Result<(i32, String, String, bool), std::io::Error> {
-        #[cfg(feature = "sandbox")]
-        let (program, args) = if let Some(ref policy) = self.sandbox_policy {
-            restflow_sandbox::wrap_command(policy, "sh", &["-c", command])
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?
-        } else {
-            (
-                "sh".to_string(),
-                vec!["-c".to_string(), command.to_string()],
-            )
+        abort_on: Option<&Regex>,
+    ) -> std::result::Result<CommandRun, std::io::Error> {
+        // Containers enforce the limits themselves; the sandbox and host
+        // limits only apply to commands run directly on the host.
+        let invocation = self.container.as_ref().map(|container| {
+            container.invocation(Some(workdir), &self.resource_limits, &["sh", "-c", command])
+        });
+        let (program, args) = match &invocation {
+            Some(invocation) => (invocation.program.clone(), invocation.args.clone()),
+            None => self.host_command(command)?,
         };
-        #[cfg(not(feature = "sandbox"))]
-        let (program, args) = (
-            "sh".to_string(),
-            vec!["-c".to_string(), command.to_string()],
-        );
 
         let mut cmd = Command::new(&program);
         cmd.args(&args)
@@ -125,50 +253,150 @@ impl BashTool {
             cmd.process_group(0);
         }
 
+        let limits_guard = if self.resource_limits.is_unlimited() || invocation.is_some() {
+            None
+        } else {
+            let guard = self
+                .resource_limits
+                .prepare()
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            Some(Arc::new(guard))
+        };
+
+        // Join the cgroup before the sandbox hook restricts file system access.
+        #[cfg(unix)]
+        if let Some(guard) = limits_guard.clone() {
+            unsafe {
+                cmd.pre_exec(move || guard.pre_exec());
+            }
+        }
+
         #[cfg(all(unix, feature = "sandbox"))]
-        if let Some(ref policy) = self.sandbox_policy {
+        if let Some(ref policy) = self.sandbox_policy
+            && invocation.is_none()
+        {
             let policy = policy.clone();
             unsafe {
                 cmd.pre_exec(move || {
                     restflow_sandbox::pre_exec_hook(&policy)
-                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+                        .map_err(|e| std::io::Error::other(e.to_string()))
                 });
             }
         }
 
-        let child = cmd.spawn()?;
+        let mut child = cmd.spawn()?;
+        #[cfg(windows)]
+        if let Some(guard) = limits_guard.as_ref()
+            && let Some(handle) = child.raw_handle()
+        {
+            guard
+                .attach(handle)
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+        }
         #[cfg(unix)]
         let process_group_id = child.id().map(|pid| pid as i32);
 
-        let output =
-            match timeout(Duration::from_secs(timeout_secs), child.wait_with_output()).await {
-                Ok(result) => result?,
-                Err(_) => {
-                    #[cfg(unix)]
-                    if let Some(process_group_id) = process_group_id {
-                        let pgid = Pid::from_raw(process_group_id);
-                        let _ = killpg(pgid, Signal::SIGTERM);
-                        sleep(Duration::from_millis(500)).await;
-                        let _ = killpg(pgid, Signal::SIGKILL);
+        // Stream both pipes as they produce output, so the chat shows
+        // progress and `abort_on` can stop the command early.
+        let (chunk_tx, mut chunk_rx) = mpsc::channel::<(OutputPipe, Vec<u8>)>(64);
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_pipe(stdout, OutputPipe::Stdout, chunk_tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_pipe(stderr, OutputPipe::Stderr, chunk_tx.clone()));
+        }
+        drop(chunk_tx);
+
+        let mut stdout = CapturedOutput::default();
+        let mut stderr = CapturedOutput::default();
+        let mut aborted_on = None;
+        let mut pipes_open = true;
+        let deadline = sleep(Duration::from_secs(timeout_secs));
+        tokio::pin!(deadline);
+
+        let status = loop {
+            tokio::select! {
+                chunk = chunk_rx.recv(), if pipes_open => {
+                    let Some((pipe, bytes)) = chunk else {
+                        pipes_open = false;
+                        if let Some(pattern) = abort_on.filter(|_| aborted_on.is_none()) {
+                            aborted_on = stdout.match_rest(pattern).or_else(|| stderr.match_rest(pattern));
+                        }
+                        continue;
+                    };
+                    let captured = match pipe {
+                        OutputPipe::Stdout => &mut stdout,
+                        OutputPipe::Stderr => &mut stderr,
+                    };
+                    let text = captured.push(&bytes);
+                    emit_tool_output(&text);
+                    if let Some(pattern) = abort_on.filter(|_| aborted_on.is_none())
+                        && let Some(line) = captured.match_lines(&text, pattern)
+                    {
+                        aborted_on = Some(line);
+                        self.stop_command(
+                            #[cfg(unix)]
+                            process_group_id,
+                            invocation.as_ref(),
+                        )
+                        .await;
+                        let _ = child.start_kill();
                     }
+                }
+                status = child.wait(), if !pipes_open => break status?,
+                _ = &mut deadline => {
+                    self.stop_command(
+                        #[cfg(unix)]
+                        process_group_id,
+                        invocation.as_ref(),
+                    )
+                    .await;
 
                     return Err(std::io::Error::new(
                         std::io::ErrorKind::TimedOut,
                         format!("Timeout after {timeout_secs} seconds"),
                     ));
                 }
-            };
+            }
+        };
 
-        let exit_code = output.status.code().unwrap_or(-1);
-        let (stdout, stdout_truncated) = self.truncate_output(&output.stdout);
-        let (stderr, stderr_truncated) = self.truncate_output(&output.stderr);
+        let exit_code = status.code().unwrap_or(-1);
+        let (stdout, stdout_truncated) = self.truncate_output(&stdout.bytes);
+        let (stderr, stderr_truncated) = self.truncate_output(&stderr.bytes);
+        let limit_violation = match &invocation {
+            Some(_) => container::limit_violation(&self.resource_limits, &status),
+            None => limits_guard
+                .as_ref()
+                .and_then(|guard| guard.violation(&status)),
+        };
 
-        Ok((
+        Ok(CommandRun {
             exit_code,
             stdout,
             stderr,
-            stdout_truncated || stderr_truncated,
-        ))
+            truncated: stdout_truncated || stderr_truncated,
+            limit_violation,
+            aborted_on,
+        })
+    }
+
+    /// Terminate the command's process group, or its container.
+    async fn stop_command(
+        &self,
+        #[cfg(unix)] process_group_id: Option<i32>,
+        invocation: Option<&ContainerInvocation>,
+    ) {
+        #[cfg(unix)]
+        if let Some(process_group_id) = process_group_id {
+            let pgid = Pid::from_raw(process_group_id);
+            let _ = killpg(pgid, Signal::SIGTERM);
+            sleep(Duration::from_millis(500)).await;
+            let _ = killpg(pgid, Signal::SIGKILL);
+        }
+        // Killing the engine client leaves the container running.
+        if let (Some(container), Some(invocation)) = (&self.container, invocation) {
+            container.kill(&invocation.name).await;
+        }
     }
 
     fn truncate_output(&self, bytes: &[u8]) -> (String, bool) {
@@ -279,6 +507,9 @@ pub struct BashInput {
     pub workdir: Option<String>,
     #[serde(default)]
     pub timeout: Option<u64>,
+    /// Regular expression; the command is stopped once an output line matches.
+    #[serde(default)]
+    pub abort_on: Option<String>,
     #[serde(default)]
     pub yolo_mode: bool,
 }
@@ -291,6 +522,12 @@ pub struct BashOutput {
     pub stderr: String,
     pub truncated: bool,
     pub duration_ms: u64,
+    /// Resource limit the command ran into, when that is why it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_limit: Option<String>,
+    /// Output line that matched `abort_on` and stopped the command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aborted_on: Option<String>,
 }
 
 #[async_trait]
@@ -318,6 +555,10 @@ impl Tool for BashTool {
                 "timeout": {
                     "type": "integer",
                     "description": "Timeout in seconds (default: 300)"
+                },
+                "abort_on": {
+                    "type": "string",
+                    "description": "Regular expression matched against each output line; the command is stopped at the first match, e.g. \"^error\" to end a failing build early"
                 }
             },
             "required": ["command"]
@@ -333,6 +574,15 @@ impl Tool for BashTool {
         };
 
         let timeout_secs = input.timeout.unwrap_or(self.timeout_secs);
+        let abort_on = match input.abort_on.as_deref().map(Regex::new).transpose() {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                return Ok(ToolOutput::non_retryable_error(
+                    format!("Invalid abort_on pattern: {}", e),
+                    ToolErrorCategory::Config,
+                ));
+            }
+        };
 
         // yolo_mode is an explicit caller opt-out for policy checks.
         // Keep default as gated when a security gate is present.
@@ -383,28 +633,48 @@ impl Tool for BashTool {
         let start = Instant::now();
 
         let result = self
-            .run_command(&input.command, &workdir, timeout_secs)
+            .run_command(&input.command, &workdir, timeout_secs, abort_on.as_ref())
             .await;
 
         let duration_ms = start.elapsed().as_millis() as u64;
 
         match result {
-            Ok((exit_code, stdout, stderr, truncated)) => {
-                let failure_meta =
-                    (exit_code != 0).then(|| Self::classify_command_failure(&stderr));
+            Ok(run) => {
+                let exit_code = run.exit_code;
+                let failure_meta = if run.limit_violation.is_some() || run.aborted_on.is_some() {
+                    Some((ToolErrorCategory::Execution, false))
+                } else {
+                    (exit_code != 0).then(|| Self::classify_command_failure(&run.stderr))
+                };
+                let error = match (&run.aborted_on, run.limit_violation) {
+                    (Some(line), _) => Some(format!(
+                        "Command stopped because output matched abort_on: {}",
+                        line
+                    )),
+                    (None, Some(violation)) => Some(format!(
+                        "Command exited with code {}: {}",
+                        exit_code, violation
+                    )),
+                    (None, None) => {
+                        (exit_code != 0).then(|| format!("Command exited with code {}", exit_code))
+                    }
+                };
+                let success =
+                    exit_code == 0 && run.limit_violation.is_none() && run.aborted_on.is_none();
                 let output = BashOutput {
                     exit_code,
-                    stdout,
-                    stderr,
-                    truncated,
+                    stdout: run.stdout,
+                    stderr: run.stderr,
+                    truncated: run.truncated,
                     duration_ms,
+                    resource_limit: run.limit_violation.map(|violation| violation.to_string()),
+                    aborted_on: run.aborted_on,
                 };
 
                 Ok(ToolOutput {
-                    success: exit_code == 0,
+                    success,
                     result: serde_json::to_value(&output)?,
-                    error: (exit_code != 0)
-                        .then(|| format!("Command exited with code {}", exit_code)),
+                    error,
                     error_category: failure_meta.as_ref().map(|(category, _)| category.clone()),
                     retryable: failure_meta.map(|(_, retryable)| retryable),
                     retry_after_ms: None,
@@ -513,6 +783,105 @@ mod tests {
         assert!(output.error.as_ref().unwrap().contains("Timeout"));
     }
 
+    #[test]
+    fn test_captured_output_holds_back_split_characters() {
+        let mut captured = CapturedOutput::default();
+        let bytes = "né\n".as_bytes();
+        assert_eq!(captured.push(&bytes[..2]), "n");
+        assert_eq!(captured.push(&bytes[2..]), "é\n");
+        assert_eq!(captured.bytes, bytes);
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_bash_tool_streams_output() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool = BashTool::new().with_workdir(temp.path().to_string_lossy().into_owned());
+        let streamed = Arc::new(std::sync::Mutex::new(String::new()));
+        let sink_streamed = streamed.clone();
+        let sink: crate::ToolOutputSink =
+            Arc::new(move |output: &str| sink_streamed.lock().unwrap().push_str(output));
+
+        let output = crate::with_tool_output_sink(
+            sink,
+            tool.execute(serde_json::json!({
+                "command": "echo first; echo second >&2"
+            })),
+        )
+        .await
+        .unwrap();
+
+        assert!(output.success);
+        let streamed = streamed.lock().unwrap();
+        assert!(streamed.contains("first\n"));
+        assert!(streamed.contains("second\n"));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_bash_tool_aborts_on_matching_output() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool = BashTool::new().with_workdir(temp.path().to_string_lossy().into_owned());
+        let start = Instant::now();
+        let output = tool
+            .execute(serde_json::json!({
+                "command": "echo building; echo 'error: missing semicolon'; sleep 10",
+                "abort_on": "^error"
+            }))
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!output.success);
+        let result: BashOutput = serde_json::from_value(output.result).unwrap();
+        assert_eq!(
+            result.aborted_on.as_deref(),
+            Some("error: missing semicolon")
+        );
+        assert!(result.stdout.contains("building"));
+    }
+
+    #[tokio::test]
+    async fn test_bash_tool_rejects_invalid_abort_pattern() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool = BashTool::new().with_workdir(temp.path().to_string_lossy().into_owned());
+        let output = tool
+            .execute(serde_json::json!({
+                "command": "true",
+                "abort_on": "("
+            }))
+            .await
+            .unwrap();
+
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("Invalid abort_on pattern"));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_bash_tool_execute_with_resource_limits() {
+        let temp = tempfile::tempdir().unwrap();
+        let tool = BashTool::new()
+            .with_workdir(temp.path().to_string_lossy().into_owned())
+            .with_resource_limits(ResourceLimits {
+                memory_bytes: Some(512 * 1024 * 1024),
+                cpu_weight: Some(50),
+                max_processes: Some(64),
+                cpu_time_secs: Some(60),
+            });
+        let output = tool
+            .execute(serde_json::json!({
+                "command": "echo limited"
+            }))
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        let result: BashOutput = serde_json::from_value(output.result).unwrap();
+        assert!(result.stdout.contains("limited"));
+        assert!(result.resource_limit.is_none());
+    }
+
     #[tokio::test]
     async fn test_bash_tool_requires_explicit_workdir() {
         let tool = BashTool::new();
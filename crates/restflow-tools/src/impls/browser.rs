@@ -4,11 +4,17 @@
 //! styles:
 //! - Direct JS/TS code execution (AI can write browser code)
 //! - Structured action plans for common web automation steps
+//!
+//! When a vision tool is attached, `run_actions` can optionally retry failed
+//! `click`/`fill` actions by locating the target in the failure screenshot and
+//! clicking its coordinates instead of the selector.
 
 use async_trait::async_trait;
 use restflow_browser::{
-    BrowserAction, BrowserService, NewSessionRequest, RunActionsRequest, RunScriptRequest,
-    ScriptLanguage, ScriptRuntime,
+    ArtifactRetentionPolicy, BrowserAction, BrowserExecutionResult, BrowserFailureSnapshot,
+    BrowserService, MouseButton, NavigationPolicy, NetworkMode, NewSessionRequest,
+    RunActionsRequest, RunScriptRequest, ScriptEventSender, ScriptLanguage, ScriptRuntime,
+    ScriptSandbox,
 };
 use restflow_traits::DEFAULT_AGENT_BROWSER_TIMEOUT_SECS;
 use serde::Deserialize;
@@ -22,14 +28,28 @@ use crate::{Tool, ToolErrorCategory, ToolOutput};
 #[serde(tag = "action", rename_all = "snake_case")]
 enum BrowserInput {
     ProbeRuntime,
+    InstallRuntime,
     NewSession {
         #[serde(default)]
         headless: Option<bool>,
+        #[serde(default)]
+        connect_over_cdp: Option<String>,
+        #[serde(default)]
+        network_mode: Option<NetworkMode>,
+        #[serde(default)]
+        fixtures_dir: Option<String>,
     },
     ListSessions,
     CloseSession {
         session_id: String,
     },
+    SessionStats {
+        session_id: String,
+    },
+    PurgeArtifacts {
+        #[serde(default)]
+        session_id: Option<String>,
+    },
     RunScript {
         session_id: String,
         code: String,
@@ -41,6 +61,10 @@ enum BrowserInput {
         timeout_secs: Option<u64>,
         #[serde(default)]
         cwd: Option<String>,
+        #[serde(default)]
+        sandbox: Option<ScriptSandbox>,
+        #[serde(default)]
+        args: Option<Value>,
     },
     RunActions {
         session_id: String,
@@ -51,13 +75,30 @@ enum BrowserInput {
         timeout_secs: Option<u64>,
         #[serde(default)]
         cwd: Option<String>,
+        #[serde(default)]
+        vision_fallback: bool,
     },
 }
 
+/// Failed selector actions retried through vision location per run.
+const MAX_VISION_FALLBACKS: usize = 3;
+
+/// Sets the focused element's value after a vision-located click, mirroring
+/// what the `fill` action does for a selector.
+const FILL_FOCUSED_ELEMENT_SCRIPT: &str = "(() => { const element = document.activeElement; if (!element || element === document.body) throw new Error('No element focused after vision click'); element.value = args.text; element.dispatchEvent(new Event('input', { bubbles: true })); element.dispatchEvent(new Event('change', { bubbles: true })); return true; })()";
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+struct VisionPoint {
+    x: f64,
+    y: f64,
+}
+
 /// Browser automation tool.
 pub struct BrowserTool {
     service: Arc<BrowserService>,
     default_timeout_secs: u64,
+    script_events: Option<ScriptEventSender>,
+    vision: Option<Arc<dyn Tool>>,
 }
 
 impl BrowserTool {
@@ -66,9 +107,29 @@ impl BrowserTool {
     }
 
     pub fn new_with_timeout(default_timeout_secs: u64) -> Result<Self> {
+        Self::new_with_policy(
+            default_timeout_secs,
+            NavigationPolicy::default(),
+            ArtifactRetentionPolicy::default(),
+        )
+    }
+
+    /// Create a tool whose sessions only navigate where `navigation` allows
+    /// and keep artifacts within `retention`.
+    pub fn new_with_policy(
+        default_timeout_secs: u64,
+        navigation: NavigationPolicy,
+        retention: ArtifactRetentionPolicy,
+    ) -> Result<Self> {
         Ok(Self {
-            service: Arc::new(BrowserService::new()?),
+            service: Arc::new(
+                BrowserService::new()?
+                    .with_navigation_policy(navigation)
+                    .with_artifact_retention(retention),
+            ),
             default_timeout_secs,
+            script_events: None,
+            vision: None,
         })
     }
 
@@ -76,7 +137,124 @@ impl BrowserTool {
         Self {
             service,
             default_timeout_secs: DEFAULT_AGENT_BROWSER_TIMEOUT_SECS,
+            script_events: None,
+            vision: None,
+        }
+    }
+
+    /// Timeout used when a call does not pass `timeout_secs`.
+    pub fn with_default_timeout(mut self, timeout_secs: u64) -> Self {
+        self.default_timeout_secs = timeout_secs;
+        self
+    }
+
+    /// Stream `rf.emit` events from run_script to `sender` while scripts run.
+    /// Emitted events are also returned in the final result either way.
+    pub fn with_script_events(mut self, sender: ScriptEventSender) -> Self {
+        self.script_events = Some(sender);
+        self
+    }
+
+    /// Use `vision` (normally the `vision` tool) to locate elements when a
+    /// `run_actions` call sets `vision_fallback`.
+    pub fn with_vision_fallback(mut self, vision: Arc<dyn Tool>) -> Self {
+        self.vision = Some(vision);
+        self
+    }
+
+    /// Ask the vision tool where `target` is in `screenshot_path`.
+    async fn locate_with_vision(
+        vision: &dyn Tool,
+        screenshot_path: &str,
+        target: &str,
+    ) -> std::result::Result<VisionPoint, String> {
+        let prompt = format!(
+            "This is a screenshot of a web page. Locate {target}. Reply with only a JSON object {{\"x\": <number>, \"y\": <number>}} giving the pixel coordinates of the center of that element in the screenshot, or {{\"found\": false}} if it is not visible."
+        );
+        let output = vision
+            .execute(json!({ "file_path": screenshot_path, "prompt": prompt }))
+            .await
+            .map_err(|error| error.to_string())?;
+        if !output.success {
+            return Err(output
+                .error
+                .unwrap_or_else(|| "Vision request failed".to_string()));
+        }
+        let text = output
+            .result
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        parse_vision_point(text)
+            .ok_or_else(|| format!("Vision could not locate {target}: {}", text.trim()))
+    }
+
+    /// Run `request`, retrying failed click/fill actions at the coordinates
+    /// the vision tool reports. Returns the final execution together with a
+    /// record of each fallback attempt.
+    async fn run_actions_with_vision(
+        &self,
+        vision: &dyn Tool,
+        mut request: RunActionsRequest,
+    ) -> Result<(BrowserExecutionResult, Vec<Value>)> {
+        // Index of each pending action in the caller's original plan.
+        let mut origins: Vec<usize> = (0..request.actions.len()).collect();
+        let mut fallbacks = Vec::new();
+        let mut execution = self.service.run_actions(&request).await?;
+
+        while execution.exit_code != 0 && fallbacks.len() < MAX_VISION_FALLBACKS {
+            let Some(failure) = execution
+                .payload
+                .as_ref()
+                .and_then(|payload| payload.get("failure"))
+                .and_then(|failure| {
+                    serde_json::from_value::<BrowserFailureSnapshot>(failure.clone()).ok()
+                })
+            else {
+                break;
+            };
+            let index = failure.action_index;
+            let (Some(action), Some(screenshot)) = (
+                request.actions.get(index),
+                failure.screenshot_path.as_deref(),
+            ) else {
+                break;
+            };
+            let Some(target) = vision_target(action) else {
+                break;
+            };
+
+            let point = match Self::locate_with_vision(vision, screenshot, &target).await {
+                Ok(point) => point,
+                Err(error) => {
+                    fallbacks.push(json!({
+                        "action_index": origins[index],
+                        "target": target,
+                        "error": error,
+                    }));
+                    break;
+                }
+            };
+            fallbacks.push(json!({
+                "action_index": origins[index],
+                "target": target,
+                "x": point.x,
+                "y": point.y,
+            }));
+
+            let replacement = vision_replacement(action, point);
+            let origin = origins[index];
+            let mut actions = replacement.clone();
+            actions.extend_from_slice(&request.actions[index + 1..]);
+            let mut next_origins = vec![origin; replacement.len()];
+            next_origins.extend_from_slice(&origins[index + 1..]);
+            request.actions = actions;
+            origins = next_origins;
+
+            execution = self.service.run_actions(&request).await?;
         }
+
+        Ok((execution, fallbacks))
     }
 
     fn format_execution_failure(message: String, details: Value) -> ToolOutput {
@@ -93,7 +271,7 @@ impl Tool for BrowserTool {
     }
 
     fn description(&self) -> &str {
-        "Chromium browser automation for AI agents using native CDP. Supports session lifecycle, direct JS/TS execution in page context, and structured actions including navigation, form interactions, keyboard/mouse input, extraction, and screenshots."
+        "Chromium browser automation for AI agents using native CDP. Supports runtime probing and install_runtime (downloads a bundled chrome-headless-shell when no Chrome is present), session lifecycle, direct JS/TS execution in page context, and structured actions including navigation, form interactions, keyboard/mouse input, extraction, and screenshots."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -105,23 +283,40 @@ impl Tool for BrowserTool {
                     "description": "Operation to perform",
                     "enum": [
                         "probe_runtime",
+                        "install_runtime",
                         "new_session",
                         "list_sessions",
                         "close_session",
+                        "session_stats",
+                        "purge_artifacts",
                         "run_script",
                         "run_actions"
                     ]
                 },
-                "session_id": { "type": "string", "description": "Browser session ID" },
+                "session_id": { "type": "string", "description": "Browser session ID. Optional for purge_artifacts, which covers every session when omitted" },
                 "headless": { "type": "boolean", "description": "Run Chromium in headless mode for new_session" },
-                "code": { "type": "string", "description": "JavaScript/TypeScript code for run_script" },
+                "connect_over_cdp": { "type": "string", "description": "Attach new_session to a running browser at a ws:// debugger URL or http://host:port CDP endpoint instead of launching one" },
+                "network_mode": {
+                    "type": "string",
+                    "enum": ["live", "record", "replay"],
+                    "description": "Network mode for new_session: live (default), record (save every response as a fixture), replay (serve recorded fixtures without network access)"
+                },
+                "fixtures_dir": { "type": "string", "description": "Directory holding network_fixtures.json for record/replay sessions. Required for replay" },
+                "code": { "type": "string", "description": "JavaScript/TypeScript code for run_script. The script can read `args` and call rf.emit(value) to report partial results before it finishes" },
+                "args": { "type": "object", "description": "JSON object exposed to run_script as `args`. evaluate actions accept the same `args` field" },
                 "language": { "type": "string", "enum": ["js", "ts"], "description": "Script language for run_script" },
                 "runtime": { "type": "string", "enum": ["auto", "node"], "description": "Execution runtime" },
                 "timeout_secs": { "type": "integer", "description": "Execution timeout in seconds" },
                 "cwd": { "type": "string", "description": "Optional working directory" },
+                "vision_fallback": { "type": "boolean", "description": "For run_actions: when a click or fill action fails, locate its target in a screenshot with the vision model and click the returned coordinates instead. Uses the action's `description` (or its selector) to describe the target" },
+                "sandbox": {
+                    "type": "string",
+                    "enum": ["none", "isolated", "restricted"],
+                    "description": "World for run_script: none (page world), isolated (separate JS world sharing the DOM), restricted (isolated with cookie/storage APIs blocked). evaluate actions accept the same `sandbox` field."
+                },
                 "actions": {
                     "type": "array",
-                    "description": "Structured browser actions for run_actions: navigate, click, fill, type, press, key_down, key_up, mouse_move, mouse_down, mouse_up, mouse_click, mouse_wheel, wait_for_selector, extract_text, screenshot, evaluate.",
+                    "description": "Structured browser actions for run_actions: navigate, click, fill, type, press, key_down, key_up, mouse_move, mouse_down, mouse_up, mouse_click, mouse_wheel, wait_for_selector, extract_text, screenshot, set_clipboard, read_clipboard, evaluate, parallel. click and fill accept an optional `description` of the target element for vision_fallback. set_clipboard takes `text`; read_clipboard returns the clipboard text and needs an http(s) page. A parallel action takes `branches` (an array of action arrays, each run in its own tab) and optional `max_concurrency`.",
                     "items": {
                         "type": "object",
                         "properties": {
@@ -143,7 +338,10 @@ impl Tool for BrowserTool {
                                     "wait_for_selector",
                                     "extract_text",
                                     "screenshot",
-                                    "evaluate"
+                                    "set_clipboard",
+                                    "read_clipboard",
+                                    "evaluate",
+                                    "parallel"
                                 ]
                             }
                         },
@@ -174,11 +372,23 @@ impl Tool for BrowserTool {
                 let probe = self.service.probe_runtime().await?;
                 Ok(ToolOutput::success(serde_json::to_value(probe)?))
             }
-            BrowserInput::NewSession { headless } => {
+            BrowserInput::InstallRuntime => {
+                let installed = self.service.install_runtime(None).await?;
+                Ok(ToolOutput::success(serde_json::to_value(installed)?))
+            }
+            BrowserInput::NewSession {
+                headless,
+                connect_over_cdp,
+                network_mode,
+                fixtures_dir,
+            } => {
                 let session = self
                     .service
                     .new_session(NewSessionRequest {
                         headless: headless.unwrap_or(true),
+                        cdp_endpoint: connect_over_cdp,
+                        network_mode: network_mode.unwrap_or_default(),
+                        fixtures_dir,
                         ..Default::default()
                     })
                     .await?;
@@ -195,6 +405,17 @@ impl Tool for BrowserTool {
                     "closed": closed
                 })))
             }
+            BrowserInput::SessionStats { session_id } => {
+                let stats = self.service.session_stats(&session_id).await?;
+                Ok(ToolOutput::success(serde_json::to_value(stats)?))
+            }
+            BrowserInput::PurgeArtifacts { session_id } => {
+                let report = self.service.purge_artifacts(session_id.as_deref()).await?;
+                Ok(ToolOutput::success(json!({
+                    "report": report,
+                    "totals": self.service.artifact_retention_stats(),
+                })))
+            }
             BrowserInput::RunScript {
                 session_id,
                 code,
@@ -202,18 +423,27 @@ impl Tool for BrowserTool {
                 runtime,
                 timeout_secs,
                 cwd,
+                sandbox,
+                args,
             } => {
-                let execution = self
-                    .service
-                    .run_script(&RunScriptRequest {
-                        session_id,
-                        code,
-                        language: language.unwrap_or_default(),
-                        runtime: runtime.unwrap_or_default(),
-                        timeout_secs: timeout_secs.unwrap_or(self.default_timeout_secs),
-                        cwd,
-                    })
-                    .await?;
+                let request = RunScriptRequest {
+                    session_id,
+                    code,
+                    language: language.unwrap_or_default(),
+                    runtime: runtime.unwrap_or_default(),
+                    timeout_secs: timeout_secs.unwrap_or(self.default_timeout_secs),
+                    cwd,
+                    sandbox: sandbox.unwrap_or_default(),
+                    args,
+                };
+                let execution = match &self.script_events {
+                    Some(sender) => {
+                        self.service
+                            .run_script_with_events(&request, sender.clone())
+                            .await?
+                    }
+                    None => self.service.run_script(&request).await?,
+                };
 
                 if execution.exit_code != 0 {
                     let details = serde_json::to_value(&execution)?;
@@ -231,38 +461,115 @@ impl Tool for BrowserTool {
                 runtime,
                 timeout_secs,
                 cwd,
+                vision_fallback,
             } => {
-                let execution = self
+                if let Err(error) = self
                     .service
-                    .run_actions(&RunActionsRequest {
-                        session_id,
-                        actions,
-                        runtime: runtime.unwrap_or_default(),
-                        timeout_secs: timeout_secs.unwrap_or(self.default_timeout_secs),
-                        cwd,
-                    })
-                    .await?;
+                    .navigation_policy()
+                    .check_actions(&actions)
+                    .await
+                {
+                    return Ok(ToolOutput::non_retryable_error(
+                        error.to_string(),
+                        ToolErrorCategory::Auth,
+                    ));
+                }
+
+                let request = RunActionsRequest {
+                    session_id,
+                    actions,
+                    runtime: runtime.unwrap_or_default(),
+                    timeout_secs: timeout_secs.unwrap_or(self.default_timeout_secs),
+                    cwd,
+                };
+                let (execution, fallbacks) = match self.vision.as_deref() {
+                    Some(vision) if vision_fallback => {
+                        self.run_actions_with_vision(vision, request).await?
+                    }
+                    _ => (self.service.run_actions(&request).await?, Vec::new()),
+                };
+
+                let mut details = serde_json::to_value(&execution)?;
+                if !fallbacks.is_empty() {
+                    details["vision_fallbacks"] = Value::Array(fallbacks);
+                }
 
                 if execution.exit_code != 0 {
-                    let details = serde_json::to_value(&execution)?;
                     return Ok(Self::format_execution_failure(
                         execution.failed_message(),
                         details,
                     ));
                 }
 
-                Ok(ToolOutput::success(serde_json::to_value(execution)?))
+                Ok(ToolOutput::success(details))
             }
         }
     }
 }
 
+/// Describe the element a selector action targets, if vision can stand in
+/// for it.
+fn vision_target(action: &BrowserAction) -> Option<String> {
+    match action {
+        BrowserAction::Click {
+            selector,
+            description,
+            ..
+        }
+        | BrowserAction::Fill {
+            selector,
+            description,
+            ..
+        } => Some(
+            description
+                .clone()
+                .unwrap_or_else(|| format!("the element matching CSS selector `{selector}`")),
+        ),
+        _ => None,
+    }
+}
+
+/// Coordinate-based actions that replace a failed selector action.
+fn vision_replacement(action: &BrowserAction, point: VisionPoint) -> Vec<BrowserAction> {
+    let click = BrowserAction::MouseClick {
+        x: point.x,
+        y: point.y,
+        button: MouseButton::default(),
+        click_count: 1,
+        modifiers: Vec::new(),
+    };
+    match action {
+        BrowserAction::Fill { text, .. } => vec![
+            click,
+            BrowserAction::Evaluate {
+                expression: FILL_FOCUSED_ELEMENT_SCRIPT.to_string(),
+                sandbox: ScriptSandbox::default(),
+                args: Some(json!({ "text": text })),
+            },
+        ],
+        _ => vec![click],
+    }
+}
+
+/// Pull `{"x": .., "y": ..}` out of a vision reply, tolerating prose or code
+/// fences around the JSON object.
+fn parse_vision_point(text: &str) -> Option<VisionPoint> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    let point: VisionPoint = serde_json::from_str(&text[start..=end]).ok()?;
+    (point.x.is_finite() && point.y.is_finite() && point.x >= 0.0 && point.y >= 0.0)
+        .then_some(point)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use restflow_browser::{BrowserExecutionResult, RuntimeProbe};
     use restflow_browser::{BrowserExecutor, RunActionsRequest, RunScriptRequest};
-    use restflow_browser::{BrowserKind, BrowserSession};
+    use restflow_browser::{BrowserKind, BrowserSession, NetworkMode, ScriptEvent};
     use std::sync::atomic::{AtomicUsize, Ordering};
     use tempfile::tempdir;
 
@@ -299,9 +606,25 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 payload: Some(json!({"success": true})),
+                trace_path: None,
             })
         }
 
+        async fn run_script_with_events(
+            &self,
+            session: &BrowserSession,
+            request: &RunScriptRequest,
+            events: ScriptEventSender,
+        ) -> anyhow::Result<BrowserExecutionResult> {
+            let _ = events.send(ScriptEvent {
+                session_id: session.id.clone(),
+                sequence: 0,
+                offset_ms: 0,
+                payload: request.args.clone().unwrap_or(Value::Null),
+            });
+            self.run_script(session, request).await
+        }
+
         async fn run_actions(
             &self,
             _session: &BrowserSession,
@@ -315,6 +638,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 payload: Some(json!({"success": true, "result": []})),
+                trace_path: None,
             })
         }
     }
@@ -353,6 +677,21 @@ mod tests {
         assert!(list.success);
         assert_eq!(list.result.as_array().map(|v| v.len()), Some(1));
 
+        let stats = tool
+            .execute(json!({ "action": "session_stats", "session_id": session_id }))
+            .await
+            .unwrap();
+        assert!(stats.success);
+        assert_eq!(stats.result["session_id"], json!(session_id));
+
+        let purged = tool
+            .execute(json!({ "action": "purge_artifacts", "session_id": session_id }))
+            .await
+            .unwrap();
+        assert!(purged.success);
+        assert_eq!(purged.result["report"]["files_removed"], json!(0));
+        assert_eq!(purged.result["totals"]["purges"], json!(1));
+
         let closed = tool
             .execute(json!({ "action": "close_session", "session_id": session_id }))
             .await
@@ -385,6 +724,33 @@ mod tests {
         assert_eq!(output.result["runtime"], json!("mock"));
     }
 
+    #[tokio::test]
+    async fn run_script_streams_events_when_sender_configured() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let tool = test_tool().with_script_events(sender);
+
+        let created = tool
+            .execute(json!({ "action": "new_session" }))
+            .await
+            .unwrap();
+        let session_id = created.result["id"].as_str().unwrap();
+
+        let output = tool
+            .execute(json!({
+                "action": "run_script",
+                "session_id": session_id,
+                "code": "rf.emit(args);",
+                "args": { "page": 2 }
+            }))
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.session_id, session_id);
+        assert_eq!(event.payload, json!({ "page": 2 }));
+    }
+
     #[tokio::test]
     async fn run_script_accepts_typescript_language() {
         let tool = test_tool();
@@ -473,6 +839,9 @@ mod tests {
             session_dir: "/tmp/s1".to_string(),
             profile_dir: "/tmp/s1/profile".to_string(),
             artifacts_dir: "/tmp/s1/artifacts".to_string(),
+            cdp_endpoint: None,
+            network_mode: NetworkMode::Live,
+            fixtures_dir: None,
         };
 
         assert_eq!(session.browser, BrowserKind::Chromium);
@@ -491,4 +860,208 @@ mod tests {
 
         assert!(output.is_err());
     }
+
+    #[tokio::test]
+    async fn run_actions_blocked_by_navigation_policy() {
+        let temp = tempdir().unwrap();
+        let executor = Arc::new(MockExecutor::default());
+        let service =
+            BrowserService::new_with_executor(temp.path().join("browser"), executor.clone())
+                .unwrap()
+                .with_navigation_policy(NavigationPolicy {
+                    denied_domains: vec!["example.com".to_string()],
+                    ..Default::default()
+                });
+        let tool = BrowserTool::with_service(Arc::new(service));
+
+        let output = tool
+            .execute(json!({
+                "action": "run_actions",
+                "session_id": "missing",
+                "actions": [{ "type": "navigate", "url": "https://www.example.com" }]
+            }))
+            .await
+            .unwrap();
+
+        assert!(!output.success);
+        assert_eq!(output.error_category, Some(ToolErrorCategory::Auth));
+        assert_eq!(executor.action_calls.load(Ordering::Relaxed), 0);
+    }
+
+    /// Fails the first run on its click action, then records later requests.
+    #[derive(Default)]
+    struct FlakySelectorExecutor {
+        requests: std::sync::Mutex<Vec<RunActionsRequest>>,
+    }
+
+    #[async_trait]
+    impl BrowserExecutor for FlakySelectorExecutor {
+        async fn probe_runtime(&self) -> anyhow::Result<RuntimeProbe> {
+            anyhow::bail!("unexpected runtime probe")
+        }
+
+        async fn run_script(
+            &self,
+            _session: &BrowserSession,
+            _request: &RunScriptRequest,
+        ) -> anyhow::Result<BrowserExecutionResult> {
+            anyhow::bail!("unexpected script run")
+        }
+
+        async fn run_actions(
+            &self,
+            _session: &BrowserSession,
+            request: &RunActionsRequest,
+        ) -> anyhow::Result<BrowserExecutionResult> {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(request.clone());
+            let payload = if requests.len() == 1 {
+                json!({
+                    "success": false,
+                    "error": "Selector not found: .btn-x7f",
+                    "failure": {
+                        "action_index": 1,
+                        "action_type": "click",
+                        "error": "Selector not found: .btn-x7f",
+                        "screenshot_path": "/tmp/action-1-failure.png"
+                    }
+                })
+            } else {
+                json!({"success": true, "result": []})
+            };
+            Ok(BrowserExecutionResult {
+                runtime: "mock".to_string(),
+                exit_code: if requests.len() == 1 { 1 } else { 0 },
+                duration_ms: 5,
+                stdout: String::new(),
+                stderr: String::new(),
+                payload: Some(payload),
+                trace_path: None,
+            })
+        }
+    }
+
+    struct MockVision {
+        reply: String,
+        prompts: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Tool for MockVision {
+        fn name(&self) -> &str {
+            "vision"
+        }
+
+        fn description(&self) -> &str {
+            "mock vision"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, input: Value) -> Result<ToolOutput> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(input["prompt"].as_str().unwrap_or_default().to_string());
+            Ok(ToolOutput::success(json!({ "description": self.reply })))
+        }
+    }
+
+    async fn run_flaky_click(
+        vision_fallback: bool,
+    ) -> (ToolOutput, Arc<FlakySelectorExecutor>, Arc<MockVision>) {
+        let temp = tempdir().unwrap();
+        let executor = Arc::new(FlakySelectorExecutor::default());
+        let vision = Arc::new(MockVision {
+            reply: "```json\n{\"x\": 120.5, \"y\": 48}\n```".to_string(),
+            prompts: Default::default(),
+        });
+        let service =
+            BrowserService::new_with_executor(temp.path().join("browser"), executor.clone())
+                .unwrap();
+        let tool =
+            BrowserTool::with_service(Arc::new(service)).with_vision_fallback(vision.clone());
+
+        let created = tool
+            .execute(json!({ "action": "new_session" }))
+            .await
+            .unwrap();
+        let output = tool
+            .execute(json!({
+                "action": "run_actions",
+                "session_id": created.result["id"],
+                "vision_fallback": vision_fallback,
+                "actions": [
+                    { "type": "navigate", "url": "https://example.com" },
+                    { "type": "click", "selector": ".btn-x7f", "description": "the blue Submit button" },
+                    { "type": "extract_text", "selector": "h1" }
+                ]
+            }))
+            .await
+            .unwrap();
+        (output, executor, vision)
+    }
+
+    #[tokio::test]
+    async fn run_actions_vision_fallback_clicks_located_coordinates() {
+        let (output, executor, vision) = run_flaky_click(true).await;
+
+        assert!(output.success);
+        assert_eq!(output.result["vision_fallbacks"][0]["action_index"], 1);
+        assert_eq!(output.result["vision_fallbacks"][0]["x"], 120.5);
+        assert!(vision.prompts.lock().unwrap()[0].contains("the blue Submit button"));
+
+        let requests = executor.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let retried = &requests[1].actions;
+        assert_eq!(retried.len(), 2);
+        assert!(matches!(
+            retried[0],
+            BrowserAction::MouseClick { x, y, .. } if x == 120.5 && y == 48.0
+        ));
+        assert!(matches!(retried[1], BrowserAction::ExtractText { .. }));
+    }
+
+    #[tokio::test]
+    async fn run_actions_without_vision_fallback_reports_failure() {
+        let (output, executor, vision) = run_flaky_click(false).await;
+
+        assert!(!output.success);
+        assert!(output.result.get("vision_fallbacks").is_none());
+        assert_eq!(executor.requests.lock().unwrap().len(), 1);
+        assert!(vision.prompts.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn vision_fill_replacement_sets_focused_value() {
+        let action = BrowserAction::Fill {
+            selector: "#email".to_string(),
+            text: "a@b.test".to_string(),
+            timeout_ms: None,
+            description: None,
+        };
+        assert_eq!(
+            vision_target(&action).as_deref(),
+            Some("the element matching CSS selector `#email`")
+        );
+
+        let actions = vision_replacement(&action, VisionPoint { x: 1.0, y: 2.0 });
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            &actions[1],
+            BrowserAction::Evaluate { args: Some(args), .. } if args["text"] == "a@b.test"
+        ));
+    }
+
+    #[test]
+    fn parse_vision_point_rejects_missing_targets() {
+        assert_eq!(
+            parse_vision_point("Center: {\"x\": 3, \"y\": 4}"),
+            Some(VisionPoint { x: 3.0, y: 4.0 })
+        );
+        assert_eq!(parse_vision_point("{\"found\": false}"), None);
+        assert_eq!(parse_vision_point("not visible"), None);
+    }
 }
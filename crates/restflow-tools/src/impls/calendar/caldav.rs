@@ -0,0 +1,333 @@
+//! CalDAV (RFC 4791) provider.
+//!
+//! Calendars are discovered with `PROPFIND` on the configured URL, events are
+//! read with a `calendar-query` REPORT (recurring events expanded by the
+//! server), and writes `PUT` whole calendar object resources guarded by
+//! ETags. Event ids are resource hrefs.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+use super::{Calendar, CalendarProvider, Event, EventChanges, EventDraft, ProviderResult, ical};
+
+const DAV_NS: &str = "DAV:";
+const CALDAV_NS: &str = "urn:ietf:params:xml:ns:caldav";
+
+const PROPFIND_CALENDARS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:displayname/>
+    <d:resourcetype/>
+  </d:prop>
+</d:propfind>"#;
+
+pub(crate) struct CalDavProvider {
+    client: reqwest::Client,
+    base_url: Url,
+    username: String,
+    password: String,
+    tz: Tz,
+}
+
+struct DavResponse {
+    status: StatusCode,
+    etag: Option<String>,
+    body: String,
+}
+
+/// One `<d:response>` of a multistatus body.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct DavEntry {
+    pub href: String,
+    pub display_name: Option<String>,
+    pub is_calendar: bool,
+    pub calendar_data: Option<String>,
+}
+
+pub(super) fn parse_multistatus(body: &str) -> ProviderResult<Vec<DavEntry>> {
+    let document =
+        roxmltree::Document::parse(body).map_err(|e| format!("Invalid CalDAV response: {}", e))?;
+    let is = |node: &roxmltree::Node, namespace: &str, name: &str| {
+        node.is_element()
+            && node.tag_name().name() == name
+            && node.tag_name().namespace() == Some(namespace)
+    };
+    let entries = document
+        .descendants()
+        .filter(|node| is(node, DAV_NS, "response"))
+        .map(|response| {
+            let mut entry = DavEntry::default();
+            for node in response.descendants() {
+                if is(&node, DAV_NS, "href") && entry.href.is_empty() {
+                    entry.href = node.text().unwrap_or_default().trim().to_string();
+                } else if is(&node, DAV_NS, "displayname") {
+                    entry.display_name = node
+                        .text()
+                        .map(|text| text.trim().to_string())
+                        .filter(|text| !text.is_empty());
+                } else if is(&node, CALDAV_NS, "calendar")
+                    && node
+                        .parent()
+                        .is_some_and(|parent| is(&parent, DAV_NS, "resourcetype"))
+                {
+                    entry.is_calendar = true;
+                } else if is(&node, CALDAV_NS, "calendar-data") {
+                    entry.calendar_data = node.text().map(str::to_string);
+                }
+            }
+            entry
+        })
+        .collect();
+    Ok(entries)
+}
+
+fn calendar_query(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+    let start = start.format("%Y%m%dT%H%M%SZ");
+    let end = end.format("%Y%m%dT%H%M%SZ");
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data>
+      <c:expand start="{start}" end="{end}"/>
+    </c:calendar-data>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{start}" end="{end}"/>
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#
+    )
+}
+
+fn last_segment(href: &str) -> String {
+    href.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(href)
+        .to_string()
+}
+
+impl CalDavProvider {
+    pub(crate) fn new(
+        client: reqwest::Client,
+        url: &str,
+        username: String,
+        password: String,
+        tz: Tz,
+    ) -> ProviderResult<Self> {
+        let base_url = Url::parse(url).map_err(|e| format!("Invalid CALDAV_URL: {}", e))?;
+        if !matches!(base_url.scheme(), "http" | "https") {
+            return Err("CALDAV_URL must be an http(s) URL.".to_string());
+        }
+        Ok(Self {
+            client,
+            base_url,
+            username,
+            password,
+            tz,
+        })
+    }
+
+    /// Resolve an href against the server. Credentials are only ever sent to
+    /// the configured origin.
+    pub(super) fn resolve(&self, href: &str) -> ProviderResult<Url> {
+        let url = self
+            .base_url
+            .join(href)
+            .map_err(|e| format!("Invalid CalDAV path '{}': {}", href, e))?;
+        if url.origin() != self.base_url.origin() {
+            return Err(format!(
+                "CalDAV path '{}' points outside the configured server.",
+                href
+            ));
+        }
+        Ok(url)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: Url,
+        headers: &[(reqwest::header::HeaderName, &str)],
+        body: Option<String>,
+    ) -> ProviderResult<DavResponse> {
+        let mut request = self
+            .client
+            .request(method.clone(), url.clone())
+            .basic_auth(&self.username, Some(&self.password))
+            .header("User-Agent", "restflow");
+        for (name, value) in headers {
+            request = request.header(name, *value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("CalDAV request failed: {}", e))?;
+        let status = response.status();
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let detail: String = body.chars().take(300).collect();
+            return Err(format!(
+                "CalDAV {} {} failed ({}): {}",
+                method,
+                url.path(),
+                status.as_u16(),
+                detail.trim()
+            ));
+        }
+        Ok(DavResponse { status, etag, body })
+    }
+
+    async fn dav_method(
+        &self,
+        method: &'static [u8],
+        url: Url,
+        body: String,
+    ) -> ProviderResult<Vec<DavEntry>> {
+        let method = Method::from_bytes(method).map_err(|e| e.to_string())?;
+        let response = self
+            .send(
+                method,
+                url,
+                &[
+                    (reqwest::header::HeaderName::from_static("depth"), "1"),
+                    (CONTENT_TYPE, "application/xml; charset=utf-8"),
+                ],
+                Some(body),
+            )
+            .await?;
+        if response.status != StatusCode::MULTI_STATUS {
+            return Err(format!(
+                "CalDAV server returned {} instead of a multistatus response.",
+                response.status.as_u16()
+            ));
+        }
+        parse_multistatus(&response.body)
+    }
+}
+
+#[async_trait]
+impl CalendarProvider for CalDavProvider {
+    async fn list_calendars(&self) -> ProviderResult<Vec<Calendar>> {
+        let entries = self
+            .dav_method(
+                b"PROPFIND",
+                self.base_url.clone(),
+                PROPFIND_CALENDARS.to_string(),
+            )
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| entry.is_calendar)
+            .enumerate()
+            .map(|(index, entry)| Calendar {
+                name: entry
+                    .display_name
+                    .unwrap_or_else(|| last_segment(&entry.href)),
+                id: entry.href,
+                primary: index == 0,
+                timezone: None,
+            })
+            .collect())
+    }
+
+    async fn default_calendar(&self) -> ProviderResult<String> {
+        self.list_calendars()
+            .await?
+            .into_iter()
+            .next()
+            .map(|calendar| calendar.id)
+            .ok_or_else(|| "No calendars found at CALDAV_URL.".to_string())
+    }
+
+    async fn list_events(
+        &self,
+        calendar: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> ProviderResult<Vec<Event>> {
+        let entries = self
+            .dav_method(
+                b"REPORT",
+                self.resolve(calendar)?,
+                calendar_query(start, end),
+            )
+            .await?;
+        let mut events: Vec<Event> = entries
+            .iter()
+            .filter_map(|entry| {
+                let data = entry.calendar_data.as_deref()?;
+                Some(ical::parse_events(data, &entry.href, calendar, self.tz))
+            })
+            .flatten()
+            .collect();
+        events.sort_by_key(|event| event.start.to_utc(self.tz));
+        events.truncate(limit);
+        Ok(events)
+    }
+
+    async fn create_event(&self, calendar: &str, event: &EventDraft) -> ProviderResult<Event> {
+        let mut collection = self.resolve(calendar)?;
+        if !collection.path().ends_with('/') {
+            collection.set_path(&format!("{}/", collection.path()));
+        }
+        let uid = uuid::Uuid::new_v4().to_string();
+        let url = collection
+            .join(&format!("{}.ics", uid))
+            .map_err(|e| e.to_string())?;
+        let body = ical::build_event(&uid, event, Utc::now());
+        self.send(
+            Method::PUT,
+            url.clone(),
+            &[
+                (CONTENT_TYPE, "text/calendar; charset=utf-8"),
+                (IF_NONE_MATCH, "*"),
+            ],
+            Some(body.clone()),
+        )
+        .await?;
+        ical::parse_events(&body, url.path(), calendar, self.tz)
+            .into_iter()
+            .next()
+            .ok_or_else(|| "Failed to read back the created event".to_string())
+    }
+
+    async fn update_event(
+        &self,
+        calendar: &str,
+        event_id: &str,
+        changes: &EventChanges,
+    ) -> ProviderResult<Event> {
+        let url = self.resolve(event_id)?;
+        let current = self.send(Method::GET, url.clone(), &[], None).await?;
+        let body = ical::apply_changes(&current.body, changes, Utc::now());
+        let mut headers = vec![(CONTENT_TYPE, "text/calendar; charset=utf-8")];
+        if let Some(etag) = current.etag.as_deref() {
+            headers.push((IF_MATCH, etag));
+        }
+        self.send(Method::PUT, url.clone(), &headers, Some(body.clone()))
+            .await?;
+        ical::parse_events(&body, url.path(), calendar, self.tz)
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Event {} has no VEVENT component", event_id))
+    }
+}
@@ -0,0 +1,198 @@
+//! Google Calendar API v3 provider.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{Value, json};
+
+use super::{
+    Calendar, CalendarProvider, Event, EventChanges, EventDraft, EventTime, ProviderResult,
+};
+use crate::impls::code_host::{ApiClient, str_field};
+
+const GOOGLE_CALENDAR_API_BASE: &str = "https://www.googleapis.com/calendar/v3";
+
+pub(crate) struct GoogleCalendarProvider {
+    api: ApiClient,
+}
+
+impl GoogleCalendarProvider {
+    pub(crate) fn new(client: reqwest::Client, token: String, base_url: Option<String>) -> Self {
+        let headers = vec![("Authorization", format!("Bearer {}", token))];
+        Self {
+            api: ApiClient::new(
+                client,
+                "Google Calendar",
+                base_url.as_deref().unwrap_or(GOOGLE_CALENDAR_API_BASE),
+                token,
+                headers,
+            ),
+        }
+    }
+}
+
+fn events_path(calendar: &str) -> String {
+    format!("/calendars/{}/events", urlencoding::encode(calendar))
+}
+
+fn time_json(time: &EventTime) -> Value {
+    match time {
+        EventTime::DateTime(time) => json!({ "dateTime": time.to_rfc3339() }),
+        EventTime::Date(date) => json!({ "date": date.format("%Y-%m-%d").to_string() }),
+    }
+}
+
+fn parse_time(value: &Value) -> Option<EventTime> {
+    if let Some(time) = value["dateTime"].as_str() {
+        return DateTime::parse_from_rfc3339(time)
+            .ok()
+            .map(|time| EventTime::DateTime(time.with_timezone(&Utc)));
+    }
+    value["date"]
+        .as_str()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .map(EventTime::Date)
+}
+
+pub(super) fn parse_calendar(value: &Value) -> Calendar {
+    Calendar {
+        id: str_field(value, "id").unwrap_or_default(),
+        name: str_field(value, "summaryOverride")
+            .or_else(|| str_field(value, "summary"))
+            .unwrap_or_default(),
+        primary: value["primary"].as_bool().unwrap_or(false),
+        timezone: str_field(value, "timeZone"),
+    }
+}
+
+pub(super) fn parse_event(value: &Value, calendar: &str) -> Option<Event> {
+    let status = str_field(value, "status");
+    Some(Event {
+        id: str_field(value, "id")?,
+        calendar_id: calendar.to_string(),
+        title: str_field(value, "summary").unwrap_or_default(),
+        start: parse_time(&value["start"])?,
+        end: parse_time(&value["end"])?,
+        busy: value["transparency"].as_str() != Some("transparent")
+            && status.as_deref() != Some("cancelled"),
+        description: str_field(value, "description"),
+        location: str_field(value, "location"),
+        attendees: value["attendees"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|attendee| str_field(attendee, "email"))
+            .collect(),
+        status,
+        url: str_field(value, "htmlLink"),
+    })
+}
+
+fn attendees_json(attendees: &[String]) -> Value {
+    attendees
+        .iter()
+        .map(|email| json!({ "email": email.trim() }))
+        .collect()
+}
+
+#[async_trait]
+impl CalendarProvider for GoogleCalendarProvider {
+    async fn list_calendars(&self) -> ProviderResult<Vec<Calendar>> {
+        let list = self.api.get("/users/me/calendarList", &[]).await?;
+        Ok(list["items"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(parse_calendar)
+            .collect())
+    }
+
+    async fn default_calendar(&self) -> ProviderResult<String> {
+        Ok("primary".to_string())
+    }
+
+    async fn list_events(
+        &self,
+        calendar: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> ProviderResult<Vec<Event>> {
+        let list = self
+            .api
+            .get(
+                &events_path(calendar),
+                &[
+                    ("timeMin", start.to_rfc3339()),
+                    ("timeMax", end.to_rfc3339()),
+                    ("singleEvents", "true".to_string()),
+                    ("orderBy", "startTime".to_string()),
+                    ("maxResults", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(list["items"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|event| parse_event(event, calendar))
+            .collect())
+    }
+
+    async fn create_event(&self, calendar: &str, event: &EventDraft) -> ProviderResult<Event> {
+        let mut body = json!({
+            "summary": event.title,
+            "start": time_json(&event.start),
+            "end": time_json(&event.end),
+            "attendees": attendees_json(&event.attendees),
+        });
+        if let Some(description) = &event.description {
+            body["description"] = json!(description);
+        }
+        if let Some(location) = &event.location {
+            body["location"] = json!(location);
+        }
+        let created = self.api.post(&events_path(calendar), body).await?;
+        parse_event(&created, calendar)
+            .ok_or_else(|| "Google Calendar returned an unexpected event".to_string())
+    }
+
+    async fn update_event(
+        &self,
+        calendar: &str,
+        event_id: &str,
+        changes: &EventChanges,
+    ) -> ProviderResult<Event> {
+        let mut body = json!({});
+        if let Some(title) = &changes.title {
+            body["summary"] = json!(title);
+        }
+        if let Some(start) = &changes.start {
+            body["start"] = time_json(start);
+        }
+        if let Some(end) = &changes.end {
+            body["end"] = time_json(end);
+        }
+        if let Some(description) = &changes.description {
+            body["description"] = json!(description);
+        }
+        if let Some(location) = &changes.location {
+            body["location"] = json!(location);
+        }
+        if let Some(attendees) = &changes.attendees {
+            body["attendees"] = attendees_json(attendees);
+        }
+        let updated = self
+            .api
+            .patch(
+                &format!(
+                    "{}/{}",
+                    events_path(calendar),
+                    urlencoding::encode(event_id)
+                ),
+                body,
+            )
+            .await?;
+        parse_event(&updated, calendar)
+            .ok_or_else(|| "Google Calendar returned an unexpected event".to_string())
+    }
+}
@@ -0,0 +1,390 @@
+//! Minimal iCalendar (RFC 5545) support for CalDAV: reading `VEVENT`
+//! components, building new events, and editing properties of existing ones
+//! while leaving everything else (recurrence rules, alarms) untouched.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use super::{Event, EventChanges, EventDraft, EventTime};
+
+/// Maximum line length in octets before folding.
+const FOLD_WIDTH: usize = 75;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Join folded lines (continuations start with a space or tab).
+pub(super) fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        match line.strip_prefix([' ', '\t']) {
+            Some(rest) if !lines.is_empty() => lines.last_mut().unwrap().push_str(rest),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 characters.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > FOLD_WIDTH {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += len;
+    }
+    folded
+}
+
+pub(super) fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first ':' outside a quoted parameter value.
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(index, ch)| match ch {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(index),
+        _ => None,
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.trim().to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn property_name(line: &str) -> String {
+    line.split([';', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_uppercase()
+}
+
+pub(super) fn unescape_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+pub(super) fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Parse a `DATE` or `DATE-TIME` property. Floating times (no `Z`, no
+/// `TZID`) are read in `default_tz`.
+pub(super) fn parse_time(property: &Property, default_tz: Tz) -> Option<EventTime> {
+    let value = property.value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::Date);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(EventTime::DateTime(Utc.from_utc_datetime(&naive)));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let tz = property
+        .param("TZID")
+        .and_then(|tzid| tzid.parse::<Tz>().ok())
+        .unwrap_or(default_tz);
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|local| EventTime::DateTime(local.with_timezone(&Utc)))
+}
+
+/// Parse an RFC 5545 duration such as `PT1H30M`, `P1D` or `-PT15M`.
+pub(super) fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut rest = value.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut in_time = false;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('T') {
+            in_time = true;
+            rest = after;
+            continue;
+        }
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let amount: i64 = rest[..digits].parse().ok()?;
+        let unit = rest[digits..].chars().next()?;
+        total += match (unit, in_time) {
+            ('W', false) => Duration::weeks(amount),
+            ('D', false) => Duration::days(amount),
+            ('H', true) => Duration::hours(amount),
+            ('M', true) => Duration::minutes(amount),
+            ('S', true) => Duration::seconds(amount),
+            _ => return None,
+        };
+        rest = &rest[digits + 1..];
+    }
+    Some(total * sign)
+}
+
+fn attendee_email(property: &Property) -> String {
+    let value = property.value.trim();
+    value
+        .strip_prefix("mailto:")
+        .or_else(|| value.strip_prefix("MAILTO:"))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// Parse all `VEVENT` components of a calendar object resource.
+pub(super) fn parse_events(text: &str, href: &str, calendar_id: &str, tz: Tz) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    let mut nested = 0usize;
+    for line in unfold(text) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        match (property.name.as_str(), property.value.trim()) {
+            ("BEGIN", "VEVENT") if current.is_none() => current = Some(Vec::new()),
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => {
+                if let Some(properties) = current.take()
+                    && let Some(event) = event_from_properties(&properties, href, calendar_id, tz)
+                {
+                    events.push(event);
+                }
+            }
+            ("END", _) if current.is_some() => nested = nested.saturating_sub(1),
+            _ if nested == 0 => {
+                if let Some(properties) = current.as_mut() {
+                    properties.push(property);
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+fn event_from_properties(
+    properties: &[Property],
+    href: &str,
+    calendar_id: &str,
+    tz: Tz,
+) -> Option<Event> {
+    let find = |name: &str| properties.iter().find(|property| property.name == name);
+    let text = |name: &str| find(name).map(|property| unescape_text(&property.value));
+
+    let start = parse_time(find("DTSTART")?, tz)?;
+    let end = match find("DTEND").and_then(|property| parse_time(property, tz)) {
+        Some(end) => end,
+        None => match (
+            &start,
+            find("DURATION").and_then(|property| parse_duration(&property.value)),
+        ) {
+            (EventTime::DateTime(start), Some(duration)) => EventTime::DateTime(*start + duration),
+            (EventTime::Date(start), Some(duration)) => {
+                EventTime::Date(*start + Duration::days(duration.num_days().max(1)))
+            }
+            (EventTime::Date(start), None) => EventTime::Date(*start + Duration::days(1)),
+            (EventTime::DateTime(start), None) => EventTime::DateTime(*start),
+        },
+    };
+    let status = text("STATUS").map(|status| status.to_ascii_lowercase());
+    let transparent =
+        text("TRANSP").is_some_and(|transp| transp.eq_ignore_ascii_case("TRANSPARENT"));
+    Some(Event {
+        id: href.to_string(),
+        calendar_id: calendar_id.to_string(),
+        title: text("SUMMARY").unwrap_or_default(),
+        busy: !transparent && status.as_deref() != Some("cancelled"),
+        start,
+        end,
+        description: text("DESCRIPTION"),
+        location: text("LOCATION"),
+        attendees: properties
+            .iter()
+            .filter(|property| property.name == "ATTENDEE")
+            .map(attendee_email)
+            .collect(),
+        status,
+        url: None,
+    })
+}
+
+fn format_utc(time: &DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn time_line(name: &str, time: &EventTime) -> String {
+    match time {
+        EventTime::Date(date) => format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")),
+        EventTime::DateTime(time) => format!("{}:{}", name, format_utc(time)),
+    }
+}
+
+fn attendee_line(email: &str) -> String {
+    format!("ATTENDEE;RSVP=TRUE:mailto:{}", email.trim())
+}
+
+fn serialize(lines: &[String]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(&fold(line));
+        text.push_str("\r\n");
+    }
+    text
+}
+
+/// Build a calendar object resource holding one new event.
+pub(super) fn build_event(uid: &str, draft: &EventDraft, now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//RestFlow//Calendar Tool//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", format_utc(&now)),
+        time_line("DTSTART", &draft.start),
+        time_line("DTEND", &draft.end),
+        format!("SUMMARY:{}", escape_text(&draft.title)),
+    ];
+    if let Some(description) = &draft.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if let Some(location) = &draft.location {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    lines.extend(draft.attendees.iter().map(|email| attendee_line(email)));
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+    serialize(&lines)
+}
+
+/// Apply `changes` to the first `VEVENT` of `text`, replacing the edited
+/// properties and bumping `SEQUENCE` so attendees see the update.
+pub(super) fn apply_changes(text: &str, changes: &EventChanges, now: DateTime<Utc>) -> String {
+    let mut replacements: Vec<(&str, Vec<String>)> = Vec::new();
+    if let Some(title) = &changes.title {
+        replacements.push(("SUMMARY", vec![format!("SUMMARY:{}", escape_text(title))]));
+    }
+    if let Some(start) = &changes.start {
+        replacements.push(("DTSTART", vec![time_line("DTSTART", start)]));
+    }
+    if let Some(end) = &changes.end {
+        replacements.push(("DTEND", vec![time_line("DTEND", end)]));
+        replacements.push(("DURATION", Vec::new()));
+    }
+    if let Some(description) = &changes.description {
+        replacements.push((
+            "DESCRIPTION",
+            vec![format!("DESCRIPTION:{}", escape_text(description))],
+        ));
+    }
+    if let Some(location) = &changes.location {
+        replacements.push((
+            "LOCATION",
+            vec![format!("LOCATION:{}", escape_text(location))],
+        ));
+    }
+    if let Some(attendees) = &changes.attendees {
+        replacements.push((
+            "ATTENDEE",
+            attendees.iter().map(|email| attendee_line(email)).collect(),
+        ));
+    }
+
+    let mut output = Vec::new();
+    let mut in_event = false;
+    let mut edited = false;
+    let mut nested = 0usize;
+    let mut sequence = 0i64;
+    for line in unfold(text) {
+        let name = property_name(&line);
+        let value = line
+            .split_once(':')
+            .map(|(_, value)| value.trim())
+            .unwrap_or("");
+        if !in_event && !edited && name == "BEGIN" && value == "VEVENT" {
+            in_event = true;
+            output.push(line);
+            continue;
+        }
+        if in_event {
+            if name == "BEGIN" {
+                nested += 1;
+            } else if name == "END" && nested > 0 {
+                nested -= 1;
+            } else if name == "END" && value == "VEVENT" {
+                for (_, lines) in &replacements {
+                    output.extend(lines.iter().cloned());
+                }
+                output.push(format!("SEQUENCE:{}", sequence + 1));
+                output.push(format!("DTSTAMP:{}", format_utc(&now)));
+                output.push(line);
+                in_event = false;
+                edited = true;
+                continue;
+            } else if nested == 0 {
+                if name == "SEQUENCE" {
+                    sequence = value.parse().unwrap_or(0);
+                    continue;
+                }
+                if name == "DTSTAMP" || replacements.iter().any(|(replaced, _)| *replaced == name) {
+                    continue;
+                }
+            }
+        }
+        output.push(line);
+    }
+    serialize(&output)
+}
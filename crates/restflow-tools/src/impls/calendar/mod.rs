@@ -0,0 +1,837 @@
+//! Calendar integration tool (Google Calendar, CalDAV).
+//!
+//! Lets scheduling agents list calendars and events, find free slots, and
+//! create or update events. Each backend is a [`CalendarProvider`]; free-slot
+//! search runs on top of `list_events`, so it works the same everywhere.
+//!
+//! Credentials come from secrets, following the auth profile naming:
+//! - Google: an OAuth access token in `GOOGLE_CALENDAR_TOKEN` (or
+//!   `GOOGLE_CALENDAR_API_KEY`, as stored by `manage_auth_profiles` for the
+//!   `google_calendar` provider).
+//! - CalDAV: `CALDAV_URL` (calendar home or calendar collection),
+//!   `CALDAV_USERNAME` and `CALDAV_PASSWORD`.
+
+mod caldav;
+mod google;
+mod ical;
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+use crate::Result;
+use crate::check_security;
+use crate::http_client::build_http_client;
+use crate::security::SecurityGate;
+use crate::{SecretResolver, Tool, ToolAction, ToolOutput};
+
+use self::caldav::CalDavProvider;
+use self::google::GoogleCalendarProvider;
+
+const DEFAULT_EVENT_LIMIT: usize = 50;
+const MAX_EVENT_LIMIT: usize = 250;
+const DEFAULT_SLOT_LIMIT: usize = 10;
+/// Longest range scanned by `list_events` and `find_free_slots`.
+const MAX_RANGE_DAYS: i64 = 92;
+
+pub(crate) type ProviderResult<T> = std::result::Result<T, String>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProviderKind {
+    #[default]
+    Google,
+    Caldav,
+}
+
+/// Start or end of an event: a point in time, or a date for all-day events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EventTime {
+    DateTime(DateTime<Utc>),
+    Date(NaiveDate),
+}
+
+impl EventTime {
+    fn is_date(&self) -> bool {
+        matches!(self, Self::Date(_))
+    }
+
+    /// Instant of this time; dates start at midnight in `tz`.
+    fn to_utc(self, tz: Tz) -> DateTime<Utc> {
+        match self {
+            Self::DateTime(time) => time,
+            Self::Date(date) => local_to_utc(date.and_time(NaiveTime::MIN), tz),
+        }
+    }
+
+    fn to_json(self, tz: Tz) -> Value {
+        match self {
+            Self::DateTime(time) => Value::String(format_time(time, tz)),
+            Self::Date(date) => Value::String(date.format("%Y-%m-%d").to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Calendar {
+    pub id: String,
+    pub name: String,
+    pub primary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Event {
+    pub id: String,
+    pub calendar_id: String,
+    pub title: String,
+    pub start: EventTime,
+    pub end: EventTime,
+    /// Whether the event blocks time (not cancelled or marked free).
+    pub busy: bool,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendees: Vec<String>,
+    pub status: Option<String>,
+    pub url: Option<String>,
+}
+
+impl Event {
+    fn to_json(&self, tz: Tz) -> Value {
+        json!({
+            "id": self.id,
+            "calendar_id": self.calendar_id,
+            "title": self.title,
+            "start": self.start.to_json(tz),
+            "end": self.end.to_json(tz),
+            "all_day": self.start.is_date(),
+            "busy": self.busy,
+            "description": self.description,
+            "location": self.location,
+            "attendees": self.attendees,
+            "status": self.status,
+            "url": self.url,
+        })
+    }
+}
+
+pub(crate) struct EventDraft {
+    pub title: String,
+    pub start: EventTime,
+    pub end: EventTime,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendees: Vec<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct EventChanges {
+    pub title: Option<String>,
+    pub start: Option<EventTime>,
+    pub end: Option<EventTime>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendees: Option<Vec<String>>,
+}
+
+/// A calendar backend.
+#[async_trait]
+pub(crate) trait CalendarProvider: Send + Sync {
+    async fn list_calendars(&self) -> ProviderResult<Vec<Calendar>>;
+
+    /// Calendar used when the caller does not name one.
+    async fn default_calendar(&self) -> ProviderResult<String>;
+
+    async fn list_events(
+        &self,
+        calendar: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> ProviderResult<Vec<Event>>;
+
+    async fn create_event(&self, calendar: &str, event: &EventDraft) -> ProviderResult<Event>;
+
+    async fn update_event(
+        &self,
+        calendar: &str,
+        event_id: &str,
+        changes: &EventChanges,
+    ) -> ProviderResult<Event>;
+}
+
+fn local_to_utc(naive: NaiveDateTime, tz: Tz) -> DateTime<Utc> {
+    // Times skipped by a DST change fall back to reading them as UTC offsets.
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+fn format_time(time: DateTime<Utc>, tz: Tz) -> String {
+    time.with_timezone(&tz).to_rfc3339()
+}
+
+/// Parse a time given by the model: RFC 3339, a local date-time
+/// (`2026-10-20T09:00`) in `tz`, or a date. Dates become all-day times when
+/// `all_day` is set and local midnight otherwise.
+fn parse_input_time(value: &str, tz: Tz, all_day: bool) -> std::result::Result<EventTime, String> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(if all_day {
+            EventTime::Date(date)
+        } else {
+            EventTime::DateTime(local_to_utc(date.and_time(NaiveTime::MIN), tz))
+        });
+    }
+    let time = if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        time.with_timezone(&Utc)
+    } else {
+        let normalized = value.replacen(' ', "T", 1);
+        let naive = NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%dT%H:%M:%S")
+            .or_else(|_| NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%dT%H:%M"))
+            .map_err(|_| {
+                format!(
+                    "Invalid time '{}'. Use RFC 3339 (2026-10-20T09:00:00+02:00), a local time (2026-10-20T09:00) or a date (2026-10-20).",
+                    value
+                )
+            })?;
+        local_to_utc(naive, tz)
+    };
+    Ok(if all_day {
+        EventTime::Date(time.with_timezone(&tz).date_naive())
+    } else {
+        EventTime::DateTime(time)
+    })
+}
+
+fn parse_timezone(timezone: Option<&str>) -> std::result::Result<Tz, String> {
+    match timezone.map(str::trim).filter(|tz| !tz.is_empty()) {
+        Some(name) => name.parse::<Tz>().map_err(|_| {
+            format!(
+                "Unknown timezone '{}'. Use an IANA name such as Europe/Berlin.",
+                name
+            )
+        }),
+        None => Ok(Tz::UTC),
+    }
+}
+
+fn parse_clock(value: &str) -> std::result::Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time of day '{}'. Use HH:MM.", value))
+}
+
+/// Range to search, with the end after the start and not too far away.
+fn parse_range(
+    start: &str,
+    end: &str,
+    tz: Tz,
+) -> std::result::Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let start = parse_input_time(start, tz, false)?.to_utc(tz);
+    let end = parse_input_time(end, tz, false)?.to_utc(tz);
+    if end <= start {
+        return Err("end must be after start.".to_string());
+    }
+    if end - start > Duration::days(MAX_RANGE_DAYS) {
+        return Err(format!("Time range is limited to {} days.", MAX_RANGE_DAYS));
+    }
+    Ok((start, end))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WorkingHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// Free ranges of at least `duration` inside `[start, end)`, limited to
+/// working hours in `tz` and skipping weekends unless `include_weekends`.
+pub(crate) fn find_free_slots(
+    mut busy: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    duration: Duration,
+    tz: Tz,
+    working_hours: Option<WorkingHours>,
+    include_weekends: bool,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    busy.sort();
+    let mut slots = Vec::new();
+    let mut day = start.with_timezone(&tz).date_naive();
+    let last_day = end.with_timezone(&tz).date_naive();
+    while day <= last_day {
+        let weekend = matches!(day.weekday(), Weekday::Sat | Weekday::Sun);
+        if include_weekends || !weekend {
+            let (day_start, day_end) = match working_hours {
+                Some(hours) => (
+                    local_to_utc(day.and_time(hours.start), tz),
+                    local_to_utc(day.and_time(hours.end), tz),
+                ),
+                None => (
+                    local_to_utc(day.and_time(NaiveTime::MIN), tz),
+                    local_to_utc(day.succ_opt().unwrap_or(day).and_time(NaiveTime::MIN), tz),
+                ),
+            };
+            let mut cursor = day_start.max(start);
+            let window_end = day_end.min(end);
+            for (busy_start, busy_end) in &busy {
+                if *busy_end <= cursor || *busy_start >= window_end {
+                    continue;
+                }
+                if *busy_start - cursor >= duration {
+                    slots.push((cursor, *busy_start));
+                }
+                cursor = cursor.max(*busy_end);
+            }
+            if window_end - cursor >= duration {
+                slots.push((cursor, window_end));
+            }
+        }
+        match day.succ_opt() {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+    slots
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarInput {
+    #[serde(default)]
+    provider: ProviderKind,
+    /// IANA timezone for reading local times and formatting results.
+    timezone: Option<String>,
+    #[serde(flatten)]
+    operation: CalendarOperation,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum CalendarOperation {
+    ListCalendars,
+    ListEvents {
+        calendar: Option<String>,
+        start: String,
+        end: String,
+        limit: Option<usize>,
+    },
+    FindFreeSlots {
+        #[serde(default)]
+        calendars: Vec<String>,
+        start: String,
+        end: String,
+        duration_minutes: i64,
+        working_hours_start: Option<String>,
+        working_hours_end: Option<String>,
+        #[serde(default)]
+        include_weekends: bool,
+        limit: Option<usize>,
+    },
+    CreateEvent {
+        calendar: Option<String>,
+        title: String,
+        start: String,
+        end: Option<String>,
+        #[serde(default)]
+        all_day: bool,
+        description: Option<String>,
+        location: Option<String>,
+        #[serde(default)]
+        attendees: Vec<String>,
+    },
+    UpdateEvent {
+        calendar: Option<String>,
+        event_id: String,
+        title: Option<String>,
+        start: Option<String>,
+        end: Option<String>,
+        #[serde(default)]
+        all_day: bool,
+        description: Option<String>,
+        location: Option<String>,
+        attendees: Option<Vec<String>>,
+    },
+}
+
+/// Calendar tool for Google Calendar and CalDAV servers.
+pub struct CalendarTool {
+    client: reqwest::Client,
+    secret_resolver: Option<SecretResolver>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl CalendarTool {
+    pub fn new() -> std::result::Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: build_http_client()?,
+            secret_resolver: None,
+            security_gate: None,
+            agent_id: None,
+            task_id: None,
+        })
+    }
+
+    pub fn with_secret_resolver(mut self, resolver: SecretResolver) -> Self {
+        self.secret_resolver = Some(resolver);
+        self
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    fn resolve_secret(&self, key: &str) -> Option<String> {
+        self.secret_resolver
+            .as_ref()
+            .and_then(|resolver| resolver(key))
+            .filter(|value| !value.trim().is_empty())
+    }
+
+    fn require_secret(&self, key: &str) -> std::result::Result<String, String> {
+        self.resolve_secret(key)
+            .ok_or_else(|| format!("{} is not set. Store it with the manage_secrets tool.", key))
+    }
+
+    fn provider(
+        &self,
+        kind: ProviderKind,
+        tz: Tz,
+    ) -> std::result::Result<Box<dyn CalendarProvider>, String> {
+        let client = self.client.clone();
+        Ok(match kind {
+            ProviderKind::Google => {
+                let token = self
+                    .resolve_secret("GOOGLE_CALENDAR_TOKEN")
+                    .or_else(|| self.resolve_secret("GOOGLE_CALENDAR_API_KEY"))
+                    .ok_or_else(|| {
+                        "GOOGLE_CALENDAR_TOKEN is not set. Store a Google OAuth access token with the manage_secrets tool, or add a google_calendar auth profile."
+                            .to_string()
+                    })?;
+                let base_url = self.resolve_secret("GOOGLE_CALENDAR_API_URL");
+                Box::new(GoogleCalendarProvider::new(client, token, base_url))
+            }
+            ProviderKind::Caldav => Box::new(CalDavProvider::new(
+                client,
+                &self.require_secret("CALDAV_URL")?,
+                self.require_secret("CALDAV_USERNAME")?,
+                self.require_secret("CALDAV_PASSWORD")?,
+                tz,
+            )?),
+        })
+    }
+
+    async fn check_action_allowed(
+        &self,
+        operation: &str,
+        target: String,
+        summary: String,
+    ) -> Result<Option<String>> {
+        let action = ToolAction {
+            tool_name: self.name().to_string(),
+            operation: operation.to_string(),
+            target,
+            summary,
+        };
+
+        check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await
+    }
+
+    async fn run(
+        &self,
+        provider: &dyn CalendarProvider,
+        operation: CalendarOperation,
+        tz: Tz,
+    ) -> Result<std::result::Result<Value, String>> {
+        let calendar_or_default = |calendar: Option<String>| async move {
+            match calendar.filter(|calendar| !calendar.trim().is_empty()) {
+                Some(calendar) => Ok(calendar),
+                None => provider.default_calendar().await,
+            }
+        };
+
+        let result = match operation {
+            CalendarOperation::ListCalendars => provider
+                .list_calendars()
+                .await
+                .map(|calendars| json!({ "calendars": calendars })),
+            CalendarOperation::ListEvents {
+                calendar,
+                start,
+                end,
+                limit,
+            } => {
+                let limit = limit
+                    .unwrap_or(DEFAULT_EVENT_LIMIT)
+                    .clamp(1, MAX_EVENT_LIMIT);
+                let range = parse_range(&start, &end, tz);
+                match (range, calendar_or_default(calendar).await) {
+                    (Ok((start, end)), Ok(calendar)) => provider
+                        .list_events(&calendar, start, end, limit)
+                        .await
+                        .map(|events| {
+                            json!({
+                                "calendar": calendar,
+                                "timezone": tz.name(),
+                                "events": events.iter().map(|event| event.to_json(tz)).collect::<Vec<_>>(),
+                            })
+                        }),
+                    (Err(message), _) | (_, Err(message)) => Err(message),
+                }
+            }
+            CalendarOperation::FindFreeSlots {
+                calendars,
+                start,
+                end,
+                duration_minutes,
+                working_hours_start,
+                working_hours_end,
+                include_weekends,
+                limit,
+            } => {
+                self.free_slots(
+                    provider,
+                    FreeSlotQuery {
+                        calendars,
+                        start,
+                        end,
+                        duration_minutes,
+                        working_hours_start,
+                        working_hours_end,
+                        include_weekends,
+                        limit,
+                    },
+                    tz,
+                )
+                .await
+            }
+            CalendarOperation::CreateEvent {
+                calendar,
+                title,
+                start,
+                end,
+                all_day,
+                description,
+                location,
+                attendees,
+            } => {
+                let draft = match event_draft(title, &start, end.as_deref(), all_day, tz) {
+                    Ok(times) => EventDraft {
+                        description,
+                        location,
+                        attendees,
+                        ..times
+                    },
+                    Err(message) => return Ok(Err(message)),
+                };
+                let calendar = match calendar_or_default(calendar).await {
+                    Ok(calendar) => calendar,
+                    Err(message) => return Ok(Err(message)),
+                };
+                let summary = format!(
+                    "Create event \"{}\" at {}",
+                    draft.title,
+                    draft.start.to_json(tz).as_str().unwrap_or_default()
+                );
+                if let Some(message) = self
+                    .check_action_allowed("create_event", calendar.clone(), summary)
+                    .await?
+                {
+                    return Ok(Err(message));
+                }
+                provider
+                    .create_event(&calendar, &draft)
+                    .await
+                    .map(|event| json!({ "created": true, "event": event.to_json(tz) }))
+            }
+            CalendarOperation::UpdateEvent {
+                calendar,
+                event_id,
+                title,
+                start,
+                end,
+                all_day,
+                description,
+                location,
+                attendees,
+            } => {
+                let changes = match event_changes(start, end, all_day, tz) {
+                    Ok(times) => EventChanges {
+                        title,
+                        description,
+                        location,
+                        attendees,
+                        ..times
+                    },
+                    Err(message) => return Ok(Err(message)),
+                };
+                let calendar = match calendar_or_default(calendar).await {
+                    Ok(calendar) => calendar,
+                    Err(message) => return Ok(Err(message)),
+                };
+                if let Some(message) = self
+                    .check_action_allowed(
+                        "update_event",
+                        format!("{}/{}", calendar, event_id),
+                        format!("Update event {}", event_id),
+                    )
+                    .await?
+                {
+                    return Ok(Err(message));
+                }
+                provider
+                    .update_event(&calendar, &event_id, &changes)
+                    .await
+                    .map(|event| json!({ "updated": true, "event": event.to_json(tz) }))
+            }
+        };
+        Ok(result)
+    }
+
+    async fn free_slots(
+        &self,
+        provider: &dyn CalendarProvider,
+        query: FreeSlotQuery,
+        tz: Tz,
+    ) -> std::result::Result<Value, String> {
+        if query.duration_minutes <= 0 {
+            return Err("duration_minutes must be positive.".to_string());
+        }
+        let (start, end) = parse_range(&query.start, &query.end, tz)?;
+        let working_hours = match (&query.working_hours_start, &query.working_hours_end) {
+            (None, None) => None,
+            (start, end) => {
+                let hours = WorkingHours {
+                    start: parse_clock(start.as_deref().unwrap_or("09:00"))?,
+                    end: parse_clock(end.as_deref().unwrap_or("17:00"))?,
+                };
+                if hours.end <= hours.start {
+                    return Err("working_hours_end must be after working_hours_start.".to_string());
+                }
+                Some(hours)
+            }
+        };
+        let calendars = if query.calendars.is_empty() {
+            vec![provider.default_calendar().await?]
+        } else {
+            query.calendars
+        };
+
+        let mut busy = Vec::new();
+        for calendar in &calendars {
+            let events = provider
+                .list_events(calendar, start, end, MAX_EVENT_LIMIT)
+                .await?;
+            busy.extend(
+                events
+                    .into_iter()
+                    .filter(|event| event.busy)
+                    .map(|event| (event.start.to_utc(tz), event.end.to_utc(tz))),
+            );
+        }
+
+        let limit = query.limit.unwrap_or(DEFAULT_SLOT_LIMIT).clamp(1, 100);
+        let slots = find_free_slots(
+            busy,
+            start,
+            end,
+            Duration::minutes(query.duration_minutes),
+            tz,
+            working_hours,
+            query.include_weekends,
+        );
+        Ok(json!({
+            "calendars": calendars,
+            "timezone": tz.name(),
+            "duration_minutes": query.duration_minutes,
+            "slots": slots
+                .iter()
+                .take(limit)
+                .map(|(start, end)| json!({
+                    "start": format_time(*start, tz),
+                    "end": format_time(*end, tz),
+                    "minutes": (*end - *start).num_minutes(),
+                }))
+                .collect::<Vec<_>>(),
+            "total_slots": slots.len(),
+        }))
+    }
+}
+
+struct FreeSlotQuery {
+    calendars: Vec<String>,
+    start: String,
+    end: String,
+    duration_minutes: i64,
+    working_hours_start: Option<String>,
+    working_hours_end: Option<String>,
+    include_weekends: bool,
+    limit: Option<usize>,
+}
+
+/// Start and end of a new event. Without `end`, timed events last an hour
+/// and all-day events one day.
+fn event_draft(
+    title: String,
+    start: &str,
+    end: Option<&str>,
+    all_day: bool,
+    tz: Tz,
+) -> std::result::Result<EventDraft, String> {
+    if title.trim().is_empty() {
+        return Err("title must not be empty.".to_string());
+    }
+    let start = parse_input_time(start, tz, all_day)?;
+    let end = match (end, start) {
+        (Some(end), _) => parse_input_time(end, tz, all_day)?,
+        (None, EventTime::Date(date)) => EventTime::Date(date + Duration::days(1)),
+        (None, EventTime::DateTime(time)) => EventTime::DateTime(time + Duration::hours(1)),
+    };
+    if end.to_utc(tz) <= start.to_utc(tz) {
+        return Err("end must be after start.".to_string());
+    }
+    Ok(EventDraft {
+        title,
+        start,
+        end,
+        description: None,
+        location: None,
+        attendees: Vec::new(),
+    })
+}
+
+fn event_changes(
+    start: Option<String>,
+    end: Option<String>,
+    all_day: bool,
+    tz: Tz,
+) -> std::result::Result<EventChanges, String> {
+    if start.is_some() != end.is_some() {
+        return Err("Provide both start and end when rescheduling an event.".to_string());
+    }
+    let start = start
+        .map(|start| parse_input_time(&start, tz, all_day))
+        .transpose()?;
+    let end = end
+        .map(|end| parse_input_time(&end, tz, all_day))
+        .transpose()?;
+    if let (Some(start), Some(end)) = (start, end)
+        && end.to_utc(tz) <= start.to_utc(tz)
+    {
+        return Err("end must be after start.".to_string());
+    }
+    Ok(EventChanges {
+        start,
+        end,
+        ..EventChanges::default()
+    })
+}
+
+#[async_trait]
+impl Tool for CalendarTool {
+    fn name(&self) -> &str {
+        "calendar"
+    }
+
+    fn description(&self) -> &str {
+        "Access Google Calendar or CalDAV calendars: list calendars and events, find free time slots, and create or update events. Times accept RFC 3339 or local times in the given timezone."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["list_calendars", "list_events", "find_free_slots", "create_event", "update_event"],
+                    "description": "Calendar operation to perform"
+                },
+                "provider": {
+                    "type": "string",
+                    "enum": ["google", "caldav"],
+                    "description": "Calendar backend (default: google)"
+                },
+                "timezone": {
+                    "type": "string",
+                    "description": "IANA timezone for local times, working hours and results (default: UTC)"
+                },
+                "calendar": {
+                    "type": "string",
+                    "description": "Calendar id from list_calendars (default: primary calendar)"
+                },
+                "calendars": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "find_free_slots: calendars whose events count as busy (default: primary calendar)"
+                },
+                "start": { "type": "string", "description": "Range or event start, e.g. 2026-10-20T09:00 or 2026-10-20" },
+                "end": { "type": "string", "description": "Range or event end. All-day event end dates are exclusive." },
+                "limit": { "type": "integer", "description": "Maximum events or slots to return" },
+                "duration_minutes": { "type": "integer", "description": "find_free_slots: minimum slot length" },
+                "working_hours_start": { "type": "string", "description": "find_free_slots: start of the working day, HH:MM (default 09:00 when working hours are set)" },
+                "working_hours_end": { "type": "string", "description": "find_free_slots: end of the working day, HH:MM (default 17:00 when working hours are set)" },
+                "include_weekends": { "type": "boolean", "description": "find_free_slots: include Saturdays and Sundays (default false)" },
+                "event_id": { "type": "string", "description": "update_event: event id from list_events" },
+                "title": { "type": "string", "description": "Event title" },
+                "all_day": { "type": "boolean", "description": "Treat start/end as dates of an all-day event" },
+                "description": { "type": "string" },
+                "location": { "type": "string" },
+                "attendees": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Attendee email addresses (update_event replaces the list)"
+                }
+            },
+            "required": ["operation"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let input: CalendarInput = match serde_json::from_value(input) {
+            Ok(input) => input,
+            Err(e) => {
+                return Ok(ToolOutput::error(format!(
+                    "Invalid input: {}. Required: operation (list_calendars|list_events|find_free_slots|create_event|update_event).",
+                    e
+                )));
+            }
+        };
+        let tz = match parse_timezone(input.timezone.as_deref()) {
+            Ok(tz) => tz,
+            Err(message) => return Ok(ToolOutput::error(message)),
+        };
+        let provider = match self.provider(input.provider, tz) {
+            Ok(provider) => provider,
+            Err(message) => return Ok(ToolOutput::error(message)),
+        };
+
+        match self.run(provider.as_ref(), input.operation, tz).await? {
+            Ok(result) => Ok(ToolOutput::success(result)),
+            Err(message) => Ok(ToolOutput::error(message)),
+        }
+    }
+}
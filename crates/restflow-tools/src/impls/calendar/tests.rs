@@ -0,0 +1,243 @@
+use super::*;
+
+fn utc(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+const SAMPLE_ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:abc-123\r\n\
+SUMMARY:Team sync\\, weekly\r\n\
+DTSTART;TZID=Europe/Berlin:20261020T100000\r\n\
+DURATION:PT30M\r\n\
+DESCRIPTION:Agenda:\\nupdates and\r\n  blockers\r\n\
+ATTENDEE;CN=\"Ann: PM\":mailto:ann@example.com\r\n\
+SEQUENCE:2\r\n\
+BEGIN:VALARM\r\n\
+TRIGGER:-PT15M\r\n\
+ACTION:DISPLAY\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:holiday\r\n\
+SUMMARY:Holiday\r\n\
+DTSTART;VALUE=DATE:20261021\r\n\
+TRANSP:TRANSPARENT\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+#[test]
+fn test_parse_ical_events() {
+    let events = ical::parse_events(SAMPLE_ICS, "/cal/abc.ics", "/cal/", Tz::UTC);
+    assert_eq!(events.len(), 2);
+
+    let sync = &events[0];
+    assert_eq!(sync.id, "/cal/abc.ics");
+    assert_eq!(sync.title, "Team sync, weekly");
+    assert_eq!(sync.start, EventTime::DateTime(utc("2026-10-20T08:00:00Z")));
+    assert_eq!(sync.end, EventTime::DateTime(utc("2026-10-20T08:30:00Z")));
+    assert_eq!(
+        sync.description.as_deref(),
+        Some("Agenda:\nupdates and blockers")
+    );
+    assert_eq!(sync.attendees, vec!["ann@example.com"]);
+    assert!(sync.busy);
+
+    let holiday = &events[1];
+    assert_eq!(
+        holiday.end,
+        EventTime::Date(NaiveDate::from_ymd_opt(2026, 10, 22).unwrap())
+    );
+    assert!(!holiday.busy);
+}
+
+#[test]
+fn test_build_and_update_ical_event() {
+    let draft = EventDraft {
+        title: "Review; plan".to_string(),
+        start: EventTime::DateTime(utc("2026-10-20T09:00:00Z")),
+        end: EventTime::DateTime(utc("2026-10-20T10:00:00Z")),
+        description: Some("line one\nline two".to_string()),
+        location: None,
+        attendees: vec!["bob@example.com".to_string()],
+    };
+    let now = utc("2026-10-16T12:00:00Z");
+    let text = ical::build_event("uid-1", &draft, now);
+    assert!(text.contains("SUMMARY:Review\\; plan\r\n"));
+    assert!(text.contains("DTSTART:20261020T090000Z\r\n"));
+    let parsed = ical::parse_events(&text, "/cal/uid-1.ics", "/cal/", Tz::UTC);
+    assert_eq!(parsed[0].title, "Review; plan");
+    assert_eq!(parsed[0].description.as_deref(), Some("line one\nline two"));
+
+    let changes = EventChanges {
+        title: Some("Moved sync".to_string()),
+        start: Some(EventTime::DateTime(utc("2026-10-20T12:00:00Z"))),
+        end: Some(EventTime::DateTime(utc("2026-10-20T12:45:00Z"))),
+        ..EventChanges::default()
+    };
+    let updated = ical::apply_changes(SAMPLE_ICS, &changes, now);
+    assert!(updated.contains("SEQUENCE:3\r\n"));
+    assert!(updated.contains("TRIGGER:-PT15M\r\n"));
+    assert!(!updated.contains("DURATION"));
+    let events = ical::parse_events(&updated, "/cal/abc.ics", "/cal/", Tz::UTC);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].title, "Moved sync");
+    assert_eq!(
+        events[0].end,
+        EventTime::DateTime(utc("2026-10-20T12:45:00Z"))
+    );
+    assert_eq!(events[0].attendees, vec!["ann@example.com"]);
+    assert_eq!(events[1].title, "Holiday");
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(ical::parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+    assert_eq!(ical::parse_duration("P1DT2H"), Some(Duration::hours(26)));
+    assert_eq!(ical::parse_duration("-PT15M"), Some(Duration::minutes(-15)));
+    assert_eq!(ical::parse_duration("1H"), None);
+}
+
+#[test]
+fn test_parse_input_time() {
+    let berlin: Tz = "Europe/Berlin".parse().unwrap();
+    assert_eq!(
+        parse_input_time("2026-10-20T09:00", berlin, false).unwrap(),
+        EventTime::DateTime(utc("2026-10-20T07:00:00Z"))
+    );
+    assert_eq!(
+        parse_input_time("2026-10-20T09:00:00-04:00", berlin, false).unwrap(),
+        EventTime::DateTime(utc("2026-10-20T13:00:00Z"))
+    );
+    assert_eq!(
+        parse_input_time("2026-10-20", berlin, true).unwrap(),
+        EventTime::Date(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap())
+    );
+    assert!(parse_input_time("next tuesday", berlin, false).is_err());
+}
+
+#[test]
+fn test_find_free_slots_with_working_hours() {
+    let berlin: Tz = "Europe/Berlin".parse().unwrap();
+    let hours = WorkingHours {
+        start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+    };
+    // Friday 2026-10-23 to Monday 2026-10-26 (Berlin is UTC+2 until the 25th).
+    let busy = vec![
+        (utc("2026-10-23T07:00:00Z"), utc("2026-10-23T08:00:00Z")),
+        (utc("2026-10-23T07:30:00Z"), utc("2026-10-23T10:00:00Z")),
+        (utc("2026-10-23T12:00:00Z"), utc("2026-10-23T14:30:00Z")),
+    ];
+    let slots = find_free_slots(
+        busy,
+        utc("2026-10-23T00:00:00Z"),
+        utc("2026-10-27T00:00:00Z"),
+        Duration::minutes(60),
+        berlin,
+        Some(hours),
+        false,
+    );
+
+    assert_eq!(
+        slots,
+        vec![
+            (utc("2026-10-23T10:00:00Z"), utc("2026-10-23T12:00:00Z")),
+            // Monday after the DST change: 09:00-17:00 CET.
+            (utc("2026-10-26T08:00:00Z"), utc("2026-10-26T16:00:00Z")),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_multistatus() {
+    let body = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/dav/calendars/ann/</d:href>
+    <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/dav/calendars/ann/work/</d:href>
+    <d:propstat><d:prop>
+      <d:displayname>Work</d:displayname>
+      <d:resourcetype><d:collection/><cal:calendar/></d:resourcetype>
+    </d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/dav/calendars/ann/work/a.ics</d:href>
+    <d:propstat><d:prop><cal:calendar-data>BEGIN:VCALENDAR
+END:VCALENDAR</cal:calendar-data></d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+    let entries = caldav::parse_multistatus(body).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert!(!entries[0].is_calendar);
+    assert!(entries[1].is_calendar);
+    assert_eq!(entries[1].display_name.as_deref(), Some("Work"));
+    assert!(
+        entries[2]
+            .calendar_data
+            .as_deref()
+            .unwrap()
+            .starts_with("BEGIN:VCALENDAR")
+    );
+}
+
+#[test]
+fn test_parse_google_event() {
+    let value = json!({
+        "id": "evt1",
+        "summary": "Lunch",
+        "status": "confirmed",
+        "transparency": "transparent",
+        "start": { "dateTime": "2026-10-20T12:00:00+02:00" },
+        "end": { "dateTime": "2026-10-20T13:00:00+02:00" },
+        "attendees": [{ "email": "ann@example.com" }],
+        "htmlLink": "https://calendar.google.com/event?eid=1",
+    });
+    let event = google::parse_event(&value, "primary").unwrap();
+    assert_eq!(
+        event.start,
+        EventTime::DateTime(utc("2026-10-20T10:00:00Z"))
+    );
+    assert!(!event.busy);
+    assert_eq!(event.attendees, vec!["ann@example.com"]);
+}
+
+#[tokio::test]
+async fn test_missing_credentials() {
+    let tool = CalendarTool::new()
+        .unwrap()
+        .with_secret_resolver(Arc::new(|_| None));
+    let output = tool
+        .execute(json!({ "operation": "list_calendars", "provider": "caldav" }))
+        .await
+        .unwrap();
+    assert!(!output.success);
+    assert!(output.error.unwrap().contains("CALDAV_URL"));
+
+    let output = tool
+        .execute(json!({ "operation": "list_calendars", "timezone": "Mars/Olympus" }))
+        .await
+        .unwrap();
+    assert!(output.error.unwrap().contains("Unknown timezone"));
+}
+
+#[test]
+fn test_caldav_rejects_foreign_hrefs() {
+    let provider = CalDavProvider::new(
+        reqwest::Client::new(),
+        "https://dav.example.com/calendars/ann/",
+        "ann".to_string(),
+        "secret".to_string(),
+        Tz::UTC,
+    )
+    .unwrap();
+    assert!(provider.resolve("/calendars/ann/work/").is_ok());
+    assert!(provider.resolve("https://evil.example.net/steal").is_err());
+    assert!(provider.resolve("//evil.example.net/steal").is_err());
+}
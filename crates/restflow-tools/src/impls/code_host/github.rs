@@ -0,0 +1,361 @@
+//! GitHub REST API provider.
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::{
+    ApiClient, CheckState, CiCheck, CiStatus, CodeHostProvider, Comment, CommentTarget, Issue,
+    ItemState, NewIssue, NewPullRequest, ProviderResult, PullRequest, Review, ReviewComment,
+    ReviewFeedback, id_field, str_field,
+};
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+const GITHUB_API_VERSION: &str = "2022-11-28";
+
+pub(crate) struct GitHubProvider {
+    api: ApiClient,
+}
+
+impl GitHubProvider {
+    pub(crate) fn new(client: reqwest::Client, token: String, base_url: Option<String>) -> Self {
+        let headers = vec![
+            ("Authorization", format!("Bearer {}", token)),
+            ("Accept", "application/vnd.github+json".to_string()),
+            ("X-GitHub-Api-Version", GITHUB_API_VERSION.to_string()),
+        ];
+        Self {
+            api: ApiClient::new(
+                client,
+                "GitHub",
+                base_url.as_deref().unwrap_or(GITHUB_API_BASE),
+                token,
+                headers,
+            ),
+        }
+    }
+}
+
+fn state_param(state: ItemState) -> &'static str {
+    match state {
+        ItemState::Open => "open",
+        ItemState::Closed => "closed",
+        ItemState::All => "all",
+    }
+}
+
+fn login(value: &Value) -> Option<String> {
+    value.get("user").and_then(|user| str_field(user, "login"))
+}
+
+pub(super) fn parse_issue(value: &Value) -> Issue {
+    Issue {
+        number: value["number"].as_u64().unwrap_or_default(),
+        title: str_field(value, "title").unwrap_or_default(),
+        state: str_field(value, "state").unwrap_or_default(),
+        author: login(value),
+        url: str_field(value, "html_url"),
+        labels: value["labels"]
+            .as_array()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| str_field(label, "name"))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        created_at: str_field(value, "created_at"),
+    }
+}
+
+pub(super) fn parse_pull_request(value: &Value) -> PullRequest {
+    let state = if value["merged_at"].is_string() {
+        "merged".to_string()
+    } else {
+        str_field(value, "state").unwrap_or_default()
+    };
+    PullRequest {
+        number: value["number"].as_u64().unwrap_or_default(),
+        title: str_field(value, "title").unwrap_or_default(),
+        state,
+        author: login(value),
+        url: str_field(value, "html_url"),
+        head: str_field(&value["head"], "ref"),
+        base: str_field(&value["base"], "ref"),
+        head_sha: str_field(&value["head"], "sha"),
+        draft: value["draft"].as_bool().unwrap_or(false),
+        body: None,
+    }
+}
+
+fn parse_review_comment(value: &Value) -> ReviewComment {
+    ReviewComment {
+        id: id_field(value, "id"),
+        thread_id: None,
+        author: login(value),
+        body: str_field(value, "body").unwrap_or_default(),
+        path: str_field(value, "path"),
+        line: value["line"]
+            .as_u64()
+            .or_else(|| value["original_line"].as_u64()),
+        url: str_field(value, "html_url"),
+        in_reply_to: value
+            .get("in_reply_to_id")
+            .filter(|id| !id.is_null())
+            .map(|id| id.to_string()),
+    }
+}
+
+fn check_run_state(value: &Value) -> (CheckState, String) {
+    if value["status"].as_str() != Some("completed") {
+        let status = str_field(value, "status").unwrap_or_default();
+        return (CheckState::Pending, status);
+    }
+    let conclusion = str_field(value, "conclusion").unwrap_or_default();
+    let state = match conclusion.as_str() {
+        "success" | "neutral" => CheckState::Success,
+        "skipped" => CheckState::Skipped,
+        _ => CheckState::Failure,
+    };
+    (state, conclusion)
+}
+
+/// Merge check runs (GitHub Actions, apps) with legacy commit statuses.
+pub(super) fn parse_checks(check_runs: &Value, combined_status: &Value) -> Vec<CiCheck> {
+    let mut checks: Vec<CiCheck> = check_runs["check_runs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|run| {
+            let (state, detail) = check_run_state(run);
+            CiCheck {
+                name: str_field(run, "name").unwrap_or_default(),
+                state,
+                detail,
+                url: str_field(run, "html_url"),
+            }
+        })
+        .collect();
+
+    checks.extend(
+        combined_status["statuses"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|status| {
+                let detail = str_field(status, "state").unwrap_or_default();
+                let state = match detail.as_str() {
+                    "success" => CheckState::Success,
+                    "pending" => CheckState::Pending,
+                    _ => CheckState::Failure,
+                };
+                CiCheck {
+                    name: str_field(status, "context").unwrap_or_default(),
+                    state,
+                    detail,
+                    url: str_field(status, "target_url"),
+                }
+            }),
+    );
+    checks
+}
+
+fn created_comment(value: &Value) -> Comment {
+    Comment {
+        id: id_field(value, "id"),
+        url: str_field(value, "html_url"),
+    }
+}
+
+#[async_trait]
+impl CodeHostProvider for GitHubProvider {
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ItemState,
+        limit: usize,
+    ) -> ProviderResult<Vec<Issue>> {
+        let items = self
+            .api
+            .get(
+                &format!("/repos/{}/issues", repo),
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        // The issues endpoint also returns pull requests.
+        Ok(items
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|item| item.get("pull_request").is_none())
+            .map(parse_issue)
+            .collect())
+    }
+
+    async fn create_issue(&self, repo: &str, issue: NewIssue<'_>) -> ProviderResult<Issue> {
+        let created = self
+            .api
+            .post(
+                &format!("/repos/{}/issues", repo),
+                json!({
+                    "title": issue.title,
+                    "body": issue.body,
+                    "labels": issue.labels,
+                }),
+            )
+            .await?;
+        Ok(parse_issue(&created))
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: ItemState,
+        limit: usize,
+    ) -> ProviderResult<Vec<PullRequest>> {
+        let items = self
+            .api
+            .get(
+                &format!("/repos/{}/pulls", repo),
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(items
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(parse_pull_request)
+            .collect())
+    }
+
+    async fn get_pull_request(&self, repo: &str, number: u64) -> ProviderResult<PullRequest> {
+        let value = self
+            .api
+            .get(&format!("/repos/{}/pulls/{}", repo, number), &[])
+            .await?;
+        let mut pull_request = parse_pull_request(&value);
+        pull_request.body = str_field(&value, "body");
+        Ok(pull_request)
+    }
+
+    async fn create_pull_request(
+        &self,
+        repo: &str,
+        pull_request: NewPullRequest<'_>,
+    ) -> ProviderResult<PullRequest> {
+        let created = self
+            .api
+            .post(
+                &format!("/repos/{}/pulls", repo),
+                json!({
+                    "title": pull_request.title,
+                    "head": pull_request.head,
+                    "base": pull_request.base,
+                    "body": pull_request.body,
+                    "draft": pull_request.draft,
+                }),
+            )
+            .await?;
+        Ok(parse_pull_request(&created))
+    }
+
+    async fn list_review_comments(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> ProviderResult<ReviewFeedback> {
+        let page = [("per_page", "100".to_string())];
+        let reviews = self
+            .api
+            .get(&format!("/repos/{}/pulls/{}/reviews", repo, number), &page)
+            .await?;
+        let comments = self
+            .api
+            .get(&format!("/repos/{}/pulls/{}/comments", repo, number), &page)
+            .await?;
+
+        let reviews = reviews
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|review| Review {
+                id: id_field(review, "id"),
+                author: login(review),
+                state: str_field(review, "state").unwrap_or_default(),
+                body: str_field(review, "body").unwrap_or_default(),
+            })
+            .collect();
+        let comments = comments
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(parse_review_comment)
+            .collect();
+        Ok(ReviewFeedback { reviews, comments })
+    }
+
+    async fn comment(
+        &self,
+        repo: &str,
+        number: u64,
+        _target: CommentTarget,
+        body: &str,
+    ) -> ProviderResult<Comment> {
+        // Pull requests share the issue comment endpoint.
+        let created = self
+            .api
+            .post(
+                &format!("/repos/{}/issues/{}/comments", repo, number),
+                json!({ "body": body }),
+            )
+            .await?;
+        Ok(created_comment(&created))
+    }
+
+    async fn reply_review_comment(
+        &self,
+        repo: &str,
+        number: u64,
+        comment_id: &str,
+        body: &str,
+    ) -> ProviderResult<Comment> {
+        let comment_id: u64 = comment_id
+            .parse()
+            .map_err(|_| format!("Invalid GitHub comment id '{}'", comment_id))?;
+        let created = self
+            .api
+            .post(
+                &format!(
+                    "/repos/{}/pulls/{}/comments/{}/replies",
+                    repo, number, comment_id
+                ),
+                json!({ "body": body }),
+            )
+            .await?;
+        Ok(created_comment(&created))
+    }
+
+    async fn ci_status(&self, repo: &str, git_ref: &str) -> ProviderResult<CiStatus> {
+        let git_ref = urlencoding::encode(git_ref);
+        let check_runs = self
+            .api
+            .get(
+                &format!("/repos/{}/commits/{}/check-runs", repo, git_ref),
+                &[("per_page", "100".to_string())],
+            )
+            .await?;
+        let combined_status = self
+            .api
+            .get(&format!("/repos/{}/commits/{}/status", repo, git_ref), &[])
+            .await?;
+        Ok(CiStatus::from_checks(parse_checks(
+            &check_runs,
+            &combined_status,
+        )))
+    }
+}
@@ -0,0 +1,359 @@
+//! GitLab REST API provider. Merge requests map onto pull requests.
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use super::{
+    ApiClient, CheckState, CiCheck, CiStatus, CodeHostProvider, Comment, CommentTarget, Issue,
+    ItemState, NewIssue, NewPullRequest, ProviderResult, PullRequest, ReviewComment,
+    ReviewFeedback, id_field, str_field,
+};
+
+const GITLAB_API_BASE: &str = "https://gitlab.com/api/v4";
+
+pub(crate) struct GitLabProvider {
+    api: ApiClient,
+}
+
+impl GitLabProvider {
+    pub(crate) fn new(client: reqwest::Client, token: String, base_url: Option<String>) -> Self {
+        let headers = vec![("PRIVATE-TOKEN", token.clone())];
+        Self {
+            api: ApiClient::new(
+                client,
+                "GitLab",
+                base_url.as_deref().unwrap_or(GITLAB_API_BASE),
+                token,
+                headers,
+            ),
+        }
+    }
+}
+
+fn project_path(repo: &str) -> String {
+    format!("/projects/{}", urlencoding::encode(repo))
+}
+
+fn state_param(state: ItemState) -> &'static str {
+    match state {
+        ItemState::Open => "opened",
+        ItemState::Closed => "closed",
+        ItemState::All => "all",
+    }
+}
+
+/// GitLab reports `opened`; normalize to the GitHub spelling.
+fn normalize_state(value: &Value) -> String {
+    match value["state"].as_str() {
+        Some("opened") => "open".to_string(),
+        Some(state) => state.to_string(),
+        None => String::new(),
+    }
+}
+
+fn username(value: &Value) -> Option<String> {
+    value
+        .get("author")
+        .and_then(|user| str_field(user, "username"))
+}
+
+pub(super) fn parse_issue(value: &Value) -> Issue {
+    Issue {
+        number: value["iid"].as_u64().unwrap_or_default(),
+        title: str_field(value, "title").unwrap_or_default(),
+        state: normalize_state(value),
+        author: username(value),
+        url: str_field(value, "web_url"),
+        labels: value["labels"]
+            .as_array()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        created_at: str_field(value, "created_at"),
+    }
+}
+
+pub(super) fn parse_merge_request(value: &Value) -> PullRequest {
+    PullRequest {
+        number: value["iid"].as_u64().unwrap_or_default(),
+        title: str_field(value, "title").unwrap_or_default(),
+        state: normalize_state(value),
+        author: username(value),
+        url: str_field(value, "web_url"),
+        head: str_field(value, "source_branch"),
+        base: str_field(value, "target_branch"),
+        head_sha: str_field(value, "sha"),
+        draft: value["draft"]
+            .as_bool()
+            .or_else(|| value["work_in_progress"].as_bool())
+            .unwrap_or(false),
+        body: None,
+    }
+}
+
+/// Flatten merge request discussions into review comments, skipping
+/// system notes such as "added 1 commit".
+pub(super) fn parse_discussions(discussions: &Value) -> Vec<ReviewComment> {
+    let mut comments = Vec::new();
+    for discussion in discussions.as_array().into_iter().flatten() {
+        let thread_id = str_field(discussion, "id");
+        let mut first_note: Option<String> = None;
+        for note in discussion["notes"].as_array().into_iter().flatten() {
+            if note["system"].as_bool() == Some(true) {
+                continue;
+            }
+            let id = id_field(note, "id");
+            let position = &note["position"];
+            comments.push(ReviewComment {
+                id: id.clone(),
+                thread_id: thread_id.clone(),
+                author: username(note),
+                body: str_field(note, "body").unwrap_or_default(),
+                path: str_field(position, "new_path").or_else(|| str_field(position, "old_path")),
+                line: position["new_line"]
+                    .as_u64()
+                    .or_else(|| position["old_line"].as_u64()),
+                url: None,
+                in_reply_to: first_note.clone(),
+            });
+            first_note.get_or_insert(id);
+        }
+    }
+    comments
+}
+
+pub(super) fn parse_jobs(jobs: &Value) -> Vec<CiCheck> {
+    jobs.as_array()
+        .into_iter()
+        .flatten()
+        .map(|job| {
+            let detail = str_field(job, "status").unwrap_or_default();
+            let state = match detail.as_str() {
+                "success" => CheckState::Success,
+                "failed" if job["allow_failure"].as_bool() == Some(true) => CheckState::Skipped,
+                "failed" | "canceled" => CheckState::Failure,
+                "skipped" | "manual" => CheckState::Skipped,
+                _ => CheckState::Pending,
+            };
+            CiCheck {
+                name: str_field(job, "name").unwrap_or_default(),
+                state,
+                detail,
+                url: str_field(job, "web_url"),
+            }
+        })
+        .collect()
+}
+
+fn is_commit_sha(git_ref: &str) -> bool {
+    git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn created_note(value: &Value) -> Comment {
+    Comment {
+        id: id_field(value, "id"),
+        url: None,
+    }
+}
+
+#[async_trait]
+impl CodeHostProvider for GitLabProvider {
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ItemState,
+        limit: usize,
+    ) -> ProviderResult<Vec<Issue>> {
+        let items = self
+            .api
+            .get(
+                &format!("{}/issues", project_path(repo)),
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(items
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(parse_issue)
+            .collect())
+    }
+
+    async fn create_issue(&self, repo: &str, issue: NewIssue<'_>) -> ProviderResult<Issue> {
+        let created = self
+            .api
+            .post(
+                &format!("{}/issues", project_path(repo)),
+                json!({
+                    "title": issue.title,
+                    "description": issue.body,
+                    "labels": issue.labels.join(","),
+                }),
+            )
+            .await?;
+        Ok(parse_issue(&created))
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: ItemState,
+        limit: usize,
+    ) -> ProviderResult<Vec<PullRequest>> {
+        let items = self
+            .api
+            .get(
+                &format!("{}/merge_requests", project_path(repo)),
+                &[
+                    ("state", state_param(state).to_string()),
+                    ("per_page", limit.to_string()),
+                ],
+            )
+            .await?;
+        Ok(items
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(parse_merge_request)
+            .collect())
+    }
+
+    async fn get_pull_request(&self, repo: &str, number: u64) -> ProviderResult<PullRequest> {
+        let value = self
+            .api
+            .get(
+                &format!("{}/merge_requests/{}", project_path(repo), number),
+                &[],
+            )
+            .await?;
+        let mut pull_request = parse_merge_request(&value);
+        pull_request.body = str_field(&value, "description");
+        Ok(pull_request)
+    }
+
+    async fn create_pull_request(
+        &self,
+        repo: &str,
+        pull_request: NewPullRequest<'_>,
+    ) -> ProviderResult<PullRequest> {
+        let title = if pull_request.draft {
+            format!("Draft: {}", pull_request.title)
+        } else {
+            pull_request.title.to_string()
+        };
+        let created = self
+            .api
+            .post(
+                &format!("{}/merge_requests", project_path(repo)),
+                json!({
+                    "title": title,
+                    "source_branch": pull_request.head,
+                    "target_branch": pull_request.base,
+                    "description": pull_request.body,
+                }),
+            )
+            .await?;
+        Ok(parse_merge_request(&created))
+    }
+
+    async fn list_review_comments(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> ProviderResult<ReviewFeedback> {
+        let discussions = self
+            .api
+            .get(
+                &format!(
+                    "{}/merge_requests/{}/discussions",
+                    project_path(repo),
+                    number
+                ),
+                &[("per_page", "100".to_string())],
+            )
+            .await?;
+        Ok(ReviewFeedback {
+            reviews: Vec::new(),
+            comments: parse_discussions(&discussions),
+        })
+    }
+
+    async fn comment(
+        &self,
+        repo: &str,
+        number: u64,
+        target: CommentTarget,
+        body: &str,
+    ) -> ProviderResult<Comment> {
+        let collection = match target {
+            CommentTarget::PullRequest => "merge_requests",
+            CommentTarget::Issue => "issues",
+        };
+        let created = self
+            .api
+            .post(
+                &format!("{}/{}/{}/notes", project_path(repo), collection, number),
+                json!({ "body": body }),
+            )
+            .await?;
+        Ok(created_note(&created))
+    }
+
+    async fn reply_review_comment(
+        &self,
+        repo: &str,
+        number: u64,
+        comment_id: &str,
+        body: &str,
+    ) -> ProviderResult<Comment> {
+        if !comment_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("Invalid GitLab thread id '{}'", comment_id));
+        }
+        let created = self
+            .api
+            .post(
+                &format!(
+                    "{}/merge_requests/{}/discussions/{}/notes",
+                    project_path(repo),
+                    number,
+                    comment_id
+                ),
+                json!({ "body": body }),
+            )
+            .await?;
+        Ok(created_note(&created))
+    }
+
+    async fn ci_status(&self, repo: &str, git_ref: &str) -> ProviderResult<CiStatus> {
+        let filter = if is_commit_sha(git_ref) { "sha" } else { "ref" };
+        let pipelines = self
+            .api
+            .get(
+                &format!("{}/pipelines", project_path(repo)),
+                &[(filter, git_ref.to_string()), ("per_page", "1".to_string())],
+            )
+            .await?;
+        let Some(pipeline) = pipelines.as_array().and_then(|list| list.first()) else {
+            return Ok(CiStatus::from_checks(Vec::new()));
+        };
+        let jobs = self
+            .api
+            .get(
+                &format!(
+                    "{}/pipelines/{}/jobs",
+                    project_path(repo),
+                    id_field(pipeline, "id")
+                ),
+                &[("per_page", "100".to_string())],
+            )
+            .await?;
+        Ok(CiStatus::from_checks(parse_jobs(&jobs)))
+    }
+}
@@ -0,0 +1,689 @@
+//! Code hosting integration tool (GitHub, GitLab).
+//!
+//! Lets coding agents list and open issues and pull requests, read review
+//! feedback, reply to it, and check CI results. Each host is a
+//! [`CodeHostProvider`] that maps its REST API onto the shared types below,
+//! so the tool schema stays the same for every provider.
+//!
+//! Tokens are read from secrets (`GITHUB_TOKEN`, `GITLAB_TOKEN`). Self-hosted
+//! instances are supported through the optional `GITHUB_API_URL` and
+//! `GITLAB_API_URL` secrets.
+
+mod github;
+mod gitlab;
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+use crate::Result;
+use crate::check_security;
+use crate::http_client::build_http_client;
+use crate::security::SecurityGate;
+use crate::{SecretResolver, Tool, ToolAction, ToolOutput};
+
+use self::github::GitHubProvider;
+use self::gitlab::GitLabProvider;
+
+const DEFAULT_LIST_LIMIT: usize = 20;
+const MAX_LIST_LIMIT: usize = 100;
+
+pub(crate) type ProviderResult<T> = std::result::Result<T, String>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProviderKind {
+    #[default]
+    Github,
+    Gitlab,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ItemState {
+    #[default]
+    Open,
+    Closed,
+    All,
+}
+
+/// What a top-level comment is attached to. GitLab keeps separate endpoints
+/// for issues and merge requests; GitHub accepts either number on one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CommentTarget {
+    #[default]
+    PullRequest,
+    Issue,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeHostInput {
+    #[serde(default)]
+    provider: ProviderKind,
+    repo: String,
+    #[serde(flatten)]
+    operation: CodeHostOperation,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum CodeHostOperation {
+    ListIssues {
+        #[serde(default)]
+        state: ItemState,
+        limit: Option<usize>,
+    },
+    CreateIssue {
+        title: String,
+        body: Option<String>,
+        #[serde(default)]
+        labels: Vec<String>,
+    },
+    ListPullRequests {
+        #[serde(default)]
+        state: ItemState,
+        limit: Option<usize>,
+    },
+    GetPullRequest {
+        number: u64,
+    },
+    CreatePullRequest {
+        title: String,
+        head: String,
+        base: String,
+        body: Option<String>,
+        #[serde(default)]
+        draft: bool,
+    },
+    ListReviewComments {
+        number: u64,
+    },
+    Comment {
+        number: u64,
+        body: String,
+        #[serde(default)]
+        target: CommentTarget,
+    },
+    ReplyReviewComment {
+        number: u64,
+        comment_id: String,
+        body: String,
+    },
+    CiStatus {
+        #[serde(rename = "ref")]
+        git_ref: String,
+    },
+}
+
+impl CodeHostOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::ListIssues { .. } => "list_issues",
+            Self::CreateIssue { .. } => "create_issue",
+            Self::ListPullRequests { .. } => "list_pull_requests",
+            Self::GetPullRequest { .. } => "get_pull_request",
+            Self::CreatePullRequest { .. } => "create_pull_request",
+            Self::ListReviewComments { .. } => "list_review_comments",
+            Self::Comment { .. } => "comment",
+            Self::ReplyReviewComment { .. } => "reply_review_comment",
+            Self::CiStatus { .. } => "ci_status",
+        }
+    }
+
+    /// Summary for the security gate, or `None` for read-only operations.
+    fn write_summary(&self, repo: &str) -> Option<String> {
+        match self {
+            Self::CreateIssue { title, .. } => Some(format!("Open issue '{}' in {}", title, repo)),
+            Self::CreatePullRequest {
+                title, head, base, ..
+            } => Some(format!(
+                "Open pull request '{}' ({} -> {}) in {}",
+                title, head, base, repo
+            )),
+            Self::Comment { number, .. } => Some(format!("Comment on #{} in {}", number, repo)),
+            Self::ReplyReviewComment { number, .. } => Some(format!(
+                "Reply to review comment on #{} in {}",
+                number, repo
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub author: Option<String>,
+    pub url: Option<String>,
+    pub labels: Vec<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub author: Option<String>,
+    pub url: Option<String>,
+    pub head: Option<String>,
+    pub base: Option<String>,
+    pub head_sha: Option<String>,
+    pub draft: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Review {
+    pub id: String,
+    pub author: Option<String>,
+    pub state: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ReviewComment {
+    pub id: String,
+    /// Thread to reply to (GitLab discussion id). GitHub replies by `id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    pub author: Option<String>,
+    pub body: String,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub url: Option<String>,
+    pub in_reply_to: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ReviewFeedback {
+    pub reviews: Vec<Review>,
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Comment {
+    pub id: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CheckState {
+    Pending,
+    Success,
+    Failure,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CiCheck {
+    pub name: String,
+    pub state: CheckState,
+    /// Provider-specific status or conclusion, e.g. `timed_out`.
+    pub detail: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CiStatus {
+    /// `success`, `failure`, `pending`, or `none` when no checks ran.
+    pub state: &'static str,
+    pub checks: Vec<CiCheck>,
+}
+
+impl CiStatus {
+    pub(crate) fn from_checks(checks: Vec<CiCheck>) -> Self {
+        let state = if checks.is_empty() {
+            "none"
+        } else if checks.iter().any(|c| c.state == CheckState::Failure) {
+            "failure"
+        } else if checks.iter().any(|c| c.state == CheckState::Pending) {
+            "pending"
+        } else {
+            "success"
+        };
+        Self { state, checks }
+    }
+}
+
+pub(crate) struct NewIssue<'a> {
+    pub title: &'a str,
+    pub body: Option<&'a str>,
+    pub labels: &'a [String],
+}
+
+pub(crate) struct NewPullRequest<'a> {
+    pub title: &'a str,
+    pub head: &'a str,
+    pub base: &'a str,
+    pub body: Option<&'a str>,
+    pub draft: bool,
+}
+
+/// REST API of a code hosting service.
+#[async_trait]
+pub(crate) trait CodeHostProvider: Send + Sync {
+    async fn list_issues(
+        &self,
+        repo: &str,
+        state: ItemState,
+        limit: usize,
+    ) -> ProviderResult<Vec<Issue>>;
+
+    async fn create_issue(&self, repo: &str, issue: NewIssue<'_>) -> ProviderResult<Issue>;
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: ItemState,
+        limit: usize,
+    ) -> ProviderResult<Vec<PullRequest>>;
+
+    async fn get_pull_request(&self, repo: &str, number: u64) -> ProviderResult<PullRequest>;
+
+    async fn create_pull_request(
+        &self,
+        repo: &str,
+        pull_request: NewPullRequest<'_>,
+    ) -> ProviderResult<PullRequest>;
+
+    async fn list_review_comments(&self, repo: &str, number: u64)
+    -> ProviderResult<ReviewFeedback>;
+
+    async fn comment(
+        &self,
+        repo: &str,
+        number: u64,
+        target: CommentTarget,
+        body: &str,
+    ) -> ProviderResult<Comment>;
+
+    async fn reply_review_comment(
+        &self,
+        repo: &str,
+        number: u64,
+        comment_id: &str,
+        body: &str,
+    ) -> ProviderResult<Comment>;
+
+    async fn ci_status(&self, repo: &str, git_ref: &str) -> ProviderResult<CiStatus>;
+}
+
+/// Authenticated JSON client shared by the providers.
+pub(crate) struct ApiClient {
+    client: reqwest::Client,
+    provider: &'static str,
+    base_url: String,
+    token: String,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl ApiClient {
+    pub(crate) fn new(
+        client: reqwest::Client,
+        provider: &'static str,
+        base_url: &str,
+        token: String,
+        headers: Vec<(&'static str, String)>,
+    ) -> Self {
+        Self {
+            client,
+            provider,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            headers,
+        }
+    }
+
+    pub(crate) async fn get(&self, path: &str, query: &[(&str, String)]) -> ProviderResult<Value> {
+        let url = url::Url::parse_with_params(&format!("{}{}", self.base_url, path), query)
+            .map_err(|e| format!("Invalid {} API URL: {}", self.provider, e))?;
+        self.send(self.client.get(url)).await
+    }
+
+    pub(crate) async fn post(&self, path: &str, body: Value) -> ProviderResult<Value> {
+        let request = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .json(&body);
+        self.send(request).await
+    }
+
+    pub(crate) async fn patch(&self, path: &str, body: Value) -> ProviderResult<Value> {
+        let request = self
+            .client
+            .patch(format!("{}{}", self.base_url, path))
+            .json(&body);
+        self.send(request).await
+    }
+
+    async fn send(&self, mut request: reqwest::RequestBuilder) -> ProviderResult<Value> {
+        request = request.header("User-Agent", "restflow");
+        for (name, value) in &self.headers {
+            request = request.header(*name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| self.sanitize(&format!("{} request failed: {}", self.provider, e)))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        let body: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            let detail = match body.get("message").or_else(|| body.get("error")) {
+                Some(Value::String(message)) => message.clone(),
+                Some(other) => other.to_string(),
+                None => text,
+            };
+            return Err(self.sanitize(&format!(
+                "{} API error ({}): {}",
+                self.provider,
+                status.as_u16(),
+                detail
+            )));
+        }
+        Ok(body)
+    }
+
+    fn sanitize(&self, message: &str) -> String {
+        if self.token.is_empty() {
+            return message.to_string();
+        }
+        message.replace(&self.token, "***")
+    }
+}
+
+pub(crate) fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+pub(crate) fn id_field(value: &Value, key: &str) -> String {
+    match value.get(key) {
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Number(id)) => id.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Accept `owner/name` (GitHub) or `group/subgroup/name` (GitLab).
+fn validate_repo(repo: &str) -> std::result::Result<(), String> {
+    let valid_chars = repo
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+    let segments: Vec<&str> = repo.split('/').collect();
+    if !valid_chars
+        || segments.len() < 2
+        || segments
+            .iter()
+            .any(|segment| segment.is_empty() || *segment == "." || *segment == "..")
+    {
+        return Err(format!(
+            "Invalid repo '{}'. Use the form 'owner/name'.",
+            repo
+        ));
+    }
+    Ok(())
+}
+
+/// Issue, pull request, review, and CI tool for GitHub and GitLab.
+pub struct GitHubTool {
+    client: reqwest::Client,
+    secret_resolver: Option<SecretResolver>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl GitHubTool {
+    pub fn new() -> std::result::Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: build_http_client()?,
+            secret_resolver: None,
+            security_gate: None,
+            agent_id: None,
+            task_id: None,
+        })
+    }
+
+    pub fn with_secret_resolver(mut self, resolver: SecretResolver) -> Self {
+        self.secret_resolver = Some(resolver);
+        self
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    fn resolve_secret(&self, key: &str) -> Option<String> {
+        self.secret_resolver
+            .as_ref()
+            .and_then(|resolver| resolver(key))
+            .filter(|value| !value.trim().is_empty())
+    }
+
+    fn provider(
+        &self,
+        kind: ProviderKind,
+    ) -> std::result::Result<Box<dyn CodeHostProvider>, String> {
+        let (token_key, url_key) = match kind {
+            ProviderKind::Github => ("GITHUB_TOKEN", "GITHUB_API_URL"),
+            ProviderKind::Gitlab => ("GITLAB_TOKEN", "GITLAB_API_URL"),
+        };
+        let token = self.resolve_secret(token_key).ok_or_else(|| {
+            format!(
+                "{} is not set. Store an access token with the manage_secrets tool.",
+                token_key
+            )
+        })?;
+        let base_url = self.resolve_secret(url_key);
+        let client = self.client.clone();
+        Ok(match kind {
+            ProviderKind::Github => Box::new(GitHubProvider::new(client, token, base_url)),
+            ProviderKind::Gitlab => Box::new(GitLabProvider::new(client, token, base_url)),
+        })
+    }
+
+    async fn check_action_allowed(
+        &self,
+        operation: &str,
+        target: String,
+        summary: String,
+    ) -> Result<Option<String>> {
+        let action = ToolAction {
+            tool_name: self.name().to_string(),
+            operation: operation.to_string(),
+            target,
+            summary,
+        };
+
+        check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await
+    }
+}
+
+async fn run_operation(
+    provider: &dyn CodeHostProvider,
+    repo: &str,
+    operation: CodeHostOperation,
+) -> ProviderResult<Value> {
+    let limit = |limit: Option<usize>| limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT);
+    let to_json = |value: std::result::Result<Value, serde_json::Error>| {
+        value.map_err(|e| format!("Failed to serialize result: {}", e))
+    };
+
+    match operation {
+        CodeHostOperation::ListIssues { state, limit: n } => {
+            let issues = provider.list_issues(repo, state, limit(n)).await?;
+            Ok(json!({ "issues": issues }))
+        }
+        CodeHostOperation::CreateIssue {
+            title,
+            body,
+            labels,
+        } => {
+            let issue = NewIssue {
+                title: &title,
+                body: body.as_deref(),
+                labels: &labels,
+            };
+            to_json(serde_json::to_value(
+                provider.create_issue(repo, issue).await?,
+            ))
+        }
+        CodeHostOperation::ListPullRequests { state, limit: n } => {
+            let pull_requests = provider.list_pull_requests(repo, state, limit(n)).await?;
+            Ok(json!({ "pull_requests": pull_requests }))
+        }
+        CodeHostOperation::GetPullRequest { number } => to_json(serde_json::to_value(
+            provider.get_pull_request(repo, number).await?,
+        )),
+        CodeHostOperation::CreatePullRequest {
+            title,
+            head,
+            base,
+            body,
+            draft,
+        } => {
+            let pull_request = NewPullRequest {
+                title: &title,
+                head: &head,
+                base: &base,
+                body: body.as_deref(),
+                draft,
+            };
+            to_json(serde_json::to_value(
+                provider.create_pull_request(repo, pull_request).await?,
+            ))
+        }
+        CodeHostOperation::ListReviewComments { number } => to_json(serde_json::to_value(
+            provider.list_review_comments(repo, number).await?,
+        )),
+        CodeHostOperation::Comment {
+            number,
+            body,
+            target,
+        } => to_json(serde_json::to_value(
+            provider.comment(repo, number, target, &body).await?,
+        )),
+        CodeHostOperation::ReplyReviewComment {
+            number,
+            comment_id,
+            body,
+        } => to_json(serde_json::to_value(
+            provider
+                .reply_review_comment(repo, number, &comment_id, &body)
+                .await?,
+        )),
+        CodeHostOperation::CiStatus { git_ref } => to_json(serde_json::to_value(
+            provider.ci_status(repo, &git_ref).await?,
+        )),
+    }
+}
+
+#[async_trait]
+impl Tool for GitHubTool {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    fn description(&self) -> &str {
+        "Work with GitHub or GitLab repositories: list and create issues and pull requests, read review comments, comment and reply to reviews, and check CI status. Uses GITHUB_TOKEN or GITLAB_TOKEN from secrets."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": [
+                        "list_issues", "create_issue", "list_pull_requests", "get_pull_request",
+                        "create_pull_request", "list_review_comments", "comment",
+                        "reply_review_comment", "ci_status"
+                    ],
+                    "description": "Operation to perform"
+                },
+                "provider": {
+                    "type": "string",
+                    "enum": ["github", "gitlab"],
+                    "description": "Code host (default: github). GitLab merge requests are pull requests here."
+                },
+                "repo": { "type": "string", "description": "Repository as owner/name (GitLab: group/project)" },
+                "number": { "type": "integer", "description": "Issue or pull request number" },
+                "state": {
+                    "type": "string",
+                    "enum": ["open", "closed", "all"],
+                    "description": "list_issues/list_pull_requests: state filter (default: open)"
+                },
+                "limit": { "type": "integer", "description": "Maximum items to list (default 20, max 100)" },
+                "title": { "type": "string", "description": "create_issue/create_pull_request: title" },
+                "body": { "type": "string", "description": "Issue/pull request description or comment text" },
+                "labels": { "type": "array", "items": { "type": "string" }, "description": "create_issue: labels" },
+                "head": { "type": "string", "description": "create_pull_request: source branch" },
+                "base": { "type": "string", "description": "create_pull_request: target branch" },
+                "draft": { "type": "boolean", "description": "create_pull_request: open as draft" },
+                "target": {
+                    "type": "string",
+                    "enum": ["pull_request", "issue"],
+                    "description": "comment: whether number is a pull request or an issue (default: pull_request)"
+                },
+                "comment_id": {
+                    "type": "string",
+                    "description": "reply_review_comment: review comment id (GitLab: thread_id)"
+                },
+                "ref": { "type": "string", "description": "ci_status: branch, tag, or commit SHA" }
+            },
+            "required": ["operation", "repo"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let input: CodeHostInput = match serde_json::from_value(input) {
+            Ok(input) => input,
+            Err(e) => return Ok(ToolOutput::error(format!("Invalid input: {}", e))),
+        };
+        if let Err(message) = validate_repo(&input.repo) {
+            return Ok(ToolOutput::error(message));
+        }
+
+        if let Some(summary) = input.operation.write_summary(&input.repo)
+            && let Some(message) = self
+                .check_action_allowed(input.operation.name(), input.repo.clone(), summary)
+                .await?
+        {
+            return Ok(ToolOutput::error(message));
+        }
+
+        let provider = match self.provider(input.provider) {
+            Ok(provider) => provider,
+            Err(message) => return Ok(ToolOutput::error(message)),
+        };
+        match run_operation(provider.as_ref(), &input.repo, input.operation).await {
+            Ok(result) => Ok(ToolOutput::success(result)),
+            Err(message) => Ok(ToolOutput::error(message)),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use super::*;
+use std::collections::HashMap;
+
+fn tool_with_secrets(secrets: &[(&str, &str)]) -> GitHubTool {
+    let secrets: HashMap<String, String> = secrets
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    GitHubTool::new()
+        .unwrap()
+        .with_secret_resolver(Arc::new(move |key| secrets.get(key).cloned()))
+}
+
+#[test]
+fn test_validate_repo() {
+    assert!(validate_repo("octocat/hello-world").is_ok());
+    assert!(validate_repo("group/subgroup/project.name").is_ok());
+    assert!(validate_repo("octocat").is_err());
+    assert!(validate_repo("octocat/../admin").is_err());
+    assert!(validate_repo("octocat/repo?x=1").is_err());
+    assert!(validate_repo("/octocat/repo").is_err());
+}
+
+#[test]
+fn test_github_issue_and_pull_request_parsing() {
+    let issue = github::parse_issue(&json!({
+        "number": 7,
+        "title": "Crash on start",
+        "state": "open",
+        "user": { "login": "octocat" },
+        "html_url": "https://github.com/o/r/issues/7",
+        "labels": [{ "name": "bug" }],
+        "created_at": "2024-01-01T00:00:00Z"
+    }));
+    assert_eq!(issue.number, 7);
+    assert_eq!(issue.author.as_deref(), Some("octocat"));
+    assert_eq!(issue.labels, vec!["bug"]);
+
+    let pull_request = github::parse_pull_request(&json!({
+        "number": 8,
+        "title": "Fix crash",
+        "state": "closed",
+        "merged_at": "2024-01-02T00:00:00Z",
+        "user": { "login": "octocat" },
+        "head": { "ref": "fix-crash", "sha": "abc123" },
+        "base": { "ref": "main" },
+        "draft": false
+    }));
+    assert_eq!(pull_request.state, "merged");
+    assert_eq!(pull_request.head.as_deref(), Some("fix-crash"));
+    assert_eq!(pull_request.base.as_deref(), Some("main"));
+    assert_eq!(pull_request.head_sha.as_deref(), Some("abc123"));
+}
+
+#[test]
+fn test_github_checks_combine_runs_and_statuses() {
+    let checks = github::parse_checks(
+        &json!({ "check_runs": [
+            { "name": "build", "status": "completed", "conclusion": "success" },
+            { "name": "lint", "status": "in_progress", "conclusion": null }
+        ]}),
+        &json!({ "statuses": [
+            { "context": "ci/legacy", "state": "failure", "target_url": "https://ci/1" }
+        ]}),
+    );
+    assert_eq!(checks.len(), 3);
+    assert_eq!(checks[1].state, CheckState::Pending);
+    assert_eq!(checks[2].state, CheckState::Failure);
+    assert_eq!(CiStatus::from_checks(checks).state, "failure");
+}
+
+#[test]
+fn test_ci_status_summary() {
+    let check = |state| CiCheck {
+        name: "job".to_string(),
+        state,
+        detail: String::new(),
+        url: None,
+    };
+    assert_eq!(CiStatus::from_checks(Vec::new()).state, "none");
+    assert_eq!(
+        CiStatus::from_checks(vec![check(CheckState::Success), check(CheckState::Skipped)]).state,
+        "success"
+    );
+    assert_eq!(
+        CiStatus::from_checks(vec![check(CheckState::Success), check(CheckState::Pending)]).state,
+        "pending"
+    );
+}
+
+#[test]
+fn test_gitlab_merge_request_and_discussions_parsing() {
+    let merge_request = gitlab::parse_merge_request(&json!({
+        "iid": 3,
+        "title": "Add feature",
+        "state": "opened",
+        "author": { "username": "dev" },
+        "source_branch": "feature",
+        "target_branch": "main",
+        "sha": "def456",
+        "draft": true
+    }));
+    assert_eq!(merge_request.number, 3);
+    assert_eq!(merge_request.state, "open");
+    assert!(merge_request.draft);
+
+    let comments = gitlab::parse_discussions(&json!([
+        { "id": "d1", "notes": [
+            { "id": 10, "body": "Rename this", "author": { "username": "reviewer" },
+              "position": { "new_path": "src/lib.rs", "new_line": 12 } },
+            { "id": 11, "body": "Done", "author": { "username": "dev" } }
+        ]},
+        { "id": "d2", "notes": [
+            { "id": 12, "body": "added 1 commit", "system": true }
+        ]}
+    ]));
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].thread_id.as_deref(), Some("d1"));
+    assert_eq!(comments[0].path.as_deref(), Some("src/lib.rs"));
+    assert_eq!(comments[0].line, Some(12));
+    assert_eq!(comments[1].in_reply_to.as_deref(), Some("10"));
+}
+
+#[test]
+fn test_gitlab_jobs_parsing() {
+    let checks = gitlab::parse_jobs(&json!([
+        { "name": "test", "status": "success" },
+        { "name": "flaky", "status": "failed", "allow_failure": true },
+        { "name": "deploy", "status": "manual" },
+        { "name": "lint", "status": "running" }
+    ]));
+    let states: Vec<CheckState> = checks.iter().map(|check| check.state).collect();
+    assert_eq!(
+        states,
+        vec![
+            CheckState::Success,
+            CheckState::Skipped,
+            CheckState::Skipped,
+            CheckState::Pending
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_missing_token_reports_secret_name() {
+    let tool = tool_with_secrets(&[]);
+    let output = tool
+        .execute(json!({ "operation": "list_issues", "repo": "octocat/hello" }))
+        .await
+        .unwrap();
+    assert!(!output.success);
+    assert!(output.error.unwrap().contains("GITHUB_TOKEN"));
+
+    let output = tool
+        .execute(json!({
+            "operation": "ci_status",
+            "provider": "gitlab",
+            "repo": "group/project",
+            "ref": "main"
+        }))
+        .await
+        .unwrap();
+    assert!(!output.success);
+    assert!(output.error.unwrap().contains("GITLAB_TOKEN"));
+}
+
+#[tokio::test]
+async fn test_invalid_repo_rejected_before_request() {
+    let tool = tool_with_secrets(&[("GITHUB_TOKEN", "token")]);
+    let output = tool
+        .execute(json!({ "operation": "list_issues", "repo": "../etc" }))
+        .await
+        .unwrap();
+    assert!(!output.success);
+    assert!(output.error.unwrap().contains("Invalid repo"));
+}
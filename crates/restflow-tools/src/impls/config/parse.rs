@@ -33,6 +33,16 @@ pub(crate) fn parse_optional_timeout(value: &Value, key: &str) -> Result<Option<
         .ok_or_else(|| ToolError::Tool(format!("{key} must be a number or null")))
 }
 
+pub(crate) fn parse_optional_f64(value: &Value, key: &str) -> Result<Option<f64>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_f64()
+        .map(Some)
+        .ok_or_else(|| ToolError::Tool(format!("{key} must be a number or null")))
+}
+
 pub(crate) fn parse_optional_string_list(value: &Value, key: &str) -> Result<Option<Vec<String>>> {
     if value.is_null() {
         return Ok(None);
@@ -53,6 +63,13 @@ pub(crate) fn parse_optional_string_list(value: &Value, key: &str) -> Result<Opt
     Ok(Some(result))
 }
 
+pub(crate) fn parse_string(value: &Value, key: &str) -> Result<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ToolError::Tool(format!("{key} must be a string")))
+}
+
 #[allow(dead_code)]
 pub(crate) fn parse_optional_string(value: &Value, key: &str) -> Result<Option<String>> {
     if value.is_null() {
@@ -285,6 +285,8 @@ async fn test_listed_retention_fields_are_settable() {
         ("system.checkpoint_retention_days", json!(5)),
         ("system.memory_chunk_retention_days", json!(120)),
         ("system.log_file_retention_days", json!(30)),
+        ("system.artifact_retention_days", json!(7)),
+        ("system.artifact_max_total_mb", json!(512)),
     ];
 
     for (key, value) in updates {
@@ -303,6 +305,50 @@ async fn test_listed_retention_fields_are_settable() {
     }
 }
 
+#[tokio::test]
+async fn test_set_and_clear_budgets() {
+    let ctx = setup_storage();
+    let tool = ConfigTool::new(ctx.store).with_write(true);
+
+    for (key, value) in [
+        ("system.daily_budget_usd", json!(2.5)),
+        ("system.monthly_budget_usd", json!(40)),
+    ] {
+        let output = tool
+            .execute(json!({ "operation": "set", "key": key, "value": value }))
+            .await
+            .unwrap();
+        assert!(output.success, "set should succeed for '{key}'");
+    }
+
+    let output = tool.execute(json!({ "operation": "get" })).await.unwrap();
+    assert_eq!(
+        output.result.pointer("/system/daily_budget_usd"),
+        Some(&json!(2.5))
+    );
+    assert_eq!(
+        output.result.pointer("/system/monthly_budget_usd"),
+        Some(&json!(40.0))
+    );
+
+    let output = tool
+        .execute(json!({
+            "operation": "set",
+            "key": "system.daily_budget_usd",
+            "value": null
+        }))
+        .await
+        .unwrap();
+    assert!(output.success);
+    let output = tool.execute(json!({ "operation": "get" })).await.unwrap();
+    assert!(
+        output
+            .result
+            .pointer("/system/daily_budget_usd")
+            .is_none_or(Value::is_null)
+    );
+}
+
 #[tokio::test]
 async fn test_set_agent_defaults() {
     let ctx = setup_storage();
@@ -314,6 +360,15 @@ async fn test_set_agent_defaults() {
         ("agent.bash_timeout_secs", json!(600)),
         ("agent.python_timeout_secs", json!(60)),
         ("agent.browser_timeout_secs", json!(240)),
+        ("agent.browser_allowed_domains", json!(["example.com"])),
+        ("agent.browser_block_private_networks", json!(true)),
+        ("agent.browser_artifact_max_age_secs", json!(86400)),
+        ("agent.subprocess_memory_limit_mb", json!(1024)),
+        ("agent.subprocess_max_processes", json!(64)),
+        ("agent.subprocess_cpu_time_secs", json!(300)),
+        ("agent.container_runtime", json!("docker")),
+        ("agent.container_network", json!(true)),
+        ("agent.subprocess_network_allow", json!(["*:443"])),
         ("agent.process_session_ttl_secs", json!(5400)),
         ("agent.approval_timeout_secs", json!(420)),
         ("agent.max_iterations", json!(50)),
@@ -370,6 +425,52 @@ async fn test_set_agent_defaults() {
         agent.get("browser_timeout_secs").and_then(|v| v.as_u64()),
         Some(240)
     );
+    assert_eq!(
+        agent.get("browser_allowed_domains"),
+        Some(&json!(["example.com"]))
+    );
+    assert_eq!(
+        agent
+            .get("browser_block_private_networks")
+            .and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        agent
+            .get("browser_artifact_max_age_secs")
+            .and_then(|v| v.as_u64()),
+        Some(86400)
+    );
+    assert_eq!(
+        agent
+            .get("subprocess_memory_limit_mb")
+            .and_then(|v| v.as_u64()),
+        Some(1024)
+    );
+    assert_eq!(
+        agent
+            .get("subprocess_max_processes")
+            .and_then(|v| v.as_u64()),
+        Some(64)
+    );
+    assert_eq!(
+        agent
+            .get("subprocess_cpu_time_secs")
+            .and_then(|v| v.as_u64()),
+        Some(300)
+    );
+    assert_eq!(
+        agent.get("container_runtime").and_then(|v| v.as_str()),
+        Some("docker")
+    );
+    assert_eq!(
+        agent.get("container_network").and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        agent.get("subprocess_network_allow"),
+        Some(&json!(["*:443"]))
+    );
     assert_eq!(
         agent
             .get("process_session_ttl_secs")
@@ -457,11 +558,15 @@ async fn test_set_runtime_channel_and_registry_defaults() {
     let updates = [
         ("runtime.background_runner_poll_interval_ms", json!(15000)),
         ("runtime.background_runner_max_concurrent_tasks", json!(8)),
+        ("runtime.background_runner_auto_resume", json!(true)),
         ("runtime.chat_max_session_history", json!(40)),
         ("channel.telegram_api_timeout_secs", json!(45)),
         ("channel.telegram_polling_timeout_secs", json!(55)),
+        ("channel.voice_replies", json!(true)),
         ("registry.github_cache_ttl_secs", json!(900)),
         ("registry.marketplace_cache_ttl_secs", json!(450)),
+        ("registry.trusted_skill_keys", json!(["c2lnbmluZy1rZXk="])),
+        ("registry.require_signed_skills", json!(true)),
     ];
 
     for (key, value) in updates {
@@ -501,6 +606,13 @@ async fn test_set_runtime_channel_and_registry_defaults() {
             .and_then(|value| value.as_u64()),
         Some(40)
     );
+    assert_eq!(
+        output
+            .result
+            .pointer("/runtime/background_runner_auto_resume")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
     assert_eq!(
         output
             .result
@@ -515,6 +627,13 @@ async fn test_set_runtime_channel_and_registry_defaults() {
             .and_then(|value| value.as_u64()),
         Some(55)
     );
+    assert_eq!(
+        output
+            .result
+            .pointer("/channel/voice_replies")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
     assert_eq!(
         output
             .result
@@ -529,6 +648,13 @@ async fn test_set_runtime_channel_and_registry_defaults() {
             .and_then(|value| value.as_u64()),
         Some(450)
     );
+    assert_eq!(
+        output
+            .result
+            .pointer("/registry/require_signed_skills")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
 }
 
 #[tokio::test]
@@ -6,7 +6,8 @@ use crate::Result;
 
 use super::super::fields;
 use super::super::parse::{
-    parse_optional_string_list, parse_optional_timeout, parse_u64, parse_usize,
+    parse_bool, parse_optional_string, parse_optional_string_list, parse_optional_timeout,
+    parse_string, parse_string_list, parse_u32, parse_u64, parse_usize,
 };
 
 pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) -> Result<()> {
@@ -27,6 +28,59 @@ pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) ->
         "browser_timeout_secs" => {
             config.agent.browser_timeout_secs = parse_u64(value, "agent.browser_timeout_secs")?;
         }
+        "browser_allowed_domains" => {
+            config.agent.browser_allowed_domains =
+                parse_string_list(value, "agent.browser_allowed_domains")?;
+        }
+        "browser_denied_domains" => {
+            config.agent.browser_denied_domains =
+                parse_string_list(value, "agent.browser_denied_domains")?;
+        }
+        "browser_block_private_networks" => {
+            config.agent.browser_block_private_networks =
+                parse_bool(value, "agent.browser_block_private_networks")?;
+        }
+        "browser_artifact_max_session_bytes" => {
+            config.agent.browser_artifact_max_session_bytes =
+                parse_u64(value, "agent.browser_artifact_max_session_bytes")?;
+        }
+        "browser_artifact_max_total_bytes" => {
+            config.agent.browser_artifact_max_total_bytes =
+                parse_u64(value, "agent.browser_artifact_max_total_bytes")?;
+        }
+        "browser_artifact_max_age_secs" => {
+            config.agent.browser_artifact_max_age_secs =
+                parse_u64(value, "agent.browser_artifact_max_age_secs")?;
+        }
+        "subprocess_memory_limit_mb" => {
+            config.agent.subprocess_memory_limit_mb =
+                parse_u64(value, "agent.subprocess_memory_limit_mb")?;
+        }
+        "subprocess_cpu_weight" => {
+            config.agent.subprocess_cpu_weight = parse_u32(value, "agent.subprocess_cpu_weight")?;
+        }
+        "subprocess_max_processes" => {
+            config.agent.subprocess_max_processes =
+                parse_u32(value, "agent.subprocess_max_processes")?;
+        }
+        "subprocess_cpu_time_secs" => {
+            config.agent.subprocess_cpu_time_secs =
+                parse_u64(value, "agent.subprocess_cpu_time_secs")?;
+        }
+        "container_runtime" => {
+            config.agent.container_runtime =
+                parse_optional_string(value, "agent.container_runtime")?;
+        }
+        "container_image" => {
+            config.agent.container_image = parse_string(value, "agent.container_image")?;
+        }
+        "container_network" => {
+            config.agent.container_network = parse_bool(value, "agent.container_network")?;
+        }
+        "subprocess_network_allow" => {
+            config.agent.subprocess_network_allow =
+                parse_optional_string_list(value, "agent.subprocess_network_allow")?;
+        }
         "process_session_ttl_secs" => {
             config.agent.process_session_ttl_secs =
                 parse_u64(value, "agent.process_session_ttl_secs")?;
@@ -5,7 +5,7 @@ use restflow_traits::config_types::ConfigDocument;
 use crate::Result;
 
 use super::super::fields;
-use super::super::parse::{parse_u32, parse_u64};
+use super::super::parse::{parse_bool, parse_u32, parse_u64};
 
 pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) -> Result<()> {
     match field {
@@ -17,6 +17,9 @@ pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) ->
             config.channel.telegram_polling_timeout_secs =
                 parse_u32(value, "channel.telegram_polling_timeout_secs")?;
         }
+        "voice_replies" => {
+            config.channel.voice_replies = parse_bool(value, "channel.voice_replies")?;
+        }
         _ => {
             return Err(fields::unknown_domain_field(
                 "channel",
@@ -5,7 +5,7 @@ use restflow_traits::config_types::ConfigDocument;
 use crate::Result;
 
 use super::super::fields;
-use super::super::parse::parse_u64;
+use super::super::parse::{parse_bool, parse_string_list, parse_u64};
 
 pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) -> Result<()> {
     match field {
@@ -17,6 +17,14 @@ pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) ->
             config.registry.marketplace_cache_ttl_secs =
                 parse_u64(value, "registry.marketplace_cache_ttl_secs")?;
         }
+        "trusted_skill_keys" => {
+            config.registry.trusted_skill_keys =
+                parse_string_list(value, "registry.trusted_skill_keys")?;
+        }
+        "require_signed_skills" => {
+            config.registry.require_signed_skills =
+                parse_bool(value, "registry.require_signed_skills")?;
+        }
         _ => {
             return Err(fields::unknown_domain_field(
                 "registry",
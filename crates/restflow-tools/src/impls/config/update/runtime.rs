@@ -5,7 +5,7 @@ use restflow_traits::config_types::ConfigDocument;
 use crate::Result;
 
 use super::super::fields;
-use super::super::parse::{parse_u64, parse_usize};
+use super::super::parse::{parse_bool, parse_u64, parse_usize};
 
 pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) -> Result<()> {
     match field {
@@ -17,6 +17,10 @@ pub(crate) fn apply(field: &str, value: &Value, config: &mut ConfigDocument) ->
             config.runtime.background_runner_max_concurrent_tasks =
                 parse_usize(value, "runtime.background_runner_max_concurrent_tasks")?;
         }
+        "background_runner_auto_resume" => {
+            config.runtime.background_runner_auto_resume =
+                parse_bool(value, "runtime.background_runner_auto_resume")?;
+        }
         "chat_max_session_history" => {
             config.runtime.chat_max_session_history =
                 parse_usize(value, "runtime.chat_max_session_history")?;
@@ -4,7 +4,10 @@ use restflow_traits::config_types::ConfigDocument;
 
 use crate::Result;
 
-use super::super::parse::{parse_optional_timeout, parse_string_list, parse_u32, parse_u64};
+use super::super::parse::{
+    parse_optional_f64, parse_optional_string, parse_optional_timeout, parse_string_list,
+    parse_u32, parse_u64,
+};
 
 pub(crate) fn supports_key(key: &str) -> bool {
     matches!(
@@ -20,6 +23,11 @@ pub(crate) fn supports_key(key: &str) -> bool {
             | "system.checkpoint_retention_days"
             | "system.memory_chunk_retention_days"
             | "system.log_file_retention_days"
+            | "system.artifact_retention_days"
+            | "system.artifact_max_total_mb"
+            | "system.daily_budget_usd"
+            | "system.monthly_budget_usd"
+            | "system.otlp_endpoint"
             | "system.experimental_features"
     )
 }
@@ -59,6 +67,21 @@ pub(crate) fn apply(key: &str, value: &Value, config: &mut ConfigDocument) -> Re
         "system.log_file_retention_days" => {
             config.system.log_file_retention_days = parse_u32(value, key)?;
         }
+        "system.artifact_retention_days" => {
+            config.system.artifact_retention_days = parse_u32(value, key)?;
+        }
+        "system.artifact_max_total_mb" => {
+            config.system.artifact_max_total_mb = parse_u64(value, key)?;
+        }
+        "system.daily_budget_usd" => {
+            config.system.daily_budget_usd = parse_optional_f64(value, key)?;
+        }
+        "system.monthly_budget_usd" => {
+            config.system.monthly_budget_usd = parse_optional_f64(value, key)?;
+        }
+        "system.otlp_endpoint" => {
+            config.system.otlp_endpoint = parse_optional_string(value, key)?;
+        }
         "system.experimental_features" => {
             config.system.experimental_features = parse_string_list(value, key)?;
         }
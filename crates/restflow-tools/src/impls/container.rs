@@ -0,0 +1,263 @@
+//! Container execution backend for bash and python tool processes.
+//!
+//! Each invocation runs in an ephemeral `run --rm` container of Docker or
+//! Podman instead of a host process. The container gets no capabilities, no
+//! environment from the host, no network unless enabled, and only the
+//! workspace bind-mounted at its host path, so absolute paths in commands
+//! keep working. [`ResourceLimits`] are translated into engine flags.
+//!
+//! This isolates untrusted code more strongly than the in-process sandbox,
+//! at the cost of container start-up time per call.
+
+use std::process::ExitStatus;
+
+use restflow_sandbox::limits::DEFAULT_CPU_WEIGHT;
+use restflow_sandbox::{LimitViolation, ResourceLimits};
+use tokio::process::Command;
+
+/// Docker's default `--cpu-shares` value, matching cgroup `cpu.weight` 100.
+const DEFAULT_CPU_SHARES: u64 = 1024;
+
+/// Container engine used to run tool processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerEngine {
+    Docker,
+    Podman,
+}
+
+impl ContainerEngine {
+    /// Parse an engine name as used in the `agent.container_runtime` setting.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "docker" => Some(Self::Docker),
+            "podman" => Some(Self::Podman),
+            _ => None,
+        }
+    }
+
+    /// Engine CLI executable.
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+/// Container settings for bash and python tool processes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerConfig {
+    pub engine: ContainerEngine,
+    /// Image providing `sh` and `python3`.
+    pub image: String,
+    /// Give the container network access.
+    pub network: bool,
+}
+
+/// One container run: the engine command line and the container's name.
+pub(crate) struct ContainerInvocation {
+    pub name: String,
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl ContainerConfig {
+    pub fn new(engine: ContainerEngine, image: impl Into<String>) -> Self {
+        Self {
+            engine,
+            image: image.into(),
+            network: false,
+        }
+    }
+
+    pub fn with_network(mut self, network: bool) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Engine command running `command` in a fresh container with `workdir`
+    /// mounted read-write and used as working directory.
+    pub(crate) fn invocation(
+        &self,
+        workdir: Option<&str>,
+        limits: &ResourceLimits,
+        command: &[&str],
+    ) -> ContainerInvocation {
+        let name = format!("restflow-{}", uuid::Uuid::new_v4().simple());
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--rm".into(),
+            "--init".into(),
+            "--name".into(),
+            name.clone(),
+            "--cap-drop".into(),
+            "ALL".into(),
+            "--security-opt".into(),
+            "no-new-privileges".into(),
+        ];
+        if !self.network {
+            args.extend(["--network".into(), "none".into()]);
+        }
+        if let Some(workdir) = workdir {
+            args.extend([
+                "--volume".into(),
+                format!("{workdir}:{workdir}"),
+                "--workdir".into(),
+                workdir.to_string(),
+            ]);
+            if let Some(user) = self.workspace_user(workdir) {
+                args.extend(["--user".into(), user]);
+            }
+        }
+        args.extend(limit_args(limits));
+        args.push(self.image.clone());
+        args.extend(command.iter().map(|arg| arg.to_string()));
+
+        ContainerInvocation {
+            name,
+            program: self.engine.program().to_string(),
+            args,
+        }
+    }
+
+    /// Run as the workspace owner so files the command creates stay editable
+    /// on the host. Rootless Podman already maps the container's root to the
+    /// calling user.
+    #[cfg(unix)]
+    fn workspace_user(&self, workdir: &str) -> Option<String> {
+        use std::os::unix::fs::MetadataExt;
+
+        if self.engine == ContainerEngine::Podman {
+            return None;
+        }
+        let metadata = std::fs::metadata(workdir).ok()?;
+        Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+    }
+
+    #[cfg(not(unix))]
+    fn workspace_user(&self, _workdir: &str) -> Option<String> {
+        None
+    }
+
+    /// Stop a container whose engine client was abandoned, e.g. on timeout.
+    pub(crate) async fn kill(&self, name: &str) {
+        let result = Command::new(self.engine.program())
+            .args(["kill", name])
+            .output()
+            .await;
+        if let Err(error) = result {
+            tracing::warn!(container = name, error = %error, "Failed to kill tool container");
+        }
+    }
+}
+
+/// Engine flags enforcing `limits` inside the container.
+fn limit_args(limits: &ResourceLimits) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(bytes) = limits.memory_bytes {
+        // An equal swap limit keeps the container from swapping.
+        args.extend([
+            "--memory".into(),
+            bytes.to_string(),
+            "--memory-swap".into(),
+            bytes.to_string(),
+        ]);
+    }
+    if let Some(weight) = limits.cpu_weight {
+        let shares =
+            (u64::from(weight) * DEFAULT_CPU_SHARES / u64::from(DEFAULT_CPU_WEIGHT)).max(2);
+        args.extend(["--cpu-shares".into(), shares.to_string()]);
+    }
+    if let Some(max) = limits.max_processes {
+        args.extend(["--pids-limit".into(), max.to_string()]);
+    }
+    if let Some(secs) = limits.cpu_time_secs {
+        args.extend([
+            "--ulimit".into(),
+            format!("cpu={}:{}", secs, secs.saturating_add(1)),
+        ]);
+    }
+    args
+}
+
+/// The limit a containerized command ran into, judged from the exit status
+/// the engine forwards: 137 (`SIGKILL`) for the OOM killer and 152
+/// (`SIGXCPU`) for the CPU time limit.
+pub(crate) fn limit_violation(
+    limits: &ResourceLimits,
+    status: &ExitStatus,
+) -> Option<LimitViolation> {
+    match status.code()? {
+        137 => limits
+            .memory_bytes
+            .map(|limit_bytes| LimitViolation::Memory { limit_bytes }),
+        152 => limits
+            .cpu_time_secs
+            .map(|limit_secs| LimitViolation::CpuTime { limit_secs }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_names() {
+        assert_eq!(
+            ContainerEngine::from_name("Docker"),
+            Some(ContainerEngine::Docker)
+        );
+        assert_eq!(
+            ContainerEngine::from_name("podman"),
+            Some(ContainerEngine::Podman)
+        );
+        assert_eq!(ContainerEngine::from_name("lxc"), None);
+    }
+
+    #[test]
+    fn test_invocation_isolates_and_limits_container() {
+        let config = ContainerConfig::new(ContainerEngine::Podman, "python:3.12-slim");
+        let limits = ResourceLimits {
+            memory_bytes: Some(256 * 1024 * 1024),
+            cpu_weight: Some(50),
+            max_processes: Some(32),
+            cpu_time_secs: Some(10),
+        };
+        let invocation = config.invocation(Some("/work"), &limits, &["sh", "-c", "ls"]);
+        assert_eq!(invocation.program, "podman");
+        let args = invocation.args.join(" ");
+        assert!(args.starts_with("run --rm"));
+        assert!(args.contains(&format!("--name {}", invocation.name)));
+        assert!(args.contains("--network none"));
+        assert!(args.contains("--volume /work:/work --workdir /work"));
+        assert!(args.contains("--memory 268435456 --memory-swap 268435456"));
+        assert!(args.contains("--cpu-shares 512"));
+        assert!(args.contains("--pids-limit 32"));
+        assert!(args.contains("--ulimit cpu=10:11"));
+        assert!(args.ends_with("python:3.12-slim sh -c ls"));
+
+        let networked = config.with_network(true);
+        let invocation = networked.invocation(None, &ResourceLimits::default(), &["true"]);
+        assert!(!invocation.args.iter().any(|arg| arg == "--network"));
+        assert!(!invocation.args.iter().any(|arg| arg == "--volume"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_container_exit_codes_map_to_violations() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let limits = ResourceLimits {
+            memory_bytes: Some(1024),
+            ..Default::default()
+        };
+        let oom = ExitStatus::from_raw(137 << 8);
+        assert_eq!(
+            limit_violation(&limits, &oom),
+            Some(LimitViolation::Memory { limit_bytes: 1024 })
+        );
+        let cpu = ExitStatus::from_raw(152 << 8);
+        assert_eq!(limit_violation(&limits, &cpu), None);
+    }
+}
@@ -0,0 +1,772 @@
+//! Tool for building large deliverables incrementally across iterations.
+//!
+//! The agent starts a draft, appends validated fragments (markdown sections,
+//! JSON rows or CSV rows) over several turns, and finalizes it into a saved
+//! deliverable. This avoids generating huge documents in a single response.
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::Result;
+use crate::{Tool, ToolOutput};
+use restflow_traits::store::DeliverableStore;
+
+const MAX_OPEN_DRAFTS: usize = 16;
+const MAX_DRAFT_BYTES: usize = 5 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DraftFormat {
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl DraftFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Markdown => "text/markdown",
+            Self::Json => "application/json",
+            Self::Csv => "text/csv",
+        }
+    }
+
+    fn default_deliverable_type(self) -> &'static str {
+        match self {
+            Self::Markdown => "report",
+            Self::Json | Self::Csv => "data",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DeliverableDraft {
+    title: String,
+    format: DraftFormat,
+    deliverable_type: String,
+    task_id: Option<String>,
+    execution_id: Option<String>,
+    columns: Vec<String>,
+    row_schema: Option<Value>,
+    schema: Option<Value>,
+    metadata: Option<Value>,
+    sections: Vec<String>,
+    rows: Vec<Value>,
+    bytes: usize,
+}
+
+impl DeliverableDraft {
+    fn fragment_count(&self) -> usize {
+        match self.format {
+            DraftFormat::Markdown => self.sections.len(),
+            DraftFormat::Json | DraftFormat::Csv => self.rows.len(),
+        }
+    }
+
+    fn summary(&self, draft_id: &str) -> Value {
+        json!({
+            "draft_id": draft_id,
+            "title": self.title,
+            "format": self.format_name(),
+            "fragments": self.fragment_count(),
+            "bytes": self.bytes,
+        })
+    }
+
+    fn format_name(&self) -> &'static str {
+        match self.format {
+            DraftFormat::Markdown => "markdown",
+            DraftFormat::Json => "json",
+            DraftFormat::Csv => "csv",
+        }
+    }
+
+    /// Validate and normalize a fragment, returning the value to store and its size.
+    fn prepare_fragment(&self, fragment: Value) -> std::result::Result<(Value, usize), String> {
+        match self.format {
+            DraftFormat::Markdown => {
+                let section = fragment
+                    .as_str()
+                    .ok_or_else(|| "Markdown fragments must be strings".to_string())?;
+                if section.trim().is_empty() {
+                    return Err("Markdown fragments must not be empty".to_string());
+                }
+                Ok((Value::String(section.to_string()), section.len()))
+            }
+            DraftFormat::Json => {
+                if let Some(schema) = &self.row_schema {
+                    validate_against_schema(&fragment, schema, "$")?;
+                }
+                let size = fragment.to_string().len();
+                Ok((fragment, size))
+            }
+            DraftFormat::Csv => {
+                let row = csv_row_to_object(&self.columns, fragment)?;
+                if let Some(schema) = &self.row_schema {
+                    validate_against_schema(&row, schema, "$")?;
+                }
+                let size = row.to_string().len();
+                Ok((row, size))
+            }
+        }
+    }
+
+    fn push_fragment(&mut self, fragment: Value, size: usize) {
+        match (self.format, fragment) {
+            (DraftFormat::Markdown, Value::String(section)) => self.sections.push(section),
+            (_, row) => self.rows.push(row),
+        }
+        self.bytes += size;
+    }
+
+    fn render(&self) -> std::result::Result<String, String> {
+        match self.format {
+            DraftFormat::Markdown => Ok(self.sections.join("\n\n")),
+            DraftFormat::Json => {
+                let document = Value::Array(self.rows.clone());
+                if let Some(schema) = &self.schema {
+                    validate_against_schema(&document, schema, "$")?;
+                }
+                serde_json::to_string_pretty(&document).map_err(|error| error.to_string())
+            }
+            DraftFormat::Csv => {
+                if let Some(schema) = &self.schema {
+                    validate_against_schema(&Value::Array(self.rows.clone()), schema, "$")?;
+                }
+                let mut lines = Vec::with_capacity(self.rows.len() + 1);
+                lines.push(
+                    self.columns
+                        .iter()
+                        .map(|column| csv_escape(column))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                );
+                for row in &self.rows {
+                    let cells = self
+                        .columns
+                        .iter()
+                        .map(|column| csv_escape(&csv_cell(row.get(column))))
+                        .collect::<Vec<_>>();
+                    lines.push(cells.join(","));
+                }
+                Ok(lines.join("\n"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum DeliverableBuilderInput {
+    Start {
+        title: String,
+        format: DraftFormat,
+        #[serde(default, rename = "type")]
+        deliverable_type: Option<String>,
+        #[serde(default)]
+        task_id: Option<String>,
+        #[serde(default)]
+        execution_id: Option<String>,
+        #[serde(default)]
+        columns: Vec<String>,
+        #[serde(default)]
+        row_schema: Option<Value>,
+        #[serde(default)]
+        schema: Option<Value>,
+        #[serde(default)]
+        metadata: Option<Value>,
+    },
+    Append {
+        draft_id: String,
+        fragments: Vec<Value>,
+    },
+    Status {
+        draft_id: String,
+    },
+    Finalize {
+        draft_id: String,
+    },
+    Discard {
+        draft_id: String,
+    },
+}
+
+/// Builds deliverables from fragments appended over multiple iterations.
+#[derive(Clone)]
+pub struct DeliverableBuilderTool {
+    store: Arc<dyn DeliverableStore>,
+    drafts: Arc<Mutex<HashMap<String, DeliverableDraft>>>,
+}
+
+impl DeliverableBuilderTool {
+    pub fn new(store: Arc<dyn DeliverableStore>) -> Self {
+        Self {
+            store,
+            drafts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn start(
+        &self,
+        title: String,
+        format: DraftFormat,
+        deliverable_type: Option<String>,
+        task_id: Option<String>,
+        execution_id: Option<String>,
+        columns: Vec<String>,
+        row_schema: Option<Value>,
+        schema: Option<Value>,
+        metadata: Option<Value>,
+    ) -> ToolOutput {
+        let deliverable_type = deliverable_type
+            .map(|value| value.trim().to_lowercase())
+            .unwrap_or_else(|| format.default_deliverable_type().to_string());
+        if !matches!(deliverable_type.as_str(), "report" | "data" | "artifact") {
+            return ToolOutput::error(format!(
+                "Invalid type '{}'. Supported: report, data, artifact",
+                deliverable_type
+            ));
+        }
+        if format == DraftFormat::Csv && columns.is_empty() {
+            return ToolOutput::error("CSV drafts require a non-empty 'columns' list");
+        }
+
+        let mut drafts = self.drafts.lock();
+        if drafts.len() >= MAX_OPEN_DRAFTS {
+            return ToolOutput::error(format!(
+                "Too many open drafts (max {}). Finalize or discard one first.",
+                MAX_OPEN_DRAFTS
+            ));
+        }
+
+        let draft_id = Uuid::new_v4().to_string();
+        let draft = DeliverableDraft {
+            title,
+            format,
+            deliverable_type,
+            task_id,
+            execution_id,
+            columns,
+            row_schema,
+            schema,
+            metadata,
+            sections: Vec::new(),
+            rows: Vec::new(),
+            bytes: 0,
+        };
+        let summary = draft.summary(&draft_id);
+        drafts.insert(draft_id, draft);
+        ToolOutput::success(summary)
+    }
+
+    fn append(&self, draft_id: &str, fragments: Vec<Value>) -> ToolOutput {
+        let mut drafts = self.drafts.lock();
+        let Some(draft) = drafts.get_mut(draft_id) else {
+            return ToolOutput::error(format!("Draft not found: {}", draft_id));
+        };
+
+        // Validate the whole batch first so a bad fragment appends nothing.
+        let mut prepared = Vec::with_capacity(fragments.len());
+        let mut batch_bytes = 0;
+        for (index, fragment) in fragments.into_iter().enumerate() {
+            match draft.prepare_fragment(fragment) {
+                Ok((value, size)) => {
+                    batch_bytes += size;
+                    prepared.push((value, size));
+                }
+                Err(error) => {
+                    return ToolOutput::error(format!("Fragment {} rejected: {}", index, error));
+                }
+            }
+        }
+        if draft.bytes + batch_bytes > MAX_DRAFT_BYTES {
+            return ToolOutput::error(format!(
+                "Draft would exceed {} bytes; finalize it and start a new one",
+                MAX_DRAFT_BYTES
+            ));
+        }
+
+        let appended = prepared.len();
+        for (value, size) in prepared {
+            draft.push_fragment(value, size);
+        }
+        let mut summary = draft.summary(draft_id);
+        summary["appended"] = json!(appended);
+        ToolOutput::success(summary)
+    }
+
+    fn finalize(&self, draft_id: &str) -> Result<ToolOutput> {
+        let Some(draft) = self.drafts.lock().get(draft_id).cloned() else {
+            return Ok(ToolOutput::error(format!("Draft not found: {}", draft_id)));
+        };
+        if draft.fragment_count() == 0 {
+            return Ok(ToolOutput::error("Draft is empty; append fragments first"));
+        }
+
+        // Keep the draft on validation failure so the agent can fix and retry.
+        let content = match draft.render() {
+            Ok(content) => content,
+            Err(error) => {
+                return Ok(ToolOutput::error(format!(
+                    "Schema validation failed: {}",
+                    error
+                )));
+            }
+        };
+
+        let task_id = draft
+            .task_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("standalone-task");
+        let execution_id = draft
+            .execution_id
+            .clone()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        let result = self.store.save_deliverable(
+            task_id,
+            &execution_id,
+            &draft.deliverable_type,
+            &draft.title,
+            &content,
+            None,
+            Some(draft.format.content_type()),
+            draft.metadata.clone(),
+        )?;
+        self.drafts.lock().remove(draft_id);
+        Ok(ToolOutput::success(result))
+    }
+}
+
+#[async_trait]
+impl Tool for DeliverableBuilderTool {
+    fn name(&self) -> &str {
+        "deliverable_builder"
+    }
+
+    fn description(&self) -> &str {
+        "Build a large deliverable incrementally. Start a draft (markdown, json or csv), append validated fragments across iterations, then finalize to validate against the schema and save it. Use instead of save_deliverable when the output is too large for one response."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["start", "append", "status", "finalize", "discard"],
+                    "description": "Operation to perform"
+                },
+                "draft_id": {
+                    "type": "string",
+                    "description": "Draft ID returned by start"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Deliverable title (start)"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["markdown", "json", "csv"],
+                    "description": "markdown: fragments are sections; json: fragments are array items; csv: fragments are rows (arrays or objects keyed by column)"
+                },
+                "type": {
+                    "type": "string",
+                    "enum": ["report", "data", "artifact"],
+                    "description": "Deliverable type (defaults to report for markdown, data otherwise)"
+                },
+                "task_id": { "type": "string", "description": "Optional background task ID" },
+                "execution_id": { "type": "string", "description": "Optional execution ID" },
+                "columns": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "CSV column names (required for csv)"
+                },
+                "row_schema": {
+                    "type": "object",
+                    "description": "JSON Schema each json/csv fragment must satisfy when appended"
+                },
+                "schema": {
+                    "type": "object",
+                    "description": "JSON Schema the assembled json/csv document (an array) must satisfy at finalize"
+                },
+                "metadata": { "type": "object", "description": "Optional metadata payload" },
+                "fragments": {
+                    "type": "array",
+                    "description": "Fragments to append (append)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let params: DeliverableBuilderInput = match serde_json::from_value(input) {
+            Ok(p) => p,
+            Err(e) => return Ok(ToolOutput::error(format!("Invalid input: {}", e))),
+        };
+
+        match params {
+            DeliverableBuilderInput::Start {
+                title,
+                format,
+                deliverable_type,
+                task_id,
+                execution_id,
+                columns,
+                row_schema,
+                schema,
+                metadata,
+            } => Ok(self.start(
+                title,
+                format,
+                deliverable_type,
+                task_id,
+                execution_id,
+                columns,
+                row_schema,
+                schema,
+                metadata,
+            )),
+            DeliverableBuilderInput::Append {
+                draft_id,
+                fragments,
+            } => Ok(self.append(&draft_id, fragments)),
+            DeliverableBuilderInput::Status { draft_id } => {
+                Ok(match self.drafts.lock().get(&draft_id) {
+                    Some(draft) => ToolOutput::success(draft.summary(&draft_id)),
+                    None => ToolOutput::error(format!("Draft not found: {}", draft_id)),
+                })
+            }
+            DeliverableBuilderInput::Finalize { draft_id } => self.finalize(&draft_id),
+            DeliverableBuilderInput::Discard { draft_id } => {
+                let discarded = self.drafts.lock().remove(&draft_id).is_some();
+                Ok(ToolOutput::success(
+                    json!({ "draft_id": draft_id, "discarded": discarded }),
+                ))
+            }
+        }
+    }
+}
+
+fn csv_row_to_object(columns: &[String], fragment: Value) -> std::result::Result<Value, String> {
+    match fragment {
+        Value::Array(cells) => {
+            if cells.len() != columns.len() {
+                return Err(format!(
+                    "expected {} cells, got {}",
+                    columns.len(),
+                    cells.len()
+                ));
+            }
+            Ok(Value::Object(
+                columns.iter().cloned().zip(cells).collect::<Map<_, _>>(),
+            ))
+        }
+        Value::Object(map) => {
+            if let Some(unknown) = map.keys().find(|key| !columns.contains(key)) {
+                return Err(format!("unknown column '{}'", unknown));
+            }
+            Ok(Value::Object(map))
+        }
+        _ => Err("CSV fragments must be arrays or objects".to_string()),
+    }
+}
+
+fn csv_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Validate a value against the commonly used subset of JSON Schema:
+/// `type`, `enum`, `required`, `properties`, `additionalProperties: false`,
+/// `items`, `minItems` and `maxItems`.
+fn validate_against_schema(
+    value: &Value,
+    schema: &Value,
+    path: &str,
+) -> std::result::Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| json_type_matches(value, name)) {
+            return Err(format!("{} must be of type {}", path, allowed.join(" | ")));
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array)
+        && !options.contains(value)
+    {
+        return Err(format!(
+            "{} must be one of {}",
+            path,
+            Value::Array(options.clone())
+        ));
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    return Err(format!("{} is missing required property '{}'", path, key));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, child) in object {
+            match properties.and_then(|props| props.get(key)) {
+                Some(child_schema) => {
+                    validate_against_schema(child, child_schema, &format!("{}.{}", path, key))?;
+                }
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    return Err(format!("{} has unexpected property '{}'", path, key));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+            && (items.len() as u64) < min
+        {
+            return Err(format!("{} must have at least {} items", path, min));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+            && (items.len() as u64) > max
+        {
+            return Err(format!("{} must have at most {} items", path, max));
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                validate_against_schema(item, item_schema, &format!("{}[{}]", path, index))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn json_type_matches(value: &Value, type_name: &str) -> bool {
+    match type_name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RecordingDeliverableStore {
+        saved: Mutex<Vec<(String, String, Option<String>)>>,
+    }
+
+    impl DeliverableStore for RecordingDeliverableStore {
+        fn save_deliverable(
+            &self,
+            _task_id: &str,
+            _execution_id: &str,
+            deliverable_type: &str,
+            title: &str,
+            content: &str,
+            _file_path: Option<&str>,
+            content_type: Option<&str>,
+            _metadata: Option<Value>,
+        ) -> Result<Value> {
+            self.saved.lock().push((
+                deliverable_type.to_string(),
+                content.to_string(),
+                content_type.map(str::to_string),
+            ));
+            Ok(json!({ "id": "d1", "title": title }))
+        }
+    }
+
+    fn tool() -> (DeliverableBuilderTool, Arc<RecordingDeliverableStore>) {
+        let store = Arc::new(RecordingDeliverableStore {
+            saved: Mutex::new(Vec::new()),
+        });
+        (DeliverableBuilderTool::new(store.clone()), store)
+    }
+
+    async fn start(tool: &DeliverableBuilderTool, input: Value) -> String {
+        let out = tool.execute(input).await.unwrap();
+        assert!(out.success, "{:?}", out.error);
+        out.result["draft_id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_markdown_sections_are_joined_on_finalize() {
+        let (tool, store) = tool();
+        let draft_id = start(
+            &tool,
+            json!({"action": "start", "title": "Report", "format": "markdown"}),
+        )
+        .await;
+
+        for section in ["# Intro", "## Findings"] {
+            let out = tool
+                .execute(json!({"action": "append", "draft_id": draft_id, "fragments": [section]}))
+                .await
+                .unwrap();
+            assert!(out.success);
+        }
+
+        let out = tool
+            .execute(json!({"action": "finalize", "draft_id": draft_id}))
+            .await
+            .unwrap();
+        assert!(out.success);
+        let saved = store.saved.lock();
+        assert_eq!(saved[0].0, "report");
+        assert_eq!(saved[0].1, "# Intro\n\n## Findings");
+        assert_eq!(saved[0].2.as_deref(), Some("text/markdown"));
+    }
+
+    #[tokio::test]
+    async fn test_json_rows_validated_on_append_and_finalize() {
+        let (tool, store) = tool();
+        let draft_id = start(
+            &tool,
+            json!({
+                "action": "start",
+                "title": "Rows",
+                "format": "json",
+                "row_schema": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {"name": {"type": "string"}, "score": {"type": "integer"}}
+                },
+                "schema": {"type": "array", "minItems": 2}
+            }),
+        )
+        .await;
+
+        let rejected = tool
+            .execute(json!({
+                "action": "append",
+                "draft_id": draft_id,
+                "fragments": [{"name": "a"}, {"score": 1}]
+            }))
+            .await
+            .unwrap();
+        assert!(!rejected.success);
+        assert!(
+            rejected
+                .error
+                .unwrap()
+                .contains("missing required property 'name'")
+        );
+
+        tool.execute(json!({"action": "append", "draft_id": draft_id, "fragments": [{"name": "a", "score": 1}]}))
+            .await
+            .unwrap();
+        let too_short = tool
+            .execute(json!({"action": "finalize", "draft_id": draft_id}))
+            .await
+            .unwrap();
+        assert!(!too_short.success);
+        assert!(too_short.error.unwrap().contains("at least 2 items"));
+
+        tool.execute(
+            json!({"action": "append", "draft_id": draft_id, "fragments": [{"name": "b"}]}),
+        )
+        .await
+        .unwrap();
+        let out = tool
+            .execute(json!({"action": "finalize", "draft_id": draft_id}))
+            .await
+            .unwrap();
+        assert!(out.success);
+        let content: Value = serde_json::from_str(&store.saved.lock()[0].1).unwrap();
+        assert_eq!(content.as_array().map(Vec::len), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_csv_rows_are_escaped() {
+        let (tool, store) = tool();
+        let draft_id = start(
+            &tool,
+            json!({"action": "start", "title": "Table", "format": "csv", "columns": ["name", "note"]}),
+        )
+        .await;
+
+        let out = tool
+            .execute(json!({
+                "action": "append",
+                "draft_id": draft_id,
+                "fragments": [["a", "plain"], {"name": "b", "note": "has, comma"}]
+            }))
+            .await
+            .unwrap();
+        assert!(out.success);
+        let bad = tool
+            .execute(json!({"action": "append", "draft_id": draft_id, "fragments": [["only one"]]}))
+            .await
+            .unwrap();
+        assert!(!bad.success);
+
+        tool.execute(json!({"action": "finalize", "draft_id": draft_id}))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.saved.lock()[0].1,
+            "name,note\na,plain\nb,\"has, comma\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_draft_and_discard() {
+        let (tool, _) = tool();
+        let out = tool
+            .execute(json!({"action": "status", "draft_id": "missing"}))
+            .await
+            .unwrap();
+        assert!(!out.success);
+
+        let draft_id = start(
+            &tool,
+            json!({"action": "start", "title": "T", "format": "markdown"}),
+        )
+        .await;
+        let out = tool
+            .execute(json!({"action": "discard", "draft_id": draft_id}))
+            .await
+            .unwrap();
+        assert_eq!(out.result["discarded"], json!(true));
+    }
+}
@@ -1,4 +1,8 @@
 //! Diagnostics tool backed by a diagnostics provider (LSP).
+//!
+//! Besides per-file language-server diagnostics, the tool can report runtime
+//! prerequisites such as the browser runtime so agents find missing pieces
+//! before a browser session fails to start.
 
 use async_trait::async_trait;
 use serde_json::{Value, json};
@@ -39,16 +43,22 @@ impl Tool for DiagnosticsTool {
     }
 
     fn description(&self) -> &str {
-        "Return language-server diagnostics for a file path, including errors and warnings."
+        "Return language-server diagnostics for a file path, including errors and warnings. With check=runtime, report runtime prerequisites instead (Chromium found, Node.js version, TypeScript support) along with remediation steps."
     }
 
     fn parameters_schema(&self) -> Value {
         json!({
             "type": "object",
             "properties": {
+                "check": {
+                    "type": "string",
+                    "enum": ["file", "runtime"],
+                    "description": "What to diagnose: file (language-server diagnostics for path) or runtime (browser and script runtime prerequisites)",
+                    "default": "file"
+                },
                 "path": {
                     "type": "string",
-                    "description": "File path to fetch diagnostics for"
+                    "description": "File path to fetch diagnostics for. Required when check is file"
                 },
                 "timeout_ms": {
                     "type": "integer",
@@ -56,12 +66,26 @@ impl Tool for DiagnosticsTool {
                     "default": self.default_timeout_ms,
                     "minimum": 1
                 }
-            },
-            "required": ["path"]
+            }
         })
     }
 
     async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        match args.get("check").and_then(|v| v.as_str()).unwrap_or("file") {
+            "file" => {}
+            "runtime" => {
+                let report = self.provider.runtime_report().await.map_err(|error| {
+                    ToolError::Tool(format!("Runtime diagnostics failed: {error}"))
+                })?;
+                return Ok(ToolOutput::success(report));
+            }
+            other => {
+                return Err(ToolError::Tool(format!(
+                    "Unknown check '{other}'. Use 'file' or 'runtime'."
+                )));
+            }
+        }
+
         let path = args
             .get("path")
             .and_then(|v| v.as_str())
@@ -186,6 +210,27 @@ mod tests {
         assert_eq!(schema["properties"]["timeout_ms"]["default"], json!(9_000));
     }
 
+    #[tokio::test]
+    async fn execute_runtime_check_uses_provider_report() {
+        let provider = Arc::new(MockDiagnosticsProvider {
+            fail_open: AtomicBool::new(true),
+            fail_wait: AtomicBool::new(true),
+        });
+        let tool = DiagnosticsTool::new(provider);
+
+        let error = tool
+            .execute(json!({ "check": "runtime" }))
+            .await
+            .expect_err("mock provider has no runtime report");
+        assert!(error.to_string().contains("Runtime diagnostics failed"));
+
+        let error = tool
+            .execute(json!({ "check": "network" }))
+            .await
+            .expect_err("unknown check should be rejected");
+        assert!(error.to_string().contains("Unknown check 'network'"));
+    }
+
     #[tokio::test]
     async fn execute_rejects_zero_timeout() {
         let provider = Arc::new(MockDiagnosticsProvider {
@@ -0,0 +1,547 @@
+//! fanout tool - Map a prompt template over inputs with bounded-concurrency sub-agents.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::time::{Duration, timeout};
+
+use crate::impls::operation_assessment::{enforce_confirmation_or_defer, preview_output};
+use crate::{Result, Tool, ToolError, ToolOutput};
+use restflow_contracts::request::{
+    InlineAgentRunConfig as ContractInlineAgentRunConfig,
+    RunSpawnRequest as ContractRunSpawnRequest,
+};
+use restflow_traits::{
+    AgentOperationAssessor, SubagentCompletion, SubagentManager, SubagentStatus,
+    normalize_legacy_approval_replay,
+};
+
+/// Maximum number of inputs accepted in one fanout call.
+const MAX_FANOUT_ITEMS: usize = 100;
+/// Sub-agents kept in flight when `max_concurrency` is omitted.
+const DEFAULT_FANOUT_CONCURRENCY: usize = 4;
+/// Extra wait beyond the sub-agent timeout so children can report their own timeout.
+const WAIT_GRACE_SECS: u64 = 5;
+const ITEM_PLACEHOLDER: &str = "{{item}}";
+const INDEX_PLACEHOLDER: &str = "{{index}}";
+
+/// Parameters for fanout tool.
+#[derive(Debug, Clone, Deserialize)]
+struct FanoutParams {
+    items: Vec<Value>,
+    prompt: String,
+    #[serde(default)]
+    agent: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    provider: Option<String>,
+    #[serde(default)]
+    inline_system_prompt: Option<String>,
+    #[serde(default)]
+    inline_allowed_tools: Option<Vec<String>>,
+    #[serde(default)]
+    max_concurrency: Option<usize>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default, alias = "parent_execution_id")]
+    parent_run_id: Option<String>,
+    #[serde(default)]
+    trace_session_id: Option<String>,
+    #[serde(default)]
+    trace_scope_id: Option<String>,
+    #[serde(default)]
+    preview: bool,
+    #[serde(default)]
+    approval_id: Option<String>,
+}
+
+/// fanout tool: runs one sub-agent per input and aggregates the results.
+pub struct FanoutTool {
+    manager: Arc<dyn SubagentManager>,
+    assessor: Option<Arc<dyn AgentOperationAssessor>>,
+}
+
+impl FanoutTool {
+    pub fn new(manager: Arc<dyn SubagentManager>) -> Self {
+        Self {
+            manager,
+            assessor: None,
+        }
+    }
+
+    pub fn with_assessor(mut self, assessor: Arc<dyn AgentOperationAssessor>) -> Self {
+        self.assessor = Some(assessor);
+        self
+    }
+
+    fn spawn_request(params: &FanoutParams, index: usize) -> ContractRunSpawnRequest {
+        let inline =
+            if params.inline_system_prompt.is_some() || params.inline_allowed_tools.is_some() {
+                Some(ContractInlineAgentRunConfig {
+                    name: None,
+                    system_prompt: params.inline_system_prompt.clone(),
+                    allowed_tools: params.inline_allowed_tools.clone(),
+                    max_iterations: None,
+                })
+            } else {
+                None
+            };
+        ContractRunSpawnRequest {
+            agent_id: params.agent.clone(),
+            inline,
+            task: render_prompt(&params.prompt, &params.items[index], index),
+            timeout_secs: params.timeout_secs,
+            model: params.model.clone(),
+            model_provider: params.provider.clone(),
+            parent_run_id: params.parent_run_id.clone(),
+            trace_session_id: params.trace_session_id.clone(),
+            trace_scope_id: params.trace_scope_id.clone(),
+            ..ContractRunSpawnRequest::default()
+        }
+    }
+}
+
+/// Fill `{{item}}` and `{{index}}` in `template`; the item is appended when
+/// the template has no item placeholder.
+fn render_prompt(template: &str, item: &Value, index: usize) -> String {
+    let item_text = match item {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let prompt = template.replace(INDEX_PLACEHOLDER, &index.to_string());
+    if prompt.contains(ITEM_PLACEHOLDER) {
+        prompt.replace(ITEM_PLACEHOLDER, &item_text)
+    } else {
+        format!("{prompt}\n\nInput:\n{item_text}")
+    }
+}
+
+fn item_entry(index: usize, item: &Value, task_id: Option<&str>, status: &str) -> Value {
+    json!({
+        "index": index,
+        "item": item,
+        "task_id": task_id,
+        "status": status,
+    })
+}
+
+fn completion_entry(index: usize, item: &Value, completion: SubagentCompletion) -> Value {
+    let status = match completion.status {
+        SubagentStatus::Completed => "completed",
+        SubagentStatus::Failed => "failed",
+        SubagentStatus::Interrupted => "interrupted",
+        SubagentStatus::TimedOut => "timed_out",
+        SubagentStatus::Pending => "pending",
+        SubagentStatus::Running => "running",
+    };
+    let mut entry = item_entry(index, item, Some(&completion.id), status);
+    if let Some(result) = completion.result {
+        entry["duration_ms"] = json!(result.duration_ms);
+        if let Some(tokens) = result.tokens_used {
+            entry["tokens_used"] = json!(tokens);
+        }
+        if let Some(cost) = result.cost_usd {
+            entry["cost_usd"] = json!(cost);
+        }
+        if completion.status == SubagentStatus::Completed {
+            entry["output"] = json!(result.output);
+        } else {
+            entry["error"] = json!(result.error.unwrap_or_else(|| "Unknown error".to_string()));
+        }
+    }
+    entry
+}
+
+fn report(results: Vec<Value>, max_concurrency: usize, started_at: Instant) -> Value {
+    let succeeded = results
+        .iter()
+        .filter(|entry| entry["status"] == "completed")
+        .count();
+    let failed = results.len() - succeeded;
+    let total_tokens: u64 = results
+        .iter()
+        .filter_map(|entry| entry["tokens_used"].as_u64())
+        .sum();
+    let total_cost_usd: f64 = results
+        .iter()
+        .filter_map(|entry| entry["cost_usd"].as_f64())
+        .sum();
+    let status = if failed == 0 {
+        "completed"
+    } else if succeeded == 0 {
+        "failed"
+    } else {
+        "partial"
+    };
+    json!({
+        "status": status,
+        "total": results.len(),
+        "succeeded": succeeded,
+        "failed": failed,
+        "max_concurrency": max_concurrency,
+        "duration_ms": started_at.elapsed().as_millis() as u64,
+        "total_tokens": total_tokens,
+        "total_cost_usd": total_cost_usd,
+        "results": results,
+    })
+}
+
+#[async_trait]
+impl Tool for FanoutTool {
+    fn name(&self) -> &str {
+        "fanout"
+    }
+
+    fn description(&self) -> &str {
+        "Run one sub-agent per input item from a shared prompt template, with bounded concurrency, and return an aggregated report of all results."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": MAX_FANOUT_ITEMS,
+                    "description": "Inputs to process; one sub-agent runs per item"
+                },
+                "prompt": {
+                    "type": "string",
+                    "description": "Task template. '{{item}}' is replaced with the item (JSON for non-strings) and '{{index}}' with its zero-based position. Without '{{item}}', the item is appended."
+                },
+                "agent": {
+                    "type": "string",
+                    "description": "Optional agent ID or name; omit to use a temporary sub-agent"
+                },
+                "model": {
+                    "type": "string",
+                    "description": "Optional model override for every sub-agent"
+                },
+                "provider": {
+                    "type": "string",
+                    "description": "Optional provider paired with model"
+                },
+                "inline_system_prompt": {
+                    "type": "string",
+                    "description": "Optional system prompt for temporary sub-agents"
+                },
+                "inline_allowed_tools": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional tool allowlist for temporary sub-agents"
+                },
+                "max_concurrency": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "default": DEFAULT_FANOUT_CONCURRENCY,
+                    "description": "Maximum sub-agents running at once, capped by free sub-agent slots"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Per-item timeout in seconds; defaults to the sub-agent timeout"
+                },
+                "preview": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Validate and preview capability warnings without spawning"
+                },
+                "approval_id": {
+                    "type": "string",
+                    "description": "Approval ID returned by preview when confirmation is required"
+                }
+            },
+            "required": ["items", "prompt"]
+        })
+    }
+
+    async fn execute(&self, mut input: Value) -> Result<ToolOutput> {
+        normalize_legacy_approval_replay(&mut input);
+        let params: FanoutParams = serde_json::from_value(input)
+            .map_err(|err| ToolError::Tool(format!("Invalid parameters: {}", err)))?;
+        if params.items.is_empty() {
+            return Err(ToolError::Tool("'items' must not be empty.".to_string()));
+        }
+        if params.items.len() > MAX_FANOUT_ITEMS {
+            return Err(ToolError::Tool(format!(
+                "fanout accepts at most {} items, got {}.",
+                MAX_FANOUT_ITEMS,
+                params.items.len()
+            )));
+        }
+        if params.prompt.trim().is_empty() {
+            return Err(ToolError::Tool("'prompt' must not be empty.".to_string()));
+        }
+
+        if let Some(assessor) = &self.assessor {
+            let requests = (0..params.items.len())
+                .map(|index| Self::spawn_request(&params, index))
+                .collect();
+            let assessment = assessor
+                .assess_subagent_batch("fanout", requests, false)
+                .await?;
+            if params.preview {
+                return Ok(preview_output(assessment));
+            }
+            if let Some(output) =
+                enforce_confirmation_or_defer(&assessment, params.approval_id.as_deref())?
+            {
+                return Ok(output);
+            }
+        } else if params.preview {
+            return Err(ToolError::Tool(
+                "Sub-agent capability preview is unavailable in this runtime.".to_string(),
+            ));
+        }
+
+        let config = self.manager.config();
+        let available_slots = config
+            .max_parallel_agents
+            .saturating_sub(self.manager.running_count());
+        if available_slots == 0 {
+            return Err(ToolError::Tool(format!(
+                "No sub-agent slots are available (max_parallel: {}).",
+                config.max_parallel_agents
+            )));
+        }
+        let max_concurrency = params
+            .max_concurrency
+            .unwrap_or(DEFAULT_FANOUT_CONCURRENCY)
+            .clamp(1, available_slots);
+        let wait_window = Duration::from_secs(
+            params
+                .timeout_secs
+                .unwrap_or(config.subagent_timeout_secs)
+                .saturating_add(WAIT_GRACE_SECS),
+        );
+
+        let started_at = Instant::now();
+        let mut results: Vec<Option<Value>> = vec![None; params.items.len()];
+        let mut in_flight: Vec<(String, usize)> = Vec::with_capacity(max_concurrency);
+        let mut next = 0;
+        loop {
+            while in_flight.len() < max_concurrency && next < params.items.len() {
+                match self.manager.spawn(Self::spawn_request(&params, next)) {
+                    Ok(handle) => in_flight.push((handle.id, next)),
+                    Err(error) => {
+                        let mut entry = item_entry(next, &params.items[next], None, "failed");
+                        entry["error"] = json!(format!("Failed to spawn sub-agent: {error}"));
+                        results[next] = Some(entry);
+                    }
+                }
+                next += 1;
+            }
+            if in_flight.is_empty() {
+                break;
+            }
+
+            let task_ids: Vec<String> = in_flight.iter().map(|(id, _)| id.clone()).collect();
+            match timeout(wait_window, self.manager.wait_first(&task_ids)).await {
+                Ok(Some(completion)) => {
+                    let Some(position) = in_flight.iter().position(|(id, _)| *id == completion.id)
+                    else {
+                        continue;
+                    };
+                    let (_, index) = in_flight.swap_remove(position);
+                    results[index] =
+                        Some(completion_entry(index, &params.items[index], completion));
+                }
+                Ok(None) => {
+                    for (task_id, index) in in_flight.drain(..) {
+                        results[index] = Some(item_entry(
+                            index,
+                            &params.items[index],
+                            Some(&task_id),
+                            "not_found",
+                        ));
+                    }
+                }
+                Err(_) => {
+                    for (task_id, index) in in_flight.drain(..) {
+                        results[index] = Some(item_entry(
+                            index,
+                            &params.items[index],
+                            Some(&task_id),
+                            "timeout",
+                        ));
+                    }
+                    for (index, item) in params.items.iter().enumerate().skip(next) {
+                        results[index] = Some(item_entry(index, item, None, "skipped"));
+                    }
+                    break;
+                }
+            }
+        }
+
+        let results = results.into_iter().flatten().collect();
+        Ok(ToolOutput::success(report(
+            results,
+            max_concurrency,
+            started_at,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use restflow_ai::agent::{
+        SubagentConfig, SubagentDefLookup, SubagentDefSnapshot, SubagentDefSummary, SubagentDeps,
+        SubagentManagerImpl, SubagentTracker,
+    };
+    use restflow_ai::llm::{MockLlmClient, MockStep};
+    use restflow_ai::tools::ToolRegistry;
+    use tokio::sync::mpsc;
+
+    struct MockDefLookup;
+
+    impl SubagentDefLookup for MockDefLookup {
+        fn lookup(&self, id: &str) -> Option<SubagentDefSnapshot> {
+            (id == "tester").then(|| SubagentDefSnapshot {
+                name: id.to_string(),
+                system_prompt: "You are a test agent.".to_string(),
+                allowed_tools: vec![],
+                max_iterations: Some(1),
+                default_model: None,
+            })
+        }
+
+        fn list_callable(&self) -> Vec<SubagentDefSummary> {
+            vec![SubagentDefSummary {
+                id: "tester".to_string(),
+                name: "tester".to_string(),
+                description: "test agent".to_string(),
+                tags: vec![],
+            }]
+        }
+    }
+
+    fn make_manager(
+        mock_steps: Vec<MockStep>,
+        max_parallel_agents: usize,
+    ) -> (Arc<SubagentTracker>, Arc<dyn SubagentManager>) {
+        let (tx, rx) = mpsc::channel(16);
+        let tracker = Arc::new(SubagentTracker::new(tx, rx));
+        let deps = SubagentDeps {
+            tracker: tracker.clone(),
+            definitions: Arc::new(MockDefLookup),
+            llm_client: Arc::new(MockLlmClient::from_steps("mock", mock_steps)),
+            tool_registry: Arc::new(ToolRegistry::new()),
+            config: SubagentConfig {
+                max_parallel_agents,
+                subagent_timeout_secs: 10,
+                max_iterations: 5,
+                max_depth: 1,
+            },
+            llm_client_factory: None,
+            orchestrator: None,
+        };
+        let manager: Arc<dyn SubagentManager> = Arc::new(SubagentManagerImpl::from_deps(&deps));
+        (tracker, manager)
+    }
+
+    #[test]
+    fn test_render_prompt_fills_placeholders() {
+        assert_eq!(
+            render_prompt("Review {{item}} ({{index}})", &json!("a.rs"), 2),
+            "Review a.rs (2)"
+        );
+        assert_eq!(
+            render_prompt("Summarize", &json!({"id": 7}), 0),
+            "Summarize\n\nInput:\n{\"id\":7}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fanout_aggregates_results_in_input_order() {
+        let (tracker, manager) = make_manager(
+            vec![
+                MockStep::text("one"),
+                MockStep::text("two"),
+                MockStep::text("three"),
+            ],
+            5,
+        );
+        let tool = FanoutTool::new(manager);
+
+        let output = tool
+            .execute(json!({
+                "items": ["a", "b", "c"],
+                "prompt": "Handle {{item}}",
+                "agent": "tester",
+                "max_concurrency": 2,
+                "parent_run_id": "parent-1"
+            }))
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        assert_eq!(output.result["status"], "completed");
+        assert_eq!(output.result["total"], 3);
+        assert_eq!(output.result["succeeded"], 3);
+        assert_eq!(output.result["max_concurrency"], 2);
+        let results = output.result["results"].as_array().unwrap();
+        let items: Vec<&str> = results
+            .iter()
+            .map(|entry| entry["item"].as_str().unwrap())
+            .collect();
+        assert_eq!(items, vec!["a", "b", "c"]);
+        assert!(results.iter().all(|entry| entry["output"].is_string()));
+        let tasks: Vec<String> = tracker.all().into_iter().map(|state| state.task).collect();
+        assert!(tasks.contains(&"Handle b".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_fanout_caps_concurrency_to_free_slots() {
+        let (_tracker, manager) = make_manager(vec![MockStep::text("ok"), MockStep::text("ok")], 1);
+        let tool = FanoutTool::new(manager);
+
+        let output = tool
+            .execute(json!({
+                "items": [1, 2],
+                "prompt": "Process {{item}}",
+                "agent": "tester",
+                "max_concurrency": 8
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(output.result["max_concurrency"], 1);
+        assert_eq!(output.result["succeeded"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_fanout_reports_failed_items() {
+        let (_tracker, manager) = make_manager(vec![MockStep::error("LLM error")], 5);
+        let tool = FanoutTool::new(manager);
+
+        let output = tool
+            .execute(json!({
+                "items": ["only"],
+                "prompt": "Process {{item}}",
+                "agent": "tester"
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(output.result["status"], "failed");
+        assert_eq!(output.result["failed"], 1);
+        assert!(output.result["results"][0]["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_fanout_rejects_empty_items() {
+        let (_tracker, manager) = make_manager(vec![], 5);
+        let tool = FanoutTool::new(manager);
+
+        let err = tool
+            .execute(json!({"items": [], "prompt": "x"}))
+            .await
+            .expect_err("empty items should fail");
+        assert!(err.to_string().contains("must not be empty"));
+    }
+}
@@ -0,0 +1,1119 @@
+//! Structured git tool for AI agents.
+//!
+//! Runs the `git` CLI with machine-readable output formats (porcelain v2,
+//! `--numstat`, custom `--format` strings) and returns parsed JSON, so agents
+//! do not have to scrape human-oriented output through `bash`.
+//!
+//! Guardrails:
+//! - Force pushes are refused unless the tool is built with
+//!   [`GitTool::allow_force_push`], and then use `--force-with-lease`.
+//! - Branch, remote and revision arguments may not start with `-`, and paths
+//!   are always passed after `--`, so inputs cannot smuggle in extra flags.
+//! - Mutating operations go through the security gate.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::Result;
+use crate::check_security;
+use crate::security::SecurityGate;
+use crate::{Tool, ToolAction, ToolOutput};
+
+const DEFAULT_LOG_LIMIT: usize = 20;
+const MAX_LOG_LIMIT: usize = 200;
+/// Maximum size of a returned patch (200 KB).
+const MAX_DIFF_BYTES: usize = 200 * 1024;
+const GIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+
+#[derive(Debug, Deserialize)]
+struct GitInput {
+    path: Option<String>,
+    #[serde(flatten)]
+    operation: GitOperation,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum GitOperation {
+    Status,
+    Diff {
+        #[serde(default)]
+        staged: bool,
+        revision: Option<String>,
+        #[serde(default)]
+        paths: Vec<String>,
+        #[serde(default)]
+        stat: bool,
+    },
+    Add {
+        #[serde(default)]
+        paths: Vec<String>,
+        #[serde(default)]
+        all: bool,
+    },
+    Commit {
+        message: String,
+        #[serde(default)]
+        all: bool,
+    },
+    Branch {
+        #[serde(default)]
+        action: BranchAction,
+        name: Option<String>,
+        start_point: Option<String>,
+    },
+    Log {
+        limit: Option<usize>,
+        revision: Option<String>,
+        #[serde(default)]
+        paths: Vec<String>,
+    },
+    Stash {
+        #[serde(default)]
+        action: StashAction,
+        message: Option<String>,
+        #[serde(default)]
+        include_untracked: bool,
+        index: Option<usize>,
+    },
+    Worktree {
+        #[serde(default)]
+        action: WorktreeAction,
+        worktree: Option<String>,
+        branch: Option<String>,
+        new_branch: Option<String>,
+    },
+    Push {
+        remote: Option<String>,
+        branch: Option<String>,
+        #[serde(default)]
+        set_upstream: bool,
+        #[serde(default)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BranchAction {
+    #[default]
+    List,
+    Create,
+    Switch,
+    Delete,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StashAction {
+    #[default]
+    List,
+    Push,
+    Pop,
+    Apply,
+    Drop,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WorktreeAction {
+    #[default]
+    List,
+    Add,
+    Remove,
+}
+
+impl GitOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Diff { .. } => "diff",
+            Self::Add { .. } => "add",
+            Self::Commit { .. } => "commit",
+            Self::Branch { .. } => "branch",
+            Self::Log { .. } => "log",
+            Self::Stash { .. } => "stash",
+            Self::Worktree { .. } => "worktree",
+            Self::Push { .. } => "push",
+        }
+    }
+
+    /// Read-only operations skip the security gate.
+    fn is_read_only(&self) -> bool {
+        match self {
+            Self::Status | Self::Diff { .. } | Self::Log { .. } => true,
+            Self::Branch { action, .. } => matches!(action, BranchAction::List),
+            Self::Stash { action, .. } => matches!(action, StashAction::List),
+            Self::Worktree { action, .. } => matches!(action, WorktreeAction::List),
+            Self::Add { .. } | Self::Commit { .. } | Self::Push { .. } => false,
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            Self::Add { paths, all } => {
+                if *all {
+                    "git add --all".to_string()
+                } else {
+                    format!("git add {}", paths.join(" "))
+                }
+            }
+            Self::Commit { message, .. } => {
+                format!("git commit: {}", message.lines().next().unwrap_or_default())
+            }
+            Self::Branch { action, name, .. } => format!(
+                "git branch {:?} {}",
+                action,
+                name.as_deref().unwrap_or_default()
+            ),
+            Self::Stash { action, .. } => format!("git stash {:?}", action),
+            Self::Worktree {
+                action, worktree, ..
+            } => format!(
+                "git worktree {:?} {}",
+                action,
+                worktree.as_deref().unwrap_or_default()
+            ),
+            Self::Push {
+                remote,
+                branch,
+                force,
+                ..
+            } => format!(
+                "git push {} {}{}",
+                remote.as_deref().unwrap_or("(default remote)"),
+                branch.as_deref().unwrap_or("(current branch)"),
+                if *force { " (force)" } else { "" }
+            ),
+            other => format!("git {}", other.name()),
+        }
+    }
+}
+
+/// Captured result of one `git` invocation.
+struct GitRun {
+    success: bool,
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+impl GitRun {
+    fn error_message(&self, args: &[String]) -> String {
+        let detail = if self.stderr.trim().is_empty() {
+            self.stdout.trim()
+        } else {
+            self.stderr.trim()
+        };
+        format!(
+            "git {} failed (exit code {}): {}",
+            args.first().map(String::as_str).unwrap_or_default(),
+            self.exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+            detail
+        )
+    }
+}
+
+/// Reject user-supplied refs and names that git would parse as options.
+fn validate_arg(kind: &str, value: &str) -> std::result::Result<(), String> {
+    if value.trim().is_empty() {
+        return Err(format!("{} must not be empty.", kind));
+    }
+    if value.starts_with('-') {
+        return Err(format!("{} '{}' must not start with '-'.", kind, value));
+    }
+    Ok(())
+}
+
+fn require_arg<'a>(kind: &str, value: Option<&'a str>) -> std::result::Result<&'a str, String> {
+    let value = value.ok_or_else(|| format!("'{}' is required for this operation.", kind))?;
+    validate_arg(kind, value)?;
+    Ok(value)
+}
+
+fn status_code_name(code: char) -> &'static str {
+    match code {
+        'M' => "modified",
+        'T' => "type_changed",
+        'A' => "added",
+        'D' => "deleted",
+        'R' => "renamed",
+        'C' => "copied",
+        'U' => "unmerged",
+        _ => "unknown",
+    }
+}
+
+/// Parse `git status --porcelain=v2 --branch` output.
+fn parse_status(output: &str) -> Value {
+    let mut branch = Value::Null;
+    let mut commit = Value::Null;
+    let mut upstream = Value::Null;
+    let mut ahead = 0i64;
+    let mut behind = 0i64;
+    let mut staged = Vec::new();
+    let mut unstaged = Vec::new();
+    let mut untracked = Vec::new();
+    let mut conflicted = Vec::new();
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" if value != "(initial)" => commit = json!(value),
+                "branch.head" if value != "(detached)" => branch = json!(value),
+                "branch.upstream" => upstream = json!(value),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let (kind, rest) = line.split_at(line.len().min(2));
+        match kind {
+            "1 " | "2 " => {
+                // Ordinary entries have 7 fields before the path; renames and
+                // copies add a similarity score and "<path>\t<orig_path>".
+                let fields = if kind == "1 " { 8 } else { 9 };
+                let parts: Vec<&str> = rest.splitn(fields, ' ').collect();
+                let (Some(xy), Some(path_field)) = (parts.first(), parts.get(fields - 1)) else {
+                    continue;
+                };
+                let (path, orig_path) = match path_field.split_once('\t') {
+                    Some((path, orig)) => (path, Some(orig)),
+                    None => (*path_field, None),
+                };
+                let mut codes = xy.chars();
+                let index = codes.next().unwrap_or('.');
+                let worktree = codes.next().unwrap_or('.');
+                if index != '.' {
+                    let mut entry = json!({ "path": path, "status": status_code_name(index) });
+                    if let Some(orig) = orig_path {
+                        entry["orig_path"] = json!(orig);
+                    }
+                    staged.push(entry);
+                }
+                if worktree != '.' {
+                    unstaged.push(json!({ "path": path, "status": status_code_name(worktree) }));
+                }
+            }
+            "u " => {
+                if let Some(path) = rest.splitn(10, ' ').nth(9) {
+                    conflicted.push(json!(path));
+                }
+            }
+            "? " => untracked.push(json!(rest)),
+            _ => {}
+        }
+    }
+
+    let clean =
+        staged.is_empty() && unstaged.is_empty() && untracked.is_empty() && conflicted.is_empty();
+    json!({
+        "branch": branch,
+        "commit": commit,
+        "upstream": upstream,
+        "ahead": ahead,
+        "behind": behind,
+        "clean": clean,
+        "staged": staged,
+        "unstaged": unstaged,
+        "untracked": untracked,
+        "conflicted": conflicted,
+    })
+}
+
+/// Parse `git diff --numstat` output. Binary files report `-` counts.
+fn parse_numstat(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let additions = parts.next()?;
+            let deletions = parts.next()?;
+            let path = parts.next()?;
+            let binary = additions == "-" && deletions == "-";
+            Some(json!({
+                "path": path,
+                "additions": additions.parse::<u64>().ok(),
+                "deletions": deletions.parse::<u64>().ok(),
+                "binary": binary,
+            }))
+        })
+        .collect()
+}
+
+fn parse_records(output: &str) -> impl Iterator<Item = Vec<&str>> {
+    output
+        .split(RECORD_SEPARATOR)
+        .map(str::trim)
+        .filter(|record| !record.is_empty())
+        .map(|record| record.split(FIELD_SEPARATOR).collect())
+}
+
+fn parse_log(output: &str) -> Vec<Value> {
+    parse_records(output)
+        .filter(|fields| fields.len() >= 6)
+        .map(|fields| {
+            json!({
+                "hash": fields[0],
+                "short_hash": fields[1],
+                "author_name": fields[2],
+                "author_email": fields[3],
+                "date": fields[4],
+                "subject": fields[5],
+            })
+        })
+        .collect()
+}
+
+fn parse_branches(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(FIELD_SEPARATOR).collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            let upstream = (!fields[3].is_empty()).then_some(fields[3]);
+            Some(json!({
+                "name": fields[0],
+                "commit": fields[1],
+                "current": fields[2] == "*",
+                "upstream": upstream,
+                "tracking": fields[4],
+            }))
+        })
+        .collect()
+}
+
+fn parse_stash_list(output: &str) -> Vec<Value> {
+    parse_records(output)
+        .filter(|fields| fields.len() >= 3)
+        .map(|fields| {
+            json!({
+                "ref": fields[0],
+                "commit": fields[1],
+                "message": fields[2],
+            })
+        })
+        .collect()
+}
+
+/// Parse `git worktree list --porcelain` output.
+fn parse_worktrees(output: &str) -> Vec<Value> {
+    let mut worktrees = Vec::new();
+    for block in output.split("\n\n") {
+        let mut entry = json!({
+            "path": Value::Null,
+            "head": Value::Null,
+            "branch": Value::Null,
+            "detached": false,
+            "bare": false,
+            "locked": false,
+        });
+        for line in block.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "worktree" => entry["path"] = json!(value),
+                "HEAD" => entry["head"] = json!(value),
+                "branch" => {
+                    entry["branch"] = json!(value.strip_prefix("refs/heads/").unwrap_or(value))
+                }
+                "detached" | "bare" | "locked" => entry[key] = json!(true),
+                _ => {}
+            }
+        }
+        if !entry["path"].is_null() {
+            worktrees.push(entry);
+        }
+    }
+    worktrees
+}
+
+fn truncate_diff(diff: String) -> (String, bool) {
+    if diff.len() <= MAX_DIFF_BYTES {
+        return (diff, false);
+    }
+    let mut end = MAX_DIFF_BYTES;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    (
+        format!(
+            "{}...\n[Diff truncated, {} bytes total]",
+            &diff[..end],
+            diff.len()
+        ),
+        true,
+    )
+}
+
+/// Structured git operations tool.
+pub struct GitTool {
+    base_dir: Option<PathBuf>,
+    require_base_dir: bool,
+    allow_force_push: bool,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl Default for GitTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitTool {
+    pub fn new() -> Self {
+        Self {
+            base_dir: None,
+            require_base_dir: false,
+            allow_force_push: false,
+            security_gate: None,
+            agent_id: None,
+            task_id: None,
+        }
+    }
+
+    pub fn with_base_dir(mut self, base: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base.into());
+        self
+    }
+
+    pub fn require_base_dir(mut self) -> Self {
+        self.require_base_dir = true;
+        self
+    }
+
+    /// Permit `push` with `force: true` (sent as `--force-with-lease`).
+    pub fn allow_force_push(mut self, allow: bool) -> Self {
+        self.allow_force_push = allow;
+        self
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    fn resolve_repo(&self, path: Option<&str>) -> std::result::Result<PathBuf, String> {
+        if let Some(p) = path {
+            let candidate = PathBuf::from(p);
+            if candidate.is_absolute() {
+                Ok(candidate)
+            } else if let Some(base) = &self.base_dir {
+                Ok(base.join(candidate))
+            } else {
+                Err(
+                    "Relative repository paths require an explicit workspace root or base directory."
+                        .to_string(),
+                )
+            }
+        } else if let Some(base) = &self.base_dir {
+            Ok(base.clone())
+        } else if self.require_base_dir {
+            Err("This tool requires an explicit workspace root or base directory.".to_string())
+        } else {
+            Err("A repository path or base directory is required.".to_string())
+        }
+    }
+
+    async fn check_action_allowed(
+        &self,
+        operation: &str,
+        target: String,
+        summary: String,
+    ) -> Result<Option<String>> {
+        let action = ToolAction {
+            tool_name: self.name().to_string(),
+            operation: operation.to_string(),
+            target,
+            summary,
+        };
+
+        check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await
+    }
+
+    async fn run(&self, repo: &Path, args: &[String]) -> std::result::Result<GitRun, String> {
+        let mut cmd = Command::new("git");
+        cmd.arg("-C")
+            .arg(repo)
+            .args(["-c", "core.quotepath=off", "-c", "color.ui=false"])
+            .args(args)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_EDITOR", "true")
+            .env("GIT_PAGER", "cat")
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+
+        let output = match tokio::time::timeout(GIT_TIMEOUT, cmd.output()).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => return Err(format!("Failed to run git: {}", e)),
+            Err(_) => {
+                return Err(format!(
+                    "git {} timed out after {} seconds",
+                    args.first().map(String::as_str).unwrap_or_default(),
+                    GIT_TIMEOUT.as_secs()
+                ));
+            }
+        };
+
+        Ok(GitRun {
+            success: output.status.success(),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    /// Run git and return stdout, turning a non-zero exit into an error.
+    async fn git(&self, repo: &Path, args: &[&str]) -> std::result::Result<String, String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let run = self.run(repo, &args).await?;
+        if run.success {
+            Ok(run.stdout)
+        } else {
+            Err(run.error_message(&args))
+        }
+    }
+
+    async fn status(&self, repo: &Path) -> std::result::Result<Value, String> {
+        let output = self
+            .git(repo, &["status", "--porcelain=v2", "--branch"])
+            .await?;
+        Ok(parse_status(&output))
+    }
+
+    async fn execute_operation(
+        &self,
+        repo: &Path,
+        operation: GitOperation,
+    ) -> std::result::Result<Value, String> {
+        match operation {
+            GitOperation::Status => self.status(repo).await,
+            GitOperation::Diff {
+                staged,
+                revision,
+                paths,
+                stat,
+            } => {
+                let mut base: Vec<&str> = vec!["diff"];
+                if staged {
+                    base.push("--cached");
+                }
+                if let Some(revision) = revision.as_deref() {
+                    validate_arg("revision", revision)?;
+                    base.push(revision);
+                }
+                let mut pathspec: Vec<&str> = vec!["--"];
+                pathspec.extend(paths.iter().map(String::as_str));
+
+                let numstat_args = [&base[..], &["--numstat"], &pathspec[..]].concat();
+                let files = parse_numstat(&self.git(repo, &numstat_args).await?);
+                if stat {
+                    return Ok(json!({ "files": files }));
+                }
+                let patch_args = [&base[..], &pathspec[..]].concat();
+                let (diff, truncated) = truncate_diff(self.git(repo, &patch_args).await?);
+                Ok(json!({ "files": files, "diff": diff, "truncated": truncated }))
+            }
+            GitOperation::Add { paths, all } => {
+                if all {
+                    self.git(repo, &["add", "--all"]).await?;
+                } else {
+                    if paths.is_empty() {
+                        return Err("'paths' is required unless 'all' is true.".to_string());
+                    }
+                    let mut args = vec!["add", "--"];
+                    args.extend(paths.iter().map(String::as_str));
+                    self.git(repo, &args).await?;
+                }
+                let staged = self.git(repo, &["diff", "--cached", "--name-only"]).await?;
+                Ok(json!({ "staged": staged.lines().collect::<Vec<_>>() }))
+            }
+            GitOperation::Commit { message, all } => {
+                if message.trim().is_empty() {
+                    return Err("Commit message must not be empty.".to_string());
+                }
+                let mut args = vec!["commit", "--message", message.as_str()];
+                if all {
+                    args.push("--all");
+                }
+                self.git(repo, &args).await?;
+                let format = format!("--format=%H{0}%h{0}%s", FIELD_SEPARATOR);
+                let output = self.git(repo, &["log", "-1", &format]).await?;
+                let fields: Vec<&str> = output.trim_end().split(FIELD_SEPARATOR).collect();
+                Ok(json!({
+                    "hash": fields.first(),
+                    "short_hash": fields.get(1),
+                    "subject": fields.get(2),
+                }))
+            }
+            GitOperation::Branch {
+                action,
+                name,
+                start_point,
+            } => match action {
+                BranchAction::List => {
+                    let format = format!(
+                        "--format=%(refname:short){0}%(objectname:short){0}%(HEAD){0}%(upstream:short){0}%(upstream:track)",
+                        FIELD_SEPARATOR
+                    );
+                    let output = self.git(repo, &["branch", "--list", &format]).await?;
+                    Ok(json!({ "branches": parse_branches(&output) }))
+                }
+                BranchAction::Create => {
+                    let name = require_arg("name", name.as_deref())?;
+                    let mut args = vec!["branch", name];
+                    if let Some(start) = start_point.as_deref() {
+                        validate_arg("start_point", start)?;
+                        args.push(start);
+                    }
+                    self.git(repo, &args).await?;
+                    Ok(json!({ "created": name }))
+                }
+                BranchAction::Switch => {
+                    let name = require_arg("name", name.as_deref())?;
+                    self.git(repo, &["switch", name]).await?;
+                    Ok(json!({ "switched_to": name }))
+                }
+                BranchAction::Delete => {
+                    // `-d` refuses to drop unmerged work; there is no forced variant.
+                    let name = require_arg("name", name.as_deref())?;
+                    self.git(repo, &["branch", "-d", name]).await?;
+                    Ok(json!({ "deleted": name }))
+                }
+            },
+            GitOperation::Log {
+                limit,
+                revision,
+                paths,
+            } => {
+                let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT).clamp(1, MAX_LOG_LIMIT);
+                let limit_arg = format!("--max-count={}", limit);
+                let format = format!(
+                    "--format=%H{0}%h{0}%an{0}%ae{0}%aI{0}%s{1}",
+                    FIELD_SEPARATOR, RECORD_SEPARATOR
+                );
+                let mut args = vec!["log", limit_arg.as_str(), format.as_str()];
+                if let Some(revision) = revision.as_deref() {
+                    validate_arg("revision", revision)?;
+                    args.push(revision);
+                }
+                args.push("--");
+                args.extend(paths.iter().map(String::as_str));
+                let output = self.git(repo, &args).await?;
+                Ok(json!({ "commits": parse_log(&output) }))
+            }
+            GitOperation::Stash {
+                action,
+                message,
+                include_untracked,
+                index,
+            } => {
+                let stash_ref = format!("stash@{{{}}}", index.unwrap_or(0));
+                match action {
+                    StashAction::List => {
+                        let format = format!(
+                            "--format=%gd{0}%H{0}%gs{1}",
+                            FIELD_SEPARATOR, RECORD_SEPARATOR
+                        );
+                        let output = self.git(repo, &["stash", "list", &format]).await?;
+                        Ok(json!({ "stashes": parse_stash_list(&output) }))
+                    }
+                    StashAction::Push => {
+                        let mut args = vec!["stash", "push"];
+                        if include_untracked {
+                            args.push("--include-untracked");
+                        }
+                        if let Some(message) = message.as_deref() {
+                            args.extend(["--message", message]);
+                        }
+                        let output = self.git(repo, &args).await?;
+                        Ok(json!({
+                            "stashed": !output.contains("No local changes to save"),
+                            "message": output.trim(),
+                        }))
+                    }
+                    StashAction::Pop | StashAction::Apply | StashAction::Drop => {
+                        let verb = match action {
+                            StashAction::Pop => "pop",
+                            StashAction::Apply => "apply",
+                            _ => "drop",
+                        };
+                        let output = self.git(repo, &["stash", verb, &stash_ref]).await?;
+                        Ok(json!({ "stash": stash_ref, "message": output.trim() }))
+                    }
+                }
+            }
+            GitOperation::Worktree {
+                action,
+                worktree,
+                branch,
+                new_branch,
+            } => match action {
+                WorktreeAction::List => {
+                    let output = self.git(repo, &["worktree", "list", "--porcelain"]).await?;
+                    Ok(json!({ "worktrees": parse_worktrees(&output) }))
+                }
+                WorktreeAction::Add => {
+                    let worktree = require_arg("worktree", worktree.as_deref())?;
+                    let mut args = vec!["worktree", "add"];
+                    if let Some(new_branch) = new_branch.as_deref() {
+                        validate_arg("new_branch", new_branch)?;
+                        args.extend(["-b", new_branch]);
+                    }
+                    args.push(worktree);
+                    if let Some(branch) = branch.as_deref() {
+                        validate_arg("branch", branch)?;
+                        args.push(branch);
+                    }
+                    self.git(repo, &args).await?;
+                    Ok(json!({ "added": worktree }))
+                }
+                WorktreeAction::Remove => {
+                    let worktree = require_arg("worktree", worktree.as_deref())?;
+                    self.git(repo, &["worktree", "remove", worktree]).await?;
+                    Ok(json!({ "removed": worktree }))
+                }
+            },
+            GitOperation::Push {
+                remote,
+                branch,
+                set_upstream,
+                force,
+            } => {
+                if force && !self.allow_force_push {
+                    return Err(
+                        "Force push is not allowed. Push without 'force' or ask the user to enable force pushes."
+                            .to_string(),
+                    );
+                }
+                let mut args = vec!["push", "--porcelain"];
+                if force {
+                    args.push("--force-with-lease");
+                }
+                if set_upstream {
+                    args.push("--set-upstream");
+                }
+                if let Some(remote) = remote.as_deref() {
+                    validate_arg("remote", remote)?;
+                    args.push(remote);
+                }
+                if let Some(branch) = branch.as_deref() {
+                    validate_arg("branch", branch)?;
+                    // A leading '+' in a refspec forces the update.
+                    if branch.starts_with('+') && !self.allow_force_push {
+                        return Err("Force push refspecs ('+ref') are not allowed.".to_string());
+                    }
+                    if remote.is_none() {
+                        return Err("'remote' is required when 'branch' is given.".to_string());
+                    }
+                    args.push(branch);
+                }
+                let output = self.git(repo, &args).await?;
+                Ok(json!({ "forced": force, "output": output.trim() }))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for GitTool {
+    fn name(&self) -> &str {
+        "git"
+    }
+
+    fn description(&self) -> &str {
+        "Run git operations and get structured JSON results: status, diff, add, commit, branch, log, stash, worktree, push. Prefer this over running git through bash. Force pushes are refused unless enabled."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["status", "diff", "add", "commit", "branch", "log", "stash", "worktree", "push"],
+                    "description": "Git operation to run"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Repository directory. Defaults to the workspace root."
+                },
+                "action": {
+                    "type": "string",
+                    "description": "Sub-action. branch: list|create|switch|delete; stash: list|push|pop|apply|drop; worktree: list|add|remove. Defaults to list."
+                },
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Pathspecs for diff, add and log"
+                },
+                "staged": { "type": "boolean", "description": "diff: compare the index against HEAD" },
+                "stat": { "type": "boolean", "description": "diff: return per-file line counts without the patch" },
+                "revision": { "type": "string", "description": "diff/log: revision or range, e.g. HEAD~3 or main..HEAD" },
+                "all": { "type": "boolean", "description": "add: stage all changes; commit: include tracked modifications" },
+                "message": { "type": "string", "description": "commit/stash push: message" },
+                "name": { "type": "string", "description": "branch: branch name" },
+                "start_point": { "type": "string", "description": "branch create: commit to start from" },
+                "limit": { "type": "integer", "description": "log: maximum commits (default 20, max 200)" },
+                "include_untracked": { "type": "boolean", "description": "stash push: include untracked files" },
+                "index": { "type": "integer", "description": "stash pop/apply/drop: stash index (default 0)" },
+                "worktree": { "type": "string", "description": "worktree add/remove: worktree directory" },
+                "branch": { "type": "string", "description": "worktree add: branch to check out; push: branch to push" },
+                "new_branch": { "type": "string", "description": "worktree add: create this branch for the worktree" },
+                "remote": { "type": "string", "description": "push: remote name" },
+                "set_upstream": { "type": "boolean", "description": "push: set the pushed branch as upstream" },
+                "force": { "type": "boolean", "description": "push: force with lease. Refused unless force pushes are enabled." }
+            },
+            "required": ["operation"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let input: GitInput = match serde_json::from_value(input) {
+            Ok(input) => input,
+            Err(e) => {
+                return Ok(ToolOutput::error(format!(
+                    "Invalid input: {}. Required: operation (status|diff|add|commit|branch|log|stash|worktree|push).",
+                    e
+                )));
+            }
+        };
+
+        let repo = match self.resolve_repo(input.path.as_deref()) {
+            Ok(repo) => repo,
+            Err(message) => return Ok(ToolOutput::error(message)),
+        };
+
+        let operation = input.operation;
+        if !operation.is_read_only()
+            && let Some(message) = self
+                .check_action_allowed(
+                    operation.name(),
+                    repo.display().to_string(),
+                    operation.summary(),
+                )
+                .await?
+        {
+            return Ok(ToolOutput::error(message));
+        }
+
+        match self.execute_operation(&repo, operation).await {
+            Ok(result) => Ok(ToolOutput::success(result)),
+            Err(message) => Ok(ToolOutput::error(message)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git_available() -> bool {
+        StdCommand::new("git").arg("--version").output().is_ok()
+    }
+
+    fn init_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for args in [
+            &["init", "--initial-branch=main"][..],
+            &["config", "user.name", "Test"],
+            &["config", "user.email", "test@example.com"],
+            &["config", "commit.gpgsign", "false"],
+        ] {
+            let output = StdCommand::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+        }
+        dir
+    }
+
+    fn tool_for(dir: &TempDir) -> GitTool {
+        GitTool::new().with_base_dir(dir.path()).require_base_dir()
+    }
+
+    #[test]
+    fn test_parse_status_porcelain_v2() {
+        let output = "# branch.oid 1234\n# branch.head main\n# branch.upstream origin/main\n# branch.ab +2 -1\n1 M. N... 100644 100644 100644 aaa bbb src/lib.rs\n1 .M N... 100644 100644 100644 aaa bbb README.md\n2 R. N... 100644 100644 100644 aaa bbb R100 new name.rs\told.rs\nu UU N... 100644 100644 100644 100644 aaa bbb ccc conflict.rs\n? notes.txt\n";
+        let status = parse_status(output);
+        assert_eq!(status["branch"], "main");
+        assert_eq!(status["upstream"], "origin/main");
+        assert_eq!(status["ahead"], 2);
+        assert_eq!(status["behind"], 1);
+        assert_eq!(status["clean"], false);
+        assert_eq!(status["staged"][0]["path"], "src/lib.rs");
+        assert_eq!(status["staged"][0]["status"], "modified");
+        assert_eq!(status["staged"][1]["path"], "new name.rs");
+        assert_eq!(status["staged"][1]["orig_path"], "old.rs");
+        assert_eq!(status["unstaged"][0]["path"], "README.md");
+        assert_eq!(status["conflicted"][0], "conflict.rs");
+        assert_eq!(status["untracked"][0], "notes.txt");
+    }
+
+    #[test]
+    fn test_parse_worktrees() {
+        let output = "worktree /repo\nHEAD abc\nbranch refs/heads/main\n\nworktree /repo-wt\nHEAD def\ndetached\n";
+        let worktrees = parse_worktrees(output);
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0]["branch"], "main");
+        assert_eq!(worktrees[1]["detached"], true);
+    }
+
+    #[tokio::test]
+    async fn test_add_commit_status_and_log() {
+        if !git_available() {
+            return;
+        }
+        let dir = init_repo();
+        let tool = tool_for(&dir);
+        std::fs::write(dir.path().join("a.txt"), "hello\n").unwrap();
+
+        let status = tool
+            .execute(json!({ "operation": "status" }))
+            .await
+            .unwrap();
+        assert!(status.success, "{:?}", status.error);
+        assert_eq!(status.result["untracked"][0], "a.txt");
+
+        let add = tool
+            .execute(json!({ "operation": "add", "paths": ["a.txt"] }))
+            .await
+            .unwrap();
+        assert!(add.success, "{:?}", add.error);
+        assert_eq!(add.result["staged"][0], "a.txt");
+
+        let commit = tool
+            .execute(json!({ "operation": "commit", "message": "Add a" }))
+            .await
+            .unwrap();
+        assert!(commit.success, "{:?}", commit.error);
+        assert_eq!(commit.result["subject"], "Add a");
+
+        std::fs::write(dir.path().join("a.txt"), "hello\nworld\n").unwrap();
+        let diff = tool.execute(json!({ "operation": "diff" })).await.unwrap();
+        assert!(diff.success, "{:?}", diff.error);
+        assert_eq!(diff.result["files"][0]["path"], "a.txt");
+        assert_eq!(diff.result["files"][0]["additions"], 1);
+        assert!(diff.result["diff"].as_str().unwrap().contains("+world"));
+
+        let log = tool.execute(json!({ "operation": "log" })).await.unwrap();
+        assert!(log.success, "{:?}", log.error);
+        assert_eq!(log.result["commits"][0]["subject"], "Add a");
+        assert_eq!(log.result["commits"][0]["author_email"], "test@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_branch_create_and_list() {
+        if !git_available() {
+            return;
+        }
+        let dir = init_repo();
+        let tool = tool_for(&dir);
+        std::fs::write(dir.path().join("a.txt"), "hello\n").unwrap();
+        tool.execute(json!({ "operation": "add", "all": true }))
+            .await
+            .unwrap();
+        tool.execute(json!({ "operation": "commit", "message": "init" }))
+            .await
+            .unwrap();
+
+        let create = tool
+            .execute(json!({ "operation": "branch", "action": "create", "name": "feature" }))
+            .await
+            .unwrap();
+        assert!(create.success, "{:?}", create.error);
+
+        let list = tool
+            .execute(json!({ "operation": "branch" }))
+            .await
+            .unwrap();
+        let branches = list.result["branches"].as_array().unwrap();
+        assert_eq!(branches.len(), 2);
+        assert!(
+            branches
+                .iter()
+                .any(|b| b["name"] == "main" && b["current"] == true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_force_push_refused_by_default() {
+        let tool = GitTool::new().with_base_dir("/tmp");
+        let output = tool
+            .execute(json!({ "operation": "push", "remote": "origin", "force": true }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("Force push is not allowed"));
+
+        let output = tool
+            .execute(json!({ "operation": "push", "remote": "origin", "branch": "+main" }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn test_option_like_arguments_rejected() {
+        let tool = GitTool::new().with_base_dir("/tmp");
+        let output = tool
+            .execute(json!({ "operation": "branch", "action": "switch", "name": "--orphan" }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("must not start with '-'"));
+    }
+
+    #[tokio::test]
+    async fn test_requires_base_dir() {
+        let tool = GitTool::new().require_base_dir();
+        let output = tool
+            .execute(json!({ "operation": "status" }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("workspace root"));
+    }
+}
@@ -77,7 +77,7 @@ impl HttpTool {
             .map_err(|e| format!("Redirect target blocked by network allowlist: {}", e))
     }
 
-    fn classify_status(status: u16) -> (ToolErrorCategory, bool) {
+    pub(crate) fn classify_status(status: u16) -> (ToolErrorCategory, bool) {
         match status {
             401 | 403 => (ToolErrorCategory::Auth, false),
             404 => (ToolErrorCategory::NotFound, false),
@@ -54,6 +54,13 @@ enum MarketplaceOperation {
         id: String,
     },
     ListInstalled,
+    CheckUpdates {
+        #[serde(default)]
+        source: Option<String>,
+    },
+    Activate {
+        id: String,
+    },
 }
 
 #[async_trait]
@@ -63,7 +70,7 @@ impl Tool for MarketplaceTool {
     }
 
     fn description(&self) -> &str {
-        "Search marketplace skills and install/uninstall them into local skill storage."
+        "Search marketplace skills, install/uninstall them into local skill storage (install accepts id@version and pulls in dependencies), activate skills held back by missing binaries or secrets, and check installed skills for updates."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -72,9 +79,12 @@ impl Tool for MarketplaceTool {
             "properties": {
                 "operation": {
                     "type": "string",
-                    "enum": ["search", "info", "install", "uninstall", "list_installed"]
+                    "enum": ["search", "info", "install", "uninstall", "list_installed", "check_updates", "activate"]
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Skill id; install also accepts id@version to pin a version"
                 },
-                "id": { "type": "string" },
                 "query": { "type": "string" },
                 "category": { "type": "string" },
                 "tags": {
@@ -140,6 +150,14 @@ impl Tool for MarketplaceTool {
                 let result = self.store.list_installed()?;
                 Ok(ToolOutput::success(result))
             }
+            MarketplaceOperation::CheckUpdates { source } => {
+                let result = self.store.check_updates(source.as_deref()).await?;
+                Ok(ToolOutput::success(result))
+            }
+            MarketplaceOperation::Activate { id } => {
+                let result = self.store.activate_skill(&id)?;
+                Ok(ToolOutput::success(result))
+            }
         }
     }
 }
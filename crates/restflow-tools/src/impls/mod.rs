@@ -22,6 +22,7 @@ pub mod multiedit;
 
 // Migrated from restflow-ai
 pub mod agent_crud;
+pub mod api_connector;
 pub mod auth_profile;
 pub mod background_agent;
 pub mod task {
@@ -53,9 +54,14 @@ pub mod task {
         }
     }
 }
+pub mod calendar;
+pub mod code_host;
 pub mod config;
+pub mod container;
+pub mod deliverable_builder;
 pub mod diagnostics;
 pub mod file_tracker;
+pub mod git;
 pub mod jina_reader;
 pub mod memory_mgmt;
 pub mod memory_store;
@@ -67,7 +73,11 @@ pub mod reply;
 pub mod save_deliverable;
 pub mod secrets;
 pub mod session;
+pub mod shell_session;
 pub mod skill;
+pub mod speak;
+pub mod spreadsheet;
+pub mod sql;
 pub mod switch_model;
 pub mod transcribe;
 pub mod vision;
@@ -84,6 +94,7 @@ pub mod security_query;
 pub mod terminal;
 pub mod trigger;
 pub mod unified_memory_search;
+pub mod usage_budget;
 
 // Search tools
 pub mod glob_tool;
@@ -94,11 +105,13 @@ pub mod task_list;
 pub mod batch;
 
 // Migrated from restflow-core
+pub mod fanout;
 pub mod list_subagents;
 pub mod registry_builder;
 pub mod spawn;
 pub mod spawn_subagent;
 pub(crate) mod spawn_subagent_batch;
+pub mod use_plan;
 pub mod use_skill;
 pub mod wait_subagents;
 
@@ -118,22 +131,32 @@ pub use telegram::{TelegramTool, send_telegram_notification};
 
 // Re-export migrated tools
 pub use agent_crud::AgentCrudTool;
+pub use api_connector::ApiConnectorTool;
 pub use auth_profile::AuthProfileTool;
 pub use background_agent::TaskTool;
+pub use calendar::CalendarTool;
+pub use code_host::GitHubTool;
 pub use config::ConfigTool;
+pub use container::{ContainerConfig, ContainerEngine};
+pub use deliverable_builder::DeliverableBuilderTool;
 pub use diagnostics::DiagnosticsTool;
+pub use git::GitTool;
 pub use jina_reader::JinaReaderTool;
 pub use memory_mgmt::MemoryManagementTool;
 pub use memory_store::{DeleteMemoryTool, ListMemoryTool, ReadMemoryTool, SaveMemoryTool};
 pub use monty_python::{PythonTool, RunPythonTool};
 pub use patch::PatchTool;
 pub use process::ProcessTool;
-pub use python_backend::{PythonExecutionBackend, PythonExecutionLimits};
+pub use python_backend::{PythonExecutionBackend, PythonExecutionLimits, ProcessPythonBackend};
 pub use reply::ReplyTool;
 pub use save_deliverable::SaveDeliverableTool;
 pub use secrets::{SecretGetPolicy, SecretsTool};
 pub use session::SessionTool;
+pub use shell_session::ShellSessionTool;
 pub use skill::SkillTool;
+pub use speak::SpeakTool;
+pub use spreadsheet::SpreadsheetTool;
+pub use sql::SqlTool;
 pub use switch_model::SwitchModelTool;
 pub use transcribe::{TranscribeConfig, TranscribeTool};
 pub use vision::VisionTool;
@@ -150,6 +173,7 @@ pub use security_query::SecurityQueryTool;
 pub use terminal::TerminalTool;
 pub use trigger::TriggerTool;
 pub use unified_memory_search::UnifiedMemorySearchTool;
+pub use usage_budget::UsageBudgetTool;
 
 // Re-export search tools
 pub use glob_tool::GlobTool;
@@ -160,12 +184,14 @@ pub use task_list::TaskListTool;
 pub use batch::BatchTool;
 
 // Re-export core-migrated tools
+pub use fanout::FanoutTool;
 pub use list_subagents::ListSubagentsTool;
 pub use registry_builder::{
     BashConfig, FileConfig, SecretsConfig, ToolRegistryBuilder, default_registry,
 };
 pub use spawn::SpawnTool;
 pub use spawn_subagent::SpawnSubagentTool;
+pub use use_plan::UsePlanTool;
 pub use use_skill::UseSkillTool;
 pub use wait_subagents::WaitSubagentsTool;
 
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use restflow_sandbox::ResourceLimits;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::sync::Arc;
@@ -72,8 +73,14 @@ impl RunPythonTool {
         self
     }
 
-    #[cfg(test)]
-    fn with_backend(mut self, backend: Arc<dyn PythonExecutionBackend>) -> Self {
+    /// Run code in interpreter processes capped by `limits`.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.backend = Arc::new(ProcessPythonBackend::monty().with_resource_limits(limits));
+        self
+    }
+
+    /// Run code on a custom execution backend.
+    pub fn with_backend(mut self, backend: Arc<dyn PythonExecutionBackend>) -> Self {
         self.backend = backend;
         self
     }
@@ -108,6 +115,18 @@ impl PythonTool {
             inner: self.inner.with_security(security_gate, agent_id, task_id),
         }
     }
+
+    pub fn with_resource_limits(self, limits: ResourceLimits) -> Self {
+        Self {
+            inner: self.inner.with_resource_limits(limits),
+        }
+    }
+
+    pub fn with_backend(self, backend: Arc<dyn PythonExecutionBackend>) -> Self {
+        Self {
+            inner: self.inner.with_backend(backend),
+        }
+    }
 }
 
 fn python_parameters_schema() -> Value {
@@ -126,7 +145,7 @@ fn python_parameters_schema() -> Value {
                 "type": "object",
                 "properties": {
                     "max_time_ms": { "type": "integer", "description": "Maximum runtime in milliseconds (enforced)" },
-                    "max_memory_mb": { "type": "integer", "description": "Reserved for future support; currently rejected by process backend" },
+                    "max_memory_mb": { "type": "integer", "description": "Memory ceiling in MiB for the interpreter process tree (enforced)" },
                     "max_steps": { "type": "integer", "description": "Reserved for future support; currently rejected by process backend" }
                 }
             }
@@ -168,9 +187,12 @@ async fn execute_python(tool: &RunPythonTool, input: Value) -> Result<ToolOutput
 
     match tool.backend.execute(request).await {
         Ok(output) => Ok(ToolOutput {
-            success: output.exit_code == 0 && !output.timed_out,
+            success: output.exit_code == 0 && !output.timed_out && output.resource_limit.is_none(),
+            error: output
+                .resource_limit
+                .as_ref()
+                .map(|violation| format!("Python execution failed: {}", violation)),
             result: serde_json::to_value(output).unwrap_or(Value::Null),
-            error: None,
             error_category: None,
             retryable: None,
             retry_after_ms: None,
@@ -244,6 +266,7 @@ mod tests {
                 runtime: "monty".to_string(),
                 timed_out: false,
                 limits: request.limits,
+                resource_limit: None,
             })
         }
     }
@@ -284,13 +307,13 @@ mod tests {
             .execute(json!({
                 "code": "print('x')",
                 "limits": {
-                    "max_memory_mb": 128
+                    "max_steps": 1000
                 }
             }))
             .await
             .expect("tool execute should return output");
         assert!(!output.success);
         let error = output.error.unwrap_or_default();
-        assert!(error.contains("max_memory_mb"));
+        assert!(error.contains("max_steps"));
     }
 }
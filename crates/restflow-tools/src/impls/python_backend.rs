@@ -1,13 +1,18 @@
 //! Python execution backend for AI agents
 //!
-//! Provides Python code execution via the Monty sandbox runtime.
+//! Provides Python code execution via the Monty sandbox runtime, or via
+//! `python3` in an ephemeral container when a container backend is set.
 
 use async_trait::async_trait;
+use restflow_sandbox::ResourceLimits;
 use serde::{Deserialize, Serialize};
 use std::process::Stdio;
+use std::sync::Arc;
 use tokio::process::Command;
 use tokio::time::{Duration, timeout};
 
+use super::container::{self, ContainerConfig};
+
 const MONTY_EXECUTABLE_ENV: &str = "RESTFLOW_MONTY_EXECUTABLE";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -51,6 +56,9 @@ pub struct PythonExecutionResult {
     pub runtime: String,
     pub timed_out: bool,
     pub limits: Option<PythonExecutionLimits>,
+    /// Resource limit the process ran into, when that is why it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_limit: Option<String>,
 }
 
 #[async_trait]
@@ -61,12 +69,49 @@ pub trait PythonExecutionBackend: Send + Sync {
     ) -> std::result::Result<PythonExecutionResult, String>;
 }
 
-#[derive(Clone)]
-pub struct ProcessPythonBackend;
+#[derive(Clone, Default)]
+pub struct ProcessPythonBackend {
+    resource_limits: ResourceLimits,
+    container: Option<ContainerConfig>,
+    #[cfg(feature = "sandbox")]
+    sandbox_policy: Option<restflow_sandbox::SandboxPolicy>,
+}
 
 impl ProcessPythonBackend {
     pub fn monty() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Cap memory, CPU share and process count of every interpreter process.
+    pub fn with_resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = limits;
+        self
+    }
+
+    /// Run code with `python3` in an ephemeral container instead of Monty on
+    /// the host.
+    pub fn with_container(mut self, container: ContainerConfig) -> Self {
+        self.container = Some(container);
+        self
+    }
+
+    /// Run the interpreter under `policy` when it runs on the host.
+    #[cfg(feature = "sandbox")]
+    pub fn with_sandbox_policy(mut self, policy: restflow_sandbox::SandboxPolicy) -> Self {
+        self.sandbox_policy = Some(policy);
+        self
+    }
+
+    /// Host command line for running `code`, wrapped by the sandbox if
+    /// configured.
+    fn host_command(&self, code: &str) -> std::result::Result<(String, Vec<String>), String> {
+        let executable = self.resolve_python_executable();
+        #[cfg(feature = "sandbox")]
+        if let Some(ref policy) = self.sandbox_policy {
+            return restflow_sandbox::wrap_command(policy, &executable, &["-c", code])
+                .map_err(|err| format!("failed to apply sandbox: {}", err));
+        }
+        Ok((executable, vec!["-c".to_string(), code.to_string()]))
     }
 
     fn resolve_python_executable(&self) -> String {
@@ -82,15 +127,31 @@ impl ProcessPythonBackend {
             return Ok(());
         };
 
-        if limits.max_memory_mb.is_some() || limits.max_steps.is_some() {
-            return Err(
-                "max_memory_mb and max_steps are not supported by process backend yet".to_string(),
-            );
+        if limits.max_steps.is_some() {
+            return Err("max_steps is not supported by process backend yet".to_string());
         }
 
         Ok(())
     }
 
+    /// Backend limits tightened by the request's `max_memory_mb`.
+    fn effective_resource_limits(&self, request: &PythonExecutionRequest) -> ResourceLimits {
+        let mut limits = self.resource_limits;
+        if let Some(max_memory_mb) = request
+            .limits
+            .as_ref()
+            .and_then(|limits| limits.max_memory_mb)
+        {
+            let requested = max_memory_mb.saturating_mul(1024 * 1024);
+            limits.memory_bytes = Some(
+                limits
+                    .memory_bytes
+                    .map_or(requested, |configured| configured.min(requested)),
+            );
+        }
+        limits
+    }
+
     fn effective_timeout_duration(request: &PythonExecutionRequest) -> Duration {
         let timeout_ms_from_seconds = request.timeout_seconds.saturating_mul(1000);
         let timeout_ms = request
@@ -113,44 +174,111 @@ impl PythonExecutionBackend for ProcessPythonBackend {
     ) -> std::result::Result<PythonExecutionResult, String> {
         Self::validate_limits(&request)?;
 
-        let executable = self.resolve_python_executable();
+        let resource_limits = self.effective_resource_limits(&request);
+        let invocation = self.container.as_ref().map(|container| {
+            container.invocation(None, &resource_limits, &["python3", "-c", &request.code])
+        });
+        let (executable, args, runtime) = match (&self.container, &invocation) {
+            (Some(container), Some(invocation)) => (
+                invocation.program.clone(),
+                invocation.args.clone(),
+                format!("{}:python3", container.engine.program()),
+            ),
+            _ => {
+                let (executable, args) = self.host_command(&request.code)?;
+                (executable, args, request.runtime.as_str().to_string())
+            }
+        };
         let mut cmd = Command::new(&executable);
-        cmd.arg("-c")
-            .arg(&request.code)
-            .kill_on_drop(true)
+        cmd.args(&args);
+        cmd.kill_on_drop(true)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        // Containers enforce the limits themselves.
+        let limits_guard = if resource_limits.is_unlimited() || invocation.is_some() {
+            None
+        } else {
+            let guard = resource_limits
+                .prepare()
+                .map_err(|err| format!("failed to apply resource limits: {}", err))?;
+            Some(Arc::new(guard))
+        };
+        #[cfg(unix)]
+        if let Some(guard) = limits_guard.clone() {
+            unsafe {
+                cmd.pre_exec(move || guard.pre_exec());
+            }
+        }
+        #[cfg(all(unix, feature = "sandbox"))]
+        if let Some(ref policy) = self.sandbox_policy
+            && invocation.is_none()
+        {
+            let policy = policy.clone();
+            unsafe {
+                cmd.pre_exec(move || {
+                    restflow_sandbox::pre_exec_hook(&policy)
+                        .map_err(|err| std::io::Error::other(err.to_string()))
+                });
+            }
+        }
+
         let timeout_duration = Self::effective_timeout_duration(&request);
-        let execution = timeout(timeout_duration, cmd.output()).await;
+        let execution = timeout(timeout_duration, async {
+            let child = cmd.spawn()?;
+            #[cfg(windows)]
+            if let Some(guard) = limits_guard.as_ref()
+                && let Some(handle) = child.raw_handle()
+            {
+                guard
+                    .attach(handle)
+                    .map_err(|err| std::io::Error::other(err.to_string()))?;
+            }
+            child.wait_with_output().await
+        })
+        .await;
         match execution {
             Ok(Ok(output)) => {
                 let exit_code = output.status.code().unwrap_or(-1);
+                let resource_limit = match &invocation {
+                    Some(_) => container::limit_violation(&resource_limits, &output.status),
+                    None => limits_guard
+                        .as_ref()
+                        .and_then(|guard| guard.violation(&output.status)),
+                }
+                .map(|violation| violation.to_string());
                 Ok(PythonExecutionResult {
                     stdout: String::from_utf8_lossy(&output.stdout).to_string(),
                     stderr: String::from_utf8_lossy(&output.stderr).to_string(),
                     exit_code,
-                    runtime: request.runtime.as_str().to_string(),
+                    runtime,
                     timed_out: false,
                     limits: request.limits,
+                    resource_limit,
                 })
             }
             Ok(Err(err)) => Err(format!(
-                "monty runtime execution failed ({}): {}",
-                executable, err
+                "{} runtime execution failed ({}): {}",
+                runtime, executable, err
             )),
-            Err(_) => Ok(PythonExecutionResult {
-                stdout: String::new(),
-                stderr: format!(
-                    "Python execution timed out after {} ms",
-                    timeout_duration.as_millis()
-                ),
-                exit_code: 124,
-                runtime: request.runtime.as_str().to_string(),
-                timed_out: true,
-                limits: request.limits,
-            }),
+            Err(_) => {
+                if let (Some(container), Some(invocation)) = (&self.container, &invocation) {
+                    container.kill(&invocation.name).await;
+                }
+                Ok(PythonExecutionResult {
+                    stdout: String::new(),
+                    stderr: format!(
+                        "Python execution timed out after {} ms",
+                        timeout_duration.as_millis()
+                    ),
+                    exit_code: 124,
+                    runtime,
+                    timed_out: true,
+                    limits: request.limits,
+                    resource_limit: None,
+                })
+            }
         }
     }
 }
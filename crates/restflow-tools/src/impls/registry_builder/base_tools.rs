@@ -3,19 +3,27 @@ use std::sync::Arc;
 
 use crate::impls::batch::BatchTool;
 use crate::impls::browser::BrowserTool;
+use crate::impls::calendar::CalendarTool;
+use crate::impls::code_host::GitHubTool;
 use crate::impls::edit::EditTool;
+use crate::impls::git::GitTool;
 use crate::impls::glob_tool::GlobTool;
 use crate::impls::grep_tool::GrepTool;
 use crate::impls::jina_reader::JinaReaderTool;
 use crate::impls::monty_python::{PythonTool, RunPythonTool};
 use crate::impls::multiedit::MultiEditTool;
 use crate::impls::patch::PatchTool;
+use crate::impls::python_backend::PythonExecutionBackend;
+use crate::impls::speak::SpeakTool;
+use crate::impls::spreadsheet::SpreadsheetTool;
+use crate::impls::sql::SqlTool;
 use crate::impls::transcribe::{TranscribeConfig, TranscribeTool};
 use crate::impls::vision::VisionTool;
 use crate::impls::web_fetch::WebFetchTool;
 use crate::impls::web_search::WebSearchTool;
 use crate::impls::{DiscordTool, EmailTool, HttpTool, SlackTool, TelegramTool};
 use crate::{SecretResolver, ToolRegistry};
+use restflow_browser::{ArtifactRetentionPolicy, BrowserService, NavigationPolicy};
 use restflow_traits::store::DiagnosticsProvider;
 
 use super::ToolRegistryBuilder;
@@ -58,12 +66,55 @@ impl ToolRegistryBuilder {
         Ok(self)
     }
 
+    pub fn with_github(mut self) -> std::result::Result<Self, reqwest::Error> {
+        self.registry.register(GitHubTool::new()?);
+        Ok(self)
+    }
+
+    pub fn with_github_with_resolver(
+        mut self,
+        resolver: SecretResolver,
+    ) -> std::result::Result<Self, reqwest::Error> {
+        self.registry
+            .register(GitHubTool::new()?.with_secret_resolver(resolver));
+        Ok(self)
+    }
+
+    /// Register the SQL tool in read-only mode.
+    pub fn with_sql_with_resolver(mut self, resolver: SecretResolver) -> Self {
+        self.registry
+            .register(SqlTool::new().with_secret_resolver(resolver));
+        self
+    }
+
+    pub fn with_calendar(mut self) -> std::result::Result<Self, reqwest::Error> {
+        self.registry.register(CalendarTool::new()?);
+        Ok(self)
+    }
+
+    pub fn with_calendar_with_resolver(
+        mut self,
+        resolver: SecretResolver,
+    ) -> std::result::Result<Self, reqwest::Error> {
+        self.registry
+            .register(CalendarTool::new()?.with_secret_resolver(resolver));
+        Ok(self)
+    }
+
     pub fn with_python(mut self) -> Self {
         self.registry.register(RunPythonTool::new());
         self.registry.register(PythonTool::new());
         self
     }
 
+    pub fn with_python_backend(mut self, backend: Arc<dyn PythonExecutionBackend>) -> Self {
+        self.registry
+            .register(RunPythonTool::new().with_backend(backend.clone()));
+        self.registry
+            .register(PythonTool::new().with_backend(backend));
+        self
+    }
+
     pub fn with_browser(mut self) -> anyhow::Result<Self> {
         self.registry.register(BrowserTool::new()?);
         Ok(self)
@@ -75,6 +126,46 @@ impl ToolRegistryBuilder {
         Ok(self)
     }
 
+    pub fn with_browser_policy(
+        mut self,
+        timeout_secs: u64,
+        navigation: NavigationPolicy,
+        retention: ArtifactRetentionPolicy,
+    ) -> anyhow::Result<Self> {
+        self.registry.register(BrowserTool::new_with_policy(
+            timeout_secs,
+            navigation,
+            retention,
+        )?);
+        Ok(self)
+    }
+
+    /// Register the browser tool on a shared `service`, so other tools
+    /// (e.g. `use_plan`) can run in the same browser sessions.
+    pub fn with_browser_service(mut self, service: Arc<BrowserService>, timeout_secs: u64) -> Self {
+        self.registry
+            .register(BrowserTool::with_service(service).with_default_timeout(timeout_secs));
+        self
+    }
+
+    /// Register the browser tool on `service` with the vision tool attached,
+    /// so `run_actions` calls can set `vision_fallback` to locate elements
+    /// whose selectors no longer match.
+    pub fn with_browser_vision_fallback(
+        mut self,
+        service: Arc<BrowserService>,
+        timeout_secs: u64,
+        resolver: SecretResolver,
+    ) -> std::result::Result<Self, reqwest::Error> {
+        let vision = Arc::new(VisionTool::new(resolver)?);
+        self.registry.register(
+            BrowserTool::with_service(service)
+                .with_default_timeout(timeout_secs)
+                .with_vision_fallback(vision),
+        );
+        Ok(self)
+    }
+
     pub fn with_transcribe(
         mut self,
         resolver: SecretResolver,
@@ -93,6 +184,11 @@ impl ToolRegistryBuilder {
         Ok(self)
     }
 
+    pub fn with_speak(mut self, resolver: SecretResolver) -> Self {
+        self.registry.register(SpeakTool::new(resolver));
+        self
+    }
+
     pub fn with_vision(
         mut self,
         resolver: SecretResolver,
@@ -226,6 +322,24 @@ impl ToolRegistryBuilder {
         self
     }
 
+    pub fn with_git_and_base_dir(mut self, base_dir: Option<PathBuf>) -> Self {
+        let mut tool = GitTool::new().require_base_dir();
+        if let Some(base_dir) = base_dir {
+            tool = tool.with_base_dir(base_dir);
+        }
+        self.registry.register(tool);
+        self
+    }
+
+    pub fn with_spreadsheet_and_base_dir(mut self, base_dir: Option<PathBuf>) -> Self {
+        let mut tool = SpreadsheetTool::new().require_base_dir();
+        if let Some(base_dir) = base_dir {
+            tool = tool.with_base_dir(base_dir);
+        }
+        self.registry.register(tool);
+        self
+    }
+
     pub fn with_glob(mut self) -> Self {
         self.registry.register(GlobTool::new());
         self
@@ -1,10 +1,12 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::impls::container::ContainerConfig;
 use crate::impls::file_tracker::FileTracker;
 use crate::impls::secrets::SecretGetPolicy;
 use crate::impls::{BashTool, FileTool};
 use crate::security::bash_security::BashSecurityConfig;
+use restflow_sandbox::ResourceLimits;
 
 /// Configuration for bash tool security.
 #[derive(Debug, Clone)]
@@ -19,6 +21,14 @@ pub struct BashConfig {
     pub allow_sudo: bool,
     /// Maximum total bytes for stdout/stderr output payload.
     pub max_output_bytes: usize,
+    /// Memory, CPU share and process caps for spawned commands.
+    pub resource_limits: ResourceLimits,
+    /// Run commands in an ephemeral container instead of on the host.
+    pub container: Option<ContainerConfig>,
+    /// Outbound network access of commands run on the host. `None` leaves
+    /// it unrestricted.
+    #[cfg(feature = "sandbox")]
+    pub network: Option<restflow_sandbox::NetworkPolicy>,
 }
 
 impl Default for BashConfig {
@@ -30,19 +40,42 @@ impl Default for BashConfig {
             blocked_commands: security.blocked_commands,
             allow_sudo: security.allow_sudo,
             max_output_bytes: 1_000_000,
+            resource_limits: ResourceLimits::default(),
+            container: None,
+            #[cfg(feature = "sandbox")]
+            network: None,
         }
     }
 }
 
 impl BashConfig {
+    /// Whether resource limits, a container or a network policy confine
+    /// spawned commands. Tools that open unconfined shells, such as
+    /// `shell_session`, should not be offered next to a confined `bash`.
+    pub fn is_sandboxed(&self) -> bool {
+        let sandboxed =
+            self.resource_limits != ResourceLimits::default() || self.container.is_some();
+        #[cfg(feature = "sandbox")]
+        let sandboxed = sandboxed || self.network.is_some();
+        sandboxed
+    }
+
     /// Convert into a [`BashTool`].
     pub fn into_bash_tool(self) -> BashTool {
         let mut tool = BashTool::new()
             .with_timeout(self.timeout_secs)
-            .with_max_output(self.max_output_bytes);
+            .with_max_output(self.max_output_bytes)
+            .with_resource_limits(self.resource_limits);
         if let Some(workdir) = self.working_dir {
             tool = tool.with_workdir(workdir);
         }
+        if let Some(container) = self.container {
+            tool = tool.with_container(container);
+        }
+        #[cfg(feature = "sandbox")]
+        if let Some(network) = self.network {
+            tool = tool.with_sandbox_policy(restflow_sandbox::SandboxPolicy::network_only(network));
+        }
         tool
     }
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::impls::fanout::FanoutTool;
 use crate::impls::list_subagents::ListSubagentsTool;
 use crate::impls::spawn::SpawnTool;
 use crate::impls::spawn_subagent::SpawnSubagentTool;
@@ -44,6 +45,11 @@ impl ToolRegistryBuilder {
         self
     }
 
+    pub fn with_fanout(mut self, manager: Arc<dyn SubagentManager>) -> Self {
+        self.registry.register(FanoutTool::new(manager));
+        self
+    }
+
     pub fn with_use_skill(mut self, provider: Arc<dyn SkillProvider>) -> Self {
         self.registry.register(UseSkillTool::new(provider));
         self
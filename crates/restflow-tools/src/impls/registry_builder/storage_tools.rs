@@ -5,6 +5,7 @@ use crate::impls::agent_crud::AgentCrudTool;
 use crate::impls::auth_profile::AuthProfileTool;
 use crate::impls::background_agent::BackgroundAgentTool;
 use crate::impls::config::ConfigTool;
+use crate::impls::deliverable_builder::DeliverableBuilderTool;
 use crate::impls::diagnostics::DiagnosticsTool;
 use crate::impls::kv_store::KvStoreTool;
 use crate::impls::manage_ops::ManageOpsTool;
@@ -22,14 +23,18 @@ use crate::impls::skill::SkillTool;
 use crate::impls::terminal::TerminalTool;
 use crate::impls::trigger::TriggerTool;
 use crate::impls::unified_memory_search::UnifiedMemorySearchTool;
+use crate::impls::usage_budget::UsageBudgetTool;
+use crate::impls::use_plan::UsePlanTool;
 use crate::impls::work_item::WorkItemTool;
 use crate::security::SecurityGate;
+use restflow_browser::BrowserService;
 use restflow_traits::AgentOperationAssessor;
 use restflow_traits::skill::SkillProvider;
 use restflow_traits::store::{
-    AgentStore, AuthProfileStore, ConfigStore, DeliverableStore, DiagnosticsProvider, KvStore,
-    MarketplaceStore, MemoryManager, MemoryStore, OpsProvider, SecretStore, SecurityQueryProvider,
-    SessionStore, TaskStore, TerminalStore, TriggerStore, UnifiedMemorySearch, WorkItemProvider,
+    AgentStore, AuthProfileStore, BrowserPlanStore, ConfigStore, DeliverableStore,
+    DiagnosticsProvider, KvStore, MarketplaceStore, MemoryManager, MemoryStore, OpsProvider,
+    SecretStore, SecurityQueryProvider, SessionStore, TaskStore, TerminalStore, TriggerStore,
+    UnifiedMemorySearch, UsageStore, WorkItemProvider,
 };
 use restflow_traits::TeamCoordinator;
 
@@ -117,6 +122,11 @@ impl ToolRegistryBuilder {
         self
     }
 
+    pub fn with_deliverable_builder(mut self, store: Arc<dyn DeliverableStore>) -> Self {
+        self.registry.register(DeliverableBuilderTool::new(store));
+        self
+    }
+
     pub fn with_unified_search(mut self, search: Arc<dyn UnifiedMemorySearch>) -> Self {
         self.registry.register(UnifiedMemorySearchTool::new(search));
         self
@@ -148,6 +158,15 @@ impl ToolRegistryBuilder {
         self
     }
 
+    pub fn with_usage_budget(mut self, store: Arc<dyn UsageStore>, agent_id: Option<&str>) -> Self {
+        let mut tool = UsageBudgetTool::new(store);
+        if let Some(agent_id) = agent_id {
+            tool = tool.with_default_agent(agent_id);
+        }
+        self.registry.register(tool);
+        self
+    }
+
     pub fn with_auth_profile(mut self, store: Arc<dyn AuthProfileStore>) -> Self {
         self.registry
             .register(AuthProfileTool::new(store).with_write(true));
@@ -262,6 +281,17 @@ impl ToolRegistryBuilder {
         self
     }
 
+    pub fn with_browser_plans(
+        mut self,
+        store: Arc<dyn BrowserPlanStore>,
+        service: Arc<BrowserService>,
+        timeout_secs: u64,
+    ) -> Self {
+        self.registry
+            .register(UsePlanTool::new(store, service).with_default_timeout(timeout_secs));
+        self
+    }
+
     pub fn with_terminal(mut self, store: Arc<dyn TerminalStore>) -> Self {
         self.registry.register(TerminalTool::new(store));
         self
@@ -7,3 +7,24 @@ fn test_build_with_batch_registers_batch_and_preserves_tools() {
     assert!(registry.has("python"));
     assert!(registry.has("run_python"));
 }
+
+#[test]
+fn test_bash_config_reports_sandbox_settings() {
+    assert!(!BashConfig::default().is_sandboxed());
+    let limited = BashConfig {
+        resource_limits: restflow_sandbox::ResourceLimits {
+            max_processes: Some(16),
+            ..Default::default()
+        },
+        ..BashConfig::default()
+    };
+    assert!(limited.is_sandboxed());
+    #[cfg(feature = "sandbox")]
+    assert!(
+        BashConfig {
+            network: Some(restflow_sandbox::NetworkPolicy::Deny),
+            ..BashConfig::default()
+        }
+        .is_sandboxed()
+    );
+}
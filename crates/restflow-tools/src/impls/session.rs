@@ -89,6 +89,8 @@ enum SessionAction {
         include_archived: Option<bool>,
         #[serde(default)]
         limit: Option<u32>,
+        #[serde(default)]
+        offset: Option<u32>,
     },
     Cleanup,
 }
@@ -144,7 +146,7 @@ impl Tool for SessionTool {
                 },
                 "query": {
                     "type": "string",
-                    "description": "Search query (for search)"
+                    "description": "Full-text search over message content (for search); quote words to match a phrase"
                 },
                 "retention": {
                     "type": "string",
@@ -154,6 +156,11 @@ impl Tool for SessionTool {
                     "type": "integer",
                     "description": "Max results (for search)",
                     "minimum": 1
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Results to skip for pagination (for search)",
+                    "minimum": 0
                 }
             },
             "required": ["operation"]
@@ -245,6 +252,7 @@ impl Tool for SessionTool {
                     skill_id,
                     include_archived,
                     limit,
+                    offset,
                 } => {
                     let request = SessionSearchQuery {
                         query,
@@ -252,6 +260,7 @@ impl Tool for SessionTool {
                         skill_id,
                         include_archived,
                         limit,
+                        offset,
                     };
                     ToolOutput::success(
                         self.store.search_sessions(request).map_err(|e| {
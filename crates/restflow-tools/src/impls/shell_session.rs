@@ -0,0 +1,514 @@
+//! Interactive shell session tool for AI agents.
+//!
+//! Unlike `bash`, which runs one command and returns, a shell session is a
+//! long-lived shell on a PTY. The agent sends keystrokes and reads the output
+//! produced since its last read, which makes REPLs and interactive installers
+//! usable. Sessions live in the shared [`ProcessManager`], so they also show
+//! up in the `process` tool.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::Result;
+use crate::check_security;
+use crate::security::SecurityGate;
+use crate::{Tool, ToolAction, ToolOutput};
+use restflow_traits::store::{ProcessManager, ProcessPollResult};
+
+/// Time to wait for output after opening a shell or sending input.
+const DEFAULT_WAIT_MS: u64 = 1_000;
+const MAX_WAIT_MS: u64 = 30_000;
+/// Output is considered complete once the shell stays quiet this long.
+const OUTPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+fn missing_session_message(session_id: &str) -> String {
+    format!(
+        "Shell session '{}' not found. Use action 'open' to start a new shell.",
+        session_id
+    )
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ShellSessionAction {
+    Open {
+        cwd: Option<String>,
+        wait_ms: Option<u64>,
+    },
+    Send {
+        session_id: String,
+        #[serde(default)]
+        input: Option<String>,
+        #[serde(default)]
+        keys: Vec<String>,
+        wait_ms: Option<u64>,
+    },
+    Read {
+        session_id: String,
+        wait_ms: Option<u64>,
+    },
+    Close {
+        session_id: String,
+    },
+}
+
+/// Bytes a terminal sends for a named key such as `enter` or `ctrl-c`.
+fn key_sequence(key: &str) -> Option<String> {
+    let key = key.trim().to_ascii_lowercase();
+    let sequence = match key.as_str() {
+        "enter" | "return" => "\r",
+        "tab" => "\t",
+        "backspace" => "\x7f",
+        "escape" | "esc" => "\x1b",
+        "space" => " ",
+        "up" => "\x1b[A",
+        "down" => "\x1b[B",
+        "right" => "\x1b[C",
+        "left" => "\x1b[D",
+        "home" => "\x1b[H",
+        "end" => "\x1b[F",
+        "delete" => "\x1b[3~",
+        _ => {
+            let letter = key
+                .strip_prefix("ctrl-")
+                .or_else(|| key.strip_prefix("ctrl+"))?;
+            let mut chars = letter.chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
+                return None;
+            };
+            if !ch.is_ascii_lowercase() {
+                return None;
+            }
+            return Some(((ch as u8 & 0x1f) as char).to_string());
+        }
+    };
+    Some(sequence.to_string())
+}
+
+/// Interactive PTY shell tool
+pub struct ShellSessionTool {
+    manager: Arc<dyn ProcessManager>,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl ShellSessionTool {
+    pub fn new(manager: Arc<dyn ProcessManager>) -> Self {
+        Self {
+            manager,
+            security_gate: None,
+            agent_id: None,
+            task_id: None,
+        }
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    async fn check_action_allowed(
+        &self,
+        operation: &str,
+        target: String,
+        summary: String,
+    ) -> Result<Option<String>> {
+        let action = ToolAction {
+            tool_name: self.name().to_string(),
+            operation: operation.to_string(),
+            target,
+            summary,
+        };
+
+        check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await
+    }
+
+    /// Collect output until the shell goes quiet, exits, or `wait_ms` passes.
+    async fn read_output(
+        &self,
+        session_id: &str,
+        wait_ms: Option<u64>,
+    ) -> anyhow::Result<ProcessPollResult> {
+        let wait = Duration::from_millis(wait_ms.unwrap_or(DEFAULT_WAIT_MS).min(MAX_WAIT_MS));
+        let deadline = Instant::now() + wait;
+        let mut output = String::new();
+        loop {
+            let mut result = self.manager.poll(session_id)?;
+            let received = !result.output.is_empty();
+            output.push_str(&result.output);
+
+            let quiet = !output.is_empty() && !received;
+            if quiet || result.exit_code.is_some() || Instant::now() >= deadline {
+                result.output = output;
+                return Ok(result);
+            }
+            tokio::time::sleep(
+                OUTPUT_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())),
+            )
+            .await;
+        }
+    }
+
+    fn output_result(result: anyhow::Result<ProcessPollResult>, session_id: &str) -> ToolOutput {
+        match result {
+            Ok(result) => ToolOutput::success(json!({
+                "session_id": result.session_id,
+                "output": result.output,
+                "status": result.status,
+                "exit_code": result.exit_code,
+            })),
+            Err(e) if e.to_string().contains("Session not found") => {
+                ToolOutput::error(missing_session_message(session_id))
+            }
+            Err(e) => ToolOutput::error(format!(
+                "Failed to read shell session '{}': {}",
+                session_id, e
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ShellSessionTool {
+    fn name(&self) -> &str {
+        "shell_session"
+    }
+
+    fn description(&self) -> &str {
+        "Drive a persistent interactive shell on a PTY: open a shell, send input and keystrokes, read output produced since the last read, and close it. Use for REPLs and interactive programs; use bash for one-shot commands."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "description": "Action to perform: open, send, read, close",
+                    "enum": ["open", "send", "read", "close"]
+                },
+                "session_id": { "type": "string", "description": "Shell session id returned by open" },
+                "cwd": { "type": "string", "description": "Working directory for open" },
+                "input": {
+                    "type": "string",
+                    "description": "Text to type. Add keys: [\"enter\"] to submit a line"
+                },
+                "keys": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Named keys sent after input: enter, tab, backspace, escape, space, up, down, left, right, home, end, delete, ctrl-<letter>"
+                },
+                "wait_ms": {
+                    "type": "integer",
+                    "description": "Maximum time to wait for output before returning (default 1000, max 30000)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let action: ShellSessionAction = match serde_json::from_value(input) {
+            Ok(action) => action,
+            Err(e) => {
+                return Ok(ToolOutput::error(format!(
+                    "Invalid input: {}. Required: action (open|send|read|close).",
+                    e
+                )));
+            }
+        };
+
+        match action {
+            ShellSessionAction::Open { cwd, wait_ms } => {
+                let target = cwd.clone().unwrap_or_else(|| "shell".to_string());
+                if let Some(message) = self
+                    .check_action_allowed(
+                        "open",
+                        target.clone(),
+                        format!("Open shell in {}", target),
+                    )
+                    .await?
+                {
+                    return Ok(ToolOutput::error(message));
+                }
+
+                match self.manager.spawn_shell(cwd) {
+                    Ok(session_id) => {
+                        let result = self.read_output(&session_id, wait_ms).await;
+                        Ok(Self::output_result(result, &session_id))
+                    }
+                    Err(e) => Ok(ToolOutput::error(format!(
+                        "Failed to open shell: {}. Check that the working directory is valid.",
+                        e
+                    ))),
+                }
+            }
+            ShellSessionAction::Send {
+                session_id,
+                input,
+                keys,
+                wait_ms,
+            } => {
+                let mut data = input.unwrap_or_default();
+                for key in &keys {
+                    match key_sequence(key) {
+                        Some(sequence) => data.push_str(&sequence),
+                        None => {
+                            return Ok(ToolOutput::error(format!(
+                                "Unknown key '{}'. Supported keys: enter, tab, backspace, escape, space, up, down, left, right, home, end, delete, ctrl-<letter>.",
+                                key
+                            )));
+                        }
+                    }
+                }
+                if data.is_empty() {
+                    return Ok(ToolOutput::error(
+                        "Nothing to send. Provide input, keys, or both.",
+                    ));
+                }
+
+                if let Some(message) = self
+                    .check_action_allowed(
+                        "send",
+                        session_id.clone(),
+                        format!(
+                            "Send input to shell session {}: {}",
+                            session_id,
+                            data.trim()
+                        ),
+                    )
+                    .await?
+                {
+                    return Ok(ToolOutput::error(message));
+                }
+
+                if let Err(e) = self.manager.write(&session_id, &data) {
+                    if e.to_string().contains("Session not found") {
+                        return Ok(ToolOutput::error(missing_session_message(&session_id)));
+                    }
+                    return Ok(ToolOutput::error(format!(
+                        "Failed to send input to shell session '{}': {}",
+                        session_id, e
+                    )));
+                }
+                let result = self.read_output(&session_id, wait_ms).await;
+                Ok(Self::output_result(result, &session_id))
+            }
+            ShellSessionAction::Read {
+                session_id,
+                wait_ms,
+            } => {
+                if let Some(message) = self
+                    .check_action_allowed(
+                        "read",
+                        session_id.clone(),
+                        format!("Read output of shell session {}", session_id),
+                    )
+                    .await?
+                {
+                    return Ok(ToolOutput::error(message));
+                }
+
+                let result = self.read_output(&session_id, wait_ms).await;
+                Ok(Self::output_result(result, &session_id))
+            }
+            ShellSessionAction::Close { session_id } => {
+                if let Some(message) = self
+                    .check_action_allowed(
+                        "close",
+                        session_id.clone(),
+                        format!("Close shell session {}", session_id),
+                    )
+                    .await?
+                {
+                    return Ok(ToolOutput::error(message));
+                }
+
+                match self.manager.kill(&session_id) {
+                    Ok(()) => Ok(ToolOutput::success(
+                        json!({"session_id": session_id, "closed": true}),
+                    )),
+                    Err(e) => {
+                        if e.to_string().contains("Session not found") {
+                            return Ok(ToolOutput::error(missing_session_message(&session_id)));
+                        }
+                        Ok(ToolOutput::error(format!(
+                            "Failed to close shell session '{}': {}",
+                            session_id, e
+                        )))
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use restflow_traits::store::{ProcessLog, ProcessSessionInfo};
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockShellManager {
+        written: Mutex<Vec<String>>,
+        chunks: Mutex<VecDeque<String>>,
+        killed: Mutex<Vec<String>>,
+    }
+
+    impl MockShellManager {
+        fn with_chunks(chunks: &[&str]) -> Self {
+            Self {
+                chunks: Mutex::new(chunks.iter().map(|chunk| chunk.to_string()).collect()),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl ProcessManager for MockShellManager {
+        fn spawn(&self, _command: String, _cwd: Option<String>) -> anyhow::Result<String> {
+            anyhow::bail!("not used")
+        }
+
+        fn poll(&self, session_id: &str) -> anyhow::Result<ProcessPollResult> {
+            if session_id != "shell-1" {
+                anyhow::bail!("Session not found: {}", session_id);
+            }
+            let output = self.chunks.lock().unwrap().pop_front().unwrap_or_default();
+            Ok(ProcessPollResult {
+                session_id: session_id.to_string(),
+                output,
+                status: "running".to_string(),
+                exit_code: None,
+            })
+        }
+
+        fn write(&self, session_id: &str, data: &str) -> anyhow::Result<()> {
+            if session_id != "shell-1" {
+                anyhow::bail!("Session not found: {}", session_id);
+            }
+            self.written.lock().unwrap().push(data.to_string());
+            Ok(())
+        }
+
+        fn kill(&self, session_id: &str) -> anyhow::Result<()> {
+            self.killed.lock().unwrap().push(session_id.to_string());
+            Ok(())
+        }
+
+        fn list(&self) -> anyhow::Result<Vec<ProcessSessionInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn log(
+            &self,
+            _session_id: &str,
+            _offset: usize,
+            _limit: usize,
+        ) -> anyhow::Result<ProcessLog> {
+            anyhow::bail!("not used")
+        }
+
+        fn spawn_shell(&self, _cwd: Option<String>) -> anyhow::Result<String> {
+            Ok("shell-1".to_string())
+        }
+    }
+
+    #[test]
+    fn test_key_sequences() {
+        assert_eq!(key_sequence("Enter").as_deref(), Some("\r"));
+        assert_eq!(key_sequence("ctrl-c").as_deref(), Some("\x03"));
+        assert_eq!(key_sequence("ctrl+d").as_deref(), Some("\x04"));
+        assert_eq!(key_sequence("up").as_deref(), Some("\x1b[A"));
+        assert_eq!(key_sequence("ctrl-1"), None);
+        assert_eq!(key_sequence("hyper"), None);
+    }
+
+    #[tokio::test]
+    async fn test_send_writes_input_and_keys_then_collects_output() {
+        let manager = Arc::new(MockShellManager::with_chunks(&[
+            "$ ", "", ">>> 2\n", ">>> ", "",
+        ]));
+        let tool = ShellSessionTool::new(manager.clone());
+
+        let opened = tool.execute(json!({"action": "open"})).await.unwrap();
+        assert!(opened.success);
+        assert_eq!(opened.result["session_id"], "shell-1");
+        assert_eq!(opened.result["output"], "$ ");
+
+        let sent = tool
+            .execute(json!({
+                "action": "send",
+                "session_id": "shell-1",
+                "input": "1 + 1",
+                "keys": ["enter"]
+            }))
+            .await
+            .unwrap();
+        assert!(sent.success);
+        assert_eq!(sent.result["output"], ">>> 2\n>>> ");
+        assert_eq!(
+            *manager.written.lock().unwrap(),
+            vec!["1 + 1\r".to_string()]
+        );
+
+        let closed = tool
+            .execute(json!({"action": "close", "session_id": "shell-1"}))
+            .await
+            .unwrap();
+        assert!(closed.success);
+        assert_eq!(*manager.killed.lock().unwrap(), vec!["shell-1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_read_returns_empty_output_after_wait() {
+        let tool = ShellSessionTool::new(Arc::new(MockShellManager::default()));
+        let read = tool
+            .execute(json!({"action": "read", "session_id": "shell-1", "wait_ms": 0}))
+            .await
+            .unwrap();
+        assert!(read.success);
+        assert_eq!(read.result["output"], "");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_keys_and_missing_sessions() {
+        let tool = ShellSessionTool::new(Arc::new(MockShellManager::default()));
+
+        let unknown = tool
+            .execute(json!({"action": "send", "session_id": "shell-1", "keys": ["hyper"]}))
+            .await
+            .unwrap();
+        assert!(!unknown.success);
+        assert!(unknown.error.unwrap().contains("Unknown key 'hyper'"));
+
+        let missing = tool
+            .execute(json!({"action": "send", "session_id": "shell-9", "input": "ls"}))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+        assert_eq!(
+            missing.error.as_deref(),
+            Some("Shell session 'shell-9' not found. Use action 'open' to start a new shell.")
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Text-to-speech tool using OpenAI speech models.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::PathBuf;
+
+use crate::Result;
+use crate::audio::speech::{
+    MAX_SPEECH_INPUT_CHARS, SpeechFormat, SpeechOptions, synthesize_speech,
+};
+use crate::impls::transcribe::ensure_default_media_dir;
+use crate::{SecretResolver, Tool, ToolError, ToolOutput};
+
+#[derive(Debug, Deserialize)]
+struct SpeakInput {
+    text: String,
+    voice: Option<String>,
+    model: Option<String>,
+    format: Option<String>,
+    instructions: Option<String>,
+}
+
+/// Tool for turning text into an audio file with OpenAI speech models.
+///
+/// Audio is written to the RestFlow media directory (or `output_dir` when
+/// set), where channel adapters and the `transcribe` tool can pick it up.
+pub struct SpeakTool {
+    secret_resolver: SecretResolver,
+    output_dir: Option<PathBuf>,
+}
+
+impl SpeakTool {
+    pub fn new(secret_resolver: SecretResolver) -> Self {
+        Self {
+            secret_resolver,
+            output_dir: None,
+        }
+    }
+
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(output_dir.into());
+        self
+    }
+
+    fn resolve_api_key(&self) -> Option<String> {
+        (self.secret_resolver)("OPENAI_API_KEY")
+    }
+
+    fn resolve_output_dir(&self) -> Result<PathBuf> {
+        if let Some(dir) = &self.output_dir {
+            std::fs::create_dir_all(dir)?;
+            return Ok(dir.clone());
+        }
+        ensure_default_media_dir().ok_or_else(|| {
+            ToolError::Tool("Unable to resolve the RestFlow media directory.".to_string())
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for SpeakTool {
+    fn name(&self) -> &str {
+        "speak"
+    }
+
+    fn description(&self) -> &str {
+        "Convert text to speech with OpenAI speech models and save it as a local audio file."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": format!("Text to speak (at most {MAX_SPEECH_INPUT_CHARS} characters).")
+                },
+                "voice": {
+                    "type": "string",
+                    "description": "Optional voice name (e.g., 'alloy', 'nova', 'onyx'). Defaults to alloy."
+                },
+                "model": {
+                    "type": "string",
+                    "description": "Optional model name. Defaults to gpt-4o-mini-tts."
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["opus", "mp3", "aac", "flac", "wav"],
+                    "description": "Audio format. Defaults to opus (Ogg), which chat apps play as a voice note."
+                },
+                "instructions": {
+                    "type": "string",
+                    "description": "Optional delivery instructions such as tone or pacing."
+                }
+            },
+            "required": ["text"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let params: SpeakInput = serde_json::from_value(input)?;
+
+        let format = match params.format.as_deref() {
+            Some(value) => match SpeechFormat::parse(value) {
+                Some(format) => format,
+                None => {
+                    return Ok(ToolOutput::error(format!(
+                        "Unsupported audio format '{}'. Use opus, mp3, aac, flac or wav.",
+                        value
+                    )));
+                }
+            },
+            None => SpeechFormat::Opus,
+        };
+        if params.text.trim().is_empty() {
+            return Ok(ToolOutput::error("'text' must not be empty."));
+        }
+
+        let api_key = self.resolve_api_key().ok_or_else(|| {
+            ToolError::Tool(
+                "Missing OPENAI_API_KEY. Set it via manage_secrets tool with {operation: 'set', key: 'OPENAI_API_KEY', value: '...'}.".to_string(),
+            )
+        })?;
+
+        let mut options = SpeechOptions {
+            format,
+            instructions: params.instructions,
+            ..SpeechOptions::default()
+        };
+        if let Some(voice) = params.voice.filter(|value| !value.trim().is_empty()) {
+            options.voice = voice;
+        }
+        if let Some(model) = params.model.filter(|value| !value.trim().is_empty()) {
+            options.model = model;
+        }
+
+        let audio = synthesize_speech(&api_key, &params.text, &options)
+            .await
+            .map_err(|error| ToolError::Tool(error.to_string()))?;
+
+        let file_path = self.resolve_output_dir()?.join(format!(
+            "speech-{}.{}",
+            uuid::Uuid::new_v4(),
+            format.extension()
+        ));
+        tokio::fs::write(&file_path, &audio).await?;
+
+        Ok(ToolOutput::success(json!({
+            "file_path": file_path.to_string_lossy(),
+            "format": format.extension(),
+            "voice": options.voice,
+            "model": options.model,
+            "bytes": audio.len(),
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_speak_schema() {
+        let resolver: SecretResolver = Arc::new(|_| None);
+        let tool = SpeakTool::new(resolver);
+        let schema = tool.parameters_schema();
+        assert_eq!(tool.name(), "speak");
+        assert_eq!(schema["required"], json!(["text"]));
+        assert!(schema["properties"].get("voice").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_speak_rejects_unknown_format() {
+        let resolver: SecretResolver = Arc::new(|_| Some("key".to_string()));
+        let tool = SpeakTool::new(resolver);
+        let output = tool
+            .execute(json!({ "text": "hello", "format": "midi" }))
+            .await
+            .unwrap();
+        assert!(!output.success);
+        assert!(output.error.unwrap().contains("Unsupported audio format"));
+    }
+
+    #[tokio::test]
+    async fn test_speak_requires_api_key() {
+        let resolver: SecretResolver = Arc::new(|_| None);
+        let tool = SpeakTool::new(resolver);
+        let err = tool.execute(json!({ "text": "hello" })).await.unwrap_err();
+        assert!(err.to_string().contains("OPENAI_API_KEY"));
+    }
+}
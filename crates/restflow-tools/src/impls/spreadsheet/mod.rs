@@ -0,0 +1,401 @@
+//! Spreadsheet tool for AI agents.
+//!
+//! Reads CSV, TSV and workbook files (XLSX, XLS, ODS via calamine), filters
+//! and aggregates rows, and writes CSV/TSV or XLSX (via rust_xlsxwriter).
+//! Results are returned as column names plus row arrays, so agents do not
+//! need ad-hoc Python scripts for routine spreadsheet work.
+
+mod table;
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::Result;
+use crate::check_security;
+use crate::security::SecurityGate;
+use crate::{Tool, ToolAction, ToolOutput};
+
+use self::table::{Aggregation, Filter, Table};
+
+const DEFAULT_ROW_LIMIT: usize = 100;
+const MAX_ROW_LIMIT: usize = 1_000;
+
+fn default_has_header() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum SpreadsheetOperation {
+    ListSheets {
+        path: String,
+    },
+    Read {
+        path: String,
+        sheet: Option<String>,
+        #[serde(default = "default_has_header")]
+        has_header: bool,
+        #[serde(default)]
+        filters: Vec<Filter>,
+        columns: Option<Vec<String>>,
+        #[serde(default)]
+        offset: usize,
+        limit: Option<usize>,
+    },
+    Aggregate {
+        path: String,
+        sheet: Option<String>,
+        #[serde(default = "default_has_header")]
+        has_header: bool,
+        #[serde(default)]
+        filters: Vec<Filter>,
+        #[serde(default)]
+        group_by: Vec<String>,
+        aggregations: Vec<Aggregation>,
+    },
+    Write {
+        path: String,
+        sheet: Option<String>,
+        columns: Vec<String>,
+        rows: Vec<Value>,
+    },
+}
+
+/// Convert input rows (arrays, or objects keyed by column) into table rows.
+fn rows_from_input(
+    columns: &[String],
+    rows: Vec<Value>,
+) -> std::result::Result<Vec<Vec<Value>>, String> {
+    rows.into_iter()
+        .enumerate()
+        .map(|(index, row)| match row {
+            Value::Array(mut cells) => {
+                if cells.len() > columns.len() {
+                    return Err(format!(
+                        "Row {} has {} cells but only {} columns are defined.",
+                        index,
+                        cells.len(),
+                        columns.len()
+                    ));
+                }
+                cells.resize(columns.len(), Value::Null);
+                Ok(cells)
+            }
+            Value::Object(mut fields) => Ok(columns
+                .iter()
+                .map(|column| fields.remove(column).unwrap_or(Value::Null))
+                .collect()),
+            _ => Err(format!(
+                "Row {} must be an array of cells or an object keyed by column.",
+                index
+            )),
+        })
+        .collect()
+}
+
+async fn blocking<T: Send + 'static>(
+    task: impl FnOnce() -> std::result::Result<T, String> + Send + 'static,
+) -> std::result::Result<T, String> {
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| format!("Spreadsheet task failed: {}", e))?
+}
+
+/// Spreadsheet tool for CSV/TSV and XLSX files.
+pub struct SpreadsheetTool {
+    base_dir: Option<PathBuf>,
+    require_base_dir: bool,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl Default for SpreadsheetTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpreadsheetTool {
+    pub fn new() -> Self {
+        Self {
+            base_dir: None,
+            require_base_dir: false,
+            security_gate: None,
+            agent_id: None,
+            task_id: None,
+        }
+    }
+
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    pub fn require_base_dir(mut self) -> Self {
+        self.require_base_dir = true;
+        self
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    fn resolve_path(&self, path: &str) -> std::result::Result<PathBuf, String> {
+        crate::impls::path_utils::resolve_path_with_policy(
+            path,
+            self.base_dir.as_deref(),
+            self.require_base_dir,
+        )
+    }
+
+    async fn check_action_allowed(&self, path: &Path, summary: String) -> Result<Option<String>> {
+        let action = ToolAction {
+            tool_name: self.name().to_string(),
+            operation: "write".to_string(),
+            target: path.display().to_string(),
+            summary,
+        };
+
+        check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await
+    }
+
+    async fn load_filtered(
+        &self,
+        path: &str,
+        sheet: Option<String>,
+        has_header: bool,
+        filters: Vec<Filter>,
+    ) -> std::result::Result<(PathBuf, Table, Option<String>), String> {
+        let path = self.resolve_path(path)?;
+        let file = path.clone();
+        let (table, sheet) = blocking(move || {
+            let (table, sheet) = table::load(&file, sheet.as_deref(), has_header)?;
+            Ok((table::apply_filters(table, &filters)?, sheet))
+        })
+        .await?;
+        Ok((path, table, sheet))
+    }
+
+    async fn run(
+        &self,
+        operation: SpreadsheetOperation,
+    ) -> Result<std::result::Result<Value, String>> {
+        let result = match operation {
+            SpreadsheetOperation::ListSheets { path } => {
+                let path = match self.resolve_path(&path) {
+                    Ok(path) => path,
+                    Err(message) => return Ok(Err(message)),
+                };
+                let file = path.clone();
+                blocking(move || table::sheet_names(&file))
+                    .await
+                    .map(|sheets| {
+                        json!({
+                            "path": path.display().to_string(),
+                            "sheets": sheets,
+                        })
+                    })
+            }
+            SpreadsheetOperation::Read {
+                path,
+                sheet,
+                has_header,
+                filters,
+                columns,
+                offset,
+                limit,
+            } => {
+                let limit = limit.unwrap_or(DEFAULT_ROW_LIMIT).clamp(1, MAX_ROW_LIMIT);
+                self.load_filtered(&path, sheet, has_header, filters)
+                    .await
+                    .and_then(|(path, table, sheet)| {
+                        let table = match columns {
+                            Some(columns) => table.select(&columns)?,
+                            None => table,
+                        };
+                        let total_rows = table.rows.len();
+                        let rows: Vec<_> =
+                            table.rows.into_iter().skip(offset).take(limit).collect();
+                        Ok(json!({
+                            "path": path.display().to_string(),
+                            "sheet": sheet,
+                            "columns": table.columns,
+                            "total_rows": total_rows,
+                            "offset": offset,
+                            "row_count": rows.len(),
+                            "truncated": offset + rows.len() < total_rows,
+                            "rows": rows,
+                        }))
+                    })
+            }
+            SpreadsheetOperation::Aggregate {
+                path,
+                sheet,
+                has_header,
+                filters,
+                group_by,
+                aggregations,
+            } => self
+                .load_filtered(&path, sheet, has_header, filters)
+                .await
+                .and_then(|(path, table, sheet)| {
+                    let matched_rows = table.rows.len();
+                    let summary = table::aggregate(&table, &group_by, &aggregations)?;
+                    Ok(json!({
+                        "path": path.display().to_string(),
+                        "sheet": sheet,
+                        "matched_rows": matched_rows,
+                        "columns": summary.columns,
+                        "rows": summary.rows,
+                    }))
+                }),
+            SpreadsheetOperation::Write {
+                path,
+                sheet,
+                columns,
+                rows,
+            } => {
+                if columns.is_empty() {
+                    return Ok(Err("columns must not be empty.".to_string()));
+                }
+                let path = match self.resolve_path(&path) {
+                    Ok(path) => path,
+                    Err(message) => return Ok(Err(message)),
+                };
+                let rows = match rows_from_input(&columns, rows) {
+                    Ok(rows) => rows,
+                    Err(message) => return Ok(Err(message)),
+                };
+                let summary = format!(
+                    "Write {} rows x {} columns to {}",
+                    rows.len(),
+                    columns.len(),
+                    path.display()
+                );
+                if let Some(message) = self.check_action_allowed(&path, summary).await? {
+                    return Ok(Err(message));
+                }
+                let row_count = rows.len();
+                let column_count = columns.len();
+                let table = Table { columns, rows };
+                let file = path.clone();
+                blocking(move || table::save(&file, &table, sheet.as_deref()))
+                    .await
+                    .map(|()| {
+                        json!({
+                            "path": path.display().to_string(),
+                            "rows_written": row_count,
+                            "columns": column_count,
+                        })
+                    })
+            }
+        };
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Tool for SpreadsheetTool {
+    fn name(&self) -> &str {
+        "spreadsheet"
+    }
+
+    fn description(&self) -> &str {
+        "Read, filter, aggregate and write spreadsheets. Reads CSV, TSV, XLSX, XLS and ODS files; writes CSV, TSV and XLSX. Returns column names and row arrays."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["list_sheets", "read", "aggregate", "write"],
+                    "description": "list_sheets: workbook sheet names; read: rows with optional filters; aggregate: grouped count/sum/avg/min/max; write: create or replace a file"
+                },
+                "path": { "type": "string", "description": "Spreadsheet file path. The format follows the extension." },
+                "sheet": { "type": "string", "description": "Workbook sheet name (defaults to the first sheet). For write, the name of the created sheet." },
+                "has_header": { "type": "boolean", "description": "Whether the first row holds column names (default true). Without a header, columns are named A, B, C, ..." },
+                "filters": {
+                    "type": "array",
+                    "description": "read/aggregate: keep rows matching all filters",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "column": { "type": "string" },
+                            "op": { "type": "string", "enum": ["eq", "ne", "gt", "gte", "lt", "lte", "contains", "is_empty", "not_empty"] },
+                            "value": { "description": "Value to compare against. Numbers compare numerically." }
+                        },
+                        "required": ["column", "op"]
+                    }
+                },
+                "columns": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "read: columns to return (default all). write: column names of the new file."
+                },
+                "offset": { "type": "integer", "description": "read: rows to skip after filtering" },
+                "limit": { "type": "integer", "description": "read: maximum rows to return (default 100, max 1000)" },
+                "group_by": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "aggregate: columns to group by (omit for a single summary row)"
+                },
+                "aggregations": {
+                    "type": "array",
+                    "description": "aggregate: e.g. [{\"function\": \"sum\", \"column\": \"amount\"}, {\"function\": \"count\"}]",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "function": { "type": "string", "enum": ["count", "sum", "avg", "min", "max"] },
+                            "column": { "type": "string" }
+                        },
+                        "required": ["function"]
+                    }
+                },
+                "rows": {
+                    "type": "array",
+                    "description": "write: rows as arrays of cells or objects keyed by column name"
+                }
+            },
+            "required": ["operation", "path"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let operation: SpreadsheetOperation = match serde_json::from_value(input) {
+            Ok(operation) => operation,
+            Err(e) => {
+                return Ok(ToolOutput::error(format!(
+                    "Invalid input: {}. Required: operation (list_sheets|read|aggregate|write) and path.",
+                    e
+                )));
+            }
+        };
+
+        match self.run(operation).await? {
+            Ok(result) => Ok(ToolOutput::success(result)),
+            Err(message) => Ok(ToolOutput::error(message)),
+        }
+    }
+}
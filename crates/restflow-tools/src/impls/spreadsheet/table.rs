@@ -0,0 +1,562 @@
+//! In-memory table model for the spreadsheet tool: loading CSV/XLSX files,
+//! filtering and aggregating rows, and writing tables back out.
+//!
+//! Cells are JSON values (`null`, numbers, strings, booleans), so results can
+//! be returned to the model without another conversion step.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use calamine::{Data, Reader, open_workbook_auto};
+use rust_xlsxwriter::{Format, Workbook};
+use serde::Deserialize;
+use serde_json::{Number, Value};
+
+/// Largest file the tool will load (50 MB).
+pub(super) const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FileFormat {
+    Csv,
+    Tsv,
+    Workbook,
+}
+
+impl FileFormat {
+    pub(super) fn from_path(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            "xlsx" | "xlsm" | "xlsb" | "xls" | "ods" => Ok(Self::Workbook),
+            other => Err(format!(
+                "Unsupported spreadsheet extension '{}'. Use .csv, .tsv, .xlsx, .xlsm, .xlsb, .xls or .ods.",
+                other
+            )),
+        }
+    }
+
+    fn delimiter(self) -> u8 {
+        if self == Self::Tsv { b'\t' } else { b',' }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    pub(super) fn column_index(&self, name: &str) -> Result<usize, String> {
+        self.columns
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown column '{}'. Available columns: {}",
+                    name,
+                    self.columns.join(", ")
+                )
+            })
+    }
+
+    /// Keep only `columns`, in the given order.
+    pub(super) fn select(self, columns: &[String]) -> Result<Self, String> {
+        let indexes = columns
+            .iter()
+            .map(|name| self.column_index(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|row| indexes.iter().map(|&index| row[index].clone()).collect())
+            .collect();
+        Ok(Self {
+            columns: columns.to_vec(),
+            rows,
+        })
+    }
+
+    fn from_grid(mut grid: Vec<Vec<Value>>, has_header: bool) -> Self {
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut grid {
+            row.resize(width, Value::Null);
+        }
+        let columns = if has_header && !grid.is_empty() {
+            header_names(&grid.remove(0))
+        } else {
+            (0..width).map(column_letter).collect()
+        };
+        Self {
+            columns,
+            rows: grid,
+        }
+    }
+}
+
+/// Spreadsheet-style column name (`A`, `B`, ..., `AA`) for a 0-based index.
+fn column_letter(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Header row to column names, filling blanks and making names unique.
+fn header_names(header: &[Value]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    header
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| {
+            let base = match cell {
+                Value::Null => column_letter(index),
+                Value::String(text) if text.trim().is_empty() => column_letter(index),
+                Value::String(text) => text.trim().to_string(),
+                other => other.to_string(),
+            };
+            let count = seen.entry(base.clone()).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                base
+            } else {
+                format!("{}_{}", base, count)
+            }
+        })
+        .collect()
+}
+
+/// Infer a JSON value from a CSV field.
+fn parse_field(field: &str) -> Value {
+    let trimmed = field.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    if let Ok(value) = trimmed.parse::<i64>() {
+        return Value::from(value);
+    }
+    if let Ok(value) = trimmed.parse::<f64>()
+        && let Some(number) = Number::from_f64(value)
+    {
+        return Value::Number(number);
+    }
+    match trimmed.to_ascii_lowercase().as_str() {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::String(field.to_string()),
+    }
+}
+
+fn cell_to_json(cell: &Data) -> Value {
+    match cell {
+        Data::Empty => Value::Null,
+        Data::Int(value) => Value::from(*value),
+        Data::Float(value) => Number::from_f64(*value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Data::String(text) => Value::String(text.clone()),
+        Data::Bool(value) => Value::Bool(*value),
+        Data::DateTime(datetime) if datetime.is_datetime() => datetime
+            .as_datetime()
+            .map(|value| Value::String(value.format("%Y-%m-%dT%H:%M:%S").to_string()))
+            .unwrap_or_else(|| Value::String(datetime.to_string())),
+        Data::DateTime(duration) => Value::String(duration.to_string()),
+        Data::DateTimeIso(text) | Data::DurationIso(text) => Value::String(text.clone()),
+        Data::Error(error) => Value::String(format!("#{:?}", error)),
+    }
+}
+
+fn check_size(path: &Path) -> Result<(), String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?
+        .len();
+    if size > MAX_FILE_BYTES {
+        return Err(format!(
+            "'{}' is {} bytes; the spreadsheet tool loads files up to {} bytes.",
+            path.display(),
+            size,
+            MAX_FILE_BYTES
+        ));
+    }
+    Ok(())
+}
+
+/// Sheet names of a workbook. CSV and TSV files have a single unnamed sheet.
+pub(super) fn sheet_names(path: &Path) -> Result<Vec<String>, String> {
+    match FileFormat::from_path(path)? {
+        FileFormat::Csv | FileFormat::Tsv => Ok(Vec::new()),
+        FileFormat::Workbook => {
+            let workbook = open_workbook_auto(path)
+                .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+            Ok(workbook.sheet_names())
+        }
+    }
+}
+
+/// Load a sheet (first one by default) as a table.
+pub(super) fn load(
+    path: &Path,
+    sheet: Option<&str>,
+    has_header: bool,
+) -> Result<(Table, Option<String>), String> {
+    check_size(path)?;
+    let format = FileFormat::from_path(path)?;
+    match format {
+        FileFormat::Csv | FileFormat::Tsv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .delimiter(format.delimiter())
+                .has_headers(false)
+                .flexible(true)
+                .from_path(path)
+                .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+            let mut grid = Vec::new();
+            for record in reader.records() {
+                let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+                grid.push(record.iter().map(parse_field).collect());
+            }
+            Ok((Table::from_grid(grid, has_header), None))
+        }
+        FileFormat::Workbook => {
+            let mut workbook = open_workbook_auto(path)
+                .map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+            let names = workbook.sheet_names();
+            let sheet = match sheet {
+                Some(sheet) if names.iter().any(|name| name == sheet) => sheet.to_string(),
+                Some(sheet) => {
+                    return Err(format!(
+                        "Sheet '{}' not found. Available sheets: {}",
+                        sheet,
+                        names.join(", ")
+                    ));
+                }
+                None => names
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| "Workbook has no sheets".to_string())?,
+            };
+            let range = workbook
+                .worksheet_range(&sheet)
+                .map_err(|e| format!("Failed to read sheet '{}': {}", sheet, e))?;
+            let grid = range
+                .rows()
+                .map(|row| row.iter().map(cell_to_json).collect())
+                .collect();
+            Ok((Table::from_grid(grid, has_header), Some(sheet)))
+        }
+    }
+}
+
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Write `table` to `path`, replacing any existing file.
+pub(super) fn save(path: &Path, table: &Table, sheet: Option<&str>) -> Result<(), String> {
+    let format = FileFormat::from_path(path)?;
+    match format {
+        FileFormat::Csv | FileFormat::Tsv => {
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(format.delimiter())
+                .from_path(path)
+                .map_err(|e| format!("Failed to create '{}': {}", path.display(), e))?;
+            let write_error = |e: csv::Error| format!("Failed to write CSV: {}", e);
+            writer.write_record(&table.columns).map_err(write_error)?;
+            for row in &table.rows {
+                writer
+                    .write_record(row.iter().map(field_text))
+                    .map_err(write_error)?;
+            }
+            writer
+                .flush()
+                .map_err(|e| format!("Failed to write CSV: {}", e))
+        }
+        FileFormat::Workbook => {
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"))
+            {
+                return Err("Workbooks can only be written as .xlsx files.".to_string());
+            }
+            let xlsx_error = |e: rust_xlsxwriter::XlsxError| format!("Failed to write XLSX: {}", e);
+            let mut workbook = Workbook::new();
+            let worksheet = workbook.add_worksheet();
+            if let Some(sheet) = sheet {
+                worksheet.set_name(sheet).map_err(xlsx_error)?;
+            }
+            let bold = Format::new().set_bold();
+            for (col, name) in table.columns.iter().enumerate() {
+                worksheet
+                    .write_string_with_format(0, col as u16, name, &bold)
+                    .map_err(xlsx_error)?;
+            }
+            for (row_index, row) in table.rows.iter().enumerate() {
+                let row_num = row_index as u32 + 1;
+                for (col, value) in row.iter().enumerate() {
+                    let col = col as u16;
+                    match value {
+                        Value::Null => {}
+                        Value::Bool(value) => {
+                            worksheet
+                                .write_boolean(row_num, col, *value)
+                                .map_err(xlsx_error)?;
+                        }
+                        Value::Number(number) => {
+                            worksheet
+                                .write_number(row_num, col, number.as_f64().unwrap_or_default())
+                                .map_err(xlsx_error)?;
+                        }
+                        other => {
+                            worksheet
+                                .write_string(row_num, col, field_text(other))
+                                .map_err(xlsx_error)?;
+                        }
+                    }
+                }
+            }
+            worksheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+            workbook.save(path).map_err(xlsx_error)
+        }
+    }
+}
+
+/// Numeric value of a cell, parsing numeric strings.
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+    IsEmpty,
+    NotEmpty,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct Filter {
+    pub column: String,
+    pub op: FilterOp,
+    #[serde(default)]
+    pub value: Value,
+}
+
+fn compare(cell: &Value, target: &Value) -> Option<std::cmp::Ordering> {
+    match (as_number(cell), as_number(target)) {
+        (Some(left), Some(right)) => left.partial_cmp(&right),
+        _ if cell.is_null() => None,
+        _ => Some(field_text(cell).cmp(&field_text(target))),
+    }
+}
+
+fn matches(cell: &Value, filter: &Filter) -> bool {
+    use std::cmp::Ordering;
+    let is_empty = match cell {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        _ => false,
+    };
+    match filter.op {
+        FilterOp::IsEmpty => is_empty,
+        FilterOp::NotEmpty => !is_empty,
+        FilterOp::Contains => field_text(cell)
+            .to_lowercase()
+            .contains(&field_text(&filter.value).to_lowercase()),
+        FilterOp::Eq => compare(cell, &filter.value) == Some(Ordering::Equal),
+        FilterOp::Ne => compare(cell, &filter.value) != Some(Ordering::Equal),
+        FilterOp::Gt => compare(cell, &filter.value) == Some(Ordering::Greater),
+        FilterOp::Gte => matches!(
+            compare(cell, &filter.value),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        FilterOp::Lt => compare(cell, &filter.value) == Some(Ordering::Less),
+        FilterOp::Lte => matches!(
+            compare(cell, &filter.value),
+            Some(Ordering::Less | Ordering::Equal)
+        ),
+    }
+}
+
+/// Keep rows matching every filter.
+pub(super) fn apply_filters(table: Table, filters: &[Filter]) -> Result<Table, String> {
+    let indexed = filters
+        .iter()
+        .map(|filter| Ok((table.column_index(&filter.column)?, filter)))
+        .collect::<Result<Vec<_>, String>>()?;
+    let rows = table
+        .rows
+        .into_iter()
+        .filter(|row| {
+            indexed
+                .iter()
+                .all(|(index, filter)| matches(&row[*index], filter))
+        })
+        .collect();
+    Ok(Table {
+        columns: table.columns,
+        rows,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum AggregateFn {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFn {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct Aggregation {
+    pub function: AggregateFn,
+    /// Column to aggregate. Optional for `count`, which then counts rows.
+    pub column: Option<String>,
+}
+
+impl Aggregation {
+    fn output_name(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}_{}", self.function.name(), column),
+            None => self.function.name().to_string(),
+        }
+    }
+}
+
+fn number_value(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Value::from(value as i64)
+    } else {
+        Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn aggregate_rows(rows: &[&Vec<Value>], function: AggregateFn, index: Option<usize>) -> Value {
+    let Some(index) = index else {
+        return Value::from(rows.len());
+    };
+    let cells = rows.iter().map(|row| &row[index]);
+    if function == AggregateFn::Count {
+        return Value::from(cells.filter(|cell| !cell.is_null()).count());
+    }
+    let numbers: Vec<f64> = cells.filter_map(as_number).collect();
+    if numbers.is_empty() {
+        return Value::Null;
+    }
+    let value = match function {
+        AggregateFn::Count => unreachable!("count is handled above"),
+        AggregateFn::Sum => numbers.iter().sum(),
+        AggregateFn::Avg => numbers.iter().sum::<f64>() / numbers.len() as f64,
+        AggregateFn::Min => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+        AggregateFn::Max => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    };
+    number_value(value)
+}
+
+/// Group rows by `group_by` (in first-seen order) and compute `aggregations`
+/// for each group.
+pub(super) fn aggregate(
+    table: &Table,
+    group_by: &[String],
+    aggregations: &[Aggregation],
+) -> Result<Table, String> {
+    if aggregations.is_empty() {
+        return Err("At least one aggregation is required.".to_string());
+    }
+    let group_indexes = group_by
+        .iter()
+        .map(|name| table.column_index(name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value_indexes = aggregations
+        .iter()
+        .map(|aggregation| match &aggregation.column {
+            Some(column) => table.column_index(column).map(Some),
+            None if aggregation.function == AggregateFn::Count => Ok(None),
+            None => Err(format!(
+                "Aggregation '{}' requires a column.",
+                aggregation.function.name()
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut order: Vec<Vec<Value>> = Vec::new();
+    let mut groups: HashMap<String, Vec<&Vec<Value>>> = HashMap::new();
+    for row in &table.rows {
+        let key: Vec<Value> = group_indexes
+            .iter()
+            .map(|&index| row[index].clone())
+            .collect();
+        let key_text = Value::Array(key.clone()).to_string();
+        groups
+            .entry(key_text)
+            .or_insert_with(|| {
+                order.push(key);
+                Vec::new()
+            })
+            .push(row);
+    }
+    // Without grouping, an empty table still yields one summary row.
+    if group_indexes.is_empty() && order.is_empty() {
+        groups.insert(Value::Array(Vec::new()).to_string(), Vec::new());
+        order.push(Vec::new());
+    }
+
+    let mut columns = group_by.to_vec();
+    columns.extend(aggregations.iter().map(Aggregation::output_name));
+    let rows = order
+        .into_iter()
+        .map(|key| {
+            let members = &groups[&Value::Array(key.clone()).to_string()];
+            let mut row = key;
+            row.extend(
+                aggregations
+                    .iter()
+                    .zip(&value_indexes)
+                    .map(|(aggregation, index)| {
+                        aggregate_rows(members, aggregation.function, *index)
+                    }),
+            );
+            row
+        })
+        .collect();
+    Ok(Table { columns, rows })
+}
@@ -0,0 +1,177 @@
+use super::*;
+use tempfile::TempDir;
+
+const SALES_CSV: &str = "region,product,amount\n\
+north,apples,10\n\
+south,pears,2.5\n\
+north,pears,4\n\
+east,apples,\n";
+
+fn setup() -> (TempDir, SpreadsheetTool) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("sales.csv"), SALES_CSV).unwrap();
+    let tool = SpreadsheetTool::new()
+        .with_base_dir(dir.path())
+        .require_base_dir();
+    (dir, tool)
+}
+
+#[tokio::test]
+async fn test_read_csv_with_filters_and_columns() {
+    let (_dir, tool) = setup();
+    let output = tool
+        .execute(json!({
+            "operation": "read",
+            "path": "sales.csv",
+            "filters": [{ "column": "amount", "op": "gte", "value": 4 }],
+            "columns": ["product", "amount"],
+        }))
+        .await
+        .unwrap();
+
+    assert!(output.success, "{:?}", output.error);
+    assert_eq!(output.result["columns"], json!(["product", "amount"]));
+    assert_eq!(output.result["rows"], json!([["apples", 10], ["pears", 4]]));
+    assert_eq!(output.result["total_rows"], 2);
+    assert_eq!(output.result["truncated"], false);
+}
+
+#[tokio::test]
+async fn test_read_limit_and_offset() {
+    let (_dir, tool) = setup();
+    let output = tool
+        .execute(json!({
+            "operation": "read",
+            "path": "sales.csv",
+            "offset": 1,
+            "limit": 2,
+        }))
+        .await
+        .unwrap();
+
+    assert!(output.success, "{:?}", output.error);
+    assert_eq!(output.result["row_count"], 2);
+    assert_eq!(output.result["rows"][0][1], "pears");
+    assert_eq!(output.result["truncated"], true);
+}
+
+#[tokio::test]
+async fn test_aggregate_group_by() {
+    let (_dir, tool) = setup();
+    let output = tool
+        .execute(json!({
+            "operation": "aggregate",
+            "path": "sales.csv",
+            "group_by": ["region"],
+            "aggregations": [
+                { "function": "count" },
+                { "function": "sum", "column": "amount" },
+                { "function": "max", "column": "amount" },
+            ],
+        }))
+        .await
+        .unwrap();
+
+    assert!(output.success, "{:?}", output.error);
+    assert_eq!(
+        output.result["columns"],
+        json!(["region", "count", "sum_amount", "max_amount"])
+    );
+    assert_eq!(
+        output.result["rows"],
+        json!([
+            ["north", 2, 14, 10],
+            ["south", 1, 2.5, 2.5],
+            ["east", 1, null, null],
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_write_xlsx_round_trip() {
+    let (dir, tool) = setup();
+    let output = tool
+        .execute(json!({
+            "operation": "write",
+            "path": "report.xlsx",
+            "sheet": "Summary",
+            "columns": ["name", "score", "passed"],
+            "rows": [
+                ["alice", 9.5, true],
+                { "name": "bob", "score": 7 },
+            ],
+        }))
+        .await
+        .unwrap();
+    assert!(output.success, "{:?}", output.error);
+    assert!(dir.path().join("report.xlsx").exists());
+
+    let sheets = tool
+        .execute(json!({ "operation": "list_sheets", "path": "report.xlsx" }))
+        .await
+        .unwrap();
+    assert_eq!(sheets.result["sheets"], json!(["Summary"]));
+
+    let output = tool
+        .execute(json!({ "operation": "read", "path": "report.xlsx" }))
+        .await
+        .unwrap();
+    assert!(output.success, "{:?}", output.error);
+    assert_eq!(output.result["sheet"], "Summary");
+    assert_eq!(output.result["columns"], json!(["name", "score", "passed"]));
+    assert_eq!(
+        output.result["rows"],
+        json!([["alice", 9.5, true], ["bob", 7.0, null]])
+    );
+}
+
+#[tokio::test]
+async fn test_write_csv_and_read_without_header() {
+    let (dir, tool) = setup();
+    let output = tool
+        .execute(json!({
+            "operation": "write",
+            "path": "out.csv",
+            "columns": ["a", "b"],
+            "rows": [[1, "x,y"], [2, null]],
+        }))
+        .await
+        .unwrap();
+    assert!(output.success, "{:?}", output.error);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("out.csv")).unwrap(),
+        "a,b\n1,\"x,y\"\n2,\n"
+    );
+
+    let output = tool
+        .execute(json!({ "operation": "read", "path": "out.csv", "has_header": false }))
+        .await
+        .unwrap();
+    assert_eq!(output.result["columns"], json!(["A", "B"]));
+    assert_eq!(output.result["rows"][0], json!(["a", "b"]));
+}
+
+#[tokio::test]
+async fn test_rejects_paths_outside_base_dir() {
+    let (_dir, tool) = setup();
+    let output = tool
+        .execute(json!({ "operation": "read", "path": "../../etc/passwd.csv" }))
+        .await
+        .unwrap();
+    assert!(!output.success);
+}
+
+#[tokio::test]
+async fn test_unknown_column() {
+    let (_dir, tool) = setup();
+    let output = tool
+        .execute(json!({
+            "operation": "read",
+            "path": "sales.csv",
+            "filters": [{ "column": "price", "op": "gt", "value": 1 }],
+        }))
+        .await
+        .unwrap();
+    assert!(!output.success);
+    assert!(output.error.unwrap().contains("Available columns"));
+}
@@ -0,0 +1,335 @@
+//! Database drivers for the SQL tool.
+//!
+//! Each backend opens a single connection per call. Read-only calls run in a
+//! read-only transaction (Postgres, MySQL) or on a read-only connection
+//! (SQLite), so the database rejects writes even if statement checks miss
+//! one.
+
+use std::str::FromStr;
+
+use futures::TryStreamExt;
+use futures::stream::BoxStream;
+use serde_json::{Value, json};
+use sqlx::mysql::{MySqlConnection, MySqlRow};
+use sqlx::postgres::{PgConnection, PgRow};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection, SqliteRow};
+use sqlx::types::Json;
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx::types::{Decimal, Uuid};
+use sqlx::{AssertSqlSafe, Column, ConnectOptions, Connection, Row, TypeInfo};
+
+/// Server-side statement timeout applied to Postgres sessions.
+const POSTGRES_STATEMENT_TIMEOUT: &str = "SET LOCAL statement_timeout = '30s'";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Backend {
+    Sqlite,
+    Postgres,
+    MySql,
+}
+
+impl Backend {
+    pub(super) fn from_url(url: &str) -> Result<Self, String> {
+        let scheme = url.split(':').next().unwrap_or_default();
+        match scheme.to_ascii_lowercase().as_str() {
+            "sqlite" => Ok(Self::Sqlite),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "mysql" | "mariadb" => Ok(Self::MySql),
+            other => Err(format!(
+                "Unsupported database scheme '{}'. Use sqlite://, postgres://, or mysql:// URLs.",
+                other
+            )),
+        }
+    }
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Sqlite => "sqlite",
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+        }
+    }
+
+    /// Query listing user tables and views.
+    pub(super) fn list_tables_sql(self) -> &'static str {
+        match self {
+            Self::Sqlite => {
+                "SELECT 'main' AS table_schema, name AS table_name, type AS table_type \
+                 FROM sqlite_master WHERE type IN ('table', 'view') \
+                 AND name NOT LIKE 'sqlite_%' ORDER BY name"
+            }
+            Self::Postgres => {
+                "SELECT table_schema::text, table_name::text, table_type::text \
+                 FROM information_schema.tables \
+                 WHERE table_schema NOT IN ('pg_catalog', 'information_schema') \
+                 ORDER BY table_schema, table_name"
+            }
+            Self::MySql => {
+                "SELECT table_schema AS table_schema, table_name AS table_name, \
+                 table_type AS table_type FROM information_schema.tables \
+                 WHERE table_schema = DATABASE() ORDER BY table_name"
+            }
+        }
+    }
+
+    /// Query describing the columns of one table. Binds the table name and,
+    /// for Postgres, an optional schema.
+    pub(super) fn describe_table_sql(self) -> &'static str {
+        match self {
+            Self::Sqlite => {
+                "SELECT name AS column_name, type AS data_type, \
+                 CASE WHEN \"notnull\" = 1 THEN 'NO' ELSE 'YES' END AS is_nullable, \
+                 dflt_value AS column_default, pk AS primary_key \
+                 FROM pragma_table_info(?1)"
+            }
+            Self::Postgres => {
+                "SELECT column_name::text, data_type::text, is_nullable::text, \
+                 column_default::text FROM information_schema.columns \
+                 WHERE table_name = $1 AND table_schema = COALESCE($2, current_schema()) \
+                 ORDER BY ordinal_position"
+            }
+            Self::MySql => {
+                "SELECT column_name AS column_name, column_type AS data_type, \
+                 is_nullable AS is_nullable, column_default AS column_default, \
+                 column_key AS column_key FROM information_schema.columns \
+                 WHERE table_schema = DATABASE() AND table_name = ? \
+                 ORDER BY ordinal_position"
+            }
+        }
+    }
+}
+
+pub(super) enum RequestMode {
+    /// Return up to `limit` rows.
+    Rows { limit: usize },
+    /// Return the number of affected rows.
+    Execute,
+}
+
+pub(super) struct SqlRequest<'a> {
+    pub sql: &'a str,
+    pub params: &'a [Value],
+    pub mode: RequestMode,
+    pub read_only: bool,
+}
+
+pub(super) struct RowSet {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub truncated: bool,
+}
+
+pub(super) enum SqlResponse {
+    Rows(RowSet),
+    Affected(u64),
+}
+
+macro_rules! bind_params {
+    ($query:expr, $params:expr) => {{
+        let mut query = $query;
+        for param in $params {
+            query = match param {
+                Value::Null => query.bind(None::<String>),
+                Value::Bool(value) => query.bind(*value),
+                Value::Number(number) => match number.as_i64() {
+                    Some(value) => query.bind(value),
+                    None => query.bind(number.as_f64()),
+                },
+                Value::String(value) => query.bind(value.clone()),
+                other => query.bind(Json(other.clone())),
+            };
+        }
+        query
+    }};
+}
+
+/// Return the first type in the list that the column decodes as.
+macro_rules! decode_first {
+    ($row:expr, $index:expr, $($ty:ty => $convert:expr),+ $(,)?) => {{
+        $(
+            if let Ok(value) = $row.try_get::<Option<$ty>, _>($index) {
+                return value.map($convert).unwrap_or(Value::Null);
+            }
+        )+
+    }};
+}
+
+fn bytes_to_json(bytes: Vec<u8>) -> Value {
+    match String::from_utf8(bytes) {
+        Ok(text) => Value::String(text),
+        Err(e) => Value::String(format!("0x{}", hex::encode(e.into_bytes()))),
+    }
+}
+
+fn unsupported<R: Row>(row: &R, index: usize) -> Value {
+    let type_name = row.columns()[index].type_info().name().to_string();
+    Value::String(format!("<unsupported type {}>", type_name))
+}
+
+fn sqlite_value(row: &SqliteRow, index: usize) -> Value {
+    decode_first!(row, index,
+        i64 => |v| json!(v),
+        f64 => |v| json!(v),
+        String => Value::String,
+        Vec<u8> => bytes_to_json,
+    );
+    unsupported(row, index)
+}
+
+fn postgres_value(row: &PgRow, index: usize) -> Value {
+    decode_first!(row, index,
+        bool => Value::Bool,
+        i16 => |v| json!(v),
+        i32 => |v| json!(v),
+        i64 => |v| json!(v),
+        f32 => |v| json!(v),
+        f64 => |v| json!(v),
+        Decimal => |v| Value::String(v.to_string()),
+        String => Value::String,
+        Value => |v| v,
+        Uuid => |v| Value::String(v.to_string()),
+        DateTime<Utc> => |v| Value::String(v.to_rfc3339()),
+        NaiveDateTime => |v| Value::String(v.to_string()),
+        NaiveDate => |v| Value::String(v.to_string()),
+        NaiveTime => |v| Value::String(v.to_string()),
+        Vec<u8> => bytes_to_json,
+    );
+    unsupported(row, index)
+}
+
+fn mysql_value(row: &MySqlRow, index: usize) -> Value {
+    decode_first!(row, index,
+        bool => Value::Bool,
+        i64 => |v| json!(v),
+        i32 => |v| json!(v),
+        i16 => |v| json!(v),
+        i8 => |v| json!(v),
+        u64 => |v| json!(v),
+        u32 => |v| json!(v),
+        u16 => |v| json!(v),
+        u8 => |v| json!(v),
+        f64 => |v| json!(v),
+        f32 => |v| json!(v),
+        Decimal => |v| Value::String(v.to_string()),
+        String => Value::String,
+        Value => |v| v,
+        DateTime<Utc> => |v| Value::String(v.to_rfc3339()),
+        NaiveDateTime => |v| Value::String(v.to_string()),
+        NaiveDate => |v| Value::String(v.to_string()),
+        NaiveTime => |v| Value::String(v.to_string()),
+        Vec<u8> => bytes_to_json,
+    );
+    unsupported(row, index)
+}
+
+async fn collect_rows<R: Row>(
+    mut stream: BoxStream<'_, Result<R, sqlx::Error>>,
+    limit: usize,
+    to_json: fn(&R, usize) -> Value,
+) -> Result<RowSet, sqlx::Error> {
+    let mut columns = Vec::new();
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    while let Some(row) = stream.try_next().await? {
+        if rows.len() == limit {
+            truncated = true;
+            break;
+        }
+        if columns.is_empty() {
+            columns = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+        }
+        rows.push((0..row.len()).map(|index| to_json(&row, index)).collect());
+    }
+    Ok(RowSet {
+        columns,
+        rows,
+        truncated,
+    })
+}
+
+async fn run_sqlite(url: &str, request: SqlRequest<'_>) -> Result<SqlResponse, sqlx::Error> {
+    let mut conn: SqliteConnection = SqliteConnectOptions::from_str(url)?
+        .read_only(request.read_only)
+        .connect()
+        .await?;
+    let query = bind_params!(sqlx::query(AssertSqlSafe(request.sql)), request.params);
+    let response = match request.mode {
+        RequestMode::Rows { limit } => {
+            SqlResponse::Rows(collect_rows(query.fetch(&mut conn), limit, sqlite_value).await?)
+        }
+        RequestMode::Execute => {
+            SqlResponse::Affected(query.execute(&mut conn).await?.rows_affected())
+        }
+    };
+    conn.close().await?;
+    Ok(response)
+}
+
+async fn run_postgres(url: &str, request: SqlRequest<'_>) -> Result<SqlResponse, sqlx::Error> {
+    let mut conn = PgConnection::connect(url).await?;
+    let mut tx = if request.read_only {
+        conn.begin_with("BEGIN READ ONLY").await?
+    } else {
+        conn.begin().await?
+    };
+    sqlx::query(POSTGRES_STATEMENT_TIMEOUT)
+        .execute(&mut *tx)
+        .await?;
+    let query = bind_params!(sqlx::query(AssertSqlSafe(request.sql)), request.params);
+    let response = match request.mode {
+        RequestMode::Rows { limit } => {
+            SqlResponse::Rows(collect_rows(query.fetch(&mut *tx), limit, postgres_value).await?)
+        }
+        RequestMode::Execute => {
+            SqlResponse::Affected(query.execute(&mut *tx).await?.rows_affected())
+        }
+    };
+    if request.read_only {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+    conn.close().await?;
+    Ok(response)
+}
+
+async fn run_mysql(url: &str, request: SqlRequest<'_>) -> Result<SqlResponse, sqlx::Error> {
+    let mut conn = MySqlConnection::connect(url).await?;
+    let mut tx = if request.read_only {
+        conn.begin_with("START TRANSACTION READ ONLY").await?
+    } else {
+        conn.begin().await?
+    };
+    let query = bind_params!(sqlx::query(AssertSqlSafe(request.sql)), request.params);
+    let response = match request.mode {
+        RequestMode::Rows { limit } => {
+            SqlResponse::Rows(collect_rows(query.fetch(&mut *tx), limit, mysql_value).await?)
+        }
+        RequestMode::Execute => {
+            SqlResponse::Affected(query.execute(&mut *tx).await?.rows_affected())
+        }
+    };
+    if request.read_only {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+    conn.close().await?;
+    Ok(response)
+}
+
+pub(super) async fn run(
+    backend: Backend,
+    url: &str,
+    request: SqlRequest<'_>,
+) -> Result<SqlResponse, sqlx::Error> {
+    match backend {
+        Backend::Sqlite => run_sqlite(url, request).await,
+        Backend::Postgres => run_postgres(url, request).await,
+        Backend::MySql => run_mysql(url, request).await,
+    }
+}
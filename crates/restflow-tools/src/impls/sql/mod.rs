@@ -0,0 +1,441 @@
+//! SQL database query tool for AI agents.
+//!
+//! Connections are database URLs stored as secrets: `DATABASE_URL` for the
+//! default connection and `<NAME>_DATABASE_URL` for named ones (e.g.
+//! `connection: "analytics"` reads `ANALYTICS_DATABASE_URL`). SQLite,
+//! Postgres and MySQL/MariaDB URLs are supported.
+//!
+//! The tool is read-only unless built with [`SqlTool::with_write`]: only
+//! single read statements are accepted, and they run in a read-only
+//! transaction or connection. Values are always passed as bound parameters.
+
+mod driver;
+#[cfg(test)]
+mod tests;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Result;
+use crate::check_security;
+use crate::security::SecurityGate;
+use crate::{SecretResolver, Tool, ToolAction, ToolOutput};
+
+use self::driver::{Backend, RequestMode, RowSet, SqlRequest, SqlResponse};
+
+const DEFAULT_ROW_LIMIT: usize = 100;
+const MAX_ROW_LIMIT: usize = 1_000;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONNECTION_SECRET: &str = "DATABASE_URL";
+
+/// Leading keywords of statements that only read data.
+const READ_KEYWORDS: &[&str] = &[
+    "select", "with", "explain", "show", "describe", "desc", "values", "table",
+];
+
+#[derive(Debug, Deserialize)]
+struct SqlInput {
+    connection: Option<String>,
+    #[serde(flatten)]
+    operation: SqlOperation,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum SqlOperation {
+    Query {
+        sql: String,
+        #[serde(default)]
+        params: Vec<Value>,
+        limit: Option<usize>,
+    },
+    Execute {
+        sql: String,
+        #[serde(default)]
+        params: Vec<Value>,
+    },
+    ListTables,
+    DescribeTable {
+        table: String,
+        schema: Option<String>,
+    },
+}
+
+/// Skip whitespace and SQL comments at the start of `sql`.
+fn skip_leading_trivia(mut sql: &str) -> &str {
+    loop {
+        sql = sql.trim_start();
+        if let Some(rest) = sql.strip_prefix("--") {
+            sql = rest.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+        } else if let Some(rest) = sql.strip_prefix("/*") {
+            sql = rest.split_once("*/").map(|(_, rest)| rest).unwrap_or("");
+        } else {
+            return sql;
+        }
+    }
+}
+
+fn first_keyword(sql: &str) -> String {
+    skip_leading_trivia(sql)
+        .trim_start_matches('(')
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Whether `sql` contains more than one statement. Semicolons inside quotes
+/// and comments, and trailing semicolons, are ignored.
+fn has_multiple_statements(sql: &str) -> bool {
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b';' => {
+                let rest = skip_leading_trivia(&sql[i + 1..]);
+                if !rest.trim_start_matches(';').trim().is_empty() {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
+
+fn validate_statement(sql: &str, read_only: bool) -> std::result::Result<(), String> {
+    if skip_leading_trivia(sql).is_empty() {
+        return Err("SQL statement must not be empty.".to_string());
+    }
+    if has_multiple_statements(sql) {
+        return Err("Only a single SQL statement is allowed per call.".to_string());
+    }
+    if read_only && !READ_KEYWORDS.contains(&first_keyword(sql).as_str()) {
+        return Err(
+            "Only read statements (SELECT, WITH, EXPLAIN, SHOW, DESCRIBE, VALUES) are allowed in query. Use execute for writes."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Secret holding the URL of `connection`.
+fn connection_secret_key(connection: Option<&str>) -> std::result::Result<String, String> {
+    let Some(name) = connection.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(DEFAULT_CONNECTION_SECRET.to_string());
+    };
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!(
+            "Invalid connection name '{}'. Use letters, digits and underscores.",
+            name
+        ));
+    }
+    Ok(format!("{}_DATABASE_URL", name.to_ascii_uppercase()))
+}
+
+/// Remove the connection URL and its password from driver errors.
+fn sanitize_error(message: &str, url: &str) -> String {
+    let mut message = message.replace(url, "<connection url>");
+    if let Ok(parsed) = url::Url::parse(url)
+        && let Some(password) = parsed.password()
+        && !password.is_empty()
+    {
+        message = message.replace(password, "***");
+    }
+    message
+}
+
+fn rows_result(backend: Backend, rows: RowSet) -> Value {
+    json!({
+        "backend": backend.name(),
+        "columns": rows.columns,
+        "row_count": rows.rows.len(),
+        "rows": rows.rows,
+        "truncated": rows.truncated,
+    })
+}
+
+/// SQL query tool over SQLite, Postgres and MySQL connections.
+pub struct SqlTool {
+    secret_resolver: Option<SecretResolver>,
+    allow_write: bool,
+    security_gate: Option<Arc<dyn SecurityGate>>,
+    agent_id: Option<String>,
+    task_id: Option<String>,
+}
+
+impl Default for SqlTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqlTool {
+    pub fn new() -> Self {
+        Self {
+            secret_resolver: None,
+            allow_write: false,
+            security_gate: None,
+            agent_id: None,
+            task_id: None,
+        }
+    }
+
+    pub fn with_secret_resolver(mut self, resolver: SecretResolver) -> Self {
+        self.secret_resolver = Some(resolver);
+        self
+    }
+
+    /// Allow the `execute` operation (INSERT, UPDATE, DDL, ...).
+    pub fn with_write(mut self, allow_write: bool) -> Self {
+        self.allow_write = allow_write;
+        self
+    }
+
+    pub fn with_security(
+        mut self,
+        security_gate: Arc<dyn SecurityGate>,
+        agent_id: impl Into<String>,
+        task_id: impl Into<String>,
+    ) -> Self {
+        self.security_gate = Some(security_gate);
+        self.agent_id = Some(agent_id.into());
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    fn resolve_connection(
+        &self,
+        connection: Option<&str>,
+    ) -> std::result::Result<(Backend, String), String> {
+        let key = connection_secret_key(connection)?;
+        let url = self
+            .secret_resolver
+            .as_ref()
+            .and_then(|resolver| resolver(&key))
+            .filter(|url| !url.trim().is_empty())
+            .ok_or_else(|| {
+                format!(
+                    "Database connection secret {} is not set. Store the database URL with the manage_secrets tool.",
+                    key
+                )
+            })?;
+        let backend = Backend::from_url(&url)?;
+        Ok((backend, url))
+    }
+
+    async fn check_action_allowed(
+        &self,
+        operation: &str,
+        target: String,
+        summary: String,
+    ) -> Result<Option<String>> {
+        let action = ToolAction {
+            tool_name: self.name().to_string(),
+            operation: operation.to_string(),
+            target,
+            summary,
+        };
+
+        check_security(
+            self.security_gate.as_deref(),
+            action,
+            self.agent_id.as_deref(),
+            self.task_id.as_deref(),
+        )
+        .await
+    }
+
+    async fn run(
+        &self,
+        backend: Backend,
+        url: &str,
+        request: SqlRequest<'_>,
+    ) -> std::result::Result<SqlResponse, String> {
+        match tokio::time::timeout(QUERY_TIMEOUT, driver::run(backend, url, request)).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(e)) => Err(sanitize_error(
+                &format!("{} error: {}", backend.name(), e),
+                url,
+            )),
+            Err(_) => Err(format!(
+                "Query timed out after {} seconds",
+                QUERY_TIMEOUT.as_secs()
+            )),
+        }
+    }
+
+    async fn fetch_rows(
+        &self,
+        backend: Backend,
+        url: &str,
+        sql: &str,
+        params: &[Value],
+        limit: usize,
+    ) -> std::result::Result<Value, String> {
+        let request = SqlRequest {
+            sql,
+            params,
+            mode: RequestMode::Rows { limit },
+            read_only: true,
+        };
+        match self.run(backend, url, request).await? {
+            SqlResponse::Rows(rows) => Ok(rows_result(backend, rows)),
+            SqlResponse::Affected(_) => Err("Query returned no rows".to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SqlTool {
+    fn name(&self) -> &str {
+        "sql"
+    }
+
+    fn description(&self) -> &str {
+        "Query SQLite, Postgres or MySQL databases configured as secrets (DATABASE_URL or <NAME>_DATABASE_URL). Supports parameterized read queries with row limits, listing tables and describing columns. Writes through execute are disabled unless enabled for this agent."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["query", "execute", "list_tables", "describe_table"],
+                    "description": "query: run a read statement; execute: run a write statement (if enabled); list_tables; describe_table"
+                },
+                "connection": {
+                    "type": "string",
+                    "description": "Connection name. 'analytics' reads the ANALYTICS_DATABASE_URL secret; omit to use DATABASE_URL."
+                },
+                "sql": {
+                    "type": "string",
+                    "description": "A single SQL statement. Use placeholders ($1 for Postgres, ? for MySQL/SQLite) instead of inlining values."
+                },
+                "params": {
+                    "type": "array",
+                    "description": "Values bound to the statement placeholders, in order"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "query: maximum rows to return (default 100, max 1000)"
+                },
+                "table": { "type": "string", "description": "describe_table: table name" },
+                "schema": { "type": "string", "description": "describe_table: schema (Postgres only; defaults to the current schema)" }
+            },
+            "required": ["operation"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let input: SqlInput = match serde_json::from_value(input) {
+            Ok(input) => input,
+            Err(e) => {
+                return Ok(ToolOutput::error(format!(
+                    "Invalid input: {}. Required: operation (query|execute|list_tables|describe_table).",
+                    e
+                )));
+            }
+        };
+
+        if let SqlOperation::Execute { .. } = input.operation
+            && !self.allow_write
+        {
+            return Ok(ToolOutput::error(
+                "Writes are disabled: this SQL tool is read-only. Use query for read statements.",
+            ));
+        }
+
+        let (backend, url) = match self.resolve_connection(input.connection.as_deref()) {
+            Ok(connection) => connection,
+            Err(message) => return Ok(ToolOutput::error(message)),
+        };
+        let connection = input
+            .connection
+            .clone()
+            .unwrap_or_else(|| "default".to_string());
+
+        let result = match input.operation {
+            SqlOperation::Query { sql, params, limit } => {
+                if let Err(message) = validate_statement(&sql, true) {
+                    return Ok(ToolOutput::error(message));
+                }
+                let limit = limit.unwrap_or(DEFAULT_ROW_LIMIT).clamp(1, MAX_ROW_LIMIT);
+                self.fetch_rows(backend, &url, &sql, &params, limit).await
+            }
+            SqlOperation::Execute { sql, params } => {
+                if let Err(message) = validate_statement(&sql, false) {
+                    return Ok(ToolOutput::error(message));
+                }
+                let summary: String = sql.chars().take(200).collect();
+                if let Some(message) = self
+                    .check_action_allowed("execute", connection, summary)
+                    .await?
+                {
+                    return Ok(ToolOutput::error(message));
+                }
+                let request = SqlRequest {
+                    sql: &sql,
+                    params: &params,
+                    mode: RequestMode::Execute,
+                    read_only: false,
+                };
+                self.run(backend, &url, request)
+                    .await
+                    .map(|response| match response {
+                        SqlResponse::Affected(rows) => json!({
+                            "backend": backend.name(),
+                            "rows_affected": rows,
+                        }),
+                        SqlResponse::Rows(rows) => rows_result(backend, rows),
+                    })
+            }
+            SqlOperation::ListTables => {
+                self.fetch_rows(backend, &url, backend.list_tables_sql(), &[], MAX_ROW_LIMIT)
+                    .await
+            }
+            SqlOperation::DescribeTable { table, schema } => {
+                let mut params = vec![Value::String(table)];
+                if backend == Backend::Postgres {
+                    params.push(schema.map(Value::String).unwrap_or(Value::Null));
+                }
+                self.fetch_rows(
+                    backend,
+                    &url,
+                    backend.describe_table_sql(),
+                    &params,
+                    MAX_ROW_LIMIT,
+                )
+                .await
+            }
+        };
+
+        match result {
+            Ok(result) => Ok(ToolOutput::success(result)),
+            Err(message) => Ok(ToolOutput::error(message)),
+        }
+    }
+}
@@ -7,8 +7,8 @@ use std::sync::Arc;
 use crate::{Result, ToolError};
 use crate::{Tool, ToolOutput};
 use restflow_models::{
-    ProviderSelector, parse_model_reference, parse_provider_selector, resolve_available_model_name,
-    split_provider_qualified_model,
+    LocalBackend, ProviderSelector, parse_local_model, parse_model_reference,
+    parse_provider_selector, resolve_available_model_name, split_provider_qualified_model,
 };
 use restflow_traits::{LlmProvider, LlmSwitcher};
 
@@ -42,13 +42,29 @@ impl SwitchModelTool {
         }
 
         let provider_raw = requested_provider.expect("requested_provider checked above");
+        let model_raw = requested_model.expect("requested_model checked above");
+
+        // Local models are served by whatever the endpoint has pulled, not the catalog.
+        if let Some(backend) = LocalBackend::from_prefix(provider_raw) {
+            let model = match parse_local_model(model_raw) {
+                Some((inline_backend, model)) if inline_backend == backend => model,
+                Some(_) => {
+                    return Err(ToolError::Tool(format!(
+                        "Model '{model_raw}' does not belong to provider '{}'",
+                        backend.prefix()
+                    )));
+                }
+                None => model_raw,
+            };
+            return Ok(backend.reference(model));
+        }
+
         let provider = parse_provider_selector(provider_raw).ok_or_else(|| {
             ToolError::Tool(format!(
-                "Unknown provider: {provider_raw}. Use provider names like openai, anthropic, minimax, minimax-coding-plan, zai, zai-coding-plan, claude-code, openai-codex, gemini-cli"
+                "Unknown provider: {provider_raw}. Use provider names like openai, anthropic, minimax, minimax-coding-plan, zai, zai-coding-plan, google, bedrock, claude-code, openai-codex, gemini-cli, ollama, local"
             ))
         })?;
 
-        let model_raw = requested_model.expect("requested_model checked above");
         if let Some(model) = resolve_available_model_name(model_raw, &available) {
             if !self.model_matches_provider(&model, provider) {
                 return Err(ToolError::Tool(format!(
@@ -105,7 +121,7 @@ impl Tool for SwitchModelTool {
             "properties": {
                 "provider": {
                     "type": "string",
-                    "description": "Provider selector (e.g. openai, anthropic, claude-code, openai-codex, gemini-cli)"
+                    "description": "Provider selector (e.g. openai, anthropic, claude-code, openai-codex, gemini-cli, ollama, local)"
                 },
                 "model": {
                     "type": "string",
@@ -352,6 +368,35 @@ mod tests {
         assert_eq!(factory.calls(), vec![("gpt-5.3-codex".to_string(), None)]);
     }
 
+    #[tokio::test]
+    async fn execute_switches_to_local_ollama_model() {
+        let factory = Arc::new(MockFactory::new(
+            vec!["claude-sonnet-4-5"],
+            vec![
+                ("claude-sonnet-4-5", LlmProvider::Anthropic),
+                ("ollama:llama3.1:8b", LlmProvider::Ollama),
+            ],
+            vec![],
+            vec![],
+        ));
+        let (tool, llm) = build_tool(factory.clone());
+
+        let output = tool
+            .execute(json!({
+                "provider": "ollama",
+                "model": "llama3.1:8b"
+            }))
+            .await
+            .expect("switch should succeed");
+
+        assert!(output.success);
+        assert_eq!(llm.current_model(), "ollama:llama3.1:8b");
+        assert_eq!(
+            factory.calls(),
+            vec![("ollama:llama3.1:8b".to_string(), None)]
+        );
+    }
+
     #[tokio::test]
     async fn execute_rejects_provider_model_mismatch() {
         let factory = Arc::new(MockFactory::new(
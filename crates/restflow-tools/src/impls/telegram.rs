@@ -3,19 +3,34 @@
 use async_trait::async_trait;
 use reqwest::Client;
 use reqwest::StatusCode;
+use reqwest::multipart::{Form, Part};
 use serde::Deserialize;
 use serde_json::{Value, json};
+use std::path::Path;
 
 use crate::http_client::build_http_client;
 use crate::{Result, ToolError};
 use crate::{Tool, ToolOutput};
 
 const TELEGRAM_API_BASE: &str = "https://api.telegram.org";
+/// Telegram rejects callback data longer than 64 bytes.
+const MAX_CALLBACK_DATA_BYTES: usize = 64;
+const PHOTO_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
+
+#[derive(Debug, Deserialize)]
+struct TelegramButton {
+    text: String,
+    #[serde(default)]
+    callback_data: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+}
 
 #[derive(Debug, Deserialize)]
 struct TelegramInput {
     bot_token: String,
     chat_id: String,
+    #[serde(default)]
     message: String,
     #[serde(default)]
     parse_mode: Option<String>,
@@ -25,6 +40,55 @@ struct TelegramInput {
     disable_notification: bool,
     #[serde(default)]
     message_thread_id: Option<i64>,
+    #[serde(default)]
+    buttons: Vec<Vec<TelegramButton>>,
+    #[serde(default)]
+    edit_message_id: Option<i64>,
+    #[serde(default)]
+    file_path: Option<String>,
+}
+
+/// Build an `inline_keyboard` reply markup from button rows.
+fn inline_keyboard(rows: &[Vec<TelegramButton>]) -> std::result::Result<Value, String> {
+    let mut keyboard = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut buttons = Vec::with_capacity(row.len());
+        for button in row {
+            if button.text.trim().is_empty() {
+                return Err("Every button needs a non-empty 'text'".to_string());
+            }
+            let entry = match (&button.callback_data, &button.url) {
+                (Some(data), None) => {
+                    if data.is_empty() || data.len() > MAX_CALLBACK_DATA_BYTES {
+                        return Err(format!(
+                            "Button '{}' callback_data must be 1-{} bytes",
+                            button.text, MAX_CALLBACK_DATA_BYTES
+                        ));
+                    }
+                    json!({ "text": button.text, "callback_data": data })
+                }
+                (None, Some(url)) => json!({ "text": button.text, "url": url }),
+                _ => {
+                    return Err(format!(
+                        "Button '{}' needs exactly one of 'callback_data' or 'url'",
+                        button.text
+                    ));
+                }
+            };
+            buttons.push(entry);
+        }
+        if !buttons.is_empty() {
+            keyboard.push(Value::Array(buttons));
+        }
+    }
+    Ok(json!({ "inline_keyboard": keyboard }))
+}
+
+fn is_photo_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| PHOTO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
 }
 
 /// Telegram notification tool for sending messages via Bot API.
@@ -78,13 +142,28 @@ impl TelegramTool {
         )
     }
 
-    async fn send_message(&self, input: &TelegramInput) -> Result<Value> {
-        let url = Self::api_url(&input.bot_token, "sendMessage");
+    async fn send_message(
+        &self,
+        input: &TelegramInput,
+        reply_markup: Option<Value>,
+    ) -> Result<Value> {
+        let method = if input.edit_message_id.is_some() {
+            "editMessageText"
+        } else {
+            "sendMessage"
+        };
+        let url = Self::api_url(&input.bot_token, method);
 
         let mut payload = json!({
             "chat_id": input.chat_id,
             "text": input.message,
         });
+        if let Some(message_id) = input.edit_message_id {
+            payload["message_id"] = json!(message_id);
+        }
+        if let Some(markup) = reply_markup {
+            payload["reply_markup"] = markup;
+        }
 
         if let Some(ref parse_mode) = input.parse_mode {
             payload["parse_mode"] = json!(parse_mode);
@@ -95,7 +174,9 @@ impl TelegramTool {
         if input.disable_notification {
             payload["disable_notification"] = json!(true);
         }
-        if let Some(thread_id) = input.message_thread_id {
+        if let Some(thread_id) = input.message_thread_id
+            && input.edit_message_id.is_none()
+        {
             payload["message_thread_id"] = json!(thread_id);
         }
 
@@ -107,6 +188,63 @@ impl TelegramTool {
             .await
             .map_err(|e| ToolError::Tool(Self::sanitize_request_error(&e, &input.bot_token)))?;
 
+        self.parse_response(response, &input.bot_token).await
+    }
+
+    /// Upload a local file as a photo (image extensions) or document, using
+    /// `message` as the caption.
+    async fn send_file(
+        &self,
+        input: &TelegramInput,
+        file_path: &str,
+        reply_markup: Option<Value>,
+    ) -> Result<Value> {
+        let path = Path::new(file_path);
+        let (method, field) = if is_photo_path(path) {
+            ("sendPhoto", "photo")
+        } else {
+            ("sendDocument", "document")
+        };
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| ToolError::Tool(format!("Failed to read '{}': {}", file_path, e)))?;
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file")
+            .to_string();
+
+        let mut form = Form::new()
+            .text("chat_id", input.chat_id.clone())
+            .part(field, Part::bytes(bytes).file_name(file_name));
+        if !input.message.is_empty() {
+            form = form.text("caption", input.message.clone());
+        }
+        if let Some(ref parse_mode) = input.parse_mode {
+            form = form.text("parse_mode", parse_mode.clone());
+        }
+        if input.disable_notification {
+            form = form.text("disable_notification", "true");
+        }
+        if let Some(thread_id) = input.message_thread_id {
+            form = form.text("message_thread_id", thread_id.to_string());
+        }
+        if let Some(markup) = reply_markup {
+            form = form.text("reply_markup", markup.to_string());
+        }
+
+        let response = self
+            .client
+            .post(Self::api_url(&input.bot_token, method))
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| ToolError::Tool(Self::sanitize_request_error(&e, &input.bot_token)))?;
+
+        self.parse_response(response, &input.bot_token).await
+    }
+
+    async fn parse_response(&self, response: reqwest::Response, bot_token: &str) -> Result<Value> {
         let status = response.status();
         let body: Value = response
             .json()
@@ -117,9 +255,7 @@ impl TelegramTool {
             Ok(body)
         } else {
             Err(ToolError::Tool(Self::format_api_error(
-                status,
-                &body,
-                &input.bot_token,
+                status, &body, bot_token,
             )))
         }
     }
@@ -132,7 +268,7 @@ impl Tool for TelegramTool {
     }
 
     fn description(&self) -> &str {
-        "Send Telegram Bot API messages to a chat with optional formatting and notification flags. This is the PRIMARY Telegram tool."
+        "Send Telegram Bot API messages to a chat with optional formatting, inline keyboard buttons, photo/document attachments, or edits to a previously sent message. This is the PRIMARY Telegram tool."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -149,7 +285,7 @@ impl Tool for TelegramTool {
                 },
                 "message": {
                     "type": "string",
-                    "description": "Message text to send"
+                    "description": "Message text to send (caption when file_path is set)"
                 },
                 "parse_mode": {
                     "type": "string",
@@ -167,9 +303,33 @@ impl Tool for TelegramTool {
                 "message_thread_id": {
                     "type": "integer",
                     "description": "Unique identifier for the target message thread (topic) in a forum or supergroup"
+                },
+                "buttons": {
+                    "type": "array",
+                    "description": "Inline keyboard rows. Pressing a callback_data button sends that text back to the bot as a user message (e.g. '/approve <task_id>' or an option label).",
+                    "items": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "text": { "type": "string" },
+                                "callback_data": { "type": "string", "description": "At most 64 bytes" },
+                                "url": { "type": "string" }
+                            },
+                            "required": ["text"]
+                        }
+                    }
+                },
+                "edit_message_id": {
+                    "type": "integer",
+                    "description": "Edit this previously sent message instead of sending a new one"
+                },
+                "file_path": {
+                    "type": "string",
+                    "description": "Local file to send; images are sent as photos, anything else as a document"
                 }
             },
-            "required": ["bot_token", "chat_id", "message"]
+            "required": ["bot_token", "chat_id"]
         })
     }
 
@@ -185,11 +345,28 @@ impl Tool for TelegramTool {
         if params.chat_id.is_empty() {
             return Ok(ToolOutput::error("chat_id is required"));
         }
-        if params.message.is_empty() {
+        if params.message.is_empty() && params.file_path.is_none() {
             return Ok(ToolOutput::error("message is required"));
         }
+        if params.edit_message_id.is_some() && params.file_path.is_some() {
+            return Ok(ToolOutput::error(
+                "edit_message_id cannot be combined with file_path",
+            ));
+        }
+        let reply_markup = if params.buttons.is_empty() {
+            None
+        } else {
+            match inline_keyboard(&params.buttons) {
+                Ok(markup) => Some(markup),
+                Err(e) => return Ok(ToolOutput::error(e)),
+            }
+        };
 
-        match self.send_message(&params).await {
+        let result = match params.file_path.as_deref() {
+            Some(file_path) => self.send_file(&params, file_path, reply_markup).await,
+            None => self.send_message(&params, reply_markup).await,
+        };
+        match result {
             Ok(response) => {
                 let message_id = response
                     .get("result")
@@ -198,6 +375,7 @@ impl Tool for TelegramTool {
 
                 Ok(ToolOutput::success(json!({
                     "sent": true,
+                    "edited": params.edit_message_id.is_some(),
                     "message_id": message_id,
                     "chat_id": params.chat_id
                 })))
@@ -273,6 +451,62 @@ mod tests {
         assert!(result.error.unwrap().contains("bot_token is required"));
     }
 
+    #[tokio::test]
+    async fn test_telegram_tool_rejects_invalid_buttons() {
+        let tool = TelegramTool::new().unwrap();
+        let result = tool
+            .execute(json!({
+                "bot_token": "123:ABC",
+                "chat_id": "123",
+                "message": "Pick one",
+                "buttons": [[{ "text": "Both", "callback_data": "a", "url": "https://example.com" }]]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("exactly one"));
+
+        let result = tool
+            .execute(json!({
+                "bot_token": "123:ABC",
+                "chat_id": "123",
+                "message": "Pick one",
+                "buttons": [[{ "text": "Long", "callback_data": "x".repeat(65) }]]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("64 bytes"));
+    }
+
+    #[test]
+    fn test_inline_keyboard_markup() {
+        let rows: Vec<Vec<TelegramButton>> = serde_json::from_value(json!([
+            [
+                { "text": "Approve", "callback_data": "/approve task-1" },
+                { "text": "Deny", "callback_data": "/deny task-1" }
+            ],
+            [{ "text": "Docs", "url": "https://example.com" }]
+        ]))
+        .unwrap();
+        let markup = inline_keyboard(&rows).unwrap();
+        assert_eq!(
+            markup["inline_keyboard"][0][1],
+            json!({ "text": "Deny", "callback_data": "/deny task-1" })
+        );
+        assert_eq!(
+            markup["inline_keyboard"][1][0]["url"],
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn test_is_photo_path() {
+        assert!(is_photo_path(Path::new("/tmp/chart.PNG")));
+        assert!(!is_photo_path(Path::new("/tmp/report.pdf")));
+        assert!(!is_photo_path(Path::new("/tmp/noext")));
+    }
+
     #[test]
     fn test_telegram_api_error_masks_token() {
         let body = json!({
@@ -48,7 +48,7 @@ impl Default for TranscribeConfig {
     }
 }
 
-fn ensure_default_media_dir() -> Option<PathBuf> {
+pub(crate) fn ensure_default_media_dir() -> Option<PathBuf> {
     let restflow_dir = std::env::var("RESTFLOW_DIR")
         .ok()
         .filter(|dir| !dir.trim().is_empty())
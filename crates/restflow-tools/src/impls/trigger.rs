@@ -50,7 +50,7 @@ impl Tool for TriggerTool {
     }
 
     fn description(&self) -> &str {
-        "Create/list/enable/disable workflow triggers."
+        "Create/list/enable/disable workflow triggers and scheduled digests."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -65,7 +65,7 @@ impl Tool for TriggerTool {
                 "workflow_id": { "type": "string" },
                 "trigger_config": {
                     "type": "object",
-                    "description": "TriggerConfig payload with a `type` discriminator (manual/webhook/schedule)."
+                    "description": "TriggerConfig payload with a `type` discriminator (manual/webhook/schedule/browser_schedule/digest). A digest sends a periodic summary to the notification channel: {type: 'digest', cron, timezone, digest: 'task_progress'|'memory_highlights', agent_id?}."
                 }
             },
             "required": ["operation"]
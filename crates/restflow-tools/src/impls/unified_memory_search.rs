@@ -8,6 +8,8 @@ use crate::{Result, ToolError};
 use crate::{Tool, ToolOutput};
 use restflow_traits::store::UnifiedMemorySearch;
 
+const SEARCH_MODES: &[&str] = &["keyword", "phrase", "regex", "semantic", "hybrid"];
+
 pub struct UnifiedMemorySearchTool {
     search: Arc<dyn UnifiedMemorySearch>,
 }
@@ -40,6 +42,11 @@ impl Tool for UnifiedMemorySearchTool {
                     "type": "string",
                     "description": "Agent ID to search within"
                 },
+                "mode": {
+                    "type": "string",
+                    "enum": SEARCH_MODES,
+                    "description": "How memory is matched: keyword, phrase, regex, semantic (meaning-based), or hybrid (keyword + semantic). Defaults to hybrid when semantic recall is configured, otherwise keyword"
+                },
                 "include_sessions": {
                     "type": "boolean",
                     "description": "Whether to search chat sessions",
@@ -71,6 +78,15 @@ impl Tool for UnifiedMemorySearchTool {
             .get("agent_id")
             .and_then(|v| v.as_str())
             .ok_or_else(|| ToolError::Tool("Missing agent_id parameter".to_string()))?;
+        let mode = input.get("mode").and_then(|v| v.as_str());
+        if let Some(mode) = mode
+            && !SEARCH_MODES.contains(&mode)
+        {
+            return Err(ToolError::Tool(format!(
+                "Invalid mode '{mode}'; expected one of: {}",
+                SEARCH_MODES.join(", ")
+            )));
+        }
         let include_sessions = input
             .get("include_sessions")
             .and_then(|v| v.as_bool())
@@ -88,7 +104,7 @@ impl Tool for UnifiedMemorySearchTool {
 
         let results = self
             .search
-            .search(agent_id, query, include_sessions, limit, offset)?;
+            .search(agent_id, query, mode, include_sessions, limit, offset)?;
         Ok(ToolOutput::success(results))
     }
 }
@@ -0,0 +1,196 @@
+//! Tool for checking an agent's LLM spend against a budget.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+use crate::Result;
+use crate::{Tool, ToolOutput};
+use restflow_traits::store::UsageStore;
+
+#[derive(Clone)]
+pub struct UsageBudgetTool {
+    store: Arc<dyn UsageStore>,
+    default_agent_id: Option<String>,
+}
+
+impl UsageBudgetTool {
+    pub fn new(store: Arc<dyn UsageStore>) -> Self {
+        Self {
+            store,
+            default_agent_id: None,
+        }
+    }
+
+    /// Check this agent's spend when the caller does not pass `agent_id`.
+    pub fn with_default_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.default_agent_id = Some(agent_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageBudgetInput {
+    #[serde(default)]
+    agent_id: Option<String>,
+    #[serde(default)]
+    period: Option<String>,
+    #[serde(default)]
+    budget_usd: Option<f64>,
+}
+
+fn period_start(period: &str) -> Option<i64> {
+    let now = Utc::now();
+    let start = match period {
+        "day" => now - Duration::days(1),
+        "week" => now - Duration::days(7),
+        "month" => now - Duration::days(30),
+        "all" => return Some(0),
+        _ => return None,
+    };
+    Some(start.timestamp_millis())
+}
+
+#[async_trait]
+impl Tool for UsageBudgetTool {
+    fn name(&self) -> &str {
+        "check_usage_budget"
+    }
+
+    fn description(&self) -> &str {
+        "Check how many tokens and USD an agent has spent on LLM calls over a period, and whether that exceeds a budget. Call before expensive work to stay within a spending cap."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "agent_id": {
+                    "type": "string",
+                    "description": "Agent to check. Defaults to the current agent."
+                },
+                "period": {
+                    "type": "string",
+                    "enum": ["day", "week", "month", "all"],
+                    "description": "Trailing window to sum spend over (default: day)"
+                },
+                "budget_usd": {
+                    "type": "number",
+                    "description": "Spending cap in USD to compare against"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let params: UsageBudgetInput = match serde_json::from_value(input) {
+            Ok(p) => p,
+            Err(e) => return Ok(ToolOutput::error(format!("Invalid input: {}", e))),
+        };
+
+        let Some(agent_id) = params
+            .agent_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .or(self.default_agent_id.as_deref())
+        else {
+            return Ok(ToolOutput::error("agent_id is required"));
+        };
+
+        let period = params.period.as_deref().unwrap_or("day");
+        let Some(since_ms) = period_start(period) else {
+            return Ok(ToolOutput::error(format!(
+                "Invalid period '{}'. Supported: day, week, month, all",
+                period
+            )));
+        };
+
+        if let Some(budget) = params.budget_usd
+            && (!budget.is_finite() || budget < 0.0)
+        {
+            return Ok(ToolOutput::error(
+                "budget_usd must be a non-negative number",
+            ));
+        }
+
+        let result = self
+            .store
+            .budget_status(agent_id, since_ms, params.budget_usd)?;
+        Ok(ToolOutput::success(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockUsageStore;
+
+    impl UsageStore for MockUsageStore {
+        fn budget_status(
+            &self,
+            agent_id: &str,
+            since_ms: i64,
+            budget_usd: Option<f64>,
+        ) -> Result<Value> {
+            Ok(json!({
+                "agent_id": agent_id,
+                "since": since_ms,
+                "budget_usd": budget_usd,
+                "exceeded": budget_usd.is_some_and(|budget| budget <= 2.0),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_defaults_to_current_agent() {
+        let tool = UsageBudgetTool::new(Arc::new(MockUsageStore)).with_default_agent("agent-1");
+        let out = tool
+            .execute(json!({ "budget_usd": 1.5 }))
+            .await
+            .expect("tool call should succeed");
+        assert!(out.success);
+        assert_eq!(out.result["agent_id"], "agent-1");
+        assert_eq!(out.result["exceeded"], true);
+    }
+
+    #[tokio::test]
+    async fn test_all_period_starts_at_epoch() {
+        let tool = UsageBudgetTool::new(Arc::new(MockUsageStore));
+        let out = tool
+            .execute(json!({ "agent_id": "agent-2", "period": "all" }))
+            .await
+            .expect("tool call should succeed");
+        assert!(out.success);
+        assert_eq!(out.result["since"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_requires_agent_without_default() {
+        let tool = UsageBudgetTool::new(Arc::new(MockUsageStore));
+        let out = tool
+            .execute(json!({}))
+            .await
+            .expect("tool call should succeed");
+        assert!(!out.success);
+        assert!(out.error.expect("error expected").contains("agent_id"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_period() {
+        let tool = UsageBudgetTool::new(Arc::new(MockUsageStore)).with_default_agent("agent-1");
+        let out = tool
+            .execute(json!({ "period": "year" }))
+            .await
+            .expect("tool call should succeed");
+        assert!(!out.success);
+        assert!(
+            out.error
+                .expect("error expected")
+                .contains("Invalid period")
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Tool for running saved browser action plans by name.
+//!
+//! Saved plans are versioned `run_actions` plans stored by the user. Running
+//! one renders its `${payload.<path>}` placeholders and executes the actions
+//! either in an existing browser session or in a throwaway one.
+
+use async_trait::async_trait;
+use restflow_browser::{BrowserAction, BrowserService, NewSessionRequest, RunActionsRequest};
+use restflow_traits::DEFAULT_AGENT_BROWSER_TIMEOUT_SECS;
+use restflow_traits::store::BrowserPlanStore;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+use crate::Result;
+use crate::{Tool, ToolErrorCategory, ToolOutput};
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum UsePlanOperation {
+    List,
+    Run {
+        name: String,
+        #[serde(default)]
+        version: Option<u32>,
+        #[serde(default)]
+        payload: Option<Value>,
+        #[serde(default)]
+        session_id: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct ResolvedPlan {
+    name: String,
+    version: u32,
+    actions: Vec<BrowserAction>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+pub struct UsePlanTool {
+    store: Arc<dyn BrowserPlanStore>,
+    service: Arc<BrowserService>,
+    default_timeout_secs: u64,
+}
+
+impl UsePlanTool {
+    pub fn new(store: Arc<dyn BrowserPlanStore>, service: Arc<BrowserService>) -> Self {
+        Self {
+            store,
+            service,
+            default_timeout_secs: DEFAULT_AGENT_BROWSER_TIMEOUT_SECS,
+        }
+    }
+
+    pub fn with_default_timeout(mut self, timeout_secs: u64) -> Self {
+        self.default_timeout_secs = timeout_secs;
+        self
+    }
+
+    async fn run_plan(&self, plan: ResolvedPlan, session_id: Option<String>) -> Result<ToolOutput> {
+        if let Err(error) = self
+            .service
+            .navigation_policy()
+            .check_actions(&plan.actions)
+            .await
+        {
+            return Ok(ToolOutput::non_retryable_error(
+                error.to_string(),
+                ToolErrorCategory::Auth,
+            ));
+        }
+
+        let (session_id, throwaway) = match session_id {
+            Some(session_id) => (session_id, false),
+            None => (
+                self.service
+                    .new_session(NewSessionRequest::default())
+                    .await?
+                    .id,
+                true,
+            ),
+        };
+        let execution = self
+            .service
+            .run_actions(&RunActionsRequest {
+                session_id: session_id.clone(),
+                actions: plan.actions,
+                runtime: Default::default(),
+                timeout_secs: plan.timeout_secs.unwrap_or(self.default_timeout_secs),
+                cwd: None,
+            })
+            .await;
+        if throwaway && let Err(error) = self.service.close_session(&session_id).await {
+            tracing::warn!(session_id = %session_id, error = %error, "Failed to close plan browser session");
+        }
+        let execution = execution?;
+
+        if execution.exit_code != 0 {
+            let mut output = ToolOutput::non_retryable_error(
+                execution.failed_message(),
+                ToolErrorCategory::Execution,
+            );
+            output.result = serde_json::to_value(&execution)?;
+            return Ok(output);
+        }
+
+        let results = execution
+            .payload
+            .as_ref()
+            .and_then(|payload| payload.get("result"))
+            .cloned()
+            .unwrap_or_else(|| json!([]));
+        Ok(ToolOutput::success(json!({
+            "name": plan.name,
+            "version": plan.version,
+            "duration_ms": execution.duration_ms,
+            "results": results,
+        })))
+    }
+}
+
+#[async_trait]
+impl Tool for UsePlanTool {
+    fn name(&self) -> &str {
+        "use_plan"
+    }
+
+    fn description(&self) -> &str {
+        "Run a saved browser action plan by name instead of writing the actions again. Operations: list (saved plans with their latest version), run (render the plan with an optional payload and execute it; pass session_id to reuse a browser session, otherwise a throwaway session is used)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "enum": ["list", "run"]
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Saved plan name (required for run)."
+                },
+                "version": {
+                    "type": "integer",
+                    "description": "Plan version to run, defaults to the latest."
+                },
+                "payload": {
+                    "type": "object",
+                    "description": "Values for `${payload.<path>}` placeholders in the plan."
+                },
+                "session_id": {
+                    "type": "string",
+                    "description": "Existing browser session to run the plan in."
+                }
+            },
+            "required": ["operation"]
+        })
+    }
+
+    async fn execute(&self, input: Value) -> Result<ToolOutput> {
+        let operation: UsePlanOperation = serde_json::from_value(input)?;
+        match operation {
+            UsePlanOperation::List => Ok(ToolOutput::success(self.store.list_plans()?)),
+            UsePlanOperation::Run {
+                name,
+                version,
+                payload,
+                session_id,
+            } => {
+                let payload = payload.unwrap_or_else(|| json!({}));
+                let resolved = self.store.resolve_plan(&name, version, &payload)?;
+                let plan: ResolvedPlan = serde_json::from_value(resolved)?;
+                self.run_plan(plan, session_id).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use restflow_browser::{
+        BrowserExecutionResult, BrowserExecutor, BrowserSession, NavigationPolicy,
+        RunScriptRequest, RuntimeProbe,
+    };
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    struct MockStore;
+
+    impl BrowserPlanStore for MockStore {
+        fn list_plans(&self) -> Result<Value> {
+            Ok(json!([{ "name": "prices", "version": 2 }]))
+        }
+
+        fn resolve_plan(&self, name: &str, version: Option<u32>, payload: &Value) -> Result<Value> {
+            Ok(json!({
+                "name": name,
+                "version": version.unwrap_or(2),
+                "actions": [{ "type": "navigate", "url": payload["url"] }],
+                "timeout_secs": 5
+            }))
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        requests: Mutex<Vec<RunActionsRequest>>,
+    }
+
+    #[async_trait]
+    impl BrowserExecutor for RecordingExecutor {
+        async fn probe_runtime(&self) -> anyhow::Result<RuntimeProbe> {
+            anyhow::bail!("unexpected runtime probe")
+        }
+
+        async fn run_script(
+            &self,
+            _session: &BrowserSession,
+            _request: &RunScriptRequest,
+        ) -> anyhow::Result<BrowserExecutionResult> {
+            anyhow::bail!("unexpected script run")
+        }
+
+        async fn run_actions(
+            &self,
+            _session: &BrowserSession,
+            request: &RunActionsRequest,
+        ) -> anyhow::Result<BrowserExecutionResult> {
+            self.requests.lock().unwrap().push(request.clone());
+            Ok(BrowserExecutionResult {
+                runtime: "mock".to_string(),
+                exit_code: 0,
+                duration_ms: 3,
+                stdout: String::new(),
+                stderr: String::new(),
+                payload: Some(json!({ "success": true, "result": [{ "type": "navigate" }] })),
+                trace_path: None,
+            })
+        }
+    }
+
+    fn test_tool(
+        executor: Arc<RecordingExecutor>,
+        navigation: NavigationPolicy,
+    ) -> (UsePlanTool, Arc<BrowserService>, tempfile::TempDir) {
+        let temp = tempdir().unwrap();
+        let service = Arc::new(
+            BrowserService::new_with_executor(temp.path().join("browser"), executor)
+                .unwrap()
+                .with_navigation_policy(navigation),
+        );
+        (
+            UsePlanTool::new(Arc::new(MockStore), service.clone()),
+            service,
+            temp,
+        )
+    }
+
+    #[tokio::test]
+    async fn run_renders_plan_in_throwaway_session() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let (tool, service, _temp) = test_tool(executor.clone(), NavigationPolicy::default());
+
+        let output = tool
+            .execute(json!({
+                "operation": "run",
+                "name": "prices",
+                "payload": { "url": "https://example.com" }
+            }))
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        assert_eq!(output.result["version"], 2);
+        assert_eq!(output.result["results"][0]["type"], "navigate");
+        {
+            let requests = executor.requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].timeout_secs, 5);
+        }
+        assert!(service.list_sessions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_is_blocked_by_navigation_policy() {
+        let executor = Arc::new(RecordingExecutor::default());
+        let (tool, _service, _temp) = test_tool(
+            executor.clone(),
+            NavigationPolicy {
+                denied_domains: vec!["example.com".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let output = tool
+            .execute(json!({
+                "operation": "run",
+                "name": "prices",
+                "payload": { "url": "https://example.com" }
+            }))
+            .await
+            .unwrap();
+
+        assert!(!output.success);
+        assert_eq!(output.error_category, Some(ToolErrorCategory::Auth));
+        assert!(executor.requests.lock().unwrap().is_empty());
+    }
+}
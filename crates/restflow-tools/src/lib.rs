@@ -21,7 +21,8 @@ pub mod skill;
 pub use restflow_traits::error::{Result, ToolError};
 pub use restflow_traits::registry::ToolRegistry;
 pub use restflow_traits::tool::{
-    SecretResolver, Tool, ToolErrorCategory, ToolOutput, ToolSchema, check_security,
+    SecretResolver, Tool, ToolErrorCategory, ToolOutput, ToolOutputSink, ToolSchema,
+    check_security, emit_tool_output, with_tool_output_sink,
 };
 pub use restflow_traits::toolset::{Toolset, ToolsetContext};
 pub use restflow_traits::wrapper::{RateLimitWrapper, TimeoutWrapper, ToolWrapper, WrappedTool};
@@ -37,6 +38,8 @@ pub use restflow_traits::security::{SecurityDecision, SecurityGate, ToolAction};
 
 // Re-export tool implementations (original 7)
 pub use impls::BrowserTool;
+pub use restflow_browser::{ArtifactRetentionPolicy, NavigationPolicy};
+pub use restflow_sandbox::{NetworkEndpoint, NetworkPolicy, ResourceLimits, SandboxPolicy};
 pub use impls::telegram::send_telegram_notification;
 pub use impls::{BashTool, DiscordTool, EmailTool, FileTool, HttpTool, SlackTool, TelegramTool};
 
@@ -45,12 +48,12 @@ pub use impls::{EditTool, MultiEditTool};
 
 // Re-export migrated tool implementations
 pub use impls::{
-    AgentCrudTool, AuthProfileTool, ConfigTool, DeleteMemoryTool, DiagnosticsTool, JinaReaderTool,
-    ListMemoryTool, MemoryManagementTool, PatchTool, ProcessTool, PythonExecutionBackend,
+    AgentCrudTool, ApiConnectorTool, AuthProfileTool, CalendarTool, ConfigTool, ContainerConfig, ContainerEngine, DeleteMemoryTool, DeliverableBuilderTool,
+    DiagnosticsTool, GitHubTool, JinaReaderTool, ListMemoryTool, MemoryManagementTool, PatchTool, ProcessPythonBackend, ProcessTool, PythonExecutionBackend,
     PythonExecutionLimits, PythonTool, ReadMemoryTool, ReplyTool, RunPythonTool,
-    SaveDeliverableTool, SaveMemoryTool, SecretGetPolicy, SecretsTool, SessionTool, SkillTool,
-    SwitchModelTool, TaskTool, TranscribeConfig, TranscribeTool, VisionTool, WebFetchTool,
-    WebSearchTool, WorkItemTool,
+    SaveDeliverableTool, SaveMemoryTool, SecretGetPolicy, SecretsTool, SessionTool, ShellSessionTool, SkillTool, SpeakTool, SpreadsheetTool, SqlTool,
+    SwitchModelTool, TaskTool, TranscribeConfig, TranscribeTool, UsageBudgetTool, VisionTool,
+    WebFetchTool, WebSearchTool, WorkItemTool,
 };
 
 // Re-export tool_registry inline migrated tools
@@ -60,15 +63,15 @@ pub use impls::{
 };
 
 // Re-export search tools
-pub use impls::{GlobTool, GrepTool, TaskListTool};
+pub use impls::{GitTool, GlobTool, GrepTool, TaskListTool};
 
 // Re-export batch tool
 pub use impls::BatchTool;
 
 // Re-export core-migrated tools
 pub use impls::{
-    BashConfig, FileConfig, ListSubagentsTool, SecretsConfig, SpawnSubagentTool, SpawnTool,
-    ToolRegistryBuilder, UseSkillTool, WaitSubagentsTool, default_registry,
+    BashConfig, FanoutTool, FileConfig, ListSubagentsTool, SecretsConfig, SpawnSubagentTool, SpawnTool,
+    ToolRegistryBuilder, UsePlanTool, UseSkillTool, WaitSubagentsTool, default_registry,
 };
 
 // Legacy compatibility exports.
@@ -18,6 +18,8 @@ const DEFAULT_BACKGROUND_TASK_RETENTION_DAYS: u32 = 7;
 const DEFAULT_CHECKPOINT_RETENTION_DAYS: u32 = 3;
 const DEFAULT_MEMORY_CHUNK_RETENTION_DAYS: u32 = 90;
 const DEFAULT_LOG_FILE_RETENTION_DAYS: u32 = 30;
+const DEFAULT_ARTIFACT_RETENTION_DAYS: u32 = 14;
+const DEFAULT_ARTIFACT_MAX_TOTAL_MB: u64 = 2048;
 const DEFAULT_MEMORY_SEARCH_LIMIT: u32 = 10;
 const DEFAULT_SESSION_LIST_LIMIT: u32 = 20;
 
@@ -106,6 +108,14 @@ pub struct SystemSection {
     pub checkpoint_retention_days: u32,
     pub memory_chunk_retention_days: u32,
     pub log_file_retention_days: u32,
+    pub artifact_retention_days: u32,
+    pub artifact_max_total_mb: u64,
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
     pub experimental_features: Vec<String>,
 }
 
@@ -123,6 +133,11 @@ impl Default for SystemSection {
             checkpoint_retention_days: DEFAULT_CHECKPOINT_RETENTION_DAYS,
             memory_chunk_retention_days: DEFAULT_MEMORY_CHUNK_RETENTION_DAYS,
             log_file_retention_days: DEFAULT_LOG_FILE_RETENTION_DAYS,
+            artifact_retention_days: DEFAULT_ARTIFACT_RETENTION_DAYS,
+            artifact_max_total_mb: DEFAULT_ARTIFACT_MAX_TOTAL_MB,
+            daily_budget_usd: None,
+            monthly_budget_usd: None,
+            otlp_endpoint: None,
             experimental_features: Vec::new(),
         }
     }
@@ -142,6 +157,11 @@ impl From<&SystemConfig> for SystemSection {
             checkpoint_retention_days: config.checkpoint_retention_days,
             memory_chunk_retention_days: config.memory_chunk_retention_days,
             log_file_retention_days: config.log_file_retention_days,
+            artifact_retention_days: config.artifact_retention_days,
+            artifact_max_total_mb: config.artifact_max_total_mb,
+            daily_budget_usd: config.daily_budget_usd,
+            monthly_budget_usd: config.monthly_budget_usd,
+            otlp_endpoint: config.otlp_endpoint.clone(),
             experimental_features: config.experimental_features.clone(),
         }
     }
@@ -158,6 +178,20 @@ pub struct AgentDefaults {
     pub bash_timeout_secs: u64,
     pub python_timeout_secs: u64,
     pub browser_timeout_secs: u64,
+    pub browser_allowed_domains: Vec<String>,
+    pub browser_denied_domains: Vec<String>,
+    pub browser_block_private_networks: bool,
+    pub browser_artifact_max_session_bytes: u64,
+    pub browser_artifact_max_total_bytes: u64,
+    pub browser_artifact_max_age_secs: u64,
+    pub subprocess_memory_limit_mb: u64,
+    pub subprocess_cpu_weight: u32,
+    pub subprocess_max_processes: u32,
+    pub subprocess_cpu_time_secs: u64,
+    pub container_runtime: Option<String>,
+    pub container_image: String,
+    pub container_network: bool,
+    pub subprocess_network_allow: Option<Vec<String>>,
     pub process_session_ttl_secs: u64,
     pub approval_timeout_secs: u64,
     pub max_iterations: usize,
@@ -186,6 +220,20 @@ impl Default for AgentDefaults {
             bash_timeout_secs: DEFAULT_AGENT_BASH_TIMEOUT_SECS,
             python_timeout_secs: DEFAULT_AGENT_PYTHON_TIMEOUT_SECS,
             browser_timeout_secs: DEFAULT_AGENT_BROWSER_TIMEOUT_SECS,
+            browser_allowed_domains: Vec::new(),
+            browser_denied_domains: Vec::new(),
+            browser_block_private_networks: false,
+            browser_artifact_max_session_bytes: 0,
+            browser_artifact_max_total_bytes: 0,
+            browser_artifact_max_age_secs: 0,
+            subprocess_memory_limit_mb: 0,
+            subprocess_cpu_weight: 0,
+            subprocess_max_processes: 0,
+            subprocess_cpu_time_secs: 0,
+            container_runtime: None,
+            container_image: DEFAULT_AGENT_CONTAINER_IMAGE.to_string(),
+            container_network: false,
+            subprocess_network_allow: None,
             process_session_ttl_secs: DEFAULT_PROCESS_SESSION_TTL_SECS,
             approval_timeout_secs: DEFAULT_AGENT_APPROVAL_TIMEOUT_SECS,
             max_iterations: DEFAULT_AGENT_MAX_ITERATIONS,
@@ -246,6 +294,7 @@ impl Default for ApiDefaults {
 pub struct RuntimeDefaults {
     pub background_runner_poll_interval_ms: u64,
     pub background_runner_max_concurrent_tasks: usize,
+    pub background_runner_auto_resume: bool,
     pub chat_max_session_history: usize,
 }
 
@@ -256,6 +305,7 @@ impl Default for RuntimeDefaults {
         Self {
             background_runner_poll_interval_ms: DEFAULT_BACKGROUND_RUNNER_POLL_INTERVAL_MS,
             background_runner_max_concurrent_tasks: DEFAULT_BACKGROUND_RUNNER_MAX_CONCURRENT_TASKS,
+            background_runner_auto_resume: false,
             chat_max_session_history: DEFAULT_CHAT_MAX_SESSION_HISTORY,
         }
     }
@@ -269,6 +319,8 @@ impl Default for RuntimeDefaults {
 pub struct ChannelDefaults {
     pub telegram_api_timeout_secs: u64,
     pub telegram_polling_timeout_secs: u32,
+    pub voice_replies: bool,
+    pub webhooks: Vec<WebhookSinkConfig>,
 }
 
 pub type ChannelSettings = ChannelDefaults;
@@ -278,10 +330,22 @@ impl Default for ChannelDefaults {
         Self {
             telegram_api_timeout_secs: DEFAULT_TELEGRAM_API_TIMEOUT_SECS,
             telegram_polling_timeout_secs: DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS,
+            voice_replies: false,
+            webhooks: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct WebhookSinkConfig {
+    pub url: String,
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
 // ── RegistryDefaults ─────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -290,6 +354,8 @@ impl Default for ChannelDefaults {
 pub struct RegistryDefaults {
     pub github_cache_ttl_secs: u64,
     pub marketplace_cache_ttl_secs: u64,
+    pub trusted_skill_keys: Vec<String>,
+    pub require_signed_skills: bool,
 }
 
 pub type RegistrySettings = RegistryDefaults;
@@ -299,10 +365,68 @@ impl Default for RegistryDefaults {
         Self {
             github_cache_ttl_secs: DEFAULT_GITHUB_CACHE_TTL_SECS,
             marketplace_cache_ttl_secs: DEFAULT_MARKETPLACE_CACHE_TTL_SECS,
+            trusted_skill_keys: Vec::new(),
+            require_signed_skills: false,
+        }
+    }
+}
+
+// ── MemoryDefaults ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(default)]
+pub struct MemoryDefaults {
+    pub auto_tag: bool,
+    pub max_suggested_tags: usize,
+    pub tag_retention: Vec<TagRetentionRule>,
+    pub embedding_model: Option<String>,
+    pub embedding_dimension: usize,
+    pub embedding_base_url: Option<String>,
+    pub embedding_api_key_secret: Option<String>,
+    pub hybrid_vector_weight: f64,
+    pub hybrid_rrf_k: u32,
+    pub consolidation_enabled: bool,
+    pub consolidation_interval_hours: u64,
+    pub consolidation_model: Option<String>,
+    pub consolidation_min_cluster_size: usize,
+    pub consolidation_similarity: f64,
+    pub session_retention_days: u32,
+    pub max_chunks_per_agent: u32,
+}
+
+pub type MemorySettings = MemoryDefaults;
+
+impl Default for MemoryDefaults {
+    fn default() -> Self {
+        Self {
+            auto_tag: true,
+            max_suggested_tags: DEFAULT_MEMORY_MAX_SUGGESTED_TAGS,
+            tag_retention: Vec::new(),
+            embedding_model: None,
+            embedding_dimension: DEFAULT_MEMORY_EMBEDDING_DIMENSION,
+            embedding_base_url: None,
+            embedding_api_key_secret: None,
+            hybrid_vector_weight: DEFAULT_MEMORY_HYBRID_VECTOR_WEIGHT,
+            hybrid_rrf_k: DEFAULT_MEMORY_HYBRID_RRF_K,
+            consolidation_enabled: false,
+            consolidation_interval_hours: DEFAULT_MEMORY_CONSOLIDATION_INTERVAL_HOURS,
+            consolidation_model: None,
+            consolidation_min_cluster_size: DEFAULT_MEMORY_CONSOLIDATION_MIN_CLUSTER_SIZE,
+            consolidation_similarity: DEFAULT_MEMORY_CONSOLIDATION_SIMILARITY,
+            session_retention_days: DEFAULT_MEMORY_SESSION_RETENTION_DAYS,
+            max_chunks_per_agent: DEFAULT_MEMORY_MAX_CHUNKS_PER_AGENT,
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct TagRetentionRule {
+    pub tag: String,
+    pub retention_days: u32,
+}
+
 // ── SystemConfig ─────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -322,6 +446,14 @@ pub struct SystemConfig {
     pub checkpoint_retention_days: u32,
     pub memory_chunk_retention_days: u32,
     pub log_file_retention_days: u32,
+    pub artifact_retention_days: u32,
+    pub artifact_max_total_mb: u64,
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_budget_usd: Option<f64>,
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
     pub experimental_features: Vec<String>,
     #[serde(default)]
     pub agent: AgentSettings,
@@ -333,6 +465,8 @@ pub struct SystemConfig {
     pub channel_defaults: ChannelSettings,
     #[serde(default)]
     pub registry_defaults: RegistrySettings,
+    #[serde(default)]
+    pub memory_defaults: MemorySettings,
 }
 
 impl Default for SystemConfig {
@@ -349,12 +483,18 @@ impl Default for SystemConfig {
             checkpoint_retention_days: DEFAULT_CHECKPOINT_RETENTION_DAYS,
             memory_chunk_retention_days: DEFAULT_MEMORY_CHUNK_RETENTION_DAYS,
             log_file_retention_days: DEFAULT_LOG_FILE_RETENTION_DAYS,
+            artifact_retention_days: DEFAULT_ARTIFACT_RETENTION_DAYS,
+            artifact_max_total_mb: DEFAULT_ARTIFACT_MAX_TOTAL_MB,
+            daily_budget_usd: None,
+            monthly_budget_usd: None,
+            otlp_endpoint: None,
             experimental_features: Vec::new(),
             agent: AgentSettings::default(),
             api_defaults: ApiSettings::default(),
             runtime_defaults: RuntimeSettings::default(),
             channel_defaults: ChannelSettings::default(),
             registry_defaults: RegistrySettings::default(),
+            memory_defaults: MemorySettings::default(),
         }
     }
 }
@@ -371,6 +511,7 @@ pub struct ConfigDocument {
     pub runtime: RuntimeSettings,
     pub channel: ChannelSettings,
     pub registry: RegistrySettings,
+    pub memory: MemorySettings,
     #[serde(default)]
     pub cli: CliConfig,
 }
@@ -384,6 +525,7 @@ impl ConfigDocument {
             runtime: system.runtime_defaults,
             channel: system.channel_defaults,
             registry: system.registry_defaults,
+            memory: system.memory_defaults,
             cli,
         }
     }
@@ -401,12 +543,18 @@ impl ConfigDocument {
             checkpoint_retention_days: self.system.checkpoint_retention_days,
             memory_chunk_retention_days: self.system.memory_chunk_retention_days,
             log_file_retention_days: self.system.log_file_retention_days,
+            artifact_retention_days: self.system.artifact_retention_days,
+            artifact_max_total_mb: self.system.artifact_max_total_mb,
+            daily_budget_usd: self.system.daily_budget_usd,
+            monthly_budget_usd: self.system.monthly_budget_usd,
+            otlp_endpoint: self.system.otlp_endpoint.clone(),
             experimental_features: self.system.experimental_features.clone(),
             agent: self.agent.clone(),
             api_defaults: self.api.clone(),
             runtime_defaults: self.runtime.clone(),
             channel_defaults: self.channel.clone(),
             registry_defaults: self.registry.clone(),
+            memory_defaults: self.memory.clone(),
         }
     }
 
@@ -417,5 +565,6 @@ impl ConfigDocument {
         self.runtime = system.runtime_defaults;
         self.channel = system.channel_defaults;
         self.registry = system.registry_defaults;
+        self.memory = system.memory_defaults;
     }
 }
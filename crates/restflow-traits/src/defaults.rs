@@ -42,6 +42,9 @@ pub const DEFAULT_AGENT_TASK_TIMEOUT_SECS: u64 = 1800;
 /// Default maximum execution duration (seconds) for background tasks.
 pub const DEFAULT_AGENT_MAX_DURATION_SECS: u64 = 1800;
 
+/// Default image for containerized bash and python tool processes.
+pub const DEFAULT_AGENT_CONTAINER_IMAGE: &str = "python:3.12-slim";
+
 /// Default TTL (seconds) for finished process sessions.
 pub const DEFAULT_PROCESS_SESSION_TTL_SECS: u64 = 30 * 60;
 
@@ -108,6 +111,33 @@ pub const DEFAULT_GITHUB_CACHE_TTL_SECS: u64 = 600;
 /// Default cache TTL (seconds) for marketplace registry results.
 pub const DEFAULT_MARKETPLACE_CACHE_TTL_SECS: u64 = 300;
 
+/// Default cap on tags suggested for a new memory chunk.
+pub const DEFAULT_MEMORY_MAX_SUGGESTED_TAGS: usize = 3;
+
+/// Default embedding width for memory vectors (OpenAI text-embedding-3-small).
+pub const DEFAULT_MEMORY_EMBEDDING_DIMENSION: usize = 1536;
+
+/// Default share of hybrid memory ranking given to vector similarity.
+pub const DEFAULT_MEMORY_HYBRID_VECTOR_WEIGHT: f64 = 0.7;
+
+/// Default reciprocal rank fusion constant for hybrid memory search.
+pub const DEFAULT_MEMORY_HYBRID_RRF_K: u32 = 60;
+
+/// Default hours between memory consolidation passes.
+pub const DEFAULT_MEMORY_CONSOLIDATION_INTERVAL_HOURS: u64 = 24;
+
+/// Default minimum number of related chunks merged by consolidation.
+pub const DEFAULT_MEMORY_CONSOLIDATION_MIN_CLUSTER_SIZE: usize = 3;
+
+/// Default similarity (0.0-1.0) for chunks to join a consolidation cluster.
+pub const DEFAULT_MEMORY_CONSOLIDATION_SIMILARITY: f64 = 0.9;
+
+/// Default days before session-scoped memory chunks expire.
+pub const DEFAULT_MEMORY_SESSION_RETENTION_DAYS: u32 = 30;
+
+/// Default cap on memory chunks kept per agent.
+pub const DEFAULT_MEMORY_MAX_CHUNKS_PER_AGENT: u32 = 10_000;
+
 /// Default file cache entry cap for agent session caches.
 pub const DEFAULT_AGENT_CACHE_FILE_MAX_ENTRIES: usize = 100;
 
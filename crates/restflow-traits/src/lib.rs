@@ -23,6 +23,7 @@ pub mod model;
 pub mod network;
 pub mod orchestrator;
 pub mod registry;
+pub mod secret_ref;
 pub mod security;
 pub mod skill;
 pub mod steer;
@@ -47,7 +48,10 @@ pub use assessment::{
 };
 
 // Tool trait and core types
-pub use tool::{SecretResolver, Tool, ToolErrorCategory, ToolOutput, ToolSchema, check_security};
+pub use tool::{
+    SecretResolver, Tool, ToolErrorCategory, ToolOutput, ToolOutputSink, ToolSchema,
+    check_security, emit_tool_output, with_tool_output_sink,
+};
 
 // Registry and toolset
 pub use registry::ToolRegistry;
@@ -70,16 +74,17 @@ pub use skill::{SkillContent, SkillInfo, SkillProvider, SkillRecord, SkillUpdate
 
 // Store traits
 pub use store::{
-    AgentCreateRequest, AgentStore, AgentUpdateRequest, AuthProfileCreateRequest, AuthProfileStore,
-    AuthProfileTestRequest, BackgroundAgentControlRequest, BackgroundAgentConvertSessionRequest,
+    AgentCreateRequest, AgentStore, AgentUpdateRequest, ApiConnectorStore,
+    AuthProfileCreateRequest, AuthProfileStore, AuthProfileTestRequest,
+    BackgroundAgentControlRequest, BackgroundAgentConvertSessionRequest,
     BackgroundAgentCreateRequest, BackgroundAgentDeleteRequest,
     BackgroundAgentDeliverableListRequest, BackgroundAgentMessageListRequest,
     BackgroundAgentMessageRequest, BackgroundAgentProgressRequest, BackgroundAgentStore,
     BackgroundAgentTraceListRequest, BackgroundAgentTraceReadRequest, BackgroundAgentUpdateRequest,
-    ConfigStore, CredentialInput, DeliverableStore, DiagnosticsProvider, KvStore, MarketplaceStore,
-    MemoryClearRequest, MemoryCompactRequest, MemoryExportRequest, MemoryManager, MemoryStore,
-    OpsProvider, ProcessLog, ProcessManager, ProcessPollResult, ProcessSessionInfo, ReplySender,
-    SecretStore, SecurityQueryProvider, SessionCreateRequest, SessionListFilter,
+    BrowserPlanStore, ConfigStore, CredentialInput, DeliverableStore, DiagnosticsProvider, KvStore,
+    MarketplaceStore, MemoryClearRequest, MemoryCompactRequest, MemoryExportRequest, MemoryManager,
+    MemoryStore, OpsProvider, ProcessLog, ProcessManager, ProcessPollResult, ProcessSessionInfo,
+    ReplySender, SecretStore, SecurityQueryProvider, SessionCreateRequest, SessionListFilter,
     SessionSearchQuery, SessionStore, TaskControlRequest, TaskConvertSessionRequest,
     TaskCreateRequest, TaskDeleteRequest, TaskDeliverableListRequest, TaskMessageListRequest,
     TaskMessageRequest, TaskProgressRequest, TaskStore, TaskTraceListRequest, TaskTraceReadRequest,
@@ -104,7 +109,8 @@ pub use subagent::{
     InlineChildRunConfig, InlineRunConfig, InlineSubagentConfig, SpawnHandle, SpawnPriority,
     SpawnRequest, SubagentCompletion, SubagentConfig, SubagentDefLookup, SubagentDefSnapshot,
     SubagentDefSummary, SubagentEffectiveLimits, SubagentLimitSource, SubagentManager,
-    SubagentResult, SubagentSpawner, SubagentState, SubagentStatus,
+    SubagentProgress, SubagentProgressEvent, SubagentResult, SubagentSpawner, SubagentState,
+    SubagentStatus,
 };
 
 // LLM switching
@@ -122,21 +128,26 @@ pub use defaults::{
     DEFAULT_AGENT_BROWSER_TIMEOUT_SECS, DEFAULT_AGENT_CACHE_FILE_MAX_BYTES,
     DEFAULT_AGENT_CACHE_FILE_MAX_ENTRIES, DEFAULT_AGENT_CACHE_PERMISSION_TTL_SECS,
     DEFAULT_AGENT_CACHE_SEARCH_MAX_ENTRIES, DEFAULT_AGENT_CACHE_SEARCH_TTL_SECS,
-    DEFAULT_AGENT_COMPACT_PRESERVE_TOKENS, DEFAULT_AGENT_CONTEXT_WINDOW_TOKENS,
-    DEFAULT_AGENT_LLM_TIMEOUT_SECS, DEFAULT_AGENT_MAX_DURATION_SECS, DEFAULT_AGENT_MAX_ITERATIONS,
-    DEFAULT_AGENT_MAX_TOOL_CALLS, DEFAULT_AGENT_MAX_TOOL_CONCURRENCY,
-    DEFAULT_AGENT_MAX_TOOL_RESULT_LENGTH, DEFAULT_AGENT_PRUNE_TOOL_MAX_CHARS,
-    DEFAULT_AGENT_PYTHON_TIMEOUT_SECS, DEFAULT_AGENT_TASK_TIMEOUT_SECS,
-    DEFAULT_AGENT_TOOL_TIMEOUT_SECS, DEFAULT_API_DIAGNOSTICS_TIMEOUT_MS,
-    DEFAULT_API_WEB_SEARCH_RESULTS, DEFAULT_BACKGROUND_MAX_TOOL_CALLS,
-    DEFAULT_BACKGROUND_RUNNER_MAX_CONCURRENT_TASKS, DEFAULT_BACKGROUND_RUNNER_POLL_INTERVAL_MS,
-    DEFAULT_BG_MESSAGE_LIST_LIMIT, DEFAULT_BG_PROGRESS_EVENT_LIMIT, DEFAULT_BG_TRACE_LINE_LIMIT,
-    DEFAULT_BG_TRACE_LIST_LIMIT, DEFAULT_CHAT_MAX_SESSION_HISTORY, DEFAULT_GITHUB_CACHE_TTL_SECS,
+    DEFAULT_AGENT_COMPACT_PRESERVE_TOKENS, DEFAULT_AGENT_CONTAINER_IMAGE,
+    DEFAULT_AGENT_CONTEXT_WINDOW_TOKENS, DEFAULT_AGENT_LLM_TIMEOUT_SECS,
+    DEFAULT_AGENT_MAX_DURATION_SECS, DEFAULT_AGENT_MAX_ITERATIONS, DEFAULT_AGENT_MAX_TOOL_CALLS,
+    DEFAULT_AGENT_MAX_TOOL_CONCURRENCY, DEFAULT_AGENT_MAX_TOOL_RESULT_LENGTH,
+    DEFAULT_AGENT_PRUNE_TOOL_MAX_CHARS, DEFAULT_AGENT_PYTHON_TIMEOUT_SECS,
+    DEFAULT_AGENT_TASK_TIMEOUT_SECS, DEFAULT_AGENT_TOOL_TIMEOUT_SECS,
+    DEFAULT_API_DIAGNOSTICS_TIMEOUT_MS, DEFAULT_API_WEB_SEARCH_RESULTS,
+    DEFAULT_BACKGROUND_MAX_TOOL_CALLS, DEFAULT_BACKGROUND_RUNNER_MAX_CONCURRENT_TASKS,
+    DEFAULT_BACKGROUND_RUNNER_POLL_INTERVAL_MS, DEFAULT_BG_MESSAGE_LIST_LIMIT,
+    DEFAULT_BG_PROGRESS_EVENT_LIMIT, DEFAULT_BG_TRACE_LINE_LIMIT, DEFAULT_BG_TRACE_LIST_LIMIT,
+    DEFAULT_CHAT_MAX_SESSION_HISTORY, DEFAULT_GITHUB_CACHE_TTL_SECS,
     DEFAULT_MARKETPLACE_CACHE_TTL_SECS, DEFAULT_MAX_PARALLEL_SUBAGENTS,
-    DEFAULT_PROCESS_SESSION_TTL_SECS, DEFAULT_SUBAGENT_MAX_DEPTH, DEFAULT_SUBAGENT_TIMEOUT_SECS,
-    DEFAULT_TELEGRAM_API_TIMEOUT_SECS, DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS,
-    DEFAULT_WORKSPACE_CONTEXT_MAX_FILE_BYTES, DEFAULT_WORKSPACE_CONTEXT_MAX_TOTAL_BYTES,
-    MAX_API_WEB_SEARCH_RESULTS,
+    DEFAULT_MEMORY_CONSOLIDATION_INTERVAL_HOURS, DEFAULT_MEMORY_CONSOLIDATION_MIN_CLUSTER_SIZE,
+    DEFAULT_MEMORY_CONSOLIDATION_SIMILARITY, DEFAULT_MEMORY_EMBEDDING_DIMENSION,
+    DEFAULT_MEMORY_HYBRID_RRF_K, DEFAULT_MEMORY_HYBRID_VECTOR_WEIGHT,
+    DEFAULT_MEMORY_MAX_CHUNKS_PER_AGENT, DEFAULT_MEMORY_MAX_SUGGESTED_TAGS,
+    DEFAULT_MEMORY_SESSION_RETENTION_DAYS, DEFAULT_PROCESS_SESSION_TTL_SECS,
+    DEFAULT_SUBAGENT_MAX_DEPTH, DEFAULT_SUBAGENT_TIMEOUT_SECS, DEFAULT_TELEGRAM_API_TIMEOUT_SECS,
+    DEFAULT_TELEGRAM_POLLING_TIMEOUT_SECS, DEFAULT_WORKSPACE_CONTEXT_MAX_FILE_BYTES,
+    DEFAULT_WORKSPACE_CONTEXT_MAX_TOTAL_BYTES, MAX_API_WEB_SEARCH_RESULTS,
 };
 
 // Cache types
@@ -144,3 +155,9 @@ pub use cache::{AgentCache, CachedSearchResult, SearchMatch};
 
 // Shared text helpers
 pub use text::floor_char_boundary;
+
+// Secret references in stored config
+pub use secret_ref::{
+    SecretRefError, has_secret_refs, resolve_secret_refs, resolve_secret_refs_in_value,
+    secret_ref_names,
+};
@@ -61,7 +61,7 @@ define_llm_provider_enum! {
     OpenAI => { name: "openai", base_url: "https://api.openai.com/v1" },
     Anthropic => { name: "anthropic", base_url: "" },
     DeepSeek => { name: "deepseek", base_url: "https://api.deepseek.com/v1" },
-    Google => { name: "google", base_url: "https://generativelanguage.googleapis.com/v1beta/openai" },
+    Google => { name: "google", base_url: "https://generativelanguage.googleapis.com/v1beta" },
     Groq => { name: "groq", base_url: "https://api.groq.com/openai/v1" },
     OpenRouter => { name: "openrouter", base_url: "https://openrouter.ai/api/v1" },
     XAI => { name: "xai", base_url: "https://api.x.ai/v1" },
@@ -74,6 +74,16 @@ define_llm_provider_enum! {
     SiliconFlow => { name: "siliconflow", base_url: "https://api.siliconflow.cn/v1" },
     MiniMax => { name: "minimax", base_url: "https://api.minimax.io" },
     MiniMaxCodingPlan => { name: "minimax-coding-plan", base_url: "https://api.minimax.io" },
+    Bedrock => { name: "bedrock", base_url: "https://bedrock-runtime.us-east-1.amazonaws.com" },
+    Ollama => { name: "ollama", base_url: "http://localhost:11434/v1" },
+    Local => { name: "local", base_url: "http://localhost:8080/v1" },
+}
+
+impl LlmProvider {
+    /// Whether HTTP requests need an API key. Local servers run unauthenticated.
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, Self::Ollama | Self::Local)
+    }
 }
 
 /// Result of a successful model swap.
@@ -140,7 +150,7 @@ pub trait LlmSwitcher: Send + Sync {
         let client_kind = self
             .client_kind_for_model(model)
             .unwrap_or(ClientKind::Http);
-        let api_key = if client_kind.is_cli() {
+        let api_key = if client_kind.is_cli() || !provider.requires_api_key() {
             self.resolve_api_key(provider)
         } else {
             Some(self.resolve_api_key(provider).ok_or_else(|| {
@@ -238,6 +248,13 @@ mod tests {
         assert_eq!(result.new_model, "gpt-5");
     }
 
+    #[test]
+    fn local_providers_do_not_require_api_key() {
+        assert!(!LlmProvider::Ollama.requires_api_key());
+        assert!(!LlmProvider::Local.requires_api_key());
+        assert!(LlmProvider::OpenAI.requires_api_key());
+    }
+
     #[test]
     fn current_runtime_provider_defaults_to_model_lookup() {
         let switcher = MockSwitcher::new(ClientKind::Http, Some("test-key"));
@@ -71,6 +71,7 @@ define_model_provider! {
     SiliconFlow => { canonical: "siliconflow", key: "siliconflow", aliases: [] },
     MiniMax => { canonical: "minimax", key: "minimax", aliases: [] },
     MiniMaxCodingPlan => { canonical: "minimax-coding-plan", key: "minimaxcodingplan", aliases: ["minimaxcoding"] },
+    Bedrock => { canonical: "bedrock", key: "bedrock", aliases: ["awsbedrock", "amazonbedrock"] },
 }
 
 impl<'de> Deserialize<'de> for ModelProvider {
@@ -90,8 +90,16 @@ impl ToolRegistry {
 
     /// Execute a tool by name.
     /// All tools execute in parallel; concurrency is controlled by the executor's semaphore.
+    #[tracing::instrument(
+        name = "tool.execute",
+        skip(self, input),
+        fields(tool = name, success = tracing::field::Empty)
+    )]
     pub async fn execute_safe(&self, name: &str, input: Value) -> Result<ToolOutput> {
-        self.execute(name, input).await
+        let result = self.execute(name, input).await;
+        let success = result.as_ref().is_ok_and(|output| output.success);
+        tracing::Span::current().record("success", success);
+        result
     }
 }
 
@@ -0,0 +1,174 @@
+//! `${secret:NAME}` references in stored configuration.
+//!
+//! Config values such as webhook URLs, headers and connector base URLs may
+//! reference a stored secret by name instead of embedding it. References are
+//! replaced only when the value is used, so stored records never hold the
+//! plaintext. Write `$${secret:NAME}` for a literal `${secret:NAME}`.
+
+use serde_json::Value;
+use thiserror::Error;
+
+const REF_PREFIX: &str = "${secret:";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SecretRefError {
+    #[error("Secret '{0}' is referenced but not set")]
+    Missing(String),
+    #[error("Unterminated secret reference: missing '}}' after '{0}'")]
+    Unterminated(String),
+    #[error("Secret reference has an empty name")]
+    EmptyName,
+}
+
+/// Whether `input` contains at least one `${secret:NAME}` reference.
+pub fn has_secret_refs(input: &str) -> bool {
+    input.contains(REF_PREFIX)
+}
+
+/// Names referenced by `input`, in order of appearance.
+pub fn secret_ref_names(input: &str) -> Result<Vec<String>, SecretRefError> {
+    let mut names = Vec::new();
+    render(input, |name| {
+        names.push(name.to_string());
+        Ok(String::new())
+    })?;
+    Ok(names)
+}
+
+/// Replace every `${secret:NAME}` in `input` with the value from `resolve`.
+///
+/// A reference to a secret that `resolve` cannot find is an error rather than
+/// being left in place or replaced with an empty string.
+pub fn resolve_secret_refs(
+    input: &str,
+    resolve: impl Fn(&str) -> Option<String>,
+) -> Result<String, SecretRefError> {
+    render(input, |name| {
+        resolve(name).ok_or_else(|| SecretRefError::Missing(name.to_string()))
+    })
+}
+
+/// Resolve references in every string inside `value`, recursively.
+///
+/// Object keys are left untouched.
+pub fn resolve_secret_refs_in_value(
+    value: &mut Value,
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> Result<(), SecretRefError> {
+    match value {
+        Value::String(text) if text.contains(REF_PREFIX) => {
+            *text = resolve_secret_refs(text, resolve)?;
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_secret_refs_in_value(item, resolve)?;
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                resolve_secret_refs_in_value(item, resolve)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn render(
+    input: &str,
+    mut substitute: impl FnMut(&str) -> Result<String, SecretRefError>,
+) -> Result<String, SecretRefError> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find(REF_PREFIX) {
+        if start > 0 && rest[..start].ends_with('$') {
+            // `$${secret:` is an escaped literal; emit it without the extra `$`.
+            output.push_str(&rest[..start - 1]);
+            output.push_str(REF_PREFIX);
+            rest = &rest[start + REF_PREFIX.len()..];
+            continue;
+        }
+        output.push_str(&rest[..start]);
+        let after = &rest[start + REF_PREFIX.len()..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| SecretRefError::Unterminated(rest[start..].to_string()))?;
+        let name = after[..end].trim();
+        if name.is_empty() {
+            return Err(SecretRefError::EmptyName);
+        }
+        output.push_str(&substitute(name)?);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "API_TOKEN" => Some("t0k3n".to_string()),
+            "SHOP" => Some("acme".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_replaces_references() {
+        let resolved = resolve_secret_refs(
+            "https://${secret:SHOP}.example.com/hook?key=${secret:API_TOKEN}",
+            lookup,
+        )
+        .unwrap();
+        assert_eq!(resolved, "https://acme.example.com/hook?key=t0k3n");
+        assert_eq!(
+            resolve_secret_refs("plain value", lookup).unwrap(),
+            "plain value"
+        );
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        assert_eq!(
+            resolve_secret_refs("Bearer ${secret:MISSING}", lookup),
+            Err(SecretRefError::Missing("MISSING".to_string()))
+        );
+        assert!(matches!(
+            resolve_secret_refs("Bearer ${secret:API_TOKEN", lookup),
+            Err(SecretRefError::Unterminated(_))
+        ));
+        assert_eq!(
+            resolve_secret_refs("${secret: }", lookup),
+            Err(SecretRefError::EmptyName)
+        );
+    }
+
+    #[test]
+    fn test_escaped_reference_is_literal() {
+        assert_eq!(
+            resolve_secret_refs("$${secret:API_TOKEN} -> ${secret:API_TOKEN}", lookup).unwrap(),
+            "${secret:API_TOKEN} -> t0k3n"
+        );
+        assert!(secret_ref_names("$${secret:API_TOKEN}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_in_value_and_names() {
+        let mut value = json!({
+            "headers": { "Authorization": "Bearer ${secret:API_TOKEN}" },
+            "tags": ["${secret:SHOP}", 3],
+        });
+        resolve_secret_refs_in_value(&mut value, &lookup).unwrap();
+        assert_eq!(value["headers"]["Authorization"], "Bearer t0k3n");
+        assert_eq!(value["tags"][0], "acme");
+
+        assert_eq!(
+            secret_ref_names("${secret:A}/${secret: B }").unwrap(),
+            vec!["A", "B"]
+        );
+    }
+}
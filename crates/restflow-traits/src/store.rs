@@ -691,6 +691,8 @@ pub struct SessionSearchQuery {
     pub include_archived: Option<bool>,
     #[serde(default)]
     pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -808,6 +810,18 @@ pub trait DeliverableStore: Send + Sync {
     ) -> Result<Value>;
 }
 
+// ── UsageStore ───────────────────────────────────────────────────────
+
+pub trait UsageStore: Send + Sync {
+    /// Report an agent's LLM spend since `since_ms` against an optional budget.
+    fn budget_status(
+        &self,
+        agent_id: &str,
+        since_ms: i64,
+        budget_usd: Option<f64>,
+    ) -> Result<Value>;
+}
+
 // ── WorkItemProvider ─────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -911,6 +925,12 @@ pub trait ProcessManager: Send + Sync {
     fn kill(&self, session_id: &str) -> anyhow::Result<()>;
     fn list(&self) -> anyhow::Result<Vec<ProcessSessionInfo>>;
     fn log(&self, session_id: &str, offset: usize, limit: usize) -> anyhow::Result<ProcessLog>;
+
+    /// Start a long-lived interactive shell on a PTY and return its session
+    /// id. The session is driven with `write`, `poll` and `kill`.
+    fn spawn_shell(&self, _cwd: Option<String>) -> anyhow::Result<String> {
+        anyhow::bail!("Interactive shells are not supported by this process manager")
+    }
 }
 
 // ── DiagnosticsProvider ──────────────────────────────────────────────
@@ -925,6 +945,14 @@ pub trait DiagnosticsProvider: Send + Sync {
         timeout: Duration,
     ) -> Result<Vec<lsp_types::Diagnostic>>;
     async fn get_diagnostics(&self, path: &Path) -> Result<Vec<lsp_types::Diagnostic>>;
+
+    /// Probe runtime prerequisites (browser runtime, Node.js, TypeScript
+    /// support) and return them with remediation steps.
+    async fn runtime_report(&self) -> Result<Value> {
+        Err(crate::error::ToolError::Tool(
+            "Runtime diagnostics are not available from this provider".to_string(),
+        ))
+    }
 }
 
 // ── ReplySender ──────────────────────────────────────────────────────
@@ -955,6 +983,41 @@ pub trait TriggerStore: Send + Sync {
     fn delete_trigger(&self, id: &str) -> Result<Value>;
 }
 
+// ── BrowserPlanStore ────────────────────────────────────────────────
+
+pub trait BrowserPlanStore: Send + Sync {
+    fn list_plans(&self) -> Result<Value>;
+    /// Load a saved plan and render its actions against `payload`.
+    ///
+    /// Returns `{ name, version, actions, timeout_secs }` where `actions` is
+    /// ready to run.
+    fn resolve_plan(&self, name: &str, version: Option<u32>, payload: &Value) -> Result<Value>;
+}
+
+// ── ApiConnectorStore ───────────────────────────────────────────────
+
+/// Allowlist entry that registers the operations of every API connector.
+pub const API_CONNECTORS_TOOL_GROUP: &str = "api_connectors";
+/// Allowlist prefix that registers one connector's operations, e.g. `connector:github`.
+pub const API_CONNECTOR_TOOL_PREFIX: &str = "connector:";
+
+/// Whether an allowlist entry names API connector tools rather than a single tool.
+pub fn is_api_connector_tool_group(tool_name: &str) -> bool {
+    tool_name == API_CONNECTORS_TOOL_GROUP || tool_name.starts_with(API_CONNECTOR_TOOL_PREFIX)
+}
+
+pub trait ApiConnectorStore: Send + Sync {
+    /// List the operations of every connector, or of one connector.
+    ///
+    /// Returns `[{ connector, operation, tool_name, description, parameters }]`
+    /// where `parameters` is the JSON schema of the tool arguments.
+    fn list_operations(&self, connector: Option<&str>) -> Result<Value>;
+    /// Render the HTTP request for an operation call with auth applied.
+    ///
+    /// Returns `{ method, url, headers, body }`.
+    fn prepare_request(&self, connector: &str, operation: &str, args: &Value) -> Result<Value>;
+}
+
 // ── TerminalStore ───────────────────────────────────────────────────
 
 pub trait TerminalStore: Send + Sync {
@@ -973,10 +1036,15 @@ pub trait TerminalStore: Send + Sync {
 // ── UnifiedMemorySearch ─────────────────────────────────────────────
 
 pub trait UnifiedMemorySearch: Send + Sync {
+    /// Search memory and, optionally, chat sessions.
+    ///
+    /// `mode` is one of `keyword`, `phrase`, `regex`, `semantic` or `hybrid`;
+    /// `None` lets the implementation pick its best available mode.
     fn search(
         &self,
         agent_id: &str,
         query: &str,
+        mode: Option<&str>,
         include_sessions: bool,
         limit: u32,
         offset: u32,
@@ -1024,6 +1092,8 @@ pub trait MarketplaceStore: Send + Sync {
     -> Result<Value>;
     fn uninstall_skill(&self, id: &str) -> Result<Value>;
     fn list_installed(&self) -> Result<Value>;
+    async fn check_updates(&self, source: Option<&str>) -> Result<Value>;
+    fn activate_skill(&self, id: &str) -> Result<Value>;
 }
 
 // ── SecretStore ──────────────────────────────────────────────────────
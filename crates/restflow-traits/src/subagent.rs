@@ -321,6 +321,38 @@ pub struct SubagentCompletion {
     pub result: Option<SubagentResult>,
 }
 
+/// Live progress notification from a running sub-agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubagentProgress {
+    /// Task ID
+    pub id: String,
+
+    /// Name of the agent doing the work.
+    pub agent_name: String,
+
+    /// Parent run ID, when this progress belongs to a child run.
+    pub parent_run_id: Option<String>,
+
+    /// Iteration the sub-agent is on (1-based; 0 before the first).
+    pub iteration: usize,
+
+    /// What happened.
+    pub event: SubagentProgressEvent,
+}
+
+/// Kind of sub-agent progress.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubagentProgressEvent {
+    /// A new ReAct iteration started.
+    IterationStarted,
+    /// A tool call started.
+    ToolStarted { tool_name: String },
+    /// A tool call finished.
+    ToolCompleted { tool_name: String, success: bool },
+    /// The sub-agent reached a terminal status.
+    Finished { status: SubagentStatus },
+}
+
 /// High-level subagent lifecycle management.
 ///
 /// Abstracts `SubagentTracker` + `SubagentDefLookup` + `spawn_subagent` so that
@@ -367,6 +399,27 @@ pub trait SubagentManager: Send + Sync {
         parent_run_id: &str,
     ) -> Option<SubagentCompletion>;
 
+    /// Wait until the first of `task_ids` finishes, returning its outcome.
+    ///
+    /// Returns `None` when `task_ids` is empty. The default implementation
+    /// polls `list_running`; runtimes with direct state access should
+    /// override it.
+    async fn wait_first(&self, task_ids: &[String]) -> Option<SubagentCompletion> {
+        if task_ids.is_empty() {
+            return None;
+        }
+        loop {
+            let running = self.list_running();
+            if let Some(task_id) = task_ids
+                .iter()
+                .find(|task_id| !running.iter().any(|state| &state.id == *task_id))
+            {
+                return self.wait(task_id).await;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+        }
+    }
+
     /// Access the sub-agent configuration.
     fn config(&self) -> &SubagentConfig;
 }
@@ -47,6 +47,28 @@ pub async fn check_security(
     Ok(Some(format!("Action blocked: {}", reason)))
 }
 
+/// Callback receiving incremental output of the running tool call.
+pub type ToolOutputSink = Arc<dyn Fn(&str) + Send + Sync>;
+
+tokio::task_local! {
+    static TOOL_OUTPUT_SINK: ToolOutputSink;
+}
+
+/// Run a tool future with `sink` receiving the output it streams through
+/// [`emit_tool_output`].
+pub async fn with_tool_output_sink<F: Future>(sink: ToolOutputSink, future: F) -> F::Output {
+    TOOL_OUTPUT_SINK.scope(sink, future).await
+}
+
+/// Stream partial output of the current tool call, e.g. command output as
+/// it is produced. Does nothing when the caller does not listen for it.
+pub fn emit_tool_output(output: &str) {
+    if output.is_empty() {
+        return;
+    }
+    let _ = TOOL_OUTPUT_SINK.try_with(|sink| sink(output));
+}
+
 /// JSON Schema for tool parameters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolSchema {
@@ -205,7 +205,9 @@ fn session_id_of(event: &ChatSessionEvent) -> &str {
         ChatSessionEvent::Created { session_id }
         | ChatSessionEvent::Updated { session_id }
         | ChatSessionEvent::MessageAdded { session_id, .. }
-        | ChatSessionEvent::Deleted { session_id } => session_id,
+        | ChatSessionEvent::Deleted { session_id }
+        | ChatSessionEvent::ToolApprovalRequested { session_id, .. }
+        | ChatSessionEvent::ToolApprovalResolved { session_id, .. } => session_id,
     }
 }
 
@@ -261,7 +261,12 @@ pub fn message_from_stream_frame(frame: &StreamFrame) -> Option<ShellMessage> {
         StreamFrame::Error(error) => Some(ShellMessage::ErrorNotice {
             content: format!("Stream error {}: {}", error.code, error.message),
         }),
-        StreamFrame::Start { .. } | StreamFrame::Event { .. } | StreamFrame::Done { .. } => None,
+        StreamFrame::Start { .. }
+        | StreamFrame::ToolOutput { .. }
+        | StreamFrame::SubagentProgress { .. }
+        | StreamFrame::Event { .. }
+        | StreamFrame::Resync { .. }
+        | StreamFrame::Done { .. } => None,
     }
 }
 
@@ -270,7 +275,16 @@ pub fn message_from_session_event(event: &ChatSessionEvent) -> Option<ShellMessa
         ChatSessionEvent::Created { .. }
         | ChatSessionEvent::Updated { .. }
         | ChatSessionEvent::MessageAdded { .. }
-        | ChatSessionEvent::Deleted { .. } => None,
+        | ChatSessionEvent::Deleted { .. }
+        | ChatSessionEvent::ToolApprovalResolved { .. } => None,
+        ChatSessionEvent::ToolApprovalRequested {
+            approval_id,
+            tool_name,
+            ..
+        } => Some(ShellMessage::ApprovalNotice {
+            approval_id: Some(approval_id.clone()),
+            content: format!("Tool {tool_name} is waiting for approval ({approval_id})"),
+        }),
     }
 }
 
@@ -516,4 +530,17 @@ mod tests {
         };
         assert!(message_from_session_event(&event).is_none());
     }
+
+    #[test]
+    fn surfaces_tool_approval_requests() {
+        let event = ChatSessionEvent::ToolApprovalRequested {
+            session_id: "session-1".to_string(),
+            approval_id: "approval-1".to_string(),
+            tool_name: "bash".to_string(),
+        };
+        assert!(matches!(
+            message_from_session_event(&event),
+            Some(ShellMessage::ApprovalNotice { approval_id: Some(id), .. }) if id == "approval-1"
+        ));
+    }
 }
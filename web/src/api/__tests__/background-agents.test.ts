@@ -5,9 +5,11 @@ import {
   getTask,
   getTaskEvents,
   getTaskStreamEventName,
+  listDeadLetterTasks,
   listMemoryChunksByTag,
   listMemoryChunksForSession,
   listMemorySessions,
+  listRunnableTasks,
   listTasks,
   pauseTask,
   runTaskNow,
@@ -54,6 +56,22 @@ describe('task api memory endpoints', () => {
     expect(result).toBeNull()
   })
 
+  it('lists the runnable queue and dead-lettered tasks', async () => {
+    vi.mocked(requestTyped).mockResolvedValue([])
+
+    await listRunnableTasks()
+    await listDeadLetterTasks()
+
+    expect(requestTyped).toHaveBeenNthCalledWith(1, {
+      type: 'ListRunnableTasks',
+      data: { current_time: null },
+    })
+    expect(requestTyped).toHaveBeenNthCalledWith(2, {
+      type: 'ListTasks',
+      data: { status: 'dead_letter' },
+    })
+  })
+
   it('calls list memory sessions with agent_id', async () => {
     vi.mocked(requestTyped).mockResolvedValueOnce([])
 
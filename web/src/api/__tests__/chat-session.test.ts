@@ -12,6 +12,7 @@ import {
   listChatSessionsBySkill,
   rebuildExternalChatSession,
   renameChatSession,
+  searchChatSessions,
   sendChatMessage,
   subscribeSessionEvents,
   updateChatSession,
@@ -150,6 +151,31 @@ describe('chat session API', () => {
     })
   })
 
+  it('searches chat messages with a full-text query', async () => {
+    vi.mocked(requestTyped).mockResolvedValue({ hits: [], total_count: 0, has_more: false })
+
+    await searchChatSessions({
+      query: 'webhook',
+      agent_id: 'agent-1',
+      include_archived: false,
+      limit: 20,
+      offset: 0,
+    })
+
+    expect(requestTyped).toHaveBeenCalledWith({
+      type: 'SearchChatSessions',
+      data: {
+        query: {
+          query: 'webhook',
+          agent_id: 'agent-1',
+          include_archived: false,
+          limit: 20,
+          offset: 0,
+        },
+      },
+    })
+  })
+
   it('subscribes to daemon session events over the shared stream endpoint', async () => {
     const callback = vi.fn()
     vi.mocked(streamClient).mockReturnValue(
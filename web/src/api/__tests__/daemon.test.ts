@@ -19,6 +19,7 @@ describe('daemon API', () => {
       pid: 1234,
       started_at_ms: 1,
       uptime_secs: 45,
+      components: [],
     })
 
     const result = await getCliDaemonStatus()
@@ -2,6 +2,7 @@ import { beforeEach, describe, expect, it, vi } from 'vitest'
 
 import {
   getExecutionRunThread,
+  getRelatedEntities,
   listChildRuns,
   listExecutionContainers,
   listRuns,
@@ -67,6 +68,24 @@ describe('execution-console api', () => {
     })
   })
 
+  it('requests related entities by entity id', async () => {
+    vi.mocked(requestTyped).mockResolvedValue({
+      entity_id: 'task-1',
+      kind: 'background_task',
+      outgoing: [],
+      incoming: [],
+    })
+
+    await getRelatedEntities('task-1')
+
+    expect(requestTyped).toHaveBeenCalledWith({
+      type: 'GetRelatedEntities',
+      data: {
+        entity_id: 'task-1',
+      },
+    })
+  })
+
   it('lists child runs by parent run id', async () => {
     vi.mocked(requestTyped).mockResolvedValue([])
 
@@ -34,6 +34,23 @@ describe('http-client', () => {
     expect(result).toEqual({ ok: true })
   })
 
+  it('sends the configured API token as a bearer header', async () => {
+    vi.stubEnv('VITE_DAEMON_API_TOKEN', 'rft_test')
+    vi.stubGlobal('fetch', vi.fn().mockResolvedValue(createJsonResponse({
+      response_type: 'Success',
+      data: { ok: true },
+    })))
+
+    await requestTyped({ type: 'Ping' })
+    await fetchJson('/api/health')
+
+    const [, requestInit] = vi.mocked(fetch).mock.calls[0]!
+    expect((requestInit?.headers as Record<string, string>).Authorization).toBe('Bearer rft_test')
+    const [, fetchInit] = vi.mocked(fetch).mock.calls[1]!
+    expect(new Headers(fetchInit?.headers).get('Authorization')).toBe('Bearer rft_test')
+    vi.unstubAllEnvs()
+  })
+
   it('throws structured backend errors', async () => {
     vi.stubGlobal('fetch', vi.fn().mockResolvedValue(createJsonResponse({
       response_type: 'Error',
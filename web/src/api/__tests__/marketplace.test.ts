@@ -17,6 +17,7 @@ import { fetchJson } from '../http-client'
 import type { GatingCheckResult, SkillManifest, SkillVersion } from '@/types/generated'
 
 vi.mock('../http-client', () => ({
+  authHeaders: vi.fn(() => ({})),
   buildUrl: vi.fn((path: string) => `http://127.0.0.1:8787${path}`),
   fetchJson: vi.fn(),
 }))
@@ -49,6 +50,7 @@ const createManifest = (id: string, categories: string[] = ['productivity']): Sk
   readme: null,
   changelog: null,
   metadata: {},
+  signature: null,
 })
 
 const passingGating: GatingCheckResult = {
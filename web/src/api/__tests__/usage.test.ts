@@ -0,0 +1,52 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest'
+import { getUsageStats } from '@/api/usage'
+import type { UsageStats } from '@/types/generated/UsageStats'
+import { requestTyped } from '../http-client'
+
+vi.mock('../http-client', () => ({
+  requestTyped: vi.fn(),
+}))
+
+const mockedRequestTyped = vi.mocked(requestTyped)
+
+describe('usage api', () => {
+  const emptyStats: UsageStats = {
+    totals: {
+      call_count: 0,
+      prompt_tokens: 0,
+      completion_tokens: 0,
+      total_tokens: 0,
+      cost_usd: 0,
+    },
+    by_session: [],
+    by_agent: [],
+    by_day: [],
+  }
+
+  beforeEach(() => {
+    vi.clearAllMocks()
+  })
+
+  it('requests usage stats for a filter', async () => {
+    mockedRequestTyped.mockResolvedValue(emptyStats)
+
+    const result = await getUsageStats({ agent_id: 'agent-1', from_time: 1000 })
+
+    expect(mockedRequestTyped).toHaveBeenCalledWith({
+      type: 'GetUsageStats',
+      data: { query: { agent_id: 'agent-1', from_time: 1000 } },
+    })
+    expect(result).toEqual(emptyStats)
+  })
+
+  it('defaults to an unfiltered query', async () => {
+    mockedRequestTyped.mockResolvedValue(emptyStats)
+
+    await getUsageStats()
+
+    expect(mockedRequestTyped).toHaveBeenCalledWith({
+      type: 'GetUsageStats',
+      data: { query: {} },
+    })
+  })
+})
@@ -0,0 +1,90 @@
+/**
+ * Saved Browser Plan API
+ *
+ * Browser-first wrappers around daemon request contracts.
+ */
+
+import { requestTyped } from './http-client'
+import type { SavedBrowserPlan } from '@/types/generated'
+
+export interface SaveBrowserPlanRequest {
+  name: string
+  description?: string | null
+  actions: unknown[]
+  timeout_secs?: number | null
+}
+
+export interface BrowserPlanRunOutcome {
+  success: boolean
+  duration_ms: number
+  results: unknown[]
+  error?: string
+}
+
+export interface BrowserRuntimeProbe {
+  node_available: boolean
+  node_version: string | null
+  node_typescript_available: boolean
+  playwright_package_available: boolean
+  chromium_cache_detected: boolean
+  ready: boolean
+  notes: string[]
+}
+
+export interface BrowserDoctorReport {
+  probe: BrowserRuntimeProbe
+  remediation: string[]
+}
+
+export async function listBrowserPlans(): Promise<SavedBrowserPlan[]> {
+  return requestTyped<SavedBrowserPlan[]>({ type: 'ListBrowserPlans' })
+}
+
+export async function getBrowserPlan(name: string, version?: number): Promise<SavedBrowserPlan> {
+  return requestTyped<SavedBrowserPlan>({
+    type: 'GetBrowserPlan',
+    data: { name, version: version ?? null },
+  })
+}
+
+export async function listBrowserPlanVersions(name: string): Promise<SavedBrowserPlan[]> {
+  return requestTyped<SavedBrowserPlan[]>({
+    type: 'ListBrowserPlanVersions',
+    data: { name },
+  })
+}
+
+export async function saveBrowserPlan(request: SaveBrowserPlanRequest): Promise<SavedBrowserPlan> {
+  return requestTyped<SavedBrowserPlan>({
+    type: 'SaveBrowserPlan',
+    data: {
+      name: request.name,
+      description: request.description ?? null,
+      actions: request.actions,
+      timeout_secs: request.timeout_secs ?? null,
+    },
+  })
+}
+
+export async function deleteBrowserPlan(name: string): Promise<boolean> {
+  const response = await requestTyped<{ deleted: boolean }>({
+    type: 'DeleteBrowserPlan',
+    data: { name },
+  })
+  return response.deleted
+}
+
+export async function runBrowserPlan(
+  name: string,
+  payload?: Record<string, unknown>,
+  version?: number,
+): Promise<BrowserPlanRunOutcome> {
+  return requestTyped<BrowserPlanRunOutcome>({
+    type: 'RunBrowserPlan',
+    data: { name, version: version ?? null, payload: payload ?? null },
+  })
+}
+
+export async function getBrowserDoctor(): Promise<BrowserDoctorReport> {
+  return requestTyped<BrowserDoctorReport>({ type: 'BrowserDoctor' })
+}
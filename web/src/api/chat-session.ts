@@ -9,8 +9,17 @@ import type { ChatSession } from '@/types/generated/ChatSession'
 import type { ChatSessionSummary } from '@/types/generated/ChatSessionSummary'
 import type { ChatMessage } from '@/types/generated/ChatMessage'
 import type { ChatSessionEvent } from '@/types/generated/ChatSessionEvent'
+import type { ChatSearchQuery } from '@/types/generated/ChatSearchQuery'
+import type { ChatSearchResult } from '@/types/generated/ChatSearchResult'
 
-export type { ChatSession, ChatSessionSummary, ChatMessage, ChatSessionEvent }
+export type {
+  ChatSession,
+  ChatSessionSummary,
+  ChatMessage,
+  ChatSessionEvent,
+  ChatSearchQuery,
+  ChatSearchResult,
+}
 export type UnlistenFn = () => void
 
 export interface CreateChatSessionRequest {
@@ -132,6 +141,13 @@ export async function listChatSessionsBySkill(skillId: string): Promise<ChatSess
   })
 }
 
+export async function searchChatSessions(query: ChatSearchQuery): Promise<ChatSearchResult> {
+  return requestTyped<ChatSearchResult>({
+    type: 'SearchChatSessions',
+    data: { query },
+  })
+}
+
 export async function executeChatSession(sessionId: string): Promise<ChatSession> {
   return requestTyped<ChatSession>({
     type: 'ExecuteChatSession',
@@ -159,6 +175,12 @@ export async function subscribeSessionEvents(
           frame.data.event.session
         ) {
           callback(frame.data.event.session)
+        } else if (frame.stream_type === 'resync') {
+          for (const event of frame.data.events) {
+            if ('session' in event && event.session) {
+              callback(event.session)
+            }
+          }
         }
       }
     } catch (error) {
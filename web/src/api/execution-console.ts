@@ -1,11 +1,12 @@
 import type { ExecutionContainerSummary } from '@/types/generated/ExecutionContainerSummary'
 import type { ChildRunListQuery } from '@/types/generated/ChildRunListQuery'
 import type { ExecutionThread } from '@/types/generated/ExecutionThread'
+import type { RelatedEntities } from '@/types/generated/RelatedEntities'
 import type { RunListQuery } from '@/types/generated/RunListQuery'
 import type { RunSummary } from '@/types/generated/RunSummary'
 import { requestTyped } from './http-client'
 
-export type { ChildRunListQuery, RunListQuery, RunSummary }
+export type { ChildRunListQuery, RelatedEntities, RunListQuery, RunSummary }
 
 export async function listExecutionContainers(): Promise<ExecutionContainerSummary[]> {
   return requestTyped<ExecutionContainerSummary[]>({
@@ -35,3 +36,10 @@ export async function listChildRuns(
     data: { query },
   })
 }
+
+export async function getRelatedEntities(entityId: string): Promise<RelatedEntities> {
+  return requestTyped<RelatedEntities>({
+    type: 'GetRelatedEntities',
+    data: { entity_id: entityId },
+  })
+}
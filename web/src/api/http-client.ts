@@ -79,13 +79,34 @@ function normalizeStreamFrame(frame: RawStreamFrame): StreamFrame {
         stream_type: 'tool_call',
         data: frame.data as { id: string; name: string; arguments: unknown },
       }
+    case 'tool_output':
+      return {
+        stream_type: 'tool_output',
+        data: frame.data as { id: string; content: string },
+      }
     case 'tool_result':
       return {
         stream_type: 'tool_result',
         data: frame.data as { id: string; result: string; success: boolean },
       }
+    case 'subagent_progress':
+      return {
+        stream_type: 'subagent_progress',
+        data: frame.data as {
+          task_id: string
+          agent_name: string
+          iteration: number
+          status: string
+          tool: string | null
+        },
+      }
     case 'event':
       return { stream_type: 'event', data: frame.data as { event: IpcStreamEvent } }
+    case 'resync':
+      return {
+        stream_type: 'resync',
+        data: frame.data as { dropped: number; events: IpcStreamEvent[] },
+      }
     case 'done':
       return {
         stream_type: 'done',
@@ -115,6 +136,20 @@ export function buildUrl(path: string): string {
   return `${resolveBaseUrl()}${path}`
 }
 
+/** Bearer header for remote daemons that require an API token. */
+export function authHeaders(): Record<string, string> {
+  const token = import.meta.env.VITE_DAEMON_API_TOKEN?.trim()
+  return token ? { Authorization: `Bearer ${token}` } : {}
+}
+
+function withAuth(init?: RequestInit): RequestInit {
+  const headers = new Headers(init?.headers)
+  for (const [name, value] of Object.entries(authHeaders())) {
+    headers.set(name, value)
+  }
+  return { ...init, headers }
+}
+
 async function readJson<T>(response: Response): Promise<T> {
   if (!response.ok) {
     const text = await response.text()
@@ -141,7 +176,7 @@ async function readJson<T>(response: Response): Promise<T> {
 }
 
 export async function fetchJson<T>(path: string, init?: RequestInit): Promise<T> {
-  const response = await fetch(buildUrl(path), init)
+  const response = await fetch(buildUrl(path), withAuth(init))
   return readJson<T>(response)
 }
 
@@ -151,6 +186,7 @@ export async function requestClient(request: IpcRequest): Promise<IpcResponse> {
     headers: {
       'Content-Type': 'application/json',
       Accept: 'application/json',
+      ...authHeaders(),
     },
     body: JSON.stringify(request),
   })
@@ -193,6 +229,7 @@ export async function* streamClient(
     headers: {
       'Content-Type': 'application/json',
       Accept: 'application/x-ndjson',
+      ...authHeaders(),
     },
     body: JSON.stringify(request),
     signal: init?.signal,
@@ -6,10 +6,12 @@ export * from './chat-stream'
 export * from './config'
 export * from './daemon'
 export * from './hooks'
+export * from './browser-plans'
 export * from './marketplace'
 export * from './secrets'
 export * from './skills'
 export * from './execution-traces'
+export * from './usage'
 export * from './voice'
 export {
   listTasks,
@@ -28,6 +30,7 @@ export {
 } from './task'
 export {
   getExecutionRunThread,
+  getRelatedEntities,
   listChildRuns,
   listExecutionContainers,
   listRuns,
@@ -4,7 +4,7 @@
  * Browser-first wrappers around daemon marketplace HTTP endpoints.
  */
 
-import { buildUrl, fetchJson } from './http-client'
+import { authHeaders, buildUrl, fetchJson } from './http-client'
 import type { GatingCheckResult, Skill, SkillManifest, SkillVersion } from '@/types/generated'
 
 export type MarketplaceSource = 'marketplace' | 'github'
@@ -67,7 +67,7 @@ export interface MarketplaceStats {
 async function postNoContent(path: string, body: unknown): Promise<void> {
   const response = await fetch(buildUrl(path), {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
+    headers: { 'Content-Type': 'application/json', ...authHeaders() },
     body: JSON.stringify(body),
   })
 
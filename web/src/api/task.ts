@@ -9,6 +9,7 @@ import type { MemorySession } from '@/types/generated/MemorySession'
 import type { Task } from '@/types/generated/Task'
 import type { TaskConversionResult } from '@/types/generated/TaskConversionResult'
 import type { TaskEvent } from '@/types/generated/TaskEvent'
+import type { WakeCondition } from '@/types/generated/WakeCondition'
 import { requestOptional, requestTyped } from './http-client'
 
 export type { Task } from '@/types/generated/Task'
@@ -33,6 +34,21 @@ export async function listTasks(): Promise<Task[]> {
   })
 }
 
+/** Runnable tasks in queue order: highest priority first, then longest waiting. */
+export async function listRunnableTasks(): Promise<Task[]> {
+  return requestTyped<Task[]>({
+    type: 'ListRunnableTasks',
+    data: { current_time: null },
+  })
+}
+
+export async function listDeadLetterTasks(): Promise<Task[]> {
+  return requestTyped<Task[]>({
+    type: 'ListTasks',
+    data: { status: 'dead_letter' },
+  })
+}
+
 export async function getTask(id: string): Promise<Task | null> {
   return requestOptional<Task>({
     type: 'GetTask',
@@ -68,6 +84,27 @@ export async function runTaskNow(id: string): Promise<Task> {
   })
 }
 
+export async function runTaskWithSecrets(
+  id: string,
+  secrets: Record<string, string>,
+): Promise<Task> {
+  return requestTyped<Task>({
+    type: 'RunTaskWithSecrets',
+    data: { id, secrets },
+  })
+}
+
+export async function setTaskWakeCondition(
+  id: string,
+  condition: WakeCondition | null,
+  checkpointId?: string,
+): Promise<Task> {
+  return requestTyped<Task>({
+    type: 'SetTaskWakeCondition',
+    data: { id, condition, checkpoint_id: checkpointId ?? null },
+  })
+}
+
 export async function steerTask(taskId: string, instruction: string): Promise<SteerTaskResult> {
   return requestTyped<SteerTaskResult>({
     type: 'SendTaskMessage',
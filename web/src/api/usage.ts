@@ -0,0 +1,10 @@
+import type { UsageQuery } from '@/types/generated/UsageQuery'
+import type { UsageStats } from '@/types/generated/UsageStats'
+import { requestTyped } from './http-client'
+
+export async function getUsageStats(query: UsageQuery = {}): Promise<UsageStats> {
+  return requestTyped<UsageStats>({
+    type: 'GetUsageStats',
+    data: { query },
+  })
+}
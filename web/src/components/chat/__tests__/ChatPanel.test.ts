@@ -832,6 +832,7 @@ describe('ChatPanel', () => {
             metric_sample: null,
             provider_health: null,
             log_record: null,
+            security_decision: null,
           },
           {
             id: 'event-tool-1',
@@ -863,6 +864,7 @@ describe('ChatPanel', () => {
             metric_sample: null,
             provider_health: null,
             log_record: null,
+            security_decision: null,
           },
           {
             id: 'event-assistant-1',
@@ -892,6 +894,7 @@ describe('ChatPanel', () => {
             metric_sample: null,
             provider_health: null,
             log_record: null,
+            security_decision: null,
           },
         ],
         stats: {},
@@ -994,6 +997,7 @@ describe('ChatPanel', () => {
             metric_sample: null,
             provider_health: null,
             log_record: null,
+            security_decision: null,
           },
         ],
         stats: {},
@@ -1121,6 +1125,7 @@ describe('ChatPanel', () => {
             metric_sample: null,
             provider_health: null,
             log_record: null,
+            security_decision: null,
           },
         ],
         stats: {},
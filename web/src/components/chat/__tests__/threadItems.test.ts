@@ -37,6 +37,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
             {
               id: 'event-tool-1',
@@ -73,6 +74,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
             {
               id: 'event-assistant-1',
@@ -102,6 +104,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
           ],
           stats: {} as any,
@@ -179,6 +182,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
           ],
           stats: {} as any,
@@ -430,6 +434,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
             {
               id: 'event-assistant',
@@ -459,6 +464,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
           ],
           stats: {} as any,
@@ -543,6 +549,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
           ],
           stats: {} as any,
@@ -669,6 +676,7 @@ describe('threadItems', () => {
               metric_sample: null,
               provider_health: null,
               log_record: null,
+              security_decision: null,
             },
           ],
           stats: {} as any,
@@ -87,6 +87,7 @@ const fixtureManifest: MarketplaceSearchItem['manifest'] = {
   readme: null,
   changelog: null,
   metadata: {},
+  signature: null,
 }
 
 const fixtureSearchItems: MarketplaceSearchItem[] = [
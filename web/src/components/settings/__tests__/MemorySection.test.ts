@@ -116,6 +116,10 @@ describe('MemorySection', () => {
       total_tokens: 128,
       oldest_memory: null,
       newest_memory: null,
+      archived_count: 0,
+      consolidated_count: 0,
+      session_retention_days: 30,
+      max_chunks: 10000,
     })
     mockedListMemorySessions.mockResolvedValue([
       {
@@ -48,6 +48,7 @@ describe('ToolPanel', () => {
               metric_sample_count: 0n,
               provider_health_count: 0n,
               log_record_count: 0n,
+              security_decision_count: 0n,
               total_tokens: 42n,
               total_cost_usd: 0.12,
               time_range: null,
@@ -109,6 +110,7 @@ describe('ToolPanel', () => {
               metric_sample_count: 0n,
               provider_health_count: 0n,
               log_record_count: 0n,
+              security_decision_count: 0n,
               total_tokens: 42n,
               total_cost_usd: 0.12,
               time_range: null,
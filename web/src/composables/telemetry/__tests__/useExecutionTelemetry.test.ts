@@ -54,6 +54,7 @@ describe('useExecutionTelemetry', () => {
         metric_sample_count: 0n,
         provider_health_count: 0n,
         log_record_count: 0n,
+        security_decision_count: 0n,
         total_tokens: 0n,
         total_cost_usd: 0,
         time_range: null,
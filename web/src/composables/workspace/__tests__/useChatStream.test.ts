@@ -87,6 +87,86 @@ describe('useChatStream', () => {
     wrapper.unmount()
   })
 
+  it('appends streamed tool output to the running tool step', async () => {
+    vi.mocked(openChatStream).mockReturnValue({
+      streamId: 'msg-5',
+      frames: createFrames([
+        {
+          stream_type: 'tool_call',
+          data: { id: 'tool-1', name: 'bash', arguments: { command: 'cargo build' } },
+        },
+        { stream_type: 'tool_output', data: { id: 'tool-1', content: 'Compiling a\n' } },
+        { stream_type: 'tool_output', data: { id: 'tool-1', content: 'Compiling b\n' } },
+      ]),
+    })
+
+    const wrapper = createHarness()
+    const vm = wrapper.vm as unknown as { stream: ReturnType<typeof useChatStream> }
+
+    await vm.stream.send('build')
+    await flushPromises()
+
+    expect(vm.stream.state.value.steps).toHaveLength(1)
+    expect(vm.stream.state.value.steps[0]?.status).toBe('running')
+    expect(vm.stream.state.value.steps[0]?.result).toBe('Compiling a\nCompiling b\n')
+
+    wrapper.unmount()
+  })
+
+  it('tracks live sub-agent progress as a step per task', async () => {
+    vi.mocked(openChatStream).mockReturnValue({
+      streamId: 'msg-6',
+      frames: createFrames([
+        {
+          stream_type: 'subagent_progress',
+          data: {
+            task_id: 'task-1',
+            agent_name: 'researcher',
+            iteration: 1,
+            status: 'running',
+            tool: null,
+          },
+        },
+        {
+          stream_type: 'subagent_progress',
+          data: {
+            task_id: 'task-1',
+            agent_name: 'researcher',
+            iteration: 2,
+            status: 'running',
+            tool: 'web_search',
+          },
+        },
+        {
+          stream_type: 'subagent_progress',
+          data: {
+            task_id: 'task-2',
+            agent_name: 'coder',
+            iteration: 3,
+            status: 'timed_out',
+            tool: null,
+          },
+        },
+      ]),
+    })
+
+    const wrapper = createHarness()
+    const vm = wrapper.vm as unknown as { stream: ReturnType<typeof useChatStream> }
+
+    await vm.stream.send('delegate')
+    await flushPromises()
+
+    const steps = vm.stream.state.value.steps
+    expect(steps).toHaveLength(2)
+    expect(steps[0]?.type).toBe('subagent')
+    expect(steps[0]?.status).toBe('running')
+    expect(steps[0]?.displayName).toBe('researcher · iteration 2 · web_search')
+    expect(steps[1]?.status).toBe('failed')
+    expect(steps[1]?.result).toBe('timed_out')
+
+    wrapper.unmount()
+  })
+
   it('syncs persisted events by run_id so stream-backed traces stay on the canonical path', async () => {
     vi.mocked(openChatStream).mockReturnValue({
       streamId: 'msg-4',
@@ -139,10 +139,16 @@ export function useChatStream(sessionId: () => string | null) {
     })
   }
 
+  function appendToolOutput(id: string, content: string): void {
+    const step = state.value.steps.find((item) => item.toolId === id)
+    if (!step || step.status !== 'running') return
+    step.result = (step.result ?? '') + content
+  }
+
   function applyToolResult(id: string, result: string, success: boolean): void {
     const step =
       state.value.steps.find((item) => item.toolId === id) ??
-      state.value.steps.find((item) => item.status === 'running')
+      state.value.steps.find((item) => item.type === 'tool_call' && item.status === 'running')
 
     if (!step) {
       state.value.steps.push({
@@ -160,6 +166,28 @@ export function useChatStream(sessionId: () => string | null) {
     step.displayName = formatToolDisplayName(step.name, step.arguments, result)
   }
 
+  function applySubagentProgress(
+    taskId: string,
+    agentName: string,
+    iteration: number,
+    status: string,
+    tool: string | null,
+  ): void {
+    let step = state.value.steps.find((item) => item.type === 'subagent' && item.toolId === taskId)
+    if (!step) {
+      step = { type: 'subagent', name: agentName, status: 'running', toolId: taskId }
+      state.value.steps.push(step)
+    }
+    const activity = tool ? ` · ${tool}` : ''
+    step.displayName = `${agentName} · iteration ${iteration}${activity}`
+    if (status === 'completed') {
+      step.status = 'completed'
+    } else if (status !== 'running') {
+      step.status = 'failed'
+      step.result = status
+    }
+  }
+
   async function syncPersistedExecutionEvents(runId: string): Promise<void> {
     try {
       const events = await queryRunExecutionTraces(runId, { limit: 200, offset: 0 })
@@ -273,9 +301,21 @@ export function useChatStream(sessionId: () => string | null) {
           case 'tool_call':
             upsertToolCall(frame.data.id, frame.data.name, frame.data.arguments)
             break
+          case 'tool_output':
+            appendToolOutput(frame.data.id, frame.data.content)
+            break
           case 'tool_result':
             applyToolResult(frame.data.id, frame.data.result, frame.data.success)
             break
+          case 'subagent_progress':
+            applySubagentProgress(
+              frame.data.task_id,
+              frame.data.agent_name,
+              frame.data.iteration,
+              frame.data.status,
+              frame.data.tool,
+            )
+            break
           case 'done':
             state.value.isStreaming = false
             state.value.completedAt = Date.now()
@@ -290,6 +330,7 @@ export function useChatStream(sessionId: () => string | null) {
             await syncPersistedExecutionEvents(runId)
             return
           case 'event':
+          case 'resync':
             break
         }
       }
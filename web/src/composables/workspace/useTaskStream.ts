@@ -144,6 +144,15 @@ export function useTaskStream(trackTaskId: () => string | null) {
           continue
         }
 
+        if (frame.stream_type === 'resync') {
+          for (const event of frame.data.events) {
+            if ('background_agent' in event && event.background_agent) {
+              handleStreamEvent(event.background_agent)
+            }
+          }
+          continue
+        }
+
         if (frame.stream_type === 'error') {
           streamState.value.error = frame.data.message
           streamState.value.isStreaming = false
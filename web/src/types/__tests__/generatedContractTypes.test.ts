@@ -54,6 +54,7 @@ describe('generated contract types', () => {
     expect(eventSource).toContain('source: ExecutionTraceSource')
     expect(eventSource).toContain('provider_health: ProviderHealthTrace | null')
     expect(eventSource).toContain('log_record: LogRecordTrace | null')
+    expect(eventSource).toContain('security_decision: SecurityDecisionTrace | null')
     expect(timelineSource).toContain('stats: ExecutionTraceStats')
     expect(metricsSource).toContain('samples: Array<ExecutionTraceEvent>')
     expect(providerHealthSource).toContain('events: Array<ExecutionTraceEvent>')
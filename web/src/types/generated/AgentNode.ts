@@ -1,6 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ApiKeyConfig } from "./ApiKeyConfig";
 import type { CodexCliExecutionMode } from "./CodexCliExecutionMode";
+import type { ExecutionStrategy } from "./ExecutionStrategy";
 import type { ModelId } from "./ModelId";
 import type { ModelRef } from "./ModelRef";
 import type { ModelRoutingConfig } from "./ModelRoutingConfig";
@@ -57,4 +58,13 @@ skill_preflight_policy_mode?: SkillPreflightPolicyMode,
 /**
  * Optional tier-based model routing policy.
  */
-model_routing?: ModelRoutingConfig, };
+model_routing?: ModelRoutingConfig, 
+/**
+ * Simulate side-effecting tools (shell, file writes, outbound messages)
+ * so a run previews the agent's plan without acting on it.
+ */
+dry_run?: boolean, 
+/**
+ * Execution strategy (`react` | `plan_and_execute`), defaulting to `react`.
+ */
+execution_strategy?: ExecutionStrategy, };
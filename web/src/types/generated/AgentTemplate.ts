@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AgentNode } from "./AgentNode";
+import type { AgentTemplateTask } from "./AgentTemplateTask";
+
+/**
+ * A ready-made agent setup that can be provisioned in one step.
+ *
+ * Provisioning creates the agent, then one background task per entry in
+ * `tasks`. Skills listed on the agent are attached when installed.
+ */
+export type AgentTemplate = { id: string, name: string, description: string, agent: AgentNode, tasks: Array<AgentTemplateTask>, 
+/**
+ * Secrets the agent's tools read at run time.
+ */
+required_secrets: Array<string>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskSchedule } from "./TaskSchedule";
+
+/**
+ * A scheduled background task created alongside a template agent.
+ */
+export type AgentTemplateTask = { name: string, description: string | null, input: string, schedule: TaskSchedule, };
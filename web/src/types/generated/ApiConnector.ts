@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConnectorAuth } from "./ConnectorAuth";
+import type { ConnectorOperation } from "./ConnectorOperation";
+
+/**
+ * A REST API integration generated from an OpenAPI document.
+ *
+ * Each operation is exposed to agents as its own tool; authentication is
+ * applied from stored secrets when the operation is called, so the
+ * connector itself never holds credentials.
+ */
+export type ApiConnector = { 
+/**
+ * Unique connector name, used as the tool name prefix.
+ */
+name: string, description: string | null, 
+/**
+ * Base URL that operation paths are appended to.
+ */
+base_url: string, auth: ConnectorAuth, operations: Array<ConnectorOperation>, 
+/**
+ * Unix timestamp in milliseconds when the connector was first imported.
+ */
+created_at: number, 
+/**
+ * Unix timestamp in milliseconds when the connector was last imported.
+ */
+updated_at: number, };
@@ -1,6 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AuthProvider } from "./AuthProvider";
 import type { CredentialSource } from "./CredentialSource";
+import type { OAuthClientConfig } from "./OAuthClientConfig";
 import type { ProfileHealth } from "./ProfileHealth";
 import type { SecureCredential } from "./SecureCredential";
 
@@ -59,4 +60,8 @@ failure_count: number,
 /**
  * Cooldown end time if in cooldown
  */
-cooldown_until: string | null, };
+cooldown_until: string | null, 
+/**
+ * OAuth client used to obtain and refresh the credential
+ */
+oauth?: OAuthClientConfig | null, };
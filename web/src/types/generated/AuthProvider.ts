@@ -7,4 +7,4 @@
  * - `Anthropic`: Direct API calls using `sk-ant-api03-...` keys
  * - `ClaudeCode`: Claude Code CLI with OAuth tokens (`sk-ant-oat01-...`)
  */
-export type AuthProvider = "anthropic" | "claude_code" | "openai" | "openai_codex" | "google" | "other";
+export type AuthProvider = "anthropic" | "claude_code" | "openai" | "openai_codex" | "google" | "bedrock" | "other";
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Browser action plan run directly by a webhook or scheduled trigger.
+ *
+ * String values in `actions` may reference the trigger payload with
+ * `${payload.<path>}`; a string that is only a placeholder is replaced by
+ * the referenced JSON value as-is.
+ */
+export type BrowserPlan = { 
+/**
+ * Browser actions in the `run_actions` format.
+ */
+actions: any, 
+/**
+ * Overall timeout for the plan, defaults to the agent browser timeout.
+ */
+timeout_secs: number | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Character range of a matched term within a snippet.
+ */
+export type ChatSearchHighlight = { 
+/**
+ * Start offset in characters
+ */
+start: number, 
+/**
+ * End offset in characters (exclusive)
+ */
+end: number, };
@@ -0,0 +1,44 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChatRole } from "./ChatRole";
+import type { ChatSearchHighlight } from "./ChatSearchHighlight";
+
+/**
+ * A chat message matching a [`ChatSearchQuery`].
+ */
+export type ChatSearchHit = { 
+/**
+ * Session ID
+ */
+session_id: string, 
+/**
+ * Session name
+ */
+session_name: string, 
+/**
+ * Agent ID
+ */
+agent_id: string, 
+/**
+ * Matching message ID
+ */
+message_id: string, 
+/**
+ * Role of the message sender
+ */
+role: ChatRole, 
+/**
+ * Message timestamp in milliseconds
+ */
+timestamp: number, 
+/**
+ * Relevance score (higher is better)
+ */
+score: number, 
+/**
+ * Excerpt of the message around the first match
+ */
+snippet: string, 
+/**
+ * Matched terms within `snippet`
+ */
+highlights: Array<ChatSearchHighlight>, };
@@ -0,0 +1,33 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Full-text search over chat message content.
+ *
+ * Every term must appear in a message for it to match; `"quoted text"` is
+ * matched as a phrase.
+ */
+export type ChatSearchQuery = { 
+/**
+ * Search text
+ */
+query: string, 
+/**
+ * Only search this agent's sessions
+ */
+agent_id?: string, 
+/**
+ * Only search sessions for this skill
+ */
+skill_id?: string, 
+/**
+ * Also search archived sessions
+ */
+include_archived: boolean, 
+/**
+ * Maximum hits to return
+ */
+limit: number, 
+/**
+ * Hits to skip, for pagination
+ */
+offset: number, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChatSearchHit } from "./ChatSearchHit";
+
+/**
+ * One page of chat search hits.
+ */
+export type ChatSearchResult = { 
+/**
+ * Hits on this page, best first
+ */
+hits: Array<ChatSearchHit>, 
+/**
+ * Total number of matching messages
+ */
+total_count: number, 
+/**
+ * Whether more hits follow this page
+ */
+has_more: boolean, };
@@ -3,3 +3,5 @@ export type ChatSessionEvent =
   | { type: 'Updated'; session_id: string }
   | { type: 'MessageAdded'; session_id: string; source: string }
   | { type: 'Deleted'; session_id: string }
+  | { type: 'ToolApprovalRequested'; session_id: string; approval_id: string; tool_name: string }
+  | { type: 'ToolApprovalResolved'; session_id: string; approval_id: string; approved: boolean }
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Concurrency limits that keep bursts of triggers from starting too many runs.
+ */
+export type ConcurrencyLimits = { 
+/**
+ * Named group shared with other tasks, e.g. `"deploy"` or `"scraping"`.
+ */
+group: string | null, 
+/**
+ * Maximum runs of tasks in `group` at once; this task waits while the
+ * group is full.
+ */
+max_group_runs: number | null, 
+/**
+ * Maximum runs at once across all tasks bound to the same agent.
+ */
+max_agent_runs: number | null, };
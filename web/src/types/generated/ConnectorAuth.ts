@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiKeyLocation } from "./ApiKeyLocation";
+
+/**
+ * How requests made by a connector are authenticated.
+ *
+ * `secret` is the name of a stored secret, resolved at call time.
+ */
+export type ConnectorAuth = { "type": "none" } | { "type": "bearer", secret: string, } | { "type": "api_key", secret: string, name: string, location: ApiKeyLocation, } | { "type": "basic", secret: string, };
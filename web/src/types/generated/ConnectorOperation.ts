@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConnectorParameter } from "./ConnectorParameter";
+
+/**
+ * A single callable API operation.
+ */
+export type ConnectorOperation = { 
+/**
+ * Operation id, unique within the connector.
+ */
+id: string, 
+/**
+ * Upper-case HTTP method.
+ */
+method: string, 
+/**
+ * Path template relative to the base URL, e.g. `/pets/{petId}`.
+ */
+path: string, summary: string | null, parameters: Array<ConnectorParameter>, 
+/**
+ * JSON schema of the request body, if the operation takes one.
+ */
+request_body: any | null, };
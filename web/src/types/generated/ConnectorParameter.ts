@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ParameterLocation } from "./ParameterLocation";
+
+export type ConnectorParameter = { name: string, location: ParameterLocation, required: boolean, description: string | null, 
+/**
+ * JSON schema of the parameter value.
+ */
+schema: any, };
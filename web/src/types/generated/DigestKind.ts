@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Content of a scheduled digest.
+ */
+export type DigestKind = "task_progress" | "memory_highlights";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of entity tracked in the relationship graph.
+ */
+export type EntityKind = "agent" | "chat_session" | "background_task" | "deliverable";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EntityKind } from "./EntityKind";
+
+/**
+ * Reference to a single entity in the relationship graph.
+ */
+export type EntityRef = { kind: EntityKind, id: string, };
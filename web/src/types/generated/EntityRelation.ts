@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EntityRef } from "./EntityRef";
+import type { RelationKind } from "./RelationKind";
+
+/**
+ * Directed edge stored in the relationship index.
+ */
+export type EntityRelation = { source: EntityRef, relation: RelationKind, target: EntityRef, 
+/**
+ * Unix timestamp in milliseconds when the edge was recorded.
+ */
+created_at: bigint, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReplayStep } from "./ReplayStep";
+
+/**
+ * Step-by-step view of a recorded execution.
+ */
+export type ExecutionReplay = { execution_id: string, task_id: string | null, agent_id: string | null, run_id: string | null, 
+/**
+ * Recorded steps in execution order.
+ */
+steps: Array<ReplayStep>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How an agent works towards its goal.
+ */
+export type ExecutionStrategy = "react" | "plan_and_execute";
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type ExecutionTraceCategory = "llm_call" | "tool_call" | "model_switch" | "lifecycle" | "message" | "metric_sample" | "provider_health" | "log_record";
+export type ExecutionTraceCategory = "llm_call" | "tool_call" | "model_switch" | "lifecycle" | "message" | "metric_sample" | "provider_health" | "log_record" | "security_decision";
@@ -8,6 +8,7 @@ import type { MessageTrace } from "./MessageTrace";
 import type { MetricSampleTrace } from "./MetricSampleTrace";
 import type { ModelSwitchTrace } from "./ModelSwitchTrace";
 import type { ProviderHealthTrace } from "./ProviderHealthTrace";
+import type { SecurityDecisionTrace } from "./SecurityDecisionTrace";
 import type { ToolCallTrace } from "./ToolCallTrace";
 
-export type ExecutionTraceEvent = { id: string, task_id: string, agent_id: string, category: ExecutionTraceCategory, source: ExecutionTraceSource, timestamp: number, subflow_path: string[], run_id: string | null, parent_run_id: string | null, session_id: string | null, turn_id: string | null, requested_model: string | null, effective_model: string | null, provider: string | null, attempt: number | null, llm_call: LlmCallTrace | null, tool_call: ToolCallTrace | null, model_switch: ModelSwitchTrace | null, lifecycle: LifecycleTrace | null, message: MessageTrace | null, metric_sample: MetricSampleTrace | null, provider_health: ProviderHealthTrace | null, log_record: LogRecordTrace | null, };
+export type ExecutionTraceEvent = { id: string, task_id: string, agent_id: string, category: ExecutionTraceCategory, source: ExecutionTraceSource, timestamp: number, subflow_path: string[], run_id: string | null, parent_run_id: string | null, session_id: string | null, turn_id: string | null, requested_model: string | null, effective_model: string | null, provider: string | null, attempt: number | null, llm_call: LlmCallTrace | null, tool_call: ToolCallTrace | null, model_switch: ModelSwitchTrace | null, lifecycle: LifecycleTrace | null, message: MessageTrace | null, metric_sample: MetricSampleTrace | null, provider_health: ProviderHealthTrace | null, log_record: LogRecordTrace | null, security_decision: SecurityDecisionTrace | null, };
@@ -1,4 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ExecutionTraceTimeRange } from "./ExecutionTraceTimeRange";
 
-export type ExecutionTraceStats = { total_events: bigint, llm_call_count: bigint, tool_call_count: bigint, model_switch_count: bigint, lifecycle_count: bigint, message_count: bigint, metric_sample_count: bigint, provider_health_count: bigint, log_record_count: bigint, total_tokens: bigint, total_cost_usd: number, time_range: ExecutionTraceTimeRange | null, };
+export type ExecutionTraceStats = { total_events: bigint, llm_call_count: bigint, tool_call_count: bigint, model_switch_count: bigint, lifecycle_count: bigint, message_count: bigint, metric_sample_count: bigint, provider_health_count: bigint, log_record_count: bigint, security_decision_count: bigint, total_tokens: bigint, total_cost_usd: number, time_range: ExecutionTraceTimeRange | null, };
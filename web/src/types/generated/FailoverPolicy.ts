@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Fallback models tried in order when a provider is rate-limited or down.
+ */
+export type FailoverPolicy = { 
+/**
+ * Fallback models, tried in order.
+ */
+fallback_models: Array<string>, 
+/**
+ * Delay before the first failover in milliseconds, doubled per attempt.
+ */
+backoff_ms: number, };
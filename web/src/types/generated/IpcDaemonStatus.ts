@@ -1,3 +1,11 @@
+export type DaemonComponentState = 'running' | 'disabled' | 'failed'
+
+export type DaemonComponentStatus = {
+  name: string
+  state: DaemonComponentState
+  detail?: string
+}
+
 export type IpcDaemonStatus = {
   status: string
   protocol_version: string
@@ -5,4 +13,5 @@ export type IpcDaemonStatus = {
   pid: number
   started_at_ms: number
   uptime_secs: number
+  components: DaemonComponentStatus[]
 }
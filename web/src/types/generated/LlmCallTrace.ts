@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type LlmCallTrace = { model: string, input_tokens: number | null, output_tokens: number | null, total_tokens: number | null, cost_usd: number | null, duration_ms: bigint | null, is_reasoning: boolean | null, message_count: number | null, };
+export type LlmCallTrace = { model: string, input_tokens: number | null, output_tokens: number | null, total_tokens: number | null, cost_usd: number | null, duration_ms: bigint | null, is_reasoning: boolean | null, message_count: number | null, cache_read_tokens: number | null, cache_write_tokens: number | null, cache_savings_usd: number | null, };
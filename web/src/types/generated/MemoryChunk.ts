@@ -72,4 +72,13 @@ embedding_model?: string,
 /**
  * Embedding dimension (for validation)
  */
-embedding_dim?: number, };
+embedding_dim?: number, 
+/**
+ * Unix timestamp in milliseconds when consolidation archived this chunk.
+ * Archived chunks are kept but excluded from search.
+ */
+archived_at?: number, 
+/**
+ * IDs of the chunks this chunk summarizes, when written by consolidation
+ */
+consolidated_from?: Array<string>, };
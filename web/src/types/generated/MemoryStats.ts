@@ -27,4 +27,20 @@ oldest_memory: number | null,
 /**
  * Newest memory timestamp
  */
-newest_memory: number | null, };
+newest_memory: number | null, 
+/**
+ * Chunks archived by consolidation (included in `chunk_count`)
+ */
+archived_count: number, 
+/**
+ * Summary chunks written by consolidation (included in `chunk_count`)
+ */
+consolidated_count: number, 
+/**
+ * Days before session chunks expire (0 keeps them)
+ */
+session_retention_days: number, 
+/**
+ * Chunk limit before least recently used chunks are evicted (0 for none)
+ */
+max_chunks: number, };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FailoverPolicy } from "./FailoverPolicy";
 
 /**
  * Model routing configuration for automatic tier-based model selection.
@@ -23,4 +24,8 @@ complex_model?: string,
 /**
  * Escalate to complex tier after a failed iteration.
  */
-escalate_on_failure: boolean, };
+escalate_on_failure: boolean, 
+/**
+ * Retry rate-limited or failed LLM requests on fallback models.
+ */
+failover?: FailoverPolicy, };
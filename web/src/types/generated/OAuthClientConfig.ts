@@ -0,0 +1,33 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * OAuth2 client registration used by an auth profile.
+ *
+ * The client secret itself lives in SecretStorage; only its reference is kept
+ * here so the configuration can be stored alongside the profile.
+ */
+export type OAuthClientConfig = { 
+/**
+ * OAuth client identifier
+ */
+client_id: string, 
+/**
+ * Reference to the client secret in SecretStorage (public clients have none)
+ */
+client_secret_ref?: string | null, 
+/**
+ * Authorization endpoint for the authorization-code flow
+ */
+authorization_url: string, 
+/**
+ * Token endpoint used for code exchange, device polling and refresh
+ */
+token_url: string, 
+/**
+ * Device authorization endpoint, when the provider supports it
+ */
+device_authorization_url?: string | null, 
+/**
+ * Requested scopes
+ */
+scopes: Array<string>, };
@@ -40,4 +40,16 @@ status: ApprovalStatus,
 /**
  * Optional reason for rejection
  */
-rejection_reason?: string | null, };
+rejection_reason?: string | null, 
+/**
+ * Tool awaiting approval (tool approval policy only)
+ */
+tool_name?: string | null, 
+/**
+ * Arguments of the tool call awaiting approval
+ */
+arguments?: any, 
+/**
+ * Chat session the requesting run belongs to
+ */
+session_id?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Queue priority for runnable tasks, using the same levels as sub-agent spawns.
+ */
+export type QueuePriority = "low" | "normal" | "high";
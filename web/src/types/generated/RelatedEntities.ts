@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EntityKind } from "./EntityKind";
+import type { EntityRelation } from "./EntityRelation";
+
+/**
+ * Typed neighborhood of an entity returned by `get_related`.
+ */
+export type RelatedEntities = { entity_id: string, 
+/**
+ * Kind of the entity, when any edge touches it.
+ */
+kind: EntityKind | null, 
+/**
+ * Edges recorded by this entity (e.g. the agent a task was spawned by).
+ */
+outgoing: Array<EntityRelation>, 
+/**
+ * Edges pointing at this entity (e.g. the tasks an agent spawned).
+ */
+incoming: Array<EntityRelation>, };
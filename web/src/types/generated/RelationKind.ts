@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Typed edge between two entities. Edges point from the entity that
+ * records the link to the one it references.
+ */
+export type RelationKind = "spawned_by" | "triggered_by" | "produced_by" | "owns" | "bound_to";
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of re-executing a recorded run from one of its steps.
+ */
+export type ReplayRunResult = { 
+/**
+ * Execution the replay started from.
+ */
+source_execution_id: string, 
+/**
+ * Step of the source execution the replay started from.
+ */
+from_step: number, 
+/**
+ * Execution ID of the new run; can itself be replayed.
+ */
+execution_id: string, 
+/**
+ * Agent that ran the replay.
+ */
+agent_id: string, 
+/**
+ * Final answer of the new run.
+ */
+output: string, };
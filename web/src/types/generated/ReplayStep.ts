@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ExecutionStep } from "./ExecutionStep";
+
+/**
+ * One recorded step of an execution.
+ */
+export type ReplayStep = { 
+/**
+ * Position to pass when re-executing from this step.
+ */
+step: number, 
+/**
+ * ReAct iteration reached at this step.
+ */
+iteration: number, 
+/**
+ * Agent status: "running" | "completed" | "failed" | "max_iterations" |
+ * "interrupted" | "resource_exhausted"
+ */
+status: string, 
+/**
+ * Creation timestamp in milliseconds since epoch.
+ */
+created_at: number, 
+/**
+ * LLM messages, tool calls and tool outputs added since the previous step.
+ */
+messages: Array<ExecutionStep>, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BrowserPlan } from "./BrowserPlan";
+
+/**
+ * A named browser action plan saved for re-use.
+ *
+ * Saving a plan under an existing name stores a new version; older
+ * versions stay available until the plan is deleted.
+ */
+export type SavedBrowserPlan = { name: string, description: string | null, 
+/**
+ * Version number, starting at 1.
+ */
+version: number, plan: BrowserPlan, 
+/**
+ * Unix timestamp in milliseconds when the first version was saved.
+ */
+created_at: number, 
+/**
+ * Unix timestamp in milliseconds when this version was saved.
+ */
+updated_at: number, };
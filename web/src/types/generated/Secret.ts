@@ -3,4 +3,12 @@
 /**
  * A stored secret with metadata
  */
-export type Secret = { key: string, value: string, description: string | null, created_at: number, updated_at: number, };
+export type Secret = { key: string, value: string, description: string | null, created_at: number, updated_at: number, 
+/**
+ * When the value last changed after creation
+ */
+rotated_at: number | null, 
+/**
+ * When the value should be considered expired
+ */
+expires_at: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Aggregated reads of one secret by one tool (and agent)
+ */
+export type SecretAccess = { tool: string, agent_id: string | null, count: number, first_used_at: number, last_used_at: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of a security gate check on a command or tool action.
+ */
+export type SecurityDecisionTrace = { tool_name: string, operation: string, target: string, allowed: boolean, requires_approval: boolean, approval_id: string | null, reason: string | null, };
@@ -3,6 +3,7 @@ import type { GatingRequirements } from "./GatingRequirements";
 import type { SkillAuthor } from "./SkillAuthor";
 import type { SkillDependency } from "./SkillDependency";
 import type { SkillPermissions } from "./SkillPermissions";
+import type { SkillSignature } from "./SkillSignature";
 import type { SkillSource } from "./SkillSource";
 import type { SkillVersion } from "./SkillVersion";
 
@@ -81,4 +82,8 @@ changelog: string | null,
 /**
  * Additional metadata
  */
-metadata: Record<string, unknown>, };
+metadata: Record<string, unknown>, 
+/**
+ * Publisher signature over the skill id, version and content
+ */
+signature: SkillSignature | null, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Detached ed25519 signature published alongside a skill manifest
+ */
+export type SkillSignature = { 
+/**
+ * Base64-encoded ed25519 public key of the publisher
+ */
+public_key: string, 
+/**
+ * Base64-encoded signature over [`SkillSignature::payload`]
+ */
+signature: string, };
@@ -9,10 +9,25 @@ export type StreamFrame =
       stream_type: 'tool_call'
       data: { id: string; name: string; arguments: unknown }
     }
+  | { stream_type: 'tool_output'; data: { id: string; content: string } }
   | {
       stream_type: 'tool_result'
       data: { id: string; result: string; success: boolean }
     }
+  | {
+      stream_type: 'subagent_progress'
+      data: {
+        task_id: string
+        agent_name: string
+        iteration: number
+        status: string
+        tool: string | null
+      }
+    }
   | { stream_type: 'event'; data: { event: IpcStreamEvent } }
+  | {
+      stream_type: 'resync'
+      data: { dropped: number; events: IpcStreamEvent[] }
+    }
   | { stream_type: 'done'; data: { total_tokens?: number | null } }
   | { stream_type: 'error'; data: ErrorPayload }
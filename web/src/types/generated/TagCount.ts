@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Number of memory chunks carrying a tag.
+ */
+export type TagCount = { 
+/**
+ * Tag name
+ */
+tag: string, 
+/**
+ * Number of chunks with this tag
+ */
+count: number, };
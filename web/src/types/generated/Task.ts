@@ -1,12 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConcurrencyLimits } from "./ConcurrencyLimits";
 import type { ContinuationConfig } from "./ContinuationConfig";
 import type { DurabilityMode } from "./DurabilityMode";
 import type { ExecutionMode } from "./ExecutionMode";
 import type { MemoryConfig } from "./MemoryConfig";
 import type { NotificationConfig } from "./NotificationConfig";
 import type { ResourceLimits } from "./ResourceLimits";
+import type { TaskQueuePolicy } from "./TaskQueuePolicy";
 import type { TaskSchedule } from "./TaskSchedule";
 import type { TaskStatus } from "./TaskStatus";
+import type { TaskWake } from "./TaskWake";
 import type { WebhookConfig } from "./WebhookConfig";
 
 /**
@@ -91,6 +94,10 @@ continuation_total_iterations: number,
  * Number of continuation segments completed so far.
  */
 continuation_segments_completed: number, 
+/**
+ * Concurrency limits shared with other tasks
+ */
+concurrency: ConcurrencyLimits, 
 /**
  * Current status of the task
  */
@@ -119,6 +126,14 @@ success_count: number,
  * Count of failed executions
  */
 failure_count: number, 
+/**
+ * Queue priority and retry policy
+ */
+queue: TaskQueuePolicy, 
+/**
+ * Consecutive failed runs counted against the queue retry limit
+ */
+retry_attempts: number, 
 /**
  * Cumulative tokens used across executions
  */
@@ -138,4 +153,8 @@ webhook: WebhookConfig | null,
 /**
  * Summary message pointer for compacted task sessions
  */
-summary_message_id: string | null, };
+summary_message_id: string | null, 
+/**
+ * Wake condition registered while the task is paused
+ */
+wake: TaskWake | null, };
@@ -1,10 +1,12 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConcurrencyLimits } from "./ConcurrencyLimits";
 import type { ContinuationConfig } from "./ContinuationConfig";
 import type { DurabilityMode } from "./DurabilityMode";
 import type { ExecutionMode } from "./ExecutionMode";
 import type { MemoryConfig } from "./MemoryConfig";
 import type { NotificationConfig } from "./NotificationConfig";
 import type { ResourceLimits } from "./ResourceLimits";
+import type { TaskQueuePolicy } from "./TaskQueuePolicy";
 import type { TaskSchedule } from "./TaskSchedule";
 
 /**
@@ -70,4 +72,12 @@ prerequisites: Array<string> | null,
 /**
  * New continuation policy
  */
-continuation: ContinuationConfig | null, };
+continuation: ContinuationConfig | null, 
+/**
+ * New queue priority and retry policy
+ */
+queue: TaskQueuePolicy | null, 
+/**
+ * New concurrency limits
+ */
+concurrency: ConcurrencyLimits | null, };
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueuePriority } from "./QueuePriority";
+
+/**
+ * Queue policy controlling ordering and retries of task executions.
+ */
+export type TaskQueuePolicy = { 
+/**
+ * Priority used when several tasks are runnable at once.
+ */
+priority: QueuePriority, 
+/**
+ * Retries after a failed run before the task is dead-lettered.
+ *
+ * `None` keeps rescheduling failed tasks on their regular schedule.
+ */
+max_retries: number | null, 
+/**
+ * Delay before the first retry; doubles with each further attempt.
+ */
+retry_backoff_secs: number, 
+/**
+ * Upper bound for the retry delay.
+ */
+max_backoff_secs: number, };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConcurrencyLimits } from "./ConcurrencyLimits";
 import type { ContinuationConfig } from "./ContinuationConfig";
 import type { DurabilityMode } from "./DurabilityMode";
 import type { ExecutionMode } from "./ExecutionMode";
@@ -72,4 +73,8 @@ prerequisites: Array<string>,
 /**
  * Optional continuation policy for long-horizon execution
  */
-continuation: ContinuationConfig | null, };
+continuation: ContinuationConfig | null, 
+/**
+ * Optional concurrency limits shared with other tasks
+ */
+concurrency: ConcurrencyLimits | null, };
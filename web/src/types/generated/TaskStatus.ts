@@ -3,4 +3,4 @@
 /**
  * Status of an agent task
  */
-export type TaskStatus = "active" | "paused" | "running" | "completed" | "failed" | "interrupted";
+export type TaskStatus = "active" | "paused" | "running" | "completed" | "failed" | "interrupted" | "dead_letter";
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WakeCondition } from "./WakeCondition";
+import type { WakeEvent } from "./WakeEvent";
+
+/**
+ * Wake condition registered by a paused task.
+ */
+export type TaskWake = { condition: WakeCondition, 
+/**
+ * Checkpoint to resume from; defaults to the task's latest checkpoint
+ */
+checkpoint_id: string | null, registered_at: number, 
+/**
+ * Set once the condition fires, until the runner resumes the task
+ */
+fired: WakeEvent | null, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SecurityAction } from "./SecurityAction";
+import type { ToolRule } from "./ToolRule";
+
+/**
+ * Approval policy applied to every tool call made by an agent.
+ *
+ * Rules are evaluated by descending priority; the first matching rule
+ * decides whether the call runs, is blocked or waits for user approval.
+ */
+export type ToolApprovalPolicy = { 
+/**
+ * Action for tool calls not matching any rule (default: allow)
+ */
+default_action: SecurityAction, 
+/**
+ * Per-tool and per-argument rules
+ */
+rules: Array<ToolRule>, 
+/**
+ * Approval timeout in seconds (default: 300 = 5 minutes)
+ */
+approval_timeout_secs: bigint, };
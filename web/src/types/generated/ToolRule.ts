@@ -4,4 +4,10 @@ import type { SecurityAction } from "./SecurityAction";
 /**
  * Tool-specific security rule.
  */
-export type ToolRule = { id: string, tool_name: string, operation?: string | null, target_pattern: string, action: SecurityAction, description?: string | null, priority: number, };
+export type ToolRule = { id: string, tool_name: string, operation?: string | null, 
+/**
+ * Argument whose string value is matched against `target_pattern` by
+ * the tool approval policy. When unset the pattern is matched against
+ * the JSON-encoded arguments.
+ */
+argument?: string | null, target_pattern: string, action: SecurityAction, description?: string | null, priority: number, };
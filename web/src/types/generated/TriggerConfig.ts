@@ -1,4 +1,22 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AuthConfig } from "./AuthConfig";
+import type { BrowserPlan } from "./BrowserPlan";
+import type { DigestKind } from "./DigestKind";
 
-export type TriggerConfig = { "type": "manual" } | { "type": "webhook", path: string, method: string, auth: AuthConfig | null, } | { "type": "schedule", cron: string, timezone: string | null, payload: any, };
+export type TriggerConfig = { "type": "manual" } | { "type": "webhook", path: string, method: string, auth: AuthConfig | null, browser_plan?: BrowserPlan, } | { "type": "schedule", cron: string, timezone: string | null, payload: any, } | { "type": "browser_schedule", cron: string, timezone: string | null, plan: BrowserPlan, 
+/**
+ * Payload used to render `${payload.<path>}` placeholders.
+ */
+payload: any, 
+/**
+ * Send a notification after each successful run.
+ */
+notify: boolean, 
+/**
+ * Only notify when the result differs from the previous run.
+ */
+notify_only_on_change: boolean, } | { "type": "digest", cron: string, timezone: string | null, digest: DigestKind, 
+/**
+ * Limit the digest to tasks and memories of this agent.
+ */
+agent_id: string | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UsageTotals } from "./UsageTotals";
+
+/**
+ * Usage grouped under one key (session ID, agent ID, or UTC day).
+ */
+export type UsageBucket = { 
+/**
+ * Group key
+ */
+key: string, 
+/**
+ * Usage within the group
+ */
+totals: UsageTotals, };
@@ -0,0 +1,31 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UsageTotals } from "./UsageTotals";
+
+/**
+ * Spend of an agent against a budget.
+ */
+export type UsageBudgetStatus = { 
+/**
+ * Agent ID
+ */
+agent_id: string, 
+/**
+ * Start of the budget window in milliseconds
+ */
+since: number, 
+/**
+ * Usage within the window
+ */
+spent: UsageTotals, 
+/**
+ * Budget in USD, if one was given
+ */
+budget_usd?: number, 
+/**
+ * Budget left in USD, if a budget was given
+ */
+remaining_usd?: number, 
+/**
+ * Whether spend has reached the budget
+ */
+exceeded: boolean, };
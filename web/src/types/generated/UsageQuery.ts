@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Filter for usage aggregation.
+ */
+export type UsageQuery = { 
+/**
+ * Only count calls made by this agent
+ */
+agent_id?: string, 
+/**
+ * Only count calls made in this chat session
+ */
+session_id?: string, 
+/**
+ * Inclusive lower bound in milliseconds
+ */
+from_time?: number, 
+/**
+ * Exclusive upper bound in milliseconds
+ */
+to_time?: number, };
@@ -0,0 +1,62 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Token usage and cost of a single LLM call.
+ */
+export type UsageRecord = { 
+/**
+ * Record ID (the originating trace event ID)
+ */
+id: string, 
+/**
+ * Call timestamp in milliseconds
+ */
+timestamp: number, 
+/**
+ * Agent that made the call
+ */
+agent_id: string, 
+/**
+ * Chat session the call belongs to, if any
+ */
+session_id?: string, 
+/**
+ * Background task the call belongs to
+ */
+task_id: string, 
+/**
+ * Run the call belongs to, if any
+ */
+run_id?: string, 
+/**
+ * Provider that served the call
+ */
+provider?: string, 
+/**
+ * Model that served the call
+ */
+model: string, 
+/**
+ * Prompt (input) tokens
+ */
+prompt_tokens: number, 
+/**
+ * Completion (output) tokens
+ */
+completion_tokens: number, 
+/**
+ * Cost in USD, when the model's pricing is known
+ */
+cost_usd?: number, 
+/**
+ * Prompt tokens served from the provider's prompt cache
+ */
+cache_read_tokens: number, 
+/**
+ * Prompt tokens written to the provider's prompt cache
+ */
+cache_write_tokens: number, 
+/**
+ * USD saved by prompt caching, when the model's pricing is known
+ */
+cache_savings_usd?: number, };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UsageBucket } from "./UsageBucket";
+import type { UsageTotals } from "./UsageTotals";
+
+/**
+ * Aggregated usage returned by `get_usage_stats`.
+ */
+export type UsageStats = { 
+/**
+ * Usage across all matching calls
+ */
+totals: UsageTotals, 
+/**
+ * Usage per chat session, most expensive first
+ */
+by_session: Array<UsageBucket>, 
+/**
+ * Usage per agent, most expensive first
+ */
+by_agent: Array<UsageBucket>, 
+/**
+ * Usage per UTC day (`YYYY-MM-DD`), oldest first
+ */
+by_day: Array<UsageBucket>, };
@@ -0,0 +1,38 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Summed usage over a set of LLM calls.
+ */
+export type UsageTotals = { 
+/**
+ * Number of LLM calls
+ */
+call_count: number, 
+/**
+ * Prompt (input) tokens
+ */
+prompt_tokens: number, 
+/**
+ * Completion (output) tokens
+ */
+completion_tokens: number, 
+/**
+ * Prompt plus completion tokens
+ */
+total_tokens: number, 
+/**
+ * Cost in USD of calls with known pricing
+ */
+cost_usd: number, 
+/**
+ * Prompt tokens served from the prompt cache
+ */
+cache_read_tokens: number, 
+/**
+ * Prompt tokens written to the prompt cache
+ */
+cache_write_tokens: number, 
+/**
+ * USD saved by prompt caching
+ */
+cache_savings_usd: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ItemStatus } from "./ItemStatus";
+
+/**
+ * Condition that wakes a paused task.
+ */
+export type WakeCondition = { "type": "webhook_received" } | { "type": "file_changed", path: string, 
+/**
+ * Modification time observed at registration (`None` when the file
+ * did not exist yet, so its creation also wakes the task)
+ */
+baseline_modified_at: number | null, } | { "type": "work_item_status", item_id: string, status: ItemStatus, } | { "type": "time_reached", at: number, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Event that satisfied a task's wake condition.
+ */
+export type WakeEvent = { 
+/**
+ * Kind of the condition that fired
+ */
+kind: string, 
+/**
+ * Timestamp when the condition fired (milliseconds since epoch)
+ */
+fired_at: number, 
+/**
+ * Event details injected into the resumed run
+ */
+details: any, };
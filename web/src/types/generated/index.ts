@@ -6,11 +6,16 @@ export * from './AgentInput'
 export * from './AgentNode'
 export * from './AgentOutput'
 export * from './AgentSession'
+export * from './AgentTemplate'
+export * from './AgentTemplateTask'
+export * from './ApiConnector'
 export * from './ApiKeyConfig'
+export * from './ApiKeyLocation'
 export * from './AuthConfig'
 export * from './AuthProfile'
 export * from './AuthProvider'
 export * from './BinaryRequirement'
+export * from './BrowserPlan'
 export * from './ChannelType'
 export * from './ChatExecutionStatus'
 export * from './ChatMediaType'
@@ -22,9 +27,14 @@ export * from './ChatSession'
 export * from './ChatSessionMetadata'
 export * from './ChatSessionSummary'
 export * from './CliExecutionConfig'
+export * from './ConcurrencyLimits'
+export * from './ConnectorAuth'
+export * from './ConnectorOperation'
+export * from './ConnectorParameter'
 export * from './Credential'
 export * from './CredentialSource'
 export * from './CodexCliExecutionMode'
+export * from './DigestKind'
 export * from './DiscoverySummary'
 export * from './Edge'
 export * from './EmailInput'
@@ -32,6 +42,7 @@ export * from './EmailOutput'
 export * from './EnvVarRequirement'
 export * from './ExecutionDetails'
 export * from './ExecutionMode'
+export * from './ExecutionReplay'
 export * from './ExecutionStats'
 export * from './ExecutionStep'
 export * from './ExecutionStepInfo'
@@ -81,22 +92,29 @@ export * from './ModelSwitchTrace'
 export * from './Node'
 export * from './NodeType'
 export * from './NotificationConfig'
+export * from './OAuthClientConfig'
 export * from './OsType'
+export * from './ParameterLocation'
 export * from './Position'
 export * from './PrintInput'
 export * from './PrintOutput'
 export * from './ProfileHealth'
 export * from './Provider'
+export * from './QueuePriority'
 export * from './RankedSearchResult'
+export * from './ReplayRunResult'
+export * from './ReplayStep'
 export * from './ResponseMode'
 export * from './RunnerStatus'
 export * from './RunnerStatusEvent'
+export * from './SavedBrowserPlan'
 export * from './ScheduleInput'
 export * from './ScheduleOutput'
 export * from './ScoreBreakdown'
 export * from './ScoredChunk'
 export * from './SearchMode'
 export * from './Secret'
+export * from './SecretAccess'
 export * from './SecureCredential'
 export * from './SessionMessage'
 export * from './SessionMetadata'
@@ -106,6 +124,7 @@ export * from './SkillDependency'
 export * from './SkillManifest'
 export * from './SkillPermission'
 export * from './SkillPermissions'
+export * from './SkillSignature'
 export * from './SkillSource'
 export * from './SkillVersion'
 export * from './SourceTypeFilter'
@@ -120,6 +139,7 @@ export * from './SubagentState'
 export * from './SubagentStatus'
 export * from './SystemStats'
 export * from './SystemStatus'
+export * from './TagCount'
 export * from './Task'
 export * from './TaskControlAction'
 export * from './TaskConversionResult'
@@ -128,19 +148,29 @@ export * from './TaskEventType'
 export * from './TaskMessage'
 export * from './TaskPatch'
 export * from './TaskProgress'
+export * from './TaskQueuePolicy'
 export * from './TaskSchedule'
 export * from './TaskSpec'
 export * from './TaskStatus'
 export * from './TaskStreamEvent'
+export * from './TaskWake'
 export * from './TerminalSession'
 export * from './TerminalStatus'
 export * from './ToolCallInfo'
 export * from './ToolCallTrace'
 export * from './TriggerConfig'
 export * from './TriggerStatus'
+export * from './UsageBucket'
+export * from './UsageBudgetStatus'
+export * from './UsageQuery'
+export * from './UsageRecord'
+export * from './UsageStats'
+export * from './UsageTotals'
 export * from './UseSkillParams'
 export * from './VersionRequirement'
 export * from './WaitSubagentsParams'
+export * from './WakeCondition'
+export * from './WakeEvent'
 export * from './WebhookConfig'
 export * from './WebhookResponse'
 export * from './WebhookTriggerInput'
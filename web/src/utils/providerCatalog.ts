@@ -11,6 +11,7 @@ const PRIMARY_PROVIDER_ORDER: Provider[] = [
 const SECONDARY_PROVIDER_ORDER: Provider[] = [
   'anthropic',
   'google',
+  'bedrock',
   'deepseek',
   'groq',
   'openrouter',
@@ -33,6 +34,7 @@ const PROVIDER_LABELS: Record<Provider, string> = {
   codex: 'Codex',
   deepseek: 'DeepSeek',
   google: 'Google',
+  bedrock: 'AWS Bedrock',
   groq: 'Groq',
   openrouter: 'OpenRouter',
   xai: 'XAI',